@@ -0,0 +1,129 @@
+//! Database throughput as concurrent writers contend for the pool.
+//!
+//! Runs against a file-backed WAL database, as the server does, with
+//! 1, 8 and `INTERCOM_BENCH_WRITERS` concurrent tasks. Each task writes
+//! its own session's activity and approval requests, so contention is on
+//! the single pooled connection rather than on the same rows.
+//!
+//! ```text
+//! cargo bench --bench db_contention
+//! INTERCOM_BENCH_WRITERS=128 INTERCOM_BENCH_OPS=1000 cargo bench --bench db_contention
+//! ```
+
+#![allow(clippy::expect_used, clippy::unwrap_used, missing_docs)]
+
+mod support;
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use agent_intercom::models::approval::{ApprovalRequest, RiskLevel};
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::persistence::approval_repo::ApprovalRepo;
+use agent_intercom::persistence::db::{self, Database};
+use agent_intercom::persistence::session_repo::SessionRepo;
+
+use support::{knob, Report, Samples};
+
+/// Which write each task repeats.
+#[derive(Clone, Copy)]
+enum Workload {
+    /// `update_last_activity`, as every tool call does.
+    Activity,
+    /// `ApprovalRepo::create`, as every `check_clearance` does.
+    Approval,
+}
+
+async fn active_session(db: &Arc<Database>, n: usize) -> String {
+    let repo = SessionRepo::new(Arc::clone(db));
+    let session = Session::new(
+        "U_BENCH".into(),
+        "/bench".into(),
+        Some(format!("writer {n}")),
+        SessionMode::Remote,
+    );
+    let created = repo.create(&session).await.expect("create session");
+    repo.update_status(&created.id, SessionStatus::Active)
+        .await
+        .expect("activate session")
+        .id
+}
+
+async fn run(db: &Arc<Database>, writers: usize, ops: usize, workload: Workload) -> Samples {
+    let mut tasks = Vec::with_capacity(writers);
+    for n in 0..writers {
+        let session_id = active_session(db, n).await;
+        let db = Arc::clone(db);
+        tasks.push(tokio::spawn(async move {
+            let sessions = SessionRepo::new(Arc::clone(&db));
+            let approvals = ApprovalRepo::new(db);
+            let mut samples = Samples::default();
+            for op in 0..ops {
+                match workload {
+                    Workload::Activity => samples
+                        .time(sessions.update_last_activity(&session_id, Some("ping".into())))
+                        .await
+                        .expect("activity"),
+                    Workload::Approval => {
+                        let request = ApprovalRequest::new(
+                            session_id.clone(),
+                            format!("change {op}"),
+                            None,
+                            "+x".into(),
+                            "src/lib.rs".into(),
+                            RiskLevel::Low,
+                            "hash".into(),
+                        );
+                        samples
+                            .time(approvals.create(&request))
+                            .await
+                            .expect("approval");
+                    }
+                }
+            }
+            samples
+        }));
+    }
+    let mut samples = Samples::default();
+    for task in tasks {
+        samples.merge(task.await.expect("writer task"));
+    }
+    samples
+}
+
+#[tokio::main]
+async fn main() {
+    let ops = knob("INTERCOM_BENCH_OPS", 200);
+    let max_writers = knob("INTERCOM_BENCH_WRITERS", 32);
+
+    let temp = tempfile::tempdir().expect("tempdir");
+    let path = temp.path().join("bench.db");
+    let db = Arc::new(
+        db::connect(path.to_str().expect("utf8 path"))
+            .await
+            .expect("db"),
+    );
+
+    let mut report = Report::new("db_contention — concurrent writers on one WAL database");
+    let mut levels: Vec<usize> = [1, 8, max_writers]
+        .into_iter()
+        .filter(|n| *n <= max_writers)
+        .collect();
+    levels.dedup();
+    for writers in levels {
+        for (label, workload) in [
+            ("last_activity", Workload::Activity),
+            ("approval insert", Workload::Approval),
+        ] {
+            let started = Instant::now();
+            let samples = run(&db, writers, ops, workload).await;
+            report.row(
+                &format!("{label} x{writers} writers"),
+                writers * ops,
+                started.elapsed(),
+                samples,
+            );
+        }
+    }
+    report.finish();
+}
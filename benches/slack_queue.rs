@@ -0,0 +1,86 @@
+//! Slack outgoing-queue throughput and backpressure.
+//!
+//! Needs the `chaos` feature: every post is dropped just before the HTTP
+//! call, so the numbers cover the bounded queue and its worker without
+//! touching Slack. Producers enqueue concurrently, as sessions do with
+//! `broadcast`; the run ends when the worker has drained every message.
+//! p99 enqueue latency rises once producers outpace the worker and the
+//! 256-slot queue fills.
+//!
+//! ```text
+//! cargo bench --features chaos --bench slack_queue
+//! ```
+
+#![allow(clippy::expect_used, clippy::unwrap_used, missing_docs)]
+
+mod support;
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use agent_intercom::chaos;
+use agent_intercom::slack::client::{SlackMessage, SlackService};
+use slack_morphism::prelude::SlackChannelId;
+
+use support::{bench_config, knob, Report, Samples};
+
+#[tokio::main]
+async fn main() {
+    let messages = knob("INTERCOM_BENCH_MESSAGES", 2000);
+    let max_producers = knob("INTERCOM_BENCH_PRODUCERS", 32);
+    chaos::faults().set_drop_percent(100);
+
+    let temp = tempfile::tempdir().expect("tempdir");
+    let config = bench_config(temp.path().to_str().expect("utf8 path"));
+    let mut slack_config = config.slack;
+    slack_config.bot_token = "xoxb-bench".into();
+    slack_config.app_token = "xapp-bench".into();
+
+    let mut report = Report::new("slack_queue — enqueue to drained, posts dropped by chaos");
+    let mut levels: Vec<usize> = [1, 8, max_producers]
+        .into_iter()
+        .filter(|n| *n <= max_producers)
+        .collect();
+    levels.dedup();
+    for producers in levels {
+        let (service, runtime) =
+            SlackService::start(&slack_config, &config.slack_rate_limit).expect("slack service");
+        let service = Arc::new(service);
+        let per_producer = messages / producers;
+
+        let started = Instant::now();
+        let mut tasks = Vec::with_capacity(producers);
+        for producer in 0..producers {
+            let service = Arc::clone(&service);
+            tasks.push(tokio::spawn(async move {
+                let mut samples = Samples::default();
+                for n in 0..per_producer {
+                    let message = SlackMessage::plain(
+                        SlackChannelId("C_BENCH".into()),
+                        format!("producer {producer} message {n}"),
+                    );
+                    samples
+                        .time(service.enqueue(message))
+                        .await
+                        .expect("enqueue");
+                }
+                samples
+            }));
+        }
+        let mut samples = Samples::default();
+        for task in tasks {
+            samples.merge(task.await.expect("producer task"));
+        }
+        // Closing the queue lets the worker exit once it has drained.
+        drop(service);
+        runtime.queue_task.await.expect("queue worker");
+
+        report.row(
+            &format!("enqueue x{producers} producers"),
+            producers * per_producer,
+            started.elapsed(),
+            samples,
+        );
+    }
+    report.finish();
+}
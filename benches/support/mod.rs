@@ -0,0 +1,143 @@
+//! Shared harness for the `benches/` suite.
+//!
+//! The benches run without `#[bench]` or external crates (`harness = false`):
+//! each scenario is timed here and printed as one table row. Set
+//! `INTERCOM_BENCH_MIN_OPS` to fail the run when any scenario's throughput
+//! falls below that many operations per second, so CI can guard against
+//! regressions without a stored baseline.
+
+#![allow(dead_code)]
+
+use std::time::{Duration, Instant};
+
+use agent_intercom::config::GlobalConfig;
+
+/// Environment variable naming the throughput floor in operations/second.
+const MIN_OPS_ENV: &str = "INTERCOM_BENCH_MIN_OPS";
+
+/// Read a positive integer knob from the environment, or `default`.
+pub fn knob(name: &str, default: usize) -> usize {
+    std::env::var(name)
+        .ok()
+        .and_then(|raw| raw.parse().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(default)
+}
+
+/// A minimal server configuration rooted at `workspace_root`, with a
+/// unique IPC name so concurrent runs do not collide.
+pub fn bench_config(workspace_root: &str) -> GlobalConfig {
+    let toml = format!(
+        r#"
+default_workspace_root = '{root}'
+http_port = 0
+ipc_name = "bench-{ipc}"
+max_concurrent_sessions = 1000
+host_cli = "echo"
+
+[slack]
+
+[timeouts]
+approval_seconds = 60
+prompt_seconds = 60
+wait_seconds = 60
+
+[stall]
+enabled = false
+inactivity_threshold_seconds = 300
+escalation_threshold_seconds = 120
+max_retries = 3
+default_nudge_message = "continue"
+"#,
+        root = workspace_root.replace('\\', "\\\\"),
+        ipc = uuid::Uuid::new_v4().simple(),
+    );
+    GlobalConfig::from_toml_str(&toml).expect("valid bench config")
+}
+
+/// Latencies collected for one scenario.
+#[derive(Debug, Default)]
+pub struct Samples {
+    latencies: Vec<Duration>,
+}
+
+impl Samples {
+    /// Time `op` and record its latency.
+    pub async fn time<F, T>(&mut self, op: F) -> T
+    where
+        F: std::future::Future<Output = T>,
+    {
+        let started = Instant::now();
+        let out = op.await;
+        self.latencies.push(started.elapsed());
+        out
+    }
+
+    /// Fold another task's samples into these.
+    pub fn merge(&mut self, other: Samples) {
+        self.latencies.extend(other.latencies);
+    }
+
+    fn percentile(sorted: &[Duration], pct: usize) -> Duration {
+        if sorted.is_empty() {
+            return Duration::ZERO;
+        }
+        sorted[(sorted.len() - 1) * pct / 100]
+    }
+}
+
+/// Results table printed by one bench binary.
+pub struct Report {
+    title: &'static str,
+    below_floor: Vec<String>,
+}
+
+impl Report {
+    /// Print the table header.
+    pub fn new(title: &'static str) -> Self {
+        println!("\n{title}");
+        println!(
+            "{:<36} {:>9} {:>10} {:>12} {:>10} {:>10}",
+            "scenario", "ops", "elapsed", "ops/s", "p50", "p99"
+        );
+        Self {
+            title,
+            below_floor: Vec::new(),
+        }
+    }
+
+    /// Print one scenario: `ops` operations completed in `elapsed`, with
+    /// per-operation latencies when collected.
+    pub fn row(&mut self, scenario: &str, ops: usize, elapsed: Duration, samples: Samples) {
+        let mut sorted = samples.latencies;
+        sorted.sort_unstable();
+        #[allow(clippy::cast_precision_loss)] // Counts are far below 2^52.
+        let rate = ops as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+        println!(
+            "{scenario:<36} {ops:>9} {:>10} {rate:>12.0} {:>10} {:>10}",
+            format!("{:.1?}", elapsed),
+            format!("{:.1?}", Samples::percentile(&sorted, 50)),
+            format!("{:.1?}", Samples::percentile(&sorted, 99)),
+        );
+        if let Some(floor) = std::env::var(MIN_OPS_ENV)
+            .ok()
+            .and_then(|raw| raw.parse::<f64>().ok())
+        {
+            if rate < floor {
+                self.below_floor
+                    .push(format!("{scenario}: {rate:.0} ops/s < {floor:.0}"));
+            }
+        }
+    }
+
+    /// Exit non-zero if any scenario fell below `INTERCOM_BENCH_MIN_OPS`.
+    pub fn finish(self) {
+        if !self.below_floor.is_empty() {
+            eprintln!("{}: below {MIN_OPS_ENV}:", self.title);
+            for line in &self.below_floor {
+                eprintln!("  {line}");
+            }
+            std::process::exit(1);
+        }
+    }
+}
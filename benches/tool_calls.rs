@@ -0,0 +1,153 @@
+//! Tool-call throughput under load.
+//!
+//! Starts a real server (HTTP transport on an ephemeral port, in-memory
+//! database) and a load generator that connects many fake MCP sessions
+//! over in-memory transports. Each session issues `ping` and `broadcast`
+//! calls back to back, so the numbers cover the full rmcp dispatch, tool
+//! handler, and per-call database writes.
+//!
+//! ```text
+//! cargo bench --bench tool_calls
+//! INTERCOM_BENCH_SESSIONS=64 INTERCOM_BENCH_CALLS=500 cargo bench --bench tool_calls
+//! ```
+
+#![allow(clippy::expect_used, clippy::unwrap_used, missing_docs)]
+
+mod support;
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use agent_intercom::mcp::handler::IntercomServer;
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::persistence::db;
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::server::{ServerBuilder, Transport};
+use agent_intercom::state::AppState;
+use rmcp::model::CallToolRequestParam;
+use rmcp::service::RunningService;
+use rmcp::{RoleClient, ServiceExt};
+use serde_json::json;
+
+use support::{bench_config, knob, Report, Samples};
+
+/// One fake agent: an MCP client bound to its own session.
+type FakeAgent = RunningService<RoleClient, ()>;
+
+/// Create `count` active sessions and connect a client to each, as
+/// spawned agents would.
+async fn connect_agents(state: &Arc<AppState>, root: &str, count: usize) -> Vec<FakeAgent> {
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let mut agents = Vec::with_capacity(count);
+    for n in 0..count {
+        let session = Session::new(
+            "U_BENCH".into(),
+            root.into(),
+            Some(format!("load session {n}")),
+            SessionMode::Remote,
+        );
+        let created = repo.create(&session).await.expect("create session");
+        repo.update_status(&created.id, SessionStatus::Active)
+            .await
+            .expect("activate session");
+
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let server = IntercomServer::with_overrides(Arc::clone(state), None, Some(created.id));
+        tokio::spawn(async move {
+            if let Ok(service) = server.serve(tokio::io::split(server_io)).await {
+                let _ = service.waiting().await;
+            }
+        });
+        agents.push(
+            ().serve(tokio::io::split(client_io))
+                .await
+                .expect("mcp handshake"),
+        );
+    }
+    agents
+}
+
+/// Run `calls` tool calls on every agent concurrently, alternating `ping`
+/// and `broadcast` when `mixed`, otherwise `ping` only.
+async fn drive(agents: &[FakeAgent], calls: usize, mixed: bool) -> Samples {
+    let mut tasks = Vec::with_capacity(agents.len());
+    for agent in agents {
+        let peer = agent.peer().clone();
+        tasks.push(tokio::spawn(async move {
+            let mut samples = Samples::default();
+            for n in 0..calls {
+                let request = if mixed && n % 2 == 1 {
+                    CallToolRequestParam {
+                        name: "broadcast".into(),
+                        arguments: json!({ "message": format!("step {n}") })
+                            .as_object()
+                            .cloned(),
+                        task: None,
+                    }
+                } else {
+                    CallToolRequestParam {
+                        name: "ping".into(),
+                        arguments: json!({ "status_message": "working" }).as_object().cloned(),
+                        task: None,
+                    }
+                };
+                samples
+                    .time(peer.call_tool(request))
+                    .await
+                    .expect("tool call");
+            }
+            samples
+        }));
+    }
+    let mut samples = Samples::default();
+    for task in tasks {
+        samples.merge(task.await.expect("load task"));
+    }
+    samples
+}
+
+#[tokio::main]
+async fn main() {
+    let calls = knob("INTERCOM_BENCH_CALLS", 200);
+    let max_sessions = knob("INTERCOM_BENCH_SESSIONS", 32);
+
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let server = ServerBuilder::new(bench_config(root))
+        .transport(Transport::Sse)
+        .load_credentials(false)
+        .database(Arc::new(db::connect_memory().await.expect("db")))
+        .start()
+        .await
+        .expect("server start");
+    let state = Arc::clone(server.state());
+
+    let mut report = Report::new("tool_calls — fake MCP sessions over in-memory transports");
+    let mut levels: Vec<usize> = [1, 8, max_sessions]
+        .into_iter()
+        .filter(|n| *n <= max_sessions)
+        .collect();
+    levels.dedup();
+    for sessions in levels {
+        let agents = connect_agents(&state, root, sessions).await;
+        for mixed in [false, true] {
+            let started = Instant::now();
+            let samples = drive(&agents, calls, mixed).await;
+            let label = if mixed { "ping+broadcast" } else { "ping" };
+            report.row(
+                &format!("{label} x{sessions} sessions"),
+                sessions * calls,
+                started.elapsed(),
+                samples,
+            );
+        }
+        for agent in agents {
+            let _ = agent.cancel().await;
+        }
+    }
+    server
+        .run_until(std::future::ready(()))
+        .await
+        .expect("shutdown");
+    report.finish();
+}
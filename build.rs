@@ -0,0 +1,21 @@
+//! Build script: compiles `proto/intercom_control.proto` into the gRPC
+//! control plane stubs when the `grpc` feature is enabled (ADR-0019).
+//!
+//! The proto is parsed with `protox`, so no `protoc` install is needed.
+
+fn main() {
+    println!("cargo::rerun-if-changed=build.rs");
+    #[cfg(feature = "grpc")]
+    if let Err(err) = compile_control_proto() {
+        println!("cargo::error=failed to compile proto/intercom_control.proto: {err}");
+    }
+}
+
+#[cfg(feature = "grpc")]
+fn compile_control_proto() -> Result<(), Box<dyn std::error::Error>> {
+    const PROTO: &str = "proto/intercom_control.proto";
+    println!("cargo::rerun-if-changed={PROTO}");
+    let descriptors = protox::compile([PROTO], ["proto"])?;
+    tonic_prost_build::configure().compile_fds(descriptors)?;
+    Ok(())
+}
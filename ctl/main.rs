@@ -2,13 +2,26 @@
 
 //! `agent-intercom-ctl` — local CLI companion for `agent-intercom`.
 //!
-//! Connects to the IPC socket and sends JSON commands to the server.
+//! Sends commands to the server over its IPC socket using
+//! [`agent_intercom::ipc::client::IpcClient`].
 //! Designed for local overrides when the operator is physically present.
+//! Every subcommand prints as a table, JSON or bare IDs (`--output`).
 
-use std::io::{BufRead, BufReader, Write};
+mod output;
 
+use std::path::{Path, PathBuf};
+
+use agent_intercom::ipc::client::{IpcClient, IpcCommand, ReportOutcome};
+use agent_intercom::mode::ServerMode;
+use agent_intercom::models::session::{SessionMode, SessionStatus};
+use agent_intercom::models::steering::SteeringPriority;
+use agent_intercom::orchestrator::session_bulk::BulkAction;
+use agent_intercom::orchestrator::session_logs::LogLine;
+use agent_intercom::AppError;
 use clap::{Parser, Subcommand};
-use interprocess::local_socket::{traits::Stream as _, GenericNamespaced, Stream, ToNsName};
+use serde::Serialize;
+
+use crate::output::{emit, OutputFormat, Table};
 
 #[derive(Debug, Parser)]
 #[command(
@@ -30,25 +43,18 @@ struct Cli {
     /// When `--ipc-name` is not set, the mode determines the IPC socket
     /// name: `agent-intercom` for MCP, `agent-intercom-acp` for ACP.
     /// This matches the auto-suffix applied by the server (ADR-0015).
-    #[arg(long, value_enum, default_value_t = CtlMode::Mcp)]
-    mode: CtlMode,
+    #[arg(long, value_enum, default_value_t = ServerMode::Mcp)]
+    mode: ServerMode,
+
+    /// Output format: `table` (default; coloured when stdout is a
+    /// terminal), `json` for scripts, or `quiet` for bare IDs.
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    output: OutputFormat,
 
     #[command(subcommand)]
     command: Command,
 }
 
-/// Protocol mode selector for the ctl companion.
-///
-/// Mirrors [`agent_intercom::mode::ServerMode`] but is defined locally
-/// because the ctl binary does not depend on the library crate.
-#[derive(Debug, Copy, Clone, Eq, PartialEq, clap::ValueEnum)]
-enum CtlMode {
-    /// Connect to the MCP server instance (default pipe: `agent-intercom`).
-    Mcp,
-    /// Connect to the ACP server instance (default pipe: `agent-intercom-acp`).
-    Acp,
-}
-
 impl Cli {
     /// Resolve the effective IPC socket name.
     ///
@@ -59,8 +65,8 @@ impl Cli {
             name.clone()
         } else {
             match self.mode {
-                CtlMode::Mcp => "agent-intercom".into(),
-                CtlMode::Acp => "agent-intercom-acp".into(),
+                ServerMode::Mcp => "agent-intercom".into(),
+                ServerMode::Acp => "agent-intercom-acp".into(),
             }
         }
     }
@@ -69,17 +75,21 @@ impl Cli {
 #[derive(Debug, Subcommand)]
 enum Command {
     /// List active sessions.
-    List,
+    List {
+        /// Only sessions carrying this `key=value` tag (repeatable).
+        #[arg(long = "tag", value_name = "KEY=VALUE")]
+        tags: Vec<String>,
+    },
 
     /// Approve a pending approval request.
     Approve {
-        /// Approval request ID.
+        /// Approval request ID, short ID, or unique prefix of either.
         id: String,
     },
 
     /// Reject a pending approval request.
     Reject {
-        /// Approval request ID.
+        /// Approval request ID, short ID, or unique prefix of either.
         id: String,
         /// Optional rejection reason.
         #[arg(long)]
@@ -95,13 +105,20 @@ enum Command {
     /// Switch operational mode.
     Mode {
         /// Target mode: remote, local, or hybrid.
-        mode: String,
+        #[arg(value_parser = parse_session_mode)]
+        mode: SessionMode,
     },
 
     /// Queue a steering message for the active agent session.
     Steer {
         /// Message text to send to the agent.
         instruction: String,
+        /// Interrupt the agent's current turn and deliver immediately.
+        #[arg(long)]
+        now: bool,
+        /// Drop the message if still undelivered after this long (e.g. `10m`).
+        #[arg(long)]
+        ttl: Option<String>,
     },
 
     /// Queue a task work item for delivery at the next agent cold-start.
@@ -109,95 +126,342 @@ enum Command {
         /// Task description or instruction text.
         instruction: String,
     },
+
+    /// Generate a Markdown report of a session (transcript, decisions, diffs).
+    Report {
+        /// Session ID, short ID, or unique prefix of either.
+        session_id: String,
+        /// Write the report to this file instead of stdout.
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Print the newest log lines a session streamed with `stream_log`.
+    Logs {
+        /// Session ID, short ID, or unique prefix of either.
+        session_id: String,
+        /// Number of lines to print (1 to 1000).
+        #[arg(short = 'n', default_value_t = 200)]
+        lines: usize,
+    },
+
+    /// Hide ended sessions from listings and recovery, or bring them back.
+    Session {
+        #[command(subcommand)]
+        action: SessionAction,
+    },
+
+    /// Clear (terminate), pause, or soft-delete every matching session in
+    /// one server-side call, and print a summary.
+    Sessions {
+        /// Action to apply to each matching session.
+        #[arg(value_enum)]
+        action: BulkAction,
+        /// Only sessions with this status (repeatable). Defaults: clear
+        /// takes every live or interrupted session, pause active ones,
+        /// delete ended ones.
+        #[arg(long = "status", value_parser = parse_session_status)]
+        statuses: Vec<SessionStatus>,
+        /// Only sessions owned by this user ID.
+        #[arg(long)]
+        owner: Option<String>,
+        /// Only sessions not updated for at least this long (e.g. `7d`).
+        #[arg(long)]
+        older_than: Option<String>,
+        /// Act on every session when no filter is given.
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Change the server's log level at runtime, for the whole server or
+    /// one tracing target (e.g. `agent_intercom::slack`), without a
+    /// restart. Prints the filter now in force; with no level, only prints
+    /// it.
+    LogLevel {
+        /// trace, debug, info, warn, error or off.
+        level: Option<String>,
+        /// Tracing target to apply the level to; the default level when
+        /// omitted.
+        target: Option<String>,
+        /// Restore the filter the server started with.
+        #[arg(long, conflicts_with_all = ["level", "target"])]
+        reset: bool,
+    },
+
+    /// Show the Slack Socket Mode connection state, or force a clean
+    /// reconnect when Slack leaves the connection half-open.
+    Slack {
+        #[command(subcommand)]
+        action: SlackAction,
+    },
+
+    /// Inject faults into a server built with `--features chaos`, to
+    /// exercise recovery paths. Prints the settings now in force.
+    #[command(hide = true)]
+    Chaos {
+        /// Drop this percentage of Slack posts (0 to 100).
+        #[arg(long, value_parser = clap::value_parser!(u8).range(0..=100))]
+        drop_percent: Option<u8>,
+        /// Hold each approval, prompt and wait resolution this many
+        /// milliseconds.
+        #[arg(long)]
+        delay_ms: Option<u64>,
+        /// Kill the Socket Mode connection so it reconnects.
+        #[arg(long)]
+        kill_socket: bool,
+        /// Turn every fault off before applying the others.
+        #[arg(long)]
+        reset: bool,
+    },
 }
 
-fn main() {
+#[derive(Debug, Subcommand)]
+enum SessionAction {
+    /// Soft-delete an ended session. It disappears from `sessions`,
+    /// `history` and crash recovery; its records stay for audit and
+    /// retention.
+    Delete {
+        /// Session ID, short ID, or unique prefix of either.
+        session_id: String,
+    },
+
+    /// Restore a soft-deleted session.
+    Restore {
+        /// Session ID, short ID, or unique prefix of either.
+        session_id: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum SlackAction {
+    /// Print when the connection opened, the last hello from Slack, and
+    /// how often it has dropped.
+    Status,
+
+    /// Drop every Socket Mode connection and register again. Events sent
+    /// while reconnecting are redelivered by Slack.
+    Reconnect,
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
     let args = Cli::parse();
+    let ipc_name = args.effective_ipc_name();
 
-    let request_json = match &args.command {
-        Command::List => serde_json::json!({ "command": "list" }),
+    let mut client = match IpcClient::connect(&ipc_name).await {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("Failed to connect to server: {err}");
+            eprintln!("Is agent-intercom running with ipc_name '{ipc_name}'?");
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(err) = run(&mut client, args.command, args.output).await {
+        match err {
+            AppError::Ipc(msg) => eprintln!("Error: {msg}"),
+            other => eprintln!("Error: {other}"),
+        }
+        std::process::exit(1);
+    }
+}
+
+/// Send `command` and print its result in `format`.
+#[allow(clippy::too_many_lines)] // One match arm per subcommand.
+async fn run(
+    client: &mut IpcClient,
+    command: Command,
+    format: OutputFormat,
+) -> agent_intercom::Result<()> {
+    match command {
+        Command::List { tags } => {
+            let sessions = client.list_tagged(&tags).await?;
+            emit(format, sessions.as_slice(), output::sessions, |sessions| {
+                sessions.iter().map(|s| s.session_id.clone()).collect()
+            });
+        }
         Command::Approve { id } => {
-            serde_json::json!({ "command": "approve", "id": id })
+            emit(
+                format,
+                &client.approve(&id).await?,
+                output::approval,
+                |_| Vec::new(),
+            );
         }
         Command::Reject { id, reason } => {
-            let mut req = serde_json::json!({ "command": "reject", "id": id });
-            if let Some(r) = reason {
-                req["reason"] = serde_json::Value::String(r.clone());
-            }
-            req
+            let outcome = client.reject(&id, reason.as_deref()).await?;
+            emit(format, &outcome, output::approval, |_| Vec::new());
         }
         Command::Resume { instruction } => {
-            let mut req = serde_json::json!({ "command": "resume" });
-            if let Some(inst) = instruction {
-                req["instruction"] = serde_json::Value::String(inst.clone());
-            }
-            req
+            let outcome = client.resume(None, instruction.as_deref()).await?;
+            emit(format, &outcome, output::resume, |o| {
+                vec![o.session_id.clone()]
+            });
         }
         Command::Mode { mode } => {
-            serde_json::json!({ "command": "mode", "mode": mode })
+            let change = client.set_mode(mode).await?;
+            emit(format, &change, |c| output::mode(*c), |_| Vec::new());
         }
-        Command::Steer { instruction } => {
-            serde_json::json!({ "command": "steer", "instruction": instruction })
+        Command::Steer {
+            instruction,
+            now,
+            ttl,
+        } => {
+            let priority = if now {
+                SteeringPriority::Interrupt
+            } else {
+                SteeringPriority::Normal
+            };
+            let outcome = client.steer(&instruction, priority, ttl.as_deref()).await?;
+            emit(format, &outcome, output::steer, |o| {
+                vec![o.session_id.clone()]
+            });
         }
         Command::Task { instruction } => {
-            serde_json::json!({ "command": "task", "instruction": instruction })
+            let outcome = client.task(&instruction).await?;
+            emit(format, &outcome, output::task, |o| vec![o.task_id.clone()]);
         }
-    };
-
-    let ipc_name = args.effective_ipc_name();
-
-    match send_ipc_command(&ipc_name, &request_json) {
-        Ok(response) => {
-            if let Some(obj) = response.as_object() {
-                let ok = obj
-                    .get("ok")
-                    .and_then(serde_json::Value::as_bool)
-                    .unwrap_or(false);
-                if ok {
-                    if let Some(data) = obj.get("data") {
-                        println!("{}", serde_json::to_string_pretty(data).unwrap_or_default());
-                    } else {
-                        println!("OK");
-                    }
-                } else {
-                    let err_msg = obj
-                        .get("error")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("unknown error");
-                    eprintln!("Error: {err_msg}");
-                    std::process::exit(1);
+        Command::Report { session_id, out } => {
+            let report = client.report(&session_id).await?;
+            write_report(&report, out.as_deref(), format);
+        }
+        Command::Logs { session_id, lines } => {
+            let outcome = client.logs(&session_id, Some(lines)).await?;
+            emit(format, &outcome, output::logs, |o| {
+                o.lines.iter().map(LogLine::render).collect()
+            });
+        }
+        Command::Session { action } => {
+            let outcome = match action {
+                SessionAction::Delete { session_id } => client.delete_session(&session_id).await?,
+                SessionAction::Restore { session_id } => {
+                    client.restore_session(&session_id).await?
                 }
-            } else {
-                println!("{response}");
-            }
+            };
+            emit(format, &outcome, output::deletion, |o| {
+                vec![o.session_id.clone()]
+            });
         }
-        Err(err) => {
-            eprintln!("Failed to connect to server: {err}");
-            eprintln!("Is agent-intercom running with ipc_name '{ipc_name}'?");
-            std::process::exit(1);
+        Command::Sessions {
+            action,
+            statuses,
+            owner,
+            older_than,
+            all,
+        } => {
+            let command = IpcCommand::Sessions {
+                action,
+                statuses,
+                owner,
+                older_than,
+                all,
+            };
+            let outcome = client.bulk_sessions(&command).await?;
+            emit(format, &outcome, output::bulk, |o| o.succeeded.clone());
         }
+        Command::LogLevel {
+            level,
+            target,
+            reset,
+        } => {
+            let command = IpcCommand::LogLevel {
+                level,
+                target,
+                reset,
+            };
+            let outcome = client.log_level(&command).await?;
+            emit(format, &outcome, output::log_level, |o| {
+                vec![o.filter.clone()]
+            });
+        }
+        Command::Slack { action } => {
+            let outcome = match action {
+                SlackAction::Status => client.slack_status().await?,
+                SlackAction::Reconnect => client.slack_reconnect().await?,
+            };
+            emit(format, &outcome, output::slack_connection, |o| {
+                vec![o.socket.connected.to_string()]
+            });
+        }
+        Command::Chaos {
+            drop_percent,
+            delay_ms,
+            kill_socket,
+            reset,
+        } => {
+            let command = IpcCommand::Chaos {
+                drop_percent,
+                delay_ms,
+                kill_socket,
+                reset,
+            };
+            let outcome = client.chaos(&command).await?;
+            emit(format, &outcome, |o| output::chaos(*o), |_| Vec::new());
+        }
+    }
+    Ok(())
+}
+
+/// Parse a `mode` argument.
+fn parse_session_mode(value: &str) -> Result<SessionMode, String> {
+    match value {
+        "remote" => Ok(SessionMode::Remote),
+        "local" => Ok(SessionMode::Local),
+        "hybrid" => Ok(SessionMode::Hybrid),
+        other => Err(format!(
+            "invalid mode '{other}': expected remote, local, or hybrid"
+        )),
     }
 }
 
-/// Connect to the IPC socket, send a JSON command, and read the response.
-fn send_ipc_command(
-    ipc_name: &str,
-    request: &serde_json::Value,
-) -> std::result::Result<serde_json::Value, Box<dyn std::error::Error>> {
-    let name = ipc_name.to_ns_name::<GenericNamespaced>()?;
-    let mut stream = Stream::connect(name)?;
-
-    // Send request as a single JSON line.
-    let mut request_line = serde_json::to_string(request)?;
-    request_line.push('\n');
-    stream.write_all(request_line.as_bytes())?;
-    stream.flush()?;
-
-    // Read response line.
-    let mut reader = BufReader::new(&stream);
-    let mut response_line = String::new();
-    reader.read_line(&mut response_line)?;
-
-    let response: serde_json::Value = serde_json::from_str(response_line.trim())?;
-    Ok(response)
+/// Parse a `--status` argument.
+fn parse_session_status(value: &str) -> Result<SessionStatus, String> {
+    match value {
+        "created" => Ok(SessionStatus::Created),
+        "active" => Ok(SessionStatus::Active),
+        "paused" => Ok(SessionStatus::Paused),
+        "terminated" => Ok(SessionStatus::Terminated),
+        "interrupted" => Ok(SessionStatus::Interrupted),
+        other => Err(format!(
+            "invalid status '{other}': expected created, active, paused, terminated, or interrupted"
+        )),
+    }
+}
+
+/// Where `report --out` wrote a report; the `--output json` form.
+#[derive(Serialize)]
+struct ReportWritten<'a> {
+    session_id: &'a str,
+    path: &'a Path,
+}
+
+/// Print a report's Markdown (`table`, `quiet`) or response (`json`), or
+/// write the Markdown to `out` and say where.
+fn write_report(report: &ReportOutcome, out: Option<&Path>, format: OutputFormat) {
+    let Some(path) = out else {
+        match format {
+            OutputFormat::Json => emit(format, report, |_| Table::default(), |_| Vec::new()),
+            OutputFormat::Table | OutputFormat::Quiet => print!("{}", report.markdown),
+        }
+        return;
+    };
+    if let Err(err) = std::fs::write(path, &report.markdown) {
+        eprintln!("Failed to write {}: {err}", path.display());
+        std::process::exit(1);
+    }
+    let written = ReportWritten {
+        session_id: &report.session_id,
+        path,
+    };
+    emit(
+        format,
+        &written,
+        |w| {
+            Table::record(vec![
+                ("SESSION", w.session_id.into()),
+                ("WRITTEN", w.path.display().to_string().into()),
+            ])
+        },
+        |w| vec![w.path.display().to_string()],
+    );
 }
@@ -0,0 +1,466 @@
+//! Output formats for `agent-intercom-ctl` (`--output json|table|quiet`).
+//!
+//! `json` prints the typed response of the IPC client as-is, so its schema
+//! is the serde form of the structs in `agent_intercom::ipc::client`.
+//! `table` is for people: aligned columns, coloured when stdout is a
+//! terminal and `NO_COLOR` is unset. `quiet` prints only the identifiers a
+//! script would pipe on, one per line, or nothing.
+
+use std::fmt::Write as _;
+use std::io::IsTerminal;
+
+use agent_intercom::ipc::client::{
+    ApprovalOutcome, ChaosOutcome, LogLevelOutcome, LogsOutcome, ModeChange, ResumeOutcome,
+    SessionDeletion, SessionSummary, SlackConnectionOutcome, SteerOutcome, TaskOutcome,
+};
+use agent_intercom::models::approval::ApprovalStatus;
+use agent_intercom::models::session::{format_tags, SessionStatus};
+use agent_intercom::orchestrator::session_bulk::BulkOutcome;
+use agent_intercom::orchestrator::session_logs::LogLevel;
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// How a subcommand prints its result.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Pretty-printed JSON of the response.
+    Json,
+    /// Aligned, human-readable columns.
+    #[default]
+    Table,
+    /// Identifiers only, one per line.
+    Quiet,
+}
+
+/// Colour hint for a table cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tone {
+    /// No colour.
+    Plain,
+    /// Healthy or successful (green).
+    Good,
+    /// Needs attention (yellow).
+    Warn,
+    /// Ended or failed (red).
+    Bad,
+    /// Secondary detail (dim).
+    Dim,
+}
+
+impl Tone {
+    fn ansi(self) -> Option<&'static str> {
+        match self {
+            Self::Plain => None,
+            Self::Good => Some("32"),
+            Self::Warn => Some("33"),
+            Self::Bad => Some("31"),
+            Self::Dim => Some("2"),
+        }
+    }
+}
+
+/// One table cell: its text and colour hint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    text: String,
+    tone: Tone,
+}
+
+impl Cell {
+    /// A cell with `tone`.
+    pub fn toned(text: impl Into<String>, tone: Tone) -> Self {
+        Self {
+            text: text.into(),
+            tone,
+        }
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Self::toned(text, Tone::Plain)
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(text: &str) -> Self {
+        Self::toned(text, Tone::Plain)
+    }
+}
+
+/// Rows under a header, rendered with each column as wide as its widest
+/// cell.
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Table {
+    /// An empty table with `headers`.
+    pub fn new(headers: &[&'static str]) -> Self {
+        Self {
+            headers: headers.to_vec(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// A two-column `FIELD VALUE` table of one record.
+    pub fn record(fields: Vec<(&'static str, Cell)>) -> Self {
+        let mut table = Self::new(&["FIELD", "VALUE"]);
+        for (name, value) in fields {
+            table.row(vec![Cell::from(name), value]);
+        }
+        table
+    }
+
+    /// Append a row; missing trailing cells render empty.
+    pub fn row(&mut self, cells: Vec<Cell>) {
+        self.rows.push(cells);
+    }
+
+    /// Render the table, with ANSI colours when `color` is set.
+    pub fn render(&self, color: bool) -> String {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (index, cell) in row.iter().enumerate() {
+                let width = cell.text.chars().count();
+                match widths.get_mut(index) {
+                    Some(current) => *current = (*current).max(width),
+                    None => widths.push(width),
+                }
+            }
+        }
+
+        let mut out = String::new();
+        let header: Vec<Cell> = self
+            .headers
+            .iter()
+            .map(|h| Cell::toned(*h, Tone::Plain))
+            .collect();
+        render_line(&mut out, &header, &widths, color.then_some(BOLD));
+        for row in &self.rows {
+            let tones = row.iter().map(|cell| cell.tone.ansi());
+            let codes: Vec<Option<&str>> = if color {
+                tones.collect()
+            } else {
+                vec![None; row.len()]
+            };
+            render_row(&mut out, row, &widths, &codes);
+        }
+        out
+    }
+}
+
+/// ANSI code for the header row.
+const BOLD: &str = "1";
+
+/// Append the header line, bold when `code` is set.
+fn render_line(out: &mut String, cells: &[Cell], widths: &[usize], code: Option<&str>) {
+    render_row(out, cells, widths, &vec![code; cells.len()]);
+}
+
+/// Append one line of `cells` padded to `widths`, each wrapped in its ANSI
+/// `codes` entry when there is one. The last cell is not padded.
+fn render_row(out: &mut String, cells: &[Cell], widths: &[usize], codes: &[Option<&str>]) {
+    let last = cells.len().saturating_sub(1);
+    for (index, cell) in cells.iter().enumerate() {
+        if index > 0 {
+            out.push_str("  ");
+        }
+        match codes.get(index).copied().flatten() {
+            Some(code) => {
+                let _ = write!(out, "\u{1b}[{code}m{}\u{1b}[0m", cell.text);
+            }
+            None => out.push_str(&cell.text),
+        }
+        if index < last {
+            let width = widths.get(index).copied().unwrap_or(0);
+            let pad = width.saturating_sub(cell.text.chars().count());
+            out.push_str(&" ".repeat(pad));
+        }
+    }
+    out.push('\n');
+}
+
+/// Whether stdout should get ANSI colours.
+pub fn use_color() -> bool {
+    std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+}
+
+/// Print `value` in `format`: as JSON, as the table `table` builds, or as
+/// the identifiers `quiet` returns.
+pub fn emit<T: Serialize + ?Sized>(
+    format: OutputFormat,
+    value: &T,
+    table: impl FnOnce(&T) -> Table,
+    quiet: impl FnOnce(&T) -> Vec<String>,
+) {
+    match format {
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(value).unwrap_or_default()
+        ),
+        OutputFormat::Table => print!("{}", table(value).render(use_color())),
+        OutputFormat::Quiet => {
+            for line in quiet(value) {
+                println!("{line}");
+            }
+        }
+    }
+}
+
+/// Wire name of a serde enum value, e.g. `interrupted`.
+pub fn label(value: &impl Serialize) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(text)) => text,
+        Ok(other) => other.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+/// Cell for a yes/no flag.
+fn flag(value: bool) -> Cell {
+    Cell::toned(
+        if value { "yes" } else { "no" },
+        if value { Tone::Good } else { Tone::Dim },
+    )
+}
+
+/// Tone for a session status.
+fn status_tone(status: SessionStatus) -> Tone {
+    match status {
+        SessionStatus::Active => Tone::Good,
+        SessionStatus::Paused => Tone::Warn,
+        SessionStatus::Interrupted => Tone::Bad,
+        SessionStatus::Created | SessionStatus::Terminated => Tone::Dim,
+    }
+}
+
+/// `list`: one row per session.
+pub fn sessions(sessions: &[SessionSummary]) -> Table {
+    let mut table = Table::new(&[
+        "SESSION",
+        "ID",
+        "STATUS",
+        "MODE",
+        "LAST TOOL",
+        "UPDATED",
+        "TAGS",
+        "WORKSPACE",
+    ]);
+    for session in sessions {
+        table.row(vec![
+            session.session_id.clone().into(),
+            session.short_id.clone().into(),
+            Cell::toned(label(&session.status), status_tone(session.status)),
+            label(&session.mode).into(),
+            Cell::toned(
+                session.last_tool.clone().unwrap_or_else(|| "-".into()),
+                Tone::Dim,
+            ),
+            session
+                .updated_at
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+                .into(),
+            Cell::toned(
+                if session.tags.is_empty() {
+                    "-".into()
+                } else {
+                    format_tags(&session.tags)
+                },
+                Tone::Dim,
+            ),
+            session.workspace_root.clone().into(),
+        ]);
+    }
+    table
+}
+
+/// `approve` and `reject`.
+pub fn approval(outcome: &ApprovalOutcome) -> Table {
+    let tone = match outcome.status {
+        ApprovalStatus::Approved => Tone::Good,
+        ApprovalStatus::Rejected => Tone::Bad,
+        _ => Tone::Plain,
+    };
+    Table::record(vec![
+        ("REQUEST", outcome.request_id.clone().into()),
+        ("STATUS", Cell::toned(label(&outcome.status), tone)),
+    ])
+}
+
+/// `resume`.
+pub fn resume(outcome: &ResumeOutcome) -> Table {
+    Table::record(vec![
+        ("SESSION", outcome.session_id.clone().into()),
+        ("STATUS", Cell::toned(outcome.status.clone(), Tone::Good)),
+    ])
+}
+
+/// `mode`.
+pub fn mode(change: ModeChange) -> Table {
+    Table::record(vec![
+        ("PREVIOUS", label(&change.previous_mode).into()),
+        (
+            "CURRENT",
+            Cell::toned(label(&change.current_mode), Tone::Good),
+        ),
+    ])
+}
+
+/// `steer`.
+pub fn steer(outcome: &SteerOutcome) -> Table {
+    Table::record(vec![
+        ("SESSION", outcome.session_id.clone().into()),
+        ("QUEUED", flag(outcome.queued)),
+        ("DELIVERED", flag(outcome.delivered)),
+        ("PUSHED", flag(outcome.pushed)),
+    ])
+}
+
+/// `task`.
+pub fn task(outcome: &TaskOutcome) -> Table {
+    Table::record(vec![
+        ("TASK", outcome.task_id.clone().into()),
+        ("QUEUED", flag(outcome.queued)),
+    ])
+}
+
+/// `logs`: one row per line, warnings and errors coloured.
+pub fn logs(outcome: &LogsOutcome) -> Table {
+    let mut table = Table::new(&["TIME", "LEVEL", "MESSAGE"]);
+    for line in &outcome.lines {
+        let tone = match line.level {
+            LogLevel::Debug => Tone::Dim,
+            LogLevel::Info => Tone::Plain,
+            LogLevel::Warn => Tone::Warn,
+            LogLevel::Error => Tone::Bad,
+        };
+        table.row(vec![
+            line.at.format("%H:%M:%S").to_string().into(),
+            Cell::toned(line.level.label(), tone),
+            line.detail().into(),
+        ]);
+    }
+    table
+}
+
+/// `session delete` and `session restore`.
+pub fn deletion(outcome: &SessionDeletion) -> Table {
+    Table::record(vec![
+        ("SESSION", outcome.session_id.clone().into()),
+        (
+            "DELETED",
+            Cell::toned(
+                if outcome.deleted { "yes" } else { "no" },
+                if outcome.deleted {
+                    Tone::Warn
+                } else {
+                    Tone::Good
+                },
+            ),
+        ),
+    ])
+}
+
+/// `sessions`: one row per matched session, failures with their error.
+pub fn bulk(outcome: &BulkOutcome) -> Table {
+    let mut table = Table::new(&["SESSION", "RESULT", "ERROR"]);
+    for id in &outcome.succeeded {
+        table.row(vec![
+            id.clone().into(),
+            Cell::toned(label(&outcome.action), Tone::Good),
+        ]);
+    }
+    for failure in &outcome.failed {
+        table.row(vec![
+            failure.session_id.clone().into(),
+            Cell::toned("failed", Tone::Bad),
+            failure.error.clone().into(),
+        ]);
+    }
+    table
+}
+
+/// Filter reported by `log-level`.
+pub fn log_level(outcome: &LogLevelOutcome) -> Table {
+    Table::record(vec![("FILTER", outcome.filter.clone().into())])
+}
+
+/// `slack status` and `slack reconnect`.
+pub fn slack_connection(outcome: &SlackConnectionOutcome) -> Table {
+    let when = |at: Option<chrono::DateTime<chrono::Utc>>| -> Cell {
+        at.map_or_else(
+            || Cell::toned("-", Tone::Dim),
+            |at| at.format("%Y-%m-%d %H:%M:%S").to_string().into(),
+        )
+    };
+    let socket = &outcome.socket;
+    let mut rows = vec![
+        (
+            "CONNECTED",
+            Cell::toned(
+                if socket.connected { "yes" } else { "no" },
+                if socket.connected {
+                    Tone::Good
+                } else {
+                    Tone::Bad
+                },
+            ),
+        ),
+        ("SINCE", when(socket.connected_since)),
+        ("LAST HELLO", when(socket.last_hello)),
+        ("DISCONNECTS", socket.disconnects.to_string().into()),
+        ("FORCED", socket.forced_reconnects.to_string().into()),
+    ];
+    if outcome.reconnecting {
+        rows.push(("RECONNECTING", flag(true)));
+    }
+    Table::record(rows)
+}
+
+/// `chaos`.
+pub fn chaos(outcome: ChaosOutcome) -> Table {
+    Table::record(vec![
+        ("DROP %", outcome.drop_percent.to_string().into()),
+        ("DELAY MS", outcome.delay_ms.to_string().into()),
+        ("SOCKET KILLED", flag(outcome.socket_killed)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cell, Table, Tone};
+
+    #[test]
+    fn columns_align_to_widest_cell_without_color() {
+        let mut table = Table::new(&["SESSION", "STATUS"]);
+        table.row(vec!["a".into(), Cell::toned("interrupted", Tone::Bad)]);
+        table.row(vec!["long-session-id".into(), "active".into()]);
+
+        assert_eq!(
+            table.render(false),
+            "SESSION          STATUS\n\
+             a                interrupted\n\
+             long-session-id  active\n"
+        );
+    }
+
+    #[test]
+    fn color_wraps_header_and_toned_cells_only() {
+        let mut table = Table::new(&["ID", "STATUS"]);
+        table.row(vec!["x".into(), Cell::toned("active", Tone::Good)]);
+
+        let rendered = table.render(true);
+        assert!(
+            rendered.starts_with("\u{1b}[1mID\u{1b}[0m  "),
+            "{rendered:?}"
+        );
+        assert!(
+            rendered.ends_with("x   \u{1b}[32mactive\u{1b}[0m\n"),
+            "{rendered:?}"
+        );
+    }
+}
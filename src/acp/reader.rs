@@ -24,6 +24,7 @@
 //! | `status/update`    | [`AgentEvent::StatusUpdated`]                  |
 //! | `prompt/forward`   | [`AgentEvent::PromptForwarded`]                |
 //! | `heartbeat`        | [`AgentEvent::HeartbeatReceived`]              |
+//! | `intercom/subscribe`| [`AgentEvent::HooksSubscribed`]               |
 //! | *(any other)*      | Skipped; logged at `DEBUG`                     |
 
 use std::sync::Arc;
@@ -38,6 +39,7 @@ use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
 use crate::acp::codec::AcpCodec;
+use crate::driver::session_hooks::{SubscribeParams, SUBSCRIBE_METHOD};
 use crate::driver::{AgentDriver, AgentEvent, PermissionOption};
 use crate::models::progress::ProgressItem;
 use crate::models::session::ConnectivityStatus;
@@ -257,6 +259,7 @@ pub fn parse_inbound_line(session_id: &str, line: &str) -> Result<Option<AgentEv
         "status/update" => parse_status_update(session_id, envelope),
         "prompt/forward" => parse_prompt_forward(session_id, envelope),
         "heartbeat" => parse_heartbeat(session_id, envelope),
+        SUBSCRIBE_METHOD => parse_subscribe(session_id, envelope),
         // ACP `session/update` — streaming content from the agent during prompt
         // execution.  Treated as a status update so the operator sees progress.
         "session/update" => parse_session_update(session_id, envelope),
@@ -575,6 +578,9 @@ async fn flush_queued_messages(
     }
 
     // Post "back online" notification to the Slack thread.
+    if session_repo.is_muted(session_id).await {
+        return;
+    }
     if let (Some(ref slack), Some(ref channel_id)) = (&ctx.slack, &ctx.channel_id) {
         let thread_ts = ctx.thread_ts.as_deref().map(|s| SlackTs(s.to_owned()));
         let text = format!(
@@ -614,6 +620,7 @@ fn parse_clearance_request(session_id: &str, env: AcpEnvelope) -> Result<Option<
         diff: params.diff,
         file_path: params.file_path,
         risk_level: params.risk_level,
+        snippets: Vec::new(),
     }))
 }
 
@@ -659,6 +666,23 @@ fn parse_heartbeat(session_id: &str, env: AcpEnvelope) -> Result<Option<AgentEve
     }))
 }
 
+/// Parse an `intercom/subscribe` envelope into [`AgentEvent::HooksSubscribed`].
+///
+/// Absent params subscribe the session to every hook kind.
+fn parse_subscribe(session_id: &str, env: AcpEnvelope) -> Result<Option<AgentEvent>> {
+    let params: SubscribeParams = if env.params.is_null() {
+        SubscribeParams::default()
+    } else {
+        serde_json::from_value(env.params)
+            .map_err(|e| AppError::Acp(format!("invalid intercom/subscribe params: {e}")))?
+    };
+
+    Ok(Some(AgentEvent::HooksSubscribed {
+        session_id: session_id.to_owned(),
+        events: params.kinds(),
+    }))
+}
+
 /// Parse a standard ACP `session/request_permission` envelope into
 /// [`AgentEvent::PermissionRequested`] (ADR-0016 conformance).
 ///
@@ -11,6 +11,7 @@
 //! verifies process readiness via the ACP handshake (`initialize` /
 //! `initialized` exchange).
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use tokio::io::BufReader;
@@ -58,6 +59,9 @@ pub struct SpawnConfig {
     pub host_cli_args: Vec<String>,
     /// Workspace root directory; the child process starts in this directory.
     pub workspace_root: PathBuf,
+    /// Variables the operator set with `session-start --env`, already
+    /// checked against `[session_env] allowed`.
+    pub env: BTreeMap<String, String>,
 }
 
 // ── Connection handle ────────────────────────────────────────────────────────
@@ -88,8 +92,9 @@ pub struct AcpConnection {
 /// 1. Validates that `session_id` is non-empty.
 /// 2. Builds a `tokio::process::Command` with `env_clear()` and only the
 ///    variables listed in [`ALLOWED_ENV_VARS`].
-/// 3. Passes `INTERCOM_SESSION_ID` as an explicit environment variable.
-/// 4. Returns the connection handle immediately — readiness is verified
+/// 3. Adds the operator-set variables in [`SpawnConfig::env`].
+/// 4. Passes `INTERCOM_SESSION_ID` as an explicit environment variable.
+/// 5. Returns the connection handle immediately — readiness is verified
 ///    by the caller via the ACP handshake (`initialize` / `initialized`).
 ///
 /// The initial prompt is **not** passed as a CLI argument. Instead, the caller
@@ -114,6 +119,9 @@ pub fn spawn_agent(config: &SpawnConfig, session_id: &str) -> Result<AcpConnecti
         }
     }
 
+    // Operator-set variables may override the inherited ones.
+    cmd.envs(&config.env);
+
     // Inject ACP-specific context variables.
     cmd.env("INTERCOM_SESSION_ID", session_id);
 
@@ -1,28 +1,209 @@
 //! ACP writer task.
 //!
-//! Receives outbound JSON messages from a tokio [`mpsc`] channel, stamps each
-//! with a monotonically increasing `seq` field (ES-008, FR-040), serialises the
-//! message to a single-line JSON string, and writes the NDJSON line to the
-//! agent's `stdin` using [`tokio::io::AsyncWriteExt`].
+//! Receives outbound JSON messages from a bounded tokio [`mpsc`] channel,
+//! stamps each with a monotonically increasing `seq` field (ES-008, FR-040),
+//! serialises the message to a single-line JSON string, and writes the NDJSON
+//! line to the agent's `stdin` using [`tokio::io::AsyncWriteExt`].
 //!
 //! Each serialised message is terminated by a `\n` byte, producing valid
 //! newline-delimited JSON (NDJSON) as required by the ACP wire format.
 //!
-//! On write failure (e.g., broken pipe / agent crash), the task logs `WARN`
-//! with the `method`, `session_id`, and `seq` fields, marks the session as
-//! `Interrupted` in the database, and returns an error (ES-008, FR-041).
+//! On write failure (e.g., broken pipe / agent crash) or when a single write
+//! exceeds [`WriterLimits::write_timeout`] (a stuck agent that stopped reading
+//! stdin), the task logs `WARN` with the `method`, `session_id`, and `seq`
+//! fields, marks the session as `Interrupted` in the database, and returns an
+//! error (ES-008, FR-041).
+//!
+//! Producers never await the writer directly: they call [`enqueue`], which
+//! applies the configured [`AcpWriterOverflow`] policy so a saturated queue
+//! fails fast (or after a bounded wait) instead of blocking Slack handlers.
+//! Queue depth, drops, and timeouts are tracked in [`WriterMetrics`].
 
-use std::sync::{atomic::AtomicU64, Arc};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
+use serde::Serialize;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, warn};
 
+use crate::config::{AcpConfig, AcpWriterOverflow};
 use crate::models::session::SessionStatus;
 use crate::persistence::session_repo::SessionRepo;
 use crate::{AppError, Result};
 
+/// Queue depth (as a percentage of capacity) at which saturation is logged.
+const SATURATION_WARN_PERCENT: usize = 80;
+
+/// Bounded-queue and timeout settings shared by the writer task and producers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriterLimits {
+    /// Capacity of the per-session outbound [`mpsc`] channel.
+    pub queue_capacity: usize,
+    /// Maximum duration of a single stdin write, and of a blocked enqueue
+    /// under [`AcpWriterOverflow::Wait`].
+    pub write_timeout: Duration,
+    /// Behaviour when the outbound queue is full.
+    pub overflow: AcpWriterOverflow,
+}
+
+impl WriterLimits {
+    /// Build writer limits from the `[acp]` configuration section.
+    #[must_use]
+    pub fn from_config(acp: &AcpConfig) -> Self {
+        Self {
+            queue_capacity: acp.writer_queue_capacity.max(1),
+            write_timeout: Duration::from_secs(acp.write_timeout_seconds.max(1)),
+            overflow: acp.writer_overflow,
+        }
+    }
+}
+
+impl Default for WriterLimits {
+    fn default() -> Self {
+        Self::from_config(&AcpConfig::default())
+    }
+}
+
+/// Per-session outbound queue counters.
+///
+/// Shared between [`enqueue`] (producer side) and [`run_writer`] (consumer
+/// side). All counters are monotonic except `depth`, which tracks the number
+/// of messages currently queued but not yet written.
+#[derive(Debug, Default)]
+pub struct WriterMetrics {
+    enqueued: AtomicU64,
+    written: AtomicU64,
+    rejected: AtomicU64,
+    enqueue_timeouts: AtomicU64,
+    write_timeouts: AtomicU64,
+    depth: AtomicUsize,
+    high_water: AtomicUsize,
+}
+
+/// Point-in-time copy of [`WriterMetrics`] suitable for serialisation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct WriterMetricsSnapshot {
+    /// Messages accepted into the queue.
+    pub enqueued: u64,
+    /// Messages successfully written to the agent's stdin.
+    pub written: u64,
+    /// Enqueue attempts rejected because the queue was full.
+    pub rejected: u64,
+    /// Enqueue attempts that timed out waiting for queue space.
+    pub enqueue_timeouts: u64,
+    /// Writes that exceeded the write timeout.
+    pub write_timeouts: u64,
+    /// Messages currently queued.
+    pub depth: usize,
+    /// Highest queue depth observed.
+    pub high_water: usize,
+}
+
+impl WriterMetrics {
+    /// Capture the current counter values.
+    #[must_use]
+    pub fn snapshot(&self) -> WriterMetricsSnapshot {
+        WriterMetricsSnapshot {
+            enqueued: self.enqueued.load(Ordering::Relaxed),
+            written: self.written.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            enqueue_timeouts: self.enqueue_timeouts.load(Ordering::Relaxed),
+            write_timeouts: self.write_timeouts.load(Ordering::Relaxed),
+            depth: self.depth.load(Ordering::Relaxed),
+            high_water: self.high_water.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Reserve a queue slot in the depth gauge ahead of the actual send, so a
+    /// writer that dequeues immediately cannot observe a negative depth.
+    fn reserve_slot(&self) -> usize {
+        self.depth.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    fn record_dequeued(&self) {
+        // Saturating decrement: a message sent with a raw `Sender::send`
+        // (bypassing `enqueue`) must not underflow the gauge.
+        let _ = self
+            .depth
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |d| {
+                Some(d.saturating_sub(1))
+            });
+    }
+}
+
+/// Queue `msg` on a session's outbound writer channel.
+///
+/// Applies `limits.overflow` when the queue is full:
+/// - [`AcpWriterOverflow::Reject`] fails immediately.
+/// - [`AcpWriterOverflow::Wait`] waits up to `limits.write_timeout`.
+///
+/// A `WARN` is logged once the queue depth reaches 80% of capacity.
+///
+/// # Errors
+///
+/// - [`AppError::Acp`]`("outbound queue full …")` when the queue is saturated.
+/// - [`AppError::Acp`]`("write failed: stream closed …")` when the writer task
+///   has exited (agent disconnected).
+pub async fn enqueue(
+    tx: &mpsc::Sender<serde_json::Value>,
+    msg: serde_json::Value,
+    limits: &WriterLimits,
+    metrics: &WriterMetrics,
+    session_id: &str,
+) -> Result<()> {
+    let depth = metrics.reserve_slot();
+    let sent = match limits.overflow {
+        AcpWriterOverflow::Reject => match tx.try_send(msg) {
+            Ok(()) => Ok(()),
+            Err(mpsc::error::TrySendError::Closed(_)) => Err(None),
+            Err(mpsc::error::TrySendError::Full(_)) => Err(Some(&metrics.rejected)),
+        },
+        AcpWriterOverflow::Wait => match tx.send_timeout(msg, limits.write_timeout).await {
+            Ok(()) => Ok(()),
+            Err(mpsc::error::SendTimeoutError::Closed(_)) => Err(None),
+            Err(mpsc::error::SendTimeoutError::Timeout(_)) => Err(Some(&metrics.enqueue_timeouts)),
+        },
+    };
+
+    match sent {
+        Ok(()) => {
+            metrics.enqueued.fetch_add(1, Ordering::Relaxed);
+            metrics.high_water.fetch_max(depth, Ordering::Relaxed);
+            let capacity = tx.max_capacity();
+            if depth * 100 >= capacity * SATURATION_WARN_PERCENT {
+                warn!(
+                    session_id,
+                    depth, capacity, "acp writer: outbound queue nearing saturation"
+                );
+            }
+            Ok(())
+        }
+        Err(None) => {
+            metrics.record_dequeued();
+            warn!(session_id, "acp writer: enqueue failed — stream closed");
+            Err(AppError::Acp(format!(
+                "write failed: stream closed for session '{session_id}'"
+            )))
+        }
+        Err(Some(counter)) => {
+            metrics.record_dequeued();
+            counter.fetch_add(1, Ordering::Relaxed);
+            warn!(
+                session_id,
+                capacity = tx.max_capacity(),
+                overflow = ?limits.overflow,
+                "acp writer: outbound queue saturated — message not delivered"
+            );
+            Err(AppError::Acp(format!(
+                "outbound queue full for session '{session_id}' — agent is not reading its stream"
+            )))
+        }
+    }
+}
+
 /// ACP writer task — stamps, serialises, and writes outbound JSON messages.
 ///
 /// Receives [`serde_json::Value`] objects from `msg_rx`, stamps each with the
@@ -33,11 +214,15 @@ use crate::{AppError, Result};
 /// - `cancel` is triggered (graceful shutdown), or
 /// - `msg_rx` is closed (all senders dropped).
 ///
-/// On write failure the task:
+/// On write failure — including a write that does not complete within
+/// `write_timeout` because the agent stopped draining its stdin — the task:
 /// 1. Logs `WARN` with `session_id`, `method`, and `seq`.
 /// 2. Calls [`SessionRepo::set_terminated`] to mark the session `Interrupted`.
 /// 3. Returns [`AppError::Acp`]`("write failed: …")`.
 ///
+/// Exiting drops `msg_rx`, so producers blocked in [`enqueue`] fail fast with
+/// a "stream closed" error rather than waiting on a dead session.
+///
 /// # Type parameter
 ///
 /// `W` — any [`tokio::io::AsyncWrite`] + [`Unpin`] + [`Send`] type.  In
@@ -48,7 +233,9 @@ use crate::{AppError, Result};
 ///
 /// - [`AppError::Acp`]`("failed to serialise outbound message: …")` if
 ///   serialisation fails (should not occur for `Value`).
-/// - [`AppError::Acp`]`("write failed: …")` if the write to `stdin` fails.
+/// - [`AppError::Acp`]`("write failed: …")` if the write to `stdin` fails or
+///   times out.
+#[allow(clippy::too_many_arguments)]
 pub async fn run_writer<W>(
     session_id: String,
     stdin: W,
@@ -56,6 +243,8 @@ pub async fn run_writer<W>(
     cancel: CancellationToken,
     counter: Arc<AtomicU64>,
     db: Arc<sqlx::SqlitePool>,
+    write_timeout: Duration,
+    metrics: Arc<WriterMetrics>,
 ) -> Result<()>
 where
     W: tokio::io::AsyncWrite + Unpin + Send,
@@ -78,8 +267,9 @@ where
                         break;
                     }
                     Some(mut value) => {
+                        metrics.record_dequeued();
                         // Stamp sequence number before serialisation.
-                        let seq = counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                        let seq = counter.fetch_add(1, Ordering::Relaxed);
                         let method = value
                             .get("method")
                             .and_then(|m| m.as_str())
@@ -99,7 +289,18 @@ where
                         // NDJSON: append the newline delimiter.
                         bytes.push(b'\n');
 
-                        if let Err(e) = stdin.write_all(&bytes).await {
+                        let write_result = if let Ok(result) =
+                            tokio::time::timeout(write_timeout, stdin.write_all(&bytes)).await
+                        {
+                            result.map_err(|e| e.to_string())
+                        } else {
+                            metrics.write_timeouts.fetch_add(1, Ordering::Relaxed);
+                            Err(format!(
+                                "timed out after {write_timeout:?} — agent is not reading stdin"
+                            ))
+                        };
+
+                        if let Err(e) = write_result {
                             warn!(
                                 session_id,
                                 method,
@@ -126,6 +327,7 @@ where
                             }
                             return Err(AppError::Acp(format!("write failed: {e}")));
                         }
+                        metrics.written.fetch_add(1, Ordering::Relaxed);
                     }
                 }
             }
@@ -2,10 +2,17 @@
 //!
 //! Provides the [`AuditLogger`] trait and associated types. The primary
 //! implementation, [`JsonlAuditWriter`], appends JSONL records to
-//! daily-rotating files in `.intercom/logs/`.
+//! daily-rotating files in `.intercom/logs/`, and copies every entry that
+//! names a session to that session's own file in `.intercom/logs/sessions/`.
+//! Files rotate by size and day and are pruned by the retention task (see
+//! [`rotation`]).
 
+pub mod reader;
+pub mod rotation;
 pub mod writer;
 
+use std::path::{Path, PathBuf};
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -41,6 +48,47 @@ pub enum AuditEventType {
     AcpSteerDelivered,
     /// Task queued for ACP agent execution (FR-043).
     AcpTaskQueued,
+    /// Agent-originated event observed on the event bus (clearance or prompt
+    /// request, session termination). The variant name is in `result_summary`.
+    AgentEvent,
+    /// Steering message dropped because its TTL elapsed before delivery.
+    SteeringExpired,
+    /// Session interrupted on reaching its time-box deadline.
+    SessionTimeBoxExpired,
+    /// Relay message sent from one session to another.
+    RelaySent,
+    /// Relay message collected by its recipient session.
+    RelayDelivered,
+    /// Knowledge note proposed by an agent.
+    KnowledgeProposed,
+    /// Operator approved or rejected a knowledge note; the outcome is in
+    /// `result_summary`.
+    KnowledgeDecided,
+    /// Forwarded prompt answered by a `[[prompts.auto]]` rule.
+    PromptAutoDecision,
+    /// Session re-routed to another Slack channel with `session-move`.
+    SessionMove,
+    /// Command received over the local IPC socket (`agent-intercom-ctl`).
+    /// The verb is in `command`, its arguments in `parameters`, the peer in
+    /// `operator_id`, and `ok` or the error in `result_summary`.
+    IpcCommand,
+    /// Session acquired a workspace path lock with `acquire_lock`. The path
+    /// is in `result_summary`, the lock ID in `request_id`.
+    LockAcquired,
+    /// Session released a workspace path lock with `release_lock`.
+    LockReleased,
+    /// Operator re-opened an approval request that had timed out.
+    ApprovalRevived,
+    /// Operator added a `[commands]` alias with `commands add`. The alias is
+    /// in `parameters`, its shell command in `command`.
+    CommandAliasAdded,
+    /// Operator restored a workspace snapshot with `workspace-restore`. The
+    /// snapshot ID is in `request_id`, the snapshot taken just before in
+    /// `parameters`, and the files restored and removed in `result_summary`.
+    WorkspaceRestored,
+    /// The file of an unapplied diff changed on disk after the diff was
+    /// proposed, so the request was marked stale. The reason names the file.
+    ApprovalStale,
 }
 
 /// A structured record of an agent interaction event.
@@ -64,7 +112,8 @@ pub struct AuditEntry {
     pub reason: Option<String>,
     /// Approval request identifier (for approval/rejection events).
     pub request_id: Option<String>,
-    /// Terminal command (for command approval/rejection events).
+    /// Terminal command (for command approval/rejection events) or IPC verb
+    /// (for `ipc_command` events).
     pub command: Option<String>,
 }
 
@@ -93,6 +142,13 @@ impl AuditEntry {
         self
     }
 
+    /// Set the parameters for this entry.
+    #[must_use]
+    pub fn with_parameters(mut self, parameters: serde_json::Value) -> Self {
+        self.parameters = Some(parameters);
+        self
+    }
+
     /// Set the MCP tool name for this entry.
     #[must_use]
     pub fn with_tool(mut self, tool_name: String) -> Self {
@@ -136,6 +192,27 @@ impl AuditEntry {
     }
 }
 
+/// Directory holding the audit log for `workspace_root`.
+#[must_use]
+pub fn log_dir(workspace_root: &Path) -> PathBuf {
+    workspace_root.join(".intercom/logs")
+}
+
+/// Per-session log file for `session_id` under the audit `log_dir`:
+/// `<log_dir>/sessions/<id>.jsonl`.
+///
+/// Returns `None` for IDs that are not a plain file stem (anything other
+/// than ASCII alphanumerics, `-` and `_`), so a crafted ID cannot name a
+/// path outside the sessions directory.
+#[must_use]
+pub fn session_log_path(log_dir: &Path, session_id: &str) -> Option<PathBuf> {
+    let safe = !session_id.is_empty()
+        && session_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    safe.then(|| log_dir.join("sessions").join(format!("{session_id}.jsonl")))
+}
+
 /// Writes structured audit entries to a persistent store.
 ///
 /// Implementations must be [`Send`] and [`Sync`] to allow sharing across
@@ -0,0 +1,121 @@
+//! Read-side lookups over the JSONL audit log.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use tracing::warn;
+
+use super::{rotation, AuditEntry, AuditEventType};
+
+/// Find the approval/rejection audit entry recorded for each of `request_ids`.
+///
+/// Daily files are scanned newest first and the scan stops once every
+/// request has been found, so recent decisions are cheap to look up however
+/// long the log has grown. Requests decided without an audit entry (or whose
+/// files have been pruned) are simply absent from the result. Unreadable
+/// files and malformed lines are skipped.
+#[must_use]
+pub fn find_decisions(log_dir: &Path, request_ids: &[&str]) -> HashMap<String, AuditEntry> {
+    let mut found: HashMap<String, AuditEntry> = HashMap::new();
+    if request_ids.is_empty() {
+        return found;
+    }
+
+    for path in log_files(log_dir).into_iter().rev() {
+        let contents = match rotation::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) => {
+                warn!(%err, path = %path.display(), "skipping unreadable audit log");
+                continue;
+            }
+        };
+        // Within a day, a later entry for the same request wins.
+        for entry in contents
+            .lines()
+            .rev()
+            .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+        {
+            if !matches!(
+                entry.event_type,
+                AuditEventType::Approval | AuditEventType::Rejection
+            ) {
+                continue;
+            }
+            let Some(request_id) = entry.request_id.clone() else {
+                continue;
+            };
+            if request_ids.contains(&request_id.as_str()) && !found.contains_key(&request_id) {
+                found.insert(request_id, entry);
+            }
+        }
+        if found.len() == request_ids.len() {
+            break;
+        }
+    }
+
+    found
+}
+
+/// All audit entries recorded for `session_id` on or after `since`, oldest
+/// first.
+///
+/// Only daily files dated `since` or later are read, so passing the
+/// session's start date keeps the scan to the session's lifetime.
+#[must_use]
+pub fn entries_for_session(log_dir: &Path, session_id: &str, since: NaiveDate) -> Vec<AuditEntry> {
+    let mut entries = Vec::new();
+    for path in log_files(log_dir) {
+        if path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(rotation::audit_file_date)
+            .is_some_and(|date| date < since)
+        {
+            continue;
+        }
+        let contents = match rotation::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) => {
+                warn!(%err, path = %path.display(), "skipping unreadable audit log");
+                continue;
+            }
+        };
+        entries.extend(
+            contents
+                .lines()
+                .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+                .filter(|entry| entry.session_id.as_deref() == Some(session_id)),
+        );
+    }
+    entries
+}
+
+/// Audit files in `log_dir`, oldest first: live `audit-YYYY-MM-DD.jsonl`
+/// files and their rotated `audit-YYYY-MM-DD.NNN.jsonl[.gz]` segments.
+///
+/// The names sort chronologically, each day's segments before its live
+/// file. A missing or unreadable directory yields no files.
+fn log_files(log_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(log_dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(std::result::Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| {
+                    let name = name.strip_suffix(".gz").unwrap_or(name);
+                    name.starts_with("audit-")
+                        && Path::new(name)
+                            .extension()
+                            .is_some_and(|ext| ext == "jsonl")
+                })
+        })
+        .collect();
+    files.sort_unstable();
+    files
+}
@@ -0,0 +1,285 @@
+//! Size- and age-based rotation, gzip compression and retention for log
+//! files (`[logging]`).
+//!
+//! A live file rotates when it would grow past `max_file_bytes` or when the
+//! calendar day changes. Its contents move to a numbered segment named
+//! after the day it was written — `audit-2026-10-17.001.jsonl` for an audit
+//! file, `agent-intercom.log.2026-10-17.001` for the tracing log — which is
+//! gzip-compressed (`.gz`) when `compress` is set. Segment numbers sort
+//! before the live file, so a plain name sort stays chronological.
+//!
+//! [`prune`] runs from the retention task: it compresses audit files left
+//! live by a restart and deletes rotated files, and per-session logs, older
+//! than `retention_days`.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use tracing::warn;
+
+use crate::config::LoggingConfig;
+
+/// Suffix of compressed segments.
+const GZ_SUFFIX: &str = ".gz";
+
+/// Path of the next free segment `<prefix>NNN<suffix>` in `dir`, counting
+/// compressed and uncompressed segments alike.
+#[must_use]
+pub fn next_segment(dir: &Path, prefix: &str, suffix: &str) -> PathBuf {
+    let highest = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(std::result::Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_owned();
+            let rest = name.strip_prefix(prefix)?;
+            let rest = rest.strip_suffix(GZ_SUFFIX).unwrap_or(rest);
+            rest.strip_suffix(suffix)?.parse::<u32>().ok()
+        })
+        .max()
+        .unwrap_or(0);
+    dir.join(format!("{prefix}{:03}{suffix}", highest + 1))
+}
+
+/// Move `live` to `segment`, gzip-compressing it when `compress` is set.
+/// Returns the final path.
+///
+/// # Errors
+///
+/// Returns the I/O error from the rename or the compression. A failed
+/// compression leaves the uncompressed segment in place.
+pub fn rotate(live: &Path, segment: &Path, compress: bool) -> io::Result<PathBuf> {
+    fs::rename(live, segment)?;
+    if compress {
+        gzip(segment)
+    } else {
+        Ok(segment.to_owned())
+    }
+}
+
+/// Compress `path` to `<path>.gz` and remove the original.
+///
+/// # Errors
+///
+/// Returns the I/O error from reading, writing or removing the file.
+pub fn gzip(path: &Path) -> io::Result<PathBuf> {
+    let mut target = path.as_os_str().to_owned();
+    target.push(GZ_SUFFIX);
+    let target = PathBuf::from(target);
+    let mut input = BufReader::new(File::open(path)?);
+    let mut encoder = GzEncoder::new(File::create(&target)?, Compression::default());
+    io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?.sync_all()?;
+    fs::remove_file(path)?;
+    Ok(target)
+}
+
+/// Read a log file, decompressing it when its name ends in `.gz`.
+///
+/// # Errors
+///
+/// Returns the I/O error from reading, or `InvalidData` for corrupt
+/// compressed data or non-UTF-8 contents.
+pub fn read_to_string(path: &Path) -> io::Result<String> {
+    if !is_compressed(path) {
+        return fs::read_to_string(path);
+    }
+    let mut contents = String::new();
+    GzDecoder::new(File::open(path)?).read_to_string(&mut contents)?;
+    Ok(contents)
+}
+
+fn is_compressed(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(GZ_SUFFIX))
+}
+
+/// Date of an audit file from its name: `audit-YYYY-MM-DD…`.
+#[must_use]
+pub fn audit_file_date(name: &str) -> Option<NaiveDate> {
+    let date = name.strip_prefix("audit-")?.get(..10)?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+/// A tracing log file that rotates by size and by day (`[logging] file`).
+///
+/// Segments are named `<file>.<YYYY-MM-DD>.<NNN>[.gz]` next to the live
+/// file. Wrap it in a [`std::sync::Mutex`] to use it as a
+/// `tracing_subscriber` writer.
+pub struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    compress: bool,
+    file: File,
+    size: u64,
+    opened_on: NaiveDate,
+}
+
+impl RotatingFile {
+    /// Open (appending to) the live file at `path`, creating its directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error if the directory or file cannot be created.
+    pub fn open(path: impl Into<PathBuf>, config: &LoggingConfig) -> io::Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let metadata = file.metadata()?;
+        let opened_on = metadata
+            .modified()
+            .map_or_else(|_| Utc::now(), DateTime::<Utc>::from)
+            .date_naive();
+        Ok(Self {
+            path,
+            max_bytes: config.max_file_bytes,
+            compress: config.compress,
+            file,
+            size: metadata.len(),
+            opened_on,
+        })
+    }
+
+    /// Rotated segments of the tracing log live next to it and start with
+    /// `<file name>.`; `None` when the path has no file name.
+    fn segment_prefix(path: &Path) -> Option<String> {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| format!("{name}."))
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let dir = self
+            .path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        if let Some(prefix) = Self::segment_prefix(&self.path) {
+            let prefix = format!("{prefix}{}.", self.opened_on);
+            let segment = next_segment(dir, &prefix, "");
+            rotate(&self.path, &segment, self.compress)?;
+        }
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+        self.opened_on = Utc::now().date_naive();
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let over_size = self.size > 0 && self.size + buf.len() as u64 > self.max_bytes;
+        if over_size || self.opened_on != Utc::now().date_naive() {
+            if let Err(err) = self.rotate() {
+                // Keep logging to the live file rather than losing output.
+                eprintln!("log rotation failed for {}: {err}", self.path.display());
+                self.size = 0;
+                self.opened_on = Utc::now().date_naive();
+            }
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Compress stale audit files and delete expired log files.
+///
+/// In `log_dir`, audit files from earlier days that are still uncompressed
+/// are rotated into compressed segments (when `compress` is set). With a
+/// non-zero `retention_days`, audit files dated before the cutoff,
+/// per-session logs (`sessions/*.jsonl`) not written since the cutoff, and
+/// rotated segments of the tracing log `file` dated before it are deleted.
+/// Returns the number of files deleted. Failures on individual files are
+/// logged and skipped.
+#[must_use]
+pub fn prune(log_dir: &Path, config: &LoggingConfig, now: DateTime<Utc>) -> usize {
+    let today = now.date_naive();
+    let cutoff = (config.retention_days > 0)
+        .then(|| today - chrono::Duration::days(i64::from(config.retention_days)));
+    let mut deleted = 0;
+
+    for (path, name) in files_in(log_dir) {
+        let Some(date) = audit_file_date(&name) else {
+            continue;
+        };
+        if cutoff.is_some_and(|cutoff| date < cutoff) {
+            deleted += remove(&path);
+        } else if config.compress && date < today && name == format!("audit-{date}.jsonl") {
+            let segment = next_segment(log_dir, &format!("audit-{date}."), ".jsonl");
+            if let Err(err) = rotate(&path, &segment, true) {
+                warn!(%err, path = %path.display(), "failed to compress audit log");
+            }
+        }
+    }
+
+    let Some(cutoff) = cutoff else {
+        return deleted;
+    };
+
+    for (path, _) in files_in(&log_dir.join("sessions")) {
+        let modified = fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .map(|m| DateTime::<Utc>::from(m).date_naive());
+        if modified.is_ok_and(|date| date < cutoff) {
+            deleted += remove(&path);
+        }
+    }
+
+    if let Some(ref file) = config.file {
+        if let Some(prefix) = RotatingFile::segment_prefix(file) {
+            let dir = file.parent().filter(|p| !p.as_os_str().is_empty());
+            for (path, name) in files_in(dir.unwrap_or_else(|| Path::new("."))) {
+                let date = name
+                    .strip_prefix(&prefix)
+                    .and_then(|rest| rest.get(..10))
+                    .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+                if date.is_some_and(|date| date < cutoff) {
+                    deleted += remove(&path);
+                }
+            }
+        }
+    }
+
+    deleted
+}
+
+/// Regular files directly in `dir` with their UTF-8 names.
+fn files_in(dir: &Path) -> Vec<(PathBuf, String)> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_owned();
+            Some((entry.path(), name))
+        })
+        .collect()
+}
+
+fn remove(path: &Path) -> usize {
+    match fs::remove_file(path) {
+        Ok(()) => 1,
+        Err(err) => {
+            warn!(%err, path = %path.display(), "failed to delete expired log file");
+            0
+        }
+    }
+}
@@ -1,4 +1,4 @@
-//! JSONL audit log writer with daily file rotation.
+//! JSONL audit log writer with size and daily rotation and per-session copies.
 
 use std::{
     fs::{self, OpenOptions},
@@ -10,21 +10,33 @@ use std::{
 use chrono::{NaiveDate, Utc};
 use tracing::warn;
 
-use super::{AuditEntry, AuditLogger};
+use super::{rotation, AuditEntry, AuditLogger};
+use crate::config::LoggingConfig;
 use crate::Result;
 
 /// Internal state protected by a mutex.
 struct WriterState {
     current_date: NaiveDate,
     writer: BufWriter<fs::File>,
+    size: u64,
 }
 
-/// A daily-rotating JSONL audit log writer.
+/// A rotating JSONL audit log writer.
 ///
 /// Appends one JSON object per line to `<log_dir>/audit-YYYY-MM-DD.jsonl`.
-/// Automatically opens a new file when the calendar date changes between writes.
+/// When the calendar date changes between writes, or the file would grow
+/// past `[logging] max_file_bytes`, the live file is moved to a numbered
+/// segment (gzip-compressed when `[logging] compress` is set; see
+/// [`rotation`]) and a new one is started.
+///
+/// Entries carrying a `session_id` are also appended to
+/// `<log_dir>/sessions/<id>.jsonl` (see [`super::session_log_path`]), so one
+/// session's tool calls, events and decisions can be read without filtering
+/// the shared daily file. A failed per-session write is logged and does not
+/// fail the entry.
 pub struct JsonlAuditWriter {
     log_dir: PathBuf,
+    logging: LoggingConfig,
     state: Mutex<Option<WriterState>>,
 }
 
@@ -45,13 +57,25 @@ impl JsonlAuditWriter {
         })?;
         Ok(Self {
             log_dir,
+            logging: LoggingConfig::default(),
             state: Mutex::new(None),
         })
     }
 
-    fn open_for_date(log_dir: &Path, date: NaiveDate) -> crate::Result<BufWriter<fs::File>> {
-        let file_name = format!("audit-{date}.jsonl");
-        let path = log_dir.join(file_name);
+    /// Rotate and compress according to `logging` instead of the
+    /// `[logging]` defaults.
+    #[must_use]
+    pub fn with_logging(mut self, logging: LoggingConfig) -> Self {
+        self.logging = logging;
+        self
+    }
+
+    fn live_path(log_dir: &Path, date: NaiveDate) -> PathBuf {
+        log_dir.join(format!("audit-{date}.jsonl"))
+    }
+
+    fn open_for_date(log_dir: &Path, date: NaiveDate) -> crate::Result<WriterState> {
+        let path = Self::live_path(log_dir, date);
         let file = OpenOptions::new()
             .create(true)
             .append(true)
@@ -59,33 +83,77 @@ impl JsonlAuditWriter {
             .map_err(|e| {
                 crate::AppError::Io(format!("failed to open audit log {}: {e}", path.display()))
             })?;
-        Ok(BufWriter::new(file))
+        let size = file.metadata().map_or(0, |m| m.len());
+        Ok(WriterState {
+            current_date: date,
+            writer: BufWriter::new(file),
+            size,
+        })
+    }
+
+    /// Move the live file for `date` to its next segment. A live file that
+    /// is already gone (rotated by the retention task) is left alone.
+    fn rotate_out(&self, date: NaiveDate) {
+        let live = Self::live_path(&self.log_dir, date);
+        if !live.exists() {
+            return;
+        }
+        let segment = rotation::next_segment(&self.log_dir, &format!("audit-{date}."), ".jsonl");
+        if let Err(e) = rotation::rotate(&live, &segment, self.logging.compress) {
+            warn!(path = %live.display(), "failed to rotate audit log: {e}");
+        }
+    }
+
+    /// Append `line` to the per-session file for `session_id`.
+    ///
+    /// The file is opened per write rather than cached, so long-running
+    /// servers do not accumulate one handle per session ever seen.
+    fn append_session_line(&self, session_id: &str, line: &str) -> std::io::Result<()> {
+        let Some(path) = super::session_log_path(&self.log_dir, session_id) else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        writeln!(file, "{line}")
     }
 }
 
 impl AuditLogger for JsonlAuditWriter {
     fn log_entry(&self, entry: AuditEntry) -> Result<()> {
         let today = Utc::now().date_naive();
+        let line = serde_json::to_string(&entry)
+            .map_err(|e| crate::AppError::Io(format!("failed to serialize audit entry: {e}")))?;
+        let line_len = line.len() as u64 + 1;
 
         let mut guard = self
             .state
             .lock()
             .map_err(|_| crate::AppError::Io("audit writer mutex poisoned".to_string()))?;
 
-        let needs_rotation = guard.as_ref().is_none_or(|s| s.current_date != today);
+        let needs_rotation = guard.as_ref().is_none_or(|s| {
+            s.current_date != today
+                || (s.size > 0 && s.size + line_len > self.logging.max_file_bytes)
+        });
 
         if needs_rotation {
-            let new_writer = Self::open_for_date(&self.log_dir, today)?;
-            *guard = Some(WriterState {
-                current_date: today,
-                writer: new_writer,
-            });
+            if let Some(previous) = guard.take() {
+                drop(previous.writer);
+                self.rotate_out(previous.current_date);
+            }
+            let mut state = Self::open_for_date(&self.log_dir, today)?;
+            // A live file left over from a previous run may already be full.
+            if state.size > 0 && state.size + line_len > self.logging.max_file_bytes {
+                drop(state.writer);
+                self.rotate_out(today);
+                state = Self::open_for_date(&self.log_dir, today)?;
+            }
+            *guard = Some(state);
         }
 
         if let Some(state) = guard.as_mut() {
-            let line = serde_json::to_string(&entry).map_err(|e| {
-                crate::AppError::Io(format!("failed to serialize audit entry: {e}"))
-            })?;
+            state.size += line_len;
             if let Err(e) = writeln!(state.writer, "{line}") {
                 warn!("failed to write audit log entry: {e}");
                 return Err(crate::AppError::Io(format!("audit write failed: {e}")));
@@ -94,6 +162,11 @@ impl AuditLogger for JsonlAuditWriter {
                 warn!("failed to flush audit log: {e}");
                 return Err(crate::AppError::Io(format!("audit flush failed: {e}")));
             }
+            if let Some(ref session_id) = entry.session_id {
+                if let Err(e) = self.append_session_line(session_id, &line) {
+                    warn!(session_id, "failed to write session log entry: {e}");
+                }
+            }
         }
 
         Ok(())
@@ -0,0 +1,117 @@
+//! Fault injection for exercising recovery paths (feature `chaos`).
+//!
+//! Compiled only with `--features chaos`. Faults are process-wide and off
+//! until set, either directly through [`faults`] in tests or with the
+//! `chaos` IPC command (`agent-intercom-ctl chaos`):
+//!
+//! - **Dropped Slack posts** — a percentage of outgoing posts fail as if
+//!   Slack never received them. Drops are spread evenly rather than drawn
+//!   at random (50% drops every second post), so tests stay deterministic.
+//! - **Delayed resolution** — each approval, prompt and wait oneshot is
+//!   held for a fixed time before the decision reaches the agent.
+//! - **Socket kill** — the Socket Mode connection is torn down and
+//!   re-established, which replays the reconnect path (T095 re-post).
+
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use tokio::sync::Notify;
+use tracing::warn;
+
+static FAULTS: LazyLock<Faults> = LazyLock::new(Faults::default);
+
+/// The process-wide fault settings.
+#[must_use]
+pub fn faults() -> &'static Faults {
+    &FAULTS
+}
+
+/// Fault settings and the socket kill switch.
+#[derive(Debug, Default)]
+pub struct Faults {
+    drop_percent: AtomicU8,
+    posts: AtomicU64,
+    resolution_delay_ms: AtomicU64,
+    socket_listening: AtomicBool,
+    socket_kill: Notify,
+}
+
+impl Faults {
+    /// Drop `percent` (capped at 100) of Slack posts from now on.
+    pub fn set_drop_percent(&self, percent: u8) {
+        self.drop_percent.store(percent.min(100), Ordering::Relaxed);
+        self.posts.store(0, Ordering::Relaxed);
+    }
+
+    /// Percentage of Slack posts being dropped.
+    #[must_use]
+    pub fn drop_percent(&self) -> u8 {
+        self.drop_percent.load(Ordering::Relaxed)
+    }
+
+    /// Hold every oneshot resolution for `delay`.
+    pub fn set_resolution_delay(&self, delay: Duration) {
+        let millis = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX);
+        self.resolution_delay_ms.store(millis, Ordering::Relaxed);
+    }
+
+    /// Current resolution delay.
+    #[must_use]
+    pub fn resolution_delay(&self) -> Duration {
+        Duration::from_millis(self.resolution_delay_ms.load(Ordering::Relaxed))
+    }
+
+    /// Turn every fault off.
+    pub fn reset(&self) {
+        self.set_drop_percent(0);
+        self.set_resolution_delay(Duration::ZERO);
+    }
+
+    /// Whether the next Slack post should be dropped.
+    #[must_use]
+    pub fn drop_post(&self) -> bool {
+        let percent = u64::from(self.drop_percent());
+        if percent == 0 {
+            return false;
+        }
+        let n = self.posts.fetch_add(1, Ordering::Relaxed) + 1;
+        let dropped = n * percent / 100 > (n - 1) * percent / 100;
+        if dropped {
+            warn!(post = n, percent, "chaos: dropping slack post");
+        }
+        dropped
+    }
+
+    /// Wait out the resolution delay, if any.
+    pub async fn delay_resolution(&self) {
+        let delay = self.resolution_delay();
+        if !delay.is_zero() {
+            warn!(
+                delay_ms = delay.as_millis(),
+                "chaos: delaying oneshot resolution"
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Tear down the Socket Mode connection. Returns `false` when no
+    /// connection is running.
+    pub fn kill_socket(&self) -> bool {
+        let listening = self.socket_listening.load(Ordering::Relaxed);
+        if listening {
+            warn!("chaos: killing socket mode connection");
+            self.socket_kill.notify_waiters();
+        }
+        listening
+    }
+
+    /// Resolve when [`Faults::kill_socket`] is called. Used by the Socket
+    /// Mode task, which marks the connection as running while it waits.
+    pub async fn socket_killed(&self) {
+        let notified = self.socket_kill.notified();
+        self.socket_listening.store(true, Ordering::Relaxed);
+        notified.await;
+        self.socket_listening.store(false, Ordering::Relaxed);
+    }
+}
@@ -1,14 +1,20 @@
 //! Global configuration parsing, validation, and credential loading.
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use chrono::{Datelike, NaiveDateTime, NaiveTime};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::config_env;
 use crate::mode::ServerMode;
+use crate::models::approval::RiskLevel;
+use crate::models::prompt::{PromptDecision, PromptType};
 use crate::{AppError, Result};
 
 /// Strip the Windows `\\?\` extended-length path prefix from a [`PathBuf`].
@@ -49,9 +55,28 @@ pub fn strip_unc_prefix(path: PathBuf) -> PathBuf {
 /// channel_id   = "C0123456789"
 /// label        = "My Repository"
 /// path         = "/home/user/projects/my-repo"
+///
+/// [[workspace.proxy]]
+/// name    = "fs"
+/// command = "npx"
+/// args    = ["-y", "@modelcontextprotocol/server-filesystem", "."]
+/// tools   = ["read_*", "write_file"]
+///
+/// [[workspace.approval_channels]]
+/// channel_id = "C0SECURITY01"
+/// min_risk   = "critical"
+///
+/// [workspace.working_hours]
+/// hours                  = "09:00-17:00"
+/// weekdays_only          = true
+/// after_hours_channel_id = "C0ONCALL0001"
+///
+/// [workspace.bootstrap]
+/// commands        = ["npm ci", "cargo fetch"]
+/// timeout_seconds = 600
 /// ```
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub struct WorkspaceMapping {
     /// Short identifier supplied by agents as `?workspace_id=<id>`.
     ///
@@ -68,6 +93,277 @@ pub struct WorkspaceMapping {
     /// Used in ACP mode as the `current_dir()` for the spawned agent process.
     /// Falls back to `GlobalConfig::default_workspace_root` when absent.
     pub path: Option<PathBuf>,
+    /// Downstream MCP servers whose tools are re-exported to this
+    /// workspace's agents behind the approval gate (`[[workspace.proxy]]`).
+    #[serde(default)]
+    pub proxy: Vec<ProxyServerConfig>,
+    /// Further channels approval requests are posted to alongside
+    /// `channel_id` (`[[workspace.approval_channels]]`).
+    #[serde(default)]
+    pub approval_channels: Vec<ApprovalChannel>,
+    /// Channel sessions move to outside working hours
+    /// (`[workspace.working_hours]`).
+    #[serde(default)]
+    pub working_hours: Option<WorkingHours>,
+    /// Commands run in the workspace before a spawned agent starts
+    /// (`[workspace.bootstrap]`).
+    #[serde(default)]
+    pub bootstrap: Option<WorkspaceBootstrap>,
+}
+
+impl WorkspaceMapping {
+    /// The channel this workspace's sessions post to at local time `now`:
+    /// `channel_id` within working hours, or when none are configured, and
+    /// the after-hours channel otherwise.
+    #[must_use]
+    pub fn channel_at(&self, now: NaiveDateTime) -> &str {
+        match self.working_hours {
+            Some(ref hours) if !hours.contains(now) => &hours.after_hours_channel_id,
+            _ => &self.channel_id,
+        }
+    }
+
+    /// Whether `channel_id` is one of the channels [`channel_at`](Self::channel_at)
+    /// picks between.
+    #[must_use]
+    pub fn routes_to(&self, channel_id: &str) -> bool {
+        self.channel_id == channel_id
+            || self
+                .working_hours
+                .as_ref()
+                .is_some_and(|hours| hours.after_hours_channel_id == channel_id)
+    }
+
+    /// Channels besides `channel_id` that an approval of `risk` is posted
+    /// to, in configuration order.
+    #[must_use]
+    pub fn approval_fanout(&self, risk: RiskLevel) -> Vec<String> {
+        self.approval_channels
+            .iter()
+            .filter(|extra| risk >= extra.min_risk)
+            .map(|extra| extra.channel_id.clone())
+            .collect()
+    }
+}
+
+/// An extra channel a workspace's approval requests are posted to
+/// (`[[workspace.approval_channels]]`).
+///
+/// Each copy carries live buttons; the first authorized decision in any
+/// channel resolves the request and every other copy is updated with the
+/// outcome.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ApprovalChannel {
+    /// Slack channel ID to post copies in.
+    pub channel_id: String,
+    /// Lowest risk level posted to this channel.
+    #[serde(default = "default_approval_channel_risk")]
+    pub min_risk: RiskLevel,
+}
+
+/// Working hours of a workspace's channel (`[workspace.working_hours]`).
+///
+/// Outside `hours` (or at weekends, with `weekdays_only`) sessions of the
+/// workspace post to `after_hours_channel_id` instead of the workspace
+/// channel, for example an on-call channel. The choice is made each time a
+/// session's agent calls a tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct WorkingHours {
+    /// Server-local working hours, e.g. `"09:00-17:00"`. A window ending
+    /// before it starts spans midnight.
+    pub hours: String,
+    /// Count only Monday to Friday as working days.
+    #[serde(default)]
+    pub weekdays_only: bool,
+    /// Slack channel ID sessions post to outside working hours.
+    pub after_hours_channel_id: String,
+}
+
+impl WorkingHours {
+    /// Whether local time `now` falls within working hours.
+    #[must_use]
+    pub fn contains(&self, now: NaiveDateTime) -> bool {
+        in_window(self.hours.as_str(), self.weekdays_only, now)
+    }
+}
+
+/// Commands that prepare a workspace before an ACP session's agent starts
+/// (`[workspace.bootstrap]`), such as `npm ci` or `cargo fetch`.
+///
+/// They run in order in the workspace root through the platform shell. The
+/// first one that fails or runs out of time aborts the session start, so
+/// the agent never starts in a broken environment.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct WorkspaceBootstrap {
+    /// Shell commands, run in order.
+    pub commands: Vec<String>,
+    /// Seconds each command may run before it counts as failed.
+    #[serde(default = "default_bootstrap_timeout_seconds")]
+    pub timeout_seconds: u64,
+}
+
+fn default_bootstrap_timeout_seconds() -> u64 {
+    600
+}
+
+fn default_approval_channel_risk() -> RiskLevel {
+    RiskLevel::Low
+}
+
+/// Validate the `[[workspace.approval_channels]]` entries of one workspace:
+/// each names a channel other than the workspace's own, at most once.
+fn validate_approval_channels(mapping: &WorkspaceMapping) -> Result<()> {
+    let mut seen: HashSet<&str> = HashSet::new();
+    for extra in &mapping.approval_channels {
+        if extra.channel_id.is_empty() {
+            return Err(AppError::Config(format!(
+                "workspace '{}': approval_channels entry needs a channel_id",
+                mapping.workspace_id
+            )));
+        }
+        if extra.channel_id == mapping.channel_id || !seen.insert(extra.channel_id.as_str()) {
+            return Err(AppError::Config(format!(
+                "workspace '{}': approval channel '{}' is listed twice or is the workspace channel",
+                mapping.workspace_id, extra.channel_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validate a workspace's `[workspace.working_hours]`: a parseable window
+/// and an after-hours channel other than the workspace's own.
+fn validate_working_hours(mapping: &WorkspaceMapping) -> Result<()> {
+    let Some(ref hours) = mapping.working_hours else {
+        return Ok(());
+    };
+    if parse_hours(&hours.hours).is_none() {
+        return Err(AppError::Config(format!(
+            "workspace '{}': working_hours.hours '{}' must look like 09:00-17:00",
+            mapping.workspace_id, hours.hours
+        )));
+    }
+    if hours.after_hours_channel_id.is_empty() || hours.after_hours_channel_id == mapping.channel_id
+    {
+        return Err(AppError::Config(format!(
+            "workspace '{}': working_hours.after_hours_channel_id must name a channel other \
+             than the workspace channel",
+            mapping.workspace_id
+        )));
+    }
+    Ok(())
+}
+
+/// Validate a workspace's `[workspace.bootstrap]`: at least one command,
+/// none blank, and a timeout of at least a second.
+fn validate_bootstrap(mapping: &WorkspaceMapping) -> Result<()> {
+    let Some(ref bootstrap) = mapping.bootstrap else {
+        return Ok(());
+    };
+    if bootstrap.commands.is_empty()
+        || bootstrap
+            .commands
+            .iter()
+            .any(|command| command.trim().is_empty())
+    {
+        return Err(AppError::Config(format!(
+            "workspace '{}': bootstrap.commands must list non-empty commands",
+            mapping.workspace_id
+        )));
+    }
+    if bootstrap.timeout_seconds == 0 {
+        return Err(AppError::Config(format!(
+            "workspace '{}': bootstrap.timeout_seconds must be at least 1",
+            mapping.workspace_id
+        )));
+    }
+    Ok(())
+}
+
+/// A downstream MCP server proxied through a workspace (`[[workspace.proxy]]`).
+///
+/// agent-intercom launches `command` as a stdio MCP server in the workspace
+/// directory and re-exports the downstream tools matching `tools` as
+/// `<name>__<tool>`. Each proxied call is auto-approved, held for operator
+/// approval, or denied by the workspace policy's `proxy` rules, which match
+/// the qualified name `<name>.<tool>`.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ProxyServerConfig {
+    /// Namespace for the re-exported tools (ASCII letters, digits, `-`).
+    pub name: String,
+    /// Executable that starts the downstream server.
+    pub command: String,
+    /// Arguments passed to `command`.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Extra environment variables for the downstream process.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Glob patterns selecting the downstream tools to re-export.
+    ///
+    /// Tools matching no pattern stay hidden; an empty list exports nothing.
+    #[serde(default)]
+    pub tools: Vec<String>,
+}
+
+impl ProxyServerConfig {
+    /// Whether the downstream tool `tool` is selected for re-export.
+    #[must_use]
+    pub fn exports(&self, tool: &str) -> bool {
+        self.tools
+            .iter()
+            .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+            .any(|pattern| pattern.matches(tool))
+    }
+}
+
+/// Validate the `[[workspace.proxy]]` entries of one workspace.
+///
+/// Proxy names become tool-name prefixes, so they must be unique within the
+/// workspace and limited to characters every MCP client accepts in a tool
+/// name. Underscores are rejected so the `__` separator stays unambiguous.
+fn validate_proxy_servers(mapping: &WorkspaceMapping) -> Result<()> {
+    let mut names: HashSet<&str> = HashSet::new();
+    for server in &mapping.proxy {
+        if server.name.is_empty()
+            || !server
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            return Err(AppError::Config(format!(
+                "invalid proxy name '{}' in workspace '{}': use ASCII letters, digits and '-'",
+                server.name, mapping.workspace_id
+            )));
+        }
+        if server.command.trim().is_empty() {
+            return Err(AppError::Config(format!(
+                "proxy '{}' in workspace '{}' has an empty command",
+                server.name, mapping.workspace_id
+            )));
+        }
+        if let Some(bad) = server
+            .tools
+            .iter()
+            .find(|pattern| glob::Pattern::new(pattern).is_err())
+        {
+            return Err(AppError::Config(format!(
+                "invalid tools pattern '{bad}' for proxy '{}' in workspace '{}'",
+                server.name, mapping.workspace_id
+            )));
+        }
+        if !names.insert(server.name.as_str()) {
+            return Err(AppError::Config(format!(
+                "duplicate proxy name '{}' in workspace '{}'",
+                server.name, mapping.workspace_id
+            )));
+        }
+    }
+    Ok(())
 }
 
 /// Validate that every workspace mapping has a unique `channel_id`.
@@ -101,8 +397,8 @@ pub(crate) fn validate_unique_channel_ids(mappings: &[WorkspaceMapping]) -> Resu
 ///
 /// Tokens and team ID are loaded at runtime via OS keychain or environment
 /// variables, not from the TOML config file (FR-036).
-#[derive(Clone, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
+#[derive(Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub struct SlackConfig {
     /// Default channel where notifications are posted.
     ///
@@ -128,6 +424,27 @@ pub struct SlackConfig {
     /// Files whose extension is NOT in this map are uploaded as plain `.txt`.
     #[serde(default)]
     pub markdown_upload_extensions: HashMap<String, String>,
+    /// Bot IDs (`B...`) of Workflow Builder workflows allowed to run
+    /// `steer` / `task` steps by posting a message. Empty disables
+    /// workflow steps.
+    #[serde(default)]
+    pub workflow_bot_ids: Vec<String>,
+    /// How messages are rendered. `plain_text` sends every message as text
+    /// without Block Kit, for clients where blocks fail to display.
+    #[serde(default)]
+    pub render_mode: SlackRenderMode,
+}
+
+/// How Slack messages are rendered.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SlackRenderMode {
+    /// Block Kit layout with a generated `text` fallback (default).
+    #[default]
+    Blocks,
+    /// Text only: blocks are rendered to text and not sent. Buttons are
+    /// listed by label and cannot be clicked.
+    PlainText,
 }
 
 impl std::fmt::Debug for SlackConfig {
@@ -141,6 +458,8 @@ impl std::fmt::Debug for SlackConfig {
                 "markdown_upload_extensions",
                 &self.markdown_upload_extensions,
             )
+            .field("workflow_bot_ids", &self.workflow_bot_ids)
+            .field("render_mode", &self.render_mode)
             .finish()
     }
 }
@@ -159,8 +478,8 @@ impl SlackConfig {
 }
 
 /// Configurable timeout values (seconds) for blocking tool interactions.
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub struct TimeoutConfig {
     /// Approval request timeout.
     #[serde(default = "default_approval_seconds")]
@@ -171,136 +490,2019 @@ pub struct TimeoutConfig {
     /// Wait-for-instruction timeout; 0 means no timeout.
     #[serde(default)]
     pub wait_seconds: u64,
+    /// How long a disconnected direct-connection session is kept for a
+    /// reconnecting client (an IDE window reload) before it is terminated;
+    /// 0 terminates it on disconnect.
+    #[serde(default = "default_reconnect_grace_seconds")]
+    pub reconnect_grace_seconds: u64,
+    /// How often a blocked `check_clearance`, `transmit` or `standby` sends
+    /// the client a progress notification, when it asked for progress; 0
+    /// disables them.
+    #[serde(default = "default_progress_interval_seconds")]
+    pub progress_interval_seconds: u64,
+    /// Time an approval is given again when the operator opens its
+    /// rejection modal or pages its diff with less than half of it left;
+    /// 0 disables the extension.
+    #[serde(default = "default_decision_grace_seconds")]
+    pub decision_grace_seconds: u64,
+}
+
+impl TimeoutConfig {
+    /// The interval between progress keepalives, or `None` when they are
+    /// disabled.
+    #[must_use]
+    pub fn progress_interval(&self) -> Option<std::time::Duration> {
+        (self.progress_interval_seconds > 0)
+            .then(|| std::time::Duration::from_secs(self.progress_interval_seconds))
+    }
+
+    /// The grace an approval gets on operator activity, or `None` when
+    /// activity does not extend it.
+    #[must_use]
+    pub fn decision_grace(&self) -> Option<std::time::Duration> {
+        (self.decision_grace_seconds > 0)
+            .then(|| std::time::Duration::from_secs(self.decision_grace_seconds))
+    }
 }
 
 fn default_approval_seconds() -> u64 {
     3600
 }
 
-fn default_prompt_seconds() -> u64 {
-    1800
-}
+fn default_reconnect_grace_seconds() -> u64 {
+    30
+}
+
+fn default_progress_interval_seconds() -> u64 {
+    15
+}
+
+fn default_decision_grace_seconds() -> u64 {
+    300
+}
+
+fn default_prompt_seconds() -> u64 {
+    1800
+}
+
+/// Most quick-reply buttons allowed per prompt type.
+pub const MAX_QUICK_REPLIES: usize = 5;
+
+/// Longest quick-reply label Slack renders on a button.
+pub const MAX_QUICK_REPLY_LEN: usize = 75;
+
+/// Forwarded prompt quick replies and automatic answers.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct PromptsConfig {
+    /// Quick-reply buttons shown on `transmit` prompts, keyed by prompt
+    /// type. Clicking one refines the prompt with the button's label as
+    /// the instruction.
+    #[serde(default)]
+    pub quick_replies: HashMap<PromptType, Vec<String>>,
+    /// Rules that answer prompts without the operator (`[[prompts.auto]]`).
+    /// The first matching rule wins.
+    #[serde(default)]
+    pub auto: Vec<PromptAutoRule>,
+}
+
+/// Answer prompts of one type automatically, optionally only inside a
+/// time window or once the session has sent several in a row.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct PromptAutoRule {
+    /// Prompt type the rule answers.
+    pub prompt_type: PromptType,
+    /// Decision returned to the agent.
+    pub decision: PromptDecision,
+    /// Instruction returned with a `refine` decision.
+    #[serde(default)]
+    pub instruction: Option<String>,
+    /// Apply only once the session has sent at least this many prompts of
+    /// `prompt_type` in a row, counting the current one.
+    #[serde(default = "default_after_consecutive")]
+    pub after_consecutive: u32,
+    /// Apply only between these server-local times, e.g. `"09:00-17:00"`.
+    /// A window ending before it starts spans midnight.
+    #[serde(default)]
+    pub hours: Option<String>,
+    /// Apply only Monday to Friday.
+    #[serde(default)]
+    pub weekdays_only: bool,
+}
+
+fn default_after_consecutive() -> u32 {
+    1
+}
+
+impl PromptAutoRule {
+    /// Whether the rule answers a prompt of `prompt_type` that is the
+    /// `consecutive`th of its type in a row, sent at local time `now`.
+    #[must_use]
+    pub fn applies(&self, prompt_type: PromptType, consecutive: u32, now: NaiveDateTime) -> bool {
+        if self.prompt_type != prompt_type || consecutive < self.after_consecutive {
+            return false;
+        }
+        match self.hours.as_deref() {
+            Some(hours) => in_window(hours, self.weekdays_only, now),
+            None => !self.weekdays_only || now.weekday().number_from_monday() <= 5,
+        }
+    }
+}
+
+/// Whether local time `now` falls in the `HH:MM-HH:MM` window `hours`, on
+/// a weekday when `weekdays_only`. An unparseable window always matches.
+fn in_window(hours: &str, weekdays_only: bool, now: NaiveDateTime) -> bool {
+    if weekdays_only && now.weekday().number_from_monday() > 5 {
+        return false;
+    }
+    match parse_hours(hours) {
+        Some((start, end)) if start <= end => (start..end).contains(&now.time()),
+        Some((start, end)) => now.time() >= start || now.time() < end,
+        None => true,
+    }
+}
+
+/// Parse an `HH:MM-HH:MM` time window.
+fn parse_hours(hours: &str) -> Option<(NaiveTime, NaiveTime)> {
+    let (start, end) = hours.split_once('-')?;
+    let start = NaiveTime::parse_from_str(start.trim(), "%H:%M").ok()?;
+    let end = NaiveTime::parse_from_str(end.trim(), "%H:%M").ok()?;
+    Some((start, end))
+}
+
+impl PromptsConfig {
+    /// Quick replies configured for `prompt_type`, in display order.
+    #[must_use]
+    pub fn quick_replies_for(&self, prompt_type: PromptType) -> &[String] {
+        self.quick_replies
+            .get(&prompt_type)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// The first auto rule answering a prompt of `prompt_type` that is the
+    /// `consecutive`th of its type in a row, sent at local time `now`.
+    #[must_use]
+    pub fn auto_rule(
+        &self,
+        prompt_type: PromptType,
+        consecutive: u32,
+        now: NaiveDateTime,
+    ) -> Option<&PromptAutoRule> {
+        self.auto
+            .iter()
+            .find(|rule| rule.applies(prompt_type, consecutive, now))
+    }
+
+    fn validate(&self) -> Result<()> {
+        for (prompt_type, replies) in &self.quick_replies {
+            if replies.len() > MAX_QUICK_REPLIES {
+                return Err(AppError::Config(format!(
+                    "[prompts.quick_replies] {} has more than {MAX_QUICK_REPLIES} entries",
+                    prompt_type.as_str()
+                )));
+            }
+            if replies
+                .iter()
+                .any(|reply| reply.trim().is_empty() || reply.chars().count() > MAX_QUICK_REPLY_LEN)
+            {
+                return Err(AppError::Config(format!(
+                    "[prompts.quick_replies] {} entries must be non-empty and at most \
+                     {MAX_QUICK_REPLY_LEN} characters",
+                    prompt_type.as_str()
+                )));
+            }
+        }
+        for rule in &self.auto {
+            let prompt_type = rule.prompt_type.as_str();
+            if rule.after_consecutive == 0 {
+                return Err(AppError::Config(format!(
+                    "[[prompts.auto]] {prompt_type}: after_consecutive must be greater than zero"
+                )));
+            }
+            if rule
+                .hours
+                .as_deref()
+                .is_some_and(|h| parse_hours(h).is_none())
+            {
+                return Err(AppError::Config(format!(
+                    "[[prompts.auto]] {prompt_type}: hours must look like \"09:00-17:00\""
+                )));
+            }
+            let has_instruction = rule
+                .instruction
+                .as_deref()
+                .is_some_and(|text| !text.trim().is_empty());
+            if rule.decision == PromptDecision::Refine && !has_instruction {
+                return Err(AppError::Config(format!(
+                    "[[prompts.auto]] {prompt_type}: a refine decision needs an instruction"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Where a `broadcast` message is posted.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BroadcastDestination {
+    /// The session's thread (the channel itself when there is none).
+    Thread,
+    /// A top-level message in the channel.
+    Channel,
+    /// A direct message to the session owner, or to every authorized user
+    /// when the session belongs to the local agent.
+    Dm,
+    /// A top-level channel message that mentions `escalation_mention`.
+    Escalate,
+}
+
+impl BroadcastDestination {
+    /// Returns the `snake_case` name used in `config.toml`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Thread => "thread",
+            Self::Channel => "channel",
+            Self::Dm => "dm",
+            Self::Escalate => "escalate",
+        }
+    }
+}
+
+/// Routing of `broadcast` messages by level (`[broadcast]`).
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct BroadcastConfig {
+    /// Destinations for `info` messages.
+    #[serde(default = "default_broadcast_route")]
+    pub info: Vec<BroadcastDestination>,
+    /// Destinations for `success` messages.
+    #[serde(default = "default_broadcast_route")]
+    pub success: Vec<BroadcastDestination>,
+    /// Destinations for `warning` messages.
+    #[serde(default = "default_broadcast_route")]
+    pub warning: Vec<BroadcastDestination>,
+    /// Destinations for `error` messages.
+    #[serde(default = "default_broadcast_route")]
+    pub error: Vec<BroadcastDestination>,
+    /// Mention prepended to escalated messages, e.g. `<!here>`,
+    /// `<!subteam^S0123>` or `<@U0123>`.
+    #[serde(default = "default_escalation_mention")]
+    pub escalation_mention: String,
+}
+
+fn default_broadcast_route() -> Vec<BroadcastDestination> {
+    vec![BroadcastDestination::Thread]
+}
+
+fn default_escalation_mention() -> String {
+    "<!here>".into()
+}
+
+impl Default for BroadcastConfig {
+    fn default() -> Self {
+        Self {
+            info: default_broadcast_route(),
+            success: default_broadcast_route(),
+            warning: default_broadcast_route(),
+            error: default_broadcast_route(),
+            escalation_mention: default_escalation_mention(),
+        }
+    }
+}
+
+impl BroadcastConfig {
+    /// Destinations for a message of `level`. Unknown levels route like
+    /// `info`.
+    #[must_use]
+    pub fn route(&self, level: &str) -> &[BroadcastDestination] {
+        match level {
+            "success" => &self.success,
+            "warning" => &self.warning,
+            "error" => &self.error,
+            _ => &self.info,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        for (level, route) in [
+            ("info", &self.info),
+            ("success", &self.success),
+            ("warning", &self.warning),
+            ("error", &self.error),
+        ] {
+            if route.is_empty() {
+                return Err(AppError::Config(format!(
+                    "[broadcast] {level} needs at least one destination"
+                )));
+            }
+            if route.contains(&BroadcastDestination::Escalate)
+                && self.escalation_mention.trim().is_empty()
+            {
+                return Err(AppError::Config(format!(
+                    "[broadcast] {level} escalates, so escalation_mention must not be empty"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Stall detection configuration.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct StallConfig {
+    /// Whether stall detection is enabled.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Idle threshold before triggering alert.
+    #[serde(default = "default_inactivity_threshold")]
+    pub inactivity_threshold_seconds: u64,
+    /// Delay before auto-nudging when unattended.
+    #[serde(default = "default_escalation_threshold")]
+    pub escalation_threshold_seconds: u64,
+    /// Maximum consecutive auto-nudges before escalation.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Default nudge message delivered to the agent.
+    #[serde(default = "default_nudge_message")]
+    pub default_nudge_message: String,
+    /// Learn each session's typical interval between tool calls and alert
+    /// on deviations from it instead of the fixed inactivity threshold.
+    #[serde(default)]
+    pub adaptive: bool,
+    /// Adaptive mode: alert once idle for this many typical intervals.
+    #[serde(default = "default_adaptive_multiplier")]
+    pub adaptive_multiplier: u32,
+    /// Adaptive mode: lower bound on the learned threshold.
+    #[serde(default = "default_adaptive_min_seconds")]
+    pub adaptive_min_seconds: u64,
+    /// Adaptive mode: upper bound on the learned threshold.
+    #[serde(default = "default_adaptive_max_seconds")]
+    pub adaptive_max_seconds: u64,
+    /// Require agents to `ping` with a status within every
+    /// `heartbeat_deadline_seconds`, refusing other tool calls once overdue.
+    #[serde(default)]
+    pub heartbeat_required: bool,
+    /// Longest an agent may go between heartbeats when they are required.
+    #[serde(default = "default_heartbeat_deadline")]
+    pub heartbeat_deadline_seconds: u64,
+    /// Idle time after a progress snapshot reports every item done before
+    /// the operator is asked whether the agent is finished; `0` disables
+    /// completion detection.
+    #[serde(default = "default_completion_grace")]
+    pub completion_grace_seconds: u64,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_inactivity_threshold() -> u64 {
+    300
+}
+
+fn default_escalation_threshold() -> u64 {
+    120
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_adaptive_multiplier() -> u32 {
+    4
+}
+
+fn default_adaptive_min_seconds() -> u64 {
+    60
+}
+
+fn default_adaptive_max_seconds() -> u64 {
+    1800
+}
+
+fn default_heartbeat_deadline() -> u64 {
+    600
+}
+
+fn default_completion_grace() -> u64 {
+    120
+}
+
+fn default_nudge_message() -> String {
+    "Continue working on the current task. Pick up where you left off.".into()
+}
+
+fn default_retention_days() -> u32 {
+    30
+}
+
+fn default_max_concurrent_sessions() -> u32 {
+    3
+}
+
+fn default_acp_max_sessions() -> usize {
+    5
+}
+
+fn default_acp_startup_timeout_seconds() -> u64 {
+    30
+}
+
+fn default_acp_max_msg_rate() -> u32 {
+    10
+}
+
+fn default_acp_http_port() -> u16 {
+    3001
+}
+
+fn default_acp_writer_queue_capacity() -> usize {
+    256
+}
+
+fn default_acp_write_timeout_seconds() -> u64 {
+    10
+}
+
+/// ACP-mode specific configuration.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct AcpConfig {
+    /// Maximum number of concurrent ACP sessions.
+    ///
+    /// Requests beyond this limit are rejected with a descriptive error.
+    /// Defaults to `5`.
+    #[serde(default = "default_acp_max_sessions")]
+    pub max_sessions: usize,
+    /// Seconds to wait for the agent to emit its ready signal on stdout.
+    ///
+    /// If no line is received within this window the spawner kills the
+    /// process and returns `AppError::Acp("startup timeout")`. Defaults
+    /// to `30`.
+    #[serde(default = "default_acp_startup_timeout_seconds")]
+    pub startup_timeout_seconds: u64,
+    /// Maximum inbound ACP messages per second (token-bucket rate limit, FR-044).
+    ///
+    /// Exceeding this rate triggers a warning log and the message is dropped.
+    /// Sustained flooding (> 50 consecutive drops) terminates the session.
+    /// Set to `0` to disable rate limiting. Defaults to `10`.
+    #[serde(default = "default_acp_max_msg_rate")]
+    pub max_msg_rate: u32,
+    /// HTTP port for the MCP tool endpoint when running in ACP mode.
+    ///
+    /// Defaults to `3001` so that an ACP server instance can run concurrently
+    /// with an MCP server instance (which defaults to port `3000`).  The CLI
+    /// `--port` flag takes precedence over this value.
+    #[serde(default = "default_acp_http_port")]
+    pub http_port: u16,
+    /// Capacity of each session's bounded outbound writer queue.
+    ///
+    /// Operator decisions and steering are queued here before the writer task
+    /// flushes them to the agent's stdin. Defaults to `256`.
+    #[serde(default = "default_acp_writer_queue_capacity")]
+    pub writer_queue_capacity: usize,
+    /// Seconds a single stdin write (or a blocked enqueue under the `wait`
+    /// overflow policy) may take before the stream is treated as stuck.
+    ///
+    /// A stuck write marks the session `Interrupted`, so Slack handlers
+    /// awaiting `resolve_clearance` never block indefinitely. Defaults to `10`.
+    #[serde(default = "default_acp_write_timeout_seconds")]
+    pub write_timeout_seconds: u64,
+    /// Behaviour when a session's outbound queue is full.
+    ///
+    /// `wait` (default) waits up to `write_timeout_seconds` for space;
+    /// `reject` fails the enqueue immediately.
+    #[serde(default)]
+    pub writer_overflow: AcpWriterOverflow,
+}
+
+/// Overflow policy applied when an ACP session's outbound queue is full.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AcpWriterOverflow {
+    /// Wait up to the write timeout for queue space, then fail (default).
+    #[default]
+    Wait,
+    /// Fail the enqueue immediately without waiting.
+    Reject,
+}
+
+impl Default for AcpConfig {
+    fn default() -> Self {
+        Self {
+            max_sessions: default_acp_max_sessions(),
+            startup_timeout_seconds: default_acp_startup_timeout_seconds(),
+            max_msg_rate: default_acp_max_msg_rate(),
+            http_port: default_acp_http_port(),
+            writer_queue_capacity: default_acp_writer_queue_capacity(),
+            write_timeout_seconds: default_acp_write_timeout_seconds(),
+            writer_overflow: AcpWriterOverflow::default(),
+        }
+    }
+}
+
+fn default_http_port() -> u16 {
+    3000
+}
+
+fn default_ipc_name() -> String {
+    "agent-intercom".into()
+}
+
+/// CODEOWNERS-aware approver routing.
+///
+/// Maps the GitHub handles and teams that appear in a workspace's
+/// CODEOWNERS file to Slack user IDs. Mapped owners are @-mentioned on
+/// approval requests for files they own; with `require_owner_approval`
+/// only they can accept or reject those requests.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct CodeOwnersConfig {
+    /// GitHub handle or team (e.g. `@alice`, `@org/platform`) to Slack user
+    /// ID. The leading `@` is optional and handles match case-insensitively.
+    #[serde(default)]
+    pub slack_users: HashMap<String, String>,
+    /// Restrict approval decisions on owned files to the mapped owners.
+    ///
+    /// Files without a mapped owner fall back to the session owner.
+    #[serde(default)]
+    pub require_owner_approval: bool,
+}
+
+impl CodeOwnersConfig {
+    /// Slack user IDs mapped to `owners`, deduplicated, in CODEOWNERS order.
+    #[must_use]
+    pub fn slack_ids_for(&self, owners: &[String]) -> Vec<String> {
+        let mut ids: Vec<String> = Vec::new();
+        for owner in owners {
+            let handle = owner.trim_start_matches('@');
+            let mapped = self
+                .slack_users
+                .iter()
+                .find(|(key, _)| key.trim_start_matches('@').eq_ignore_ascii_case(handle));
+            if let Some((_, id)) = mapped {
+                if !ids.contains(id) {
+                    ids.push(id.clone());
+                }
+            }
+        }
+        ids
+    }
+}
+
+/// Issue tracker that receives session completion comments.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueProvider {
+    /// GitHub or GitHub Enterprise issues and pull requests.
+    Github,
+    /// Jira (Cloud or Data Center).
+    Jira,
+}
+
+/// Issue tracker integration for sessions started with `--issue`.
+///
+/// Without a `provider` the issue reference is still shown in Slack, but
+/// no completion comment is posted. The API token is loaded at runtime
+/// from the keychain or `ISSUE_TRACKER_TOKEN`, never from `config.toml`.
+#[derive(Clone, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct IssuesConfig {
+    /// Tracker to post completion comments to.
+    #[serde(default)]
+    pub provider: Option<IssueProvider>,
+    /// API base URL. Defaults to `https://api.github.com` for GitHub; must
+    /// be set for Jira (e.g. `https://acme.atlassian.net`).
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// GitHub `owner/repo` used when the issue is given as a bare number.
+    #[serde(default)]
+    pub default_repo: Option<String>,
+    /// API token (populated at runtime).
+    #[serde(skip)]
+    pub token: String,
+    /// Account email for Jira basic auth (populated at runtime from
+    /// `ISSUE_TRACKER_USER`). Bearer auth is used when empty.
+    #[serde(skip)]
+    pub user: String,
+}
+
+impl std::fmt::Debug for IssuesConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IssuesConfig")
+            .field("provider", &self.provider)
+            .field("base_url", &self.base_url)
+            .field("default_repo", &self.default_repo)
+            .field("token", &"[REDACTED]")
+            .field("user", &self.user)
+            .finish()
+    }
+}
+
+/// Transport security for the SMTP connection.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpSecurity {
+    /// Plain connection upgraded with `STARTTLS` (usually port 587).
+    #[default]
+    Starttls,
+    /// Implicit TLS from the first byte (usually port 465).
+    Tls,
+    /// No encryption. Only for local relays and testing.
+    None,
+}
+
+/// Email delivery of session summaries.
+///
+/// When `host` is set, every session that ends is summarised (prompt,
+/// duration, approvals, files changed, final status) and mailed to its
+/// owner. SMTP credentials are loaded at runtime from the keychain or
+/// `SMTP_USERNAME` / `SMTP_PASSWORD`, never from `config.toml`.
+#[derive(Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct SmtpConfig {
+    /// SMTP server host name. Summaries are disabled when unset.
+    #[serde(default)]
+    pub host: Option<String>,
+    /// SMTP server port.
+    #[serde(default = "default_smtp_port")]
+    pub port: u16,
+    /// Connection security.
+    #[serde(default)]
+    pub security: SmtpSecurity,
+    /// Sender address, e.g. `agent-intercom <intercom@acme.test>`.
+    #[serde(default)]
+    pub from: Option<String>,
+    /// Slack user ID to email address for session owners.
+    #[serde(default)]
+    pub recipients: HashMap<String, String>,
+    /// Addresses copied on every summary (e.g. a team archive mailbox).
+    #[serde(default)]
+    pub cc: Vec<String>,
+    /// SMTP username (populated at runtime). No authentication when empty.
+    #[serde(skip)]
+    pub username: String,
+    /// SMTP password (populated at runtime).
+    #[serde(skip)]
+    pub password: String,
+}
+
+impl SmtpConfig {
+    /// Whether summary emails are enabled.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.host.is_some()
+    }
+
+    /// Email address of the session owner `owner_id`, if mapped.
+    #[must_use]
+    pub fn recipient_for(&self, owner_id: &str) -> Option<&str> {
+        self.recipients.get(owner_id).map(String::as_str)
+    }
+}
+
+impl Default for SmtpConfig {
+    fn default() -> Self {
+        Self {
+            host: None,
+            port: default_smtp_port(),
+            security: SmtpSecurity::default(),
+            from: None,
+            recipients: HashMap::new(),
+            cc: Vec::new(),
+            username: String::new(),
+            password: String::new(),
+        }
+    }
+}
+
+impl std::fmt::Debug for SmtpConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SmtpConfig")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("security", &self.security)
+            .field("from", &self.from)
+            .field("recipients", &self.recipients)
+            .field("cc", &self.cc)
+            .field("username", &self.username)
+            .field("password", &"[REDACTED]")
+            .finish()
+    }
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+/// Inbound approval webhooks (`POST /api/v1/approvals/{id}/decision`).
+///
+/// Lets chat bots and CI systems accept or reject pending approvals over
+/// HTTP. Every request must be signed with the shared secret, which is
+/// loaded at runtime from the keychain or `WEBHOOK_SECRET`, never from
+/// `config.toml`.
+/// The read-only wait endpoint is signed with a separate secret
+/// (`WEBHOOK_WAIT_SECRET`), so an agent wrapper that may wait on its own
+/// requests cannot also decide them; without it the endpoint is not
+/// mounted.
+#[derive(Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct WebhooksConfig {
+    /// Mount the webhook endpoint on the HTTP transport.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Largest accepted difference between a request's timestamp and the
+    /// server clock, in seconds.
+    #[serde(default = "default_webhook_max_skew_seconds")]
+    pub max_skew_seconds: u64,
+    /// HMAC-SHA256 signing secret (populated at runtime).
+    #[serde(skip)]
+    pub secret: String,
+    /// HMAC-SHA256 secret for the read-only wait endpoint (populated at
+    /// runtime; empty leaves the endpoint unmounted).
+    #[serde(skip)]
+    pub wait_secret: String,
+}
+
+impl Default for WebhooksConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_skew_seconds: default_webhook_max_skew_seconds(),
+            secret: String::new(),
+            wait_secret: String::new(),
+        }
+    }
+}
+
+impl std::fmt::Debug for WebhooksConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhooksConfig")
+            .field("enabled", &self.enabled)
+            .field("max_skew_seconds", &self.max_skew_seconds)
+            .field("secret", &"[REDACTED]")
+            .field("wait_secret", &"[REDACTED]")
+            .finish()
+    }
+}
+
+fn default_webhook_max_skew_seconds() -> u64 {
+    300
+}
+
+/// Signed approval links (`[approval_links]`).
+///
+/// Approval notifications carry a short-lived URL that opens a minimal
+/// approval page on the HTTP transport, so an operator can decide from a
+/// phone or mail client without Slack. Links are HMAC-signed with a secret
+/// loaded from the keychain or `APPROVAL_LINK_SECRET`; without one, a
+/// random secret is generated per run and links die on restart. The same
+/// page backs the dashboard that browsers paired with `/intercom pair` use,
+/// and the secret also signs read-only `/intercom share` links.
+#[derive(Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ApprovalLinksConfig {
+    /// Add links to approval notifications and mount the approval page.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Externally reachable URL of the HTTP transport, e.g.
+    /// `https://intercom.acme.test`. Required when enabled.
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// How long a link stays valid, in seconds.
+    #[serde(default = "default_approval_link_ttl_seconds")]
+    pub ttl_seconds: u64,
+    /// Also email the link to the session owner (needs `[smtp]`).
+    #[serde(default = "default_true")]
+    pub email: bool,
+    /// How long a `/intercom pair` QR code can be redeemed, in seconds.
+    #[serde(default = "default_pairing_ttl_seconds")]
+    pub pairing_ttl_seconds: u64,
+    /// How long a paired browser stays signed in, in days.
+    #[serde(default = "default_device_ttl_days")]
+    pub device_ttl_days: u32,
+    /// Default lifetime of a read-only `/intercom share` link, in seconds.
+    #[serde(default = "default_share_ttl_seconds")]
+    pub share_ttl_seconds: u64,
+    /// HMAC-SHA256 signing secret (populated at runtime).
+    #[serde(skip)]
+    pub secret: String,
+}
+
+impl ApprovalLinksConfig {
+    fn validate(&self) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let valid = self
+            .base_url
+            .as_deref()
+            .is_some_and(|url| url.starts_with("http://") || url.starts_with("https://"));
+        if !valid {
+            return Err(AppError::Config(
+                "[approval_links] base_url must be an http:// or https:// URL when enabled".into(),
+            ));
+        }
+        if self.ttl_seconds == 0
+            || self.pairing_ttl_seconds == 0
+            || self.device_ttl_days == 0
+            || self.share_ttl_seconds == 0
+        {
+            return Err(AppError::Config(
+                "[approval_links] ttl_seconds, pairing_ttl_seconds, device_ttl_days and \
+                 share_ttl_seconds must be greater than zero"
+                    .into(),
+            ));
+        }
+        // Both are added to the clock when a browser pairs.
+        if self.pairing_ttl_seconds > 86_400 || self.device_ttl_days > 3650 {
+            return Err(AppError::Config(
+                "[approval_links] pairing_ttl_seconds must be at most 86400 and \
+                 device_ttl_days at most 3650"
+                    .into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ApprovalLinksConfig {
+    /// Generate a random per-run signing secret when links are enabled and
+    /// none was loaded. Links signed with it stop verifying on restart.
+    pub fn ensure_secret(&mut self) {
+        if self.enabled && self.secret.is_empty() {
+            self.secret = format!(
+                "{}{}",
+                uuid::Uuid::new_v4().simple(),
+                uuid::Uuid::new_v4().simple()
+            );
+        }
+    }
+}
+
+impl Default for ApprovalLinksConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base_url: None,
+            ttl_seconds: default_approval_link_ttl_seconds(),
+            email: true,
+            pairing_ttl_seconds: default_pairing_ttl_seconds(),
+            device_ttl_days: default_device_ttl_days(),
+            share_ttl_seconds: default_share_ttl_seconds(),
+            secret: String::new(),
+        }
+    }
+}
+
+impl std::fmt::Debug for ApprovalLinksConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApprovalLinksConfig")
+            .field("enabled", &self.enabled)
+            .field("base_url", &self.base_url)
+            .field("ttl_seconds", &self.ttl_seconds)
+            .field("email", &self.email)
+            .field("pairing_ttl_seconds", &self.pairing_ttl_seconds)
+            .field("device_ttl_days", &self.device_ttl_days)
+            .field("share_ttl_seconds", &self.share_ttl_seconds)
+            .field("secret", &"[REDACTED]")
+            .finish()
+    }
+}
+
+fn default_approval_link_ttl_seconds() -> u64 {
+    900
+}
+
+fn default_pairing_ttl_seconds() -> u64 {
+    300
+}
+
+fn default_device_ttl_days() -> u32 {
+    30
+}
+
+fn default_share_ttl_seconds() -> u64 {
+    86_400
+}
+
+/// gRPC control plane (`[grpc]`, ADR-0019).
+///
+/// Serves the IPC commands and a stream of agent events on
+/// `127.0.0.1:{port}`, for builds with the `grpc` cargo feature. Callers
+/// authenticate with `authorization: Bearer <token>`; the token is loaded
+/// at runtime from the keychain or `GRPC_TOKEN`, never from `config.toml`.
+#[derive(Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct GrpcConfig {
+    /// Start the gRPC listener.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Loopback port to listen on.
+    #[serde(default = "default_grpc_port")]
+    pub port: u16,
+    /// Bearer token callers must present (populated at runtime).
+    #[serde(skip)]
+    pub token: String,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: default_grpc_port(),
+            token: String::new(),
+        }
+    }
+}
+
+impl std::fmt::Debug for GrpcConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GrpcConfig")
+            .field("enabled", &self.enabled)
+            .field("port", &self.port)
+            .field("token", &"[REDACTED]")
+            .finish()
+    }
+}
+
+fn default_grpc_port() -> u16 {
+    50051
+}
+
+/// Shape of the short IDs shown in Slack (`[ids] format`).
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IdFormat {
+    /// A kind prefix and random characters, e.g. `ses-7f3k` (default).
+    #[default]
+    Short,
+    /// The first eight characters of the UUID, as in earlier releases.
+    Uuid,
+}
+
+/// Human-readable short IDs for sessions and approval requests (`[ids]`).
+///
+/// Every record keeps its UUID; the short ID is stored next to it, shown in
+/// Slack, and accepted anywhere a UUID or UUID prefix is.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct IdsConfig {
+    /// How new short IDs are formed.
+    #[serde(default)]
+    pub format: IdFormat,
+    /// Random characters after the prefix in `short` IDs (3–12). A clash
+    /// with an existing ID adds one more.
+    #[serde(default = "default_short_id_length")]
+    pub length: usize,
+}
+
+impl IdsConfig {
+    fn validate(&self) -> Result<()> {
+        if !(3..=12).contains(&self.length) {
+            return Err(AppError::Config(
+                "[ids] length must be between 3 and 12".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for IdsConfig {
+    fn default() -> Self {
+        Self {
+            format: IdFormat::default(),
+            length: default_short_id_length(),
+        }
+    }
+}
+
+fn default_short_id_length() -> usize {
+    4
+}
+
+/// Size limits on what agents send (`[limits]`).
+///
+/// Diffs larger than an MCP message comfortably carries can be uploaded in
+/// chunks to the staging area (`POST /api/v1/staging`) and passed to
+/// `check_clearance` as `diff_ref`; `max_diff_bytes` caps both paths.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// Largest diff `check_clearance` accepts, inline or staged, in bytes.
+    #[serde(default = "default_max_diff_bytes")]
+    pub max_diff_bytes: usize,
+    /// Largest `check_clearance` snippet, in bytes.
+    #[serde(default = "default_max_snippet_bytes")]
+    pub max_snippet_bytes: usize,
+    /// Seconds an unused staged upload is kept before it is discarded.
+    #[serde(default = "default_staging_ttl_seconds")]
+    pub staging_ttl_seconds: u64,
+    /// Largest HTTP request body accepted on `/mcp`, in bytes.
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+}
+
+impl LimitsConfig {
+    fn validate(&self) -> Result<()> {
+        if self.max_diff_bytes == 0 || self.max_snippet_bytes == 0 || self.max_body_bytes == 0 {
+            return Err(AppError::Config(
+                "[limits] max_diff_bytes, max_snippet_bytes and max_body_bytes must be positive"
+                    .into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_diff_bytes: default_max_diff_bytes(),
+            max_snippet_bytes: default_max_snippet_bytes(),
+            staging_ttl_seconds: default_staging_ttl_seconds(),
+            max_body_bytes: default_max_body_bytes(),
+        }
+    }
+}
+
+fn default_max_diff_bytes() -> usize {
+    4 * 1024 * 1024
+}
+
+fn default_max_snippet_bytes() -> usize {
+    32 * 1024
+}
+
+fn default_staging_ttl_seconds() -> u64 {
+    3600
+}
+
+fn default_max_body_bytes() -> usize {
+    8 * 1024 * 1024
+}
+
+/// What `check_clearance` posts for review when the agent sends no
+/// `snippets` (`[snippets]`).
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct SnippetsConfig {
+    /// Review material posted in place of agent snippets.
+    #[serde(default)]
+    pub fallback: SnippetFallback,
+    /// Most snippets generated for one request.
+    #[serde(default = "default_max_snippets")]
+    pub max_snippets: usize,
+}
+
+impl SnippetsConfig {
+    fn validate(&self) -> Result<()> {
+        if self.max_snippets == 0 {
+            return Err(AppError::Config(
+                "[snippets] max_snippets must be positive".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for SnippetsConfig {
+    fn default() -> Self {
+        Self {
+            fallback: SnippetFallback::default(),
+            max_snippets: default_max_snippets(),
+        }
+    }
+}
+
+fn default_max_snippets() -> usize {
+    5
+}
+
+/// Review material for a `check_clearance` request without agent snippets.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SnippetFallback {
+    /// Generate snippets from the diff: each changed region with the
+    /// declaration around it (default).
+    #[default]
+    Generate,
+    /// Upload the whole original file.
+    OriginalFile,
+    /// Post nothing beyond the approval card.
+    None,
+}
+
+/// Rotation, compression and retention of log files (`[logging]`).
+///
+/// Applies to the JSONL audit files in `.intercom/logs/` and, when `file`
+/// is set, to the server's tracing output. A file rotates on reaching
+/// `max_file_bytes` or when the day changes; the retention task deletes
+/// rotated files, and per-session logs, older than `retention_days`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct LoggingConfig {
+    /// Size at which a log file is rotated, in bytes.
+    #[serde(default = "default_max_log_file_bytes")]
+    pub max_file_bytes: u64,
+    /// Gzip rotated files.
+    #[serde(default = "default_compress_logs")]
+    pub compress: bool,
+    /// Days rotated log files are kept. `0` keeps them forever.
+    #[serde(default = "default_log_retention_days")]
+    pub retention_days: u32,
+    /// Also write tracing output to this file (in addition to stderr).
+    #[serde(default)]
+    pub file: Option<PathBuf>,
+}
+
+impl LoggingConfig {
+    fn validate(&self) -> Result<()> {
+        if self.max_file_bytes == 0 {
+            return Err(AppError::Config(
+                "[logging] max_file_bytes must be positive".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: default_max_log_file_bytes(),
+            compress: default_compress_logs(),
+            retention_days: default_log_retention_days(),
+            file: None,
+        }
+    }
+}
+
+fn default_max_log_file_bytes() -> u64 {
+    32 * 1024 * 1024
+}
+
+fn default_compress_logs() -> bool {
+    true
+}
+
+fn default_log_retention_days() -> u32 {
+    90
+}
+
+/// Slack Web API call budget (`[slack_rate_limit]`).
+///
+/// Every Slack API call the server makes is counted against one shared
+/// budget, and a `429` response's `Retry-After` pauses all callers at once.
+/// Once calls in the last minute reach `near_limit_percent` of
+/// `calls_per_minute`, or while a `Retry-After` is in force, low-priority
+/// updates (heartbeat status lines, in-place status edits) are held for up
+/// to `batch_window_seconds`, coalesced and posted as one message per
+/// thread.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct SlackRateLimitConfig {
+    /// Slack API calls per minute the server budgets for.
+    #[serde(default = "default_slack_calls_per_minute")]
+    pub calls_per_minute: u32,
+    /// Share of the budget, in percent, at which low-priority updates
+    /// start being batched.
+    #[serde(default = "default_slack_near_limit_percent")]
+    pub near_limit_percent: u8,
+    /// Longest a low-priority update is held while the budget is tight.
+    #[serde(default = "default_slack_batch_window_seconds")]
+    pub batch_window_seconds: u64,
+    /// How long channel history read through the channel resource is
+    /// served from cache before new messages are fetched. `0` disables
+    /// the cache.
+    #[serde(default = "default_slack_history_cache_seconds")]
+    pub history_cache_seconds: u64,
+}
+
+impl SlackRateLimitConfig {
+    fn validate(&self) -> Result<()> {
+        if self.calls_per_minute == 0 {
+            return Err(AppError::Config(
+                "[slack_rate_limit] calls_per_minute must be positive".into(),
+            ));
+        }
+        if !(1..=100).contains(&self.near_limit_percent) {
+            return Err(AppError::Config(
+                "[slack_rate_limit] near_limit_percent must be between 1 and 100".into(),
+            ));
+        }
+        if self.batch_window_seconds == 0 {
+            return Err(AppError::Config(
+                "[slack_rate_limit] batch_window_seconds must be positive".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for SlackRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            calls_per_minute: default_slack_calls_per_minute(),
+            near_limit_percent: default_slack_near_limit_percent(),
+            batch_window_seconds: default_slack_batch_window_seconds(),
+            history_cache_seconds: default_slack_history_cache_seconds(),
+        }
+    }
+}
+
+fn default_slack_calls_per_minute() -> u32 {
+    50
+}
+
+fn default_slack_near_limit_percent() -> u8 {
+    80
+}
+
+fn default_slack_batch_window_seconds() -> u64 {
+    10
+}
+
+fn default_slack_history_cache_seconds() -> u64 {
+    15
+}
+
+/// Queue-depth alarms (`[alarms]`).
+///
+/// Every `check_interval_seconds` the server samples its internal queues:
+/// Slack messages waiting to be sent, undelivered steering messages and
+/// approval requests awaiting a decision. A queue at or above its threshold
+/// for `sustain_seconds` raises an alarm in Slack, and a second notice
+/// follows once it drops back below. A threshold of `0` turns off that
+/// queue's alarm.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct AlarmsConfig {
+    /// Sample the queues and post alarms.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Alarm threshold for the Slack outgoing message queue.
+    #[serde(default = "default_alarm_slack_outbound")]
+    pub slack_outbound: u64,
+    /// Alarm threshold for undelivered steering messages, across sessions.
+    #[serde(default = "default_alarm_steering")]
+    pub steering: u64,
+    /// Alarm threshold for approval requests awaiting a decision.
+    #[serde(default = "default_alarm_pending_approvals")]
+    pub pending_approvals: u64,
+    /// How long a queue must stay at or above its threshold to alarm.
+    #[serde(default = "default_alarm_sustain_seconds")]
+    pub sustain_seconds: u64,
+    /// How often the queues are sampled.
+    #[serde(default = "default_alarm_check_interval_seconds")]
+    pub check_interval_seconds: u64,
+    /// Channel alarms post to. Defaults to `[slack] channel_id`, or every
+    /// active session's channel when that is empty too.
+    #[serde(default)]
+    pub channel_id: Option<String>,
+    /// Prepend `[broadcast] escalation_mention` to raised alarms.
+    #[serde(default = "default_true")]
+    pub escalate: bool,
+}
+
+impl AlarmsConfig {
+    fn validate(&self) -> Result<()> {
+        if self.sustain_seconds == 0 {
+            return Err(AppError::Config(
+                "[alarms] sustain_seconds must be positive".into(),
+            ));
+        }
+        if self.check_interval_seconds == 0 {
+            return Err(AppError::Config(
+                "[alarms] check_interval_seconds must be positive".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for AlarmsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            slack_outbound: default_alarm_slack_outbound(),
+            steering: default_alarm_steering(),
+            pending_approvals: default_alarm_pending_approvals(),
+            sustain_seconds: default_alarm_sustain_seconds(),
+            check_interval_seconds: default_alarm_check_interval_seconds(),
+            channel_id: None,
+            escalate: true,
+        }
+    }
+}
+
+fn default_alarm_slack_outbound() -> u64 {
+    128
+}
+
+fn default_alarm_steering() -> u64 {
+    50
+}
+
+fn default_alarm_pending_approvals() -> u64 {
+    25
+}
+
+fn default_alarm_sustain_seconds() -> u64 {
+    120
+}
+
+fn default_alarm_check_interval_seconds() -> u64 {
+    15
+}
+
+/// Self-monitoring watchdog (`[watchdog]`).
+///
+/// Every `interval_seconds` the watchdog exercises the critical path: a
+/// database write and read, Slack `auth.test` and, with `ipc`, a round trip
+/// through the IPC endpoint. A failing check puts the server in degraded
+/// mode and posts a notice naming the failing components; a second notice
+/// follows once every check passes again. Background subsystems that stop
+/// (the Slack Socket Mode listener, the retention task) are restarted with
+/// backoff and reported the same way.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct WatchdogConfig {
+    /// Run the periodic checks.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Seconds between checks.
+    #[serde(default = "default_watchdog_interval_seconds")]
+    pub interval_seconds: u64,
+    /// Also check an IPC round trip on `ipc_name`. Enable when the IPC
+    /// server runs alongside this one.
+    #[serde(default)]
+    pub ipc: bool,
+    /// Channel notices post to. Defaults to `[slack] channel_id`, or every
+    /// active session's channel when that is empty too.
+    #[serde(default)]
+    pub channel_id: Option<String>,
+    /// Prepend `[broadcast] escalation_mention` to degraded-mode notices.
+    #[serde(default = "default_true")]
+    pub escalate: bool,
+}
+
+impl WatchdogConfig {
+    fn validate(&self) -> Result<()> {
+        if self.interval_seconds == 0 {
+            return Err(AppError::Config(
+                "[watchdog] interval_seconds must be positive".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_seconds: default_watchdog_interval_seconds(),
+            ipc: false,
+            channel_id: None,
+            escalate: true,
+        }
+    }
+}
+
+fn default_watchdog_interval_seconds() -> u64 {
+    60
+}
+
+/// Shared knowledge base (`[knowledge]`).
+///
+/// Agents propose notes with `propose_knowledge`; each is posted to Slack
+/// and becomes readable by every session through the `intercom://knowledge`
+/// resource only once an operator approves it.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct KnowledgeConfig {
+    /// Accept proposals and serve approved notes.
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// Restart behaviour for interrupted sessions and for requests still
+/// awaiting a decision (`[recovery]`).
+///
+/// By default a shutdown marks pending approvals and prompts `Interrupted`.
+/// With `rearm_pending` they stay pending instead, and the next startup
+/// re-posts them and waits for the operator again, so an agent that
+/// reconnects can pick up the decision with `reboot`.
+///
+/// `on_startup` picks what happens to the sessions the previous run left
+/// interrupted, beyond the summary posted to the global channel; only
+/// sessions interrupted within `max_age_minutes` are touched.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct RecoveryConfig {
+    /// Keep pending approvals and prompts across a restart and re-arm them
+    /// on startup.
+    #[serde(default)]
+    pub rearm_pending: bool,
+    /// What to do with interrupted sessions on startup.
+    #[serde(default)]
+    pub on_startup: StartupRecovery,
+    /// Only sessions interrupted at most this long before startup are
+    /// recovered.
+    #[serde(default = "default_recovery_max_age_minutes")]
+    pub max_age_minutes: u64,
+}
+
+impl Default for RecoveryConfig {
+    fn default() -> Self {
+        Self {
+            rearm_pending: false,
+            on_startup: StartupRecovery::default(),
+            max_age_minutes: default_recovery_max_age_minutes(),
+        }
+    }
+}
+
+impl RecoveryConfig {
+    /// Whether a shutdown leaves pending approvals and prompts pending for
+    /// the next startup to re-arm.
+    #[must_use]
+    pub fn keeps_pending(&self) -> bool {
+        self.rearm_pending || self.on_startup >= StartupRecovery::Repost
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.max_age_minutes == 0 {
+            return Err(AppError::Config(
+                "recovery.max_age_minutes must be at least 1".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Startup handling of interrupted sessions (`[recovery] on_startup`).
+///
+/// Each level does everything the one before it does.
+#[derive(
+    Debug, Clone, Copy, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq, PartialOrd, Ord,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupRecovery {
+    /// Post a summary and leave the sessions interrupted (default).
+    #[default]
+    Notify,
+    /// Make MCP sessions active again so their agents can reconnect
+    /// within `reconnect_grace_seconds`.
+    Reactivate,
+    /// Also re-post their pending approvals and prompts, each with a fresh
+    /// timeout.
+    Repost,
+    /// Also respawn the agents of sessions the server spawned.
+    Respawn,
+}
+
+fn default_recovery_max_age_minutes() -> u64 {
+    60
+}
+
+/// Files whose approved diffs need a second confirmation
+/// (`[protected_paths]`).
+///
+/// `check_diff` on an approval whose `file_path` matches one of `patterns`
+/// posts a summary with Confirm and Cancel buttons and writes the file only
+/// once an operator confirms, however long ago the diff was approved.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ProtectedPathsConfig {
+    /// Globs over workspace-relative paths (`migrations/**`,
+    /// `deploy/*.yaml`).
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+impl ProtectedPathsConfig {
+    /// Whether applying a diff to `file_path` needs a fresh confirmation.
+    #[must_use]
+    pub fn protects(&self, file_path: &str) -> bool {
+        let file_path = file_path.replace('\\', "/");
+        let file_path = file_path.trim_start_matches("./");
+        self.patterns
+            .iter()
+            .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+            .any(|pattern| pattern.matches(file_path))
+    }
+
+    fn validate(&self) -> Result<()> {
+        if let Some(bad) = self
+            .patterns
+            .iter()
+            .find(|pattern| glob::Pattern::new(pattern).is_err())
+        {
+            return Err(AppError::Config(format!(
+                "[protected_paths] invalid glob '{bad}'"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Value of [`ClientToolRule::client`] matching clients not listed in
+/// [`ClientsConfig::known`].
+pub const UNKNOWN_CLIENT: &str = "unknown";
+
+/// Tools hidden from and refused to particular MCP clients (`[clients]`).
+///
+/// A client is identified by the implementation name it sends in the MCP
+/// `initialize` handshake (`clientInfo.name`, e.g. `claude-code`).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ClientsConfig {
+    /// Globs over client names that count as known; any other client, or
+    /// one that sends no name, is matched by `client = "unknown"` rules.
+    #[serde(default)]
+    pub known: Vec<String>,
+    /// Tool gates, each applied to the clients it matches
+    /// (`[[clients.rules]]`).
+    #[serde(default)]
+    pub rules: Vec<ClientToolRule>,
+}
+
+/// One `[[clients.rules]]` entry.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ClientToolRule {
+    /// Glob over client names, or `"unknown"` for clients matching none of
+    /// [`ClientsConfig::known`].
+    pub client: String,
+    /// Globs over tool names the matched clients may not see or call.
+    pub deny: Vec<String>,
+}
+
+impl ClientsConfig {
+    /// Whether `tool` is denied to the client named `client`.
+    #[must_use]
+    pub fn denies(&self, client: Option<&str>, tool: &str) -> bool {
+        let unknown = client.is_none_or(|name| !matches_any(&self.known, name));
+        self.rules
+            .iter()
+            .filter(|rule| {
+                if rule.client == UNKNOWN_CLIENT {
+                    unknown
+                } else {
+                    client.is_some_and(|name| matches_any(std::slice::from_ref(&rule.client), name))
+                }
+            })
+            .any(|rule| matches_any(&rule.deny, tool))
+    }
+
+    fn validate(&self) -> Result<()> {
+        for rule in &self.rules {
+            if rule.client.is_empty() || rule.deny.is_empty() {
+                return Err(AppError::Config(
+                    "[[clients.rules]] entries need a client and at least one deny pattern".into(),
+                ));
+            }
+        }
+        if let Some(bad) = self
+            .known
+            .iter()
+            .chain(self.rules.iter().map(|rule| &rule.client))
+            .chain(self.rules.iter().flat_map(|rule| &rule.deny))
+            .find(|pattern| glob::Pattern::new(pattern).is_err())
+        {
+            return Err(AppError::Config(format!("[clients] invalid glob '{bad}'")));
+        }
+        Ok(())
+    }
+}
+
+/// Whether `name` matches one of the globs in `patterns`.
+fn matches_any(patterns: &[String], name: &str) -> bool {
+    patterns
+        .iter()
+        .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+        .any(|pattern| pattern.matches(name))
+}
+
+/// Changes the server applies at a time the operator chose
+/// (`[scheduled_apply]`).
+///
+/// `/intercom apply-at <request> <HH:MM>` approves a pending request and
+/// holds the diff until then. Once applied, each of `verify_commands` runs
+/// in the workspace root and the results are reported to the operator and
+/// the agent.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ScheduledApplyConfig {
+    /// Shell commands run in order after a scheduled apply (`cargo check`,
+    /// `npm test`). A failure is reported but does not undo the apply.
+    #[serde(default)]
+    pub verify_commands: Vec<String>,
+    /// Seconds each verification command may run before it is killed.
+    #[serde(default = "default_verify_timeout_seconds")]
+    pub verify_timeout_seconds: u64,
+}
+
+impl ScheduledApplyConfig {
+    fn validate(&self) -> Result<()> {
+        if self.verify_timeout_seconds == 0 {
+            return Err(AppError::Config(
+                "[scheduled_apply] verify_timeout_seconds must be positive".into(),
+            ));
+        }
+        if self.verify_commands.iter().any(|c| c.trim().is_empty()) {
+            return Err(AppError::Config(
+                "[scheduled_apply] verify_commands must not contain empty commands".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for ScheduledApplyConfig {
+    fn default() -> Self {
+        Self {
+            verify_commands: Vec::new(),
+            verify_timeout_seconds: default_verify_timeout_seconds(),
+        }
+    }
+}
+
+fn default_verify_timeout_seconds() -> u64 {
+    600
+}
+
+/// Automatic periodic checkpoints (`[checkpoints]`).
+///
+/// With `interval_minutes` set, every active session gets an unlabelled
+/// checkpoint once its newest checkpoint is that old, so there is always a
+/// recent restore point. Each automatic checkpoint then prunes the
+/// session's automatic checkpoints to the newest `keep_last`; operator and
+/// agent checkpoints (milestones) are spared unless `keep_milestones` is
+/// off.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct CheckpointsConfig {
+    /// Minutes between automatic checkpoints of a session; `0` disables
+    /// them.
+    #[serde(default)]
+    pub interval_minutes: u64,
+    /// Checkpoints kept per session when pruning.
+    #[serde(default = "default_checkpoint_keep_last")]
+    pub keep_last: usize,
+    /// Never prune checkpoints taken by an operator or agent.
+    #[serde(default = "default_true")]
+    pub keep_milestones: bool,
+}
+
+impl Default for CheckpointsConfig {
+    fn default() -> Self {
+        Self {
+            interval_minutes: 0,
+            keep_last: default_checkpoint_keep_last(),
+            keep_milestones: true,
+        }
+    }
+}
+
+impl CheckpointsConfig {
+    /// The interval between automatic checkpoints, or `None` when they are
+    /// disabled.
+    #[must_use]
+    pub fn interval(&self) -> Option<std::time::Duration> {
+        (self.interval_minutes > 0).then(|| std::time::Duration::from_mins(self.interval_minutes))
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.keep_last == 0 {
+            return Err(AppError::Config(
+                "[checkpoints] keep_last must be at least 1".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn default_checkpoint_keep_last() -> usize {
+    5
+}
+
+/// Scheduled wake-ups from `standby` (`[standby]`).
+///
+/// An agent calling `standby` with `wake_at` is resumed at that time with
+/// its own `wake_instruction`, or with this one when it gave none.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct StandbyConfig {
+    /// Instruction returned to an agent woken on schedule.
+    #[serde(default = "default_wake_instruction")]
+    pub wake_instruction: String,
+}
+
+impl Default for StandbyConfig {
+    fn default() -> Self {
+        Self {
+            wake_instruction: default_wake_instruction(),
+        }
+    }
+}
+
+impl StandbyConfig {
+    fn validate(&self) -> Result<()> {
+        if self.wake_instruction.trim().is_empty() {
+            return Err(AppError::Config(
+                "[standby] wake_instruction must not be empty".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn default_wake_instruction() -> String {
+    "Scheduled wake-up: continue with your work.".into()
+}
+
+/// Prefix of the variables the spawner sets itself; never operator-set.
+const RESERVED_ENV_PREFIX: &str = "INTERCOM_";
+
+/// Environment variables operators may set on ACP agent processes
+/// (`[session_env]`).
+///
+/// `session-start --env KEY=VAL` is refused unless `KEY` matches one of
+/// `allowed`. The session record keeps the variables for `session-restart`
+/// and reports, with the values of names matching `redact` replaced by
+/// [`REDACTED`].
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct SessionEnvConfig {
+    /// Globs over variable names operators may set (`NODE_ENV`,
+    /// `FEATURE_*`). Empty refuses every `--env`.
+    #[serde(default)]
+    pub allowed: Vec<String>,
+    /// Globs, matched case-insensitively, over names whose values are not
+    /// recorded.
+    #[serde(default = "default_redacted_env")]
+    pub redact: Vec<String>,
+}
+
+impl Default for SessionEnvConfig {
+    fn default() -> Self {
+        Self {
+            allowed: Vec::new(),
+            redact: default_redacted_env(),
+        }
+    }
+}
+
+impl SessionEnvConfig {
+    /// Whether operators may set `name`. Names starting with `INTERCOM_`
+    /// are always refused.
+    #[must_use]
+    pub fn permits(&self, name: &str) -> bool {
+        !name.starts_with(RESERVED_ENV_PREFIX)
+            && self
+                .allowed
+                .iter()
+                .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+                .any(|pattern| pattern.matches(name))
+    }
+
+    /// Refuse `env` unless every name in it is [permitted](Self::permits).
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` naming the refused variables.
+    pub fn check(&self, env: &BTreeMap<String, String>) -> Result<()> {
+        let refused: Vec<&str> = env
+            .keys()
+            .filter(|name| !self.permits(name))
+            .map(String::as_str)
+            .collect();
+        if refused.is_empty() {
+            return Ok(());
+        }
+        Err(AppError::Config(format!(
+            "environment variables not allowed by [session_env] allowed: {}",
+            refused.join(", ")
+        )))
+    }
+
+    /// `env` as recorded on the session: values of names matching `redact`
+    /// replaced by [`REDACTED`].
+    #[must_use]
+    pub fn redacted(&self, env: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+        let options = glob::MatchOptions {
+            case_sensitive: false,
+            ..glob::MatchOptions::default()
+        };
+        let secret: Vec<glob::Pattern> = self
+            .redact
+            .iter()
+            .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+            .collect();
+        env.iter()
+            .map(|(name, value)| {
+                let value = if secret.iter().any(|p| p.matches_with(name, options)) {
+                    REDACTED.to_owned()
+                } else {
+                    value.clone()
+                };
+                (name.clone(), value)
+            })
+            .collect()
+    }
 
-/// Stall detection configuration.
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
-pub struct StallConfig {
-    /// Whether stall detection is enabled.
-    #[serde(default = "default_true")]
-    pub enabled: bool,
-    /// Idle threshold before triggering alert.
-    #[serde(default = "default_inactivity_threshold")]
-    pub inactivity_threshold_seconds: u64,
-    /// Delay before auto-nudging when unattended.
-    #[serde(default = "default_escalation_threshold")]
-    pub escalation_threshold_seconds: u64,
-    /// Maximum consecutive auto-nudges before escalation.
-    #[serde(default = "default_max_retries")]
-    pub max_retries: u32,
-    /// Default nudge message delivered to the agent.
-    #[serde(default = "default_nudge_message")]
-    pub default_nudge_message: String,
+    fn validate(&self) -> Result<()> {
+        if let Some(bad) = self
+            .allowed
+            .iter()
+            .chain(&self.redact)
+            .find(|pattern| glob::Pattern::new(pattern).is_err())
+        {
+            return Err(AppError::Config(format!(
+                "[session_env] invalid glob '{bad}'"
+            )));
+        }
+        Ok(())
+    }
 }
 
-fn default_true() -> bool {
-    true
+fn default_redacted_env() -> Vec<String> {
+    ["*TOKEN*", "*SECRET*", "*PASSWORD*", "*KEY*", "*CREDENTIAL*"]
+        .map(String::from)
+        .to_vec()
 }
 
-fn default_inactivity_threshold() -> u64 {
-    300
+/// Periodic drift checks of the auto-approve rules (`[policy_drift]`).
+///
+/// An approved auto-approve suggestion is written to `.intercom/settings.json`,
+/// the workspace's `*.code-workspace` file and `.vscode/settings.json`.
+/// Manual edits can make the three disagree; every `interval_minutes` each
+/// workspace's files are compared and a disagreement is reported in Slack
+/// with a Reconcile button.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct PolicyDriftConfig {
+    /// Minutes between drift checks; `0` disables them.
+    #[serde(default = "default_policy_drift_interval_minutes")]
+    pub interval_minutes: u64,
 }
 
-fn default_escalation_threshold() -> u64 {
-    120
+impl Default for PolicyDriftConfig {
+    fn default() -> Self {
+        Self {
+            interval_minutes: default_policy_drift_interval_minutes(),
+        }
+    }
 }
 
-fn default_max_retries() -> u32 {
-    3
+impl PolicyDriftConfig {
+    /// The interval between drift checks, or `None` when they are disabled.
+    #[must_use]
+    pub fn interval(&self) -> Option<std::time::Duration> {
+        (self.interval_minutes > 0).then(|| std::time::Duration::from_mins(self.interval_minutes))
+    }
 }
 
-fn default_nudge_message() -> String {
-    "Continue working on the current task. Pick up where you left off.".into()
+fn default_policy_drift_interval_minutes() -> u64 {
+    60
 }
 
-fn default_retention_days() -> u32 {
-    30
+/// Full-workspace snapshots (`[snapshots]`).
+///
+/// `/intercom workspace-snapshot` (or `session-start --snapshot`) archives
+/// the workspace into `.intercom/snapshots/` with a hash manifest, and
+/// `/intercom workspace-restore` puts it back. `.intercom/` itself is never
+/// archived.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct SnapshotsConfig {
+    /// Globs over workspace-relative paths left out of snapshots and left
+    /// alone by a restore (`target/**`, `*.log`).
+    #[serde(default = "default_snapshot_exclude")]
+    pub exclude: Vec<String>,
+    /// Snapshots kept per workspace; older ones are deleted.
+    #[serde(default = "default_snapshot_retention")]
+    pub retention: usize,
 }
 
-fn default_max_concurrent_sessions() -> u32 {
-    3
-}
+impl SnapshotsConfig {
+    /// Whether the workspace-relative `path` is left out of snapshots.
+    #[must_use]
+    pub fn excludes(&self, path: &str) -> bool {
+        let path = path.replace('\\', "/");
+        let path = path.trim_start_matches("./");
+        if path == ".intercom" || path.starts_with(".intercom/") {
+            return true;
+        }
+        self.exclude
+            .iter()
+            .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+            .any(|pattern| pattern.matches(path))
+    }
 
-fn default_acp_max_sessions() -> usize {
-    5
+    /// Whether the whole directory `path` is left out, so a walk need not
+    /// descend into it: it matches a pattern itself, or a `dir/**` pattern
+    /// names it.
+    #[must_use]
+    pub fn excludes_dir(&self, path: &str) -> bool {
+        self.excludes(path)
+            || self.exclude.iter().any(|pattern| {
+                pattern
+                    .strip_suffix("/**")
+                    .and_then(|dir| glob::Pattern::new(dir).ok())
+                    .is_some_and(|dir| dir.matches(path))
+            })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.retention == 0 {
+            return Err(AppError::Config(
+                "[snapshots] retention must be at least 1".into(),
+            ));
+        }
+        if let Some(bad) = self
+            .exclude
+            .iter()
+            .find(|pattern| glob::Pattern::new(pattern).is_err())
+        {
+            return Err(AppError::Config(format!(
+                "[snapshots] invalid glob '{bad}'"
+            )));
+        }
+        Ok(())
+    }
 }
 
-fn default_acp_startup_timeout_seconds() -> u64 {
-    30
+impl Default for SnapshotsConfig {
+    fn default() -> Self {
+        Self {
+            exclude: default_snapshot_exclude(),
+            retention: default_snapshot_retention(),
+        }
+    }
 }
 
-fn default_acp_max_msg_rate() -> u32 {
-    10
+fn default_snapshot_exclude() -> Vec<String> {
+    ["target/**", "node_modules/**", ".git/**"]
+        .map(str::to_owned)
+        .to_vec()
 }
 
-fn default_acp_http_port() -> u16 {
-    3001
+fn default_snapshot_retention() -> usize {
+    5
 }
 
-/// ACP-mode specific configuration.
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
-pub struct AcpConfig {
-    /// Maximum number of concurrent ACP sessions.
-    ///
-    /// Requests beyond this limit are rejected with a descriptive error.
-    /// Defaults to `5`.
-    #[serde(default = "default_acp_max_sessions")]
-    pub max_sessions: usize,
-    /// Seconds to wait for the agent to emit its ready signal on stdout.
-    ///
-    /// If no line is received within this window the spawner kills the
-    /// process and returns `AppError::Acp("startup timeout")`. Defaults
-    /// to `30`.
-    #[serde(default = "default_acp_startup_timeout_seconds")]
-    pub startup_timeout_seconds: u64,
-    /// Maximum inbound ACP messages per second (token-bucket rate limit, FR-044).
-    ///
-    /// Exceeding this rate triggers a warning log and the message is dropped.
-    /// Sustained flooding (> 50 consecutive drops) terminates the session.
-    /// Set to `0` to disable rate limiting. Defaults to `10`.
-    #[serde(default = "default_acp_max_msg_rate")]
-    pub max_msg_rate: u32,
-    /// HTTP port for the MCP tool endpoint when running in ACP mode.
-    ///
-    /// Defaults to `3001` so that an ACP server instance can run concurrently
-    /// with an MCP server instance (which defaults to port `3000`).  The CLI
-    /// `--port` flag takes precedence over this value.
-    #[serde(default = "default_acp_http_port")]
-    pub http_port: u16,
+/// Handling of agents that connect with an unmapped `workspace_id`
+/// (`[workspace_discovery]`).
+///
+/// Disabled by default: such sessions run without Slack routing. When
+/// enabled, the workspace is held as pending until an operator confirms it
+/// with `/intercom workspace approve <id>`, which adds the `[[workspace]]`
+/// entry to `config.toml`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct WorkspaceDiscoveryConfig {
+    /// Track unmapped workspaces and announce them in Slack.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Channel that pending workspaces post to until approved; also receives
+    /// the discovery notices. Empty disables the fallback.
+    #[serde(default)]
+    pub unmapped_channel_id: String,
+    /// Create a public channel per discovered workspace
+    /// (`<channel_prefix><workspace_id>`, needs the `channels:manage` scope).
+    #[serde(default)]
+    pub create_channels: bool,
+    /// Name prefix for created channels.
+    #[serde(default = "default_discovery_channel_prefix")]
+    pub channel_prefix: String,
 }
 
-impl Default for AcpConfig {
+impl Default for WorkspaceDiscoveryConfig {
     fn default() -> Self {
         Self {
-            max_sessions: default_acp_max_sessions(),
-            startup_timeout_seconds: default_acp_startup_timeout_seconds(),
-            max_msg_rate: default_acp_max_msg_rate(),
-            http_port: default_acp_http_port(),
+            enabled: false,
+            unmapped_channel_id: String::new(),
+            create_channels: false,
+            channel_prefix: default_discovery_channel_prefix(),
         }
     }
 }
 
-fn default_http_port() -> u16 {
-    3000
-}
-
-fn default_ipc_name() -> String {
-    "agent-intercom".into()
+fn default_discovery_channel_prefix() -> String {
+    "intercom-".into()
 }
 
 /// Database configuration for the `SQLite` persistence layer.
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub struct DatabaseConfig {
     /// Relative or absolute path to the `SQLite` database file.
     ///
@@ -323,7 +2525,7 @@ fn default_db_path() -> PathBuf {
 }
 
 /// Verbosity level for Slack status messages.
-#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum SlackDetailLevel {
     /// Minimal output — errors and key events only.
@@ -335,13 +2537,41 @@ pub enum SlackDetailLevel {
     Verbose,
 }
 
+impl SlackDetailLevel {
+    /// Returns the `snake_case` name used in `config.toml`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Minimal => "minimal",
+            Self::Standard => "standard",
+            Self::Verbose => "verbose",
+        }
+    }
+
+    /// Parse the `snake_case` name used in `config.toml`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` for an unknown level.
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "minimal" => Ok(Self::Minimal),
+            "standard" => Ok(Self::Standard),
+            "verbose" => Ok(Self::Verbose),
+            other => Err(AppError::Config(format!(
+                "invalid detail level '{other}'; expected minimal, standard or verbose"
+            ))),
+        }
+    }
+}
+
 fn default_slack_detail_level() -> SlackDetailLevel {
     SlackDetailLevel::Standard
 }
 
 /// Global configuration parsed from `config.toml`.
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub struct GlobalConfig {
     /// Default workspace root used for the primary stdio agent.
     pub default_workspace_root: PathBuf,
@@ -353,6 +2583,13 @@ pub struct GlobalConfig {
     /// variable via [`GlobalConfig::load_authorized_users`]. Not read from `config.toml`.
     #[serde(skip)]
     pub authorized_user_ids: Vec<String>,
+    /// Slack user IDs with the read-only [`Role::Observer`] role.
+    ///
+    /// Observers may run the slash commands that only report (sessions,
+    /// history, decisions, logs) but cannot approve, steer or manage
+    /// sessions. IDs also in `authorized_user_ids` are operators.
+    #[serde(default)]
+    pub observer_user_ids: Vec<String>,
     /// Maximum concurrent agent sessions.
     #[serde(default = "default_max_concurrent_sessions")]
     pub max_concurrent_sessions: u32,
@@ -374,10 +2611,26 @@ pub struct GlobalConfig {
     /// Named pipe / Unix socket identifier.
     #[serde(default = "default_ipc_name")]
     pub ipc_name: String,
+    /// Extra groups allowed to open the IPC named pipe on Windows, as SIDs
+    /// (`S-1-5-32-544`) or SDDL aliases (`BA`). The server's own account
+    /// and `SYSTEM` are always allowed. Ignored on other platforms.
+    #[serde(default)]
+    pub ipc_allowed_groups: Vec<String>,
+    /// Extra UIDs allowed to issue IPC commands on Unix, checked against
+    /// the connecting peer's credentials. The server's own UID is always
+    /// allowed. Ignored on Windows.
+    #[serde(default)]
+    pub ipc_allowed_uids: Vec<u32>,
     /// Timeout configuration for blocking flows.
     pub timeouts: TimeoutConfig,
     /// Stall detection thresholds and behavior.
     pub stall: StallConfig,
+    /// Forwarded prompt quick replies and auto-policies.
+    #[serde(default)]
+    pub prompts: PromptsConfig,
+    /// Destinations for `broadcast` messages by level.
+    #[serde(default)]
+    pub broadcast: BroadcastConfig,
     /// Days after session termination before data is purged.
     #[serde(default = "default_retention_days")]
     pub retention_days: u32,
@@ -392,6 +2645,78 @@ pub struct GlobalConfig {
     /// ACP-mode configuration (max sessions, startup timeout).
     #[serde(default)]
     pub acp: AcpConfig,
+    /// CODEOWNERS handle mapping and approver routing.
+    #[serde(default)]
+    pub codeowners: CodeOwnersConfig,
+    /// Issue tracker integration for `session-start --issue`.
+    #[serde(default)]
+    pub issues: IssuesConfig,
+    /// Email delivery of session summaries.
+    #[serde(default)]
+    pub smtp: SmtpConfig,
+    /// Signed inbound approval webhooks.
+    #[serde(default)]
+    pub webhooks: WebhooksConfig,
+    /// Signed browser approval links in notifications.
+    #[serde(default)]
+    pub approval_links: ApprovalLinksConfig,
+    /// gRPC control plane listener.
+    #[serde(default)]
+    pub grpc: GrpcConfig,
+    /// Handling of agents with an unmapped `workspace_id`.
+    #[serde(default)]
+    pub workspace_discovery: WorkspaceDiscoveryConfig,
+    /// Short session and approval IDs.
+    #[serde(default)]
+    pub ids: IdsConfig,
+    /// Pending approvals and prompts across restarts.
+    #[serde(default)]
+    pub recovery: RecoveryConfig,
+    /// Diff and snippet size limits.
+    #[serde(default)]
+    pub limits: LimitsConfig,
+    /// Review snippets posted when the agent sends none.
+    #[serde(default)]
+    pub snippets: SnippetsConfig,
+    /// Operator-curated knowledge base shared across sessions.
+    #[serde(default)]
+    pub knowledge: KnowledgeConfig,
+    /// Files whose approved diffs need a second confirmation to apply.
+    #[serde(default)]
+    pub protected_paths: ProtectedPathsConfig,
+    /// Tools hidden from and refused to particular MCP clients.
+    #[serde(default)]
+    pub clients: ClientsConfig,
+    /// Verification run after changes applied on an operator's schedule.
+    #[serde(default)]
+    pub scheduled_apply: ScheduledApplyConfig,
+    /// Full-workspace snapshots and their retention.
+    #[serde(default)]
+    pub snapshots: SnapshotsConfig,
+    /// Automatic periodic checkpoints and their pruning.
+    #[serde(default)]
+    pub checkpoints: CheckpointsConfig,
+    /// Default instruction for scheduled wake-ups from standby.
+    #[serde(default)]
+    pub standby: StandbyConfig,
+    /// Environment variables operators may set on agent processes.
+    #[serde(default)]
+    pub session_env: SessionEnvConfig,
+    /// Periodic comparison of the auto-approve rule files.
+    #[serde(default)]
+    pub policy_drift: PolicyDriftConfig,
+    /// Log file rotation, compression and retention.
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    /// Slack Web API call budget and low-priority update batching.
+    #[serde(default)]
+    pub slack_rate_limit: SlackRateLimitConfig,
+    /// Alarms on sustained internal queue build-up.
+    #[serde(default)]
+    pub alarms: AlarmsConfig,
+    /// Periodic critical-path checks and subsystem restarts.
+    #[serde(default)]
+    pub watchdog: WatchdogConfig,
     /// Workspace-to-channel routing table.
     ///
     /// Each `[[workspace]]` entry in `config.toml` maps a `workspace_id`
@@ -405,30 +2730,210 @@ pub struct GlobalConfig {
     pub workspaces: Vec<WorkspaceMapping>,
 }
 
+/// Access level of a Slack user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Listed in `authorized_user_ids`: may start, steer and approve.
+    Operator,
+    /// Listed in `observer_user_ids`: may only view sessions and decisions.
+    Observer,
+}
+
+/// Top-level table holding named profiles (`[profile.dev]`).
+const PROFILE_TABLE: &str = "profile";
+
+/// Placeholder for secret values in [`GlobalConfig::redacted_json`].
+pub const REDACTED: &str = "[redacted]";
+
+/// Merge `overlay` into `base`: nested tables key by key, other values
+/// replaced.
+fn merge_tables(base: &mut toml::Table, overlay: &toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(nested)) => {
+                merge_tables(existing, nested);
+            }
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
 impl GlobalConfig {
-    /// Load and validate configuration from a TOML file path.
+    /// Load and validate configuration from a TOML file path, with
+    /// `INTERCOM_*` environment overrides applied.
     ///
     /// # Errors
     ///
     /// Returns `AppError::Config` if the file cannot be read or contains
     /// invalid TOML, or if validation fails.
     pub fn load_from_path(path: impl AsRef<Path>) -> Result<Self> {
+        Self::load_profile_from_path(path, None)
+    }
+
+    /// [`GlobalConfig::load_from_path`] with `[profile.<name>]` applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` if the file cannot be read, the profile
+    /// does not exist, or parsing or validation fails.
+    pub fn load_profile_from_path(path: impl AsRef<Path>, profile: Option<&str>) -> Result<Self> {
         let raw = fs::read_to_string(path)
             .map_err(|err| AppError::Config(format!("failed to read config: {err}")))?;
-        Self::from_toml_str(&raw)
+        Self::from_toml_layers(&raw, profile, env::vars())
     }
 
     /// Parse configuration from a TOML string and normalize paths.
     ///
+    /// `[profile.*]` tables are ignored; see [`GlobalConfig::from_toml_layers`].
+    ///
     /// # Errors
     ///
     /// Returns `AppError::Config` if parsing or validation fails.
     pub fn from_toml_str(raw: &str) -> Result<Self> {
-        let mut config: Self = toml::from_str(raw)?;
+        Self::from_toml_layers(raw, None, std::iter::empty())
+    }
+
+    /// Parse configuration from layers, later ones winning:
+    ///
+    /// 1. the base tables of `raw`;
+    /// 2. `[profile.<name>]` from `raw`, for `profile` or, when that is
+    ///    `None`, the `INTERCOM_PROFILE` entry of `vars`;
+    /// 3. the other `INTERCOM_*` entries of `vars` (see [`crate::config_env`]).
+    ///
+    /// Profile tables merge key by key into the base; arrays such as
+    /// `[[workspace]]` are replaced whole. When neither a profile nor an
+    /// override applies and `raw` has no profiles, parse errors keep their
+    /// line numbers.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` if parsing fails, the profile does not
+    /// exist, an override is invalid, or validation fails.
+    pub fn from_toml_layers<I>(raw: &str, profile: Option<&str>, vars: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let vars: Vec<(String, String)> = vars
+            .into_iter()
+            .filter(|(name, _)| name.starts_with(config_env::ENV_PREFIX))
+            .collect();
+        let profile = profile
+            .map(str::to_owned)
+            .or_else(|| {
+                vars.iter()
+                    .find(|(name, _)| name == config_env::PROFILE_VAR)
+                    .map(|(_, value)| value.clone())
+            })
+            .filter(|name| !name.is_empty());
+
+        let mut table: toml::Table = toml::from_str(raw)?;
+        let profiles = table.remove(PROFILE_TABLE);
+        if let Some(name) = profile.as_deref() {
+            let overlay = profiles
+                .as_ref()
+                .and_then(toml::Value::as_table)
+                .and_then(|profiles| profiles.get(name))
+                .ok_or_else(|| {
+                    AppError::Config(format!(
+                        "profile '{name}' not found; defined profiles: {}",
+                        Self::profile_names(raw).unwrap_or_default().join(", ")
+                    ))
+                })?
+                .as_table()
+                .ok_or_else(|| AppError::Config(format!("[profile.{name}] must be a table")))?;
+            merge_tables(&mut table, overlay);
+            tracing::info!(profile = name, "applied config profile");
+        }
+
+        let applied = config_env::apply_env_overrides(&mut table, &Self::json_schema(), vars)?;
+        if !applied.is_empty() {
+            tracing::info!(overrides = ?applied, "applied config overrides from environment");
+        }
+
+        let mut config: Self = if profiles.is_none() && applied.is_empty() {
+            toml::from_str(raw)?
+        } else {
+            toml::Value::Table(table).try_into()?
+        };
         config.validate()?;
         Ok(config)
     }
 
+    /// Names of the `[profile.*]` tables in `raw`, sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` if `raw` is not valid TOML.
+    pub fn profile_names(raw: &str) -> Result<Vec<String>> {
+        let table: toml::Table = toml::from_str(raw)?;
+        let mut names: Vec<String> = table
+            .get(PROFILE_TABLE)
+            .and_then(toml::Value::as_table)
+            .map(|profiles| profiles.keys().cloned().collect())
+            .unwrap_or_default();
+        names.sort();
+        Ok(names)
+    }
+
+    /// JSON Schema for `config.toml`, derived from these structs.
+    ///
+    /// Fields populated at runtime (tokens, authorized users) are absent,
+    /// and every table rejects unknown keys, as [`GlobalConfig::from_toml_str`]
+    /// does. `profile` accepts named tables with the same keys, all optional.
+    #[must_use]
+    pub fn json_schema() -> serde_json::Value {
+        let mut schema = schemars::schema_for!(Self).to_value();
+        let overlay = serde_json::json!({
+            "type": "object",
+            "properties": schema["properties"].clone(),
+            "additionalProperties": false,
+        });
+        if let Some(properties) = schema
+            .get_mut("properties")
+            .and_then(serde_json::Value::as_object_mut)
+        {
+            properties.insert(
+                PROFILE_TABLE.to_owned(),
+                serde_json::json!({
+                    "description": "Named overlays selected with --profile or INTERCOM_PROFILE.",
+                    "type": "object",
+                    "additionalProperties": overlay,
+                }),
+            );
+        }
+        schema
+    }
+
+    /// The configuration as JSON, safe to show to agents.
+    ///
+    /// Runtime credentials (Slack tokens, signing secrets, SMTP and issue
+    /// tracker logins) and authorized users are never serialized; proxy
+    /// `env` values are replaced with [`REDACTED`], since they usually
+    /// carry API keys.
+    #[must_use]
+    pub fn redacted_json(&self) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        let workspaces = value
+            .get_mut("workspace")
+            .and_then(serde_json::Value::as_array_mut);
+        for workspace in workspaces.into_iter().flatten() {
+            let servers = workspace
+                .get_mut("proxy")
+                .and_then(serde_json::Value::as_array_mut);
+            for server in servers.into_iter().flatten() {
+                let env = server
+                    .get_mut("env")
+                    .and_then(serde_json::Value::as_object_mut);
+                for secret in env.into_iter().flat_map(|env| env.values_mut()) {
+                    *secret = REDACTED.into();
+                }
+            }
+        }
+        value
+    }
+
     /// Load Slack credentials from OS keychain with env-var fallback, and load
     /// authorized user IDs from `SLACK_MEMBER_IDS`.
     ///
@@ -447,14 +2952,48 @@ impl GlobalConfig {
     /// # Errors
     ///
     /// Returns `AppError::Config` if neither keychain nor env vars provide
-    /// the required Slack tokens, or if `SLACK_MEMBER_IDS` is
-    /// absent or empty.
+    /// the required Slack tokens (or the webhook secret or gRPC token when
+    /// those are enabled), or if `SLACK_MEMBER_IDS` is absent or empty.
     pub async fn load_credentials(&mut self, mode: ServerMode) -> Result<()> {
         let _span = tracing::info_span!("load_credentials", ?mode).entered();
         self.slack.app_token = load_credential("slack_app_token", "SLACK_APP_TOKEN", mode).await?;
         self.slack.bot_token = load_credential("slack_bot_token", "SLACK_BOT_TOKEN", mode).await?;
         // SLACK_TEAM_ID is optional per FR-041 — absence is not an error.
         self.slack.team_id = load_optional_credential("slack_team_id", "SLACK_TEAM_ID", mode).await;
+        if self.issues.provider.is_some() {
+            self.issues.token =
+                load_optional_credential("issue_tracker_token", "ISSUE_TRACKER_TOKEN", mode).await;
+            self.issues.user =
+                load_optional_credential("issue_tracker_user", "ISSUE_TRACKER_USER", mode).await;
+        }
+        if self.smtp.is_enabled() {
+            self.smtp.username =
+                load_optional_credential("smtp_username", "SMTP_USERNAME", mode).await;
+            self.smtp.password =
+                load_optional_credential("smtp_password", "SMTP_PASSWORD", mode).await;
+        }
+        if self.webhooks.enabled {
+            self.webhooks.secret =
+                load_credential("webhook_secret", "WEBHOOK_SECRET", mode).await?;
+            self.webhooks.wait_secret =
+                load_optional_credential("webhook_wait_secret", "WEBHOOK_WAIT_SECRET", mode).await;
+            if !self.webhooks.wait_secret.is_empty()
+                && self.webhooks.wait_secret == self.webhooks.secret
+            {
+                return Err(AppError::Config(
+                    "WEBHOOK_WAIT_SECRET must differ from WEBHOOK_SECRET".into(),
+                ));
+            }
+        }
+        if self.grpc.enabled {
+            self.grpc.token = load_credential("grpc_token", "GRPC_TOKEN", mode).await?;
+        }
+        if self.approval_links.enabled {
+            self.approval_links.secret =
+                load_optional_credential("approval_link_secret", "APPROVAL_LINK_SECRET", mode)
+                    .await;
+            self.approval_links.ensure_secret();
+        }
         self.load_authorized_users(mode)?;
         Ok(())
     }
@@ -519,6 +3058,19 @@ impl GlobalConfig {
         &self.database.path
     }
 
+    /// The role `user_id` holds, or `None` for users with no access.
+    /// Operators take precedence over observers.
+    #[must_use]
+    pub fn role_of(&self, user_id: &str) -> Option<Role> {
+        if self.authorized_user_ids.iter().any(|id| id == user_id) {
+            Some(Role::Operator)
+        } else if self.observer_user_ids.iter().any(|id| id == user_id) {
+            Some(Role::Observer)
+        } else {
+            None
+        }
+    }
+
     /// Validate that a Slack user is authorized to manage sessions.
     ///
     /// # Errors
@@ -547,6 +3099,51 @@ impl GlobalConfig {
 
         self.validate_workspace_mappings()?;
 
+        if self.stall.adaptive
+            && (self.stall.adaptive_multiplier == 0
+                || self.stall.adaptive_min_seconds > self.stall.adaptive_max_seconds)
+        {
+            return Err(AppError::Config(
+                "[stall] adaptive_multiplier must be greater than zero and \
+                 adaptive_min_seconds must not exceed adaptive_max_seconds"
+                    .into(),
+            ));
+        }
+
+        if self.stall.heartbeat_required && self.stall.heartbeat_deadline_seconds == 0 {
+            return Err(AppError::Config(
+                "[stall] heartbeat_deadline_seconds must be greater than zero".into(),
+            ));
+        }
+
+        self.prompts.validate()?;
+        self.broadcast.validate()?;
+        self.approval_links.validate()?;
+        self.ids.validate()?;
+        self.limits.validate()?;
+        self.snippets.validate()?;
+        self.logging.validate()?;
+        self.slack_rate_limit.validate()?;
+        self.alarms.validate()?;
+        self.watchdog.validate()?;
+        self.protected_paths.validate()?;
+        self.clients.validate()?;
+        self.recovery.validate()?;
+        self.scheduled_apply.validate()?;
+        self.snapshots.validate()?;
+        self.checkpoints.validate()?;
+        self.standby.validate()?;
+        self.session_env.validate()?;
+        for group in &self.ipc_allowed_groups {
+            crate::ipc::security::validate_group(group)?;
+        }
+
+        if self.smtp.is_enabled() && self.smtp.from.is_none() {
+            return Err(AppError::Config(
+                "[smtp] from is required when host is set".into(),
+            ));
+        }
+
         Ok(())
     }
 
@@ -576,6 +3173,10 @@ impl GlobalConfig {
                     mapping.workspace_id
                 )));
             }
+            validate_proxy_servers(mapping)?;
+            validate_approval_channels(mapping)?;
+            validate_working_hours(mapping)?;
+            validate_bootstrap(mapping)?;
         }
         Ok(())
     }
@@ -0,0 +1,165 @@
+//! `INTERCOM_*` environment-variable overrides layered over `config.toml`.
+//!
+//! Each variable names one config key: the part after `INTERCOM_` is
+//! lower-cased and `__` separates table levels, so `INTERCOM_HTTP_PORT`
+//! sets `http_port` and `INTERCOM_STALL__ENABLED` sets `[stall] enabled`.
+//!
+//! Values are typed with the config JSON Schema
+//! ([`GlobalConfig::json_schema`](crate::GlobalConfig::json_schema)):
+//! string keys take the raw text, everything else is parsed as a TOML
+//! value (`42`, `true`, `["a", "b"]`, `{ key = "v" }`). A variable naming
+//! no config key is an error, like an unknown key in the file.
+//!
+//! `INTERCOM_PROFILE` is not an override: it selects a `[profile.<name>]`
+//! table when `--profile` is not given.
+
+use crate::{AppError, Result};
+
+/// Prefix of override variables.
+pub const ENV_PREFIX: &str = "INTERCOM_";
+
+/// Separator between table levels in a variable name.
+const LEVEL_SEPARATOR: &str = "__";
+
+/// Selects a `[profile.<name>]` table when `--profile` is not given.
+pub const PROFILE_VAR: &str = "INTERCOM_PROFILE";
+
+/// Variables that are not config overrides: the profile selector and the
+/// ones the spawners set for child agents.
+const RESERVED: [&str; 4] = [
+    PROFILE_VAR,
+    "INTERCOM_WORKSPACE_ROOT",
+    "INTERCOM_MCP_URL",
+    "INTERCOM_SESSION_ID",
+];
+
+/// Apply every `INTERCOM_*` entry of `vars` to `table`.
+///
+/// Returns the applied variable names, sorted.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` naming the variable when it matches no config
+/// key or its value does not parse.
+pub fn apply_env_overrides<I>(
+    table: &mut toml::Table,
+    schema: &serde_json::Value,
+    vars: I,
+) -> Result<Vec<String>>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let mut overrides: Vec<(String, String)> = vars
+        .into_iter()
+        .filter(|(name, _)| name.starts_with(ENV_PREFIX) && !RESERVED.contains(&name.as_str()))
+        .collect();
+    overrides.sort();
+
+    for (name, raw) in &overrides {
+        let path: Vec<String> = name[ENV_PREFIX.len()..]
+            .split(LEVEL_SEPARATOR)
+            .map(str::to_ascii_lowercase)
+            .collect();
+        if path.iter().any(String::is_empty) {
+            return Err(AppError::Config(format!(
+                "{name}: empty key segment in override variable"
+            )));
+        }
+        let target = path
+            .iter()
+            .try_fold(schema, |node, key| child(schema, node, key))
+            .ok_or_else(|| {
+                AppError::Config(format!("{name}: no config key `{}`", path.join(".")))
+            })?;
+        let value = if accepts_string(schema, target) {
+            toml::Value::String(raw.clone())
+        } else {
+            parse_value(raw).map_err(|err| {
+                AppError::Config(format!("{name}: value is not valid TOML: {err}"))
+            })?
+        };
+        insert(table, &path, value)
+            .map_err(|key| AppError::Config(format!("{name}: `{key}` is not a table")))?;
+    }
+
+    Ok(overrides.into_iter().map(|(name, _)| name).collect())
+}
+
+/// Follow a `$ref` to its definition.
+fn resolve<'a>(root: &'a serde_json::Value, node: &'a serde_json::Value) -> &'a serde_json::Value {
+    node.get("$ref")
+        .and_then(serde_json::Value::as_str)
+        .and_then(|reference| reference.strip_prefix("#/"))
+        .and_then(|pointer| root.pointer(&format!("/{pointer}")))
+        .unwrap_or(node)
+}
+
+/// Alternatives of an `anyOf`/`oneOf` node, or the node itself.
+fn variants<'a>(
+    root: &'a serde_json::Value,
+    node: &'a serde_json::Value,
+) -> Vec<&'a serde_json::Value> {
+    let node = resolve(root, node);
+    ["anyOf", "oneOf"]
+        .iter()
+        .find_map(|key| node.get(*key).and_then(serde_json::Value::as_array))
+        .map_or_else(
+            || vec![node],
+            |alternatives| alternatives.iter().map(|alt| resolve(root, alt)).collect(),
+        )
+}
+
+/// Schema of `key` inside the table described by `node`.
+fn child<'a>(
+    root: &'a serde_json::Value,
+    node: &'a serde_json::Value,
+    key: &str,
+) -> Option<&'a serde_json::Value> {
+    variants(root, node).into_iter().find_map(|table| {
+        table
+            .get("properties")
+            .and_then(|properties| properties.get(key))
+            .or_else(|| table.get("additionalProperties").filter(|v| v.is_object()))
+    })
+}
+
+/// Whether `node` (or one of its alternatives) is a string.
+fn accepts_string(root: &serde_json::Value, node: &serde_json::Value) -> bool {
+    variants(root, node)
+        .into_iter()
+        .any(|variant| match variant.get("type") {
+            Some(serde_json::Value::String(kind)) => kind == "string",
+            Some(serde_json::Value::Array(kinds)) => kinds.iter().any(|k| k == "string"),
+            _ => false,
+        })
+}
+
+/// Parse `raw` as the right-hand side of a TOML key/value pair.
+fn parse_value(raw: &str) -> std::result::Result<toml::Value, String> {
+    let mut wrapper: toml::Table =
+        toml::from_str(&format!("value = {raw}")).map_err(|err| err.message().to_owned())?;
+    wrapper
+        .remove("value")
+        .ok_or_else(|| "missing value".to_owned())
+}
+
+/// Set `path` in `table`, creating intermediate tables. On failure returns
+/// the key that holds a non-table value.
+fn insert(
+    table: &mut toml::Table,
+    path: &[String],
+    value: toml::Value,
+) -> std::result::Result<(), String> {
+    let Some((last, parents)) = path.split_last() else {
+        return Ok(());
+    };
+    let mut current = table;
+    for key in parents {
+        let entry = current
+            .entry(key.clone())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        current = entry.as_table_mut().ok_or_else(|| key.clone())?;
+    }
+    current.insert(last.clone(), value);
+    Ok(())
+}
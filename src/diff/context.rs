@@ -0,0 +1,379 @@
+//! Language-aware context expansion for approval diffs.
+//!
+//! Agents usually send diffs with three lines of context, which rarely
+//! shows the operator what the changed lines belong to. For display only,
+//! [`expand_context`] widens each hunk of a unified diff to the whole
+//! function or block around it, read from the original file with light
+//! heuristics chosen by the language `file_extension_language` reports:
+//! brace matching for C-like languages and indentation for Python.
+//!
+//! A block is only taken whole when it has at most [`MAX_BLOCK_LINES`]
+//! lines; otherwise the next smaller enclosing block is used, or the hunk
+//! is left as it was. The stored diff, which is what gets applied, is never
+//! changed.
+
+use std::fmt::Write as _;
+
+use diffy::{Line, Patch};
+
+/// Largest block, in lines, a hunk is widened to.
+pub const MAX_BLOCK_LINES: usize = 60;
+
+/// Most signature, attribute and doc-comment lines taken in above a block.
+const MAX_HEADER_LINES: usize = 15;
+
+/// How blocks are found for a language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Syntax {
+    /// `{` … `}` blocks. `quotes` are the string delimiters to skip.
+    Braces { quotes: &'static [char] },
+    /// Blocks opened by `def`/`class` lines and closed by dedent.
+    Indent,
+}
+
+impl Syntax {
+    fn for_language(language: &str) -> Option<Self> {
+        match language {
+            "rust" | "go" | "java" | "csharp" | "cpp" | "c" | "css" | "hcl" => {
+                Some(Self::Braces { quotes: &['"'] })
+            }
+            "typescript" | "javascript" => Some(Self::Braces {
+                quotes: &['"', '\'', '`'],
+            }),
+            "bash" | "powershell" => Some(Self::Braces {
+                quotes: &['"', '\''],
+            }),
+            "python" => Some(Self::Indent),
+            _ => None,
+        }
+    }
+}
+
+/// One hunk of the diff, in 0-based line positions.
+struct Hunk<'a> {
+    /// First original line the hunk covers (or inserts before).
+    old_begin: usize,
+    /// One past the last original line it covers.
+    old_end: usize,
+    /// First line of the new file the hunk covers.
+    new_begin: usize,
+    /// Prefix (`' '`, `'-'`, `'+'`) and text of each line.
+    lines: Vec<(char, &'a str)>,
+}
+
+/// Hunks merged after widening, ready to render.
+struct Group {
+    old_begin: usize,
+    old_end: usize,
+    new_begin: usize,
+    /// Where the original file has been copied up to.
+    cursor: usize,
+    lines: Vec<String>,
+}
+
+/// Widen every hunk of `diff` to the function or block around it in
+/// `original`.
+///
+/// Returns `None` when the language is not supported, `diff` is not a
+/// unified diff with hunks, its context does not match `original`, or no
+/// hunk would change.
+#[must_use]
+pub fn expand_context(diff: &str, original: &str, language: &str) -> Option<String> {
+    let syntax = Syntax::for_language(language)?;
+    let patch = Patch::from_str(diff).ok()?;
+    if patch.hunks().is_empty() {
+        return None;
+    }
+    let source: Vec<&str> = original.lines().collect();
+    let hunks = parse_hunks(&patch, &source)?;
+
+    let widened: Vec<(usize, usize)> = hunks
+        .iter()
+        .map(|hunk| widen(&source, syntax, hunk))
+        .collect();
+    if hunks
+        .iter()
+        .zip(&widened)
+        .all(|(hunk, &(begin, end))| begin == hunk.old_begin && end == hunk.old_end)
+    {
+        return None;
+    }
+
+    let mut groups: Vec<Group> = Vec::new();
+    for (hunk, &(begin, end)) in hunks.iter().zip(&widened) {
+        match groups.last_mut() {
+            Some(group) if begin <= group.old_end => {
+                copy_context(&mut group.lines, &source, group.cursor, hunk.old_begin);
+                group.lines.extend(render_lines(hunk));
+                group.cursor = hunk.old_end;
+                group.old_end = group.old_end.max(end);
+            }
+            previous => {
+                let begin = previous.map_or(begin, |group| begin.max(group.old_end));
+                let mut lines = Vec::new();
+                copy_context(&mut lines, &source, begin, hunk.old_begin);
+                lines.extend(render_lines(hunk));
+                groups.push(Group {
+                    old_begin: begin,
+                    old_end: end,
+                    new_begin: hunk.new_begin - (hunk.old_begin - begin),
+                    cursor: hunk.old_end,
+                    lines,
+                });
+            }
+        }
+    }
+
+    let mut out = String::new();
+    for line in diff.lines().take_while(|line| !line.starts_with("@@")) {
+        out.push_str(line);
+        out.push('\n');
+    }
+    for mut group in groups {
+        copy_context(&mut group.lines, &source, group.cursor, group.old_end);
+        let old_len = group.old_end - group.old_begin;
+        let new_len = group.lines.iter().filter(|l| !l.starts_with('-')).count();
+        let _ = writeln!(
+            out,
+            "@@ -{},{old_len} +{},{new_len} @@",
+            range_start(group.old_begin, old_len),
+            range_start(group.new_begin, new_len)
+        );
+        for line in group.lines {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+    Some(out)
+}
+
+/// The hunks of `patch`, or `None` if their context and removed lines do
+/// not match `source`.
+fn parse_hunks<'a>(patch: &'a Patch<'a, str>, source: &[&str]) -> Option<Vec<Hunk<'a>>> {
+    let mut hunks = Vec::new();
+    for hunk in patch.hunks() {
+        let old = hunk.old_range();
+        let new = hunk.new_range();
+        // A zero-length range names the line the hunk follows.
+        let old_begin = if old.is_empty() {
+            old.start()
+        } else {
+            old.start() - 1
+        };
+        let new_begin = if new.is_empty() {
+            new.start()
+        } else {
+            new.start() - 1
+        };
+        let lines: Vec<(char, &str)> = hunk
+            .lines()
+            .iter()
+            .map(|line| match line {
+                Line::Context(text) => (' ', trim_newline(text)),
+                Line::Delete(text) => ('-', trim_newline(text)),
+                Line::Insert(text) => ('+', trim_newline(text)),
+            })
+            .collect();
+        let mut at = old_begin;
+        for &(kind, text) in &lines {
+            if kind != '+' {
+                if source.get(at) != Some(&text) {
+                    return None;
+                }
+                at += 1;
+            }
+        }
+        hunks.push(Hunk {
+            old_begin,
+            old_end: at,
+            new_begin,
+            lines,
+        });
+    }
+    Some(hunks)
+}
+
+/// The original lines, `[begin, end)`, `hunk` should show once widened.
+fn widen(source: &[&str], syntax: Syntax, hunk: &Hunk<'_>) -> (usize, usize) {
+    let first = hunk.old_begin.min(source.len().saturating_sub(1));
+    let last = hunk.old_end.saturating_sub(1).max(first);
+    let block = match syntax {
+        Syntax::Braces { quotes } => brace_block(source, quotes, first, last),
+        Syntax::Indent => indent_block(source, first, last),
+    };
+    let Some((open, close)) = block else {
+        return (hunk.old_begin, hunk.old_end);
+    };
+    let begin = with_header(source, open).min(hunk.old_begin);
+    let end = (close + 1).max(hunk.old_end).min(source.len());
+    (begin, end)
+}
+
+/// The outermost `{` … `}` block around lines `first..=last` with at most
+/// [`MAX_BLOCK_LINES`] lines, as its opening and closing line.
+fn brace_block(
+    source: &[&str],
+    quotes: &[char],
+    first: usize,
+    last: usize,
+) -> Option<(usize, usize)> {
+    let mut open: Vec<usize> = Vec::new();
+    let mut blocks: Vec<(usize, usize)> = Vec::new();
+    for (index, line) in source.iter().enumerate() {
+        for brace in braces(line, quotes) {
+            if brace == '{' {
+                open.push(index);
+            } else if let Some(start) = open.pop() {
+                if start != index {
+                    blocks.push((start, index));
+                }
+            }
+        }
+    }
+    let fits = |&&(start, end): &&(usize, usize)| end - start < MAX_BLOCK_LINES;
+    let enclosing = blocks
+        .iter()
+        .filter(|&&(start, end)| start <= first && end >= last)
+        .filter(fits)
+        .max_by_key(|&&(start, end)| end - start);
+    if let Some(&block) = enclosing {
+        return Some(block);
+    }
+    // Not inside a small enough block: take in the blocks the hunk cuts.
+    let (start, end) = blocks
+        .iter()
+        .filter(|&&(start, end)| start <= last && end >= first)
+        .filter(fits)
+        .fold((first, last), |(lo, hi), &(start, end)| {
+            (lo.min(start), hi.max(end))
+        });
+    (end - start < MAX_BLOCK_LINES && (start, end) != (first, last)).then_some((start, end))
+}
+
+/// The braces on `line` outside strings and `//` comments.
+fn braces(line: &str, quotes: &[char]) -> Vec<char> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut found = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match quote {
+            Some(q) => {
+                if c == '\\' {
+                    i += 1;
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None if c == '/' && chars.get(i + 1) == Some(&'/') => break,
+            // Character literals such as '{' and '\n'.
+            None if c == '\'' && !quotes.contains(&'\'') => {
+                if chars.get(i + 2) == Some(&'\'') {
+                    i += 2;
+                } else if chars.get(i + 1) == Some(&'\\') && chars.get(i + 3) == Some(&'\'') {
+                    i += 3;
+                }
+            }
+            None if quotes.contains(&c) => quote = Some(c),
+            None if c == '{' || c == '}' => found.push(c),
+            None => {}
+        }
+        i += 1;
+    }
+    found
+}
+
+/// The outermost `def`/`class` block around lines `first..=last` with at
+/// most [`MAX_BLOCK_LINES`] lines, as its opening and last line.
+fn indent_block(source: &[&str], first: usize, last: usize) -> Option<(usize, usize)> {
+    let mut level = (first..=last)
+        .filter_map(|index| source.get(index))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| indent(line))
+        .min()?;
+    let mut best = None;
+    let mut index = first;
+    while index > 0 && level > 0 {
+        index -= 1;
+        let line = source[index];
+        if line.trim().is_empty() || indent(line) >= level {
+            continue;
+        }
+        level = indent(line);
+        let head = line.trim_start();
+        if !["def ", "async def ", "class "]
+            .iter()
+            .any(|keyword| head.starts_with(keyword))
+        {
+            continue;
+        }
+        let end = (index + 1..source.len())
+            .take_while(|&i| source[i].trim().is_empty() || indent(source[i]) > level)
+            .filter(|&i| !source[i].trim().is_empty())
+            .last()
+            .unwrap_or(index)
+            .max(last);
+        if end - index >= MAX_BLOCK_LINES {
+            break;
+        }
+        best = Some((index, end));
+    }
+    best
+}
+
+/// Width of the leading whitespace of `line`.
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// The first line of the signature, attributes and doc comments above the
+/// block opened on line `open`.
+fn with_header(source: &[&str], open: usize) -> usize {
+    let mut start = open;
+    while start > 0 && open - start < MAX_HEADER_LINES {
+        let above = source[start - 1].trim();
+        let continues =
+            source[start].trim_start().starts_with(['{', ')']) || above.ends_with([',', '(']);
+        let decoration = ["#[", "#!", "@", "///", "//", "/*", "*"]
+            .iter()
+            .any(|prefix| above.starts_with(prefix));
+        if above.is_empty() || !(continues || decoration) {
+            break;
+        }
+        start -= 1;
+    }
+    start
+}
+
+/// Append original lines `[from, to)` as context lines.
+fn copy_context(lines: &mut Vec<String>, source: &[&str], from: usize, to: usize) {
+    lines.extend(
+        source
+            .iter()
+            .take(to)
+            .skip(from)
+            .map(|line| format!(" {line}")),
+    );
+}
+
+fn render_lines(hunk: &Hunk<'_>) -> Vec<String> {
+    hunk.lines
+        .iter()
+        .map(|(kind, text)| format!("{kind}{text}"))
+        .collect()
+}
+
+/// The start of a hunk range as written in a `@@` header.
+fn range_start(begin: usize, len: usize) -> usize {
+    if len == 0 {
+        begin
+    } else {
+        begin + 1
+    }
+}
+
+fn trim_newline(text: &str) -> &str {
+    text.strip_suffix('\n')
+        .map_or(text, |t| t.strip_suffix('\r').unwrap_or(t))
+}
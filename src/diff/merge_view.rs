@@ -0,0 +1,102 @@
+//! Three-way merge view of a diff that no longer applies.
+//!
+//! When `check_diff` cannot apply an approved diff because the file moved
+//! on, the operator may ask for a merge instead of forcing or rejecting it.
+//! [`merge_view`] renders the file as it is on disk with every region the
+//! diff touches replaced by diff3-style conflict markers: the current
+//! lines, the lines the diff was written against, and the lines it
+//! proposes. The agent resolves the markers and proposes a fresh diff.
+
+use diffy::{Line, Patch};
+
+/// Marker opening the lines currently on disk.
+pub const CURRENT_MARKER: &str = "<<<<<<< current";
+/// Marker opening the lines the diff was written against.
+pub const BASE_MARKER: &str = "||||||| base";
+/// Marker separating the proposed lines.
+pub const PROPOSED_SEPARATOR: &str = "=======";
+/// Marker closing a conflict region.
+pub const PROPOSED_MARKER: &str = ">>>>>>> proposed";
+
+/// Render `current` (the file on disk) merged with `diff` as conflict
+/// regions, one per hunk, at the positions the hunk headers name.
+///
+/// Content that is not a unified diff replaces the whole file, so it
+/// becomes a single region with an empty base. Returns `None` when a
+/// unified diff cannot be parsed.
+#[must_use]
+pub fn merge_view(current: &str, diff: &str) -> Option<String> {
+    let current = current.replace("\r\n", "\n");
+    let diff = diff.replace("\r\n", "\n");
+    let disk: Vec<&str> = current.lines().collect();
+
+    if !(diff.starts_with("--- ") || diff.starts_with("diff ")) {
+        let mut out = String::new();
+        push_region(&mut out, &disk, &[], &diff.lines().collect::<Vec<_>>());
+        return Some(out);
+    }
+
+    let patch = Patch::from_str(&diff).ok()?;
+    let mut out = String::new();
+    let mut at = 0;
+    for hunk in patch.hunks() {
+        let old = hunk.old_range();
+        // A zero-length range names the line the hunk follows.
+        let begin = if old.is_empty() {
+            old.start()
+        } else {
+            old.start() - 1
+        };
+        let begin = begin.clamp(at, disk.len());
+        let end = (begin + old.len()).min(disk.len());
+        push_lines(&mut out, &disk[at..begin]);
+
+        let mut base = Vec::new();
+        let mut proposed = Vec::new();
+        for line in hunk.lines() {
+            match line {
+                Line::Context(text) => {
+                    base.push(trim_newline(text));
+                    proposed.push(trim_newline(text));
+                }
+                Line::Delete(text) => base.push(trim_newline(text)),
+                Line::Insert(text) => proposed.push(trim_newline(text)),
+            }
+        }
+        push_region(&mut out, &disk[begin..end], &base, &proposed);
+        at = end;
+    }
+    push_lines(&mut out, &disk[at..]);
+    Some(out)
+}
+
+/// Number of conflict regions in a rendered merge view.
+#[must_use]
+pub fn region_count(view: &str) -> usize {
+    view.lines().filter(|line| *line == CURRENT_MARKER).count()
+}
+
+fn push_region(out: &mut String, current: &[&str], base: &[&str], proposed: &[&str]) {
+    out.push_str(CURRENT_MARKER);
+    out.push('\n');
+    push_lines(out, current);
+    out.push_str(BASE_MARKER);
+    out.push('\n');
+    push_lines(out, base);
+    out.push_str(PROPOSED_SEPARATOR);
+    out.push('\n');
+    push_lines(out, proposed);
+    out.push_str(PROPOSED_MARKER);
+    out.push('\n');
+}
+
+fn push_lines(out: &mut String, lines: &[&str]) {
+    for line in lines {
+        out.push_str(line);
+        out.push('\n');
+    }
+}
+
+fn trim_newline(text: &str) -> &str {
+    text.strip_suffix('\n').unwrap_or(text)
+}
@@ -1,12 +1,18 @@
-//! Diff utilities, path safety, and file writing.
+//! Diff utilities, path safety, file writing, approval ownership context,
+//! context expansion for displaying approval diffs, review snippets
+//! generated from them, and merge views of diffs that no longer apply.
 
 use std::path::{Path, PathBuf};
 
 use crate::Result;
 
 pub mod applicator;
+pub mod context;
+pub mod merge_view;
+pub mod ownership;
 pub mod patcher;
 pub mod path_safety;
+pub mod snippets;
 pub mod writer;
 
 /// Validate that `candidate` resides within `workspace_root`, returning an absolute normalized path.
@@ -0,0 +1,325 @@
+//! Ownership context for approval requests.
+//!
+//! Gives the operator a quick read on whether they are the right person to
+//! approve a change: who last touched the affected lines (a `git blame`
+//! summary over the diff's hunks) and which CODEOWNERS entries cover the
+//! file. Both are best-effort — a workspace that is not a git repository,
+//! a new file, or a missing CODEOWNERS file simply yields less context.
+//!
+//! Owners mapped to Slack users in [`CodeOwnersConfig`] are also the
+//! routing targets for the approval: they are @-mentioned, and with
+//! `require_owner_approval` set they are the only users who may decide it
+//! (see [`required_approvers`]).
+
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use diffy::Patch;
+use glob::{MatchOptions, Pattern};
+use tracing::debug;
+
+use crate::config::CodeOwnersConfig;
+
+/// Locations searched for a CODEOWNERS file, in GitHub's precedence order.
+pub const CODEOWNERS_LOCATIONS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// Upper bound on the `git blame` call so a slow repository cannot delay
+/// the approval post.
+const BLAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Most authors listed in a blame summary.
+const MAX_BLAME_AUTHORS: usize = 3;
+
+/// A single CODEOWNERS rule: a path pattern and the owners it assigns.
+#[derive(Debug, Clone)]
+struct OwnerRule {
+    pattern: Pattern,
+    /// Set for patterns ending in `/`, which only match directory contents.
+    dir_only: bool,
+    owners: Vec<String>,
+}
+
+/// Parsed CODEOWNERS file.
+///
+/// Matching follows GitHub's rules: the last matching pattern wins, a
+/// pattern without a leading or inner `/` matches at any depth, and a
+/// pattern naming a directory covers everything beneath it.
+#[derive(Debug, Clone, Default)]
+pub struct CodeOwners {
+    rules: Vec<OwnerRule>,
+}
+
+impl CodeOwners {
+    /// Parse CODEOWNERS content. Lines with invalid patterns are skipped.
+    #[must_use]
+    pub fn parse(content: &str) -> Self {
+        let rules = content
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let mut tokens = line.split_whitespace();
+                let raw = tokens.next()?;
+                let owners = tokens
+                    .take_while(|t| !t.starts_with('#'))
+                    .map(str::to_owned)
+                    .collect();
+                let dir_only = raw.ends_with('/');
+                let trimmed = raw.trim_end_matches('/');
+                let anchored = trimmed.starts_with('/') || trimmed.contains('/');
+                let body = trimmed.trim_start_matches('/');
+                let glob = if anchored {
+                    body.to_owned()
+                } else {
+                    format!("**/{body}")
+                };
+                match Pattern::new(&glob) {
+                    Ok(pattern) => Some(OwnerRule {
+                        pattern,
+                        dir_only,
+                        owners,
+                    }),
+                    Err(err) => {
+                        debug!(%err, pattern = raw, "skipping invalid CODEOWNERS pattern");
+                        None
+                    }
+                }
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Load the first CODEOWNERS file found under `workspace_root`.
+    ///
+    /// Returns `None` when the workspace has no CODEOWNERS file.
+    #[must_use]
+    pub fn load(workspace_root: &Path) -> Option<Self> {
+        CODEOWNERS_LOCATIONS.iter().find_map(|rel| {
+            std::fs::read_to_string(workspace_root.join(rel))
+                .ok()
+                .map(|content| Self::parse(&content))
+        })
+    }
+
+    /// Owners of `file_path` (workspace-relative, `/`-separated).
+    ///
+    /// Empty when no rule matches or the matching rule lists no owners.
+    #[must_use]
+    pub fn owners_for(&self, file_path: &str) -> Vec<String> {
+        let path = file_path.replace('\\', "/");
+        let path = path.trim_start_matches("./").trim_start_matches('/');
+        let options = MatchOptions {
+            case_sensitive: true,
+            require_literal_separator: true,
+            require_literal_leading_dot: false,
+        };
+        // Candidate paths: every ancestor directory, then the file itself.
+        let mut candidates: Vec<(&str, bool)> = path
+            .match_indices('/')
+            .map(|(idx, _)| (&path[..idx], true))
+            .collect();
+        candidates.push((path, false));
+
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                candidates.iter().any(|(candidate, is_dir)| {
+                    (*is_dir || !rule.dir_only) && rule.pattern.matches_with(candidate, options)
+                })
+            })
+            .map(|rule| rule.owners.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// One author's share of the blamed lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameAuthor {
+    /// Author name as recorded in the commit.
+    pub name: String,
+    /// Number of blamed lines last touched by this author.
+    pub lines: usize,
+    /// Most recent commit time among those lines.
+    pub last_touched: Option<DateTime<Utc>>,
+}
+
+/// Ownership context attached to an approval message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApprovalContext {
+    /// Authors who last touched the affected lines, most lines first.
+    pub blame: Vec<BlameAuthor>,
+    /// CODEOWNERS entries covering the file.
+    pub owners: Vec<String>,
+    /// Slack user IDs mapped to `owners`.
+    pub approvers: Vec<String>,
+    /// Whether only `approvers` may decide the request.
+    pub approvers_required: bool,
+}
+
+impl ApprovalContext {
+    /// Whether there is nothing to show.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.blame.is_empty() && self.owners.is_empty()
+    }
+
+    /// Gather blame and CODEOWNERS context for `file_path` in `workspace_root`,
+    /// resolving owners to Slack users through `config`.
+    ///
+    /// Never fails: every lookup that cannot be completed is skipped.
+    pub async fn gather(
+        workspace_root: &Path,
+        file_path: &str,
+        diff: &str,
+        config: &CodeOwnersConfig,
+    ) -> Self {
+        let owners = CodeOwners::load(workspace_root)
+            .map(|co| co.owners_for(file_path))
+            .unwrap_or_default();
+        let approvers = config.slack_ids_for(&owners);
+        let approvers_required = config.require_owner_approval && !approvers.is_empty();
+        let blame = blame_summary(workspace_root, file_path, diff).await;
+        Self {
+            blame,
+            owners,
+            approvers,
+            approvers_required,
+        }
+    }
+}
+
+/// Slack users who must decide an approval for `file_path`.
+///
+/// Empty unless `require_owner_approval` is set and at least one CODEOWNERS
+/// entry covering the file is mapped to a Slack user; callers then fall back
+/// to the session-owner check.
+#[must_use]
+pub fn required_approvers(
+    config: &CodeOwnersConfig,
+    workspace_root: &Path,
+    file_path: &str,
+) -> Vec<String> {
+    if !config.require_owner_approval || config.slack_users.is_empty() {
+        return Vec::new();
+    }
+    CodeOwners::load(workspace_root)
+        .map(|co| config.slack_ids_for(&co.owners_for(file_path)))
+        .unwrap_or_default()
+}
+
+/// Line ranges (1-based, inclusive) of the original file touched by `diff`.
+///
+/// Returns `None` when `diff` is not a unified diff with hunks (a
+/// full-file replacement), which callers treat as "the whole file".
+#[must_use]
+pub fn affected_ranges(diff: &str) -> Option<Vec<(usize, usize)>> {
+    let patch = Patch::from_str(diff).ok()?;
+    if patch.hunks().is_empty() {
+        return None;
+    }
+    Some(
+        patch
+            .hunks()
+            .iter()
+            .map(diffy::Hunk::old_range)
+            .filter(|range| !range.is_empty())
+            .map(|range| (range.start(), range.end() - 1))
+            .collect(),
+    )
+}
+
+/// Summarise `git blame` for the lines of `file_path` touched by `diff`.
+///
+/// Returns an empty vector when the workspace is not a git repository, the
+/// file is untracked, or `git` is unavailable.
+pub async fn blame_summary(workspace_root: &Path, file_path: &str, diff: &str) -> Vec<BlameAuthor> {
+    let mut cmd = tokio::process::Command::new("git");
+    cmd.arg("-C")
+        .arg(workspace_root)
+        .args(["blame", "--line-porcelain"]);
+    if let Some(ranges) = affected_ranges(diff) {
+        if ranges.is_empty() {
+            // Pure additions: no existing lines were touched.
+            return Vec::new();
+        }
+        for (start, end) in ranges {
+            cmd.arg("-L").arg(format!("{start},{end}"));
+        }
+    }
+    cmd.arg("--").arg(file_path).kill_on_drop(true);
+
+    let output = match tokio::time::timeout(BLAME_TIMEOUT, cmd.output()).await {
+        Ok(Ok(output)) if output.status.success() => output,
+        Ok(Ok(output)) => {
+            debug!(
+                file_path,
+                stderr = %String::from_utf8_lossy(&output.stderr).trim(),
+                "git blame unavailable"
+            );
+            return Vec::new();
+        }
+        Ok(Err(err)) => {
+            debug!(%err, file_path, "failed to run git blame");
+            return Vec::new();
+        }
+        Err(_elapsed) => {
+            debug!(file_path, "git blame timed out");
+            return Vec::new();
+        }
+    };
+    summarise_porcelain(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Author name git reports for uncommitted working-tree lines.
+const UNCOMMITTED_AUTHOR: &str = "Not Committed Yet";
+
+/// Aggregate `git blame --line-porcelain` output by author.
+///
+/// Uncommitted lines are left out.
+#[must_use]
+pub fn summarise_porcelain(porcelain: &str) -> Vec<BlameAuthor> {
+    let mut authors: Vec<BlameAuthor> = Vec::new();
+    let mut current: Option<usize> = None;
+    for line in porcelain.lines() {
+        if let Some(name) = line.strip_prefix("author ") {
+            if name == UNCOMMITTED_AUTHOR {
+                current = None;
+                continue;
+            }
+            let idx = authors
+                .iter()
+                .position(|a| a.name == name)
+                .unwrap_or_else(|| {
+                    authors.push(BlameAuthor {
+                        name: name.to_owned(),
+                        lines: 0,
+                        last_touched: None,
+                    });
+                    authors.len() - 1
+                });
+            authors[idx].lines += 1;
+            current = Some(idx);
+        } else if let Some(epoch) = line.strip_prefix("author-time ") {
+            let touched = epoch
+                .trim()
+                .parse::<i64>()
+                .ok()
+                .and_then(|secs| DateTime::from_timestamp(secs, 0));
+            if let (Some(idx), Some(touched)) = (current, touched) {
+                let author = &mut authors[idx];
+                author.last_touched = author.last_touched.max(Some(touched));
+            }
+        }
+    }
+    authors.sort_by(|a, b| {
+        b.lines
+            .cmp(&a.lines)
+            .then(b.last_touched.cmp(&a.last_touched))
+    });
+    authors.truncate(MAX_BLAME_AUTHORS);
+    authors
+}
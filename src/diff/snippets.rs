@@ -0,0 +1,226 @@
+//! Review snippets generated from a diff.
+//!
+//! When an agent calls `check_clearance` without `snippets`, the server
+//! builds them itself (`[snippets] fallback = "generate"`), so the review
+//! thread looks the same whoever wrote the request. Each hunk, widened to
+//! its enclosing block by [`context::expand_context`], becomes one snippet
+//! showing the code as it reads after the change, labelled with the
+//! declaration it belongs to and its line range.
+
+use std::borrow::Cow;
+use std::fmt::Write as _;
+
+use diffy::{Line, Patch};
+
+use crate::diff::context;
+
+/// Most characters of code in one generated snippet; longer regions are
+/// cut at a line boundary with a note.
+pub const MAX_SNIPPET_CHARS: usize = 2_400;
+
+/// Longest line shown in a snippet, in characters.
+const MAX_LINE_CHARS: usize = 200;
+
+/// Lines searched above a hunk for the declaration it belongs to.
+const DECLARATION_LOOKBACK: usize = 200;
+
+/// Longest declaration shown in a label, in characters.
+const MAX_LABEL_CHARS: usize = 80;
+
+/// Modifiers skipped before a declaration keyword.
+const MODIFIERS: &[&str] = &[
+    "pub(crate) ",
+    "pub(super) ",
+    "pub ",
+    "export ",
+    "default ",
+    "async ",
+    "unsafe ",
+    "const ",
+    "public ",
+    "private ",
+    "protected ",
+    "internal ",
+    "static ",
+    "abstract ",
+    "final ",
+    "override ",
+    "virtual ",
+];
+
+/// Keywords that open a declaration.
+const KEYWORDS: &[&str] = &[
+    "fn ",
+    "impl ",
+    "impl<",
+    "trait ",
+    "struct ",
+    "enum ",
+    "mod ",
+    "def ",
+    "class ",
+    "interface ",
+    "function ",
+    "function(",
+    "func ",
+    "type ",
+    "resource ",
+    "module ",
+];
+
+/// One generated review snippet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    /// Declaration and line range, e.g. `fn retry(attempts: u32) — lines 12–30`.
+    pub label: String,
+    /// The code of the region.
+    pub content: String,
+}
+
+/// Generate up to `max` snippets for `diff`, one per changed region.
+///
+/// `original` is the file before the change, used to widen hunks and to
+/// find the declaration above a hunk that starts inside one; `language` is
+/// the name `file_extension_language` reports. A `diff` that is not a
+/// unified diff (raw file content) yields a single snippet of the start of
+/// the file. Returns an empty list for an empty diff.
+#[must_use]
+pub fn generate(diff: &str, original: Option<&str>, language: &str, max: usize) -> Vec<Snippet> {
+    if diff.trim().is_empty() || max == 0 {
+        return Vec::new();
+    }
+    let widened = original.and_then(|original| context::expand_context(diff, original, language));
+    let text = widened.as_deref().unwrap_or(diff);
+    let patch = match Patch::from_str(text) {
+        Ok(patch) if !patch.hunks().is_empty() => patch,
+        _ => {
+            let lines: Vec<&str> = diff.lines().collect();
+            return vec![Snippet {
+                label: format!("Whole file \u{2014} {}", line_range(1, lines.len())),
+                content: cut(&lines),
+            }];
+        }
+    };
+    let source: Vec<&str> = original.map(|o| o.lines().collect()).unwrap_or_default();
+
+    patch
+        .hunks()
+        .iter()
+        .take(max)
+        .map(|hunk| {
+            let mut after = Vec::new();
+            let mut before = Vec::new();
+            for line in hunk.lines() {
+                match line {
+                    Line::Context(text) => {
+                        after.push(trim_newline(text));
+                        before.push(trim_newline(text));
+                    }
+                    Line::Insert(text) => after.push(trim_newline(text)),
+                    Line::Delete(text) => before.push(trim_newline(text)),
+                }
+            }
+            let (mut lines, mut start, removed) = if after.is_empty() {
+                (before, hunk.old_range().start(), true)
+            } else {
+                (after, hunk.new_range().start(), false)
+            };
+            // Blank context at either end says nothing about the change.
+            let leading = lines.iter().take_while(|l| l.trim().is_empty()).count();
+            if leading < lines.len() {
+                lines.drain(..leading);
+                start += leading;
+                while lines.last().is_some_and(|l| l.trim().is_empty()) {
+                    lines.pop();
+                }
+            }
+            let declaration = lines.iter().find_map(|line| declaration(line)).or_else(|| {
+                let above = hunk.old_range().start().saturating_sub(1).min(source.len());
+                source[above.saturating_sub(DECLARATION_LOOKBACK)..above]
+                    .iter()
+                    .rev()
+                    .find_map(|line| declaration(line))
+            });
+            let range = line_range(start.max(1), lines.len());
+            let range = if removed {
+                format!("{range} (removed)")
+            } else {
+                range
+            };
+            Snippet {
+                label: declaration.map_or_else(
+                    || capitalize(&range),
+                    |declaration| format!("{declaration} \u{2014} {range}"),
+                ),
+                content: cut(&lines),
+            }
+        })
+        .collect()
+}
+
+/// The declaration `line` opens, trimmed for a label, or `None` when it
+/// opens none.
+fn declaration(line: &str) -> Option<String> {
+    let trimmed = line.trim();
+    let mut rest = trimmed;
+    while let Some(stripped) = MODIFIERS.iter().find_map(|m| rest.strip_prefix(m)) {
+        rest = stripped;
+    }
+    if !KEYWORDS.iter().any(|keyword| rest.starts_with(keyword)) {
+        return None;
+    }
+    let label = trimmed.trim_end_matches(['{', ':', ' ']);
+    Some(if label.chars().count() > MAX_LABEL_CHARS {
+        let short: String = label.chars().take(MAX_LABEL_CHARS).collect();
+        format!("{short}\u{2026}")
+    } else {
+        label.to_owned()
+    })
+}
+
+/// `lines N–M` for `count` lines from `start`.
+fn line_range(start: usize, count: usize) -> String {
+    if count <= 1 {
+        format!("line {start}")
+    } else {
+        format!("lines {start}\u{2013}{}", start + count - 1)
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+/// `lines` joined, cut at a line boundary after [`MAX_SNIPPET_CHARS`].
+fn cut(lines: &[&str]) -> String {
+    let mut content = String::new();
+    for (shown, line) in lines.iter().enumerate() {
+        let line = shorten(line);
+        if !content.is_empty() && content.len() + line.len() + 1 > MAX_SNIPPET_CHARS {
+            let more = lines.len() - shown;
+            let noun = if more == 1 { "line" } else { "lines" };
+            let _ = write!(content, "\u{2026} ({more} more {noun})");
+            return content;
+        }
+        content.push_str(&line);
+        content.push('\n');
+    }
+    content.truncate(content.trim_end_matches('\n').len());
+    content
+}
+
+/// `line`, cut after [`MAX_LINE_CHARS`] characters.
+fn shorten(line: &str) -> Cow<'_, str> {
+    match line.char_indices().nth(MAX_LINE_CHARS) {
+        Some((end, _)) => Cow::Owned(format!("{}\u{2026}", &line[..end])),
+        None => Cow::Borrowed(line),
+    }
+}
+
+fn trim_newline(text: &str) -> &str {
+    text.strip_suffix('\n')
+        .map_or(text, |t| t.strip_suffix('\r').unwrap_or(t))
+}
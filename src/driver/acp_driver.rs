@@ -14,6 +14,15 @@
 //! 3. Agent emits `prompt/forward` → event consumer calls
 //!    [`AcpDriver::register_prompt_request`].
 //! 4. Agent disconnects → [`AcpDriver::deregister_session`] removes the writer.
+//!
+//! # Backpressure
+//!
+//! Writer channels are bounded ([`WriterLimits::queue_capacity`]). Every send
+//! goes through [`writer::enqueue`], so a stuck agent that stops reading its
+//! stdin surfaces as an [`AppError::Acp`] after at most
+//! [`WriterLimits::write_timeout`] instead of blocking the Slack handler that
+//! is resolving a clearance. Per-session queue counters are available via
+//! [`AcpDriver::writer_metrics`].
 
 use std::collections::HashMap;
 use std::future::Future;
@@ -22,9 +31,10 @@ use std::sync::{atomic::AtomicU64, Arc};
 
 use serde_json::{json, Value};
 use tokio::sync::{mpsc, Mutex};
-use tracing::{debug, warn};
+use tracing::debug;
 use uuid::Uuid;
 
+use crate::acp::writer::{self, WriterLimits, WriterMetrics, WriterMetricsSnapshot};
 use crate::driver::{AgentDriver, PermissionOption};
 use crate::{AppError, Result};
 
@@ -54,8 +64,15 @@ struct PendingPermission {
     raw_id: Value,
 }
 
+/// Outbound writer channel and queue counters for one session.
+#[derive(Debug, Clone)]
+struct SessionWriter {
+    tx: mpsc::Sender<Value>,
+    metrics: Arc<WriterMetrics>,
+}
+
 /// Shared map type alias for session writer channels.
-type WriterMap = Arc<Mutex<HashMap<String, mpsc::Sender<Value>>>>;
+type WriterMap = Arc<Mutex<HashMap<String, SessionWriter>>>;
 
 /// Shared map type alias for agent-assigned session IDs.
 type AgentSessionIdMap = Arc<Mutex<HashMap<String, String>>>;
@@ -69,8 +86,8 @@ type SeqCounterMap = Arc<Mutex<HashMap<String, Arc<AtomicU64>>>>;
 ///
 /// Maintains five shared maps protected by async mutexes:
 ///
-/// - `stream_writers`: `session_id` → [`mpsc::Sender<Value>`] registered when
-///   an ACP session connects.
+/// - `stream_writers`: `session_id` → [`mpsc::Sender<Value>`] (plus its
+///   [`WriterMetrics`]) registered when an ACP session connects.
 /// - `agent_session_ids`: intercom `session_id` → ACP `agent_session_id`
 ///   (from the `session/new` handshake result).
 /// - `seq_counters`: `session_id` → [`Arc<AtomicU64>`] shared with the
@@ -96,12 +113,20 @@ pub struct AcpDriver {
     pending_prompts_acp: Arc<Mutex<HashMap<String, PendingPromptAcp>>>,
     /// Pending standard permission requests: `request_id` → session + options.
     pending_permissions: Arc<Mutex<HashMap<String, PendingPermission>>>,
+    /// Outbound queue capacity, overflow policy, and write timeout.
+    limits: WriterLimits,
 }
 
 impl AcpDriver {
-    /// Create a new `AcpDriver` with empty maps.
+    /// Create a new `AcpDriver` with empty maps and default writer limits.
     #[must_use]
     pub fn new() -> Self {
+        Self::with_limits(WriterLimits::default())
+    }
+
+    /// Create a new `AcpDriver` applying `limits` to every session's writer.
+    #[must_use]
+    pub fn with_limits(limits: WriterLimits) -> Self {
         Self {
             stream_writers: Arc::new(Mutex::new(HashMap::new())),
             agent_session_ids: Arc::new(Mutex::new(HashMap::new())),
@@ -109,17 +134,29 @@ impl AcpDriver {
             pending_clearances: Arc::new(Mutex::new(HashMap::new())),
             pending_prompts_acp: Arc::new(Mutex::new(HashMap::new())),
             pending_permissions: Arc::new(Mutex::new(HashMap::new())),
+            limits,
         }
     }
 
+    /// Writer limits applied to every registered session.
+    ///
+    /// Callers creating a session's outbound channel should size it with
+    /// [`WriterLimits::queue_capacity`] and pass
+    /// [`WriterLimits::write_timeout`] to [`writer::run_writer`].
+    #[must_use]
+    pub fn limits(&self) -> WriterLimits {
+        self.limits
+    }
+
     /// Register a new session's outbound writer channel.
     ///
     /// Creates a fresh [`AtomicU64`] sequence counter for the session, stores
     /// it in the driver's counter map, and returns it so the caller can pass
-    /// it directly to [`run_writer`].
+    /// it directly to [`run_writer`]. A fresh [`WriterMetrics`] is also
+    /// created; fetch it with [`AcpDriver::writer_metrics`].
     ///
     /// If a session with the same `session_id` is already registered, the old
-    /// sender and counter are replaced with the new ones.
+    /// sender, counter, and metrics are replaced with the new ones.
     pub async fn register_session(
         &self,
         session_id: &str,
@@ -127,10 +164,13 @@ impl AcpDriver {
     ) -> Arc<AtomicU64> {
         let counter = Arc::new(AtomicU64::new(0));
         {
-            self.stream_writers
-                .lock()
-                .await
-                .insert(session_id.to_owned(), tx);
+            self.stream_writers.lock().await.insert(
+                session_id.to_owned(),
+                SessionWriter {
+                    tx,
+                    metrics: Arc::new(WriterMetrics::default()),
+                },
+            );
         }
         {
             self.seq_counters
@@ -142,6 +182,28 @@ impl AcpDriver {
         counter
     }
 
+    /// Outbound queue counters for a registered session.
+    ///
+    /// Returns `None` when the session has no registered writer.
+    pub async fn writer_metrics(&self, session_id: &str) -> Option<Arc<WriterMetrics>> {
+        self.stream_writers
+            .lock()
+            .await
+            .get(session_id)
+            .map(|w| Arc::clone(&w.metrics))
+    }
+
+    /// Snapshot the outbound queue counters of every registered session.
+    pub async fn writer_metrics_snapshot(&self) -> Vec<(String, WriterMetricsSnapshot)> {
+        let writers = self.stream_writers.lock().await;
+        let mut snapshots: Vec<(String, WriterMetricsSnapshot)> = writers
+            .iter()
+            .map(|(id, w)| (id.clone(), w.metrics.snapshot()))
+            .collect();
+        snapshots.sort_by(|a, b| a.0.cmp(&b.0));
+        snapshots
+    }
+
     /// Register the ACP agent-assigned session ID for an intercom session.
     ///
     /// Must be called after `session/new` handshake completes. The agent
@@ -287,7 +349,7 @@ impl AgentDriver for AcpDriver {
                         "reason": reason,
                     }
                 });
-                return send_to_session(&self.stream_writers, &session_id, msg).await;
+                return send_to_session(&self.stream_writers, &self.limits, &session_id, msg).await;
             }
 
             // Standard ACP permission path (ADR-0016): reply with a JSON-RPC
@@ -311,7 +373,7 @@ impl AgentDriver for AcpDriver {
                     "id": raw_id,
                     "result": { "outcome": outcome },
                 });
-                return send_to_session(&self.stream_writers, &session_id, msg).await;
+                return send_to_session(&self.stream_writers, &self.limits, &session_id, msg).await;
             }
 
             Err(AppError::NotFound(format!(
@@ -367,7 +429,7 @@ impl AgentDriver for AcpDriver {
                     ]
                 }
             });
-            send_to_session(&self.stream_writers, &session_id, msg).await
+            send_to_session(&self.stream_writers, &self.limits, &session_id, msg).await
         })
     }
 
@@ -380,11 +442,11 @@ impl AgentDriver for AcpDriver {
         let session_id = session_id.to_owned();
         Box::pin(async move {
             // Clone the sender and drop the lock before awaiting (F-04).
-            let tx = {
+            let writer = {
                 let writers = self.stream_writers.lock().await;
                 writers.get(&session_id).cloned()
             };
-            let Some(tx) = tx else {
+            let Some(writer) = writer else {
                 // Session already gone — idempotent, return Ok.
                 debug!(
                     session_id,
@@ -398,15 +460,7 @@ impl AgentDriver for AcpDriver {
                 "params": { "reason": "Operator requested termination" }
             });
 
-            tx.send(msg).await.map_err(|_| {
-                warn!(
-                    session_id,
-                    "acp driver: interrupt send failed — stream closed"
-                );
-                AppError::Acp(format!(
-                    "write failed: stream closed for session '{session_id}'"
-                ))
-            })
+            writer::enqueue(&writer.tx, msg, &self.limits, &writer.metrics, &session_id).await
         })
     }
 
@@ -449,7 +503,7 @@ impl AgentDriver for AcpDriver {
                 }
             });
 
-            send_to_session(&self.stream_writers, &session_id, msg).await
+            send_to_session(&self.stream_writers, &self.limits, &session_id, msg).await
         })
     }
 
@@ -500,7 +554,30 @@ impl AgentDriver for AcpDriver {
                     ]
                 }
             });
-            send_to_session(&self.stream_writers, &session_id, msg).await
+            send_to_session(&self.stream_writers, &self.limits, &session_id, msg).await
+        })
+    }
+
+    /// Write a JSON-RPC notification (no `id`) to the agent's ACP stream.
+    ///
+    /// # Errors
+    ///
+    /// - [`AppError::NotFound`] if `session_id` has no registered writer.
+    /// - [`AppError::Acp`] if the writer channel is closed.
+    fn notify(
+        &self,
+        session_id: &str,
+        method: &str,
+        params: Value,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+        let session_id = session_id.to_owned();
+        let msg = json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+        });
+        Box::pin(async move {
+            send_to_session(&self.stream_writers, &self.limits, &session_id, msg).await
         })
     }
 }
@@ -537,25 +614,28 @@ fn select_option_id(options: &[PermissionOption], approved: bool) -> Option<Stri
 /// Look up the writer for `session_id` and send `msg` through it.
 ///
 /// Clones the sender and drops the lock before awaiting the send to avoid
-/// holding the mutex across an `.await` point (F-04).
+/// holding the mutex across an `.await` point (F-04). The send itself goes
+/// through [`writer::enqueue`], so a saturated queue fails within
+/// `limits.write_timeout` instead of blocking the caller.
 ///
 /// Returns [`AppError::NotFound`] if no writer is registered for the session,
-/// or [`AppError::Acp`] if the channel is closed (agent disconnected).
-async fn send_to_session(writers: &WriterMap, session_id: &str, msg: Value) -> Result<()> {
-    let tx = {
+/// or [`AppError::Acp`] if the channel is closed (agent disconnected) or the
+/// queue is saturated.
+async fn send_to_session(
+    writers: &WriterMap,
+    limits: &WriterLimits,
+    session_id: &str,
+    msg: Value,
+) -> Result<()> {
+    let writer = {
         let guard = writers.lock().await;
         guard.get(session_id).cloned()
     };
-    let Some(tx) = tx else {
+    let Some(writer) = writer else {
         return Err(AppError::NotFound(format!(
             "no ACP writer registered for session '{session_id}'"
         )));
     };
 
-    tx.send(msg).await.map_err(|_| {
-        warn!(session_id, "acp driver: send failed — stream closed");
-        AppError::Acp(format!(
-            "write failed: stream closed for session '{session_id}'"
-        ))
-    })
+    writer::enqueue(&writer.tx, msg, limits, &writer.metrics, session_id).await
 }
@@ -0,0 +1,143 @@
+//! Broadcast bus carrying [`AgentEvent`]s from every driver.
+//!
+//! ACP sessions emit events from their stream readers; MCP sessions emit
+//! them from tool handlers (`heartbeat`, `ask_approval`, `forward_prompt`,
+//! and every tool notice meant for Slack) and on transport disconnect. Both sides publish onto one [`EventBus`]
+//! so cross-cutting consumers — audit logging, metrics, Slack status
+//! posting, and later dashboard streams — subscribe once instead of being
+//! called directly from each producer.
+//!
+//! The bus is lossy by design: a subscriber that falls more than the
+//! channel capacity behind receives [`broadcast::error::RecvError::Lagged`]
+//! and skips ahead. Flows that must not drop events (clearance routing in
+//! ACP mode) keep their dedicated `mpsc` channel and publish here as well.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::driver::AgentEvent;
+use crate::models::session::ProtocolMode;
+
+/// Default number of events buffered per subscriber before it lags.
+pub const DEFAULT_BUS_CAPACITY: usize = 1024;
+
+/// An [`AgentEvent`] tagged with its origin.
+#[derive(Debug, Clone)]
+pub struct BusEvent {
+    /// Protocol of the driver that produced the event.
+    pub source: ProtocolMode,
+    /// Slack channel the producer would have posted to, when known.
+    ///
+    /// MCP connections resolve their channel from the transport
+    /// (`?channel_id=` or the global default) rather than the session
+    /// record, so producers pass it along for Slack-facing subscribers.
+    pub channel_id: Option<String>,
+    /// The event payload.
+    pub event: AgentEvent,
+}
+
+/// Per-kind event counters maintained by the metrics subscriber.
+#[derive(Debug, Default)]
+pub struct EventMetrics {
+    by_kind: Mutex<BTreeMap<&'static str, u64>>,
+    published: AtomicU64,
+    lagged: AtomicU64,
+}
+
+/// Point-in-time copy of [`EventMetrics`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct EventMetricsSnapshot {
+    /// Events seen by the metrics subscriber, keyed by [`AgentEvent::kind`].
+    pub by_kind: BTreeMap<&'static str, u64>,
+    /// Events published on the bus.
+    pub published: u64,
+    /// Events skipped by lagging subscribers.
+    pub lagged: u64,
+}
+
+impl EventMetrics {
+    /// Count one observed event of `kind`.
+    pub fn record(&self, kind: &'static str) {
+        let mut map = self.by_kind.lock().unwrap_or_else(PoisonError::into_inner);
+        *map.entry(kind).or_default() += 1;
+    }
+
+    /// Count `skipped` events dropped by a lagging subscriber.
+    pub fn record_lagged(&self, skipped: u64) {
+        self.lagged.fetch_add(skipped, Ordering::Relaxed);
+    }
+
+    /// Capture the current counter values.
+    #[must_use]
+    pub fn snapshot(&self) -> EventMetricsSnapshot {
+        EventMetricsSnapshot {
+            by_kind: self
+                .by_kind
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone(),
+            published: self.published.load(Ordering::Relaxed),
+            lagged: self.lagged.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Fan-out channel for [`BusEvent`]s.
+pub struct EventBus {
+    tx: broadcast::Sender<BusEvent>,
+    metrics: Arc<EventMetrics>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_BUS_CAPACITY)
+    }
+}
+
+impl EventBus {
+    /// Create a bus buffering up to `capacity` events per subscriber.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity.max(1));
+        Self {
+            tx,
+            metrics: Arc::default(),
+        }
+    }
+
+    /// Publish `event` to all current subscribers.
+    ///
+    /// Publishing with no subscribers is not an error; the event is simply
+    /// dropped.
+    pub fn publish(&self, source: ProtocolMode, channel_id: Option<String>, event: AgentEvent) {
+        self.metrics.published.fetch_add(1, Ordering::Relaxed);
+        // `send` only fails when there are no receivers.
+        let _ = self.tx.send(BusEvent {
+            source,
+            channel_id,
+            event,
+        });
+    }
+
+    /// Register a new subscriber. It receives events published after this call.
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<BusEvent> {
+        self.tx.subscribe()
+    }
+
+    /// Number of live subscribers.
+    #[must_use]
+    pub fn subscriber_count(&self) -> usize {
+        self.tx.receiver_count()
+    }
+
+    /// Shared counters updated by the metrics subscriber.
+    #[must_use]
+    pub fn metrics(&self) -> Arc<EventMetrics> {
+        Arc::clone(&self.metrics)
+    }
+}
@@ -5,12 +5,21 @@
 //! All maps are `Arc<tokio::sync::Mutex<...>>` clones of the maps held by
 //! [`AppState`](crate::state::AppState) so every Slack handler and
 //! every MCP tool handler share the same in-memory state.
+//!
+//! Drivers bound to a live connection also carry the client's transport
+//! [`Peer`], through which server-initiated notifications are pushed:
+//! [`NUDGE_METHOD`] for prompts and nudges, [`STEERING_METHOD`] for
+//! steering messages, [`INTERRUPT_METHOD`] for cancellation requests, and
+//! session hook events. Over Streamable HTTP these
+//! arrive on the client's SSE stream without waiting for a tool call.
 
 use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 
+use rmcp::model::{CustomNotification, ServerNotification};
+use rmcp::service::{Peer, RoleServer};
 use tokio::sync::Mutex;
 use tracing::warn;
 
@@ -21,6 +30,15 @@ use crate::state::{
 use crate::AppError;
 use crate::Result;
 
+/// Notification carrying an operator nudge or prompt to an MCP client.
+pub const NUDGE_METHOD: &str = "intercom/nudge";
+
+/// Notification carrying a steering message to an MCP client.
+pub const STEERING_METHOD: &str = "intercom/steering";
+
+/// Notification asking an MCP client to cancel its current turn.
+pub const INTERRUPT_METHOD: &str = "intercom/interrupt";
+
 /// MCP-protocol implementation of [`AgentDriver`].
 ///
 /// Resolves pending agent requests by delivering responses through the
@@ -37,6 +55,9 @@ pub struct McpDriver {
     pending_prompts: PendingPrompts,
     /// Pending wait-for-instruction `oneshot` senders keyed by `session_id`.
     pending_waits: PendingWaits,
+    /// Transport peer of the bound session, used for server-initiated
+    /// notifications. `None` for drivers not tied to a connection.
+    peer: Option<Peer<RoleServer>>,
 }
 
 impl McpDriver {
@@ -51,9 +72,18 @@ impl McpDriver {
             pending_approvals: approvals,
             pending_prompts: prompts,
             pending_waits: waits,
+            peer: None,
         }
     }
 
+    /// Attach the session's transport peer so [`AgentDriver::notify`] can
+    /// reach the connected client.
+    #[must_use]
+    pub fn with_peer(mut self, peer: Peer<RoleServer>) -> Self {
+        self.peer = Some(peer);
+        self
+    }
+
     /// Create an `McpDriver` backed by empty maps, wrapped in `Arc<dyn AgentDriver>`.
     ///
     /// Intended for use in tests that do not need pre-seeded pending channels.
@@ -62,7 +92,7 @@ impl McpDriver {
     ///
     /// ```rust,ignore
     /// let driver = McpDriver::new_empty();
-    /// assert!(driver.interrupt("any-session").await.is_ok());
+    /// assert!(driver.interrupt("any-session").await.is_err());
     /// ```
     #[must_use]
     pub fn new_empty() -> Arc<dyn AgentDriver> {
@@ -70,6 +100,7 @@ impl McpDriver {
             pending_approvals: Arc::new(Mutex::new(HashMap::new())),
             pending_prompts: Arc::new(Mutex::new(HashMap::new())),
             pending_waits: Arc::new(Mutex::new(HashMap::new())),
+            peer: None,
         })
     }
 }
@@ -100,6 +131,8 @@ impl AgentDriver for McpDriver {
                     "no pending clearance for request_id '{request_id}'"
                 )));
             };
+            #[cfg(feature = "chaos")]
+            crate::chaos::faults().delay_resolution().await;
 
             let response = ApprovalResponse {
                 status: if approved {
@@ -118,27 +151,38 @@ impl AgentDriver for McpDriver {
         })
     }
 
-    /// Send a prompt or nudge to the agent.
+    /// Send a prompt or nudge to the agent as an [`NUDGE_METHOD`] notification.
     ///
-    /// In the MCP implementation this is a stub — ACP stream wiring is added
-    /// in a later phase. Always returns `Ok(())`.
+    /// # Errors
+    ///
+    /// - [`AppError::NotFound`] if the driver is not bound to a connection.
+    /// - [`AppError::Mcp`] if the transport rejects the notification.
     fn send_prompt(
         &self,
-        _session_id: &str,
-        _prompt: &str,
+        session_id: &str,
+        prompt: &str,
     ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
-        Box::pin(async move { Ok(()) })
+        let params = serde_json::json!({
+            "session_id": session_id,
+            "message": prompt,
+        });
+        self.notify(session_id, NUDGE_METHOD, params)
     }
 
-    /// Interrupt / cancel the agent's current work.
+    /// Ask the agent to cancel its current turn with an [`INTERRUPT_METHOD`]
+    /// notification.
     ///
-    /// This operation is idempotent — calling on an unknown or already-terminated
-    /// session always returns `Ok(())`.
-    fn interrupt(
-        &self,
-        _session_id: &str,
-    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
-        Box::pin(async move { Ok(()) })
+    /// # Errors
+    ///
+    /// - [`AppError::NotFound`] if the driver is not bound to a connection,
+    ///   so callers can fall back to queueing.
+    /// - [`AppError::Mcp`] if the transport rejects the notification.
+    fn interrupt(&self, session_id: &str) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+        let params = serde_json::json!({
+            "session_id": session_id,
+            "reason": "operator requested interrupt",
+        });
+        self.notify(session_id, INTERRUPT_METHOD, params)
     }
 
     /// Resolve a pending continuation prompt by delivering a `PromptResponse`.
@@ -167,6 +211,8 @@ impl AgentDriver for McpDriver {
                     "no pending prompt for prompt_id '{prompt_id}'"
                 )));
             };
+            #[cfg(feature = "chaos")]
+            crate::chaos::faults().delay_resolution().await;
 
             let response = PromptResponse {
                 decision,
@@ -205,6 +251,8 @@ impl AgentDriver for McpDriver {
                     "no pending wait for session_id '{session_id}'"
                 )));
             };
+            #[cfg(feature = "chaos")]
+            crate::chaos::faults().delay_resolution().await;
 
             let response = WaitResponse {
                 status: "resumed".to_owned(),
@@ -218,6 +266,33 @@ impl AgentDriver for McpDriver {
             Ok(())
         })
     }
+
+    /// Send a custom MCP notification to the bound client.
+    ///
+    /// # Errors
+    ///
+    /// - [`AppError::NotFound`] if the driver is not bound to a connection.
+    /// - [`AppError::Mcp`] if the transport rejects the notification.
+    fn notify(
+        &self,
+        session_id: &str,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+        let session_id = session_id.to_owned();
+        let notification =
+            ServerNotification::CustomNotification(CustomNotification::new(method, Some(params)));
+        Box::pin(async move {
+            let Some(ref peer) = self.peer else {
+                return Err(AppError::NotFound(format!(
+                    "no connected MCP client for session '{session_id}'"
+                )));
+            };
+            peer.send_notification(notification)
+                .await
+                .map_err(|err| AppError::Mcp(format!("failed to send notification: {err}")))
+        })
+    }
 }
 
 // Verify Send + Sync at compile time.
@@ -6,7 +6,10 @@
 //! route through this trait.
 
 pub mod acp_driver;
+pub mod event_bus;
 pub mod mcp_driver;
+pub mod registry;
+pub mod session_hooks;
 
 use std::future::Future;
 use std::pin::Pin;
@@ -14,6 +17,7 @@ use std::pin::Pin;
 use serde::Deserialize;
 
 use crate::models::progress::ProgressItem;
+use crate::slack::client::SlackMessage;
 use crate::Result;
 
 /// A single operator-selectable option offered by a standard ACP
@@ -32,7 +36,27 @@ pub struct PermissionOption {
     pub kind: String,
 }
 
+/// A curated code excerpt attached to an approval request for review.
+///
+/// Supplied by `ask_approval` callers and posted as a threaded Slack reply
+/// using inline code blocks, which Slack always renders as readable text.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CodeSnippet {
+    /// Short human-readable label (e.g. `"handle() — main entry point"`).
+    pub label: String,
+    /// Markdown code-fence language hint (e.g. `"rust"`). May be empty, in
+    /// which case the code block is untagged.
+    #[serde(default)]
+    pub language: String,
+    /// The code content to display.
+    pub content: String,
+}
+
 /// Events emitted by driver implementations into the shared event channel.
+///
+/// Every event is also published on the [`event_bus::EventBus`] so that
+/// protocol-independent subscribers (audit, metrics, Slack status posting)
+/// observe MCP and ACP sessions uniformly.
 #[derive(Debug, Clone)]
 pub enum AgentEvent {
     /// Agent requests operator approval for a file operation.
@@ -51,6 +75,9 @@ pub enum AgentEvent {
         file_path: String,
         /// Risk classification (`low`, `high`, `critical`).
         risk_level: String,
+        /// Curated code excerpts for review. Empty when the agent supplied
+        /// none; ACP `clearance/request` never carries any.
+        snippets: Vec<CodeSnippet>,
     },
     /// Agent issued a standard ACP `session/request_permission` (ADR-0016).
     ///
@@ -120,6 +147,56 @@ pub enum AgentEvent {
         /// Session that produced stream activity.
         session_id: String,
     },
+    /// Agent registered its session hook subscriptions (`intercom/subscribe`).
+    HooksSubscribed {
+        /// Session that subscribed.
+        session_id: String,
+        /// Hook kinds the agent wants delivered.
+        events: Vec<session_hooks::SessionHookKind>,
+    },
+    /// A tool call produced a Slack notice for the operator, such as a
+    /// relay message, path lock change or applied diff.
+    NoticePosted {
+        /// Session whose tool call produced the notice.
+        session_id: String,
+        /// The message to post, with its channel and thread.
+        message: SlackMessage,
+    },
+}
+
+impl AgentEvent {
+    /// Session this event belongs to.
+    #[must_use]
+    pub fn session_id(&self) -> &str {
+        match self {
+            Self::ClearanceRequested { session_id, .. }
+            | Self::PermissionRequested { session_id, .. }
+            | Self::StatusUpdated { session_id, .. }
+            | Self::PromptForwarded { session_id, .. }
+            | Self::HeartbeatReceived { session_id, .. }
+            | Self::SessionTerminated { session_id, .. }
+            | Self::StreamActivity { session_id }
+            | Self::HooksSubscribed { session_id, .. }
+            | Self::NoticePosted { session_id, .. } => session_id,
+        }
+    }
+
+    /// Stable `snake_case` name of the event variant, used for metrics keys
+    /// and audit summaries.
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::ClearanceRequested { .. } => "clearance_requested",
+            Self::PermissionRequested { .. } => "permission_requested",
+            Self::StatusUpdated { .. } => "status_updated",
+            Self::PromptForwarded { .. } => "prompt_forwarded",
+            Self::HeartbeatReceived { .. } => "heartbeat_received",
+            Self::SessionTerminated { .. } => "session_terminated",
+            Self::StreamActivity { .. } => "stream_activity",
+            Self::HooksSubscribed { .. } => "hooks_subscribed",
+            Self::NoticePosted { .. } => "notice_posted",
+        }
+    }
 }
 
 /// Protocol-agnostic interface between the application core and an agent.
@@ -160,15 +237,16 @@ pub trait AgentDriver: Send + Sync {
 
     /// Interrupt/cancel the agent's current work.
     ///
-    /// In MCP: Sends a cancellation signal via the MCP transport.
+    /// In MCP: Sends an `intercom/interrupt` notification to the connected client.
     /// In ACP: Writes a `session/interrupt` message to the agent stream.
     ///
-    /// This operation is idempotent — calling on an already-terminated session
+    /// In ACP this is idempotent — calling on an already-terminated session
     /// returns `Ok(())`.
     ///
     /// # Errors
     ///
-    /// Returns [`AppError::Acp`](crate::AppError::Acp) if the stream write fails for active sessions.
+    /// Returns [`AppError::Acp`](crate::AppError::Acp) if the stream write fails for active sessions,
+    /// or [`AppError::NotFound`](crate::AppError::NotFound) if no MCP client is connected.
     fn interrupt(&self, session_id: &str) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>>;
 
     /// Resolve a pending continuation prompt.
@@ -201,4 +279,25 @@ pub trait AgentDriver: Send + Sync {
         session_id: &str,
         instruction: Option<String>,
     ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>>;
+
+    /// Push a one-way notification to the agent, outside any pending request.
+    ///
+    /// Used for [session hooks](session_hooks). In MCP the notification is
+    /// sent over the session's transport peer; in ACP it is written to the
+    /// agent stream. The default implementation drops the notification, for
+    /// drivers with no channel to push through.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Mcp`](crate::AppError::Mcp) or
+    /// [`AppError::Acp`](crate::AppError::Acp) if delivery fails.
+    fn notify(
+        &self,
+        session_id: &str,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+        let _ = (session_id, method, params);
+        Box::pin(async { Ok(()) })
+    }
 }
@@ -0,0 +1,86 @@
+//! Per-session driver registry for mixed MCP/ACP deployments.
+//!
+//! A single server can host sessions speaking different protocols: ACP
+//! sessions spawned from Slack, and MCP sessions connected over stdio or
+//! Streamable HTTP. Operator actions (Slack buttons, modals, IPC commands)
+//! must reach the driver that owns the session, not whichever driver was
+//! chosen for the server mode at startup.
+//!
+//! [`DriverRegistry`] records that binding explicitly. Sessions register
+//! their driver when they come online and deregister on termination;
+//! lookups for unknown sessions fall back to the server's default driver
+//! via [`AppState::driver_for`](crate::state::AppState::driver_for).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::sync::RwLock;
+
+use crate::driver::AgentDriver;
+
+/// Maps `session_id` → the [`AgentDriver`] responsible for that session.
+#[derive(Default)]
+pub struct DriverRegistry {
+    drivers: RwLock<HashMap<String, Arc<dyn AgentDriver>>>,
+}
+
+impl DriverRegistry {
+    /// Create an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind `session_id` to `driver`, replacing any previous binding.
+    ///
+    /// Returns the previously registered driver, if any.
+    pub async fn register(
+        &self,
+        session_id: &str,
+        driver: Arc<dyn AgentDriver>,
+    ) -> Option<Arc<dyn AgentDriver>> {
+        self.drivers
+            .write()
+            .await
+            .insert(session_id.to_owned(), driver)
+    }
+
+    /// Remove the binding for `session_id`.
+    ///
+    /// Returns the removed driver, or `None` if the session was not registered.
+    pub async fn deregister(&self, session_id: &str) -> Option<Arc<dyn AgentDriver>> {
+        self.drivers.write().await.remove(session_id)
+    }
+
+    /// Look up the driver bound to `session_id`.
+    pub async fn get(&self, session_id: &str) -> Option<Arc<dyn AgentDriver>> {
+        self.drivers.read().await.get(session_id).cloned()
+    }
+
+    /// Look up the driver bound to `session_id`, or clone `fallback` when the
+    /// session has no explicit binding.
+    pub async fn resolve(
+        &self,
+        session_id: &str,
+        fallback: &Arc<dyn AgentDriver>,
+    ) -> Arc<dyn AgentDriver> {
+        self.get(session_id)
+            .await
+            .unwrap_or_else(|| Arc::clone(fallback))
+    }
+
+    /// Whether `session_id` has an explicit binding.
+    pub async fn contains(&self, session_id: &str) -> bool {
+        self.drivers.read().await.contains_key(session_id)
+    }
+
+    /// Number of sessions with an explicit binding.
+    pub async fn len(&self) -> usize {
+        self.drivers.read().await.len()
+    }
+
+    /// Whether no sessions are registered.
+    pub async fn is_empty(&self) -> bool {
+        self.drivers.read().await.is_empty()
+    }
+}
@@ -0,0 +1,176 @@
+//! Session-level event hooks delivered to agents mid-run.
+//!
+//! Agents normally learn about operator-side changes — a queued steering
+//! message, an operational mode switch, a new checkpoint — only when they
+//! next call a blocking tool such as `ping`. Session hooks let an agent opt
+//! in to being told as soon as the change happens:
+//!
+//! 1. The agent sends an `intercom/subscribe` notification (MCP) or message
+//!    (ACP) listing the [`SessionHookKind`]s it cares about. Omitting
+//!    `events` subscribes to every kind.
+//! 2. When a matching change occurs, the server sends an `intercom/event`
+//!    notification through the session's driver
+//!    ([`AgentDriver::notify`](crate::driver::AgentDriver::notify)) with the
+//!    payload built by [`SessionHookEvent::params`].
+//!
+//! Delivery is best-effort: hooks are advisory, and the authoritative state
+//! is still returned by the next tool call.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, PoisonError};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::models::session::SessionMode;
+use crate::models::steering::SteeringSource;
+
+/// Method an agent sends to register its hook subscriptions.
+pub const SUBSCRIBE_METHOD: &str = "intercom/subscribe";
+
+/// Method the server uses to deliver a hook event to the agent.
+pub const EVENT_METHOD: &str = "intercom/event";
+
+/// Categories of session event an agent can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionHookKind {
+    /// A steering message was queued for the session.
+    SteeringQueued,
+    /// The session's operational mode changed.
+    ModeChanged,
+    /// A checkpoint of the session was created.
+    CheckpointCreated,
+}
+
+impl SessionHookKind {
+    /// Every hook kind, used when a subscription omits `events`.
+    pub const ALL: [Self; 3] = [
+        Self::SteeringQueued,
+        Self::ModeChanged,
+        Self::CheckpointCreated,
+    ];
+}
+
+/// Parameters of an `intercom/subscribe` message.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SubscribeParams {
+    /// Kinds to subscribe to; `None` subscribes to all of them.
+    #[serde(default)]
+    pub events: Option<Vec<SessionHookKind>>,
+}
+
+impl SubscribeParams {
+    /// The kinds this subscription covers.
+    #[must_use]
+    pub fn kinds(&self) -> Vec<SessionHookKind> {
+        self.events
+            .clone()
+            .unwrap_or_else(|| SessionHookKind::ALL.to_vec())
+    }
+}
+
+/// A session event delivered to subscribed agents.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SessionHookEvent {
+    /// A steering message was queued and awaits the next `ping`.
+    SteeringQueued {
+        /// Steering record identifier.
+        message_id: String,
+        /// Operator's message text.
+        message: String,
+        /// Where the message was submitted.
+        source: SteeringSource,
+    },
+    /// The operational mode changed.
+    ModeChanged {
+        /// Mode before the change.
+        previous_mode: SessionMode,
+        /// Mode after the change.
+        current_mode: SessionMode,
+    },
+    /// A checkpoint was created.
+    CheckpointCreated {
+        /// Checkpoint identifier.
+        checkpoint_id: String,
+        /// Operator-supplied label, if any.
+        label: Option<String>,
+    },
+}
+
+impl SessionHookEvent {
+    /// The subscription category of this event.
+    #[must_use]
+    pub fn kind(&self) -> SessionHookKind {
+        match self {
+            Self::SteeringQueued { .. } => SessionHookKind::SteeringQueued,
+            Self::ModeChanged { .. } => SessionHookKind::ModeChanged,
+            Self::CheckpointCreated { .. } => SessionHookKind::CheckpointCreated,
+        }
+    }
+
+    /// Build the `intercom/event` params object for `session_id`.
+    ///
+    /// The event's fields are flattened next to `kind` and `session_id`.
+    #[must_use]
+    pub fn params(&self, session_id: &str) -> Value {
+        let mut params =
+            serde_json::to_value(self).unwrap_or_else(|_| Value::Object(serde_json::Map::new()));
+        if let Value::Object(ref mut map) = params {
+            map.insert(
+                "session_id".to_owned(),
+                Value::String(session_id.to_owned()),
+            );
+        }
+        params
+    }
+}
+
+/// Per-session hook subscriptions.
+#[derive(Debug, Default)]
+pub struct SessionHooks {
+    subscriptions: Mutex<HashMap<String, HashSet<SessionHookKind>>>,
+}
+
+impl SessionHooks {
+    /// Create an empty subscription table.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the subscriptions of `session_id` with `kinds`.
+    ///
+    /// An empty `kinds` list unsubscribes the session.
+    pub fn subscribe(&self, session_id: &str, kinds: impl IntoIterator<Item = SessionHookKind>) {
+        let kinds: HashSet<SessionHookKind> = kinds.into_iter().collect();
+        let mut map = self
+            .subscriptions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if kinds.is_empty() {
+            map.remove(session_id);
+        } else {
+            map.insert(session_id.to_owned(), kinds);
+        }
+    }
+
+    /// Drop every subscription held by `session_id`.
+    pub fn clear(&self, session_id: &str) {
+        self.subscriptions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(session_id);
+    }
+
+    /// Whether `session_id` wants events of `kind`.
+    #[must_use]
+    pub fn is_subscribed(&self, session_id: &str, kind: SessionHookKind) -> bool {
+        self.subscriptions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(session_id)
+            .is_some_and(|kinds| kinds.contains(&kind))
+    }
+}
@@ -36,6 +36,8 @@ pub enum AppError {
     Io(String),
     /// Agent Client Protocol stream or session failure.
     Acp(String),
+    /// External integration (issue tracker) request failure.
+    Integration(String),
 }
 
 impl Display for AppError {
@@ -55,6 +57,7 @@ impl Display for AppError {
             Self::AlreadyConsumed(msg) => write!(f, "already consumed: {msg}"),
             Self::Io(msg) => write!(f, "io: {msg}"),
             Self::Acp(msg) => write!(f, "acp: {msg}"),
+            Self::Integration(msg) => write!(f, "integration: {msg}"),
         }
     }
 }
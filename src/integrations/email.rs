@@ -0,0 +1,477 @@
+//! Email session summaries (`[smtp]`).
+//!
+//! When a session ends, a summary of it — prompt, duration, approvals,
+//! files changed and final status — is rendered as Markdown (the plain-text
+//! part) and HTML and mailed to the session owner, for teams that archive
+//! work records outside Slack. Owners are mapped to addresses through
+//! [`SmtpConfig::recipients`]; sessions whose owner has no address are
+//! skipped unless `cc` recipients are configured.
+//!
+//! With `[approval_links] email = true`, each pending approval is also
+//! mailed to the session owner as a signed link to the approval page.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use tracing::{info, warn};
+
+use crate::config::{SmtpConfig, SmtpSecurity};
+use crate::models::approval::{ApprovalRequest, ApprovalStatus};
+use crate::models::session::{format_tags, Session};
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::db::Database;
+use crate::slack::blocks::format_elapsed;
+use crate::{AppError, GlobalConfig, Result};
+
+/// Upper bound on a single SMTP exchange.
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Everything a summary email reports about one ended session.
+#[derive(Debug, Clone)]
+pub struct SessionSummary {
+    /// The ended session.
+    pub session: Session,
+    /// Why the session ended, e.g. `stopped by operator`.
+    pub reason: String,
+    /// Approval requests raised during the session, oldest first.
+    pub approvals: Vec<ApprovalRequest>,
+}
+
+impl SessionSummary {
+    /// Load the approvals for `session` and build its summary.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the approvals cannot be listed.
+    pub async fn gather(db: &Arc<Database>, session: Session, reason: &str) -> Result<Self> {
+        let approvals = ApprovalRepo::new(Arc::clone(db))
+            .list_for_session(&session.id)
+            .await?;
+        Ok(Self {
+            session,
+            reason: reason.to_owned(),
+            approvals,
+        })
+    }
+
+    /// Distinct files with accepted changes, in the order first approved.
+    #[must_use]
+    pub fn files_changed(&self) -> Vec<&str> {
+        let mut files: Vec<&str> = Vec::new();
+        for approval in &self.approvals {
+            let accepted = matches!(
+                approval.status,
+                ApprovalStatus::Approved | ApprovalStatus::Consumed
+            );
+            if accepted && !files.contains(&approval.file_path.as_str()) {
+                files.push(&approval.file_path);
+            }
+        }
+        files
+    }
+
+    /// Email subject line.
+    #[must_use]
+    pub fn subject(&self) -> String {
+        let short_id = &self.session.short_id;
+        match self.session.title {
+            Some(ref title) => format!("Session {short_id} ended: {title}"),
+            None => format!("Session {short_id} ended"),
+        }
+    }
+
+    /// Summary as Markdown, used for the plain-text part.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut body = format!("# {}\n\n", self.subject());
+        for (label, value) in self.facts() {
+            body.push_str("- **");
+            body.push_str(label);
+            body.push_str(":** ");
+            body.push_str(&value);
+            body.push('\n');
+        }
+        if let Some(ref prompt) = self.session.prompt {
+            body.push_str("\n## Prompt\n\n");
+            for line in prompt.lines() {
+                body.push_str("> ");
+                body.push_str(line);
+                body.push('\n');
+            }
+        }
+        body.push_str("\n## Approvals\n\n");
+        if self.approvals.is_empty() {
+            body.push_str("No approval requests.\n");
+        }
+        for approval in &self.approvals {
+            body.push_str("- ");
+            body.push_str(approval_status_label(approval.status));
+            body.push_str(" — ");
+            body.push_str(&approval.title);
+            body.push_str(" (`");
+            body.push_str(&approval.file_path);
+            body.push_str("`)\n");
+        }
+        body.push_str("\n## Files changed\n\n");
+        let files = self.files_changed();
+        if files.is_empty() {
+            body.push_str("None.\n");
+        }
+        for file in files {
+            body.push_str("- `");
+            body.push_str(file);
+            body.push_str("`\n");
+        }
+        body
+    }
+
+    /// Summary as an HTML document.
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        let mut body = format!(
+            "<!DOCTYPE html>\n<html><body>\n<h1>{}</h1>\n<ul>\n",
+            escape_html(&self.subject())
+        );
+        for (label, value) in self.facts() {
+            body.push_str("<li><strong>");
+            body.push_str(label);
+            body.push_str(":</strong> ");
+            body.push_str(&escape_html(&value));
+            body.push_str("</li>\n");
+        }
+        body.push_str("</ul>\n");
+        if let Some(ref prompt) = self.session.prompt {
+            body.push_str("<h2>Prompt</h2>\n<blockquote><pre>");
+            body.push_str(&escape_html(prompt));
+            body.push_str("</pre></blockquote>\n");
+        }
+        body.push_str("<h2>Approvals</h2>\n");
+        if self.approvals.is_empty() {
+            body.push_str("<p>No approval requests.</p>\n");
+        } else {
+            body.push_str("<table>\n<tr><th>Status</th><th>Change</th><th>File</th></tr>\n");
+            for approval in &self.approvals {
+                body.push_str("<tr><td>");
+                body.push_str(approval_status_label(approval.status));
+                body.push_str("</td><td>");
+                body.push_str(&escape_html(&approval.title));
+                body.push_str("</td><td><code>");
+                body.push_str(&escape_html(&approval.file_path));
+                body.push_str("</code></td></tr>\n");
+            }
+            body.push_str("</table>\n");
+        }
+        body.push_str("<h2>Files changed</h2>\n");
+        let files = self.files_changed();
+        if files.is_empty() {
+            body.push_str("<p>None.</p>\n");
+        } else {
+            body.push_str("<ul>\n");
+            for file in files {
+                body.push_str("<li><code>");
+                body.push_str(&escape_html(file));
+                body.push_str("</code></li>\n");
+            }
+            body.push_str("</ul>\n");
+        }
+        body.push_str("</body></html>\n");
+        body
+    }
+
+    /// Label/value pairs shown at the top of both renderings.
+    fn facts(&self) -> Vec<(&'static str, String)> {
+        let session = &self.session;
+        let mut facts = vec![
+            ("Session", session.id.clone()),
+            ("Final status", session.status.as_str().to_owned()),
+            ("Reason", self.reason.clone()),
+            ("Workspace", session.workspace_root.clone()),
+            ("Started", session.created_at.to_rfc3339()),
+        ];
+        if let Some(ended_at) = session.terminated_at {
+            facts.push(("Ended", ended_at.to_rfc3339()));
+            facts.push((
+                "Duration",
+                format_elapsed(
+                    ended_at
+                        .signed_duration_since(session.created_at)
+                        .num_seconds(),
+                ),
+            ));
+        }
+        if let Some(ref issue_ref) = session.issue_ref {
+            facts.push(("Issue", issue_ref.clone()));
+        }
+        if !session.tags.is_empty() {
+            facts.push(("Tags", format_tags(&session.tags)));
+        }
+        let decided = |status| self.approvals.iter().filter(|a| a.status == status).count();
+        facts.push((
+            "Approvals",
+            format!(
+                "{} requested, {} approved, {} rejected",
+                self.approvals.len(),
+                decided(ApprovalStatus::Approved) + decided(ApprovalStatus::Consumed),
+                decided(ApprovalStatus::Rejected)
+            ),
+        ));
+        facts
+    }
+}
+
+/// Human-readable approval outcome.
+pub(crate) fn approval_status_label(status: ApprovalStatus) -> &'static str {
+    match status {
+        ApprovalStatus::Pending => "pending",
+        ApprovalStatus::Approved => "approved",
+        ApprovalStatus::Rejected => "rejected",
+        ApprovalStatus::Expired => "expired",
+        ApprovalStatus::Consumed => "applied",
+        ApprovalStatus::Interrupted => "interrupted",
+    }
+}
+
+/// Escape text for inclusion in HTML element content or attribute values.
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Parse a configured address, naming the setting in the error.
+fn parse_mailbox(value: &str, setting: &str) -> Result<Mailbox> {
+    value
+        .parse()
+        .map_err(|err| AppError::Config(format!("invalid [smtp] {setting} '{value}': {err}")))
+}
+
+/// Build the multipart summary message.
+///
+/// Returns `None` when there is nobody to send it to.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for a missing or invalid sender or recipient
+/// address.
+pub fn build_message(config: &SmtpConfig, summary: &SessionSummary) -> Result<Option<Message>> {
+    let owner = config.recipient_for(&summary.session.owner_user_id);
+    if owner.is_none() && config.cc.is_empty() {
+        return Ok(None);
+    }
+    let from = config
+        .from
+        .as_deref()
+        .ok_or_else(|| AppError::Config("[smtp] from is required".into()))?;
+    let mut builder = Message::builder()
+        .from(parse_mailbox(from, "from")?)
+        .subject(summary.subject());
+    if let Some(owner) = owner {
+        builder = builder.to(parse_mailbox(owner, "recipients")?);
+    }
+    for cc in &config.cc {
+        builder = if owner.is_some() {
+            builder.cc(parse_mailbox(cc, "cc")?)
+        } else {
+            builder.to(parse_mailbox(cc, "cc")?)
+        };
+    }
+    let message = builder
+        .multipart(MultiPart::alternative_plain_html(
+            summary.to_markdown(),
+            summary.to_html(),
+        ))
+        .map_err(|err| AppError::Integration(format!("failed to build summary email: {err}")))?;
+    Ok(Some(message))
+}
+
+/// Email `summary` to the session owner.
+///
+/// Returns `false` without connecting when summaries are disabled or the
+/// owner has no mapped address and no `cc` is configured.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for invalid addresses, or
+/// `AppError::Integration` when the SMTP exchange fails.
+pub async fn send_summary(config: &SmtpConfig, summary: &SessionSummary) -> Result<bool> {
+    let Some(ref host) = config.host else {
+        return Ok(false);
+    };
+    let Some(message) = build_message(config, summary)? else {
+        return Ok(false);
+    };
+    transport(config, host)?
+        .send(message)
+        .await
+        .map_err(|err| AppError::Integration(format!("failed to send summary email: {err}")))?;
+    info!(session_id = %summary.session.id, "emailed session summary");
+    Ok(true)
+}
+
+/// SMTP transport for `host` with the configured security and credentials.
+fn transport(config: &SmtpConfig, host: &str) -> Result<AsyncSmtpTransport<Tokio1Executor>> {
+    let smtp_err = |err| AppError::Integration(format!("smtp: {err}"));
+    let mut transport = match config.security {
+        SmtpSecurity::Starttls => {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host).map_err(smtp_err)?
+        }
+        SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(host).map_err(smtp_err)?,
+        SmtpSecurity::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+    }
+    .port(config.port)
+    .timeout(Some(SMTP_TIMEOUT));
+    if !config.username.is_empty() {
+        transport = transport.credentials(Credentials::new(
+            config.username.clone(),
+            config.password.clone(),
+        ));
+    }
+    Ok(transport.build())
+}
+
+/// Gather and email the summary in the background so a slow mail server
+/// never delays the caller.
+pub fn spawn_summary_email(
+    config: Arc<GlobalConfig>,
+    db: Arc<Database>,
+    session: Session,
+    reason: String,
+) {
+    if !config.smtp.is_enabled() {
+        return;
+    }
+    tokio::spawn(async move {
+        let session_id = session.id.clone();
+        let result = match SessionSummary::gather(&db, session, &reason).await {
+            Ok(summary) => send_summary(&config.smtp, &summary).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            warn!(%err, %session_id, "failed to email session summary");
+        }
+    });
+}
+
+/// A pending approval to email to its session owner as a signed link
+/// (`[approval_links] email = true`).
+#[derive(Debug, Clone)]
+pub struct ApprovalLinkNotice {
+    /// Slack user ID of the session owner.
+    pub owner_id: String,
+    /// Title of the approval request.
+    pub title: String,
+    /// File the request changes.
+    pub file_path: String,
+    /// Risk level, e.g. `high`.
+    pub risk_level: String,
+    /// Signed approval page URL.
+    pub url: String,
+    /// How long the link stays valid, e.g. `15m 0s`.
+    pub expires_in: String,
+}
+
+impl ApprovalLinkNotice {
+    fn to_text(&self) -> String {
+        format!(
+            "Approval needed: {}\n\nFile: {}\nRisk: {}\n\nReview and decide: {}\n\n\
+             The link expires in {}. Anyone with it can decide the request, \
+             so do not forward this email.\n",
+            self.title, self.file_path, self.risk_level, self.url, self.expires_in
+        )
+    }
+
+    fn to_html(&self) -> String {
+        format!(
+            "<h2>Approval needed: {}</h2>\n<p>File: <code>{}</code><br>Risk: {}</p>\n\
+             <p><a href=\"{}\">Review and decide</a></p>\n\
+             <p>The link expires in {}. Anyone with it can decide the request, \
+             so do not forward this email.</p>\n",
+            escape_html(&self.title),
+            escape_html(&self.file_path),
+            escape_html(&self.risk_level),
+            escape_html(&self.url),
+            escape_html(&self.expires_in)
+        )
+    }
+}
+
+/// Build the approval link message for `notice`, addressed to the owner
+/// only: `cc` archives must not receive a usable approval link.
+///
+/// Returns `None` when the owner has no mapped address.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for a missing or invalid sender or recipient
+/// address.
+pub fn build_approval_link_message(
+    config: &SmtpConfig,
+    notice: &ApprovalLinkNotice,
+) -> Result<Option<Message>> {
+    let Some(owner) = config.recipient_for(&notice.owner_id) else {
+        return Ok(None);
+    };
+    let from = config
+        .from
+        .as_deref()
+        .ok_or_else(|| AppError::Config("[smtp] from is required".into()))?;
+    let message = Message::builder()
+        .from(parse_mailbox(from, "from")?)
+        .to(parse_mailbox(owner, "recipients")?)
+        .subject(format!("Approval needed: {}", notice.title))
+        .multipart(MultiPart::alternative_plain_html(
+            notice.to_text(),
+            notice.to_html(),
+        ))
+        .map_err(|err| AppError::Integration(format!("failed to build approval email: {err}")))?;
+    Ok(Some(message))
+}
+
+/// Email `notice` to its session owner.
+///
+/// Returns `false` without connecting when email is disabled or the owner
+/// has no mapped address.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for invalid addresses, or
+/// `AppError::Integration` when the SMTP exchange fails.
+pub async fn send_approval_link(config: &SmtpConfig, notice: &ApprovalLinkNotice) -> Result<bool> {
+    let Some(ref host) = config.host else {
+        return Ok(false);
+    };
+    let Some(message) = build_approval_link_message(config, notice)? else {
+        return Ok(false);
+    };
+    transport(config, host)?
+        .send(message)
+        .await
+        .map_err(|err| AppError::Integration(format!("failed to send approval email: {err}")))?;
+    info!(title = %notice.title, "emailed approval link");
+    Ok(true)
+}
+
+/// Email `notice` in the background so a slow mail server never delays
+/// the approval request.
+pub fn spawn_approval_link_email(config: Arc<GlobalConfig>, notice: ApprovalLinkNotice) {
+    if !config.smtp.is_enabled() {
+        return;
+    }
+    tokio::spawn(async move {
+        if let Err(err) = send_approval_link(&config.smtp, &notice).await {
+            warn!(%err, title = %notice.title, "failed to email approval link");
+        }
+    });
+}
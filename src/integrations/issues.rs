@@ -0,0 +1,275 @@
+//! Issue tracker linking for sessions (`session-start --issue`).
+//!
+//! A session may carry an issue reference — a Jira key (`PROJ-123`), a
+//! GitHub reference (`owner/repo#42`, `#42` with a default repository), or
+//! an issue URL. The reference is shown in the session's Slack messages,
+//! and when the session ends a completion comment summarising it is posted
+//! back to the issue through the configured [`IssueProvider`].
+
+use std::sync::Arc;
+
+use tracing::{info, warn};
+
+use crate::config::{IssueProvider, IssuesConfig};
+use crate::models::progress::ProgressStatus;
+use crate::models::session::{Session, SessionStatus};
+use crate::slack::blocks::format_elapsed;
+use crate::{AppError, GlobalConfig, Result};
+
+/// Default GitHub REST API base URL.
+pub const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// Where a completion comment is posted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IssueTarget {
+    /// A GitHub issue or pull request.
+    Github {
+        /// Repository as `owner/repo`.
+        repo: String,
+        /// Issue or pull request number.
+        number: u64,
+    },
+    /// A Jira issue.
+    Jira {
+        /// Issue key, e.g. `PROJ-123`.
+        key: String,
+    },
+}
+
+/// Whether `value` looks like a Jira issue key (`PROJ-123`).
+fn is_jira_key(value: &str) -> bool {
+    let Some((project, number)) = value.split_once('-') else {
+        return false;
+    };
+    project.starts_with(|c: char| c.is_ascii_uppercase())
+        && project
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        && !number.is_empty()
+        && number.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Parse an issue reference without consulting any configuration.
+///
+/// Bare numbers (`42`, `#42`) need a default repository and are left to
+/// [`resolve_target`].
+fn parse_reference(issue_ref: &str) -> Option<IssueTarget> {
+    if let Some(rest) = issue_ref
+        .strip_prefix("https://")
+        .or_else(|| issue_ref.strip_prefix("http://"))
+    {
+        let segments: Vec<&str> = rest.trim_end_matches('/').split('/').collect();
+        if let Some(pos) = segments.iter().position(|s| *s == "browse") {
+            let key = segments.get(pos + 1)?;
+            return is_jira_key(key).then(|| IssueTarget::Jira {
+                key: (*key).to_owned(),
+            });
+        }
+        // https://host/owner/repo/issues/42 (or /pull/42)
+        if let [_host, owner, repo, "issues" | "pull", number, ..] = segments.as_slice() {
+            return Some(IssueTarget::Github {
+                repo: format!("{owner}/{repo}"),
+                number: number.parse().ok()?,
+            });
+        }
+        return None;
+    }
+    if let Some((repo, number)) = issue_ref.split_once('#') {
+        if repo.split('/').count() == 2 && !repo.starts_with('/') && !repo.ends_with('/') {
+            return Some(IssueTarget::Github {
+                repo: repo.to_owned(),
+                number: number.parse().ok()?,
+            });
+        }
+        return None;
+    }
+    is_jira_key(issue_ref).then(|| IssueTarget::Jira {
+        key: issue_ref.to_owned(),
+    })
+}
+
+/// Check that `issue_ref` is a recognisable issue reference.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` naming the accepted forms.
+pub fn validate_issue_ref(issue_ref: &str) -> Result<()> {
+    let bare_number = issue_ref.trim_start_matches('#').parse::<u64>().is_ok();
+    if bare_number || parse_reference(issue_ref).is_some() {
+        Ok(())
+    } else {
+        Err(AppError::Config(format!(
+            "invalid issue '{issue_ref}' — use a Jira key (PROJ-123), owner/repo#42, #42, \
+             or an issue URL"
+        )))
+    }
+}
+
+/// Resolve `issue_ref` to the issue a completion comment is posted to.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` when the reference is malformed, is a bare
+/// number without `default_repo`, or belongs to a different tracker than
+/// the configured provider.
+pub fn resolve_target(config: &IssuesConfig, issue_ref: &str) -> Result<IssueTarget> {
+    let target = if let Ok(number) = issue_ref.trim_start_matches('#').parse::<u64>() {
+        let repo = config.default_repo.clone().ok_or_else(|| {
+            AppError::Config(format!(
+                "issue '{issue_ref}' needs [issues] default_repo to name its repository"
+            ))
+        })?;
+        IssueTarget::Github { repo, number }
+    } else {
+        validate_issue_ref(issue_ref)?;
+        parse_reference(issue_ref)
+            .ok_or_else(|| AppError::Config(format!("invalid issue '{issue_ref}'")))?
+    };
+    let matches_provider = matches!(
+        (config.provider, &target),
+        (Some(IssueProvider::Github), IssueTarget::Github { .. })
+            | (Some(IssueProvider::Jira), IssueTarget::Jira { .. })
+    );
+    if matches_provider {
+        Ok(target)
+    } else {
+        Err(AppError::Config(format!(
+            "issue '{issue_ref}' does not match the configured issue provider"
+        )))
+    }
+}
+
+/// Short label for an issue reference, e.g. `PROJ-123` or `owner/repo#42`.
+#[must_use]
+pub fn issue_label(issue_ref: &str) -> String {
+    match parse_reference(issue_ref) {
+        Some(IssueTarget::Github { repo, number }) => format!("{repo}#{number}"),
+        Some(IssueTarget::Jira { key }) => key,
+        None => issue_ref.to_owned(),
+    }
+}
+
+/// Render the completion comment posted to the issue.
+#[must_use]
+pub fn completion_comment(session: &Session, reason: &str) -> String {
+    let short_id = &session.short_id;
+    let outcome = match session.status {
+        SessionStatus::Terminated => "ended",
+        SessionStatus::Interrupted => "was interrupted",
+        _ => "finished",
+    };
+    let mut body = format!("agent-intercom session {short_id} {outcome}: {reason}.\n");
+    if let Some(ref title) = session.title {
+        body.push_str("\nTask: ");
+        body.push_str(title);
+        body.push('\n');
+    }
+    if let Some(ended_at) = session.terminated_at {
+        body.push_str("\n- Duration: ");
+        body.push_str(&format_elapsed(
+            ended_at
+                .signed_duration_since(session.created_at)
+                .num_seconds(),
+        ));
+    }
+    body.push_str("\n- Last tool: ");
+    body.push_str(session.last_tool.as_deref().unwrap_or("none"));
+    if let Some(ref items) = session.progress_snapshot {
+        body.push_str("\n\nProgress:");
+        for item in items {
+            let mark = match item.status {
+                ProgressStatus::Done => "[x]",
+                ProgressStatus::InProgress => "[~]",
+                ProgressStatus::Pending => "[ ]",
+            };
+            body.push_str("\n- ");
+            body.push_str(mark);
+            body.push(' ');
+            body.push_str(&item.label);
+        }
+    }
+    body.push('\n');
+    body
+}
+
+/// Post `body` as a comment on `target`.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` when Jira has no `base_url`, or
+/// `AppError::Integration` when the request fails or is rejected.
+pub async fn post_comment(config: &IssuesConfig, target: &IssueTarget, body: &str) -> Result<()> {
+    let client = reqwest::Client::new();
+    let payload = serde_json::json!({ "body": body });
+    let request = match target {
+        IssueTarget::Github { repo, number } => {
+            let base = config.base_url.as_deref().unwrap_or(GITHUB_API_URL);
+            client
+                .post(format!(
+                    "{}/repos/{repo}/issues/{number}/comments",
+                    base.trim_end_matches('/')
+                ))
+                .header("Accept", "application/vnd.github+json")
+                .header("User-Agent", "agent-intercom")
+                .bearer_auth(&config.token)
+        }
+        IssueTarget::Jira { key } => {
+            let base = config
+                .base_url
+                .as_deref()
+                .ok_or_else(|| AppError::Config("[issues] base_url is required for Jira".into()))?;
+            let request = client.post(format!(
+                "{}/rest/api/2/issue/{key}/comment",
+                base.trim_end_matches('/')
+            ));
+            if config.user.is_empty() {
+                request.bearer_auth(&config.token)
+            } else {
+                request.basic_auth(&config.user, Some(&config.token))
+            }
+        }
+    };
+    request
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|err| AppError::Integration(format!("failed to post issue comment: {err}")))?
+        .error_for_status()
+        .map_err(|err| AppError::Integration(format!("issue comment rejected: {err}")))?;
+    Ok(())
+}
+
+/// Post the completion comment for `session` to its linked issue.
+///
+/// Returns `false` without contacting the tracker when the session has no
+/// issue or no provider is configured.
+///
+/// # Errors
+///
+/// Returns the error from [`resolve_target`] or [`post_comment`].
+pub async fn report_completion(
+    config: &IssuesConfig,
+    session: &Session,
+    reason: &str,
+) -> Result<bool> {
+    let (Some(ref issue_ref), Some(_)) = (&session.issue_ref, config.provider) else {
+        return Ok(false);
+    };
+    let target = resolve_target(config, issue_ref)?;
+    post_comment(config, &target, &completion_comment(session, reason)).await?;
+    info!(session_id = %session.id, issue = %issue_ref, "posted completion comment to issue");
+    Ok(true)
+}
+
+/// Report completion in the background so slow trackers never delay the
+/// caller (for example a Slack command that must answer within seconds).
+pub fn spawn_completion_report(config: Arc<GlobalConfig>, session: Session, reason: String) {
+    if session.issue_ref.is_none() || config.issues.provider.is_none() {
+        return;
+    }
+    tokio::spawn(async move {
+        if let Err(err) = report_completion(&config.issues, &session, &reason).await {
+            warn!(%err, session_id = %session.id, "failed to post issue completion comment");
+        }
+    });
+}
@@ -0,0 +1,8 @@
+//! Integrations with external services.
+//!
+//! - [`email`] — mails a summary of each ended session to its owner.
+//! - [`issues`] — links sessions to a GitHub or Jira issue and posts a
+//!   completion comment when the session ends.
+
+pub mod email;
+pub mod issues;
@@ -0,0 +1,626 @@
+//! Typed async client for the local IPC protocol.
+//!
+//! `agent-intercom-ctl` is built on this client; other tools can use it to
+//! control a running server programmatically. A connection stays open
+//! across calls, so several commands can share one socket.
+//!
+//! ```no_run
+//! use agent_intercom::ipc::client::IpcClient;
+//!
+//! # async fn demo() -> agent_intercom::Result<()> {
+//! let mut client = IpcClient::connect("agent-intercom").await?;
+//! for session in client.list().await? {
+//!     println!("{} {:?}", session.session_id, session.status);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use interprocess::local_socket::tokio::{prelude::*, RecvHalf, SendHalf};
+use interprocess::local_socket::GenericNamespaced;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::models::approval::ApprovalStatus;
+use crate::models::session::{SessionMode, SessionStatus};
+use crate::models::steering::SteeringPriority;
+use crate::orchestrator::session_bulk::{BulkAction, BulkOutcome};
+use crate::orchestrator::session_logs::LogLine;
+use crate::slack::socket_health::SocketHealthSnapshot;
+use crate::{AppError, Result};
+
+/// A command understood by the IPC server.
+///
+/// Serializes to the wire format, e.g. `{"command":"approve","id":"req-123"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "lowercase")]
+pub enum IpcCommand {
+    /// List active sessions.
+    List {
+        /// `key=value` tags every listed session must carry.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        tags: Vec<String>,
+    },
+    /// Approve a pending approval request.
+    Approve {
+        /// Approval request ID, short ID, or unique prefix.
+        id: String,
+    },
+    /// Reject a pending approval request.
+    Reject {
+        /// Approval request ID, short ID, or unique prefix.
+        id: String,
+        /// Rejection reason.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+    /// Resume a waiting agent.
+    Resume {
+        /// Session to resume; the first waiting session when omitted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        /// Instruction delivered with the resume.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        instruction: Option<String>,
+    },
+    /// Switch the active session's operational mode.
+    Mode {
+        /// Target mode.
+        mode: SessionMode,
+    },
+    /// Queue a steering message for the active session.
+    Steer {
+        /// Message text.
+        instruction: String,
+        /// Delivery urgency (server default: normal).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        priority: Option<SteeringPriority>,
+        /// Time-to-live such as `10m`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ttl: Option<String>,
+    },
+    /// Queue a task for the next agent cold-start.
+    Task {
+        /// Task text.
+        instruction: String,
+    },
+    /// Render a session's Markdown report.
+    Report {
+        /// Session ID or unique prefix.
+        id: String,
+    },
+    /// Read a session's streamed log lines.
+    Logs {
+        /// Session ID or unique prefix.
+        id: String,
+        /// Number of lines (server default: 200).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        limit: Option<usize>,
+    },
+    /// Hide an ended session from listings and recovery.
+    #[serde(rename = "session-delete")]
+    SessionDelete {
+        /// Session ID or unique prefix.
+        id: String,
+    },
+    /// Undo [`IpcCommand::SessionDelete`].
+    #[serde(rename = "session-restore")]
+    SessionRestore {
+        /// Session ID or unique prefix.
+        id: String,
+    },
+    /// Apply one action to every matching session.
+    Sessions {
+        /// Action to apply.
+        action: BulkAction,
+        /// Statuses to select (server default depends on the action).
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        statuses: Vec<SessionStatus>,
+        /// Owner to select.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        owner: Option<String>,
+        /// Minimum time since the last update, such as `7d`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        older_than: Option<String>,
+        /// Required to act on every session when no filter is given.
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        all: bool,
+    },
+    /// Set, reset or read the server's tracing filter.
+    #[serde(rename = "log-level")]
+    LogLevel {
+        /// Level to set: `trace`, `debug`, `info`, `warn`, `error` or `off`.
+        /// Reads the current filter when absent.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        level: Option<String>,
+        /// Tracing target the level applies to; the default level when
+        /// absent.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        target: Option<String>,
+        /// Restore the filter the server started with.
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        reset: bool,
+    },
+    /// Report the Slack Socket Mode connection state.
+    #[serde(rename = "slack-status")]
+    SlackStatus,
+    /// Drop and re-register the Slack Socket Mode connections.
+    #[serde(rename = "slack-reconnect")]
+    SlackReconnect,
+    /// Set fault injection (servers built with the `chaos` feature only).
+    Chaos {
+        /// Percentage of Slack posts to drop.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        drop_percent: Option<u8>,
+        /// Milliseconds to hold each oneshot resolution.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        delay_ms: Option<u64>,
+        /// Kill the Socket Mode connection.
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        kill_socket: bool,
+        /// Turn every fault off before applying the others.
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        reset: bool,
+    },
+}
+
+impl IpcCommand {
+    /// Command verb as sent on the wire.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::List { .. } => "list",
+            Self::Approve { .. } => "approve",
+            Self::Reject { .. } => "reject",
+            Self::Resume { .. } => "resume",
+            Self::Mode { .. } => "mode",
+            Self::Steer { .. } => "steer",
+            Self::Task { .. } => "task",
+            Self::Report { .. } => "report",
+            Self::Logs { .. } => "logs",
+            Self::SessionDelete { .. } => "session-delete",
+            Self::SessionRestore { .. } => "session-restore",
+            Self::Sessions { .. } => "sessions",
+            Self::LogLevel { .. } => "log-level",
+            Self::SlackStatus => "slack-status",
+            Self::SlackReconnect => "slack-reconnect",
+            Self::Chaos { .. } => "chaos",
+        }
+    }
+}
+
+/// One entry of the `list` response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionSummary {
+    /// Session ID.
+    pub session_id: String,
+    /// Short ID shown in Slack, e.g. `ses-7f3k` (absent from servers that
+    /// predate short IDs).
+    #[serde(default)]
+    pub short_id: String,
+    /// Lifecycle status.
+    pub status: SessionStatus,
+    /// Operational mode.
+    pub mode: SessionMode,
+    /// Workspace directory.
+    pub workspace_root: String,
+    /// Most recent tool called.
+    pub last_tool: Option<String>,
+    /// Last activity.
+    pub updated_at: DateTime<Utc>,
+    /// Session tags (absent from servers that predate tagging).
+    #[serde(default)]
+    pub tags: BTreeMap<String, String>,
+}
+
+/// Response to `approve` and `reject`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalOutcome {
+    /// Approval request ID.
+    pub request_id: String,
+    /// New status: approved or rejected.
+    pub status: ApprovalStatus,
+}
+
+/// Response to `resume`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResumeOutcome {
+    /// Session that was resumed.
+    pub session_id: String,
+    /// Always `resumed`.
+    pub status: String,
+}
+
+/// Response to `mode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModeChange {
+    /// Mode before the switch.
+    pub previous_mode: SessionMode,
+    /// Mode after the switch.
+    pub current_mode: SessionMode,
+}
+
+/// Response to `steer`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SteerOutcome {
+    /// Session the message targets.
+    pub session_id: String,
+    /// Whether the message was queued for later delivery.
+    pub queued: bool,
+    /// Whether an interrupt was delivered immediately.
+    #[serde(default)]
+    pub delivered: bool,
+    /// Whether the queued message was also pushed as an MCP notification.
+    #[serde(default)]
+    pub pushed: bool,
+}
+
+/// Response to `task`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskOutcome {
+    /// Inbox item ID.
+    pub task_id: String,
+    /// Always true.
+    pub queued: bool,
+}
+
+/// Response to `report`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportOutcome {
+    /// Full session ID.
+    pub session_id: String,
+    /// Rendered Markdown report.
+    pub markdown: String,
+}
+
+/// Response to `logs`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogsOutcome {
+    /// Full session ID.
+    pub session_id: String,
+    /// Lines buffered for the session in total.
+    pub buffered: usize,
+    /// Requested lines, oldest first.
+    pub lines: Vec<LogLine>,
+}
+
+/// Response to `session-delete` and `session-restore`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionDeletion {
+    /// Full session ID.
+    pub session_id: String,
+    /// Whether the session is now hidden.
+    pub deleted: bool,
+}
+
+/// Response to `log-level`: the filter now in force.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogLevelOutcome {
+    /// Tracing filter in `RUST_LOG` syntax, e.g. `info,agent_intercom::slack=debug`.
+    pub filter: String,
+}
+
+/// Response to `slack-status` and `slack-reconnect`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlackConnectionOutcome {
+    /// Connection state; for `slack-reconnect`, as of the request, before
+    /// the listener has dropped its connections.
+    #[serde(flatten)]
+    pub socket: SocketHealthSnapshot,
+    /// Whether a reconnect was requested.
+    #[serde(default)]
+    pub reconnecting: bool,
+}
+
+/// Response to `chaos`: the fault settings now in force.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChaosOutcome {
+    /// Percentage of Slack posts being dropped.
+    pub drop_percent: u8,
+    /// Milliseconds each oneshot resolution is held.
+    pub delay_ms: u64,
+    /// Whether a Socket Mode connection was killed.
+    pub socket_killed: bool,
+}
+
+/// Request envelope: the command plus the shared-secret token.
+#[derive(Serialize)]
+struct Envelope<'a> {
+    #[serde(flatten)]
+    command: &'a IpcCommand,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auth_token: Option<&'a str>,
+}
+
+/// Response envelope as sent by the server.
+#[derive(Deserialize)]
+struct Response {
+    ok: bool,
+    #[serde(default)]
+    data: Option<serde_json::Value>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// An open connection to the IPC server.
+#[derive(Debug)]
+pub struct IpcClient {
+    reader: BufReader<RecvHalf>,
+    writer: SendHalf,
+    auth_token: Option<String>,
+}
+
+impl IpcClient {
+    /// Connect to the server listening on `ipc_name` (its `ipc_name` config).
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Ipc` if the name is invalid or nothing listens on it.
+    pub async fn connect(ipc_name: &str) -> Result<Self> {
+        let name = ipc_name
+            .to_ns_name::<GenericNamespaced>()
+            .map_err(|err| AppError::Ipc(format!("invalid ipc socket name '{ipc_name}': {err}")))?;
+        let stream = LocalSocketStream::connect(name)
+            .await
+            .map_err(|err| AppError::Ipc(format!("failed to connect to '{ipc_name}': {err}")))?;
+        let (reader, writer) = stream.split();
+        Ok(Self {
+            reader: BufReader::new(reader),
+            writer,
+            auth_token: None,
+        })
+    }
+
+    /// Send `token` with every request, for servers that require one.
+    #[must_use]
+    pub fn with_auth_token(mut self, token: impl Into<String>) -> Self {
+        self.auth_token = Some(token.into());
+        self
+    }
+
+    /// Send `command` and return the response's `data` payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Ipc` on a socket failure, a malformed response, or
+    /// when the server reports an error (its message is kept verbatim).
+    pub async fn send(&mut self, command: &IpcCommand) -> Result<serde_json::Value> {
+        let envelope = Envelope {
+            command,
+            auth_token: self.auth_token.as_deref(),
+        };
+        let mut line = serde_json::to_string(&envelope)
+            .map_err(|err| AppError::Ipc(format!("failed to encode request: {err}")))?;
+        line.push('\n');
+        self.writer
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|err| AppError::Ipc(format!("failed to send request: {err}")))?;
+
+        line.clear();
+        let read = self
+            .reader
+            .read_line(&mut line)
+            .await
+            .map_err(|err| AppError::Ipc(format!("failed to read response: {err}")))?;
+        if read == 0 {
+            return Err(AppError::Ipc("server closed the connection".into()));
+        }
+        let response: Response = serde_json::from_str(line.trim())
+            .map_err(|err| AppError::Ipc(format!("malformed response: {err}")))?;
+        if response.ok {
+            Ok(response.data.unwrap_or(serde_json::Value::Null))
+        } else {
+            Err(AppError::Ipc(
+                response.error.unwrap_or_else(|| "unknown error".into()),
+            ))
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(&mut self, command: &IpcCommand) -> Result<T> {
+        let data = self.send(command).await?;
+        serde_json::from_value(data).map_err(|err| {
+            AppError::Ipc(format!("unexpected '{}' response: {err}", command.name()))
+        })
+    }
+
+    /// List active sessions.
+    ///
+    /// # Errors
+    ///
+    /// See [`IpcClient::send`].
+    pub async fn list(&mut self) -> Result<Vec<SessionSummary>> {
+        self.list_tagged(&[]).await
+    }
+
+    /// List active sessions carrying every `key=value` tag in `tags`.
+    ///
+    /// # Errors
+    ///
+    /// See [`IpcClient::send`]; a malformed tag is rejected by the server.
+    pub async fn list_tagged(&mut self, tags: &[String]) -> Result<Vec<SessionSummary>> {
+        #[derive(Deserialize)]
+        struct Sessions {
+            sessions: Vec<SessionSummary>,
+        }
+        let data: Sessions = self
+            .call(&IpcCommand::List {
+                tags: tags.to_vec(),
+            })
+            .await?;
+        Ok(data.sessions)
+    }
+
+    /// Approve the pending approval request `id`.
+    ///
+    /// # Errors
+    ///
+    /// See [`IpcClient::send`].
+    pub async fn approve(&mut self, id: &str) -> Result<ApprovalOutcome> {
+        self.call(&IpcCommand::Approve { id: id.to_owned() }).await
+    }
+
+    /// Reject the pending approval request `id`.
+    ///
+    /// # Errors
+    ///
+    /// See [`IpcClient::send`].
+    pub async fn reject(&mut self, id: &str, reason: Option<&str>) -> Result<ApprovalOutcome> {
+        self.call(&IpcCommand::Reject {
+            id: id.to_owned(),
+            reason: reason.map(str::to_owned),
+        })
+        .await
+    }
+
+    /// Resume `session_id`, or the first waiting session when `None`.
+    ///
+    /// # Errors
+    ///
+    /// See [`IpcClient::send`].
+    pub async fn resume(
+        &mut self,
+        session_id: Option<&str>,
+        instruction: Option<&str>,
+    ) -> Result<ResumeOutcome> {
+        self.call(&IpcCommand::Resume {
+            id: session_id.map(str::to_owned),
+            instruction: instruction.map(str::to_owned),
+        })
+        .await
+    }
+
+    /// Switch the active session to `mode`.
+    ///
+    /// # Errors
+    ///
+    /// See [`IpcClient::send`].
+    pub async fn set_mode(&mut self, mode: SessionMode) -> Result<ModeChange> {
+        self.call(&IpcCommand::Mode { mode }).await
+    }
+
+    /// Queue a steering message for the active session.
+    ///
+    /// # Errors
+    ///
+    /// See [`IpcClient::send`].
+    pub async fn steer(
+        &mut self,
+        instruction: &str,
+        priority: SteeringPriority,
+        ttl: Option<&str>,
+    ) -> Result<SteerOutcome> {
+        self.call(&IpcCommand::Steer {
+            instruction: instruction.to_owned(),
+            priority: Some(priority),
+            ttl: ttl.map(str::to_owned),
+        })
+        .await
+    }
+
+    /// Queue a task for the next agent cold-start.
+    ///
+    /// # Errors
+    ///
+    /// See [`IpcClient::send`].
+    pub async fn task(&mut self, instruction: &str) -> Result<TaskOutcome> {
+        self.call(&IpcCommand::Task {
+            instruction: instruction.to_owned(),
+        })
+        .await
+    }
+
+    /// Render the Markdown report of session `id` (full ID or prefix).
+    ///
+    /// # Errors
+    ///
+    /// See [`IpcClient::send`].
+    pub async fn report(&mut self, id: &str) -> Result<ReportOutcome> {
+        self.call(&IpcCommand::Report { id: id.to_owned() }).await
+    }
+
+    /// Read the newest `limit` log lines of session `id` (full ID or prefix).
+    ///
+    /// # Errors
+    ///
+    /// See [`IpcClient::send`].
+    pub async fn logs(&mut self, id: &str, limit: Option<usize>) -> Result<LogsOutcome> {
+        self.call(&IpcCommand::Logs {
+            id: id.to_owned(),
+            limit,
+        })
+        .await
+    }
+
+    /// Soft-delete the ended session `id` (full ID or prefix).
+    ///
+    /// # Errors
+    ///
+    /// See [`IpcClient::send`].
+    pub async fn delete_session(&mut self, id: &str) -> Result<SessionDeletion> {
+        self.call(&IpcCommand::SessionDelete { id: id.to_owned() })
+            .await
+    }
+
+    /// Restore the soft-deleted session `id` (full ID or prefix).
+    ///
+    /// # Errors
+    ///
+    /// See [`IpcClient::send`].
+    pub async fn restore_session(&mut self, id: &str) -> Result<SessionDeletion> {
+        self.call(&IpcCommand::SessionRestore { id: id.to_owned() })
+            .await
+    }
+
+    /// Apply a bulk action; `command` must be [`IpcCommand::Sessions`].
+    ///
+    /// # Errors
+    ///
+    /// See [`IpcClient::send`].
+    pub async fn bulk_sessions(&mut self, command: &IpcCommand) -> Result<BulkOutcome> {
+        self.call(command).await
+    }
+
+    /// Set, reset or read the tracing filter; `command` must be
+    /// [`IpcCommand::LogLevel`].
+    ///
+    /// # Errors
+    ///
+    /// See [`IpcClient::send`]; an unknown level is rejected by the server.
+    pub async fn log_level(&mut self, command: &IpcCommand) -> Result<LogLevelOutcome> {
+        self.call(command).await
+    }
+
+    /// Read the Slack Socket Mode connection state.
+    ///
+    /// # Errors
+    ///
+    /// See [`IpcClient::send`]; servers without Slack refuse the command.
+    pub async fn slack_status(&mut self) -> Result<SlackConnectionOutcome> {
+        self.call(&IpcCommand::SlackStatus).await
+    }
+
+    /// Force a clean Slack Socket Mode reconnect.
+    ///
+    /// # Errors
+    ///
+    /// See [`IpcClient::send`]; the server refuses when Slack is not
+    /// configured or the listener is not running.
+    pub async fn slack_reconnect(&mut self) -> Result<SlackConnectionOutcome> {
+        self.call(&IpcCommand::SlackReconnect).await
+    }
+
+    /// Set fault injection; `command` must be [`IpcCommand::Chaos`].
+    ///
+    /// # Errors
+    ///
+    /// See [`IpcClient::send`]. Servers built without the `chaos` feature
+    /// refuse the command.
+    pub async fn chaos(&mut self, command: &IpcCommand) -> Result<ChaosOutcome> {
+        self.call(command).await
+    }
+}
@@ -0,0 +1,582 @@
+//! gRPC control plane (ADR-0019).
+//!
+//! Serves `proto/intercom_control.proto` on `127.0.0.1:{[grpc] port}` for
+//! tooling in languages where the JSON-line socket is awkward. Every unary
+//! RPC is translated into the matching [`IpcCommand`] and run through the
+//! IPC server's handlers, so the two surfaces cannot disagree about what a
+//! command does; `StreamEvents` follows the agent [`EventBus`].
+//!
+//! Callers authenticate with `authorization: Bearer <token>`, where the
+//! token is `[grpc]`'s runtime secret. Commands are audited like IPC
+//! commands, with the peer recorded as `grpc:<addr>`.
+//!
+//! [`EventBus`]: crate::driver::event_bus::EventBus
+
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use futures_util::Stream;
+use serde::de::DeserializeOwned;
+use tokio::sync::broadcast::error::RecvError;
+use tokio_util::sync::CancellationToken;
+use tonic::transport::server::TcpIncoming;
+use tonic::{Request, Response, Status};
+use tracing::{info, warn};
+
+use crate::driver::event_bus::BusEvent;
+use crate::driver::AgentEvent;
+use crate::ipc::client::{self, IpcCommand};
+use crate::ipc::server::run_command;
+use crate::models::session::{SessionMode, SessionStatus};
+use crate::models::steering::SteeringPriority;
+use crate::orchestrator::session_bulk::BulkAction;
+use crate::orchestrator::session_logs::LogLevel;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Types and stubs generated from `proto/intercom_control.proto`.
+#[allow(missing_docs, clippy::pedantic)]
+pub mod proto {
+    tonic::include_proto!("agent_intercom.control.v1");
+}
+
+use proto::intercom_control_server::{IntercomControl, IntercomControlServer};
+
+/// Bind the gRPC listener on `127.0.0.1:{[grpc] port}`.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` if the port cannot be bound.
+pub async fn bind_grpc(state: &AppState) -> Result<tokio::net::TcpListener> {
+    let bind = SocketAddr::from(([127, 0, 0, 1], state.config.grpc.port));
+    tokio::net::TcpListener::bind(bind)
+        .await
+        .map_err(|err| AppError::Config(format!("failed to bind gRPC on {bind}: {err}")))
+}
+
+/// Serve the control plane on a listener from [`bind_grpc`] until `ct` is
+/// cancelled. Open `StreamEvents` calls end at cancellation too.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` if no token is configured, and
+/// `AppError::Ipc` if the server fails.
+pub async fn serve_with_listener(
+    listener: tokio::net::TcpListener,
+    state: Arc<AppState>,
+    ct: CancellationToken,
+) -> Result<()> {
+    if state.config.grpc.token.is_empty() {
+        return Err(AppError::Config(
+            "[grpc] needs a token: set GRPC_TOKEN or the grpc_token keychain entry".into(),
+        ));
+    }
+    let token: Arc<str> = format!("Bearer {}", state.config.grpc.token).into();
+    let addr = listener.local_addr().ok();
+    let service = ControlService {
+        state,
+        ct: ct.clone(),
+    };
+    let server = IntercomControlServer::with_interceptor(service, move |request: Request<()>| {
+        check_token(&request, &token).map(|()| request)
+    });
+
+    info!(?addr, "gRPC control plane listening");
+    tonic::transport::Server::builder()
+        .add_service(server)
+        .serve_with_incoming_shutdown(TcpIncoming::from(listener), ct.cancelled_owned())
+        .await
+        .map_err(|err| AppError::Ipc(format!("gRPC server failed: {err}")))
+}
+
+/// Refuse requests whose `authorization` metadata is not `expected`.
+fn check_token(request: &Request<()>, expected: &str) -> std::result::Result<(), Status> {
+    match request.metadata().get("authorization") {
+        Some(value) if value.as_bytes() == expected.as_bytes() => Ok(()),
+        _ => {
+            warn!(peer = ?request.remote_addr(), "gRPC request rejected: invalid token");
+            Err(Status::unauthenticated("invalid or missing bearer token"))
+        }
+    }
+}
+
+/// [`IntercomControl`] backed by the IPC command handlers.
+struct ControlService {
+    state: Arc<AppState>,
+    ct: CancellationToken,
+}
+
+impl ControlService {
+    /// Run `command` and decode its payload as `T`.
+    async fn run<T: DeserializeOwned, R>(
+        &self,
+        request: &Request<R>,
+        command: IpcCommand,
+    ) -> std::result::Result<T, Status> {
+        let peer = request
+            .remote_addr()
+            .map_or_else(|| "grpc".to_owned(), |addr| format!("grpc:{addr}"));
+        let data = run_command(&command, &self.state, &peer)
+            .await
+            .map_err(Status::failed_precondition)?;
+        serde_json::from_value(data).map_err(|err| {
+            Status::internal(format!("unexpected {} response: {err}", command.name()))
+        })
+    }
+}
+
+type EventStream =
+    Pin<Box<dyn Stream<Item = std::result::Result<proto::AgentEvent, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl IntercomControl for ControlService {
+    async fn list_sessions(
+        &self,
+        request: Request<proto::ListSessionsRequest>,
+    ) -> std::result::Result<Response<proto::ListSessionsResponse>, Status> {
+        let tags = request.get_ref().tags.clone();
+        let data: ListData = self.run(&request, IpcCommand::List { tags }).await?;
+        let sessions = data
+            .sessions
+            .into_iter()
+            .map(|session| proto::SessionSummary {
+                status: session_status(session.status).into(),
+                mode: session_mode(session.mode).into(),
+                session_id: session.session_id,
+                workspace_root: session.workspace_root,
+                last_tool: session.last_tool,
+                updated_at: session.updated_at.to_rfc3339(),
+                tags: session.tags.into_iter().collect(),
+                short_id: session.short_id,
+            })
+            .collect();
+        Ok(Response::new(proto::ListSessionsResponse { sessions }))
+    }
+
+    async fn approve(
+        &self,
+        request: Request<proto::ApproveRequest>,
+    ) -> std::result::Result<Response<proto::ApprovalOutcome>, Status> {
+        let id = request.get_ref().request_id.clone();
+        let outcome: client::ApprovalOutcome =
+            self.run(&request, IpcCommand::Approve { id }).await?;
+        Ok(Response::new(approval_outcome(outcome)))
+    }
+
+    async fn reject(
+        &self,
+        request: Request<proto::RejectRequest>,
+    ) -> std::result::Result<Response<proto::ApprovalOutcome>, Status> {
+        let proto::RejectRequest { request_id, reason } = request.get_ref().clone();
+        let command = IpcCommand::Reject {
+            id: request_id,
+            reason,
+        };
+        let outcome: client::ApprovalOutcome = self.run(&request, command).await?;
+        Ok(Response::new(approval_outcome(outcome)))
+    }
+
+    async fn resume(
+        &self,
+        request: Request<proto::ResumeRequest>,
+    ) -> std::result::Result<Response<proto::ResumeOutcome>, Status> {
+        let proto::ResumeRequest {
+            session_id,
+            instruction,
+        } = request.get_ref().clone();
+        let command = IpcCommand::Resume {
+            id: session_id,
+            instruction,
+        };
+        let outcome: client::ResumeOutcome = self.run(&request, command).await?;
+        Ok(Response::new(proto::ResumeOutcome {
+            session_id: outcome.session_id,
+        }))
+    }
+
+    async fn set_mode(
+        &self,
+        request: Request<proto::SetModeRequest>,
+    ) -> std::result::Result<Response<proto::ModeChange>, Status> {
+        let mode = match request.get_ref().mode() {
+            proto::SessionMode::Remote => SessionMode::Remote,
+            proto::SessionMode::Local => SessionMode::Local,
+            proto::SessionMode::Hybrid => SessionMode::Hybrid,
+            proto::SessionMode::Unspecified => {
+                return Err(Status::invalid_argument("mode is required"));
+            }
+        };
+        let change: client::ModeChange = self.run(&request, IpcCommand::Mode { mode }).await?;
+        Ok(Response::new(proto::ModeChange {
+            previous_mode: session_mode(change.previous_mode).into(),
+            current_mode: session_mode(change.current_mode).into(),
+        }))
+    }
+
+    async fn steer(
+        &self,
+        request: Request<proto::SteerRequest>,
+    ) -> std::result::Result<Response<proto::SteerOutcome>, Status> {
+        let proto::SteerRequest {
+            instruction,
+            interrupt,
+            ttl,
+        } = request.get_ref().clone();
+        let command = IpcCommand::Steer {
+            instruction,
+            priority: interrupt.then_some(SteeringPriority::Interrupt),
+            ttl,
+        };
+        let outcome: client::SteerOutcome = self.run(&request, command).await?;
+        Ok(Response::new(proto::SteerOutcome {
+            session_id: outcome.session_id,
+            queued: outcome.queued,
+            delivered: outcome.delivered,
+            pushed: outcome.pushed,
+        }))
+    }
+
+    async fn queue_task(
+        &self,
+        request: Request<proto::QueueTaskRequest>,
+    ) -> std::result::Result<Response<proto::TaskOutcome>, Status> {
+        let instruction = request.get_ref().instruction.clone();
+        let outcome: client::TaskOutcome =
+            self.run(&request, IpcCommand::Task { instruction }).await?;
+        Ok(Response::new(proto::TaskOutcome {
+            task_id: outcome.task_id,
+        }))
+    }
+
+    async fn report(
+        &self,
+        request: Request<proto::ReportRequest>,
+    ) -> std::result::Result<Response<proto::ReportOutcome>, Status> {
+        let id = request.get_ref().session_id.clone();
+        let outcome: client::ReportOutcome = self.run(&request, IpcCommand::Report { id }).await?;
+        Ok(Response::new(proto::ReportOutcome {
+            session_id: outcome.session_id,
+            markdown: outcome.markdown,
+        }))
+    }
+
+    async fn logs(
+        &self,
+        request: Request<proto::LogsRequest>,
+    ) -> std::result::Result<Response<proto::LogsOutcome>, Status> {
+        let command = IpcCommand::Logs {
+            id: request.get_ref().session_id.clone(),
+            limit: request.get_ref().limit.map(|limit| limit as usize),
+        };
+        let outcome: client::LogsOutcome = self.run(&request, command).await?;
+        let lines = outcome
+            .lines
+            .into_iter()
+            .map(|line| proto::LogLine {
+                at: line.at.to_rfc3339(),
+                level: log_level(line.level).into(),
+                message: line.message,
+                fields_json: serde_json::Value::Object(line.fields).to_string(),
+            })
+            .collect();
+        Ok(Response::new(proto::LogsOutcome {
+            session_id: outcome.session_id,
+            buffered: u32::try_from(outcome.buffered).unwrap_or(u32::MAX),
+            lines,
+        }))
+    }
+
+    async fn delete_session(
+        &self,
+        request: Request<proto::SessionDeletionRequest>,
+    ) -> std::result::Result<Response<proto::SessionDeletion>, Status> {
+        let id = request.get_ref().session_id.clone();
+        let outcome: client::SessionDeletion =
+            self.run(&request, IpcCommand::SessionDelete { id }).await?;
+        Ok(Response::new(session_deletion(outcome)))
+    }
+
+    async fn restore_session(
+        &self,
+        request: Request<proto::SessionDeletionRequest>,
+    ) -> std::result::Result<Response<proto::SessionDeletion>, Status> {
+        let id = request.get_ref().session_id.clone();
+        let outcome: client::SessionDeletion = self
+            .run(&request, IpcCommand::SessionRestore { id })
+            .await?;
+        Ok(Response::new(session_deletion(outcome)))
+    }
+
+    async fn bulk_sessions(
+        &self,
+        request: Request<proto::BulkSessionsRequest>,
+    ) -> std::result::Result<Response<proto::BulkOutcome>, Status> {
+        let body = request.get_ref();
+        let action = match body.action() {
+            proto::BulkAction::Clear => BulkAction::Clear,
+            proto::BulkAction::Pause => BulkAction::Pause,
+            proto::BulkAction::Delete => BulkAction::Delete,
+            proto::BulkAction::Unspecified => {
+                return Err(Status::invalid_argument("action is required"));
+            }
+        };
+        let statuses = body
+            .statuses()
+            .map(|status| match status {
+                proto::SessionStatus::Created => Ok(SessionStatus::Created),
+                proto::SessionStatus::Active => Ok(SessionStatus::Active),
+                proto::SessionStatus::Paused => Ok(SessionStatus::Paused),
+                proto::SessionStatus::Terminated => Ok(SessionStatus::Terminated),
+                proto::SessionStatus::Interrupted => Ok(SessionStatus::Interrupted),
+                proto::SessionStatus::Unspecified => {
+                    Err(Status::invalid_argument("statuses must not be unspecified"))
+                }
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let command = IpcCommand::Sessions {
+            action,
+            statuses,
+            owner: body.owner.clone(),
+            older_than: body.older_than.clone(),
+            all: body.all,
+        };
+        let outcome: crate::orchestrator::session_bulk::BulkOutcome =
+            self.run(&request, command).await?;
+        Ok(Response::new(proto::BulkOutcome {
+            action: body.action,
+            matched: u32::try_from(outcome.matched).unwrap_or(u32::MAX),
+            succeeded: outcome.succeeded,
+            failed: outcome
+                .failed
+                .into_iter()
+                .map(|failure| proto::BulkFailure {
+                    session_id: failure.session_id,
+                    error: failure.error,
+                })
+                .collect(),
+        }))
+    }
+
+    async fn set_log_level(
+        &self,
+        request: Request<proto::LogLevelRequest>,
+    ) -> std::result::Result<Response<proto::LogLevelOutcome>, Status> {
+        let proto::LogLevelRequest {
+            level,
+            target,
+            reset,
+        } = request.get_ref().clone();
+        let command = IpcCommand::LogLevel {
+            level,
+            target,
+            reset,
+        };
+        let outcome: client::LogLevelOutcome = self.run(&request, command).await?;
+        Ok(Response::new(proto::LogLevelOutcome {
+            filter: outcome.filter,
+        }))
+    }
+
+    async fn slack_status(
+        &self,
+        request: Request<proto::SlackConnectionRequest>,
+    ) -> std::result::Result<Response<proto::SlackConnectionOutcome>, Status> {
+        let outcome: client::SlackConnectionOutcome =
+            self.run(&request, IpcCommand::SlackStatus).await?;
+        Ok(Response::new(slack_connection(outcome)))
+    }
+
+    async fn slack_reconnect(
+        &self,
+        request: Request<proto::SlackConnectionRequest>,
+    ) -> std::result::Result<Response<proto::SlackConnectionOutcome>, Status> {
+        let outcome: client::SlackConnectionOutcome =
+            self.run(&request, IpcCommand::SlackReconnect).await?;
+        Ok(Response::new(slack_connection(outcome)))
+    }
+
+    type StreamEventsStream = EventStream;
+
+    async fn stream_events(
+        &self,
+        request: Request<proto::StreamEventsRequest>,
+    ) -> std::result::Result<Response<Self::StreamEventsStream>, Status> {
+        let filter = request.into_inner();
+        let events = self.state.event_bus.subscribe();
+        let stream = futures_util::stream::unfold(
+            (events, self.ct.clone(), filter, 0_u64),
+            |(mut events, ct, filter, mut lagged)| async move {
+                loop {
+                    let next = tokio::select! {
+                        () = ct.cancelled() => return None,
+                        next = events.recv() => next,
+                    };
+                    match next {
+                        Ok(event) if wanted(&filter, &event) => {
+                            let item = agent_event(&event, lagged);
+                            return Some((Ok(item), (events, ct, filter, 0)));
+                        }
+                        Ok(_) => {}
+                        Err(RecvError::Lagged(skipped)) => lagged += skipped,
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            },
+        );
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+/// `list` payload.
+#[derive(serde::Deserialize)]
+struct ListData {
+    sessions: Vec<client::SessionSummary>,
+}
+
+/// Whether `event` passes a `StreamEvents` filter.
+fn wanted(filter: &proto::StreamEventsRequest, event: &BusEvent) -> bool {
+    (filter.session_ids.is_empty()
+        || filter
+            .session_ids
+            .iter()
+            .any(|id| id == event.event.session_id()))
+        && (filter.kinds.is_empty() || filter.kinds.iter().any(|kind| kind == event.event.kind()))
+}
+
+/// Convert a bus event, noting how many events were skipped before it.
+fn agent_event(event: &BusEvent, lagged: u64) -> proto::AgentEvent {
+    proto::AgentEvent {
+        source: event.source.as_str().to_owned(),
+        session_id: event.event.session_id().to_owned(),
+        kind: event.event.kind().to_owned(),
+        channel_id: event.channel_id.clone(),
+        payload_json: event_payload(&event.event).to_string(),
+        lagged,
+    }
+}
+
+/// Event-specific fields of `event` as a JSON object.
+fn event_payload(event: &AgentEvent) -> serde_json::Value {
+    match event {
+        AgentEvent::ClearanceRequested {
+            request_id,
+            title,
+            description,
+            diff,
+            file_path,
+            risk_level,
+            ..
+        } => serde_json::json!({
+            "request_id": request_id,
+            "title": title,
+            "description": description,
+            "diff": diff,
+            "file_path": file_path,
+            "risk_level": risk_level,
+        }),
+        AgentEvent::PermissionRequested {
+            request_id,
+            title,
+            description,
+            file_path,
+            risk_level,
+            options,
+            ..
+        } => serde_json::json!({
+            "request_id": request_id,
+            "title": title,
+            "description": description,
+            "file_path": file_path,
+            "risk_level": risk_level,
+            "options": options
+                .iter()
+                .map(|option| serde_json::json!({
+                    "option_id": option.option_id,
+                    "name": option.name,
+                    "kind": option.kind,
+                }))
+                .collect::<Vec<_>>(),
+        }),
+        AgentEvent::StatusUpdated { message, .. } => serde_json::json!({ "message": message }),
+        AgentEvent::PromptForwarded {
+            prompt_id,
+            prompt_text,
+            prompt_type,
+            ..
+        } => serde_json::json!({
+            "prompt_id": prompt_id,
+            "prompt_text": prompt_text,
+            "prompt_type": prompt_type,
+        }),
+        AgentEvent::HeartbeatReceived { progress, .. } => {
+            serde_json::json!({ "progress": progress })
+        }
+        AgentEvent::SessionTerminated {
+            exit_code, reason, ..
+        } => serde_json::json!({ "exit_code": exit_code, "reason": reason }),
+        AgentEvent::StreamActivity { .. } => serde_json::json!({}),
+        AgentEvent::HooksSubscribed { events, .. } => serde_json::json!({ "events": events }),
+        AgentEvent::NoticePosted { message, .. } => serde_json::json!({
+            "channel_id": message.channel.0,
+            "thread_ts": message.thread_ts.as_ref().map(|ts| ts.0.as_str()),
+            "text": message.text,
+        }),
+    }
+}
+
+fn approval_outcome(outcome: client::ApprovalOutcome) -> proto::ApprovalOutcome {
+    proto::ApprovalOutcome {
+        request_id: outcome.request_id,
+        status: serde_json::to_value(outcome.status)
+            .ok()
+            .and_then(|status| status.as_str().map(str::to_owned))
+            .unwrap_or_default(),
+    }
+}
+
+fn session_deletion(outcome: client::SessionDeletion) -> proto::SessionDeletion {
+    proto::SessionDeletion {
+        session_id: outcome.session_id,
+        deleted: outcome.deleted,
+    }
+}
+
+fn slack_connection(outcome: client::SlackConnectionOutcome) -> proto::SlackConnectionOutcome {
+    let socket = outcome.socket;
+    proto::SlackConnectionOutcome {
+        connected: socket.connected,
+        connected_since: socket.connected_since.map(|at| at.to_rfc3339()),
+        last_hello: socket.last_hello.map(|at| at.to_rfc3339()),
+        disconnects: socket.disconnects,
+        forced_reconnects: socket.forced_reconnects,
+        reconnecting: outcome.reconnecting,
+    }
+}
+
+fn session_status(status: SessionStatus) -> proto::SessionStatus {
+    match status {
+        SessionStatus::Created => proto::SessionStatus::Created,
+        SessionStatus::Active => proto::SessionStatus::Active,
+        SessionStatus::Paused => proto::SessionStatus::Paused,
+        SessionStatus::Terminated => proto::SessionStatus::Terminated,
+        SessionStatus::Interrupted => proto::SessionStatus::Interrupted,
+    }
+}
+
+fn session_mode(mode: SessionMode) -> proto::SessionMode {
+    match mode {
+        SessionMode::Remote => proto::SessionMode::Remote,
+        SessionMode::Local => proto::SessionMode::Local,
+        SessionMode::Hybrid => proto::SessionMode::Hybrid,
+    }
+}
+
+fn log_level(level: LogLevel) -> proto::LogLevel {
+    match level {
+        LogLevel::Debug => proto::LogLevel::Debug,
+        LogLevel::Info => proto::LogLevel::Info,
+        LogLevel::Warn => proto::LogLevel::Warn,
+        LogLevel::Error => proto::LogLevel::Error,
+    }
+}
@@ -1,7 +1,14 @@
 //! Local IPC layer for `agent-intercom-ctl` interaction.
 //!
 //! Provides a named pipe (Windows) or Unix domain socket (Linux/macOS)
-//! server that accepts JSON-line commands from the companion CLI.
+//! server that accepts JSON-line commands from the companion CLI, and the
+//! typed client the CLI uses to talk to it. On Windows the pipe is
+//! restricted to the server's own account (see [`security`]). Builds with
+//! the `grpc` feature also serve the same commands over gRPC (`grpc`).
 
+pub mod client;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod security;
 pub mod server;
 pub mod socket;
@@ -0,0 +1,136 @@
+//! Access control for the IPC socket.
+//!
+//! `GenericNamespaced` names map to `\\.\pipe\<name>` on Windows, which by
+//! default any local user can open. The server therefore creates the pipe
+//! with an explicit, protected DACL: full access for the pipe's owner (the
+//! account the server runs as) and `SYSTEM`, plus any groups listed in
+//! `ipc_allowed_groups`. Everyone else gets `ERROR_ACCESS_DENIED` on connect.
+//! The descriptor is expressed in SDDL so it can be built and tested on any
+//! platform; only turning it into a security descriptor is Windows-only.
+//!
+//! On Unix the socket file is created `0600` where the platform supports it,
+//! but Linux puts namespaced sockets in the abstract namespace, which has no
+//! file permissions at all. Every connection is therefore also checked by
+//! peer credentials (`SO_PEERCRED`): only the server's own UID and
+//! `ipc_allowed_uids` may issue commands.
+
+use std::fmt::Write as _;
+
+use crate::{AppError, Result};
+
+/// Access granted to every allowed principal: `GENERIC_ALL`.
+const FULL_ACCESS: &str = "GA";
+
+/// Principals always allowed: the pipe's owner (`OW`, Owner Rights) and the
+/// local system account (`SY`).
+const ALWAYS_ALLOWED: [&str; 2] = ["OW", "SY"];
+
+/// Check that `group` is a SID string (`S-1-5-32-544`) or a two-letter SDDL
+/// alias (`BA`, `IU`, …).
+///
+/// # Errors
+///
+/// Returns `AppError::Config` naming the entry otherwise. Account names are
+/// rejected because resolving them needs a Windows API call at startup.
+pub fn validate_group(group: &str) -> Result<()> {
+    let is_alias = group.len() == 2 && group.bytes().all(|b| b.is_ascii_uppercase());
+    let is_sid = group.strip_prefix("S-1-").is_some_and(|rest| {
+        rest.split('-')
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+    });
+    if is_alias || is_sid {
+        Ok(())
+    } else {
+        Err(AppError::Config(format!(
+            "ipc_allowed_groups entry '{group}' must be a SID such as S-1-5-32-544 \
+             or an SDDL alias such as BA"
+        )))
+    }
+}
+
+/// SDDL for the pipe's security descriptor: a protected DACL allowing the
+/// owner, `SYSTEM`, and `allowed_groups`.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` if any group fails [`validate_group`].
+pub fn pipe_sddl(allowed_groups: &[String]) -> Result<String> {
+    let mut sddl = String::from("D:P");
+    for principal in ALWAYS_ALLOWED {
+        let _ = write!(sddl, "(A;;{FULL_ACCESS};;;{principal})");
+    }
+    for group in allowed_groups {
+        validate_group(group)?;
+        if !ALWAYS_ALLOWED.contains(&group.as_str()) {
+            let _ = write!(sddl, "(A;;{FULL_ACCESS};;;{group})");
+        }
+    }
+    Ok(sddl)
+}
+
+/// Build the pipe's security descriptor for `allowed_groups`.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for an invalid group and `AppError::Ipc` if
+/// Windows rejects the descriptor.
+#[cfg(windows)]
+pub fn pipe_security_descriptor(
+    allowed_groups: &[String],
+) -> Result<interprocess::os::windows::security_descriptor::SecurityDescriptor> {
+    use interprocess::os::windows::security_descriptor::SecurityDescriptor;
+
+    let sddl = pipe_sddl(allowed_groups)?;
+    let wide = widestring::U16CString::from_str(&sddl)
+        .map_err(|err| AppError::Ipc(format!("invalid pipe security descriptor: {err}")))?;
+    SecurityDescriptor::deserialize(&wide)
+        .map_err(|err| AppError::Ipc(format!("invalid pipe security descriptor '{sddl}': {err}")))
+}
+
+/// Whether a Unix peer running as `peer_uid` may issue IPC commands: it must
+/// be the server's own UID or listed in `allowed_uids`.
+#[must_use]
+pub fn uid_allowed(peer_uid: u32, server_uid: u32, allowed_uids: &[u32]) -> bool {
+    peer_uid == server_uid || allowed_uids.contains(&peer_uid)
+}
+
+/// UID the server runs as.
+#[cfg(unix)]
+#[must_use]
+pub fn server_uid() -> u32 {
+    nix::unistd::getuid().as_raw()
+}
+
+/// UID of the process on the other end of `stream`.
+///
+/// # Errors
+///
+/// Returns the OS error if the peer credentials cannot be read.
+#[cfg(unix)]
+pub fn peer_uid(stream: &interprocess::local_socket::tokio::Stream) -> std::io::Result<u32> {
+    match stream {
+        interprocess::local_socket::tokio::Stream::UdSocket(socket) => {
+            socket.inner().peer_cred().map(|cred| cred.uid())
+        }
+    }
+}
+
+/// How the audit log names the process on the other end of `stream`:
+/// `ipc:uid=<uid>` on Unix, `ipc:pid=<pid>` on Windows, or `ipc:unknown`
+/// when the OS will not say.
+#[must_use]
+pub fn peer_identity(stream: &interprocess::local_socket::tokio::Stream) -> String {
+    #[cfg(unix)]
+    let peer = peer_uid(stream).map(|uid| format!("uid={uid}"));
+    #[cfg(windows)]
+    let peer = match stream {
+        interprocess::local_socket::tokio::Stream::NamedPipe(pipe) => pipe
+            .inner()
+            .client_process_id()
+            .map(|pid| format!("pid={pid}")),
+    };
+    match peer {
+        Ok(peer) => format!("ipc:{peer}"),
+        Err(_) => "ipc:unknown".to_owned(),
+    }
+}
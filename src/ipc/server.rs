@@ -13,6 +13,14 @@
 //! {"command": "reject", "id": "req-123", "reason": "too risky"}
 //! {"command": "resume", "instruction": "deploy to staging"}
 //! {"command": "mode", "mode": "local"}
+//! {"command": "report", "id": "3f2a9c1e"}
+//! {"command": "logs", "id": "3f2a9c1e", "limit": 200}
+//! {"command": "session-delete", "id": "3f2a9c1e"}
+//! {"command": "session-restore", "id": "3f2a9c1e"}
+//! {"command": "sessions", "action": "clear", "statuses": ["interrupted"], "older_than": "7d"}
+//! {"command": "log-level", "level": "debug", "target": "agent_intercom::slack"}
+//! {"command": "log-level", "reset": true}
+//! {"command": "chaos", "drop_percent": 50, "delay_ms": 2000, "kill_socket": true}
 //! ```
 //!
 //! Response (one JSON object per line):
@@ -20,6 +28,11 @@
 //! {"ok": true, "data": { ... } }
 //! {"ok": false, "error": "not found"}
 //! ```
+//!
+//! `proto/intercom_control.proto` mirrors these commands for the gRPC
+//! control plane in `ipc::grpc` (ADR-0019); keep the two in step. `chaos`
+//! is a debug command, served only by builds with the `chaos` feature, and
+//! stays out of the proto.
 
 use std::sync::Arc;
 
@@ -29,12 +42,20 @@ use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio_util::sync::CancellationToken;
 use tracing::{info, info_span, warn, Instrument};
 
-use crate::models::session::SessionMode;
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::driver::session_hooks::SessionHookEvent;
+use crate::ipc::security;
+use crate::models::session::{parse_tag, SessionMode, SessionStatus};
+use crate::models::steering::SteeringPriority;
+use crate::orchestrator::session_bulk::{self, BulkAction, SessionSelector};
+use crate::orchestrator::session_logs::{DEFAULT_LOG_TAIL, LOG_BUFFER_CAPACITY};
+use crate::orchestrator::session_report::{find_session, SessionReport};
 use crate::persistence::approval_repo::ApprovalRepo;
 use crate::persistence::session_repo::SessionRepo;
+use crate::slack::handlers::parse_duration;
 use crate::slack::handlers::steer as steer_handler;
 use crate::slack::handlers::task as task_handler;
-use crate::state::{AppState, ApprovalResponse, WaitResponse};
+use crate::state::AppState;
 use crate::{AppError, Result};
 
 /// Inbound IPC request from `agent-intercom-ctl`.
@@ -42,7 +63,8 @@ use crate::{AppError, Result};
 struct IpcRequest {
     /// Command verb.
     command: String,
-    /// Entity identifier (for `approve`, `reject`).
+    /// Entity identifier (for `approve`, `reject`, `report`, `logs`,
+    /// `session-delete`, `session-restore`).
     id: Option<String>,
     /// Rejection reason or resume instruction text.
     reason: Option<String>,
@@ -50,6 +72,45 @@ struct IpcRequest {
     instruction: Option<String>,
     /// Target mode (for `mode` command).
     mode: Option<String>,
+    /// Steering priority: `normal` (default) or `interrupt` (for `steer`).
+    priority: Option<String>,
+    /// Steering time-to-live such as `10m` (for `steer`).
+    ttl: Option<String>,
+    /// Number of lines to return (for `logs`).
+    limit: Option<usize>,
+    /// Bulk action (for `sessions`).
+    action: Option<BulkAction>,
+    /// Statuses to select (for `sessions`; the action's defaults if absent).
+    statuses: Option<Vec<SessionStatus>>,
+    /// Owner to select (for `sessions`).
+    owner: Option<String>,
+    /// Minimum time since the last update, such as `7d` (for `sessions`).
+    older_than: Option<String>,
+    /// Act on every session when no filter is given (for `sessions`).
+    #[serde(default)]
+    all: bool,
+    /// `key=value` tags a listed session must carry (for `list`).
+    #[serde(default)]
+    tags: Vec<String>,
+    /// Level to set (for `log-level`); omit to read the current filter.
+    level: Option<String>,
+    /// Tracing target the level applies to (for `log-level`); the default
+    /// level when absent.
+    target: Option<String>,
+    /// Percentage of Slack posts to drop (for `chaos`).
+    #[cfg(feature = "chaos")]
+    drop_percent: Option<u8>,
+    /// Milliseconds to hold each oneshot resolution (for `chaos`).
+    #[cfg(feature = "chaos")]
+    delay_ms: Option<u64>,
+    /// Kill the Socket Mode connection (for `chaos`).
+    #[cfg(feature = "chaos")]
+    #[serde(default)]
+    kill_socket: bool,
+    /// Restore the startup filter (for `log-level`), or turn every fault
+    /// off before applying the others (for `chaos`).
+    #[serde(default)]
+    reset: bool,
     /// Shared-secret authentication token.
     auth_token: Option<String>,
 }
@@ -89,7 +150,10 @@ impl IpcResponse {
 ///
 /// # Errors
 ///
-/// Returns `AppError::Ipc` if the listener cannot be created.
+/// Returns `AppError::Ipc` if the listener cannot be created, or on Windows
+/// `AppError::Config` if `ipc_allowed_groups` is invalid. On Unix, peers
+/// outside `ipc_allowed_uids` are answered with `unauthorized` and
+/// disconnected.
 pub fn spawn_ipc_server(
     state: Arc<AppState>,
     ct: CancellationToken,
@@ -101,10 +165,31 @@ pub fn spawn_ipc_server(
         .to_ns_name::<GenericNamespaced>()
         .map_err(|err| AppError::Ipc(format!("invalid ipc socket name '{name}': {err}")))?;
 
-    let listener = ListenerOptions::new()
-        .name(listener_name)
-        .create_tokio()
-        .map_err(|err| AppError::Ipc(format!("failed to create ipc listener: {err}")))?;
+    let options = ListenerOptions::new().name(listener_name.clone());
+    #[cfg(windows)]
+    let listener = {
+        use interprocess::os::windows::local_socket::ListenerOptionsExt;
+        options
+            .security_descriptor(security::pipe_security_descriptor(
+                &state.config.ipc_allowed_groups,
+            )?)
+            .create_tokio()
+    };
+    #[cfg(unix)]
+    let listener = {
+        use interprocess::os::unix::local_socket::ListenerOptionsExt;
+        // Owner-only socket file. Platforms that cannot set it (and Linux
+        // abstract names, which ignore it) rely on the peer UID check.
+        match options.mode(0o600).create_tokio() {
+            Err(err) if err.kind() == std::io::ErrorKind::Unsupported => {
+                warn!(%err, "IPC socket file mode unsupported; relying on peer UID checks");
+                ListenerOptions::new().name(listener_name).create_tokio()
+            }
+            result => result,
+        }
+    };
+    let listener =
+        listener.map_err(|err| AppError::Ipc(format!("failed to create ipc listener: {err}")))?;
 
     info!(ipc_name = %name, "IPC server listening");
 
@@ -145,6 +230,17 @@ async fn handle_connection(
 ) {
     let span = info_span!("ipc_conn");
     async move {
+        let peer = security::peer_identity(&stream);
+        #[cfg(unix)]
+        if let Err(message) = check_peer(&stream, &state) {
+            let response = IpcResponse::error(message);
+            audit_command(&state, &peer, "connect", None, &response);
+            let (_, mut writer) = stream.split();
+            let response = serde_json::to_string(&response).unwrap_or_default();
+            let _ = writer.write_all(format!("{response}\n").as_bytes()).await;
+            return;
+        }
+
         let (reader, mut writer) = stream.split();
         let mut buf_reader = BufReader::new(reader);
         let mut line = String::new();
@@ -159,10 +255,17 @@ async fn handle_connection(
                         continue;
                     }
 
-                    let response = match serde_json::from_str::<IpcRequest>(trimmed) {
-                        Ok(request) => dispatch_command(&request, &state).await,
-                        Err(err) => IpcResponse::error(format!("invalid json: {err}")),
+                    let (verb, response) = match serde_json::from_str::<IpcRequest>(trimmed) {
+                        Ok(request) => {
+                            let response = dispatch_command(&request, &state).await;
+                            (request.command, response)
+                        }
+                        Err(err) => (
+                            "invalid".to_owned(),
+                            IpcResponse::error(format!("invalid json: {err}")),
+                        ),
                     };
+                    audit_command(&state, &peer, &verb, Some(trimmed), &response);
 
                     let mut response_line = serde_json::to_string(&response).unwrap_or_else(|_| {
                         r#"{"ok":false,"error":"serialization failed"}"#.to_owned()
@@ -187,6 +290,73 @@ async fn handle_connection(
     .await;
 }
 
+/// Refuse a Unix peer whose UID is neither the server's nor in
+/// `ipc_allowed_uids`.
+#[cfg(unix)]
+fn check_peer(
+    stream: &interprocess::local_socket::tokio::Stream,
+    state: &AppState,
+) -> std::result::Result<(), String> {
+    let peer_uid = security::peer_uid(stream).map_err(|err| {
+        warn!(%err, "IPC connection refused: peer credentials unavailable");
+        "unauthorized: peer credentials unavailable".to_owned()
+    })?;
+    if security::uid_allowed(
+        peer_uid,
+        security::server_uid(),
+        &state.config.ipc_allowed_uids,
+    ) {
+        Ok(())
+    } else {
+        warn!(peer_uid, "IPC connection refused: uid not allowed");
+        Err(format!("unauthorized: uid {peer_uid} is not allowed"))
+    }
+}
+
+/// Record one IPC command in the audit log: the verb, its arguments
+/// without the auth token, the peer, and the outcome.
+fn audit_command(
+    state: &AppState,
+    peer: &str,
+    verb: &str,
+    raw: Option<&str>,
+    response: &IpcResponse,
+) {
+    let Some(ref logger) = state.audit_logger else {
+        return;
+    };
+    let mut entry = AuditEntry::new(AuditEventType::IpcCommand)
+        .with_command(verb.to_owned())
+        .with_operator(peer.to_owned())
+        .with_result(match response.error {
+            Some(ref error) => format!("error: {error}"),
+            None => "ok".to_owned(),
+        });
+    if let Some(serde_json::Value::Object(mut args)) =
+        raw.and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+    {
+        args.remove("command");
+        args.remove("auth_token");
+        if matches!(verb, "approve" | "reject") {
+            if let Some(id) = args.get("id").and_then(serde_json::Value::as_str) {
+                entry = entry.with_request_id(id.to_owned());
+            }
+        }
+        entry = entry.with_parameters(serde_json::Value::Object(args));
+    }
+    if let Some(session_id) = response
+        .data
+        .as_ref()
+        .and_then(|data| data.get("session_id"))
+        .and_then(serde_json::Value::as_str)
+    {
+        entry = entry.with_session(session_id.to_owned());
+    }
+    if let Err(err) = logger.log_entry(entry) {
+        warn!(%err, "audit log write failed (ipc command)");
+    }
+}
+
 /// Route an IPC command to the appropriate handler.
 async fn dispatch_command(request: &IpcRequest, state: &Arc<AppState>) -> IpcResponse {
     let span = info_span!("ipc_command", command = %request.command);
@@ -203,33 +373,152 @@ async fn dispatch_command(request: &IpcRequest, state: &Arc<AppState>) -> IpcRes
         }
     }
 
+    route_command(request, state).await
+}
+
+/// Run `command` as if it had arrived on the socket, for control surfaces
+/// that authenticate their callers themselves (the gRPC service). The
+/// command is audited under `peer`. Returns the response payload, or the
+/// handler's error message.
+#[cfg(feature = "grpc")]
+pub(crate) async fn run_command(
+    command: &crate::ipc::client::IpcCommand,
+    state: &Arc<AppState>,
+    peer: &str,
+) -> std::result::Result<serde_json::Value, String> {
+    let raw = serde_json::to_string(command).map_err(|err| err.to_string())?;
+    let response = match serde_json::from_str::<IpcRequest>(&raw) {
+        Ok(request) => {
+            let span = info_span!("ipc_command", command = %request.command);
+            route_command(&request, state).instrument(span).await
+        }
+        Err(err) => IpcResponse::error(format!("invalid command: {err}")),
+    };
+    audit_command(state, peer, command.name(), Some(&raw), &response);
+    match response.error {
+        Some(error) => Err(error),
+        None => Ok(response.data.unwrap_or_default()),
+    }
+}
+
+/// Route an authenticated command to its handler.
+async fn route_command(request: &IpcRequest, state: &Arc<AppState>) -> IpcResponse {
     match request.command.as_str() {
-        "list" => handle_list(state).await,
+        "list" => handle_list(request, state).await,
         "approve" => handle_approve(request, state).await,
         "reject" => handle_reject(request, state).await,
         "resume" => handle_resume(request, state).await,
         "mode" => handle_mode(request, state).await,
         "steer" => handle_steer(request, state).await,
         "task" => handle_task(request, state).await,
+        "report" => handle_report(request, state).await,
+        "logs" => handle_logs(request, state).await,
+        "session-delete" => handle_session_deleted(request, state, true).await,
+        "session-restore" => handle_session_deleted(request, state, false).await,
+        "sessions" => handle_sessions_bulk(request, state).await,
+        "log-level" => handle_log_level(request, state),
+        "slack-status" => handle_slack_connection(state, false),
+        "slack-reconnect" => handle_slack_connection(state, true),
+        #[cfg(feature = "chaos")]
+        "chaos" => handle_chaos(request),
+        #[cfg(not(feature = "chaos"))]
+        "chaos" => IpcResponse::error("chaos faults need a server built with `--features chaos`"),
         other => IpcResponse::error(format!("unknown command: {other}")),
     }
 }
 
-/// List active sessions.
-async fn handle_list(state: &Arc<AppState>) -> IpcResponse {
+/// Set fault injection and report the settings now in force.
+#[cfg(feature = "chaos")]
+fn handle_chaos(request: &IpcRequest) -> IpcResponse {
+    let faults = crate::chaos::faults();
+    if request.reset {
+        faults.reset();
+    }
+    if let Some(percent) = request.drop_percent {
+        faults.set_drop_percent(percent);
+    }
+    if let Some(delay_ms) = request.delay_ms {
+        faults.set_resolution_delay(std::time::Duration::from_millis(delay_ms));
+    }
+    let socket_killed = request.kill_socket && faults.kill_socket();
+    warn!(
+        drop_percent = faults.drop_percent(),
+        delay_ms = faults.resolution_delay().as_millis(),
+        socket_killed,
+        "chaos faults updated via IPC"
+    );
+    IpcResponse::success(serde_json::json!({
+        "drop_percent": faults.drop_percent(),
+        "delay_ms": faults.resolution_delay().as_millis(),
+        "socket_killed": socket_killed,
+    }))
+}
+
+/// Change, reset or read the tracing filter.
+fn handle_log_level(request: &IpcRequest, state: &AppState) -> IpcResponse {
+    let Some(ref filter) = state.log_filter else {
+        return IpcResponse::error("this server does not support runtime log levels");
+    };
+    let result = if request.reset {
+        filter.reset()
+    } else if let Some(ref level) = request.level {
+        filter.set(level, request.target.as_deref())
+    } else {
+        Ok(filter.current())
+    };
+    match result {
+        Ok(current) => {
+            if request.reset || request.level.is_some() {
+                info!(filter = %current, "log filter changed via IPC");
+            }
+            IpcResponse::success(serde_json::json!({ "filter": current }))
+        }
+        Err(err) => IpcResponse::error(format!("failed to set log level: {err}")),
+    }
+}
+
+/// Report the Socket Mode connection state, after asking the listener to
+/// reconnect when `reconnect` is set.
+fn handle_slack_connection(state: &AppState, reconnect: bool) -> IpcResponse {
+    let Some(ref slack) = state.slack else {
+        return IpcResponse::error("slack is not configured on this server");
+    };
+    let health = slack.socket_health();
+    if reconnect && !health.request_reconnect() {
+        return IpcResponse::error("socket mode listener is not running");
+    }
+    let mut body = serde_json::to_value(health.snapshot()).unwrap_or_default();
+    body["reconnecting"] = serde_json::Value::Bool(reconnect);
+    IpcResponse::success(body)
+}
+
+/// List active sessions, narrowed to those carrying every requested tag.
+async fn handle_list(request: &IpcRequest, state: &Arc<AppState>) -> IpcResponse {
+    let tags = match request
+        .tags
+        .iter()
+        .map(|raw| parse_tag(raw))
+        .collect::<Result<Vec<_>>>()
+    {
+        Ok(tags) => tags,
+        Err(err) => return IpcResponse::error(err.to_string()),
+    };
     let session_repo = SessionRepo::new(Arc::clone(&state.db));
     match session_repo.list_active().await {
         Ok(sessions) => {
             let items: Vec<serde_json::Value> = sessions
                 .iter()
+                .filter(|s| s.has_tags(&tags))
                 .map(|s| {
                     serde_json::json!({
                         "session_id": s.id,
+                        "short_id": s.short_id,
                         "status": format!("{:?}", s.status).to_lowercase(),
                         "mode": format!("{:?}", s.mode).to_lowercase(),
                         "workspace_root": s.workspace_root,
                         "last_tool": s.last_tool,
                         "updated_at": s.updated_at.to_rfc3339(),
+                        "tags": s.tags,
                     })
                 })
                 .collect();
@@ -239,6 +528,118 @@ async fn handle_list(state: &Arc<AppState>) -> IpcResponse {
     }
 }
 
+/// Render the Markdown report for a session (full ID or prefix) via IPC.
+async fn handle_report(request: &IpcRequest, state: &Arc<AppState>) -> IpcResponse {
+    let Some(ref id) = request.id else {
+        return IpcResponse::error("missing required 'id' field");
+    };
+    match SessionReport::load(&state.db, id).await {
+        Ok(report) => IpcResponse::success(serde_json::json!({
+            "session_id": report.session.id,
+            "markdown": report.to_markdown(),
+        })),
+        Err(err) => IpcResponse::error(format!("failed to build report: {err}")),
+    }
+}
+
+/// Return the newest log lines a session (full ID or prefix) streamed.
+async fn handle_logs(request: &IpcRequest, state: &Arc<AppState>) -> IpcResponse {
+    let Some(ref id) = request.id else {
+        return IpcResponse::error("missing required 'id' field");
+    };
+    let limit = request.limit.unwrap_or(DEFAULT_LOG_TAIL);
+    if !(1..=LOG_BUFFER_CAPACITY).contains(&limit) {
+        return IpcResponse::error(format!("limit must be from 1 to {LOG_BUFFER_CAPACITY}"));
+    }
+    match find_session(&state.db, id).await {
+        Ok(session) => IpcResponse::success(serde_json::json!({
+            "session_id": session.id,
+            "buffered": state.session_logs.buffered(&session.id),
+            "lines": state.session_logs.tail(&session.id, limit),
+        })),
+        Err(err) => IpcResponse::error(format!("failed to read logs: {err}")),
+    }
+}
+
+/// Soft-delete an ended session (full ID or prefix), hiding it from
+/// listings and recovery, or restore it (`deleted` false).
+async fn handle_session_deleted(
+    request: &IpcRequest,
+    state: &Arc<AppState>,
+    deleted: bool,
+) -> IpcResponse {
+    let Some(ref id) = request.id else {
+        return IpcResponse::error("missing required 'id' field");
+    };
+    let verb = if deleted { "delete" } else { "restore" };
+    let session = match find_session(&state.db, id).await {
+        Ok(session) => session,
+        Err(err) => return IpcResponse::error(format!("failed to {verb} session: {err}")),
+    };
+    if deleted
+        && !matches!(
+            session.status,
+            SessionStatus::Terminated | SessionStatus::Interrupted
+        )
+    {
+        return IpcResponse::error(format!(
+            "session {} is {}; only ended sessions can be deleted",
+            session.id,
+            session.status.as_str()
+        ));
+    }
+    if session.deleted_at.is_some() == deleted {
+        let state_label = if deleted {
+            "already deleted"
+        } else {
+            "not deleted"
+        };
+        return IpcResponse::error(format!("session {} is {state_label}", session.id));
+    }
+
+    if let Err(err) = SessionRepo::new(Arc::clone(&state.db))
+        .set_deleted(&session.id, deleted)
+        .await
+    {
+        return IpcResponse::error(format!("failed to {verb} session: {err}"));
+    }
+    info!(session_id = %session.id, deleted, "session visibility changed via IPC");
+    IpcResponse::success(serde_json::json!({
+        "session_id": session.id,
+        "deleted": deleted,
+    }))
+}
+
+/// Apply a bulk action to every session the request's filters select.
+async fn handle_sessions_bulk(request: &IpcRequest, state: &Arc<AppState>) -> IpcResponse {
+    let Some(action) = request.action else {
+        return IpcResponse::error("missing required 'action' field");
+    };
+    let older_than = match request
+        .older_than
+        .as_deref()
+        .map(parse_duration)
+        .transpose()
+    {
+        Ok(age) => age,
+        Err(err) => return IpcResponse::error(err.to_string()),
+    };
+    let selector = SessionSelector {
+        statuses: request.statuses.clone().unwrap_or_default(),
+        owner: request.owner.clone(),
+        older_than,
+    };
+    if !selector.is_narrowed() && !request.all {
+        return IpcResponse::error(
+            "refusing to act on every session: give a status, owner or age filter, or 'all'",
+        );
+    }
+    match session_bulk::apply(state, action, &selector).await {
+        Ok(outcome) => IpcResponse::success(serde_json::to_value(outcome).unwrap_or_default()),
+        Err(err) => IpcResponse::error(format!("failed to select sessions: {err}")),
+    }
+}
+
 /// Approve a pending approval request via IPC.
 async fn handle_approve(request: &IpcRequest, state: &Arc<AppState>) -> IpcResponse {
     let Some(ref id) = request.id else {
@@ -247,6 +648,10 @@ async fn handle_approve(request: &IpcRequest, state: &Arc<AppState>) -> IpcRespo
 
     // Update DB status.
     let approval_repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let id = &match request_uuid(&approval_repo, id).await {
+        Ok(id) => id,
+        Err(err) => return IpcResponse::error(format!("failed to approve: {err}")),
+    };
     if let Err(err) = approval_repo
         .update_status(id, crate::models::approval::ApprovalStatus::Approved)
         .await
@@ -254,17 +659,7 @@ async fn handle_approve(request: &IpcRequest, state: &Arc<AppState>) -> IpcRespo
         return IpcResponse::error(format!("failed to approve: {err}"));
     }
 
-    // Resolve the pending oneshot.
-    {
-        let mut pending = state.pending_approvals.lock().await;
-        if let Some(tx) = pending.remove(id.as_str()) {
-            let response = ApprovalResponse {
-                status: "approved".to_owned(),
-                reason: None,
-            };
-            let _ = tx.send(response);
-        }
-    }
+    resolve_clearance(state, &approval_repo, id, true, None).await;
 
     info!(request_id = %id, "approved via IPC");
     IpcResponse::success(serde_json::json!({ "request_id": id, "status": "approved" }))
@@ -283,6 +678,10 @@ async fn handle_reject(request: &IpcRequest, state: &Arc<AppState>) -> IpcRespon
 
     // Update DB status.
     let approval_repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let id = &match request_uuid(&approval_repo, id).await {
+        Ok(id) => id,
+        Err(err) => return IpcResponse::error(format!("failed to reject: {err}")),
+    };
     if let Err(err) = approval_repo
         .update_status(id, crate::models::approval::ApprovalStatus::Rejected)
         .await
@@ -290,57 +689,96 @@ async fn handle_reject(request: &IpcRequest, state: &Arc<AppState>) -> IpcRespon
         return IpcResponse::error(format!("failed to reject: {err}"));
     }
 
-    // Resolve the pending oneshot.
-    {
-        let mut pending = state.pending_approvals.lock().await;
-        if let Some(tx) = pending.remove(id.as_str()) {
-            let response = ApprovalResponse {
-                status: "rejected".to_owned(),
-                reason: Some(reason.clone()),
-            };
-            let _ = tx.send(response);
-        }
-    }
+    resolve_clearance(state, &approval_repo, id, false, Some(reason.clone())).await;
 
     info!(request_id = %id, "rejected via IPC");
     IpcResponse::success(serde_json::json!({ "request_id": id, "status": "rejected" }))
 }
 
+/// UUID of the approval request `id` names, which may be its short ID
+/// (`apr-9mxq`) or a unique prefix. Unknown IDs are passed through
+/// unchanged so the status update reports them.
+async fn request_uuid(approval_repo: &ApprovalRepo, id: &str) -> Result<String> {
+    Ok(approval_repo
+        .resolve(id)
+        .await?
+        .map_or_else(|| id.to_owned(), |approval| approval.id))
+}
+
+/// Deliver a clearance decision to the agent that requested it.
+///
+/// MCP tool calls park on the shared oneshot map even when the session itself
+/// speaks ACP (tools called over HTTP), so that map is tried first; otherwise
+/// the decision is routed through the driver bound to the approval's session.
+/// Shared with the approval webhook and approval links.
+pub(crate) async fn resolve_clearance(
+    state: &Arc<AppState>,
+    approval_repo: &ApprovalRepo,
+    request_id: &str,
+    approved: bool,
+    reason: Option<String>,
+) {
+    let mcp = state.mcp_driver();
+    if mcp
+        .resolve_clearance(request_id, approved, reason.clone())
+        .await
+        .is_ok()
+    {
+        return;
+    }
+
+    let session_id = approval_repo
+        .get_by_id(request_id)
+        .await
+        .ok()
+        .flatten()
+        .map(|record| record.session_id)
+        .unwrap_or_default();
+    let driver = state.driver_for(&session_id).await;
+    if let Err(err) = driver.resolve_clearance(request_id, approved, reason).await {
+        warn!(request_id, session_id, %err, "no agent waiting on clearance");
+    }
+}
+
 /// Resume a waiting agent via IPC.
 ///
-/// When `request.id` contains a session ID, that specific session is resumed.
-/// Otherwise the first pending wait is used (for single-session scenarios).
+/// When `request.id` contains a session ID, that specific session is resumed
+/// through its registered driver. Otherwise the first pending wait is used
+/// (for single-session scenarios).
 async fn handle_resume(request: &IpcRequest, state: &Arc<AppState>) -> IpcResponse {
     let instruction = request.instruction.clone();
 
-    // Prefer explicit session_id from the request when available.
-    let session_id = if let Some(ref sid) = request.id {
-        let pending = state.pending_waits.lock().await;
-        if pending.contains_key(sid) {
-            Some(sid.clone())
+    // Prefer explicit session_id from the request when available; it may be
+    // a prefix or short ID.
+    let session_id = if let Some(ref raw) = request.id {
+        let sid = &match find_session(&state.db, raw).await {
+            Ok(session) => session.id,
+            Err(AppError::NotFound(_)) => raw.clone(),
+            Err(err) => return IpcResponse::error(format!("failed to resume: {err}")),
+        };
+        let mcp_waiting = state.pending_waits.lock().await.contains_key(sid);
+        let driver = if mcp_waiting {
+            state.mcp_driver()
+        } else if state.driver_registry.contains(sid).await {
+            state.driver_for(sid).await
         } else {
             return IpcResponse::error(format!("session {sid} is not waiting"));
+        };
+        if let Err(err) = driver.resolve_wait(sid, instruction.clone()).await {
+            return IpcResponse::error(format!("failed to resume session {sid}: {err}"));
         }
+        sid.clone()
     } else {
         let pending = state.pending_waits.lock().await;
-        pending.keys().next().cloned()
-    };
-
-    let Some(session_id) = session_id else {
-        return IpcResponse::error("no agent currently waiting for instruction");
-    };
-
-    // Resolve the oneshot.
-    {
-        let mut pending = state.pending_waits.lock().await;
-        if let Some(tx) = pending.remove(&session_id) {
-            let response = WaitResponse {
-                status: "resumed".to_owned(),
-                instruction: instruction.clone(),
-            };
-            let _ = tx.send(response);
+        let Some(sid) = pending.keys().next().cloned() else {
+            return IpcResponse::error("no agent currently waiting for instruction");
+        };
+        drop(pending);
+        if let Err(err) = state.mcp_driver().resolve_wait(&sid, instruction).await {
+            return IpcResponse::error(format!("failed to resume session {sid}: {err}"));
         }
-    }
+        sid
+    };
 
     info!(session_id = %session_id, "agent resumed via IPC");
     IpcResponse::success(serde_json::json!({ "session_id": session_id, "status": "resumed" }))
@@ -375,6 +813,16 @@ async fn handle_mode(request: &IpcRequest, state: &Arc<AppState>) -> IpcResponse
         return IpcResponse::error(format!("failed to update mode: {err}"));
     }
 
+    state
+        .notify_session_hook(
+            &session.id,
+            SessionHookEvent::ModeChanged {
+                previous_mode,
+                current_mode: mode,
+            },
+        )
+        .await;
+
     info!(
         session_id = %session.id,
         ?previous_mode,
@@ -407,7 +855,21 @@ async fn handle_steer(request: &IpcRequest, state: &Arc<AppState>) -> IpcRespons
         return IpcResponse::error("missing required 'instruction' field (the message text)");
     };
 
-    match steer_handler::store_from_ipc(text, state).await {
+    let mut options = steer_handler::SteerOptions::default();
+    match request.priority.as_deref() {
+        None | Some("normal") => {}
+        Some("interrupt") => options.priority = SteeringPriority::Interrupt,
+        Some(other) => return IpcResponse::error(format!("invalid priority: {other}")),
+    }
+    if let Some(ref ttl) = request.ttl {
+        match crate::slack::handlers::parse_duration(ttl) {
+            Ok(ttl) => options.ttl = Some(ttl),
+            Err(AppError::Config(msg)) => return IpcResponse::error(msg),
+            Err(err) => return IpcResponse::error(err.to_string()),
+        }
+    }
+
+    match steer_handler::store_from_ipc_with(text, options, state).await {
         Ok(data) => IpcResponse::success(data),
         Err(AppError::Config(msg)) => IpcResponse::error(msg),
         Err(err) => IpcResponse::error(format!("steer failed: {err}")),
@@ -8,20 +8,28 @@
 
 pub mod acp;
 pub mod audit;
+#[cfg(feature = "chaos")]
+pub mod chaos;
 pub mod config;
+pub mod config_env;
 pub mod config_watcher;
 pub mod diff;
 pub mod driver;
 pub mod errors;
+pub mod integrations;
 pub mod ipc;
+pub mod log_filter;
 pub mod mcp;
 pub mod mode;
 pub mod models;
 pub mod orchestrator;
 pub mod persistence;
 pub mod policy;
+pub mod server;
 pub mod slack;
 pub mod state;
+#[cfg(feature = "test-support")]
+pub mod test_support;
 
 pub use config::GlobalConfig;
 pub use errors::{AppError, Result};
@@ -0,0 +1,136 @@
+//! Runtime control of the tracing filter (`log-level` IPC command).
+//!
+//! The server binary installs its `EnvFilter` behind a
+//! [`tracing_subscriber::reload`] layer and hands the reload handle to
+//! [`LogFilter::new`]. Operators then raise or lower the level of the
+//! whole server, or of one target such as `agent_intercom::slack`, with
+//! `agent-intercom-ctl log-level debug [target]`, capturing debug logs for a
+//! misbehaving subsystem without restarting and losing in-flight sessions.
+//! `log-level reset` restores the filter the server started with.
+
+use std::sync::{Mutex, PoisonError};
+
+use tracing_subscriber::reload::Handle;
+use tracing_subscriber::EnvFilter;
+
+use crate::{AppError, Result};
+
+/// Levels accepted by [`LogFilter::set`].
+pub const LEVELS: [&str; 6] = ["trace", "debug", "info", "warn", "error", "off"];
+
+type Apply = Box<dyn Fn(EnvFilter) -> Result<()> + Send + Sync>;
+
+/// The live tracing filter and the directives it was built from.
+pub struct LogFilter {
+    initial: Vec<String>,
+    directives: Mutex<Vec<String>>,
+    apply: Apply,
+}
+
+impl std::fmt::Debug for LogFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LogFilter")
+            .field("current", &self.current())
+            .finish_non_exhaustive()
+    }
+}
+
+impl LogFilter {
+    /// Control the filter behind `handle`, which was built from `spec`
+    /// (`RUST_LOG` syntax, e.g. `info,sqlx=warn`).
+    #[must_use]
+    pub fn new<S: 'static>(handle: Handle<EnvFilter, S>, spec: &str) -> Self {
+        Self::with_apply(spec, move |filter| {
+            handle
+                .reload(filter)
+                .map_err(|err| AppError::Config(format!("failed to reload log filter: {err}")))
+        })
+    }
+
+    /// Control a filter through `apply`, which installs each new filter.
+    #[must_use]
+    pub fn with_apply(
+        spec: &str,
+        apply: impl Fn(EnvFilter) -> Result<()> + Send + Sync + 'static,
+    ) -> Self {
+        let initial: Vec<String> = spec
+            .split(',')
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+            .map(str::to_owned)
+            .collect();
+        Self {
+            directives: Mutex::new(initial.clone()),
+            initial,
+            apply: Box::new(apply),
+        }
+    }
+
+    /// The filter now in force, in `RUST_LOG` syntax.
+    #[must_use]
+    pub fn current(&self) -> String {
+        self.directives
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .join(",")
+    }
+
+    /// Set `level` for `target`, or the default level when `target` is
+    /// `None`, keeping every other directive. Returns the new filter.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` for an unknown level, a target the
+    /// filter syntax rejects, or a failed reload.
+    pub fn set(&self, level: &str, target: Option<&str>) -> Result<String> {
+        let level = level.to_ascii_lowercase();
+        if !LEVELS.contains(&level.as_str()) {
+            return Err(AppError::Config(format!(
+                "unknown log level '{level}' (expected one of {})",
+                LEVELS.join(", ")
+            )));
+        }
+        let mut directives = self
+            .directives
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut next = directives.clone();
+        if let Some(target) = target {
+            if target.is_empty() || target.contains([',', '=', ' ']) {
+                return Err(AppError::Config(format!("invalid log target '{target}'")));
+            }
+            let prefix = format!("{target}=");
+            next.retain(|d| !d.starts_with(&prefix) && d != target);
+            next.push(format!("{target}={level}"));
+        } else {
+            // A bare level is the default; per-target directives stay.
+            next.retain(|d| d.contains('='));
+            next.insert(0, level);
+        }
+        self.install(&next)?;
+        *directives = next;
+        Ok(directives.join(","))
+    }
+
+    /// Restore the filter the server started with. Returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` if the reload fails.
+    pub fn reset(&self) -> Result<String> {
+        let mut directives = self
+            .directives
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        self.install(&self.initial)?;
+        directives.clone_from(&self.initial);
+        Ok(directives.join(","))
+    }
+
+    fn install(&self, directives: &[String]) -> Result<()> {
+        let spec = directives.join(",");
+        let filter = EnvFilter::try_new(&spec)
+            .map_err(|err| AppError::Config(format!("invalid log filter '{spec}': {err}")))?;
+        (self.apply)(filter)
+    }
+}
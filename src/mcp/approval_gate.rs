@@ -0,0 +1,168 @@
+//! Operator approval for actions that are not file changes.
+//!
+//! Proxied tool calls and agent-requested subtasks are approved through the
+//! same records and buttons as `check_clearance`: an [`ApprovalRequest`] is
+//! persisted, posted with Accept/Reject buttons in the session's Slack
+//! thread, and resolved by the shared approval handlers through
+//! `pending_approvals`. Such requests carry a sentinel `original_hash`, so
+//! they can never be applied through `check_diff`. The request message is
+//! the request's [`Anchor`]: a timeout replaces its buttons and is noted in
+//! its thread.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
+use tokio::sync::oneshot;
+use tracing::warn;
+
+use crate::driver::AgentEvent;
+use crate::mcp::approval_link;
+use crate::mcp::proxy::PROXY_CALL_HASH;
+use crate::mcp::tools::spawn_subtask::SUBTASK_HASH;
+use crate::models::approval::{ApprovalRequest, ApprovalStatus};
+use crate::models::session::{ProtocolMode, Session};
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::slack::anchor::Anchor;
+use crate::slack::blocks;
+use crate::slack::client::{SlackMessage, SlackService};
+use crate::state::{AppState, ApprovalResponse};
+
+/// Whether an approval request with `original_hash` is posted by
+/// [`request_decision`] rather than by the Slack request subscriber.
+#[must_use]
+pub fn posts_own_card(original_hash: &str) -> bool {
+    [PROXY_CALL_HASH, SUBTASK_HASH].contains(&original_hash)
+}
+
+/// Outcome of an approval request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision {
+    /// The operator accepted.
+    Approved,
+    /// The operator rejected, with an optional reason.
+    Rejected(Option<String>),
+    /// The pending request was dropped without a decision.
+    Abandoned,
+    /// No decision arrived within the approval timeout (seconds).
+    TimedOut(u64),
+}
+
+/// Post `approval` in `session`'s thread on `channel` and wait for the
+/// operator's decision.
+///
+/// `text` is the notification fallback of the Slack message. A request
+/// that times out is marked `Expired` and a notice is posted in the thread.
+///
+/// # Errors
+///
+/// Returns `rmcp::ErrorData` if the request cannot be persisted.
+pub async fn request_decision(
+    state: &Arc<AppState>,
+    slack: &SlackService,
+    channel: &str,
+    session: &Session,
+    approval: &ApprovalRequest,
+    text: String,
+) -> std::result::Result<Decision, rmcp::ErrorData> {
+    let approval_repo = ApprovalRepo::new(Arc::clone(&state.db));
+    approval_repo.create(approval).await.map_err(|err| {
+        rmcp::ErrorData::internal_error(format!("failed to persist approval request: {err}"), None)
+    })?;
+    let request_id = approval.id.clone();
+
+    state.event_bus.publish(
+        ProtocolMode::Mcp,
+        Some(channel.to_owned()),
+        AgentEvent::ClearanceRequested {
+            request_id: request_id.clone(),
+            session_id: session.id.clone(),
+            title: approval.title.clone(),
+            description: approval.description.clone().unwrap_or_default(),
+            diff: Some(approval.diff_content.clone()),
+            file_path: approval.file_path.clone(),
+            risk_level: approval.risk_level.as_str().to_owned(),
+            snippets: Vec::new(),
+        },
+    );
+
+    let (tx, rx) = oneshot::channel::<ApprovalResponse>();
+    state
+        .pending_approvals
+        .lock()
+        .await
+        .insert(request_id.clone(), tx);
+
+    let mut message_blocks = blocks::build_approval_blocks(
+        &approval.title,
+        approval.description.as_deref(),
+        &approval.diff_content,
+        &approval.file_path,
+        approval.risk_level,
+    );
+    message_blocks.extend(
+        blocks::hunk_page_blocks(&request_id, &approval.diff_content, 0)
+            .into_iter()
+            .flatten(),
+    );
+    if let Some(link) = approval_link::slack_line(&state.config.approval_links, &request_id) {
+        message_blocks.push(blocks::text_section(&link));
+    }
+    message_blocks.push(blocks::text_section(&blocks::expiry_line(
+        chrono::Utc::now(),
+        state.config.timeouts.approval_seconds,
+    )));
+    message_blocks.push(blocks::approval_buttons(&request_id));
+    let msg = SlackMessage {
+        channel: SlackChannelId(channel.to_owned()),
+        text: Some(text),
+        blocks: Some(message_blocks),
+        thread_ts: session.thread_ts.clone().map(SlackTs),
+    };
+    let anchor = Anchor::post(slack, msg).await;
+    if let Some(ref anchor) = anchor {
+        if let Err(err) = approval_repo
+            .set_slack_anchor(&request_id, &anchor.ts.0, &anchor.thread_ts.0)
+            .await
+        {
+            warn!(%err, request_id, "failed to record approval message anchor");
+        }
+    }
+
+    let timeout_seconds = state.config.timeouts.approval_seconds;
+    let response = state
+        .snoozes
+        .wait(&request_id, Duration::from_secs(timeout_seconds), rx)
+        .await;
+    state.pending_approvals.lock().await.remove(&request_id);
+
+    Ok(match response {
+        Some(Ok(resp)) if resp.status == "approved" => Decision::Approved,
+        Some(Ok(resp)) => Decision::Rejected(resp.reason),
+        Some(Err(_)) => Decision::Abandoned,
+        None => {
+            let _ = approval_repo
+                .decide(&request_id, ApprovalStatus::Expired)
+                .await;
+            if let Some(anchor) = anchor {
+                anchor
+                    .resolve(
+                        slack,
+                        &format!("\u{23f1}\u{fe0f} *Timed out* \u{2014} {}", approval.title),
+                    )
+                    .await;
+                anchor
+                    .follow_up(
+                        slack,
+                        format!(
+                            "\u{23f1}\u{fe0f} Approval for *{}* timed out after {timeout_seconds} seconds",
+                            approval.title
+                        ),
+                        None,
+                    )
+                    .await;
+            }
+            Decision::TimedOut(timeout_seconds)
+        }
+    })
+}
@@ -0,0 +1,357 @@
+//! Signed approval links for deciding from a browser.
+//!
+//! When `[approval_links] enabled = true`, every approval notification
+//! carries a short-lived URL, `{base_url}/approvals/{id}?exp=…&sig=…`.
+//! Opening it renders a minimal approval page (title, file, risk, diff)
+//! with Approve and Reject buttons; submitting the form resolves the
+//! request exactly as a Slack button would.
+//!
+//! ## Signing
+//!
+//! `sig` is the hex HMAC-SHA256 of `approval-link.<id>.<exp>` keyed with
+//! the link secret, and `exp` is the Unix time the link stops working.
+//! The link is a bearer credential: anyone holding it can decide the
+//! request until it expires, so it is only sent to the session's channel
+//! and owner. Opening the page never decides anything — only the form
+//! post does — so mail scanners that prefetch links are harmless.
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::get;
+use axum::Form;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use tracing::{info, warn};
+
+use super::approval_webhook::{
+    apply_decision, decode_hex, Acting, Applied, Decider, WebhookDecision,
+};
+use crate::config::ApprovalLinksConfig;
+use crate::integrations::email::{approval_status_label, escape_html};
+use crate::models::approval::{ApprovalRequest, ApprovalStatus};
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::slack::blocks::format_elapsed;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Route path of the approval page.
+pub const LINK_PATH: &str = "/approvals/{id}";
+
+/// Diff lines shown on the page; longer diffs are cut with a note.
+const MAX_DIFF_LINES: usize = 400;
+
+/// `exp` and `sig` query parameters of a link. Missing values fail
+/// verification rather than the extractor, so every bad link gets the same
+/// page.
+#[derive(Debug, Default, Deserialize)]
+struct LinkQuery {
+    #[serde(default)]
+    exp: i64,
+    #[serde(default)]
+    sig: String,
+}
+
+/// Form posted by the approval page.
+#[derive(Debug, Deserialize)]
+struct DecisionForm {
+    #[serde(default)]
+    exp: i64,
+    #[serde(default)]
+    sig: String,
+    decision: WebhookDecision,
+    #[serde(default)]
+    reason: String,
+}
+
+/// Signature domain of approval links.
+const LINK_SCOPE: &str = "approval-link";
+
+/// Hex signature of the link for `request_id` expiring at `expires`.
+#[must_use]
+pub fn sign(secret: &str, request_id: &str, expires: i64) -> String {
+    sign_scoped(secret, LINK_SCOPE, request_id, expires)
+}
+
+/// Check a link's expiry and signature.
+///
+/// # Errors
+///
+/// Returns `AppError::Unauthorized` when the link expired before `now` or
+/// the signature does not match.
+pub fn verify(
+    secret: &str,
+    request_id: &str,
+    expires: i64,
+    signature: &str,
+    now: i64,
+) -> Result<()> {
+    verify_scoped(secret, LINK_SCOPE, request_id, expires, signature, now)
+}
+
+/// Hex HMAC-SHA256 of `<scope>.<subject>.<expires>`. The scope keeps links
+/// of one kind from being replayed as another.
+pub(crate) fn sign_scoped(secret: &str, scope: &str, subject: &str, expires: i64) -> String {
+    mac(secret, scope, subject, expires)
+        .finalize()
+        .into_bytes()
+        .iter()
+        .fold(String::new(), |mut out, byte| {
+            let _ = write!(out, "{byte:02x}");
+            out
+        })
+}
+
+/// [`verify`] for links signed with [`sign_scoped`].
+pub(crate) fn verify_scoped(
+    secret: &str,
+    scope: &str,
+    subject: &str,
+    expires: i64,
+    signature: &str,
+    now: i64,
+) -> Result<()> {
+    let invalid = || AppError::Unauthorized("invalid link signature".into());
+    if secret.is_empty() {
+        return Err(invalid());
+    }
+    if expires <= now {
+        return Err(AppError::Unauthorized("link has expired".into()));
+    }
+    let digest = decode_hex(signature).ok_or_else(invalid)?;
+    mac(secret, scope, subject, expires)
+        .verify_slice(&digest)
+        .map_err(|_| invalid())
+}
+
+/// Signed link for `request_id`, valid for `ttl_seconds` from `now`, or
+/// `None` when approval links are disabled or have no signing secret.
+#[must_use]
+pub fn link_url(config: &ApprovalLinksConfig, request_id: &str, now: i64) -> Option<String> {
+    if !config.enabled || config.secret.is_empty() {
+        return None;
+    }
+    let base = config.base_url.as_deref()?.trim_end_matches('/');
+    let expires = now.saturating_add(i64::try_from(config.ttl_seconds).unwrap_or(i64::MAX));
+    let sig = sign(&config.secret, request_id, expires);
+    Some(format!(
+        "{base}/approvals/{request_id}?exp={expires}&sig={sig}"
+    ))
+}
+
+/// How long a new link stays valid, e.g. `15m 0s`.
+#[must_use]
+pub fn ttl_label(config: &ApprovalLinksConfig) -> String {
+    format_elapsed(i64::try_from(config.ttl_seconds).unwrap_or(i64::MAX))
+}
+
+/// Slack mrkdwn line linking to the approval page for `request_id`, or
+/// `None` when approval links are disabled.
+#[must_use]
+pub fn slack_line(config: &ApprovalLinksConfig, request_id: &str) -> Option<String> {
+    let url = link_url(config, request_id, chrono::Utc::now().timestamp())?;
+    Some(format!(
+        "\u{1f517} <{url}|Decide in a browser> \u{b7} expires in {}",
+        ttl_label(config)
+    ))
+}
+
+/// Router serving the approval page and its form.
+pub fn router(state: Arc<AppState>) -> axum::Router {
+    axum::Router::new()
+        .route(LINK_PATH, get(show).post(decide))
+        .with_state(state)
+}
+
+fn mac(secret: &str, scope: &str, subject: &str, expires: i64) -> Hmac<Sha256> {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(secret.as_bytes())
+        .unwrap_or_else(|_| unreachable!("HMAC accepts keys of any length"));
+    mac.update(scope.as_bytes());
+    mac.update(b".");
+    mac.update(subject.as_bytes());
+    mac.update(b".");
+    mac.update(expires.to_string().as_bytes());
+    mac
+}
+
+/// Check the link, logging refusals.
+fn check(state: &AppState, request_id: &str, expires: i64, signature: &str) -> Result<()> {
+    verify(
+        &state.config.approval_links.secret,
+        request_id,
+        expires,
+        signature,
+        chrono::Utc::now().timestamp(),
+    )
+    .inspect_err(|err| warn!(request_id, %err, "approval link refused"))
+}
+
+/// Handler for `GET /approvals/{id}`: the approval page.
+async fn show(
+    State(state): State<Arc<AppState>>,
+    Path(request_id): Path<String>,
+    Query(query): Query<LinkQuery>,
+) -> Response {
+    if let Err(err) = check(&state, &request_id, query.exp, &query.sig) {
+        return page(StatusCode::UNAUTHORIZED, "Link not valid", &err.to_string());
+    }
+    match ApprovalRepo::new(Arc::clone(&state.db))
+        .get_by_id(&request_id)
+        .await
+    {
+        Ok(Some(approval)) if approval.status == ApprovalStatus::Pending => (
+            StatusCode::OK,
+            Html(render_request(
+                &approval,
+                &[("exp", query.exp.to_string()), ("sig", query.sig.clone())],
+            )),
+        )
+            .into_response(),
+        Ok(Some(approval)) => already_decided(approval.status),
+        Ok(None) => not_found(),
+        Err(err) => page(StatusCode::INTERNAL_SERVER_ERROR, "Error", &err.to_string()),
+    }
+}
+
+/// Handler for `POST /approvals/{id}`: apply the operator's decision.
+async fn decide(
+    State(state): State<Arc<AppState>>,
+    Path(request_id): Path<String>,
+    Form(form): Form<DecisionForm>,
+) -> Response {
+    if let Err(err) = check(&state, &request_id, form.exp, &form.sig) {
+        return page(StatusCode::UNAUTHORIZED, "Link not valid", &err.to_string());
+    }
+
+    let approved = form.decision == WebhookDecision::Approve;
+    let reason = (!approved).then(|| {
+        let reason = form.reason.trim();
+        if reason.is_empty() {
+            "rejected via approval link".to_owned()
+        } else {
+            reason.to_owned()
+        }
+    });
+    let decider = Decider {
+        operator: "approval-link".to_owned(),
+        via: "via approval link".to_owned(),
+        acting: Acting::SessionOwner,
+    };
+
+    let result = apply_decision(&state, &request_id, approved, &decider, reason).await;
+    if matches!(result, Ok(Applied::Decided(_))) {
+        info!(request_id, approved, "approval resolved via approval link");
+    }
+    decision_page(result, approved)
+}
+
+/// The page answering a decision form.
+pub(crate) fn decision_page(result: Result<Applied>, approved: bool) -> Response {
+    match result {
+        Ok(Applied::Decided(status)) => page(
+            StatusCode::OK,
+            if approved { "Approved" } else { "Rejected" },
+            &format!("The request is now {}.", approval_status_label(status)),
+        ),
+        Ok(Applied::Unknown) => not_found(),
+        Ok(Applied::NotPending(status)) => already_decided(status),
+        Ok(Applied::Refused(message)) => page(StatusCode::FORBIDDEN, "Not allowed", &message),
+        Err(err) => page(StatusCode::INTERNAL_SERVER_ERROR, "Error", &err.to_string()),
+    }
+}
+
+/// `404` page for an unknown request.
+pub(crate) fn not_found() -> Response {
+    page(
+        StatusCode::NOT_FOUND,
+        "Not found",
+        "This approval request does not exist.",
+    )
+}
+
+/// `409` page for a request that is no longer pending.
+pub(crate) fn already_decided(status: ApprovalStatus) -> Response {
+    page(
+        StatusCode::CONFLICT,
+        "Already decided",
+        &format!("This request is {}.", approval_status_label(status)),
+    )
+}
+
+/// A page with one heading and one message.
+pub(crate) fn page(status: StatusCode, heading: &str, message: &str) -> Response {
+    let body = format!(
+        "<h1>{}</h1><p>{}</p>",
+        escape_html(heading),
+        escape_html(message)
+    );
+    (status, Html(document(heading, &body))).into_response()
+}
+
+/// The approval page for a pending `approval`, whose form posts back to
+/// the page's own URL with `hidden` fields added.
+pub(crate) fn render_request(approval: &ApprovalRequest, hidden: &[(&str, String)]) -> String {
+    let mut body = format!("<h1>{}</h1><dl>", escape_html(&approval.title));
+    let _ = write!(
+        body,
+        "<dt>File</dt><dd><code>{}</code></dd><dt>Risk</dt><dd>{}</dd></dl>",
+        escape_html(&approval.file_path),
+        approval.risk_level.as_str()
+    );
+    if let Some(ref description) = approval.description {
+        let _ = write!(body, "<p>{}</p>", escape_html(description));
+    }
+    if !approval.diff_content.is_empty() {
+        let lines: Vec<&str> = approval.diff_content.lines().collect();
+        let shown = lines
+            .iter()
+            .take(MAX_DIFF_LINES)
+            .copied()
+            .collect::<Vec<_>>()
+            .join("\n");
+        let _ = write!(body, "<pre>{}</pre>", escape_html(&shown));
+        if lines.len() > MAX_DIFF_LINES {
+            let _ = write!(
+                body,
+                "<p><em>{} more lines not shown.</em></p>",
+                lines.len() - MAX_DIFF_LINES
+            );
+        }
+    }
+    body.push_str("<form method=\"post\">");
+    for (name, value) in hidden {
+        let _ = write!(
+            body,
+            "<input type=\"hidden\" name=\"{name}\" value=\"{}\">",
+            escape_html(value)
+        );
+    }
+    body.push_str(
+        "<textarea name=\"reason\" placeholder=\"Reason (optional, for rejections)\"></textarea>\
+         <button name=\"decision\" value=\"approve\">Approve</button>\
+         <button name=\"decision\" value=\"reject\" class=\"reject\">Reject</button>\
+         </form>",
+    );
+    document(&approval.title, &body)
+}
+
+/// Wrap `body` in a small, mobile-friendly HTML document.
+pub(crate) fn document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\">\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+         <meta name=\"referrer\" content=\"no-referrer\">\
+         <title>{}</title><style>\
+         body{{font-family:sans-serif;max-width:48rem;margin:1rem auto;padding:0 1rem}}\
+         pre{{overflow-x:auto;background:#f4f4f4;padding:.5rem}}\
+         textarea{{width:100%;min-height:4rem;margin:.5rem 0}}\
+         button{{font-size:1.1rem;padding:.6rem 1.4rem;margin-right:.5rem}}\
+         .reject{{color:#b00}}\
+         </style></head><body>{body}</body></html>",
+        escape_html(title)
+    )
+}
@@ -0,0 +1,533 @@
+//! Inbound approval webhooks for chat bots and CI systems.
+//!
+//! `POST /api/v1/approvals/{id}/decision` accepts or rejects a pending
+//! approval request, resolving the waiting agent exactly as a Slack button
+//! or `agent-intercom-ctl approve` would. `GET /api/v1/approvals/{id}/wait`
+//! long-polls for the decision, so an agent whose blocking `check_clearance`
+//! call was severed by a transport drop can still learn it, along with any
+//! rejection reason. Mounted on the HTTP transport when
+//! `[webhooks] enabled = true`; the wait endpoint only when a separate wait
+//! secret is configured.
+//!
+//! ## Signing
+//!
+//! Callers send the Unix time in [`TIMESTAMP_HEADER`] and
+//! `sha256=<hex>` in [`SIGNATURE_HEADER`], where the hex digest is the
+//! HMAC-SHA256 of `<timestamp>.<path>.<body>` keyed with the shared secret.
+//! Covering the path binds a signature to one approval request, so a
+//! captured decision cannot be replayed against another. A wait request
+//! has an empty body and is keyed with the read-only wait secret: it can
+//! observe decisions but not make them.
+//! Requests whose timestamp is more than `max_skew_seconds` away from the
+//! server clock are refused, which bounds replays.
+//!
+//! ```json
+//! { "decision": "approve" | "reject", "reason": "optional", "actor": "optional" }
+//! ```
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Bytes;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::Json;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
+use tracing::{info, warn};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::diff::ownership;
+use crate::ipc::server::resolve_clearance;
+use crate::models::approval::{ApprovalRequest, ApprovalStatus};
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::blocks;
+use crate::slack::handlers::check_approval_authority;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Header carrying the request's Unix timestamp in seconds.
+pub const TIMESTAMP_HEADER: &str = "x-intercom-timestamp";
+
+/// Header carrying the request signature, `sha256=<hex>`.
+pub const SIGNATURE_HEADER: &str = "x-intercom-signature";
+
+/// Route path of the decision endpoint.
+pub const DECISION_PATH: &str = "/api/v1/approvals/{id}/decision";
+
+/// Route path of the long-poll wait endpoint.
+pub const WAIT_PATH: &str = "/api/v1/approvals/{id}/wait";
+
+/// Seconds a wait request holds when it gives no `timeout`.
+const DEFAULT_WAIT_SECONDS: u64 = 30;
+
+/// Longest a single wait request may hold, in seconds.
+pub const MAX_WAIT_SECONDS: u64 = 300;
+
+/// Operator decision carried by a webhook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDecision {
+    /// Accept the request.
+    Approve,
+    /// Reject the request.
+    Reject,
+}
+
+/// JSON body of a decision webhook.
+#[derive(Debug, Deserialize)]
+struct DecisionBody {
+    decision: WebhookDecision,
+    reason: Option<String>,
+    /// Caller-supplied name of who decided, recorded in the audit log.
+    actor: Option<String>,
+}
+
+/// Query string of a wait request.
+#[derive(Debug, Deserialize)]
+struct WaitQuery {
+    /// Seconds to hold the request while it is pending.
+    timeout: Option<u64>,
+}
+
+/// Compute the signature header value for `body` sent to `path` at
+/// `timestamp`.
+#[must_use]
+pub fn sign(secret: &str, timestamp: i64, path: &str, body: &[u8]) -> String {
+    let digest = mac(secret, timestamp, path, body).finalize().into_bytes();
+    digest
+        .iter()
+        .fold(String::from("sha256="), |mut out, byte| {
+            let _ = write!(out, "{byte:02x}");
+            out
+        })
+}
+
+/// Check a request's timestamp and signature headers against `path` and
+/// `body`.
+///
+/// # Errors
+///
+/// Returns `AppError::Unauthorized` when `secret` is empty, a header is
+/// missing or malformed, the timestamp is more than `max_skew_seconds`
+/// from `now`, or the signature does not match.
+pub fn verify(
+    secret: &str,
+    timestamp: Option<&str>,
+    signature: Option<&str>,
+    path: &str,
+    body: &[u8],
+    now: i64,
+    max_skew_seconds: u64,
+) -> Result<()> {
+    // An empty key is public: anyone could sign with it.
+    if secret.is_empty() {
+        return Err(AppError::Unauthorized(
+            "no signing secret configured".into(),
+        ));
+    }
+    let timestamp: i64 = timestamp
+        .and_then(|value| value.trim().parse().ok())
+        .ok_or_else(|| AppError::Unauthorized(format!("missing or invalid {TIMESTAMP_HEADER}")))?;
+    if now.abs_diff(timestamp) > max_skew_seconds {
+        return Err(AppError::Unauthorized(
+            "request timestamp is outside the allowed window".into(),
+        ));
+    }
+    let digest = signature
+        .and_then(|value| value.trim().strip_prefix("sha256="))
+        .and_then(decode_hex)
+        .ok_or_else(|| AppError::Unauthorized(format!("missing or invalid {SIGNATURE_HEADER}")))?;
+    mac(secret, timestamp, path, body)
+        .verify_slice(&digest)
+        .map_err(|_| AppError::Unauthorized("signature mismatch".into()))
+}
+
+/// Router serving the decision endpoint and, with a wait secret, the wait
+/// endpoint.
+pub fn router(state: Arc<AppState>) -> axum::Router {
+    let mut router = axum::Router::new().route(DECISION_PATH, post(decide));
+    if state.config.webhooks.wait_secret.is_empty() {
+        info!("approval wait endpoint not mounted: no webhook wait secret");
+    } else {
+        router = router.route(WAIT_PATH, get(wait));
+    }
+    router.with_state(state)
+}
+
+fn mac(secret: &str, timestamp: i64, path: &str, body: &[u8]) -> Hmac<Sha256> {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(secret.as_bytes())
+        .unwrap_or_else(|_| unreachable!("HMAC accepts keys of any length"));
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(path.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+/// Decode lower- or upper-case hex, or `None` if it is malformed.
+pub(crate) fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn reply(status: StatusCode, body: serde_json::Value) -> Response {
+    (status, Json(body)).into_response()
+}
+
+fn refuse(status: StatusCode, message: impl Into<String>) -> Response {
+    reply(status, serde_json::json!({ "error": message.into() }))
+}
+
+/// Handler for `POST /api/v1/approvals/{id}/decision`.
+async fn decide(
+    State(state): State<Arc<AppState>>,
+    Path(request_id): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let webhooks = &state.config.webhooks;
+    let path = DECISION_PATH.replace("{id}", &request_id);
+    if let Err(err) = verify(
+        &webhooks.secret,
+        header(TIMESTAMP_HEADER),
+        header(SIGNATURE_HEADER),
+        &path,
+        &body,
+        chrono::Utc::now().timestamp(),
+        webhooks.max_skew_seconds,
+    ) {
+        warn!(request_id, %err, "approval webhook refused");
+        return refuse(StatusCode::UNAUTHORIZED, err.to_string());
+    }
+
+    let input: DecisionBody = match serde_json::from_slice(&body) {
+        Ok(input) => input,
+        Err(err) => {
+            return refuse(
+                StatusCode::BAD_REQUEST,
+                format!("invalid decision body: {err}"),
+            )
+        }
+    };
+
+    let approved = input.decision == WebhookDecision::Approve;
+    let actor = input.actor.unwrap_or_else(|| "webhook".to_owned());
+    let reason = if approved {
+        None
+    } else {
+        Some(
+            input
+                .reason
+                .unwrap_or_else(|| format!("rejected via webhook by {actor}")),
+        )
+    };
+    let decider = Decider {
+        operator: format!("webhook:{actor}"),
+        via: format!("via webhook by {actor}"),
+        acting: Acting::Integration(actor.clone()),
+    };
+
+    match apply_decision(&state, &request_id, approved, &decider, reason).await {
+        Ok(Applied::Decided(status)) => {
+            info!(request_id, approved, actor, "approval resolved via webhook");
+            reply(
+                StatusCode::OK,
+                serde_json::json!({ "request_id": request_id, "status": status }),
+            )
+        }
+        Ok(Applied::Unknown) => refuse(
+            StatusCode::NOT_FOUND,
+            format!("unknown approval request '{request_id}'"),
+        ),
+        Ok(Applied::Refused(message)) => refuse(StatusCode::FORBIDDEN, message),
+        Ok(Applied::NotPending(status)) => reply(
+            StatusCode::CONFLICT,
+            serde_json::json!({
+                "error": "approval request is no longer pending",
+                "status": status,
+            }),
+        ),
+        Err(err) => refuse(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+/// Handler for `GET /api/v1/approvals/{id}/wait?timeout=<seconds>`.
+///
+/// Answers as soon as the request leaves `pending`, with the operator's
+/// `reason` for a rejection, or with `"status": "pending"` once `timeout`
+/// (default 30, at most [`MAX_WAIT_SECONDS`]) elapses, so callers simply
+/// poll again.
+async fn wait(
+    State(state): State<Arc<AppState>>,
+    Path(request_id): Path<String>,
+    Query(query): Query<WaitQuery>,
+    headers: HeaderMap,
+) -> Response {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let webhooks = &state.config.webhooks;
+    let path = WAIT_PATH.replace("{id}", &request_id);
+    if let Err(err) = verify(
+        &webhooks.wait_secret,
+        header(TIMESTAMP_HEADER),
+        header(SIGNATURE_HEADER),
+        &path,
+        b"",
+        chrono::Utc::now().timestamp(),
+        webhooks.max_skew_seconds,
+    ) {
+        warn!(request_id, %err, "approval wait refused");
+        return refuse(StatusCode::UNAUTHORIZED, err.to_string());
+    }
+
+    let timeout = Duration::from_secs(
+        query
+            .timeout
+            .unwrap_or(DEFAULT_WAIT_SECONDS)
+            .min(MAX_WAIT_SECONDS),
+    );
+    let deadline = tokio::time::Instant::now() + timeout;
+    let approval_repo = ApprovalRepo::new(Arc::clone(&state.db));
+    loop {
+        // Subscribe before reading so a decision landing in between still
+        // wakes this request.
+        let changed = ApprovalRepo::status_changed();
+        tokio::pin!(changed);
+        changed.as_mut().enable();
+
+        let record = match approval_repo.get_by_id(&request_id).await {
+            Ok(Some(record)) => record,
+            Ok(None) => {
+                return refuse(
+                    StatusCode::NOT_FOUND,
+                    format!("unknown approval request '{request_id}'"),
+                )
+            }
+            Err(err) => return refuse(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+        };
+        if record.status != ApprovalStatus::Pending
+            || tokio::time::timeout_at(deadline, changed).await.is_err()
+        {
+            return reply(
+                StatusCode::OK,
+                serde_json::json!({
+                    "request_id": record.id,
+                    "status": record.status,
+                    "reason": record.decision_reason,
+                }),
+            );
+        }
+    }
+}
+
+/// Who made an out-of-band decision.
+pub(crate) struct Decider {
+    /// Audit log `operator_id`, e.g. `webhook:ci-bot`.
+    pub operator: String,
+    /// How the Slack message that replaces the buttons credits the
+    /// decision, e.g. `via webhook by ci-bot`.
+    pub via: String,
+    /// Whose authority the decision is checked against.
+    pub acting: Acting,
+}
+
+/// Whose authority an out-of-band decision carries, checked with
+/// [`check_approval_authority`] as a Slack button click would be.
+pub(crate) enum Acting {
+    /// A known Slack user, e.g. the operator of a paired browser.
+    User(String),
+    /// Whoever holds a link issued to the session owner.
+    SessionOwner,
+    /// A signed integration naming its `actor`. Trusted like the session
+    /// owner, except that code-owner routing still applies to `actor`.
+    Integration(String),
+}
+
+/// Result of [`apply_decision`].
+pub(crate) enum Applied {
+    /// The decision was recorded; the request's new status.
+    Decided(ApprovalStatus),
+    /// No request has this ID.
+    Unknown,
+    /// The request was already decided, expired or interrupted.
+    NotPending(ApprovalStatus),
+    /// The decider may not decide this request; why.
+    Refused(String),
+}
+
+/// Approve or reject a pending request outside Slack: check the decider's
+/// authority, update the record, audit it, resolve the waiting agent, and
+/// replace the Slack buttons. Shared by the webhook, signed approval links
+/// and paired browsers.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the record cannot be read or updated.
+pub(crate) async fn apply_decision(
+    state: &Arc<AppState>,
+    request_id: &str,
+    approved: bool,
+    decider: &Decider,
+    reason: Option<String>,
+) -> Result<Applied> {
+    let approval_repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let Some(record) = approval_repo.get_by_id(request_id).await? else {
+        return Ok(Applied::Unknown);
+    };
+    if let Err(err) = check_authority(state, &record, &decider.acting).await? {
+        warn!(request_id, operator = decider.operator, %err, "out-of-band decision refused");
+        return Ok(Applied::Refused(err.to_string()));
+    }
+
+    let status = if approved {
+        ApprovalStatus::Approved
+    } else {
+        ApprovalStatus::Rejected
+    };
+    // Only a request still pending at write time is decided, so a racing
+    // Slack click, timeout or second webhook cannot be overwritten.
+    if !approval_repo
+        .decide_with_reason(request_id, status, reason.as_deref())
+        .await?
+    {
+        let current = approval_repo
+            .get_by_id(request_id)
+            .await?
+            .map_or(record.status, |current| current.status);
+        return Ok(Applied::NotPending(current));
+    }
+    audit(
+        state,
+        &record.session_id,
+        request_id,
+        approved,
+        &decider.operator,
+        reason.as_deref(),
+    );
+    resolve_clearance(state, &approval_repo, request_id, approved, reason.clone()).await;
+    replace_buttons(
+        state,
+        &record.session_id,
+        record.slack_ts.as_deref(),
+        approved,
+        &decider.via,
+        reason.as_deref(),
+    )
+    .await;
+    Ok(Applied::Decided(status))
+}
+
+/// Check `acting` against the same ownership and code-owner rules as the
+/// Slack buttons. The outer error is a database failure; the inner one the
+/// refusal.
+async fn check_authority(
+    state: &AppState,
+    record: &ApprovalRequest,
+    acting: &Acting,
+) -> Result<std::result::Result<(), AppError>> {
+    let Some(session) = SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(&record.session_id)
+        .await?
+    else {
+        return Ok(Err(AppError::NotFound(format!(
+            "session {} not found",
+            record.session_id
+        ))));
+    };
+    let required = ownership::required_approvers(
+        &state.config.codeowners,
+        std::path::Path::new(&session.workspace_root),
+        &record.file_path,
+    );
+    Ok(match acting {
+        Acting::User(user_id) => check_approval_authority(&session, &required, user_id),
+        Acting::SessionOwner => {
+            check_approval_authority(&session, &required, &session.owner_user_id)
+        }
+        Acting::Integration(_) if required.is_empty() => Ok(()),
+        Acting::Integration(actor) => check_approval_authority(&session, &required, actor),
+    })
+}
+
+/// Replace the request's Slack buttons with the out-of-band decision.
+async fn replace_buttons(
+    state: &AppState,
+    session_id: &str,
+    slack_ts: Option<&str>,
+    approved: bool,
+    via: &str,
+    reason: Option<&str>,
+) {
+    let (Some(slack), Some(ts)) = (state.slack.as_ref(), slack_ts) else {
+        return;
+    };
+    let Ok(Some(session)) = SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(session_id)
+        .await
+    else {
+        return;
+    };
+    let Some(channel) = session.channel_id else {
+        return;
+    };
+    let text = if approved {
+        format!("\u{2705} *Approved* {via}")
+    } else {
+        format!(
+            "\u{274c} *Rejected* {via}: {}",
+            reason.unwrap_or("no reason given")
+        )
+    };
+    if let Err(err) = slack
+        .update_message(
+            SlackChannelId(channel),
+            SlackTs(ts.to_owned()),
+            vec![blocks::text_section(&text)],
+        )
+        .await
+    {
+        warn!(%err, session_id, "failed to replace approval buttons after out-of-band decision");
+    }
+}
+
+/// Record an out-of-band decision in the audit log.
+fn audit(
+    state: &AppState,
+    session_id: &str,
+    request_id: &str,
+    approved: bool,
+    operator: &str,
+    reason: Option<&str>,
+) {
+    let Some(ref logger) = state.audit_logger else {
+        return;
+    };
+    let event_type = if approved {
+        AuditEventType::Approval
+    } else {
+        AuditEventType::Rejection
+    };
+    let mut entry = AuditEntry::new(event_type)
+        .with_session(session_id.to_owned())
+        .with_request_id(request_id.to_owned())
+        .with_operator(operator.to_owned());
+    if let Some(reason) = reason {
+        entry = entry.with_reason(reason.to_owned());
+    }
+    if let Err(err) = logger.log_entry(entry) {
+        warn!(%err, "audit log write failed (out-of-band approval)");
+    }
+}
@@ -0,0 +1,30 @@
+//! Configuration endpoint.
+//!
+//! `GET /api/v1/config` answers the same JSON document as the
+//! `intercom://config` MCP resource (see
+//! [`server_config`](super::resources::server_config)): the effective
+//! configuration with secrets redacted, and the command aliases.
+
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::routing::get;
+use axum::Json;
+
+use super::resources::server_config;
+use crate::state::AppState;
+
+/// Route path of the configuration endpoint.
+pub const CONFIG_PATH: &str = "/api/v1/config";
+
+/// Router serving [`CONFIG_PATH`].
+pub fn router(state: Arc<AppState>) -> axum::Router {
+    axum::Router::new()
+        .route(CONFIG_PATH, get(config))
+        .with_state(state)
+}
+
+/// Handler for `GET /api/v1/config`.
+async fn config(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
+    Json(server_config::document(&state))
+}
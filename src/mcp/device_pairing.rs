@@ -0,0 +1,406 @@
+//! Device pairing for the mobile web approver.
+//!
+//! `/intercom pair` shows the operator a QR code of a one-time pairing URL,
+//! `{base_url}/pair/{token}`. Opening it on a phone and confirming stores a
+//! device token in an `HttpOnly` cookie; from then on the browser can open
+//! `/dashboard`, which lists the operator's pending approvals and decides
+//! them without per-request links. Part of the `[approval_links]` web
+//! approver, so it shares its `base_url` and is mounted with it.
+//!
+//! Pairing and device tokens are random and stored only as SHA-256 hashes.
+//! A pairing token works once and for `pairing_ttl_seconds`; a device stays
+//! signed in for `device_ttl_days` or until `/intercom pair revoke`. Every
+//! dashboard request re-checks that the operator is still authorized and
+//! only shows requests from sessions they own.
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::get;
+use axum::Form;
+use chrono::{DateTime, Utc};
+use qrcode::render::unicode::Dense1x2;
+use qrcode::{EcLevel, QrCode};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use super::approval_link::{
+    already_decided, decision_page, document, not_found, page, render_request,
+};
+use super::approval_webhook::{apply_decision, Acting, Applied, Decider, WebhookDecision};
+use crate::config::ApprovalLinksConfig;
+use crate::diff::ownership;
+use crate::integrations::email::escape_html;
+use crate::models::approval::{ApprovalRequest, ApprovalStatus};
+use crate::models::device::PairedDevice;
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::device_repo::DeviceRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::handlers::check_approval_authority;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Cookie carrying a paired browser's device token.
+pub const DEVICE_COOKIE: &str = "intercom_device";
+
+/// Route path of the pairing page.
+pub const PAIR_PATH: &str = "/pair/{token}";
+
+/// Route path of the dashboard.
+pub const DASHBOARD_PATH: &str = "/dashboard";
+
+/// Longest user agent kept as a device label.
+const MAX_LABEL_LEN: usize = 120;
+
+/// A pairing offer ready to show to the operator.
+#[derive(Debug, Clone)]
+pub struct PairingOffer {
+    /// One-time pairing URL encoded in the QR code.
+    pub url: String,
+    /// The QR code as Unicode half blocks, for a Slack code block.
+    pub qr: String,
+    /// When the pairing URL stops working.
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Decision form posted by the dashboard's approval page.
+#[derive(Debug, Deserialize)]
+struct DeviceDecisionForm {
+    decision: WebhookDecision,
+    #[serde(default)]
+    reason: String,
+}
+
+/// A fresh random token: `words` UUID v4s, hex-encoded.
+fn new_token(words: usize) -> String {
+    (0..words)
+        .map(|_| uuid::Uuid::new_v4().simple().to_string())
+        .collect()
+}
+
+/// Hex SHA-256 of `token`, the form tokens are stored in.
+#[must_use]
+pub fn token_hash(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .fold(String::new(), |mut out, byte| {
+            let _ = write!(out, "{byte:02x}");
+            out
+        })
+}
+
+/// Render `data` as a QR code of Unicode half blocks with a quiet zone.
+///
+/// # Errors
+///
+/// Returns `AppError::Integration` if `data` is too long for a QR code.
+pub fn qr_text(data: &str) -> Result<String> {
+    let code = QrCode::with_error_correction_level(data.as_bytes(), EcLevel::L)
+        .map_err(|err| AppError::Integration(format!("cannot encode QR code: {err}")))?;
+    Ok(code.render::<Dense1x2>().quiet_zone(true).build())
+}
+
+/// Create a pairing offer for `user_id`.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` when approval links are disabled, or
+/// `AppError::Db` if the offer cannot be stored.
+pub async fn create_offer(
+    config: &ApprovalLinksConfig,
+    repo: &DeviceRepo,
+    user_id: &str,
+) -> Result<PairingOffer> {
+    let base = config
+        .base_url
+        .as_deref()
+        .filter(|_| config.enabled)
+        .ok_or_else(|| {
+            AppError::Config("device pairing needs `[approval_links] enabled = true`".into())
+        })?
+        .trim_end_matches('/');
+    let ttl = i64::try_from(config.pairing_ttl_seconds).unwrap_or(i64::MAX);
+    let expires_at = Utc::now() + chrono::Duration::seconds(ttl);
+    let token = new_token(1);
+    repo.create_offer(
+        &PairedDevice::offer(user_id.to_owned(), expires_at),
+        &token_hash(&token),
+    )
+    .await?;
+    let url = format!("{base}/pair/{token}");
+    Ok(PairingOffer {
+        qr: qr_text(&url)?,
+        url,
+        expires_at,
+    })
+}
+
+/// Router serving the pairing page and the dashboard.
+pub fn router(state: Arc<AppState>) -> axum::Router {
+    axum::Router::new()
+        .route(PAIR_PATH, get(show_pairing).post(pair))
+        .route(DASHBOARD_PATH, get(dashboard))
+        .route("/dashboard/approvals/{id}", get(show_approval).post(decide))
+        .with_state(state)
+}
+
+/// `401` page for an unpaired browser.
+fn not_paired() -> Response {
+    page(
+        StatusCode::UNAUTHORIZED,
+        "Browser not paired",
+        "Run the pair slash command in Slack and scan its QR code to use the dashboard.",
+    )
+}
+
+/// The device token from the request's cookies.
+fn device_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .find_map(|pair| {
+            let (name, value) = pair.trim().split_once('=')?;
+            (name == DEVICE_COOKIE).then_some(value)
+        })
+}
+
+/// The paired device making the request, or the response refusing it.
+async fn authenticate(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> std::result::Result<PairedDevice, Response> {
+    let Some(token) = device_token(headers) else {
+        return Err(not_paired());
+    };
+    match DeviceRepo::new(Arc::clone(&state.db))
+        .authenticate(&token_hash(token), Utc::now())
+        .await
+    {
+        Ok(Some(device)) if state.config.ensure_authorized(&device.user_id).is_ok() => Ok(device),
+        Ok(Some(device)) => {
+            warn!(
+                user_id = device.user_id,
+                "paired device of unauthorized user refused"
+            );
+            Err(not_paired())
+        }
+        Ok(None) => Err(not_paired()),
+        Err(err) => Err(page(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Error",
+            &err.to_string(),
+        )),
+    }
+}
+
+/// The pending-or-decided request `request_id` if `device`'s operator owns
+/// its session or may decide it (a code owner it is routed to), or the
+/// response refusing it. Other requests are reported as unknown.
+async fn owned_request(
+    state: &AppState,
+    device: &PairedDevice,
+    request_id: &str,
+) -> std::result::Result<ApprovalRequest, Response> {
+    let error = |err: AppError| page(StatusCode::INTERNAL_SERVER_ERROR, "Error", &err.to_string());
+    let approval = ApprovalRepo::new(Arc::clone(&state.db))
+        .get_by_id(request_id)
+        .await
+        .map_err(error)?
+        .ok_or_else(not_found)?;
+    let session = SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(&approval.session_id)
+        .await
+        .map_err(error)?
+        .ok_or_else(not_found)?;
+    let required = ownership::required_approvers(
+        &state.config.codeowners,
+        std::path::Path::new(&session.workspace_root),
+        &approval.file_path,
+    );
+    if session.owner_user_id == device.user_id
+        || check_approval_authority(&session, &required, &device.user_id).is_ok()
+    {
+        Ok(approval)
+    } else {
+        Err(not_found())
+    }
+}
+
+/// Handler for `GET /pair/{token}`: confirm before pairing, so link
+/// previews and scanners cannot use up the token.
+async fn show_pairing(State(state): State<Arc<AppState>>, Path(token): Path<String>) -> Response {
+    match DeviceRepo::new(Arc::clone(&state.db))
+        .find_offer(&token_hash(&token), Utc::now())
+        .await
+    {
+        Ok(Some(_)) => {
+            let body = "<h1>Pair this browser</h1>\
+                 <p>This browser will be able to view and decide your pending approvals.</p>\
+                 <form method=\"post\"><button>Pair</button></form>";
+            (StatusCode::OK, Html(document("Pair this browser", body))).into_response()
+        }
+        Ok(None) => pairing_expired(),
+        Err(err) => page(StatusCode::INTERNAL_SERVER_ERROR, "Error", &err.to_string()),
+    }
+}
+
+fn pairing_expired() -> Response {
+    page(
+        StatusCode::UNAUTHORIZED,
+        "Pairing code not valid",
+        "This pairing code was already used or has expired. Run the pair slash command again.",
+    )
+}
+
+/// Handler for `POST /pair/{token}`: redeem the offer, set the device
+/// cookie and open the dashboard.
+async fn pair(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let config = &state.config.approval_links;
+    let label = headers
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(|agent| agent.chars().take(MAX_LABEL_LEN).collect::<String>());
+    let now = Utc::now();
+    let device_token = new_token(2);
+    let redeemed = DeviceRepo::new(Arc::clone(&state.db))
+        .redeem(
+            &token_hash(&token),
+            &token_hash(&device_token),
+            label.as_deref(),
+            now,
+            now + chrono::Duration::days(i64::from(config.device_ttl_days)),
+        )
+        .await;
+    match redeemed {
+        Ok(Some(device)) => {
+            info!(
+                user_id = device.user_id,
+                device_id = device.id,
+                "browser paired"
+            );
+            let secure = config
+                .base_url
+                .as_deref()
+                .is_some_and(|url| url.starts_with("https://"));
+            let cookie = format!(
+                "{DEVICE_COOKIE}={device_token}; Path=/; Max-Age={}; HttpOnly; SameSite=Lax{}",
+                u64::from(config.device_ttl_days) * 86_400,
+                if secure { "; Secure" } else { "" }
+            );
+            (
+                StatusCode::SEE_OTHER,
+                [
+                    (header::SET_COOKIE, cookie),
+                    (header::LOCATION, DASHBOARD_PATH.to_owned()),
+                ],
+            )
+                .into_response()
+        }
+        Ok(None) => pairing_expired(),
+        Err(err) => page(StatusCode::INTERNAL_SERVER_ERROR, "Error", &err.to_string()),
+    }
+}
+
+/// Handler for `GET /dashboard`: the operator's pending approvals.
+async fn dashboard(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
+    let device = match authenticate(&state, &headers).await {
+        Ok(device) => device,
+        Err(response) => return response,
+    };
+    let pending = match ApprovalRepo::new(Arc::clone(&state.db))
+        .list_pending_for_owner(&device.user_id)
+        .await
+    {
+        Ok(pending) => pending,
+        Err(err) => return page(StatusCode::INTERNAL_SERVER_ERROR, "Error", &err.to_string()),
+    };
+
+    let mut body = String::from("<h1>Pending approvals</h1>");
+    if pending.is_empty() {
+        body.push_str("<p>Nothing is waiting for you.</p>");
+    } else {
+        body.push_str("<ul>");
+        for approval in &pending {
+            let _ = write!(
+                body,
+                "<li><a href=\"/dashboard/approvals/{}\">{}</a><br><code>{}</code> \u{b7} {}</li>",
+                escape_html(&approval.id),
+                escape_html(&approval.title),
+                escape_html(&approval.file_path),
+                approval.risk_level.as_str()
+            );
+        }
+        body.push_str("</ul>");
+    }
+    (StatusCode::OK, Html(document("Pending approvals", &body))).into_response()
+}
+
+/// Handler for `GET /dashboard/approvals/{id}`: one request's approval page.
+async fn show_approval(
+    State(state): State<Arc<AppState>>,
+    Path(request_id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let device = match authenticate(&state, &headers).await {
+        Ok(device) => device,
+        Err(response) => return response,
+    };
+    match owned_request(&state, &device, &request_id).await {
+        Ok(approval) if approval.status == ApprovalStatus::Pending => {
+            (StatusCode::OK, Html(render_request(&approval, &[]))).into_response()
+        }
+        Ok(approval) => already_decided(approval.status),
+        Err(response) => response,
+    }
+}
+
+/// Handler for `POST /dashboard/approvals/{id}`: apply the decision.
+async fn decide(
+    State(state): State<Arc<AppState>>,
+    Path(request_id): Path<String>,
+    headers: HeaderMap,
+    Form(form): Form<DeviceDecisionForm>,
+) -> Response {
+    let device = match authenticate(&state, &headers).await {
+        Ok(device) => device,
+        Err(response) => return response,
+    };
+    if let Err(response) = owned_request(&state, &device, &request_id).await {
+        return response;
+    }
+
+    let approved = form.decision == WebhookDecision::Approve;
+    let reason = (!approved).then(|| {
+        let reason = form.reason.trim();
+        if reason.is_empty() {
+            "rejected from a paired browser".to_owned()
+        } else {
+            reason.to_owned()
+        }
+    });
+    let decider = Decider {
+        operator: format!("device:{}", device.user_id),
+        via: format!("by <@{}> from a paired browser", device.user_id),
+        acting: Acting::User(device.user_id.clone()),
+    };
+    let result = apply_decision(&state, &request_id, approved, &decider, reason).await;
+    if matches!(result, Ok(Applied::Decided(_))) {
+        info!(
+            request_id,
+            approved,
+            user_id = device.user_id,
+            "approval resolved from a paired browser"
+        );
+    }
+    decision_page(result, approved)
+}
@@ -0,0 +1,179 @@
+//! Chunked upload of large diffs to a server-side staging area.
+//!
+//! A multi-megabyte diff does not belong in an MCP message. Agents (or
+//! their wrappers) instead open an upload, append the diff in chunks, and
+//! pass the returned ID to `check_clearance` as `diff_ref`:
+//!
+//! - `POST /api/v1/staging` opens an upload and answers
+//!   `{"staging_id": "..."}`.
+//! - `POST /api/v1/staging/{id}?offset=<n>` appends the raw request body.
+//!   `offset` must equal the bytes received so far, so a retried chunk is
+//!   refused with `409` and the current length instead of being doubled.
+//!
+//! Uploads are capped at `[limits] max_diff_bytes`, live in memory, are
+//! consumed by the `check_clearance` call that references them, and are
+//! discarded after `[limits] staging_ttl_seconds` if never used.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use axum::body::Bytes;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::Json;
+use serde::Deserialize;
+use tracing::info;
+
+use crate::state::AppState;
+
+/// Route path that opens an upload.
+pub const STAGING_PATH: &str = "/api/v1/staging";
+
+/// Route path that appends a chunk to an upload.
+pub const CHUNK_PATH: &str = "/api/v1/staging/{id}";
+
+#[derive(Debug)]
+struct Upload {
+    bytes: Vec<u8>,
+    touched: Instant,
+}
+
+/// Why a chunk was not appended.
+#[derive(Debug, PartialEq, Eq)]
+pub enum AppendError {
+    /// No open upload has this ID.
+    Unknown,
+    /// `offset` is not the current length, which is carried.
+    Offset(usize),
+    /// The upload would exceed the configured maximum.
+    TooLarge,
+}
+
+/// Open uploads, keyed by staging ID.
+#[derive(Debug, Default)]
+pub struct DiffStaging {
+    uploads: Mutex<HashMap<String, Upload>>,
+}
+
+impl DiffStaging {
+    /// Create an empty staging area.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open an upload and return its ID, discarding uploads idle for
+    /// longer than `ttl`.
+    pub fn open(&self, ttl: Duration) -> String {
+        let mut uploads = self.lock();
+        uploads.retain(|_, upload| upload.touched.elapsed() < ttl);
+        let id = uuid::Uuid::new_v4().to_string();
+        uploads.insert(
+            id.clone(),
+            Upload {
+                bytes: Vec::new(),
+                touched: Instant::now(),
+            },
+        );
+        id
+    }
+
+    /// Append `chunk` at `offset` to upload `id`, keeping it within `max`
+    /// bytes. Returns the new length.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`AppendError`] for unknown uploads, a wrong offset, or
+    /// when the upload would grow past `max`.
+    pub fn append(
+        &self,
+        id: &str,
+        offset: usize,
+        chunk: &[u8],
+        max: usize,
+    ) -> Result<usize, AppendError> {
+        let mut uploads = self.lock();
+        let upload = uploads.get_mut(id).ok_or(AppendError::Unknown)?;
+        if offset != upload.bytes.len() {
+            return Err(AppendError::Offset(upload.bytes.len()));
+        }
+        if upload.bytes.len() + chunk.len() > max {
+            return Err(AppendError::TooLarge);
+        }
+        upload.bytes.extend_from_slice(chunk);
+        upload.touched = Instant::now();
+        Ok(upload.bytes.len())
+    }
+
+    /// Remove upload `id` and return its contents.
+    pub fn take(&self, id: &str) -> Option<Vec<u8>> {
+        self.lock().remove(id).map(|upload| upload.bytes)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Upload>> {
+        self.uploads.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Query string of a chunk upload.
+#[derive(Debug, Deserialize)]
+struct ChunkQuery {
+    #[serde(default)]
+    offset: usize,
+}
+
+/// Router serving the staging endpoints.
+pub fn router(state: Arc<AppState>) -> axum::Router {
+    axum::Router::new()
+        .route(STAGING_PATH, post(open))
+        .route(CHUNK_PATH, post(append))
+        .with_state(state)
+}
+
+fn reply(status: StatusCode, body: serde_json::Value) -> Response {
+    (status, Json(body)).into_response()
+}
+
+/// Handler for `POST /api/v1/staging`.
+async fn open(State(state): State<Arc<AppState>>) -> Response {
+    let ttl = Duration::from_secs(state.config.limits.staging_ttl_seconds);
+    let id = state.diff_staging.open(ttl);
+    info!(staging_id = id, "diff upload opened");
+    reply(StatusCode::CREATED, serde_json::json!({ "staging_id": id }))
+}
+
+/// Handler for `POST /api/v1/staging/{id}?offset=<n>`.
+async fn append(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<ChunkQuery>,
+    chunk: Bytes,
+) -> Response {
+    let max = state.config.limits.max_diff_bytes;
+    match state.diff_staging.append(&id, query.offset, &chunk, max) {
+        Ok(length) => reply(
+            StatusCode::OK,
+            serde_json::json!({ "staging_id": id, "bytes": length }),
+        ),
+        Err(AppendError::Unknown) => reply(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({ "error": format!("unknown or expired upload '{id}'") }),
+        ),
+        Err(AppendError::Offset(length)) => reply(
+            StatusCode::CONFLICT,
+            serde_json::json!({
+                "error": "offset does not match the bytes received",
+                "bytes": length,
+            }),
+        ),
+        Err(AppendError::TooLarge) => reply(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            serde_json::json!({
+                "error": format!("diff exceeds [limits] max_diff_bytes ({max} bytes)"),
+            }),
+        ),
+    }
+}
@@ -4,22 +4,35 @@ use std::future::Future;
 use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
+use chrono::Utc;
 use rmcp::handler::server::{
     tool::{ToolCallContext, ToolRoute, ToolRouter},
     ServerHandler,
 };
 use rmcp::model::{
-    CallToolRequestParam, CallToolResult, Implementation, ListResourceTemplatesResult,
-    ListResourcesResult, ListToolsResult, PaginatedRequestParam, ReadResourceRequestParam,
-    ReadResourceResult, ServerCapabilities, ServerInfo, Tool,
+    CallToolRequestParam, CallToolResult, CustomNotification, Implementation,
+    ListResourceTemplatesResult, ListResourcesResult, ListToolsResult, PaginatedRequestParam,
+    ReadResourceRequestParam, ReadResourceResult, ServerCapabilities, ServerInfo, Tool,
 };
 use rmcp::service::{NotificationContext, RequestContext, RoleServer};
 use tokio_util::sync::CancellationToken;
 use tracing::{info, info_span, warn};
 
 use crate::audit::{AuditEntry, AuditEventType};
-use crate::models::session::{Session, SessionMode, SessionStatus};
-use crate::orchestrator::stall_detector::StallDetector;
+use crate::config::WorkspaceMapping;
+use crate::driver::session_hooks::{SubscribeParams, SUBSCRIBE_METHOD};
+use crate::driver::AgentEvent;
+use crate::mcp::progress::{self, Keepalive};
+use crate::mcp::proxy;
+use crate::models::session::{
+    ConnectivityStatus, ProtocolMode, Session, SessionMode, SessionStatus,
+};
+use crate::orchestrator::heartbeat_enforcer::{
+    self, HEARTBEAT_DIRECTIVE, HEARTBEAT_REQUIRED_STATUS,
+};
+use crate::orchestrator::session_manager::{self, PAUSE_DIRECTIVE, PAUSE_REQUESTED_STATUS};
+use crate::orchestrator::stall_detector::{AdaptiveThreshold, StallDetector};
+use crate::orchestrator::working_hours;
 use crate::persistence::session_repo::SessionRepo;
 
 use crate::state::AppState;
@@ -29,9 +42,19 @@ use crate::state::AppState;
 /// Distinguishes locally-initiated sessions from sessions spawned via the Slack
 /// `/spawn` command (which use the operator's real Slack user ID).  Used by
 /// `on_initialized` to clean up stale direct-connection sessions on reconnect.
-const LOCAL_AGENT_OWNER: &str = "agent:local";
+pub(crate) const LOCAL_AGENT_OWNER: &str = "agent:local";
+
+/// Blocking tools at which a pending operator pause takes effect.
+///
+/// `standby` also applies the pause but then runs normally, since waiting
+/// is exactly what a paused agent should do.
+const PAUSE_CHECKPOINT_TOOLS: [&str; 4] = ["check_clearance", "transmit", "ping", "standby"];
 
-/// MCP server implementation that exposes the nine agent-intercom tools.
+/// Tools an agent may call while its heartbeat is overdue: the heartbeat
+/// itself, and waiting for the operator.
+const HEARTBEAT_EXEMPT_TOOLS: [&str; 2] = ["ping", "standby"];
+
+/// MCP server implementation that exposes the nineteen agent-intercom tools.
 pub struct IntercomServer {
     state: Arc<AppState>,
     /// Per-session Slack channel override supplied via SSE query parameter.
@@ -119,6 +142,17 @@ impl IntercomServer {
         })
     }
 
+    /// Return the channel this connection's messages are posted to.
+    ///
+    /// A session moved with `session-move` posts to its new channel;
+    /// otherwise this is [`effective_channel_id`](Self::effective_channel_id).
+    #[must_use]
+    pub fn routed_channel_id(&self) -> Option<String> {
+        self.bound_session_id()
+            .and_then(|id| self.state.session_channels.get(id))
+            .or_else(|| self.effective_channel_id().map(str::to_owned))
+    }
+
     /// Return the pre-existing DB session ID override for this connection (T112 / HITL-003).
     ///
     /// In ACP mode, spawned agent subprocesses connect to the HTTP MCP endpoint
@@ -133,12 +167,40 @@ impl IntercomServer {
         self.session_id_override.as_deref()
     }
 
+    /// Return the DB session this connection is bound to.
+    ///
+    /// The spawner-supplied [`session_id_override`](Self::session_id_override)
+    /// wins; otherwise the session auto-created by `on_initialized` for a
+    /// direct connection. `None` before the handshake completes.
+    #[must_use]
+    pub fn bound_session_id(&self) -> Option<&str> {
+        self.session_id_override
+            .as_deref()
+            .or_else(|| self.session_db_id.get().map(String::as_str))
+    }
+
+    /// Return the workspace whose `[[workspace.proxy]]` servers this
+    /// connection sees, if any.
+    ///
+    /// Resolved from the live mapping table by the connection's channel, so
+    /// connections without a channel never see proxied tools.
+    #[must_use]
+    pub fn proxy_workspace(&self) -> Option<WorkspaceMapping> {
+        let channel_id = self.channel_id_override.as_deref()?;
+        let mappings = self.state.workspace_mappings.read().ok()?;
+        mappings
+            .iter()
+            .find(|m| m.channel_id == channel_id && !m.proxy.is_empty())
+            .cloned()
+    }
+
     /// Access the shared application state.
     #[must_use]
     pub fn state(&self) -> &Arc<AppState> {
         &self.state
     }
 
+    #[allow(clippy::too_many_lines)] // One match arm per tool.
     fn tool_router() -> &'static ToolRouter<Self> {
         static ROUTER: std::sync::OnceLock<ToolRouter<IntercomServer>> = std::sync::OnceLock::new();
         ROUTER.get_or_init(|| {
@@ -192,6 +254,61 @@ impl IntercomServer {
                             Box::pin(crate::mcp::tools::wait_for_instruction::handle(context))
                         }));
                     }
+                    "relay_send" => {
+                        router.add_route(ToolRoute::new_dyn(tool, |context| {
+                            Box::pin(crate::mcp::tools::relay_send::handle(context))
+                        }));
+                    }
+                    "relay_receive" => {
+                        router.add_route(ToolRoute::new_dyn(tool, |context| {
+                            Box::pin(crate::mcp::tools::relay_receive::handle(context))
+                        }));
+                    }
+                    "spawn_subtask" => {
+                        router.add_route(ToolRoute::new_dyn(tool, |context| {
+                            Box::pin(crate::mcp::tools::spawn_subtask::handle(context))
+                        }));
+                    }
+                    "stream_log" => {
+                        router.add_route(ToolRoute::new_dyn(tool, |context| {
+                            Box::pin(crate::mcp::tools::stream_log::handle(context))
+                        }));
+                    }
+                    "store_context" => {
+                        router.add_route(ToolRoute::new_dyn(tool, |context| {
+                            Box::pin(crate::mcp::tools::store_context::handle(context))
+                        }));
+                    }
+                    "get_context" => {
+                        router.add_route(ToolRoute::new_dyn(tool, |context| {
+                            Box::pin(crate::mcp::tools::get_context::handle(context))
+                        }));
+                    }
+                    "propose_knowledge" => {
+                        router.add_route(ToolRoute::new_dyn(tool, |context| {
+                            Box::pin(crate::mcp::tools::propose_knowledge::handle(context))
+                        }));
+                    }
+                    "approval_stats" => {
+                        router.add_route(ToolRoute::new_dyn(tool, |context| {
+                            Box::pin(crate::mcp::tools::approval_stats::handle(context))
+                        }));
+                    }
+                    "acquire_lock" => {
+                        router.add_route(ToolRoute::new_dyn(tool, |context| {
+                            Box::pin(crate::mcp::tools::acquire_lock::handle(context))
+                        }));
+                    }
+                    "release_lock" => {
+                        router.add_route(ToolRoute::new_dyn(tool, |context| {
+                            Box::pin(crate::mcp::tools::release_lock::handle(context))
+                        }));
+                    }
+                    "checkpoint" => {
+                        router.add_route(ToolRoute::new_dyn(tool, |context| {
+                            Box::pin(crate::mcp::tools::checkpoint::handle(context))
+                        }));
+                    }
                     _ => {
                         router.add_route(ToolRoute::new_dyn(tool, |_context| {
                             Box::pin(async {
@@ -224,7 +341,9 @@ impl IntercomServer {
                 name: "check_clearance".into(),
                 description: Some(
                     "Submit a code proposal for remote operator approval via Slack. \
-                     Blocks until the operator responds or the timeout elapses."
+                     Blocks until the operator responds or the timeout elapses. \
+                     For very large diffs, upload to /api/v1/staging and pass \
+                     diff_ref with an empty diff."
                         .into(),
                 ),
                 input_schema: Self::schema(serde_json::json!({
@@ -233,6 +352,7 @@ impl IntercomServer {
                         "title": { "type": "string" },
                         "description": { "type": "string" },
                         "diff": { "type": "string" },
+                        "diff_ref": { "type": "string" },
                         "file_path": { "type": "string" },
                         "risk_level": { "type": "string", "enum": ["low", "high", "critical"], "default": "low" }
                     },
@@ -344,13 +464,16 @@ impl IntercomServer {
                 name: "reboot".into(),
                 description: Some(
                     "Retrieve the last known state from persistent storage. Called on \
-                     startup to check for interrupted sessions or pending requests."
+                     startup to check for interrupted sessions or pending requests. \
+                     Set wait to block until a request re-armed after a server \
+                     restart is decided."
                         .into(),
                 ),
                 input_schema: Self::schema(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "session_id": { "type": "string" }
+                        "session_id": { "type": "string" },
+                        "wait": { "type": "boolean" }
                     }
                 })),
                 output_schema: None,
@@ -381,14 +504,24 @@ impl IntercomServer {
                 name: "standby".into(),
                 description: Some(
                     "Place the agent in standby, polling for a resume signal or new \
-                     command from the operator via Slack."
+                     command from the operator via Slack. With wake_at, the agent is \
+                     resumed at that time with wake_instruction unless the operator \
+                     answers first."
                         .into(),
                 ),
                 input_schema: Self::schema(serde_json::json!({
                     "type": "object",
                     "properties": {
                         "message": { "type": "string", "default": "Agent is idle and awaiting instructions." },
-                        "timeout_seconds": { "type": "integer", "default": 0 }
+                        "timeout_seconds": { "type": "integer", "default": 0 },
+                        "wake_at": {
+                            "type": "string",
+                            "description": "Resume at this time: HH:MM server-local (next occurrence) or an RFC 3339 timestamp. Overrides timeout_seconds."
+                        },
+                        "wake_instruction": {
+                            "type": "string",
+                            "description": "Instruction returned on a scheduled wake-up. Defaults to [standby] wake_instruction."
+                        }
                     }
                 })),
                 output_schema: None,
@@ -427,6 +560,326 @@ impl IntercomServer {
                 icons: None,
                 meta: None,
             },
+            Tool {
+                name: "relay_send".into(),
+                description: Some(
+                    "Send a structured message to another agent session, which \
+                     collects it with relay_receive. Messages are persisted, audited, \
+                     and shown to the operator in Slack. Non-blocking."
+                        .into(),
+                ),
+                input_schema: Self::schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "to_session_id": {
+                            "type": "string",
+                            "description": "Full ID of the recipient session, which must share this session's owner or workspace"
+                        },
+                        "payload": {
+                            "description": "Message body: any JSON value, at most 64 KiB serialized"
+                        },
+                        "topic": {
+                            "type": "string",
+                            "description": "Optional label the recipient can filter on"
+                        }
+                    },
+                    "required": ["to_session_id", "payload"]
+                })),
+                output_schema: None,
+                annotations: None,
+                title: None,
+                icons: None,
+                meta: None,
+            },
+            Tool {
+                name: "relay_receive".into(),
+                description: Some(
+                    "Collect relay messages other sessions sent to this session, oldest \
+                     first. Each message is delivered once. Waits up to timeout_seconds \
+                     for a message when none is pending."
+                        .into(),
+                ),
+                input_schema: Self::schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "topic": {
+                            "type": "string",
+                            "description": "Only collect messages with this topic"
+                        },
+                        "timeout_seconds": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "maximum": 3600,
+                            "default": 0
+                        }
+                    }
+                })),
+                output_schema: None,
+                annotations: None,
+                title: None,
+                icons: None,
+                meta: None,
+            },
+            Tool {
+                name: "spawn_subtask".into(),
+                description: Some(
+                    "Ask the operator to start a child agent session with the given \
+                     prompt in this workspace. Blocks until the operator decides. When \
+                     the child ends, a completion report arrives as a steering message."
+                        .into(),
+                ),
+                input_schema: Self::schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "prompt": {
+                            "type": "string",
+                            "description": "Prompt the child agent starts with"
+                        },
+                        "title": {
+                            "type": "string",
+                            "description": "Short label shown to the operator"
+                        }
+                    },
+                    "required": ["prompt"]
+                })),
+                output_schema: None,
+                annotations: None,
+                title: None,
+                icons: None,
+                meta: None,
+            },
+            Tool {
+                name: "stream_log".into(),
+                description: Some(
+                    "Append a structured log line to this session's log buffer without \
+                     posting to Slack. The operator reads the newest lines on demand. \
+                     Use for verbose progress output; use broadcast for messages the \
+                     operator should see. Non-blocking."
+                        .into(),
+                ),
+                input_schema: Self::schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "message": { "type": "string" },
+                        "level": {
+                            "type": "string",
+                            "enum": ["debug", "info", "warn", "error"],
+                            "default": "info"
+                        },
+                        "fields": {
+                            "type": "object",
+                            "description": "Structured key/value context, at most 4 KiB serialized"
+                        }
+                    },
+                    "required": ["message"]
+                })),
+                output_schema: None,
+                annotations: None,
+                title: None,
+                icons: None,
+                meta: None,
+            },
+            Tool {
+                name: "store_context".into(),
+                description: Some(
+                    "Store a small JSON note (a decision made, a URL discovered) under a \
+                     key for this session. Notes survive restarts and are returned by \
+                     reboot. Storing null removes the key. Non-blocking."
+                        .into(),
+                ),
+                input_schema: Self::schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "key": {
+                            "type": "string",
+                            "description": "Key, at most 128 bytes, unique within the session"
+                        },
+                        "value": {
+                            "description": "Any JSON value, at most 4 KiB serialized; null removes the key"
+                        }
+                    },
+                    "required": ["key", "value"]
+                })),
+                output_schema: None,
+                annotations: None,
+                title: None,
+                icons: None,
+                meta: None,
+            },
+            Tool {
+                name: "get_context".into(),
+                description: Some(
+                    "Read the notes this session stored with store_context: one key, \
+                     or all of them. Non-blocking."
+                        .into(),
+                ),
+                input_schema: Self::schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "key": {
+                            "type": "string",
+                            "description": "Only return this key"
+                        }
+                    }
+                })),
+                output_schema: None,
+                annotations: None,
+                title: None,
+                icons: None,
+                meta: None,
+            },
+            Tool {
+                name: "propose_knowledge".into(),
+                description: Some(
+                    "Propose a note for the shared knowledge base (e.g. how to run the \
+                     e2e suite). The operator approves it in Slack; approved notes are \
+                     readable by every session via the intercom://knowledge resource. \
+                     Non-blocking."
+                        .into(),
+                ),
+                input_schema: Self::schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "title": {
+                            "type": "string",
+                            "description": "Short summary, at most 200 bytes"
+                        },
+                        "body": {
+                            "type": "string",
+                            "description": "Note body in Markdown, at most 8 KiB"
+                        },
+                        "tags": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Up to 10 labels of at most 32 bytes each"
+                        }
+                    },
+                    "required": ["title", "body"]
+                })),
+                output_schema: None,
+                annotations: None,
+                title: None,
+                icons: None,
+                meta: None,
+            },
+            Tool {
+                name: "approval_stats".into(),
+                description: Some(
+                    "Report how the operator decided recent approval requests: approval \
+                     rate overall and by risk level, and the most common rejection \
+                     reasons. Use it to judge when check_clearance is worth asking and \
+                     when auto_check suffices. Non-blocking."
+                        .into(),
+                ),
+                input_schema: Self::schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "scope": {
+                            "type": "string",
+                            "enum": ["workspace", "session"],
+                            "default": "workspace",
+                            "description": "Cover every session in this workspace, or only this session"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "maximum": 500,
+                            "default": 100,
+                            "description": "How many of the most recent decided requests to consider"
+                        }
+                    }
+                })),
+                output_schema: None,
+                annotations: None,
+                title: None,
+                icons: None,
+                meta: None,
+            },
+            Tool {
+                name: "acquire_lock".into(),
+                description: Some(
+                    "Lock a file or directory of this workspace before editing it, so \
+                     concurrent sessions in the same repository do not edit the same \
+                     files. A directory lock covers everything below it. When another \
+                     session holds an overlapping lock, waits in request order up to \
+                     timeout_seconds and tells the operator. Call release_lock when done."
+                        .into(),
+                ),
+                input_schema: Self::schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "path": {
+                            "type": "string",
+                            "description": "File or directory relative to the workspace root; \".\" locks the whole workspace"
+                        },
+                        "reason": {
+                            "type": "string",
+                            "description": "Why the path is needed, shown to the operator while others wait"
+                        },
+                        "timeout_seconds": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "maximum": 3600,
+                            "default": 300,
+                            "description": "Seconds to wait behind a conflicting lock. 0 returns at once"
+                        }
+                    },
+                    "required": ["path"]
+                })),
+                output_schema: None,
+                annotations: None,
+                title: None,
+                icons: None,
+                meta: None,
+            },
+            Tool {
+                name: "release_lock".into(),
+                description: Some(
+                    "Release a lock taken with acquire_lock, or every lock this session \
+                     holds when path is omitted. Locks of ended sessions are released \
+                     automatically. Non-blocking."
+                        .into(),
+                ),
+                input_schema: Self::schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "path": {
+                            "type": "string",
+                            "description": "Path given to acquire_lock; omit to release all"
+                        }
+                    }
+                })),
+                output_schema: None,
+                annotations: None,
+                title: None,
+                icons: None,
+                meta: None,
+            },
+            Tool {
+                name: "checkpoint".into(),
+                description: Some(
+                    "Take a named checkpoint of this session at a milestone (e.g. \
+                     \"tests passing before refactor\"): session state and workspace \
+                     file hashes, so the operator can later see what changed since. \
+                     Posts a confirmation to the session thread. Non-blocking."
+                        .into(),
+                ),
+                input_schema: Self::schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "label": {
+                            "type": "string",
+                            "description": "Milestone the checkpoint marks, at most 200 bytes"
+                        }
+                    },
+                    "required": ["label"]
+                })),
+                output_schema: None,
+                annotations: None,
+                title: None,
+                icons: None,
+                meta: None,
+            },
         ]
     }
 }
@@ -441,14 +894,26 @@ async fn spawn_stall_detector_for_session(state: &AppState, session_id: &str) {
     }
     if let (Some(ref detectors), Some(ref tx)) = (&state.stall_detectors, &state.stall_event_tx) {
         let session_cancel = CancellationToken::new();
-        let detector = StallDetector::new(
+        let stall = &state.config.stall;
+        let mut detector = StallDetector::new(
             session_id.to_owned(),
-            Duration::from_secs(state.config.stall.inactivity_threshold_seconds),
-            Duration::from_secs(state.config.stall.escalation_threshold_seconds),
-            state.config.stall.max_retries,
+            Duration::from_secs(stall.inactivity_threshold_seconds),
+            Duration::from_secs(stall.escalation_threshold_seconds),
+            stall.max_retries,
             tx.clone(),
             session_cancel,
         );
+        if stall.adaptive {
+            detector = detector.with_adaptive(AdaptiveThreshold::new(
+                stall.adaptive_multiplier,
+                Duration::from_secs(stall.adaptive_min_seconds),
+                Duration::from_secs(stall.adaptive_max_seconds),
+            ));
+        }
+        if stall.completion_grace_seconds > 0 {
+            detector =
+                detector.with_completion_grace(Duration::from_secs(stall.completion_grace_seconds));
+        }
         let handle = detector.spawn();
         detectors.lock().await.insert(session_id.to_owned(), handle);
         info!(session_id, "stall detector spawned");
@@ -484,9 +949,11 @@ impl ServerHandler for IntercomServer {
     #[allow(clippy::too_many_lines)]
     fn on_initialized(
         &self,
-        _context: NotificationContext<RoleServer>,
+        context: NotificationContext<RoleServer>,
     ) -> impl Future<Output = ()> + Send + '_ {
         let state = Arc::clone(&self.state);
+        let peer = context.peer;
+        let client = peer.peer_info().map(|info| info.client_info.clone());
         let session_id_override = self.session_id_override.clone();
         let channel_id_override = self.channel_id_override.clone();
         let is_remote = self.channel_id_override.is_some();
@@ -504,6 +971,23 @@ impl ServerHandler for IntercomServer {
                             status = ?session.status,
                             "spawned agent connected to pre-created session"
                         );
+                        record_client(&session_repo, &session.id, client.as_ref()).await;
+                        // Bind MCP-protocol sessions to the MCP driver. ACP sessions
+                        // calling tools over HTTP keep the binding made at spawn time.
+                        if session.protocol_mode == ProtocolMode::Mcp {
+                            state
+                                .driver_registry
+                                .register(&session.id, state.mcp_driver_with_peer(peer))
+                                .await;
+                        }
+                        // Spawned agents connect with only `session_id`, so the
+                        // connection has no channel of its own: post to the one
+                        // recorded by `/spawn`, `spawn_subtask` or a respawn.
+                        if let Some(ref channel) = session.channel_id {
+                            if channel_id_override.as_deref() != Some(channel.as_str()) {
+                                state.session_channels.set(&session.id, channel);
+                            }
+                        }
                         // Spawn a per-session stall detector for the spawned agent (FR-028).
                         spawn_stall_detector_for_session(&state, &session.id).await;
                     }
@@ -518,7 +1002,52 @@ impl ServerHandler for IntercomServer {
             }
 
             // ── Case 2: Direct connection — auto-create a session ────────────
-            //
+            let workspace_root = channel_id_override
+                .as_deref()
+                .map_or_else(
+                    || state.config.default_workspace_root(),
+                    |ch| state.config.workspace_root_for_channel(ch),
+                )
+                .to_string_lossy()
+                .into_owned();
+            let mode = if is_remote {
+                SessionMode::Remote
+            } else {
+                SessionMode::Local
+            };
+
+            // A client reconnecting within the grace window (an IDE window
+            // reload) takes its session back instead of starting a new one,
+            // so reload storms neither end sessions nor post to Slack.
+            let grace = Duration::from_secs(state.config.timeouts.reconnect_grace_seconds);
+            let client_name = client.as_ref().map(|client| client.name.as_str());
+            if let Some(session) =
+                reusable_session(&session_repo, &workspace_root, mode, client_name, grace).await
+            {
+                if let Err(err) = session_repo
+                    .set_connectivity_status(&session.id, ConnectivityStatus::Online)
+                    .await
+                {
+                    warn!(%err, session_id = %session.id, "failed to mark session online");
+                }
+                info!(session_id = %session.id, "direct connection resumed session after reconnect");
+                if session_db_id.set(session.id.clone()).is_err() {
+                    warn!(session_id = %session.id, "session_db_id was already set (unexpected)");
+                }
+                record_client(&session_repo, &session.id, client.as_ref()).await;
+                if let Some(ref channel) = session.channel_id {
+                    if channel_id_override.as_deref() != Some(channel.as_str()) {
+                        state.session_channels.set(&session.id, channel);
+                    }
+                }
+                state
+                    .driver_registry
+                    .register(&session.id, state.mcp_driver_with_peer(peer))
+                    .await;
+                spawn_stall_detector_for_session(&state, &session.id).await;
+                return;
+            }
+
             // Before creating, terminate any stale active direct-connection
             // sessions left behind by prior window reloads or reconnections.
             // Only sessions owned by LOCAL_AGENT_OWNER are cleaned up — spawned
@@ -553,19 +1082,6 @@ impl ServerHandler for IntercomServer {
                 }
             }
 
-            let workspace_root = channel_id_override
-                .as_deref()
-                .map_or_else(
-                    || state.config.default_workspace_root(),
-                    |ch| state.config.workspace_root_for_channel(ch),
-                )
-                .to_string_lossy()
-                .into_owned();
-            let mode = if is_remote {
-                SessionMode::Remote
-            } else {
-                SessionMode::Local
-            };
             let session = Session::new(
                 LOCAL_AGENT_OWNER.to_owned(),
                 workspace_root,
@@ -585,6 +1101,7 @@ impl ServerHandler for IntercomServer {
                                 mode = ?mode,
                                 "auto-created session activated on direct connection"
                             );
+                            record_client(&session_repo, &created.id, client.as_ref()).await;
                             // Record the session ID so Drop can terminate it
                             // when the transport closes (T045/T046).
                             if session_db_id.set(created.id.clone()).is_err() {
@@ -601,6 +1118,10 @@ impl ServerHandler for IntercomServer {
                                     warn!(%err, "audit log write failed (session start)");
                                 }
                             }
+                            state
+                                .driver_registry
+                                .register(&created.id, state.mcp_driver_with_peer(peer))
+                                .await;
                             // Spawn a per-session stall detector for direct connections (FR-028).
                             spawn_stall_detector_for_session(&state, &created.id).await;
 
@@ -621,7 +1142,7 @@ impl ServerHandler for IntercomServer {
                                         ),
                                         text: Some(format!(
                                             "\u{1f680} Session `{}` connected",
-                                            session.id.chars().take(8).collect::<String>()
+                                            session.short_id
                                         )),
                                         blocks: Some(started_blocks),
                                         thread_ts: None,
@@ -669,6 +1190,41 @@ impl ServerHandler for IntercomServer {
         }
     }
 
+    /// Handle client-initiated custom notifications.
+    ///
+    /// Only `intercom/subscribe` is recognised: it replaces the connection's
+    /// session hook subscriptions. Other methods are ignored.
+    fn on_custom_notification(
+        &self,
+        notification: CustomNotification,
+        _context: NotificationContext<RoleServer>,
+    ) -> impl Future<Output = ()> + Send + '_ {
+        let session_id = self
+            .session_id_override
+            .clone()
+            .or_else(|| self.session_db_id.get().cloned());
+        let state = Arc::clone(&self.state);
+        async move {
+            if notification.method != SUBSCRIBE_METHOD {
+                return;
+            }
+            let Some(session_id) = session_id else {
+                warn!("intercom/subscribe received before session was established");
+                return;
+            };
+            match notification.params_as::<SubscribeParams>() {
+                Ok(params) => {
+                    let kinds = params.unwrap_or_default().kinds();
+                    info!(session_id, ?kinds, "session hooks subscribed");
+                    state.session_hooks.subscribe(&session_id, kinds);
+                }
+                Err(err) => {
+                    warn!(%err, session_id, "invalid intercom/subscribe params");
+                }
+            }
+        }
+    }
+
     fn call_tool(
         &self,
         request: CallToolRequestParam,
@@ -681,15 +1237,26 @@ impl ServerHandler for IntercomServer {
         // Reset stall timer on every tool call (T053).
         let state = Arc::clone(&self.state);
         let audit_logger = self.state.audit_logger.clone();
-        let session_id = self.session_db_id.get().cloned();
         // For spawned agents session_db_id is never set; fall back to the
         // pre-assigned session_id_override so the correct detector is reset.
-        let effective_session_id = self
-            .session_id_override
-            .clone()
-            .or_else(|| session_id.clone());
+        let effective_session_id = self.bound_session_id().map(str::to_owned);
+        // Tools gated by `[clients]` are refused before anything else runs.
+        let client = context
+            .peer
+            .peer_info()
+            .map(|info| info.client_info.name.clone());
+        let gated = state.config.clients.denies(client.as_deref(), &tool_name);
+        let keepalive = Keepalive::for_call(&state.config.timeouts, &tool_name, &context);
 
         async move {
+            // Working-hours routing is decided as the call is about to post.
+            if let Some(ref sid) = effective_session_id {
+                let now = chrono::Local::now().naive_local();
+                if let Err(err) = working_hours::route_session(&state, sid, now).await {
+                    warn!(%err, session_id = %sid, "working-hours routing failed");
+                }
+            }
+
             // Reset stall detector only for the calling session (T053).
             if let (Some(ref detectors), Some(ref sid)) =
                 (&state.stall_detectors, &effective_session_id)
@@ -699,9 +1266,55 @@ impl ServerHandler for IntercomServer {
                 }
             }
 
-            let result = router
-                .call(ToolCallContext::new(self, request, context))
-                .await;
+            // Heartbeat-required mode: refuse work once the agent's heartbeat
+            // is overdue, and stop its clock while a call is being served.
+            let heartbeat_deadline = heartbeat_enforcer::deadline(&state);
+            let heartbeat_session = effective_session_id
+                .as_deref()
+                .filter(|_| heartbeat_deadline.is_some() && !gated);
+            let heartbeat_overdue = match (heartbeat_session, heartbeat_deadline) {
+                (Some(sid), Some(deadline))
+                    if !HEARTBEAT_EXEMPT_TOOLS.contains(&tool_name.as_str()) =>
+                {
+                    state.heartbeats.is_overdue(sid, Utc::now(), deadline)
+                }
+                _ => false,
+            };
+            if let (Some(sid), false) = (heartbeat_session, heartbeat_overdue) {
+                state.heartbeats.start_call(sid, Utc::now());
+            }
+
+            let paused = match effective_session_id {
+                Some(ref sid)
+                    if !gated
+                        && !heartbeat_overdue
+                        && PAUSE_CHECKPOINT_TOOLS.contains(&tool_name.as_str()) =>
+                {
+                    apply_pending_pause(&state, sid).await
+                }
+                _ => None,
+            };
+
+            let result = match (paused, heartbeat_session) {
+                _ if gated => {
+                    info!(tool = %tool_name, client = ?client, "tool denied to client");
+                    Ok(client_refusal(&tool_name, client.as_deref()))
+                }
+                (Some(ref sid), _) if tool_name != "standby" => pause_directive(sid),
+                (_, Some(sid)) if heartbeat_overdue => heartbeat_directive(sid),
+                _ if proxy::is_proxied_name(&tool_name) => proxy::call_tool(self, request).await,
+                _ => {
+                    progress::with_keepalive(
+                        router.call(ToolCallContext::new(self, request, context)),
+                        keepalive,
+                    )
+                    .await
+                }
+            };
+
+            if let (Some(sid), false) = (heartbeat_session, heartbeat_overdue) {
+                state.heartbeats.finish_call(sid, Utc::now());
+            }
 
             // Reset again after tool completion to avoid false stall triggers.
             if let (Some(ref detectors), Some(ref sid)) =
@@ -734,11 +1347,23 @@ impl ServerHandler for IntercomServer {
     fn list_tools(
         &self,
         _request: Option<PaginatedRequestParam>,
-        _context: RequestContext<RoleServer>,
+        context: RequestContext<RoleServer>,
     ) -> impl Future<Output = Result<ListToolsResult, rmcp::ErrorData>> + Send + '_ {
-        let tools = Self::all_tools();
-
-        std::future::ready(Ok(ListToolsResult::with_all_items(tools)))
+        let workspace = self.proxy_workspace();
+        let state = Arc::clone(&self.state);
+        let client = context
+            .peer
+            .peer_info()
+            .map(|info| info.client_info.name.clone());
+        async move {
+            let mut tools = Self::all_tools();
+            if let Some(ref workspace) = workspace {
+                let root = proxy::workspace_root(&state, workspace);
+                tools.extend(state.proxy.tools_for(workspace, &root).await);
+            }
+            tools.retain(|tool| !state.config.clients.denies(client.as_deref(), &tool.name));
+            Ok(ListToolsResult::with_all_items(tools))
+        }
     }
 
     fn list_resources(
@@ -746,14 +1371,29 @@ impl ServerHandler for IntercomServer {
         _request: Option<PaginatedRequestParam>,
         _context: RequestContext<RoleServer>,
     ) -> impl Future<Output = Result<ListResourcesResult, rmcp::ErrorData>> + Send + '_ {
-        let result = match self.effective_channel_id() {
-            Some(channel_id) => crate::mcp::resources::slack_channel::list_resources(channel_id),
+        let mut result = match self.routed_channel_id() {
+            Some(channel_id) => crate::mcp::resources::slack_channel::list_resources(&channel_id),
             None => ListResourcesResult {
                 resources: vec![],
                 next_cursor: None,
                 ..Default::default()
             },
         };
+        if self.state.config.knowledge.enabled {
+            result
+                .resources
+                .push(crate::mcp::resources::knowledge::resource());
+        }
+        result
+            .resources
+            .push(crate::mcp::resources::server_config::resource());
+        if let Some(session_id) = self.bound_session_id() {
+            result
+                .resources
+                .extend(crate::mcp::resources::session_context::resources_for(
+                    session_id,
+                ));
+        }
         std::future::ready(Ok(result))
     }
 
@@ -763,9 +1403,20 @@ impl ServerHandler for IntercomServer {
         _context: RequestContext<RoleServer>,
     ) -> impl Future<Output = Result<ListResourceTemplatesResult, rmcp::ErrorData>> + Send + '_
     {
-        std::future::ready(Ok(
-            crate::mcp::resources::slack_channel::resource_templates(),
-        ))
+        let mut result = crate::mcp::resources::slack_channel::resource_templates();
+        result
+            .resource_templates
+            .push(crate::mcp::resources::session_report::resource_template());
+        result
+            .resource_templates
+            .push(crate::mcp::resources::session_timeline::resource_template());
+        result
+            .resource_templates
+            .push(crate::mcp::resources::session_logs::resource_template());
+        result
+            .resource_templates
+            .extend(crate::mcp::resources::session_context::resource_templates());
+        std::future::ready(Ok(result))
     }
 
     fn read_resource(
@@ -774,8 +1425,63 @@ impl ServerHandler for IntercomServer {
         _context: RequestContext<RoleServer>,
     ) -> impl Future<Output = Result<ReadResourceResult, rmcp::ErrorData>> + Send + '_ {
         let state = Arc::clone(&self.state);
-        let effective_channel = self.effective_channel_id().map(str::to_owned);
+        let effective_channel = self.routed_channel_id();
         async move {
+            if crate::mcp::resources::knowledge::is_knowledge_uri(&request.uri) {
+                return crate::mcp::resources::knowledge::read_resource(&request, &state)
+                    .await
+                    .map_err(|err| {
+                        rmcp::ErrorData::internal_error(
+                            format!("resource read failed: {err}"),
+                            None,
+                        )
+                    });
+            }
+            if crate::mcp::resources::server_config::is_config_uri(&request.uri) {
+                return Ok(crate::mcp::resources::server_config::read_resource(
+                    &request, &state,
+                ));
+            }
+            if crate::mcp::resources::session_report::parse_report_uri(&request.uri).is_some() {
+                return crate::mcp::resources::session_report::read_resource(&request, &state)
+                    .await
+                    .map_err(|err| {
+                        rmcp::ErrorData::internal_error(
+                            format!("resource read failed: {err}"),
+                            None,
+                        )
+                    });
+            }
+            if crate::mcp::resources::session_timeline::parse_timeline_uri(&request.uri).is_some() {
+                return crate::mcp::resources::session_timeline::read_resource(&request, &state)
+                    .await
+                    .map_err(|err| {
+                        rmcp::ErrorData::internal_error(
+                            format!("resource read failed: {err}"),
+                            None,
+                        )
+                    });
+            }
+            if crate::mcp::resources::session_context::parse_context_uri(&request.uri).is_some() {
+                return crate::mcp::resources::session_context::read_resource(&request, &state)
+                    .await
+                    .map_err(|err| {
+                        rmcp::ErrorData::internal_error(
+                            format!("resource read failed: {err}"),
+                            None,
+                        )
+                    });
+            }
+            if crate::mcp::resources::session_logs::parse_logs_uri(&request.uri).is_some() {
+                return crate::mcp::resources::session_logs::read_resource(&request, &state)
+                    .await
+                    .map_err(|err| {
+                        rmcp::ErrorData::internal_error(
+                            format!("resource read failed: {err}"),
+                            None,
+                        )
+                    });
+            }
             let channel = effective_channel.ok_or_else(|| {
                 rmcp::ErrorData::invalid_params(
                     "no Slack channel configured for this session; \
@@ -792,6 +1498,132 @@ impl ServerHandler for IntercomServer {
     }
 }
 
+/// Apply an operator pause pending for `session_id`, if any.
+///
+/// Returns the session ID when the session was just paused.
+async fn apply_pending_pause(state: &AppState, session_id: &str) -> Option<String> {
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    match session_manager::take_pending_pause(session_id, &repo, &state.pause_requests).await {
+        Ok(Some(session)) => {
+            info!(session_id, "pending pause applied at agent checkpoint");
+            if let Some(ref slack) = state.slack {
+                session_manager::notify_session_paused(&session, slack).await;
+            }
+            Some(session.id)
+        }
+        Ok(None) => None,
+        Err(err) => {
+            warn!(%err, session_id, "failed to apply pending pause");
+            None
+        }
+    }
+}
+
+/// Tool result telling the agent to stop and wait in `standby`.
+fn pause_directive(session_id: &str) -> Result<CallToolResult, rmcp::ErrorData> {
+    let body = serde_json::json!({
+        "status": PAUSE_REQUESTED_STATUS,
+        "session_id": session_id,
+        "directive": PAUSE_DIRECTIVE,
+    });
+    let content = rmcp::model::Content::json(body).map_err(|err| {
+        rmcp::ErrorData::internal_error(format!("failed to serialize pause directive: {err}"), None)
+    })?;
+    Ok(CallToolResult::success(vec![content]))
+}
+
+/// Error tool result for a call to `tool`, which `[clients]` denies to
+/// `client`.
+fn client_refusal(tool: &str, client: Option<&str>) -> CallToolResult {
+    let client = client.unwrap_or("unnamed client");
+    CallToolResult::error(vec![rmcp::model::Content::text(format!(
+        "tool '{tool}' is not available to MCP client '{client}'"
+    ))])
+}
+
+/// The direct-connection session a client reconnecting within `grace` of
+/// its disconnect should resume: active but offline, last updated within
+/// `grace`, with the same workspace, mode and client name.
+async fn reusable_session(
+    repo: &SessionRepo,
+    workspace_root: &str,
+    mode: SessionMode,
+    client_name: Option<&str>,
+    grace: Duration,
+) -> Option<Session> {
+    if grace.is_zero() {
+        return None;
+    }
+    let cutoff = Utc::now() - chrono::Duration::from_std(grace).ok()?;
+    let sessions = match repo.list_active().await {
+        Ok(sessions) => sessions,
+        Err(err) => {
+            warn!(%err, "failed to query active sessions for reconnect");
+            return None;
+        }
+    };
+    // Newest first, so the most recently dropped session wins.
+    sessions.into_iter().find(|session| {
+        session.owner_user_id == LOCAL_AGENT_OWNER
+            && session.connectivity_status == ConnectivityStatus::Offline
+            && session.workspace_root == workspace_root
+            && session.mode == mode
+            && session.client_name.as_deref() == client_name
+            && session.updated_at >= cutoff
+    })
+}
+
+/// Wait out the reconnect `grace` window for the disconnected session `id`
+/// and report whether it should now be terminated: it has not ended and,
+/// after a non-zero grace, no client took it back or dropped it since.
+async fn still_disconnected(repo: &SessionRepo, id: &str, grace: Duration) -> bool {
+    if !grace.is_zero() {
+        tokio::time::sleep(grace).await;
+    }
+    let Ok(Some(session)) = repo.get_by_id(id).await else {
+        return false;
+    };
+    if matches!(
+        session.status,
+        SessionStatus::Terminated | SessionStatus::Interrupted
+    ) {
+        return false;
+    }
+    grace.is_zero()
+        || (session.connectivity_status == ConnectivityStatus::Offline
+            && chrono::Duration::from_std(grace)
+                .is_ok_and(|grace| session.updated_at + grace <= Utc::now()))
+}
+
+/// Record the MCP client implementation `session_id` connected with.
+async fn record_client(repo: &SessionRepo, session_id: &str, client: Option<&Implementation>) {
+    let Some(client) = client else {
+        return;
+    };
+    if let Err(err) = repo
+        .set_client(session_id, &client.name, &client.version)
+        .await
+    {
+        warn!(%err, session_id, "failed to record MCP client");
+    }
+}
+
+/// Tool result telling an agent with an overdue heartbeat to `ping` first.
+fn heartbeat_directive(session_id: &str) -> Result<CallToolResult, rmcp::ErrorData> {
+    let body = serde_json::json!({
+        "status": HEARTBEAT_REQUIRED_STATUS,
+        "session_id": session_id,
+        "directive": HEARTBEAT_DIRECTIVE,
+    });
+    let content = rmcp::model::Content::json(body).map_err(|err| {
+        rmcp::ErrorData::internal_error(
+            format!("failed to serialize heartbeat directive: {err}"),
+            None,
+        )
+    })?;
+    Ok(CallToolResult::success(vec![content]))
+}
+
 impl Drop for IntercomServer {
     /// Mark the associated DB session as `Terminated` when the MCP transport closes.
     ///
@@ -805,7 +1637,8 @@ impl Drop for IntercomServer {
     ///
     /// Only direct-connection sessions (Case 2 of `on_initialized`) store an ID
     /// in `session_db_id`.  Spawned-agent servers leave it unset, so their Drop
-    /// is always a no-op for the DB path but still cleans up the stall detector.
+    /// is always a no-op for the DB path but still cleans up the stall detector
+    /// and the session's MCP driver binding, and marks the session `offline`.
     fn drop(&mut self) {
         // ── Remove stall detector for both Case 1 and Case 2 ────────────────
         // Case 1 (spawned): session_id_override is set; Case 2 (direct): session_db_id is set.
@@ -823,6 +1656,40 @@ impl Drop for IntercomServer {
                             info!(session_id = %sid, "stall detector removed on session end");
                         }
                     }
+                    state.heartbeats.forget(&sid);
+                    // Drop the MCP driver binding and mark the agent offline
+                    // so steering queues until it reconnects. ACP sessions
+                    // own both through the stream lifecycle, so leave those
+                    // alone.
+                    let repo = SessionRepo::new(Arc::clone(&state.db));
+                    let protocol = repo
+                        .get_by_id(&sid)
+                        .await
+                        .ok()
+                        .flatten()
+                        .map(|s| s.protocol_mode);
+                    if protocol != Some(ProtocolMode::Acp) {
+                        // Unbind before marking offline: a reconnecting
+                        // client may take an offline session back and bind
+                        // its own driver.
+                        state.driver_registry.deregister(&sid).await;
+                        state.session_hooks.clear(&sid);
+                        if let Err(err) = repo
+                            .set_connectivity_status(&sid, ConnectivityStatus::Offline)
+                            .await
+                        {
+                            warn!(%err, session_id = %sid, "failed to mark session offline");
+                        }
+                        state.event_bus.publish(
+                            ProtocolMode::Mcp,
+                            None,
+                            AgentEvent::SessionTerminated {
+                                session_id: sid,
+                                exit_code: None,
+                                reason: "transport disconnected".to_owned(),
+                            },
+                        );
+                    }
                 });
             }
         }
@@ -830,11 +1697,25 @@ impl Drop for IntercomServer {
         // ── Terminate direct-connection session in the database (Case 2) ────
         if let Some(id) = self.session_db_id.get().cloned() {
             let db = Arc::clone(&self.state.db);
+            let grace = Duration::from_secs(self.state.config.timeouts.reconnect_grace_seconds);
             let audit_logger = self.state.audit_logger.clone();
             let slack = self.state.slack.clone();
+            let log_dir = crate::audit::log_dir(self.state.config.default_workspace_root());
             if let Ok(handle) = tokio::runtime::Handle::try_current() {
                 handle.spawn(async move {
                     let session_repo = SessionRepo::new(db);
+                    if !still_disconnected(&session_repo, &id, grace).await {
+                        return;
+                    }
+                    // Audit-log session termination (T061). Written before the
+                    // Slack summary so the uploaded session log includes it.
+                    if let Some(ref logger) = audit_logger {
+                        let entry = AuditEntry::new(AuditEventType::SessionTerminate)
+                            .with_session(id.clone());
+                        if let Err(err) = logger.log_entry(entry) {
+                            warn!(%err, "audit log write failed (session terminate)");
+                        }
+                    }
                     match session_repo
                         .set_terminated(&id, SessionStatus::Terminated)
                         .await
@@ -847,6 +1728,7 @@ impl Drop for IntercomServer {
                                     &terminated_session,
                                     "transport disconnected",
                                     s,
+                                    &log_dir,
                                 )
                                 .await;
                             }
@@ -859,14 +1741,6 @@ impl Drop for IntercomServer {
                             );
                         }
                     }
-                    // Audit-log session termination (T061).
-                    if let Some(ref logger) = audit_logger {
-                        let entry = AuditEntry::new(AuditEventType::SessionTerminate)
-                            .with_session(id.clone());
-                        if let Err(err) = logger.log_entry(entry) {
-                            warn!(%err, "audit log write failed (session terminate)");
-                        }
-                    }
                 });
             }
             // If no runtime is active, the stale-session sweep in the next
@@ -0,0 +1,290 @@
+//! Health and metrics endpoints.
+//!
+//! - `GET /health` answers `ok` while the process is up (liveness).
+//! - `GET /health/ready` answers `200` when the database responds, every
+//!   supervised background task is running and, on a server with Slack,
+//!   Socket Mode is connected; `503` otherwise. The JSON body carries the
+//!   Socket Mode connection state and each supervised task's status either
+//!   way.
+//! - `GET /metrics` exposes Slack connection, Slack outgoing queue, Slack
+//!   rate budget, supervised task, event bus and per-session ACP writer
+//!   queue counters in the Prometheus text format.
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Instant;
+
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Json;
+
+use crate::acp::writer::WriterMetricsSnapshot;
+use crate::state::AppState;
+
+/// Router serving `/health`, `/health/ready` and `/metrics`.
+pub fn router(state: Arc<AppState>) -> axum::Router {
+    axum::Router::new()
+        .route("/health", get(health))
+        .route("/health/ready", get(ready))
+        .route("/metrics", get(metrics))
+        .with_state(state)
+}
+
+/// Handler for `GET /health` — returns 200 OK with a plain-text body.
+///
+/// Useful for probing liveness without initiating an MCP session.
+async fn health() -> &'static str {
+    "ok"
+}
+
+/// Handler for `GET /health/ready`.
+async fn ready(State(state): State<Arc<AppState>>) -> Response {
+    let database = sqlx::query("SELECT 1")
+        .execute(state.db.as_ref())
+        .await
+        .is_ok();
+    let socket = state
+        .slack
+        .as_ref()
+        .map(|slack| slack.socket_health().snapshot());
+    let subsystems = state.subsystems.snapshot();
+    let ready = database
+        && socket.as_ref().is_none_or(|s| s.connected)
+        && subsystems.iter().all(|s| s.running);
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    let body = serde_json::json!({
+        "ready": ready,
+        "database": database,
+        "slack": socket,
+        "subsystems": subsystems,
+    });
+    (status, Json(body)).into_response()
+}
+
+/// Handler for `GET /metrics`.
+async fn metrics(State(state): State<Arc<AppState>>) -> Response {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        render_metrics(&state).await,
+    )
+        .into_response()
+}
+
+/// Current metrics in the Prometheus text exposition format.
+pub async fn render_metrics(state: &AppState) -> String {
+    let mut out = String::new();
+    if let Some(ref slack) = state.slack {
+        let socket = slack.socket_health().snapshot();
+        metric(
+            &mut out,
+            "slack_socket_connected",
+            "gauge",
+            "Whether Slack Socket Mode is connected.",
+            u64::from(socket.connected),
+        );
+        if let Some(since) = socket.connected_since {
+            metric(
+                &mut out,
+                "slack_socket_connected_since_seconds",
+                "gauge",
+                "Unix time the current Socket Mode connection opened.",
+                since.timestamp(),
+            );
+        }
+        if let Some(hello) = socket.last_hello {
+            metric(
+                &mut out,
+                "slack_socket_last_hello_seconds",
+                "gauge",
+                "Unix time of the last hello from Slack.",
+                hello.timestamp(),
+            );
+        }
+        metric(
+            &mut out,
+            "slack_socket_disconnects_total",
+            "counter",
+            "Socket Mode connections lost since start.",
+            socket.disconnects,
+        );
+        metric(
+            &mut out,
+            "slack_socket_forced_reconnects_total",
+            "counter",
+            "Socket Mode reconnects forced with `slack reconnect`.",
+            socket.forced_reconnects,
+        );
+        metric(
+            &mut out,
+            "slack_outbound_queue_depth",
+            "gauge",
+            "Messages and updates waiting on the Slack outgoing queue.",
+            slack.queue_depth(),
+        );
+        let budget = slack.rate_budget();
+        metric(
+            &mut out,
+            "slack_api_calls_last_minute",
+            "gauge",
+            "Slack Web API calls made in the last minute.",
+            budget.calls_in_window(Instant::now()),
+        );
+        metric(
+            &mut out,
+            "slack_rate_limited_total",
+            "counter",
+            "Slack Web API calls answered with 429.",
+            budget.rate_limited_count(),
+        );
+    }
+
+    subsystem_metrics(&mut out, state);
+    if let Some(ref acp) = state.acp_driver {
+        writer_metrics(&mut out, &acp.writer_metrics_snapshot().await);
+    }
+
+    let events = state.event_bus.metrics().snapshot();
+    metric(
+        &mut out,
+        "events_published_total",
+        "counter",
+        "Agent events published on the event bus.",
+        events.published,
+    );
+    metric(
+        &mut out,
+        "events_lagged_total",
+        "counter",
+        "Agent events skipped by lagging subscribers.",
+        events.lagged,
+    );
+    let name = "agent_intercom_events_total";
+    let _ = writeln!(out, "# HELP {name} Agent events seen, by kind.");
+    let _ = writeln!(out, "# TYPE {name} counter");
+    for (kind, count) in &events.by_kind {
+        let _ = writeln!(out, "{name}{{kind=\"{kind}\"}} {count}");
+    }
+    out
+}
+
+/// Running state and restarts of each supervised background task.
+fn subsystem_metrics(out: &mut String, state: &AppState) {
+    let subsystems = state.subsystems.snapshot();
+    labelled(
+        out,
+        "subsystem_up",
+        "gauge",
+        "Whether a supervised background task is running.",
+        "subsystem",
+        subsystems
+            .iter()
+            .map(|s| (s.component.as_str(), u64::from(s.running))),
+    );
+    labelled(
+        out,
+        "subsystem_restarts_total",
+        "counter",
+        "Restarts of a supervised background task since start.",
+        "subsystem",
+        subsystems
+            .iter()
+            .map(|s| (s.component.as_str(), s.restarts)),
+    );
+}
+
+/// Name, type, help text and value of one ACP writer metric.
+type WriterSeries = (
+    &'static str,
+    &'static str,
+    &'static str,
+    fn(&WriterMetricsSnapshot) -> u64,
+);
+
+/// Outbound queue counters and backpressure of each ACP session's writer.
+fn writer_metrics(out: &mut String, writers: &[(String, WriterMetricsSnapshot)]) {
+    let series: [WriterSeries; 7] = [
+        (
+            "acp_writer_enqueued_total",
+            "counter",
+            "Messages accepted into an ACP session's outbound queue.",
+            |m| m.enqueued,
+        ),
+        (
+            "acp_writer_written_total",
+            "counter",
+            "Messages written to an ACP agent's stdin.",
+            |m| m.written,
+        ),
+        (
+            "acp_writer_rejected_total",
+            "counter",
+            "Messages refused because an ACP outbound queue was full.",
+            |m| m.rejected,
+        ),
+        (
+            "acp_writer_enqueue_timeouts_total",
+            "counter",
+            "Messages that timed out waiting for ACP outbound queue space.",
+            |m| m.enqueue_timeouts,
+        ),
+        (
+            "acp_writer_write_timeouts_total",
+            "counter",
+            "Writes to an ACP agent's stdin that exceeded the write timeout.",
+            |m| m.write_timeouts,
+        ),
+        (
+            "acp_writer_queue_depth",
+            "gauge",
+            "Messages waiting on an ACP session's outbound queue.",
+            |m| m.depth as u64,
+        ),
+        (
+            "acp_writer_queue_high_water",
+            "gauge",
+            "Highest depth an ACP session's outbound queue has reached.",
+            |m| m.high_water as u64,
+        ),
+    ];
+    for (name, kind, help, value) in series {
+        labelled(
+            out,
+            name,
+            kind,
+            help,
+            "session",
+            writers.iter().map(|(id, m)| (id.as_str(), value(m))),
+        );
+    }
+}
+
+/// Append one sample per labelled series, labelled `label`.
+fn labelled<'a, V: std::fmt::Display>(
+    out: &mut String,
+    name: &str,
+    kind: &str,
+    help: &str,
+    label: &str,
+    samples: impl Iterator<Item = (&'a str, V)>,
+) {
+    let name = format!("agent_intercom_{name}");
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+    for (value_label, value) in samples {
+        let _ = writeln!(out, "{name}{{{label}=\"{value_label}\"}} {value}");
+    }
+}
+
+/// Append one unlabelled sample named `agent_intercom_<name>`.
+fn metric(out: &mut String, name: &str, kind: &str, help: &str, value: impl std::fmt::Display) {
+    let name = format!("agent_intercom_{name}");
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+    let _ = writeln!(out, "{name} {value}");
+}
@@ -1,8 +1,18 @@
 //! Model Context Protocol server layer.
 
+pub mod approval_gate;
+pub mod approval_link;
+pub mod approval_webhook;
+pub mod config_api;
 pub mod context;
+pub mod device_pairing;
+pub mod diff_staging;
 pub mod handler;
+pub mod health;
+pub mod progress;
+pub mod proxy;
 pub mod resources;
+pub mod session_share;
 pub mod sse;
 pub mod tools;
 pub mod transport;
@@ -0,0 +1,87 @@
+//! Progress keepalives for blocking tool calls.
+//!
+//! `check_clearance`, `transmit` and `standby` can wait on the operator for
+//! far longer than an IDE client's own request timeout. When the client
+//! asked for progress (a `progressToken` in the call's `_meta`), a
+//! `notifications/progress` is sent every `timeouts.progress_interval_seconds`
+//! while the call is pending, which resets those timeouts. SSE comment
+//! keepalives are not used: some Streamable-HTTP clients cannot parse them.
+
+use std::future::Future;
+use std::time::Duration;
+
+use rmcp::model::{ProgressNotificationParam, ProgressToken};
+use rmcp::service::{Peer, RequestContext, RoleServer};
+use tokio::time::{Instant, MissedTickBehavior};
+use tracing::debug;
+
+use crate::config::TimeoutConfig;
+
+/// Tools that block on the operator and send progress keepalives.
+pub const KEEPALIVE_TOOLS: [&str; 3] = ["check_clearance", "transmit", "standby"];
+
+/// Where and how often to send keepalives for one call.
+pub struct Keepalive {
+    /// Connection the call arrived on.
+    pub peer: Peer<RoleServer>,
+    /// Token the client attached to the call.
+    pub token: ProgressToken,
+    /// Time between notifications.
+    pub interval: Duration,
+}
+
+impl Keepalive {
+    /// Keepalive settings for a call of `tool`, or `None` when the tool does
+    /// not block, keepalives are disabled, or the client sent no progress
+    /// token.
+    #[must_use]
+    pub fn for_call(
+        timeouts: &TimeoutConfig,
+        tool: &str,
+        context: &RequestContext<RoleServer>,
+    ) -> Option<Self> {
+        if !KEEPALIVE_TOOLS.contains(&tool) {
+            return None;
+        }
+        Some(Self {
+            interval: timeouts.progress_interval()?,
+            token: context.meta.get_progress_token()?,
+            peer: context.peer.clone(),
+        })
+    }
+}
+
+/// Drive `call` to completion, sending a progress notification every
+/// `keepalive.interval` until it finishes. Without a keepalive, `call` is
+/// simply awaited.
+///
+/// `progress` is the number of seconds waited so far, so it increases with
+/// every notification as the protocol requires. A notification that cannot
+/// be sent is logged and does not affect the call.
+pub async fn with_keepalive<F: Future>(call: F, keepalive: Option<Keepalive>) -> F::Output {
+    let Some(keepalive) = keepalive else {
+        return call.await;
+    };
+    let started = Instant::now();
+    let mut ticks = tokio::time::interval_at(started + keepalive.interval, keepalive.interval);
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    tokio::pin!(call);
+    loop {
+        tokio::select! {
+            output = &mut call => return output,
+            _ = ticks.tick() => {
+                let waited = started.elapsed().as_secs();
+                let param = ProgressNotificationParam {
+                    progress_token: keepalive.token.clone(),
+                    #[allow(clippy::cast_precision_loss)] // Seconds waited fit an f64 exactly.
+                    progress: waited as f64,
+                    total: None,
+                    message: Some(format!("Waiting for the operator ({waited}s)")),
+                };
+                if let Err(err) = keepalive.peer.notify_progress(param).await {
+                    debug!(%err, "progress keepalive not sent");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,454 @@
+//! MCP proxy mode: an approval-enforcing gateway to downstream MCP servers.
+//!
+//! Each `[[workspace.proxy]]` entry names a stdio MCP server that
+//! agent-intercom starts in the workspace directory and talks to as an MCP
+//! client. The downstream tools selected by the entry's `tools` globs are
+//! re-exported to the workspace's agents as `<server>__<tool>`. Each call
+//! is classified by the workspace policy's `proxy` rules over its qualified
+//! name `<server>.<tool>` (see [`PolicyEvaluator::classify_proxy_call`]):
+//! auto-approved calls are forwarded at once, denied calls are refused, and
+//! the rest are forwarded only once the operator accepts the approval
+//! request posted to Slack, at the risk level the `proxy` rules give it.
+//!
+//! Downstream servers are connected lazily on first use and shared by every
+//! session of the workspace. A server whose configuration changes on
+//! hot-reload, or whose process exits, is restarted on the next use.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Content, Tool};
+use rmcp::service::{RoleClient, RunningService, ServiceExt};
+use rmcp::transport::{IntoTransport, TokioChildProcess};
+use tokio::sync::Mutex;
+use tracing::{info, info_span, warn, Instrument};
+
+use crate::config::{ProxyServerConfig, WorkspaceMapping};
+use crate::mcp::approval_gate::{self, Decision};
+use crate::mcp::handler::IntercomServer;
+use crate::mcp::tools::util;
+use crate::models::approval::{ApprovalRequest, ApprovalStatus};
+use crate::models::policy::ProxyAction;
+use crate::models::session::Session;
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::policy::evaluator::PolicyEvaluator;
+use crate::policy::loader::PolicyLoader;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Separator between the proxy name and the downstream tool name in
+/// re-exported tool names.
+pub const TOOL_SEPARATOR: &str = "__";
+
+/// `original_hash` recorded on approval requests for proxied tool calls.
+///
+/// Marks the record as a tool call rather than a file change, so it can
+/// never be applied through `check_diff`.
+pub const PROXY_CALL_HASH: &str = "proxy_call";
+
+/// Name under which `tool` of proxy `server` is re-exported.
+#[must_use]
+pub fn exported_name(server: &str, tool: &str) -> String {
+    format!("{server}{TOOL_SEPARATOR}{tool}")
+}
+
+/// Qualified `<server>.<tool>` name used by policy rules and approvals.
+#[must_use]
+pub fn qualified_name(server: &str, tool: &str) -> String {
+    format!("{server}.{tool}")
+}
+
+/// Whether `name` has the shape of a re-exported tool name.
+///
+/// Built-in tools never contain the separator.
+#[must_use]
+pub fn is_proxied_name(name: &str) -> bool {
+    name.contains(TOOL_SEPARATOR)
+}
+
+/// A connected downstream MCP server.
+pub struct Downstream {
+    config: ProxyServerConfig,
+    /// Downstream tools selected for re-export, under their original names.
+    tools: Vec<Tool>,
+    service: RunningService<RoleClient, ()>,
+}
+
+impl Downstream {
+    /// Start `config.command` in `workspace_root` and connect to it over stdio.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Mcp` if the process cannot be started or the MCP
+    /// handshake or tool listing fails.
+    pub async fn spawn(config: &ProxyServerConfig, workspace_root: &Path) -> Result<Self> {
+        let mut command = tokio::process::Command::new(&config.command);
+        command
+            .args(&config.args)
+            .envs(&config.env)
+            .current_dir(workspace_root);
+        let transport = TokioChildProcess::new(command).map_err(|err| {
+            AppError::Mcp(format!("failed to start proxy '{}': {err}", config.name))
+        })?;
+        Self::connect(config.clone(), transport).await
+    }
+
+    /// Connect to a downstream server over an established transport.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Mcp` if the MCP handshake or tool listing fails.
+    pub async fn connect<T, E, A>(config: ProxyServerConfig, transport: T) -> Result<Self>
+    where
+        T: IntoTransport<RoleClient, E, A>,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let service = ().serve(transport).await.map_err(|err| {
+            AppError::Mcp(format!("proxy '{}' handshake failed: {err}", config.name))
+        })?;
+        let tools: Vec<Tool> = service
+            .list_all_tools()
+            .await
+            .map_err(|err| {
+                AppError::Mcp(format!("proxy '{}' tools/list failed: {err}", config.name))
+            })?
+            .into_iter()
+            .filter(|tool| config.exports(&tool.name))
+            .collect();
+        info!(
+            proxy = %config.name,
+            tools = tools.len(),
+            "connected to downstream MCP server"
+        );
+        Ok(Self {
+            config,
+            tools,
+            service,
+        })
+    }
+
+    /// The proxy name from `[[workspace.proxy]]`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    /// The re-exported tools, renamed to `<server>__<tool>`.
+    #[must_use]
+    pub fn exported_tools(&self) -> Vec<Tool> {
+        self.tools
+            .iter()
+            .map(|tool| {
+                let mut exported = tool.clone();
+                exported.name = exported_name(self.name(), &tool.name).into();
+                let via = format!("[via {}; may require operator approval]", self.name());
+                exported.description = Some(match tool.description {
+                    Some(ref description) => format!("{description} {via}").into(),
+                    None => via.into(),
+                });
+                exported
+            })
+            .collect()
+    }
+
+    /// The re-exported downstream tool called `tool`, if any.
+    #[must_use]
+    pub fn tool(&self, tool: &str) -> Option<&Tool> {
+        self.tools.iter().find(|t| t.name == tool)
+    }
+
+    /// Forward `request` to the downstream `tool`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Mcp` if the downstream call fails at the protocol
+    /// level. Tool-level failures arrive as an error `CallToolResult`.
+    pub async fn call(
+        &self,
+        tool: &str,
+        mut request: CallToolRequestParam,
+    ) -> Result<CallToolResult> {
+        request.name = tool.to_owned().into();
+        self.service.call_tool(request).await.map_err(|err| {
+            AppError::Mcp(format!(
+                "proxy call {} failed: {err}",
+                qualified_name(self.name(), tool)
+            ))
+        })
+    }
+
+    /// Whether the connection can no longer be used.
+    fn is_closed(&self) -> bool {
+        self.service.is_transport_closed()
+    }
+}
+
+/// The connection of one proxy of one workspace, once started.
+type Slot = Arc<Mutex<Option<Arc<Downstream>>>>;
+
+/// Downstream connections keyed by workspace ID and proxy name.
+///
+/// Each key has its own slot: starting a server holds only that slot's
+/// lock, so concurrent callers of the same server wait for one start while
+/// every other server stays usable.
+#[derive(Default)]
+pub struct ProxyRegistry {
+    servers: Mutex<HashMap<(String, String), Slot>>,
+}
+
+impl ProxyRegistry {
+    /// The slot for `key`, created empty on first use.
+    async fn slot(&self, key: (String, String)) -> Slot {
+        Arc::clone(self.servers.lock().await.entry(key).or_default())
+    }
+
+    /// Register an already-connected downstream for `workspace_id`.
+    ///
+    /// It is reused for as long as its configuration matches the
+    /// workspace's `[[workspace.proxy]]` entry of the same name.
+    pub async fn register(&self, workspace_id: &str, downstream: Downstream) {
+        let key = (workspace_id.to_owned(), downstream.name().to_owned());
+        *self.slot(key).await.lock().await = Some(Arc::new(downstream));
+    }
+
+    /// Return the live connection for `config`, starting it if needed.
+    async fn connection(
+        &self,
+        workspace: &WorkspaceMapping,
+        config: &ProxyServerConfig,
+        workspace_root: &Path,
+    ) -> Result<Arc<Downstream>> {
+        let slot = self
+            .slot((workspace.workspace_id.clone(), config.name.clone()))
+            .await;
+        // Held across the spawn so concurrent callers never start duplicates.
+        let mut current = slot.lock().await;
+        if let Some(ref existing) = *current {
+            if existing.config == *config && !existing.is_closed() {
+                return Ok(Arc::clone(existing));
+            }
+        }
+        let downstream = Arc::new(Downstream::spawn(config, workspace_root).await?);
+        *current = Some(Arc::clone(&downstream));
+        Ok(downstream)
+    }
+
+    /// Tools re-exported to agents of `workspace`.
+    ///
+    /// Downstream servers that fail to start are logged and skipped.
+    pub async fn tools_for(
+        &self,
+        workspace: &WorkspaceMapping,
+        workspace_root: &Path,
+    ) -> Vec<Tool> {
+        let mut tools = Vec::new();
+        for config in &workspace.proxy {
+            match self.connection(workspace, config, workspace_root).await {
+                Ok(downstream) => tools.extend(downstream.exported_tools()),
+                Err(err) => warn!(%err, proxy = %config.name, "proxy unavailable"),
+            }
+        }
+        tools
+    }
+
+    /// Resolve a re-exported tool name to its downstream and original name.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` when `exported` names no re-exported
+    /// tool of `workspace`, or `AppError::Mcp` if its server cannot start.
+    pub async fn resolve(
+        &self,
+        workspace: &WorkspaceMapping,
+        workspace_root: &Path,
+        exported: &str,
+    ) -> Result<(Arc<Downstream>, String)> {
+        let not_found = || AppError::NotFound(format!("unknown tool '{exported}'"));
+        let (config, tool) = workspace
+            .proxy
+            .iter()
+            .find_map(|config| {
+                exported
+                    .strip_prefix(config.name.as_str())?
+                    .strip_prefix(TOOL_SEPARATOR)
+                    .map(|tool| (config, tool))
+            })
+            .ok_or_else(not_found)?;
+        if !config.exports(tool) {
+            return Err(not_found());
+        }
+        let downstream = self.connection(workspace, config, workspace_root).await?;
+        if downstream.tool(tool).is_none() {
+            return Err(not_found());
+        }
+        Ok((downstream, tool.to_owned()))
+    }
+
+    /// Drop every connection, stopping the downstream processes.
+    pub async fn shutdown(&self) {
+        self.servers.lock().await.clear();
+    }
+}
+
+/// Directory downstream servers of `workspace` run in.
+#[must_use]
+pub fn workspace_root(state: &AppState, workspace: &WorkspaceMapping) -> PathBuf {
+    workspace
+        .path
+        .clone()
+        .unwrap_or_else(|| state.config.default_workspace_root().to_path_buf())
+}
+
+/// Outcome of the approval gate for one proxied call.
+enum Clearance {
+    /// Forward the call; carries the matched policy rule, or the approval
+    /// request to mark consumed once the call has been forwarded.
+    Granted {
+        rule: Option<String>,
+        request_id: Option<String>,
+    },
+    /// Refuse the call with this explanation.
+    Refused(String),
+}
+
+/// Handle a call to a re-exported tool: gate it, then forward it.
+///
+/// # Errors
+///
+/// Returns `rmcp::ErrorData` for unknown tools and infrastructure failures.
+/// Refused calls are reported to the agent as error tool results.
+pub async fn call_tool(
+    server: &IntercomServer,
+    request: CallToolRequestParam,
+) -> std::result::Result<CallToolResult, rmcp::ErrorData> {
+    let state = Arc::clone(server.state());
+    let unknown =
+        || rmcp::ErrorData::invalid_params(format!("unknown tool '{}'", request.name), None);
+    let workspace = server.proxy_workspace().ok_or_else(unknown)?;
+    let root = workspace_root(&state, &workspace);
+    let (downstream, tool) = match state.proxy.resolve(&workspace, &root, &request.name).await {
+        Ok(resolved) => resolved,
+        Err(AppError::NotFound(_)) => return Err(unknown()),
+        Err(err) => return Err(rmcp::ErrorData::internal_error(err.to_string(), None)),
+    };
+    let qualified = qualified_name(downstream.name(), &tool);
+    let span = info_span!("proxy_call", tool = %qualified);
+
+    async move {
+        let session = util::bound_session(&state, server.bound_session_id()).await?;
+        let clearance = clear_call(server, &state, &session, &downstream, &tool, &request).await?;
+
+        let (rule, request_id) = match clearance {
+            Clearance::Granted { rule, request_id } => (rule, request_id),
+            Clearance::Refused(message) => {
+                info!(%message, "proxied call refused");
+                return Ok(CallToolResult::error(vec![Content::text(message)]));
+            }
+        };
+        info!(matched_rule = ?rule, request_id = ?request_id, "forwarding proxied call");
+
+        // Consume before forwarding so the approval can never be replayed.
+        if let Some(ref request_id) = request_id {
+            let _ = ApprovalRepo::new(Arc::clone(&state.db))
+                .update_status(request_id, ApprovalStatus::Consumed)
+                .await;
+        }
+        let _ = SessionRepo::new(Arc::clone(&state.db))
+            .update_last_activity(&session.id, Some(exported_name(downstream.name(), &tool)))
+            .await;
+
+        downstream
+            .call(&tool, request)
+            .await
+            .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
+    }
+    .instrument(span)
+    .await
+}
+
+/// Run the approval gate for a proxied call.
+async fn clear_call(
+    server: &IntercomServer,
+    state: &Arc<AppState>,
+    session: &Session,
+    downstream: &Downstream,
+    tool: &str,
+    request: &CallToolRequestParam,
+) -> std::result::Result<Clearance, rmcp::ErrorData> {
+    let qualified = qualified_name(downstream.name(), tool);
+    let policy = PolicyLoader::load_cached(&state.policy_cache, Path::new(&session.workspace_root))
+        .await
+        .map_err(|err| {
+            rmcp::ErrorData::internal_error(format!("failed to load workspace policy: {err}"), None)
+        })?;
+    let verdict = PolicyEvaluator::classify_proxy_call(&qualified, &policy);
+    match verdict.action {
+        ProxyAction::AutoApprove => {
+            return Ok(Clearance::Granted {
+                rule: verdict.matched_rule,
+                request_id: None,
+            });
+        }
+        ProxyAction::Deny => {
+            let rule = verdict
+                .matched_rule
+                .as_deref()
+                .unwrap_or("proxy:default_action");
+            return Ok(Clearance::Refused(format!(
+                "`{qualified}` is denied by workspace policy ({rule})"
+            )));
+        }
+        ProxyAction::RequireApproval => {}
+    }
+
+    let (Some(slack), Some(channel)) = (state.slack.as_ref(), server.routed_channel_id()) else {
+        return Ok(Clearance::Refused(format!(
+            "`{qualified}` requires operator approval, but Slack is not configured \
+             for this session"
+        )));
+    };
+
+    let arguments = serde_json::to_string_pretty(&request.arguments.clone().unwrap_or_default())
+        .unwrap_or_default();
+    let description = downstream
+        .tool(tool)
+        .and_then(|t| t.description.as_deref())
+        .map(str::to_owned);
+    let approval = ApprovalRequest::new(
+        session.id.clone(),
+        format!("Call {qualified}"),
+        description,
+        arguments,
+        qualified.clone(),
+        verdict.risk_level,
+        PROXY_CALL_HASH.to_owned(),
+    );
+    let decision = approval_gate::request_decision(
+        state,
+        slack,
+        &channel,
+        session,
+        &approval,
+        format!("\u{1f50c} Proxied tool call: `{qualified}`"),
+    )
+    .await?;
+
+    Ok(match decision {
+        Decision::Approved => Clearance::Granted {
+            rule: None,
+            request_id: Some(approval.id),
+        },
+        Decision::Rejected(Some(reason)) => {
+            Clearance::Refused(format!("operator rejected `{qualified}`: {reason}"))
+        }
+        Decision::Rejected(None) => Clearance::Refused(format!("operator rejected `{qualified}`")),
+        Decision::Abandoned => {
+            Clearance::Refused(format!("approval for `{qualified}` was abandoned"))
+        }
+        Decision::TimedOut(seconds) => Clearance::Refused(format!(
+            "approval for `{qualified}` timed out after {seconds} seconds"
+        )),
+    })
+}
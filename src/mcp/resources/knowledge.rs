@@ -0,0 +1,105 @@
+//! `intercom://knowledge` MCP resource handler.
+//!
+//! Serves the operator-approved notes of the shared knowledge base as
+//! JSON, so every session can read what earlier agents learned. Notes
+//! still pending or rejected are never included.
+
+use std::sync::Arc;
+
+use rmcp::model::{
+    Annotated, RawResource, ReadResourceRequestParam, ReadResourceResult, Resource,
+    ResourceContents,
+};
+use tracing::info;
+
+use crate::persistence::knowledge_repo::KnowledgeRepo;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// URI of the knowledge base resource.
+pub const RESOURCE_URI: &str = "intercom://knowledge";
+
+/// Human-readable name for this resource.
+pub const RESOURCE_NAME: &str = "Knowledge Base";
+
+/// Description of this resource.
+pub const RESOURCE_DESCRIPTION: &str = "Operator-approved notes shared across sessions: \
+     how to build, test, and work in this team's repositories.";
+
+/// Whether `uri` addresses the knowledge base.
+///
+/// # Examples
+///
+/// ```
+/// use agent_intercom::mcp::resources::knowledge::is_knowledge_uri;
+///
+/// assert!(is_knowledge_uri("intercom://knowledge"));
+/// assert!(!is_knowledge_uri("intercom://session/3f2a9c1e/report"));
+/// ```
+#[must_use]
+pub fn is_knowledge_uri(uri: &str) -> bool {
+    uri == RESOURCE_URI
+}
+
+/// Resource entry for `resources/list`.
+#[must_use]
+pub fn resource() -> Resource {
+    Annotated::new(
+        RawResource {
+            uri: RESOURCE_URI.into(),
+            name: RESOURCE_NAME.into(),
+            description: Some(RESOURCE_DESCRIPTION.into()),
+            mime_type: Some("application/json".into()),
+            size: None,
+            title: None,
+            icons: None,
+            meta: None,
+        },
+        None,
+    )
+}
+
+/// Handle `resources/read` for the knowledge base.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` when `[knowledge]` is disabled, or
+/// `AppError::Db` if loading the notes fails.
+pub async fn read_resource(
+    request: &ReadResourceRequestParam,
+    state: &Arc<AppState>,
+) -> Result<ReadResourceResult> {
+    if !state.config.knowledge.enabled {
+        return Err(AppError::Config(
+            "the knowledge base is disabled; set [knowledge] enabled = true".into(),
+        ));
+    }
+
+    let notes = KnowledgeRepo::new(Arc::clone(&state.db))
+        .list_approved()
+        .await?;
+    info!(count = notes.len(), "reading knowledge resource");
+
+    let notes: Vec<serde_json::Value> = notes
+        .iter()
+        .map(|note| {
+            serde_json::json!({
+                "id": note.id,
+                "title": note.title,
+                "body": note.body,
+                "tags": note.tags,
+                "workspace_root": note.workspace_root,
+                "approved_by": note.decided_by,
+                "approved_at": note.decided_at.map(|at| at.to_rfc3339()),
+            })
+        })
+        .collect();
+    let body = serde_json::json!({ "notes": notes });
+
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContents::text(
+            body.to_string(),
+            request.uri.clone(),
+        )],
+    })
+}
@@ -1,3 +1,9 @@
 //! MCP resources exposed by the server.
 
+pub mod knowledge;
+pub mod server_config;
+pub mod session_context;
+pub mod session_logs;
+pub mod session_report;
+pub mod session_timeline;
 pub mod slack_channel;
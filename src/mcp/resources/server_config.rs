@@ -0,0 +1,101 @@
+//! `intercom://config` MCP resource handler.
+//!
+//! Serves the effective runtime configuration, redacted, and the command
+//! aliases of `[commands]` as JSON, so an agent can discover which
+//! aliases exist and how the server is set up instead of guessing. The
+//! same document is served over HTTP at
+//! [`CONFIG_PATH`](crate::mcp::config_api::CONFIG_PATH).
+
+use std::sync::{Arc, PoisonError};
+
+use rmcp::model::{
+    Annotated, RawResource, ReadResourceRequestParam, ReadResourceResult, Resource,
+    ResourceContents,
+};
+use tracing::info;
+
+use crate::orchestrator::command_aliases;
+use crate::state::AppState;
+
+/// URI of the configuration resource.
+pub const RESOURCE_URI: &str = "intercom://config";
+
+/// Human-readable name for this resource.
+pub const RESOURCE_NAME: &str = "Server Configuration";
+
+/// Description of this resource.
+pub const RESOURCE_DESCRIPTION: &str = "Effective server configuration with secrets redacted, \
+     and the command aliases operators can run.";
+
+/// Whether `uri` addresses the configuration resource.
+///
+/// # Examples
+///
+/// ```
+/// use agent_intercom::mcp::resources::server_config::is_config_uri;
+///
+/// assert!(is_config_uri("intercom://config"));
+/// assert!(!is_config_uri("intercom://knowledge"));
+/// ```
+#[must_use]
+pub fn is_config_uri(uri: &str) -> bool {
+    uri == RESOURCE_URI
+}
+
+/// Resource entry for `resources/list`.
+#[must_use]
+pub fn resource() -> Resource {
+    Annotated::new(
+        RawResource {
+            uri: RESOURCE_URI.into(),
+            name: RESOURCE_NAME.into(),
+            description: Some(RESOURCE_DESCRIPTION.into()),
+            mime_type: Some("application/json".into()),
+            size: None,
+            title: None,
+            icons: None,
+            meta: None,
+        },
+        None,
+    )
+}
+
+/// The configuration document: `config`, the redacted configuration with
+/// the current (hot-reloaded) workspace mappings and aliases, and
+/// `command_aliases`, sorted by alias.
+#[must_use]
+pub fn document(state: &AppState) -> serde_json::Value {
+    let mut config = (*state.config).clone();
+    state
+        .workspace_mappings
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone_into(&mut config.workspaces);
+
+    let aliases = command_aliases::list(state);
+    config.commands.extend(aliases.clone());
+    let aliases: Vec<serde_json::Value> = aliases
+        .into_iter()
+        .map(|(alias, command)| serde_json::json!({ "alias": alias, "command": command }))
+        .collect();
+
+    serde_json::json!({
+        "config": config.redacted_json(),
+        "command_aliases": aliases,
+    })
+}
+
+/// Handle `resources/read` for the configuration resource.
+#[must_use]
+pub fn read_resource(
+    request: &ReadResourceRequestParam,
+    state: &Arc<AppState>,
+) -> ReadResourceResult {
+    info!("reading config resource");
+    ReadResourceResult {
+        contents: vec![ResourceContents::text(
+            document(state).to_string(),
+            request.uri.clone(),
+        )],
+    }
+}
@@ -0,0 +1,313 @@
+//! `intercom://session/{id}/{decisions,plan,steering}` MCP resource handlers.
+//!
+//! Small Markdown views of a session meant to be attached as chat context
+//! by IDE clients (Copilot, Cursor): the operator's recent decisions, the
+//! session's current plan, and the steering backlog it has yet to pick up.
+//! The connection's own session lists them as concrete resources, so a
+//! client can attach them without knowing the session ID.
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use rmcp::model::{
+    Annotated, RawResource, RawResourceTemplate, ReadResourceRequestParam, ReadResourceResult,
+    Resource, ResourceContents, ResourceTemplate,
+};
+use tracing::info;
+
+use crate::integrations::email::approval_status_label;
+use crate::models::approval::{ApprovalRequest, ApprovalStatus};
+use crate::models::progress::ProgressStatus;
+use crate::models::prompt::ContinuationPrompt;
+use crate::models::session::Session;
+use crate::models::steering::{SteeringMessage, SteeringPriority, SteeringSource};
+use crate::orchestrator::session_report::{decision_label, find_session, one_line, timestamp};
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::prompt_repo::PromptRepo;
+use crate::persistence::steering_repo::SteeringRepo;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// How many decisions the decisions view lists.
+pub const RECENT_DECISIONS: usize = 20;
+
+/// One of the session context views.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextView {
+    /// The operator's most recent decisions on the session's requests.
+    Decisions,
+    /// The session's task and latest progress snapshot.
+    Plan,
+    /// Steering messages queued for the session but not yet delivered.
+    Steering,
+}
+
+impl ContextView {
+    /// Every view, in listing order.
+    pub const ALL: [Self; 3] = [Self::Decisions, Self::Plan, Self::Steering];
+
+    /// URI path segment after the session ID.
+    #[must_use]
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Self::Decisions => "decisions",
+            Self::Plan => "plan",
+            Self::Steering => "steering",
+        }
+    }
+
+    /// Human-readable resource name.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Decisions => "Recent Decisions",
+            Self::Plan => "Current Plan",
+            Self::Steering => "Steering Backlog",
+        }
+    }
+
+    /// Resource description.
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Decisions => {
+                "Markdown list of the operator's most recent approval and prompt \
+                 decisions in an agent session, newest first."
+            }
+            Self::Plan => {
+                "Markdown summary of an agent session's task and its latest \
+                 progress snapshot."
+            }
+            Self::Steering => {
+                "Markdown list of steering messages queued for an agent session \
+                 and not yet delivered."
+            }
+        }
+    }
+
+    fn from_suffix(suffix: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|view| view.suffix() == suffix)
+    }
+}
+
+/// Parse an `intercom://session/{id}/{view}` URI for one of the context
+/// views and return the session ID and view.
+///
+/// Returns `None` if the URI does not match the expected pattern.
+///
+/// # Examples
+///
+/// ```
+/// use agent_intercom::mcp::resources::session_context::{parse_context_uri, ContextView};
+///
+/// assert_eq!(
+///     parse_context_uri("intercom://session/3f2a9c1e/plan"),
+///     Some(("3f2a9c1e", ContextView::Plan))
+/// );
+/// assert_eq!(parse_context_uri("intercom://session/3f2a9c1e/report"), None);
+/// ```
+#[must_use]
+pub fn parse_context_uri(uri: &str) -> Option<(&str, ContextView)> {
+    let rest = uri.strip_prefix("intercom://session/")?;
+    let (session_id, suffix) = rest.split_once('/')?;
+    if session_id.is_empty() {
+        return None;
+    }
+    Some((session_id, ContextView::from_suffix(suffix)?))
+}
+
+/// Resource templates for the context views of any session.
+#[must_use]
+pub fn resource_templates() -> Vec<ResourceTemplate> {
+    ContextView::ALL
+        .into_iter()
+        .map(|view| {
+            Annotated::new(
+                RawResourceTemplate {
+                    uri_template: format!("intercom://session/{{id}}/{}", view.suffix()),
+                    name: view.name().into(),
+                    description: Some(view.description().into()),
+                    mime_type: Some("text/markdown".into()),
+                    title: None,
+                    icons: None,
+                },
+                None,
+            )
+        })
+        .collect()
+}
+
+/// The context views of `session_id`, listed for the connection bound to it.
+#[must_use]
+pub fn resources_for(session_id: &str) -> Vec<Resource> {
+    ContextView::ALL
+        .into_iter()
+        .map(|view| {
+            Annotated::new(
+                RawResource {
+                    uri: format!("intercom://session/{session_id}/{}", view.suffix()),
+                    name: view.name().into(),
+                    description: Some(view.description().into()),
+                    mime_type: Some("text/markdown".into()),
+                    size: None,
+                    title: None,
+                    icons: None,
+                    meta: None,
+                },
+                None,
+            )
+        })
+        .collect()
+}
+
+/// Handle `resources/read` for a session context view.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for a malformed URI, `AppError::NotFound`
+/// when the session does not exist, or `AppError::Db` if loading fails.
+pub async fn read_resource(
+    request: &ReadResourceRequestParam,
+    state: &Arc<AppState>,
+) -> Result<ReadResourceResult> {
+    let (session_id, view) = parse_context_uri(&request.uri).ok_or_else(|| {
+        AppError::Config(format!(
+            "invalid resource URI: expected intercom://session/{{id}}/decisions, /plan or \
+             /steering, got '{}'",
+            request.uri
+        ))
+    })?;
+
+    info!(
+        session_id,
+        view = view.suffix(),
+        "reading session context resource"
+    );
+    let session = find_session(&state.db, session_id).await?;
+    let markdown = match view {
+        ContextView::Decisions => {
+            let approvals = ApprovalRepo::new(Arc::clone(&state.db))
+                .list_for_session(&session.id)
+                .await?;
+            let prompts = PromptRepo::new(Arc::clone(&state.db))
+                .list_for_session(&session.id)
+                .await?;
+            decisions_markdown(&approvals, &prompts)
+        }
+        ContextView::Plan => plan_markdown(&session),
+        ContextView::Steering => {
+            let backlog = SteeringRepo::new(Arc::clone(&state.db))
+                .fetch_unconsumed(&session.id)
+                .await?;
+            steering_markdown(&backlog)
+        }
+    };
+
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContents::text(markdown, request.uri.clone())],
+    })
+}
+
+/// Render the operator's [`RECENT_DECISIONS`] most recent decisions,
+/// newest first. Pending requests are left out.
+#[must_use]
+pub fn decisions_markdown(approvals: &[ApprovalRequest], prompts: &[ContinuationPrompt]) -> String {
+    let mut decisions: Vec<(DateTime<Utc>, String)> = approvals
+        .iter()
+        .filter(|a| a.status != ApprovalStatus::Pending)
+        .map(|a| {
+            (
+                a.created_at,
+                format!(
+                    "**{}** approval `{}`: {} (`{}`)",
+                    approval_status_label(a.status),
+                    a.short_id,
+                    one_line(&a.title),
+                    a.file_path
+                ),
+            )
+        })
+        .collect();
+    decisions.extend(prompts.iter().filter_map(|p| {
+        let decision = p.decision?;
+        let mut text = format!(
+            "**{}** prompt: {}",
+            decision_label(decision),
+            one_line(&p.prompt_text)
+        );
+        if let Some(ref instruction) = p.instruction {
+            let _ = write!(text, " \u{2014} \"{}\"", one_line(instruction));
+        }
+        Some((p.created_at, text))
+    }));
+    decisions.sort_by_key(|d| std::cmp::Reverse(d.0));
+
+    let mut out = String::from("# Recent decisions\n\n");
+    if decisions.is_empty() {
+        out.push_str("No operator decisions yet.\n");
+    }
+    for (at, text) in decisions.iter().take(RECENT_DECISIONS) {
+        let _ = writeln!(out, "- `{}` {text}", timestamp(*at));
+    }
+    out
+}
+
+/// Render the session's task and its latest progress snapshot.
+#[must_use]
+pub fn plan_markdown(session: &Session) -> String {
+    let mut out = String::from("# Current plan\n\n");
+    if let Some(ref title) = session.title {
+        let _ = writeln!(out, "**{}**\n", one_line(title));
+    }
+    if let Some(ref prompt) = session.prompt {
+        out.push_str("## Task\n\n");
+        for line in prompt.lines() {
+            let _ = writeln!(out, "> {line}");
+        }
+        out.push('\n');
+    }
+    out.push_str("## Progress\n\n");
+    match session.progress_snapshot {
+        Some(ref items) if !items.is_empty() => {
+            for item in items {
+                let mark = match item.status {
+                    ProgressStatus::Done => "[x]",
+                    ProgressStatus::InProgress => "[~]",
+                    ProgressStatus::Pending => "[ ]",
+                };
+                let _ = writeln!(out, "- {mark} {}", item.label);
+            }
+        }
+        _ => out.push_str("No progress reported yet; send a `progress_snapshot` with `ping`.\n"),
+    }
+    out
+}
+
+/// Render steering messages not yet delivered to the session, oldest first.
+#[must_use]
+pub fn steering_markdown(backlog: &[SteeringMessage]) -> String {
+    let mut out = String::from("# Steering backlog\n\n");
+    if backlog.is_empty() {
+        out.push_str("No steering messages are waiting.\n");
+    }
+    for msg in backlog {
+        let origin = match msg.source {
+            SteeringSource::Slack => "operator via Slack",
+            SteeringSource::Ipc => "operator via local CLI",
+            SteeringSource::Subtask => "subtask report",
+        };
+        let urgent = if msg.priority == SteeringPriority::Interrupt {
+            " **interrupt**"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            out,
+            "- `{}` ({origin}){urgent} {}",
+            timestamp(msg.created_at),
+            one_line(&msg.message)
+        );
+    }
+    out
+}
@@ -0,0 +1,93 @@
+//! `intercom://session/{id}/logs` MCP resource handler.
+//!
+//! Serves the log lines a session streamed with `stream_log`, oldest first,
+//! as JSON. The whole ring buffer is returned.
+
+use std::sync::Arc;
+
+use rmcp::model::{
+    Annotated, RawResourceTemplate, ReadResourceRequestParam, ReadResourceResult, ResourceContents,
+    ResourceTemplate,
+};
+use tracing::info;
+
+use crate::orchestrator::session_logs::LOG_BUFFER_CAPACITY;
+use crate::orchestrator::session_report::find_session;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Human-readable name for this resource.
+pub const RESOURCE_NAME: &str = "Session Logs";
+
+/// Description of this resource.
+pub const RESOURCE_DESCRIPTION: &str = "Structured log lines an agent session streamed with \
+     stream_log, oldest first. Only the newest lines are kept.";
+
+/// Parse an `intercom://session/{id}/logs` URI and return the session ID.
+///
+/// Returns `None` if the URI does not match the expected pattern.
+///
+/// # Examples
+///
+/// ```
+/// use agent_intercom::mcp::resources::session_logs::parse_logs_uri;
+///
+/// assert_eq!(parse_logs_uri("intercom://session/3f2a9c1e/logs"), Some("3f2a9c1e"));
+/// assert_eq!(parse_logs_uri("intercom://session/3f2a9c1e/timeline"), None);
+/// ```
+#[must_use]
+pub fn parse_logs_uri(uri: &str) -> Option<&str> {
+    let rest = uri.strip_prefix("intercom://session/")?;
+    let (session_id, suffix) = rest.split_once('/')?;
+    if suffix != "logs" || session_id.is_empty() {
+        return None;
+    }
+    Some(session_id)
+}
+
+/// Resource template for session logs.
+#[must_use]
+pub fn resource_template() -> ResourceTemplate {
+    Annotated::new(
+        RawResourceTemplate {
+            uri_template: "intercom://session/{id}/logs".into(),
+            name: RESOURCE_NAME.into(),
+            description: Some(RESOURCE_DESCRIPTION.into()),
+            mime_type: Some("application/json".into()),
+            title: None,
+            icons: None,
+        },
+        None,
+    )
+}
+
+/// Handle `resources/read` for session logs.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for a malformed URI, `AppError::NotFound`
+/// when the session does not exist, or `AppError::Db` if the lookup fails.
+pub async fn read_resource(
+    request: &ReadResourceRequestParam,
+    state: &Arc<AppState>,
+) -> Result<ReadResourceResult> {
+    let id = parse_logs_uri(&request.uri).ok_or_else(|| {
+        AppError::Config(format!(
+            "invalid resource URI: expected intercom://session/{{id}}/logs, got '{}'",
+            request.uri
+        ))
+    })?;
+
+    let session = find_session(&state.db, id).await?;
+    let lines = state.session_logs.tail(&session.id, LOG_BUFFER_CAPACITY);
+    info!(session_id = %session.id, count = lines.len(), "reading session logs resource");
+    let json = serde_json::to_string_pretty(&serde_json::json!({
+        "session_id": session.id,
+        "lines": lines,
+    }))
+    .map_err(|err| AppError::Mcp(format!("failed to serialize session logs: {err}")))?;
+
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContents::text(json, request.uri.clone())],
+    })
+}
@@ -0,0 +1,90 @@
+//! `intercom://session/{id}/report` MCP resource handler.
+//!
+//! Exposes the Markdown [`SessionReport`] of a session so clients can
+//! attach it to pull requests or compliance reviews without shelling out
+//! to `agent-intercom-ctl report`.
+
+use std::sync::Arc;
+
+use rmcp::model::{
+    Annotated, RawResourceTemplate, ReadResourceRequestParam, ReadResourceResult, ResourceContents,
+    ResourceTemplate,
+};
+use tracing::info;
+
+use crate::orchestrator::session_report::SessionReport;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Human-readable name for this resource.
+pub const RESOURCE_NAME: &str = "Session Report";
+
+/// Description of this resource.
+pub const RESOURCE_DESCRIPTION: &str = "Markdown report of an agent session: overview, \
+     transcript, operator decisions, and the diffs that were applied.";
+
+/// Parse an `intercom://session/{id}/report` URI and return the session ID.
+///
+/// Returns `None` if the URI does not match the expected pattern.
+///
+/// # Examples
+///
+/// ```
+/// use agent_intercom::mcp::resources::session_report::parse_report_uri;
+///
+/// assert_eq!(parse_report_uri("intercom://session/3f2a9c1e/report"), Some("3f2a9c1e"));
+/// assert_eq!(parse_report_uri("slack://channel/C012345/recent"), None);
+/// ```
+#[must_use]
+pub fn parse_report_uri(uri: &str) -> Option<&str> {
+    let rest = uri.strip_prefix("intercom://session/")?;
+    let (session_id, suffix) = rest.split_once('/')?;
+    if suffix != "report" || session_id.is_empty() {
+        return None;
+    }
+    Some(session_id)
+}
+
+/// Resource template for session reports.
+#[must_use]
+pub fn resource_template() -> ResourceTemplate {
+    Annotated::new(
+        RawResourceTemplate {
+            uri_template: "intercom://session/{id}/report".into(),
+            name: RESOURCE_NAME.into(),
+            description: Some(RESOURCE_DESCRIPTION.into()),
+            mime_type: Some("text/markdown".into()),
+            title: None,
+            icons: None,
+        },
+        None,
+    )
+}
+
+/// Handle `resources/read` for a session report.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for a malformed URI, `AppError::NotFound`
+/// when the session does not exist, or `AppError::Db` if loading fails.
+pub async fn read_resource(
+    request: &ReadResourceRequestParam,
+    state: &Arc<AppState>,
+) -> Result<ReadResourceResult> {
+    let session_id = parse_report_uri(&request.uri).ok_or_else(|| {
+        AppError::Config(format!(
+            "invalid resource URI: expected intercom://session/{{id}}/report, got '{}'",
+            request.uri
+        ))
+    })?;
+
+    info!(session_id, "reading session report resource");
+    let report = SessionReport::load(&state.db, session_id).await?;
+
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContents::text(
+            report.to_markdown(),
+            request.uri.clone(),
+        )],
+    })
+}
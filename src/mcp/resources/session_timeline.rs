@@ -0,0 +1,91 @@
+//! `intercom://session/{id}/timeline` MCP resource handler.
+//!
+//! Serves the JSON [`SessionTimeline`] of a session, letting an agent review
+//! what happened earlier in a long session: its tool calls, the operator's
+//! decisions, steering it received and any stalls.
+
+use std::sync::Arc;
+
+use rmcp::model::{
+    Annotated, RawResourceTemplate, ReadResourceRequestParam, ReadResourceResult, ResourceContents,
+    ResourceTemplate,
+};
+use tracing::info;
+
+use crate::audit;
+use crate::orchestrator::session_timeline::SessionTimeline;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Human-readable name for this resource.
+pub const RESOURCE_NAME: &str = "Session Timeline";
+
+/// Description of this resource.
+pub const RESOURCE_DESCRIPTION: &str = "Chronological JSON timeline of an agent session: \
+     tool calls, approval requests and decisions, forwarded prompts, steering, and stalls.";
+
+/// Parse an `intercom://session/{id}/timeline` URI and return the session ID.
+///
+/// Returns `None` if the URI does not match the expected pattern.
+///
+/// # Examples
+///
+/// ```
+/// use agent_intercom::mcp::resources::session_timeline::parse_timeline_uri;
+///
+/// assert_eq!(parse_timeline_uri("intercom://session/3f2a9c1e/timeline"), Some("3f2a9c1e"));
+/// assert_eq!(parse_timeline_uri("intercom://session/3f2a9c1e/report"), None);
+/// ```
+#[must_use]
+pub fn parse_timeline_uri(uri: &str) -> Option<&str> {
+    let rest = uri.strip_prefix("intercom://session/")?;
+    let (session_id, suffix) = rest.split_once('/')?;
+    if suffix != "timeline" || session_id.is_empty() {
+        return None;
+    }
+    Some(session_id)
+}
+
+/// Resource template for session timelines.
+#[must_use]
+pub fn resource_template() -> ResourceTemplate {
+    Annotated::new(
+        RawResourceTemplate {
+            uri_template: "intercom://session/{id}/timeline".into(),
+            name: RESOURCE_NAME.into(),
+            description: Some(RESOURCE_DESCRIPTION.into()),
+            mime_type: Some("application/json".into()),
+            title: None,
+            icons: None,
+        },
+        None,
+    )
+}
+
+/// Handle `resources/read` for a session timeline.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for a malformed URI, `AppError::NotFound`
+/// when the session does not exist, or `AppError::Db` if loading fails.
+pub async fn read_resource(
+    request: &ReadResourceRequestParam,
+    state: &Arc<AppState>,
+) -> Result<ReadResourceResult> {
+    let session_id = parse_timeline_uri(&request.uri).ok_or_else(|| {
+        AppError::Config(format!(
+            "invalid resource URI: expected intercom://session/{{id}}/timeline, got '{}'",
+            request.uri
+        ))
+    })?;
+
+    info!(session_id, "reading session timeline resource");
+    let log_dir = audit::log_dir(state.config.default_workspace_root());
+    let timeline = SessionTimeline::load(&state.db, &log_dir, session_id).await?;
+    let json = serde_json::to_string_pretty(&timeline)
+        .map_err(|err| AppError::Mcp(format!("failed to serialize timeline: {err}")))?;
+
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContents::text(json, request.uri.clone())],
+    })
+}
@@ -10,8 +10,10 @@ use rmcp::model::{
     ReadResourceRequestParam, ReadResourceResult, ResourceContents,
 };
 use serde_json::json;
+use slack_morphism::prelude::{SlackChannelId, SlackHistoryMessage, SlackTs};
 use tracing::{info, warn};
 
+use crate::slack::history_cache;
 use crate::state::AppState;
 use crate::{AppError, Result};
 
@@ -21,15 +23,22 @@ pub const DEFAULT_LIMIT: u16 = 20;
 /// Minimum allowed limit value.
 const MIN_LIMIT: u16 = 1;
 
-/// Maximum allowed limit value.
-const MAX_LIMIT: u16 = 100;
+/// Maximum allowed limit value: as many messages as the history cache keeps.
+const MAX_LIMIT: u16 = history_cache::CAPACITY;
+
+/// Most threads whose replies one read inlines.
+pub const MAX_EXPANDED_THREADS: usize = 10;
+
+/// Most replies inlined per thread.
+pub const THREAD_REPLY_LIMIT: u16 = 50;
 
 /// Human-readable name for this resource.
 pub const RESOURCE_NAME: &str = "Slack Channel History";
 
 /// Description of this resource.
 pub const RESOURCE_DESCRIPTION: &str = "Recent chat history from the configured Slack channel. \
-     Allows the agent to read operator instructions posted directly in the channel.";
+     Allows the agent to read operator instructions posted directly in the channel. \
+     Add ?cursor=<next_cursor> to page back and ?threads=true to include thread replies.";
 
 /// Parse a `slack://channel/{id}/recent` URI and return the channel ID.
 ///
@@ -41,11 +50,16 @@ pub const RESOURCE_DESCRIPTION: &str = "Recent chat history from the configured
 /// use agent_intercom::mcp::resources::slack_channel::parse_channel_uri;
 ///
 /// assert_eq!(parse_channel_uri("slack://channel/C012345/recent"), Some("C012345"));
+/// assert_eq!(
+///     parse_channel_uri("slack://channel/C012345/recent?limit=50&threads=true"),
+///     Some("C012345")
+/// );
 /// assert_eq!(parse_channel_uri("http://example.com"), None);
 /// ```
 #[must_use]
 pub fn parse_channel_uri(uri: &str) -> Option<&str> {
-    let rest = uri.strip_prefix("slack://channel/")?;
+    let path = uri.split_once('?').map_or(uri, |(path, _)| path);
+    let rest = path.strip_prefix("slack://channel/")?;
     let (channel_id, suffix) = rest.split_once('/')?;
     if suffix != "recent" || channel_id.is_empty() {
         return None;
@@ -100,17 +114,84 @@ pub fn list_resources(channel_id: &str) -> ListResourcesResult {
     }
 }
 
+/// Query parameters of a channel history read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryQuery {
+    /// Messages per page, clamped to `[1, 100]`.
+    pub limit: u16,
+    /// Read the page of messages older than this timestamp: the
+    /// `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+    /// Inline the replies of threaded messages.
+    pub threads: bool,
+}
+
+/// Parse the `limit`, `cursor` and `threads` query parameters of a
+/// channel resource URI. An unparseable `limit` falls back to
+/// [`DEFAULT_LIMIT`]; unknown parameters are ignored.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` when `cursor` is not a Slack message
+/// timestamp or `threads` is not `true` or `false`.
+pub fn parse_query(uri: &str) -> Result<HistoryQuery> {
+    let mut query = HistoryQuery {
+        limit: DEFAULT_LIMIT,
+        cursor: None,
+        threads: false,
+    };
+    let Some((_, params)) = uri.split_once('?') else {
+        return Ok(query);
+    };
+    for (key, value) in params.split('&').filter_map(|pair| pair.split_once('=')) {
+        match key {
+            "limit" => query.limit = clamp_limit(value.parse().ok()),
+            "cursor" => {
+                if !is_slack_ts(value) {
+                    return Err(AppError::Config(format!(
+                        "invalid cursor '{value}': pass the next_cursor of a previous page"
+                    )));
+                }
+                query.cursor = Some(value.to_owned());
+            }
+            "threads" => {
+                query.threads = value.parse().map_err(|_| {
+                    AppError::Config(format!("invalid threads '{value}': expected true or false"))
+                })?;
+            }
+            _ => {}
+        }
+    }
+    Ok(query)
+}
+
+/// Whether `value` looks like a Slack message timestamp (`1712345678.000100`).
+fn is_slack_ts(value: &str) -> bool {
+    value.split_once('.').is_some_and(|(secs, micros)| {
+        !secs.is_empty()
+            && !micros.is_empty()
+            && secs
+                .bytes()
+                .chain(micros.bytes())
+                .all(|b| b.is_ascii_digit())
+    })
+}
+
 /// Handle `resources/read` for the Slack channel history resource.
 ///
-/// Fetches recent messages from the configured Slack channel using the
+/// Fetches messages from the configured Slack channel using the
 /// `conversations.history` API and returns them in the contract-defined
-/// `{messages, has_more}` JSON format.
+/// `{messages, has_more, next_cursor}` JSON format. The first page comes
+/// through the channel's history cache; later pages (`cursor`) and thread
+/// replies (`threads=true`, up to [`MAX_EXPANDED_THREADS`] threads of
+/// [`THREAD_REPLY_LIMIT`] replies each) are fetched directly.
 ///
 /// # Errors
 ///
 /// Returns `AppError::Config` if the requested channel ID does not match
-/// the configured channel. Returns `AppError::Slack` if the Slack service
-/// is unavailable or the API call fails.
+/// the configured channel or a query parameter is invalid. Returns
+/// `AppError::Slack` if the Slack service is unavailable or the history
+/// call fails.
 pub async fn read_resource(
     request: &ReadResourceRequestParam,
     state: &Arc<AppState>,
@@ -134,53 +215,68 @@ pub async fn read_resource(
         .as_ref()
         .ok_or_else(|| AppError::Slack("slack service not available (local-only mode)".into()))?;
 
-    // Parse optional limit from the URI query parameters and clamp to
-    // the valid [1, 100] range.  Falls back to DEFAULT_LIMIT.
-    let user_limit = request
-        .uri
-        .split_once('?')
-        .and_then(|(_, query)| {
-            query
-                .split('&')
-                .find_map(|pair| pair.strip_prefix("limit="))
-        })
-        .and_then(|v| v.parse::<u16>().ok());
-    let limit = clamp_limit(user_limit);
-    let slack_channel = slack_morphism::prelude::SlackChannelId(channel_id.to_owned());
+    let query = parse_query(&request.uri)?;
+    let slack_channel = SlackChannelId(channel_id.to_owned());
 
-    info!(channel_id, limit, "reading slack channel history resource");
+    info!(
+        channel_id,
+        limit = query.limit,
+        cursor = query.cursor.as_deref(),
+        threads = query.threads,
+        "reading slack channel history resource"
+    );
 
-    let (messages, has_more) = slack.fetch_history_with_more(slack_channel, limit).await?;
+    let (messages, has_more) = match query.cursor {
+        Some(ref cursor) => {
+            slack
+                .fetch_history_before(slack_channel.clone(), query.limit, SlackTs(cursor.clone()))
+                .await?
+        }
+        None => {
+            slack
+                .cached_history(slack_channel.clone(), query.limit)
+                .await?
+        }
+    };
 
     // Convert to contract schema.
-    let mut output_messages = Vec::with_capacity(messages.len());
-    for msg in &messages {
-        let ts = msg.origin.ts.0.clone();
-        let user = msg
-            .sender
-            .user
-            .as_ref()
-            .map_or_else(|| "unknown".to_owned(), |u| u.0.clone());
-        let text = msg.content.text.clone().unwrap_or_default();
-        let thread_ts = msg.origin.thread_ts.as_ref().map(|t| t.0.clone());
-
-        let mut entry = json!({
-            "ts": ts,
-            "user": user,
-            "text": text,
-        });
-
-        if let Some(thread) = thread_ts {
-            entry["thread_ts"] = json!(thread);
-        }
+    let mut output_messages: Vec<serde_json::Value> = messages.iter().map(message_json).collect();
 
-        output_messages.push(entry);
+    if query.threads {
+        let threaded = messages
+            .iter()
+            .zip(output_messages.iter_mut())
+            .filter(|(msg, _)| msg.parent.reply_count.unwrap_or(0) > 0)
+            .take(MAX_EXPANDED_THREADS);
+        for (msg, entry) in threaded {
+            match slack
+                .fetch_thread_replies(
+                    slack_channel.clone(),
+                    msg.origin.ts.clone(),
+                    THREAD_REPLY_LIMIT,
+                )
+                .await
+            {
+                Ok((replies, more)) => {
+                    entry["replies"] = replies.iter().map(message_json).collect();
+                    entry["replies_has_more"] = json!(more);
+                }
+                Err(err) => {
+                    warn!(%err, channel_id, ts = %msg.origin.ts.0, "failed to expand thread");
+                }
+            }
+        }
     }
 
-    let body = json!({
+    let mut body = json!({
         "messages": output_messages,
         "has_more": has_more,
     });
+    if has_more {
+        if let Some(oldest) = messages.last() {
+            body["next_cursor"] = json!(oldest.origin.ts.0);
+        }
+    }
 
     let uri = request.uri.clone();
     Ok(ReadResourceResult {
@@ -188,6 +284,27 @@ pub async fn read_resource(
     })
 }
 
+/// One message in the contract schema.
+fn message_json(msg: &SlackHistoryMessage) -> serde_json::Value {
+    let user = msg
+        .sender
+        .user
+        .as_ref()
+        .map_or_else(|| "unknown".to_owned(), |u| u.0.clone());
+    let mut entry = json!({
+        "ts": msg.origin.ts.0,
+        "user": user,
+        "text": msg.content.text.clone().unwrap_or_default(),
+    });
+    if let Some(ref thread) = msg.origin.thread_ts {
+        entry["thread_ts"] = json!(thread.0);
+    }
+    if let Some(count) = msg.parent.reply_count.filter(|count| *count > 0) {
+        entry["reply_count"] = json!(count);
+    }
+    entry
+}
+
 /// Clamp a user-provided limit to the valid `[1, 100]` range.
 #[must_use]
 pub fn clamp_limit(limit: Option<u16>) -> u16 {
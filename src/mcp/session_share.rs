@@ -0,0 +1,217 @@
+//! Read-only share links for watching a session from a browser.
+//!
+//! `/intercom share <session_id>` returns a signed URL,
+//! `{base_url}/share/{session_id}?exp=…&sig=…`, that stakeholders can open
+//! without a Slack seat or operator rights. The page shows the session's
+//! status and progress, the requests waiting for approval and the recent
+//! decisions. It has no forms and the route accepts only `GET`, so a
+//! holder can watch but never approve.
+//!
+//! Links are signed like approval links, with their own scope so a share
+//! link cannot be replayed against `/approvals/{id}`.
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::get;
+use chrono::Utc;
+use serde::Deserialize;
+use tracing::warn;
+
+use super::approval_link::{document, page, sign_scoped, verify_scoped};
+use crate::config::ApprovalLinksConfig;
+use crate::integrations::email::{approval_status_label, escape_html};
+use crate::models::approval::{ApprovalRequest, ApprovalStatus};
+use crate::models::progress::ProgressStatus;
+use crate::models::session::Session;
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Route path of the share page.
+pub const SHARE_PATH: &str = "/share/{session_id}";
+
+/// Signature domain of share links.
+const SHARE_SCOPE: &str = "session-share";
+
+/// Decided requests listed on the page, newest first.
+const RECENT_DECISIONS: usize = 20;
+
+/// `exp` and `sig` query parameters of a share link.
+#[derive(Debug, Default, Deserialize)]
+struct ShareQuery {
+    #[serde(default)]
+    exp: i64,
+    #[serde(default)]
+    sig: String,
+}
+
+/// Hex signature of the share link for `session_id` expiring at `expires`.
+#[must_use]
+pub fn sign(secret: &str, session_id: &str, expires: i64) -> String {
+    sign_scoped(secret, SHARE_SCOPE, session_id, expires)
+}
+
+/// Read-only link to `session_id`, valid for `ttl_seconds` from `now`.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` when approval links are disabled or have no
+/// signing secret, since share links need their `base_url` and secret.
+pub fn share_url(
+    config: &ApprovalLinksConfig,
+    session_id: &str,
+    ttl_seconds: i64,
+    now: i64,
+) -> Result<String> {
+    let base = config
+        .base_url
+        .as_deref()
+        .filter(|_| config.enabled)
+        .ok_or_else(|| {
+            AppError::Config("share links need `[approval_links] enabled = true`".into())
+        })?
+        .trim_end_matches('/');
+    if config.secret.is_empty() {
+        return Err(AppError::Config(
+            "share links need an approval link signing secret".into(),
+        ));
+    }
+    let expires = now.saturating_add(ttl_seconds);
+    let sig = sign(&config.secret, session_id, expires);
+    Ok(format!("{base}/share/{session_id}?exp={expires}&sig={sig}"))
+}
+
+/// Router serving the read-only share page.
+pub fn router(state: Arc<AppState>) -> axum::Router {
+    axum::Router::new()
+        .route(SHARE_PATH, get(show))
+        .with_state(state)
+}
+
+/// Handler for `GET /share/{session_id}`.
+async fn show(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+    Query(query): Query<ShareQuery>,
+) -> Response {
+    if let Err(err) = verify_scoped(
+        &state.config.approval_links.secret,
+        SHARE_SCOPE,
+        &session_id,
+        query.exp,
+        &query.sig,
+        Utc::now().timestamp(),
+    ) {
+        warn!(session_id, %err, "share link refused");
+        return page(StatusCode::UNAUTHORIZED, "Link not valid", &err.to_string());
+    }
+
+    let error = |err: AppError| page(StatusCode::INTERNAL_SERVER_ERROR, "Error", &err.to_string());
+    let session = match SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(&session_id)
+        .await
+    {
+        Ok(Some(session)) if session.deleted_at.is_none() => session,
+        Ok(_) => {
+            return page(
+                StatusCode::NOT_FOUND,
+                "Not found",
+                "This session no longer exists.",
+            )
+        }
+        Err(err) => return error(err),
+    };
+    let approvals = match ApprovalRepo::new(Arc::clone(&state.db))
+        .list_for_session(&session.id)
+        .await
+    {
+        Ok(approvals) => approvals,
+        Err(err) => return error(err),
+    };
+    (StatusCode::OK, Html(render(&session, &approvals))).into_response()
+}
+
+/// The share page for `session` and its approval requests, oldest first.
+fn render(session: &Session, approvals: &[ApprovalRequest]) -> String {
+    let title = session
+        .title
+        .clone()
+        .or_else(|| session.prompt.clone())
+        .unwrap_or_else(|| session.id.clone());
+    let mut body = format!("<h1>{}</h1><dl>", escape_html(&title));
+    let _ = write!(
+        body,
+        "<dt>Status</dt><dd>{}</dd><dt>Connection</dt><dd>{} {}</dd><dt>Started</dt><dd>{}</dd>\
+         <dt>Last activity</dt><dd>{}</dd>",
+        session.status.as_str(),
+        session.connectivity_status.indicator(),
+        session.connectivity_status.as_str(),
+        session.created_at.format("%Y-%m-%d %H:%M UTC"),
+        session
+            .last_activity_at
+            .unwrap_or(session.updated_at)
+            .format("%Y-%m-%d %H:%M UTC")
+    );
+    if let Some(ref tool) = session.last_tool {
+        let _ = write!(body, "<dt>Last tool</dt><dd>{}</dd>", escape_html(tool));
+    }
+    body.push_str("</dl>");
+
+    if let Some(ref items) = session.progress_snapshot {
+        body.push_str("<h2>Progress</h2><ul>");
+        for item in items {
+            let mark = match item.status {
+                ProgressStatus::Done => "\u{2705}",
+                ProgressStatus::InProgress => "\u{1f504}",
+                ProgressStatus::Pending => "\u{2b1c}",
+            };
+            let _ = write!(body, "<li>{mark} {}</li>", escape_html(&item.label));
+        }
+        body.push_str("</ul>");
+    }
+
+    let pending: Vec<_> = approvals
+        .iter()
+        .filter(|a| a.status == ApprovalStatus::Pending)
+        .collect();
+    if !pending.is_empty() {
+        body.push_str("<h2>Waiting for approval</h2><ul>");
+        for approval in pending {
+            push_request(&mut body, approval, "pending");
+        }
+        body.push_str("</ul>");
+    }
+
+    let decided: Vec<_> = approvals
+        .iter()
+        .rev()
+        .filter(|a| a.status != ApprovalStatus::Pending)
+        .take(RECENT_DECISIONS)
+        .collect();
+    if !decided.is_empty() {
+        body.push_str("<h2>Recent decisions</h2><ul>");
+        for approval in decided {
+            push_request(&mut body, approval, approval_status_label(approval.status));
+        }
+        body.push_str("</ul>");
+    }
+
+    body.push_str("<p><em>Read-only view. Reload for the latest state.</em></p>");
+    document(&title, &body)
+}
+
+fn push_request(body: &mut String, approval: &ApprovalRequest, status: &str) {
+    let _ = write!(
+        body,
+        "<li><strong>{}</strong> \u{b7} {status}<br><code>{}</code> \u{b7} {} risk \u{b7} {}</li>",
+        escape_html(&approval.title),
+        escape_html(&approval.file_path),
+        approval.risk_level.as_str(),
+        approval.created_at.format("%H:%M UTC")
+    );
+}
@@ -18,6 +18,10 @@
 //! The rmcp `StreamableHttpService` requires both. The
 //! [`ensure_accept_header`] middleware patches the header before it reaches
 //! rmcp, avoiding a 406 rejection.
+//!
+//! Request bodies are capped at `[limits] max_body_bytes`. Only requests
+//! that may be an `initialize` (a POST without `Mcp-Session-Id`) are
+//! buffered for sanitization; everything else is streamed through to rmcp.
 
 use std::net::SocketAddr;
 use std::sync::{Arc, Mutex};
@@ -27,6 +31,7 @@ use axum::http::StatusCode;
 use axum::middleware::{self, Next};
 use axum::response::{IntoResponse, Response};
 use axum::routing::get;
+use futures_util::StreamExt;
 use rmcp::transport::streamable_http_server::{
     session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService,
 };
@@ -35,17 +40,41 @@ use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
 use super::handler::IntercomServer;
+use super::{
+    approval_link, approval_webhook, config_api, device_pairing, diff_staging, health,
+    session_share,
+};
 use crate::mode::ServerMode;
 use crate::models::session::SessionStatus;
+use crate::orchestrator::workspace_discovery;
 use crate::persistence::session_repo::SessionRepo;
 use crate::state::AppState;
 use crate::{AppError, Result};
 
-/// Handler for `GET /health` — returns 200 OK with a plain-text body.
-///
-/// Useful for probing liveness without initiating an MCP session.
-async fn health() -> &'static str {
-    "ok"
+/// Merge the enabled out-of-Slack routes: the decision webhook, signed
+/// approval links, the paired-device dashboard and read-only share links.
+fn with_approval_routes(mut router: axum::Router, state: &Arc<AppState>) -> axum::Router {
+    if state.config.webhooks.enabled && state.config.webhooks.secret.is_empty() {
+        warn!("[webhooks] enabled without a signing secret — webhook not mounted");
+    } else if state.config.webhooks.enabled {
+        router = router.merge(approval_webhook::router(Arc::clone(state)));
+        info!(
+            path = approval_webhook::DECISION_PATH,
+            "approval webhook enabled"
+        );
+    }
+    if state.config.approval_links.enabled && state.config.approval_links.secret.is_empty() {
+        warn!("[approval_links] enabled without a signing secret — links not mounted");
+    } else if state.config.approval_links.enabled {
+        router = router.merge(approval_link::router(Arc::clone(state)));
+        router = router.merge(device_pairing::router(Arc::clone(state)));
+        router = router.merge(session_share::router(Arc::clone(state)));
+        info!(
+            path = approval_link::LINK_PATH,
+            "approval links, dashboard and share links enabled"
+        );
+    }
+    router
 }
 
 /// ACP mode session authentication guard (HITL-003 / FR-033).
@@ -118,6 +147,14 @@ async fn acp_session_guard(
 /// routing is performed exclusively via the `[[workspace]]` mapping table.
 type PendingParams = Arc<Mutex<(Option<String>, Option<String>)>>;
 
+/// State shared with [`ensure_accept_header`].
+#[derive(Clone)]
+struct AcceptState {
+    pending: PendingParams,
+    /// `[limits] max_body_bytes`.
+    max_body_bytes: usize,
+}
+
 /// Update the shared pending-params slot from URL query parameters.
 ///
 /// Called by `ensure_accept_header` before the slot is read by the rmcp
@@ -155,14 +192,19 @@ fn update_pending_from_uri(uri: &axum::http::Uri, pending: &PendingParams) {
 ///    fields and downgrades the protocol version to `"2025-03-26"`
 ///    (the latest version rmcp 0.13 supports), allowing rmcp to
 ///    accept the request.
+///
+/// Bodies over `max_body_bytes` are refused with `413`. Only a POST without
+/// `Mcp-Session-Id` can be an `initialize`, so only those are buffered;
+/// other bodies are streamed to rmcp with the same cap applied per chunk.
 async fn ensure_accept_header(
-    axum::extract::State(pending): axum::extract::State<PendingParams>,
+    axum::extract::State(accept_state): axum::extract::State<AcceptState>,
     request: Request,
     next: Next,
 ) -> Response {
+    let max_body_bytes = accept_state.max_body_bytes;
     // Store session_id and workspace_id from the URL so the factory can pick
     // them up when rmcp creates a new session.
-    update_pending_from_uri(request.uri(), &pending);
+    update_pending_from_uri(request.uri(), &accept_state.pending);
     let method = request.method().clone();
     let uri = request.uri().clone();
     let accept_before = request
@@ -184,59 +226,56 @@ async fn ensure_accept_header(
         .unwrap_or("<none>")
         .to_owned();
 
-    // Read the request body so we can inspect and potentially rewrite it.
+    let declared_length = request
+        .headers()
+        .get(axum::http::header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if declared_length.is_some_and(|len| len > max_body_bytes) {
+        debug!(%method, %uri, ?declared_length, "request body over limit");
+        return body_too_large(max_body_bytes);
+    }
+
     let (parts, body) = request.into_parts();
-    let body_bytes = match axum::body::to_bytes(body, 64 * 1024).await {
-        Ok(b) => b,
-        Err(err) => {
-            debug!(%method, %uri, %err, "failed to read request body");
-            return (StatusCode::BAD_REQUEST, "failed to read body").into_response();
-        }
-    };
+    let may_be_initialize = method == axum::http::Method::POST && session_id == "<none>";
+    let body = if may_be_initialize {
+        // Read the request body so we can inspect and potentially rewrite it.
+        let body_bytes = match axum::body::to_bytes(body, max_body_bytes).await {
+            Ok(b) => b,
+            Err(err) => {
+                debug!(%method, %uri, %err, "failed to read request body");
+                return (StatusCode::BAD_REQUEST, "failed to read body").into_response();
+            }
+        };
 
-    let body_preview = String::from_utf8_lossy(&body_bytes[..body_bytes.len().min(512)]);
-    debug!(
-        %method, %uri,
-        accept = %accept_before,
-        content_type = %content_type,
-        session_id = %session_id,
-        body = %body_preview,
-        "mcp request received (pre-middleware)"
-    );
+        let body_preview = String::from_utf8_lossy(&body_bytes[..body_bytes.len().min(512)]);
+        debug!(
+            %method, %uri,
+            accept = %accept_before,
+            content_type = %content_type,
+            session_id = %session_id,
+            body = %body_preview,
+            "mcp request received (pre-middleware)"
+        );
 
-    // Sanitize Initialize requests for rmcp 0.13 compatibility.
-    let final_body = sanitize_initialize_body(&body_bytes);
+        // Sanitize Initialize requests for rmcp 0.13 compatibility.
+        axum::body::Body::from(sanitize_initialize_body(&body_bytes))
+    } else {
+        debug!(
+            %method, %uri,
+            accept = %accept_before,
+            content_type = %content_type,
+            session_id = %session_id,
+            ?declared_length,
+            "mcp request received (pre-middleware, streamed)"
+        );
+        limit_body(body, max_body_bytes)
+    };
 
     // Reconstruct the request with the (possibly rewritten) body.
-    let mut request = Request::from_parts(parts, axum::body::Body::from(final_body));
+    let mut request = Request::from_parts(parts, body);
 
-    // Fix up the Accept header.
-    if let Some(accept) = request.headers().get(axum::http::header::ACCEPT) {
-        if let Ok(val) = accept.to_str() {
-            let has_json = val.contains("application/json");
-            let has_sse = val.contains("text/event-stream");
-            if has_json && !has_sse {
-                let new_val = format!("{val}, text/event-stream");
-                if let Ok(hv) = new_val.parse() {
-                    request.headers_mut().insert(axum::http::header::ACCEPT, hv);
-                }
-            } else if has_sse && !has_json {
-                let new_val = format!("application/json, {val}");
-                if let Ok(hv) = new_val.parse() {
-                    request.headers_mut().insert(axum::http::header::ACCEPT, hv);
-                }
-            } else if !has_json && !has_sse {
-                if let Ok(hv) = "application/json, text/event-stream".parse() {
-                    request.headers_mut().insert(axum::http::header::ACCEPT, hv);
-                }
-            }
-        }
-    } else {
-        // No Accept header at all — add the required one.
-        if let Ok(hv) = "application/json, text/event-stream".parse() {
-            request.headers_mut().insert(axum::http::header::ACCEPT, hv);
-        }
-    }
+    fix_accept_header(request.headers_mut());
 
     let response = next.run(request).await;
 
@@ -269,6 +308,66 @@ async fn ensure_accept_header(
     final_response
 }
 
+/// Ensure `Accept` lists both `application/json` and `text/event-stream`,
+/// which rmcp requires.
+fn fix_accept_header(headers: &mut axum::http::HeaderMap) {
+    if let Some(accept) = headers.get(axum::http::header::ACCEPT) {
+        if let Ok(val) = accept.to_str() {
+            let has_json = val.contains("application/json");
+            let has_sse = val.contains("text/event-stream");
+            if has_json && !has_sse {
+                let new_val = format!("{val}, text/event-stream");
+                if let Ok(hv) = new_val.parse() {
+                    headers.insert(axum::http::header::ACCEPT, hv);
+                }
+            } else if has_sse && !has_json {
+                let new_val = format!("application/json, {val}");
+                if let Ok(hv) = new_val.parse() {
+                    headers.insert(axum::http::header::ACCEPT, hv);
+                }
+            } else if !has_json && !has_sse {
+                if let Ok(hv) = "application/json, text/event-stream".parse() {
+                    headers.insert(axum::http::header::ACCEPT, hv);
+                }
+            }
+        }
+    } else {
+        // No Accept header at all — add the required one.
+        if let Ok(hv) = "application/json, text/event-stream".parse() {
+            headers.insert(axum::http::header::ACCEPT, hv);
+        }
+    }
+}
+
+/// Response for a body over `[limits] max_body_bytes`.
+fn body_too_large(max_body_bytes: usize) -> Response {
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        format!(
+            "request body exceeds {max_body_bytes} bytes ([limits] max_body_bytes); \
+             stage large diffs via {} instead",
+            diff_staging::STAGING_PATH
+        ),
+    )
+        .into_response()
+}
+
+/// Pass `body` through unbuffered, failing the stream once more than
+/// `max_body_bytes` have been read.
+fn limit_body(body: axum::body::Body, max_body_bytes: usize) -> axum::body::Body {
+    let mut seen = 0_usize;
+    axum::body::Body::from_stream(body.into_data_stream().map(move |chunk| {
+        let chunk = chunk?;
+        seen += chunk.len();
+        if seen > max_body_bytes {
+            return Err(axum::Error::new(format!(
+                "request body exceeds {max_body_bytes} bytes ([limits] max_body_bytes)"
+            )));
+        }
+        Ok(chunk)
+    }))
+}
+
 /// The protocol version that rmcp 0.13 reports as LATEST.
 const RMCP_LATEST_PROTOCOL_VERSION: &str = "2025-03-26";
 
@@ -425,9 +524,9 @@ pub async fn bind_http(state: &AppState) -> Result<tokio::net::TcpListener> {
 /// Serve the HTTP/Streamable-HTTP MCP transport on a pre-bound listener.
 ///
 /// Call [`bind_http`] first to obtain the listener.  This split allows
-/// `main` to detect port conflicts eagerly — before spawning the
-/// long-lived serve task — so it can shut down already-started services
-/// and exit cleanly.
+/// [`ServerBuilder::start`](crate::server::ServerBuilder::start) to detect
+/// port conflicts eagerly — before spawning the long-lived serve task — so
+/// it can shut down already-started services and fail cleanly.
 ///
 /// # Errors
 ///
@@ -477,8 +576,9 @@ pub async fn serve_with_listener(
             // F-10: resolve the effective channel ID via workspace_id only.
             //
             // If workspace_id is present but not found in the hot-reloadable
-            // mapping table, the session runs without a Slack channel
-            // (local-only mode).  There is no fallback to a bare channel_id
+            // mapping table, `[workspace_discovery]` may route it to a
+            // pending channel; otherwise the session runs without a Slack
+            // channel (local-only mode).  There is no fallback to a bare channel_id
             // parameter — that parameter has been removed.
             let effective_channel: Option<String> = {
                 let mappings = state_for_factory
@@ -492,14 +592,19 @@ pub async fn serve_with_listener(
                         .iter()
                         .find(|m| m.workspace_id.as_str() == ws_id.as_str())
                         .map(|m| m.channel_id.clone());
-                    if resolved.is_none() {
-                        warn!(
-                            workspace_id = %ws_id,
-                            "workspace_id not found in [[workspace]] config; \
-                             session will run without Slack channel routing"
-                        );
-                    }
-                    resolved
+                    resolved.or_else(|| {
+                        drop(mappings);
+                        let discovered =
+                            workspace_discovery::resolve_unmapped(&state_for_factory, ws_id);
+                        if discovered.is_none() {
+                            warn!(
+                                workspace_id = %ws_id,
+                                "workspace_id not found in [[workspace]] config; \
+                                 session will run without Slack channel routing"
+                            );
+                        }
+                        discovered
+                    })
                 } else {
                     // No workspace_id → no channel (F-10: channel_id fallback removed).
                     None
@@ -529,7 +634,10 @@ pub async fn serve_with_listener(
     let mcp_service = axum::Router::new()
         .fallback_service(service)
         .layer(middleware::from_fn_with_state(
-            pending_params,
+            AcceptState {
+                pending: pending_params,
+                max_body_bytes: state.config.limits.max_body_bytes,
+            },
             ensure_accept_header,
         ))
         .layer(middleware::from_fn_with_state(
@@ -539,11 +647,17 @@ pub async fn serve_with_listener(
 
     let router = axum::Router::new()
         .nest("/mcp", mcp_service)
-        .route("/health", get(health))
         .route("/sse", get(sse_gone))
-        .layer(middleware::from_fn(log_all_requests));
-
-    info!("registered routes: /mcp, /health, /sse");
+        .merge(health::router(Arc::clone(&state)))
+        .merge(config_api::router(Arc::clone(&state)))
+        .merge(diff_staging::router(Arc::clone(&state)));
+    let router = with_approval_routes(router, &state).layer(middleware::from_fn(log_all_requests));
+
+    info!(
+        "registered routes: /mcp, /health, /health/ready, /metrics, /sse, {}, {}",
+        config_api::CONFIG_PATH,
+        diff_staging::STAGING_PATH
+    );
     info!(%bind, "starting HTTP/Streamable-HTTP MCP transport");
 
     axum::serve(listener, router)
@@ -2,23 +2,41 @@
 //!
 //! Applies previously approved code changes to the local file system.
 //! Validates approval status, checks file integrity via SHA-256 hash
-//! comparison, and performs atomic writes.
+//! comparison, and performs atomic writes. Files matching
+//! `[protected_paths]` are only written after a fresh confirmation in Slack
+//! (see [`apply_guard`](crate::orchestrator::apply_guard)), and requests
+//! the operator scheduled with `apply-at` are left to the server (see
+//! [`scheduled_apply`](crate::orchestrator::scheduled_apply)). Diffs whose
+//! file changed on disk after they were proposed are refused as stale
+//! unless forced (see
+//! [`file_change_watcher`](crate::orchestrator::file_change_watcher)).
+//! Diffs that fail the hash check or no longer apply are recorded as
+//! conflicts and put to the operator, who forces, merges or rejects them
+//! (see [`merge_view`](crate::diff::merge_view)).
 
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
+use chrono::Utc;
 use rmcp::handler::server::tool::ToolCallContext;
 use rmcp::model::CallToolResult;
-use slack_morphism::prelude::SlackChannelId;
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
 use tracing::{info, info_span, warn, Instrument};
 
+use crate::diff::merge_view::merge_view;
 use crate::diff::patcher::apply_patch;
 use crate::diff::writer::write_full_file;
 use crate::mcp::handler::IntercomServer;
-use crate::models::approval::ApprovalStatus;
+use crate::models::approval::{ApprovalRequest, ApprovalStatus};
+use crate::models::conflict::{ApplyConflict, ConflictKind, ConflictResolution};
+use crate::orchestrator::apply_guard::{self, ApplyDecision};
 use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::conflict_repo::ConflictRepo;
 use crate::persistence::session_repo::SessionRepo;
 use crate::slack::blocks;
-use crate::slack::client::SlackMessage;
+use crate::slack::client::{SlackMessage, SlackService};
+use crate::state::AppState;
 
 /// Input parameters for the `accept_diff` tool per mcp-tools.json contract.
 #[derive(Debug, serde::Deserialize)]
@@ -42,6 +60,235 @@ fn error_result(code: &str, message: &str) -> CallToolResult {
         .unwrap_or_else(|_| rmcp::model::Content::text(format!("{code}: {message}")))])
 }
 
+/// Build the error response for a conflict the operator was asked to
+/// settle: the standard error schema plus the conflict record, how it was
+/// resolved, and the merge view when one was asked for.
+fn conflict_result(
+    code: &str,
+    message: &str,
+    conflict_id: &str,
+    resolution: ConflictResolution,
+    merge_view: Option<String>,
+) -> CallToolResult {
+    let mut body = serde_json::json!({
+        "status": "error",
+        "error_code": code,
+        "error_message": message,
+        "conflict_id": conflict_id,
+        "resolution": resolution.as_str(),
+    });
+    if let Some(view) = merge_view {
+        body["merge_view"] = serde_json::Value::String(view);
+    }
+    CallToolResult::success(vec![rmcp::model::Content::json(body)
+        .unwrap_or_else(|_| rmcp::model::Content::text(format!("{code}: {message}")))])
+}
+
+/// Record a conflict on `approval`, ask the operator to settle it in the
+/// session's thread and wait for the answer.
+///
+/// Returns the operator who chose Force Apply, or the error result to hand
+/// the agent: the merge view for Merge View, `conflict_rejected` (with the
+/// approval moved to `Rejected`) for Reject, and the plain
+/// `patch_conflict` when no one answers in time or Slack or the session
+/// channel is missing.
+async fn arbitrate_conflict(
+    state: &AppState,
+    approval: &ApprovalRequest,
+    channel_id: Option<&str>,
+    thread_ts: Option<&str>,
+    kind: ConflictKind,
+    detail: &str,
+    path: &Path,
+) -> Result<String, CallToolResult> {
+    let (Some(slack), Some(ch)) = (&state.slack, channel_id) else {
+        return Err(error_result("patch_conflict", detail));
+    };
+
+    let repo = ConflictRepo::new(Arc::clone(&state.db));
+    let conflict = ApplyConflict::new(
+        approval.id.clone(),
+        approval.session_id.clone(),
+        approval.file_path.clone(),
+        kind,
+        detail.to_owned(),
+    );
+    if let Err(err) = repo.create(&conflict).await {
+        warn!(%err, request_id = %approval.id, "failed to record apply conflict");
+        return Err(error_result("patch_conflict", detail));
+    }
+
+    let rx = state.apply_confirmations.register(&conflict.id);
+    let msg = SlackMessage {
+        channel: SlackChannelId(ch.to_owned()),
+        text: Some(format!(
+            "\u{2694}\u{fe0f} Apply conflict on {}",
+            approval.file_path
+        )),
+        blocks: Some(blocks::apply_conflict_blocks(approval, &conflict)),
+        thread_ts: thread_ts.map(|ts| SlackTs(ts.to_owned())),
+    };
+    super::util::post_notice(state, &approval.session_id, msg);
+
+    let timeout_seconds = state.config.timeouts.approval_seconds;
+    let decision = state
+        .apply_confirmations
+        .wait(&conflict.id, rx, Duration::from_secs(timeout_seconds))
+        .await;
+    let (resolution, user_id) = match &decision {
+        Some(ApplyDecision::Confirmed { user_id }) => {
+            (ConflictResolution::ForceApply, Some(user_id.as_str()))
+        }
+        Some(ApplyDecision::Merge { user_id }) => {
+            (ConflictResolution::Merge, Some(user_id.as_str()))
+        }
+        Some(ApplyDecision::Cancelled { user_id }) => {
+            (ConflictResolution::Reject, Some(user_id.as_str()))
+        }
+        None => (ConflictResolution::Expired, None),
+    };
+    if let Err(err) = repo
+        .resolve(&conflict.id, resolution, user_id, Utc::now())
+        .await
+    {
+        warn!(%err, conflict_id = %conflict.id, "failed to record conflict resolution");
+    }
+    info!(
+        conflict_id = %conflict.id,
+        request_id = %approval.id,
+        resolution = resolution.as_str(),
+        "apply conflict settled"
+    );
+
+    match decision {
+        Some(ApplyDecision::Confirmed { user_id }) => Ok(user_id),
+        Some(ApplyDecision::Merge { .. }) => {
+            let view = share_merge_view(slack, ch, thread_ts, approval, &conflict.id, path).await;
+            Err(conflict_result(
+                "patch_conflict",
+                "the operator asked for a merge: resolve the conflict markers in merge_view \
+                 against the current file and propose a fresh diff with check_clearance",
+                &conflict.id,
+                resolution,
+                view,
+            ))
+        }
+        Some(ApplyDecision::Cancelled { .. }) => {
+            if let Err(err) = ApprovalRepo::new(Arc::clone(&state.db))
+                .update_status(&approval.id, ApprovalStatus::Rejected)
+                .await
+            {
+                warn!(%err, request_id = %approval.id, "failed to reject conflicting diff");
+            }
+            Err(conflict_result(
+                "conflict_rejected",
+                "the operator rejected the conflicting diff; it will not be applied",
+                &conflict.id,
+                resolution,
+                None,
+            ))
+        }
+        None => Err(conflict_result(
+            "patch_conflict",
+            detail,
+            &conflict.id,
+            resolution,
+            None,
+        )),
+    }
+}
+
+/// Render the merge view of `approval` against the file at `path` and
+/// upload it where the conflict was posted.
+async fn share_merge_view(
+    slack: &SlackService,
+    channel_id: &str,
+    thread_ts: Option<&str>,
+    approval: &ApprovalRequest,
+    conflict_id: &str,
+    path: &Path,
+) -> Option<String> {
+    let current = tokio::fs::read_to_string(path).await.unwrap_or_default();
+    let view = merge_view(&current, &approval.diff_content)?;
+    let name = Path::new(&approval.file_path)
+        .file_name()
+        .map_or_else(|| "file".to_owned(), |n| n.to_string_lossy().into_owned());
+    if let Err(err) = slack
+        .upload_file(
+            SlackChannelId(channel_id.to_owned()),
+            &format!("{name}.merge"),
+            &view,
+            thread_ts.map(|ts| SlackTs(ts.to_owned())),
+            Some("text"),
+        )
+        .await
+    {
+        warn!(%err, conflict_id, "failed to upload merge view");
+    }
+    Some(view)
+}
+
+/// Ask the operator to confirm writing `approval` to a protected path and
+/// wait for the answer.
+///
+/// Returns the confirming user, or the error result to hand the agent when
+/// the apply is cancelled, times out, or cannot be asked for because Slack
+/// or the session channel is missing. The approval stays `Approved`, so the
+/// agent may call `check_diff` again.
+async fn confirm_protected(
+    state: &AppState,
+    approval: &ApprovalRequest,
+    channel_id: Option<&str>,
+    force: bool,
+) -> Result<String, CallToolResult> {
+    let Some(ch) = channel_id.filter(|_| state.slack.is_some()) else {
+        return Err(error_result(
+            "confirmation_unavailable",
+            "file path is protected and needs an operator's confirmation in Slack, \
+             which is not available for this session",
+        ));
+    };
+
+    let rx = state.apply_confirmations.register(&approval.id);
+    let msg = SlackMessage {
+        channel: SlackChannelId(ch.to_owned()),
+        text: Some(format!(
+            "\u{1f6e1}\u{fe0f} Confirm apply to protected path {}",
+            approval.file_path
+        )),
+        blocks: Some(blocks::protected_apply_blocks(
+            approval,
+            apply_guard::diff_stats(&approval.diff_content),
+            force,
+        )),
+        thread_ts: None,
+    };
+    super::util::post_notice(state, &approval.session_id, msg);
+
+    let timeout_seconds = state.config.timeouts.approval_seconds;
+    match state
+        .apply_confirmations
+        .wait(&approval.id, rx, Duration::from_secs(timeout_seconds))
+        .await
+    {
+        Some(ApplyDecision::Confirmed { user_id }) => Ok(user_id),
+        Some(ApplyDecision::Cancelled { user_id } | ApplyDecision::Merge { user_id }) => {
+            info!(request_id = %approval.id, user_id, "protected apply cancelled");
+            Err(error_result(
+                "apply_cancelled",
+                "operator cancelled applying the diff to a protected path",
+            ))
+        }
+        None => {
+            info!(request_id = %approval.id, timeout_seconds, "protected apply unconfirmed");
+            Err(error_result(
+                "confirmation_timeout",
+                "no operator confirmed applying the diff to a protected path in time",
+            ))
+        }
+    }
+}
+
 /// Handle the `accept_diff` tool call.
 ///
 /// # Errors
@@ -53,7 +300,7 @@ pub async fn handle(
     context: ToolCallContext<'_, IntercomServer>,
 ) -> Result<CallToolResult, rmcp::ErrorData> {
     let state = Arc::clone(context.service.state());
-    let channel_id = context.service.effective_channel_id().map(str::to_owned);
+    let channel_id = context.service.routed_channel_id();
     let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
 
     let input: AcceptDiffInput =
@@ -101,6 +348,33 @@ pub async fn handle(
             ));
         }
 
+        // ── Stale diffs must be re-proposed ──────────────────
+        if let Some(stale_at) = approval.stale_at {
+            if !input.force {
+                return Ok(error_result(
+                    "stale_diff",
+                    &format!(
+                        "{} changed on disk at {} after the diff was proposed; re-read it and \
+                         propose a rebased diff with check_clearance",
+                        approval.file_path,
+                        stale_at.to_rfc3339()
+                    ),
+                ));
+            }
+        }
+
+        // ── Scheduled applies are made by the server ─────────
+        if let Some(apply_after) = approval.apply_after {
+            return Ok(error_result(
+                "apply_scheduled",
+                &format!(
+                    "the operator scheduled the server to apply this diff at {}; its result \
+                     arrives as a steering message",
+                    apply_after.to_rfc3339()
+                ),
+            ));
+        }
+
         // ── Resolve session for workspace root ───────────────
         let session_repo = SessionRepo::new(Arc::clone(&state.db));
         let Some(session) = session_repo
@@ -127,6 +401,23 @@ pub async fn handle(
             ));
         };
 
+        // ── Determine write mode ─────────────────────────────
+        let is_unified_diff =
+            approval.diff_content.starts_with("--- ") || approval.diff_content.starts_with("diff ");
+
+        // Guard: reject non-unified content for existing non-empty files.
+        // write_full_file is intentional only for new/empty file creation.
+        if !is_unified_diff {
+            let existing_size = validated_path.metadata().map_or(0, |m| m.len());
+            if existing_size > 0 {
+                return Ok(error_result(
+                    "invalid_diff",
+                    "diff content does not appear to be a unified diff (missing '--- ' header); \
+                     submit a properly-formatted unified diff for existing files",
+                ));
+            }
+        }
+
         // ── Hash comparison (integrity check) ────────────────
         let current_hash = super::util::compute_file_hash(&validated_path)
             .await
@@ -145,28 +436,26 @@ pub async fn handle(
             "file integrity check"
         );
 
+        // ── Diverged files are put to the operator ───────────
+        let mut forced_by = None;
         if !hash_matches && !input.force {
-            // T059 / S029 — Post conflict alert to Slack before returning error.
-            if let (Some(ref slack), Some(ref ch)) = (&state.slack, &channel_id) {
-                let channel = SlackChannelId(ch.clone());
-                let msg = SlackMessage {
-                    channel,
-                    text: Some(format!(
-                        "\u{274c} Patch conflict: {} has changed since proposal",
-                        approval.file_path
-                    )),
-                    blocks: Some(vec![blocks::diff_conflict_section(&approval.file_path)]),
-                    thread_ts: None,
-                };
-                let _ = slack.enqueue(msg).await;
-            }
-            return Ok(error_result(
-                "patch_conflict",
+            match arbitrate_conflict(
+                &state,
+                &approval,
+                channel_id.as_deref(),
+                session.thread_ts.as_deref(),
+                ConflictKind::HashMismatch,
                 "file content has changed since proposal was created",
-            ));
+                &validated_path,
+            )
+            .await
+            {
+                Ok(user_id) => forced_by = Some(user_id),
+                Err(result) => return Ok(result),
+            }
         }
 
-        if !hash_matches && input.force {
+        if !hash_matches {
             warn!(
                 request_id = %input.request_id,
                 file_path = %approval.file_path,
@@ -174,7 +463,7 @@ pub async fn handle(
             );
 
             // Log force-apply warning to Slack.
-            if let (Some(ref slack), Some(ref ch)) = (&state.slack, &channel_id) {
+            if let Some(ref ch) = channel_id {
                 let channel = SlackChannelId(ch.clone());
                 let msg = SlackMessage {
                     channel,
@@ -185,28 +474,21 @@ pub async fn handle(
                     blocks: Some(vec![blocks::diff_force_warning_section(
                         &approval.file_path,
                     )]),
-                    thread_ts: None,
+                    thread_ts: session.thread_ts.clone().map(SlackTs),
                 };
-                let _ = slack.enqueue(msg).await;
+                super::util::post_notice(&state, &session.id, msg);
             }
         }
 
-        // ── Determine write mode and apply ───────────────────
-        let is_unified_diff =
-            approval.diff_content.starts_with("--- ") || approval.diff_content.starts_with("diff ");
-
-        // Guard: reject non-unified content for existing non-empty files.
-        // write_full_file is intentional only for new/empty file creation.
-        if !is_unified_diff {
-            let existing_size = validated_path.metadata().map_or(0, |m| m.len());
-            if existing_size > 0 {
-                return Ok(error_result(
-                    "invalid_diff",
-                    "diff content does not appear to be a unified diff (missing '--- ' header); \
-                     submit a properly-formatted unified diff for existing files",
-                ));
+        // ── Protected paths need a fresh confirmation ────────
+        let confirmed_by = if state.config.protected_paths.protects(&approval.file_path) {
+            match confirm_protected(&state, &approval, channel_id.as_deref(), !hash_matches).await {
+                Ok(user_id) => Some(user_id),
+                Err(result) => return Ok(result),
             }
-        }
+        } else {
+            None
+        };
 
         let write_result = if is_unified_diff {
             apply_patch(&validated_path, &approval.diff_content, &workspace_root)
@@ -217,10 +499,24 @@ pub async fn handle(
         let summary = match write_result {
             Ok(s) => s,
             Err(err) => {
-                return Ok(error_result(
-                    "patch_conflict",
-                    &format!("failed to apply changes: {err}"),
-                ));
+                let detail = format!("failed to apply changes: {err}");
+                if !is_unified_diff {
+                    return Ok(error_result("patch_conflict", &detail));
+                }
+                // Force Apply is not offered for hunk conflicts.
+                let result = arbitrate_conflict(
+                    &state,
+                    &approval,
+                    channel_id.as_deref(),
+                    session.thread_ts.as_deref(),
+                    ConflictKind::HunkConflict,
+                    &detail,
+                    &validated_path,
+                )
+                .await
+                .err()
+                .unwrap_or_else(|| error_result("patch_conflict", &detail));
+                return Ok(result);
             }
         };
 
@@ -230,7 +526,7 @@ pub async fn handle(
         }
 
         // ── Post confirmation to Slack ───────────────────────
-        if let (Some(ref slack), Some(ref ch)) = (&state.slack, &channel_id) {
+        if let Some(ref ch) = channel_id {
             let channel = SlackChannelId(ch.clone());
             let msg = SlackMessage {
                 channel,
@@ -242,9 +538,9 @@ pub async fn handle(
                     &approval.file_path,
                     summary.bytes_written,
                 )]),
-                thread_ts: None,
+                thread_ts: session.thread_ts.clone().map(SlackTs),
             };
-            let _ = slack.enqueue(msg).await;
+            super::util::post_notice(&state, &session.id, msg);
         }
 
         // ── Update session last_tool ─────────────────────────
@@ -260,13 +556,19 @@ pub async fn handle(
         );
 
         // ── Build response ───────────────────────────────────
-        let response = serde_json::json!({
+        let mut response = serde_json::json!({
             "status": "applied",
             "files_written": [{
                 "path": approval.file_path,
                 "bytes": summary.bytes_written,
             }],
         });
+        if let Some(user_id) = confirmed_by {
+            response["confirmed_by"] = serde_json::Value::String(user_id);
+        }
+        if let Some(user_id) = forced_by {
+            response["forced_by"] = serde_json::Value::String(user_id);
+        }
 
         Ok(CallToolResult::success(vec![rmcp::model::Content::json(
             response,
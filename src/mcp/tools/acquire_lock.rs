@@ -0,0 +1,268 @@
+//! `acquire_lock` MCP tool handler.
+//!
+//! Claims a file or directory of the calling session's workspace so that
+//! concurrent sessions in the same repository do not edit it at the same
+//! time. A request that conflicts with another session's lock queues
+//! behind it, in request order, and the operator is told who waits for
+//! whom in both sessions' Slack threads. Blocks up to `timeout_seconds`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use rmcp::handler::server::tool::ToolCallContext;
+use rmcp::model::CallToolResult;
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
+use tracing::{info, info_span, warn, Instrument};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::mcp::handler::IntercomServer;
+use crate::mcp::tools::util::{self, truncate_text};
+use crate::models::path_lock::{self, LockGrant, LockStatus, PathLock};
+use crate::models::session::Session;
+use crate::persistence::lock_repo::LockRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::client::SlackMessage;
+use crate::state::AppState;
+
+/// Seconds to wait for a conflicting lock when `timeout_seconds` is omitted.
+const DEFAULT_TIMEOUT_SECONDS: u64 = 300;
+
+/// Longest accepted `timeout_seconds`.
+const MAX_TIMEOUT_SECONDS: u64 = 3600;
+
+/// How often a queued request checks whether it can be granted.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Input parameters for `acquire_lock`.
+#[derive(Debug, serde::Deserialize)]
+struct AcquireLockInput {
+    /// File or directory, relative to the workspace root.
+    path: String,
+    /// Why the session wants the path, shown to the operator.
+    reason: Option<String>,
+    /// Seconds to wait behind a conflicting lock.
+    timeout_seconds: Option<u64>,
+}
+
+/// Removes a queued request when the call ends without it, including when
+/// the agent disconnects mid-wait, so it does not block later requests.
+struct QueuedRequest {
+    repo: LockRepo,
+    id: Option<String>,
+}
+
+impl QueuedRequest {
+    /// Keep the request: it was granted.
+    fn keep(&mut self) {
+        self.id = None;
+    }
+}
+
+impl Drop for QueuedRequest {
+    fn drop(&mut self) {
+        if let Some(id) = self.id.take() {
+            let repo = self.repo.clone();
+            tokio::spawn(async move {
+                if let Err(err) = repo.remove(&id).await {
+                    warn!(%err, lock_id = id, "failed to remove abandoned lock request");
+                }
+            });
+        }
+    }
+}
+
+/// Handle the `acquire_lock` tool call.
+///
+/// # Errors
+///
+/// Returns `rmcp::ErrorData` for invalid parameters, paths outside the
+/// workspace, and infrastructure failures.
+#[allow(clippy::too_many_lines)] // Queue, wait, announce and respond in one flow.
+pub async fn handle(
+    context: ToolCallContext<'_, IntercomServer>,
+) -> Result<CallToolResult, rmcp::ErrorData> {
+    let state = Arc::clone(context.service.state());
+    let bound = context.service.bound_session_id().map(str::to_owned);
+    let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
+
+    let input: AcquireLockInput =
+        serde_json::from_value(serde_json::Value::Object(args)).map_err(|err| {
+            rmcp::ErrorData::invalid_params(format!("invalid acquire_lock parameters: {err}"), None)
+        })?;
+    let timeout_seconds = input
+        .timeout_seconds
+        .unwrap_or(DEFAULT_TIMEOUT_SECONDS)
+        .min(MAX_TIMEOUT_SECONDS);
+
+    let span = info_span!("acquire_lock", path = %input.path, timeout_seconds);
+
+    async move {
+        let session = util::bound_session(&state, bound.as_deref()).await?;
+        let (root, path) = util::lock_path(&session.workspace_root, &input.path)?;
+        let repo = LockRepo::new(Arc::clone(&state.db));
+        let db_err = |err: crate::AppError| {
+            rmcp::ErrorData::internal_error(format!("failed to update path locks: {err}"), None)
+        };
+
+        // A lock the session already holds on the path or a directory
+        // above it covers the request.
+        let covering = repo
+            .held_by(&session.id)
+            .await
+            .map_err(db_err)?
+            .into_iter()
+            .find(|held| held.path == path || path_lock::is_below(&path, &held.path));
+        if let Some(held) = covering {
+            return respond(&serde_json::json!({
+                "status": "acquired",
+                "lock_id": held.id,
+                "path": held.path,
+                "waited_seconds": 0,
+            }));
+        }
+
+        let request = PathLock::queued(session.id.clone(), root, path.clone(), input.reason);
+        repo.enqueue(&request).await.map_err(db_err)?;
+        let mut queued = QueuedRequest {
+            repo: repo.clone(),
+            id: Some(request.id.clone()),
+        };
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(timeout_seconds);
+        let mut waited = false;
+        let outcome = loop {
+            match repo.try_grant(&request.id).await.map_err(db_err)? {
+                LockGrant::Acquired(lock) => break Ok(lock),
+                LockGrant::Blocked(blocker) => {
+                    if !waited {
+                        waited = true;
+                        announce_wait(&state, &session, &request, &blocker).await;
+                    }
+                    if tokio::time::Instant::now() >= deadline {
+                        break Err(blocker);
+                    }
+                }
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        };
+        let waited_seconds = (Utc::now() - request.requested_at).num_seconds().max(0);
+
+        let _ = SessionRepo::new(Arc::clone(&state.db))
+            .update_last_activity(&session.id, Some("acquire_lock".to_owned()))
+            .await;
+
+        match outcome {
+            Ok(lock) => {
+                queued.keep();
+                if let Some(ref logger) = state.audit_logger {
+                    let entry = AuditEntry::new(AuditEventType::LockAcquired)
+                        .with_session(session.id.clone())
+                        .with_request_id(lock.id.clone())
+                        .with_result(lock.path.clone());
+                    if let Err(err) = logger.log_entry(entry) {
+                        warn!(%err, "audit log write failed (lock acquired)");
+                    }
+                }
+                if waited {
+                    let text = format!(
+                        "\u{1f513} `{}` acquired `{}` after {waited_seconds}s",
+                        session.short_id, lock.path
+                    );
+                    announce(&state, &[&session], &text);
+                }
+                info!(lock_id = %lock.id, path = %lock.path, waited_seconds, "path lock acquired");
+                respond(&serde_json::json!({
+                    "status": "acquired",
+                    "lock_id": lock.id,
+                    "path": lock.path,
+                    "waited_seconds": waited_seconds,
+                }))
+            }
+            Err(blocker) => {
+                let text = format!(
+                    "\u{23f1}\u{fe0f} `{}` stopped waiting for `{path}` after {waited_seconds}s",
+                    session.short_id
+                );
+                announce(&state, &[&session], &text);
+                info!(path = %path, blocked_by = %blocker.session_id, "path lock wait timed out");
+                respond(&serde_json::json!({
+                    "status": "timeout",
+                    "path": path,
+                    "waited_seconds": waited_seconds,
+                    "blocked_by": {
+                        "session_id": blocker.session_id,
+                        "path": blocker.path,
+                        "status": blocker.status.as_str(),
+                    },
+                }))
+            }
+        }
+    }
+    .instrument(span)
+    .await
+}
+
+fn respond(response: &serde_json::Value) -> Result<CallToolResult, rmcp::ErrorData> {
+    Ok(CallToolResult::success(vec![rmcp::model::Content::json(
+        response,
+    )
+    .map_err(|err| {
+        rmcp::ErrorData::internal_error(
+            format!("failed to serialize acquire_lock response: {err}"),
+            None,
+        )
+    })?]))
+}
+
+/// Tell the operator, in the waiting and the blocking session's threads,
+/// that `request` queues behind `blocker`.
+async fn announce_wait(state: &AppState, waiter: &Session, request: &PathLock, blocker: &PathLock) {
+    let holder = SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(&blocker.session_id)
+        .await
+        .ok()
+        .flatten();
+    let holder_label = holder
+        .as_ref()
+        .map_or_else(|| blocker.session_id.clone(), |s| s.short_id.clone());
+    let reason = request
+        .reason
+        .as_deref()
+        .map(|r| format!(" ({})", truncate_text(r, 200)))
+        .unwrap_or_default();
+    let cause = match blocker.status {
+        LockStatus::Held => format!("`{holder_label}` holds `{}`", blocker.path),
+        LockStatus::Queued => format!("`{holder_label}` asked for `{}` first", blocker.path),
+    };
+    let text = format!(
+        "\u{1f512} `{}` is waiting for `{}`{reason}: {cause}",
+        waiter.short_id, request.path
+    );
+    match holder {
+        Some(ref holder) => announce(state, &[waiter, holder], &text),
+        None => announce(state, &[waiter], &text),
+    }
+}
+
+/// Post `text` once in each distinct Slack thread of `sessions`.
+fn announce(state: &AppState, sessions: &[&Session], text: &str) {
+    let mut posted: Vec<(&str, Option<&str>)> = Vec::new();
+    for session in sessions {
+        let Some(ref channel_id) = session.channel_id else {
+            continue;
+        };
+        let target = (channel_id.as_str(), session.thread_ts.as_deref());
+        if posted.contains(&target) {
+            continue;
+        }
+        posted.push(target);
+        let msg = SlackMessage {
+            channel: SlackChannelId(channel_id.clone()),
+            text: Some(text.to_owned()),
+            blocks: None,
+            thread_ts: session.thread_ts.clone().map(SlackTs),
+        };
+        util::post_notice(state, &session.id, msg);
+    }
+}
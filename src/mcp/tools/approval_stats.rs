@@ -0,0 +1,120 @@
+//! `approval_stats` MCP tool handler.
+//!
+//! Reports how the operator decided recent approval requests — approval
+//! rate overall and by risk level, and the most common rejection reasons —
+//! so an agent can calibrate when to ask with `check_clearance` and when
+//! `auto_check` suffices. Read-only; returns immediately.
+
+use std::sync::Arc;
+
+use rmcp::handler::server::tool::ToolCallContext;
+use rmcp::model::CallToolResult;
+use tracing::{debug, info_span, Instrument};
+
+use crate::audit;
+use crate::mcp::handler::IntercomServer;
+use crate::mcp::tools::util;
+use crate::models::approval::ApprovalStatus;
+use crate::orchestrator::approval_stats::ApprovalStats;
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::session_repo::SessionRepo;
+
+/// Requests considered when `limit` is omitted.
+const DEFAULT_LIMIT: u32 = 100;
+
+/// Most requests a single call considers.
+const MAX_LIMIT: u32 = 500;
+
+/// Which requests the statistics cover.
+#[derive(Debug, Clone, Copy, Default, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Scope {
+    /// Every session in the caller's workspace.
+    #[default]
+    Workspace,
+    /// Only the calling session.
+    Session,
+}
+
+/// Input parameters for `approval_stats`.
+#[derive(Debug, serde::Deserialize)]
+struct ApprovalStatsInput {
+    /// Which requests to cover.
+    #[serde(default)]
+    scope: Scope,
+    /// How many of the most recent decided requests to consider.
+    limit: Option<u32>,
+}
+
+/// Handle the `approval_stats` tool call.
+///
+/// # Errors
+///
+/// Returns `rmcp::ErrorData` for invalid parameters or persistence
+/// failures.
+pub async fn handle(
+    context: ToolCallContext<'_, IntercomServer>,
+) -> Result<CallToolResult, rmcp::ErrorData> {
+    let state = Arc::clone(context.service.state());
+    let bound = context.service.bound_session_id().map(str::to_owned);
+    let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
+
+    let input: ApprovalStatsInput = serde_json::from_value(serde_json::Value::Object(args))
+        .map_err(|err| {
+            rmcp::ErrorData::invalid_params(
+                format!("invalid approval_stats parameters: {err}"),
+                None,
+            )
+        })?;
+    let limit = input.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let span = info_span!("approval_stats", scope = ?input.scope, limit);
+
+    async move {
+        let session = util::bound_session(&state, bound.as_deref()).await?;
+        let repo = ApprovalRepo::new(Arc::clone(&state.db));
+        let (scope, requests) = match input.scope {
+            Scope::Workspace => (
+                "workspace",
+                repo.list_decided_for_workspace(&session.workspace_root, limit)
+                    .await,
+            ),
+            Scope::Session => ("session", repo.list_decided(Some(&session.id), limit).await),
+        };
+        let requests = requests.map_err(|err| {
+            rmcp::ErrorData::internal_error(format!("failed to load approvals: {err}"), None)
+        })?;
+
+        let rejected: Vec<&str> = requests
+            .iter()
+            .filter(|r| r.status == ApprovalStatus::Rejected)
+            .map(|r| r.id.as_str())
+            .collect();
+        let log_dir = audit::log_dir(state.config.default_workspace_root());
+        let decisions = audit::reader::find_decisions(&log_dir, &rejected);
+        let summary = ApprovalStats::from_requests(&requests, &decisions);
+
+        let _ = SessionRepo::new(Arc::clone(&state.db))
+            .update_last_activity(&session.id, Some("approval_stats".to_owned()))
+            .await;
+        debug!(
+            session_id = %session.id,
+            decided = summary.overall.decided(),
+            "approval stats computed"
+        );
+
+        let mut response = summary.to_json();
+        response["scope"] = serde_json::json!(scope);
+        Ok(CallToolResult::success(vec![rmcp::model::Content::json(
+            response,
+        )
+        .map_err(|err| {
+            rmcp::ErrorData::internal_error(
+                format!("failed to serialize approval_stats response: {err}"),
+                None,
+            )
+        })?]))
+    }
+    .instrument(span)
+    .await
+}
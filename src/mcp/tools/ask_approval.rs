@@ -1,9 +1,15 @@
 //! `ask_approval` MCP tool handler (T038, T040, T042).
 //!
-//! Submits a code proposal for remote operator approval via Slack.
-//! Blocks the agent until the operator responds (Accept/Reject) or
-//! the configured timeout elapses.
-
+//! Submits a code proposal for remote operator approval via Slack, with
+//! ownership context (blame summary and CODEOWNERS matches) for the file
+//! and a warning when other sessions have pending or recent changes to it.
+//! Hunks are shown widened to the functions they change (see
+//! [`diff::context`](crate::diff::context)). Blocks the agent until the
+//! operator responds (Accept/Reject) or the configured timeout elapses.
+//! Rejections carry the operator's reason both as text and as structured
+//! [`RejectionFeedback`](crate::models::rejection::RejectionFeedback).
+
+use std::fmt::Write as _;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -13,30 +19,19 @@ use slack_morphism::prelude::SlackChannelId;
 use tokio::sync::oneshot;
 use tracing::{info, info_span, warn, Instrument};
 
+use crate::config::LimitsConfig;
+use crate::diff::ownership;
+use crate::driver::{AgentEvent, CodeSnippet};
 use crate::mcp::handler::IntercomServer;
 use crate::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use crate::models::rejection::RejectionFeedback;
+use crate::models::session::ProtocolMode;
 use crate::persistence::approval_repo::ApprovalRepo;
 use crate::persistence::session_repo::SessionRepo;
-use crate::slack::blocks;
 use crate::slack::client::SlackMessage;
-use crate::state::ApprovalResponse;
-
-/// A curated code excerpt supplied by the agent for operator review.
-///
-/// Snippets are posted as a threaded Slack reply using inline code blocks,
-/// which Slack always renders as readable text (no content-scanner issues).
-#[derive(Debug, serde::Deserialize)]
-struct CodeSnippet {
-    /// Short human-readable label (e.g. `"handle() — main entry point"`).
-    label: String,
-    /// Markdown code-fence language hint (e.g. `"rust"`, `"toml"`).  May be
-    /// empty, in which case the code block is untagged.
-    #[serde(default)]
-    language: String,
-    /// The code content to display.  Truncated server-side if it exceeds
-    /// `SNIPPET_CHAR_LIMIT` characters.
-    content: String,
-}
+use crate::slack::handlers::resolved;
+use crate::slack::{approval_fanout, blocks, request_cards};
+use crate::state::{AppState, ApprovalResponse};
 
 /// Input parameters for the `ask_approval` tool per mcp-tools.json contract.
 #[derive(Debug, serde::Deserialize)]
@@ -45,8 +40,12 @@ struct AskApprovalInput {
     title: String,
     /// Contextual details about the proposed change.
     description: Option<String>,
-    /// Standard unified diff or raw file content.
+    /// Standard unified diff or raw file content. Empty when `diff_ref`
+    /// names a staged upload instead.
+    #[serde(default)]
     diff: String,
+    /// Staging ID of a diff uploaded in chunks to `/api/v1/staging`.
+    diff_ref: Option<String>,
     /// Target file path relative to `workspace_root`.
     file_path: String,
     /// Risk classification.
@@ -58,12 +57,67 @@ struct AskApprovalInput {
     /// the affected file (new functions, modified logic, key interfaces)
     /// and supplies them here.  The server posts these as a threaded Slack
     /// reply using inline code blocks so the operator can review them
-    /// without opening any attachment.  When omitted, the server falls back
-    /// to uploading the full original file as a Slack file attachment.
+    /// without opening any attachment.  When omitted, `[snippets] fallback`
+    /// decides: snippets generated from the diff, the full original file as
+    /// a Slack file attachment, or nothing.
     #[serde(default)]
     snippets: Vec<CodeSnippet>,
 }
 
+/// The diff to review: `diff` itself, or the staged upload `diff_ref`
+/// names (consuming it).
+fn resolve_diff(
+    state: &AppState,
+    diff: String,
+    diff_ref: Option<&str>,
+) -> Result<String, rmcp::ErrorData> {
+    let Some(staging_id) = diff_ref else {
+        return Ok(diff);
+    };
+    if !diff.is_empty() {
+        return Err(rmcp::ErrorData::invalid_params(
+            "pass either diff or diff_ref, not both",
+            None,
+        ));
+    }
+    let bytes = state.diff_staging.take(staging_id).ok_or_else(|| {
+        rmcp::ErrorData::invalid_params(format!("unknown or expired diff_ref '{staging_id}'"), None)
+    })?;
+    String::from_utf8(bytes).map_err(|_| {
+        rmcp::ErrorData::invalid_params(format!("staged diff '{staging_id}' is not UTF-8"), None)
+    })
+}
+
+/// Refuse diffs and snippets larger than `[limits]` allows.
+fn check_limits(input: &AskApprovalInput, limits: &LimitsConfig) -> Result<(), rmcp::ErrorData> {
+    if input.diff.len() > limits.max_diff_bytes {
+        return Err(rmcp::ErrorData::invalid_params(
+            format!(
+                "diff is {} bytes, over the {}-byte limit ([limits] max_diff_bytes)",
+                input.diff.len(),
+                limits.max_diff_bytes
+            ),
+            None,
+        ));
+    }
+    if let Some(snippet) = input
+        .snippets
+        .iter()
+        .find(|snippet| snippet.content.len() > limits.max_snippet_bytes)
+    {
+        return Err(rmcp::ErrorData::invalid_params(
+            format!(
+                "snippet '{}' is {} bytes, over the {}-byte limit ([limits] max_snippet_bytes)",
+                snippet.label,
+                snippet.content.len(),
+                limits.max_snippet_bytes
+            ),
+            None,
+        ));
+    }
+    Ok(())
+}
+
 fn default_risk_level() -> RiskLevel {
     RiskLevel::Low
 }
@@ -78,7 +132,7 @@ pub async fn handle(
     context: ToolCallContext<'_, IntercomServer>,
 ) -> Result<CallToolResult, rmcp::ErrorData> {
     let state = Arc::clone(context.service.state());
-    let channel_id = context.service.effective_channel_id().map(str::to_owned);
+    let channel_id = context.service.routed_channel_id();
     let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
 
     let input: AskApprovalInput =
@@ -96,7 +150,8 @@ pub async fn handle(
         // ── Early Slack channel check (T061 / S033) ─────────
         // Return a descriptive error instead of blocking indefinitely when
         // Slack is not configured or no channel_id is set for this session.
-        if state.slack.is_none() || channel_id.is_none() {
+        // A simulated operator answers without Slack.
+        if state.simulator.is_none() && (state.slack.is_none() || channel_id.is_none()) {
             let (error_code, error_message) = if state.slack.is_none() {
                 (
                     "slack_unavailable",
@@ -122,6 +177,11 @@ pub async fn handle(
             })]));
         }
 
+        // ── Diff source and size limits ──────────────────────
+        let mut input = input;
+        input.diff = resolve_diff(&state, input.diff, input.diff_ref.as_deref())?;
+        check_limits(&input, &state.config.limits)?;
+
         // ── Resolve session ──────────────────────────────────
         // In ACP mode the agent subprocess supplies `?session_id=<id>` so we
         // can pin the tool call to the exact session (T112 / HITL-003).
@@ -177,13 +237,6 @@ pub async fn handle(
                 )
             })?;
 
-        // ── Read original file content for Slack attachment ──
-        // T084: Upload the existing file alongside the diff so operators
-        // can review full context. Handles new-file and read-error cases
-        // gracefully (T085, T086).
-        let original_content =
-            read_original_file_for_attachment(&validated_path, &original_hash).await;
-
         // ── Create ApprovalRequest record ────────────────────
         let approval = ApprovalRequest::new(
             session.id.clone(),
@@ -197,212 +250,34 @@ pub async fn handle(
         let request_id = approval.id.clone();
 
         let approval_repo = ApprovalRepo::new(Arc::clone(&state.db));
-        let _created = approval_repo.create(&approval).await.map_err(|err| {
+        approval_repo.create(&approval).await.map_err(|err| {
             rmcp::ErrorData::internal_error(
                 format!("failed to persist approval request: {err}"),
                 None,
             )
         })?;
 
-        // S037: effective_thread_ts tracks which Slack thread to use for all
-        // subsequent messages in this tool call. Starts as the session's
-        // existing thread_ts; updated to the approval post ts if this is the
-        // session's first message (so timeout/follow-up notifications land in
-        // the thread, not at the channel root).
-        let mut effective_thread_ts = session_thread_ts.clone();
-
         // ── Post to Slack ────────────────────────────────────
-        // US17: when the session lives in a thread, post plain text (no
-        // block-kit buttons) and register a thread-reply fallback for
-        // @-mention decisions.  Main channel messages keep block-kit.
+        // The Slack request subscriber posts the card (text-only inside a
+        // session thread, US17) and records its anchor on the request.
         let is_threaded = session_thread_ts.is_some();
-
-        if let (Some(ref slack), Some(ref ch)) = (&state.slack, &channel_id) {
-            let channel = SlackChannelId(ch.clone());
-
-            if is_threaded {
-                // US17: text-only thread approval — no blocks/buttons.
-                // Mirror the main-channel path: inline for short diffs,
-                // upload as a thread snippet for large diffs (RI-004).
-                let diff_line_count = input.diff.lines().count();
-                let inline_diff = (diff_line_count <= blocks::INLINE_DIFF_THRESHOLD)
-                    .then_some(input.diff.as_str());
-                let text_body = blocks::build_text_only_approval(
-                    &input.title,
-                    inline_diff,
-                    &input.file_path,
-                    &input.risk_level,
-                    input.description.as_deref(),
-                );
-                let msg = SlackMessage {
-                    channel: channel.clone(),
-                    text: Some(text_body),
-                    blocks: None,
-                    thread_ts: session_thread_ts.clone(),
-                };
-                if let Err(err) = slack.enqueue(msg).await {
-                    warn!(%err, "failed to enqueue text-only approval message");
-                }
-                // Mirror main-channel path: upload large diffs as a file snippet
-                // pinned to the session thread (upload_file already accepts thread_ts).
-                if diff_line_count > blocks::INLINE_DIFF_THRESHOLD {
-                    let upload_span =
-                        info_span!("slack_upload_diff_thread", request_id = %request_id);
-                    async {
-                        let sanitized = input.file_path.replace(['/', '.'], "_");
-                        let filename = format!("{sanitized}.diff.txt");
-                        if let Err(err) = slack
-                            .upload_file(
-                                channel.clone(),
-                                &filename,
-                                &input.diff,
-                                session_thread_ts.clone(),
-                                Some("text"),
-                            )
-                            .await
-                        {
-                            warn!(%err, "failed to upload diff snippet to thread");
-                        }
-                    }
-                    .instrument(upload_span)
-                    .await;
-                }
-            } else {
-                let mut message_blocks = blocks::build_approval_blocks(
-                    &input.title,
-                    input.description.as_deref(),
-                    &input.diff,
-                    &input.file_path,
-                    input.risk_level,
-                );
-                message_blocks.push(blocks::approval_buttons(&request_id));
-
-                let diff_line_count = input.diff.lines().count();
-
-                if diff_line_count > blocks::INLINE_DIFF_THRESHOLD {
-                    // Upload large diff as a file snippet.  Pass snippet_type
-                    // "text" so Slack pre-classifies the file before its content
-                    // scanner runs, preventing the "Binary" label.
-                    let upload_span = info_span!("slack_upload_diff", request_id = %request_id);
-                    async {
-                        let sanitized = input.file_path.replace(['/', '.'], "_");
-                        let filename = format!("{sanitized}.diff.txt");
-                        if let Err(err) = slack
-                            .upload_file(
-                                channel.clone(),
-                                &filename,
-                                &input.diff,
-                                None,
-                                Some("text"),
-                            )
-                            .await
-                        {
-                            warn!(%err, "failed to upload diff snippet to slack");
-                        }
-                    }
-                    .instrument(upload_span)
-                    .await;
-                }
-
-                // Post the approval message directly so we can capture the Slack
-                // `ts` for threading snippet replies.
-                let post_span = info_span!("slack_post_approval", request_id = %request_id);
-                let approval_ts: Option<slack_morphism::prelude::SlackTs> = async {
-                    let msg = SlackMessage {
-                        channel: channel.clone(),
-                        text: Some(format!("\u{1f4cb} Approval Request: {}", input.title)),
-                        blocks: Some(message_blocks),
-                        // S037: post inside session thread when thread_ts is set;
-                        // otherwise post at channel root (and this ts becomes the root).
-                        thread_ts: None,
-                    };
-                    match slack.post_message_direct(msg).await {
-                        Ok(ts) => {
-                            // S036: if the session had no thread_ts, record this
-                            // approval message as the session's thread root.
-                            if session_thread_ts.is_none() {
-                                if let Err(err) =
-                                    session_repo.set_thread_ts(&session.id, &ts.0).await
-                                {
-                                    warn!(%err, session_id = %session.id,
-                                    "failed to record thread_ts from approval message");
-                                }
-                            }
-                            Some(ts)
-                        }
-                        Err(err) => {
-                            warn!(%err, "failed to post approval message");
-                            None
-                        }
-                    }
-                }
-                .instrument(post_span)
-                .await;
-
-                // S037: if this was the session's first Slack message, the approval
-                // post ts becomes the thread root for all subsequent messages.
-                if session_thread_ts.is_none() {
-                    effective_thread_ts = approval_ts.clone();
-                }
-
-                // ── Snippet thread (preferred) or file upload (fallback) ──────
-                //
-                // When the agent supplies curated `snippets`, post them as a
-                // threaded Slack reply.  Inline code blocks in messages always
-                // render as readable text — no content-scanner interference.
-                //
-                // When no snippets are provided, fall back to uploading the full
-                // original file for operator review (T084–T086).
-                if !input.snippets.is_empty() {
-                    if let Some(ref ts) = approval_ts {
-                        let snippet_span =
-                            info_span!("slack_post_snippets", request_id = %request_id);
-                        async {
-                            let snippet_blocks = blocks::code_snippet_blocks(
-                                &input
-                                    .snippets
-                                    .iter()
-                                    .map(|s| {
-                                        (s.label.as_str(), s.language.as_str(), s.content.as_str())
-                                    })
-                                    .collect::<Vec<_>>(),
-                            );
-                            let msg = SlackMessage {
-                                channel: channel.clone(),
-                                text: Some("Code snippets for review".into()),
-                                blocks: Some(snippet_blocks),
-                                thread_ts: Some(ts.clone()),
-                            };
-                            if let Err(err) = slack.enqueue(msg).await {
-                                warn!(%err, "failed to post snippet thread");
-                            }
-                        }
-                        .instrument(snippet_span)
-                        .await;
-                    }
-                } else if let Some(ref original) = original_content {
-                    // Fallback: upload the full original file (T084). Skipped for
-                    // new files (T085) or unreadable files (T086).
-                    let orig_span = info_span!("slack_upload_original", request_id = %request_id);
-                    async {
-                        let sanitized = input.file_path.replace(['/', '.'], "_");
-                        let filename = format!("{sanitized}.original.txt");
-                        let lang =
-                            crate::slack::commands::file_extension_language(&input.file_path);
-                        if let Err(err) = slack
-                            .upload_file(channel.clone(), &filename, original, None, Some(lang))
-                            .await
-                        {
-                            warn!(%err, "failed to upload original file to slack");
-                        }
-                    }
-                    .instrument(orig_span)
-                    .await;
-                }
-            } // end else (non-threaded)
-        } else {
+        if state.slack.is_none() {
             warn!("slack not configured; approval request will block without notification");
         }
+        state.event_bus.publish(
+            ProtocolMode::Mcp,
+            channel_id.clone(),
+            AgentEvent::ClearanceRequested {
+                request_id: request_id.clone(),
+                session_id: session.id.clone(),
+                title: input.title.clone(),
+                description: input.description.clone().unwrap_or_default(),
+                diff: Some(input.diff.clone()),
+                file_path: input.file_path.clone(),
+                risk_level: input.risk_level.as_str().to_owned(),
+                snippets: std::mem::take(&mut input.snippets),
+            },
+        );
 
         // ── Register oneshot and wait ────────────────────────
         let (tx, rx) = oneshot::channel::<ApprovalResponse>();
@@ -418,7 +293,16 @@ pub async fn handle(
                     .as_ref()
                     .map(|ts| ts.0.clone())
                     .unwrap_or_default();
-                let authorized_user = session.owner_user_id.clone();
+                // Thread fallbacks accept a single user: the first mapped
+                // code owner when owner approval is enforced.
+                let authorized_user = ownership::required_approvers(
+                    &state.config.codeowners,
+                    &workspace_root,
+                    &input.file_path,
+                )
+                .into_iter()
+                .next()
+                .unwrap_or_else(|| session.owner_user_id.clone());
                 let fallback_ch = ch.clone();
                 let fallback_ts = thread_ts.clone();
 
@@ -437,6 +321,7 @@ pub async fn handle(
                 if registered {
                     let state_fb = Arc::clone(&state);
                     let rid = request_id.clone();
+                    let title_fb = input.title.clone();
                     tokio::spawn(async move {
                         let timeout =
                             Duration::from_secs(state_fb.config.timeouts.approval_seconds);
@@ -454,8 +339,16 @@ pub async fn handle(
                                         } else {
                                             Some(decision.instruction)
                                         };
+                                        let status_line = if approved {
+                                            format!("\u{2705} *Approved* \u{2014} {title_fb}")
+                                        } else {
+                                            format!(
+                                                "\u{274c} *Rejected* \u{2014} {title_fb}: {}",
+                                                reason.as_deref().unwrap_or("no reason given")
+                                            )
+                                        };
                                         if let Err(err) = state_fb
-                                            .driver
+                                            .mcp_driver()
                                             .resolve_clearance(&rid, approved, reason)
                                             .await
                                         {
@@ -464,6 +357,16 @@ pub async fn handle(
                                                 %err,
                                                 "US17: failed to resolve clearance from thread reply"
                                             );
+                                        } else if let Some(slack) = state_fb.slack.as_ref() {
+                                            if let Some(anchor) = request_cards::approval_anchor(
+                                                &state_fb,
+                                                &fallback_ch,
+                                                &rid,
+                                            )
+                                            .await
+                                            {
+                                                anchor.resolve(slack, &status_line).await;
+                                            }
                                         }
                                     }
                                     keyword => {
@@ -504,15 +407,18 @@ pub async fn handle(
         let timeout_seconds = state.config.timeouts.approval_seconds;
         let timeout_duration = Duration::from_secs(timeout_seconds);
 
-        let response = tokio::time::timeout(timeout_duration, rx).await;
+        let response = state
+            .snoozes
+            .wait(&request_id, timeout_duration, rx)
+            .await;
 
         let (status, reason) = match response {
-            Ok(Ok(resp)) => (resp.status, resp.reason),
-            Ok(Err(_)) => {
+            Some(Ok(resp)) => (resp.status, resp.reason),
+            Some(Err(_)) => {
                 // Sender dropped without sending (e.g., server shutdown).
                 ("timeout".to_owned(), None)
             }
-            Err(_elapsed) => {
+            None => {
                 // Timeout expired — mark as expired and notify Slack.
                 info!(
                     request_id = %request_id,
@@ -520,27 +426,56 @@ pub async fn handle(
                     "approval request timed out"
                 );
                 let _ = approval_repo
-                    .update_status(&request_id, ApprovalStatus::Expired)
+                    .decide(&request_id, ApprovalStatus::Expired)
                     .await;
 
-                if let (Some(ref slack), Some(ref ch)) = (&state.slack, &channel_id) {
-                    let channel = SlackChannelId(ch.clone());
-                    let msg = SlackMessage {
-                        channel,
-                        text: Some(format!(
-                            "\u{23f1}\u{fe0f} Approval request '{}' timed out",
-                            input.title
-                        )),
-                        blocks: Some(vec![blocks::severity_section(
-                            "warning",
-                            &format!(
-                                "Approval request *{}* timed out after {} seconds",
-                                input.title, timeout_seconds
-                            ),
-                        )]),
-                        thread_ts: effective_thread_ts.clone(),
+                if let Some(ref slack) = state.slack {
+                    let text = format!(
+                        "\u{23f1}\u{fe0f} Approval request '{}' timed out",
+                        input.title
+                    );
+                    let notice = vec![blocks::severity_section(
+                        "warning",
+                        &format!(
+                            "Approval request *{}* timed out after {} seconds",
+                            input.title, timeout_seconds
+                        ),
+                    )];
+                    let anchor = match channel_id {
+                        Some(ref ch) => request_cards::approval_anchor(&state, ch, &request_id).await,
+                        None => None,
                     };
-                    let _ = slack.enqueue(msg).await;
+                    if let Some(ref anchor) = anchor {
+                        // Block Kit cards offer a Revive button; thread
+                        // approvals stay text-only (US17).
+                        if is_threaded {
+                            anchor
+                                .resolve(
+                                    slack,
+                                    &format!(
+                                        "\u{23f1}\u{fe0f} *Timed out* \u{2014} {}",
+                                        input.title
+                                    ),
+                                )
+                                .await;
+                        } else {
+                            anchor
+                                .replace(
+                                    slack,
+                                    blocks::timed_out_approval(&input.title, &request_id),
+                                )
+                                .await;
+                        }
+                        anchor.follow_up(slack, text, Some(notice)).await;
+                    } else if let Some(ref ch) = channel_id {
+                        let msg = SlackMessage {
+                            channel: SlackChannelId(ch.clone()),
+                            text: Some(text),
+                            blocks: Some(notice),
+                            thread_ts: session_thread_ts.clone(),
+                        };
+                        super::util::post_notice(&state, &session.id, msg);
+                    }
                 }
 
                 ("timeout".to_owned(), None)
@@ -553,6 +488,22 @@ pub async fn handle(
             pending.remove(&request_id);
         }
 
+        // Rewrite fan-out copies that still show buttons.
+        let outcome = match status.as_str() {
+            "approved" => ApprovalStatus::Approved,
+            "rejected" => ApprovalStatus::Rejected,
+            _ => ApprovalStatus::Expired,
+        };
+        let mut status_line = format!(
+            "{} \u{2014} {}",
+            resolved::approval_outcome(outcome),
+            input.title
+        );
+        if let Some(ref reason) = reason {
+            let _ = write!(status_line, ": {reason}");
+        }
+        approval_fanout::resolve_copies(&state, &request_id, &status_line).await;
+
         // Update session last_tool.
         let _ = session_repo
             .update_last_activity(&session.id, Some("ask_approval".to_owned()))
@@ -572,6 +523,18 @@ pub async fn handle(
         if let Some(ref r) = reason {
             response_json["reason"] = serde_json::Value::String(r.clone());
         }
+        if status == "rejected" {
+            response_json["feedback"] =
+                serde_json::json!(RejectionFeedback::from_reason(reason.as_deref()));
+        }
+        if status == "approved" {
+            if let Ok(Some(record)) = approval_repo.get_by_id(&request_id).await {
+                if let Some(apply_after) = record.apply_after {
+                    response_json["apply_after"] =
+                        serde_json::Value::String(apply_after.to_rfc3339());
+                }
+            }
+        }
 
         Ok(CallToolResult::success(vec![rmcp::model::Content::json(
             response_json,
@@ -22,6 +22,7 @@ use uuid::Uuid;
 
 use crate::audit::{AuditEntry, AuditEventType};
 use crate::mcp::handler::IntercomServer;
+use crate::mcp::tools::util;
 use crate::persistence::session_repo::SessionRepo;
 use crate::policy::evaluator::{AutoApproveContext, PolicyEvaluator};
 use crate::policy::loader::PolicyLoader;
@@ -58,7 +59,7 @@ pub async fn handle(
     context: ToolCallContext<'_, IntercomServer>,
 ) -> Result<CallToolResult, rmcp::ErrorData> {
     let state = Arc::clone(context.service.state());
-    let channel_id = context.service.effective_channel_id().map(str::to_owned);
+    let channel_id = context.service.routed_channel_id();
     let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
 
     let input: CheckAutoApproveInput = serde_json::from_value(serde_json::Value::Object(args))
@@ -91,27 +92,14 @@ pub async fn handle(
         let workspace_root = std::path::PathBuf::from(&session.workspace_root);
 
         // ── Resolve workspace policy (cache-first, T052) ────
-        // 1. Try the shared PolicyCache for an O(1) hit.
-        let policy = {
-            let cache_guard = state.policy_cache.read().await;
-            cache_guard.get(&workspace_root).cloned()
-        };
-
-        let policy = if let Some(cached) = policy {
-            info!("policy cache hit — using pre-compiled policy");
-            cached
-        } else {
-            // 2. Cache miss: load from disk and back-fill the cache.
-            let loaded = PolicyLoader::load(&workspace_root).map_err(|err| {
+        let policy = PolicyLoader::load_cached(&state.policy_cache, &workspace_root)
+            .await
+            .map_err(|err| {
                 rmcp::ErrorData::internal_error(
                     format!("failed to load workspace policy: {err}"),
                     None,
                 )
             })?;
-            let mut cache_guard = state.policy_cache.write().await;
-            cache_guard.insert(workspace_root.clone(), loaded.clone());
-            loaded
-        };
 
         // ── Evaluate policy ──────────────────────────────────
         let result = PolicyEvaluator::check(&input.tool_name, &input.context, &policy);
@@ -130,7 +118,7 @@ pub async fn handle(
         // immediately with the policy result.
         if input.kind.as_deref() == Some("terminal_command") && !result.auto_approved {
             match (&state.slack, &channel_id) {
-                (Some(slack), Some(ch)) => {
+                (Some(_), Some(ch)) => {
                     let request_id = Uuid::new_v4().to_string();
                     let (tx, rx) = oneshot::channel::<ApprovalResponse>();
                     {
@@ -155,66 +143,54 @@ pub async fn handle(
                         thread_ts: None,
                     };
 
-                    if let Err(err) = slack.enqueue(slack_msg).await {
-                        warn!(
-                            %err,
-                            tool_name = %input.tool_name,
-                            "failed to post command approval to Slack"
-                        );
-                        state.pending_approvals.lock().await.remove(&request_id);
+                    util::post_notice(&state, &session.id, slack_msg);
+
+                    let timeout =
+                        Duration::from_secs(state.config.timeouts.approval_seconds);
+                    let approved = if let Some(Ok(resp)) =
+                        state.snoozes.wait(&request_id, timeout, rx).await
+                    {
+                        resp.status == "approved"
+                    } else {
+                        // Timeout or channel closed — clean up and deny.
+                        state
+                            .pending_approvals
+                            .lock()
+                            .await
+                            .remove(&request_id);
                         state
                             .pending_command_approvals
                             .lock()
                             .await
                             .remove(&request_id);
-                    } else {
-                        let timeout =
-                            Duration::from_secs(state.config.timeouts.approval_seconds);
-                        let approved = if let Ok(Ok(resp)) =
-                            tokio::time::timeout(timeout, rx).await
-                        {
-                            resp.status == "approved"
+                        false
+                    };
+                    let terminal_response = serde_json::json!({
+                        "auto_approved": approved,
+                        "matched_rule": if approved {
+                            serde_json::json!("operator:approved")
+                        } else {
+                            serde_json::Value::Null
+                        },
+                    });
+                    // Audit-log the operator decision (RI-06).
+                    if let Some(ref logger) = state.audit_logger {
+                        let event_type = if approved {
+                            AuditEventType::CommandApproval
                         } else {
-                            // Timeout or channel closed — clean up and deny.
-                            state
-                                .pending_approvals
-                                .lock()
-                                .await
-                                .remove(&request_id);
-                            state
-                                .pending_command_approvals
-                                .lock()
-                                .await
-                                .remove(&request_id);
-                            false
+                            AuditEventType::CommandRejection
                         };
-                        let terminal_response = serde_json::json!({
-                            "auto_approved": approved,
-                            "matched_rule": if approved {
-                                serde_json::json!("operator:approved")
-                            } else {
-                                serde_json::Value::Null
-                            },
-                        });
-                        // Audit-log the operator decision (RI-06).
-                        if let Some(ref logger) = state.audit_logger {
-                            let event_type = if approved {
-                                AuditEventType::CommandApproval
-                            } else {
-                                AuditEventType::CommandRejection
-                            };
-                            let entry = AuditEntry::new(event_type)
-                                .with_session(session.id.clone())
-                                .with_command(input.tool_name.clone())
-                                .with_request_id(request_id.clone());
-                            if let Err(err) = logger.log_entry(entry) {
-                                warn!(%err, "audit log write failed (terminal command gate)");
-                            }
+                        let entry = AuditEntry::new(event_type)
+                            .with_session(session.id.clone())
+                            .with_command(input.tool_name.clone())
+                            .with_request_id(request_id.clone());
+                        if let Err(err) = logger.log_entry(entry) {
+                            warn!(%err, "audit log write failed (terminal command gate)");
                         }
-                        return Ok(CallToolResult::success(vec![
-                            rmcp::model::Content::json(terminal_response)?,
-                        ]));
                     }
+                    return Ok(CallToolResult::success(vec![
+                        rmcp::model::Content::json(terminal_response)?,
+                    ]));
                 }
                 _ => {
                     info!(
@@ -223,7 +199,7 @@ pub async fn handle(
                     );
                 }
             }
-            // Slack unavailable or enqueue failed — audit and return deny.
+            // Slack unavailable — audit and return deny.
             if let Some(ref logger) = state.audit_logger {
                 let entry = AuditEntry::new(AuditEventType::CommandRejection)
                     .with_session(session.id.clone())
@@ -0,0 +1,114 @@
+//! `checkpoint` MCP tool handler.
+//!
+//! Lets an agent take a named checkpoint at a milestone of its own ("tests
+//! passing before refactor") through
+//! [`checkpoint_manager::create_checkpoint`], exactly as the operator's
+//! `session-checkpoint` command does, and posts a confirmation to the
+//! session's Slack thread. Returns immediately.
+
+use std::sync::Arc;
+
+use rmcp::handler::server::tool::ToolCallContext;
+use rmcp::model::CallToolResult;
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
+use tracing::{info, info_span, Instrument};
+
+use crate::mcp::handler::IntercomServer;
+use crate::mcp::tools::util;
+use crate::orchestrator::checkpoint_manager;
+use crate::persistence::checkpoint_repo::CheckpointRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::client::SlackMessage;
+
+/// Longest accepted label, in bytes.
+const MAX_LABEL_BYTES: usize = 200;
+
+/// Input parameters for `checkpoint`.
+#[derive(Debug, serde::Deserialize)]
+struct CheckpointInput {
+    /// Milestone the checkpoint marks.
+    label: String,
+}
+
+/// Handle the `checkpoint` tool call.
+///
+/// # Errors
+///
+/// Returns `rmcp::ErrorData` for invalid parameters, or when the
+/// checkpoint cannot be created.
+pub async fn handle(
+    context: ToolCallContext<'_, IntercomServer>,
+) -> Result<CallToolResult, rmcp::ErrorData> {
+    let state = Arc::clone(context.service.state());
+    let bound = context.service.bound_session_id().map(str::to_owned);
+    let channel_id = context.service.routed_channel_id();
+    let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
+
+    let input: CheckpointInput =
+        serde_json::from_value(serde_json::Value::Object(args)).map_err(|err| {
+            rmcp::ErrorData::invalid_params(format!("invalid checkpoint parameters: {err}"), None)
+        })?;
+    let label = input.label.trim().to_owned();
+    if label.is_empty() || label.len() > MAX_LABEL_BYTES {
+        return Err(rmcp::ErrorData::invalid_params(
+            format!("label must be 1 to {MAX_LABEL_BYTES} bytes"),
+            None,
+        ));
+    }
+
+    let span = info_span!("checkpoint", label = %label);
+
+    async move {
+        let session = util::bound_session(&state, bound.as_deref()).await?;
+        let session_repo = SessionRepo::new(Arc::clone(&state.db));
+        let checkpoint = checkpoint_manager::create_checkpoint(
+            &session.id,
+            Some(&label),
+            &session_repo,
+            &CheckpointRepo::new(Arc::clone(&state.db)),
+        )
+        .await
+        .map_err(|err| {
+            rmcp::ErrorData::internal_error(format!("failed to create checkpoint: {err}"), None)
+        })?;
+
+        if let Some(channel) = channel_id.as_deref() {
+            let msg = SlackMessage {
+                channel: SlackChannelId(channel.to_owned()),
+                text: Some(format!(
+                    "\u{1f4cc} Agent checkpoint `{}` (label: _{label}_). {} files hashed. \
+                     Compare with `session-restore {}`.",
+                    checkpoint.id,
+                    checkpoint.file_hashes.len(),
+                    checkpoint.id,
+                )),
+                blocks: None,
+                thread_ts: session.thread_ts.clone().map(SlackTs),
+            };
+            util::post_notice(&state, &session.id, msg);
+        }
+
+        let _ = session_repo
+            .update_last_activity(&session.id, Some("checkpoint".to_owned()))
+            .await;
+        info!(checkpoint_id = %checkpoint.id, "agent checkpoint created");
+
+        let body = serde_json::json!({
+            "status": "created",
+            "checkpoint_id": checkpoint.id,
+            "label": label,
+            "files_hashed": checkpoint.file_hashes.len(),
+        });
+        Ok(CallToolResult::success(vec![rmcp::model::Content::json(
+            &body,
+        )
+        .map_err(|err| {
+            rmcp::ErrorData::internal_error(
+                format!("failed to serialize checkpoint response: {err}"),
+                None,
+            )
+        })?]))
+    }
+    .instrument(span)
+    .await
+}
@@ -2,7 +2,10 @@
 //!
 //! Forwards an agent-generated continuation prompt to the remote operator
 //! via Slack with Continue/Refine/Stop buttons. Blocks the agent until
-//! the operator responds or the configured timeout elapses.
+//! the operator responds or the configured timeout elapses, unless a
+//! `[[prompts.auto]]` rule answers the prompt first. A stop carries
+//! structured [`RejectionFeedback`](crate::models::rejection::RejectionFeedback)
+//! read from the operator's instruction.
 
 use std::sync::Arc;
 use std::time::Duration;
@@ -13,12 +16,16 @@ use slack_morphism::prelude::SlackChannelId;
 use tokio::sync::oneshot;
 use tracing::{info, info_span, warn, Instrument};
 
+use crate::driver::AgentEvent;
 use crate::mcp::handler::IntercomServer;
 use crate::models::prompt::{ContinuationPrompt, PromptDecision, PromptType};
+use crate::models::rejection::RejectionFeedback;
+use crate::models::session::ProtocolMode;
+use crate::orchestrator::prompt_policy;
 use crate::persistence::prompt_repo::PromptRepo;
 use crate::persistence::session_repo::SessionRepo;
-use crate::slack::blocks;
 use crate::slack::client::SlackMessage;
+use crate::slack::{blocks, request_cards};
 use crate::state::PromptResponse;
 
 /// Input parameters for the `forward_prompt` tool per mcp-tools.json contract.
@@ -49,7 +56,7 @@ pub async fn handle(
     context: ToolCallContext<'_, IntercomServer>,
 ) -> Result<CallToolResult, rmcp::ErrorData> {
     let state = Arc::clone(context.service.state());
-    let channel_id = context.service.effective_channel_id().map(str::to_owned);
+    let channel_id = context.service.routed_channel_id();
     let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
 
     let input: ForwardPromptInput = serde_json::from_value(serde_json::Value::Object(args))
@@ -69,7 +76,8 @@ pub async fn handle(
         // ── Early Slack channel check (T067 / S040) ────────
         // Return a descriptive error instead of blocking indefinitely when
         // no Slack channel is configured for this session.
-        if state.slack.is_none() || channel_id.is_none() {
+        // A simulated operator answers without Slack.
+        if state.simulator.is_none() && (state.slack.is_none() || channel_id.is_none()) {
             let (error_code, error_message) = if state.slack.is_none() {
                 (
                     "slack_unavailable",
@@ -145,68 +153,35 @@ pub async fn handle(
             )
         })?;
 
+        // ── Auto-policy ──────────────────────────────────────
+        // Decided before publishing, so the Slack request subscriber sees an
+        // answered prompt and posts no card for it.
+        let auto = prompt_policy::auto_decide(&state, &session, &created).await;
+        state.event_bus.publish(
+            ProtocolMode::Mcp,
+            channel_id.clone(),
+            AgentEvent::PromptForwarded {
+                session_id: session.id.clone(),
+                prompt_id: prompt_id.clone(),
+                prompt_text: input.prompt_text.clone(),
+                prompt_type: input.prompt_type.as_str().to_owned(),
+            },
+        );
+        if let Some((decision, instruction)) = auto {
+            let _ = session_repo
+                .update_last_activity(&session.id, Some("forward_prompt".to_owned()))
+                .await;
+            return decision_result(decision.as_str(), instruction);
+        }
+
         // ── Post to Slack ────────────────────────────────────
-        // US17: when the session lives in a thread, post plain text (no
-        // block-kit buttons) and use the @-mention thread-reply mechanism
-        // for operator decisions.  Main channel messages keep block-kit.
+        // The Slack request subscriber posts the card (text-only inside a
+        // session thread, US17) and records its anchor on the prompt.
         let is_threaded = session_thread_ts.is_some();
-
-        if let (Some(ref slack), Some(ref ch)) = (&state.slack, &channel_id) {
-            let channel = SlackChannelId(ch.clone());
-
-            if is_threaded {
-                // US17: text-only thread prompt — no blocks.
-                let text_body = blocks::build_text_only_prompt(
-                    &input.prompt_text,
-                    input.prompt_type,
-                    input.elapsed_seconds,
-                    input.actions_taken,
-                );
-                let msg = SlackMessage {
-                    channel,
-                    text: Some(text_body),
-                    blocks: None,
-                    thread_ts: session_thread_ts.clone(),
-                };
-                if let Err(err) = slack.enqueue(msg).await {
-                    warn!(%err, "failed to enqueue text-only prompt message");
-                }
-            } else {
-                // Main channel: block-kit with buttons (unchanged).
-                let message_blocks = blocks::build_prompt_blocks(
-                    &input.prompt_text,
-                    input.prompt_type,
-                    input.elapsed_seconds,
-                    input.actions_taken,
-                    &prompt_id,
-                );
-
-                let post_span = info_span!("slack_post_prompt", prompt_id = %prompt_id);
-                async {
-                    let msg = SlackMessage {
-                        channel,
-                        text: Some(format!(
-                            "\u{1f4ac} {} Prompt: {}",
-                            blocks::prompt_type_label(input.prompt_type),
-                            blocks::truncate_text(&input.prompt_text, 100),
-                        )),
-                        blocks: Some(message_blocks),
-                        thread_ts: None,
-                    };
-                    if let Err(err) = slack.enqueue(msg).await {
-                        warn!(%err, "failed to enqueue prompt message");
-                    }
-                }
-                .instrument(post_span)
-                .await;
-            }
-        } else {
+        if state.slack.is_none() {
             warn!("slack not configured; prompt will block without notification");
         }
 
-        // Suppress unused variable warning.
-        let _ = &created;
-
         // ── Register oneshot and wait ────────────────────────
         let (tx, rx) = oneshot::channel::<PromptResponse>();
         {
@@ -256,8 +231,10 @@ pub async fn handle(
                                 } else {
                                     Some(decision.instruction)
                                 };
+                                let status_line =
+                                    format!("\u{1f4ac} Prompt answered: *{}*", decision.keyword);
                                 if let Err(err) = state_fb
-                                    .driver
+                                    .mcp_driver()
                                     .resolve_prompt(&pid, &decision.keyword, inst)
                                     .await
                                 {
@@ -266,6 +243,13 @@ pub async fn handle(
                                         %err,
                                         "US17: failed to resolve prompt from thread reply"
                                     );
+                                } else if let Some(slack) = state_fb.slack.as_ref() {
+                                    if let Some(anchor) =
+                                        request_cards::prompt_anchor(&state_fb, &fallback_ch, &pid)
+                                            .await
+                                    {
+                                        anchor.resolve(slack, &status_line).await;
+                                    }
                                 }
                             }
                             Ok(Err(_)) => {
@@ -290,16 +274,16 @@ pub async fn handle(
         let timeout_seconds = state.config.timeouts.prompt_seconds;
         let timeout_duration = Duration::from_secs(timeout_seconds);
 
-        let response = tokio::time::timeout(timeout_duration, rx).await;
+        let response = state.snoozes.wait(&prompt_id, timeout_duration, rx).await;
 
         let (decision, instruction) = match response {
-            Ok(Ok(resp)) => (resp.decision, resp.instruction),
-            Ok(Err(_)) => {
+            Some(Ok(resp)) => (resp.decision, resp.instruction),
+            Some(Err(_)) => {
                 // Sender dropped without sending (e.g., server shutdown).
                 // Default to "continue" per FR-008.
                 ("continue".to_owned(), None)
             }
-            Err(_elapsed) => {
+            None => {
                 // Timeout expired — auto-respond with "continue" per FR-008.
                 info!(
                     prompt_id = %prompt_id,
@@ -307,24 +291,42 @@ pub async fn handle(
                     "continuation prompt timed out; auto-continuing"
                 );
                 let _ = prompt_repo
-                    .update_decision(&prompt_id, PromptDecision::Continue, None)
+                    .decide(&prompt_id, PromptDecision::Continue, None)
                     .await;
 
-                if let (Some(ref slack), Some(ref ch)) = (&state.slack, &channel_id) {
-                    let channel = SlackChannelId(ch.clone());
-                    let msg = SlackMessage {
-                        channel,
-                        text: Some(format!(
-                            "\u{23f1}\u{fe0f} Prompt '{}' timed out \u{2014} auto-continuing",
-                            blocks::truncate_text(&input.prompt_text, 60),
-                        )),
-                        blocks: Some(vec![blocks::severity_section(
-                            "warning",
-                            &format!("Prompt timed out after {timeout_seconds}s — auto-continuing"),
-                        )]),
-                        thread_ts: session_thread_ts.clone(),
+                if let Some(ref slack) = state.slack {
+                    let text = format!(
+                        "\u{23f1}\u{fe0f} Prompt '{}' timed out \u{2014} auto-continuing",
+                        blocks::truncate_text(&input.prompt_text, 60),
+                    );
+                    let notice = vec![blocks::severity_section(
+                        "warning",
+                        &format!("Prompt timed out after {timeout_seconds}s — auto-continuing"),
+                    )];
+                    let anchor = match channel_id {
+                        Some(ref ch) => request_cards::prompt_anchor(&state, ch, &prompt_id).await,
+                        None => None,
                     };
-                    let _ = slack.enqueue(msg).await;
+                    if let Some(ref anchor) = anchor {
+                        anchor
+                            .resolve(
+                                slack,
+                                &format!(
+                                    "\u{23f1}\u{fe0f} *Timed out* \u{2014} auto-continued: {}",
+                                    blocks::truncate_text(&input.prompt_text, 60),
+                                ),
+                            )
+                            .await;
+                        anchor.follow_up(slack, text, Some(notice)).await;
+                    } else if let Some(ref ch) = channel_id {
+                        let msg = SlackMessage {
+                            channel: SlackChannelId(ch.clone()),
+                            text: Some(text),
+                            blocks: Some(notice),
+                            thread_ts: session_thread_ts.clone(),
+                        };
+                        super::util::post_notice(&state, &session.id, msg);
+                    }
                 }
 
                 ("continue".to_owned(), None)
@@ -348,24 +350,36 @@ pub async fn handle(
             "forward_prompt resolved"
         );
 
-        // ── Build response ───────────────────────────────────
-        let mut response_json = serde_json::json!({
-            "decision": decision,
-        });
-        if let Some(ref inst) = instruction {
-            response_json["instruction"] = serde_json::Value::String(inst.clone());
-        }
-
-        Ok(CallToolResult::success(vec![rmcp::model::Content::json(
-            response_json,
-        )
-        .map_err(|err| {
-            rmcp::ErrorData::internal_error(
-                format!("failed to serialize forward_prompt response: {err}"),
-                None,
-            )
-        })?]))
+        decision_result(&decision, instruction)
     }
     .instrument(span)
     .await
 }
+
+/// Build the tool result carrying the operator's (or an auto-policy's)
+/// decision.
+fn decision_result(
+    decision: &str,
+    instruction: Option<String>,
+) -> Result<CallToolResult, rmcp::ErrorData> {
+    let mut response_json = serde_json::json!({
+        "decision": decision,
+    });
+    if decision == "stop" {
+        response_json["feedback"] =
+            serde_json::json!(RejectionFeedback::from_reason(instruction.as_deref()));
+    }
+    if let Some(inst) = instruction {
+        response_json["instruction"] = serde_json::Value::String(inst);
+    }
+
+    Ok(CallToolResult::success(vec![rmcp::model::Content::json(
+        response_json,
+    )
+    .map_err(|err| {
+        rmcp::ErrorData::internal_error(
+            format!("failed to serialize forward_prompt response: {err}"),
+            None,
+        )
+    })?]))
+}
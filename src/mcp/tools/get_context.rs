@@ -0,0 +1,87 @@
+//! `get_context` MCP tool handler.
+//!
+//! Reads the notes the calling session stored with `store_context`: one
+//! key, or all of them ordered by key. Returns immediately.
+
+use std::sync::Arc;
+
+use rmcp::handler::server::tool::ToolCallContext;
+use rmcp::model::CallToolResult;
+use tracing::{debug, info_span, Instrument};
+
+use crate::mcp::handler::IntercomServer;
+use crate::mcp::tools::util;
+use crate::models::session_context::ContextEntry;
+use crate::persistence::context_repo::ContextRepo;
+
+/// Input parameters for `get_context`.
+#[derive(Debug, serde::Deserialize)]
+struct GetContextInput {
+    /// Only return this key.
+    key: Option<String>,
+}
+
+/// Render entries as the `entries` array shared with `reboot`.
+#[must_use]
+pub fn entries_json(entries: &[ContextEntry]) -> serde_json::Value {
+    entries
+        .iter()
+        .map(|entry| {
+            serde_json::json!({
+                "key": entry.key,
+                "value": entry.value,
+                "updated_at": entry.updated_at.to_rfc3339(),
+            })
+        })
+        .collect()
+}
+
+/// Handle the `get_context` tool call.
+///
+/// # Errors
+///
+/// Returns `rmcp::ErrorData` for invalid parameters or persistence
+/// failures.
+pub async fn handle(
+    context: ToolCallContext<'_, IntercomServer>,
+) -> Result<CallToolResult, rmcp::ErrorData> {
+    let state = Arc::clone(context.service.state());
+    let bound = context.service.bound_session_id().map(str::to_owned);
+    let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
+
+    let input: GetContextInput =
+        serde_json::from_value(serde_json::Value::Object(args)).map_err(|err| {
+            rmcp::ErrorData::invalid_params(format!("invalid get_context parameters: {err}"), None)
+        })?;
+
+    let span = info_span!("get_context", key = ?input.key);
+
+    async move {
+        let session = util::bound_session(&state, bound.as_deref()).await?;
+        let repo = ContextRepo::new(Arc::clone(&state.db));
+        let entries = match input.key.as_deref().map(str::trim) {
+            Some(key) => repo
+                .get(&session.id, key)
+                .await
+                .map(|entry| entry.into_iter().collect()),
+            None => repo.list_for_session(&session.id).await,
+        }
+        .map_err(|err| {
+            rmcp::ErrorData::internal_error(format!("failed to read context: {err}"), None)
+        })?;
+        debug!(session_id = %session.id, count = entries.len(), "session context read");
+
+        let response = serde_json::json!({ "entries": entries_json(&entries) });
+        Ok(CallToolResult::success(vec![rmcp::model::Content::json(
+            response,
+        )
+        .map_err(|err| {
+            rmcp::ErrorData::internal_error(
+                format!("failed to serialize get_context response: {err}"),
+                None,
+            )
+        })?]))
+    }
+    .instrument(span)
+    .await
+}
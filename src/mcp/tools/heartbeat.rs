@@ -1,20 +1,24 @@
 //! `heartbeat` MCP tool handler (T049).
 //!
-//! Lightweight liveness signal that resets the stall detection timer
-//! and optionally stores a structured progress snapshot on the session.
+//! Lightweight liveness signal that resets the stall detection timer,
+//! marks the session online, and optionally stores a structured progress
+//! snapshot on the session.
 
 use std::sync::Arc;
 
+use chrono::Utc;
 use rmcp::handler::server::tool::ToolCallContext;
 use rmcp::model::CallToolResult;
-use tracing::{info, info_span, Instrument};
+use tracing::{info, info_span, warn, Instrument};
 
+use crate::driver::AgentEvent;
 use crate::mcp::handler::IntercomServer;
-use crate::models::progress::{validate_snapshot, ProgressItem};
-use crate::models::session::Session;
+use crate::models::progress::{all_done, validate_snapshot, ProgressItem};
+use crate::models::session::{ConnectivityStatus, ProtocolMode, Session};
+use crate::orchestrator::heartbeat_enforcer;
 use crate::persistence::session_repo::SessionRepo;
 use crate::persistence::steering_repo::SteeringRepo;
-use crate::slack::client::SlackMessage;
+use crate::state::AppState;
 
 /// Input parameters per mcp-tools.json contract.
 #[derive(Debug, serde::Deserialize)]
@@ -45,7 +49,7 @@ pub async fn handle(
     context: ToolCallContext<'_, IntercomServer>,
 ) -> Result<CallToolResult, rmcp::ErrorData> {
     let state = Arc::clone(context.service.state());
-    let channel_id = context.service.effective_channel_id().map(str::to_owned);
+    let channel_id = context.service.routed_channel_id();
     let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
 
     let input: HeartbeatInput =
@@ -82,16 +86,47 @@ pub async fn handle(
         if let Some(ref detectors) = state.stall_detectors {
             let guards = detectors.lock().await;
             if let Some(detector_handle) = guards.get(&session.id) {
+                if let Some(ref snapshot) = input.progress_snapshot {
+                    detector_handle.set_work_complete(all_done(snapshot));
+                }
                 detector_handle.reset();
                 info!(session_id = %session.id, "stall timer reset by heartbeat");
             }
         }
 
-        // ── Optional: log status_message to Slack ────────────
+        // ── Connectivity ─────────────────────────────────────
+        mark_online(&state, &session_repo, &session).await;
+
+        // ── Heartbeat-required mode ──────────────────────────
+        // Only a ping that says what the agent is doing counts.
+        if input
+            .status_message
+            .as_deref()
+            .is_some_and(|msg| !msg.trim().is_empty())
+        {
+            heartbeat_enforcer::record_heartbeat(&state, &session).await;
+        }
+
+        // ── Publish to the agent event bus ───────────────────
+        // The Slack status subscriber forwards `status_message` to the
+        // connection's channel; audit and metrics subscribers observe both.
+        state.event_bus.publish(
+            ProtocolMode::Mcp,
+            channel_id.clone(),
+            AgentEvent::HeartbeatReceived {
+                session_id: session.id.clone(),
+                progress: input.progress_snapshot.clone(),
+            },
+        );
         if let Some(ref msg) = input.status_message {
-            if let Some(ref ch) = channel_id {
-                send_heartbeat_to_slack(&state, ch, msg).await;
-            }
+            state.event_bus.publish(
+                ProtocolMode::Mcp,
+                channel_id.clone(),
+                AgentEvent::StatusUpdated {
+                    session_id: session.id.clone(),
+                    message: msg.clone(),
+                },
+            );
         }
 
         // ── Fetch and deliver pending steering messages ──────
@@ -106,12 +141,15 @@ pub async fn handle(
         );
 
         // ── Build response ───────────────────────────────────
-        let response = serde_json::json!({
+        let mut response = serde_json::json!({
             "acknowledged": true,
             "session_id": session.id,
             "stall_detection_enabled": stall_enabled,
             "pending_steering": steering_texts,
         });
+        if let Some(deadline) = heartbeat_enforcer::deadline(&state) {
+            response["heartbeat_deadline_seconds"] = deadline.as_secs().into();
+        }
 
         Ok(CallToolResult::success(vec![rmcp::model::Content::json(
             response,
@@ -158,6 +196,32 @@ async fn update_session_progress(
         })
 }
 
+/// Mark `session` online: a ping proves the agent is reachable.
+///
+/// A session held stalled for a missed mandatory heartbeat is left to
+/// [`heartbeat_enforcer::record_heartbeat`], which marks it online only once
+/// a ping with a status lifts the block.
+async fn mark_online(state: &AppState, session_repo: &SessionRepo, session: &Session) {
+    if session.connectivity_status == ConnectivityStatus::Online {
+        return;
+    }
+    let blocked = heartbeat_enforcer::deadline(state).is_some_and(|deadline| {
+        state
+            .heartbeats
+            .is_overdue(&session.id, Utc::now(), deadline)
+    });
+    if blocked {
+        return;
+    }
+    match session_repo
+        .set_connectivity_status(&session.id, ConnectivityStatus::Online)
+        .await
+    {
+        Ok(()) => info!(session_id = %session.id, "session back online after ping"),
+        Err(err) => warn!(%err, session_id = %session.id, "failed to mark session online"),
+    }
+}
+
 /// Fetch all unconsumed steering messages for the session and mark them consumed.
 ///
 /// Returns a `Vec<String>` of message texts in insertion order.
@@ -179,19 +243,3 @@ async fn fetch_and_consume_steering(
     }
     Ok(texts)
 }
-
-/// Forward a heartbeat status message to the Slack channel, if configured.
-async fn send_heartbeat_to_slack(state: &crate::state::AppState, channel_id: &str, msg: &str) {
-    if let Some(ref slack) = state.slack {
-        let channel = slack_morphism::prelude::SlackChannelId(channel_id.to_owned());
-        let slack_msg = SlackMessage {
-            channel,
-            text: Some(format!("\u{1f493} {msg}")),
-            blocks: Some(vec![crate::slack::blocks::severity_section("info", msg)]),
-            thread_ts: None,
-        };
-        if let Err(err) = slack.enqueue(slack_msg).await {
-            tracing::warn!(%err, "failed to enqueue heartbeat status to slack");
-        }
-    }
-}
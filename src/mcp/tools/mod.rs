@@ -1,12 +1,23 @@
 //! MCP tool handlers.
 
 pub mod accept_diff;
+pub mod acquire_lock;
+pub mod approval_stats;
 pub mod ask_approval;
 pub mod check_auto_approve;
+pub mod checkpoint;
 pub mod forward_prompt;
+pub mod get_context;
 pub mod heartbeat;
+pub mod propose_knowledge;
 pub mod recover_state;
+pub mod relay_receive;
+pub mod relay_send;
+pub mod release_lock;
 pub mod remote_log;
 pub mod set_operational_mode;
+pub mod spawn_subtask;
+pub mod store_context;
+pub mod stream_log;
 pub mod util;
 pub mod wait_for_instruction;
@@ -0,0 +1,187 @@
+//! `propose_knowledge` MCP tool handler.
+//!
+//! Lets an agent propose a note for the shared knowledge base ("how to
+//! run the e2e suite"). The note is stored as pending and posted to the
+//! operator in the session's Slack thread; once approved it is served to
+//! every future session through the `intercom://knowledge` resource.
+//! Returns immediately without waiting for the decision.
+
+use std::sync::Arc;
+
+use rmcp::handler::server::tool::ToolCallContext;
+use rmcp::model::CallToolResult;
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
+use tracing::{info, info_span, warn, Instrument};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::mcp::handler::IntercomServer;
+use crate::mcp::tools::util;
+use crate::models::knowledge::KnowledgeNote;
+use crate::persistence::knowledge_repo::KnowledgeRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::blocks;
+use crate::slack::client::SlackMessage;
+
+/// Longest accepted title, in bytes.
+const MAX_TITLE_BYTES: usize = 200;
+
+/// Largest accepted body, in bytes.
+const MAX_BODY_BYTES: usize = 8192;
+
+/// Most tags a note may carry.
+const MAX_TAGS: usize = 10;
+
+/// Longest accepted tag, in bytes.
+const MAX_TAG_BYTES: usize = 32;
+
+/// Input parameters for `propose_knowledge`.
+#[derive(Debug, serde::Deserialize)]
+struct ProposeKnowledgeInput {
+    /// Short summary of the note.
+    title: String,
+    /// Note body (Markdown).
+    body: String,
+    /// Optional labels.
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// Handle the `propose_knowledge` tool call.
+///
+/// # Errors
+///
+/// Returns `rmcp::ErrorData` for invalid parameters or persistence
+/// failures. Refusals are reported in the result's `status`.
+pub async fn handle(
+    context: ToolCallContext<'_, IntercomServer>,
+) -> Result<CallToolResult, rmcp::ErrorData> {
+    let state = Arc::clone(context.service.state());
+    let bound = context.service.bound_session_id().map(str::to_owned);
+    let channel_id = context.service.routed_channel_id();
+    let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
+
+    let input: ProposeKnowledgeInput = serde_json::from_value(serde_json::Value::Object(args))
+        .map_err(|err| {
+            rmcp::ErrorData::invalid_params(
+                format!("invalid propose_knowledge parameters: {err}"),
+                None,
+            )
+        })?;
+    let (title, body, tags) = validate(&input)?;
+
+    let span = info_span!("propose_knowledge", title = %title);
+
+    async move {
+        if !state.config.knowledge.enabled {
+            return respond(&serde_json::json!({
+                "status": "error",
+                "error_code": "knowledge_disabled",
+                "error_message": "the knowledge base is disabled on this server",
+            }));
+        }
+        let Some(channel) = channel_id.as_deref().filter(|_| state.slack.is_some()) else {
+            return respond(&serde_json::json!({
+                "status": "error",
+                "error_code": "slack_unavailable",
+                "error_message": "propose_knowledge requires operator approval, but Slack is \
+                                  not configured for this session",
+            }));
+        };
+
+        let session = util::bound_session(&state, bound.as_deref()).await?;
+        let note = KnowledgeNote::new(
+            title,
+            body,
+            tags,
+            session.id.clone(),
+            session.workspace_root.clone(),
+        );
+        KnowledgeRepo::new(Arc::clone(&state.db))
+            .insert(&note)
+            .await
+            .map_err(|err| {
+                rmcp::ErrorData::internal_error(
+                    format!("failed to store knowledge note: {err}"),
+                    None,
+                )
+            })?;
+
+        let msg = SlackMessage {
+            channel: SlackChannelId(channel.to_owned()),
+            text: Some(format!("\u{1f4da} Knowledge note proposed: {}", note.title)),
+            blocks: Some(blocks::knowledge_proposal_blocks(
+                &note.id,
+                &note.title,
+                &note.body,
+                &note.tags,
+            )),
+            thread_ts: session.thread_ts.clone().map(SlackTs),
+        };
+        util::post_notice(&state, &session.id, msg);
+
+        if let Some(ref logger) = state.audit_logger {
+            let entry = AuditEntry::new(AuditEventType::KnowledgeProposed)
+                .with_session(session.id.clone())
+                .with_request_id(note.id.clone())
+                .with_result(note.title.clone());
+            if let Err(err) = logger.log_entry(entry) {
+                warn!(%err, "audit log write failed (knowledge proposed)");
+            }
+        }
+
+        let _ = SessionRepo::new(Arc::clone(&state.db))
+            .update_last_activity(&session.id, Some("propose_knowledge".to_owned()))
+            .await;
+        info!(note_id = %note.id, "knowledge note proposed");
+
+        respond(&serde_json::json!({ "status": "proposed", "note_id": note.id }))
+    }
+    .instrument(span)
+    .await
+}
+
+/// Trim and bound-check the proposal, returning `(title, body, tags)`.
+fn validate(
+    input: &ProposeKnowledgeInput,
+) -> Result<(String, String, Vec<String>), rmcp::ErrorData> {
+    let title = input.title.trim();
+    if title.is_empty() || title.len() > MAX_TITLE_BYTES {
+        return Err(rmcp::ErrorData::invalid_params(
+            format!("title must be 1 to {MAX_TITLE_BYTES} bytes"),
+            None,
+        ));
+    }
+    let body = input.body.trim();
+    if body.is_empty() || body.len() > MAX_BODY_BYTES {
+        return Err(rmcp::ErrorData::invalid_params(
+            format!("body must be 1 to {MAX_BODY_BYTES} bytes"),
+            None,
+        ));
+    }
+    let tags: Vec<String> = input
+        .tags
+        .iter()
+        .map(|tag| tag.trim().to_owned())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    if tags.len() > MAX_TAGS || tags.iter().any(|tag| tag.len() > MAX_TAG_BYTES) {
+        return Err(rmcp::ErrorData::invalid_params(
+            format!("at most {MAX_TAGS} tags of up to {MAX_TAG_BYTES} bytes each"),
+            None,
+        ));
+    }
+    Ok((title.to_owned(), body.to_owned(), tags))
+}
+
+/// Wrap a JSON body in a successful tool result.
+fn respond(body: &serde_json::Value) -> Result<CallToolResult, rmcp::ErrorData> {
+    Ok(CallToolResult::success(vec![rmcp::model::Content::json(
+        body,
+    )
+    .map_err(|err| {
+        rmcp::ErrorData::internal_error(
+            format!("failed to serialize propose_knowledge response: {err}"),
+            None,
+        )
+    })?]))
+}
@@ -2,19 +2,27 @@
 //!
 //! Retrieves the last known state from persistent storage. Called by the
 //! agent on startup to check for interrupted sessions or pending requests.
+//! With `wait`, it also blocks on a request re-armed after a server restart
+//! (`[recovery] rearm_pending`) and returns its decision.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use rmcp::handler::server::tool::ToolCallContext;
 use rmcp::model::CallToolResult;
 use tracing::{info, info_span, Instrument};
 
 use crate::mcp::handler::IntercomServer;
+use crate::models::approval::ApprovalStatus;
+use crate::models::prompt::PromptDecision;
 use crate::models::session::Session;
+use crate::orchestrator::rearm::Rearmed;
 use crate::persistence::approval_repo::ApprovalRepo;
 use crate::persistence::checkpoint_repo::CheckpointRepo;
+use crate::persistence::context_repo::ContextRepo;
 use crate::persistence::inbox_repo::InboxRepo;
 use crate::persistence::prompt_repo::PromptRepo;
+use crate::persistence::session_note_repo::SessionNoteRepo;
 use crate::persistence::session_repo::SessionRepo;
 use crate::state::AppState;
 
@@ -24,6 +32,9 @@ struct RecoverStateInput {
     /// Optional session to recover. When omitted, finds the most recently
     /// active/interrupted session.
     session_id: Option<String>,
+    /// Block until the session's re-armed request, if any, is decided.
+    #[serde(default)]
+    wait: bool,
 }
 
 /// Handle the `recover_state` tool call.
@@ -39,7 +50,7 @@ pub async fn handle(
     // Derive the channel from the per-connection context (query param override
     // or global config fallback) rather than reading the global config directly.
     // This ensures inbox tasks are scoped to the correct workspace channel (C14).
-    let channel_id = context.service.effective_channel_id().map(str::to_owned);
+    let channel_id = context.service.routed_channel_id();
     let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
 
     let input: RecoverStateInput = serde_json::from_value(serde_json::Value::Object(args))
@@ -70,7 +81,15 @@ pub async fn handle(
         };
 
         // ── Collect pending data and build response ──────────
-        let response = build_recovered_response(&state, &session, channel_id.as_deref()).await?;
+        let mut response =
+            build_recovered_response(&state, &session, channel_id.as_deref()).await?;
+
+        // ── Wait for a re-armed request ──────────────────────
+        if input.wait {
+            if let Some(decision) = await_rearmed(&state, &session.id).await {
+                response["decision"] = decision;
+            }
+        }
 
         let pending_count = response
             .get("pending_requests")
@@ -108,8 +127,8 @@ async fn resolve_session(
     }
 }
 
-/// Collect pending approvals, prompts, checkpoints, and progress snapshot
-/// into the `recovered` response JSON.
+/// Collect pending approvals, prompts, checkpoints, stored context, operator
+/// notes and progress snapshot into the `recovered` response JSON.
 async fn build_recovered_response(
     state: &AppState,
     session: &Session,
@@ -141,20 +160,28 @@ async fn build_recovered_response(
     // ── Build pending_requests array ─────────────────────
     let mut pending_requests = Vec::new();
     if let Some(ref approval) = pending_approval {
-        pending_requests.push(serde_json::json!({
+        let mut entry = serde_json::json!({
             "request_id": approval.id,
             "type": "approval",
             "title": approval.title,
             "created_at": approval.created_at.to_rfc3339(),
-        }));
+        });
+        if state.rearmed.contains(&approval.id) {
+            entry["rearmed"] = serde_json::json!(true);
+        }
+        pending_requests.push(entry);
     }
     if let Some(ref prompt) = pending_prompt {
-        pending_requests.push(serde_json::json!({
+        let mut entry = serde_json::json!({
             "request_id": prompt.id,
             "type": "prompt",
             "title": prompt.prompt_text,
             "created_at": prompt.created_at.to_rfc3339(),
-        }));
+        });
+        if state.rearmed.contains(&prompt.id) {
+            entry["rearmed"] = serde_json::json!(true);
+        }
+        pending_requests.push(entry);
     }
 
     // ── Last checkpoint ──────────────────────────────────
@@ -172,6 +199,14 @@ async fn build_recovered_response(
         })
     });
 
+    // ── Stored context ───────────────────────────────────
+    let context = ContextRepo::new(Arc::clone(&state.db))
+        .list_for_session(&session.id)
+        .await
+        .map_err(|err| {
+            rmcp::ErrorData::internal_error(format!("failed to query session context: {err}"), None)
+        })?;
+
     // ── Progress snapshot ────────────────────────────────
     let progress_snapshot = session
         .progress_snapshot
@@ -193,6 +228,12 @@ async fn build_recovered_response(
     if let Some(snap) = progress_snapshot {
         response["progress_snapshot"] = snap;
     }
+    if !context.is_empty() {
+        response["context"] = super::get_context::entries_json(&context);
+    }
+    if let Some(notes) = fetch_notes(state, &session.id).await? {
+        response["notes"] = notes;
+    }
 
     // ── Pending inbox tasks ──────────────────────────────
     let pending_tasks = fetch_inbox_tasks(state, channel_id).await?;
@@ -201,6 +242,88 @@ async fn build_recovered_response(
     Ok(response)
 }
 
+/// Operator notes on `session_id`, oldest first, or `None` when there are
+/// none.
+async fn fetch_notes(
+    state: &AppState,
+    session_id: &str,
+) -> Result<Option<serde_json::Value>, rmcp::ErrorData> {
+    let notes = SessionNoteRepo::new(Arc::clone(&state.db))
+        .list_for_session(session_id)
+        .await
+        .map_err(|err| {
+            rmcp::ErrorData::internal_error(format!("failed to query session notes: {err}"), None)
+        })?;
+    if notes.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(
+        notes
+            .iter()
+            .map(|note| {
+                serde_json::json!({
+                    "author": note.author,
+                    "text": note.text,
+                    "created_at": note.created_at.to_rfc3339(),
+                })
+            })
+            .collect(),
+    ))
+}
+
+/// Wait for the decision on the session's re-armed request, if it has one.
+///
+/// Uses the normal approval or prompt timeout. A timed-out approval is
+/// marked `Expired` and a timed-out prompt auto-continues, as when the
+/// original tool call timed out.
+async fn await_rearmed(state: &AppState, session_id: &str) -> Option<serde_json::Value> {
+    let (id, rearmed) = state.rearmed.take_for_session(session_id)?;
+    info!(request_id = %id, "waiting for re-armed request");
+    let decision = match rearmed {
+        Rearmed::Approval(rx) => {
+            let timeout = Duration::from_secs(state.config.timeouts.approval_seconds);
+            let (status, reason) = match state.snoozes.wait(&id, timeout, rx).await {
+                Some(Ok(resp)) => (resp.status, resp.reason),
+                Some(Err(_)) => ("timeout".to_owned(), None),
+                None => {
+                    state.pending_approvals.lock().await.remove(&id);
+                    let _ = ApprovalRepo::new(Arc::clone(&state.db))
+                        .decide(&id, ApprovalStatus::Expired)
+                        .await;
+                    ("timeout".to_owned(), None)
+                }
+            };
+            serde_json::json!({
+                "request_id": id,
+                "type": "approval",
+                "status": status,
+                "reason": reason,
+            })
+        }
+        Rearmed::Prompt(rx) => {
+            let timeout = Duration::from_secs(state.config.timeouts.prompt_seconds);
+            let (decision, instruction) = match state.snoozes.wait(&id, timeout, rx).await {
+                Some(Ok(resp)) => (resp.decision, resp.instruction),
+                Some(Err(_)) => ("continue".to_owned(), None),
+                None => {
+                    state.pending_prompts.lock().await.remove(&id);
+                    let _ = PromptRepo::new(Arc::clone(&state.db))
+                        .decide(&id, PromptDecision::Continue, None)
+                        .await;
+                    ("continue".to_owned(), None)
+                }
+            };
+            serde_json::json!({
+                "request_id": id,
+                "type": "prompt",
+                "decision": decision,
+                "instruction": instruction,
+            })
+        }
+    };
+    Some(decision)
+}
+
 /// Fetch unconsumed inbox tasks for the given channel and mark them consumed
 /// in a batch after building the response. Returns a JSON-ready array of task
 /// objects.
@@ -0,0 +1,139 @@
+//! `relay_receive` MCP tool handler.
+//!
+//! Collects the relay messages other sessions sent to the calling session
+//! with `relay_send`, oldest first. Each message is delivered exactly once.
+//! With `timeout_seconds` greater than zero the call waits for at least one
+//! message to arrive, up to an hour; otherwise it returns immediately.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use rmcp::handler::server::tool::ToolCallContext;
+use rmcp::model::CallToolResult;
+use tracing::{info, info_span, warn, Instrument};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::mcp::handler::IntercomServer;
+use crate::mcp::tools::util;
+use crate::models::relay::RelayMessage;
+use crate::persistence::relay_repo::RelayRepo;
+use crate::persistence::session_repo::SessionRepo;
+
+/// Longest accepted `timeout_seconds`.
+const MAX_TIMEOUT_SECONDS: u64 = 3600;
+
+/// How often a waiting `relay_receive` checks for new messages.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Input parameters for `relay_receive`.
+#[derive(Debug, serde::Deserialize)]
+struct RelayReceiveInput {
+    /// Only collect messages with this topic.
+    topic: Option<String>,
+    /// Seconds to wait for a message when none is pending (default: 0).
+    #[serde(default)]
+    timeout_seconds: u64,
+}
+
+/// Handle the `relay_receive` tool call.
+///
+/// # Errors
+///
+/// Returns `rmcp::ErrorData` for invalid parameters or infrastructure
+/// failures.
+pub async fn handle(
+    context: ToolCallContext<'_, IntercomServer>,
+) -> Result<CallToolResult, rmcp::ErrorData> {
+    let state = Arc::clone(context.service.state());
+    let bound = context.service.bound_session_id().map(str::to_owned);
+    let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
+
+    let input: RelayReceiveInput = serde_json::from_value(serde_json::Value::Object(args))
+        .map_err(|err| {
+            rmcp::ErrorData::invalid_params(
+                format!("invalid relay_receive parameters: {err}"),
+                None,
+            )
+        })?;
+    let timeout_seconds = input.timeout_seconds.min(MAX_TIMEOUT_SECONDS);
+
+    let span = info_span!("relay_receive", topic = ?input.topic, timeout_seconds);
+
+    async move {
+        let session = util::bound_session(&state, bound.as_deref()).await?;
+        let repo = RelayRepo::new(Arc::clone(&state.db));
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(timeout_seconds);
+
+        let delivered = loop {
+            let pending = repo
+                .fetch_pending(&session.id, input.topic.as_deref())
+                .await
+                .map_err(|err| {
+                    rmcp::ErrorData::internal_error(
+                        format!("failed to query relay messages: {err}"),
+                        None,
+                    )
+                })?;
+            let mut delivered: Vec<RelayMessage> = Vec::with_capacity(pending.len());
+            for mut message in pending {
+                let now = Utc::now();
+                // Another receiver on the same session may have claimed it.
+                if matches!(repo.mark_delivered(&message.id, now).await, Ok(true)) {
+                    message.delivered_at = Some(now);
+                    delivered.push(message);
+                }
+            }
+            if !delivered.is_empty() || tokio::time::Instant::now() >= deadline {
+                break delivered;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        };
+
+        if let Some(ref logger) = state.audit_logger {
+            for message in &delivered {
+                let entry = AuditEntry::new(AuditEventType::RelayDelivered)
+                    .with_session(session.id.clone())
+                    .with_request_id(message.id.clone())
+                    .with_result(format!("received from {}", message.from_session_id));
+                if let Err(err) = logger.log_entry(entry) {
+                    warn!(%err, "audit log write failed (relay delivered)");
+                }
+            }
+        }
+
+        let _ = SessionRepo::new(Arc::clone(&state.db))
+            .update_last_activity(&session.id, Some("relay_receive".to_owned()))
+            .await;
+        info!(
+            session_id = %session.id,
+            count = delivered.len(),
+            "relay messages delivered"
+        );
+
+        let messages: Vec<serde_json::Value> = delivered
+            .into_iter()
+            .map(|message| {
+                serde_json::json!({
+                    "message_id": message.id,
+                    "from_session_id": message.from_session_id,
+                    "topic": message.topic,
+                    "payload": message.payload,
+                    "sent_at": message.created_at.to_rfc3339(),
+                })
+            })
+            .collect();
+        let response = serde_json::json!({ "messages": messages });
+        Ok(CallToolResult::success(vec![rmcp::model::Content::json(
+            response,
+        )
+        .map_err(|err| {
+            rmcp::ErrorData::internal_error(
+                format!("failed to serialize relay_receive response: {err}"),
+                None,
+            )
+        })?]))
+    }
+    .instrument(span)
+    .await
+}
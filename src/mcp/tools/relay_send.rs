@@ -0,0 +1,206 @@
+//! `relay_send` MCP tool handler.
+//!
+//! Sends a structured message from the calling session to another session,
+//! which collects it with `relay_receive`. The recipient must be named by
+//! its full ID and share the sender's owner or workspace. Every message is
+//! persisted, audit-logged, and announced in both sessions' Slack threads
+//! so the operator can follow the exchange. Returns immediately.
+
+use std::sync::Arc;
+
+use rmcp::handler::server::tool::ToolCallContext;
+use rmcp::model::CallToolResult;
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
+use tracing::{info, info_span, warn, Instrument};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::mcp::handler::IntercomServer;
+use crate::mcp::tools::util::{self, truncate_text};
+use crate::models::relay::RelayMessage;
+use crate::models::session::{Session, SessionStatus};
+use crate::persistence::relay_repo::RelayRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::client::SlackMessage;
+use crate::state::AppState;
+
+/// Largest accepted payload, measured as serialized JSON.
+pub const MAX_PAYLOAD_BYTES: usize = 64 * 1024;
+
+/// Input parameters for `relay_send`.
+#[derive(Debug, serde::Deserialize)]
+struct RelaySendInput {
+    /// Full recipient session ID.
+    to_session_id: String,
+    /// Structured message body.
+    payload: serde_json::Value,
+    /// Optional label the recipient can filter on.
+    topic: Option<String>,
+}
+
+/// Handle the `relay_send` tool call.
+///
+/// # Errors
+///
+/// Returns `rmcp::ErrorData` for invalid parameters, unknown or finished
+/// recipients, and infrastructure failures.
+pub async fn handle(
+    context: ToolCallContext<'_, IntercomServer>,
+) -> Result<CallToolResult, rmcp::ErrorData> {
+    let state = Arc::clone(context.service.state());
+    let bound = context.service.bound_session_id().map(str::to_owned);
+    let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
+
+    let input: RelaySendInput =
+        serde_json::from_value(serde_json::Value::Object(args)).map_err(|err| {
+            rmcp::ErrorData::invalid_params(format!("invalid relay_send parameters: {err}"), None)
+        })?;
+
+    let span = info_span!("relay_send", to = %input.to_session_id, topic = ?input.topic);
+
+    async move {
+        let size = serde_json::to_string(&input.payload)
+            .map_err(|err| {
+                rmcp::ErrorData::invalid_params(format!("invalid relay payload: {err}"), None)
+            })?
+            .len();
+        if size > MAX_PAYLOAD_BYTES {
+            return Err(rmcp::ErrorData::invalid_params(
+                format!("relay payload is {size} bytes; the limit is {MAX_PAYLOAD_BYTES}"),
+                None,
+            ));
+        }
+
+        let sender = util::bound_session(&state, bound.as_deref()).await?;
+        let recipient = resolve_recipient(&state, &sender, &input.to_session_id).await?;
+        if recipient.id == sender.id {
+            return Err(rmcp::ErrorData::invalid_params(
+                "relay_send cannot target the calling session",
+                None,
+            ));
+        }
+        if matches!(
+            recipient.status,
+            SessionStatus::Terminated | SessionStatus::Interrupted
+        ) {
+            return Err(rmcp::ErrorData::invalid_params(
+                format!(
+                    "session {} is {} and cannot receive relay messages",
+                    recipient.id,
+                    recipient.status.as_str()
+                ),
+                None,
+            ));
+        }
+
+        let message = RelayRepo::new(Arc::clone(&state.db))
+            .insert(&RelayMessage::new(
+                sender.id.clone(),
+                recipient.id.clone(),
+                input.topic,
+                input.payload,
+            ))
+            .await
+            .map_err(|err| {
+                rmcp::ErrorData::internal_error(
+                    format!("failed to store relay message: {err}"),
+                    None,
+                )
+            })?;
+
+        if let Some(ref logger) = state.audit_logger {
+            let entry = AuditEntry::new(AuditEventType::RelaySent)
+                .with_session(sender.id.clone())
+                .with_request_id(message.id.clone())
+                .with_result(format!("sent to {}", recipient.id));
+            if let Err(err) = logger.log_entry(entry) {
+                warn!(%err, "audit log write failed (relay sent)");
+            }
+        }
+        announce(&state, &message, &sender, &recipient);
+
+        let _ = SessionRepo::new(Arc::clone(&state.db))
+            .update_last_activity(&sender.id, Some("relay_send".to_owned()))
+            .await;
+        info!(message_id = %message.id, to = %recipient.id, "relay message sent");
+
+        let response = serde_json::json!({
+            "status": "sent",
+            "message_id": message.id,
+            "to_session_id": recipient.id,
+        });
+        Ok(CallToolResult::success(vec![rmcp::model::Content::json(
+            response,
+        )
+        .map_err(|err| {
+            rmcp::ErrorData::internal_error(
+                format!("failed to serialize relay_send response: {err}"),
+                None,
+            )
+        })?]))
+    }
+    .instrument(span)
+    .await
+}
+
+/// Look up the recipient by its full ID.
+///
+/// Sessions of another owner in another workspace are reported as unknown,
+/// so an agent can neither message nor discover them.
+async fn resolve_recipient(
+    state: &AppState,
+    sender: &Session,
+    id: &str,
+) -> Result<Session, rmcp::ErrorData> {
+    let found = SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(id)
+        .await
+        .map_err(|err| {
+            rmcp::ErrorData::internal_error(format!("failed to query session: {err}"), None)
+        })?;
+    match found {
+        Some(session)
+            if session.owner_user_id == sender.owner_user_id
+                || session.workspace_root == sender.workspace_root =>
+        {
+            Ok(session)
+        }
+        _ => Err(rmcp::ErrorData::invalid_params(
+            format!("unknown session '{id}'"),
+            None,
+        )),
+    }
+}
+
+/// Post the relay notice in the sender's and the recipient's Slack threads.
+fn announce(state: &AppState, message: &RelayMessage, sender: &Session, recipient: &Session) {
+    let topic = message
+        .topic
+        .as_deref()
+        .map(|t| format!(" [{t}]"))
+        .unwrap_or_default();
+    let text = format!(
+        "\u{1f4e8} Relay `{}` \u{2192} `{}`{topic}: {}",
+        sender.short_id,
+        recipient.short_id,
+        truncate_text(&message.payload.to_string(), 300)
+    );
+
+    let mut posted: Vec<(&str, Option<&str>)> = Vec::new();
+    for session in [sender, recipient] {
+        let Some(ref channel_id) = session.channel_id else {
+            continue;
+        };
+        let target = (channel_id.as_str(), session.thread_ts.as_deref());
+        if posted.contains(&target) {
+            continue;
+        }
+        posted.push(target);
+        let msg = SlackMessage {
+            channel: SlackChannelId(channel_id.clone()),
+            text: Some(text.clone()),
+            blocks: None,
+            thread_ts: session.thread_ts.clone().map(SlackTs),
+        };
+        util::post_notice(state, &session.id, msg);
+    }
+}
@@ -0,0 +1,98 @@
+//! `release_lock` MCP tool handler.
+//!
+//! Releases path locks the calling session took with `acquire_lock`: the
+//! lock on one path, or every lock it holds. Sessions waiting for those
+//! paths are granted them on their next check. Returns immediately.
+
+use std::sync::Arc;
+
+use rmcp::handler::server::tool::ToolCallContext;
+use rmcp::model::CallToolResult;
+use tracing::{info, info_span, warn, Instrument};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::mcp::handler::IntercomServer;
+use crate::mcp::tools::util;
+use crate::persistence::lock_repo::LockRepo;
+use crate::persistence::session_repo::SessionRepo;
+
+/// Input parameters for `release_lock`.
+#[derive(Debug, serde::Deserialize)]
+struct ReleaseLockInput {
+    /// Path given to `acquire_lock`; every held lock when omitted.
+    path: Option<String>,
+}
+
+/// Handle the `release_lock` tool call.
+///
+/// # Errors
+///
+/// Returns `rmcp::ErrorData` for invalid parameters, paths outside the
+/// workspace, and infrastructure failures.
+pub async fn handle(
+    context: ToolCallContext<'_, IntercomServer>,
+) -> Result<CallToolResult, rmcp::ErrorData> {
+    let state = Arc::clone(context.service.state());
+    let bound = context.service.bound_session_id().map(str::to_owned);
+    let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
+
+    let input: ReleaseLockInput =
+        serde_json::from_value(serde_json::Value::Object(args)).map_err(|err| {
+            rmcp::ErrorData::invalid_params(format!("invalid release_lock parameters: {err}"), None)
+        })?;
+
+    let span = info_span!("release_lock", path = ?input.path);
+
+    async move {
+        let session = util::bound_session(&state, bound.as_deref()).await?;
+        let path = input
+            .path
+            .as_deref()
+            .map(|path| util::lock_path(&session.workspace_root, path).map(|(_, path)| path))
+            .transpose()?;
+
+        let released = LockRepo::new(Arc::clone(&state.db))
+            .release(&session.id, path.as_deref())
+            .await
+            .map_err(|err| {
+                rmcp::ErrorData::internal_error(
+                    format!("failed to release path locks: {err}"),
+                    None,
+                )
+            })?;
+
+        if let Some(ref logger) = state.audit_logger {
+            for lock in &released {
+                let entry = AuditEntry::new(AuditEventType::LockReleased)
+                    .with_session(session.id.clone())
+                    .with_request_id(lock.id.clone())
+                    .with_result(lock.path.clone());
+                if let Err(err) = logger.log_entry(entry) {
+                    warn!(%err, "audit log write failed (lock released)");
+                }
+            }
+        }
+
+        let _ = SessionRepo::new(Arc::clone(&state.db))
+            .update_last_activity(&session.id, Some("release_lock".to_owned()))
+            .await;
+        info!(session_id = %session.id, count = released.len(), "path locks released");
+
+        let paths: Vec<String> = released.into_iter().map(|lock| lock.path).collect();
+        let response = serde_json::json!({
+            "status": "released",
+            "released": paths,
+        });
+        Ok(CallToolResult::success(vec![rmcp::model::Content::json(
+            response,
+        )
+        .map_err(|err| {
+            rmcp::ErrorData::internal_error(
+                format!("failed to serialize release_lock response: {err}"),
+                None,
+            )
+        })?]))
+    }
+    .instrument(span)
+    .await
+}
@@ -1,18 +1,26 @@
 //! `remote_log` MCP tool handler (T055, T056).
 //!
-//! Sends a non-blocking status log message to the Slack channel with
-//! severity-based formatting. Returns immediately without waiting for
-//! operator action.
+//! Sends a non-blocking status log message to Slack with severity-based
+//! formatting. `[broadcast]` maps each level to its destinations: the
+//! session thread, the channel, the operator's DMs, or a channel escalation.
+//! The session owner's `/intercom prefs` override the global
+//! `slack_detail_level` and can move the thread post to a DM; quiet hours
+//! hold back DMs. Returns immediately without waiting for operator action.
+//! Only `error` messages get through a session mute.
 
 use std::sync::Arc;
 
+use chrono::{Local, NaiveTime, Utc};
 use rmcp::handler::server::tool::ToolCallContext;
 use rmcp::model::CallToolResult;
 use slack_morphism::prelude::{SlackChannelId, SlackTs};
 use tracing::{info, info_span, warn, Instrument};
 
-use crate::config::SlackDetailLevel;
+use crate::config::{BroadcastDestination, GlobalConfig};
 use crate::mcp::handler::IntercomServer;
+use crate::models::preferences::{NotificationDelivery, UserPreferences};
+use crate::models::session::Session;
+use crate::persistence::preferences_repo::PreferencesRepo;
 use crate::persistence::session_repo::SessionRepo;
 use crate::slack::blocks;
 use crate::slack::client::SlackMessage;
@@ -46,7 +54,7 @@ pub async fn handle(
     context: ToolCallContext<'_, IntercomServer>,
 ) -> Result<CallToolResult, rmcp::ErrorData> {
     let state = Arc::clone(context.service.state());
-    let channel_id = context.service.effective_channel_id().map(str::to_owned);
+    let channel_id = context.service.routed_channel_id();
     let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
 
     let input: RemoteLogInput =
@@ -96,17 +104,31 @@ pub async fn handle(
                     .map(|ts| SlackTs(ts.to_owned()))
             });
 
-        // ── Detail level filter (T065) ───────────────────────
-        let detail_str = match state.config.slack_detail_level {
-            SlackDetailLevel::Minimal => "minimal",
-            SlackDetailLevel::Verbose => "verbose",
-            SlackDetailLevel::Standard => "standard",
+        // ── Owner preferences ────────────────────────────────
+        let prefs = if session.owner_user_id.contains(':') {
+            None
+        } else {
+            PreferencesRepo::new(Arc::clone(&state.db))
+                .get(&session.owner_user_id)
+                .await
+                .unwrap_or_else(|err| {
+                    warn!(%err, "failed to load owner preferences; using defaults");
+                    None
+                })
         };
-        if !blocks::message_visible_at_level(detail_str, &input.level) {
+
+        // ── Detail level filter (T065) and session mute ──────
+        let detail_level = prefs
+            .as_ref()
+            .and_then(|p| p.detail_level)
+            .unwrap_or(state.config.slack_detail_level);
+        let muted = input.level != "error" && session.is_muted(Utc::now());
+        if muted || !blocks::message_visible_at_level(detail_level.as_str(), &input.level) {
             info!(
                 level = %input.level,
-                detail_level = ?state.config.slack_detail_level,
-                "remote_log suppressed by detail level filter"
+                detail_level = detail_level.as_str(),
+                muted,
+                "remote_log suppressed by detail level filter or session mute"
             );
             let response = serde_json::json!({ "posted": false, "ts": "" });
             return Ok(CallToolResult::success(vec![rmcp::model::Content::json(
@@ -123,26 +145,36 @@ pub async fn handle(
         // ── Post to Slack ────────────────────────────────────
         let (posted, ts) = if let Some(ref slack) = state.slack {
             if let Some(ref ch) = channel_id {
-                let channel = SlackChannelId(ch.clone());
-
-                let severity_block = blocks::severity_section(&input.level, &input.message);
-                let msg = SlackMessage {
-                    channel,
-                    text: Some(input.message.clone()),
-                    blocks: Some(vec![severity_block]),
-                    thread_ts: effective_thread_ts,
-                };
-
-                match slack.post_message_direct(msg).await {
-                    Ok(slack_ts) => {
-                        info!(ts = %slack_ts.0, "remote_log posted to slack");
-                        (true, slack_ts.0)
-                    }
-                    Err(err) => {
-                        warn!(%err, "failed to post remote_log to slack");
-                        (false, String::new())
+                let owner = OwnerDelivery::new(
+                    prefs.as_ref(),
+                    &input.level,
+                    Local::now().naive_local().time(),
+                );
+                let messages = broadcast_messages(
+                    &state.config,
+                    &session,
+                    owner,
+                    ch,
+                    effective_thread_ts,
+                    &input.level,
+                    &input.message,
+                );
+
+                // The response reports the first message that went out.
+                let mut first_ts = None;
+                for msg in messages {
+                    let target = msg.channel.to_string();
+                    match slack.post_message_direct(msg).await {
+                        Ok(slack_ts) => {
+                            info!(ts = %slack_ts.0, target, "remote_log posted to slack");
+                            first_ts.get_or_insert(slack_ts.0);
+                        }
+                        Err(err) => {
+                            warn!(%err, target, "failed to post remote_log to slack");
+                        }
                     }
                 }
+                first_ts.map_or((false, String::new()), |ts| (true, ts))
             } else {
                 warn!(
                     "no Slack channel configured for this session; remote_log message not posted"
@@ -185,3 +217,116 @@ pub async fn handle(
     .instrument(span)
     .await
 }
+
+/// How the session owner's `/intercom prefs` shape one broadcast.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OwnerDelivery {
+    /// Where the owner receives their session notifications.
+    pub delivery: NotificationDelivery,
+    /// The owner is inside their quiet hours and the message is not an
+    /// error, so they are sent no DM.
+    pub quiet: bool,
+}
+
+impl OwnerDelivery {
+    /// Delivery of a `level` message sent at local time `now` under the
+    /// owner's `prefs`.
+    #[must_use]
+    pub fn new(prefs: Option<&UserPreferences>, level: &str, now: NaiveTime) -> Self {
+        prefs.map_or_else(Self::default, |prefs| Self {
+            delivery: prefs.delivery,
+            quiet: level != "error" && prefs.is_quiet(now),
+        })
+    }
+}
+
+/// Build the messages `[broadcast]` routes a `level` message to, in posting
+/// order: thread, channel, then one DM per recipient.
+///
+/// Without a session thread the thread post is already top-level, so it
+/// doubles as the channel post. Escalated channel posts lead with
+/// `escalation_mention`. Channel posts and DMs name the session.
+///
+/// For a session a Slack user owns, `owner` adds a DM (`both`) or moves
+/// the thread post to one (`dm`). In quiet hours the owner gets no DM and
+/// a moved thread post stays in the thread.
+#[must_use]
+pub fn broadcast_messages(
+    config: &GlobalConfig,
+    session: &Session,
+    owner: OwnerDelivery,
+    channel_id: &str,
+    thread_ts: Option<SlackTs>,
+    level: &str,
+    message: &str,
+) -> Vec<SlackMessage> {
+    let route = config.broadcast.route(level);
+    let escalate = route.contains(&BroadcastDestination::Escalate);
+    let owned_by_user = !session.owner_user_id.contains(':');
+    let owner_dm = owned_by_user
+        && !owner.quiet
+        && matches!(
+            owner.delivery,
+            NotificationDelivery::Dm | NotificationDelivery::Both
+        );
+    let to_thread = route.contains(&BroadcastDestination::Thread)
+        && !(owner_dm && owner.delivery == NotificationDelivery::Dm);
+    let to_channel = escalate || route.contains(&BroadcastDestination::Channel);
+    let mention = escalate.then_some(config.broadcast.escalation_mention.as_str());
+    let from_session = format!("Broadcast from session `{}`", session.short_id);
+
+    let build = |channel: &str, thread_ts: Option<SlackTs>, header: Option<String>| {
+        let mut msg_blocks = Vec::with_capacity(2);
+        if let Some(ref header) = header {
+            msg_blocks.push(blocks::text_section(header));
+        }
+        msg_blocks.push(blocks::severity_section(level, message));
+        SlackMessage {
+            channel: SlackChannelId(channel.to_owned()),
+            text: Some(header.map_or_else(|| message.to_owned(), |h| format!("{h}: {message}"))),
+            blocks: Some(msg_blocks),
+            thread_ts,
+        }
+    };
+    let channel_header = || {
+        Some(mention.map_or_else(
+            || from_session.clone(),
+            |mention| format!("{mention} \u{1f6a8} {from_session}"),
+        ))
+    };
+
+    let mut messages = Vec::new();
+    let thread_is_channel = thread_ts.is_none();
+    if to_thread {
+        let header = if to_channel && thread_is_channel {
+            channel_header()
+        } else {
+            None
+        };
+        messages.push(build(channel_id, thread_ts, header));
+    }
+    if to_channel && !(to_thread && thread_is_channel) {
+        messages.push(build(channel_id, None, channel_header()));
+    }
+    let routed_dm = route.contains(&BroadcastDestination::Dm) && !(owned_by_user && owner.quiet);
+    if routed_dm || owner_dm {
+        for user in dm_recipients(config, session) {
+            messages.push(build(user, None, Some(from_session.clone())));
+        }
+    }
+    messages
+}
+
+/// DM the session owner, or every authorized user when the session is
+/// owned by the local agent rather than a Slack user.
+fn dm_recipients<'a>(config: &'a GlobalConfig, session: &'a Session) -> Vec<&'a str> {
+    if session.owner_user_id.contains(':') {
+        config
+            .authorized_user_ids
+            .iter()
+            .map(String::as_str)
+            .collect()
+    } else {
+        vec![session.owner_user_id.as_str()]
+    }
+}
@@ -11,7 +11,9 @@ use rmcp::handler::server::tool::ToolCallContext;
 use rmcp::model::CallToolResult;
 use tracing::{info, info_span, Instrument};
 
+use crate::driver::session_hooks::SessionHookEvent;
 use crate::mcp::handler::IntercomServer;
+use crate::mcp::tools::util;
 use crate::models::session::SessionMode;
 use crate::persistence::session_repo::SessionRepo;
 
@@ -31,7 +33,7 @@ pub async fn handle(
     context: ToolCallContext<'_, IntercomServer>,
 ) -> Result<CallToolResult, rmcp::ErrorData> {
     let state = Arc::clone(context.service.state());
-    let channel_id = context.service.effective_channel_id().map(str::to_owned);
+    let channel_id = context.service.routed_channel_id();
     let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
 
     let input: SetModeInput =
@@ -71,13 +73,23 @@ pub async fn handle(
                 )
             })?;
 
+        state
+            .notify_session_hook(
+                &session.id,
+                SessionHookEvent::ModeChanged {
+                    previous_mode,
+                    current_mode: input.mode,
+                },
+            )
+            .await;
+
         // ── Update last activity ─────────────────────────────
         let _ = session_repo
             .update_last_activity(&session.id, Some("set_operational_mode".to_owned()))
             .await;
 
         // ── Notify Slack if connected ────────────────────────
-        if let (Some(ref slack), Some(ref ch)) = (&state.slack, &channel_id) {
+        if let Some(ref ch) = channel_id {
             // Only post to Slack if the new mode still includes Slack.
             if matches!(input.mode, SessionMode::Remote | SessionMode::Hybrid) {
                 let channel = slack_morphism::prelude::SlackChannelId(ch.clone());
@@ -98,9 +110,7 @@ pub async fn handle(
                     )]),
                     thread_ts: None,
                 };
-                if let Err(err) = slack.enqueue(msg).await {
-                    tracing::warn!(%err, "failed to notify mode change to slack");
-                }
+                util::post_notice(&state, &session.id, msg);
             }
         }
 
@@ -0,0 +1,200 @@
+//! `spawn_subtask` MCP tool handler.
+//!
+//! Lets an agent delegate work to a child session. The request is posted
+//! to the operator in the parent's Slack thread and the child is spawned
+//! only once accepted, within the concurrent session limit. The child
+//! records its parent, and its completion is reported back to the parent
+//! as a `subtask` steering message (see [`crate::orchestrator::subtask`]).
+
+use std::sync::Arc;
+
+use rmcp::handler::server::tool::ToolCallContext;
+use rmcp::model::CallToolResult;
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
+use tracing::{info, info_span, warn, Instrument};
+
+use crate::mcp::approval_gate::{self, Decision};
+use crate::mcp::handler::IntercomServer;
+use crate::mcp::tools::util::{self, truncate_text};
+use crate::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use crate::orchestrator::spawner;
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::client::SlackMessage;
+
+/// `original_hash` recorded on subtask approval requests.
+///
+/// Marks the record as a spawn request rather than a file change, so it
+/// can never be applied through `check_diff`.
+pub const SUBTASK_HASH: &str = "spawn_subtask";
+
+/// Input parameters for `spawn_subtask`.
+#[derive(Debug, serde::Deserialize)]
+struct SpawnSubtaskInput {
+    /// Prompt the child agent starts with.
+    prompt: String,
+    /// Short label shown to the operator.
+    title: Option<String>,
+}
+
+/// Handle the `spawn_subtask` tool call.
+///
+/// # Errors
+///
+/// Returns `rmcp::ErrorData` for invalid parameters or infrastructure
+/// failures. Refusals are reported in the result's `status`.
+#[allow(clippy::too_many_lines)] // Limit check, approval wait, then the spawn.
+pub async fn handle(
+    context: ToolCallContext<'_, IntercomServer>,
+) -> Result<CallToolResult, rmcp::ErrorData> {
+    let state = Arc::clone(context.service.state());
+    let bound = context.service.bound_session_id().map(str::to_owned);
+    let channel_id = context.service.routed_channel_id();
+    let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
+
+    let input: SpawnSubtaskInput = serde_json::from_value(serde_json::Value::Object(args))
+        .map_err(|err| {
+            rmcp::ErrorData::invalid_params(
+                format!("invalid spawn_subtask parameters: {err}"),
+                None,
+            )
+        })?;
+    if input.prompt.trim().is_empty() {
+        return Err(rmcp::ErrorData::invalid_params(
+            "spawn_subtask prompt cannot be empty",
+            None,
+        ));
+    }
+
+    let span = info_span!("spawn_subtask", title = ?input.title);
+
+    async move {
+        let parent = util::bound_session(&state, bound.as_deref()).await?;
+        let session_repo = SessionRepo::new(Arc::clone(&state.db));
+
+        let active = session_repo.count_active().await.map_err(|err| {
+            rmcp::ErrorData::internal_error(format!("failed to count sessions: {err}"), None)
+        })?;
+        let limit = state.config.max_concurrent_sessions;
+        if active >= i64::from(limit) {
+            return respond(&serde_json::json!({
+                "status": "error",
+                "error_code": "session_limit",
+                "error_message": format!("concurrent session limit reached ({active}/{limit})"),
+            }));
+        }
+
+        let (Some(slack), Some(channel)) = (state.slack.as_ref(), channel_id.as_deref()) else {
+            return respond(&serde_json::json!({
+                "status": "error",
+                "error_code": "slack_unavailable",
+                "error_message": "spawn_subtask requires operator approval, but Slack is not \
+                                  configured for this session",
+            }));
+        };
+
+        let title = input
+            .title
+            .clone()
+            .unwrap_or_else(|| truncate_text(input.prompt.lines().next().unwrap_or_default(), 60));
+        let approval = ApprovalRequest::new(
+            parent.id.clone(),
+            format!("Spawn subtask: {title}"),
+            Some(format!(
+                "Session `{}` asks to start a child session with this prompt.",
+                parent.id
+            )),
+            input.prompt.clone(),
+            parent.workspace_root.clone(),
+            RiskLevel::Low,
+            SUBTASK_HASH.to_owned(),
+        );
+        let decision = approval_gate::request_decision(
+            &state,
+            slack,
+            channel,
+            &parent,
+            &approval,
+            format!("\u{1f9f5} Subtask requested: {title}"),
+        )
+        .await?;
+
+        let refusal = match decision {
+            Decision::Approved => None,
+            Decision::Rejected(reason) => Some(("rejected", reason)),
+            Decision::Abandoned => Some(("rejected", Some("approval was abandoned".to_owned()))),
+            Decision::TimedOut(seconds) => Some((
+                "timeout",
+                Some(format!("no decision within {seconds} seconds")),
+            )),
+        };
+        if let Some((status, reason)) = refusal {
+            info!(status, ?reason, "subtask refused");
+            return respond(&serde_json::json!({ "status": status, "reason": reason }));
+        }
+        let _ = ApprovalRepo::new(Arc::clone(&state.db))
+            .update_status(&approval.id, ApprovalStatus::Consumed)
+            .await;
+
+        let (child_session, child) = match spawner::spawn_subtask(
+            &parent,
+            &input.prompt,
+            Some(channel),
+            &state.config,
+            &session_repo,
+            state.config.http_port,
+        )
+        .await
+        {
+            Ok(spawned) => spawned,
+            Err(err) => {
+                warn!(%err, "subtask spawn failed");
+                return respond(&serde_json::json!({
+                    "status": "error",
+                    "error_code": "spawn_failed",
+                    "error_message": err.to_string(),
+                }));
+            }
+        };
+        state
+            .active_children
+            .lock()
+            .await
+            .insert(child_session.id.clone(), child);
+
+        let msg = SlackMessage {
+            channel: SlackChannelId(channel.to_owned()),
+            text: Some(format!(
+                "\u{1f9f5} Subtask `{}` started: {title}",
+                child_session.id
+            )),
+            blocks: None,
+            thread_ts: parent.thread_ts.clone().map(SlackTs),
+        };
+        util::post_notice(&state, &parent.id, msg);
+        let _ = session_repo
+            .update_last_activity(&parent.id, Some("spawn_subtask".to_owned()))
+            .await;
+        info!(parent = %parent.id, child = %child_session.id, "subtask spawned");
+
+        respond(&serde_json::json!({
+            "status": "spawned",
+            "session_id": child_session.id,
+        }))
+    }
+    .instrument(span)
+    .await
+}
+
+/// Wrap a JSON body in a successful tool result.
+fn respond(body: &serde_json::Value) -> Result<CallToolResult, rmcp::ErrorData> {
+    Ok(CallToolResult::success(vec![rmcp::model::Content::json(
+        body,
+    )
+    .map_err(|err| {
+        rmcp::ErrorData::internal_error(
+            format!("failed to serialize spawn_subtask response: {err}"),
+            None,
+        )
+    })?]))
+}
@@ -0,0 +1,124 @@
+//! `store_context` MCP tool handler.
+//!
+//! Stores a small JSON note under a key for the calling session — a
+//! decision made, a URL discovered — in the `session_context` table. The
+//! notes survive restarts and are returned by `reboot`, so a resumed agent
+//! picks up what it had recorded. Storing `null` removes the key. Returns
+//! immediately.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use rmcp::handler::server::tool::ToolCallContext;
+use rmcp::model::CallToolResult;
+use tracing::{debug, info_span, Instrument};
+
+use crate::mcp::handler::IntercomServer;
+use crate::mcp::tools::util;
+use crate::persistence::context_repo::ContextRepo;
+use crate::persistence::session_repo::SessionRepo;
+
+/// Longest accepted key, in bytes.
+const MAX_KEY_BYTES: usize = 128;
+
+/// Largest accepted value, serialized, in bytes.
+const MAX_VALUE_BYTES: usize = 4096;
+
+/// Most keys a session may hold.
+const MAX_ENTRIES: u64 = 100;
+
+/// Input parameters for `store_context`.
+#[derive(Debug, serde::Deserialize)]
+struct StoreContextInput {
+    /// Key, unique within the session.
+    key: String,
+    /// Value to store; `null` removes the key.
+    value: serde_json::Value,
+}
+
+/// Handle the `store_context` tool call.
+///
+/// # Errors
+///
+/// Returns `rmcp::ErrorData` for invalid parameters, when the session is
+/// full, or on persistence failures.
+pub async fn handle(
+    context: ToolCallContext<'_, IntercomServer>,
+) -> Result<CallToolResult, rmcp::ErrorData> {
+    let state = Arc::clone(context.service.state());
+    let bound = context.service.bound_session_id().map(str::to_owned);
+    let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
+
+    let input: StoreContextInput = serde_json::from_value(serde_json::Value::Object(args))
+        .map_err(|err| {
+            rmcp::ErrorData::invalid_params(
+                format!("invalid store_context parameters: {err}"),
+                None,
+            )
+        })?;
+
+    let span = info_span!("store_context", key = %input.key);
+
+    async move {
+        let key = input.key.trim();
+        if key.is_empty() || key.len() > MAX_KEY_BYTES {
+            return Err(rmcp::ErrorData::invalid_params(
+                format!("key must be 1 to {MAX_KEY_BYTES} bytes"),
+                None,
+            ));
+        }
+        let value_len = serde_json::to_string(&input.value).map_or(0, |json| json.len());
+        if value_len > MAX_VALUE_BYTES {
+            return Err(rmcp::ErrorData::invalid_params(
+                format!("value is {value_len} bytes serialized; the limit is {MAX_VALUE_BYTES}"),
+                None,
+            ));
+        }
+
+        let session = util::bound_session(&state, bound.as_deref()).await?;
+        let repo = ContextRepo::new(Arc::clone(&state.db));
+        let db_err = |err: crate::AppError| {
+            rmcp::ErrorData::internal_error(format!("failed to store context: {err}"), None)
+        };
+
+        let status = if input.value.is_null() {
+            repo.delete(&session.id, key).await.map_err(db_err)?;
+            "deleted"
+        } else {
+            let exists = repo.get(&session.id, key).await.map_err(db_err)?.is_some();
+            if !exists && repo.count_for_session(&session.id).await.map_err(db_err)? >= MAX_ENTRIES
+            {
+                return Err(rmcp::ErrorData::invalid_params(
+                    format!(
+                        "session already holds {MAX_ENTRIES} context keys; \
+                         remove one by storing null"
+                    ),
+                    None,
+                ));
+            }
+            repo.upsert(&session.id, key, &input.value, Utc::now())
+                .await
+                .map_err(db_err)?;
+            "stored"
+        };
+        let entries = repo.count_for_session(&session.id).await.map_err(db_err)?;
+
+        let _ = SessionRepo::new(Arc::clone(&state.db))
+            .update_last_activity(&session.id, Some("store_context".to_owned()))
+            .await;
+        debug!(session_id = %session.id, status, entries, "session context updated");
+
+        let response = serde_json::json!({ "status": status, "key": key, "entries": entries });
+        Ok(CallToolResult::success(vec![rmcp::model::Content::json(
+            response,
+        )
+        .map_err(|err| {
+            rmcp::ErrorData::internal_error(
+                format!("failed to serialize store_context response: {err}"),
+                None,
+            )
+        })?]))
+    }
+    .instrument(span)
+    .await
+}
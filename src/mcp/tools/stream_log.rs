@@ -0,0 +1,99 @@
+//! `stream_log` MCP tool handler.
+//!
+//! Appends a structured log line to the calling session's ring buffer
+//! without posting anything to Slack. Operators read the buffer on demand
+//! with `/intercom logs`, `agent-intercom-ctl logs`, or the
+//! `intercom://session/{id}/logs` resource. Returns immediately.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use rmcp::handler::server::tool::ToolCallContext;
+use rmcp::model::CallToolResult;
+use tracing::{debug, info_span, Instrument};
+
+use crate::mcp::handler::IntercomServer;
+use crate::mcp::tools::util;
+use crate::orchestrator::session_logs::{LogLevel, LogLine};
+use crate::persistence::session_repo::SessionRepo;
+
+/// Longest message kept, in bytes; longer messages are truncated.
+const MAX_MESSAGE_BYTES: usize = 4096;
+
+/// Largest accepted `fields` object, serialized, in bytes.
+const MAX_FIELDS_BYTES: usize = 4096;
+
+/// Input parameters for `stream_log`.
+#[derive(Debug, serde::Deserialize)]
+struct StreamLogInput {
+    /// Log message.
+    message: String,
+    /// Severity (default: `info`).
+    #[serde(default = "default_level")]
+    level: LogLevel,
+    /// Structured key/value context.
+    #[serde(default)]
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+fn default_level() -> LogLevel {
+    LogLevel::Info
+}
+
+/// Handle the `stream_log` tool call.
+///
+/// # Errors
+///
+/// Returns `rmcp::ErrorData` for invalid parameters or when no session is
+/// found.
+pub async fn handle(
+    context: ToolCallContext<'_, IntercomServer>,
+) -> Result<CallToolResult, rmcp::ErrorData> {
+    let state = Arc::clone(context.service.state());
+    let bound = context.service.bound_session_id().map(str::to_owned);
+    let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
+
+    let input: StreamLogInput =
+        serde_json::from_value(serde_json::Value::Object(args)).map_err(|err| {
+            rmcp::ErrorData::invalid_params(format!("invalid stream_log parameters: {err}"), None)
+        })?;
+
+    let span = info_span!("stream_log", level = input.level.label());
+
+    async move {
+        let fields_len = serde_json::to_string(&input.fields).map_or(0, |json| json.len());
+        if fields_len > MAX_FIELDS_BYTES {
+            return Err(rmcp::ErrorData::invalid_params(
+                format!("fields is {fields_len} bytes serialized; the limit is {MAX_FIELDS_BYTES}"),
+                None,
+            ));
+        }
+
+        let session = util::bound_session(&state, bound.as_deref()).await?;
+        let line = LogLine {
+            at: Utc::now(),
+            level: input.level,
+            message: util::truncate_text(&input.message, MAX_MESSAGE_BYTES),
+            fields: input.fields,
+        };
+        let buffered = state.session_logs.push(&session.id, line);
+
+        let _ = SessionRepo::new(Arc::clone(&state.db))
+            .update_last_activity(&session.id, Some("stream_log".to_owned()))
+            .await;
+        debug!(session_id = %session.id, buffered, "log line buffered");
+
+        let response = serde_json::json!({ "status": "buffered", "buffered": buffered });
+        Ok(CallToolResult::success(vec![rmcp::model::Content::json(
+            response,
+        )
+        .map_err(|err| {
+            rmcp::ErrorData::internal_error(
+                format!("failed to serialize stream_log response: {err}"),
+                None,
+            )
+        })?]))
+    }
+    .instrument(span)
+    .await
+}
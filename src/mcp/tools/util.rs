@@ -1,9 +1,18 @@
 //! Shared utilities for MCP tool handlers.
 
 use std::path::Path;
+use std::sync::Arc;
 
 use sha2::{Digest, Sha256};
 
+use crate::diff::path_safety::validate_path;
+use crate::driver::AgentEvent;
+use crate::models::path_lock::WORKSPACE_PATH;
+use crate::models::session::{ProtocolMode, Session};
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::client::SlackMessage;
+use crate::state::AppState;
+
 /// Truncate `text` to at most `max_len` bytes.
 ///
 /// Re-exported from `slack::blocks::truncate_text` — the canonical
@@ -31,6 +40,77 @@ pub async fn compute_file_hash(path: &Path) -> Result<String, std::io::Error> {
     }
 }
 
+/// Resolve the session a tool call belongs to: the connection's `bound`
+/// session when known, otherwise the first active session.
+///
+/// # Errors
+///
+/// Returns `rmcp::ErrorData` if the lookup fails or no session is found.
+pub(crate) async fn bound_session(
+    state: &AppState,
+    bound: Option<&str>,
+) -> Result<Session, rmcp::ErrorData> {
+    let session_repo = SessionRepo::new(Arc::clone(&state.db));
+    let found = match bound {
+        Some(sid) => session_repo.get_by_id(sid).await,
+        None => session_repo
+            .list_active()
+            .await
+            .map(|sessions| sessions.into_iter().next()),
+    };
+    found
+        .map_err(|err| {
+            rmcp::ErrorData::internal_error(format!("failed to query session: {err}"), None)
+        })?
+        .ok_or_else(|| rmcp::ErrorData::internal_error("no active session found", None))
+}
+
+/// Publish `message` on the event bus for the Slack notice subscriber to
+/// post on behalf of `session_id`.
+pub(crate) fn post_notice(state: &AppState, session_id: &str, message: SlackMessage) {
+    let channel_id = Some(message.channel.0.clone());
+    state.event_bus.publish(
+        ProtocolMode::Mcp,
+        channel_id,
+        AgentEvent::NoticePosted {
+            session_id: session_id.to_owned(),
+            message,
+        },
+    );
+}
+
+/// Resolve `path` for `acquire_lock` / `release_lock`: the canonical
+/// workspace root, and `path` relative to it with `/` separators
+/// ([`WORKSPACE_PATH`] for the root itself).
+///
+/// # Errors
+///
+/// Returns `rmcp::ErrorData` if the root cannot be resolved or `path`
+/// escapes it.
+pub(crate) fn lock_path(
+    workspace_root: &str,
+    path: &str,
+) -> Result<(String, String), rmcp::ErrorData> {
+    let root = Path::new(workspace_root).canonicalize().map_err(|err| {
+        rmcp::ErrorData::internal_error(format!("workspace root invalid: {err}"), None)
+    })?;
+    let resolved = validate_path(&root, path.trim())
+        .map_err(|err| rmcp::ErrorData::invalid_params(err.to_string(), None))?;
+    let relative = resolved.strip_prefix(&root).map_err(|_| {
+        rmcp::ErrorData::invalid_params(format!("path '{path}' is outside the workspace"), None)
+    })?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|part| part.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let relative = if parts.is_empty() {
+        WORKSPACE_PATH.to_owned()
+    } else {
+        parts.join("/")
+    };
+    Ok((root.to_string_lossy().into_owned(), relative))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
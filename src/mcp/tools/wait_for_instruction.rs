@@ -4,10 +4,20 @@
 //! Resume/Stop buttons. Blocks until the operator responds via Slack
 //! (or IPC) or the configured timeout elapses. Returns the operator's
 //! instruction or a timeout status.
+//!
+//! Instructions the operator queued with `queue` while the agent was busy
+//! resolve the wait immediately: the first becomes the returned instruction
+//! and the rest are delivered as steering messages, in order.
+//!
+//! With `wake_at`, the agent parks until that time and is then resumed with
+//! its `wake_instruction` (or `[standby] wake_instruction`) unless the
+//! operator answers first.
 
+use std::fmt::Write as _;
 use std::sync::Arc;
 use std::time::Duration;
 
+use chrono::{DateTime, NaiveTime, Utc};
 use rmcp::handler::server::tool::ToolCallContext;
 use rmcp::model::CallToolResult;
 use slack_morphism::prelude::SlackChannelId;
@@ -15,12 +25,16 @@ use tokio::sync::oneshot;
 use tracing::{info, info_span, warn, Instrument};
 
 use crate::mcp::handler::IntercomServer;
+use crate::models::session::{Session, SessionStatus};
+use crate::orchestrator::scheduled_apply;
 use crate::persistence::session_repo::SessionRepo;
+use crate::slack::anchor::Anchor;
 use crate::slack::blocks;
 use crate::slack::client::SlackMessage;
-use crate::state::WaitResponse;
+use crate::slack::handlers::steer;
+use crate::state::{AppState, WaitResponse};
 
-use super::util::truncate_text;
+use super::util::{self, truncate_text};
 
 /// Default status message when none is provided.
 const DEFAULT_WAIT_MESSAGE: &str = "Agent is idle and awaiting instructions.";
@@ -34,6 +48,14 @@ struct WaitInput {
     /// Maximum wait time in seconds. 0 = indefinite.
     #[serde(default)]
     timeout_seconds: u64,
+    /// Resume without operator action at this time: `HH:MM` server-local
+    /// (the next occurrence) or an RFC 3339 timestamp. Overrides
+    /// `timeout_seconds`.
+    #[serde(default)]
+    wake_at: Option<String>,
+    /// Instruction returned on a scheduled wake-up.
+    #[serde(default)]
+    wake_instruction: Option<String>,
 }
 
 fn default_message() -> String {
@@ -50,7 +72,7 @@ pub async fn handle(
     context: ToolCallContext<'_, IntercomServer>,
 ) -> Result<CallToolResult, rmcp::ErrorData> {
     let state = Arc::clone(context.service.state());
-    let channel_id = context.service.effective_channel_id().map(str::to_owned);
+    let channel_id = context.service.routed_channel_id();
     let args: serde_json::Map<String, serde_json::Value> = context.arguments.unwrap_or_default();
 
     let input: WaitInput =
@@ -61,13 +83,84 @@ pub async fn handle(
             )
         })?;
 
+    let wake_at = input
+        .wake_at
+        .as_deref()
+        .map(|raw| {
+            parse_wake_at(raw, Utc::now()).ok_or_else(|| {
+                rmcp::ErrorData::invalid_params(
+                    format!("wake_at '{raw}' must be a future HH:MM or RFC 3339 time"),
+                    None,
+                )
+            })
+        })
+        .transpose()?;
+    let wake_instruction = input
+        .wake_instruction
+        .clone()
+        .filter(|inst| !inst.trim().is_empty())
+        .unwrap_or_else(|| state.config.standby.wake_instruction.clone());
+    // A scheduled wake-up replaces the timeout in what the operator sees.
+    let shown_timeout = if wake_at.is_some() {
+        0
+    } else {
+        input.timeout_seconds
+    };
+    let wake_line = wake_at.map(|at| {
+        format!(
+            "\u{23f0} Wakes {} unless resumed sooner",
+            blocks::slack_time(at)
+        )
+    });
+
     let span = info_span!(
         "wait_for_instruction",
         timeout_seconds = input.timeout_seconds,
+        wake_at = wake_at.map(|at| at.to_rfc3339()),
     );
 
     async move {
-        // ── Early Slack channel check (T067 / S041) ────────
+        // ── Resolve active session ───────────────────────────
+        // Pin the call to the connection's own session when known: the
+        // `?session_id=<id>` override in ACP mode, or the session created on
+        // connect. This also finds a session that was just paused, which
+        // waits here until resumed. Fall back to the first active session.
+        let session_repo = SessionRepo::new(Arc::clone(&state.db));
+        let session = if let Some(sid) = context.service.bound_session_id() {
+            session_repo
+                .get_by_id(sid)
+                .await
+                .map_err(|err| {
+                    rmcp::ErrorData::internal_error(format!("failed to query session: {err}"), None)
+                })?
+                .ok_or_else(|| rmcp::ErrorData::internal_error("session not found", None))?
+        } else {
+            let sessions = session_repo.list_active().await.map_err(|err| {
+                rmcp::ErrorData::internal_error(
+                    format!("failed to query active sessions: {err}"),
+                    None,
+                )
+            })?;
+            sessions
+                .into_iter()
+                .next()
+                .ok_or_else(|| rmcp::ErrorData::internal_error("no active session found", None))?
+        };
+
+        // ── Queued instructions resume the agent at once ─────
+        // A paused session waits here for resume, not for new work.
+        if session.status != SessionStatus::Paused {
+            let mut queued = state.instruction_queue.take_all(&session.id).into_iter();
+            if let Some(first) = queued.next() {
+                let response = resume_from_queue(&state, &session, first, queued.collect()).await;
+                let _ = session_repo
+                    .update_last_activity(&session.id, Some("wait_for_instruction".to_owned()))
+                    .await;
+                return wait_result(&response);
+            }
+        }
+
+        // ── Slack channel check (T067 / S041) ──────────────
         // Return a descriptive error instead of blocking indefinitely when
         // no Slack channel is configured for this session.
         if state.slack.is_none() || channel_id.is_none() {
@@ -95,31 +188,6 @@ pub async fn handle(
                 rmcp::model::Content::text(format!("{error_code}: {error_message}"))
             })]));
         }
-        // ── Resolve active session ───────────────────────────
-        // In ACP mode the agent subprocess supplies `?session_id=<id>` so we
-        // can pin the tool call to the exact session. Fall back to the first
-        // active session for backwards-compatible MCP mode.
-        let session_repo = SessionRepo::new(Arc::clone(&state.db));
-        let session = if let Some(sid) = context.service.session_id_override() {
-            session_repo
-                .get_by_id(sid)
-                .await
-                .map_err(|err| {
-                    rmcp::ErrorData::internal_error(format!("failed to query session: {err}"), None)
-                })?
-                .ok_or_else(|| rmcp::ErrorData::internal_error("session not found", None))?
-        } else {
-            let sessions = session_repo.list_active().await.map_err(|err| {
-                rmcp::ErrorData::internal_error(
-                    format!("failed to query active sessions: {err}"),
-                    None,
-                )
-            })?;
-            sessions
-                .into_iter()
-                .next()
-                .ok_or_else(|| rmcp::ErrorData::internal_error("no active session found", None))?
-        };
 
         // S037: capture session thread_ts so the standby notification goes
         // to the session's dedicated Slack thread.
@@ -132,32 +200,37 @@ pub async fn handle(
         // US17: when the session lives in a thread, post plain text and
         // register a thread-reply fallback for @-mention decisions.
         let is_threaded = session_thread_ts.is_some();
+        let mut anchor: Option<Anchor> = None;
 
         if let (Some(ref slack), Some(ref ch)) = (&state.slack, &channel_id) {
             let channel = SlackChannelId(ch.clone());
 
             if is_threaded {
-                let text_body = blocks::build_text_only_wait(&input.message, input.timeout_seconds);
+                let mut text_body = blocks::build_text_only_wait(&input.message, shown_timeout);
+                if let Some(ref line) = wake_line {
+                    text_body.push('\n');
+                    text_body.push_str(line);
+                }
                 let msg = SlackMessage {
                     channel,
                     text: Some(text_body),
                     blocks: None,
                     thread_ts: session_thread_ts.clone(),
                 };
-                if let Err(err) = slack.enqueue(msg).await {
-                    warn!(%err, "failed to enqueue text-only wait message");
-                }
+                anchor = Anchor::post(slack, msg).await;
             } else {
                 let mut message_blocks = vec![blocks::text_section(&format!(
                     "\u{23f8}\u{fe0f} *Agent Waiting*\n{}",
                     &input.message,
                 ))];
-                if input.timeout_seconds > 0 {
+                if shown_timeout > 0 {
                     message_blocks.push(blocks::text_section(&format!(
-                        "\u{23f1}\u{fe0f} Timeout: {}s",
-                        input.timeout_seconds,
+                        "\u{23f1}\u{fe0f} Timeout: {shown_timeout}s"
                     )));
                 }
+                if let Some(ref line) = wake_line {
+                    message_blocks.push(blocks::text_section(line));
+                }
                 message_blocks.push(blocks::wait_buttons(&session.id));
 
                 let msg = SlackMessage {
@@ -169,9 +242,7 @@ pub async fn handle(
                     blocks: Some(message_blocks),
                     thread_ts: None,
                 };
-                if let Err(err) = slack.enqueue(msg).await {
-                    warn!(%err, "failed to enqueue wait status to slack");
-                }
+                anchor = Anchor::post(slack, msg).await;
             }
         } else {
             warn!("slack not configured; wait will block without notification");
@@ -210,6 +281,7 @@ pub async fn handle(
                 if registered {
                     let state_fb = Arc::clone(&state);
                     let sid = session.id.clone();
+                    let anchor_fb = anchor.clone();
                     tokio::spawn(async move {
                         let timeout_secs = state_fb.config.timeouts.wait_seconds;
                         let wait_future = async { reply_rx.await.ok() };
@@ -235,17 +307,31 @@ pub async fn handle(
                             };
                             match decision.keyword.as_str() {
                                 "resume" | "continue" => {
-                                    if let Err(err) = state_fb.driver.resolve_wait(&sid, inst).await
+                                    // The pending wait is an MCP oneshot regardless of
+                                    // the session's protocol, so resolve it through MCP.
+                                    if let Err(err) =
+                                        state_fb.mcp_driver().resolve_wait(&sid, inst).await
                                     {
                                         warn!(
                                             session_id = sid,
                                             %err,
                                             "US17: failed to resolve wait from thread reply"
                                         );
+                                    } else if let (Some(slack), Some(anchor)) =
+                                        (state_fb.slack.as_ref(), anchor_fb.as_ref())
+                                    {
+                                        anchor
+                                            .resolve(
+                                                slack,
+                                                "\u{25b6}\u{fe0f} *Resumed* from thread reply",
+                                            )
+                                            .await;
                                     }
                                 }
                                 "stop" => {
-                                    if let Err(err) = state_fb.driver.interrupt(&sid).await {
+                                    if let Err(err) =
+                                        state_fb.driver_for(&sid).await.interrupt(&sid).await
+                                    {
                                         warn!(
                                             session_id = sid,
                                             %err,
@@ -284,7 +370,34 @@ pub async fn handle(
             input.timeout_seconds
         };
 
-        let response = if effective_timeout == 0 {
+        let response = if let Some(at) = wake_at {
+            let wake_in = (at - Utc::now()).to_std().unwrap_or_default();
+            match tokio::time::timeout(wake_in, rx).await {
+                Ok(Ok(resp)) => resp,
+                Ok(Err(_)) => WaitResponse {
+                    status: "timeout".to_owned(),
+                    instruction: None,
+                },
+                Err(_elapsed) => {
+                    info!(session_id = %session.id, "standby woke on schedule");
+                    if let (Some(slack), Some(anchor)) = (state.slack.as_ref(), anchor.as_ref()) {
+                        anchor
+                            .resolve(
+                                slack,
+                                &format!(
+                                    "\u{23f0} *Woke on schedule*: {}",
+                                    truncate_text(&wake_instruction, 200)
+                                ),
+                            )
+                            .await;
+                    }
+                    WaitResponse {
+                        status: "resumed".to_owned(),
+                        instruction: Some(wake_instruction),
+                    }
+                }
+            }
+        } else if effective_timeout == 0 {
             // Indefinite wait — no timeout.
             match rx.await {
                 Ok(resp) => resp,
@@ -312,22 +425,31 @@ pub async fn handle(
                     );
 
                     // Notify Slack of timeout.
-                    if let (Some(ref slack), Some(ref ch)) = (&state.slack, &channel_id) {
-                        let channel = SlackChannelId(ch.clone());
-                        let msg = SlackMessage {
-                            channel,
-                            text: Some(format!(
-                                "\u{23f1}\u{fe0f} Wait timed out after {effective_timeout}s"
-                            )),
-                            blocks: Some(vec![blocks::severity_section(
-                                "warning",
-                                &format!(
-                                    "Wait timed out after {effective_timeout}s — agent resuming"
-                                ),
-                            )]),
-                            thread_ts: session_thread_ts.clone(),
-                        };
-                        let _ = slack.enqueue(msg).await;
+                    if let Some(ref slack) = state.slack {
+                        let text = format!("\u{23f1}\u{fe0f} Wait timed out after {effective_timeout}s");
+                        let notice = vec![blocks::severity_section(
+                            "warning",
+                            &format!("Wait timed out after {effective_timeout}s — agent resuming"),
+                        )];
+                        if let Some(ref anchor) = anchor {
+                            anchor
+                                .resolve(
+                                    slack,
+                                    &format!(
+                                        "\u{23f1}\u{fe0f} *Timed out* after {effective_timeout}s \u{2014} agent resumed"
+                                    ),
+                                )
+                                .await;
+                            anchor.follow_up(slack, text, Some(notice)).await;
+                        } else if let Some(ref ch) = channel_id {
+                            let msg = SlackMessage {
+                                channel: SlackChannelId(ch.clone()),
+                                text: Some(text),
+                                blocks: Some(notice),
+                                thread_ts: session_thread_ts.clone(),
+                            };
+                            util::post_notice(&state, &session.id, msg);
+                        }
                     }
 
                     WaitResponse {
@@ -355,24 +477,87 @@ pub async fn handle(
             "wait_for_instruction resolved"
         );
 
-        // ── Build response ───────────────────────────────────
-        let mut response_json = serde_json::json!({
-            "status": response.status,
-        });
-        if let Some(ref inst) = response.instruction {
-            response_json["instruction"] = serde_json::Value::String(inst.clone());
-        }
-
-        Ok(CallToolResult::success(vec![rmcp::model::Content::json(
-            response_json,
-        )
-        .map_err(|err| {
-            rmcp::ErrorData::internal_error(
-                format!("failed to serialize wait_for_instruction response: {err}"),
-                None,
-            )
-        })?]))
+        wait_result(&response)
     }
     .instrument(span)
     .await
 }
+
+/// Resume `session` with the first queued instruction, delivering `rest` as
+/// steering in order, and tell the operator in the session thread.
+async fn resume_from_queue(
+    state: &AppState,
+    session: &Session,
+    first: String,
+    rest: Vec<String>,
+) -> WaitResponse {
+    for instruction in &rest {
+        if let Err(err) = steer::steer_session(state, session, instruction).await {
+            warn!(%err, session_id = %session.id, "failed to deliver queued instruction");
+        }
+    }
+    info!(
+        session_id = %session.id,
+        steered = rest.len(),
+        "standby resolved from instruction queue"
+    );
+
+    if let Some(ref channel_id) = session.channel_id {
+        let mut text = format!(
+            "\u{25b6}\u{fe0f} Agent resumed from the instruction queue: {}",
+            truncate_text(&first, 200)
+        );
+        if !rest.is_empty() {
+            let _ = write!(text, " ({} more delivered as steering)", rest.len());
+        }
+        let msg = SlackMessage {
+            channel: SlackChannelId(channel_id.clone()),
+            text: Some(text),
+            blocks: None,
+            thread_ts: session
+                .thread_ts
+                .clone()
+                .map(slack_morphism::prelude::SlackTs),
+        };
+        util::post_notice(state, &session.id, msg);
+    }
+
+    WaitResponse {
+        status: "resumed".to_owned(),
+        instruction: Some(first),
+    }
+}
+
+/// Parse a `wake_at` value as of `now`: `HH:MM` is the next occurrence of
+/// that server-local time, anything else must be an RFC 3339 timestamp
+/// after `now`.
+fn parse_wake_at(raw: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    if let Ok(time) = NaiveTime::parse_from_str(raw, "%H:%M") {
+        return Some(scheduled_apply::next_occurrence(now, time));
+    }
+    DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(|at| at.with_timezone(&Utc))
+        .filter(|at| *at > now)
+}
+
+/// Build the tool result for a resolved wait.
+fn wait_result(response: &WaitResponse) -> Result<CallToolResult, rmcp::ErrorData> {
+    let mut response_json = serde_json::json!({
+        "status": response.status,
+    });
+    if let Some(ref inst) = response.instruction {
+        response_json["instruction"] = serde_json::Value::String(inst.clone());
+    }
+
+    Ok(CallToolResult::success(vec![rmcp::model::Content::json(
+        response_json,
+    )
+    .map_err(|err| {
+        rmcp::ErrorData::internal_error(
+            format!("failed to serialize wait_for_instruction response: {err}"),
+            None,
+        )
+    })?]))
+}
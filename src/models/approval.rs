@@ -1,11 +1,17 @@
 //! Approval request model for code proposal review.
 
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-/// Risk classification for a code proposal.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+use super::short_id;
+
+/// Risk classification for a code proposal, ordered from least to most
+/// risky.
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, PartialOrd, Ord,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum RiskLevel {
     /// Low-risk change unlikely to cause issues.
@@ -16,6 +22,18 @@ pub enum RiskLevel {
     Critical,
 }
 
+impl RiskLevel {
+    /// Returns the `snake_case` string representation stored in the database.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::High => "high",
+            Self::Critical => "critical",
+        }
+    }
+}
+
 /// Lifecycle status for an approval request.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -40,6 +58,8 @@ pub enum ApprovalStatus {
 pub struct ApprovalRequest {
     /// Unique record identifier.
     pub id: String,
+    /// Human-readable ID shown in Slack, e.g. `apr-9mxq`.
+    pub short_id: String,
     /// Owning session identifier.
     pub session_id: String,
     /// Concise summary of the proposal.
@@ -58,10 +78,21 @@ pub struct ApprovalRequest {
     pub original_hash: String,
     /// Slack message timestamp for updates.
     pub slack_ts: Option<String>,
+    /// Slack thread the request's follow-ups are posted in: the session
+    /// thread, or the request message itself when posted at channel root.
+    pub thread_ts: Option<String>,
     /// Creation timestamp.
     pub created_at: DateTime<Utc>,
     /// Timestamp when the approved diff was applied.
     pub consumed_at: Option<DateTime<Utc>>,
+    /// When the server applies the approved diff itself, as scheduled with
+    /// `apply-at`. `None` leaves applying it to the agent's `check_diff`.
+    pub apply_after: Option<DateTime<Utc>>,
+    /// When the file was seen to change on disk after the diff was
+    /// proposed. A stale diff must be re-proposed against the new contents.
+    pub stale_at: Option<DateTime<Utc>>,
+    /// Why the operator rejected the request, when they said.
+    pub decision_reason: Option<String>,
 }
 
 impl ApprovalRequest {
@@ -76,8 +107,10 @@ impl ApprovalRequest {
         risk_level: RiskLevel,
         original_hash: String,
     ) -> Self {
+        let id = Uuid::new_v4().to_string();
         Self {
-            id: Uuid::new_v4().to_string(),
+            short_id: short_id::generate(short_id::APPROVAL_PREFIX, &id, 0),
+            id,
             session_id,
             title,
             description,
@@ -87,8 +120,12 @@ impl ApprovalRequest {
             status: ApprovalStatus::Pending,
             original_hash,
             slack_ts: None,
+            thread_ts: None,
             created_at: Utc::now(),
             consumed_at: None,
+            apply_after: None,
+            stale_at: None,
+            decision_reason: None,
         }
     }
 }
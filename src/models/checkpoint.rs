@@ -26,6 +26,10 @@ pub struct Checkpoint {
     pub workspace_root: String,
     /// Session's progress snapshot at checkpoint time.
     pub progress_snapshot: Option<Vec<ProgressItem>>,
+    /// Taken by the periodic `[checkpoints]` task rather than by an
+    /// operator or agent; only these are pruned by default.
+    #[serde(default)]
+    pub automatic: bool,
     /// Creation timestamp.
     pub created_at: DateTime<Utc>,
 }
@@ -49,6 +53,7 @@ impl Checkpoint {
             file_hashes,
             workspace_root,
             progress_snapshot,
+            automatic: false,
             created_at: Utc::now(),
         }
     }
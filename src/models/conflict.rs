@@ -0,0 +1,136 @@
+//! Apply conflicts the operator arbitrates (`check_diff`).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{AppError, Result};
+
+/// Why an approved diff could not be applied as proposed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// The file changed on disk after the diff was proposed.
+    HashMismatch,
+    /// The diff's hunks do not match the file's current content.
+    HunkConflict,
+}
+
+impl ConflictKind {
+    /// Database representation.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::HashMismatch => "hash_mismatch",
+            Self::HunkConflict => "hunk_conflict",
+        }
+    }
+
+    /// Parse the database representation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` for an unknown kind.
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "hash_mismatch" => Ok(Self::HashMismatch),
+            "hunk_conflict" => Ok(Self::HunkConflict),
+            other => Err(AppError::Db(format!("invalid conflict kind: {other}"))),
+        }
+    }
+}
+
+/// How the operator settled a conflict.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolution {
+    /// Apply the diff over the changed file anyway.
+    ForceApply,
+    /// Hand the agent a three-way merge view to re-propose from.
+    Merge,
+    /// Drop the diff.
+    Reject,
+    /// Nobody decided before the approval timeout.
+    Expired,
+}
+
+impl ConflictResolution {
+    /// Database representation.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ForceApply => "force_apply",
+            Self::Merge => "merge",
+            Self::Reject => "reject",
+            Self::Expired => "expired",
+        }
+    }
+
+    /// Parse the database representation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` for an unknown resolution.
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "force_apply" => Ok(Self::ForceApply),
+            "merge" => Ok(Self::Merge),
+            "reject" => Ok(Self::Reject),
+            "expired" => Ok(Self::Expired),
+            other => Err(AppError::Db(format!(
+                "invalid conflict resolution: {other}"
+            ))),
+        }
+    }
+}
+
+/// An approved diff `check_diff` could not apply, waiting for the operator
+/// to force it, ask for a merge, or reject it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApplyConflict {
+    /// Unique record identifier (UUID v4 prefixed `conflict:`).
+    pub id: String,
+    /// Approval request whose diff conflicts.
+    pub request_id: String,
+    /// Session that proposed the diff.
+    pub session_id: String,
+    /// Workspace-relative path of the target file.
+    pub file_path: String,
+    /// What went wrong.
+    pub kind: ConflictKind,
+    /// Error detail shown to the operator and the agent.
+    pub detail: String,
+    /// The operator's decision; `None` while pending.
+    pub resolution: Option<ConflictResolution>,
+    /// Slack user ID of the operator who decided.
+    pub resolved_by: Option<String>,
+    /// When the conflict was raised.
+    pub created_at: DateTime<Utc>,
+    /// When it was resolved.
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl ApplyConflict {
+    /// A pending conflict on `request_id`, raised now.
+    #[must_use]
+    pub fn new(
+        request_id: String,
+        session_id: String,
+        file_path: String,
+        kind: ConflictKind,
+        detail: String,
+    ) -> Self {
+        Self {
+            id: format!("conflict:{}", Uuid::new_v4()),
+            request_id,
+            session_id,
+            file_path,
+            kind,
+            detail,
+            resolution: None,
+            resolved_by: None,
+            created_at: Utc::now(),
+            resolved_at: None,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Paired device model for the mobile web approver.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A browser paired with an operator through `/intercom pair`.
+///
+/// A record starts as a pairing offer, identified by the hash of the token
+/// in the QR code, and becomes a device when a browser redeems it. Only
+/// token hashes are stored; the tokens themselves live in the QR code and
+/// the device's cookie.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PairedDevice {
+    /// Unique record identifier (UUID v4 prefixed `device:`).
+    pub id: String,
+    /// Slack user ID of the operator the device acts for.
+    pub user_id: String,
+    /// Browser user agent recorded when the device paired.
+    pub label: Option<String>,
+    /// When the pairing offer was created.
+    pub created_at: DateTime<Utc>,
+    /// When a browser redeemed the offer. `None` while it is an offer.
+    pub paired_at: Option<DateTime<Utc>>,
+    /// When the offer or, once paired, the device stops working.
+    pub expires_at: DateTime<Utc>,
+    /// Last authenticated request from the device.
+    pub last_seen_at: Option<DateTime<Utc>>,
+}
+
+impl PairedDevice {
+    /// Construct a pairing offer for `user_id` expiring at `expires_at`.
+    #[must_use]
+    pub fn offer(user_id: String, expires_at: DateTime<Utc>) -> Self {
+        Self {
+            id: format!("device:{}", Uuid::new_v4()),
+            user_id,
+            label: None,
+            created_at: Utc::now(),
+            paired_at: None,
+            expires_at,
+            last_seen_at: None,
+        }
+    }
+
+    /// Short form of the identifier for display and `pair revoke`.
+    #[must_use]
+    pub fn short_id(&self) -> &str {
+        let id = self.id.strip_prefix("device:").unwrap_or(&self.id);
+        id.get(..8).unwrap_or(id)
+    }
+}
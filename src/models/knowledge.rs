@@ -0,0 +1,83 @@
+//! Shared knowledge note model.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Curation state of a knowledge note.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KnowledgeStatus {
+    /// Proposed by an agent, awaiting the operator.
+    Pending,
+    /// Approved by the operator; readable by every session.
+    Approved,
+    /// Rejected by the operator; never shown to agents.
+    Rejected,
+}
+
+impl KnowledgeStatus {
+    /// Database and wire representation.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+/// A note an agent proposed for the shared knowledge base with
+/// `propose_knowledge`.
+///
+/// Notes outlive the session that proposed them. Only approved notes are
+/// served to agents, through the `intercom://knowledge` resource.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KnowledgeNote {
+    /// Unique record identifier (UUID v4 prefixed `note:`).
+    pub id: String,
+    /// Short summary, e.g. "How to run the e2e suite".
+    pub title: String,
+    /// Note body (Markdown).
+    pub body: String,
+    /// Free-form labels agents can filter on.
+    pub tags: Vec<String>,
+    /// Session that proposed the note.
+    pub proposed_by: String,
+    /// Workspace of the proposing session.
+    pub workspace_root: String,
+    /// Curation state.
+    pub status: KnowledgeStatus,
+    /// Creation timestamp.
+    pub created_at: DateTime<Utc>,
+    /// When the operator decided. `None` while pending.
+    pub decided_at: Option<DateTime<Utc>>,
+    /// Slack user who decided. `None` while pending.
+    pub decided_by: Option<String>,
+}
+
+impl KnowledgeNote {
+    /// Construct a new pending note with a generated identifier.
+    #[must_use]
+    pub fn new(
+        title: String,
+        body: String,
+        tags: Vec<String>,
+        proposed_by: String,
+        workspace_root: String,
+    ) -> Self {
+        Self {
+            id: format!("note:{}", Uuid::new_v4()),
+            title,
+            body,
+            tags,
+            proposed_by,
+            workspace_root,
+            status: KnowledgeStatus::Pending,
+            created_at: Utc::now(),
+            decided_at: None,
+            decided_by: None,
+        }
+    }
+}
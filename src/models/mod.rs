@@ -2,11 +2,23 @@
 
 pub mod approval;
 pub mod checkpoint;
+pub mod conflict;
+pub mod device;
 pub mod inbox;
 pub mod intercom_queue;
+pub mod knowledge;
+pub mod path_lock;
 pub mod policy;
+pub mod preferences;
 pub mod progress;
 pub mod prompt;
+pub mod rejection;
+pub mod relay;
+pub mod run;
 pub mod session;
+pub mod session_context;
+pub mod session_note;
+pub mod short_id;
+pub mod snapshot;
 pub mod stall;
 pub mod steering;
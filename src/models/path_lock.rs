@@ -0,0 +1,123 @@
+//! Workspace path locks coordinating concurrent sessions (`acquire_lock`).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{AppError, Result};
+
+/// Path of a lock covering the whole workspace.
+pub const WORKSPACE_PATH: &str = ".";
+
+/// Whether a lock is held or waiting for a conflicting one to go.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LockStatus {
+    /// The session owns the path.
+    Held,
+    /// The session waits for the path, in request order.
+    Queued,
+}
+
+impl LockStatus {
+    /// Database representation.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Held => "held",
+            Self::Queued => "queued",
+        }
+    }
+
+    /// Parse the database representation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` for anything but `held` or `queued`.
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "held" => Ok(Self::Held),
+            "queued" => Ok(Self::Queued),
+            other => Err(AppError::Db(format!("invalid lock status: {other}"))),
+        }
+    }
+}
+
+/// A session's claim on a file or directory of its workspace.
+///
+/// Locks are advisory: they keep sessions that ask from editing the same
+/// paths at once, and do not stop a session that never asks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PathLock {
+    /// Unique record identifier (UUID v4 prefixed `lock:`).
+    pub id: String,
+    /// Session holding or waiting for the lock.
+    pub session_id: String,
+    /// Canonical workspace root the path is relative to.
+    pub workspace_root: String,
+    /// `/`-separated path relative to the root; [`WORKSPACE_PATH`] for
+    /// the whole workspace. A directory covers everything below it.
+    pub path: String,
+    /// Held or queued.
+    pub status: LockStatus,
+    /// Why the session wants the path, shown to the operator.
+    pub reason: Option<String>,
+    /// When the session asked for the lock; orders the queue.
+    pub requested_at: DateTime<Utc>,
+    /// When the lock was granted. `None` while queued.
+    pub acquired_at: Option<DateTime<Utc>>,
+}
+
+impl PathLock {
+    /// A queued request by `session_id` for `path`.
+    #[must_use]
+    pub fn queued(
+        session_id: String,
+        workspace_root: String,
+        path: String,
+        reason: Option<String>,
+    ) -> Self {
+        Self {
+            id: format!("lock:{}", Uuid::new_v4()),
+            session_id,
+            workspace_root,
+            path,
+            status: LockStatus::Queued,
+            reason,
+            requested_at: Utc::now(),
+            acquired_at: None,
+        }
+    }
+
+    /// Whether this lock and one on `path` in the same workspace claim a
+    /// common file.
+    #[must_use]
+    pub fn overlaps(&self, path: &str) -> bool {
+        paths_overlap(&self.path, path)
+    }
+}
+
+/// Whether two workspace-relative lock paths claim a common file: they are
+/// equal, or one is a directory containing the other.
+#[must_use]
+pub fn paths_overlap(a: &str, b: &str) -> bool {
+    a == WORKSPACE_PATH || b == WORKSPACE_PATH || a == b || is_below(a, b) || is_below(b, a)
+}
+
+/// Whether lock path `path` lies inside directory `dir`.
+#[must_use]
+pub fn is_below(path: &str, dir: &str) -> bool {
+    dir == WORKSPACE_PATH
+        || path
+            .strip_prefix(dir)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Outcome of trying to grant a queued lock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockGrant {
+    /// The request now holds its path.
+    Acquired(PathLock),
+    /// The request keeps waiting behind this held or earlier queued lock.
+    Blocked(PathLock),
+}
@@ -17,6 +17,50 @@ pub struct FilePatterns {
     pub read: Vec<String>,
 }
 
+/// How a proxied downstream tool call is handled.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyAction {
+    /// Forward the call without asking the operator.
+    AutoApprove,
+    /// Hold the call until the operator accepts it.
+    #[default]
+    RequireApproval,
+    /// Refuse the call outright.
+    Deny,
+}
+
+/// Classification rules for calls to proxied downstream tools.
+///
+/// Patterns are globs over the qualified `<server>.<tool>` name
+/// (e.g. `fs.read_*`, `shell.exec`). `deny` wins over `require_approval`,
+/// which wins over `auto_approve`; calls matching nothing get
+/// `default_action`. Calls are recorded at `low` risk unless they match
+/// `critical_risk` or `high_risk`; a `critical` call is never
+/// auto-approved.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct ProxyRules {
+    /// Calls forwarded without operator approval (requires `enabled`).
+    #[serde(default)]
+    pub auto_approve: Vec<String>,
+    /// Calls that always need operator approval.
+    #[serde(default)]
+    pub require_approval: Vec<String>,
+    /// Calls that are always refused.
+    #[serde(default)]
+    pub deny: Vec<String>,
+    /// Action for calls matching no pattern.
+    #[serde(default)]
+    pub default_action: ProxyAction,
+    /// Calls whose approval requests carry `high` risk.
+    #[serde(default)]
+    pub high_risk: Vec<String>,
+    /// Calls whose approval requests carry `critical` risk.
+    #[serde(default)]
+    pub critical_risk: Vec<String>,
+}
+
 /// Deserialize `chat.tools.terminal.autoApprove` from either:
 /// - A **map** `{ "pattern": true }` or `{ "pattern": { "approve": true, ... } }`
 ///   — the format used by VS Code (`.code-workspace`, `.vscode/settings.json`)
@@ -109,6 +153,9 @@ pub struct WorkspacePolicy {
     /// Interval for summary notifications (seconds).
     #[serde(default = "default_summary_interval")]
     pub summary_interval_seconds: u64,
+    /// Classification of proxied downstream tool calls.
+    #[serde(default)]
+    pub proxy: ProxyRules,
 }
 
 fn default_risk_threshold() -> RiskLevel {
@@ -129,6 +176,7 @@ impl Default for WorkspacePolicy {
             risk_level_threshold: default_risk_threshold(),
             log_auto_approved: false,
             summary_interval_seconds: default_summary_interval(),
+            proxy: ProxyRules::default(),
         }
     }
 }
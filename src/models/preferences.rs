@@ -0,0 +1,137 @@
+//! Per-operator notification preferences.
+
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::SlackDetailLevel;
+use crate::{AppError, Result};
+
+/// Where an operator receives notifications from their sessions.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationDelivery {
+    /// Follow the `[broadcast]` routing (default).
+    #[default]
+    Channel,
+    /// A direct message instead of the session thread.
+    Dm,
+    /// The session thread and a direct message.
+    Both,
+}
+
+impl NotificationDelivery {
+    /// Database and wire representation.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Channel => "channel",
+            Self::Dm => "dm",
+            Self::Both => "both",
+        }
+    }
+
+    /// Parse the database and wire representation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` for anything but `channel`, `dm` or `both`.
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "channel" => Ok(Self::Channel),
+            "dm" => Ok(Self::Dm),
+            "both" => Ok(Self::Both),
+            other => Err(AppError::Config(format!(
+                "invalid delivery '{other}'; expected channel, dm or both"
+            ))),
+        }
+    }
+}
+
+/// A daily window of server-local time during which an operator is not
+/// sent direct messages other than errors. Wraps past midnight when
+/// `start` is later than `end`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QuietHours {
+    /// Time the window opens.
+    pub start: NaiveTime,
+    /// Time the window closes.
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    /// Parse an `HH:MM-HH:MM` window.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` for a malformed or empty window.
+    pub fn parse(range: &str) -> Result<Self> {
+        let invalid =
+            || AppError::Config(format!("invalid quiet hours '{range}'; use HH:MM-HH:MM"));
+        let (start, end) = range.trim().split_once('-').ok_or_else(invalid)?;
+        let start = NaiveTime::parse_from_str(start.trim(), "%H:%M").map_err(|_| invalid())?;
+        let end = NaiveTime::parse_from_str(end.trim(), "%H:%M").map_err(|_| invalid())?;
+        if start == end {
+            return Err(AppError::Config(
+                "quiet hours must start and end at different times".into(),
+            ));
+        }
+        Ok(Self { start, end })
+    }
+
+    /// Whether local time `now` falls inside the window.
+    #[must_use]
+    pub fn contains(&self, now: NaiveTime) -> bool {
+        if self.start < self.end {
+            (self.start..self.end).contains(&now)
+        } else {
+            now >= self.start || now < self.end
+        }
+    }
+}
+
+impl std::fmt::Display for QuietHours {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )
+    }
+}
+
+/// Notification preferences an operator set with `/intercom prefs`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UserPreferences {
+    /// Slack user ID of the operator.
+    pub user_id: String,
+    /// Detail level for the operator's sessions. `None` uses the global
+    /// `slack_detail_level`.
+    pub detail_level: Option<SlackDetailLevel>,
+    /// Where the operator's session notifications go.
+    pub delivery: NotificationDelivery,
+    /// When the operator is not sent direct messages.
+    pub quiet_hours: Option<QuietHours>,
+    /// Last change.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl UserPreferences {
+    /// Preferences of `user_id` that change nothing.
+    #[must_use]
+    pub fn new(user_id: impl Into<String>) -> Self {
+        Self {
+            user_id: user_id.into(),
+            detail_level: None,
+            delivery: NotificationDelivery::Channel,
+            quiet_hours: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Whether local time `now` is inside the operator's quiet hours.
+    #[must_use]
+    pub fn is_quiet(&self, now: NaiveTime) -> bool {
+        self.quiet_hours.is_some_and(|quiet| quiet.contains(now))
+    }
+}
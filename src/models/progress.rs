@@ -41,3 +41,10 @@ pub fn validate_snapshot(items: &[ProgressItem]) -> Result<()> {
     }
     Ok(())
 }
+
+/// Whether a snapshot reports its work finished: at least one item, and
+/// every item [`Done`](ProgressStatus::Done).
+#[must_use]
+pub fn all_done(items: &[ProgressItem]) -> bool {
+    !items.is_empty() && items.iter().all(|item| item.status == ProgressStatus::Done)
+}
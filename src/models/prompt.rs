@@ -1,11 +1,12 @@
 //! Continuation prompt model for forwarded agent prompts.
 
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 /// Category of a continuation prompt.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum PromptType {
     /// Standard continuation prompt.
@@ -18,8 +19,21 @@ pub enum PromptType {
     ResourceWarning,
 }
 
+impl PromptType {
+    /// Returns the `snake_case` string representation stored in the database.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Continuation => "continuation",
+            Self::Clarification => "clarification",
+            Self::ErrorRecovery => "error_recovery",
+            Self::ResourceWarning => "resource_warning",
+        }
+    }
+}
+
 /// Operator decision on a forwarded prompt.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum PromptDecision {
     /// Continue with current task.
@@ -30,6 +44,18 @@ pub enum PromptDecision {
     Stop,
 }
 
+impl PromptDecision {
+    /// Returns the `snake_case` string representation returned to agents.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Continue => "continue",
+            Self::Refine => "refine",
+            Self::Stop => "stop",
+        }
+    }
+}
+
 /// A forwarded meta-prompt from an agent requiring operator decision.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -52,6 +78,8 @@ pub struct ContinuationPrompt {
     pub instruction: Option<String>,
     /// Slack message timestamp.
     pub slack_ts: Option<String>,
+    /// Slack thread the prompt's follow-ups are posted in.
+    pub thread_ts: Option<String>,
     /// Creation timestamp.
     pub created_at: DateTime<Utc>,
 }
@@ -93,6 +121,7 @@ impl ContinuationPrompt {
             decision: None,
             instruction: None,
             slack_ts: None,
+            thread_ts: None,
             created_at: Utc::now(),
         }
     }
@@ -0,0 +1,141 @@
+//! Structured rejection feedback returned to agents.
+//!
+//! Operators reject a diff or stop a prompt with free text. So agents get
+//! guidance they can act on without interpreting prose, the text is read
+//! into a [`RejectionFeedback`] using a few light conventions:
+//!
+//! - A leading `<category>:` (e.g. `security: don't log the token`) sets the
+//!   [`RejectionCategory`]. The file-change watcher's `stale:` reasons
+//!   follow the same convention.
+//! - Lines starting with `-`, `*`, `•` or `1.`/`1)` are required changes.
+//! - `policy:<rule>` tokens (no space after the colon) reference the policy
+//!   rules the change breaks, e.g. `policy:protected_paths`.
+//!
+//! Everything else is the summary. A reason without any of these is still
+//! returned whole as the summary with category `other`.
+
+use serde::Serialize;
+
+/// Why a request was rejected or a prompt stopped.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectionCategory {
+    /// The change does more (or less) than was asked for.
+    Scope,
+    /// The change is wrong or breaks behavior.
+    Correctness,
+    /// Formatting, naming or structure.
+    Style,
+    /// The change weakens security.
+    Security,
+    /// The change breaks a workspace policy rule.
+    Policy,
+    /// The file changed on disk after the diff was proposed.
+    Stale,
+    /// No category given.
+    Other,
+}
+
+impl RejectionCategory {
+    /// Parse a category name, case-insensitively.
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "scope" => Some(Self::Scope),
+            "correctness" | "bug" => Some(Self::Correctness),
+            "style" => Some(Self::Style),
+            "security" => Some(Self::Security),
+            "policy" => Some(Self::Policy),
+            "stale" => Some(Self::Stale),
+            "other" => Some(Self::Other),
+            _ => None,
+        }
+    }
+}
+
+/// Machine-usable guidance derived from an operator's rejection reason.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct RejectionFeedback {
+    /// Why the request was rejected.
+    pub category: RejectionCategory,
+    /// The reason without its category prefix and required-change lines.
+    pub summary: String,
+    /// Changes the agent must make before proposing again, in order.
+    pub required_changes: Vec<String>,
+    /// Policy rules the change breaks, without the `policy:` prefix.
+    pub policy_rules: Vec<String>,
+}
+
+impl RejectionFeedback {
+    /// Read feedback from an operator's reason, if one was given.
+    #[must_use]
+    pub fn from_reason(reason: Option<&str>) -> Self {
+        let reason = reason.map(str::trim).unwrap_or_default();
+        let (explicit, body) = split_category(reason);
+
+        let mut summary = Vec::new();
+        let mut required_changes = Vec::new();
+        for line in body.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            match list_item(line) {
+                Some(item) => required_changes.push(item.to_owned()),
+                None => summary.push(line),
+            }
+        }
+
+        let mut policy_rules: Vec<String> = Vec::new();
+        for rule in reason.split_whitespace().filter_map(policy_rule) {
+            if !policy_rules.iter().any(|r| r == rule) {
+                policy_rules.push(rule.to_owned());
+            }
+        }
+
+        let category = explicit.unwrap_or(if policy_rules.is_empty() {
+            RejectionCategory::Other
+        } else {
+            RejectionCategory::Policy
+        });
+        Self {
+            category,
+            summary: summary.join(" "),
+            required_changes,
+            policy_rules,
+        }
+    }
+}
+
+/// Split a leading `<category>: ` off `reason`.
+fn split_category(reason: &str) -> (Option<RejectionCategory>, &str) {
+    let Some((head, rest)) = reason.split_once(':') else {
+        return (None, reason);
+    };
+    if !rest.starts_with(char::is_whitespace) {
+        return (None, reason);
+    }
+    match RejectionCategory::parse(head) {
+        Some(category) => (Some(category), rest.trim_start()),
+        None => (None, reason),
+    }
+}
+
+/// The text of a bulleted or numbered list item.
+fn list_item(line: &str) -> Option<&str> {
+    let rest = match line.strip_prefix(['-', '*', '\u{2022}']) {
+        Some(rest) => rest,
+        None => line
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .strip_prefix(['.', ')'])
+            .filter(|_| line.starts_with(|c: char| c.is_ascii_digit()))?,
+    };
+    rest.starts_with(char::is_whitespace)
+        .then(|| rest.trim())
+        .filter(|item| !item.is_empty())
+}
+
+/// The rule named by a `policy:<rule>` token.
+fn policy_rule(token: &str) -> Option<&str> {
+    let rule = token
+        .trim_start_matches(['(', '[', '`'])
+        .strip_prefix("policy:")?
+        .trim_end_matches(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '/' | '*')));
+    Some(rule).filter(|r| !r.is_empty())
+}
@@ -0,0 +1,49 @@
+//! Relay message model for session-to-session (agent-to-agent) exchange.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A structured message sent by one session to another via `relay_send`.
+///
+/// Relay messages are persisted until the recipient collects them with
+/// `relay_receive`, so a producer does not have to wait for its consumer to
+/// be connected.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RelayMessage {
+    /// Unique record identifier (UUID v4 prefixed `relay:`).
+    pub id: String,
+    /// Sending session identifier.
+    pub from_session_id: String,
+    /// Receiving session identifier.
+    pub to_session_id: String,
+    /// Optional label the recipient can filter on.
+    pub topic: Option<String>,
+    /// Arbitrary JSON payload.
+    pub payload: serde_json::Value,
+    /// Creation timestamp.
+    pub created_at: DateTime<Utc>,
+    /// When the recipient collected the message. `None` while pending.
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl RelayMessage {
+    /// Construct a new pending relay message with a generated identifier.
+    #[must_use]
+    pub fn new(
+        from_session_id: String,
+        to_session_id: String,
+        topic: Option<String>,
+        payload: serde_json::Value,
+    ) -> Self {
+        Self {
+            id: format!("relay:{}", Uuid::new_v4()),
+            from_session_id,
+            to_session_id,
+            topic,
+            payload,
+            created_at: Utc::now(),
+            delivered_at: None,
+        }
+    }
+}
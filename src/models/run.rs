@@ -0,0 +1,47 @@
+//! Named runs grouping related sessions (`/intercom run-start`).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A named group of sessions working on one multi-step effort, e.g.
+/// "release hardening".
+///
+/// While an operator's run is active, the sessions they start join it, and
+/// subtasks and restarts of those sessions inherit it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Run {
+    /// Unique record identifier.
+    pub id: String,
+    /// Name given with `run-start`.
+    pub name: String,
+    /// Slack user ID of the operator who started the run.
+    pub owner_user_id: String,
+    /// Channel `run-start` was run in.
+    pub channel_id: Option<String>,
+    /// When the run started.
+    pub started_at: DateTime<Utc>,
+    /// When the run ended; `None` while it is active.
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl Run {
+    /// An active run named `name`, started now by `owner_user_id`.
+    #[must_use]
+    pub fn new(name: String, owner_user_id: String, channel_id: Option<String>) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            name,
+            owner_user_id,
+            channel_id,
+            started_at: Utc::now(),
+            ended_at: None,
+        }
+    }
+
+    /// Whether the run has not ended.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.ended_at.is_none()
+    }
+}
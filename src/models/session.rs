@@ -1,10 +1,20 @@
 //! Session model and lifecycle helpers.
 
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::progress::ProgressItem;
+use super::short_id;
+use crate::{AppError, Result};
+
+/// Longest accepted session tag key.
+pub const MAX_TAG_KEY_LEN: usize = 32;
+
+/// Longest accepted session tag value.
+pub const MAX_TAG_VALUE_LEN: usize = 128;
 
 /// Lifecycle status for an agent session.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -40,7 +50,7 @@ pub enum SessionMode {
 pub enum ConnectivityStatus {
     /// Agent is actively communicating (stream messages or tool calls arriving).
     Online,
-    /// Agent process is alive but no recent activity.
+    /// Agent's transport dropped; steering is queued until it reconnects.
     Offline,
     /// Stall detector has flagged this session for inactivity.
     Stalled,
@@ -62,6 +72,8 @@ pub enum ProtocolMode {
 pub struct Session {
     /// Unique record identifier.
     pub id: String,
+    /// Human-readable ID shown in Slack, e.g. `ses-7f3k`.
+    pub short_id: String,
     /// Owning Slack user ID; immutable after creation.
     pub owner_user_id: String,
     /// Absolute path to the workspace directory for this session.
@@ -111,6 +123,46 @@ pub struct Session {
     /// with `"..."` if the prompt exceeds 80 characters. `None` for sessions
     /// created before this field was introduced.
     pub title: Option<String>,
+    /// End of the session's time box (`session-start --max-duration`).
+    ///
+    /// `None` for sessions without a budget, and cleared once the deadline
+    /// has been enforced.
+    pub deadline: Option<DateTime<Utc>>,
+    /// Length of the time box in seconds, kept after [`deadline`](Self::deadline)
+    /// is cleared so `session-restart` can give the new session the same budget.
+    pub max_duration_seconds: Option<i64>,
+    /// Whether the wrap-up warning for [`deadline`](Self::deadline) was sent.
+    pub wrap_up_sent: bool,
+    /// Issue the session works on (`session-start --issue`), e.g. `PROJ-123`,
+    /// `owner/repo#42`, or an issue URL.
+    pub issue_ref: Option<String>,
+    /// Session that spawned this one with `spawn_subtask`, if any. The
+    /// parent is told via steering when this session ends.
+    pub parent_session_id: Option<String>,
+    /// Whether non-critical Slack messages (status updates, broadcasts,
+    /// heartbeat notices) are suppressed (`/intercom mute`). Approvals and
+    /// prompts are always delivered.
+    pub muted: bool,
+    /// When a timed mute ends; `None` mutes until `/intercom unmute`.
+    pub muted_until: Option<DateTime<Utc>>,
+    /// When an operator hid the ended session from listings and recovery
+    /// (`agent-intercom-ctl session delete`). The row and its history are
+    /// kept; `session restore` clears this.
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Free-form `key=value` labels (`session-start --tag team=payments`)
+    /// used to attribute sessions to teams, cost centers or tickets.
+    pub tags: BTreeMap<String, String>,
+    /// Environment variables set for the agent process
+    /// (`session-start --env KEY=VAL`), with the values of secret-looking
+    /// names replaced by [`REDACTED`](crate::config::REDACTED).
+    pub env: BTreeMap<String, String>,
+    /// Run the session belongs to (`/intercom run-start`), if any.
+    pub run_id: Option<String>,
+    /// Implementation name the MCP client sent in its `initialize`
+    /// handshake (`clientInfo.name`), e.g. `claude-code`.
+    pub client_name: Option<String>,
+    /// Implementation version from the same handshake.
+    pub client_version: Option<String>,
 }
 
 impl SessionStatus {
@@ -127,6 +179,29 @@ impl SessionStatus {
     }
 }
 
+impl ConnectivityStatus {
+    /// Returns the `snake_case` string representation stored in the database.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Online => "online",
+            Self::Offline => "offline",
+            Self::Stalled => "stalled",
+        }
+    }
+
+    /// Colored indicator shown next to the status in Slack: green online,
+    /// yellow stalled, red offline.
+    #[must_use]
+    pub fn indicator(self) -> &'static str {
+        match self {
+            Self::Online => "\u{1f7e2}",
+            Self::Stalled => "\u{1f7e1}",
+            Self::Offline => "\u{1f534}",
+        }
+    }
+}
+
 impl ProtocolMode {
     /// Returns the `snake_case` string representation stored in the database.
     #[must_use]
@@ -148,8 +223,10 @@ impl Session {
         mode: SessionMode,
     ) -> Self {
         let now = Utc::now();
+        let id = Uuid::new_v4().to_string();
         Self {
-            id: Uuid::new_v4().to_string(),
+            short_id: short_id::generate(short_id::SESSION_PREFIX, &id, 0),
+            id,
             owner_user_id,
             workspace_root,
             status: SessionStatus::Created,
@@ -170,9 +247,36 @@ impl Session {
             restart_of: None,
             agent_session_id: None,
             title: None,
+            deadline: None,
+            max_duration_seconds: None,
+            wrap_up_sent: false,
+            issue_ref: None,
+            parent_session_id: None,
+            muted: false,
+            muted_until: None,
+            deleted_at: None,
+            tags: BTreeMap::new(),
+            env: BTreeMap::new(),
+            run_id: None,
+            client_name: None,
+            client_version: None,
         }
     }
 
+    /// Whether the session carries every `key=value` pair in `filters`.
+    #[must_use]
+    pub fn has_tags(&self, filters: &[(String, String)]) -> bool {
+        filters
+            .iter()
+            .all(|(key, value)| self.tags.get(key) == Some(value))
+    }
+
+    /// Whether non-critical Slack messages for this session are muted at `now`.
+    #[must_use]
+    pub fn is_muted(&self, now: DateTime<Utc>) -> bool {
+        self.muted && self.muted_until.is_none_or(|until| until > now)
+    }
+
     /// Determine whether a lifecycle transition is permitted.
     #[must_use]
     pub fn can_transition_to(&self, next: SessionStatus) -> bool {
@@ -192,6 +296,79 @@ impl Session {
     }
 }
 
+/// Parse a `key=value` session tag.
+///
+/// Keys are letters, digits, `-`, `_` and `.` (at most
+/// [`MAX_TAG_KEY_LEN`]); values are any non-empty text up to
+/// [`MAX_TAG_VALUE_LEN`] characters.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for a missing `=`, an invalid key, or an
+/// empty or overlong value.
+pub fn parse_tag(raw: &str) -> Result<(String, String)> {
+    let invalid = |why: &str| AppError::Config(format!("invalid tag '{raw}': {why}"));
+    let (key, value) = raw
+        .split_once('=')
+        .ok_or_else(|| invalid("expected key=value"))?;
+    let key_ok = !key.is_empty()
+        && key.len() <= MAX_TAG_KEY_LEN
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !key_ok {
+        return Err(invalid(&format!(
+            "keys are 1-{MAX_TAG_KEY_LEN} letters, digits, '-', '_' or '.'"
+        )));
+    }
+    if value.is_empty() || value.chars().count() > MAX_TAG_VALUE_LEN {
+        return Err(invalid(&format!(
+            "values are 1-{MAX_TAG_VALUE_LEN} characters"
+        )));
+    }
+    Ok((key.to_owned(), value.to_owned()))
+}
+
+/// Parse a `KEY=VAL` environment variable for the agent process.
+///
+/// Names are letters, digits and `_`, not starting with a digit; values
+/// may be empty but may not contain NUL.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for a missing `=`, an invalid name, or a
+/// value containing NUL.
+pub fn parse_env_var(raw: &str) -> Result<(String, String)> {
+    let invalid =
+        |why: &str| AppError::Config(format!("invalid environment variable '{raw}': {why}"));
+    let (name, value) = raw
+        .split_once('=')
+        .ok_or_else(|| invalid("expected KEY=VAL"))?;
+    let name_ok = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !name_ok {
+        return Err(invalid(
+            "names are letters, digits and '_', not starting with a digit",
+        ));
+    }
+    if value.contains('\0') {
+        return Err(invalid("values may not contain NUL"));
+    }
+    Ok((name.to_owned(), value.to_owned()))
+}
+
+/// Render tags as `key=value` pairs separated by spaces, in key order.
+#[must_use]
+pub fn format_tags(tags: &BTreeMap<String, String>) -> String {
+    tags.iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Truncate a prompt string to produce a session title (at most 80 chars).
 ///
 /// Returns the prompt unchanged when it is 80 characters or fewer. When
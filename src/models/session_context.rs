@@ -0,0 +1,20 @@
+//! Per-session key/value context model.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A small note an agent stored with `store_context`.
+///
+/// Entries are keyed by `(session_id, key)`, persist across restarts, and
+/// are returned by `reboot` so a resumed agent recovers what it had noted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContextEntry {
+    /// Owning session identifier.
+    pub session_id: String,
+    /// Key, unique within the session.
+    pub key: String,
+    /// Arbitrary JSON value.
+    pub value: serde_json::Value,
+    /// When the value was last written.
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,38 @@
+//! Operator notes on sessions (`/intercom note`).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A timestamped remark an operator left on a session for whoever looks
+/// at it next, e.g. "paused because of the prod incident".
+///
+/// Notes appear in the session report, its timeline and the
+/// `recover_state` payload; they are never sent to the agent as steering.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SessionNote {
+    /// Unique record identifier.
+    pub id: String,
+    /// Session the note is about.
+    pub session_id: String,
+    /// Slack user ID of the operator who wrote it.
+    pub author: String,
+    /// The note itself.
+    pub text: String,
+    /// When the note was written.
+    pub created_at: DateTime<Utc>,
+}
+
+impl SessionNote {
+    /// A note by `author` on `session_id`, written now.
+    #[must_use]
+    pub fn new(session_id: String, author: String, text: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            session_id,
+            author,
+            text,
+            created_at: Utc::now(),
+        }
+    }
+}
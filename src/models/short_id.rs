@@ -0,0 +1,90 @@
+//! Human-readable short IDs for sessions and approval requests.
+//!
+//! Records keep their UUID primary key; the short ID (`ses-7f3k`,
+//! `apr-9mxq`) is stored next to it, shown in Slack and accepted wherever a
+//! UUID or UUID prefix is. The alphabet leaves out `0`, `1`, `i`, `l` and
+//! `o` so IDs survive being read aloud or retyped from a phone.
+//!
+//! The format is process-wide: [`configure`] applies `[ids]` once at
+//! startup, and records created before that (or in tests) use the defaults.
+
+use std::fmt::Write as _;
+use std::sync::OnceLock;
+
+use uuid::Uuid;
+
+use crate::config::{IdFormat, IdsConfig};
+use crate::AppError;
+
+/// Prefix of session short IDs.
+pub const SESSION_PREFIX: &str = "ses";
+
+/// Prefix of approval request short IDs.
+pub const APPROVAL_PREFIX: &str = "apr";
+
+/// Characters a `short` ID is drawn from.
+const ALPHABET: &[u8] = b"23456789abcdefghjkmnpqrstuvwxyz";
+
+/// UUID characters used by the `uuid` format.
+const UUID_PREFIX_LEN: usize = 8;
+
+/// Candidates named in an ambiguous-prefix error.
+const MAX_CANDIDATES: usize = 5;
+
+static CONFIG: OnceLock<IdsConfig> = OnceLock::new();
+
+/// Use `config` for short IDs generated from now on.
+///
+/// Only the first call takes effect; the format is fixed for the life of
+/// the process so IDs within one run look alike.
+pub fn configure(config: &IdsConfig) {
+    let _ = CONFIG.set(config.clone());
+}
+
+/// A short ID for the record `uuid` of the kind named by `prefix`.
+///
+/// `extra` lengthens the ID, for retrying after a clash.
+#[must_use]
+pub fn generate(prefix: &str, uuid: &str, extra: usize) -> String {
+    let config = CONFIG.get_or_init(IdsConfig::default);
+    match config.format {
+        IdFormat::Short => {
+            let base = ALPHABET.len() as u128;
+            let mut bits = Uuid::new_v4().as_u128();
+            let mut id = format!("{prefix}-");
+            for _ in 0..config.length + extra {
+                let digit = usize::try_from(bits % base).unwrap_or_default();
+                id.push(char::from(ALPHABET[digit]));
+                bits /= base;
+            }
+            id
+        }
+        IdFormat::Uuid => uuid.chars().take(UUID_PREFIX_LEN + extra).collect(),
+    }
+}
+
+/// Canonical form of a user-typed ID: trimmed and lowercased, since short
+/// IDs and UUIDs are both lowercase.
+#[must_use]
+pub fn normalize(raw: &str) -> String {
+    raw.trim().to_ascii_lowercase()
+}
+
+/// Error for an ID prefix that matches several `kind` records, listing
+/// the short IDs of the first few `candidates` so one can be retyped.
+#[must_use]
+pub fn ambiguous(kind: &str, prefix: &str, candidates: &[String]) -> AppError {
+    let mut listed = candidates
+        .iter()
+        .take(MAX_CANDIDATES)
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(", ");
+    if candidates.len() > MAX_CANDIDATES {
+        let _ = write!(listed, ", and {} more", candidates.len() - MAX_CANDIDATES);
+    }
+    AppError::Config(format!(
+        "ambiguous {kind} prefix '{prefix}' matches {} {kind}s: {listed}",
+        candidates.len()
+    ))
+}
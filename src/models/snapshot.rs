@@ -0,0 +1,53 @@
+//! Full-workspace snapshots (`/intercom workspace-snapshot`).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A tar archive of a workspace taken at one moment, with a SHA-256
+/// manifest of every file in it.
+///
+/// The archive and manifest live in `.intercom/snapshots/` of the
+/// workspace; this record says where and when. Unlike a checkpoint, which
+/// only records file hashes, a snapshot can put the files back.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkspaceSnapshot {
+    /// Unique record identifier; also names the archive file.
+    pub id: String,
+    /// Absolute path of the workspace that was archived.
+    pub workspace_root: String,
+    /// Operator-supplied label, or why the snapshot was taken.
+    pub label: Option<String>,
+    /// Slack user ID of the operator who asked for it.
+    pub created_by: String,
+    /// Session the snapshot was taken for, e.g. by `session-start --snapshot`.
+    pub session_id: Option<String>,
+    /// Files in the archive.
+    pub file_count: usize,
+    /// Uncompressed size of those files.
+    pub total_bytes: u64,
+    /// When the snapshot was taken.
+    pub created_at: DateTime<Utc>,
+}
+
+impl WorkspaceSnapshot {
+    /// An empty snapshot record of `workspace_root`, taken now.
+    #[must_use]
+    pub fn new(
+        workspace_root: String,
+        label: Option<String>,
+        created_by: String,
+        session_id: Option<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            workspace_root,
+            label,
+            created_by,
+            session_id,
+            file_count: 0,
+            total_bytes: 0,
+            created_at: Utc::now(),
+        }
+    }
+}
@@ -22,6 +22,30 @@ pub enum StallAlertStatus {
     Dismissed,
 }
 
+/// How a stall ended.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StallResolution {
+    /// The agent resumed before any nudge — the alert was a false positive.
+    SelfRecovered,
+    /// The agent resumed after one or more nudges.
+    Nudged,
+    /// The operator stopped the session from the alert.
+    Stopped,
+}
+
+impl StallResolution {
+    /// Returns the `snake_case` string representation stored in the database.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SelfRecovered => "self_recovered",
+            Self::Nudged => "nudged",
+            Self::Stopped => "stopped",
+        }
+    }
+}
+
 /// A watchdog notification triggered by detected agent inactivity.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -48,6 +72,10 @@ pub struct StallAlert {
     pub slack_ts: Option<String>,
     /// Creation timestamp.
     pub created_at: DateTime<Utc>,
+    /// How the stall ended; `None` while it is open.
+    pub resolution: Option<StallResolution>,
+    /// When the stall ended.
+    pub resolved_at: Option<DateTime<Utc>>,
 }
 
 impl StallAlert {
@@ -72,6 +100,8 @@ impl StallAlert {
             progress_snapshot,
             slack_ts: None,
             created_at: Utc::now(),
+            resolution: None,
+            resolved_at: None,
         }
     }
 }
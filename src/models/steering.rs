@@ -1,6 +1,6 @@
 //! Steering message model for the operator-to-agent communication queue.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -12,6 +12,32 @@ pub enum SteeringSource {
     Slack,
     /// Message submitted via IPC (`intercom-ctl steer`).
     Ipc,
+    /// Completion report from a child session started with `spawn_subtask`.
+    Subtask,
+}
+
+/// Delivery urgency of a steering message.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SteeringPriority {
+    /// Delivered at the agent's next checkpoint (`ping` or reconnect flush).
+    #[default]
+    Normal,
+    /// Delivered immediately: the agent's current turn is interrupted and
+    /// the message sent as a fresh prompt. Queued ahead of normal messages
+    /// when immediate delivery is not possible.
+    Interrupt,
+}
+
+impl SteeringPriority {
+    /// Stable `snake_case` name used in persistence and wire payloads.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Interrupt => "interrupt",
+        }
+    }
 }
 
 /// An operator-to-agent message queued for delivery via `ping`.
@@ -39,6 +65,10 @@ pub struct SteeringMessage {
     /// is carried forward to a resumed session after a crash. `None` for
     /// messages that have never been reassigned.
     pub origin_session_id: Option<String>,
+    /// Delivery urgency.
+    pub priority: SteeringPriority,
+    /// Time after which an undelivered message is dropped. `None` never expires.
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 impl SteeringMessage {
@@ -59,6 +89,29 @@ impl SteeringMessage {
             created_at: Utc::now(),
             consumed: false,
             origin_session_id: None,
+            priority: SteeringPriority::Normal,
+            expires_at: None,
         }
     }
+
+    /// Set the delivery priority.
+    #[must_use]
+    pub fn with_priority(mut self, priority: SteeringPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Expire the message `ttl` after its creation time. A `ttl` that runs
+    /// past the end of the calendar leaves the message without an expiry.
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.expires_at = self.created_at.checked_add_signed(ttl);
+        self
+    }
+
+    /// Whether the message's TTL has elapsed at `now`.
+    #[must_use]
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|at| at <= now)
+    }
 }
@@ -0,0 +1,108 @@
+//! Second confirmation before diffs to protected paths are applied.
+//!
+//! Files matching `[protected_paths] patterns` — migration scripts,
+//! deployment manifests — are not written on the strength of an earlier
+//! approval alone. `check_diff` posts a summary of the change with Confirm
+//! Apply and Cancel buttons and waits in [`ApplyConfirmations`], keyed by
+//! the approval request ID, until an operator presses one or the approval
+//! timeout elapses. Diffs that no longer apply wait here the same way,
+//! keyed by their conflict record ID, for Force Apply, Merge View or
+//! Reject.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use tokio::sync::oneshot;
+
+/// Operator's answer to a protected-path confirmation or an apply conflict.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApplyDecision {
+    /// Write the file.
+    Confirmed {
+        /// Slack user ID of the operator who confirmed.
+        user_id: String,
+    },
+    /// Leave the file untouched.
+    Cancelled {
+        /// Slack user ID of the operator who cancelled.
+        user_id: String,
+    },
+    /// Leave the file untouched and hand the agent a merge view to
+    /// re-propose from (conflicts only).
+    Merge {
+        /// Slack user ID of the operator who asked for the merge.
+        user_id: String,
+    },
+}
+
+/// `check_diff` calls waiting for a protected-path confirmation or a
+/// conflict decision.
+#[derive(Debug, Default)]
+pub struct ApplyConfirmations {
+    pending: Mutex<HashMap<String, oneshot::Sender<ApplyDecision>>>,
+}
+
+impl ApplyConfirmations {
+    /// Start waiting for a decision on `request_id`, replacing any earlier
+    /// wait on it.
+    pub fn register(&self, request_id: &str) -> oneshot::Receiver<ApplyDecision> {
+        let (tx, rx) = oneshot::channel();
+        self.lock().insert(request_id.to_owned(), tx);
+        rx
+    }
+
+    /// Hand `decision` to the call waiting on `request_id`. Returns whether
+    /// one was waiting.
+    pub fn resolve(&self, request_id: &str, decision: ApplyDecision) -> bool {
+        self.lock()
+            .remove(request_id)
+            .is_some_and(|tx| tx.send(decision).is_ok())
+    }
+
+    /// Whether a call is waiting on `request_id`.
+    #[must_use]
+    pub fn is_pending(&self, request_id: &str) -> bool {
+        self.lock().contains_key(request_id)
+    }
+
+    /// Wait up to `timeout` for the decision on `request_id`; `None` when
+    /// it times out or the wait is dropped.
+    pub async fn wait(
+        &self,
+        request_id: &str,
+        rx: oneshot::Receiver<ApplyDecision>,
+        timeout: Duration,
+    ) -> Option<ApplyDecision> {
+        let decision = match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(decision)) => Some(decision),
+            _ => None,
+        };
+        self.lock().remove(request_id);
+        decision
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, oneshot::Sender<ApplyDecision>>> {
+        self.pending.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Lines added and removed by `diff`. Content that is not a unified diff
+/// is a whole new file: every line counts as added.
+#[must_use]
+pub fn diff_stats(diff: &str) -> (usize, usize) {
+    if !(diff.starts_with("--- ") || diff.starts_with("diff ")) {
+        return (diff.lines().count(), 0);
+    }
+    diff.lines()
+        .filter(|line| !line.starts_with("+++") && !line.starts_with("---"))
+        .fold((0, 0), |(added, removed), line| {
+            if line.starts_with('+') {
+                (added + 1, removed)
+            } else if line.starts_with('-') {
+                (added, removed + 1)
+            } else {
+                (added, removed)
+            }
+        })
+}
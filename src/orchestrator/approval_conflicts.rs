@@ -0,0 +1,125 @@
+//! Approval requests from concurrent sessions touching the same file.
+//!
+//! Before an approval request is posted, requests for the same file from
+//! other sessions in the workspace are looked up so the Slack message can
+//! warn the operator: approving both of two pending changes, or a change
+//! written against a file another session just rewrote, silently loses
+//! one of them.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use tracing::{info, warn};
+
+use crate::models::approval::{ApprovalRequest, ApprovalStatus};
+use crate::models::session::Session;
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::db::Database;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::blocks;
+use crate::Result;
+
+/// How far back approved and applied requests count as conflicts.
+pub const RECENT_WINDOW_MINUTES: i64 = 60;
+
+/// Another session's request for the same file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalConflict {
+    /// The other request.
+    pub request: ApprovalRequest,
+    /// Short ID of the session that made it.
+    pub session_short_id: String,
+}
+
+/// Requests for `file_path` from sessions other than `session` in its
+/// workspace that are pending, or were approved or applied within the
+/// last [`RECENT_WINDOW_MINUTES`] as of `now`. Newest first.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if a query fails.
+pub async fn find(
+    db: &Arc<Database>,
+    session: &Session,
+    file_path: &str,
+    now: DateTime<Utc>,
+) -> Result<Vec<ApprovalConflict>> {
+    let since = now - Duration::minutes(RECENT_WINDOW_MINUTES);
+    let requests = ApprovalRepo::new(Arc::clone(db))
+        .list_conflicting(&session.workspace_root, file_path, &session.id, since)
+        .await?;
+
+    let sessions = SessionRepo::new(Arc::clone(db));
+    let mut short_ids: HashMap<String, String> = HashMap::new();
+    let mut conflicts = Vec::with_capacity(requests.len());
+    for request in requests {
+        let session_short_id = if let Some(short_id) = short_ids.get(&request.session_id) {
+            short_id.clone()
+        } else {
+            let short_id = sessions
+                .get_by_id(&request.session_id)
+                .await?
+                .map_or_else(|| request.session_id.clone(), |s| s.short_id);
+            short_ids.insert(request.session_id.clone(), short_id.clone());
+            short_id
+        };
+        conflicts.push(ApprovalConflict {
+            request,
+            session_short_id,
+        });
+    }
+    Ok(conflicts)
+}
+
+/// The warning to add to a new approval request from `session` for
+/// `file_path`, if it conflicts with any. A failed lookup is logged and
+/// yields no warning, so it never holds up the request.
+pub async fn warning(db: &Arc<Database>, session: &Session, file_path: &str) -> Option<String> {
+    match find(db, session, file_path, Utc::now()).await {
+        Ok(conflicts) => {
+            if !conflicts.is_empty() {
+                info!(
+                    session_id = %session.id,
+                    file_path,
+                    count = conflicts.len(),
+                    "approval request conflicts with other sessions"
+                );
+            }
+            describe(&conflicts)
+        }
+        Err(err) => {
+            warn!(%err, session_id = %session.id, file_path, "approval conflict lookup failed");
+            None
+        }
+    }
+}
+
+/// One warning line per conflict, for the approval message.
+#[must_use]
+pub fn describe(conflicts: &[ApprovalConflict]) -> Option<String> {
+    if conflicts.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = conflicts
+        .iter()
+        .map(|conflict| {
+            let request = &conflict.request;
+            let what = match request.status {
+                ApprovalStatus::Approved => {
+                    "has an approved change to this file that may not be applied yet".to_owned()
+                }
+                ApprovalStatus::Consumed => format!(
+                    "applied a change to this file at {}; this diff may be stale",
+                    blocks::slack_time(request.consumed_at.unwrap_or(request.created_at))
+                ),
+                _ => "also has a pending change to this file".to_owned(),
+            };
+            format!(
+                "\u{26a0}\u{fe0f} Session `{}` {what} (`{}`)",
+                conflict.session_short_id, request.short_id
+            )
+        })
+        .collect();
+    Some(lines.join("\n"))
+}
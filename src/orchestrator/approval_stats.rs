@@ -0,0 +1,140 @@
+//! Approval outcome statistics.
+//!
+//! Summarises decided [`ApprovalRequest`]s by risk level, together with the
+//! reasons operators gave for rejections, so agents can calibrate how often
+//! they ask for approval versus relying on `auto_check`.
+
+use std::collections::HashMap;
+
+use crate::audit::AuditEntry;
+use crate::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+
+/// Most rejection reasons reported.
+const MAX_REASONS: usize = 5;
+
+/// Longest rejection reason reported, in characters.
+const MAX_REASON_CHARS: usize = 200;
+
+/// Approved and rejected counts for one slice of requests.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Outcomes {
+    /// Requests approved (including those since applied).
+    pub approved: usize,
+    /// Requests rejected.
+    pub rejected: usize,
+}
+
+impl Outcomes {
+    /// Requests decided either way.
+    #[must_use]
+    pub fn decided(&self) -> usize {
+        self.approved + self.rejected
+    }
+
+    /// Share of decided requests that were approved, as a whole
+    /// percentage; `None` when nothing was decided.
+    #[must_use]
+    pub fn approval_percent(&self) -> Option<usize> {
+        let decided = self.decided();
+        (decided > 0).then(|| (self.approved * 100 + decided / 2) / decided)
+    }
+
+    fn to_json(self) -> serde_json::Value {
+        serde_json::json!({
+            "decided": self.decided(),
+            "approved": self.approved,
+            "rejected": self.rejected,
+            "approval_percent": self.approval_percent(),
+        })
+    }
+}
+
+/// Outcome counts over a set of decided approval requests.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApprovalStats {
+    /// Outcomes over every request.
+    pub overall: Outcomes,
+    /// Outcomes of `low` risk requests.
+    pub low: Outcomes,
+    /// Outcomes of `high` risk requests.
+    pub high: Outcomes,
+    /// Outcomes of `critical` risk requests.
+    pub critical: Outcomes,
+    /// Most frequent rejection reasons with their counts, most frequent
+    /// first. Reasons differing only in case or surrounding whitespace are
+    /// counted together.
+    pub rejection_reasons: Vec<(String, usize)>,
+}
+
+impl ApprovalStats {
+    /// Compute statistics over `requests`, taking rejection reasons from
+    /// the audit `decisions` keyed by request ID. Requests still pending
+    /// are ignored.
+    #[must_use]
+    pub fn from_requests(
+        requests: &[ApprovalRequest],
+        decisions: &HashMap<String, AuditEntry>,
+    ) -> Self {
+        let mut stats = Self::default();
+        let mut reasons: HashMap<String, (String, usize)> = HashMap::new();
+        for request in requests {
+            let approved = match request.status {
+                ApprovalStatus::Approved | ApprovalStatus::Consumed => true,
+                ApprovalStatus::Rejected => false,
+                _ => continue,
+            };
+            let bucket = match request.risk_level {
+                RiskLevel::Low => &mut stats.low,
+                RiskLevel::High => &mut stats.high,
+                RiskLevel::Critical => &mut stats.critical,
+            };
+            for outcomes in [bucket, &mut stats.overall] {
+                if approved {
+                    outcomes.approved += 1;
+                } else {
+                    outcomes.rejected += 1;
+                }
+            }
+            if approved {
+                continue;
+            }
+            let reason = decisions
+                .get(&request.id)
+                .and_then(|entry| entry.reason.as_deref())
+                .map(str::trim)
+                .filter(|reason| !reason.is_empty());
+            if let Some(reason) = reason {
+                let reason: String = reason.chars().take(MAX_REASON_CHARS).collect();
+                reasons
+                    .entry(reason.to_lowercase())
+                    .or_insert_with(|| (reason, 0))
+                    .1 += 1;
+            }
+        }
+
+        let mut reasons: Vec<(String, usize)> = reasons.into_values().collect();
+        reasons.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        reasons.truncate(MAX_REASONS);
+        stats.rejection_reasons = reasons;
+        stats
+    }
+
+    /// JSON form returned by the `approval_stats` tool.
+    #[must_use]
+    pub fn to_json(&self) -> serde_json::Value {
+        let reasons: Vec<serde_json::Value> = self
+            .rejection_reasons
+            .iter()
+            .map(|(reason, count)| serde_json::json!({ "reason": reason, "count": count }))
+            .collect();
+        serde_json::json!({
+            "overall": self.overall.to_json(),
+            "by_risk_level": {
+                "low": self.low.to_json(),
+                "high": self.high.to_json(),
+                "critical": self.critical.to_json(),
+            },
+            "top_rejection_reasons": reasons,
+        })
+    }
+}
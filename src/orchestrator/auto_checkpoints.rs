@@ -0,0 +1,106 @@
+//! Automatic periodic checkpoints (`[checkpoints]`).
+//!
+//! A sweep runs on a fixed interval over active sessions. A session whose
+//! newest checkpoint, of any kind, is at least `interval_minutes` old —
+//! or which has none and started that long ago — gets an automatic one
+//! through [`checkpoint_manager::create_automatic_checkpoint`], and its
+//! checkpoints are then pruned to `keep_last`, sparing operator and agent
+//! checkpoints while `keep_milestones` is on.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::orchestrator::checkpoint_manager;
+use crate::persistence::checkpoint_repo::CheckpointRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::state::AppState;
+use crate::Result;
+
+/// How often sessions are checked for a due checkpoint.
+pub const SWEEP_INTERVAL: Duration = Duration::from_mins(1);
+
+/// Spawn the periodic checkpoint sweep.
+///
+/// No task is spawned when automatic checkpoints are disabled.
+#[must_use]
+pub fn spawn_auto_checkpoint_task(
+    state: Arc<AppState>,
+    cancel: CancellationToken,
+) -> Option<JoinHandle<()>> {
+    state.config.checkpoints.interval()?;
+    Some(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => {
+                    info!("automatic checkpoint task shutting down");
+                    break;
+                }
+                _ = interval.tick() => {
+                    if let Err(err) = checkpoint_due(&state, Utc::now()).await {
+                        warn!(%err, "automatic checkpoint sweep failed");
+                    }
+                }
+            }
+        }
+    }))
+}
+
+/// Checkpoint every active session due one as of `now`, then prune its
+/// checkpoints. Returns the number of checkpoints taken.
+///
+/// Failures for a single session are logged and skipped.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the active sessions cannot be listed.
+pub async fn checkpoint_due(state: &AppState, now: DateTime<Utc>) -> Result<usize> {
+    let config = &state.config.checkpoints;
+    let Some(interval) = config
+        .interval()
+        .and_then(|interval| chrono::Duration::from_std(interval).ok())
+    else {
+        return Ok(0);
+    };
+    let session_repo = SessionRepo::new(Arc::clone(&state.db));
+    let checkpoint_repo = CheckpointRepo::new(Arc::clone(&state.db));
+
+    let mut taken = 0;
+    for session in session_repo.list_active().await? {
+        let since = match checkpoint_repo.latest_created_at(&session.id).await {
+            Ok(latest) => latest.unwrap_or(session.created_at),
+            Err(err) => {
+                warn!(%err, session_id = %session.id, "failed to read latest checkpoint");
+                continue;
+            }
+        };
+        if now - since < interval {
+            continue;
+        }
+        if let Err(err) = checkpoint_manager::create_automatic_checkpoint(
+            &session.id,
+            &session_repo,
+            &checkpoint_repo,
+        )
+        .await
+        {
+            warn!(%err, session_id = %session.id, "automatic checkpoint failed");
+            continue;
+        }
+        taken += 1;
+        match checkpoint_repo
+            .prune(&session.id, config.keep_last, config.keep_milestones)
+            .await
+        {
+            Ok(0) => {}
+            Ok(pruned) => info!(session_id = %session.id, pruned, "pruned old checkpoints"),
+            Err(err) => warn!(%err, session_id = %session.id, "checkpoint pruning failed"),
+        }
+    }
+    Ok(taken)
+}
@@ -1,7 +1,8 @@
 //! Checkpoint creation and restore for session state snapshots.
 //!
 //! Provides [`create_checkpoint`] to snapshot a session's state and
-//! workspace file hashes, and [`restore_checkpoint`] to load a
+//! workspace file hashes ([`create_automatic_checkpoint`] for the periodic
+//! task), and [`restore_checkpoint`] to load a
 //! previous checkpoint and detect workspace divergence.
 
 use std::collections::HashMap;
@@ -31,7 +32,31 @@ pub async fn create_checkpoint(
     session_repo: &SessionRepo,
     checkpoint_repo: &CheckpointRepo,
 ) -> Result<Checkpoint> {
-    let span = info_span!("create_checkpoint", session_id, label);
+    snapshot(session_id, label, false, session_repo, checkpoint_repo).await
+}
+
+/// Create an unlabelled checkpoint marked `automatic`, as the periodic
+/// `[checkpoints]` task does.
+///
+/// # Errors
+///
+/// Same as [`create_checkpoint`].
+pub async fn create_automatic_checkpoint(
+    session_id: &str,
+    session_repo: &SessionRepo,
+    checkpoint_repo: &CheckpointRepo,
+) -> Result<Checkpoint> {
+    snapshot(session_id, None, true, session_repo, checkpoint_repo).await
+}
+
+async fn snapshot(
+    session_id: &str,
+    label: Option<&str>,
+    automatic: bool,
+    session_repo: &SessionRepo,
+    checkpoint_repo: &CheckpointRepo,
+) -> Result<Checkpoint> {
+    let span = info_span!("create_checkpoint", session_id, label, automatic);
     let _guard = span.enter();
 
     // Load the current session.
@@ -47,7 +72,7 @@ pub async fn create_checkpoint(
     let session_state = serde_json::to_value(&session)
         .map_err(|err| AppError::Db(format!("failed to serialize session state: {err}")))?;
 
-    let checkpoint = Checkpoint::new(
+    let mut checkpoint = Checkpoint::new(
         session_id.to_owned(),
         label.map(ToOwned::to_owned),
         session_state,
@@ -55,6 +80,7 @@ pub async fn create_checkpoint(
         session.workspace_root.clone(),
         session.progress_snapshot.clone(),
     );
+    checkpoint.automatic = automatic;
 
     let saved = checkpoint_repo.create(&checkpoint).await?;
 
@@ -19,8 +19,8 @@ use tokio_util::sync::CancellationToken;
 use tracing::{info, warn};
 
 use crate::config::GlobalConfig;
-use crate::models::session::SessionStatus;
-use crate::orchestrator::spawner;
+use crate::models::session::{Session, SessionStatus};
+use crate::orchestrator::{spawner, subtask};
 use crate::persistence::session_repo::SessionRepo;
 use crate::slack::client::{SlackMessage, SlackService};
 use crate::state::ActiveChildren;
@@ -141,11 +141,16 @@ async fn poll_children(
             ExitClass::Clean => {
                 info!(session_id, "spawned agent process exited normally (code 0)");
                 let session_repo = SessionRepo::new(Arc::clone(db));
-                if let Err(err) = session_repo
+                match session_repo
                     .set_terminated(&session_id, SessionStatus::Terminated)
                     .await
                 {
-                    warn!(%err, session_id, "failed to terminate session after clean exit");
+                    Ok(session) => {
+                        report_subtask(db, &session, "agent process exited normally").await;
+                    }
+                    Err(err) => {
+                        warn!(%err, session_id, "failed to terminate session after clean exit");
+                    }
                 }
                 respawn_attempts.remove(&session_id);
                 notify(
@@ -177,6 +182,7 @@ async fn poll_children(
 /// Attempt to recover a crashed session by respawning it, bounded by
 /// [`MAX_RESPAWN_ATTEMPTS`]. On success the resumed child is registered for
 /// continued monitoring and the crash-chain counter is carried forward.
+#[allow(clippy::too_many_lines)] // One branch per recovery outcome, each notifying the operator.
 async fn attempt_respawn(
     session_id: &str,
     children: &ActiveChildren,
@@ -194,11 +200,14 @@ async fn attempt_respawn(
             session_id,
             attempts, "crash recovery exhausted; marking session interrupted"
         );
-        if let Err(err) = session_repo
+        match session_repo
             .set_terminated(session_id, SessionStatus::Interrupted)
             .await
         {
-            warn!(%err, session_id, "failed to mark exhausted session interrupted");
+            Ok(session) => {
+                report_subtask(db, &session, "agent crashed and exceeded the respawn limit").await;
+            }
+            Err(err) => warn!(%err, session_id, "failed to mark exhausted session interrupted"),
         }
         notify(
             slack,
@@ -271,11 +280,16 @@ async fn attempt_respawn(
         }
         Err(err) => {
             warn!(%err, session_id, "failed to respawn crashed session");
-            if let Err(e) = session_repo
+            match session_repo
                 .set_terminated(session_id, SessionStatus::Interrupted)
                 .await
             {
-                warn!(%e, session_id, "failed to mark session interrupted after respawn failure");
+                Ok(session) => {
+                    report_subtask(db, &session, "agent crashed and could not be respawned").await;
+                }
+                Err(e) => {
+                    warn!(%e, session_id, "failed to mark session interrupted after respawn failure");
+                }
             }
             notify(
                 slack,
@@ -290,6 +304,13 @@ async fn attempt_respawn(
     }
 }
 
+/// Report a finished child session to its parent, if it is a subtask.
+async fn report_subtask(db: &Arc<sqlx::SqlitePool>, session: &Session, outcome: &str) {
+    if let Err(err) = subtask::report_to_parent(db, session, outcome).await {
+        warn!(%err, session_id = %session.id, "failed to report subtask to parent");
+    }
+}
+
 /// Enqueue a plain-text notification to the operator channel, logging on failure.
 async fn notify(slack: &Arc<SlackService>, channel: &str, text: String) {
     let msg = SlackMessage::plain(SlackChannelId(channel.to_owned()), text);
@@ -0,0 +1,125 @@
+//! Runtime edits to the `[commands]` alias table.
+//!
+//! `/intercom commands add` registers an alias for the running server and
+//! writes it back to the `config.toml` the server was started with, so it
+//! is part of `[commands]` after a restart. The file is edited with
+//! `toml_edit`, keeping comments and formatting. Aliases added this way are
+//! held in [`CommandAliases`] and listed together with the configured ones.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use toml_edit::{DocumentMut, Item, Table};
+
+use crate::slack::commands::BUILTIN_COMMANDS;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Table holding the aliases (`[commands]`).
+const COMMANDS_KEY: &str = "commands";
+
+/// Aliases added with `commands add` since startup.
+#[derive(Debug, Default)]
+pub struct CommandAliases {
+    added: Mutex<HashMap<String, String>>,
+}
+
+impl CommandAliases {
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, String>> {
+        self.added.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Every alias, configured or added since startup, keyed by alias.
+#[must_use]
+pub fn list(state: &AppState) -> BTreeMap<String, String> {
+    let mut aliases: BTreeMap<String, String> = state
+        .config
+        .commands
+        .iter()
+        .map(|(alias, command)| (alias.clone(), command.clone()))
+        .collect();
+    aliases.extend(
+        state
+            .command_aliases
+            .lock()
+            .iter()
+            .map(|(alias, command)| (alias.clone(), command.clone())),
+    );
+    aliases
+}
+
+/// Add `alias` for the shell `command` and write it to `config.toml`.
+///
+/// Returns whether the alias was written to `config.toml` (`false` when
+/// the server runs without a config file).
+///
+/// # Errors
+///
+/// Returns `AppError::Config` when the alias is not made of ASCII letters,
+/// digits, `-` and `_`, names a built-in command or an existing alias, the
+/// command is empty, or the config file cannot be parsed, and
+/// `AppError::Io` if it cannot be written.
+pub fn add(state: &AppState, alias: &str, command: &str) -> Result<bool> {
+    let valid = !alias.is_empty()
+        && alias
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(AppError::Config(format!(
+            "invalid alias '{alias}': use ASCII letters, digits, '-' and '_'"
+        )));
+    }
+    if BUILTIN_COMMANDS.contains(&alias) {
+        return Err(AppError::Config(format!(
+            "'{alias}' is a built-in command and cannot be an alias"
+        )));
+    }
+    let command = command.trim();
+    if command.is_empty() {
+        return Err(AppError::Config("alias command must not be empty".into()));
+    }
+
+    let mut added = state.command_aliases.lock();
+    let existing = state
+        .config
+        .commands
+        .get(alias)
+        .or_else(|| added.get(alias));
+    if let Some(existing) = existing {
+        return Err(AppError::Config(format!(
+            "alias '{alias}' already runs `{existing}`"
+        )));
+    }
+    let saved = match state.config_path.as_deref() {
+        Some(path) => {
+            write_to_file(path, alias, command)?;
+            true
+        }
+        None => false,
+    };
+    added.insert(alias.to_owned(), command.to_owned());
+    Ok(saved)
+}
+
+/// Set `alias = command` in the `[commands]` table of `path`.
+fn write_to_file(path: &Path, alias: &str, command: &str) -> Result<()> {
+    let mut document = std::fs::read_to_string(path)?
+        .parse::<DocumentMut>()
+        .map_err(|err| AppError::Config(format!("cannot edit {}: {err}", path.display())))?;
+    let table = document
+        .entry(COMMANDS_KEY)
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_like_mut()
+        .ok_or_else(|| {
+            AppError::Config(format!(
+                "cannot edit {}: `commands` is not a [commands] table",
+                path.display()
+            ))
+        })?;
+    table.insert(alias, toml_edit::value(command));
+
+    std::fs::write(path, document.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,367 @@
+//! Subscriber tasks for the unified [`EventBus`].
+//!
+//! Each subscriber owns one `broadcast::Receiver` and runs until the
+//! cancellation token fires or the bus is dropped:
+//!
+//! - **metrics** — counts events per [`AgentEvent::kind`] into the bus's
+//!   [`EventMetrics`](crate::driver::event_bus::EventMetrics).
+//! - **audit** — records agent-originated requests and terminations as
+//!   [`AuditEventType::AgentEvent`] entries.
+//! - **Slack status** — posts MCP status messages (the `heartbeat` tool's
+//!   `status_message`) to the connection's channel. ACP status text is
+//!   debounced and threaded by the ACP event consumer instead, so this
+//!   subscriber ignores ACP-sourced events. Muted sessions are skipped.
+//!   Status lines are low priority: while Slack's rate budget is tight
+//!   only each session's latest one is posted.
+//! - **Slack requests** — posts the approval and prompt cards for MCP
+//!   `ask_approval` and `forward_prompt` calls (see
+//!   [`request_cards`](crate::slack::request_cards)). Each card is posted on
+//!   its own task so a slow upload never holds up the next request.
+//!   Requests raised through the approval gate (proxied tool calls,
+//!   subtasks) post their own cards and are skipped.
+//! - **Slack notices** — posts the notices MCP tool handlers raise (relay
+//!   messages, path locks, checkpoints, applied diffs and the like), in the
+//!   order they were published. Three calls stay with the tool because it
+//!   needs their result: `remote_log` returns the posted message's
+//!   timestamp, `accept_diff` uploads its merge view as a file, and a
+//!   timed-out request resolves the card it holds the anchor of.
+//! - **approval links** — emails the session owner a signed approval link
+//!   for each approval request (`[approval_links] email = true`).
+
+use std::future::Future;
+use std::sync::Arc;
+
+use slack_morphism::prelude::SlackChannelId;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::audit::{AuditEntry, AuditEventType, AuditLogger};
+use crate::config::GlobalConfig;
+use crate::driver::event_bus::{BusEvent, EventBus, EventMetrics};
+use crate::driver::AgentEvent;
+use crate::integrations::email::{self, ApprovalLinkNotice};
+use crate::mcp::{approval_gate, approval_link};
+use crate::models::session::ProtocolMode;
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::db::Database;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::client::{SlackMessage, SlackService};
+use crate::slack::{blocks, request_cards};
+use crate::state::AppState;
+
+/// Drive `handle` with every event received on `rx` until cancelled.
+///
+/// Lagged receivers log the gap, count it in `metrics`, and continue from
+/// the oldest retained event.
+pub(crate) async fn run_subscriber<F, Fut>(
+    name: &'static str,
+    mut rx: Receiver<BusEvent>,
+    metrics: Arc<EventMetrics>,
+    cancel: CancellationToken,
+    mut handle: F,
+) where
+    F: FnMut(BusEvent) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    loop {
+        let received = tokio::select! {
+            () = cancel.cancelled() => {
+                info!(subscriber = name, "event subscriber shutting down");
+                break;
+            }
+            received = rx.recv() => received,
+        };
+        match received {
+            Ok(event) => handle(event).await,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!(
+                    subscriber = name,
+                    skipped, "event subscriber lagged; events dropped"
+                );
+                metrics.record_lagged(skipped);
+            }
+            Err(RecvError::Closed) => {
+                info!(subscriber = name, "event bus closed");
+                break;
+            }
+        }
+    }
+}
+
+/// Spawn the metrics subscriber.
+#[must_use]
+pub fn spawn_metrics_subscriber(
+    bus: &EventBus,
+    cancel: CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    let rx = bus.subscribe();
+    let metrics = bus.metrics();
+    tokio::spawn(async move {
+        let counters = Arc::clone(&metrics);
+        run_subscriber("metrics", rx, metrics, cancel, move |bus_event| {
+            counters.record(bus_event.event.kind());
+            std::future::ready(())
+        })
+        .await;
+    })
+}
+
+/// Build the audit entry for `event`, or `None` for high-frequency events
+/// (status text, heartbeats, stream activity) that would flood the log and
+/// for tool notices, which their tools audit themselves.
+#[must_use]
+pub fn audit_entry_for(bus_event: &BusEvent) -> Option<AuditEntry> {
+    let event = &bus_event.event;
+    let entry = AuditEntry::new(AuditEventType::AgentEvent)
+        .with_session(event.session_id().to_owned())
+        .with_result(event.kind().to_owned());
+    match event {
+        AgentEvent::ClearanceRequested { request_id, .. }
+        | AgentEvent::PermissionRequested { request_id, .. } => {
+            Some(entry.with_request_id(request_id.clone()))
+        }
+        AgentEvent::PromptForwarded { prompt_id, .. } => {
+            Some(entry.with_request_id(prompt_id.clone()))
+        }
+        AgentEvent::SessionTerminated { reason, .. } => Some(entry.with_reason(reason.clone())),
+        AgentEvent::StatusUpdated { .. }
+        | AgentEvent::HeartbeatReceived { .. }
+        | AgentEvent::StreamActivity { .. }
+        | AgentEvent::HooksSubscribed { .. }
+        | AgentEvent::NoticePosted { .. } => None,
+    }
+}
+
+/// Spawn the audit subscriber writing through `logger`.
+#[must_use]
+pub fn spawn_audit_subscriber(
+    bus: &EventBus,
+    logger: Arc<dyn AuditLogger>,
+    cancel: CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    let rx = bus.subscribe();
+    let metrics = bus.metrics();
+    tokio::spawn(run_subscriber(
+        "audit",
+        rx,
+        metrics,
+        cancel,
+        move |bus_event| {
+            if let Some(entry) = audit_entry_for(&bus_event) {
+                if let Err(err) = logger.log_entry(entry) {
+                    warn!(%err, "audit log write failed (agent event)");
+                }
+            }
+            std::future::ready(())
+        },
+    ))
+}
+
+/// Spawn the Slack status subscriber for MCP-sourced status messages.
+#[must_use]
+pub fn spawn_slack_status_subscriber(
+    bus: &EventBus,
+    slack: Arc<SlackService>,
+    db: Arc<Database>,
+    cancel: CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    let rx = bus.subscribe();
+    let metrics = bus.metrics();
+    tokio::spawn(run_subscriber(
+        "slack_status",
+        rx,
+        metrics,
+        cancel,
+        move |bus_event| {
+            let slack = Arc::clone(&slack);
+            let sessions = SessionRepo::new(Arc::clone(&db));
+            async move {
+                let BusEvent {
+                    source: ProtocolMode::Mcp,
+                    channel_id: Some(channel_id),
+                    event:
+                        AgentEvent::StatusUpdated {
+                            session_id,
+                            message,
+                        },
+                } = bus_event
+                else {
+                    return;
+                };
+                if sessions.is_muted(&session_id).await {
+                    return;
+                }
+                let msg = SlackMessage {
+                    channel: SlackChannelId(channel_id),
+                    text: Some(format!("\u{1f493} {message}")),
+                    blocks: Some(vec![blocks::severity_section("info", &message)]),
+                    thread_ts: None,
+                };
+                // Only the latest status of a session matters once Slack's
+                // budget is tight.
+                let key = Some(format!("status:{session_id}"));
+                if let Err(err) = slack.enqueue_low_priority(msg, key).await {
+                    warn!(%err, "failed to enqueue status update to slack");
+                }
+            }
+        },
+    ))
+}
+
+/// Spawn the Slack request subscriber for MCP-sourced approvals and prompts.
+#[must_use]
+pub fn spawn_slack_request_subscriber(
+    state: Arc<AppState>,
+    slack: Arc<SlackService>,
+    cancel: CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    let rx = state.event_bus.subscribe();
+    let metrics = state.event_bus.metrics();
+    tokio::spawn(run_subscriber(
+        "slack_requests",
+        rx,
+        metrics,
+        cancel,
+        move |bus_event| {
+            let BusEvent {
+                source: ProtocolMode::Mcp,
+                channel_id: Some(channel_id),
+                event,
+            } = bus_event
+            else {
+                return std::future::ready(());
+            };
+            let state = Arc::clone(&state);
+            let slack = Arc::clone(&slack);
+            match event {
+                AgentEvent::ClearanceRequested {
+                    request_id,
+                    snippets,
+                    ..
+                } => {
+                    tokio::spawn(async move {
+                        let gated = ApprovalRepo::new(Arc::clone(&state.db))
+                            .get_by_id(&request_id)
+                            .await
+                            .ok()
+                            .flatten()
+                            .is_some_and(|r| approval_gate::posts_own_card(&r.original_hash));
+                        if !gated {
+                            request_cards::post_approval(
+                                &state,
+                                &slack,
+                                &channel_id,
+                                &request_id,
+                                &snippets,
+                            )
+                            .await;
+                        }
+                    });
+                }
+                AgentEvent::PromptForwarded { prompt_id, .. } => {
+                    tokio::spawn(async move {
+                        request_cards::post_prompt(&state, &slack, &channel_id, &prompt_id).await;
+                    });
+                }
+                _ => {}
+            }
+            std::future::ready(())
+        },
+    ))
+}
+
+/// Spawn the Slack notice subscriber for MCP tool notices.
+#[must_use]
+pub fn spawn_slack_notice_subscriber(
+    bus: &EventBus,
+    slack: Arc<SlackService>,
+    cancel: CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    let rx = bus.subscribe();
+    let metrics = bus.metrics();
+    tokio::spawn(run_subscriber(
+        "slack_notices",
+        rx,
+        metrics,
+        cancel,
+        move |bus_event| {
+            let slack = Arc::clone(&slack);
+            async move {
+                let AgentEvent::NoticePosted {
+                    session_id,
+                    message,
+                } = bus_event.event
+                else {
+                    return;
+                };
+                if let Err(err) = slack.enqueue(message).await {
+                    warn!(%err, session_id, "failed to post tool notice");
+                }
+            }
+        },
+    ))
+}
+
+/// Spawn the subscriber emailing approval links to session owners.
+#[must_use]
+pub fn spawn_approval_link_subscriber(
+    bus: &EventBus,
+    config: Arc<GlobalConfig>,
+    db: Arc<Database>,
+    cancel: CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    let rx = bus.subscribe();
+    let metrics = bus.metrics();
+    tokio::spawn(run_subscriber(
+        "approval_links",
+        rx,
+        metrics,
+        cancel,
+        move |bus_event| {
+            let config = Arc::clone(&config);
+            let sessions = SessionRepo::new(Arc::clone(&db));
+            async move {
+                let (AgentEvent::ClearanceRequested {
+                    request_id,
+                    session_id,
+                    title,
+                    file_path,
+                    risk_level,
+                    ..
+                }
+                | AgentEvent::PermissionRequested {
+                    request_id,
+                    session_id,
+                    title,
+                    file_path,
+                    risk_level,
+                    ..
+                }) = bus_event.event
+                else {
+                    return;
+                };
+                let Ok(Some(session)) = sessions.get_by_id(&session_id).await else {
+                    return;
+                };
+                let Some(url) = approval_link::link_url(
+                    &config.approval_links,
+                    &request_id,
+                    chrono::Utc::now().timestamp(),
+                ) else {
+                    return;
+                };
+                let notice = ApprovalLinkNotice {
+                    owner_id: session.owner_user_id,
+                    title,
+                    file_path,
+                    risk_level,
+                    url,
+                    expires_in: approval_link::ttl_label(&config.approval_links),
+                };
+                email::spawn_approval_link_email(config, notice);
+            }
+        },
+    ))
+}
@@ -0,0 +1,359 @@
+//! Detection of out-of-band edits to files with unapplied diffs.
+//!
+//! Every pending or approved request carries the hash of its file when the
+//! diff was proposed, and `check_diff` refuses the diff once the file no
+//! longer matches. This watcher notices the change when it happens instead:
+//! it watches the directories of those files with `notify`, and when a
+//! file's hash moves away from the proposal's it marks the request stale.
+//!
+//! A stale pending request is rejected, so the waiting agent gets the
+//! reason back from `check_clearance` and the card stops offering a
+//! decision on a diff that no longer applies. A stale approved request
+//! stays approved, but `check_diff` refuses it and the agent is steered to
+//! re-propose it. Either way the session thread is told.
+//!
+//! The watched set is reconciled with the database on a fixed interval;
+//! requests first seen then, and files whose directory does not exist yet,
+//! are checked directly.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::diff::validate_workspace_path;
+use crate::ipc::server::resolve_clearance;
+use crate::mcp::tools::util::compute_file_hash;
+use crate::models::approval::{ApprovalRequest, ApprovalStatus};
+use crate::models::session::Session;
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::client::SlackMessage;
+use crate::slack::handlers::steer;
+use crate::slack::{approval_fanout, blocks};
+use crate::state::AppState;
+use crate::Result;
+
+/// How often the watched set is reconciled with the unapplied requests.
+pub const RECONCILE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long change events are collected before files are hashed, so an
+/// editor's save or `check_diff`'s own write settles first.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Files and directories currently watched.
+#[derive(Default)]
+struct Watched {
+    /// Request IDs by the file they change.
+    files: HashMap<PathBuf, Vec<String>>,
+    /// Directories registered with the watcher.
+    dirs: HashSet<PathBuf>,
+    /// Every request seen by the last reconcile.
+    known: HashSet<String>,
+}
+
+/// Spawn the watcher and its reconcile loop.
+#[must_use]
+pub fn spawn_file_change_watcher(
+    state: Arc<AppState>,
+    cancel: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let (tx, mut rx) = mpsc::unbounded_channel::<PathBuf>();
+        let mut watcher = match notify::recommended_watcher(
+            move |result: std::result::Result<Event, notify::Error>| match result {
+                Ok(event) if is_change(&event) => {
+                    for path in event.paths {
+                        let _ = tx.send(path);
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => warn!(%err, "file change watcher error"),
+            },
+        ) {
+            Ok(watcher) => Some(watcher),
+            Err(err) => {
+                warn!(%err, "file change watcher unavailable; checking on each reconcile only");
+                None
+            }
+        };
+
+        let mut tracked = Watched::default();
+        let mut interval = tokio::time::interval(RECONCILE_INTERVAL);
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => {
+                    info!("file change watcher shutting down");
+                    break;
+                }
+                _ = interval.tick() => {
+                    if let Err(err) = reconcile(&state, watcher.as_mut(), &mut tracked).await {
+                        warn!(%err, "file change watcher reconcile failed");
+                    }
+                }
+                Some(path) = rx.recv() => {
+                    tokio::time::sleep(DEBOUNCE).await;
+                    let mut changed = HashSet::from([watch_key(&path)]);
+                    while let Ok(path) = rx.try_recv() {
+                        changed.insert(watch_key(&path));
+                    }
+                    let ids: Vec<String> = changed
+                        .iter()
+                        .filter_map(|path| tracked.files.get(path))
+                        .flatten()
+                        .cloned()
+                        .collect();
+                    if let Err(err) = check_ids(&state, &ids).await {
+                        warn!(%err, "file change check failed");
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Mark every unapplied request whose file no longer matches its proposal
+/// stale, returning how many were.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the requests cannot be listed or updated.
+pub async fn check_unapplied(state: &Arc<AppState>) -> Result<usize> {
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let mut marked = 0;
+    for approval in repo.list_unapplied().await? {
+        if check(state, &approval).await? {
+            marked += 1;
+        }
+    }
+    Ok(marked)
+}
+
+/// Bring the watched directories in line with the unapplied requests, and
+/// check requests not seen before and those whose directory cannot be
+/// watched.
+async fn reconcile(
+    state: &Arc<AppState>,
+    watcher: Option<&mut RecommendedWatcher>,
+    tracked: &mut Watched,
+) -> Result<()> {
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let sessions = SessionRepo::new(Arc::clone(&state.db));
+    let mut roots: HashMap<String, Option<PathBuf>> = HashMap::new();
+    let mut files: HashMap<PathBuf, Vec<String>> = HashMap::new();
+    let mut known = HashSet::new();
+    let mut to_check = Vec::new();
+
+    for approval in repo.list_unapplied().await? {
+        known.insert(approval.id.clone());
+        if !roots.contains_key(&approval.session_id) {
+            let root = sessions
+                .get_by_id(&approval.session_id)
+                .await?
+                .map(|session| PathBuf::from(session.workspace_root));
+            roots.insert(approval.session_id.clone(), root);
+        }
+        let path = roots
+            .get(&approval.session_id)
+            .and_then(Option::as_ref)
+            .and_then(|root| validate_workspace_path(root, &approval.file_path).ok());
+        let watchable = path
+            .as_deref()
+            .and_then(Path::parent)
+            .is_some_and(Path::is_dir);
+        if !tracked.known.contains(&approval.id) || !watchable || watcher.is_none() {
+            to_check.push(approval.id.clone());
+        }
+        if let (Some(path), true) = (path, watchable) {
+            files.entry(watch_key(&path)).or_default().push(approval.id);
+        }
+    }
+
+    let dirs: HashSet<PathBuf> = files
+        .keys()
+        .filter_map(|path| path.parent().map(Path::to_path_buf))
+        .collect();
+    if let Some(watcher) = watcher {
+        for dir in tracked.dirs.difference(&dirs) {
+            let _ = watcher.unwatch(dir);
+        }
+        for dir in dirs.difference(&tracked.dirs) {
+            if let Err(err) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+                warn!(%err, dir = %dir.display(), "failed to watch directory");
+            }
+        }
+    }
+    *tracked = Watched { files, dirs, known };
+
+    check_ids(state, &to_check).await
+}
+
+/// Check the requests `ids`, skipping any no longer unapplied.
+async fn check_ids(state: &Arc<AppState>, ids: &[String]) -> Result<()> {
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    for id in ids {
+        if let Some(approval) = repo.get_by_id(id).await? {
+            check(state, &approval).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Mark `approval` stale if it is unapplied and its file no longer hashes
+/// to the proposal's, and tell the thread and the agent. Returns whether it
+/// was marked.
+async fn check(state: &Arc<AppState>, approval: &ApprovalRequest) -> Result<bool> {
+    if !matches!(
+        approval.status,
+        ApprovalStatus::Pending | ApprovalStatus::Approved
+    ) || approval.stale_at.is_some()
+    {
+        return Ok(false);
+    }
+    let Some(session) = SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(&approval.session_id)
+        .await?
+    else {
+        return Ok(false);
+    };
+    let Ok(path) = validate_workspace_path(Path::new(&session.workspace_root), &approval.file_path)
+    else {
+        return Ok(false);
+    };
+    let current = match compute_file_hash(&path).await {
+        Ok(hash) => hash,
+        Err(err) => {
+            warn!(%err, request_id = %approval.id, "cannot hash file of unapplied diff");
+            return Ok(false);
+        }
+    };
+    if current == approval.original_hash {
+        return Ok(false);
+    }
+
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    if !repo
+        .mark_stale(&approval.id, approval.status, Utc::now())
+        .await?
+    {
+        return Ok(false);
+    }
+    info!(
+        request_id = %approval.id,
+        file_path = %approval.file_path,
+        was = ?approval.status,
+        "diff marked stale: file changed on disk"
+    );
+    report(state, &repo, &session, approval).await;
+    Ok(true)
+}
+
+/// Tell the session thread, the agent and the audit log that `approval`
+/// went stale.
+async fn report(
+    state: &Arc<AppState>,
+    repo: &ApprovalRepo,
+    session: &Session,
+    approval: &ApprovalRequest,
+) {
+    let reason = stale_reason(approval);
+    if let Some(ref logger) = state.audit_logger {
+        let entry = AuditEntry::new(AuditEventType::ApprovalStale)
+            .with_session(approval.session_id.clone())
+            .with_request_id(approval.id.clone())
+            .with_reason(reason.clone());
+        if let Err(err) = logger.log_entry(entry) {
+            warn!(%err, "audit log write failed (stale approval)");
+        }
+    }
+
+    if approval.status == ApprovalStatus::Pending {
+        let status_line = format!(
+            "\u{26a0}\u{fe0f} *Stale* \u{2014} {}: `{}` changed on disk, so this diff no longer \
+             applies as proposed",
+            approval.title, approval.file_path
+        );
+        approval_fanout::resolve_copies(state, &approval.id, &status_line).await;
+        resolve_clearance(state, repo, &approval.id, false, Some(reason)).await;
+    } else if let Err(err) = steer::steer_session(state, session, &agent_notice(approval)).await {
+        warn!(%err, request_id = %approval.id, "failed to notify agent of stale diff");
+    }
+
+    if let (Some(slack), Some(channel)) = (state.slack.as_ref(), session.channel_id.as_ref()) {
+        let msg = SlackMessage {
+            channel: SlackChannelId(channel.clone()),
+            text: Some(format!("Stale diff for {}", approval.file_path)),
+            blocks: Some(vec![blocks::text_section(&operator_notice(approval))]),
+            thread_ts: session.thread_ts.clone().map(SlackTs),
+        };
+        if let Err(err) = slack.enqueue(msg).await {
+            warn!(%err, request_id = %approval.id, "failed to post stale diff notice");
+        }
+    }
+}
+
+/// Reason returned to an agent waiting on a request that went stale.
+#[must_use]
+pub fn stale_reason(approval: &ApprovalRequest) -> String {
+    format!(
+        "stale: {} changed on disk after this diff was proposed; re-read it and propose a \
+         rebased diff",
+        approval.file_path
+    )
+}
+
+/// Thread notice for a request that went stale.
+#[must_use]
+pub fn operator_notice(approval: &ApprovalRequest) -> String {
+    let outcome = if approval.status == ApprovalStatus::Pending {
+        "It was withdrawn from review"
+    } else {
+        "Its approval stands, but `check_diff` refuses it"
+    };
+    format!(
+        "\u{26a0}\u{fe0f} `{}` changed on disk after `{}` ({}) was proposed. {outcome}; the \
+         agent was asked for a rebased diff.",
+        approval.file_path, approval.short_id, approval.title
+    )
+}
+
+/// Steering message for an agent holding an approved request that went
+/// stale.
+#[must_use]
+pub fn agent_notice(approval: &ApprovalRequest) -> String {
+    format!(
+        "{} changed on disk after your approved diff `{}` ({}) was proposed, so it is stale and \
+         check_diff will refuse it. Re-read the file and propose a rebased diff with \
+         check_clearance.",
+        approval.file_path, approval.id, approval.title
+    )
+}
+
+/// Whether a notify event can change a file's contents.
+fn is_change(event: &Event) -> bool {
+    matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    )
+}
+
+/// `path` with its directory canonicalized, so event paths and request
+/// paths compare equal.
+fn watch_key(path: &Path) -> PathBuf {
+    match (path.parent(), path.file_name()) {
+        (Some(dir), Some(name)) => dir
+            .canonicalize()
+            .unwrap_or_else(|_| dir.to_path_buf())
+            .join(name),
+        _ => path.to_path_buf(),
+    }
+}
@@ -0,0 +1,283 @@
+//! Mandatory heartbeat enforcement (`[stall] heartbeat_required`).
+//!
+//! When heartbeats are required, every MCP session must `ping` with a
+//! `status_message` at least once per `heartbeat_deadline_seconds`.
+//!
+//! - Tool calls other than `ping` made after the deadline has passed are
+//!   refused with a [`HEARTBEAT_REQUIRED_STATUS`] directive.
+//! - A periodic sweep marks overdue sessions `stalled` and tells the
+//!   operator in the session thread, once per lapse.
+//! - The next `ping` with a status lifts the block, marks the session
+//!   `online` again and reports the recovery.
+//!
+//! The clock for a session starts at its first tool call and stops while
+//! one of its tool calls is being served, so waiting for an approval or an
+//! operator reply never counts against the agent. Ledger entries live in
+//! memory; after a restart every session starts a fresh deadline.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::models::session::{ConnectivityStatus, Session, SessionStatus};
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::client::SlackMessage;
+use crate::state::AppState;
+
+/// How often sessions are checked for missed heartbeats.
+pub const SWEEP_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Tool result `status` returned to an agent whose heartbeat is overdue.
+pub const HEARTBEAT_REQUIRED_STATUS: &str = "heartbeat_required";
+
+/// Instruction returned alongside [`HEARTBEAT_REQUIRED_STATUS`].
+pub const HEARTBEAT_DIRECTIVE: &str = "This server requires regular heartbeats and yours is \
+     overdue. Call `ping` with a `status_message` describing what you are doing, then repeat \
+     the refused tool call.";
+
+#[derive(Debug)]
+struct Entry {
+    last_heartbeat: DateTime<Utc>,
+    stalled: bool,
+    /// Tool calls currently being served for the session.
+    in_flight: u32,
+    /// When the oldest in-flight call started.
+    busy_since: Option<DateTime<Utc>>,
+}
+
+impl Entry {
+    fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            last_heartbeat: now,
+            stalled: false,
+            in_flight: 0,
+            busy_since: None,
+        }
+    }
+
+    /// Agent time since the last heartbeat; time spent waiting on the
+    /// server (an approval, an operator reply) does not count.
+    fn elapsed(&self, now: DateTime<Utc>) -> Duration {
+        let until = self.busy_since.unwrap_or(now);
+        (until - self.last_heartbeat).to_std().unwrap_or_default()
+    }
+}
+
+/// Last heartbeat of each session, keyed by session ID.
+#[derive(Debug, Default)]
+pub struct HeartbeatLedger {
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl HeartbeatLedger {
+    /// Create an empty ledger.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether `session_id` has gone longer than `deadline` without a
+    /// heartbeat as of `now`. A session seen for the first time starts its
+    /// clock here and is never overdue.
+    pub fn is_overdue(&self, session_id: &str, now: DateTime<Utc>, deadline: Duration) -> bool {
+        self.lock()
+            .entry(session_id.to_owned())
+            .or_insert_with(|| Entry::new(now))
+            .elapsed(now)
+            > deadline
+    }
+
+    /// Note that a tool call for `session_id` started at `now`. The
+    /// deadline is suspended until every started call has
+    /// [`finished`](Self::finish_call).
+    pub fn start_call(&self, session_id: &str, now: DateTime<Utc>) {
+        let mut entries = self.lock();
+        let entry = entries
+            .entry(session_id.to_owned())
+            .or_insert_with(|| Entry::new(now));
+        if entry.in_flight == 0 {
+            entry.busy_since = Some(now);
+        }
+        entry.in_flight += 1;
+    }
+
+    /// Note that a tool call for `session_id` finished at `now`, crediting
+    /// the time the session spent waiting on the server.
+    pub fn finish_call(&self, session_id: &str, now: DateTime<Utc>) {
+        let mut entries = self.lock();
+        let Some(entry) = entries.get_mut(session_id) else {
+            return;
+        };
+        entry.in_flight = entry.in_flight.saturating_sub(1);
+        if entry.in_flight == 0 {
+            if let Some(since) = entry.busy_since.take() {
+                entry.last_heartbeat += now - since;
+            }
+        }
+    }
+
+    /// Record a heartbeat from `session_id` at `now`.
+    ///
+    /// Returns `true` if the session had been marked stalled.
+    pub fn record(&self, session_id: &str, now: DateTime<Utc>) -> bool {
+        let mut entries = self.lock();
+        let entry = entries
+            .entry(session_id.to_owned())
+            .or_insert_with(|| Entry::new(now));
+        entry.last_heartbeat = now;
+        if entry.busy_since.is_some() {
+            entry.busy_since = Some(now);
+        }
+        std::mem::take(&mut entry.stalled)
+    }
+
+    /// Mark every session overdue as of `now` as stalled, returning those
+    /// not already marked.
+    pub fn take_newly_overdue(&self, now: DateTime<Utc>, deadline: Duration) -> Vec<String> {
+        self.lock()
+            .iter_mut()
+            .filter(|(_, entry)| !entry.stalled && entry.elapsed(now) > deadline)
+            .map(|(session_id, entry)| {
+                entry.stalled = true;
+                session_id.clone()
+            })
+            .collect()
+    }
+
+    /// Stop tracking `session_id`.
+    pub fn forget(&self, session_id: &str) {
+        self.lock().remove(session_id);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Entry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The configured heartbeat deadline, or `None` when heartbeats are optional.
+#[must_use]
+pub fn deadline(state: &AppState) -> Option<Duration> {
+    let stall = &state.config.stall;
+    stall
+        .heartbeat_required
+        .then(|| Duration::from_secs(stall.heartbeat_deadline_seconds))
+}
+
+/// Spawn the periodic missed-heartbeat sweep.
+///
+/// No task is spawned when heartbeats are optional.
+#[must_use]
+pub fn spawn_heartbeat_task(
+    state: Arc<AppState>,
+    cancel: CancellationToken,
+) -> Option<JoinHandle<()>> {
+    deadline(&state)?;
+    Some(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => {
+                    info!("heartbeat enforcement task shutting down");
+                    break;
+                }
+                _ = interval.tick() => enforce_heartbeats(&state, Utc::now()).await,
+            }
+        }
+    }))
+}
+
+/// Mark sessions whose heartbeat lapsed as of `now` stalled and notify the
+/// operator. Failures are logged per session.
+pub async fn enforce_heartbeats(state: &AppState, now: DateTime<Utc>) {
+    let Some(deadline) = deadline(state) else {
+        return;
+    };
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    for session_id in state.heartbeats.take_newly_overdue(now, deadline) {
+        let session = match repo.get_by_id(&session_id).await {
+            Ok(Some(session))
+                if !matches!(
+                    session.status,
+                    SessionStatus::Terminated | SessionStatus::Interrupted
+                ) =>
+            {
+                session
+            }
+            Ok(_) => {
+                state.heartbeats.forget(&session_id);
+                continue;
+            }
+            Err(err) => {
+                warn!(%err, session_id, "failed to load session for heartbeat check");
+                continue;
+            }
+        };
+        if let Err(err) = repo
+            .set_connectivity_status(&session.id, ConnectivityStatus::Stalled)
+            .await
+        {
+            warn!(%err, session_id, "failed to mark session stalled");
+        }
+        warn!(session_id, "heartbeat deadline missed; tool calls blocked");
+        notify(
+            state,
+            &session,
+            format!(
+                "\u{1f493} Session `{}` missed its heartbeat deadline ({}s). Tool calls are \
+                 blocked until the agent pings with a status.",
+                session.id,
+                deadline.as_secs()
+            ),
+        )
+        .await;
+    }
+}
+
+/// Record a heartbeat from `session`, lifting a missed-heartbeat block.
+pub async fn record_heartbeat(state: &AppState, session: &Session) {
+    if deadline(state).is_none() || !state.heartbeats.record(&session.id, Utc::now()) {
+        return;
+    }
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    if let Err(err) = repo
+        .set_connectivity_status(&session.id, ConnectivityStatus::Online)
+        .await
+    {
+        warn!(%err, session_id = %session.id, "failed to mark session online");
+    }
+    info!(session_id = %session.id, "heartbeat resumed; tool calls unblocked");
+    notify(
+        state,
+        session,
+        format!(
+            "\u{1f49a} Session `{}` is heartbeating again; tool calls are unblocked.",
+            session.id
+        ),
+    )
+    .await;
+}
+
+/// Post `text` to the session's thread unless the session is muted.
+async fn notify(state: &AppState, session: &Session, text: String) {
+    let (Some(ref slack), Some(ref channel_id)) = (&state.slack, &session.channel_id) else {
+        return;
+    };
+    if session.is_muted(Utc::now()) {
+        return;
+    }
+    let msg = SlackMessage {
+        channel: SlackChannelId(channel_id.clone()),
+        text: Some(text),
+        blocks: None,
+        thread_ts: session.thread_ts.clone().map(SlackTs),
+    };
+    if let Err(err) = slack.enqueue(msg).await {
+        warn!(%err, session_id = %session.id, "failed to post heartbeat notice");
+    }
+}
@@ -0,0 +1,58 @@
+//! Instructions queued for an agent's next standby (`/intercom standby-queue`).
+//!
+//! Operators can line up several instructions while an agent is busy. When
+//! the agent next calls `standby`, the wait resolves at once with the first
+//! queued instruction and the rest are delivered as steering messages, in
+//! the order they were queued. An instruction queued while the agent is
+//! already waiting resolves that wait directly.
+//!
+//! Queues live in memory: sessions still active at shutdown are marked
+//! interrupted on restart, so there is no standby left to feed.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, PoisonError};
+
+/// Queued instructions, keyed by session ID.
+#[derive(Debug, Default)]
+pub struct InstructionQueue {
+    queues: Mutex<HashMap<String, VecDeque<String>>>,
+}
+
+impl InstructionQueue {
+    /// Create an empty queue table.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `instruction` to the queue of `session_id`, returning its
+    /// 1-based position.
+    pub fn push(&self, session_id: &str, instruction: String) -> usize {
+        let mut queues = self.lock();
+        let queue = queues.entry(session_id.to_owned()).or_default();
+        queue.push_back(instruction);
+        queue.len()
+    }
+
+    /// The instructions queued for `session_id`, oldest first.
+    #[must_use]
+    pub fn list(&self, session_id: &str) -> Vec<String> {
+        self.lock()
+            .get(session_id)
+            .map(|queue| queue.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Remove and return every instruction queued for `session_id`, oldest
+    /// first.
+    pub fn take_all(&self, session_id: &str) -> Vec<String> {
+        self.lock()
+            .remove(session_id)
+            .map(Vec::from)
+            .unwrap_or_default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, VecDeque<String>>> {
+        self.queues.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
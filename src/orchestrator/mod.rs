@@ -1,12 +1,59 @@
 //! Session orchestration modules.
 //!
 //! Covers agent process spawning, session lifecycle management,
-//! checkpoint creation/restore, stall detection, stall event
-//! dispatching, and child process monitoring.
+//! checkpoint creation/restore, automatic periodic checkpoints, stall
+//! detection, stall event dispatching, stall outcome statistics,
+//! heartbeat enforcement, queued
+//! standby instructions, prompt auto-policies, snoozed decisions, pending
+//! requests re-armed after a restart, interrupted sessions recovered on
+//! startup, approval conflicts between concurrent sessions, named runs
+//! grouping sessions, streamed session logs, agent event bus subscribers,
+//! steering expiry, session time boxes,
+//! session reports, subtask completion reports, moving sessions between
+//! channels, bulk session operations, unmapped workspace discovery,
+//! runtime workspace mapping edits, internal queue alarms, child process
+//! monitoring, drift between the auto-approve rule files, approved diffs
+//! applied on an operator's schedule, full-workspace snapshots, workspace
+//! bootstrap commands, unapplied diffs gone stale through
+//! out-of-band edits, working-hours routing between a workspace's
+//! channels, and the self-monitoring watchdog.
 
+pub mod apply_guard;
+pub mod approval_conflicts;
+pub mod approval_stats;
+pub mod auto_checkpoints;
 pub mod checkpoint_manager;
 pub mod child_monitor;
+pub mod command_aliases;
+pub mod event_subscribers;
+pub mod file_change_watcher;
+pub mod heartbeat_enforcer;
+pub mod instruction_queue;
+pub mod policy_drift;
+pub mod prompt_policy;
+pub mod queue_alarms;
+pub mod rearm;
+pub mod runs;
+pub mod scheduled_apply;
+pub mod session_bulk;
+pub mod session_logs;
 pub mod session_manager;
+pub mod session_move;
+pub mod session_report;
+pub mod session_timebox;
+pub mod session_timeline;
+pub mod simulated_operator;
+pub mod snooze;
 pub mod spawner;
 pub mod stall_consumer;
 pub mod stall_detector;
+pub mod stall_stats;
+pub mod startup_recovery;
+pub mod steering_expiry;
+pub mod subtask;
+pub mod watchdog;
+pub mod working_hours;
+pub mod workspace_bootstrap;
+pub mod workspace_discovery;
+pub mod workspace_mappings;
+pub mod workspace_snapshot;
@@ -0,0 +1,377 @@
+//! Drift between the files holding a workspace's auto-approve rules
+//! (`[policy_drift]`).
+//!
+//! An approved auto-approve suggestion writes its pattern to
+//! `.intercom/settings.json`, the workspace's `*.code-workspace` file and
+//! `.vscode/settings.json`, but manual edits can make them disagree. A
+//! sweep every `interval_minutes` compares the `chat.tools.terminal.autoApprove`
+//! patterns of the files that exist in each workspace and posts any
+//! disagreement to the workspace channel with a Reconcile button, once per
+//! distinct drift. Reconciling adds every missing pattern to every file, so
+//! the files end up with the union of their rules.
+//!
+//! Only approving entries count: a pattern mapped to `false` (VS Code's
+//! "never auto-approve") is neither compared nor copied.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError};
+
+use slack_morphism::prelude::SlackChannelId;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::slack::blocks;
+use crate::slack::client::SlackMessage;
+use crate::slack::handlers::command_approve::{
+    add_pattern_to_settings, add_pattern_to_vscode_settings, add_pattern_to_workspace_file,
+    find_workspace_file, strip_jsonc_line_comment,
+};
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Setting holding the auto-approve patterns in every file.
+const AUTO_APPROVE_KEY: &str = "chat.tools.terminal.autoApprove";
+
+/// Missing patterns listed per file in a drift report before the rest are
+/// counted.
+const REPORT_PATTERN_LIMIT: usize = 10;
+
+/// Which of the three rule files a [`RuleFile`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleFileKind {
+    /// `.intercom/settings.json`, read by agent-intercom's policy loader.
+    IntercomSettings,
+    /// The first `*.code-workspace` file in the workspace root.
+    CodeWorkspace,
+    /// `.vscode/settings.json`.
+    VscodeSettings,
+}
+
+/// The auto-approve patterns one file holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleFile {
+    /// Which file this is.
+    pub kind: RuleFileKind,
+    /// Where it is.
+    pub path: PathBuf,
+    /// Patterns it auto-approves.
+    pub patterns: BTreeSet<String>,
+}
+
+/// Auto-approve rule files of one workspace that disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyDrift {
+    /// Workspace root the files belong to.
+    pub workspace_root: PathBuf,
+    /// Every rule file found, in [`RuleFileKind`] order.
+    pub files: Vec<RuleFile>,
+}
+
+impl PolicyDrift {
+    /// For each file lacking some pattern another file has, those
+    /// patterns.
+    #[must_use]
+    pub fn missing(&self) -> Vec<(&RuleFile, Vec<&str>)> {
+        let union: BTreeSet<&str> = self
+            .files
+            .iter()
+            .flat_map(|file| file.patterns.iter().map(String::as_str))
+            .collect();
+        self.files
+            .iter()
+            .filter_map(|file| {
+                let lacking: Vec<&str> = union
+                    .iter()
+                    .copied()
+                    .filter(|pattern| !file.patterns.contains(*pattern))
+                    .collect();
+                (!lacking.is_empty()).then_some((file, lacking))
+            })
+            .collect()
+    }
+
+    /// Identifies this drift, so the same disagreement is reported once.
+    #[must_use]
+    pub fn fingerprint(&self) -> String {
+        let mut text = String::new();
+        for file in &self.files {
+            let _ = write!(text, "{}:", file.path.display());
+            for pattern in &file.patterns {
+                let _ = write!(text, "{pattern}\u{1f}");
+            }
+            text.push('\u{1e}');
+        }
+        text
+    }
+
+    /// Slack text describing the drift: each file and the patterns it
+    /// lacks.
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let mut text = format!(
+            "\u{26a0}\u{fe0f} *Auto-approve rules have drifted* in `{}`",
+            self.workspace_root.display()
+        );
+        for (file, lacking) in self.missing() {
+            let _ = write!(
+                text,
+                "\n\u{2022} `{}` is missing {} pattern{}:",
+                self.relative(&file.path),
+                lacking.len(),
+                if lacking.len() == 1 { "" } else { "s" }
+            );
+            for pattern in lacking.iter().take(REPORT_PATTERN_LIMIT) {
+                let _ = write!(text, "\n    `{pattern}`");
+            }
+            if lacking.len() > REPORT_PATTERN_LIMIT {
+                let _ = write!(
+                    text,
+                    "\n    … and {} more",
+                    lacking.len() - REPORT_PATTERN_LIMIT
+                );
+            }
+        }
+        text.push_str(
+            "\nReconcile adds every missing pattern to every file. To drop a pattern, \
+             remove it from all of them.",
+        );
+        text
+    }
+
+    fn relative(&self, path: &Path) -> String {
+        path.strip_prefix(&self.workspace_root)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+}
+
+/// Read the auto-approve patterns of every rule file that exists in
+/// `workspace_root`. A file without the setting holds no patterns.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` if a file cannot be read or is not valid
+/// JSON (line comments allowed).
+pub fn rule_files(workspace_root: &Path) -> Result<Vec<RuleFile>> {
+    let mut files = Vec::new();
+    let intercom = workspace_root.join(".intercom").join("settings.json");
+    if intercom.exists() {
+        files.push(read_rule_file(RuleFileKind::IntercomSettings, intercom)?);
+    }
+    if let Some(path) = find_workspace_file(workspace_root)? {
+        files.push(read_rule_file(RuleFileKind::CodeWorkspace, path)?);
+    }
+    let vscode = workspace_root.join(".vscode").join("settings.json");
+    if vscode.exists() {
+        files.push(read_rule_file(RuleFileKind::VscodeSettings, vscode)?);
+    }
+    Ok(files)
+}
+
+/// Compare the rule files of `workspace_root`. Returns `None` when fewer
+/// than two exist or they all hold the same patterns.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` if a rule file cannot be read or parsed.
+pub fn detect(workspace_root: &Path) -> Result<Option<PolicyDrift>> {
+    let files = rule_files(workspace_root)?;
+    let agree = files
+        .windows(2)
+        .all(|pair| pair[0].patterns == pair[1].patterns);
+    if files.len() < 2 || agree {
+        return Ok(None);
+    }
+    Ok(Some(PolicyDrift {
+        workspace_root: workspace_root.to_path_buf(),
+        files,
+    }))
+}
+
+/// Add every pattern any rule file of `workspace_root` holds to the files
+/// lacking it, keeping their comments where possible. Returns the number
+/// of patterns written.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` if a rule file cannot be read, parsed or
+/// written.
+pub fn reconcile(workspace_root: &Path) -> Result<usize> {
+    let Some(drift) = detect(workspace_root)? else {
+        return Ok(0);
+    };
+    let mut written = 0;
+    for (file, lacking) in drift.missing() {
+        for pattern in lacking {
+            match file.kind {
+                RuleFileKind::IntercomSettings => add_pattern_to_settings(&file.path, pattern)?,
+                RuleFileKind::CodeWorkspace => {
+                    add_pattern_to_workspace_file(workspace_root, pattern)?;
+                }
+                RuleFileKind::VscodeSettings => {
+                    add_pattern_to_vscode_settings(workspace_root, pattern)?;
+                }
+            }
+            written += 1;
+        }
+    }
+    Ok(written)
+}
+
+fn read_rule_file(kind: RuleFileKind, path: PathBuf) -> Result<RuleFile> {
+    let raw = std::fs::read_to_string(&path)
+        .map_err(|e| AppError::Config(format!("read {}: {e}", path.display())))?;
+    let stripped = raw
+        .lines()
+        .map(|line| strip_jsonc_line_comment(line).into_owned())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let root: serde_json::Value = serde_json::from_str(&stripped)
+        .map_err(|e| AppError::Config(format!("parse {}: {e}", path.display())))?;
+    // The workspace file nests its settings under "settings".
+    let setting = match kind {
+        RuleFileKind::CodeWorkspace => root.get("settings").and_then(|s| s.get(AUTO_APPROVE_KEY)),
+        RuleFileKind::IntercomSettings | RuleFileKind::VscodeSettings => root.get(AUTO_APPROVE_KEY),
+    };
+    Ok(RuleFile {
+        kind,
+        path,
+        patterns: setting.map(approving_patterns).unwrap_or_default(),
+    })
+}
+
+/// Patterns an auto-approve setting approves: array elements, and map keys
+/// whose value is `true` or an object with `"approve": true`.
+fn approving_patterns(setting: &serde_json::Value) -> BTreeSet<String> {
+    match setting {
+        serde_json::Value::Array(items) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(str::to_owned))
+            .collect(),
+        serde_json::Value::Object(map) => map
+            .iter()
+            .filter(|(_, value)| {
+                value.as_bool().unwrap_or(false)
+                    || value
+                        .get("approve")
+                        .and_then(serde_json::Value::as_bool)
+                        .unwrap_or(false)
+            })
+            .map(|(pattern, _)| pattern.clone())
+            .collect(),
+        _ => BTreeSet::new(),
+    }
+}
+
+/// Workspace roots to check, each with the channel its drift is reported
+/// to: every `[[workspace]]` with a `path`, then `default_workspace_root`
+/// reported to the global channel.
+#[must_use]
+pub fn watched_workspaces(state: &AppState) -> Vec<(PathBuf, String)> {
+    let mut watched: Vec<(PathBuf, String)> = state
+        .workspace_mappings
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .filter_map(|m| m.path.clone().map(|path| (path, m.channel_id.clone())))
+        .collect();
+    let default_root = state.config.default_workspace_root();
+    if !watched.iter().any(|(path, _)| path == default_root) {
+        watched.push((
+            default_root.to_path_buf(),
+            state.config.slack.channel_id.clone(),
+        ));
+    }
+    watched
+}
+
+/// Spawn the periodic drift check.
+///
+/// No task is spawned when drift checks are disabled.
+#[must_use]
+pub fn spawn_policy_drift_task(
+    state: Arc<AppState>,
+    cancel: CancellationToken,
+) -> Option<JoinHandle<()>> {
+    let period = state.config.policy_drift.interval()?;
+    Some(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        let mut reported = BTreeMap::new();
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => {
+                    info!("policy drift task shutting down");
+                    break;
+                }
+                _ = interval.tick() => {
+                    for (drift, channel) in check_drift(&state, &mut reported).await {
+                        post_drift(&state, &drift, &channel).await;
+                    }
+                }
+            }
+        }
+    }))
+}
+
+/// Check every watched workspace, returning the drift not yet reported
+/// with the channel to report it to. `reported` remembers the last drift
+/// reported per workspace and forgets it once the files agree again.
+pub async fn check_drift(
+    state: &AppState,
+    reported: &mut BTreeMap<PathBuf, String>,
+) -> Vec<(PolicyDrift, String)> {
+    let mut fresh = Vec::new();
+    for (root, channel) in watched_workspaces(state) {
+        let probe = root.clone();
+        let detected = match tokio::task::spawn_blocking(move || detect(&probe)).await {
+            Ok(detected) => detected,
+            Err(err) => {
+                warn!(%err, root = %root.display(), "policy drift check panicked");
+                continue;
+            }
+        };
+        match detected {
+            Ok(Some(drift)) => {
+                let fingerprint = drift.fingerprint();
+                if reported.get(&root) != Some(&fingerprint) {
+                    reported.insert(root, fingerprint);
+                    fresh.push((drift, channel));
+                }
+            }
+            Ok(None) => {
+                reported.remove(&root);
+            }
+            Err(err) => warn!(%err, root = %root.display(), "policy drift check failed"),
+        }
+    }
+    fresh
+}
+
+async fn post_drift(state: &AppState, drift: &PolicyDrift, channel: &str) {
+    info!(
+        root = %drift.workspace_root.display(),
+        "auto-approve rule files have drifted"
+    );
+    let Some(ref slack) = state.slack else {
+        return;
+    };
+    if channel.is_empty() {
+        return;
+    }
+    let message = SlackMessage {
+        channel: SlackChannelId(channel.to_owned()),
+        text: Some("Auto-approve rules have drifted".to_owned()),
+        blocks: Some(blocks::policy_drift_blocks(
+            &drift.describe(),
+            &drift.workspace_root.to_string_lossy(),
+        )),
+        thread_ts: None,
+    };
+    if let Err(err) = slack.post_message_direct(message).await {
+        warn!(%err, channel, "failed to post policy drift report");
+    }
+}
@@ -0,0 +1,146 @@
+//! Automatic answers to forwarded prompts (`[[prompts.auto]]`).
+//!
+//! Before a prompt is posted for the operator, the configured rules are
+//! checked in order. The first rule that matches the prompt's type, the
+//! server-local time and the session's run of same-type prompts answers it:
+//! the decision is recorded on the prompt, a `prompt_auto_decision` audit
+//! entry is written, and an FYI is posted to the session thread. The agent
+//! gets the decision at once.
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use chrono::Local;
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
+use tracing::{info, warn};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::config::PromptAutoRule;
+use crate::models::prompt::{ContinuationPrompt, PromptDecision, PromptType};
+use crate::models::session::Session;
+use crate::persistence::prompt_repo::PromptRepo;
+use crate::slack::blocks;
+use crate::slack::client::SlackMessage;
+use crate::state::AppState;
+
+/// Answer `prompt` from the first matching auto rule, if any.
+///
+/// `prompt` must already be persisted. Returns the decision and instruction
+/// to hand the agent, or `None` when the operator should be asked. Lookup
+/// and storage failures are logged and leave the prompt to the operator.
+pub async fn auto_decide(
+    state: &AppState,
+    session: &Session,
+    prompt: &ContinuationPrompt,
+) -> Option<(PromptDecision, Option<String>)> {
+    let prompts = &state.config.prompts;
+    if prompts.auto.is_empty() {
+        return None;
+    }
+
+    let repo = PromptRepo::new(Arc::clone(&state.db));
+    let history = match repo.list_for_session(&session.id).await {
+        Ok(history) => history,
+        Err(err) => {
+            warn!(%err, prompt_id = %prompt.id, "failed to load prompt history for auto-policy");
+            return None;
+        }
+    };
+    let consecutive = consecutive_of_type(&history, prompt.prompt_type);
+    let rule = prompts.auto_rule(prompt.prompt_type, consecutive, Local::now().naive_local())?;
+
+    let instruction = if rule.decision == PromptDecision::Refine {
+        rule.instruction.clone()
+    } else {
+        None
+    };
+    if let Err(err) = repo
+        .update_decision(&prompt.id, rule.decision, instruction.clone())
+        .await
+    {
+        warn!(%err, prompt_id = %prompt.id, "failed to record auto-policy decision");
+        return None;
+    }
+
+    let summary = describe(rule);
+    info!(
+        prompt_id = %prompt.id,
+        session_id = %session.id,
+        decision = rule.decision.as_str(),
+        rule = %summary,
+        "prompt answered by auto-policy"
+    );
+
+    if let Some(ref logger) = state.audit_logger {
+        let entry = AuditEntry::new(AuditEventType::PromptAutoDecision)
+            .with_session(session.id.clone())
+            .with_request_id(prompt.id.clone())
+            .with_result(format!("{}: {summary}", rule.decision.as_str()));
+        if let Err(err) = logger.log_entry(entry) {
+            warn!(%err, "audit log write failed (prompt auto decision)");
+        }
+    }
+
+    notify(state, session, prompt, rule.decision, &summary).await;
+    Some((rule.decision, instruction))
+}
+
+/// How many prompts at the end of `history` (oldest first) are of
+/// `prompt_type`.
+#[must_use]
+pub fn consecutive_of_type(history: &[ContinuationPrompt], prompt_type: PromptType) -> u32 {
+    let run = history
+        .iter()
+        .rev()
+        .take_while(|prompt| prompt.prompt_type == prompt_type)
+        .count();
+    u32::try_from(run).unwrap_or(u32::MAX)
+}
+
+/// Short description of when `rule` applies, e.g.
+/// `error_recovery after 3 in a row`.
+#[must_use]
+pub fn describe(rule: &PromptAutoRule) -> String {
+    let mut text = rule.prompt_type.as_str().to_owned();
+    if rule.after_consecutive > 1 {
+        let _ = write!(text, " after {} in a row", rule.after_consecutive);
+    }
+    if let Some(ref hours) = rule.hours {
+        let _ = write!(text, " during {hours}");
+    }
+    if rule.weekdays_only {
+        text.push_str(" on weekdays");
+    }
+    text
+}
+
+/// Post an FYI about the automatic answer to the session's thread.
+async fn notify(
+    state: &AppState,
+    session: &Session,
+    prompt: &ContinuationPrompt,
+    decision: PromptDecision,
+    summary: &str,
+) {
+    let (Some(ref slack), Some(ref channel_id)) = (&state.slack, &session.channel_id) else {
+        return;
+    };
+    let label = match decision {
+        PromptDecision::Continue => "Continue",
+        PromptDecision::Refine => "Refine",
+        PromptDecision::Stop => "Stop",
+    };
+    let msg = SlackMessage {
+        channel: SlackChannelId(channel_id.clone()),
+        text: Some(format!(
+            "\u{1f916} FYI: {} prompt answered *{label}* by auto-policy `{summary}`: {}",
+            blocks::prompt_type_label(prompt.prompt_type),
+            blocks::slack_escape(&blocks::truncate_text(&prompt.prompt_text, 160)),
+        )),
+        blocks: None,
+        thread_ts: session.thread_ts.clone().map(SlackTs),
+    };
+    if let Err(err) = slack.enqueue(msg).await {
+        warn!(%err, prompt_id = %prompt.id, "failed to post auto-policy notice");
+    }
+}
@@ -0,0 +1,297 @@
+//! Alarms on sustained internal queue build-up (`[alarms]`).
+//!
+//! A queue that keeps growing is usually the first sign of a wedged
+//! subsystem: Slack messages pile up when delivery stalls, steering
+//! messages when agents stop collecting them, approvals when nobody is
+//! deciding. A periodic task samples each queue and posts an alarm once
+//! one stays at or above its threshold for `sustain_seconds`, then a
+//! notice when it drains. Alarms are posted directly, bypassing the Slack
+//! outgoing queue they may be reporting on.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::config::AlarmsConfig;
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::steering_repo::SteeringRepo;
+use crate::slack::client::post_server_notice;
+use crate::state::AppState;
+use crate::Result;
+
+/// An internal queue watched for build-up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueueKind {
+    /// Messages and updates waiting to be sent to Slack.
+    SlackOutbound,
+    /// Steering messages not yet delivered to their agent.
+    Steering,
+    /// Approval requests awaiting an operator decision.
+    PendingApprovals,
+}
+
+impl QueueKind {
+    /// Every watched queue, in reporting order.
+    pub const ALL: [Self; 3] = [Self::SlackOutbound, Self::Steering, Self::PendingApprovals];
+
+    /// Name used in logs and in `[alarms]`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SlackOutbound => "slack_outbound",
+            Self::Steering => "steering",
+            Self::PendingApprovals => "pending_approvals",
+        }
+    }
+
+    /// Name shown to the operator.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::SlackOutbound => "Slack outgoing queue",
+            Self::Steering => "Steering queue",
+            Self::PendingApprovals => "Pending approvals",
+        }
+    }
+
+    /// Likely cause, appended to a raised alarm.
+    fn hint(self) -> &'static str {
+        match self {
+            Self::SlackOutbound => "Slack delivery may be stalled or rate limited.",
+            Self::Steering => "Agents may have stopped calling `ping` to collect messages.",
+            Self::PendingApprovals => "Approval requests are waiting on an operator decision.",
+        }
+    }
+
+    /// Configured threshold; `0` turns the alarm off.
+    #[must_use]
+    pub fn threshold(self, config: &AlarmsConfig) -> u64 {
+        match self {
+            Self::SlackOutbound => config.slack_outbound,
+            Self::Steering => config.steering,
+            Self::PendingApprovals => config.pending_approvals,
+        }
+    }
+}
+
+/// A change in a queue's alarm state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlarmChange {
+    /// The queue stayed at or above its threshold for the sustain period.
+    Raised {
+        /// Queue that alarmed.
+        queue: QueueKind,
+        /// Depth at the sample that raised it.
+        depth: u64,
+        /// Threshold it crossed.
+        threshold: u64,
+        /// How long it has been at or above the threshold.
+        sustained: Duration,
+    },
+    /// A raised queue dropped back below its threshold.
+    Cleared {
+        /// Queue that recovered.
+        queue: QueueKind,
+        /// Depth at the sample that cleared it.
+        depth: u64,
+        /// Threshold it dropped below.
+        threshold: u64,
+    },
+}
+
+/// Per-queue alarm state between samples.
+#[derive(Debug, Default)]
+struct QueueState {
+    /// First sample of the current run at or above the threshold.
+    over_since: Option<Instant>,
+    /// Whether an alarm is out for the current run.
+    raised: bool,
+}
+
+/// Tracks how long each queue has been over its threshold.
+#[derive(Debug, Default)]
+pub struct QueueAlarms {
+    states: HashMap<QueueKind, QueueState>,
+}
+
+impl QueueAlarms {
+    /// Record a sample of `queue` taken at `now`. Returns the alarm to
+    /// raise or clear, if the sample changes the queue's state.
+    ///
+    /// A threshold of `0` disables the queue and forgets any run in
+    /// progress, without a clear notice.
+    pub fn observe(
+        &mut self,
+        queue: QueueKind,
+        depth: u64,
+        threshold: u64,
+        sustain: Duration,
+        now: Instant,
+    ) -> Option<AlarmChange> {
+        if threshold == 0 {
+            self.states.remove(&queue);
+            return None;
+        }
+        let state = self.states.entry(queue).or_default();
+        if depth >= threshold {
+            let since = *state.over_since.get_or_insert(now);
+            let sustained = now.saturating_duration_since(since);
+            if !state.raised && sustained >= sustain {
+                state.raised = true;
+                return Some(AlarmChange::Raised {
+                    queue,
+                    depth,
+                    threshold,
+                    sustained,
+                });
+            }
+            return None;
+        }
+        state.over_since = None;
+        if state.raised {
+            state.raised = false;
+            return Some(AlarmChange::Cleared {
+                queue,
+                depth,
+                threshold,
+            });
+        }
+        None
+    }
+
+    /// Whether an alarm is out for `queue`.
+    #[must_use]
+    pub fn is_raised(&self, queue: QueueKind) -> bool {
+        self.states.get(&queue).is_some_and(|state| state.raised)
+    }
+}
+
+/// Current depth of `queue`. The Slack queue is empty on a server without
+/// Slack.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if a count query fails.
+pub async fn depth(state: &AppState, queue: QueueKind) -> Result<u64> {
+    match queue {
+        QueueKind::SlackOutbound => Ok(state.slack.as_ref().map_or(0, |slack| {
+            u64::try_from(slack.queue_depth()).unwrap_or(u64::MAX)
+        })),
+        QueueKind::Steering => {
+            SteeringRepo::new(Arc::clone(&state.db))
+                .count_pending()
+                .await
+        }
+        QueueKind::PendingApprovals => {
+            ApprovalRepo::new(Arc::clone(&state.db))
+                .count_pending()
+                .await
+        }
+    }
+}
+
+/// Slack text for `change`. `mention` is prepended to raised alarms.
+#[must_use]
+pub fn describe(change: &AlarmChange, mention: Option<&str>) -> String {
+    match *change {
+        AlarmChange::Raised {
+            queue,
+            depth,
+            threshold,
+            sustained,
+        } => {
+            let mention = mention.map(|m| format!("{m} ")).unwrap_or_default();
+            format!(
+                "\u{1f6a8} {mention}*Queue alarm:* {} has held {depth} items \
+                 (threshold {threshold}) for {}s. {}",
+                queue.label(),
+                sustained.as_secs(),
+                queue.hint()
+            )
+        }
+        AlarmChange::Cleared {
+            queue,
+            depth,
+            threshold,
+        } => format!(
+            "\u{2705} *Queue alarm cleared:* {} is down to {depth} (threshold {threshold}).",
+            queue.label()
+        ),
+    }
+}
+
+/// Spawn the periodic queue sampler. Returns `None` when `[alarms]` is
+/// disabled.
+#[must_use]
+pub fn spawn_queue_alarm_task(
+    state: Arc<AppState>,
+    cancel: CancellationToken,
+) -> Option<JoinHandle<()>> {
+    let config = state.config.alarms.clone();
+    if !config.enabled {
+        return None;
+    }
+    Some(tokio::spawn(async move {
+        let mut alarms = QueueAlarms::default();
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(config.check_interval_seconds));
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => {
+                    info!("queue alarm task shutting down");
+                    break;
+                }
+                _ = interval.tick() => check_queues(&state, &config, &mut alarms).await,
+            }
+        }
+    }))
+}
+
+/// Sample every queue once and report alarm changes.
+async fn check_queues(state: &AppState, config: &AlarmsConfig, alarms: &mut QueueAlarms) {
+    let sustain = Duration::from_secs(config.sustain_seconds);
+    for queue in QueueKind::ALL {
+        let threshold = queue.threshold(config);
+        if threshold == 0 {
+            continue;
+        }
+        let depth = match depth(state, queue).await {
+            Ok(depth) => depth,
+            Err(err) => {
+                warn!(%err, queue = queue.as_str(), "queue depth sample failed");
+                continue;
+            }
+        };
+        let Some(change) = alarms.observe(queue, depth, threshold, sustain, Instant::now()) else {
+            continue;
+        };
+        match change {
+            AlarmChange::Raised { .. } => {
+                warn!(
+                    queue = queue.as_str(),
+                    depth, threshold, "queue alarm raised"
+                );
+            }
+            AlarmChange::Cleared { .. } => {
+                info!(
+                    queue = queue.as_str(),
+                    depth, threshold, "queue alarm cleared"
+                );
+            }
+        }
+        let mention = config
+            .escalate
+            .then_some(state.config.broadcast.escalation_mention.trim())
+            .filter(|m| !m.is_empty());
+        post_server_notice(
+            state,
+            config.channel_id.as_deref(),
+            &describe(&change, mention),
+        )
+        .await;
+    }
+}
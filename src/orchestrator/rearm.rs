@@ -0,0 +1,243 @@
+//! Re-arming pending approvals and prompts after a restart (`[recovery]`).
+//!
+//! With `rearm_pending` set, a shutdown leaves pending requests pending.
+//! On the next startup [`rearm_pending`] registers a fresh oneshot for
+//! each, so the Slack buttons resolve again, and re-posts them to their
+//! session channels. The receiving halves wait here until the agent
+//! reconnects and collects the decision with `reboot`, or, when
+//! `on_startup` re-posts them, until [`expire_unclaimed`] times them out.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use slack_morphism::prelude::SlackChannelId;
+use tokio::sync::oneshot;
+use tracing::{info, warn};
+
+use crate::models::approval::ApprovalStatus;
+use crate::models::prompt::PromptDecision;
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::prompt_repo::PromptRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::anchor::Anchor;
+use crate::slack::client::{approval_repost, prompt_repost, SlackMessage};
+use crate::state::{AppState, ApprovalResponse, PromptResponse};
+
+/// Heading of re-armed request messages.
+const HEADING: &str = "Still pending after a server restart";
+
+/// The receiving half of a re-armed request.
+#[derive(Debug)]
+pub enum Rearmed {
+    /// An approval request.
+    Approval(oneshot::Receiver<ApprovalResponse>),
+    /// A continuation prompt.
+    Prompt(oneshot::Receiver<PromptResponse>),
+}
+
+/// Re-armed requests awaiting their agent, keyed by request or prompt ID.
+#[derive(Debug, Default)]
+pub struct RearmedRequests {
+    waiters: Mutex<HashMap<String, (String, Rearmed)>>,
+}
+
+impl RearmedRequests {
+    /// Create an empty table.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Park the receiver of request `id`, which belongs to `session_id`.
+    pub fn insert(&self, id: &str, session_id: &str, rearmed: Rearmed) {
+        self.lock()
+            .insert(id.to_owned(), (session_id.to_owned(), rearmed));
+    }
+
+    /// Whether request `id` was re-armed and not yet collected.
+    #[must_use]
+    pub fn contains(&self, id: &str) -> bool {
+        self.lock().contains_key(id)
+    }
+
+    /// Remove and return a re-armed request of `session_id`, approvals
+    /// first.
+    pub fn take_for_session(&self, session_id: &str) -> Option<(String, Rearmed)> {
+        let mut waiters = self.lock();
+        let id = waiters
+            .iter()
+            .filter(|(_, (owner, _))| owner == session_id)
+            .min_by_key(|(_, (_, rearmed))| matches!(rearmed, Rearmed::Prompt(_)))
+            .map(|(id, _)| id.clone())?;
+        waiters.remove(&id).map(|(_, rearmed)| (id, rearmed))
+    }
+
+    /// Remove and return re-armed request `id` with its session ID.
+    pub fn take(&self, id: &str) -> Option<(String, Rearmed)> {
+        self.lock().remove(id)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (String, Rearmed)>> {
+        self.waiters.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Re-arm every pending approval and prompt left by the previous run.
+///
+/// Requests of sessions bound to a channel other than the global one are
+/// re-posted there; those in the global channel are re-posted by the
+/// Socket Mode hello handler. Returns the number of approvals and prompts
+/// re-armed.
+pub async fn rearm_pending(state: &AppState) -> (usize, usize) {
+    let approval_repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let prompt_repo = PromptRepo::new(Arc::clone(&state.db));
+
+    let approvals = approval_repo.list_pending().await.unwrap_or_else(|err| {
+        warn!(%err, "failed to list pending approvals to re-arm");
+        Vec::new()
+    });
+    for req in &approvals {
+        let (tx, rx) = oneshot::channel::<ApprovalResponse>();
+        state
+            .pending_approvals
+            .lock()
+            .await
+            .insert(req.id.clone(), tx);
+        state
+            .rearmed
+            .insert(&req.id, &req.session_id, Rearmed::Approval(rx));
+
+        if let Some(channel) = session_channel(state, &req.session_id).await {
+            let message = approval_repost(channel, req, HEADING);
+            if let Some(anchor) = repost(state, message).await {
+                if let Err(err) = approval_repo
+                    .set_slack_anchor(&req.id, &anchor.ts.0, &anchor.thread_ts.0)
+                    .await
+                {
+                    warn!(%err, request_id = %req.id, "failed to record re-armed approval anchor");
+                }
+            }
+        }
+    }
+
+    let prompts = prompt_repo.list_pending().await.unwrap_or_else(|err| {
+        warn!(%err, "failed to list pending prompts to re-arm");
+        Vec::new()
+    });
+    for prompt in &prompts {
+        let (tx, rx) = oneshot::channel::<PromptResponse>();
+        state
+            .pending_prompts
+            .lock()
+            .await
+            .insert(prompt.id.clone(), tx);
+        state
+            .rearmed
+            .insert(&prompt.id, &prompt.session_id, Rearmed::Prompt(rx));
+
+        if let Some(channel) = session_channel(state, &prompt.session_id).await {
+            let message = prompt_repost(
+                channel,
+                prompt,
+                state.config.prompts.quick_replies_for(prompt.prompt_type),
+                HEADING,
+            );
+            if let Some(anchor) = repost(state, message).await {
+                if let Err(err) = prompt_repo
+                    .set_slack_anchor(&prompt.id, &anchor.ts.0, &anchor.thread_ts.0)
+                    .await
+                {
+                    warn!(%err, prompt_id = %prompt.id, "failed to record re-armed prompt anchor");
+                }
+            }
+        }
+    }
+
+    info!(
+        approvals = approvals.len(),
+        prompts = prompts.len(),
+        "re-armed pending requests"
+    );
+    (approvals.len(), prompts.len())
+}
+
+/// Give every request re-armed and not yet collected a fresh timeout.
+///
+/// An approval still undecided after `approval_seconds` is marked
+/// `Expired` and a prompt after `prompt_seconds` auto-continues, as when
+/// the original tool call timed out. A decision the operator made in time
+/// stays parked for the agent.
+pub async fn expire_unclaimed(state: &Arc<AppState>) {
+    let approvals = ApprovalRepo::new(Arc::clone(&state.db))
+        .list_pending()
+        .await
+        .unwrap_or_default();
+    let prompts = PromptRepo::new(Arc::clone(&state.db))
+        .list_pending()
+        .await
+        .unwrap_or_default();
+    let approval_timeout = Duration::from_secs(state.config.timeouts.approval_seconds);
+    let prompt_timeout = Duration::from_secs(state.config.timeouts.prompt_seconds);
+    let ids = approvals
+        .into_iter()
+        .map(|req| (req.id, approval_timeout))
+        .chain(
+            prompts
+                .into_iter()
+                .map(|prompt| (prompt.id, prompt_timeout)),
+        )
+        .filter(|(id, _)| state.rearmed.contains(id));
+    for (id, timeout) in ids {
+        let state = Arc::clone(state);
+        tokio::spawn(async move {
+            tokio::time::sleep(timeout).await;
+            expire(&state, &id).await;
+        });
+    }
+}
+
+/// Expire re-armed request `id` unless its agent collected it or the
+/// operator decided it.
+async fn expire(state: &AppState, id: &str) {
+    let Some((session_id, rearmed)) = state.rearmed.take(id) else {
+        return;
+    };
+    let expired = match rearmed {
+        Rearmed::Approval(_) => {
+            ApprovalRepo::new(Arc::clone(&state.db))
+                .decide(id, ApprovalStatus::Expired)
+                .await
+        }
+        Rearmed::Prompt(_) => {
+            PromptRepo::new(Arc::clone(&state.db))
+                .decide(id, PromptDecision::Continue, None)
+                .await
+        }
+    };
+    match expired {
+        Ok(true) => {
+            state.pending_approvals.lock().await.remove(id);
+            state.pending_prompts.lock().await.remove(id);
+            info!(request_id = id, "re-armed request timed out");
+        }
+        Ok(false) => state.rearmed.insert(id, &session_id, rearmed),
+        Err(err) => warn!(%err, request_id = id, "failed to expire re-armed request"),
+    }
+}
+
+/// The session's own channel, unless it is the global channel.
+async fn session_channel(state: &AppState, session_id: &str) -> Option<SlackChannelId> {
+    let session = SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(session_id)
+        .await
+        .ok()
+        .flatten()?;
+    let channel = session.channel_id.filter(|ch| !ch.is_empty())?;
+    (channel != state.config.slack.channel_id).then_some(SlackChannelId(channel))
+}
+
+async fn repost(state: &AppState, message: SlackMessage) -> Option<Anchor> {
+    let slack = state.slack.as_ref()?;
+    Anchor::post(slack, message).await
+}
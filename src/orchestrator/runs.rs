@@ -0,0 +1,187 @@
+//! Named runs grouping related sessions (`run-start`, `run-end`,
+//! `run-summary`).
+//!
+//! An operator starts a run before a multi-agent, multi-step effort. Until
+//! they end it, every session they start joins the run, and subtasks and
+//! restarts of those sessions inherit it, so the whole effort can be
+//! summarised together: sessions, approval outcomes, files changed and
+//! stalls across all of them.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use tracing::info;
+
+use crate::models::approval::ApprovalStatus;
+use crate::models::run::Run;
+use crate::models::session::{Session, SessionStatus};
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::db::Database;
+use crate::persistence::run_repo::RunRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::persistence::stall_repo::StallAlertRepo;
+use crate::{AppError, Result};
+
+/// Longest run name accepted, in characters.
+pub const MAX_RUN_NAME_CHARS: usize = 80;
+
+/// Start a run named `name` for `user_id`.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for an empty or overlong name, or when the
+/// operator already has an active run, and `AppError::Db` if the run
+/// cannot be stored.
+pub async fn start_run(
+    db: &Arc<Database>,
+    name: &str,
+    user_id: &str,
+    channel_id: Option<&str>,
+) -> Result<Run> {
+    let name = name
+        .trim()
+        .trim_matches(|c| matches!(c, '"' | '\u{201c}' | '\u{201d}'));
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Config("usage: run-start <name>".into()));
+    }
+    if name.chars().count() > MAX_RUN_NAME_CHARS {
+        return Err(AppError::Config(format!(
+            "run names are at most {MAX_RUN_NAME_CHARS} characters"
+        )));
+    }
+    let repo = RunRepo::new(Arc::clone(db));
+    if let Some(active) = repo.active_for(user_id).await? {
+        return Err(AppError::Config(format!(
+            "run \"{}\" is still active; end it with `run-end` first",
+            active.name
+        )));
+    }
+    let run = Run::new(
+        name.to_owned(),
+        user_id.to_owned(),
+        channel_id.map(str::to_owned),
+    );
+    repo.create(&run).await?;
+    info!(run_id = %run.id, name = %run.name, user_id, "run started");
+    Ok(run)
+}
+
+/// End the active run of `user_id`.
+///
+/// # Errors
+///
+/// Returns `AppError::NotFound` when the operator has no active run, or
+/// `AppError::Db` if the update fails.
+pub async fn end_run(db: &Arc<Database>, user_id: &str) -> Result<Run> {
+    let repo = RunRepo::new(Arc::clone(db));
+    let active = repo
+        .active_for(user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("you have no active run".into()))?;
+    let ended = repo
+        .end(&active.id, Utc::now())
+        .await?
+        .ok_or_else(|| AppError::NotFound("you have no active run".into()))?;
+    info!(run_id = %ended.id, name = %ended.name, user_id, "run ended");
+    Ok(ended)
+}
+
+/// ID of the run a session started by `owner_user_id` joins, if any.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the lookup fails.
+pub async fn active_run_id(db: &Arc<Database>, owner_user_id: &str) -> Result<Option<String>> {
+    Ok(RunRepo::new(Arc::clone(db))
+        .active_for(owner_user_id)
+        .await?
+        .map(|run| run.id))
+}
+
+/// A run with totals across its sessions.
+#[derive(Debug, Clone)]
+pub struct RunSummary {
+    /// The summarised run.
+    pub run: Run,
+    /// Sessions in the run, oldest first.
+    pub sessions: Vec<Session>,
+    /// Approval requests approved (including those since applied).
+    pub approved: usize,
+    /// Approval requests rejected.
+    pub rejected: usize,
+    /// Approval requests still awaiting a decision.
+    pub pending: usize,
+    /// Distinct files with accepted changes.
+    pub files_changed: usize,
+    /// Stall alerts raised.
+    pub stalls: usize,
+}
+
+impl RunSummary {
+    /// Load the sessions of `run` and total their approvals and stalls.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if a query fails.
+    pub async fn load(db: &Arc<Database>, run: Run) -> Result<Self> {
+        let sessions = SessionRepo::new(Arc::clone(db))
+            .list_by_run(&run.id)
+            .await?;
+        let approval_repo = ApprovalRepo::new(Arc::clone(db));
+        let stall_repo = StallAlertRepo::new(Arc::clone(db));
+        let mut summary = Self {
+            run,
+            sessions: Vec::new(),
+            approved: 0,
+            rejected: 0,
+            pending: 0,
+            files_changed: 0,
+            stalls: 0,
+        };
+        let mut files: Vec<String> = Vec::new();
+        for session in &sessions {
+            for approval in approval_repo.list_for_session(&session.id).await? {
+                match approval.status {
+                    ApprovalStatus::Approved | ApprovalStatus::Consumed => {
+                        summary.approved += 1;
+                        if !files.contains(&approval.file_path) {
+                            files.push(approval.file_path);
+                        }
+                    }
+                    ApprovalStatus::Rejected => summary.rejected += 1,
+                    ApprovalStatus::Pending => summary.pending += 1,
+                    ApprovalStatus::Expired | ApprovalStatus::Interrupted => {}
+                }
+            }
+            summary.stalls += stall_repo.list_for_session(&session.id).await?.len();
+        }
+        summary.files_changed = files.len();
+        summary.sessions = sessions;
+        Ok(summary)
+    }
+
+    /// Sessions still running (created, active or paused).
+    #[must_use]
+    pub fn running(&self) -> usize {
+        self.sessions
+            .iter()
+            .filter(|s| {
+                matches!(
+                    s.status,
+                    SessionStatus::Created | SessionStatus::Active | SessionStatus::Paused
+                )
+            })
+            .count()
+    }
+
+    /// Time from the start of the run to its end, or to `now` while active.
+    #[must_use]
+    pub fn elapsed_seconds(&self, now: DateTime<Utc>) -> i64 {
+        self.run
+            .ended_at
+            .unwrap_or(now)
+            .signed_duration_since(self.run.started_at)
+            .num_seconds()
+    }
+}
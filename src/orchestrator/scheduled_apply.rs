@@ -0,0 +1,463 @@
+//! Approved diffs the server applies at a time the operator chose.
+//!
+//! `/intercom apply-at <request> <HH:MM>` approves a pending request and
+//! stores when it is due in `approval_request.apply_after`, so the schedule
+//! survives a restart. The waiting agent is told the change is approved but
+//! scheduled, and `check_diff` refuses it in the meantime. A sweep on a
+//! fixed interval then applies each due request, runs the
+//! `[scheduled_apply] verify_commands` in the workspace root, and reports
+//! the outcome in the session thread and to the agent as a steering
+//! message.
+//!
+//! A scheduled apply that cannot go ahead (the file changed, the session is
+//! gone) drops the schedule and leaves the request approved, so the agent
+//! can still apply it with `check_diff`.
+
+use std::fmt::Write as _;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Local, NaiveTime, Utc};
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::config::ScheduledApplyConfig;
+use crate::diff::ownership;
+use crate::diff::patcher::apply_patch;
+use crate::diff::writer::write_full_file;
+use crate::ipc::server::resolve_clearance;
+use crate::mcp::tools::util::compute_file_hash;
+use crate::models::approval::{ApprovalRequest, ApprovalStatus};
+use crate::models::session::Session;
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::client::SlackMessage;
+use crate::slack::handlers::{check_approval_authority, steer};
+use crate::slack::{approval_fanout, blocks};
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// How often scheduled applies are checked.
+pub const SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Longest excerpt of a failed verification command's output reported.
+const DETAIL_LIMIT: usize = 200;
+
+/// Result of one `[scheduled_apply] verify_commands` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyOutcome {
+    /// The shell command that ran.
+    pub command: String,
+    /// Whether it exited successfully within the timeout.
+    pub passed: bool,
+    /// Exit status, and the last line of output when it failed.
+    pub detail: String,
+}
+
+/// What happened when a scheduled apply fell due.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduledOutcome {
+    /// The diff was written and verification ran.
+    Applied {
+        /// Bytes written to the file.
+        bytes: usize,
+        /// Verification results, in the order the commands ran.
+        checks: Vec<VerifyOutcome>,
+    },
+    /// The diff was not written; the request is still approved.
+    NotApplied {
+        /// Why it could not be applied.
+        reason: String,
+    },
+}
+
+/// The first moment after `now` when server-local time reads `at`: later
+/// today, or tomorrow when that time has passed.
+#[must_use]
+pub fn next_occurrence(now: DateTime<Utc>, at: NaiveTime) -> DateTime<Utc> {
+    now.with_timezone(&Local)
+        .date_naive()
+        .iter_days()
+        .take(3)
+        .filter_map(|day| day.and_time(at).and_local_timezone(Local).earliest())
+        .map(|candidate| candidate.with_timezone(&Utc))
+        .find(|candidate| *candidate > now)
+        .unwrap_or(now + chrono::Duration::days(1))
+}
+
+/// Approve the pending request `request` on behalf of `user_id` and
+/// schedule the server to apply it at the next `at`, server-local time.
+///
+/// The agent waiting on the request is told it is approved and when it
+/// will be applied; the request's Slack buttons are replaced.
+///
+/// # Errors
+///
+/// Returns `AppError::NotFound` for an unknown request,
+/// `AppError::Unauthorized` when `user_id` may not decide it, and
+/// `AppError::Config` when it is no longer pending or its file is a
+/// `[protected_paths]` match, which needs a confirmation when it is
+/// applied.
+pub async fn schedule(
+    state: &Arc<AppState>,
+    request: &str,
+    at: NaiveTime,
+    user_id: &str,
+) -> Result<ApprovalRequest> {
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let record = repo
+        .resolve(request)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("approval request '{request}' not found")))?;
+    if record.status != ApprovalStatus::Pending {
+        return Err(AppError::Config(format!(
+            "approval request `{}` is already decided",
+            record.short_id
+        )));
+    }
+    if state.config.protected_paths.protects(&record.file_path) {
+        return Err(AppError::Config(format!(
+            "`{}` is a protected path and needs a confirmation when it is applied; \
+             approve it from its card instead",
+            record.file_path
+        )));
+    }
+    let session = SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(&record.session_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("session {} not found", record.session_id)))?;
+    let required = ownership::required_approvers(
+        &state.config.codeowners,
+        Path::new(&session.workspace_root),
+        &record.file_path,
+    );
+    check_approval_authority(&session, &required, user_id)?;
+
+    let apply_after = next_occurrence(Utc::now(), at);
+    if !repo.schedule_apply(&record.id, apply_after).await? {
+        return Err(AppError::Config(format!(
+            "approval request `{}` is already decided",
+            record.short_id
+        )));
+    }
+    info!(request_id = %record.id, user_id, %apply_after, "apply scheduled");
+
+    let reason = format!(
+        "apply scheduled for {}; the server applies it and reports back, do not call check_diff",
+        apply_after.to_rfc3339()
+    );
+    if let Some(ref logger) = state.audit_logger {
+        let entry = AuditEntry::new(AuditEventType::Approval)
+            .with_session(record.session_id.clone())
+            .with_request_id(record.id.clone())
+            .with_operator(user_id.to_owned())
+            .with_reason(reason.clone());
+        if let Err(err) = logger.log_entry(entry) {
+            warn!(%err, "audit log write failed (scheduled apply)");
+        }
+    }
+
+    let status_line = format!(
+        "\u{2705} *Approved* by <@{user_id}> \u{2014} {} is applied at {}",
+        record.title,
+        blocks::slack_time(apply_after)
+    );
+    approval_fanout::resolve_copies(state, &record.id, &status_line).await;
+    replace_buttons(state, &session, &record, &status_line).await;
+    resolve_clearance(state, &repo, &record.id, true, Some(reason)).await;
+
+    Ok(ApprovalRequest {
+        status: ApprovalStatus::Approved,
+        apply_after: Some(apply_after),
+        ..record
+    })
+}
+
+/// Replace the buttons on the request's own card with `status_line`.
+async fn replace_buttons(
+    state: &AppState,
+    session: &Session,
+    record: &ApprovalRequest,
+    status_line: &str,
+) {
+    let (Some(slack), Some(channel), Some(ts)) = (
+        state.slack.as_ref(),
+        session.channel_id.as_ref(),
+        record.slack_ts.as_ref(),
+    ) else {
+        return;
+    };
+    if let Err(err) = slack
+        .update_message(
+            SlackChannelId(channel.clone()),
+            SlackTs(ts.clone()),
+            vec![blocks::text_section(status_line)],
+        )
+        .await
+    {
+        warn!(%err, request_id = %record.id, "failed to replace buttons of scheduled approval");
+    }
+}
+
+/// Spawn the periodic scheduled-apply sweep.
+#[must_use]
+pub fn spawn_scheduled_apply_task(
+    state: Arc<AppState>,
+    cancel: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => {
+                    info!("scheduled apply task shutting down");
+                    break;
+                }
+                _ = interval.tick() => {
+                    if let Err(err) = apply_due(&state, Utc::now()).await {
+                        warn!(%err, "scheduled apply sweep failed");
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Apply every scheduled request that is due as of `now`.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the scheduled requests cannot be listed or
+/// updated. Verification, delivery and Slack failures are reported, not
+/// returned.
+pub async fn apply_due(state: &AppState, now: DateTime<Utc>) -> Result<()> {
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let sessions = SessionRepo::new(Arc::clone(&state.db));
+    for approval in repo.list_scheduled().await? {
+        if approval.apply_after.is_none_or(|due| due > now) {
+            continue;
+        }
+        let Some(session) = sessions.get_by_id(&approval.session_id).await? else {
+            warn!(request_id = %approval.id, "scheduled apply dropped: session not found");
+            repo.clear_apply_after(&approval.id).await?;
+            continue;
+        };
+
+        let outcome = match write(&session, &approval).await {
+            Ok(bytes) => {
+                repo.mark_consumed(&approval.id).await?;
+                let checks = run_verification(
+                    &state.config.scheduled_apply,
+                    Path::new(&session.workspace_root),
+                )
+                .await;
+                ScheduledOutcome::Applied { bytes, checks }
+            }
+            Err(reason) => {
+                repo.clear_apply_after(&approval.id).await?;
+                ScheduledOutcome::NotApplied { reason }
+            }
+        };
+        info!(request_id = %approval.id, ?outcome, "scheduled apply ran");
+        report(state, &session, &approval, &outcome).await;
+    }
+    Ok(())
+}
+
+/// Write `approval`'s diff into the session's workspace, with the same
+/// checks as `check_diff`. Returns the bytes written, or why it could not
+/// be applied.
+async fn write(
+    session: &Session,
+    approval: &ApprovalRequest,
+) -> std::result::Result<usize, String> {
+    let workspace_root = Path::new(&session.workspace_root);
+    let path = crate::diff::validate_workspace_path(workspace_root, &approval.file_path)
+        .map_err(|_| "the file path escapes the workspace root".to_owned())?;
+    let current_hash = compute_file_hash(&path)
+        .await
+        .map_err(|err| format!("the file could not be read: {err}"))?;
+    if current_hash != approval.original_hash {
+        return Err("the file has changed since the diff was proposed".into());
+    }
+
+    let is_unified_diff =
+        approval.diff_content.starts_with("--- ") || approval.diff_content.starts_with("diff ");
+    let written = if is_unified_diff {
+        apply_patch(&path, &approval.diff_content, workspace_root)
+    } else if path.metadata().map_or(0, |m| m.len()) > 0 {
+        return Err("the diff is not a unified diff but the file already exists".into());
+    } else {
+        write_full_file(&path, &approval.diff_content, workspace_root)
+    };
+    written
+        .map(|summary| summary.bytes_written)
+        .map_err(|err| format!("the diff could not be applied: {err}"))
+}
+
+/// Run each verification command in `workspace_root`, in order, stopping
+/// at the first that fails.
+pub async fn run_verification(
+    config: &ScheduledApplyConfig,
+    workspace_root: &Path,
+) -> Vec<VerifyOutcome> {
+    let timeout = Duration::from_secs(config.verify_timeout_seconds);
+    let mut outcomes = Vec::new();
+    for command in &config.verify_commands {
+        let outcome = verify(command, workspace_root, timeout).await;
+        let passed = outcome.passed;
+        outcomes.push(outcome);
+        if !passed {
+            break;
+        }
+    }
+    outcomes
+}
+
+async fn verify(command: &str, workspace_root: &Path, timeout: Duration) -> VerifyOutcome {
+    let mut cmd = shell(command);
+    cmd.current_dir(workspace_root)
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+    let (passed, detail) = match tokio::time::timeout(timeout, cmd.output()).await {
+        Ok(Ok(output)) if output.status.success() => (true, "passed".to_owned()),
+        Ok(Ok(output)) => {
+            let code = output
+                .status
+                .code()
+                .map_or_else(|| "killed".to_owned(), |code| format!("exit {code}"));
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            match last_line(&stderr).or_else(|| last_line(&stdout)) {
+                Some(line) => (false, format!("{code}: {line}")),
+                None => (false, code),
+            }
+        }
+        Ok(Err(err)) => (false, format!("could not start: {err}")),
+        Err(_) => (
+            false,
+            format!("timed out after {} seconds", timeout.as_secs()),
+        ),
+    };
+    VerifyOutcome {
+        command: command.to_owned(),
+        passed,
+        detail,
+    }
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> tokio::process::Command {
+    let mut cmd = tokio::process::Command::new("cmd");
+    cmd.args(["/C", command]);
+    cmd
+}
+
+#[cfg(not(windows))]
+fn shell(command: &str) -> tokio::process::Command {
+    let mut cmd = tokio::process::Command::new("sh");
+    cmd.args(["-c", command]);
+    cmd
+}
+
+/// Last non-blank line of `output`, shortened to [`DETAIL_LIMIT`] chars.
+fn last_line(output: &str) -> Option<String> {
+    output
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(|line| line.chars().take(DETAIL_LIMIT).collect())
+}
+
+/// Slack notice of a scheduled apply for the session thread.
+#[must_use]
+pub fn operator_notice(approval: &ApprovalRequest, outcome: &ScheduledOutcome) -> String {
+    let mut text = format!(
+        "\u{1f552} *Scheduled apply* \u{2014} `{}` (`{}`)",
+        approval.file_path, approval.short_id
+    );
+    match outcome {
+        ScheduledOutcome::Applied { bytes, checks } => {
+            let _ = write!(text, "\n\u{2705} Applied, {bytes} bytes written.");
+            if !checks.is_empty() {
+                text.push_str("\n*Verification:*");
+            }
+            for check in checks {
+                let mark = if check.passed { "\u{2705}" } else { "\u{274c}" };
+                let _ = write!(
+                    text,
+                    "\n{mark} `{}` \u{2014} {}",
+                    check.command, check.detail
+                );
+            }
+        }
+        ScheduledOutcome::NotApplied { reason } => {
+            let _ = write!(
+                text,
+                "\n\u{274c} Not applied: {reason}. The request stays approved; the agent can \
+                 apply it with `check_diff`."
+            );
+        }
+    }
+    text
+}
+
+/// Steering text telling the agent how its scheduled apply went.
+#[must_use]
+pub fn agent_notice(approval: &ApprovalRequest, outcome: &ScheduledOutcome) -> String {
+    let subject = format!(
+        "Approval request `{}` ({}) was scheduled by the operator",
+        approval.short_id, approval.title
+    );
+    match outcome {
+        ScheduledOutcome::Applied { bytes, checks } => {
+            let mut text = format!(
+                "{subject} and has now been applied: {bytes} bytes written to {}.",
+                approval.file_path
+            );
+            if !checks.is_empty() {
+                let results: Vec<String> = checks
+                    .iter()
+                    .map(|check| format!("`{}` {}", check.command, check.detail))
+                    .collect();
+                let _ = write!(text, " Verification: {}.", results.join("; "));
+            }
+            text
+        }
+        ScheduledOutcome::NotApplied { reason } => format!(
+            "{subject} but could not be applied: {reason}. It is still approved; apply it with \
+             check_diff using request_id `{}` once that is resolved.",
+            approval.id
+        ),
+    }
+}
+
+/// Post the outcome to the session thread and steer it to the agent.
+async fn report(
+    state: &AppState,
+    session: &Session,
+    approval: &ApprovalRequest,
+    outcome: &ScheduledOutcome,
+) {
+    if let (Some(slack), Some(channel)) = (state.slack.as_ref(), session.channel_id.as_ref()) {
+        let text = operator_notice(approval, outcome);
+        let msg = SlackMessage {
+            channel: SlackChannelId(channel.clone()),
+            text: Some(format!("Scheduled apply of {}", approval.file_path)),
+            blocks: Some(vec![blocks::text_section(&text)]),
+            thread_ts: session.thread_ts.clone().map(SlackTs),
+        };
+        if let Err(err) = slack.enqueue(msg).await {
+            warn!(%err, request_id = %approval.id, "failed to post scheduled apply notice");
+        }
+    }
+    if let Err(err) = steer::steer_session(state, session, &agent_notice(approval, outcome)).await {
+        warn!(%err, request_id = %approval.id, "failed to notify agent of scheduled apply");
+    }
+}
@@ -0,0 +1,234 @@
+//! Bulk session operations (`agent-intercom-ctl sessions …`).
+//!
+//! One request selects sessions by status, owner and age, applies the same
+//! action to each, and reports which sessions it succeeded for and why the
+//! others failed. A failure on one session never stops the rest.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::audit::{self, AuditEntry, AuditEventType};
+use crate::integrations::{email, issues};
+use crate::models::session::{Session, SessionStatus};
+use crate::orchestrator::{session_manager, subtask};
+use crate::persistence::session_repo::SessionRepo;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Action applied to every selected session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkAction {
+    /// Terminate, as `/intercom session-clear` does.
+    Clear,
+    /// Ask the agent to pause after its current step.
+    Pause,
+    /// Soft-delete ended sessions.
+    Delete,
+}
+
+impl BulkAction {
+    /// Statuses selected when the request names none.
+    #[must_use]
+    pub fn default_statuses(self) -> &'static [SessionStatus] {
+        match self {
+            Self::Clear => &[
+                SessionStatus::Created,
+                SessionStatus::Active,
+                SessionStatus::Paused,
+                SessionStatus::Interrupted,
+            ],
+            Self::Pause => &[SessionStatus::Active],
+            Self::Delete => &[SessionStatus::Terminated, SessionStatus::Interrupted],
+        }
+    }
+}
+
+/// Which sessions a bulk action applies to. Soft-deleted sessions are never
+/// selected.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionSelector {
+    /// Statuses to match; the action's defaults when empty.
+    pub statuses: Vec<SessionStatus>,
+    /// Only sessions owned by this user.
+    pub owner: Option<String>,
+    /// Only sessions not updated for at least this long.
+    pub older_than: Option<chrono::Duration>,
+}
+
+impl SessionSelector {
+    /// Whether any filter beyond the action's default statuses is set.
+    #[must_use]
+    pub fn is_narrowed(&self) -> bool {
+        !self.statuses.is_empty() || self.owner.is_some() || self.older_than.is_some()
+    }
+}
+
+/// A session the action could not be applied to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkFailure {
+    /// Session ID.
+    pub session_id: String,
+    /// Why the action failed.
+    pub error: String,
+}
+
+/// Summary of a bulk action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkOutcome {
+    /// Action that was applied.
+    pub action: BulkAction,
+    /// Sessions the selector matched.
+    pub matched: usize,
+    /// Sessions the action succeeded for.
+    pub succeeded: Vec<String>,
+    /// Sessions it failed for.
+    pub failed: Vec<BulkFailure>,
+}
+
+/// Apply `action` to every session `selector` matches.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the sessions cannot be listed. Failures on
+/// individual sessions are reported in [`BulkOutcome::failed`].
+pub async fn apply(
+    state: &AppState,
+    action: BulkAction,
+    selector: &SessionSelector,
+) -> Result<BulkOutcome> {
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let statuses = if selector.statuses.is_empty() {
+        action.default_statuses()
+    } else {
+        selector.statuses.as_slice()
+    };
+    let updated_before = selector.older_than.map(|age| Utc::now() - age);
+    let sessions = repo
+        .list_matching(statuses, selector.owner.as_deref(), updated_before)
+        .await?;
+
+    let mut outcome = BulkOutcome {
+        action,
+        matched: sessions.len(),
+        succeeded: Vec::new(),
+        failed: Vec::new(),
+    };
+    for session in sessions {
+        match apply_one(state, &repo, action, &session).await {
+            Ok(()) => outcome.succeeded.push(session.id),
+            Err(err) => outcome.failed.push(BulkFailure {
+                session_id: session.id,
+                error: err.to_string(),
+            }),
+        }
+    }
+    info!(
+        ?action,
+        matched = outcome.matched,
+        succeeded = outcome.succeeded.len(),
+        failed = outcome.failed.len(),
+        "bulk session action applied"
+    );
+    Ok(outcome)
+}
+
+/// Apply `action` to one session.
+async fn apply_one(
+    state: &AppState,
+    repo: &SessionRepo,
+    action: BulkAction,
+    session: &Session,
+) -> Result<()> {
+    match action {
+        BulkAction::Clear => {
+            if session.status == SessionStatus::Terminated {
+                return Err(AppError::Policy(format!(
+                    "session {} is already terminated",
+                    session.id
+                )));
+            }
+            clear_session(state, session, "terminated by operator (bulk clear)").await?;
+            audit(state, AuditEventType::AcpSessionStop, &session.id);
+        }
+        BulkAction::Pause => {
+            session_manager::request_pause(&session.id, repo, &state.pause_requests).await?;
+            audit(state, AuditEventType::AcpSessionPause, &session.id);
+        }
+        BulkAction::Delete => {
+            if !matches!(
+                session.status,
+                SessionStatus::Terminated | SessionStatus::Interrupted
+            ) {
+                return Err(AppError::Policy(format!(
+                    "session {} is {}; only ended sessions can be deleted",
+                    session.id,
+                    session.status.as_str()
+                )));
+            }
+            repo.set_deleted(&session.id, true).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Terminate `session` and run its end-of-session follow-ups: stop the
+/// child process, drop its drivers, post the session-ended summary, and
+/// report to the parent session, linked issue and summary email with
+/// `reason`.
+///
+/// # Errors
+///
+/// Propagates the errors of [`session_manager::terminate_session`].
+pub async fn clear_session(state: &AppState, session: &Session, reason: &str) -> Result<Session> {
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+
+    // Remove the child from the registry before awaiting terminate_session
+    // to avoid holding the lock guard across an await point.
+    let mut child = state.active_children.lock().await.remove(&session.id);
+    let terminated = session_manager::terminate_session(&session.id, &repo, child.as_mut()).await?;
+
+    // Deregister the ACP driver's in-memory state for this session.
+    if let Some(ref acp_driver) = state.acp_driver {
+        acp_driver.deregister_session(&session.id).await;
+    }
+    state.driver_registry.deregister(&session.id).await;
+
+    // T060 / S094: Post session-ended summary as a threaded reply.
+    if let Some(ref slack) = state.slack {
+        let log_dir = audit::log_dir(state.config.default_workspace_root());
+        session_manager::notify_session_ended(&terminated, reason, slack, &log_dir).await;
+    }
+    if let Err(err) = subtask::report_to_parent(&state.db, &terminated, reason).await {
+        warn!(%err, session_id = %terminated.id, "failed to report subtask to parent");
+    }
+    issues::spawn_completion_report(
+        Arc::clone(&state.config),
+        terminated.clone(),
+        reason.to_owned(),
+    );
+    email::spawn_summary_email(
+        Arc::clone(&state.config),
+        Arc::clone(&state.db),
+        terminated.clone(),
+        reason.to_owned(),
+    );
+
+    Ok(terminated)
+}
+
+/// Record a bulk lifecycle change in the audit log, if one is configured.
+fn audit(state: &AppState, event_type: AuditEventType, session_id: &str) {
+    if let Some(ref logger) = state.audit_logger {
+        let entry = AuditEntry::new(event_type)
+            .with_session(session_id.to_owned())
+            .with_result("bulk action via agent-intercom-ctl".to_owned());
+        if let Err(err) = logger.log_entry(entry) {
+            warn!(%err, "audit log write failed (bulk session action)");
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! Per-session ring buffers for structured agent logs (`stream_log`).
+//!
+//! Agents push verbose log lines with `stream_log` instead of `broadcast`,
+//! so they never reach Slack on their own. Each session keeps its newest
+//! [`LOG_BUFFER_CAPACITY`] lines; operators read the tail with
+//! `/intercom logs`, `agent-intercom-ctl logs`, or the
+//! `intercom://session/{id}/logs` resource.
+//!
+//! Buffers live in memory: logs do not survive a restart.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write as _;
+use std::sync::{Mutex, PoisonError};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Lines kept per session; older lines are dropped first.
+pub const LOG_BUFFER_CAPACITY: usize = 1000;
+
+/// Lines returned when a reader does not ask for a count.
+pub const DEFAULT_LOG_TAIL: usize = 200;
+
+/// Severity of a streamed log line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    /// Fine-grained diagnostics.
+    Debug,
+    /// Routine progress.
+    Info,
+    /// Something unexpected the agent worked around.
+    Warn,
+    /// A failure.
+    Error,
+}
+
+impl LogLevel {
+    /// Upper-case label used in rendered lines.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Debug => "DEBUG",
+            Self::Info => "INFO",
+            Self::Warn => "WARN",
+            Self::Error => "ERROR",
+        }
+    }
+}
+
+/// One buffered log line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogLine {
+    /// When the server received the line.
+    pub at: DateTime<Utc>,
+    /// Severity.
+    pub level: LogLevel,
+    /// Log message.
+    pub message: String,
+    /// Structured key/value context supplied by the agent.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl LogLine {
+    /// Render as a single text line, e.g.
+    /// `12:04:31 WARN  retrying fetch attempt=2`.
+    #[must_use]
+    pub fn render(&self) -> String {
+        format!(
+            "{} {:<5} {}",
+            self.at.format("%H:%M:%S"),
+            self.level.label(),
+            self.detail()
+        )
+    }
+
+    /// The message followed by its fields as `key=value` pairs.
+    #[must_use]
+    pub fn detail(&self) -> String {
+        let mut text = self.message.clone();
+        for (key, value) in &self.fields {
+            match value {
+                serde_json::Value::String(s) => {
+                    let _ = write!(text, " {key}={s}");
+                }
+                other => {
+                    let _ = write!(text, " {key}={other}");
+                }
+            }
+        }
+        text
+    }
+}
+
+/// Log ring buffers, keyed by session ID.
+#[derive(Debug)]
+pub struct SessionLogs {
+    capacity: usize,
+    buffers: Mutex<HashMap<String, VecDeque<LogLine>>>,
+}
+
+impl Default for SessionLogs {
+    fn default() -> Self {
+        Self::new(LOG_BUFFER_CAPACITY)
+    }
+}
+
+impl SessionLogs {
+    /// Create empty buffers holding at most `capacity` lines per session.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            buffers: Mutex::default(),
+        }
+    }
+
+    /// Append `line` to the buffer of `session_id`, dropping its oldest line
+    /// when full. Returns the number of lines now buffered.
+    pub fn push(&self, session_id: &str, line: LogLine) -> usize {
+        let mut buffers = self.lock();
+        let buffer = buffers.entry(session_id.to_owned()).or_default();
+        if buffer.len() >= self.capacity {
+            buffer.pop_front();
+        }
+        buffer.push_back(line);
+        buffer.len()
+    }
+
+    /// The newest `n` lines of `session_id`, oldest first.
+    #[must_use]
+    pub fn tail(&self, session_id: &str, n: usize) -> Vec<LogLine> {
+        self.lock().get(session_id).map_or_else(Vec::new, |buffer| {
+            buffer
+                .iter()
+                .skip(buffer.len().saturating_sub(n))
+                .cloned()
+                .collect()
+        })
+    }
+
+    /// Number of lines buffered for `session_id`.
+    #[must_use]
+    pub fn buffered(&self, session_id: &str) -> usize {
+        self.lock().get(session_id).map_or(0, VecDeque::len)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, VecDeque<LogLine>>> {
+        self.buffers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -3,7 +3,19 @@
 //! Provides high-level operations for controlling session state from
 //! Slack slash commands or IPC. All operations validate session
 //! ownership before proceeding (FR-013).
+//!
+//! # Pause after current step
+//!
+//! [`pause_session`] flips the stored status immediately, which the agent
+//! never observes. [`request_pause`] instead records a [`PauseRequests`]
+//! entry; the agent's next blocking tool call consumes it, receives a
+//! [`PAUSE_REQUESTED_STATUS`] directive in place of the tool's normal
+//! result, and the session transitions to `Paused` at that point. The
+//! agent then waits in `standby` until the operator resumes the session.
 
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
 use std::time::Duration;
 
 use slack_morphism::prelude::{SlackChannelId, SlackTs};
@@ -15,6 +27,116 @@ use crate::persistence::session_repo::SessionRepo;
 use crate::slack::client::{SlackMessage, SlackService};
 use crate::{AppError, Result};
 
+/// Largest per-session log uploaded to the thread when a session ends.
+/// Longer logs are trimmed to their newest lines.
+pub const SESSION_LOG_UPLOAD_LIMIT: usize = 1024 * 1024;
+
+/// Tool result `status` returned to an agent whose pause request was taken.
+pub const PAUSE_REQUESTED_STATUS: &str = "pause_requested";
+
+/// Instruction returned alongside [`PAUSE_REQUESTED_STATUS`].
+pub const PAUSE_DIRECTIVE: &str = "The operator paused this session. Stop here: make no further \
+     changes, call `standby`, and wait until the session is resumed. Repeat the interrupted \
+     tool call afterwards if it is still needed.";
+
+/// Outstanding "pause after current step" requests, keyed by session ID.
+///
+/// Held in memory only: sessions that are still active at shutdown are
+/// marked interrupted on restart, so a pending pause has nothing to apply to.
+#[derive(Debug, Default)]
+pub struct PauseRequests {
+    pending: Mutex<HashSet<String>>,
+}
+
+impl PauseRequests {
+    /// Create an empty request table.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a pause request for `session_id`.
+    ///
+    /// Returns `false` if one was already pending.
+    pub fn request(&self, session_id: &str) -> bool {
+        self.lock().insert(session_id.to_owned())
+    }
+
+    /// Withdraw the pending request for `session_id`, if any.
+    ///
+    /// Returns `true` if a request was withdrawn.
+    pub fn cancel(&self, session_id: &str) -> bool {
+        self.lock().remove(session_id)
+    }
+
+    /// Consume the pending request for `session_id`.
+    ///
+    /// Returns `true` exactly once per request.
+    pub fn take(&self, session_id: &str) -> bool {
+        self.cancel(session_id)
+    }
+
+    /// Whether a pause is pending for `session_id`.
+    #[must_use]
+    pub fn is_requested(&self, session_id: &str) -> bool {
+        self.lock().contains(session_id)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashSet<String>> {
+        self.pending.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Ask the agent of an active session to pause after its current step.
+///
+/// The session stays `Active` until the agent's next blocking tool call
+/// consumes the request (see the module docs).
+///
+/// # Errors
+///
+/// Returns `AppError::NotFound` if the session does not exist, or
+/// `AppError::Policy` if it is not active.
+pub async fn request_pause(
+    session_id: &str,
+    session_repo: &SessionRepo,
+    requests: &PauseRequests,
+) -> Result<Session> {
+    let session = session_repo
+        .get_by_id(session_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("session {session_id} not found")))?;
+    if session.status != SessionStatus::Active {
+        return Err(AppError::Policy(format!(
+            "session {session_id} is {} and cannot be paused",
+            session.status.as_str()
+        )));
+    }
+    if requests.request(session_id) {
+        info!(session_id, "pause requested after current step");
+    }
+    Ok(session)
+}
+
+/// Apply a pending pause request at one of the agent's checkpoints.
+///
+/// Consumes the request for `session_id` and transitions the session to
+/// `Paused`. Returns `None` when no request was pending.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the status transition fails; the request is
+/// consumed either way.
+pub async fn take_pending_pause(
+    session_id: &str,
+    session_repo: &SessionRepo,
+    requests: &PauseRequests,
+) -> Result<Option<Session>> {
+    if !requests.take(session_id) {
+        return Ok(None);
+    }
+    pause_session(session_id, session_repo).await.map(Some)
+}
+
 /// Pause a running session.
 ///
 /// Sets the session status to `Paused` so that no further tool calls are
@@ -167,6 +289,30 @@ pub async fn resolve_session(
     Ok(session)
 }
 
+/// Tell the session's Slack thread that the agent reached its checkpoint
+/// and the session is now paused.
+///
+/// A silent no-op when the session has no channel.
+pub async fn notify_session_paused(session: &Session, slack: &SlackService) {
+    let Some(ref channel_id) = session.channel_id else {
+        return;
+    };
+    let text = format!(
+        "\u{23f8}\u{fe0f} Session `{}` paused after the agent's current step. \
+         Use `session-resume` to continue.",
+        session.id
+    );
+    let msg = SlackMessage {
+        channel: SlackChannelId(channel_id.clone()),
+        text: Some(text),
+        blocks: None,
+        thread_ts: session.thread_ts.clone().map(SlackTs),
+    };
+    if let Err(err) = slack.enqueue(msg).await {
+        warn!(%err, session_id = %session.id, "failed to post session-paused notification");
+    }
+}
+
 /// Post a "Session ended" summary as a threaded Slack reply (T060 / S094 / S095).
 ///
 /// If the session has both a `channel_id` and a `thread_ts`, the summary is
@@ -174,12 +320,22 @@ pub async fn resolve_session(
 /// can see the final status in context.  When either field is absent the call
 /// is a silent no-op.
 ///
+/// When the session has a structured log file under `log_dir`
+/// (`sessions/<id>.jsonl`), it is uploaded to the same thread so the
+/// operator can debug this session without filtering the server-wide log.
+///
 /// # Arguments
 ///
 /// * `session` — The terminated or interrupted session.
 /// * `reason`  — Human-readable reason for the session ending.
 /// * `slack`   — Slack service used to enqueue the message.
-pub async fn notify_session_ended(session: &Session, reason: &str, slack: &SlackService) {
+/// * `log_dir` — Audit log directory holding the per-session log files.
+pub async fn notify_session_ended(
+    session: &Session,
+    reason: &str,
+    slack: &SlackService,
+    log_dir: &Path,
+) {
     let (Some(ref channel_id), Some(ref thread_ts)) = (&session.channel_id, &session.thread_ts)
     else {
         return;
@@ -188,10 +344,7 @@ pub async fn notify_session_ended(session: &Session, reason: &str, slack: &Slack
     let ended_blocks = crate::slack::blocks::session_ended_blocks(session, reason);
     let msg = SlackMessage {
         channel: SlackChannelId(channel_id.clone()),
-        text: Some(format!(
-            "\u{1f3c1} Session `{}` ended",
-            session.id.chars().take(8).collect::<String>()
-        )),
+        text: Some(format!("\u{1f3c1} Session `{}` ended", session.short_id)),
         blocks: Some(ended_blocks),
         thread_ts: Some(SlackTs(thread_ts.clone())),
     };
@@ -201,4 +354,41 @@ pub async fn notify_session_ended(session: &Session, reason: &str, slack: &Slack
     } else {
         info!(session_id = %session.id, "posted session-ended summary to thread");
     }
+
+    let Some(path) = crate::audit::session_log_path(log_dir, &session.id) else {
+        return;
+    };
+    let Ok(contents) = tokio::fs::read_to_string(&path).await else {
+        return;
+    };
+    let filename = format!("session-{}.jsonl", session.short_id);
+    if let Err(err) = slack
+        .upload_file(
+            SlackChannelId(channel_id.clone()),
+            &filename,
+            newest_lines(&contents, SESSION_LOG_UPLOAD_LIMIT),
+            Some(SlackTs(thread_ts.clone())),
+            None,
+        )
+        .await
+    {
+        warn!(%err, session_id = %session.id, "failed to upload session log");
+    }
+}
+
+/// The trailing whole lines of `contents` that fit in `limit` bytes.
+#[must_use]
+pub fn newest_lines(contents: &str, limit: usize) -> &str {
+    if contents.len() <= limit {
+        return contents;
+    }
+    let mut start = contents.len() - limit;
+    while !contents.is_char_boundary(start) {
+        start += 1;
+    }
+    let tail = &contents[start..];
+    if contents[..start].ends_with('\n') {
+        return tail;
+    }
+    tail.find('\n').map_or("", |newline| &tail[newline + 1..])
 }
@@ -0,0 +1,149 @@
+//! Re-routing a running session to another Slack channel (`session-move`).
+//!
+//! Moving a session posts an anchor message in the target channel, records
+//! the channel and the anchor's thread on the session, and leaves a pointer
+//! in the old thread. Everything posted for the session afterwards lands in
+//! the new thread, and replies there reach the agent.
+//!
+//! An MCP connection resolves its channel once, when it connects, so moved
+//! sessions are also kept in [`SessionChannels`], which the MCP handler
+//! consults before that connection channel. Spawned agents, which connect
+//! with only a `session_id`, are entered there with their recorded channel
+//! when they connect. The table lives in memory; the session record
+//! carries the move across restarts.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
+use tracing::{info, warn};
+
+use crate::models::session::{Session, SessionStatus};
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::client::SlackMessage;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Channels of moved and spawned sessions, keyed by session ID.
+#[derive(Debug, Default)]
+pub struct SessionChannels {
+    moved: Mutex<HashMap<String, String>>,
+}
+
+impl SessionChannels {
+    /// Route `session_id` to `channel_id` from now on.
+    pub fn set(&self, session_id: &str, channel_id: &str) {
+        self.lock()
+            .insert(session_id.to_owned(), channel_id.to_owned());
+    }
+
+    /// Channel `session_id` was moved to, if any.
+    #[must_use]
+    pub fn get(&self, session_id: &str) -> Option<String> {
+        self.lock().get(session_id).cloned()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, String>> {
+        self.moved.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Result of [`move_session`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveOutcome {
+    /// Channel the session posted to before the move.
+    pub from: Option<String>,
+    /// Thread started in the new channel; `None` without Slack.
+    pub thread_ts: Option<String>,
+}
+
+/// Move `session` to `channel_id` on behalf of `user_id`.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` when the session has ended or already posts to
+/// `channel_id`, `AppError::Slack` if the anchor message cannot be posted
+/// (the session is left unchanged), or `AppError::Db` if the update fails.
+pub async fn move_session(
+    state: &AppState,
+    session: &Session,
+    channel_id: &str,
+    user_id: &str,
+) -> Result<MoveOutcome> {
+    relocate(state, session, channel_id, &format!("by <@{user_id}>")).await
+}
+
+/// Move `session` to `channel_id`, saying why with `cause` ("by @user",
+/// "for after-hours routing") in both the new and the old thread.
+///
+/// # Errors
+///
+/// As [`move_session`].
+pub async fn relocate(
+    state: &AppState,
+    session: &Session,
+    channel_id: &str,
+    cause: &str,
+) -> Result<MoveOutcome> {
+    if matches!(
+        session.status,
+        SessionStatus::Terminated | SessionStatus::Interrupted
+    ) {
+        return Err(AppError::Config(format!(
+            "session {} has ended and cannot be moved",
+            session.id
+        )));
+    }
+    if session.channel_id.as_deref() == Some(channel_id) {
+        return Err(AppError::Config(format!(
+            "session {} already posts to <#{channel_id}>",
+            session.id
+        )));
+    }
+
+    let short_id = &session.short_id;
+    let from = session.channel_id.clone();
+    let origin = from
+        .as_deref()
+        .map_or_else(String::new, |channel| format!(" from <#{channel}>"));
+
+    let thread_ts = match state.slack.as_ref() {
+        Some(slack) => {
+            let anchor = SlackMessage::plain(
+                SlackChannelId(channel_id.to_owned()),
+                format!("\u{1f500} Session `{short_id}` moved here{origin} {cause}."),
+            );
+            Some(slack.post_message_direct(anchor).await?.0)
+        }
+        None => None,
+    };
+
+    SessionRepo::new(Arc::clone(&state.db))
+        .rebind_channel(&session.id, channel_id, thread_ts.as_deref())
+        .await?;
+    state.session_channels.set(&session.id, channel_id);
+    info!(
+        session_id = %session.id,
+        from = from.as_deref().unwrap_or("<none>"),
+        to = channel_id,
+        cause,
+        "session moved to another channel"
+    );
+
+    if let (Some(slack), Some(old_channel)) = (state.slack.as_ref(), from.as_deref()) {
+        let notice = SlackMessage {
+            channel: SlackChannelId(old_channel.to_owned()),
+            text: Some(format!(
+                "\u{27a1}\u{fe0f} Session `{short_id}` moved to <#{channel_id}> {cause}. \
+                 Decisions already posted here still work."
+            )),
+            blocks: None,
+            thread_ts: session.thread_ts.clone().map(SlackTs),
+        };
+        if let Err(err) = slack.enqueue(notice).await {
+            warn!(%err, session_id = %session.id, "failed to post move notice in old thread");
+        }
+    }
+
+    Ok(MoveOutcome { from, thread_ts })
+}
@@ -0,0 +1,379 @@
+//! Markdown session reports (`ctl report`, `intercom://session/{id}/report`).
+//!
+//! A report is a self-contained record of one session, suitable for
+//! attaching to a pull request or a compliance review: an overview, the
+//! chronological transcript of operator/agent interactions (approval
+//! requests, forwarded prompts, steering, relay messages, operator notes), a
+//! table of the operator's decisions, and every diff that was approved.
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::integrations::email::approval_status_label;
+use crate::models::approval::{ApprovalRequest, ApprovalStatus};
+use crate::models::progress::ProgressStatus;
+use crate::models::prompt::{ContinuationPrompt, PromptDecision};
+use crate::models::relay::RelayMessage;
+use crate::models::run::Run;
+use crate::models::session::{format_tags, Session};
+use crate::models::session_note::SessionNote;
+use crate::models::steering::{SteeringMessage, SteeringSource};
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::db::Database;
+use crate::persistence::prompt_repo::PromptRepo;
+use crate::persistence::relay_repo::RelayRepo;
+use crate::persistence::run_repo::RunRepo;
+use crate::persistence::session_note_repo::SessionNoteRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::persistence::steering_repo::SteeringRepo;
+use crate::slack::blocks::format_elapsed;
+use crate::{AppError, Result};
+
+/// Everything recorded about one session.
+#[derive(Debug, Clone)]
+pub struct SessionReport {
+    /// The reported session.
+    pub session: Session,
+    /// Approval requests raised by the session, oldest first.
+    pub approvals: Vec<ApprovalRequest>,
+    /// Prompts forwarded by the session, oldest first.
+    pub prompts: Vec<ContinuationPrompt>,
+    /// Steering messages queued for the session, oldest first.
+    pub steering: Vec<SteeringMessage>,
+    /// Relay messages the session sent or received, oldest first.
+    pub relays: Vec<RelayMessage>,
+    /// Operator notes on the session, oldest first.
+    pub notes: Vec<SessionNote>,
+    /// Run the session belongs to, if any.
+    pub run: Option<Run>,
+}
+
+/// One transcript line, ordered by time.
+struct TranscriptEntry {
+    at: DateTime<Utc>,
+    text: String,
+}
+
+impl SessionReport {
+    /// Load the report for the session whose ID is, or starts with, `id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` when no session matches, or
+    /// `AppError::Db` if a query fails.
+    pub async fn load(db: &Arc<Database>, id: &str) -> Result<Self> {
+        let session = find_session(db, id).await?;
+        let approvals = ApprovalRepo::new(Arc::clone(db))
+            .list_for_session(&session.id)
+            .await?;
+        let prompts = PromptRepo::new(Arc::clone(db))
+            .list_for_session(&session.id)
+            .await?;
+        let steering = SteeringRepo::new(Arc::clone(db))
+            .list_for_session(&session.id)
+            .await?;
+        let relays = RelayRepo::new(Arc::clone(db))
+            .list_for_session(&session.id)
+            .await?;
+        let notes = SessionNoteRepo::new(Arc::clone(db))
+            .list_for_session(&session.id)
+            .await?;
+        let run = match session.run_id {
+            Some(ref run_id) => RunRepo::new(Arc::clone(db)).get_by_id(run_id).await?,
+            None => None,
+        };
+        Ok(Self {
+            session,
+            approvals,
+            prompts,
+            steering,
+            relays,
+            notes,
+            run,
+        })
+    }
+
+    /// Approval requests whose diff was accepted.
+    fn applied(&self) -> impl Iterator<Item = &ApprovalRequest> {
+        self.approvals.iter().filter(|a| {
+            matches!(
+                a.status,
+                ApprovalStatus::Approved | ApprovalStatus::Consumed
+            )
+        })
+    }
+
+    /// Render the report as Markdown.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        self.write_overview(&mut out);
+        self.write_transcript(&mut out);
+        self.write_decisions(&mut out);
+        self.write_diffs(&mut out);
+        out
+    }
+
+    fn write_overview(&self, out: &mut String) {
+        let session = &self.session;
+        let heading = session.title.as_deref().unwrap_or(&session.id);
+        let _ = writeln!(out, "# Session report: {heading}\n");
+        out.push_str("| | |\n|---|---|\n");
+        let mut row = |label: &str, value: &str| {
+            let _ = writeln!(out, "| {label} | {} |", table_cell(value));
+        };
+        row("Session", &format!("`{}`", session.id));
+        row("Owner", &session.owner_user_id);
+        row("Workspace", &format!("`{}`", session.workspace_root));
+        row("Protocol", session.protocol_mode.as_str());
+        row("Final status", session.status.as_str());
+        row("Started", &timestamp(session.created_at));
+        if let Some(ended_at) = session.terminated_at {
+            row("Ended", &timestamp(ended_at));
+            row(
+                "Duration",
+                &format_elapsed(
+                    ended_at
+                        .signed_duration_since(session.created_at)
+                        .num_seconds(),
+                ),
+            );
+        }
+        if let Some(ref issue_ref) = session.issue_ref {
+            row("Issue", issue_ref);
+        }
+        if !session.tags.is_empty() {
+            row("Tags", &format!("`{}`", format_tags(&session.tags)));
+        }
+        if !session.env.is_empty() {
+            row("Environment", &format!("`{}`", format_tags(&session.env)));
+        }
+        if let Some(ref run) = self.run {
+            row("Run", &format!("{} (`{}`)", run.name, run.id));
+        }
+        if let Some(ref restart_of) = session.restart_of {
+            row("Restart of", &format!("`{restart_of}`"));
+        }
+        row("Last tool", session.last_tool.as_deref().unwrap_or("none"));
+
+        if let Some(ref prompt) = session.prompt {
+            out.push_str("\n## Prompt\n\n");
+            for line in prompt.lines() {
+                let _ = writeln!(out, "> {line}");
+            }
+        }
+        if let Some(ref items) = session.progress_snapshot {
+            out.push_str("\n## Progress\n\n");
+            for item in items {
+                let mark = match item.status {
+                    ProgressStatus::Done => "[x]",
+                    ProgressStatus::InProgress => "[~]",
+                    ProgressStatus::Pending => "[ ]",
+                };
+                let _ = writeln!(out, "- {mark} {}", item.label);
+            }
+        }
+    }
+
+    fn write_transcript(&self, out: &mut String) {
+        let mut entries = vec![TranscriptEntry {
+            at: self.session.created_at,
+            text: "Session started".to_owned(),
+        }];
+        for approval in &self.approvals {
+            entries.push(TranscriptEntry {
+                at: approval.created_at,
+                text: format!(
+                    "Agent requested approval: **{}** (`{}`, {} risk) — {}",
+                    approval.title,
+                    approval.file_path,
+                    approval.risk_level.as_str(),
+                    approval_status_label(approval.status)
+                ),
+            });
+        }
+        for prompt in &self.prompts {
+            let mut text = format!(
+                "Agent asked ({}): {}",
+                prompt.prompt_type.as_str().replace('_', " "),
+                one_line(&prompt.prompt_text)
+            );
+            match prompt.decision {
+                Some(decision) => {
+                    let _ = write!(text, " — operator chose **{}**", decision_label(decision));
+                    if let Some(ref instruction) = prompt.instruction {
+                        let _ = write!(text, ": {}", one_line(instruction));
+                    }
+                }
+                None => text.push_str(" — no decision"),
+            }
+            entries.push(TranscriptEntry {
+                at: prompt.created_at,
+                text,
+            });
+        }
+        for message in &self.steering {
+            let delivery = if message.consumed {
+                "delivered"
+            } else {
+                "not delivered"
+            };
+            let origin = match message.source {
+                SteeringSource::Slack => "Operator steered via Slack",
+                SteeringSource::Ipc => "Operator steered via local CLI",
+                SteeringSource::Subtask => "Subtask reported back",
+            };
+            entries.push(TranscriptEntry {
+                at: message.created_at,
+                text: format!("{origin}: {} ({delivery})", one_line(&message.message)),
+            });
+        }
+        for note in &self.notes {
+            entries.push(TranscriptEntry {
+                at: note.created_at,
+                text: format!("Operator {} noted: {}", note.author, one_line(&note.text)),
+            });
+        }
+        for relay in &self.relays {
+            let topic = relay
+                .topic
+                .as_deref()
+                .map(|t| format!(" [{t}]"))
+                .unwrap_or_default();
+            let delivery = if relay.delivered_at.is_some() {
+                "delivered"
+            } else {
+                "not delivered"
+            };
+            let text = if relay.from_session_id == self.session.id {
+                format!(
+                    "Agent relayed a message{topic} to session `{}` ({delivery})",
+                    relay.to_session_id
+                )
+            } else {
+                format!(
+                    "Agent was relayed a message{topic} from session `{}` ({delivery})",
+                    relay.from_session_id
+                )
+            };
+            entries.push(TranscriptEntry {
+                at: relay.created_at,
+                text,
+            });
+        }
+        if let Some(ended_at) = self.session.terminated_at {
+            entries.push(TranscriptEntry {
+                at: ended_at,
+                text: format!("Session ended ({})", self.session.status.as_str()),
+            });
+        }
+        // Stable sort keeps same-instant entries in insertion order.
+        entries.sort_by_key(|entry| entry.at);
+
+        out.push_str("\n## Transcript\n\n");
+        for entry in entries {
+            let _ = writeln!(out, "- `{}` {}", timestamp(entry.at), entry.text);
+        }
+    }
+
+    fn write_decisions(&self, out: &mut String) {
+        out.push_str("\n## Decisions\n\n");
+        let decided_prompts = self.prompts.iter().filter(|p| p.decision.is_some());
+        if self.approvals.is_empty() && decided_prompts.clone().next().is_none() {
+            out.push_str("No operator decisions were recorded.\n");
+            return;
+        }
+        out.push_str("| Time | Request | Decision |\n|---|---|---|\n");
+        for approval in &self.approvals {
+            let _ = writeln!(
+                out,
+                "| {} | Approval: {} (`{}`) | {} |",
+                timestamp(approval.created_at),
+                table_cell(&approval.title),
+                table_cell(&approval.file_path),
+                approval_status_label(approval.status)
+            );
+        }
+        for prompt in decided_prompts {
+            let decision = prompt.decision.map_or("", decision_label);
+            let _ = writeln!(
+                out,
+                "| {} | Prompt: {} | {} |",
+                timestamp(prompt.created_at),
+                table_cell(&one_line(&prompt.prompt_text)),
+                decision
+            );
+        }
+    }
+
+    fn write_diffs(&self, out: &mut String) {
+        out.push_str("\n## Diffs applied\n");
+        let mut any = false;
+        for approval in self.applied() {
+            any = true;
+            let fence = code_fence(&approval.diff_content);
+            let _ = write!(
+                out,
+                "\n### `{}` — {}\n\n{fence}diff\n{}",
+                approval.file_path, approval.title, approval.diff_content
+            );
+            if !approval.diff_content.ends_with('\n') {
+                out.push('\n');
+            }
+            out.push_str(&fence);
+            out.push('\n');
+        }
+        if !any {
+            out.push_str("\nNo diffs were approved.\n");
+        }
+    }
+}
+
+/// Find the session whose ID is, or starts with, `id`.
+///
+/// # Errors
+///
+/// Returns `AppError::NotFound` when no session matches, or `AppError::Db`
+/// if a query fails.
+pub(crate) async fn find_session(db: &Arc<Database>, id: &str) -> Result<Session> {
+    let sessions = SessionRepo::new(Arc::clone(db));
+    match sessions.get_by_id(id).await? {
+        Some(session) => Ok(session),
+        None => sessions
+            .get_by_prefix(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("session '{id}' not found"))),
+    }
+}
+
+/// Human-readable prompt decision.
+pub(crate) fn decision_label(decision: PromptDecision) -> &'static str {
+    match decision {
+        PromptDecision::Continue => "continue",
+        PromptDecision::Refine => "refine",
+        PromptDecision::Stop => "stop",
+    }
+}
+
+/// Report timestamp, to the second in UTC.
+pub(crate) fn timestamp(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m-%d %H:%M:%S UTC").to_string()
+}
+
+/// Collapse text onto one line for transcript and table entries.
+pub(crate) fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Escape a value for a Markdown table cell.
+fn table_cell(value: &str) -> String {
+    one_line(value).replace('|', "\\|")
+}
+
+/// A backtick fence longer than any backtick run inside `content`.
+fn code_fence(content: &str) -> String {
+    let longest = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
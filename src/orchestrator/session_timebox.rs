@@ -0,0 +1,145 @@
+//! Time-box enforcement for sessions started with `--max-duration`.
+//!
+//! A sweep runs on a fixed interval over active sessions that carry a
+//! [`deadline`](crate::models::session::Session::deadline):
+//!
+//! 1. Once the remaining budget drops below [`wrap_up_lead`], the agent is
+//!    steered to wrap up (sent once per session).
+//! 2. At the deadline the agent's current turn is interrupted, a summary
+//!    is posted to the session thread, and the deadline is cleared. The
+//!    session itself stays open so the operator can continue or stop it.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::models::session::Session;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::blocks;
+use crate::slack::client::SlackMessage;
+use crate::slack::handlers::steer;
+use crate::state::AppState;
+use crate::Result;
+
+/// How often time-boxed sessions are checked.
+pub const SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Shortest wrap-up warning lead time.
+const MIN_WRAP_UP_LEAD: chrono::Duration = chrono::Duration::minutes(1);
+
+/// Longest wrap-up warning lead time.
+const MAX_WRAP_UP_LEAD: chrono::Duration = chrono::Duration::minutes(15);
+
+/// How long before the deadline the wrap-up warning is sent.
+///
+/// A tenth of the budget, clamped to between one and fifteen minutes.
+#[must_use]
+pub fn wrap_up_lead(budget: chrono::Duration) -> chrono::Duration {
+    (budget / 10).clamp(MIN_WRAP_UP_LEAD, MAX_WRAP_UP_LEAD)
+}
+
+/// Spawn the periodic time-box sweep.
+#[must_use]
+pub fn spawn_time_box_task(state: Arc<AppState>, cancel: CancellationToken) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => {
+                    info!("session time-box task shutting down");
+                    break;
+                }
+                _ = interval.tick() => {
+                    if let Err(err) = enforce_time_boxes(&state, Utc::now()).await {
+                        warn!(%err, "session time-box sweep failed");
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Send due wrap-up warnings and enforce elapsed deadlines as of `now`.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the time-boxed sessions cannot be listed or
+/// updated. Delivery and Slack failures are logged, not returned.
+pub async fn enforce_time_boxes(state: &AppState, now: DateTime<Utc>) -> Result<()> {
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    for session in repo.list_time_boxed().await? {
+        let Some(deadline) = session.deadline else {
+            continue;
+        };
+        if now >= deadline {
+            expire(state, &repo, &session, deadline).await?;
+        } else if !session.wrap_up_sent
+            && now >= deadline - wrap_up_lead(deadline - session.created_at)
+        {
+            let remaining = (deadline - now).num_minutes().max(1);
+            let text = format!(
+                "Time box: about {remaining} minute(s) remain in this session. Wrap up now: \
+                 finish or checkpoint the current step, summarise what is done and what is \
+                 left, and stop before {}.",
+                deadline.format("%H:%M UTC")
+            );
+            if let Err(err) = steer::steer_session(state, &session, &text).await {
+                warn!(%err, session_id = %session.id, "failed to send time-box wrap-up");
+                continue;
+            }
+            repo.mark_wrap_up_sent(&session.id).await?;
+            info!(session_id = %session.id, remaining, "time-box wrap-up sent");
+        }
+    }
+    Ok(())
+}
+
+/// Interrupt a session whose deadline has passed and report it.
+async fn expire(
+    state: &AppState,
+    repo: &SessionRepo,
+    session: &Session,
+    deadline: DateTime<Utc>,
+) -> Result<()> {
+    repo.clear_deadline(&session.id).await?;
+    if let Err(err) = state
+        .driver_for(&session.id)
+        .await
+        .interrupt(&session.id)
+        .await
+    {
+        warn!(%err, session_id = %session.id, "time-box interrupt failed");
+    }
+    info!(session_id = %session.id, "session time box reached; agent interrupted");
+
+    if let Some(ref logger) = state.audit_logger {
+        let entry =
+            AuditEntry::new(AuditEventType::SessionTimeBoxExpired).with_session(session.id.clone());
+        if let Err(err) = logger.log_entry(entry) {
+            warn!(%err, "audit log write failed (time box expired)");
+        }
+    }
+
+    if let (Some(ref slack), Some(ref channel_id)) = (&state.slack, &session.channel_id) {
+        let budget = deadline - session.created_at;
+        let msg = SlackMessage {
+            channel: SlackChannelId(channel_id.clone()),
+            text: Some(format!(
+                "\u{23f1}\u{fe0f} Session `{}` reached its time box and was interrupted",
+                session.id
+            )),
+            blocks: Some(blocks::time_box_expired_blocks(session, budget)),
+            thread_ts: session.thread_ts.clone().map(SlackTs),
+        };
+        if let Err(err) = slack.enqueue(msg).await {
+            warn!(%err, session_id = %session.id, "failed to post time-box summary");
+        }
+    }
+    Ok(())
+}
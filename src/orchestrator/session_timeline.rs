@@ -0,0 +1,331 @@
+//! JSON session timelines (`intercom://session/{id}/timeline`).
+//!
+//! Where the [`SessionReport`](super::session_report::SessionReport) is a
+//! Markdown record for people, the timeline is a chronological, machine-
+//! readable list of what happened in a session — tool calls, approval
+//! requests and their decisions, forwarded prompts, steering, stalls and
+//! operator notes —
+//! so an agent deep into a long session can look back at what it did and
+//! what the operator told it.
+//!
+//! Tool calls and approval decisions come from the audit log; everything
+//! else comes from the session's repositories.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::audit::{self, AuditEntry, AuditEventType};
+use crate::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use crate::models::prompt::{ContinuationPrompt, PromptDecision, PromptType};
+use crate::models::session::{Session, SessionStatus};
+use crate::models::session_note::SessionNote;
+use crate::models::stall::{StallAlert, StallAlertStatus, StallResolution};
+use crate::models::steering::{SteeringMessage, SteeringSource};
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::db::Database;
+use crate::persistence::prompt_repo::PromptRepo;
+use crate::persistence::session_note_repo::SessionNoteRepo;
+use crate::persistence::stall_repo::StallAlertRepo;
+use crate::persistence::steering_repo::SteeringRepo;
+use crate::{AppError, Result};
+
+use super::session_report::find_session;
+
+/// Chronological record of one session.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionTimeline {
+    /// The session's ID.
+    pub session_id: String,
+    /// The session's status when the timeline was assembled.
+    pub status: SessionStatus,
+    /// Everything that happened, oldest first.
+    pub events: Vec<TimelineEntry>,
+}
+
+/// One timeline event and when it happened.
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineEntry {
+    /// When the event happened.
+    pub at: DateTime<Utc>,
+    /// What happened.
+    #[serde(flatten)]
+    pub event: TimelineEvent,
+}
+
+/// A timeline event, serialized with a `kind` tag.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TimelineEvent {
+    /// The session was created.
+    SessionStarted {
+        /// Initial task prompt, for spawned sessions.
+        prompt: Option<String>,
+    },
+    /// The agent called an MCP tool.
+    ToolCall {
+        /// Tool name.
+        tool: String,
+        /// `ok` or `error`.
+        outcome: Option<String>,
+    },
+    /// The agent asked for approval of a change.
+    ApprovalRequested {
+        /// Approval request ID.
+        request_id: String,
+        /// Proposal title.
+        title: String,
+        /// Target file.
+        file_path: String,
+        /// Declared risk.
+        risk_level: RiskLevel,
+        /// Current status of the request.
+        status: ApprovalStatus,
+    },
+    /// An operator decided an approval request.
+    ApprovalDecided {
+        /// Approval request ID.
+        request_id: String,
+        /// Whether the change was approved.
+        approved: bool,
+        /// Who decided (Slack user ID, or `webhook:<actor>`).
+        operator: Option<String>,
+        /// Rejection reason.
+        reason: Option<String>,
+    },
+    /// The agent forwarded a prompt to the operator.
+    PromptForwarded {
+        /// Prompt ID.
+        prompt_id: String,
+        /// Prompt category.
+        prompt_type: PromptType,
+        /// Prompt text.
+        text: String,
+        /// Operator decision, once made.
+        decision: Option<PromptDecision>,
+        /// Operator instruction accompanying a refine decision.
+        instruction: Option<String>,
+    },
+    /// A steering message was queued for the session.
+    Steering {
+        /// Steering message ID.
+        message_id: String,
+        /// Where the message came from.
+        source: SteeringSource,
+        /// Message text.
+        message: String,
+        /// Whether the agent has received it.
+        delivered: bool,
+    },
+    /// The session's agent went silent and a stall alert was raised.
+    Stall {
+        /// Stall alert ID.
+        alert_id: String,
+        /// Seconds idle when the alert was raised.
+        idle_seconds: i64,
+        /// Last tool called before the stall.
+        last_tool: Option<String>,
+        /// Nudges sent for this stall.
+        nudge_count: i64,
+        /// Alert lifecycle status.
+        status: StallAlertStatus,
+        /// How the stall ended; `null` while it is open.
+        resolution: Option<StallResolution>,
+    },
+    /// An operator left a note on the session with `/intercom note`.
+    Note {
+        /// Slack user ID of the operator.
+        author: String,
+        /// Note text.
+        text: String,
+    },
+    /// The session ended.
+    SessionEnded {
+        /// Final status.
+        status: SessionStatus,
+    },
+}
+
+/// Repository and audit records a timeline is assembled from.
+#[derive(Debug, Clone, Default)]
+pub struct TimelineSources {
+    /// Approval requests raised by the session.
+    pub approvals: Vec<ApprovalRequest>,
+    /// Prompts forwarded by the session.
+    pub prompts: Vec<ContinuationPrompt>,
+    /// Steering messages queued for the session.
+    pub steering: Vec<SteeringMessage>,
+    /// Stall alerts raised for the session.
+    pub stalls: Vec<StallAlert>,
+    /// Operator notes on the session.
+    pub notes: Vec<SessionNote>,
+    /// Audit entries: the session's tool calls and the decision on each of
+    /// its approval requests.
+    pub audit: Vec<AuditEntry>,
+}
+
+impl SessionTimeline {
+    /// Load the timeline for the session whose ID is, or starts with, `id`,
+    /// reading audit entries from `log_dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` when no session matches, or
+    /// `AppError::Db` if a query fails.
+    pub async fn load(db: &Arc<Database>, log_dir: &Path, id: &str) -> Result<Self> {
+        let session = find_session(db, id).await?;
+        let approvals = ApprovalRepo::new(Arc::clone(db))
+            .list_for_session(&session.id)
+            .await?;
+        let prompts = PromptRepo::new(Arc::clone(db))
+            .list_for_session(&session.id)
+            .await?;
+        let steering = SteeringRepo::new(Arc::clone(db))
+            .list_for_session(&session.id)
+            .await?;
+        let stalls = StallAlertRepo::new(Arc::clone(db))
+            .list_for_session(&session.id)
+            .await?;
+        let notes = SessionNoteRepo::new(Arc::clone(db))
+            .list_for_session(&session.id)
+            .await?;
+
+        let log_dir = log_dir.to_path_buf();
+        let session_id = session.id.clone();
+        let since = session.created_at.date_naive();
+        let request_ids: Vec<String> = approvals.iter().map(|a| a.id.clone()).collect();
+        let audit = tokio::task::spawn_blocking(move || {
+            let ids: Vec<&str> = request_ids.iter().map(String::as_str).collect();
+            let mut entries = audit::reader::entries_for_session(&log_dir, &session_id, since);
+            entries.retain(|entry| entry.event_type == AuditEventType::ToolCall);
+            entries.extend(audit::reader::find_decisions(&log_dir, &ids).into_values());
+            entries
+        })
+        .await
+        .map_err(|err| AppError::Io(format!("audit log read failed: {err}")))?;
+
+        Ok(Self::assemble(
+            &session,
+            TimelineSources {
+                approvals,
+                prompts,
+                steering,
+                stalls,
+                notes,
+                audit,
+            },
+        ))
+    }
+
+    /// Merge `sources` into a chronological timeline for `session`.
+    #[must_use]
+    pub fn assemble(session: &Session, sources: TimelineSources) -> Self {
+        let mut events = vec![TimelineEntry {
+            at: session.created_at,
+            event: TimelineEvent::SessionStarted {
+                prompt: session.prompt.clone(),
+            },
+        }];
+
+        let request_ids: HashSet<&str> = sources.approvals.iter().map(|a| a.id.as_str()).collect();
+        for entry in &sources.audit {
+            let event = match entry.event_type {
+                AuditEventType::ToolCall
+                    if entry.session_id.as_deref() == Some(session.id.as_str()) =>
+                {
+                    TimelineEvent::ToolCall {
+                        tool: entry.tool_name.clone().unwrap_or_default(),
+                        outcome: entry.result_summary.clone(),
+                    }
+                }
+                AuditEventType::Approval | AuditEventType::Rejection => {
+                    let Some(request_id) = entry
+                        .request_id
+                        .as_deref()
+                        .filter(|id| request_ids.contains(id))
+                    else {
+                        continue;
+                    };
+                    TimelineEvent::ApprovalDecided {
+                        request_id: request_id.to_owned(),
+                        approved: entry.event_type == AuditEventType::Approval,
+                        operator: entry.operator_id.clone(),
+                        reason: entry.reason.clone(),
+                    }
+                }
+                _ => continue,
+            };
+            events.push(TimelineEntry {
+                at: entry.timestamp,
+                event,
+            });
+        }
+
+        events.extend(sources.approvals.into_iter().map(|a| TimelineEntry {
+            at: a.created_at,
+            event: TimelineEvent::ApprovalRequested {
+                request_id: a.id,
+                title: a.title,
+                file_path: a.file_path,
+                risk_level: a.risk_level,
+                status: a.status,
+            },
+        }));
+        events.extend(sources.prompts.into_iter().map(|p| TimelineEntry {
+            at: p.created_at,
+            event: TimelineEvent::PromptForwarded {
+                prompt_id: p.id,
+                prompt_type: p.prompt_type,
+                text: p.prompt_text,
+                decision: p.decision,
+                instruction: p.instruction,
+            },
+        }));
+        events.extend(sources.steering.into_iter().map(|m| TimelineEntry {
+            at: m.created_at,
+            event: TimelineEvent::Steering {
+                message_id: m.id,
+                source: m.source,
+                message: m.message,
+                delivered: m.consumed,
+            },
+        }));
+        events.extend(sources.stalls.into_iter().map(|s| TimelineEntry {
+            at: s.created_at,
+            event: TimelineEvent::Stall {
+                alert_id: s.id,
+                idle_seconds: s.idle_seconds,
+                last_tool: s.last_tool,
+                nudge_count: s.nudge_count,
+                status: s.status,
+                resolution: s.resolution,
+            },
+        }));
+        events.extend(sources.notes.into_iter().map(|n| TimelineEntry {
+            at: n.created_at,
+            event: TimelineEvent::Note {
+                author: n.author,
+                text: n.text,
+            },
+        }));
+        if let Some(ended_at) = session.terminated_at {
+            events.push(TimelineEntry {
+                at: ended_at,
+                event: TimelineEvent::SessionEnded {
+                    status: session.status,
+                },
+            });
+        }
+        // Stable sort keeps same-instant entries in insertion order.
+        events.sort_by_key(|entry| entry.at);
+
+        Self {
+            session_id: session.id.clone(),
+            status: session.status,
+            events,
+        }
+    }
+}
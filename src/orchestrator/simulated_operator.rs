@@ -0,0 +1,369 @@
+//! Simulated operator for CI runs (`--simulate-operator <policy>`).
+//!
+//! Answers approval requests and forwarded prompts from a scripted policy
+//! file after a fixed delay, so agent pipelines can be exercised end to end
+//! without a human or Slack. Decisions go through the same paths as an
+//! out-of-band operator: approvals via the webhook's `apply_decision`,
+//! prompts by recording the decision and resolving the waiting agent.
+//!
+//! ```toml
+//! delay_ms = 250
+//! default_approval = "approve"
+//! default_prompt = "continue"
+//!
+//! [[approval]]
+//! path = "migrations/**"
+//! decision = "reject"
+//! reason = "migrations need a human"
+//!
+//! [[approval]]
+//! risk_level = "critical"
+//! decision = "reject"
+//!
+//! [[prompt]]
+//! prompt_type = "error_recovery"
+//! decision = "stop"
+//! ```
+//!
+//! Rules are checked in order and the first match wins; requests no rule
+//! matches get the default.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::driver::AgentEvent;
+use crate::mcp::approval_webhook::{apply_decision, Acting, Decider};
+use crate::models::approval::RiskLevel;
+use crate::models::prompt::{PromptDecision, PromptType};
+use crate::orchestrator::event_subscribers::run_subscriber;
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::prompt_repo::PromptRepo;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// How often to check whether the agent has started waiting.
+const REGISTRATION_POLL: Duration = Duration::from_millis(25);
+
+/// How long to wait for the agent to start waiting before giving up.
+const REGISTRATION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Audit `operator_id` of simulated decisions.
+pub const SIMULATED_OPERATOR_ID: &str = "simulator";
+
+/// Scripted decision on an approval request.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptedApproval {
+    /// Approve the request.
+    #[default]
+    Approve,
+    /// Reject the request.
+    Reject,
+}
+
+/// One `[[approval]]` rule. Unset matchers match everything.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ApprovalRule {
+    /// Only requests of this risk level.
+    pub risk_level: Option<RiskLevel>,
+    /// Only requests whose `file_path` matches this glob.
+    pub path: Option<String>,
+    /// Decision to make.
+    pub decision: ScriptedApproval,
+    /// Rejection reason handed to the agent.
+    pub reason: Option<String>,
+}
+
+/// One `[[prompt]]` rule. Unset matchers match everything.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct PromptRule {
+    /// Only prompts of this type.
+    pub prompt_type: Option<PromptType>,
+    /// Decision to make.
+    pub decision: PromptDecision,
+    /// Instruction handed to the agent with `refine`.
+    pub instruction: Option<String>,
+}
+
+/// A simulated operator policy file.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct OperatorScript {
+    /// Milliseconds to wait before answering, as a human would.
+    #[serde(default)]
+    pub delay_ms: u64,
+    /// Decision for approval requests no rule matches.
+    #[serde(default)]
+    pub default_approval: ScriptedApproval,
+    /// Decision for prompts no rule matches.
+    #[serde(default = "default_prompt")]
+    pub default_prompt: PromptDecision,
+    /// Approval rules, first match wins.
+    #[serde(default, rename = "approval")]
+    pub approvals: Vec<ApprovalRule>,
+    /// Prompt rules, first match wins.
+    #[serde(default, rename = "prompt")]
+    pub prompts: Vec<PromptRule>,
+}
+
+fn default_prompt() -> PromptDecision {
+    PromptDecision::Continue
+}
+
+impl OperatorScript {
+    /// Parse a policy from TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` if the TOML is invalid or a `path` glob
+    /// does not parse.
+    pub fn from_toml_str(raw: &str) -> Result<Self> {
+        let script: Self = toml::from_str(raw)
+            .map_err(|err| AppError::Config(format!("invalid simulated operator policy: {err}")))?;
+        if let Some(bad) = script
+            .approvals
+            .iter()
+            .filter_map(|rule| rule.path.as_deref())
+            .find(|pattern| glob::Pattern::new(pattern).is_err())
+        {
+            return Err(AppError::Config(format!(
+                "invalid path glob '{bad}' in simulated operator policy"
+            )));
+        }
+        Ok(script)
+    }
+
+    /// Read and parse the policy file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` if the file cannot be read or is invalid.
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path).map_err(|err| {
+            AppError::Config(format!(
+                "cannot read simulated operator policy '{}': {err}",
+                path.display()
+            ))
+        })?;
+        Self::from_toml_str(&raw)
+    }
+
+    /// Delay before each decision.
+    #[must_use]
+    pub fn delay(&self) -> Duration {
+        Duration::from_millis(self.delay_ms)
+    }
+
+    /// Decide an approval request for `file_path` at `risk_level`:
+    /// whether it is approved, and the reason for a rejection.
+    #[must_use]
+    pub fn decide_approval(
+        &self,
+        file_path: &str,
+        risk_level: RiskLevel,
+    ) -> (bool, Option<String>) {
+        let rule = self.approvals.iter().find(|rule| {
+            rule.risk_level.is_none_or(|level| level == risk_level)
+                && rule.path.as_deref().is_none_or(|pattern| {
+                    glob::Pattern::new(pattern).is_ok_and(|glob| glob.matches(file_path))
+                })
+        });
+        match rule {
+            Some(rule) => (
+                rule.decision == ScriptedApproval::Approve,
+                (rule.decision == ScriptedApproval::Reject).then(|| {
+                    rule.reason
+                        .clone()
+                        .unwrap_or_else(|| "rejected by simulated operator".to_owned())
+                }),
+            ),
+            None => (
+                self.default_approval == ScriptedApproval::Approve,
+                (self.default_approval == ScriptedApproval::Reject)
+                    .then(|| "no simulated operator rule matched".to_owned()),
+            ),
+        }
+    }
+
+    /// Decide a prompt of `prompt_type`, with the instruction for `refine`.
+    #[must_use]
+    pub fn decide_prompt(&self, prompt_type: PromptType) -> (PromptDecision, Option<String>) {
+        self.prompts
+            .iter()
+            .find(|rule| rule.prompt_type.is_none_or(|kind| kind == prompt_type))
+            .map_or((self.default_prompt, None), |rule| {
+                let instruction = (rule.decision == PromptDecision::Refine)
+                    .then(|| rule.instruction.clone())
+                    .flatten();
+                (rule.decision, instruction)
+            })
+    }
+}
+
+/// Spawn the simulated operator, answering every approval request and
+/// prompt published on the event bus.
+#[must_use]
+pub fn spawn_simulated_operator(
+    state: Arc<AppState>,
+    script: Arc<OperatorScript>,
+    cancel: CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    let rx = state.event_bus.subscribe();
+    let metrics = state.event_bus.metrics();
+    info!(
+        approval_rules = script.approvals.len(),
+        prompt_rules = script.prompts.len(),
+        delay_ms = script.delay_ms,
+        "simulated operator started"
+    );
+    tokio::spawn(run_subscriber(
+        "simulated_operator",
+        rx,
+        metrics,
+        cancel,
+        move |bus_event| {
+            let state = Arc::clone(&state);
+            let script = Arc::clone(&script);
+            match bus_event.event {
+                AgentEvent::ClearanceRequested { request_id, .. } => {
+                    tokio::spawn(async move {
+                        answer_approval(&state, &script, &request_id).await;
+                    });
+                }
+                AgentEvent::PromptForwarded { prompt_id, .. } => {
+                    tokio::spawn(async move {
+                        answer_prompt(&state, &script, &prompt_id).await;
+                    });
+                }
+                _ => {}
+            }
+            std::future::ready(())
+        },
+    ))
+}
+
+/// Decide approval request `request_id` once the agent is waiting on it.
+async fn answer_approval(state: &Arc<AppState>, script: &OperatorScript, request_id: &str) {
+    tokio::time::sleep(script.delay()).await;
+    let record = match ApprovalRepo::new(Arc::clone(&state.db))
+        .get_by_id(request_id)
+        .await
+    {
+        Ok(Some(record)) => record,
+        Ok(None) => return,
+        Err(err) => {
+            warn!(%err, request_id, "simulated operator failed to load approval request");
+            return;
+        }
+    };
+    if !wait_registered(|| async {
+        state
+            .pending_approvals
+            .lock()
+            .await
+            .contains_key(request_id)
+    })
+    .await
+    {
+        warn!(
+            request_id,
+            "simulated operator: no agent waiting on approval request"
+        );
+        return;
+    }
+
+    let (approved, reason) = script.decide_approval(&record.file_path, record.risk_level);
+    let decider = Decider {
+        operator: SIMULATED_OPERATOR_ID.to_owned(),
+        via: "by the simulated operator".to_owned(),
+        acting: Acting::SessionOwner,
+    };
+    match apply_decision(state, request_id, approved, &decider, reason).await {
+        Ok(_) => info!(request_id, approved, "simulated operator decided approval"),
+        Err(err) => warn!(%err, request_id, "simulated operator failed to decide approval"),
+    }
+}
+
+/// Answer prompt `prompt_id` once the agent is waiting on it. Prompts
+/// already answered by `[[prompts.auto]]` are left alone.
+async fn answer_prompt(state: &Arc<AppState>, script: &OperatorScript, prompt_id: &str) {
+    tokio::time::sleep(script.delay()).await;
+    let repo = PromptRepo::new(Arc::clone(&state.db));
+    let record = match repo.get_by_id(prompt_id).await {
+        Ok(Some(record)) if record.decision.is_none() => record,
+        Ok(_) => return,
+        Err(err) => {
+            warn!(%err, prompt_id, "simulated operator failed to load prompt");
+            return;
+        }
+    };
+    if !wait_registered(|| async { state.pending_prompts.lock().await.contains_key(prompt_id) })
+        .await
+    {
+        warn!(prompt_id, "simulated operator: no agent waiting on prompt");
+        return;
+    }
+
+    let (decision, instruction) = script.decide_prompt(record.prompt_type);
+    match repo.decide(prompt_id, decision, instruction.clone()).await {
+        Ok(true) => {}
+        Ok(false) => return,
+        Err(err) => {
+            warn!(%err, prompt_id, "simulated operator failed to record prompt decision");
+            return;
+        }
+    }
+    if let Some(ref logger) = state.audit_logger {
+        let entry = AuditEntry::new(AuditEventType::PromptAutoDecision)
+            .with_session(record.session_id.clone())
+            .with_request_id(prompt_id.to_owned())
+            .with_operator(SIMULATED_OPERATOR_ID.to_owned())
+            .with_result(format!("{}: simulated operator", decision.as_str()));
+        if let Err(err) = logger.log_entry(entry) {
+            warn!(%err, "audit log write failed (simulated prompt decision)");
+        }
+    }
+    if let Err(err) = state
+        .driver_for(&record.session_id)
+        .await
+        .resolve_prompt(prompt_id, decision.as_str(), instruction)
+        .await
+    {
+        warn!(%err, prompt_id, "simulated operator failed to resolve prompt");
+        return;
+    }
+    info!(
+        prompt_id,
+        decision = decision.as_str(),
+        "simulated operator answered prompt"
+    );
+}
+
+/// Poll `registered` until it holds, for at most [`REGISTRATION_TIMEOUT`].
+///
+/// The request is published before the tool registers its waiter, so a
+/// short delay can otherwise race ahead of the agent.
+async fn wait_registered<F, Fut>(registered: F) -> bool
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = bool>,
+{
+    let deadline = tokio::time::Instant::now() + REGISTRATION_TIMEOUT;
+    loop {
+        if registered().await {
+            return true;
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(REGISTRATION_POLL).await;
+    }
+}
@@ -0,0 +1,145 @@
+//! Snoozed approval and prompt decisions (the "Snooze 30m" button).
+//!
+//! Snoozing a pending approval or prompt pushes its timeout back by
+//! [`SNOOZE_DURATION`], keeps it out of the reconnect re-post until the
+//! snooze ends, and schedules a reminder for the operator. The decision
+//! buttons stay live, so the operator can still answer early.
+//!
+//! Operator activity on a request about to time out (opening the
+//! rejection modal, paging the diff) extends its timeout so that
+//! `[timeouts] decision_grace_seconds` remain, without snoozing it.
+//!
+//! Snoozes live in memory and do not survive a restart, even for requests
+//! re-armed by `[recovery] rearm_pending`.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::sync::oneshot;
+use tokio::time::Instant;
+
+/// How long one snooze defers a decision.
+pub const SNOOZE_DURATION: Duration = Duration::from_mins(30);
+
+#[derive(Debug)]
+struct Entry {
+    until: DateTime<Utc>,
+    /// Total time added to the request's timeout by its snoozes.
+    extension: Duration,
+}
+
+/// Snoozed requests, keyed by approval request or prompt ID.
+#[derive(Debug, Default)]
+pub struct SnoozeTable {
+    entries: Mutex<HashMap<String, Entry>>,
+    /// Unextended timeout of each request being waited on.
+    waiting: Mutex<HashMap<String, Instant>>,
+}
+
+impl SnoozeTable {
+    /// Create an empty snooze table.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snooze `id` for [`SNOOZE_DURATION`] from `now`, returning when the
+    /// snooze ends. Each snooze extends the request's timeout again.
+    pub fn snooze(&self, id: &str, now: DateTime<Utc>) -> DateTime<Utc> {
+        let mut entries = self.lock();
+        let entry = entries.entry(id.to_owned()).or_insert(Entry {
+            until: now,
+            extension: Duration::ZERO,
+        });
+        entry.until = now + SNOOZE_DURATION;
+        entry.extension += SNOOZE_DURATION;
+        entry.until
+    }
+
+    /// When the snooze of `id` ends, if it is still snoozed at `now`.
+    #[must_use]
+    pub fn snoozed_until(&self, id: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.lock()
+            .get(id)
+            .map(|entry| entry.until)
+            .filter(|until| *until > now)
+    }
+
+    /// Total time snoozes have added to the timeout of `id`.
+    #[must_use]
+    pub fn extension(&self, id: &str) -> Duration {
+        self.lock()
+            .get(id)
+            .map_or(Duration::ZERO, |entry| entry.extension)
+    }
+
+    /// Stop tracking `id`.
+    pub fn forget(&self, id: &str) {
+        self.lock().remove(id);
+    }
+
+    /// Extend the timeout of `id`, which is being waited on, so that
+    /// `grace` remains from `now`. Returns the new deadline, or `None` when
+    /// at least half of `grace` remained already or nothing waits on `id`.
+    pub fn extend_for_activity(
+        &self,
+        id: &str,
+        grace: Duration,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let base = *self
+            .waiting
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(id)?;
+        let mut entries = self.lock();
+        let entry = entries.entry(id.to_owned()).or_insert(Entry {
+            until: now,
+            extension: Duration::ZERO,
+        });
+        let remaining = (base + entry.extension).saturating_duration_since(Instant::now());
+        if remaining >= grace / 2 {
+            return None;
+        }
+        entry.extension += grace.saturating_sub(remaining);
+        Some(now + grace)
+    }
+
+    /// Wait up to `timeout` for the decision on `id`, plus however long it
+    /// is snoozed or extended for activity while waiting.
+    ///
+    /// Returns `None` when the (extended) timeout elapses. The snooze entry
+    /// is dropped either way.
+    pub async fn wait<T>(
+        &self,
+        id: &str,
+        timeout: Duration,
+        mut rx: oneshot::Receiver<T>,
+    ) -> Option<Result<T, oneshot::error::RecvError>> {
+        let start = Instant::now();
+        self.waiting
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(id.to_owned(), start + timeout);
+        let outcome = loop {
+            let deadline = start + timeout + self.extension(id);
+            match tokio::time::timeout_at(deadline, &mut rx).await {
+                Ok(result) => break Some(result),
+                Err(_) if start + timeout + self.extension(id) > Instant::now() => {}
+                Err(_) => break None,
+            }
+        };
+        self.forget(id);
+        self.waiting
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(id);
+        outcome
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Entry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -25,7 +25,9 @@ use crate::{AppError, Result};
 ///
 /// Creates a `Session` in the database with `Created` status, then
 /// spawns the host CLI process. The session is activated only after
-/// the process starts successfully.
+/// the process starts successfully. `channel_id` is the Slack channel the
+/// spawn was requested from; it is recorded on the session so the agent's
+/// messages, and any sessions it spawns, post there.
 ///
 /// # Errors
 ///
@@ -35,6 +37,7 @@ pub async fn spawn_session(
     prompt: &str,
     workspace_root: &str,
     owner_user_id: &str,
+    channel_id: Option<&str>,
     config: &GlobalConfig,
     session_repo: &SessionRepo,
     http_port: u16,
@@ -73,12 +76,13 @@ pub async fn spawn_session(
     // downstream components (path safety, policy loading, IPC) use a
     // consistent, fully-resolved root.
     let canonical_root = workspace_path.display().to_string();
-    let session = Session::new(
+    let mut session = Session::new(
         owner_user_id.to_owned(),
         canonical_root,
         Some(prompt.to_owned()),
         SessionMode::Remote,
     );
+    session.channel_id = channel_id.map(str::to_owned);
     let created = session_repo.create(&session).await?;
 
     // Build the MCP endpoint URL for the spawned agent.  The `session_id`
@@ -110,6 +114,82 @@ pub async fn spawn_session(
     Ok((active_session, child))
 }
 
+/// Spawn a child session requested by `parent` through `spawn_subtask`.
+///
+/// The child inherits the parent's owner, workspace and mode, posts to
+/// `channel_id` (the channel the parent posts to, falling back to the
+/// parent's recorded channel) in a thread of its own, and records the
+/// parent in `parent_session_id`. It joins the parent's run, if any. The
+/// owner check of [`spawn_session`] is skipped: the operator approved this
+/// spawn.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` if the concurrent session limit is exceeded,
+/// or `AppError::Mcp` if the process fails to spawn.
+pub async fn spawn_subtask(
+    parent: &Session,
+    prompt: &str,
+    channel_id: Option<&str>,
+    config: &GlobalConfig,
+    session_repo: &SessionRepo,
+    http_port: u16,
+) -> Result<(Session, Child)> {
+    let span = info_span!("spawn_subtask", parent_session = %parent.id);
+    let _guard = span.enter();
+
+    let active_count = session_repo.count_active().await?;
+    if active_count >= i64::from(config.max_concurrent_sessions) {
+        return Err(AppError::Config(format!(
+            "concurrent session limit reached ({}/{})",
+            active_count, config.max_concurrent_sessions
+        )));
+    }
+
+    let mut child_session = Session::new(
+        parent.owner_user_id.clone(),
+        parent.workspace_root.clone(),
+        Some(prompt.to_owned()),
+        parent.mode,
+    );
+    child_session.channel_id = channel_id
+        .map(str::to_owned)
+        .or_else(|| parent.channel_id.clone());
+    child_session.parent_session_id = Some(parent.id.clone());
+    child_session.run_id = parent.run_id.clone();
+    let created = session_repo.create(&child_session).await?;
+
+    // The parent's workspace root is already canonical.
+    let workspace_path = std::path::PathBuf::from(&created.workspace_root);
+    let mcp_url = format!("http://localhost:{http_port}/mcp?session_id={}", created.id);
+    let mut cmd = build_agent_command(config, &workspace_path, &mcp_url, &created.id, prompt);
+
+    let child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(err) => {
+            if let Err(cleanup) = session_repo
+                .set_terminated(&created.id, SessionStatus::Terminated)
+                .await
+            {
+                warn!(%cleanup, child_session = %created.id, "failed to clean up subtask session");
+            }
+            return Err(AppError::Mcp(format!("failed to spawn host cli: {err}")));
+        }
+    };
+
+    info!(
+        parent_session = parent.id,
+        child_session = created.id,
+        pid = child.id(),
+        "subtask process spawned"
+    );
+
+    let active = session_repo
+        .update_status(&created.id, SessionStatus::Active)
+        .await?;
+    Ok((active, child))
+}
+
 /// Build the host CLI command for an agent session process.
 ///
 /// Shared by [`spawn_session`] and [`respawn_session`] so both spawn paths
@@ -141,8 +221,8 @@ fn build_agent_command(
 ///
 /// Marks the `crashed` session as `Interrupted`, then creates a new session
 /// that is a restart of it (`restart_of = Some(crashed.id)`), carrying the
-/// owner, workspace, prompt, routing mode, protocol, Slack channel/thread, and
-/// ACP `agent_session_id` forward so the resumed agent rebinds to the same
+/// owner, workspace, prompt, routing mode, protocol, Slack channel/thread, run
+/// and ACP `agent_session_id` forward so the resumed agent rebinds to the same
 /// logical session. A fresh host CLI process is spawned and the resumed session
 /// is activated before it is returned.
 ///
@@ -188,6 +268,8 @@ pub async fn respawn_session(
     resumed.agent_session_id = crashed.agent_session_id.clone();
     resumed.title = crashed.title.clone();
     resumed.restart_of = Some(crashed.id.clone());
+    resumed.parent_session_id = crashed.parent_session_id.clone();
+    resumed.run_id = crashed.run_id.clone();
 
     let created = session_repo.create(&resumed).await?;
 
@@ -4,11 +4,19 @@
 //! posts formatted alert messages to the operator's Slack channel. The
 //! consumer acknowledges all event variants: posts a stall alert with
 //! action buttons on [`Stalled`], logs auto-nudge and escalation events,
-//! and posts recovery confirmations on [`SelfRecovered`].
+//! posts recovery confirmations on [`SelfRecovered`], and asks the operator
+//! whether a finished agent should be terminated or given a new task on
+//! [`Completed`].
 //!
 //! When a session has a recorded `thread_ts` the alert is posted as a
 //! threaded reply so it stays inside the session's dedicated Slack thread
-//! (S037 / S038).
+//! (S037 / S038). The alert message anchors the stall: nudge and escalation
+//! notices follow it in its thread, and self-recovery updates it in place.
+//!
+//! Every event is also recorded in the session's `stall_alert` history (see
+//! [`record_stall_event`]) so stalls show up in the session timeline.
+//! A stall marks the session's `connectivity_status` stalled and
+//! self-recovery marks it online again (see [`track_connectivity`]).
 //!
 //! # ACP nudge delivery (T097 / S064)
 //!
@@ -18,6 +26,7 @@
 //! This ensures the agent can self-correct without requiring manual
 //! operator intervention.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use slack_morphism::prelude::{SlackChannelId, SlackTs};
@@ -26,9 +35,12 @@ use tokio_util::sync::CancellationToken;
 use tracing::{info, warn};
 
 use crate::driver::AgentDriver;
-use crate::models::session::ProtocolMode;
+use crate::models::session::{ConnectivityStatus, ProtocolMode};
+use crate::models::stall::{StallAlert, StallAlertStatus, StallResolution};
 use crate::persistence::db::Database;
 use crate::persistence::session_repo::SessionRepo;
+use crate::persistence::stall_repo::StallAlertRepo;
+use crate::slack::anchor::Anchor;
 use crate::slack::blocks;
 use crate::slack::client::{SlackMessage, SlackService};
 
@@ -65,6 +77,8 @@ pub fn spawn_stall_event_consumer(
     cancel: CancellationToken,
 ) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
+        // Alert message of each session's open stall, keyed by session ID.
+        let mut open_alerts: HashMap<String, Anchor> = HashMap::new();
         loop {
             let event = tokio::select! {
                 () = cancel.cancelled() => {
@@ -84,9 +98,12 @@ pub fn spawn_stall_event_consumer(
                 StallEvent::Stalled { session_id, .. }
                 | StallEvent::AutoNudge { session_id, .. }
                 | StallEvent::Escalated { session_id, .. }
-                | StallEvent::SelfRecovered { session_id } => session_id.clone(),
+                | StallEvent::SelfRecovered { session_id }
+                | StallEvent::Completed { session_id, .. } => session_id.clone(),
             };
 
+            track_connectivity(&db, &event).await;
+
             let (effective_channel, thread_ts) =
                 resolve_session_context(&session_id_for_lookup, &channel, &db).await;
             let channel_id = SlackChannelId(effective_channel);
@@ -106,8 +123,11 @@ pub fn spawn_stall_event_consumer(
                         blocks: Some(alert_blocks),
                         thread_ts,
                     };
-                    if let Err(err) = slack.enqueue(msg).await {
-                        warn!(%err, "failed to post stall alert to slack");
+                    let anchor = Anchor::post(&slack, msg).await;
+                    let slack_ts = anchor.as_ref().map(|anchor| anchor.ts.0.clone());
+                    record_stall_event(&db, &event, slack_ts).await;
+                    if let Some(anchor) = anchor {
+                        open_alerts.insert(session_id.clone(), anchor);
                     }
                 }
                 StallEvent::AutoNudge {
@@ -115,22 +135,28 @@ pub fn spawn_stall_event_consumer(
                     nudge_count,
                 } => {
                     info!(session_id, nudge_count, "auto-nudge event");
+                    record_stall_event(&db, &event, None).await;
 
                     // T097 / S064: Deliver nudge directly on the ACP stream for
                     // ACP sessions so the agent can self-correct immediately.
                     deliver_acp_nudge_if_applicable(session_id, nudge_count, driver.as_ref(), &db)
                         .await;
 
-                    let msg = SlackMessage {
-                        channel: channel_id,
-                        text: Some(format!(
-                            "\u{1f514} Auto-nudge #{nudge_count} sent to session `{session_id}`"
-                        )),
-                        blocks: None,
-                        thread_ts,
-                    };
-                    if let Err(err) = slack.enqueue(msg).await {
-                        warn!(%err, "failed to post auto-nudge notification");
+                    let text = format!(
+                        "\u{1f514} Auto-nudge #{nudge_count} sent to session `{session_id}`"
+                    );
+                    if let Some(anchor) = open_alerts.get(session_id) {
+                        anchor.follow_up(&slack, text, None).await;
+                    } else {
+                        let msg = SlackMessage {
+                            channel: channel_id,
+                            text: Some(text),
+                            blocks: None,
+                            thread_ts,
+                        };
+                        if let Err(err) = slack.enqueue(msg).await {
+                            warn!(%err, "failed to post auto-nudge notification");
+                        }
                     }
                 }
                 StallEvent::Escalated {
@@ -138,31 +164,64 @@ pub fn spawn_stall_event_consumer(
                     nudge_count,
                 } => {
                     warn!(session_id, nudge_count, "stall escalated");
-                    let msg = SlackMessage {
-                        channel: channel_id,
-                        text: Some(format!(
-                            "\u{1f6a8} *Stall escalated* \u{2014} session `{session_id}` exceeded \
-                             {nudge_count} nudge attempts. Manual intervention required."
-                        )),
-                        blocks: None,
-                        thread_ts,
-                    };
-                    if let Err(err) = slack.enqueue(msg).await {
-                        warn!(%err, "failed to post escalation notification");
+                    record_stall_event(&db, &event, None).await;
+                    let text = format!(
+                        "\u{1f6a8} *Stall escalated* \u{2014} session `{session_id}` exceeded \
+                         {nudge_count} nudge attempts. Manual intervention required."
+                    );
+                    if let Some(anchor) = open_alerts.get(session_id) {
+                        anchor.follow_up(&slack, text, None).await;
+                    } else {
+                        let msg = SlackMessage {
+                            channel: channel_id,
+                            text: Some(text),
+                            blocks: None,
+                            thread_ts,
+                        };
+                        if let Err(err) = slack.enqueue(msg).await {
+                            warn!(%err, "failed to post escalation notification");
+                        }
                     }
                 }
                 StallEvent::SelfRecovered { ref session_id } => {
                     info!(session_id, "agent self-recovered from stall");
+                    record_stall_event(&db, &event, None).await;
+                    let text = format!(
+                        "\u{2705} Agent in session `{session_id}` has self-recovered from stall"
+                    );
+                    if let Some(anchor) = open_alerts.remove(session_id) {
+                        anchor.resolve(&slack, &text).await;
+                    } else {
+                        let msg = SlackMessage {
+                            channel: channel_id,
+                            text: Some(text),
+                            blocks: None,
+                            thread_ts,
+                        };
+                        if let Err(err) = slack.enqueue(msg).await {
+                            warn!(%err, "failed to post self-recovery notification");
+                        }
+                    }
+                }
+                StallEvent::Completed {
+                    ref session_id,
+                    idle_seconds,
+                } => {
+                    info!(
+                        session_id,
+                        idle_seconds, "posting completion prompt to slack"
+                    );
                     let msg = SlackMessage {
                         channel: channel_id,
                         text: Some(format!(
-                            "\u{2705} Agent in session `{session_id}` has self-recovered from stall"
+                            "Agent appears finished \u{2014} session {session_id} idle for \
+                             {idle_seconds}s with all work done"
                         )),
-                        blocks: None,
+                        blocks: Some(blocks::completion_prompt_blocks(session_id, idle_seconds)),
                         thread_ts,
                     };
                     if let Err(err) = slack.enqueue(msg).await {
-                        warn!(%err, "failed to post self-recovery notification");
+                        warn!(%err, "failed to post completion prompt");
                     }
                 }
             }
@@ -170,6 +229,95 @@ pub fn spawn_stall_event_consumer(
     })
 }
 
+/// Mirror a stall event in the session's `connectivity_status`:
+/// [`StallEvent::Stalled`] marks it stalled and [`StallEvent::SelfRecovered`]
+/// online. Other events leave it as it is. Best-effort, like
+/// [`record_stall_event`].
+pub async fn track_connectivity(db: &Arc<Database>, event: &StallEvent) {
+    let (session_id, status) = match event {
+        StallEvent::Stalled { session_id, .. } => (session_id, ConnectivityStatus::Stalled),
+        StallEvent::SelfRecovered { session_id } => (session_id, ConnectivityStatus::Online),
+        _ => return,
+    };
+    if let Err(err) = SessionRepo::new(Arc::clone(db))
+        .set_connectivity_status(session_id, status)
+        .await
+    {
+        warn!(%err, session_id, "failed to update connectivity status");
+    }
+}
+
+/// Record a stall event in the session's `stall_alert` history.
+///
+/// [`StallEvent::Stalled`] opens a new alert, noting the Slack message it
+/// was posted as; the other events update the session's latest open alert,
+/// and [`StallEvent::SelfRecovered`] resolves it as a false positive or as
+/// recovered after nudging depending on whether any nudge was sent.
+/// [`StallEvent::Completed`] is not a stall and is not recorded.
+/// Best-effort: failures are logged and never hold up Slack delivery.
+pub async fn record_stall_event(db: &Arc<Database>, event: &StallEvent, slack_ts: Option<String>) {
+    let repo = StallAlertRepo::new(Arc::clone(db));
+    let result = match event {
+        StallEvent::Completed { .. } => return,
+        StallEvent::Stalled {
+            session_id,
+            idle_seconds,
+        } => {
+            let session = SessionRepo::new(Arc::clone(db))
+                .get_by_id(session_id)
+                .await
+                .ok()
+                .flatten();
+            let idle_seconds = i64::try_from(*idle_seconds).unwrap_or(i64::MAX);
+            let mut alert = StallAlert::new(
+                session_id.clone(),
+                session.as_ref().and_then(|s| s.last_tool.clone()),
+                chrono::Utc::now() - chrono::Duration::seconds(idle_seconds),
+                idle_seconds,
+                session.and_then(|s| s.progress_snapshot),
+            );
+            alert.slack_ts = slack_ts;
+            repo.create(&alert).await.map(|_| ())
+        }
+        StallEvent::AutoNudge { session_id, .. }
+        | StallEvent::Escalated { session_id, .. }
+        | StallEvent::SelfRecovered { session_id } => {
+            let alert = match repo.latest_open_for_session(session_id).await {
+                Ok(Some(alert)) => alert,
+                Ok(None) => return,
+                Err(err) => {
+                    warn!(%err, session_id, "failed to load open stall alert");
+                    return;
+                }
+            };
+            match event {
+                StallEvent::AutoNudge { .. } => repo.increment_nudge_count(&alert.id).await,
+                StallEvent::Escalated { .. } => {
+                    repo.update_status(&alert.id, StallAlertStatus::Escalated)
+                        .await
+                }
+                _ => {
+                    let resolution = if alert.nudge_count == 0 {
+                        StallResolution::SelfRecovered
+                    } else {
+                        StallResolution::Nudged
+                    };
+                    match repo
+                        .update_status(&alert.id, StallAlertStatus::SelfRecovered)
+                        .await
+                    {
+                        Ok(()) => repo.resolve(&alert.id, resolution).await,
+                        Err(err) => Err(err),
+                    }
+                }
+            }
+        }
+    };
+    if let Err(err) = result {
+        warn!(%err, "failed to record stall event");
+    }
+}
+
 /// Resolve the Slack channel and thread timestamp for a session.
 ///
 /// Returns the session's `channel_id` (falling back to `default_channel`) and
@@ -7,6 +7,18 @@
 //!
 //! Events are delivered via a `tokio::sync::mpsc` channel so the
 //! orchestrator can react (post Slack alerts, issue nudges, escalate).
+//!
+//! In adaptive mode ([`StallDetector::with_adaptive`]) the detector learns
+//! the session's typical interval between activity and fires after a
+//! multiple of it, so an agent that routinely goes quiet through long
+//! compile phases is not flagged as stalled by a global threshold tuned for
+//! chattier agents.
+//!
+//! With a completion grace ([`StallDetector::with_completion_grace`]), a
+//! session whose latest progress snapshot reports every item done (see
+//! [`StallDetectorHandle::set_work_complete`]) is expected to go quiet: after
+//! the grace period the detector emits [`StallEvent::Completed`] instead of a
+//! stall alert and does not nudge.
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -14,6 +26,7 @@ use std::time::Duration;
 
 use tokio::sync::{mpsc, Notify};
 use tokio::task::JoinHandle;
+use tokio::time::Instant;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, info_span, warn, Instrument};
 
@@ -46,6 +59,71 @@ pub enum StallEvent {
         /// Session whose agent self-recovered.
         session_id: String,
     },
+    /// Agent reported all work done and has been idle past the completion
+    /// grace period.
+    Completed {
+        /// Session whose agent appears finished.
+        session_id: String,
+        /// Seconds idle when the event was generated.
+        idle_seconds: u64,
+    },
+}
+
+/// Weight of the newest interval in the adaptive moving average.
+const ADAPTIVE_SMOOTHING: f64 = 0.3;
+
+/// Intervals observed before the learned threshold replaces the fixed one.
+const ADAPTIVE_WARMUP_SAMPLES: u32 = 5;
+
+/// A session's learned inactivity threshold.
+///
+/// Tracks the interval between consecutive activity as an exponentially
+/// weighted moving average; the threshold is `multiplier` times that
+/// average, bounded to `[min, max]`. Until enough intervals have been seen
+/// the fixed threshold applies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdaptiveThreshold {
+    multiplier: u32,
+    min: Duration,
+    max: Duration,
+    average_secs: f64,
+    samples: u32,
+}
+
+impl AdaptiveThreshold {
+    /// Create a threshold that has not observed any activity yet.
+    #[must_use]
+    pub fn new(multiplier: u32, min: Duration, max: Duration) -> Self {
+        Self {
+            multiplier,
+            min,
+            max,
+            average_secs: 0.0,
+            samples: 0,
+        }
+    }
+
+    /// Record the interval between two consecutive activities.
+    pub fn observe(&mut self, interval: Duration) {
+        let secs = interval.as_secs_f64();
+        self.average_secs = if self.samples == 0 {
+            secs
+        } else {
+            ADAPTIVE_SMOOTHING * secs + (1.0 - ADAPTIVE_SMOOTHING) * self.average_secs
+        };
+        self.samples = self.samples.saturating_add(1);
+    }
+
+    /// The threshold to wait for, or `fixed` while still warming up.
+    #[must_use]
+    pub fn threshold(&self, fixed: Duration) -> Duration {
+        if self.samples < ADAPTIVE_WARMUP_SAMPLES {
+            return fixed;
+        }
+        Duration::from_secs_f64(self.average_secs * f64::from(self.multiplier))
+            .max(self.min)
+            .min(self.max)
+    }
 }
 
 /// Builder for a per-session stall detector.
@@ -61,6 +139,11 @@ pub struct StallDetector {
     /// Time already elapsed since the last activity, used to seed the first
     /// wait interval on startup (T149, FR-045).
     initial_elapsed: Duration,
+    /// Learned threshold replacing `inactivity_threshold`, when adaptive.
+    adaptive: Option<AdaptiveThreshold>,
+    /// Idle time after which a session whose work is complete is reported
+    /// finished; `None` treats it like any other session.
+    completion_grace: Option<Duration>,
 }
 
 impl StallDetector {
@@ -82,6 +165,8 @@ impl StallDetector {
             event_tx,
             cancel,
             initial_elapsed: Duration::ZERO,
+            adaptive: None,
+            completion_grace: None,
         }
     }
 
@@ -97,12 +182,30 @@ impl StallDetector {
         self
     }
 
+    /// Alert on deviations from the session's learned activity interval
+    /// instead of the fixed inactivity threshold, which still applies until
+    /// `adaptive` has warmed up.
+    #[must_use]
+    pub fn with_adaptive(mut self, adaptive: AdaptiveThreshold) -> Self {
+        self.adaptive = Some(adaptive);
+        self
+    }
+
+    /// Report a session whose work is complete as finished after `grace` of
+    /// inactivity, instead of alerting once the inactivity threshold passes.
+    #[must_use]
+    pub fn with_completion_grace(mut self, grace: Duration) -> Self {
+        self.completion_grace = Some(grace);
+        self
+    }
+
     /// Spawn the background timer task and return a handle for controlling it.
     #[must_use]
     pub fn spawn(self) -> StallDetectorHandle {
         let reset_notify = Arc::new(Notify::new());
         let paused = Arc::new(AtomicBool::new(false));
         let stalled = Arc::new(AtomicBool::new(false));
+        let work_complete = Arc::new(AtomicBool::new(false));
 
         // Clone the cancellation token so the handle can cancel the task on drop.
         let cancel_for_handle = self.cancel.clone();
@@ -118,7 +221,10 @@ impl StallDetector {
                 Arc::clone(&reset_notify),
                 Arc::clone(&paused),
                 Arc::clone(&stalled),
+                Arc::clone(&work_complete),
                 self.initial_elapsed,
+                self.adaptive,
+                self.completion_grace,
             )
             .instrument(info_span!("stall_detector")),
         );
@@ -127,6 +233,7 @@ impl StallDetector {
             reset_notify,
             paused,
             stalled,
+            work_complete,
             session_id: self.session_id,
             join_handle: Some(task_handle),
             cancel: cancel_for_handle,
@@ -135,6 +242,7 @@ impl StallDetector {
 
     /// Core timer loop.
     #[allow(clippy::too_many_arguments)] // Internal plumbing; not part of public API width.
+    #[allow(clippy::too_many_lines)] // One state machine; splitting it scatters the transitions.
     async fn run(
         session_id: String,
         inactivity_threshold: Duration,
@@ -145,16 +253,26 @@ impl StallDetector {
         reset_notify: Arc<Notify>,
         paused: Arc<AtomicBool>,
         stalled: Arc<AtomicBool>,
+        work_complete: Arc<AtomicBool>,
         initial_elapsed: Duration,
+        mut adaptive: Option<AdaptiveThreshold>,
+        completion_grace: Option<Duration>,
     ) {
         let mut nudge_count: u32 = 0;
+        let mut last_activity = Instant::now();
 
         // T149 (FR-045): shorten the first wait interval by `initial_elapsed`
         // so the detector accounts for time that passed before the server restarted.
         // After the first iteration, subsequent waits always use the full threshold.
-        let mut next_threshold = inactivity_threshold.saturating_sub(initial_elapsed);
+        let mut threshold = inactivity_threshold;
+        let mut next_threshold = threshold.saturating_sub(initial_elapsed);
 
         loop {
+            // Time spent paused is not agent activity; skip learning from it.
+            let paused_at_start = paused.load(Ordering::SeqCst);
+            let grace = completion_grace
+                .filter(|_| work_complete.load(Ordering::SeqCst))
+                .map(|grace| grace.min(next_threshold));
             // ── Wait for inactivity threshold or reset ───────
             let fired = tokio::select! {
                 () = cancel.cancelled() => {
@@ -162,7 +280,7 @@ impl StallDetector {
                     return;
                 }
                 () = Self::wait_unless_paused(
-                    next_threshold,
+                    grace.unwrap_or(next_threshold),
                     &paused,
                     &reset_notify,
                     &cancel,
@@ -170,10 +288,16 @@ impl StallDetector {
                 () = reset_notify.notified() => false,
             };
 
-            // All subsequent waits use the full threshold.
-            next_threshold = inactivity_threshold;
-
             if !fired {
+                threshold = Self::record_activity(
+                    &session_id,
+                    adaptive.as_mut(),
+                    &mut last_activity,
+                    !paused_at_start,
+                    inactivity_threshold,
+                );
+                next_threshold = threshold;
+
                 // Reset received before threshold — check self-recovery.
                 if stalled.swap(false, Ordering::SeqCst) {
                     info!(session_id, "agent self-recovered");
@@ -187,9 +311,35 @@ impl StallDetector {
                 continue;
             }
 
+            // ── Work complete: ask the operator, don't nudge ─
+            if let Some(grace) = grace {
+                let idle_secs = grace.as_secs();
+                info!(session_id, idle_secs, "agent appears finished");
+                let _ = event_tx
+                    .send(StallEvent::Completed {
+                        session_id: session_id.clone(),
+                        idle_seconds: idle_secs,
+                    })
+                    .await;
+                tokio::select! {
+                    () = cancel.cancelled() => return,
+                    () = reset_notify.notified() => {}
+                }
+                // Idling after finishing says nothing about the agent's pace.
+                threshold = Self::record_activity(
+                    &session_id,
+                    adaptive.as_mut(),
+                    &mut last_activity,
+                    false,
+                    inactivity_threshold,
+                );
+                next_threshold = threshold;
+                continue;
+            }
+
             // ── Stall detected ───────────────────────────────
             stalled.store(true, Ordering::SeqCst);
-            let idle_secs = inactivity_threshold.as_secs();
+            let idle_secs = threshold.as_secs();
             info!(session_id, idle_secs, "stall detected");
 
             let _ = event_tx
@@ -256,7 +406,46 @@ impl StallDetector {
                     })
                     .await;
             }
+
+            // The escalation loop only ends on renewed activity.
+            threshold = Self::record_activity(
+                &session_id,
+                adaptive.as_mut(),
+                &mut last_activity,
+                true,
+                inactivity_threshold,
+            );
+            next_threshold = threshold;
+        }
+    }
+
+    /// Note activity at the current instant and return the inactivity
+    /// threshold for the next wait.
+    ///
+    /// In adaptive mode the interval since the previous activity is learned
+    /// first, unless `learn` is false (the interval spanned a pause).
+    fn record_activity(
+        session_id: &str,
+        adaptive: Option<&mut AdaptiveThreshold>,
+        last_activity: &mut Instant,
+        learn: bool,
+        fixed: Duration,
+    ) -> Duration {
+        let interval = last_activity.elapsed();
+        *last_activity = Instant::now();
+        let Some(learned) = adaptive else {
+            return fixed;
+        };
+        if learn {
+            learned.observe(interval);
         }
+        let threshold = learned.threshold(fixed);
+        debug!(
+            session_id,
+            threshold_secs = threshold.as_secs(),
+            "adaptive stall threshold"
+        );
+        threshold
     }
 
     /// Sleep for a duration while respecting the pause flag.
@@ -292,6 +481,7 @@ pub struct StallDetectorHandle {
     reset_notify: Arc<Notify>,
     paused: Arc<AtomicBool>,
     stalled: Arc<AtomicBool>,
+    work_complete: Arc<AtomicBool>,
     session_id: String,
     /// Task handle for the background detector loop.
     join_handle: Option<JoinHandle<()>>,
@@ -329,6 +519,12 @@ impl StallDetectorHandle {
         self.stalled.load(Ordering::SeqCst)
     }
 
+    /// Note whether the session's latest progress snapshot reports all of
+    /// its work done. Takes effect from the next [`reset`](Self::reset).
+    pub fn set_work_complete(&self, complete: bool) {
+        self.work_complete.store(complete, Ordering::SeqCst);
+    }
+
     /// The session ID this handle controls.
     #[must_use]
     pub fn session_id(&self) -> &str {
@@ -0,0 +1,72 @@
+//! Stall outcome statistics.
+//!
+//! Summarises recorded [`StallAlert`]s by how they ended so operators can
+//! judge whether the stall thresholds fit their agents: a high share of
+//! self-recovered alerts means the detector fires too early, while many
+//! stopped sessions mean it may be firing too late.
+
+use crate::models::stall::{StallAlert, StallAlertStatus, StallResolution};
+
+/// Outcome counts over a set of stall alerts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StallStats {
+    /// Alerts considered.
+    pub total: usize,
+    /// Alerts not yet resolved.
+    pub open: usize,
+    /// Open alerts that were escalated to the operator.
+    pub escalated_open: usize,
+    /// Alerts the agent recovered from without any nudge (false positives).
+    pub self_recovered: usize,
+    /// Alerts the agent recovered from after one or more nudges.
+    pub nudged: usize,
+    /// Alerts that ended with the operator stopping the session.
+    pub stopped: usize,
+    /// Median seconds from alert to resolution, over resolved alerts.
+    pub median_resolution_seconds: Option<i64>,
+}
+
+impl StallStats {
+    /// Compute statistics over `alerts`.
+    #[must_use]
+    pub fn from_alerts(alerts: &[StallAlert]) -> Self {
+        let mut stats = Self {
+            total: alerts.len(),
+            ..Self::default()
+        };
+        let mut durations = Vec::new();
+        for alert in alerts {
+            match alert.resolution {
+                Some(StallResolution::SelfRecovered) => stats.self_recovered += 1,
+                Some(StallResolution::Nudged) => stats.nudged += 1,
+                Some(StallResolution::Stopped) => stats.stopped += 1,
+                None => {
+                    stats.open += 1;
+                    if alert.status == StallAlertStatus::Escalated {
+                        stats.escalated_open += 1;
+                    }
+                }
+            }
+            if let Some(resolved_at) = alert.resolved_at {
+                durations.push((resolved_at - alert.created_at).num_seconds().max(0));
+            }
+        }
+        durations.sort_unstable();
+        stats.median_resolution_seconds = durations.get(durations.len() / 2).copied();
+        stats
+    }
+
+    /// Alerts that have been resolved one way or another.
+    #[must_use]
+    pub fn resolved(&self) -> usize {
+        self.self_recovered + self.nudged + self.stopped
+    }
+
+    /// Share of resolved alerts that were false positives, as a whole
+    /// percentage; `None` until any alert has been resolved.
+    #[must_use]
+    pub fn false_positive_percent(&self) -> Option<usize> {
+        let resolved = self.resolved();
+        (resolved > 0).then(|| (self.self_recovered * 100 + resolved / 2) / resolved)
+    }
+}
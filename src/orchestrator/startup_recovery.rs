@@ -0,0 +1,132 @@
+//! Recovering interrupted sessions on startup (`[recovery] on_startup`).
+//!
+//! The previous run's MCP sessions, interrupted at most `max_age_minutes`
+//! before startup, are brought back according to the configured level:
+//! [`StartupRecovery::Reactivate`] makes them active again, offline until
+//! their agents reconnect, and [`StartupRecovery::Respawn`] first starts a
+//! fresh agent for each session the server spawned, as the child monitor
+//! does after a crash. A reactivated session nobody reclaims within
+//! `reconnect_grace_seconds` is marked interrupted again. Re-posting
+//! pending requests is left to [`crate::orchestrator::rearm`].
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tracing::{info, warn};
+
+use crate::config::StartupRecovery;
+use crate::mcp::handler::LOCAL_AGENT_OWNER;
+use crate::models::session::{ConnectivityStatus, ProtocolMode, Session, SessionStatus};
+use crate::orchestrator::spawner;
+use crate::persistence::session_repo::SessionRepo;
+use crate::state::AppState;
+
+/// What [`recover`] did with the interrupted sessions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Recovered {
+    /// Sessions made active again to wait for their agents.
+    pub reactivated: usize,
+    /// Sessions replaced by a freshly spawned agent.
+    pub respawned: usize,
+}
+
+/// Bring back the `interrupted` sessions per `[recovery] on_startup`.
+pub async fn recover(state: &Arc<AppState>, interrupted: &[Session]) -> Recovered {
+    let recovery = &state.config.recovery;
+    let mut recovered = Recovered::default();
+    if recovery.on_startup == StartupRecovery::Notify {
+        return recovered;
+    }
+
+    // An age too large to represent admits every interrupted session.
+    let cutoff = i64::try_from(recovery.max_age_minutes)
+        .ok()
+        .and_then(chrono::TimeDelta::try_minutes)
+        .and_then(|max_age| Utc::now().checked_sub_signed(max_age))
+        .unwrap_or(DateTime::<Utc>::MIN_UTC);
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let mut reactivated = Vec::new();
+
+    for session in interrupted
+        .iter()
+        .filter(|s| s.protocol_mode == ProtocolMode::Mcp && s.updated_at >= cutoff)
+    {
+        if recovery.on_startup == StartupRecovery::Respawn
+            && session.owner_user_id != LOCAL_AGENT_OWNER
+        {
+            if respawn(state, &repo, session).await {
+                recovered.respawned += 1;
+            }
+            continue;
+        }
+        match repo.reactivate(&session.id).await {
+            Ok(true) => {
+                info!(session_id = %session.id, "interrupted session reactivated");
+                reactivated.push(session.id.clone());
+            }
+            Ok(false) => {}
+            Err(err) => warn!(%err, session_id = %session.id, "failed to reactivate session"),
+        }
+    }
+
+    recovered.reactivated = reactivated.len();
+    if !reactivated.is_empty() {
+        let grace = Duration::from_secs(state.config.timeouts.reconnect_grace_seconds);
+        tokio::spawn(interrupt_unclaimed(repo, reactivated, Utc::now(), grace));
+    }
+    recovered
+}
+
+/// Replace `session` with a freshly spawned agent; `false` when it fails.
+async fn respawn(state: &AppState, repo: &SessionRepo, session: &Session) -> bool {
+    match spawner::respawn_session(
+        session,
+        &state.config,
+        repo,
+        &state.db,
+        state.config.http_port,
+    )
+    .await
+    {
+        Ok((resumed, child)) => {
+            info!(session_id = %session.id, resumed_id = %resumed.id, "interrupted session respawned");
+            state
+                .active_children
+                .lock()
+                .await
+                .insert(resumed.id.clone(), child);
+            true
+        }
+        Err(err) => {
+            warn!(%err, session_id = %session.id, "failed to respawn interrupted session");
+            false
+        }
+    }
+}
+
+/// After `grace`, mark interrupted again each of `ids` no agent has
+/// reconnected to since `reactivated_at`.
+async fn interrupt_unclaimed(
+    repo: SessionRepo,
+    ids: Vec<String>,
+    reactivated_at: DateTime<Utc>,
+    grace: Duration,
+) {
+    tokio::time::sleep(grace).await;
+    for id in ids {
+        let Ok(Some(session)) = repo.get_by_id(&id).await else {
+            continue;
+        };
+        let unclaimed = session.status == SessionStatus::Active
+            && session.connectivity_status == ConnectivityStatus::Offline
+            && session.updated_at <= reactivated_at;
+        if !unclaimed {
+            continue;
+        }
+        match repo.set_terminated(&id, SessionStatus::Interrupted).await {
+            Ok(_) => info!(session_id = %id, "reactivated session not reclaimed"),
+            Err(err) => warn!(%err, session_id = %id, "failed to interrupt unclaimed session"),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Expiry sweep for time-limited steering messages.
+//!
+//! Messages queued with a TTL stop being delivered once it elapses (the
+//! repository filters them out of `fetch_unconsumed`). This task deletes
+//! them on a fixed interval and reports every drop: an audit entry, plus a
+//! notice in the session's Slack thread so the operator knows the message
+//! never reached the agent.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::models::steering::SteeringMessage;
+use crate::persistence::session_repo::SessionRepo;
+use crate::persistence::steering_repo::SteeringRepo;
+use crate::slack::blocks;
+use crate::slack::client::SlackMessage;
+use crate::state::AppState;
+use crate::Result;
+
+/// How often expired steering messages are swept.
+pub const SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Longest message excerpt quoted in the Slack expiry notice.
+const EXCERPT_CHARS: usize = 120;
+
+/// Spawn the periodic expiry sweep.
+#[must_use]
+pub fn spawn_steering_expiry_task(
+    state: Arc<AppState>,
+    cancel: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => {
+                    info!("steering expiry task shutting down");
+                    break;
+                }
+                _ = interval.tick() => {
+                    if let Err(err) = expire_steering(&state).await {
+                        warn!(%err, "steering expiry sweep failed");
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Drop every expired, undelivered steering message and report it.
+///
+/// Returns the dropped messages.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the expired messages cannot be removed.
+/// Reporting failures are logged, not returned.
+pub async fn expire_steering(state: &AppState) -> Result<Vec<SteeringMessage>> {
+    let dropped = SteeringRepo::new(Arc::clone(&state.db))
+        .drop_expired(Utc::now())
+        .await?;
+    for msg in &dropped {
+        info!(
+            session_id = %msg.session_id,
+            message_id = %msg.id,
+            "steering message expired undelivered"
+        );
+        if let Some(ref logger) = state.audit_logger {
+            let entry = AuditEntry::new(AuditEventType::SteeringExpired)
+                .with_session(msg.session_id.clone())
+                .with_request_id(msg.id.clone());
+            if let Err(err) = logger.log_entry(entry) {
+                warn!(%err, "audit log write failed (steering expired)");
+            }
+        }
+        report_to_slack(state, msg).await;
+    }
+    Ok(dropped)
+}
+
+/// Post an expiry notice to the session's thread, or the message's
+/// originating channel when the session has no thread.
+async fn report_to_slack(state: &AppState, msg: &SteeringMessage) {
+    let Some(ref slack) = state.slack else {
+        return;
+    };
+    let session = SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(&msg.session_id)
+        .await
+        .ok()
+        .flatten();
+    let channel = msg
+        .channel_id
+        .clone()
+        .or_else(|| session.as_ref().and_then(|s| s.channel_id.clone()));
+    let Some(channel) = channel else {
+        return;
+    };
+    let thread_ts = session.and_then(|s| s.thread_ts).map(SlackTs);
+
+    let excerpt: String = msg.message.chars().take(EXCERPT_CHARS).collect();
+    let text = format!("Steering message expired before the agent received it: _{excerpt}_");
+    let notice = SlackMessage {
+        channel: SlackChannelId(channel),
+        text: Some(format!("\u{231b} {text}")),
+        blocks: Some(vec![blocks::severity_section("warning", &text)]),
+        thread_ts,
+    };
+    if let Err(err) = slack.enqueue(notice).await {
+        warn!(%err, message_id = %msg.id, "failed to report expired steering message");
+    }
+}
@@ -0,0 +1,89 @@
+//! Completion reports from child sessions started with `spawn_subtask`.
+//!
+//! When a child session ends, its parent is told through a steering
+//! message with [`SteeringSource::Subtask`], delivered on the parent's
+//! next `ping` like any queued steering. Parents that have already ended
+//! are not reported to.
+
+use std::sync::Arc;
+
+use tracing::info;
+
+use crate::models::progress::ProgressStatus;
+use crate::models::session::{Session, SessionStatus};
+use crate::models::steering::{SteeringMessage, SteeringSource};
+use crate::persistence::db::Database;
+use crate::persistence::session_repo::SessionRepo;
+use crate::persistence::steering_repo::SteeringRepo;
+use crate::Result;
+
+/// Queue a completion report for `child`'s parent session.
+///
+/// `outcome` says how the child ended, e.g. "agent process exited
+/// normally". Returns the queued message, or `None` when `child` is not a
+/// subtask or its parent is gone or finished.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the parent cannot be loaded or the message
+/// cannot be stored.
+pub async fn report_to_parent(
+    db: &Arc<Database>,
+    child: &Session,
+    outcome: &str,
+) -> Result<Option<SteeringMessage>> {
+    let Some(ref parent_id) = child.parent_session_id else {
+        return Ok(None);
+    };
+    let Some(parent) = SessionRepo::new(Arc::clone(db))
+        .get_by_id(parent_id)
+        .await?
+    else {
+        return Ok(None);
+    };
+    if matches!(
+        parent.status,
+        SessionStatus::Terminated | SessionStatus::Interrupted
+    ) {
+        return Ok(None);
+    }
+
+    let queued = SteeringRepo::new(Arc::clone(db))
+        .insert(&SteeringMessage::new(
+            parent.id.clone(),
+            parent.channel_id.clone(),
+            report_text(child, outcome),
+            SteeringSource::Subtask,
+        ))
+        .await?;
+    info!(parent = %parent.id, child = %child.id, "subtask completion reported to parent");
+    Ok(Some(queued))
+}
+
+/// Render the report: outcome, then the child's last progress snapshot.
+#[must_use]
+pub fn report_text(child: &Session, outcome: &str) -> String {
+    let headline = format!("Subtask `{}` ended: {outcome}.", child.id);
+    let Some(items) = child
+        .progress_snapshot
+        .as_ref()
+        .filter(|items| !items.is_empty())
+    else {
+        return headline;
+    };
+    let open: Vec<&str> = items
+        .iter()
+        .filter(|item| item.status != ProgressStatus::Done)
+        .map(|item| item.label.as_str())
+        .collect();
+    let done = items.len() - open.len();
+    if open.is_empty() {
+        format!("{headline} Progress: {done}/{done} done.")
+    } else {
+        format!(
+            "{headline} Progress: {done}/{} done (open: {}).",
+            items.len(),
+            open.join("; ")
+        )
+    }
+}
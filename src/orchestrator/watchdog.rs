@@ -0,0 +1,470 @@
+//! Self-monitoring watchdog (`[watchdog]`).
+//!
+//! A periodic task exercises the critical path — a database write and
+//! read, Slack `auth.test`, and optionally an IPC round trip — and posts a
+//! degraded-mode notice naming the failing components, then a recovery
+//! notice once every check passes again.
+//!
+//! Long-running background tasks (the Slack Socket Mode listener, the
+//! Slack outgoing queue worker, the retention task) run under
+//! [`supervise`], which restarts them with exponential backoff when they
+//! stop before shutdown. Their state is kept in [`Subsystems`], exposed on
+//! `/health/ready` and `/metrics`, and restarts are reported by the
+//! watchdog task.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::AbortOnDropHandle;
+use tracing::{error, info, warn};
+
+use crate::config::WatchdogConfig;
+use crate::ipc::client::IpcClient;
+use crate::persistence::db;
+use crate::slack::client::post_server_notice;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Upper bound on a single check, so one hung dependency cannot stall the
+/// others.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A checked dependency or a supervised subsystem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Component {
+    /// `SQLite` write and read round trip.
+    Database,
+    /// Slack Web API, checked with `auth.test`.
+    SlackApi,
+    /// Local IPC endpoint, checked with a `list` round trip.
+    Ipc,
+    /// Slack Socket Mode listener task.
+    SocketMode,
+    /// Slack outgoing queue worker task.
+    SlackQueue,
+    /// Data retention task.
+    Retention,
+}
+
+impl Component {
+    /// Name used in logs.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Database => "database",
+            Self::SlackApi => "slack_api",
+            Self::Ipc => "ipc",
+            Self::SocketMode => "socket_mode",
+            Self::SlackQueue => "slack_queue",
+            Self::Retention => "retention",
+        }
+    }
+
+    /// Name shown to the operator.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Database => "Database",
+            Self::SlackApi => "Slack API",
+            Self::Ipc => "IPC endpoint",
+            Self::SocketMode => "Slack Socket Mode listener",
+            Self::SlackQueue => "Slack outgoing queue worker",
+            Self::Retention => "Retention task",
+        }
+    }
+}
+
+/// A supervised subsystem that stopped and was started again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Restart {
+    /// Subsystem that stopped.
+    pub component: Component,
+    /// Why it stopped: a panic message or a plain exit.
+    pub reason: String,
+    /// Restarts of this subsystem so far, including this one.
+    pub count: u32,
+}
+
+/// Current state of one supervised subsystem.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SubsystemStatus {
+    /// Subsystem reported on.
+    pub component: Component,
+    /// Whether its task is running; `false` while it waits to restart.
+    pub running: bool,
+    /// Restarts since startup.
+    pub restarts: u32,
+    /// Why it last stopped, if it ever did.
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct SubsystemLog {
+    statuses: BTreeMap<Component, SubsystemStatus>,
+    unreported: Vec<Restart>,
+}
+
+impl SubsystemLog {
+    fn status(&mut self, component: Component) -> &mut SubsystemStatus {
+        self.statuses
+            .entry(component)
+            .or_insert_with(|| SubsystemStatus {
+                component,
+                running: false,
+                restarts: 0,
+                last_error: None,
+            })
+    }
+}
+
+/// Supervised subsystems and their restarts, kept by [`supervise`] and
+/// read by the watchdog task and the health endpoints.
+#[derive(Debug, Default)]
+pub struct Subsystems {
+    log: Mutex<SubsystemLog>,
+}
+
+impl Subsystems {
+    /// Mark `component` as running.
+    pub fn mark_running(&self, component: Component) {
+        self.lock().status(component).running = true;
+    }
+
+    /// Record that `component` stopped for `reason` and will be restarted,
+    /// and return the restart.
+    pub fn record(&self, component: Component, reason: impl Into<String>) -> Restart {
+        let reason = reason.into();
+        let mut log = self.lock();
+        let status = log.status(component);
+        status.running = false;
+        status.restarts = status.restarts.saturating_add(1);
+        status.last_error = Some(reason.clone());
+        let restart = Restart {
+            component,
+            reason,
+            count: status.restarts,
+        };
+        log.unreported.push(restart.clone());
+        restart
+    }
+
+    /// Restarts of `component` since startup.
+    #[must_use]
+    pub fn count(&self, component: Component) -> u32 {
+        self.lock()
+            .statuses
+            .get(&component)
+            .map_or(0, |status| status.restarts)
+    }
+
+    /// Every supervised subsystem, in [`Component`] order.
+    #[must_use]
+    pub fn snapshot(&self) -> Vec<SubsystemStatus> {
+        self.lock().statuses.values().cloned().collect()
+    }
+
+    /// Take the restarts not yet reported, oldest first.
+    #[must_use]
+    pub fn take_unreported(&self) -> Vec<Restart> {
+        std::mem::take(&mut self.lock().unreported)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SubsystemLog> {
+        self.log.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Backoff between restarts of a supervised subsystem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestartPolicy {
+    /// Delay before the first restart; doubled for each consecutive one.
+    pub initial_delay: Duration,
+    /// Cap on the delay. A subsystem that ran at least this long before
+    /// stopping starts again from `initial_delay`.
+    pub max_delay: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_mins(5),
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Delay before restart number `consecutive` (zero-based) in a row.
+    #[must_use]
+    pub fn delay(&self, consecutive: u32) -> Duration {
+        self.initial_delay
+            .checked_mul(2_u32.saturating_pow(consecutive))
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+}
+
+/// Run the task made by `spawn`, starting it again whenever it stops
+/// before `cancel` fires. Its state is kept in `subsystems`.
+///
+/// After cancellation the supervisor waits for the current task to finish
+/// on its own. Aborting the returned handle aborts the current task too.
+pub fn supervise<F>(
+    component: Component,
+    subsystems: Arc<Subsystems>,
+    policy: RestartPolicy,
+    cancel: CancellationToken,
+    mut spawn: F,
+) -> JoinHandle<()>
+where
+    F: FnMut() -> JoinHandle<()> + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut consecutive = 0_u32;
+        loop {
+            let started = Instant::now();
+            let mut task = AbortOnDropHandle::new(spawn());
+            subsystems.mark_running(component);
+            let outcome = tokio::select! {
+                () = cancel.cancelled() => {
+                    let _ = (&mut task).await;
+                    return;
+                }
+                outcome = &mut task => outcome,
+            };
+            if cancel.is_cancelled() {
+                return;
+            }
+            let reason = match outcome {
+                Ok(()) => "exited".to_owned(),
+                Err(err) if err.is_panic() => format!("panicked: {}", panic_message(err)),
+                Err(err) => format!("stopped: {err}"),
+            };
+            if started.elapsed() >= policy.max_delay {
+                consecutive = 0;
+            }
+            let delay = policy.delay(consecutive);
+            consecutive = consecutive.saturating_add(1);
+            let restart = subsystems.record(component, &reason);
+            error!(
+                component = component.as_str(),
+                reason,
+                restart = restart.count,
+                delay_ms = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX),
+                "supervised subsystem stopped; restarting"
+            );
+            tokio::select! {
+                () = cancel.cancelled() => return,
+                () = tokio::time::sleep(delay) => {}
+            }
+        }
+    })
+}
+
+/// Text of a task panic, when it carried one.
+fn panic_message(err: tokio::task::JoinError) -> String {
+    let payload = err.into_panic();
+    payload
+        .downcast_ref::<&str>()
+        .map(|msg| (*msg).to_owned())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_owned())
+}
+
+/// A change in overall health between two check rounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthChange {
+    /// At least one check fails, and the failing set differs from the
+    /// last round.
+    Degraded {
+        /// Failing components with their errors.
+        failing: Vec<(Component, String)>,
+    },
+    /// Every check passes again after a degraded round.
+    Recovered,
+}
+
+/// Tracks the failing components between check rounds.
+#[derive(Debug, Default)]
+pub struct HealthTracker {
+    failing: BTreeMap<Component, String>,
+}
+
+impl HealthTracker {
+    /// Record a round of check `results`. Returns the change to report, if
+    /// the set of failing components changed.
+    pub fn observe(&mut self, results: &[(Component, Result<()>)]) -> Option<HealthChange> {
+        let failing: BTreeMap<Component, String> = results
+            .iter()
+            .filter_map(|(component, result)| {
+                result
+                    .as_ref()
+                    .err()
+                    .map(|err| (*component, err.to_string()))
+            })
+            .collect();
+        let was_degraded = !self.failing.is_empty();
+        let same_set = failing.keys().eq(self.failing.keys());
+        self.failing = failing;
+        if self.failing.is_empty() {
+            return was_degraded.then_some(HealthChange::Recovered);
+        }
+        (!same_set).then(|| HealthChange::Degraded {
+            failing: self
+                .failing
+                .iter()
+                .map(|(component, err)| (*component, err.clone()))
+                .collect(),
+        })
+    }
+
+    /// Whether the last round had a failing check.
+    #[must_use]
+    pub fn is_degraded(&self) -> bool {
+        !self.failing.is_empty()
+    }
+}
+
+/// Slack text for `change`. `mention` is prepended to degraded notices.
+#[must_use]
+pub fn describe(change: &HealthChange, mention: Option<&str>) -> String {
+    match change {
+        HealthChange::Degraded { failing } => {
+            let mention = mention.map(|m| format!("{m} ")).unwrap_or_default();
+            let mut text = format!("\u{1f6a8} {mention}*Degraded mode:* health checks failing:");
+            for (component, err) in failing {
+                let _ = write!(text, "\n\u{2022} *{}*: {err}", component.label());
+            }
+            text
+        }
+        HealthChange::Recovered => {
+            "\u{2705} *Recovered:* every health check passes again.".to_owned()
+        }
+    }
+}
+
+/// Slack text for a subsystem restart.
+#[must_use]
+pub fn describe_restart(restart: &Restart) -> String {
+    format!(
+        "\u{1f501} *Watchdog:* {} {} and was restarted (restart {}).",
+        restart.component.label(),
+        restart.reason,
+        restart.count
+    )
+}
+
+/// Run every enabled check once.
+pub async fn run_checks(state: &AppState, config: &WatchdogConfig) -> Vec<(Component, Result<()>)> {
+    let mut results = vec![(
+        Component::Database,
+        bounded(Component::Database, db::probe(&state.db)).await,
+    )];
+    if let Some(ref slack) = state.slack {
+        results.push((
+            Component::SlackApi,
+            bounded(Component::SlackApi, slack.auth_test()).await,
+        ));
+    }
+    if config.ipc {
+        results.push((
+            Component::Ipc,
+            bounded(Component::Ipc, ipc_probe(state)).await,
+        ));
+    }
+    results
+}
+
+/// `list` round trip against this server's IPC endpoint.
+async fn ipc_probe(state: &AppState) -> Result<()> {
+    let mut client = IpcClient::connect(&state.config.ipc_name).await?;
+    if let Some(ref token) = state.ipc_auth_token {
+        client = client.with_auth_token(token.clone());
+    }
+    client.list().await.map(|_| ())
+}
+
+/// Fail `check` when it runs past [`CHECK_TIMEOUT`].
+async fn bounded(component: Component, check: impl Future<Output = Result<()>>) -> Result<()> {
+    tokio::time::timeout(CHECK_TIMEOUT, check)
+        .await
+        .unwrap_or_else(|_| {
+            let msg = format!("check timed out after {}s", CHECK_TIMEOUT.as_secs());
+            Err(match component {
+                Component::SlackApi | Component::SocketMode | Component::SlackQueue => {
+                    AppError::Slack(msg)
+                }
+                Component::Ipc => AppError::Ipc(msg),
+                Component::Database | Component::Retention => AppError::Db(msg),
+            })
+        })
+}
+
+/// Spawn the periodic watchdog. Returns `None` when `[watchdog]` is
+/// disabled; supervised subsystems are still restarted, only without
+/// Slack notices.
+#[must_use]
+pub fn spawn_watchdog_task(
+    state: Arc<AppState>,
+    cancel: CancellationToken,
+) -> Option<JoinHandle<()>> {
+    let config = state.config.watchdog.clone();
+    if !config.enabled {
+        return None;
+    }
+    Some(tokio::spawn(async move {
+        let mut health = HealthTracker::default();
+        let mut interval = tokio::time::interval(Duration::from_secs(config.interval_seconds));
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => {
+                    info!("watchdog task shutting down");
+                    break;
+                }
+                _ = interval.tick() => {
+                    check_once(&state, &config, &mut health).await;
+                }
+            }
+        }
+    }))
+}
+
+/// Run one check round and report health changes and restarts.
+async fn check_once(state: &AppState, config: &WatchdogConfig, health: &mut HealthTracker) {
+    let results = run_checks(state, config).await;
+    for (component, result) in &results {
+        if let Err(err) = result {
+            warn!(%err, component = component.as_str(), "watchdog check failed");
+        }
+    }
+    let mention = config
+        .escalate
+        .then_some(state.config.broadcast.escalation_mention.trim())
+        .filter(|m| !m.is_empty());
+    if let Some(change) = health.observe(&results) {
+        match change {
+            HealthChange::Degraded { .. } => warn!("watchdog: entering degraded mode"),
+            HealthChange::Recovered => info!("watchdog: all checks pass again"),
+        }
+        post_server_notice(
+            state,
+            config.channel_id.as_deref(),
+            &describe(&change, mention),
+        )
+        .await;
+    }
+    for restart in state.subsystems.take_unreported() {
+        post_server_notice(
+            state,
+            config.channel_id.as_deref(),
+            &describe_restart(&restart),
+        )
+        .await;
+    }
+}
@@ -0,0 +1,98 @@
+//! Working-hours routing between a workspace's channels.
+//!
+//! A `[[workspace]]` with `[workspace.working_hours]` has two channels: its
+//! own within working hours and `after_hours_channel_id` outside them, for
+//! example a team channel and an on-call channel. The choice is made when a
+//! message is about to be posted: before each tool call, [`route_session`]
+//! works out which of the two the calling session belongs in now and, when
+//! it is in the other, moves it there the way `session-move` does, so its
+//! messages continue in a new thread and replies there still reach the
+//! agent. A session an operator moved to an unrelated channel stays put.
+
+use std::sync::{Arc, PoisonError};
+
+use chrono::NaiveDateTime;
+use tracing::{info, warn};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::models::session::SessionStatus;
+use crate::orchestrator::session_move;
+use crate::persistence::session_repo::SessionRepo;
+use crate::state::AppState;
+use crate::Result;
+
+/// Move session `session_id` to the channel its workspace routes to at
+/// local time `now`, returning that channel when the session was moved.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the session cannot be read or updated, or
+/// `AppError::Slack` if the anchor in the new channel cannot be posted.
+pub async fn route_session(
+    state: &AppState,
+    session_id: &str,
+    now: NaiveDateTime,
+) -> Result<Option<String>> {
+    if !has_working_hours(state) {
+        return Ok(None);
+    }
+    let Some(session) = SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(session_id)
+        .await?
+    else {
+        return Ok(None);
+    };
+    if matches!(
+        session.status,
+        SessionStatus::Terminated | SessionStatus::Interrupted
+    ) {
+        return Ok(None);
+    }
+    let Some(current) = session.channel_id.as_deref() else {
+        return Ok(None);
+    };
+    let Some((target, after_hours)) = target_channel(state, current, now) else {
+        return Ok(None);
+    };
+
+    let cause = if after_hours {
+        "for after-hours routing"
+    } else {
+        "for working hours"
+    };
+    session_move::relocate(state, &session, &target, cause).await?;
+    info!(session_id, from = current, to = %target, "session routed by working hours");
+    if let Some(ref logger) = state.audit_logger {
+        let entry = AuditEntry::new(AuditEventType::SessionMove)
+            .with_session(session.id.clone())
+            .with_reason(format!("{cause}: <#{current}> to <#{target}>"));
+        if let Err(err) = logger.log_entry(entry) {
+            warn!(%err, "audit log write failed (working-hours move)");
+        }
+    }
+    Ok(Some(target))
+}
+
+/// Whether any workspace has working hours configured.
+fn has_working_hours(state: &AppState) -> bool {
+    state
+        .workspace_mappings
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .any(|mapping| mapping.working_hours.is_some())
+}
+
+/// The channel a session now in `current` should post to at `now`, and
+/// whether it is the after-hours one, when that is a different channel.
+fn target_channel(state: &AppState, current: &str, now: NaiveDateTime) -> Option<(String, bool)> {
+    let mappings = state
+        .workspace_mappings
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+    let mapping = mappings
+        .iter()
+        .find(|mapping| mapping.working_hours.is_some() && mapping.routes_to(current))?;
+    let target = mapping.channel_at(now);
+    (target != current).then(|| (target.to_owned(), target != mapping.channel_id))
+}
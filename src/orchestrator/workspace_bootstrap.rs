@@ -0,0 +1,239 @@
+//! Workspace bootstrap commands run before an ACP session's agent starts.
+//!
+//! A workspace's `[workspace.bootstrap] commands` — `npm ci`,
+//! `cargo fetch` — run in order in the workspace root once the session
+//! record exists but before the agent process is spawned. Their output is
+//! captured into the session's log buffer, so operators read it with
+//! `/intercom logs`. The first command that fails or runs out of time stops
+//! the bootstrap and the session start is aborted with that command named,
+//! instead of handing the agent a broken environment. Each command runs in
+//! its own process group, which is killed as a whole on timeout.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::config::WorkspaceBootstrap;
+use crate::orchestrator::session_logs::{LogLevel, LogLine, SessionLogs};
+use crate::{AppError, Result};
+
+/// Longest excerpt of a failed command's output reported.
+const DETAIL_LIMIT: usize = 200;
+
+/// Result of one bootstrap command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootstrapOutcome {
+    /// The shell command that ran.
+    pub command: String,
+    /// Whether it exited successfully within the timeout.
+    pub passed: bool,
+    /// Exit status, and the last line of output when it failed.
+    pub detail: String,
+    /// Everything it wrote to stdout.
+    pub stdout: String,
+    /// Everything it wrote to stderr.
+    pub stderr: String,
+}
+
+/// Run each bootstrap command in `workspace_root` with the operator's
+/// `env` added, in order, stopping at the first that fails.
+pub async fn run_commands(
+    bootstrap: &WorkspaceBootstrap,
+    workspace_root: &Path,
+    env: &BTreeMap<String, String>,
+) -> Vec<BootstrapOutcome> {
+    let timeout = Duration::from_secs(bootstrap.timeout_seconds);
+    let mut outcomes = Vec::new();
+    for command in &bootstrap.commands {
+        let outcome = run_one(command, workspace_root, env, timeout).await;
+        let passed = outcome.passed;
+        outcomes.push(outcome);
+        if !passed {
+            break;
+        }
+    }
+    outcomes
+}
+
+/// Bootstrap the workspace for `session_id`, capturing each command's
+/// output in `logs`.
+///
+/// # Errors
+///
+/// Returns `AppError::Acp` naming the command that failed.
+pub async fn run(
+    bootstrap: &WorkspaceBootstrap,
+    workspace_root: &Path,
+    env: &BTreeMap<String, String>,
+    session_id: &str,
+    logs: &SessionLogs,
+) -> Result<()> {
+    let outcomes = run_commands(bootstrap, workspace_root, env).await;
+    for outcome in &outcomes {
+        for line in log_lines(outcome) {
+            logs.push(session_id, line);
+        }
+    }
+    match outcomes.iter().find(|outcome| !outcome.passed) {
+        Some(failed) => Err(AppError::Acp(format!(
+            "workspace bootstrap command `{}` failed ({}); `/intercom logs {session_id}` \
+             shows its output",
+            failed.command, failed.detail
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Session log lines recording `outcome`: a header naming the command,
+/// its output (stderr as warnings), then the result.
+#[must_use]
+pub fn log_lines(outcome: &BootstrapOutcome) -> Vec<LogLine> {
+    let mut fields = serde_json::Map::new();
+    fields.insert(
+        "bootstrap".to_owned(),
+        serde_json::Value::String(outcome.command.clone()),
+    );
+    let line = |level, message: String| LogLine {
+        at: Utc::now(),
+        level,
+        message,
+        fields: fields.clone(),
+    };
+
+    let mut lines = vec![line(LogLevel::Info, format!("$ {}", outcome.command))];
+    lines.extend(
+        outcome
+            .stdout
+            .lines()
+            .map(|text| line(LogLevel::Info, text.to_owned())),
+    );
+    lines.extend(
+        outcome
+            .stderr
+            .lines()
+            .map(|text| line(LogLevel::Warn, text.to_owned())),
+    );
+    lines.push(if outcome.passed {
+        line(LogLevel::Info, "bootstrap command passed".to_owned())
+    } else {
+        line(
+            LogLevel::Error,
+            format!("bootstrap command failed: {}", outcome.detail),
+        )
+    });
+    lines
+}
+
+async fn run_one(
+    command: &str,
+    workspace_root: &Path,
+    env: &BTreeMap<String, String>,
+    timeout: Duration,
+) -> BootstrapOutcome {
+    let mut cmd = shell(command);
+    cmd.current_dir(workspace_root)
+        .envs(env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    // Its own process group, so a timeout reaches everything the shell
+    // started, not just the shell.
+    #[cfg(unix)]
+    cmd.process_group(0);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+        cmd.as_std_mut().creation_flags(CREATE_NEW_PROCESS_GROUP);
+    }
+
+    let started = cmd.spawn();
+    let pid = started.as_ref().ok().and_then(tokio::process::Child::id);
+    let finished = match started {
+        Ok(child) => tokio::time::timeout(timeout, child.wait_with_output()).await,
+        Err(err) => Ok(Err(err)),
+    };
+    let (passed, detail, stdout, stderr) = match finished {
+        Ok(Ok(output)) => {
+            let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+            let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+            if output.status.success() {
+                (true, "passed".to_owned(), stdout, stderr)
+            } else {
+                let code = output
+                    .status
+                    .code()
+                    .map_or_else(|| "killed".to_owned(), |code| format!("exit {code}"));
+                let detail = match last_line(&stderr).or_else(|| last_line(&stdout)) {
+                    Some(line) => format!("{code}: {line}"),
+                    None => code,
+                };
+                (false, detail, stdout, stderr)
+            }
+        }
+        Ok(Err(err)) => (
+            false,
+            format!("could not start: {err}"),
+            String::new(),
+            String::new(),
+        ),
+        Err(_) => {
+            if let Some(pid) = pid {
+                kill_group(pid).await;
+            }
+            (
+                false,
+                format!("timed out after {} seconds", timeout.as_secs()),
+                String::new(),
+                String::new(),
+            )
+        }
+    };
+    BootstrapOutcome {
+        command: command.to_owned(),
+        passed,
+        detail,
+        stdout,
+        stderr,
+    }
+}
+
+/// Kill the process group a timed-out command was started in.
+#[cfg(unix)]
+async fn kill_group(pid: u32) {
+    crate::acp::spawner::kill_process_group(pid).await;
+}
+
+/// Kill the process tree a timed-out command was started in.
+#[cfg(windows)]
+async fn kill_group(pid: u32) {
+    crate::acp::spawner::kill_process_tree(pid).await;
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> tokio::process::Command {
+    let mut cmd = tokio::process::Command::new("cmd");
+    cmd.args(["/C", command]);
+    cmd
+}
+
+#[cfg(not(windows))]
+fn shell(command: &str) -> tokio::process::Command {
+    let mut cmd = tokio::process::Command::new("sh");
+    cmd.args(["-c", command]);
+    cmd
+}
+
+/// Last non-blank line of `output`, shortened to [`DETAIL_LIMIT`] chars.
+fn last_line(output: &str) -> Option<String> {
+    output
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(|line| line.chars().take(DETAIL_LIMIT).collect())
+}
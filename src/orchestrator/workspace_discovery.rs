@@ -0,0 +1,242 @@
+//! Discovery of unmapped workspaces (`[workspace_discovery]`).
+//!
+//! An agent that connects with a `workspace_id` missing from
+//! `[[workspace]]` normally runs without Slack routing. With discovery
+//! enabled the workspace is recorded as pending: its sessions post to a
+//! channel created for it (`create_channels`) or to `unmapped_channel_id`,
+//! and operators are asked to confirm it. `/intercom workspace approve <id>`
+//! turns the pending entry into a live mapping and writes it to
+//! `config.toml`; `reject` ignores the workspace until restart.
+//!
+//! Pending entries live in memory: they do not survive a restart.
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use chrono::{DateTime, Utc};
+use slack_morphism::prelude::SlackChannelId;
+use tracing::{info, warn};
+
+use crate::config::WorkspaceMapping;
+use crate::orchestrator::workspace_mappings;
+use crate::slack::client::SlackMessage;
+use crate::slack::commands::slash_prefix;
+use crate::state::AppState;
+use crate::Result;
+
+/// Longest Slack channel name.
+const MAX_CHANNEL_NAME: usize = 80;
+
+/// A workspace seen on a connection but not yet mapped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingWorkspace {
+    /// `workspace_id` the agent supplied.
+    pub workspace_id: String,
+    /// Channel created for the workspace, once `conversations.create`
+    /// succeeded.
+    pub channel_id: Option<String>,
+    /// First connection.
+    pub first_seen: DateTime<Utc>,
+    /// Connections seen so far.
+    pub connections: u32,
+}
+
+/// Result of recording a connection for an unmapped workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Observed {
+    /// First connection: the workspace was added to the pending list.
+    New,
+    /// Already pending, with its created channel if any.
+    Pending {
+        /// Channel created for the workspace.
+        channel_id: Option<String>,
+    },
+    /// Rejected by an operator; the session runs without Slack routing.
+    Dismissed,
+}
+
+/// Pending and dismissed unmapped workspaces.
+#[derive(Debug, Default)]
+pub struct WorkspaceDiscovery {
+    pending: Mutex<BTreeMap<String, PendingWorkspace>>,
+    dismissed: Mutex<HashSet<String>>,
+}
+
+impl WorkspaceDiscovery {
+    /// Record a connection for the unmapped `workspace_id`.
+    pub fn observe(&self, workspace_id: &str) -> Observed {
+        if self
+            .dismissed
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains(workspace_id)
+        {
+            return Observed::Dismissed;
+        }
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(entry) = pending.get_mut(workspace_id) {
+            entry.connections = entry.connections.saturating_add(1);
+            return Observed::Pending {
+                channel_id: entry.channel_id.clone(),
+            };
+        }
+        pending.insert(
+            workspace_id.to_owned(),
+            PendingWorkspace {
+                workspace_id: workspace_id.to_owned(),
+                channel_id: None,
+                first_seen: Utc::now(),
+                connections: 1,
+            },
+        );
+        Observed::New
+    }
+
+    /// Remember the channel created for a pending workspace.
+    pub fn set_channel(&self, workspace_id: &str, channel_id: String) {
+        if let Some(entry) = self
+            .pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_mut(workspace_id)
+        {
+            entry.channel_id = Some(channel_id);
+        }
+    }
+
+    /// Pending workspaces, ordered by ID.
+    #[must_use]
+    pub fn pending(&self) -> Vec<PendingWorkspace> {
+        self.pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// Remove and return a pending workspace (on approval).
+    pub fn take(&self, workspace_id: &str) -> Option<PendingWorkspace> {
+        self.pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(workspace_id)
+    }
+
+    /// Stop tracking `workspace_id` until restart. Returns whether it was
+    /// pending.
+    pub fn dismiss(&self, workspace_id: &str) -> bool {
+        let was_pending = self.take(workspace_id).is_some();
+        if was_pending {
+            self.dismissed
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(workspace_id.to_owned());
+        }
+        was_pending
+    }
+}
+
+/// Slack channel name for a discovered workspace: `prefix` plus the
+/// workspace ID, lower-cased, with characters Slack rejects replaced by `-`.
+#[must_use]
+pub fn channel_name(prefix: &str, workspace_id: &str) -> String {
+    format!("{prefix}{workspace_id}")
+        .chars()
+        .map(|c| {
+            let c = c.to_ascii_lowercase();
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .take(MAX_CHANNEL_NAME)
+        .collect()
+}
+
+/// Channel for a session of the unmapped `workspace_id`, recording the
+/// connection. On the first connection a background task creates the
+/// workspace channel (if configured) and posts the discovery notice.
+///
+/// Returns `None` when discovery is disabled, the workspace was rejected,
+/// or no channel is available yet.
+pub fn resolve_unmapped(state: &Arc<AppState>, workspace_id: &str) -> Option<String> {
+    let config = &state.config.workspace_discovery;
+    if !config.enabled {
+        return None;
+    }
+    let fallback = Some(config.unmapped_channel_id.clone()).filter(|id| !id.is_empty());
+    match state.workspace_discovery.observe(workspace_id) {
+        Observed::Dismissed => None,
+        Observed::Pending { channel_id } => channel_id.or(fallback),
+        Observed::New => {
+            info!(workspace_id, "unmapped workspace discovered");
+            tokio::spawn(announce(Arc::clone(state), workspace_id.to_owned()));
+            fallback
+        }
+    }
+}
+
+/// Create the workspace channel (when `create_channels` is set) and post
+/// the discovery notice to the unmapped channel, or to the new channel
+/// when there is none.
+async fn announce(state: Arc<AppState>, workspace_id: String) {
+    let Some(slack) = state.slack.as_ref() else {
+        return;
+    };
+    let config = &state.config.workspace_discovery;
+
+    let mut created = None;
+    if config.create_channels {
+        let name = channel_name(&config.channel_prefix, &workspace_id);
+        match slack.create_channel(&name).await {
+            Ok(channel) => {
+                info!(workspace_id, channel_id = %channel.0, "created channel for workspace");
+                state
+                    .workspace_discovery
+                    .set_channel(&workspace_id, channel.0.clone());
+                created = Some(channel.0);
+            }
+            Err(err) => warn!(%err, workspace_id, "failed to create workspace channel"),
+        }
+    }
+
+    let notice_channel = Some(config.unmapped_channel_id.clone())
+        .filter(|id| !id.is_empty())
+        .or_else(|| created.clone());
+    let Some(notice_channel) = notice_channel else {
+        return;
+    };
+    let routed = created.map_or_else(
+        || "this channel".to_owned(),
+        |channel| format!("<#{channel}>"),
+    );
+    let prefix = slash_prefix(state.server_mode);
+    let text = format!(
+        "\u{1f50d} An agent connected with unmapped workspace `{workspace_id}`. \
+         Its sessions post to {routed} until an operator runs \
+         `/{prefix} workspace approve {workspace_id}` (or `reject`)."
+    );
+    if let Err(err) = slack
+        .enqueue(SlackMessage::plain(SlackChannelId(notice_channel), text))
+        .await
+    {
+        warn!(%err, workspace_id, "failed to post workspace discovery notice");
+    }
+}
+
+/// Map a pending workspace: add it to the routing table and `config.toml`
+/// (see [`workspace_mappings::add`]) and drop it from the pending list.
+///
+/// Returns whether the mapping was written to `config.toml`.
+///
+/// # Errors
+///
+/// Propagates the errors of [`workspace_mappings::add`].
+pub fn approve(state: &AppState, mapping: WorkspaceMapping) -> Result<bool> {
+    let workspace_id = mapping.workspace_id.clone();
+    let saved = workspace_mappings::add(state, mapping)?;
+    state.workspace_discovery.take(&workspace_id);
+    Ok(saved)
+}
@@ -0,0 +1,151 @@
+//! Runtime edits to the `[[workspace]]` routing table.
+//!
+//! `/intercom workspace add|remove` and discovery approvals change the live
+//! `workspace_mappings` so new sessions route at once, and write the change
+//! back to the `config.toml` the server was started with so it survives a
+//! restart. The file is edited with `toml_edit`, keeping comments and
+//! formatting; the config watcher then reloads the same mappings from it.
+
+use std::path::Path;
+use std::sync::PoisonError;
+
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table};
+
+use crate::config::WorkspaceMapping;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Array-of-tables key holding the mappings (`[[workspace]]`).
+const WORKSPACE_KEY: &str = "workspace";
+
+/// Add `mapping` to the routing table and `config.toml`.
+///
+/// Returns whether the mapping was written to `config.toml` (`false` when
+/// the server runs without a config file).
+///
+/// # Errors
+///
+/// Returns `AppError::Config` when an ID is empty, the workspace is already
+/// mapped, the channel already belongs to another workspace, or the config
+/// file cannot be parsed, and `AppError::Io` if it cannot be written.
+pub fn add(state: &AppState, mapping: WorkspaceMapping) -> Result<bool> {
+    if mapping.workspace_id.trim().is_empty() || mapping.channel_id.trim().is_empty() {
+        return Err(AppError::Config(
+            "workspace_id and channel_id must not be empty".into(),
+        ));
+    }
+    let mut mappings = state
+        .workspace_mappings
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some(existing) = mappings
+        .iter()
+        .find(|m| m.workspace_id == mapping.workspace_id)
+    {
+        return Err(AppError::Config(format!(
+            "workspace '{}' is already mapped to channel {}",
+            existing.workspace_id, existing.channel_id
+        )));
+    }
+    if let Some(existing) = mappings.iter().find(|m| m.channel_id == mapping.channel_id) {
+        return Err(AppError::Config(format!(
+            "channel {} is already mapped to workspace '{}'",
+            existing.channel_id, existing.workspace_id
+        )));
+    }
+    let saved = match state.config_path.as_deref() {
+        Some(path) => {
+            append_to_file(path, &mapping)?;
+            true
+        }
+        None => false,
+    };
+    mappings.push(mapping);
+    Ok(saved)
+}
+
+/// Remove the mapping for `workspace_id` from the routing table and
+/// `config.toml`.
+///
+/// Returns the removed mapping and whether `config.toml` held it; a mapping
+/// that came from a profile or environment override is only removed for
+/// this run.
+///
+/// # Errors
+///
+/// Returns `AppError::NotFound` when the workspace is not mapped,
+/// `AppError::Config` if the config file cannot be parsed, and
+/// `AppError::Io` if it cannot be written.
+pub fn remove(state: &AppState, workspace_id: &str) -> Result<(WorkspaceMapping, bool)> {
+    let mut mappings = state
+        .workspace_mappings
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    let index = mappings
+        .iter()
+        .position(|m| m.workspace_id == workspace_id)
+        .ok_or_else(|| AppError::NotFound(format!("workspace '{workspace_id}' is not mapped")))?;
+    let saved = match state.config_path.as_deref() {
+        Some(path) => remove_from_file(path, workspace_id)?,
+        None => false,
+    };
+    Ok((mappings.remove(index), saved))
+}
+
+/// Read and parse `path`, keeping its formatting.
+fn read_document(path: &Path) -> Result<DocumentMut> {
+    std::fs::read_to_string(path)?
+        .parse::<DocumentMut>()
+        .map_err(|err| AppError::Config(format!("cannot edit {}: {err}", path.display())))
+}
+
+/// Append `mapping` as a `[[workspace]]` entry of `path`.
+fn append_to_file(path: &Path, mapping: &WorkspaceMapping) -> Result<()> {
+    let mut document = read_document(path)?;
+    let entries = document
+        .entry(WORKSPACE_KEY)
+        .or_insert_with(|| Item::ArrayOfTables(ArrayOfTables::new()))
+        .as_array_of_tables_mut()
+        .ok_or_else(|| {
+            AppError::Config(format!(
+                "cannot edit {}: `workspace` is not a [[workspace]] list",
+                path.display()
+            ))
+        })?;
+
+    let mut table = Table::new();
+    table.insert("workspace_id", toml_edit::value(&mapping.workspace_id));
+    table.insert("channel_id", toml_edit::value(&mapping.channel_id));
+    if let Some(label) = mapping.label.as_deref() {
+        table.insert("label", toml_edit::value(label));
+    }
+    if let Some(workspace_path) = mapping.path.as_deref() {
+        table.insert(
+            "path",
+            toml_edit::value(workspace_path.to_string_lossy().as_ref()),
+        );
+    }
+    entries.push(table);
+
+    std::fs::write(path, document.to_string())?;
+    Ok(())
+}
+
+/// Drop every `[[workspace]]` entry of `path` with `workspace_id`.
+/// Returns whether one was found.
+fn remove_from_file(path: &Path, workspace_id: &str) -> Result<bool> {
+    let mut document = read_document(path)?;
+    let Some(entries) = document
+        .get_mut(WORKSPACE_KEY)
+        .and_then(Item::as_array_of_tables_mut)
+    else {
+        return Ok(false);
+    };
+    let before = entries.len();
+    entries.retain(|table| table.get("workspace_id").and_then(Item::as_str) != Some(workspace_id));
+    if entries.len() == before {
+        return Ok(false);
+    }
+    std::fs::write(path, document.to_string())?;
+    Ok(true)
+}
@@ -0,0 +1,474 @@
+//! Full-workspace snapshots and restore.
+//!
+//! A snapshot is a gzipped tar of every file in the workspace except the
+//! `[snapshots] exclude` globs and `.intercom/`, written to
+//! `.intercom/snapshots/<id>.tar.gz` next to a `<id>.manifest.json` of
+//! SHA-256 hashes. The newest `retention` snapshots of each workspace are
+//! kept. Checkpoints only record hashes; a snapshot is the coarser safety
+//! net that can put the files back.
+//!
+//! A restore unpacks the archive into a staging directory and checks it
+//! against the manifest before touching the workspace, then takes a
+//! snapshot of the current state so the restore itself can be undone. It
+//! refuses to write or delete through a symlink that leads out of the
+//! workspace. Files are streamed, never read whole into memory.
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write as _};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::config::SnapshotsConfig;
+use crate::models::snapshot::WorkspaceSnapshot;
+use crate::persistence::snapshot_repo::SnapshotRepo;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Directory, relative to the workspace root, holding snapshot archives.
+pub const SNAPSHOT_DIR: &str = ".intercom/snapshots";
+
+/// Characters of a snapshot ID shown in Slack; any unique prefix is
+/// accepted back.
+pub const DISPLAY_ID_LEN: usize = 8;
+
+/// Outcome of [`restore`].
+#[derive(Debug, Clone)]
+pub struct RestoreSummary {
+    /// The snapshot that was restored.
+    pub snapshot: WorkspaceSnapshot,
+    /// Snapshot of the workspace as it was just before the restore.
+    pub safety: WorkspaceSnapshot,
+    /// Files written back because they differed or were missing.
+    pub restored: usize,
+    /// Files deleted because they were not in the snapshot.
+    pub removed: usize,
+}
+
+/// The ID of `snapshot` as shown in Slack.
+#[must_use]
+pub fn display_id(snapshot: &WorkspaceSnapshot) -> &str {
+    snapshot.id.get(..DISPLAY_ID_LEN).unwrap_or(&snapshot.id)
+}
+
+/// Path of the archive of snapshot `id` in `workspace_root`.
+#[must_use]
+pub fn archive_path(workspace_root: &Path, id: &str) -> PathBuf {
+    workspace_root
+        .join(SNAPSHOT_DIR)
+        .join(format!("{id}.tar.gz"))
+}
+
+/// Path of the hash manifest of snapshot `id` in `workspace_root`.
+#[must_use]
+pub fn manifest_path(workspace_root: &Path, id: &str) -> PathBuf {
+    workspace_root
+        .join(SNAPSHOT_DIR)
+        .join(format!("{id}.manifest.json"))
+}
+
+/// Snapshot `workspace_root` on behalf of `created_by`, then delete the
+/// workspace's snapshots beyond `[snapshots] retention`.
+///
+/// # Errors
+///
+/// Returns `AppError::Io` if the workspace cannot be read or the archive
+/// written, or `AppError::Db` if the record cannot be stored.
+pub async fn create(
+    state: &AppState,
+    workspace_root: &Path,
+    label: Option<String>,
+    created_by: &str,
+    session_id: Option<String>,
+) -> Result<WorkspaceSnapshot> {
+    create_keeping(state, workspace_root, label, created_by, session_id, None).await
+}
+
+/// [`create`], sparing the snapshot `keep` from the retention sweep.
+async fn create_keeping(
+    state: &AppState,
+    workspace_root: &Path,
+    label: Option<String>,
+    created_by: &str,
+    session_id: Option<String>,
+    keep: Option<&str>,
+) -> Result<WorkspaceSnapshot> {
+    let mut snapshot = WorkspaceSnapshot::new(
+        workspace_root.to_string_lossy().into_owned(),
+        label,
+        created_by.to_owned(),
+        session_id,
+    );
+    let root = workspace_root.to_path_buf();
+    let config = state.config.snapshots.clone();
+    let id = snapshot.id.clone();
+    let (file_count, total_bytes) =
+        tokio::task::spawn_blocking(move || write_archive(&root, &config, &id))
+            .await
+            .map_err(|err| AppError::Io(format!("snapshot task failed: {err}")))??;
+    snapshot.file_count = file_count;
+    snapshot.total_bytes = total_bytes;
+
+    let repo = SnapshotRepo::new(Arc::clone(&state.db));
+    repo.create(&snapshot).await?;
+    info!(
+        snapshot_id = %snapshot.id,
+        workspace_root = %snapshot.workspace_root,
+        file_count,
+        total_bytes,
+        "workspace snapshot created"
+    );
+    prune(state, &repo, workspace_root, keep).await?;
+    Ok(snapshot)
+}
+
+/// Delete the snapshots of `workspace_root` beyond the newest `retention`,
+/// except `keep`.
+async fn prune(
+    state: &AppState,
+    repo: &SnapshotRepo,
+    workspace_root: &Path,
+    keep: Option<&str>,
+) -> Result<()> {
+    let snapshots = repo
+        .list_for_workspace(&workspace_root.to_string_lossy())
+        .await?;
+    for old in snapshots
+        .iter()
+        .skip(state.config.snapshots.retention)
+        .filter(|old| Some(old.id.as_str()) != keep)
+    {
+        for path in [
+            archive_path(workspace_root, &old.id),
+            manifest_path(workspace_root, &old.id),
+        ] {
+            if let Err(err) = std::fs::remove_file(&path) {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    warn!(%err, path = %path.display(), "failed to delete old snapshot file");
+                }
+            }
+        }
+        repo.delete(&old.id).await?;
+        info!(snapshot_id = %old.id, "old workspace snapshot deleted");
+    }
+    Ok(())
+}
+
+/// Put the workspace back the way snapshot `snapshot_id` (or an ID prefix)
+/// recorded it, on behalf of `user_id`.
+///
+/// Files that differ or are missing are written back and files not in the
+/// snapshot are deleted; excluded paths and `.intercom/` are left alone.
+/// The current state is snapshotted first.
+///
+/// # Errors
+///
+/// Returns `AppError::NotFound` for an unknown snapshot or a missing
+/// archive, `AppError::Io` when the archive does not match its manifest or
+/// the workspace cannot be written, `AppError::PathViolation` when a
+/// symlink would lead a write outside the workspace, or `AppError::Db` on
+/// a database failure.
+pub async fn restore(state: &AppState, snapshot_id: &str, user_id: &str) -> Result<RestoreSummary> {
+    let repo = SnapshotRepo::new(Arc::clone(&state.db));
+    let snapshot = repo
+        .get_by_prefix(snapshot_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("snapshot '{snapshot_id}' not found")))?;
+    let root = PathBuf::from(&snapshot.workspace_root);
+
+    let staged = {
+        let root = root.clone();
+        let id = snapshot.id.clone();
+        tokio::task::spawn_blocking(move || unpack_verified(&root, &id))
+            .await
+            .map_err(|err| AppError::Io(format!("restore task failed: {err}")))??
+    };
+
+    let safety = create_keeping(
+        state,
+        &root,
+        Some(format!("before restore of {}", display_id(&snapshot))),
+        user_id,
+        None,
+        Some(&snapshot.id),
+    )
+    .await?;
+
+    let (restored, removed) = {
+        let root = root.clone();
+        let config = state.config.snapshots.clone();
+        tokio::task::spawn_blocking(move || apply_staged(&root, &config, &staged))
+            .await
+            .map_err(|err| AppError::Io(format!("restore task failed: {err}")))??
+    };
+    info!(
+        snapshot_id = %snapshot.id,
+        safety_snapshot_id = %safety.id,
+        restored,
+        removed,
+        user_id,
+        "workspace snapshot restored"
+    );
+
+    if let Some(ref logger) = state.audit_logger {
+        let mut entry = AuditEntry::new(AuditEventType::WorkspaceRestored)
+            .with_operator(user_id.to_owned())
+            .with_request_id(snapshot.id.clone())
+            .with_parameters(serde_json::json!({
+                "workspace_root": snapshot.workspace_root,
+                "safety_snapshot_id": safety.id,
+            }))
+            .with_result(format!("{restored} restored, {removed} removed"));
+        if let Some(ref session_id) = snapshot.session_id {
+            entry = entry.with_session(session_id.clone());
+        }
+        if let Err(err) = logger.log_entry(entry) {
+            warn!(%err, "audit log write failed (workspace restore)");
+        }
+    }
+
+    Ok(RestoreSummary {
+        snapshot,
+        safety,
+        restored,
+        removed,
+    })
+}
+
+/// Workspace-relative paths of the regular files a snapshot of `root`
+/// contains, sorted. Symlinks are skipped.
+///
+/// # Errors
+///
+/// Returns `AppError::Io` if `root` cannot be read.
+pub fn collect_files(root: &Path, config: &SnapshotsConfig) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), String::new())];
+    while let Some((dir, prefix)) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let rel = if prefix.is_empty() {
+                name
+            } else {
+                format!("{prefix}/{name}")
+            };
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                if !config.excludes_dir(&rel) {
+                    pending.push((entry.path(), rel));
+                }
+            } else if file_type.is_file() && !config.excludes(&rel) {
+                files.push(rel);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Write the archive and manifest of snapshot `id`, returning the number
+/// of files and their total size.
+fn write_archive(root: &Path, config: &SnapshotsConfig, id: &str) -> Result<(usize, u64)> {
+    let files = collect_files(root, config)?;
+    let dir = root.join(SNAPSHOT_DIR);
+    std::fs::create_dir_all(&dir)?;
+
+    let staging = tempfile::NamedTempFile::new_in(&dir)?;
+    let mut builder = tar::Builder::new(GzEncoder::new(staging, Compression::default()));
+    let mut manifest = BTreeMap::new();
+    let mut total_bytes = 0u64;
+    for rel in &files {
+        let file = std::fs::File::open(root.join(rel))?;
+        let metadata = file.metadata()?;
+        let mut header = tar::Header::new_gnu();
+        header.set_metadata_in_mode(&metadata, tar::HeaderMode::Deterministic);
+        header.set_size(metadata.len());
+        let mut reader = HashingReader::new(file);
+        builder.append_data(&mut header, rel, &mut reader)?;
+        total_bytes += metadata.len();
+        manifest.insert(rel.clone(), reader.hex_digest());
+    }
+    let staging = builder.into_inner()?.finish()?;
+    staging
+        .persist(archive_path(root, id))
+        .map_err(|err| AppError::Io(format!("failed to store snapshot archive: {err}")))?;
+
+    let manifest = serde_json::to_vec_pretty(&manifest)
+        .map_err(|err| AppError::Io(format!("failed to encode snapshot manifest: {err}")))?;
+    let mut file = std::fs::File::create(manifest_path(root, id))?;
+    file.write_all(&manifest)?;
+    Ok((files.len(), total_bytes))
+}
+
+/// A snapshot unpacked into a staging directory and checked against its
+/// manifest.
+struct Staged {
+    dir: tempfile::TempDir,
+    manifest: BTreeMap<String, String>,
+}
+
+/// Unpack snapshot `id` of `root` into a staging directory, failing unless
+/// every file matches its manifest hash.
+fn unpack_verified(root: &Path, id: &str) -> Result<Staged> {
+    let archive = archive_path(root, id);
+    if !archive.is_file() {
+        return Err(AppError::NotFound(format!(
+            "snapshot archive {} is missing",
+            archive.display()
+        )));
+    }
+    let manifest: BTreeMap<String, String> =
+        serde_json::from_slice(&std::fs::read(manifest_path(root, id))?)
+            .map_err(|err| AppError::Io(format!("invalid snapshot manifest: {err}")))?;
+    if let Some(bad) = manifest.keys().find(|rel| !is_plain_relative(rel)) {
+        return Err(AppError::Io(format!(
+            "snapshot manifest names an unsafe path '{bad}'"
+        )));
+    }
+
+    let dir = tempfile::tempdir_in(root.join(SNAPSHOT_DIR))?;
+    tar::Archive::new(GzDecoder::new(std::fs::File::open(&archive)?)).unpack(dir.path())?;
+    for (rel, expected) in &manifest {
+        let staged = dir.path().join(rel);
+        let digest = std::fs::symlink_metadata(&staged)
+            .and_then(|metadata| {
+                if metadata.is_file() {
+                    sha256_file(&staged)
+                } else {
+                    Err(std::io::Error::other("not a regular file"))
+                }
+            })
+            .map_err(|err| {
+                AppError::Io(format!(
+                    "snapshot {id} is damaged: {rel} unreadable ({err})"
+                ))
+            })?;
+        if digest != *expected {
+            return Err(AppError::Io(format!(
+                "snapshot {id} is damaged: {rel} does not match its manifest"
+            )));
+        }
+    }
+    Ok(Staged { dir, manifest })
+}
+
+/// Make `root` match the staged snapshot, returning how many files were
+/// written back and how many deleted.
+///
+/// Every path is checked with [`contained_path`] before anything is
+/// changed, so a symlink leading out of `root` fails the restore rather
+/// than redirecting a write or delete.
+fn apply_staged(root: &Path, config: &SnapshotsConfig, staged: &Staged) -> Result<(usize, usize)> {
+    let root = root.canonicalize()?;
+    let stale = collect_files(&root, config)?
+        .into_iter()
+        .filter(|rel| !staged.manifest.contains_key(rel))
+        .map(|rel| contained_path(&root, &rel))
+        .collect::<Result<Vec<_>>>()?;
+    let targets = staged
+        .manifest
+        .iter()
+        .map(|(rel, expected)| Ok((rel, expected, contained_path(&root, rel)?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut removed = 0;
+    for path in stale {
+        std::fs::remove_file(path)?;
+        removed += 1;
+    }
+
+    let mut restored = 0;
+    for (rel, expected, dest) in targets {
+        if dest.is_file() && sha256_file(&dest).is_ok_and(|digest| digest == *expected) {
+            continue;
+        }
+        if dest.is_dir() {
+            std::fs::remove_dir_all(&dest)?;
+        }
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(staged.dir.path().join(rel), &dest)?;
+        restored += 1;
+    }
+    Ok((restored, removed))
+}
+
+/// `root.join(rel)`, provided neither it nor any existing directory on the
+/// way is a symlink leading outside the canonical `root`.
+///
+/// # Errors
+///
+/// Returns `AppError::PathViolation` if the path escapes `root`, or
+/// `AppError::Io` if an existing component cannot be inspected.
+fn contained_path(root: &Path, rel: &str) -> Result<PathBuf> {
+    let dest = root.join(rel);
+    let escapes = || {
+        AppError::PathViolation(format!(
+            "refusing to restore '{rel}' through a symlink outside the workspace"
+        ))
+    };
+    if std::fs::symlink_metadata(&dest).is_ok_and(|metadata| metadata.is_symlink()) {
+        return Err(escapes());
+    }
+    // The deepest ancestor that exists is where the write would land.
+    let Some(existing) = dest.ancestors().skip(1).find(|dir| dir.exists()) else {
+        return Err(escapes());
+    };
+    if existing.canonicalize()?.starts_with(root) {
+        Ok(dest)
+    } else {
+        Err(escapes())
+    }
+}
+
+/// Whether `rel` is a relative path made only of normal components.
+fn is_plain_relative(rel: &str) -> bool {
+    !rel.is_empty()
+        && Path::new(rel)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// SHA-256 hex digest of the file at `path`, read in chunks.
+fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let mut reader = HashingReader::new(std::fs::File::open(path)?);
+    std::io::copy(&mut reader, &mut std::io::sink())?;
+    Ok(reader.hex_digest())
+}
+
+/// Reader that hashes what passes through it, so a file can be archived
+/// and hashed in one streaming pass.
+struct HashingReader<R> {
+    inner: R,
+    hasher: Sha256,
+}
+
+impl<R: Read> HashingReader<R> {
+    fn new(inner: R) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// SHA-256 hex digest of everything read so far.
+    fn hex_digest(self) -> String {
+        format!("{:x}", self.hasher.finalize())
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        Ok(read)
+    }
+}
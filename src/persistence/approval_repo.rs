@@ -1,13 +1,20 @@
 //! Approval request repository for `SQLite` persistence.
 
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use tokio::sync::futures::Notified;
+use tokio::sync::Notify;
 
 use crate::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use crate::models::short_id::{self, APPROVAL_PREFIX};
 use crate::{AppError, Result};
 
-use super::db::Database;
+use super::db::{unused_short_id, Database};
+
+/// Woken whenever any approval request changes status, so callers waiting
+/// on a decision need not poll the table.
+static STATUS_CHANGED: LazyLock<Notify> = LazyLock::new(Notify::new);
 
 /// Repository wrapper around `SQLite` for approval request records.
 #[derive(Clone)]
@@ -19,6 +26,7 @@ pub struct ApprovalRepo {
 #[derive(sqlx::FromRow)]
 struct ApprovalRow {
     id: String,
+    short_id: Option<String>,
     session_id: String,
     title: String,
     description: Option<String>,
@@ -28,8 +36,12 @@ struct ApprovalRow {
     status: String,
     original_hash: String,
     slack_ts: Option<String>,
+    thread_ts: Option<String>,
     created_at: String,
     consumed_at: Option<String>,
+    apply_after: Option<String>,
+    stale_at: Option<String>,
+    decision_reason: Option<String>,
 }
 
 impl ApprovalRow {
@@ -49,8 +61,29 @@ impl ApprovalRow {
                     .map_err(|e| AppError::Db(format!("invalid consumed_at: {e}")))
             })
             .transpose()?;
+        let apply_after = self
+            .apply_after
+            .as_deref()
+            .map(|s| {
+                chrono::DateTime::parse_from_rfc3339(s)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|e| AppError::Db(format!("invalid apply_after: {e}")))
+            })
+            .transpose()?;
+        let stale_at = self
+            .stale_at
+            .as_deref()
+            .map(|s| {
+                chrono::DateTime::parse_from_rfc3339(s)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|e| AppError::Db(format!("invalid stale_at: {e}")))
+            })
+            .transpose()?;
 
         Ok(ApprovalRequest {
+            short_id: self
+                .short_id
+                .unwrap_or_else(|| self.id.chars().take(8).collect()),
             id: self.id,
             session_id: self.session_id,
             title: self.title,
@@ -61,12 +94,26 @@ impl ApprovalRow {
             status,
             original_hash: self.original_hash,
             slack_ts: self.slack_ts,
+            thread_ts: self.thread_ts,
             created_at,
             consumed_at,
+            apply_after,
+            stale_at,
+            decision_reason: self.decision_reason,
         })
     }
 }
 
+/// Wake [`ApprovalRepo::status_changed`] waiters if a conditional update
+/// changed a row, and report whether it did.
+fn notify_if_changed(rows_affected: u64) -> bool {
+    let changed = rows_affected == 1;
+    if changed {
+        STATUS_CHANGED.notify_waiters();
+    }
+    changed
+}
+
 fn parse_risk_level(s: &str) -> Result<RiskLevel> {
     match s {
         "low" => Ok(RiskLevel::Low),
@@ -77,11 +124,7 @@ fn parse_risk_level(s: &str) -> Result<RiskLevel> {
 }
 
 fn risk_level_str(r: RiskLevel) -> &'static str {
-    match r {
-        RiskLevel::Low => "low",
-        RiskLevel::High => "high",
-        RiskLevel::Critical => "critical",
-    }
+    r.as_str()
 }
 
 fn parse_approval_status(s: &str) -> Result<ApprovalStatus> {
@@ -114,6 +157,15 @@ impl ApprovalRepo {
         Self { db }
     }
 
+    /// Future that completes the next time any approval request changes
+    /// status through this repository.
+    ///
+    /// Create (and `enable`) it before reading the record, then await it,
+    /// so a change between the read and the await is not missed.
+    pub fn status_changed() -> Notified<'static> {
+        STATUS_CHANGED.notified()
+    }
+
     /// Insert a new approval request record.
     ///
     /// # Errors
@@ -124,11 +176,20 @@ impl ApprovalRepo {
         let status = approval_status_str(request.status);
         let created_at = request.created_at.to_rfc3339();
         let consumed_at = request.consumed_at.map(|dt| dt.to_rfc3339());
+        let short_id = unused_short_id(
+            &self.db,
+            "approval_request",
+            APPROVAL_PREFIX,
+            &request.id,
+            &request.short_id,
+        )
+        .await?;
 
         sqlx::query(
             "INSERT INTO approval_request (id, session_id, title, description, diff_content,
-             file_path, risk_level, status, original_hash, slack_ts, created_at, consumed_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+             file_path, risk_level, status, original_hash, slack_ts, thread_ts, created_at,
+             consumed_at, short_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
         )
         .bind(&request.id)
         .bind(&request.session_id)
@@ -140,12 +201,17 @@ impl ApprovalRepo {
         .bind(status)
         .bind(&request.original_hash)
         .bind(&request.slack_ts)
+        .bind(&request.thread_ts)
         .bind(&created_at)
         .bind(&consumed_at)
+        .bind(&short_id)
         .execute(self.db.as_ref())
         .await?;
 
-        Ok(request.clone())
+        Ok(ApprovalRequest {
+            short_id,
+            ..request.clone()
+        })
     }
 
     /// Retrieve an approval request by identifier.
@@ -165,6 +231,47 @@ impl ApprovalRepo {
         row.map(ApprovalRow::into_approval).transpose()
     }
 
+    /// Retrieve an approval request by UUID, short ID (`apr-9mxq`), or a
+    /// unique prefix of either.
+    ///
+    /// Exact matches win over prefixes. Returns `Ok(None)` if nothing
+    /// matches.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails, or `AppError::Config`,
+    /// naming the candidates, if the prefix matches more than one request.
+    pub async fn resolve(&self, id: &str) -> Result<Option<ApprovalRequest>> {
+        let id = short_id::normalize(id);
+        let row: Option<ApprovalRow> =
+            sqlx::query_as("SELECT * FROM approval_request WHERE id = ?1 OR short_id = ?1")
+                .bind(&id)
+                .fetch_optional(self.db.as_ref())
+                .await?;
+        if let Some(row) = row {
+            return row.into_approval().map(Some);
+        }
+
+        let rows: Vec<ApprovalRow> = sqlx::query_as(
+            "SELECT * FROM approval_request WHERE id LIKE ?1 OR short_id LIKE ?1 \
+             ORDER BY created_at DESC",
+        )
+        .bind(format!("{id}%"))
+        .fetch_all(self.db.as_ref())
+        .await?;
+        let mut approvals = rows
+            .into_iter()
+            .map(ApprovalRow::into_approval)
+            .collect::<Result<Vec<_>>>()?;
+        match approvals.len() {
+            0 | 1 => Ok(approvals.pop()),
+            _ => {
+                let candidates: Vec<String> = approvals.into_iter().map(|a| a.short_id).collect();
+                Err(short_id::ambiguous("approval", &id, &candidates))
+            }
+        }
+    }
+
     /// Retrieve the pending approval request for a session, if any.
     ///
     /// # Errors
@@ -197,10 +304,169 @@ impl ApprovalRepo {
             .bind(id)
             .execute(self.db.as_ref())
             .await?;
+        STATUS_CHANGED.notify_waiters();
+
+        Ok(())
+    }
+
+    /// Move a pending approval request to `status`.
+    ///
+    /// Returns `false`, changing nothing, when the request is missing or was
+    /// already resolved, so of two racing resolutions (an operator click and
+    /// the timeout, say) exactly one wins.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn decide(&self, id: &str, status: ApprovalStatus) -> Result<bool> {
+        self.decide_with_reason(id, status, None).await
+    }
+
+    /// Like [`decide`](Self::decide), also recording why the operator
+    /// decided as they did.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn decide_with_reason(
+        &self,
+        id: &str,
+        status: ApprovalStatus,
+        reason: Option<&str>,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE approval_request SET status = ?1, decision_reason = ?2 \
+             WHERE id = ?3 AND status = 'pending'",
+        )
+        .bind(approval_status_str(status))
+        .bind(reason)
+        .bind(id)
+        .execute(self.db.as_ref())
+        .await?;
 
+        Ok(notify_if_changed(result.rows_affected()))
+    }
+
+    /// Approve a pending request and schedule the server to apply it at
+    /// `apply_after`.
+    ///
+    /// Returns `false`, changing nothing, when the request is missing or was
+    /// already resolved.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn schedule_apply(&self, id: &str, apply_after: DateTime<Utc>) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE approval_request SET status = 'approved', apply_after = ?1 \
+             WHERE id = ?2 AND status = 'pending'",
+        )
+        .bind(apply_after.to_rfc3339())
+        .bind(id)
+        .execute(self.db.as_ref())
+        .await?;
+
+        Ok(notify_if_changed(result.rows_affected()))
+    }
+
+    /// List approved requests the server is scheduled to apply, soonest
+    /// first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_scheduled(&self) -> Result<Vec<ApprovalRequest>> {
+        let rows: Vec<ApprovalRow> = sqlx::query_as(
+            "SELECT * FROM approval_request WHERE status = 'approved' \
+             AND apply_after IS NOT NULL ORDER BY apply_after ASC",
+        )
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter().map(ApprovalRow::into_approval).collect()
+    }
+
+    /// Drop the schedule of a request, leaving it to the agent's
+    /// `check_diff`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn clear_apply_after(&self, id: &str) -> Result<()> {
+        sqlx::query("UPDATE approval_request SET apply_after = NULL WHERE id = ?1")
+            .bind(id)
+            .execute(self.db.as_ref())
+            .await?;
         Ok(())
     }
 
+    /// List pending and approved requests whose diff has not been applied
+    /// and is not yet known to be stale.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_unapplied(&self) -> Result<Vec<ApprovalRequest>> {
+        let rows: Vec<ApprovalRow> = sqlx::query_as(
+            "SELECT * FROM approval_request WHERE status IN ('pending', 'approved') \
+             AND stale_at IS NULL ORDER BY created_at ASC",
+        )
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter().map(ApprovalRow::into_approval).collect()
+    }
+
+    /// Record that the file of a request in `status` changed on disk at
+    /// `at`. A pending request is rejected, since its diff no longer applies
+    /// to what the operator would approve; an approved one loses any
+    /// scheduled apply.
+    ///
+    /// Returns `false`, changing nothing, when the request is no longer in
+    /// `status` or is already stale.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn mark_stale(
+        &self,
+        id: &str,
+        status: ApprovalStatus,
+        at: DateTime<Utc>,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE approval_request SET stale_at = ?1, apply_after = NULL, \
+             status = CASE status WHEN 'pending' THEN 'rejected' ELSE status END \
+             WHERE id = ?2 AND status = ?3 AND stale_at IS NULL",
+        )
+        .bind(at.to_rfc3339())
+        .bind(id)
+        .bind(approval_status_str(status))
+        .execute(self.db.as_ref())
+        .await?;
+
+        Ok(notify_if_changed(result.rows_affected()))
+    }
+
+    /// Re-open an expired approval request so it can be decided after all.
+    ///
+    /// Returns `false`, changing nothing, when the request is missing or is
+    /// not expired, so two operators reviving at once re-open it only once.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn reopen(&self, id: &str) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE approval_request SET status = 'pending' WHERE id = ?1 AND status = 'expired'",
+        )
+        .bind(id)
+        .execute(self.db.as_ref())
+        .await?;
+
+        Ok(notify_if_changed(result.rows_affected()))
+    }
+
     /// Set the Slack message timestamp on an approval request after the message is posted.
     ///
     /// The `slack_ts` value is used for subsequent `chat.update` calls that replace
@@ -219,6 +485,82 @@ impl ApprovalRepo {
         Ok(())
     }
 
+    /// Record where an approval request was posted in Slack.
+    ///
+    /// `slack_ts` is the request message itself (updated in place when the
+    /// request resolves); `thread_ts` is the thread its follow-ups go to.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn set_slack_anchor(&self, id: &str, slack_ts: &str, thread_ts: &str) -> Result<()> {
+        sqlx::query("UPDATE approval_request SET slack_ts = ?1, thread_ts = ?2 WHERE id = ?3")
+            .bind(slack_ts)
+            .bind(thread_ts)
+            .bind(id)
+            .execute(self.db.as_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Record a Slack copy of a fanned-out approval request.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the insert fails.
+    pub async fn add_message(&self, id: &str, channel_id: &str, ts: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT OR IGNORE INTO approval_message (request_id, channel_id, ts) \
+             VALUES (?1, ?2, ?3)",
+        )
+        .bind(id)
+        .bind(channel_id)
+        .bind(ts)
+        .execute(self.db.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Copies of a fanned-out request still showing live buttons, as
+    /// `(channel_id, ts)` in posting order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn unresolved_messages(&self, id: &str) -> Result<Vec<(String, String)>> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT channel_id, ts FROM approval_message \
+             WHERE request_id = ?1 AND resolved = 0 ORDER BY rowid",
+        )
+        .bind(id)
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Mark one copy of a fanned-out request as updated with its outcome.
+    /// Returns `false` if it was already marked (or is not a tracked copy).
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn resolve_message(&self, id: &str, channel_id: &str, ts: &str) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE approval_message SET resolved = 1 \
+             WHERE request_id = ?1 AND channel_id = ?2 AND ts = ?3 AND resolved = 0",
+        )
+        .bind(id)
+        .bind(channel_id)
+        .bind(ts)
+        .execute(self.db.as_ref())
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
     /// Mark an approved request as consumed with a timestamp.
     ///
     /// # Errors
@@ -251,6 +593,7 @@ impl ApprovalRepo {
         .bind(id)
         .execute(self.db.as_ref())
         .await?;
+        STATUS_CHANGED.notify_waiters();
 
         Ok(())
     }
@@ -279,6 +622,7 @@ impl ApprovalRepo {
         .bind(session_id)
         .execute(self.db.as_ref())
         .await?;
+        STATUS_CHANGED.notify_waiters();
 
         Ok(())
     }
@@ -297,6 +641,144 @@ impl ApprovalRepo {
         rows.into_iter().map(ApprovalRow::into_approval).collect()
     }
 
+    /// Number of approval requests awaiting a decision, across sessions.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn count_pending(&self) -> Result<u64> {
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM approval_request WHERE status = 'pending'")
+                .fetch_one(self.db.as_ref())
+                .await?;
+        Ok(u64::try_from(count).unwrap_or(0))
+    }
+
+    /// List pending approval requests of sessions owned by `owner_user_id`,
+    /// oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_pending_for_owner(
+        &self,
+        owner_user_id: &str,
+    ) -> Result<Vec<ApprovalRequest>> {
+        let rows: Vec<ApprovalRow> = sqlx::query_as(
+            "SELECT a.* FROM approval_request a
+             JOIN session s ON s.id = a.session_id
+             WHERE a.status = 'pending' AND s.owner_user_id = ?1
+             ORDER BY a.created_at ASC",
+        )
+        .bind(owner_user_id)
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter().map(ApprovalRow::into_approval).collect()
+    }
+
+    /// List every approval request raised by a session, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_for_session(&self, session_id: &str) -> Result<Vec<ApprovalRequest>> {
+        let rows: Vec<ApprovalRow> = sqlx::query_as(
+            "SELECT * FROM approval_request WHERE session_id = ?1 ORDER BY created_at ASC",
+        )
+        .bind(session_id)
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter().map(ApprovalRow::into_approval).collect()
+    }
+
+    /// List the most recently raised approval requests that an operator has
+    /// decided — approved (including since applied) or rejected — newest
+    /// first, optionally limited to one session.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_decided(
+        &self,
+        session_id: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<ApprovalRequest>> {
+        let rows: Vec<ApprovalRow> = sqlx::query_as(
+            "SELECT * FROM approval_request \
+             WHERE status IN ('approved', 'rejected', 'consumed') \
+             AND (?1 IS NULL OR session_id = ?1) \
+             ORDER BY created_at DESC LIMIT ?2",
+        )
+        .bind(session_id)
+        .bind(limit)
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter().map(ApprovalRow::into_approval).collect()
+    }
+
+    /// Like [`Self::list_decided`], over every session that worked in
+    /// `workspace_root`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_decided_for_workspace(
+        &self,
+        workspace_root: &str,
+        limit: u32,
+    ) -> Result<Vec<ApprovalRequest>> {
+        let rows: Vec<ApprovalRow> = sqlx::query_as(
+            "SELECT a.* FROM approval_request a \
+             JOIN session s ON s.id = a.session_id \
+             WHERE a.status IN ('approved', 'rejected', 'consumed') \
+             AND s.workspace_root = ?1 \
+             ORDER BY a.created_at DESC LIMIT ?2",
+        )
+        .bind(workspace_root)
+        .bind(limit)
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter().map(ApprovalRow::into_approval).collect()
+    }
+
+    /// Requests for `file_path` from other sessions in `workspace_root`
+    /// that may collide with a new one: pending, approved but not yet
+    /// applied, or applied — the last two only when created or applied at
+    /// or after `since`. Newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_conflicting(
+        &self,
+        workspace_root: &str,
+        file_path: &str,
+        exclude_session_id: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<ApprovalRequest>> {
+        let rows: Vec<ApprovalRow> = sqlx::query_as(
+            "SELECT a.* FROM approval_request a \
+             JOIN session s ON s.id = a.session_id \
+             WHERE s.workspace_root = ?1 AND a.file_path = ?2 AND a.session_id <> ?3 \
+             AND (a.status = 'pending' \
+                  OR (a.status = 'approved' AND a.created_at >= ?4) \
+                  OR (a.status = 'consumed' AND a.consumed_at >= ?4)) \
+             ORDER BY a.created_at DESC",
+        )
+        .bind(workspace_root)
+        .bind(file_path)
+        .bind(exclude_session_id)
+        .bind(since.to_rfc3339())
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter().map(ApprovalRow::into_approval).collect()
+    }
+
     /// Rebind a crashed session's *pending* clearances to a resumed session so
     /// mid-task approval state survives a respawn (F.3-T3).
     ///
@@ -3,7 +3,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 
 use crate::models::checkpoint::Checkpoint;
 use crate::models::progress::ProgressItem;
@@ -28,6 +28,7 @@ struct CheckpointRow {
     workspace_root: String,
     progress_snapshot: Option<String>,
     created_at: String,
+    automatic: bool,
 }
 
 impl CheckpointRow {
@@ -56,6 +57,7 @@ impl CheckpointRow {
             session_state,
             file_hashes,
             workspace_root: self.workspace_root,
+            automatic: self.automatic,
             progress_snapshot,
             created_at,
         })
@@ -89,8 +91,8 @@ impl CheckpointRepo {
 
         sqlx::query(
             "INSERT INTO checkpoint (id, session_id, label, session_state, file_hashes,
-             workspace_root, progress_snapshot, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+             workspace_root, progress_snapshot, created_at, automatic)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
         )
         .bind(&checkpoint.id)
         .bind(&checkpoint.session_id)
@@ -100,6 +102,7 @@ impl CheckpointRepo {
         .bind(&checkpoint.workspace_root)
         .bind(&progress_snapshot)
         .bind(&created_at)
+        .bind(checkpoint.automatic)
         .execute(self.db.as_ref())
         .await?;
 
@@ -139,6 +142,55 @@ impl CheckpointRepo {
             .collect()
     }
 
+    /// When the newest checkpoint of a session was taken, of any kind.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn latest_created_at(&self, session_id: &str) -> Result<Option<DateTime<Utc>>> {
+        let latest: Option<String> =
+            sqlx::query_scalar("SELECT MAX(created_at) FROM checkpoint WHERE session_id = ?1")
+                .bind(session_id)
+                .fetch_one(self.db.as_ref())
+                .await?;
+        latest
+            .map(|ts| {
+                DateTime::parse_from_rfc3339(&ts)
+                    .map(|ts| ts.with_timezone(&Utc))
+                    .map_err(|e| AppError::Db(format!("invalid created_at: {e}")))
+            })
+            .transpose()
+    }
+
+    /// Delete all but the newest `keep_last` automatic checkpoints of a
+    /// session, counting operator and agent checkpoints too unless
+    /// `keep_milestones` spares them. Returns the number deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the delete fails.
+    pub async fn prune(
+        &self,
+        session_id: &str,
+        keep_last: usize,
+        keep_milestones: bool,
+    ) -> Result<u64> {
+        let keep_last = i64::try_from(keep_last).unwrap_or(i64::MAX);
+        let result = sqlx::query(
+            "DELETE FROM checkpoint WHERE session_id = ?1 AND (automatic = 1 OR ?3 = 0)
+             AND id NOT IN (
+                 SELECT id FROM checkpoint WHERE session_id = ?1 AND (automatic = 1 OR ?3 = 0)
+                 ORDER BY created_at DESC LIMIT ?2
+             )",
+        )
+        .bind(session_id)
+        .bind(keep_last)
+        .bind(keep_milestones)
+        .execute(self.db.as_ref())
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     /// Delete all checkpoints for a given session.
     ///
     /// # Errors
@@ -0,0 +1,154 @@
+//! Apply conflict repository for `SQLite` persistence.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::models::conflict::{ApplyConflict, ConflictKind, ConflictResolution};
+use crate::{AppError, Result};
+
+use super::db::Database;
+
+/// Repository for apply conflicts awaiting or settled by the operator.
+#[derive(Clone)]
+pub struct ConflictRepo {
+    db: Arc<Database>,
+}
+
+/// Internal row struct for `SQLite` deserialization.
+#[derive(sqlx::FromRow)]
+struct ConflictRow {
+    id: String,
+    request_id: String,
+    session_id: String,
+    file_path: String,
+    kind: String,
+    detail: String,
+    resolution: Option<String>,
+    resolved_by: Option<String>,
+    created_at: String,
+    resolved_at: Option<String>,
+}
+
+impl ConflictRow {
+    fn into_conflict(self) -> Result<ApplyConflict> {
+        let created_at = chrono::DateTime::parse_from_rfc3339(&self.created_at)
+            .map_err(|e| AppError::Db(format!("invalid created_at: {e}")))?
+            .with_timezone(&Utc);
+        let resolved_at = self
+            .resolved_at
+            .as_deref()
+            .map(|s| {
+                chrono::DateTime::parse_from_rfc3339(s)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|e| AppError::Db(format!("invalid resolved_at: {e}")))
+            })
+            .transpose()?;
+
+        Ok(ApplyConflict {
+            id: self.id,
+            request_id: self.request_id,
+            session_id: self.session_id,
+            file_path: self.file_path,
+            kind: ConflictKind::parse(&self.kind)?,
+            detail: self.detail,
+            resolution: self
+                .resolution
+                .as_deref()
+                .map(ConflictResolution::parse)
+                .transpose()?,
+            resolved_by: self.resolved_by,
+            created_at,
+            resolved_at,
+        })
+    }
+}
+
+impl ConflictRepo {
+    /// Create a new repository instance.
+    #[must_use]
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Store a new conflict.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the insert fails.
+    pub async fn create(&self, conflict: &ApplyConflict) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO apply_conflict (id, request_id, session_id, file_path, kind, detail,
+                 resolution, resolved_by, created_at, resolved_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        )
+        .bind(&conflict.id)
+        .bind(&conflict.request_id)
+        .bind(&conflict.session_id)
+        .bind(&conflict.file_path)
+        .bind(conflict.kind.as_str())
+        .bind(&conflict.detail)
+        .bind(conflict.resolution.map(ConflictResolution::as_str))
+        .bind(&conflict.resolved_by)
+        .bind(conflict.created_at.to_rfc3339())
+        .bind(conflict.resolved_at.map(|at| at.to_rfc3339()))
+        .execute(self.db.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Fetch a conflict by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn get_by_id(&self, id: &str) -> Result<Option<ApplyConflict>> {
+        let row: Option<ConflictRow> = sqlx::query_as("SELECT * FROM apply_conflict WHERE id = ?1")
+            .bind(id)
+            .fetch_optional(self.db.as_ref())
+            .await?;
+        row.map(ConflictRow::into_conflict).transpose()
+    }
+
+    /// Record `resolution` by `resolved_by` at `at` on a pending conflict.
+    /// Returns `false` when the conflict does not exist or was already
+    /// resolved, so the first decision wins.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn resolve(
+        &self,
+        id: &str,
+        resolution: ConflictResolution,
+        resolved_by: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE apply_conflict SET resolution = ?1, resolved_by = ?2, resolved_at = ?3
+             WHERE id = ?4 AND resolution IS NULL",
+        )
+        .bind(resolution.as_str())
+        .bind(resolved_by)
+        .bind(at.to_rfc3339())
+        .bind(id)
+        .execute(self.db.as_ref())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Conflicts raised on the diffs of `request_id`, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_for_request(&self, request_id: &str) -> Result<Vec<ApplyConflict>> {
+        let rows: Vec<ConflictRow> = sqlx::query_as(
+            "SELECT * FROM apply_conflict WHERE request_id = ?1 ORDER BY created_at ASC",
+        )
+        .bind(request_id)
+        .fetch_all(self.db.as_ref())
+        .await?;
+        rows.into_iter().map(ConflictRow::into_conflict).collect()
+    }
+}
@@ -0,0 +1,147 @@
+//! Session context repository for `SQLite` persistence.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::models::session_context::ContextEntry;
+use crate::{AppError, Result};
+
+use super::db::Database;
+
+/// Repository for per-session key/value context entries.
+#[derive(Clone)]
+pub struct ContextRepo {
+    db: Arc<Database>,
+}
+
+/// Internal row struct for `SQLite` deserialization.
+#[derive(sqlx::FromRow)]
+struct ContextRow {
+    session_id: String,
+    key: String,
+    value: String,
+    updated_at: String,
+}
+
+impl ContextRow {
+    fn into_entry(self) -> Result<ContextEntry> {
+        let value = serde_json::from_str(&self.value)
+            .map_err(|e| AppError::Db(format!("invalid context value: {e}")))?;
+        let updated_at = chrono::DateTime::parse_from_rfc3339(&self.updated_at)
+            .map_err(|e| AppError::Db(format!("invalid updated_at: {e}")))?
+            .with_timezone(&Utc);
+
+        Ok(ContextEntry {
+            session_id: self.session_id,
+            key: self.key,
+            value,
+            updated_at,
+        })
+    }
+}
+
+impl ContextRepo {
+    /// Create a new repository instance.
+    #[must_use]
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Store `value` under `key` for a session, replacing any previous value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the value cannot be serialized or the
+    /// write fails.
+    pub async fn upsert(
+        &self,
+        session_id: &str,
+        key: &str,
+        value: &serde_json::Value,
+        at: DateTime<Utc>,
+    ) -> Result<()> {
+        let value = serde_json::to_string(value)
+            .map_err(|e| AppError::Db(format!("failed to serialize context value: {e}")))?;
+
+        sqlx::query(
+            "INSERT INTO session_context (session_id, key, value, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(session_id, key) DO UPDATE
+             SET value = excluded.value, updated_at = excluded.updated_at",
+        )
+        .bind(session_id)
+        .bind(key)
+        .bind(&value)
+        .bind(at.to_rfc3339())
+        .execute(self.db.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Fetch one entry of a session.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn get(&self, session_id: &str, key: &str) -> Result<Option<ContextEntry>> {
+        let row: Option<ContextRow> = sqlx::query_as(
+            "SELECT session_id, key, value, updated_at
+             FROM session_context
+             WHERE session_id = ?1 AND key = ?2",
+        )
+        .bind(session_id)
+        .bind(key)
+        .fetch_optional(self.db.as_ref())
+        .await?;
+
+        row.map(ContextRow::into_entry).transpose()
+    }
+
+    /// List every entry of a session, ordered by key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_for_session(&self, session_id: &str) -> Result<Vec<ContextEntry>> {
+        let rows: Vec<ContextRow> = sqlx::query_as(
+            "SELECT session_id, key, value, updated_at
+             FROM session_context
+             WHERE session_id = ?1
+             ORDER BY key ASC",
+        )
+        .bind(session_id)
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter().map(ContextRow::into_entry).collect()
+    }
+
+    /// Count the entries of a session.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn count_for_session(&self, session_id: &str) -> Result<u64> {
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM session_context WHERE session_id = ?1")
+                .bind(session_id)
+                .fetch_one(self.db.as_ref())
+                .await?;
+        Ok(u64::try_from(count).unwrap_or(0))
+    }
+
+    /// Delete one entry. Returns `false` when the key was not set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the delete fails.
+    pub async fn delete(&self, session_id: &str, key: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM session_context WHERE session_id = ?1 AND key = ?2")
+            .bind(session_id)
+            .bind(key)
+            .execute(self.db.as_ref())
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+}
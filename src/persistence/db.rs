@@ -63,3 +63,60 @@ pub async fn connect_memory() -> Result<Database> {
     schema::bootstrap_schema(&pool).await?;
     Ok(pool)
 }
+
+/// `candidate`, or a longer short ID for record `id` when `table` already
+/// has a row with `candidate`.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the lookup fails.
+pub(crate) async fn unused_short_id(
+    db: &Database,
+    table: &str,
+    prefix: &str,
+    id: &str,
+    candidate: &str,
+) -> Result<String> {
+    let sql = format!("SELECT COUNT(*) FROM {table} WHERE short_id = ?1");
+    let mut short_id = candidate.to_owned();
+    for extra in 1..=4 {
+        let taken: i64 = sqlx::query_scalar(&sql)
+            .bind(&short_id)
+            .fetch_one(db)
+            .await?;
+        if taken == 0 {
+            break;
+        }
+        short_id = crate::models::short_id::generate(prefix, id, extra);
+    }
+    Ok(short_id)
+}
+
+/// Write a fresh token to `watchdog_probe` and read it back, to check
+/// that the database still accepts writes.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the write or read fails, or reads back a
+/// different token.
+pub async fn probe(db: &Database) -> Result<()> {
+    let token = uuid::Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO watchdog_probe (id, token, checked_at) VALUES (1, ?1, ?2)
+         ON CONFLICT(id) DO UPDATE SET token = excluded.token, checked_at = excluded.checked_at",
+    )
+    .bind(&token)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(db)
+    .await?;
+    let read: String = sqlx::query_scalar("SELECT token FROM watchdog_probe WHERE id = 1")
+        .fetch_one(db)
+        .await?;
+    if read == token {
+        Ok(())
+    } else {
+        Err(AppError::Db(
+            "watchdog probe read back a different token".into(),
+        ))
+    }
+}
@@ -0,0 +1,216 @@
+//! Paired device repository for `SQLite` persistence.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use crate::models::device::PairedDevice;
+use crate::{AppError, Result};
+
+use super::db::Database;
+
+/// Repository for paired device records.
+#[derive(Clone)]
+pub struct DeviceRepo {
+    db: Arc<Database>,
+}
+
+/// Internal row struct for `SQLite` deserialization.
+#[derive(sqlx::FromRow)]
+struct DeviceRow {
+    id: String,
+    user_id: String,
+    label: Option<String>,
+    created_at: String,
+    paired_at: Option<String>,
+    expires_at: String,
+    last_seen_at: Option<String>,
+}
+
+/// Column list shared by every `SELECT` that maps into [`DeviceRow`].
+const COLUMNS: &str = "id, user_id, label, created_at, paired_at, expires_at, last_seen_at";
+
+fn parse_time(value: &str, column: &str) -> Result<DateTime<Utc>> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| AppError::Db(format!("invalid {column}: {e}")))
+}
+
+impl DeviceRow {
+    fn into_device(self) -> Result<PairedDevice> {
+        Ok(PairedDevice {
+            created_at: parse_time(&self.created_at, "created_at")?,
+            paired_at: self
+                .paired_at
+                .as_deref()
+                .map(|s| parse_time(s, "paired_at"))
+                .transpose()?,
+            expires_at: parse_time(&self.expires_at, "expires_at")?,
+            last_seen_at: self
+                .last_seen_at
+                .as_deref()
+                .map(|s| parse_time(s, "last_seen_at"))
+                .transpose()?,
+            id: self.id,
+            user_id: self.user_id,
+            label: self.label,
+        })
+    }
+}
+
+/// Fixed-width timestamp used for `expires_at`, so SQL string comparison
+/// orders instants correctly.
+fn time_str(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Micros, false)
+}
+
+impl DeviceRepo {
+    /// Create a new repository instance.
+    #[must_use]
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Store a pairing offer whose QR-code token hashes to `pairing_hash`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the insert fails.
+    pub async fn create_offer(&self, offer: &PairedDevice, pairing_hash: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO paired_device (id, user_id, pairing_hash, label, created_at, expires_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )
+        .bind(&offer.id)
+        .bind(&offer.user_id)
+        .bind(pairing_hash)
+        .bind(&offer.label)
+        .bind(time_str(offer.created_at))
+        .bind(time_str(offer.expires_at))
+        .execute(self.db.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// The unredeemed, unexpired offer for `pairing_hash`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn find_offer(
+        &self,
+        pairing_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<PairedDevice>> {
+        let row: Option<DeviceRow> = sqlx::query_as(&format!(
+            "SELECT {COLUMNS} FROM paired_device
+             WHERE pairing_hash = ?1 AND paired_at IS NULL AND expires_at > ?2"
+        ))
+        .bind(pairing_hash)
+        .bind(time_str(now))
+        .fetch_optional(self.db.as_ref())
+        .await?;
+        row.map(DeviceRow::into_device).transpose()
+    }
+
+    /// Redeem the offer for `pairing_hash`: it becomes a device identified
+    /// by `device_hash`, labelled `label`, valid until `expires_at`.
+    ///
+    /// Returns `None` when there is no such unredeemed, unexpired offer, so
+    /// a QR code pairs at most one browser.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn redeem(
+        &self,
+        pairing_hash: &str,
+        device_hash: &str,
+        label: Option<&str>,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Option<PairedDevice>> {
+        let row: Option<DeviceRow> = sqlx::query_as(&format!(
+            "UPDATE paired_device
+             SET pairing_hash = NULL, device_hash = ?2, label = ?3, paired_at = ?4,
+                 expires_at = ?5
+             WHERE pairing_hash = ?1 AND paired_at IS NULL AND expires_at > ?4
+             RETURNING {COLUMNS}"
+        ))
+        .bind(pairing_hash)
+        .bind(device_hash)
+        .bind(label)
+        .bind(time_str(now))
+        .bind(time_str(expires_at))
+        .fetch_optional(self.db.as_ref())
+        .await?;
+        row.map(DeviceRow::into_device).transpose()
+    }
+
+    /// The unexpired device whose cookie token hashes to `device_hash`,
+    /// recording `now` as its last use.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn authenticate(
+        &self,
+        device_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<PairedDevice>> {
+        let row: Option<DeviceRow> = sqlx::query_as(&format!(
+            "UPDATE paired_device SET last_seen_at = ?2
+             WHERE device_hash = ?1 AND expires_at > ?2
+             RETURNING {COLUMNS}"
+        ))
+        .bind(device_hash)
+        .bind(time_str(now))
+        .fetch_optional(self.db.as_ref())
+        .await?;
+        row.map(DeviceRow::into_device).transpose()
+    }
+
+    /// Paired, unexpired devices of `user_id`, most recently paired first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_for_user(
+        &self,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<PairedDevice>> {
+        let rows: Vec<DeviceRow> = sqlx::query_as(&format!(
+            "SELECT {COLUMNS} FROM paired_device
+             WHERE user_id = ?1 AND paired_at IS NOT NULL AND expires_at > ?2
+             ORDER BY paired_at DESC"
+        ))
+        .bind(user_id)
+        .bind(time_str(now))
+        .fetch_all(self.db.as_ref())
+        .await?;
+        rows.into_iter().map(DeviceRow::into_device).collect()
+    }
+
+    /// Delete `user_id`'s devices and offers whose ID starts with
+    /// `id_prefix` (with or without the `device:` prefix), or all of them
+    /// when `id_prefix` is `None`. Returns how many were removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the delete fails.
+    pub async fn revoke(&self, user_id: &str, id_prefix: Option<&str>) -> Result<u64> {
+        let pattern = id_prefix.map(|prefix| {
+            let prefix = prefix.strip_prefix("device:").unwrap_or(prefix);
+            format!("device:{}%", prefix.replace(['%', '_'], ""))
+        });
+        let result = sqlx::query(
+            "DELETE FROM paired_device WHERE user_id = ?1 AND (?2 IS NULL OR id LIKE ?2)",
+        )
+        .bind(user_id)
+        .bind(pattern)
+        .execute(self.db.as_ref())
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,174 @@
+//! Knowledge note repository for `SQLite` persistence.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::models::knowledge::{KnowledgeNote, KnowledgeStatus};
+use crate::{AppError, Result};
+
+use super::db::Database;
+
+/// Repository for shared knowledge notes.
+#[derive(Clone)]
+pub struct KnowledgeRepo {
+    db: Arc<Database>,
+}
+
+/// Internal row struct for `SQLite` deserialization.
+#[derive(sqlx::FromRow)]
+struct KnowledgeRow {
+    id: String,
+    title: String,
+    body: String,
+    tags: String,
+    proposed_by: String,
+    workspace_root: String,
+    status: String,
+    created_at: String,
+    decided_at: Option<String>,
+    decided_by: Option<String>,
+}
+
+/// Column list shared by every `SELECT` that maps into [`KnowledgeRow`].
+const COLUMNS: &str = "id, title, body, tags, proposed_by, workspace_root, status, \
+                       created_at, decided_at, decided_by";
+
+fn parse_status(s: &str) -> Result<KnowledgeStatus> {
+    match s {
+        "pending" => Ok(KnowledgeStatus::Pending),
+        "approved" => Ok(KnowledgeStatus::Approved),
+        "rejected" => Ok(KnowledgeStatus::Rejected),
+        other => Err(AppError::Db(format!("invalid knowledge status: {other}"))),
+    }
+}
+
+impl KnowledgeRow {
+    fn into_note(self) -> Result<KnowledgeNote> {
+        let tags = serde_json::from_str(&self.tags)
+            .map_err(|e| AppError::Db(format!("invalid knowledge tags: {e}")))?;
+        let created_at = chrono::DateTime::parse_from_rfc3339(&self.created_at)
+            .map_err(|e| AppError::Db(format!("invalid created_at: {e}")))?
+            .with_timezone(&Utc);
+        let decided_at = self
+            .decided_at
+            .map(|s| {
+                chrono::DateTime::parse_from_rfc3339(&s)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|e| AppError::Db(format!("invalid decided_at: {e}")))
+            })
+            .transpose()?;
+
+        Ok(KnowledgeNote {
+            id: self.id,
+            title: self.title,
+            body: self.body,
+            tags,
+            proposed_by: self.proposed_by,
+            workspace_root: self.workspace_root,
+            status: parse_status(&self.status)?,
+            created_at,
+            decided_at,
+            decided_by: self.decided_by,
+        })
+    }
+}
+
+impl KnowledgeRepo {
+    /// Create a new repository instance.
+    #[must_use]
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Insert a new knowledge note.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the tags cannot be serialized or the
+    /// insert fails.
+    pub async fn insert(&self, note: &KnowledgeNote) -> Result<()> {
+        let tags = serde_json::to_string(&note.tags)
+            .map_err(|e| AppError::Db(format!("failed to serialize knowledge tags: {e}")))?;
+
+        sqlx::query(
+            "INSERT INTO knowledge_note (id, title, body, tags, proposed_by, workspace_root,
+                                         status, created_at, decided_at, decided_by)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        )
+        .bind(&note.id)
+        .bind(&note.title)
+        .bind(&note.body)
+        .bind(&tags)
+        .bind(&note.proposed_by)
+        .bind(&note.workspace_root)
+        .bind(note.status.as_str())
+        .bind(note.created_at.to_rfc3339())
+        .bind(note.decided_at.map(|at| at.to_rfc3339()))
+        .bind(&note.decided_by)
+        .execute(self.db.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Fetch a note by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn get(&self, id: &str) -> Result<Option<KnowledgeNote>> {
+        let row: Option<KnowledgeRow> = sqlx::query_as(&format!(
+            "SELECT {COLUMNS} FROM knowledge_note WHERE id = ?1"
+        ))
+        .bind(id)
+        .fetch_optional(self.db.as_ref())
+        .await?;
+
+        row.map(KnowledgeRow::into_note).transpose()
+    }
+
+    /// List approved notes, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_approved(&self) -> Result<Vec<KnowledgeNote>> {
+        let rows: Vec<KnowledgeRow> = sqlx::query_as(&format!(
+            "SELECT {COLUMNS} FROM knowledge_note
+             WHERE status = 'approved'
+             ORDER BY created_at ASC, rowid ASC"
+        ))
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter().map(KnowledgeRow::into_note).collect()
+    }
+
+    /// Record the operator's decision on a pending note.
+    ///
+    /// Returns `false` when the note was already decided, so a second
+    /// click never overturns the first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn decide(
+        &self,
+        id: &str,
+        status: KnowledgeStatus,
+        decided_by: &str,
+        at: DateTime<Utc>,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE knowledge_note SET status = ?2, decided_by = ?3, decided_at = ?4
+             WHERE id = ?1 AND status = 'pending'",
+        )
+        .bind(id)
+        .bind(status.as_str())
+        .bind(decided_by)
+        .bind(at.to_rfc3339())
+        .execute(self.db.as_ref())
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+}
@@ -0,0 +1,230 @@
+//! Workspace path lock repository for `SQLite` persistence.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::models::path_lock::{LockGrant, LockStatus, PathLock};
+use crate::{AppError, Result};
+
+use super::db::Database;
+
+/// Repository for `acquire_lock` / `release_lock` records.
+#[derive(Clone)]
+pub struct LockRepo {
+    db: Arc<Database>,
+}
+
+/// Internal row struct for `SQLite` deserialization.
+#[derive(sqlx::FromRow)]
+struct LockRow {
+    id: String,
+    session_id: String,
+    workspace_root: String,
+    path: String,
+    status: String,
+    reason: Option<String>,
+    requested_at: String,
+    acquired_at: Option<String>,
+}
+
+/// Column list shared by every `SELECT` that maps into [`LockRow`].
+const COLUMNS: &str =
+    "id, session_id, workspace_root, path, status, reason, requested_at, acquired_at";
+
+/// Session statuses whose locks still count. Locks of terminated or
+/// interrupted sessions are swept the next time the workspace is checked.
+const LIVE_STATUSES: &str = "('created', 'active', 'paused')";
+
+impl LockRow {
+    fn into_lock(self) -> Result<PathLock> {
+        let status = LockStatus::parse(&self.status)?;
+        let requested_at = parse_time(&self.requested_at, "requested_at")?;
+        let acquired_at = self
+            .acquired_at
+            .as_deref()
+            .map(|s| parse_time(s, "acquired_at"))
+            .transpose()?;
+
+        Ok(PathLock {
+            id: self.id,
+            session_id: self.session_id,
+            workspace_root: self.workspace_root,
+            path: self.path,
+            status,
+            reason: self.reason,
+            requested_at,
+            acquired_at,
+        })
+    }
+}
+
+fn parse_time(value: &str, column: &str) -> Result<DateTime<Utc>> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| AppError::Db(format!("invalid {column}: {e}")))
+}
+
+impl LockRepo {
+    /// Create a new repository instance.
+    #[must_use]
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Insert a lock request, normally [`PathLock::queued`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the insert fails.
+    pub async fn enqueue(&self, lock: &PathLock) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO path_lock (id, session_id, workspace_root, path, status, reason, requested_at, acquired_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )
+        .bind(&lock.id)
+        .bind(&lock.session_id)
+        .bind(&lock.workspace_root)
+        .bind(&lock.path)
+        .bind(lock.status.as_str())
+        .bind(&lock.reason)
+        .bind(lock.requested_at.to_rfc3339())
+        .bind(lock.acquired_at.map(|at| at.to_rfc3339()))
+        .execute(self.db.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Grant the queued request `id` unless another live session holds an
+    /// overlapping path or asked for one earlier.
+    ///
+    /// Runs in one transaction, so two requests for the same path are never
+    /// both granted. Locks of sessions that are no longer live are removed
+    /// first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` if the request was removed, or
+    /// `AppError::Db` if a query fails.
+    pub async fn try_grant(&self, id: &str) -> Result<LockGrant> {
+        let mut tx = self.db.begin().await?;
+
+        let mine: LockRow =
+            sqlx::query_as(&format!("SELECT {COLUMNS} FROM path_lock WHERE id = ?1"))
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("lock request {id} not found")))?;
+        let mut mine = mine.into_lock()?;
+        if mine.status == LockStatus::Held {
+            return Ok(LockGrant::Acquired(mine));
+        }
+
+        sqlx::query(&format!(
+            "DELETE FROM path_lock
+             WHERE workspace_root = ?1
+               AND session_id NOT IN (SELECT id FROM session WHERE status IN {LIVE_STATUSES})"
+        ))
+        .bind(&mine.workspace_root)
+        .execute(&mut *tx)
+        .await?;
+
+        let rows: Vec<LockRow> = sqlx::query_as(&format!(
+            "SELECT {COLUMNS} FROM path_lock
+             WHERE workspace_root = ?1 AND session_id <> ?2
+             ORDER BY requested_at ASC, rowid ASC"
+        ))
+        .bind(&mine.workspace_root)
+        .bind(&mine.session_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        for row in rows {
+            let other = row.into_lock()?;
+            let ahead = other.status == LockStatus::Held || other.requested_at < mine.requested_at;
+            if ahead && other.overlaps(&mine.path) {
+                tx.commit().await?;
+                return Ok(LockGrant::Blocked(other));
+            }
+        }
+
+        let now = Utc::now();
+        sqlx::query("UPDATE path_lock SET status = 'held', acquired_at = ?2 WHERE id = ?1")
+            .bind(id)
+            .bind(now.to_rfc3339())
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        mine.status = LockStatus::Held;
+        mine.acquired_at = Some(now);
+        Ok(LockGrant::Acquired(mine))
+    }
+
+    /// Locks `session_id` holds, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn held_by(&self, session_id: &str) -> Result<Vec<PathLock>> {
+        let rows: Vec<LockRow> = sqlx::query_as(&format!(
+            "SELECT {COLUMNS} FROM path_lock
+             WHERE session_id = ?1 AND status = 'held'
+             ORDER BY acquired_at ASC, rowid ASC"
+        ))
+        .bind(session_id)
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter().map(LockRow::into_lock).collect()
+    }
+
+    /// Held and queued locks in `workspace_root`, in request order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_for_workspace(&self, workspace_root: &str) -> Result<Vec<PathLock>> {
+        let rows: Vec<LockRow> = sqlx::query_as(&format!(
+            "SELECT {COLUMNS} FROM path_lock
+             WHERE workspace_root = ?1
+             ORDER BY requested_at ASC, rowid ASC"
+        ))
+        .bind(workspace_root)
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter().map(LockRow::into_lock).collect()
+    }
+
+    /// Remove one lock or queued request. Returns whether it existed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the delete fails.
+    pub async fn remove(&self, id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM path_lock WHERE id = ?1")
+            .bind(id)
+            .execute(self.db.as_ref())
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Release the locks `session_id` holds on `path`, or all of them when
+    /// `path` is `None`. Returns the released locks.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if a query fails.
+    pub async fn release(&self, session_id: &str, path: Option<&str>) -> Result<Vec<PathLock>> {
+        let released: Vec<PathLock> = self
+            .held_by(session_id)
+            .await?
+            .into_iter()
+            .filter(|lock| path.is_none_or(|path| lock.path == path))
+            .collect();
+        for lock in &released {
+            self.remove(&lock.id).await?;
+        }
+        Ok(released)
+    }
+}
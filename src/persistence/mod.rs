@@ -2,13 +2,24 @@
 
 pub mod approval_repo;
 pub mod checkpoint_repo;
+pub mod conflict_repo;
+pub mod context_repo;
 pub mod db;
+pub mod device_repo;
 pub mod inbox_repo;
 pub mod intercom_queue_repo;
+pub mod knowledge_repo;
+pub mod lock_repo;
+pub mod modal_context_repo;
+pub mod preferences_repo;
 pub mod prompt_repo;
+pub mod relay_repo;
 pub mod retention;
+pub mod run_repo;
 pub mod schema;
+pub mod session_note_repo;
 pub mod session_repo;
+pub mod snapshot_repo;
 pub mod stall_repo;
 pub mod steering_repo;
 
@@ -0,0 +1,64 @@
+//! Modal context repository for `SQLite` persistence.
+//!
+//! Stores the `(channel_id, message_ts)` of the message a Slack modal was
+//! opened from, keyed by the modal `callback_id`, so the submission can
+//! still update that message after a restart.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use crate::Result;
+
+use super::db::Database;
+
+/// Repository for pending modal contexts.
+#[derive(Clone)]
+pub struct ModalContextRepo {
+    db: Arc<Database>,
+}
+
+impl ModalContextRepo {
+    /// Create a new repository instance.
+    #[must_use]
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Store the message coordinates of the modal `callback_id`, replacing
+    /// any earlier ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the insert fails.
+    pub async fn save(&self, callback_id: &str, channel_id: &str, message_ts: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO modal_context (callback_id, channel_id, message_ts, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+        )
+        .bind(callback_id)
+        .bind(channel_id)
+        .bind(message_ts)
+        .bind(Utc::now().to_rfc3339())
+        .execute(self.db.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Remove and return the `(channel_id, message_ts)` of the modal
+    /// `callback_id`, or `None` when none is stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the delete fails.
+    pub async fn take(&self, callback_id: &str) -> Result<Option<(String, String)>> {
+        let row: Option<(String, String)> = sqlx::query_as(
+            "DELETE FROM modal_context WHERE callback_id = ?1
+             RETURNING channel_id, message_ts",
+        )
+        .bind(callback_id)
+        .fetch_optional(self.db.as_ref())
+        .await?;
+        Ok(row)
+    }
+}
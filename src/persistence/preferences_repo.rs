@@ -0,0 +1,121 @@
+//! Operator notification preferences repository for `SQLite` persistence.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use crate::config::SlackDetailLevel;
+use crate::models::preferences::{NotificationDelivery, QuietHours, UserPreferences};
+use crate::{AppError, Result};
+
+use super::db::Database;
+
+/// Repository for `/intercom prefs` settings, one row per operator.
+#[derive(Clone)]
+pub struct PreferencesRepo {
+    db: Arc<Database>,
+}
+
+/// Internal row struct for `SQLite` deserialization.
+#[derive(sqlx::FromRow)]
+struct PreferencesRow {
+    user_id: String,
+    detail_level: Option<String>,
+    delivery: String,
+    quiet_hours: Option<String>,
+    updated_at: String,
+}
+
+impl PreferencesRow {
+    fn into_preferences(self) -> Result<UserPreferences> {
+        let detail_level = self
+            .detail_level
+            .as_deref()
+            .map(SlackDetailLevel::parse)
+            .transpose()
+            .map_err(|e| AppError::Db(format!("invalid detail_level: {e}")))?;
+        let delivery = NotificationDelivery::parse(&self.delivery)
+            .map_err(|e| AppError::Db(format!("invalid delivery: {e}")))?;
+        let quiet_hours = self
+            .quiet_hours
+            .as_deref()
+            .map(QuietHours::parse)
+            .transpose()
+            .map_err(|e| AppError::Db(format!("invalid quiet_hours: {e}")))?;
+        let updated_at = chrono::DateTime::parse_from_rfc3339(&self.updated_at)
+            .map_err(|e| AppError::Db(format!("invalid updated_at: {e}")))?
+            .with_timezone(&Utc);
+
+        Ok(UserPreferences {
+            user_id: self.user_id,
+            detail_level,
+            delivery,
+            quiet_hours,
+            updated_at,
+        })
+    }
+}
+
+impl PreferencesRepo {
+    /// Create a new repository instance.
+    #[must_use]
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Fetch the preferences of `user_id`, if they set any.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn get(&self, user_id: &str) -> Result<Option<UserPreferences>> {
+        let row: Option<PreferencesRow> = sqlx::query_as(
+            "SELECT user_id, detail_level, delivery, quiet_hours, updated_at
+             FROM user_preferences
+             WHERE user_id = ?1",
+        )
+        .bind(user_id)
+        .fetch_optional(self.db.as_ref())
+        .await?;
+
+        row.map(PreferencesRow::into_preferences).transpose()
+    }
+
+    /// Store `prefs`, replacing the operator's previous preferences.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the write fails.
+    pub async fn upsert(&self, prefs: &UserPreferences) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO user_preferences (user_id, detail_level, delivery, quiet_hours, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(user_id) DO UPDATE
+             SET detail_level = excluded.detail_level,
+                 delivery = excluded.delivery,
+                 quiet_hours = excluded.quiet_hours,
+                 updated_at = excluded.updated_at",
+        )
+        .bind(&prefs.user_id)
+        .bind(prefs.detail_level.map(SlackDetailLevel::as_str))
+        .bind(prefs.delivery.as_str())
+        .bind(prefs.quiet_hours.map(|quiet| quiet.to_string()))
+        .bind(prefs.updated_at.to_rfc3339())
+        .execute(self.db.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Remove the preferences of `user_id`. Returns whether any were set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the delete fails.
+    pub async fn delete(&self, user_id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM user_preferences WHERE user_id = ?1")
+            .bind(user_id)
+            .execute(self.db.as_ref())
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -27,6 +27,7 @@ struct PromptRow {
     decision: Option<String>,
     instruction: Option<String>,
     slack_ts: Option<String>,
+    thread_ts: Option<String>,
     created_at: String,
 }
 
@@ -49,6 +50,7 @@ impl PromptRow {
             decision,
             instruction: self.instruction,
             slack_ts: self.slack_ts,
+            thread_ts: self.thread_ts,
             created_at,
         })
     }
@@ -65,12 +67,7 @@ fn parse_prompt_type(s: &str) -> Result<PromptType> {
 }
 
 fn prompt_type_str(t: PromptType) -> &'static str {
-    match t {
-        PromptType::Continuation => "continuation",
-        PromptType::Clarification => "clarification",
-        PromptType::ErrorRecovery => "error_recovery",
-        PromptType::ResourceWarning => "resource_warning",
-    }
+    t.as_str()
 }
 
 fn parse_decision(s: &str) -> Result<PromptDecision> {
@@ -109,8 +106,9 @@ impl PromptRepo {
 
         sqlx::query(
             "INSERT INTO continuation_prompt (id, session_id, prompt_text, prompt_type,
-             elapsed_seconds, actions_taken, decision, instruction, slack_ts, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+             elapsed_seconds, actions_taken, decision, instruction, slack_ts, thread_ts,
+             created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
         )
         .bind(&prompt.id)
         .bind(&prompt.session_id)
@@ -121,6 +119,7 @@ impl PromptRepo {
         .bind(decision)
         .bind(&prompt.instruction)
         .bind(&prompt.slack_ts)
+        .bind(&prompt.thread_ts)
         .bind(&created_at)
         .execute(self.db.as_ref())
         .await?;
@@ -188,6 +187,52 @@ impl PromptRepo {
         Ok(())
     }
 
+    /// Record `decision` on a prompt that has none yet.
+    ///
+    /// Returns `false`, changing nothing, when the prompt is missing or was
+    /// already answered, so of two racing answers exactly one wins.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn decide(
+        &self,
+        id: &str,
+        decision: PromptDecision,
+        instruction: Option<String>,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE continuation_prompt SET decision = ?1, instruction = ?2 \
+             WHERE id = ?3 AND decision IS NULL",
+        )
+        .bind(decision_str(decision))
+        .bind(&instruction)
+        .bind(id)
+        .execute(self.db.as_ref())
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Record where a prompt was posted in Slack.
+    ///
+    /// `slack_ts` is the prompt message itself; `thread_ts` is the thread
+    /// its follow-ups go to.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn set_slack_anchor(&self, id: &str, slack_ts: &str, thread_ts: &str) -> Result<()> {
+        sqlx::query("UPDATE continuation_prompt SET slack_ts = ?1, thread_ts = ?2 WHERE id = ?3")
+            .bind(slack_ts)
+            .bind(thread_ts)
+            .bind(id)
+            .execute(self.db.as_ref())
+            .await?;
+
+        Ok(())
+    }
+
     /// List all pending prompts (no decision yet) across sessions.
     ///
     /// # Errors
@@ -202,6 +247,22 @@ impl PromptRepo {
         rows.into_iter().map(PromptRow::into_prompt).collect()
     }
 
+    /// List every prompt forwarded by a session, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_for_session(&self, session_id: &str) -> Result<Vec<ContinuationPrompt>> {
+        let rows: Vec<PromptRow> = sqlx::query_as(
+            "SELECT * FROM continuation_prompt WHERE session_id = ?1 ORDER BY created_at ASC",
+        )
+        .bind(session_id)
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter().map(PromptRow::into_prompt).collect()
+    }
+
     /// Rebind a crashed session's *undecided* prompts to a resumed session so
     /// mid-task prompt state survives a respawn (F.3-T3).
     ///
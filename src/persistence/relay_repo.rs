@@ -0,0 +1,159 @@
+//! Relay message repository for `SQLite` persistence.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::models::relay::RelayMessage;
+use crate::{AppError, Result};
+
+use super::db::Database;
+
+/// Repository for relay message records.
+#[derive(Clone)]
+pub struct RelayRepo {
+    db: Arc<Database>,
+}
+
+/// Internal row struct for `SQLite` deserialization.
+#[derive(sqlx::FromRow)]
+struct RelayRow {
+    id: String,
+    from_session_id: String,
+    to_session_id: String,
+    topic: Option<String>,
+    payload: String,
+    created_at: String,
+    delivered_at: Option<String>,
+}
+
+/// Column list shared by every `SELECT` that maps into [`RelayRow`].
+const COLUMNS: &str =
+    "id, from_session_id, to_session_id, topic, payload, created_at, delivered_at";
+
+impl RelayRow {
+    fn into_relay(self) -> Result<RelayMessage> {
+        let payload = serde_json::from_str(&self.payload)
+            .map_err(|e| AppError::Db(format!("invalid relay payload: {e}")))?;
+        let created_at = chrono::DateTime::parse_from_rfc3339(&self.created_at)
+            .map_err(|e| AppError::Db(format!("invalid created_at: {e}")))?
+            .with_timezone(&Utc);
+        let delivered_at = self
+            .delivered_at
+            .map(|s| {
+                chrono::DateTime::parse_from_rfc3339(&s)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|e| AppError::Db(format!("invalid delivered_at: {e}")))
+            })
+            .transpose()?;
+
+        Ok(RelayMessage {
+            id: self.id,
+            from_session_id: self.from_session_id,
+            to_session_id: self.to_session_id,
+            topic: self.topic,
+            payload,
+            created_at,
+            delivered_at,
+        })
+    }
+}
+
+impl RelayRepo {
+    /// Create a new repository instance.
+    #[must_use]
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Insert a new relay message record.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the payload cannot be serialized or the
+    /// insert fails.
+    pub async fn insert(&self, msg: &RelayMessage) -> Result<RelayMessage> {
+        let payload = serde_json::to_string(&msg.payload)
+            .map_err(|e| AppError::Db(format!("failed to serialize relay payload: {e}")))?;
+
+        sqlx::query(
+            "INSERT INTO relay_message (id, from_session_id, to_session_id, topic, payload, created_at, delivered_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )
+        .bind(&msg.id)
+        .bind(&msg.from_session_id)
+        .bind(&msg.to_session_id)
+        .bind(&msg.topic)
+        .bind(&payload)
+        .bind(msg.created_at.to_rfc3339())
+        .bind(msg.delivered_at.map(|at| at.to_rfc3339()))
+        .execute(self.db.as_ref())
+        .await?;
+
+        Ok(msg.clone())
+    }
+
+    /// Fetch pending messages addressed to a session, oldest first,
+    /// optionally restricted to one `topic`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn fetch_pending(
+        &self,
+        to_session_id: &str,
+        topic: Option<&str>,
+    ) -> Result<Vec<RelayMessage>> {
+        let rows: Vec<RelayRow> = sqlx::query_as(&format!(
+            "SELECT {COLUMNS}
+             FROM relay_message
+             WHERE to_session_id = ?1 AND delivered_at IS NULL
+               AND (?2 IS NULL OR topic = ?2)
+             ORDER BY created_at ASC, rowid ASC"
+        ))
+        .bind(to_session_id)
+        .bind(topic)
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter().map(RelayRow::into_relay).collect()
+    }
+
+    /// List every message a session sent or received, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_for_session(&self, session_id: &str) -> Result<Vec<RelayMessage>> {
+        let rows: Vec<RelayRow> = sqlx::query_as(&format!(
+            "SELECT {COLUMNS}
+             FROM relay_message
+             WHERE from_session_id = ?1 OR to_session_id = ?1
+             ORDER BY created_at ASC, rowid ASC"
+        ))
+        .bind(session_id)
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter().map(RelayRow::into_relay).collect()
+    }
+
+    /// Mark a relay message as delivered at `at`.
+    ///
+    /// Returns `false` when the message was already delivered, so two
+    /// concurrent receivers never both claim it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn mark_delivered(&self, id: &str, at: DateTime<Utc>) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE relay_message SET delivered_at = ?2 WHERE id = ?1 AND delivered_at IS NULL",
+        )
+        .bind(id)
+        .bind(at.to_rfc3339())
+        .execute(self.db.as_ref())
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+}
@@ -2,8 +2,11 @@
 //!
 //! Runs as a background task deleting children first
 //! (approval requests, checkpoints, prompts, stall alerts),
-//! then terminated sessions older than `retention_days`.
+//! then terminated sessions older than `retention_days`, and expired
+//! paired devices. Each run also compresses and prunes log files per
+//! `[logging]` (see [`crate::audit::rotation::prune`]).
 
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -12,6 +15,8 @@ use tokio_util::sync::CancellationToken;
 use tracing::info;
 
 use super::db::Database;
+use crate::audit::rotation;
+use crate::config::LoggingConfig;
 use crate::Result;
 
 const PURGE_INTERVAL: Duration = Duration::from_hours(1);
@@ -19,11 +24,15 @@ const PURGE_INTERVAL: Duration = Duration::from_hours(1);
 /// Spawn the retention purge background task.
 ///
 /// The first purge runs after `PURGE_INTERVAL` (1 hour), not immediately
-/// on startup.  Subsequent purges repeat at the same interval.
+/// on startup.  Subsequent purges repeat at the same interval. Each run
+/// also prunes the audit log directory `log_dir` and the tracing log
+/// according to `logging`.
 #[must_use]
 pub fn spawn_retention_task(
     db: Arc<Database>,
     retention_days: u32,
+    log_dir: PathBuf,
+    logging: LoggingConfig,
     cancel: CancellationToken,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
@@ -39,6 +48,16 @@ pub fn spawn_retention_task(
                     if let Err(err) = purge(&db, retention_days).await {
                         tracing::error!(?err, "retention purge failed");
                     }
+                    let (log_dir, logging) = (log_dir.clone(), logging.clone());
+                    match tokio::task::spawn_blocking(move || {
+                        rotation::prune(&log_dir, &logging, chrono::Utc::now())
+                    })
+                    .await
+                    {
+                        Ok(0) => {}
+                        Ok(count) => info!(count, "pruned expired log files"),
+                        Err(err) => tracing::error!(%err, "log pruning task failed"),
+                    }
                 }
             }
         }
@@ -49,12 +68,15 @@ pub fn spawn_retention_task(
 /// records.
 ///
 /// Deletion order (children before parent): `stall_alert` → `checkpoint` →
-/// `continuation_prompt` → `approval_request` → `steering_message` →
-/// `task_inbox` (by age) → `session`.
+/// `continuation_prompt` → `approval_message` → `approval_request` →
+/// `steering_message` → `relay_message` → `session_context` → `session_note` →
+/// `path_lock` → `apply_conflict` → `task_inbox` and `modal_context` (by age) → `session` →
+/// ended runs left without sessions.
 ///
 /// # Errors
 ///
 /// Returns an error if any of the delete queries fail.
+#[allow(clippy::too_many_lines)] // One delete per child table, in dependency order.
 pub async fn purge(db: &Database, retention_days: u32) -> Result<()> {
     let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(retention_days));
     let cutoff_str = cutoff.to_rfc3339();
@@ -84,6 +106,15 @@ pub async fn purge(db: &Database, retention_days: u32) -> Result<()> {
     .execute(db)
     .await?;
 
+    sqlx::query(
+        "DELETE FROM approval_message WHERE request_id IN \
+         (SELECT id FROM approval_request WHERE session_id IN \
+         (SELECT id FROM session WHERE terminated_at IS NOT NULL AND terminated_at < ?1))",
+    )
+    .bind(&cutoff_str)
+    .execute(db)
+    .await?;
+
     sqlx::query(
         "DELETE FROM approval_request WHERE session_id IN \
          (SELECT id FROM session WHERE terminated_at IS NOT NULL AND terminated_at < ?1)",
@@ -101,6 +132,49 @@ pub async fn purge(db: &Database, retention_days: u32) -> Result<()> {
     .execute(db)
     .await?;
 
+    // Relay messages go once both ends of the exchange have expired.
+    sqlx::query(
+        "DELETE FROM relay_message WHERE from_session_id IN \
+         (SELECT id FROM session WHERE terminated_at IS NOT NULL AND terminated_at < ?1) \
+         AND to_session_id IN \
+         (SELECT id FROM session WHERE terminated_at IS NOT NULL AND terminated_at < ?1)",
+    )
+    .bind(&cutoff_str)
+    .execute(db)
+    .await?;
+
+    sqlx::query(
+        "DELETE FROM session_context WHERE session_id IN \
+         (SELECT id FROM session WHERE terminated_at IS NOT NULL AND terminated_at < ?1)",
+    )
+    .bind(&cutoff_str)
+    .execute(db)
+    .await?;
+
+    sqlx::query(
+        "DELETE FROM session_note WHERE session_id IN \
+         (SELECT id FROM session WHERE terminated_at IS NOT NULL AND terminated_at < ?1)",
+    )
+    .bind(&cutoff_str)
+    .execute(db)
+    .await?;
+
+    sqlx::query(
+        "DELETE FROM path_lock WHERE session_id IN \
+         (SELECT id FROM session WHERE terminated_at IS NOT NULL AND terminated_at < ?1)",
+    )
+    .bind(&cutoff_str)
+    .execute(db)
+    .await?;
+
+    sqlx::query(
+        "DELETE FROM apply_conflict WHERE session_id IN \
+         (SELECT id FROM session WHERE terminated_at IS NOT NULL AND terminated_at < ?1)",
+    )
+    .bind(&cutoff_str)
+    .execute(db)
+    .await?;
+
     // Task inbox items are not session-scoped, so purge by created_at (T077).
     // Purge all items older than the cutoff regardless of consumed status —
     // unconsumed tasks older than the retention window are stale and should
@@ -110,6 +184,20 @@ pub async fn purge(db: &Database, retention_days: u32) -> Result<()> {
         .execute(db)
         .await?;
 
+    // Contexts of modals never submitted or dismissed (the notice can be
+    // lost) are not session-scoped either.
+    sqlx::query("DELETE FROM modal_context WHERE created_at < ?1")
+        .bind(&cutoff_str)
+        .execute(db)
+        .await?;
+
+    // Expired pairing offers and paired devices can never authenticate
+    // again; they are unrelated to sessions.
+    sqlx::query("DELETE FROM paired_device WHERE expires_at <= ?1")
+        .bind(chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Micros, false))
+        .execute(db)
+        .await?;
+
     // Parent last.
     let result =
         sqlx::query("DELETE FROM session WHERE terminated_at IS NOT NULL AND terminated_at < ?1")
@@ -122,5 +210,15 @@ pub async fn purge(db: &Database, retention_days: u32) -> Result<()> {
         info!(count = purged, "purged expired sessions and child records");
     }
 
+    // Runs go once they have ended past the cutoff and none of their
+    // sessions are left.
+    sqlx::query(
+        "DELETE FROM run WHERE ended_at IS NOT NULL AND ended_at < ?1 \
+         AND id NOT IN (SELECT run_id FROM session WHERE run_id IS NOT NULL)",
+    )
+    .bind(&cutoff_str)
+    .execute(db)
+    .await?;
+
     Ok(())
 }
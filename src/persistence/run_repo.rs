@@ -0,0 +1,161 @@
+//! Run repository for `SQLite` persistence.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::models::run::Run;
+use crate::{AppError, Result};
+
+use super::db::Database;
+
+/// Repository for the named runs that group sessions.
+#[derive(Clone)]
+pub struct RunRepo {
+    db: Arc<Database>,
+}
+
+/// Internal row struct for `SQLite` deserialization.
+#[derive(sqlx::FromRow)]
+struct RunRow {
+    id: String,
+    name: String,
+    owner_user_id: String,
+    channel_id: Option<String>,
+    started_at: String,
+    ended_at: Option<String>,
+}
+
+impl RunRow {
+    fn into_run(self) -> Result<Run> {
+        let started_at = chrono::DateTime::parse_from_rfc3339(&self.started_at)
+            .map_err(|e| AppError::Db(format!("invalid started_at: {e}")))?
+            .with_timezone(&Utc);
+        let ended_at = self
+            .ended_at
+            .as_deref()
+            .map(|s| {
+                chrono::DateTime::parse_from_rfc3339(s)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|e| AppError::Db(format!("invalid ended_at: {e}")))
+            })
+            .transpose()?;
+
+        Ok(Run {
+            id: self.id,
+            name: self.name,
+            owner_user_id: self.owner_user_id,
+            channel_id: self.channel_id,
+            started_at,
+            ended_at,
+        })
+    }
+}
+
+impl RunRepo {
+    /// Create a new repository instance.
+    #[must_use]
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Store a new run.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the insert fails, including when the owner
+    /// already has an active run.
+    pub async fn create(&self, run: &Run) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO run (id, name, owner_user_id, channel_id, started_at, ended_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )
+        .bind(&run.id)
+        .bind(&run.name)
+        .bind(&run.owner_user_id)
+        .bind(&run.channel_id)
+        .bind(run.started_at.to_rfc3339())
+        .bind(run.ended_at.map(|at| at.to_rfc3339()))
+        .execute(self.db.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Fetch a run by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn get_by_id(&self, id: &str) -> Result<Option<Run>> {
+        let row: Option<RunRow> = sqlx::query_as("SELECT * FROM run WHERE id = ?1")
+            .bind(id)
+            .fetch_optional(self.db.as_ref())
+            .await?;
+        row.map(RunRow::into_run).transpose()
+    }
+
+    /// The active run of `owner_user_id`, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn active_for(&self, owner_user_id: &str) -> Result<Option<Run>> {
+        let row: Option<RunRow> =
+            sqlx::query_as("SELECT * FROM run WHERE owner_user_id = ?1 AND ended_at IS NULL")
+                .bind(owner_user_id)
+                .fetch_optional(self.db.as_ref())
+                .await?;
+        row.map(RunRow::into_run).transpose()
+    }
+
+    /// The most recently started run of `owner_user_id`, active or not.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn latest_for(&self, owner_user_id: &str) -> Result<Option<Run>> {
+        let row: Option<RunRow> = sqlx::query_as(
+            "SELECT * FROM run WHERE owner_user_id = ?1 ORDER BY started_at DESC LIMIT 1",
+        )
+        .bind(owner_user_id)
+        .fetch_optional(self.db.as_ref())
+        .await?;
+        row.map(RunRow::into_run).transpose()
+    }
+
+    /// The run whose ID starts with `reference`, or else the most recent
+    /// run named `reference` (case-insensitive).
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn find(&self, reference: &str) -> Result<Option<Run>> {
+        let row: Option<RunRow> = sqlx::query_as(
+            "SELECT * FROM run WHERE id LIKE ?1 || '%' OR name = ?2 COLLATE NOCASE
+             ORDER BY id LIKE ?1 || '%' DESC, started_at DESC LIMIT 1",
+        )
+        .bind(reference.to_ascii_lowercase())
+        .bind(reference)
+        .fetch_optional(self.db.as_ref())
+        .await?;
+        row.map(RunRow::into_run).transpose()
+    }
+
+    /// Mark a run ended at `at`. Returns the ended run, or `None` if it
+    /// does not exist or already ended.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn end(&self, id: &str, at: DateTime<Utc>) -> Result<Option<Run>> {
+        let result = sqlx::query("UPDATE run SET ended_at = ?1 WHERE id = ?2 AND ended_at IS NULL")
+            .bind(at.to_rfc3339())
+            .bind(id)
+            .execute(self.db.as_ref())
+            .await?;
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        self.get_by_id(id).await
+    }
+}
@@ -35,12 +35,13 @@ async fn add_column_if_missing(
 
 /// Apply column migrations for the `session` table.
 ///
-/// Adds the six new columns introduced in feature 005 using idempotent
+/// Adds the columns introduced since feature 005 using idempotent
 /// `PRAGMA table_info` checks. Safe to call on every server startup.
 ///
 /// # Errors
 ///
 /// Returns `AppError::Db` if any check or migration fails.
+#[allow(clippy::too_many_lines)] // One idempotent ALTER per added column.
 async fn migrate_session_columns(pool: &SqlitePool) -> Result<()> {
     add_column_if_missing(
         pool,
@@ -106,9 +107,115 @@ async fn migrate_session_columns(pool: &SqlitePool) -> Result<()> {
     )
     .await?;
 
+    add_column_if_missing(
+        pool,
+        "session",
+        "deadline",
+        "ALTER TABLE session ADD COLUMN deadline TEXT",
+    )
+    .await?;
+
+    add_column_if_missing(
+        pool,
+        "session",
+        "wrap_up_sent",
+        "ALTER TABLE session ADD COLUMN wrap_up_sent INTEGER NOT NULL DEFAULT 0",
+    )
+    .await?;
+
+    add_column_if_missing(
+        pool,
+        "session",
+        "issue_ref",
+        "ALTER TABLE session ADD COLUMN issue_ref TEXT",
+    )
+    .await?;
+
+    add_column_if_missing(
+        pool,
+        "session",
+        "parent_session_id",
+        "ALTER TABLE session ADD COLUMN parent_session_id TEXT",
+    )
+    .await?;
+
+    add_column_if_missing(
+        pool,
+        "session",
+        "muted",
+        "ALTER TABLE session ADD COLUMN muted INTEGER NOT NULL DEFAULT 0",
+    )
+    .await?;
+
+    add_column_if_missing(
+        pool,
+        "session",
+        "muted_until",
+        "ALTER TABLE session ADD COLUMN muted_until TEXT",
+    )
+    .await?;
+
+    add_column_if_missing(
+        pool,
+        "session",
+        "deleted_at",
+        "ALTER TABLE session ADD COLUMN deleted_at TEXT",
+    )
+    .await?;
+
+    add_column_if_missing(
+        pool,
+        "session",
+        "tags",
+        "ALTER TABLE session ADD COLUMN tags TEXT",
+    )
+    .await?;
+
+    add_column_if_missing(
+        pool,
+        "session",
+        "run_id",
+        "ALTER TABLE session ADD COLUMN run_id TEXT",
+    )
+    .await?;
+
+    add_column_if_missing(
+        pool,
+        "session",
+        "client_name",
+        "ALTER TABLE session ADD COLUMN client_name TEXT",
+    )
+    .await?;
+
+    add_column_if_missing(
+        pool,
+        "session",
+        "client_version",
+        "ALTER TABLE session ADD COLUMN client_version TEXT",
+    )
+    .await?;
+
+    add_column_if_missing(
+        pool,
+        "session",
+        "env",
+        "ALTER TABLE session ADD COLUMN env TEXT",
+    )
+    .await?;
+
+    add_column_if_missing(
+        pool,
+        "session",
+        "max_duration_seconds",
+        "ALTER TABLE session ADD COLUMN max_duration_seconds INTEGER",
+    )
+    .await?;
+
     sqlx::raw_sql(
         "CREATE INDEX IF NOT EXISTS idx_session_channel ON session(channel_id, status);
-         CREATE INDEX IF NOT EXISTS idx_session_channel_thread ON session(channel_id, thread_ts);",
+         CREATE INDEX IF NOT EXISTS idx_session_channel_thread ON session(channel_id, thread_ts);
+         CREATE INDEX IF NOT EXISTS idx_session_status_updated ON session(status, updated_at);
+         CREATE INDEX IF NOT EXISTS idx_session_run ON session(run_id);",
     )
     .execute(pool)
     .await?;
@@ -123,6 +230,7 @@ async fn migrate_session_columns(pool: &SqlitePool) -> Result<()> {
 /// # Errors
 ///
 /// Returns `AppError::Db` if any DDL statement fails.
+#[allow(clippy::too_many_lines)] // Mostly the base DDL literal.
 pub async fn bootstrap_schema(pool: &SqlitePool) -> Result<()> {
     let ddl = r"
 CREATE TABLE IF NOT EXISTS session (
@@ -199,10 +307,12 @@ CREATE TABLE IF NOT EXISTS steering_message (
     session_id      TEXT NOT NULL,
     channel_id      TEXT,
     message         TEXT NOT NULL,
-    source          TEXT NOT NULL CHECK(source IN ('slack','ipc')),
+    source          TEXT NOT NULL CHECK(source IN ('slack','ipc','subtask')),
     created_at      TEXT NOT NULL,
     consumed        INTEGER NOT NULL DEFAULT 0,
-    origin_session_id TEXT
+    origin_session_id TEXT,
+    priority        TEXT NOT NULL DEFAULT 'normal' CHECK(priority IN ('normal','interrupt')),
+    expires_at      TEXT
 );
 
 CREATE TABLE IF NOT EXISTS task_inbox (
@@ -225,6 +335,293 @@ CREATE INDEX IF NOT EXISTS idx_inbox_channel_consumed ON task_inbox(channel_id,
     sqlx::raw_sql(ddl).execute(pool).await?;
     migrate_session_columns(pool).await?;
     migrate_steering_columns(pool).await?;
+    migrate_anchor_columns(pool).await?;
+    migrate_stall_resolution_columns(pool).await?;
+    migrate_short_id_columns(pool).await?;
+    migrate_apply_after_column(pool).await?;
+    migrate_stale_at_column(pool).await?;
+    migrate_decision_reason_column(pool).await?;
+    migrate_checkpoint_automatic_column(pool).await?;
+    widen_steering_sources(pool).await?;
+    create_relay_table(pool).await?;
+    create_device_table(pool).await?;
+    create_session_context_table(pool).await?;
+    create_knowledge_table(pool).await?;
+    create_approval_message_table(pool).await?;
+    create_user_preferences_table(pool).await?;
+    create_run_table(pool).await?;
+    create_path_lock_table(pool).await?;
+    create_watchdog_probe_table(pool).await?;
+    create_snapshot_table(pool).await?;
+    create_session_note_table(pool).await?;
+    create_modal_context_table(pool).await?;
+    create_apply_conflict_table(pool).await?;
+    Ok(())
+}
+
+/// Create the `relay_message` table used by `relay_send` / `relay_receive`.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the DDL fails.
+async fn create_relay_table(pool: &SqlitePool) -> Result<()> {
+    sqlx::raw_sql(
+        "CREATE TABLE IF NOT EXISTS relay_message (
+             id              TEXT PRIMARY KEY NOT NULL,
+             from_session_id TEXT NOT NULL,
+             to_session_id   TEXT NOT NULL,
+             topic           TEXT,
+             payload         TEXT NOT NULL,
+             created_at      TEXT NOT NULL,
+             delivered_at    TEXT
+         );
+         CREATE INDEX IF NOT EXISTS idx_relay_recipient
+             ON relay_message(to_session_id, delivered_at);",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Create the `paired_device` table used by `/intercom pair` and the web
+/// approver. Tokens are stored only as SHA-256 hashes.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the DDL fails.
+async fn create_device_table(pool: &SqlitePool) -> Result<()> {
+    sqlx::raw_sql(
+        "CREATE TABLE IF NOT EXISTS paired_device (
+             id           TEXT PRIMARY KEY NOT NULL,
+             user_id      TEXT NOT NULL,
+             pairing_hash TEXT UNIQUE,
+             device_hash  TEXT UNIQUE,
+             label        TEXT,
+             created_at   TEXT NOT NULL,
+             paired_at    TEXT,
+             expires_at   TEXT NOT NULL,
+             last_seen_at TEXT
+         );
+         CREATE INDEX IF NOT EXISTS idx_paired_device_user ON paired_device(user_id);",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Create the `session_context` table used by `store_context` /
+/// `get_context`.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the DDL fails.
+async fn create_session_context_table(pool: &SqlitePool) -> Result<()> {
+    sqlx::raw_sql(
+        "CREATE TABLE IF NOT EXISTS session_context (
+             session_id TEXT NOT NULL,
+             key        TEXT NOT NULL,
+             value      TEXT NOT NULL,
+             updated_at TEXT NOT NULL,
+             PRIMARY KEY (session_id, key)
+         );",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Create the `knowledge_note` table used by `propose_knowledge` and the
+/// `intercom://knowledge` resource. Notes are not session-scoped and are
+/// never purged by retention.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the DDL fails.
+async fn create_knowledge_table(pool: &SqlitePool) -> Result<()> {
+    sqlx::raw_sql(
+        "CREATE TABLE IF NOT EXISTS knowledge_note (
+             id             TEXT PRIMARY KEY NOT NULL,
+             title          TEXT NOT NULL,
+             body           TEXT NOT NULL,
+             tags           TEXT NOT NULL,
+             proposed_by    TEXT NOT NULL,
+             workspace_root TEXT NOT NULL,
+             status         TEXT NOT NULL CHECK(status IN ('pending','approved','rejected')),
+             created_at     TEXT NOT NULL,
+             decided_at     TEXT,
+             decided_by     TEXT
+         );
+         CREATE INDEX IF NOT EXISTS idx_knowledge_status ON knowledge_note(status);",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Create the `approval_message` table tracking every Slack copy of an
+/// approval request fanned out to several channels. `resolved` is set once
+/// a copy no longer carries live buttons.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the DDL fails.
+async fn create_approval_message_table(pool: &SqlitePool) -> Result<()> {
+    sqlx::raw_sql(
+        "CREATE TABLE IF NOT EXISTS approval_message (
+             request_id TEXT NOT NULL,
+             channel_id TEXT NOT NULL,
+             ts         TEXT NOT NULL,
+             resolved   INTEGER NOT NULL DEFAULT 0,
+             PRIMARY KEY (request_id, channel_id, ts)
+         );",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Create the `user_preferences` table holding each operator's
+/// `/intercom prefs` settings. Rows are not session-scoped and are never
+/// purged by retention.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the DDL fails.
+async fn create_user_preferences_table(pool: &SqlitePool) -> Result<()> {
+    sqlx::raw_sql(
+        "CREATE TABLE IF NOT EXISTS user_preferences (
+             user_id      TEXT PRIMARY KEY NOT NULL,
+             detail_level TEXT CHECK(detail_level IN ('minimal','standard','verbose')),
+             delivery     TEXT NOT NULL DEFAULT 'channel' CHECK(delivery IN ('channel','dm','both')),
+             quiet_hours  TEXT,
+             updated_at   TEXT NOT NULL
+         );",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Create the `run` table behind `/intercom run-start`. At most one run per
+/// operator is active (`ended_at IS NULL`).
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the DDL fails.
+async fn create_run_table(pool: &SqlitePool) -> Result<()> {
+    sqlx::raw_sql(
+        "CREATE TABLE IF NOT EXISTS run (
+             id            TEXT PRIMARY KEY NOT NULL,
+             name          TEXT NOT NULL,
+             owner_user_id TEXT NOT NULL,
+             channel_id    TEXT,
+             started_at    TEXT NOT NULL,
+             ended_at      TEXT
+         );
+         CREATE UNIQUE INDEX IF NOT EXISTS idx_run_active_owner
+             ON run(owner_user_id) WHERE ended_at IS NULL;",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Create the `path_lock` table behind `acquire_lock` / `release_lock`.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the DDL fails.
+async fn create_path_lock_table(pool: &SqlitePool) -> Result<()> {
+    sqlx::raw_sql(
+        "CREATE TABLE IF NOT EXISTS path_lock (
+             id             TEXT PRIMARY KEY NOT NULL,
+             session_id     TEXT NOT NULL,
+             workspace_root TEXT NOT NULL,
+             path           TEXT NOT NULL,
+             status         TEXT NOT NULL CHECK(status IN ('held','queued')),
+             reason         TEXT,
+             requested_at   TEXT NOT NULL,
+             acquired_at    TEXT
+         );
+         CREATE INDEX IF NOT EXISTS idx_path_lock_workspace
+             ON path_lock(workspace_root, requested_at);
+         CREATE INDEX IF NOT EXISTS idx_path_lock_session
+             ON path_lock(session_id);",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Create the single-row `watchdog_probe` table the watchdog writes and
+/// reads back to check the database.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the DDL fails.
+async fn create_watchdog_probe_table(pool: &SqlitePool) -> Result<()> {
+    sqlx::raw_sql(
+        "CREATE TABLE IF NOT EXISTS watchdog_probe (
+             id         INTEGER PRIMARY KEY NOT NULL CHECK(id = 1),
+             token      TEXT NOT NULL,
+             checked_at TEXT NOT NULL
+         );",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Create the `apply_conflict` table behind operator arbitration of diffs
+/// `check_diff` could not apply.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the DDL fails.
+async fn create_apply_conflict_table(pool: &SqlitePool) -> Result<()> {
+    sqlx::raw_sql(
+        "CREATE TABLE IF NOT EXISTS apply_conflict (
+             id          TEXT PRIMARY KEY NOT NULL,
+             request_id  TEXT NOT NULL,
+             session_id  TEXT NOT NULL,
+             file_path   TEXT NOT NULL,
+             kind        TEXT NOT NULL CHECK(kind IN ('hash_mismatch','hunk_conflict')),
+             detail      TEXT NOT NULL,
+             resolution  TEXT CHECK(resolution IN ('force_apply','merge','reject','expired')),
+             resolved_by TEXT,
+             created_at  TEXT NOT NULL,
+             resolved_at TEXT
+         );
+         CREATE INDEX IF NOT EXISTS idx_apply_conflict_request
+             ON apply_conflict(request_id);",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Create the `workspace_snapshot` table recording full-workspace archives.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the DDL fails.
+async fn create_snapshot_table(pool: &SqlitePool) -> Result<()> {
+    sqlx::raw_sql(
+        "CREATE TABLE IF NOT EXISTS workspace_snapshot (
+             id             TEXT PRIMARY KEY NOT NULL,
+             workspace_root TEXT NOT NULL,
+             label          TEXT,
+             created_by     TEXT NOT NULL,
+             session_id     TEXT,
+             file_count     INTEGER NOT NULL,
+             total_bytes    INTEGER NOT NULL,
+             created_at     TEXT NOT NULL
+         );
+         CREATE INDEX IF NOT EXISTS idx_workspace_snapshot_root
+             ON workspace_snapshot(workspace_root, created_at);",
+    )
+    .execute(pool)
+    .await?;
     Ok(())
 }
 
@@ -232,9 +629,10 @@ CREATE INDEX IF NOT EXISTS idx_inbox_channel_consumed ON task_inbox(channel_id,
 ///
 /// Adds the `origin_session_id` column (feature F.3) idempotently so that a
 /// crashed session's pending steering queue can be rebound to its resumed
-/// session while preserving the original owning session id. Safe to call on
-/// every server startup; a legacy database missing the column is migrated
-/// additively without data loss.
+/// session while preserving the original owning session id, and the
+/// `priority` / `expires_at` columns for urgent and time-limited steering.
+/// Safe to call on every server startup; a legacy database missing the
+/// columns is migrated additively without data loss.
 ///
 /// # Errors
 ///
@@ -247,5 +645,251 @@ async fn migrate_steering_columns(pool: &SqlitePool) -> Result<()> {
         "ALTER TABLE steering_message ADD COLUMN origin_session_id TEXT",
     )
     .await?;
+    add_column_if_missing(
+        pool,
+        "steering_message",
+        "priority",
+        "ALTER TABLE steering_message ADD COLUMN priority TEXT NOT NULL DEFAULT 'normal' \
+         CHECK(priority IN ('normal','interrupt'))",
+    )
+    .await?;
+    add_column_if_missing(
+        pool,
+        "steering_message",
+        "expires_at",
+        "ALTER TABLE steering_message ADD COLUMN expires_at TEXT",
+    )
+    .await?;
+    Ok(())
+}
+
+/// Add the per-request `thread_ts` column to `approval_request` and
+/// `continuation_prompt`.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if a check or `ALTER TABLE` fails.
+async fn migrate_anchor_columns(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(
+        pool,
+        "approval_request",
+        "thread_ts",
+        "ALTER TABLE approval_request ADD COLUMN thread_ts TEXT",
+    )
+    .await?;
+    add_column_if_missing(
+        pool,
+        "continuation_prompt",
+        "thread_ts",
+        "ALTER TABLE continuation_prompt ADD COLUMN thread_ts TEXT",
+    )
+    .await?;
+    Ok(())
+}
+
+/// Add the `apply_after` column holding when a scheduled apply is due.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the check or `ALTER TABLE` fails.
+async fn migrate_apply_after_column(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(
+        pool,
+        "approval_request",
+        "apply_after",
+        "ALTER TABLE approval_request ADD COLUMN apply_after TEXT",
+    )
+    .await
+}
+
+/// Add the `stale_at` column recording when a request's file changed on
+/// disk after its diff was proposed.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the check or `ALTER TABLE` fails.
+async fn migrate_stale_at_column(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(
+        pool,
+        "approval_request",
+        "stale_at",
+        "ALTER TABLE approval_request ADD COLUMN stale_at TEXT",
+    )
+    .await
+}
+
+/// Add the `decision_reason` column recording why a request was rejected.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the check or `ALTER TABLE` fails.
+async fn migrate_decision_reason_column(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(
+        pool,
+        "approval_request",
+        "decision_reason",
+        "ALTER TABLE approval_request ADD COLUMN decision_reason TEXT",
+    )
+    .await
+}
+
+/// Add the `automatic` column marking checkpoints taken by the periodic
+/// `[checkpoints]` task.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the check or `ALTER TABLE` fails.
+async fn migrate_checkpoint_automatic_column(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(
+        pool,
+        "checkpoint",
+        "automatic",
+        "ALTER TABLE checkpoint ADD COLUMN automatic INTEGER NOT NULL DEFAULT 0",
+    )
+    .await
+}
+
+/// Add the `resolution` / `resolved_at` columns recording how each stall
+/// alert ended, for stall analytics.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if a check or `ALTER TABLE` fails.
+async fn migrate_stall_resolution_columns(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(
+        pool,
+        "stall_alert",
+        "resolution",
+        "ALTER TABLE stall_alert ADD COLUMN resolution TEXT \
+         CHECK(resolution IN ('self_recovered','nudged','stopped'))",
+    )
+    .await?;
+    add_column_if_missing(
+        pool,
+        "stall_alert",
+        "resolved_at",
+        "ALTER TABLE stall_alert ADD COLUMN resolved_at TEXT",
+    )
+    .await?;
+    Ok(())
+}
+
+/// Add the `short_id` column to `session` and `approval_request`.
+///
+/// Rows created before short IDs get the first eight characters of their
+/// UUID, which is what Slack showed for them until now.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if a check, `ALTER TABLE`, backfill or index
+/// creation fails.
+async fn migrate_short_id_columns(pool: &SqlitePool) -> Result<()> {
+    for table in ["session", "approval_request"] {
+        add_column_if_missing(
+            pool,
+            table,
+            "short_id",
+            &format!("ALTER TABLE {table} ADD COLUMN short_id TEXT"),
+        )
+        .await?;
+        sqlx::raw_sql(&format!(
+            "UPDATE {table} SET short_id = substr(id, 1, 8) WHERE short_id IS NULL;
+             CREATE UNIQUE INDEX IF NOT EXISTS idx_{table}_short_id ON {table}(short_id);"
+        ))
+        .execute(pool)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Rebuild a legacy `steering_message` table whose `source` check predates
+/// the `subtask` source.
+///
+/// `SQLite` cannot alter a `CHECK` constraint in place, so the table is
+/// copied into a new one with the current definition inside a transaction.
+/// Runs after the column migrations, so every column exists on both sides.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the schema lookup or the rebuild fails.
+async fn widen_steering_sources(pool: &SqlitePool) -> Result<()> {
+    let table_sql: Option<String> = sqlx::query_scalar(
+        "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'steering_message'",
+    )
+    .fetch_optional(pool)
+    .await?;
+    if table_sql.is_none_or(|sql| sql.contains("'subtask'")) {
+        return Ok(());
+    }
+
+    let mut tx = pool.begin().await?;
+    sqlx::raw_sql(
+        "CREATE TABLE steering_message_new (
+             id              TEXT PRIMARY KEY NOT NULL,
+             session_id      TEXT NOT NULL,
+             channel_id      TEXT,
+             message         TEXT NOT NULL,
+             source          TEXT NOT NULL CHECK(source IN ('slack','ipc','subtask')),
+             created_at      TEXT NOT NULL,
+             consumed        INTEGER NOT NULL DEFAULT 0,
+             origin_session_id TEXT,
+             priority        TEXT NOT NULL DEFAULT 'normal' CHECK(priority IN ('normal','interrupt')),
+             expires_at      TEXT
+         );
+         INSERT INTO steering_message_new (id, session_id, channel_id, message, source,
+             created_at, consumed, origin_session_id, priority, expires_at)
+         SELECT id, session_id, channel_id, message, source, created_at, consumed,
+             origin_session_id, priority, expires_at
+         FROM steering_message;
+         DROP TABLE steering_message;
+         ALTER TABLE steering_message_new RENAME TO steering_message;
+         CREATE INDEX IF NOT EXISTS idx_steering_session_consumed
+             ON steering_message(session_id, consumed);",
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Create the `modal_context` table holding the message each open Slack
+/// modal was opened from.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the DDL fails.
+async fn create_modal_context_table(pool: &SqlitePool) -> Result<()> {
+    sqlx::raw_sql(
+        "CREATE TABLE IF NOT EXISTS modal_context (
+             callback_id TEXT PRIMARY KEY NOT NULL,
+             channel_id  TEXT NOT NULL,
+             message_ts  TEXT NOT NULL,
+             created_at  TEXT NOT NULL
+         );",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Create the `session_note` table holding operator notes on sessions.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the DDL fails.
+async fn create_session_note_table(pool: &SqlitePool) -> Result<()> {
+    sqlx::raw_sql(
+        "CREATE TABLE IF NOT EXISTS session_note (
+             id         TEXT PRIMARY KEY NOT NULL,
+             session_id TEXT NOT NULL,
+             author     TEXT NOT NULL,
+             text       TEXT NOT NULL,
+             created_at TEXT NOT NULL
+         );
+         CREATE INDEX IF NOT EXISTS idx_session_note_session
+             ON session_note(session_id, created_at);",
+    )
+    .execute(pool)
+    .await?;
     Ok(())
 }
@@ -0,0 +1,85 @@
+//! Session note repository for `SQLite` persistence.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use crate::models::session_note::SessionNote;
+use crate::{AppError, Result};
+
+use super::db::Database;
+
+/// Repository for operator notes on sessions.
+#[derive(Clone)]
+pub struct SessionNoteRepo {
+    db: Arc<Database>,
+}
+
+/// Internal row struct for `SQLite` deserialization.
+#[derive(sqlx::FromRow)]
+struct SessionNoteRow {
+    id: String,
+    session_id: String,
+    author: String,
+    text: String,
+    created_at: String,
+}
+
+impl SessionNoteRow {
+    fn into_note(self) -> Result<SessionNote> {
+        let created_at = chrono::DateTime::parse_from_rfc3339(&self.created_at)
+            .map_err(|e| AppError::Db(format!("invalid created_at: {e}")))?
+            .with_timezone(&Utc);
+        Ok(SessionNote {
+            id: self.id,
+            session_id: self.session_id,
+            author: self.author,
+            text: self.text,
+            created_at,
+        })
+    }
+}
+
+impl SessionNoteRepo {
+    /// Create a new repository instance.
+    #[must_use]
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Store a note.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the insert fails.
+    pub async fn create(&self, note: &SessionNote) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO session_note (id, session_id, author, text, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+        )
+        .bind(&note.id)
+        .bind(&note.session_id)
+        .bind(&note.author)
+        .bind(&note.text)
+        .bind(note.created_at.to_rfc3339())
+        .execute(self.db.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Notes on `session_id`, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_for_session(&self, session_id: &str) -> Result<Vec<SessionNote>> {
+        let rows: Vec<SessionNoteRow> = sqlx::query_as(
+            "SELECT * FROM session_note WHERE session_id = ?1
+             ORDER BY created_at ASC, rowid ASC",
+        )
+        .bind(session_id)
+        .fetch_all(self.db.as_ref())
+        .await?;
+        rows.into_iter().map(SessionNoteRow::into_note).collect()
+    }
+}
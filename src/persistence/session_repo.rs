@@ -1,17 +1,19 @@
 //! Session repository for `SQLite` persistence.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use sqlx::Row;
 
 use crate::models::progress::ProgressItem;
 use crate::models::session::{
     ConnectivityStatus, ProtocolMode, Session, SessionMode, SessionStatus,
 };
+use crate::models::short_id::{self, SESSION_PREFIX};
 use crate::{AppError, Result};
 
-use super::db::Database;
+use super::db::{unused_short_id, Database};
 
 /// Repository wrapper around `SQLite` for session records.
 #[derive(Clone)]
@@ -23,6 +25,7 @@ pub struct SessionRepo {
 #[derive(sqlx::FromRow)]
 struct SessionRow {
     id: String,
+    short_id: Option<String>,
     owner_user_id: String,
     workspace_root: String,
     status: String,
@@ -43,6 +46,19 @@ struct SessionRow {
     restart_of: Option<String>,
     agent_session_id: Option<String>,
     title: Option<String>,
+    deadline: Option<String>,
+    max_duration_seconds: Option<i64>,
+    wrap_up_sent: i64,
+    issue_ref: Option<String>,
+    parent_session_id: Option<String>,
+    muted: i64,
+    muted_until: Option<String>,
+    deleted_at: Option<String>,
+    tags: Option<String>,
+    env: Option<String>,
+    run_id: Option<String>,
+    client_name: Option<String>,
+    client_version: Option<String>,
 }
 
 impl SessionRow {
@@ -51,6 +67,7 @@ impl SessionRow {
     /// # Errors
     ///
     /// Returns `AppError::Db` if enum parsing or JSON deserialization fails.
+    #[allow(clippy::too_many_lines)] // One conversion per column.
     fn into_session(self) -> Result<Session> {
         let status = parse_status(&self.status)?;
         let mode = parse_mode(&self.mode)?;
@@ -89,8 +106,40 @@ impl SessionRow {
                     .map_err(|e| AppError::Db(format!("invalid last_activity_at: {e}")))
             })
             .transpose()?;
+        let deadline = self
+            .deadline
+            .as_deref()
+            .map(|s| {
+                chrono::DateTime::parse_from_rfc3339(s)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|e| AppError::Db(format!("invalid deadline: {e}")))
+            })
+            .transpose()?;
+        let muted_until = self
+            .muted_until
+            .as_deref()
+            .map(|s| {
+                chrono::DateTime::parse_from_rfc3339(s)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|e| AppError::Db(format!("invalid muted_until: {e}")))
+            })
+            .transpose()?;
+        let deleted_at = self
+            .deleted_at
+            .as_deref()
+            .map(|s| {
+                chrono::DateTime::parse_from_rfc3339(s)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|e| AppError::Db(format!("invalid deleted_at: {e}")))
+            })
+            .transpose()?;
+        let tags = parse_map(self.tags.as_deref(), "tags")?;
+        let env = parse_map(self.env.as_deref(), "env")?;
 
         Ok(Session {
+            short_id: self
+                .short_id
+                .unwrap_or_else(|| self.id.chars().take(8).collect()),
             id: self.id,
             owner_user_id: self.owner_user_id,
             workspace_root: self.workspace_root,
@@ -112,6 +161,19 @@ impl SessionRow {
             restart_of: self.restart_of,
             agent_session_id: self.agent_session_id,
             title: self.title,
+            deadline,
+            max_duration_seconds: self.max_duration_seconds,
+            wrap_up_sent: self.wrap_up_sent != 0,
+            issue_ref: self.issue_ref,
+            parent_session_id: self.parent_session_id,
+            muted: self.muted != 0,
+            muted_until,
+            deleted_at,
+            tags,
+            env,
+            run_id: self.run_id,
+            client_name: self.client_name,
+            client_version: self.client_version,
         })
     }
 }
@@ -139,6 +201,44 @@ fn status_str(s: SessionStatus) -> &'static str {
     }
 }
 
+/// `WHERE` clause skipping soft-deleted sessions and, unless `statuses` is
+/// empty, matching one bound status per entry.
+fn status_filter(statuses: &[SessionStatus]) -> String {
+    if statuses.is_empty() {
+        return " WHERE deleted_at IS NULL".to_owned();
+    }
+    let placeholders = vec!["?"; statuses.len()].join(", ");
+    format!(" WHERE deleted_at IS NULL AND status IN ({placeholders})")
+}
+
+/// `AND` clauses requiring each tag in `tags`; binds a key and a value per
+/// tag, after the status placeholders.
+fn tag_filter(tags: &[(String, String)]) -> String {
+    " AND EXISTS (SELECT 1 FROM json_each(session.tags) t WHERE t.key = ? AND t.value = ?)"
+        .repeat(tags.len())
+}
+
+/// Serialize a `key=value` map for the `tags` or `env` column; `NULL`
+/// when it is empty.
+fn map_json(map: &BTreeMap<String, String>, column: &str) -> Result<Option<String>> {
+    if map.is_empty() {
+        return Ok(None);
+    }
+    serde_json::to_string(map)
+        .map(Some)
+        .map_err(|e| AppError::Db(format!("failed to serialize {column}: {e}")))
+}
+
+/// Parse the `tags` or `env` column; `NULL` means an empty map.
+fn parse_map(raw: Option<&str>, column: &str) -> Result<BTreeMap<String, String>> {
+    raw.map_or_else(
+        || Ok(BTreeMap::new()),
+        |s| {
+            serde_json::from_str(s).map_err(|e| AppError::Db(format!("invalid {column} json: {e}")))
+        },
+    )
+}
+
 /// Parse a mode string into the domain enum.
 fn parse_mode(s: &str) -> Result<SessionMode> {
     match s {
@@ -241,14 +341,29 @@ impl SessionRepo {
         let protocol_mode = protocol_mode_str(session.protocol_mode);
         let connectivity_status = connectivity_status_str(session.connectivity_status);
         let last_activity_at = session.last_activity_at.map(|dt| dt.to_rfc3339());
+        let deadline = session.deadline.map(|dt| dt.to_rfc3339());
+        let muted_until = session.muted_until.map(|dt| dt.to_rfc3339());
+        let tags = map_json(&session.tags, "tags")?;
+        let env = map_json(&session.env, "env")?;
+        let short_id = unused_short_id(
+            &self.db,
+            "session",
+            SESSION_PREFIX,
+            &session.id,
+            &session.short_id,
+        )
+        .await?;
 
         sqlx::query(
             "INSERT INTO session (id, owner_user_id, workspace_root, status, prompt, mode,
              created_at, updated_at, terminated_at, last_tool, nudge_count, stall_paused,
              progress_snapshot, protocol_mode, channel_id, thread_ts, connectivity_status,
-             last_activity_at, restart_of, agent_session_id, title)
+             last_activity_at, restart_of, agent_session_id, title, deadline, wrap_up_sent,
+             issue_ref, parent_session_id, muted, muted_until, tags, short_id, run_id,
+             client_name, client_version, env, max_duration_seconds)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
-             ?17, ?18, ?19, ?20, ?21)",
+             ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32,
+             ?33, ?34)",
         )
         .bind(&session.id)
         .bind(&session.owner_user_id)
@@ -271,10 +386,26 @@ impl SessionRepo {
         .bind(&session.restart_of)
         .bind(&session.agent_session_id)
         .bind(&session.title)
+        .bind(&deadline)
+        .bind(i64::from(session.wrap_up_sent))
+        .bind(&session.issue_ref)
+        .bind(&session.parent_session_id)
+        .bind(i64::from(session.muted))
+        .bind(&muted_until)
+        .bind(&tags)
+        .bind(&short_id)
+        .bind(&session.run_id)
+        .bind(&session.client_name)
+        .bind(&session.client_version)
+        .bind(&env)
+        .bind(session.max_duration_seconds)
         .execute(self.db.as_ref())
         .await?;
 
-        Ok(session.clone())
+        Ok(Session {
+            short_id,
+            ..session.clone()
+        })
     }
 
     /// Retrieve a session by identifier.
@@ -293,23 +424,33 @@ impl SessionRepo {
         row.map(SessionRow::into_session).transpose()
     }
 
-    /// Retrieve a session by ID prefix.
+    /// Retrieve a session by short ID (`ses-7f3k`) or ID prefix.
     ///
-    /// Matches sessions whose ID starts with the given prefix. Returns
-    /// `Ok(None)` if no match exists, or `Err` if multiple sessions match
-    /// (ambiguous prefix).
+    /// An exact short ID match wins; otherwise matches sessions whose ID or
+    /// short ID starts with the given prefix. Case is ignored. Returns
+    /// `Ok(None)` if no match exists.
     ///
     /// # Errors
     ///
-    /// Returns `AppError::Db` on query failure or `AppError::Config` if the
-    /// prefix matches more than one session.
+    /// Returns `AppError::Db` on query failure or `AppError::Config`, naming
+    /// the candidates, if the prefix matches more than one session.
     pub async fn get_by_prefix(&self, prefix: &str) -> Result<Option<Session>> {
+        let prefix = short_id::normalize(prefix);
+        let row: Option<SessionRow> = sqlx::query_as("SELECT * FROM session WHERE short_id = ?1")
+            .bind(&prefix)
+            .fetch_optional(self.db.as_ref())
+            .await?;
+        if let Some(row) = row {
+            return row.into_session().map(Some);
+        }
+
         let pattern = format!("{prefix}%");
-        let rows: Vec<SessionRow> =
-            sqlx::query_as("SELECT * FROM session WHERE id LIKE ?1 ORDER BY updated_at DESC")
-                .bind(&pattern)
-                .fetch_all(self.db.as_ref())
-                .await?;
+        let rows: Vec<SessionRow> = sqlx::query_as(
+            "SELECT * FROM session WHERE id LIKE ?1 OR short_id LIKE ?1 ORDER BY updated_at DESC",
+        )
+        .bind(&pattern)
+        .fetch_all(self.db.as_ref())
+        .await?;
 
         match rows.len() {
             0 => Ok(None),
@@ -318,9 +459,14 @@ impl SessionRepo {
                 .next()
                 .map(SessionRow::into_session)
                 .transpose(),
-            n => Err(AppError::Config(format!(
-                "ambiguous session prefix '{prefix}' matches {n} sessions"
-            ))),
+            _ => {
+                let candidates: Vec<String> = rows
+                    .into_iter()
+                    .map(SessionRow::into_session)
+                    .map(|session| session.map(|s| s.short_id))
+                    .collect::<Result<_>>()?;
+                Err(short_id::ambiguous("session", &prefix, &candidates))
+            }
         }
     }
 
@@ -395,6 +541,78 @@ impl SessionRepo {
         rows.into_iter().map(SessionRow::into_session).collect()
     }
 
+    /// Replace a session's tags.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` if no session has `id`, or
+    /// `AppError::Db` if the update fails.
+    pub async fn set_tags(&self, id: &str, tags: &BTreeMap<String, String>) -> Result<()> {
+        let result = sqlx::query("UPDATE session SET tags = ?1 WHERE id = ?2")
+            .bind(map_json(tags, "tags")?)
+            .bind(id)
+            .execute(self.db.as_ref())
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("session {id} not found")));
+        }
+        Ok(())
+    }
+
+    /// Add a session to a run (`/intercom run-start`).
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` if no session has `id`, or
+    /// `AppError::Db` if the update fails.
+    pub async fn set_run_id(&self, id: &str, run_id: &str) -> Result<()> {
+        let result = sqlx::query("UPDATE session SET run_id = ?1 WHERE id = ?2")
+            .bind(run_id)
+            .bind(id)
+            .execute(self.db.as_ref())
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("session {id} not found")));
+        }
+        Ok(())
+    }
+
+    /// Record the MCP client implementation a session connected with.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` if no session has `id`, or
+    /// `AppError::Db` if the update fails.
+    pub async fn set_client(&self, id: &str, name: &str, version: &str) -> Result<()> {
+        let result =
+            sqlx::query("UPDATE session SET client_name = ?1, client_version = ?2 WHERE id = ?3")
+                .bind(name)
+                .bind(version)
+                .bind(id)
+                .execute(self.db.as_ref())
+                .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("session {id} not found")));
+        }
+        Ok(())
+    }
+
+    /// Sessions in a run, oldest first, soft-deleted ones included so a
+    /// run summary accounts for all of its work.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_by_run(&self, run_id: &str) -> Result<Vec<Session>> {
+        let rows: Vec<SessionRow> =
+            sqlx::query_as("SELECT * FROM session WHERE run_id = ?1 ORDER BY created_at ASC")
+                .bind(run_id)
+                .fetch_all(self.db.as_ref())
+                .await?;
+
+        rows.into_iter().map(SessionRow::into_session).collect()
+    }
+
     /// Update the progress snapshot on a session.
     ///
     /// # Errors
@@ -453,6 +671,29 @@ impl SessionRepo {
             .ok_or_else(|| AppError::NotFound(format!("session {id} not found after terminate")))
     }
 
+    /// Make an interrupted session active again, offline until its agent
+    /// reconnects, and clear `terminated_at`.
+    ///
+    /// Returns `false` when the session is no longer interrupted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn reactivate(&self, id: &str) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE session SET status = ?1, connectivity_status = ?2, terminated_at = NULL, \
+             updated_at = ?3 WHERE id = ?4 AND status = ?5",
+        )
+        .bind(SessionStatus::Active.as_str())
+        .bind(connectivity_status_str(ConnectivityStatus::Offline))
+        .bind(Utc::now().to_rfc3339())
+        .bind(id)
+        .bind(SessionStatus::Interrupted.as_str())
+        .execute(self.db.as_ref())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Count active sessions (status == `active`).
     ///
     /// # Errors
@@ -498,14 +739,15 @@ impl SessionRepo {
         Ok(count)
     }
 
-    /// Retrieve the most recently interrupted session.
+    /// Retrieve the most recently interrupted session that has not been
+    /// soft-deleted.
     ///
     /// # Errors
     ///
     /// Returns `AppError::Db` if the query fails.
     pub async fn get_most_recent_interrupted(&self) -> Result<Option<Session>> {
         let row: Option<SessionRow> = sqlx::query_as(
-            "SELECT * FROM session WHERE status = ?1 \
+            "SELECT * FROM session WHERE status = ?1 AND deleted_at IS NULL \
              ORDER BY updated_at DESC LIMIT 1",
         )
         .bind(SessionStatus::Interrupted.as_str())
@@ -515,16 +757,17 @@ impl SessionRepo {
         row.map(SessionRow::into_session).transpose()
     }
 
-    /// List all sessions with status `interrupted`.
+    /// List all sessions with status `interrupted`, except soft-deleted ones.
     ///
     /// # Errors
     ///
     /// Returns `AppError::Db` if the query fails.
     pub async fn list_interrupted(&self) -> Result<Vec<Session>> {
-        let rows: Vec<SessionRow> = sqlx::query_as("SELECT * FROM session WHERE status = ?1")
-            .bind(SessionStatus::Interrupted.as_str())
-            .fetch_all(self.db.as_ref())
-            .await?;
+        let rows: Vec<SessionRow> =
+            sqlx::query_as("SELECT * FROM session WHERE status = ?1 AND deleted_at IS NULL")
+                .bind(SessionStatus::Interrupted.as_str())
+                .fetch_all(self.db.as_ref())
+                .await?;
 
         rows.into_iter().map(SessionRow::into_session).collect()
     }
@@ -545,6 +788,111 @@ impl SessionRepo {
         rows.into_iter().map(SessionRow::into_session).collect()
     }
 
+    /// List one page of sessions whose status is in `statuses` (all sessions
+    /// when empty) and that carry every tag in `tags`, most recently updated
+    /// first, skipping `offset` rows. Soft-deleted sessions are left out.
+    ///
+    /// Served by `idx_session_status_updated`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_page(
+        &self,
+        statuses: &[SessionStatus],
+        tags: &[(String, String)],
+        limit: u32,
+        offset: u64,
+    ) -> Result<Vec<Session>> {
+        let sql = format!(
+            "SELECT * FROM session{}{} ORDER BY updated_at DESC, id LIMIT ? OFFSET ?",
+            status_filter(statuses),
+            tag_filter(tags)
+        );
+        let mut query = sqlx::query_as::<_, SessionRow>(&sql);
+        for status in statuses {
+            query = query.bind(status.as_str());
+        }
+        for (key, value) in tags {
+            query = query.bind(key).bind(value);
+        }
+        let offset = i64::try_from(offset).unwrap_or(i64::MAX);
+        let rows = query
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(self.db.as_ref())
+            .await?;
+
+        rows.into_iter().map(SessionRow::into_session).collect()
+    }
+
+    /// Count sessions whose status is in `statuses` (all sessions when
+    /// empty) and that carry every tag in `tags`; the total behind
+    /// [`Self::list_page`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn count_by_status(
+        &self,
+        statuses: &[SessionStatus],
+        tags: &[(String, String)],
+    ) -> Result<i64> {
+        let sql = format!(
+            "SELECT COUNT(*) AS cnt FROM session{}{}",
+            status_filter(statuses),
+            tag_filter(tags)
+        );
+        let mut query = sqlx::query(&sql);
+        for status in statuses {
+            query = query.bind(status.as_str());
+        }
+        for (key, value) in tags {
+            query = query.bind(key).bind(value);
+        }
+        let row = query.fetch_one(self.db.as_ref()).await?;
+
+        let count: i64 = row.get("cnt");
+        Ok(count)
+    }
+
+    /// List sessions whose status is in `statuses`, optionally owned by
+    /// `owner` and last updated before `updated_before`, oldest first.
+    /// Soft-deleted sessions are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_matching(
+        &self,
+        statuses: &[SessionStatus],
+        owner: Option<&str>,
+        updated_before: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Session>> {
+        let mut sql = format!("SELECT * FROM session{}", status_filter(statuses));
+        if owner.is_some() {
+            sql.push_str(" AND owner_user_id = ?");
+        }
+        if updated_before.is_some() {
+            sql.push_str(" AND updated_at < ?");
+        }
+        sql.push_str(" ORDER BY updated_at, id");
+
+        let mut query = sqlx::query_as::<_, SessionRow>(&sql);
+        for status in statuses {
+            query = query.bind(status.as_str());
+        }
+        if let Some(owner) = owner {
+            query = query.bind(owner);
+        }
+        if let Some(before) = updated_before {
+            query = query.bind(before.to_rfc3339());
+        }
+        let rows = query.fetch_all(self.db.as_ref()).await?;
+
+        rows.into_iter().map(SessionRow::into_session).collect()
+    }
+
     /// Return `(session_id, last_activity_at)` for all active sessions (T148, FR-045).
     ///
     /// Used by the server startup path to seed stall-detector timers from
@@ -613,7 +961,7 @@ impl SessionRepo {
     pub async fn find_interrupted_by_channel(&self, channel_id: &str) -> Result<Vec<Session>> {
         let rows: Vec<SessionRow> = sqlx::query_as(
             "SELECT * FROM session WHERE channel_id = ?1 AND status = ?2 \
-             ORDER BY updated_at DESC",
+             AND deleted_at IS NULL ORDER BY updated_at DESC",
         )
         .bind(channel_id)
         .bind(SessionStatus::Interrupted.as_str())
@@ -625,7 +973,9 @@ impl SessionRepo {
 
     /// Find a session by Slack channel and thread timestamp.
     ///
-    /// Returns `None` if no matching session exists.
+    /// A restarted session keeps its predecessor's thread, so the newest
+    /// session in the thread wins. Returns `None` if no matching session
+    /// exists.
     ///
     /// # Errors
     ///
@@ -635,12 +985,14 @@ impl SessionRepo {
         channel_id: &str,
         thread_ts: &str,
     ) -> Result<Option<Session>> {
-        let row: Option<SessionRow> =
-            sqlx::query_as("SELECT * FROM session WHERE channel_id = ?1 AND thread_ts = ?2")
-                .bind(channel_id)
-                .bind(thread_ts)
-                .fetch_optional(self.db.as_ref())
-                .await?;
+        let row: Option<SessionRow> = sqlx::query_as(
+            "SELECT * FROM session WHERE channel_id = ?1 AND thread_ts = ?2
+                 ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(channel_id)
+        .bind(thread_ts)
+        .fetch_optional(self.db.as_ref())
+        .await?;
 
         row.map(SessionRow::into_session).transpose()
     }
@@ -696,6 +1048,130 @@ impl SessionRepo {
         Ok(())
     }
 
+    /// Point a session at another channel and thread (`session-move`).
+    ///
+    /// Unlike [`set_thread_ts`](Self::set_thread_ts) this overwrites both
+    /// fields; `thread_ts = None` lets the next message start a new thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` if the session does not exist, or
+    /// `AppError::Db` if the update fails.
+    pub async fn rebind_channel(
+        &self,
+        session_id: &str,
+        channel_id: &str,
+        thread_ts: Option<&str>,
+    ) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query(
+            "UPDATE session SET channel_id = ?1, thread_ts = ?2, updated_at = ?3 WHERE id = ?4",
+        )
+        .bind(channel_id)
+        .bind(thread_ts)
+        .bind(&now)
+        .bind(session_id)
+        .execute(self.db.as_ref())
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "session {session_id} not found"
+            )));
+        }
+        Ok(())
+    }
+
+    /// List active sessions that still have a time-box deadline to enforce.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_time_boxed(&self) -> Result<Vec<Session>> {
+        let rows: Vec<SessionRow> = sqlx::query_as(
+            "SELECT * FROM session WHERE status = ?1 AND deadline IS NOT NULL \
+             ORDER BY deadline ASC",
+        )
+        .bind(status_str(SessionStatus::Active))
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter().map(SessionRow::into_session).collect()
+    }
+
+    /// Record that the wrap-up warning for a session's time box was sent.
+    ///
+    /// Leaves `updated_at` untouched so time-box bookkeeping does not count
+    /// as session activity.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn mark_wrap_up_sent(&self, session_id: &str) -> Result<()> {
+        sqlx::query("UPDATE session SET wrap_up_sent = 1 WHERE id = ?1")
+            .bind(session_id)
+            .execute(self.db.as_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Clear a session's time-box deadline once it has been enforced.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn clear_deadline(&self, session_id: &str) -> Result<()> {
+        sqlx::query("UPDATE session SET deadline = NULL WHERE id = ?1")
+            .bind(session_id)
+            .execute(self.db.as_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Mute or unmute a session's non-critical Slack messages.
+    ///
+    /// `until` bounds a mute; `None` keeps it until unmuted. Unmuting clears
+    /// `until`. Leaves `updated_at` untouched so muting does not count as
+    /// session activity.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn set_muted(
+        &self,
+        session_id: &str,
+        muted: bool,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let until = if muted {
+            until.map(|dt| dt.to_rfc3339())
+        } else {
+            None
+        };
+        sqlx::query("UPDATE session SET muted = ?1, muted_until = ?2 WHERE id = ?3")
+            .bind(i64::from(muted))
+            .bind(until)
+            .bind(session_id)
+            .execute(self.db.as_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Whether non-critical Slack messages for a session are muted right now.
+    ///
+    /// Unknown sessions and lookup failures count as not muted, so a
+    /// database hiccup never swallows a message.
+    pub async fn is_muted(&self, session_id: &str) -> bool {
+        matches!(
+            self.get_by_id(session_id).await,
+            Ok(Some(session)) if session.is_muted(Utc::now())
+        )
+    }
+
     /// Set the ACP agent-assigned session ID.
     ///
     /// Persists the `sessionId` returned by the ACP `session/new` handshake so
@@ -728,17 +1204,43 @@ impl SessionRepo {
     /// interrupted sessions.
     ///
     /// Results are ordered by `updated_at` descending (most recent first).
+    /// Soft-deleted sessions are left out.
     ///
     /// # Errors
     ///
     /// Returns `AppError::Db` if the query fails.
     pub async fn list_all_by_channel(&self, channel_id: &str) -> Result<Vec<Session>> {
-        let rows: Vec<SessionRow> =
-            sqlx::query_as("SELECT * FROM session WHERE channel_id = ?1 ORDER BY updated_at DESC")
-                .bind(channel_id)
-                .fetch_all(self.db.as_ref())
-                .await?;
+        let rows: Vec<SessionRow> = sqlx::query_as(
+            "SELECT * FROM session WHERE channel_id = ?1 AND deleted_at IS NULL \
+             ORDER BY updated_at DESC",
+        )
+        .bind(channel_id)
+        .fetch_all(self.db.as_ref())
+        .await?;
 
         rows.into_iter().map(SessionRow::into_session).collect()
     }
+
+    /// Hide an ended session from listings and recovery (`deleted` true), or
+    /// show it again. The row itself, and everything recorded against it,
+    /// is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` if no session has `session_id`, or
+    /// `AppError::Db` if the update fails.
+    pub async fn set_deleted(&self, session_id: &str, deleted: bool) -> Result<()> {
+        let deleted_at = deleted.then(|| Utc::now().to_rfc3339());
+        let result = sqlx::query("UPDATE session SET deleted_at = ?1 WHERE id = ?2")
+            .bind(deleted_at)
+            .bind(session_id)
+            .execute(self.db.as_ref())
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "session '{session_id}' not found"
+            )));
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,144 @@
+//! Workspace snapshot repository for `SQLite` persistence.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use crate::models::short_id;
+use crate::models::snapshot::WorkspaceSnapshot;
+use crate::{AppError, Result};
+
+use super::db::Database;
+
+/// Repository for the records of full-workspace snapshots.
+#[derive(Clone)]
+pub struct SnapshotRepo {
+    db: Arc<Database>,
+}
+
+/// Internal row struct for `SQLite` deserialization.
+#[derive(sqlx::FromRow)]
+struct SnapshotRow {
+    id: String,
+    workspace_root: String,
+    label: Option<String>,
+    created_by: String,
+    session_id: Option<String>,
+    file_count: i64,
+    total_bytes: i64,
+    created_at: String,
+}
+
+impl SnapshotRow {
+    fn into_snapshot(self) -> Result<WorkspaceSnapshot> {
+        let created_at = chrono::DateTime::parse_from_rfc3339(&self.created_at)
+            .map_err(|e| AppError::Db(format!("invalid created_at: {e}")))?
+            .with_timezone(&Utc);
+        Ok(WorkspaceSnapshot {
+            id: self.id,
+            workspace_root: self.workspace_root,
+            label: self.label,
+            created_by: self.created_by,
+            session_id: self.session_id,
+            file_count: usize::try_from(self.file_count).unwrap_or_default(),
+            total_bytes: u64::try_from(self.total_bytes).unwrap_or_default(),
+            created_at,
+        })
+    }
+}
+
+impl SnapshotRepo {
+    /// Create a new repository instance.
+    #[must_use]
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Store a snapshot record.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the insert fails.
+    pub async fn create(&self, snapshot: &WorkspaceSnapshot) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO workspace_snapshot (id, workspace_root, label, created_by, session_id,
+                 file_count, total_bytes, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )
+        .bind(&snapshot.id)
+        .bind(&snapshot.workspace_root)
+        .bind(&snapshot.label)
+        .bind(&snapshot.created_by)
+        .bind(&snapshot.session_id)
+        .bind(i64::try_from(snapshot.file_count).unwrap_or(i64::MAX))
+        .bind(i64::try_from(snapshot.total_bytes).unwrap_or(i64::MAX))
+        .bind(snapshot.created_at.to_rfc3339())
+        .execute(self.db.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Retrieve a snapshot by ID or ID prefix.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` on query failure or `AppError::Config`, naming
+    /// the candidates, if the prefix matches more than one snapshot.
+    pub async fn get_by_prefix(&self, prefix: &str) -> Result<Option<WorkspaceSnapshot>> {
+        let prefix = short_id::normalize(prefix);
+        let rows: Vec<SnapshotRow> = sqlx::query_as(
+            "SELECT * FROM workspace_snapshot WHERE id LIKE ?1 ORDER BY created_at DESC",
+        )
+        .bind(format!("{prefix}%"))
+        .fetch_all(self.db.as_ref())
+        .await?;
+        if let Some(exact) = rows.iter().position(|row| row.id == prefix) {
+            return rows
+                .into_iter()
+                .nth(exact)
+                .map(SnapshotRow::into_snapshot)
+                .transpose();
+        }
+        match rows.len() {
+            0 => Ok(None),
+            1 => rows
+                .into_iter()
+                .next()
+                .map(SnapshotRow::into_snapshot)
+                .transpose(),
+            _ => {
+                let candidates: Vec<String> = rows.into_iter().map(|row| row.id).collect();
+                Err(short_id::ambiguous("snapshot", &prefix, &candidates))
+            }
+        }
+    }
+
+    /// Snapshots of `workspace_root`, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_for_workspace(&self, workspace_root: &str) -> Result<Vec<WorkspaceSnapshot>> {
+        let rows: Vec<SnapshotRow> = sqlx::query_as(
+            "SELECT * FROM workspace_snapshot WHERE workspace_root = ?1
+             ORDER BY created_at DESC, rowid DESC",
+        )
+        .bind(workspace_root)
+        .fetch_all(self.db.as_ref())
+        .await?;
+        rows.into_iter().map(SnapshotRow::into_snapshot).collect()
+    }
+
+    /// Delete a snapshot record.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the delete fails.
+    pub async fn delete(&self, id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM workspace_snapshot WHERE id = ?1")
+            .bind(id)
+            .execute(self.db.as_ref())
+            .await?;
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use chrono::Utc;
 
-use crate::models::stall::{StallAlert, StallAlertStatus};
+use crate::models::stall::{StallAlert, StallAlertStatus, StallResolution};
 use crate::{AppError, Result};
 
 use super::db::Database;
@@ -29,6 +29,8 @@ struct StallAlertRow {
     progress_snapshot: Option<String>,
     slack_ts: Option<String>,
     created_at: String,
+    resolution: Option<String>,
+    resolved_at: Option<String>,
 }
 
 impl StallAlertRow {
@@ -47,6 +49,20 @@ impl StallAlertRow {
             .map(serde_json::from_str)
             .transpose()
             .map_err(|e| AppError::Db(format!("invalid progress_snapshot: {e}")))?;
+        let resolution = self
+            .resolution
+            .as_deref()
+            .map(parse_stall_resolution)
+            .transpose()?;
+        let resolved_at = self
+            .resolved_at
+            .as_deref()
+            .map(|s| {
+                chrono::DateTime::parse_from_rfc3339(s)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|e| AppError::Db(format!("invalid resolved_at: {e}")))
+            })
+            .transpose()?;
 
         Ok(StallAlert {
             id: self.id,
@@ -60,6 +76,8 @@ impl StallAlertRow {
             progress_snapshot,
             slack_ts: self.slack_ts,
             created_at,
+            resolution,
+            resolved_at,
         })
     }
 }
@@ -75,6 +93,15 @@ fn parse_stall_status(s: &str) -> Result<StallAlertStatus> {
     }
 }
 
+fn parse_stall_resolution(s: &str) -> Result<StallResolution> {
+    match s {
+        "self_recovered" => Ok(StallResolution::SelfRecovered),
+        "nudged" => Ok(StallResolution::Nudged),
+        "stopped" => Ok(StallResolution::Stopped),
+        other => Err(AppError::Db(format!("invalid stall resolution: {other}"))),
+    }
+}
+
 fn stall_status_str(s: StallAlertStatus) -> &'static str {
     match s {
         StallAlertStatus::Pending => "pending",
@@ -111,8 +138,8 @@ impl StallAlertRepo {
         sqlx::query(
             "INSERT INTO stall_alert (id, session_id, last_tool, last_activity_at,
              idle_seconds, nudge_count, status, nudge_message, progress_snapshot,
-             slack_ts, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+             slack_ts, created_at, resolution, resolved_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
         )
         .bind(&alert.id)
         .bind(&alert.session_id)
@@ -125,6 +152,8 @@ impl StallAlertRepo {
         .bind(&progress_snapshot)
         .bind(&alert.slack_ts)
         .bind(&created_at)
+        .bind(alert.resolution.map(StallResolution::as_str))
+        .bind(alert.resolved_at.map(|at| at.to_rfc3339()))
         .execute(self.db.as_ref())
         .await?;
 
@@ -164,6 +193,82 @@ impl StallAlertRepo {
         row.map(StallAlertRow::into_stall_alert).transpose()
     }
 
+    /// List every stall alert raised for a session, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_for_session(&self, session_id: &str) -> Result<Vec<StallAlert>> {
+        let rows: Vec<StallAlertRow> = sqlx::query_as(
+            "SELECT * FROM stall_alert WHERE session_id = ?1 ORDER BY created_at ASC",
+        )
+        .bind(session_id)
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter()
+            .map(StallAlertRow::into_stall_alert)
+            .collect()
+    }
+
+    /// Retrieve a session's most recent stall alert that has not yet been
+    /// resolved — the one stall events and alert buttons apply to.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn latest_open_for_session(&self, session_id: &str) -> Result<Option<StallAlert>> {
+        let row: Option<StallAlertRow> = sqlx::query_as(
+            "SELECT * FROM stall_alert \
+             WHERE session_id = ?1 AND resolution IS NULL AND status != 'dismissed' \
+             ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(session_id)
+        .fetch_optional(self.db.as_ref())
+        .await?;
+
+        row.map(StallAlertRow::into_stall_alert).transpose()
+    }
+
+    /// List stall alerts newest first, optionally limited to one session.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_recent(&self, session_id: Option<&str>) -> Result<Vec<StallAlert>> {
+        let rows: Vec<StallAlertRow> = sqlx::query_as(
+            "SELECT * FROM stall_alert WHERE (?1 IS NULL OR session_id = ?1) \
+             ORDER BY created_at DESC",
+        )
+        .bind(session_id)
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter()
+            .map(StallAlertRow::into_stall_alert)
+            .collect()
+    }
+
+    /// Record how a stall ended. An alert is resolved at most once; later
+    /// calls leave the first resolution in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the update fails.
+    pub async fn resolve(&self, id: &str, resolution: StallResolution) -> Result<()> {
+        sqlx::query(
+            "UPDATE stall_alert SET resolution = ?1, resolved_at = ?2 \
+             WHERE id = ?3 AND resolution IS NULL",
+        )
+        .bind(resolution.as_str())
+        .bind(Utc::now().to_rfc3339())
+        .bind(id)
+        .execute(self.db.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
     /// Update the status of a stall alert.
     ///
     /// # Errors
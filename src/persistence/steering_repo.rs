@@ -2,9 +2,9 @@
 
 use std::sync::Arc;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, SecondsFormat, Utc};
 
-use crate::models::steering::{SteeringMessage, SteeringSource};
+use crate::models::steering::{SteeringMessage, SteeringPriority, SteeringSource};
 use crate::{AppError, Result};
 
 use super::db::Database;
@@ -26,14 +26,28 @@ struct SteeringRow {
     created_at: String,
     consumed: i64,
     origin_session_id: Option<String>,
+    priority: String,
+    expires_at: Option<String>,
 }
 
+/// Column list shared by every `SELECT` that maps into [`SteeringRow`].
+const COLUMNS: &str = "id, session_id, channel_id, message, source, created_at, consumed, \
+                       origin_session_id, priority, expires_at";
+
 impl SteeringRow {
     fn into_steering(self) -> Result<SteeringMessage> {
         let source = parse_source(&self.source)?;
         let created_at = chrono::DateTime::parse_from_rfc3339(&self.created_at)
             .map_err(|e| AppError::Db(format!("invalid created_at: {e}")))?
             .with_timezone(&Utc);
+        let expires_at = self
+            .expires_at
+            .map(|s| {
+                chrono::DateTime::parse_from_rfc3339(&s)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|e| AppError::Db(format!("invalid expires_at: {e}")))
+            })
+            .transpose()?;
 
         Ok(SteeringMessage {
             id: self.id,
@@ -44,6 +58,8 @@ impl SteeringRow {
             created_at,
             consumed: self.consumed != 0,
             origin_session_id: self.origin_session_id,
+            priority: parse_priority(&self.priority)?,
+            expires_at,
         })
     }
 }
@@ -52,14 +68,30 @@ fn parse_source(s: &str) -> Result<SteeringSource> {
     match s {
         "slack" => Ok(SteeringSource::Slack),
         "ipc" => Ok(SteeringSource::Ipc),
+        "subtask" => Ok(SteeringSource::Subtask),
         other => Err(AppError::Db(format!("invalid steering source: {other}"))),
     }
 }
 
+fn parse_priority(s: &str) -> Result<SteeringPriority> {
+    match s {
+        "normal" => Ok(SteeringPriority::Normal),
+        "interrupt" => Ok(SteeringPriority::Interrupt),
+        other => Err(AppError::Db(format!("invalid steering priority: {other}"))),
+    }
+}
+
+/// Fixed-width timestamp used for `expires_at`, so SQL string comparison
+/// orders instants correctly.
+fn expiry_str(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Micros, false)
+}
+
 fn source_str(s: SteeringSource) -> &'static str {
     match s {
         SteeringSource::Slack => "slack",
         SteeringSource::Ipc => "ipc",
+        SteeringSource::Subtask => "subtask",
     }
 }
 
@@ -80,8 +112,8 @@ impl SteeringRepo {
         let created_at = msg.created_at.to_rfc3339();
 
         sqlx::query(
-            "INSERT INTO steering_message (id, session_id, channel_id, message, source, created_at, consumed, origin_session_id)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            "INSERT INTO steering_message (id, session_id, channel_id, message, source, created_at, consumed, origin_session_id, priority, expires_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
         )
         .bind(&msg.id)
         .bind(&msg.session_id)
@@ -91,26 +123,68 @@ impl SteeringRepo {
         .bind(&created_at)
         .bind(i64::from(msg.consumed))
         .bind(&msg.origin_session_id)
+        .bind(msg.priority.as_str())
+        .bind(msg.expires_at.map(expiry_str))
         .execute(self.db.as_ref())
         .await?;
 
         Ok(msg.clone())
     }
 
-    /// Fetch all unconsumed steering messages for a session.
+    /// Fetch all unconsumed, unexpired steering messages for a session.
     ///
-    /// Returns messages ordered by creation time (oldest first).
+    /// Interrupt-priority messages come first; within a priority, messages
+    /// are ordered by creation time (oldest first). Expired messages are
+    /// skipped here and removed by [`drop_expired`](Self::drop_expired).
     ///
     /// # Errors
     ///
     /// Returns `AppError::Db` if the query fails.
     pub async fn fetch_unconsumed(&self, session_id: &str) -> Result<Vec<SteeringMessage>> {
-        let rows: Vec<SteeringRow> = sqlx::query_as(
-            "SELECT id, session_id, channel_id, message, source, created_at, consumed, origin_session_id
+        let rows: Vec<SteeringRow> = sqlx::query_as(&format!(
+            "SELECT {COLUMNS}
              FROM steering_message
              WHERE session_id = ?1 AND consumed = 0
-             ORDER BY created_at ASC, rowid ASC",
+               AND (expires_at IS NULL OR expires_at > ?2)
+             ORDER BY priority = 'interrupt' DESC, created_at ASC, rowid ASC"
+        ))
+        .bind(session_id)
+        .bind(expiry_str(Utc::now()))
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter().map(SteeringRow::into_steering).collect()
+    }
+
+    /// Number of undelivered, unexpired steering messages across sessions.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn count_pending(&self) -> Result<u64> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM steering_message
+             WHERE consumed = 0 AND (expires_at IS NULL OR expires_at > ?1)",
         )
+        .bind(expiry_str(Utc::now()))
+        .fetch_one(self.db.as_ref())
+        .await?;
+        Ok(u64::try_from(count).unwrap_or(0))
+    }
+
+    /// List every steering message queued for a session, delivered or not,
+    /// oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query fails.
+    pub async fn list_for_session(&self, session_id: &str) -> Result<Vec<SteeringMessage>> {
+        let rows: Vec<SteeringRow> = sqlx::query_as(&format!(
+            "SELECT {COLUMNS}
+             FROM steering_message
+             WHERE session_id = ?1
+             ORDER BY created_at ASC, rowid ASC"
+        ))
         .bind(session_id)
         .fetch_all(self.db.as_ref())
         .await?;
@@ -118,6 +192,26 @@ impl SteeringRepo {
         rows.into_iter().map(SteeringRow::into_steering).collect()
     }
 
+    /// Delete every unconsumed message whose TTL elapsed at or before `now`.
+    ///
+    /// Returns the dropped messages so the caller can report them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the query or delete fails.
+    pub async fn drop_expired(&self, now: DateTime<Utc>) -> Result<Vec<SteeringMessage>> {
+        let rows: Vec<SteeringRow> = sqlx::query_as(&format!(
+            "DELETE FROM steering_message
+             WHERE consumed = 0 AND expires_at IS NOT NULL AND expires_at <= ?1
+             RETURNING {COLUMNS}"
+        ))
+        .bind(expiry_str(now))
+        .fetch_all(self.db.as_ref())
+        .await?;
+
+        rows.into_iter().map(SteeringRow::into_steering).collect()
+    }
+
     /// Rebind a crashed session's *unconsumed* steering messages to a resumed
     /// session so the pending queue survives a respawn (F.3-T2).
     ///
@@ -11,7 +11,7 @@
 use tracing::{info, info_span};
 
 use crate::models::approval::RiskLevel;
-use crate::models::policy::{CompiledWorkspacePolicy, WorkspacePolicy};
+use crate::models::policy::{CompiledWorkspacePolicy, ProxyAction, ProxyRules, WorkspacePolicy};
 
 /// Additional metadata supplied by the agent for fine-grained evaluation.
 #[derive(Debug, Clone, serde::Deserialize, Default)]
@@ -31,6 +31,17 @@ pub struct AutoApproveResult {
     pub matched_rule: Option<String>,
 }
 
+/// Classification of a proxied downstream tool call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProxyVerdict {
+    /// How the call is handled.
+    pub action: ProxyAction,
+    /// The rule that decided it, or `None` when the default applied.
+    pub matched_rule: Option<String>,
+    /// Risk recorded on the call's approval request.
+    pub risk_level: RiskLevel,
+}
+
 /// Evaluates auto-approve policy rules against a tool invocation.
 pub struct PolicyEvaluator;
 
@@ -108,6 +119,74 @@ impl PolicyEvaluator {
         // ── 6. No match → deny ──────────────────────────────
         deny()
     }
+
+    /// Classify a call to the proxied tool `qualified_name` (`<server>.<tool>`).
+    ///
+    /// Only the `proxy` rules apply; the general rules of [`Self::check`]
+    /// describe the agent's own tools and never forward a proxied call.
+    /// Evaluation order:
+    /// 1. Match against `proxy.deny` — always applies.
+    /// 2. Match against `proxy.require_approval` — always applies.
+    /// 3. If the policy is enabled and the call is not `critical` risk,
+    ///    match against `proxy.auto_approve`.
+    /// 4. Otherwise `proxy.default_action`, where `auto_approve` also
+    ///    requires the policy to be enabled and a non-`critical` call.
+    #[must_use]
+    pub fn classify_proxy_call(
+        qualified_name: &str,
+        policy: &CompiledWorkspacePolicy,
+    ) -> ProxyVerdict {
+        let _span = info_span!("policy_classify_proxy", tool = %qualified_name).entered();
+        let rules = &policy.raw.proxy;
+        let risk_level = proxy_risk(rules, qualified_name);
+        let verdict = |action, matched_rule| ProxyVerdict {
+            action,
+            matched_rule,
+            risk_level,
+        };
+
+        let buckets = [
+            (&rules.deny, ProxyAction::Deny, "proxy:deny"),
+            (
+                &rules.require_approval,
+                ProxyAction::RequireApproval,
+                "proxy:require_approval",
+            ),
+        ];
+        for (patterns, action, kind) in buckets {
+            if let Some(rule) = try_glob_match(patterns, qualified_name, kind) {
+                info!(matched_rule = %rule, ?action, "proxied call classified");
+                return verdict(action, Some(rule));
+            }
+        }
+
+        let may_auto_approve = policy.raw.enabled && risk_level != RiskLevel::Critical;
+        if may_auto_approve {
+            let auto = try_glob_match(&rules.auto_approve, qualified_name, "proxy:auto_approve");
+            if let Some(rule) = auto {
+                info!(matched_rule = %rule, "proxied call auto-approved");
+                return verdict(ProxyAction::AutoApprove, Some(rule));
+            }
+        }
+
+        match rules.default_action {
+            ProxyAction::AutoApprove if !may_auto_approve => {
+                verdict(ProxyAction::RequireApproval, None)
+            }
+            action => verdict(action, None),
+        }
+    }
+}
+
+/// Risk of a proxied call from the `critical_risk` and `high_risk` rules.
+fn proxy_risk(rules: &ProxyRules, qualified_name: &str) -> RiskLevel {
+    if try_glob_match(&rules.critical_risk, qualified_name, "proxy:critical_risk").is_some() {
+        RiskLevel::Critical
+    } else if try_glob_match(&rules.high_risk, qualified_name, "proxy:high_risk").is_some() {
+        RiskLevel::High
+    } else {
+        RiskLevel::Low
+    }
 }
 
 /// Check whether the request risk is within the policy threshold.
@@ -141,27 +220,30 @@ fn match_file_patterns(
 ) -> Option<String> {
     // Determine which pattern set to check based on tool semantics.
     let (patterns, kind) = if tool_name.contains("write") || tool_name == "accept_diff" {
-        (&policy.file_patterns.write, "write")
+        (&policy.file_patterns.write, "file_pattern:write")
     } else if tool_name.contains("read") {
-        (&policy.file_patterns.read, "read")
+        (&policy.file_patterns.read, "file_pattern:read")
     } else {
         // Try write patterns first, then read patterns.
-        if let Some(rule) = try_glob_match(&policy.file_patterns.write, file_path, "write") {
+        if let Some(rule) =
+            try_glob_match(&policy.file_patterns.write, file_path, "file_pattern:write")
+        {
             return Some(rule);
         }
-        return try_glob_match(&policy.file_patterns.read, file_path, "read");
+        return try_glob_match(&policy.file_patterns.read, file_path, "file_pattern:read");
     };
 
     try_glob_match(patterns, file_path, kind)
 }
 
-/// Try each glob pattern against the file path. Returns the first match.
-fn try_glob_match(patterns: &[String], file_path: &str, kind: &str) -> Option<String> {
+/// Try each glob pattern against `candidate`. Returns the first match as
+/// the rule `{kind}:{pattern}`.
+fn try_glob_match(patterns: &[String], candidate: &str, kind: &str) -> Option<String> {
     for pattern in patterns {
         match glob::Pattern::new(pattern) {
             Ok(glob_pat) => {
-                if glob_pat.matches(file_path) {
-                    return Some(format!("file_pattern:{kind}:{pattern}"));
+                if glob_pat.matches(candidate) {
+                    return Some(format!("{kind}:{pattern}"));
                 }
             }
             Err(err) => {
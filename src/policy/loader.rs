@@ -7,9 +7,10 @@
 use std::fs;
 use std::path::Path;
 
-use tracing::warn;
+use tracing::{info, warn};
 
 use crate::models::policy::{CompiledWorkspacePolicy, WorkspacePolicy};
+use crate::policy::watcher::PolicyCache;
 use crate::Result;
 
 /// Relative path within a workspace root to the policy file.
@@ -73,4 +74,26 @@ impl PolicyLoader {
 
         Ok(CompiledWorkspacePolicy::from_policy(policy))
     }
+
+    /// Return the policy for `workspace_root` from `cache`, loading and
+    /// caching it on a miss (T052).
+    ///
+    /// # Errors
+    ///
+    /// Propagates errors from [`PolicyLoader::load`].
+    pub async fn load_cached(
+        cache: &PolicyCache,
+        workspace_root: &Path,
+    ) -> Result<CompiledWorkspacePolicy> {
+        if let Some(cached) = cache.read().await.get(workspace_root) {
+            info!("policy cache hit — using pre-compiled policy");
+            return Ok(cached.clone());
+        }
+        let loaded = Self::load(workspace_root)?;
+        cache
+            .write()
+            .await
+            .insert(workspace_root.to_path_buf(), loaded.clone());
+        Ok(loaded)
+    }
 }
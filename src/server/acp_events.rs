@@ -0,0 +1,867 @@
+//! ACP event consumer (T099).
+//!
+//! Dispatches the [`AgentEvent`]s that every ACP reader task sends on the
+//! shared channel: stall detector resets, debounced status text for Slack,
+//! clearance and permission requests, forwarded prompts, heartbeats, hook
+//! subscriptions, and session termination.
+
+use std::sync::Arc;
+
+use tracing::{info, warn};
+
+use crate::driver::{AgentEvent, PermissionOption};
+use crate::state::AppState;
+
+/// ACP event consumer — dispatches [`AgentEvent`]s from all reader tasks.
+///
+/// Reads events from the shared channel and:
+/// - [`StreamActivity`]: resets the per-session stall detector timer (S063).
+/// - [`StatusUpdated`]: accumulates text fragments per session and posts
+///   aggregated messages to the session's Slack thread after a 2-second
+///   debounce (prevents flooding from word-by-word `agent_message_chunk`
+///   streaming).
+/// - [`SessionTerminated`]: resolves any pending clearance requests as
+///   `Interrupted` (S068) and optionally notifies the operator on Slack.
+/// - All other variants: logged at INFO for observability.
+///
+/// Exits when the channel closes or `cancel` fires.
+#[allow(clippy::too_many_lines)]
+pub(crate) async fn run_acp_event_consumer(
+    mut rx: tokio::sync::mpsc::Receiver<AgentEvent>,
+    cancel: tokio_util::sync::CancellationToken,
+    state: Arc<AppState>,
+) {
+    use crate::models::session::ProtocolMode;
+    use std::collections::HashMap;
+    use tokio::time::{Duration, Instant};
+
+    /// Per-session text accumulator for debounced Slack posting.
+    struct TextBuffer {
+        text: String,
+        last_update: Instant,
+    }
+
+    let mut text_buffers: HashMap<String, TextBuffer> = HashMap::new();
+    let debounce = Duration::from_secs(2);
+    let mut flush_interval = tokio::time::interval(Duration::from_millis(500));
+
+    loop {
+        tokio::select! {
+            biased;
+            () = cancel.cancelled() => {
+                info!("acp event consumer: cancellation received, stopping");
+                break;
+            }
+            _ = flush_interval.tick() => {
+                // Flush text buffers that have been idle for ≥ debounce duration.
+                let now = Instant::now();
+                let expired_keys: Vec<String> = text_buffers
+                    .iter()
+                    .filter(|(_, buf)| now.duration_since(buf.last_update) >= debounce)
+                    .map(|(k, _)| k.clone())
+                    .collect();
+                for session_id in expired_keys {
+                    if let Some(buf) = text_buffers.remove(&session_id) {
+                        flush_text_to_slack(&state, &session_id, &buf.text).await;
+                    }
+                }
+            }
+            event = rx.recv() => {
+                // Mirror every ACP event onto the shared bus for audit/metrics
+                // subscribers; dispatch below still consumes the reliable mpsc.
+                if let Some(ref ev) = event {
+                    state.event_bus.publish(ProtocolMode::Acp, None, ev.clone());
+                }
+                match event {
+                    None => {
+                        info!("acp event consumer: channel closed, stopping");
+                        break;
+                    }
+                    Some(AgentEvent::StreamActivity { ref session_id }) => {
+                        // Reset stall detector timer on any stream activity (S063).
+                        if let Some(ref detectors) = state.stall_detectors {
+                            let map = detectors.lock().await;
+                            if let Some(handle) = map.get(session_id) {
+                                handle.reset();
+                            }
+                        }
+                    }
+                    Some(AgentEvent::ClearanceRequested {
+                        ref session_id,
+                        ref request_id,
+                        ref title,
+                        ref description,
+                        ref diff,
+                        ref file_path,
+                        ref risk_level,
+                        ..
+                    }) => {
+                        info!(
+                            session_id,
+                            request_id,
+                            title,
+                            "acp event: clearance requested"
+                        );
+                        handle_clearance_requested(
+                            &state,
+                            session_id,
+                            request_id,
+                            title,
+                            description,
+                            diff.clone(),
+                            file_path,
+                            risk_level,
+                            ClearanceRegistration::Clearance,
+                        )
+                        .await;
+                    }
+                    Some(AgentEvent::PermissionRequested {
+                        ref session_id,
+                        ref request_id,
+                        ref request_id_raw,
+                        ref title,
+                        ref description,
+                        ref file_path,
+                        ref risk_level,
+                        ref options,
+                    }) => {
+                        info!(
+                            session_id,
+                            request_id,
+                            title,
+                            "acp event: permission requested (session/request_permission)"
+                        );
+                        handle_clearance_requested(
+                            &state,
+                            session_id,
+                            request_id,
+                            title,
+                            description,
+                            None,
+                            file_path,
+                            risk_level,
+                            ClearanceRegistration::Permission(
+                                options.clone(),
+                                request_id_raw.clone(),
+                            ),
+                        )
+                        .await;
+                    }
+                    Some(AgentEvent::StatusUpdated { ref session_id, ref message }) => {
+                        // Accumulate text; debounce flush posts to Slack thread.
+                        let entry = text_buffers
+                            .entry(session_id.clone())
+                            .or_insert_with(|| TextBuffer {
+                                text: String::new(),
+                                last_update: Instant::now(),
+                            });
+                        entry.text.push_str(message);
+                        entry.last_update = Instant::now();
+                    }
+                    Some(AgentEvent::PromptForwarded {
+                        ref session_id,
+                        ref prompt_id,
+                        ref prompt_text,
+                        ref prompt_type,
+                    }) => {
+                        info!(
+                            session_id,
+                            prompt_id,
+                            "acp event: prompt forwarded"
+                        );
+                        handle_prompt_forwarded(
+                            &state,
+                            session_id,
+                            prompt_id,
+                            prompt_text,
+                            prompt_type,
+                        )
+                        .await;
+                    }
+                    Some(AgentEvent::HooksSubscribed { ref session_id, ref events }) => {
+                        info!(session_id, ?events, "acp event: session hooks subscribed");
+                        state.session_hooks.subscribe(session_id, events.iter().copied());
+                    }
+                    Some(AgentEvent::HeartbeatReceived { ref session_id, ref progress }) => {
+                        info!(session_id, "acp event: heartbeat received");
+                        handle_heartbeat_received(&state, session_id, progress.clone()).await;
+                    }
+                    Some(AgentEvent::SessionTerminated { ref session_id, exit_code, ref reason }) => {
+                        info!(
+                            session_id,
+                            exit_code,
+                            reason,
+                            "acp event: session terminated — resolving pending clearances"
+                        );
+
+                        // Flush any buffered text before posting the termination notice.
+                        if let Some(buf) = text_buffers.remove(session_id) {
+                            flush_text_to_slack(&state, session_id, &buf.text).await;
+                        }
+
+                        handle_session_terminated(&state, session_id, reason).await;
+                    }
+                    // Notices come from MCP tool handlers, never an ACP stream.
+                    Some(AgentEvent::NoticePosted { .. }) => {}
+                }
+            }
+        }
+    }
+}
+
+/// Handle the `SessionTerminated` event: update DB status, resolve pending
+/// clearances, deregister driver state, and notify the operator on Slack,
+/// on the session's linked issue, and by email.
+async fn handle_session_terminated(state: &Arc<AppState>, session_id: &str, reason: &str) {
+    use crate::models::approval::ApprovalStatus;
+    use crate::models::session::SessionStatus;
+    use crate::persistence::approval_repo::ApprovalRepo;
+    use crate::persistence::session_repo::SessionRepo;
+    use crate::slack::client::SlackMessage;
+    use slack_morphism::prelude::{SlackChannelId, SlackTs};
+
+    let session_repo = SessionRepo::new(Arc::clone(&state.db));
+
+    // F-03: Mark the session as Interrupted in the database so it no longer
+    // appears in list_active(). Without this, sessions whose agent process
+    // exits naturally (EOF, crash) remain Active forever.
+    let terminated = match session_repo
+        .set_terminated(session_id, SessionStatus::Interrupted)
+        .await
+    {
+        Ok(session) => Some(session),
+        Err(err) => {
+            warn!(%err, session_id, "failed to mark session as interrupted on termination");
+            None
+        }
+    };
+
+    // S068: Resolve any pending clearance requests as Interrupted
+    // so the operator is not left waiting for buttons that will never be clicked.
+    let approval_repo = ApprovalRepo::new(Arc::clone(&state.db));
+    if let Err(err) = approval_repo
+        .resolve_pending_for_session(session_id, ApprovalStatus::Interrupted)
+        .await
+    {
+        warn!(%err, session_id, "failed to resolve pending clearances on termination");
+    }
+
+    // Report to the linked issue and mail the summary once the clearances
+    // above are settled, so both reflect the final approval outcomes.
+    if let Some(session) = terminated {
+        let ended = format!("agent process exited ({reason})");
+        if let Err(err) =
+            crate::orchestrator::subtask::report_to_parent(&state.db, &session, &ended).await
+        {
+            warn!(%err, session_id, "failed to report subtask to parent");
+        }
+        crate::integrations::issues::spawn_completion_report(
+            Arc::clone(&state.config),
+            session.clone(),
+            ended.clone(),
+        );
+        crate::integrations::email::spawn_summary_email(
+            Arc::clone(&state.config),
+            Arc::clone(&state.db),
+            session,
+            ended,
+        );
+    }
+
+    // Deregister the ACP driver's in-memory state for this session.
+    if let Some(ref acp_driver) = state.acp_driver {
+        acp_driver.deregister_session(session_id).await;
+    }
+    state.driver_registry.deregister(session_id).await;
+    state.session_hooks.clear(session_id);
+
+    // F-20: clean up any pending thread-reply fallback entries for this session.
+    // Dropping the senders causes the spawned waiter tasks to exit cleanly.
+    crate::slack::handlers::thread_reply::cleanup_session_fallbacks(
+        session_id,
+        &state.pending_thread_replies,
+    )
+    .await;
+
+    // Notify the operator via Slack (when available).
+    let Some(ref slack) = state.slack else { return };
+    let (ch, ts) = match session_repo.get_by_id(session_id).await {
+        Ok(Some(sess)) => (
+            sess.channel_id.unwrap_or_default(),
+            sess.thread_ts.map(SlackTs),
+        ),
+        _ => (state.config.slack.channel_id.clone(), None),
+    };
+    if !ch.is_empty() {
+        let text = format!(
+            "\u{1f534} ACP session `{session_id}` terminated \
+             (reason: {reason}). Any pending clearances have been cancelled."
+        );
+        let msg = SlackMessage {
+            channel: SlackChannelId(ch),
+            text: Some(text),
+            blocks: None,
+            thread_ts: ts,
+        };
+        if let Err(err) = slack.enqueue(msg).await {
+            warn!(%err, session_id, "failed to post termination notification");
+        }
+    }
+}
+
+/// Post accumulated text to the session's Slack thread.
+///
+/// Looks up the session's `channel_id` and `thread_ts` from the database,
+/// then enqueues a single aggregated message. Muted sessions only log the
+/// text. Silently logs failures without propagating errors.
+async fn flush_text_to_slack(state: &Arc<AppState>, session_id: &str, text: &str) {
+    use crate::persistence::session_repo::SessionRepo;
+    use crate::slack::client::SlackMessage;
+    use slack_morphism::prelude::{SlackChannelId, SlackTs};
+
+    if text.trim().is_empty() {
+        return;
+    }
+
+    let Some(ref slack) = state.slack else {
+        info!(session_id, "acp text flush: no slack service, logging only");
+        info!(session_id, text, "acp agent response");
+        return;
+    };
+
+    let session_repo = SessionRepo::new(Arc::clone(&state.db));
+    let (channel_id, thread_ts) = match session_repo.get_by_id(session_id).await {
+        Ok(Some(sess)) if sess.is_muted(chrono::Utc::now()) => {
+            info!(session_id, text, "acp agent response (session muted)");
+            return;
+        }
+        Ok(Some(sess)) => (sess.channel_id, sess.thread_ts),
+        Ok(None) => {
+            warn!(session_id, "acp text flush: session not found in db");
+            return;
+        }
+        Err(err) => {
+            warn!(%err, session_id, "acp text flush: db lookup failed");
+            return;
+        }
+    };
+
+    let Some(ch) = channel_id else {
+        info!(session_id, text, "acp agent response (no channel)");
+        return;
+    };
+
+    let msg = SlackMessage {
+        channel: SlackChannelId(ch),
+        text: Some(text.to_owned()),
+        blocks: None,
+        thread_ts: thread_ts.map(SlackTs),
+    };
+
+    if let Err(err) = slack.enqueue(msg).await {
+        warn!(%err, session_id, "acp text flush: failed to post to Slack");
+    }
+}
+
+/// Which ACP response-routing registration a clearance/permission handler
+/// should perform so the operator's decision reaches the agent.
+enum ClearanceRegistration {
+    /// Bespoke `clearance/request` — register via `register_clearance`.
+    Clearance,
+    /// Standard `session/request_permission` (ADR-0016) — register via
+    /// `register_permission`, carrying the offered options and the raw JSON-RPC
+    /// id to echo in the outcome.
+    Permission(Vec<PermissionOption>, serde_json::Value),
+}
+
+/// Handle the `ClearanceRequested` ACP event (FR-002, FR-003, FR-010, FR-011).
+///
+/// Creates and persists an [`ApprovalRequest`], registers it with the ACP
+/// driver for response routing, and posts an interactive approval message to
+/// the session's Slack thread (directly, to capture the message `ts`). The
+/// message carries the same context as the MCP path: a warning about other
+/// sessions' requests for the same file, a blame summary of the affected
+/// lines and matching CODEOWNERS entries.
+///
+/// Failures at each step are logged and handled gracefully — no panic, no
+/// propagation. If the session is not found, the event is discarded silently
+/// (with a warning). If the DB write fails, the driver registration is also
+/// skipped (SC-003) to avoid unaudited in-memory state.
+#[allow(clippy::too_many_lines)]
+#[allow(clippy::too_many_arguments)]
+async fn handle_clearance_requested(
+    state: &Arc<AppState>,
+    session_id: &str,
+    request_id: &str,
+    title: &str,
+    description: &str,
+    diff: Option<String>,
+    file_path: &str,
+    risk_level_str: &str,
+    registration: ClearanceRegistration,
+) {
+    use std::path::Path;
+
+    use crate::diff::ownership::ApprovalContext;
+    use crate::diff::validate_workspace_path;
+    use crate::mcp::tools::util::compute_file_hash;
+    use crate::models::approval::{parse_risk_level, ApprovalRequest};
+    use crate::orchestrator::approval_conflicts;
+    use crate::persistence::approval_repo::ApprovalRepo;
+    use crate::persistence::session_repo::SessionRepo;
+    use crate::slack::blocks;
+    use crate::slack::client::SlackMessage;
+    use slack_morphism::prelude::{SlackChannelId, SlackTs};
+
+    let session_repo = SessionRepo::new(Arc::clone(&state.db));
+
+    // Step 1: look up the owning session — discard if not found.
+    let session = match session_repo.get_by_id(session_id).await {
+        Ok(Some(s)) => s,
+        Ok(None) => {
+            warn!(
+                session_id,
+                "clearance requested for unknown session — discarding"
+            );
+            return;
+        }
+        Err(err) => {
+            warn!(%err, session_id, "clearance requested: session db lookup failed — discarding");
+            return;
+        }
+    };
+
+    // Step 2: parse risk level (case-sensitive, default Low per FR-011).
+    let risk_level = parse_risk_level(risk_level_str);
+
+    // Step 3: validate the file path and compute its hash.
+    // On path violation, use the "new_file" sentinel — the approval still
+    // proceeds but without an integrity check against a specific file.
+    let workspace_root = Path::new(&session.workspace_root);
+    let (validated_path, original_hash) = match validate_workspace_path(workspace_root, file_path) {
+        Ok(abs_path) => {
+            let hash = compute_file_hash(&abs_path).await.unwrap_or_else(|err| {
+                warn!(%err, session_id, file_path, "failed to compute file hash");
+                "new_file".to_owned()
+            });
+            (Some(abs_path), hash)
+        }
+        Err(err) => {
+            warn!(%err, session_id, file_path, "path validation failed — using 'new_file' sentinel");
+            (None, "new_file".to_owned())
+        }
+    };
+    let effective_file_path = match &validated_path {
+        Some(abs_path) => abs_path.strip_prefix(workspace_root).map_or_else(
+            |_| file_path.to_owned(),
+            |rel| rel.to_string_lossy().into_owned(),
+        ),
+        None => file_path.to_owned(),
+    };
+
+    // Step 4: construct the approval request.
+    // Use the agent's `request_id` as `approval.id` so that:
+    // - The Slack button value matches the driver registration key
+    // - `clearance/response` carries `"id": request_id` (per ACP JSON-RPC correlation)
+    // - `approval_repo.get_by_id(request_id)` works in the Slack approval handler
+    let diff_content = diff.unwrap_or_default();
+    let mut approval = ApprovalRequest::new(
+        session_id.to_owned(),
+        title.to_owned(),
+        Some(description.to_owned()),
+        diff_content.clone(),
+        effective_file_path.clone(),
+        risk_level,
+        original_hash,
+    );
+    approval.id = request_id.to_owned();
+    let approval_id = approval.id.clone();
+
+    // Step 5: persist to DB — skip driver registration on failure (SC-003).
+    let approval_repo = ApprovalRepo::new(Arc::clone(&state.db));
+    if let Err(err) = approval_repo.create(&approval).await {
+        warn!(%err, session_id, "failed to persist clearance request — skipping registration");
+        return;
+    }
+
+    // Step 6: register with ACP driver for response routing.
+    if let Some(ref acp_driver) = state.acp_driver {
+        match &registration {
+            ClearanceRegistration::Clearance => {
+                acp_driver
+                    .register_clearance(session_id, &approval_id)
+                    .await;
+            }
+            ClearanceRegistration::Permission(options, raw_id) => {
+                acp_driver
+                    .register_permission(session_id, &approval_id, options.clone(), raw_id.clone())
+                    .await;
+            }
+        }
+    }
+
+    // Step 7: post interactive approval message to Slack.
+    let Some(ref slack) = state.slack else {
+        info!(
+            session_id,
+            approval_id, "clearance persisted; no Slack service configured"
+        );
+        return;
+    };
+
+    let channel_id = match session.channel_id.as_deref() {
+        Some(ch) if !ch.is_empty() => ch.to_owned(),
+        _ => {
+            if state.config.slack.channel_id.is_empty() {
+                info!(
+                    session_id,
+                    approval_id, "clearance persisted; no Slack channel configured"
+                );
+                return;
+            }
+            state.config.slack.channel_id.clone()
+        }
+    };
+
+    let session_thread_ts = session.thread_ts.as_deref().map(|s| SlackTs(s.to_owned()));
+
+    // RI-002: treat empty description as absent so build_approval_blocks does
+    // not render a blank section block in the Slack message.
+    let description_opt = if description.is_empty() {
+        None
+    } else {
+        Some(description)
+    };
+
+    let mut message_blocks = blocks::build_approval_blocks(
+        title,
+        description_opt,
+        &diff_content,
+        &effective_file_path,
+        risk_level,
+    );
+    message_blocks.extend(
+        blocks::hunk_page_blocks(&approval_id, &diff_content, 0)
+            .into_iter()
+            .flatten(),
+    );
+    let context = ApprovalContext::gather(
+        workspace_root,
+        &effective_file_path,
+        &diff_content,
+        &state.config.codeowners,
+    )
+    .await;
+    if let Some(warning) =
+        approval_conflicts::warning(&state.db, &session, &effective_file_path).await
+    {
+        message_blocks.push(blocks::text_section(&warning));
+    }
+    if let Some(ctx) = blocks::approval_context_text(&context) {
+        message_blocks.push(blocks::text_section(&ctx));
+    }
+    if let Some(link) =
+        crate::mcp::approval_link::slack_line(&state.config.approval_links, &approval_id)
+    {
+        message_blocks.push(blocks::text_section(&link));
+    }
+    message_blocks.push(blocks::approval_buttons(&approval_id));
+
+    // C5: post the approval message first so we have a Slack `ts` to use as
+    // the thread anchor for the diff file upload.  Previously the upload ran
+    // before the post, which left the uploaded file detached from the session
+    // thread when `session_thread_ts` was `None`.
+    let msg = SlackMessage {
+        channel: SlackChannelId(channel_id.clone()),
+        text: Some(format!("\u{1f4cb} ACP Approval Request: {title}")),
+        blocks: Some(message_blocks),
+        thread_ts: session_thread_ts.clone(),
+    };
+
+    let posted_ts = match slack.post_message_direct(msg).await {
+        Ok(ts) => {
+            // If this session had no thread root yet, use the approval post as root.
+            if session_thread_ts.is_none() {
+                if let Err(err) = session_repo.set_thread_ts(session_id, &ts.0).await {
+                    warn!(%err, session_id, "failed to record thread_ts from clearance post");
+                }
+            }
+            // Record the message and its thread so the button-replacement
+            // handler can update it and follow-ups stay in its thread.
+            let thread_ts = session_thread_ts.as_ref().unwrap_or(&ts);
+            if let Err(err) = approval_repo
+                .set_slack_anchor(&approval_id, &ts.0, &thread_ts.0)
+                .await
+            {
+                warn!(%err, approval_id, "failed to record slack anchor on clearance approval");
+            }
+            Some(ts)
+        }
+        Err(err) => {
+            warn!(%err, session_id, approval_id, "failed to post clearance approval message to Slack");
+            None
+        }
+    };
+
+    // RI-001 / C5: upload large diffs after posting so the file is attached to
+    // the session thread (using the ts we just obtained, falling back to the
+    // pre-existing session thread ts).
+    let diff_line_count = diff_content.lines().count();
+    if diff_line_count > blocks::INLINE_DIFF_THRESHOLD {
+        let upload_thread_ts = posted_ts.or(session_thread_ts);
+        let sanitized = effective_file_path.replace(['/', '.', '\\'], "_");
+        let filename = format!("{sanitized}.diff.txt");
+        if let Err(err) = slack
+            .upload_file(
+                SlackChannelId(channel_id),
+                &filename,
+                &diff_content,
+                upload_thread_ts,
+                Some("text"),
+            )
+            .await
+        {
+            warn!(%err, session_id, approval_id, "failed to upload diff file to Slack");
+        }
+    }
+}
+
+/// Handle the `HeartbeatReceived` ACP event (T8.4).
+///
+/// Mirrors the MCP `heartbeat` tool so ACP mode gets the same liveness and
+/// steering-pickup behaviour without the HTTP endpoint: updates the progress
+/// snapshot (when valid), refreshes last activity, resets the stall timer, and
+/// delivers any queued operator steering to the agent over the ACP stream.
+async fn handle_heartbeat_received(
+    state: &Arc<AppState>,
+    session_id: &str,
+    progress: Option<Vec<crate::models::progress::ProgressItem>>,
+) {
+    use crate::acp::reader::deliver_pending_steering;
+    use crate::models::progress::{all_done, validate_snapshot};
+    use crate::persistence::session_repo::SessionRepo;
+    use crate::persistence::steering_repo::SteeringRepo;
+
+    let session_repo = SessionRepo::new(Arc::clone(&state.db));
+
+    // A heartbeat proves the agent is alive and reachable, so restore Online
+    // (mirrors the reconnect flush). Without this, an actively-heartbeating
+    // session left Offline/Stalled would keep getting operator steering routed
+    // to the durable queue with a misleading "agent offline" notice, instead of
+    // direct delivery.
+    if let Err(err) = session_repo
+        .set_connectivity_status(
+            session_id,
+            crate::models::session::ConnectivityStatus::Online,
+        )
+        .await
+    {
+        warn!(%err, session_id, "heartbeat: failed to set connectivity Online");
+    }
+
+    // Update the progress snapshot when a valid one is supplied.
+    // Divergence from the MCP `heartbeat` tool (which returns an error to the
+    // caller): this is a fire-and-forget event with no caller to reject, so an
+    // invalid snapshot is logged and ignored rather than surfaced as an error.
+    let mut work_complete = None;
+    if let Some(snapshot) = progress {
+        match validate_snapshot(&snapshot) {
+            Ok(()) => {
+                work_complete = Some(all_done(&snapshot));
+                if let Err(err) = session_repo
+                    .update_progress_snapshot(session_id, Some(snapshot))
+                    .await
+                {
+                    warn!(%err, session_id, "heartbeat: failed to update progress snapshot");
+                }
+            }
+            Err(err) => {
+                warn!(%err, session_id, "heartbeat: invalid progress snapshot — ignoring");
+            }
+        }
+    }
+
+    // Refresh last activity so the session is not considered idle.
+    if let Err(err) = session_repo
+        .update_last_activity(session_id, Some("heartbeat".into()))
+        .await
+    {
+        warn!(%err, session_id, "heartbeat: failed to update last activity");
+    }
+
+    // Reset the stall detector timer for this session.
+    if let Some(ref detectors) = state.stall_detectors {
+        let guards = detectors.lock().await;
+        if let Some(handle) = guards.get(session_id) {
+            if let Some(complete) = work_complete {
+                handle.set_work_complete(complete);
+            }
+            handle.reset();
+        }
+    }
+
+    // Deliver any queued operator steering to the agent.
+    let steering_repo = SteeringRepo::new(Arc::clone(&state.db));
+    match deliver_pending_steering(session_id, state.driver.as_ref(), &steering_repo).await {
+        Ok(count) if count > 0 => {
+            info!(
+                session_id,
+                delivered = count,
+                "heartbeat: delivered queued steering"
+            );
+        }
+        Ok(_) => {}
+        Err(err) => {
+            warn!(%err, session_id, "heartbeat: failed to fetch or deliver steering");
+        }
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+async fn handle_prompt_forwarded(
+    state: &Arc<AppState>,
+    session_id: &str,
+    prompt_id: &str,
+    prompt_text: &str,
+    prompt_type_str: &str,
+) {
+    use crate::models::prompt::{parse_prompt_type, ContinuationPrompt};
+    use crate::persistence::prompt_repo::PromptRepo;
+    use crate::persistence::session_repo::SessionRepo;
+    use crate::slack::anchor::Anchor;
+    use crate::slack::blocks;
+    use crate::slack::client::SlackMessage;
+    use slack_morphism::prelude::{SlackChannelId, SlackTs};
+
+    let session_repo = SessionRepo::new(Arc::clone(&state.db));
+
+    // Step 1: look up the owning session — discard if not found.
+    let session = match session_repo.get_by_id(session_id).await {
+        Ok(Some(s)) => s,
+        Ok(None) => {
+            warn!(
+                session_id,
+                "prompt forwarded for unknown session — discarding"
+            );
+            return;
+        }
+        Err(err) => {
+            warn!(%err, session_id, "prompt forwarded: session db lookup failed — discarding");
+            return;
+        }
+    };
+
+    // Step 2: parse prompt type string (case-sensitive, default Continuation).
+    let prompt_type = parse_prompt_type(prompt_type_str);
+
+    // Step 3: construct the continuation prompt.
+    // Override `.id` with the agent's `prompt_id` so that:
+    // - The Slack button value matches the driver registration key
+    // - `prompt/response` carries `"id": prompt_id` (ACP JSON-RPC correlation)
+    // - `prompt_repo.get_by_id(prompt_id)` works in the Slack prompt handler
+    let mut prompt = ContinuationPrompt::new(
+        session_id.to_owned(),
+        prompt_text.to_owned(),
+        prompt_type,
+        None, // elapsed_seconds — ACP-specific: not available in event
+        None, // actions_taken — ACP-specific: not available in event
+    );
+    prompt.id = prompt_id.to_owned();
+    let prompt_db_id = prompt.id.clone();
+
+    // Step 4: persist to DB — skip driver registration on failure (D3).
+    let prompt_repo = PromptRepo::new(Arc::clone(&state.db));
+    if let Err(err) = prompt_repo.create(&prompt).await {
+        warn!(%err, session_id, prompt_id, "failed to persist prompt forward — skipping registration");
+        return;
+    }
+
+    // Step 5: register with ACP driver for response routing.
+    if let Some(ref acp_driver) = state.acp_driver {
+        acp_driver
+            .register_prompt_request(session_id, &prompt_db_id)
+            .await;
+    }
+
+    // Step 5b: a `[[prompts.auto]]` rule answers without posting the prompt.
+    if let Some((decision, instruction)) =
+        crate::orchestrator::prompt_policy::auto_decide(state, &session, &prompt).await
+    {
+        if let Err(err) = state
+            .driver_for(session_id)
+            .await
+            .resolve_prompt(&prompt_db_id, decision.as_str(), instruction)
+            .await
+        {
+            warn!(%err, session_id, prompt_id, "failed to deliver auto-policy decision");
+        }
+        return;
+    }
+
+    // Step 6: build Slack blocks.
+    let mut message_blocks =
+        blocks::build_prompt_blocks(prompt_text, prompt_type, None, None, &prompt_db_id);
+    message_blocks.extend(blocks::quick_reply_buttons(
+        &prompt_db_id,
+        state.config.prompts.quick_replies_for(prompt_type),
+    ));
+
+    // Step 7: post to Slack (D2 conditional posting).
+    let Some(ref slack) = state.slack else {
+        warn!(
+            session_id,
+            prompt_id, "prompt persisted; no Slack service configured — skipping post"
+        );
+        return;
+    };
+
+    let channel_id = match session.channel_id.as_deref() {
+        Some(ch) if !ch.is_empty() => ch.to_owned(),
+        _ => {
+            if state.config.slack.channel_id.is_empty() {
+                warn!(
+                    session_id,
+                    prompt_id, "prompt persisted; no Slack channel configured — skipping post"
+                );
+                return;
+            }
+            state.config.slack.channel_id.clone()
+        }
+    };
+
+    let session_thread_ts = session.thread_ts.as_deref().map(|s| SlackTs(s.to_owned()));
+
+    let prompt_preview = blocks::truncate_text(prompt_text, 160);
+    let msg = SlackMessage {
+        channel: SlackChannelId(channel_id),
+        text: Some(format!(
+            "{} ACP Prompt: {} \u{2014} {}",
+            blocks::prompt_type_icon(prompt_type),
+            blocks::prompt_type_label(prompt_type),
+            prompt_preview,
+        )),
+        blocks: Some(message_blocks),
+        thread_ts: session_thread_ts.clone(),
+    };
+
+    // Post directly to capture the prompt's ts: it anchors the prompt's
+    // follow-ups and, when the session has no thread yet, the session thread.
+    let Some(anchor) = Anchor::post(slack, msg).await else {
+        warn!(
+            session_id,
+            prompt_id, "failed to post prompt message to Slack"
+        );
+        return;
+    };
+    if session_thread_ts.is_none() {
+        if let Err(err) = session_repo.set_thread_ts(session_id, &anchor.ts.0).await {
+            warn!(%err, session_id, "failed to record thread_ts from prompt post");
+        }
+    }
+    if let Err(err) = prompt_repo
+        .set_slack_anchor(&prompt_db_id, &anchor.ts.0, &anchor.thread_ts.0)
+        .await
+    {
+        warn!(%err, session_id, prompt_id, "failed to record prompt message anchor");
+    }
+}
@@ -0,0 +1,1109 @@
+//! Embeddable server bootstrap.
+//!
+//! [`ServerBuilder`] performs the startup sequence of the `agent-intercom`
+//! binary — configuration overrides, credentials, database, Slack, audit
+//! logging, watchers, background tasks, and the MCP transports — and
+//! returns a running [`Server`]. Other Rust programs can embed the server
+//! the same way, optionally supplying their own database or audit logger
+//! and subscribing to [`AppState::event_bus`] for custom notifications.
+//!
+//! ```no_run
+//! use agent_intercom::server::{ServerBuilder, Transport};
+//!
+//! # async fn embed() -> agent_intercom::Result<()> {
+//! let server = ServerBuilder::from_config_file("config.toml")?
+//!     .transport(Transport::Sse)
+//!     .start()
+//!     .await?;
+//! let mut events = server.state().event_bus.subscribe();
+//! tokio::spawn(async move {
+//!     while let Ok(event) = events.recv().await {
+//!         println!("{:?}", event.event);
+//!     }
+//! });
+//! server.run_until(agent_intercom::server::shutdown_signal()).await
+//! # }
+//! ```
+
+mod acp_events;
+
+use std::fmt::Write as _;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use clap::ValueEnum;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use crate::acp::writer::WriterLimits;
+use crate::audit::writer::JsonlAuditWriter;
+use crate::audit::AuditLogger;
+use crate::config::{GlobalConfig, StartupRecovery};
+use crate::config_watcher::ConfigWatcher;
+use crate::driver::acp_driver::AcpDriver;
+use crate::driver::mcp_driver::McpDriver;
+use crate::driver::{AgentDriver, AgentEvent};
+use crate::log_filter::LogFilter;
+use crate::mcp::{sse, transport};
+use crate::mode::ServerMode;
+use crate::models::approval::ApprovalStatus;
+use crate::models::prompt::PromptDecision;
+use crate::models::session::SessionStatus;
+use crate::orchestrator::simulated_operator::{self, OperatorScript};
+use crate::orchestrator::startup_recovery::{self, Recovered};
+use crate::orchestrator::{
+    auto_checkpoints, child_monitor, event_subscribers, file_change_watcher, heartbeat_enforcer,
+    policy_drift, queue_alarms, scheduled_apply, session_timebox, stall_consumer, steering_expiry,
+    watchdog,
+};
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::db::{self, Database};
+use crate::persistence::prompt_repo::PromptRepo;
+use crate::persistence::retention;
+use crate::persistence::session_repo::SessionRepo;
+use crate::policy::watcher::PolicyWatcher;
+use crate::slack::client::{SlackMessage, SlackRuntime, SlackService};
+use crate::state::{AppState, PendingApprovals, PendingPrompts, PendingWaits, StallDetectors};
+use crate::{AppError, Result};
+
+/// Which MCP transport(s) to start.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, ValueEnum)]
+pub enum Transport {
+    /// Stdio only (for direct agent invocation).
+    Stdio,
+    /// HTTP/SSE only (for remote clients).
+    Sse,
+    /// Both stdio and HTTP/SSE.
+    #[default]
+    Both,
+}
+
+/// Maximum time to wait for graceful shutdown before giving up.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Brief delay to let the Slack outgoing queue drain before aborting the task (T072).
+///
+/// This ensures any messages enqueued during `graceful_shutdown` (such as the
+/// shutdown notification) have time to be posted, regardless of whether a Slack
+/// channel is configured.
+const QUEUE_DRAIN_DELAY: Duration = Duration::from_millis(500);
+
+/// Configures and starts a [`Server`].
+pub struct ServerBuilder {
+    config: GlobalConfig,
+    config_path: Option<PathBuf>,
+    mode: ServerMode,
+    transport: Transport,
+    workspace_root: Option<PathBuf>,
+    http_port: Option<u16>,
+    load_credentials: bool,
+    database: Option<Arc<Database>>,
+    audit_logger: Option<Arc<dyn AuditLogger>>,
+    simulator: Option<OperatorScript>,
+    log_filter: Option<Arc<LogFilter>>,
+}
+
+impl ServerBuilder {
+    /// Start from an already-parsed configuration.
+    ///
+    /// Workspace mappings do not hot-reload unless a file is given with
+    /// [`config_path`](Self::config_path).
+    #[must_use]
+    pub fn new(config: GlobalConfig) -> Self {
+        Self {
+            config,
+            config_path: None,
+            mode: ServerMode::Mcp,
+            transport: Transport::Both,
+            workspace_root: None,
+            http_port: None,
+            load_credentials: true,
+            database: None,
+            audit_logger: None,
+            simulator: None,
+            log_filter: None,
+        }
+    }
+
+    /// Read and parse the TOML configuration file at `path`, with
+    /// `INTERCOM_*` environment overrides, and watch it for `[[workspace]]`
+    /// changes once started.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` if the file cannot be read or is invalid.
+    pub fn from_config_file(path: impl Into<PathBuf>) -> Result<Self> {
+        Self::from_config_profile(path, None)
+    }
+
+    /// [`ServerBuilder::from_config_file`] with `[profile.<name>]` applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` if the file cannot be read, the profile
+    /// does not exist, or the result is invalid.
+    pub fn from_config_profile(path: impl Into<PathBuf>, profile: Option<&str>) -> Result<Self> {
+        let path = path.into();
+        let config_text = std::fs::read_to_string(&path).map_err(|err| {
+            AppError::Config(format!(
+                "cannot read config file '{}': {err} — copy config.toml from the release \
+                 archive to the same directory as the binary, or pass --config <path>",
+                path.display()
+            ))
+        })?;
+        let config = GlobalConfig::from_toml_layers(&config_text, profile, std::env::vars())?;
+        Ok(Self::new(config).config_path(path))
+    }
+
+    /// The configuration the server will start with, before builder
+    /// overrides are applied.
+    #[must_use]
+    pub fn config(&self) -> &GlobalConfig {
+        &self.config
+    }
+
+    /// Watch `path` for `[[workspace]]` mapping changes.
+    #[must_use]
+    pub fn config_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config_path = Some(path.into());
+        self
+    }
+
+    /// Protocol mode (default: [`ServerMode::Mcp`]).
+    #[must_use]
+    pub fn mode(mut self, mode: ServerMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// MCP transport(s) to start (default: [`Transport::Both`]). Stdio is
+    /// never started in ACP mode.
+    #[must_use]
+    pub fn transport(mut self, transport: Transport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Override `default_workspace_root`. The path must exist.
+    #[must_use]
+    pub fn workspace_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.workspace_root = Some(root.into());
+        self
+    }
+
+    /// Override the HTTP port. Unlike `http_port` in the configuration, this
+    /// also wins over `[acp] http_port` in ACP mode.
+    #[must_use]
+    pub fn http_port(mut self, port: u16) -> Self {
+        self.http_port = Some(port);
+        self
+    }
+
+    /// Whether to load Slack and integration credentials from the keychain
+    /// and environment at startup (default: `true`). Turn off when the
+    /// configuration already carries them, or to run without Slack by
+    /// leaving `slack.bot_token` empty.
+    #[must_use]
+    pub fn load_credentials(mut self, load: bool) -> Self {
+        self.load_credentials = load;
+        self
+    }
+
+    /// Use `database` instead of connecting to the configured `db_path`.
+    /// The schema must already be applied, as [`db::connect`] does.
+    #[must_use]
+    pub fn database(mut self, database: Arc<Database>) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    /// Use `logger` instead of the JSONL audit writer under
+    /// `.intercom/logs`.
+    #[must_use]
+    pub fn audit_logger(mut self, logger: Arc<dyn AuditLogger>) -> Self {
+        self.audit_logger = Some(logger);
+        self
+    }
+
+    /// Answer approval requests and prompts from `script` instead of
+    /// waiting for a human (`--simulate-operator`). `check_clearance` and
+    /// `transmit` then work without Slack.
+    #[must_use]
+    pub fn simulate_operator(mut self, script: OperatorScript) -> Self {
+        self.simulator = Some(script);
+        self
+    }
+
+    /// Let the `log-level` IPC command adjust tracing through `filter`.
+    #[must_use]
+    pub fn log_filter(mut self, filter: LogFilter) -> Self {
+        self.log_filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Run the startup sequence and start the transports.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` for invalid configuration, missing
+    /// credentials, or an HTTP port that cannot be bound; `AppError::Db` if
+    /// the database cannot be opened; or `AppError::Slack` if the Slack
+    /// service fails to start.
+    #[allow(clippy::too_many_lines)] // Startup sequence is inherently sequential.
+    pub async fn start(self) -> Result<Server> {
+        let Self {
+            mut config,
+            config_path,
+            mode,
+            transport,
+            workspace_root,
+            http_port,
+            load_credentials,
+            database,
+            audit_logger,
+            simulator,
+            log_filter,
+        } = self;
+
+        // ── Apply overrides ─────────────────────────────
+        if let Some(ws) = workspace_root {
+            let canonical = ws
+                .canonicalize()
+                .map_err(|err| AppError::Config(format!("invalid workspace override: {err}")))?;
+            config.default_workspace_root = crate::config::strip_unc_prefix(canonical);
+        }
+        if let Some(port) = http_port {
+            config.http_port = port;
+        }
+
+        // Load Slack credentials from keyring / env vars.
+        // Mode-prefixed sources are tried first for ACP (ADR-0015).
+        if load_credentials {
+            config.load_credentials(mode).await?;
+        }
+        // Links must never be signed with an empty key, even when the
+        // embedder skipped credential loading.
+        config.approval_links.ensure_secret();
+
+        // Validate ACP-specific configuration when running in ACP mode.
+        if mode == ServerMode::Acp {
+            config.validate_for_acp_mode()?;
+            // Additional path-security validation (FR-038, FR-039): logs WARN if
+            // host_cli is outside standard directories or not found on PATH.
+            config.validate_host_cli_path().ok();
+            // Auto-suffix the IPC pipe name so MCP and ACP instances don't
+            // collide on the same named pipe (ADR-0015). Only applied when
+            // the name is still the default; an explicit override is preserved.
+            if config.ipc_name == "agent-intercom" {
+                config.ipc_name = "agent-intercom-acp".into();
+                info!(ipc_name = %config.ipc_name, "ACP mode: IPC name auto-suffixed");
+            }
+            // Use the ACP-specific HTTP port so MCP and ACP instances can run
+            // concurrently without a port conflict.  An explicit port override
+            // takes precedence over the [acp] config value.
+            if http_port.is_none() {
+                config.http_port = config.acp.http_port;
+                info!(
+                    http_port = config.http_port,
+                    "ACP mode: HTTP port set from [acp] config"
+                );
+            }
+            info!("ACP mode: host_cli validated");
+            // Check for orphan processes from prior runs (ES-004, FR-037).
+            crate::acp::spawner::check_for_orphan_processes(&config.host_cli).await;
+        }
+
+        crate::models::short_id::configure(&config.ids);
+        let config = Arc::new(config);
+        info!("configuration loaded");
+
+        // ── Initialize database ─────────────────────────────
+        let db = if let Some(database) = database {
+            database
+        } else {
+            let db_path = config.db_path().to_string_lossy().to_string();
+            Arc::new(db::connect(&db_path).await?)
+        };
+        info!("database connected");
+
+        // ── Start retention service ──────────────────────────
+        let ct = CancellationToken::new();
+        let subsystems = Arc::new(watchdog::Subsystems::default());
+        let retention_handle = {
+            let (db, cancel) = (Arc::clone(&db), ct.clone());
+            let retention_days = config.retention_days;
+            let log_dir = crate::audit::log_dir(&config.default_workspace_root);
+            let logging = config.logging.clone();
+            watchdog::supervise(
+                watchdog::Component::Retention,
+                Arc::clone(&subsystems),
+                watchdog::RestartPolicy::default(),
+                ct.clone(),
+                move || {
+                    retention::spawn_retention_task(
+                        Arc::clone(&db),
+                        retention_days,
+                        log_dir.clone(),
+                        logging.clone(),
+                        cancel.clone(),
+                    )
+                },
+            )
+        };
+        info!("retention service started");
+
+        // ── Build shared application state ──────────────────
+        let pending_approvals: PendingApprovals = PendingApprovals::default();
+        let pending_prompts: PendingPrompts = PendingPrompts::default();
+        let pending_waits: PendingWaits = PendingWaits::default();
+
+        // Build the protocol driver and ACP event channel.
+        // In MCP mode: McpDriver wraps the pending oneshot maps.
+        // In ACP mode: AcpDriver routes operator decisions to per-session streams;
+        //              a shared event channel carries inbound AgentEvents from all
+        //              reader tasks to a single consumer task started after AppState
+        //              is fully constructed so the consumer has access to the full
+        //              state (stall detectors, Slack service, DB).
+        let (driver, acp_driver_opt, acp_event_tx_opt, acp_event_recv) = if mode == ServerMode::Acp
+        {
+            let acp = Arc::new(AcpDriver::with_limits(WriterLimits::from_config(
+                &config.acp,
+            )));
+            let (tx, rx) = tokio::sync::mpsc::channel::<AgentEvent>(1024);
+            // Coerce Arc<AcpDriver> → Arc<dyn AgentDriver> via unsized coercion.
+            let driver_arc: Arc<dyn AgentDriver> = acp.clone();
+            (driver_arc, Some(acp), Some(tx), Some(rx))
+        } else {
+            // MCP mode: build McpDriver from clones of the pending maps so that
+            // Slack handlers and MCP tool handlers share the same in-memory channels.
+            let mcp = McpDriver::new(
+                Arc::clone(&pending_approvals),
+                Arc::clone(&pending_prompts),
+                Arc::clone(&pending_waits),
+            );
+            let driver_arc: Arc<dyn AgentDriver> = Arc::new(mcp);
+            (driver_arc, None, None, None)
+        };
+
+        // Start Slack client if configured.
+        // NOTE: Socket mode is wired in a second phase (below) after AppState
+        // is fully constructed so that the interaction callbacks get the live
+        // pending_prompts / pending_approvals maps.
+        let (slack_service, mut slack_runtime) = if config.slack.bot_token.is_empty() {
+            info!("slack not configured; running in local-only mode");
+            (None, None)
+        } else {
+            let (svc, runtime) = SlackService::start(&config.slack, &config.slack_rate_limit)
+                .map_err(|err| {
+                    error!(%err, "slack service start failed");
+                    err
+                })?;
+            info!("slack service started");
+            let svc = Arc::new(svc);
+            // Supervise the queue worker, adopting the one `start` spawned.
+            let mut first = Some(runtime.queue_task);
+            let worker_svc = Arc::clone(&svc);
+            let queue_task = watchdog::supervise(
+                watchdog::Component::SlackQueue,
+                Arc::clone(&subsystems),
+                watchdog::RestartPolicy::default(),
+                ct.clone(),
+                move || {
+                    first
+                        .take()
+                        .unwrap_or_else(|| worker_svc.spawn_queue_worker())
+                },
+            );
+            let runtime = SlackRuntime {
+                queue_task,
+                socket_task: runtime.socket_task,
+            };
+            (Some(svc), Some(runtime))
+        };
+
+        // Generate a random IPC auth token for this server instance.
+        let ipc_auth_token = Some(uuid::Uuid::new_v4().to_string());
+
+        // ── Initialize audit logger ─────────────────────────
+        let audit_logger = audit_logger.or_else(|| {
+            let audit_log_dir = crate::audit::log_dir(&config.default_workspace_root);
+            match JsonlAuditWriter::new(audit_log_dir) {
+                Ok(writer) => Some(
+                    Arc::new(writer.with_logging(config.logging.clone())) as Arc<dyn AuditLogger>
+                ),
+                Err(err) => {
+                    warn!(%err, "failed to initialize audit logger, continuing without audit logging");
+                    None
+                }
+            }
+        });
+
+        // ── Initialize policy watcher ────────────────────────
+        // The watcher loads the initial policy from `.intercom/settings.json`,
+        // sets up a `notify` file watcher on that directory, and hot-reloads the
+        // in-memory cache whenever the file changes.  `AppState.policy_cache` is
+        // the SAME `Arc` owned by the watcher so all hot-reload events are
+        // immediately visible to `check_auto_approve` without any additional
+        // invalidation logic.
+        let policy_watcher = PolicyWatcher::new();
+        if let Err(err) = policy_watcher
+            .register(&config.default_workspace_root)
+            .await
+        {
+            warn!(%err, "policy watcher registration failed — falling back to on-demand loads");
+        } else {
+            info!("policy watcher registered for default workspace root");
+        }
+        let policy_cache = policy_watcher.cache().clone();
+
+        // ── Initialize config watcher for workspace mapping hot-reload ───────
+        // Watches `config.toml` for changes and re-parses `[[workspace]]` entries
+        // so that new sessions always see the latest workspace→channel mappings
+        // without a server restart (FR-014).
+        let config_watcher = config_path.as_deref().and_then(|path| {
+            ConfigWatcher::new(path)
+                .map_err(|err| {
+                    warn!(%err, "config watcher failed to start — workspace mappings will not hot-reload");
+                    err
+                })
+                .ok()
+        });
+        let workspace_mappings = config_watcher.as_ref().map_or_else(
+            || Arc::new(std::sync::RwLock::new(config.workspaces.clone())),
+            ConfigWatcher::mappings,
+        );
+
+        // ── Create stall event channel ──────────────────────
+        let (stall_tx, stall_rx) = tokio::sync::mpsc::channel(256);
+
+        let state = Arc::new(AppState {
+            config: Arc::clone(&config),
+            db,
+            slack: slack_service,
+            pending_approvals,
+            pending_prompts,
+            pending_waits,
+            pending_modal_contexts: Arc::default(),
+            pending_thread_replies: Arc::default(),
+            stall_detectors: Some(StallDetectors::default()),
+            ipc_auth_token,
+            policy_cache,
+            audit_logger,
+            active_children: Arc::default(),
+            pending_command_approvals: Arc::default(),
+            stall_event_tx: Some(stall_tx),
+            driver,
+            server_mode: mode,
+            workspace_mappings,
+            acp_event_tx: acp_event_tx_opt,
+            acp_driver: acp_driver_opt,
+            driver_registry: Arc::default(),
+            event_bus: Arc::default(),
+            session_hooks: Arc::default(),
+            pause_requests: Arc::default(),
+            heartbeats: Arc::default(),
+            instruction_queue: Arc::default(),
+            snoozes: Arc::default(),
+            apply_confirmations: Arc::default(),
+            session_logs: Arc::default(),
+            session_channels: Arc::default(),
+            workspace_discovery: Arc::default(),
+            config_path,
+            command_aliases: Arc::default(),
+            proxy: Arc::default(),
+            replay_guard: Arc::default(),
+            interactions: Arc::default(),
+            rearmed: Arc::default(),
+            diff_staging: Arc::default(),
+            subsystems: Arc::clone(&subsystems),
+            simulator: simulator.map(Arc::new),
+            log_filter,
+        });
+
+        // ── Spawn agent event bus subscribers ───────────────
+        // Subscribed before any producer starts so no early events are missed.
+        let _metrics_subscriber =
+            event_subscribers::spawn_metrics_subscriber(&state.event_bus, ct.clone());
+        let _audit_subscriber = state.audit_logger.as_ref().map(|logger| {
+            event_subscribers::spawn_audit_subscriber(
+                &state.event_bus,
+                Arc::clone(logger),
+                ct.clone(),
+            )
+        });
+        let _slack_status_subscriber = state.slack.as_ref().map(|slack| {
+            event_subscribers::spawn_slack_status_subscriber(
+                &state.event_bus,
+                Arc::clone(slack),
+                Arc::clone(&state.db),
+                ct.clone(),
+            )
+        });
+        let _slack_request_subscriber = state.slack.as_ref().map(|slack| {
+            event_subscribers::spawn_slack_request_subscriber(
+                Arc::clone(&state),
+                Arc::clone(slack),
+                ct.clone(),
+            )
+        });
+        let _slack_notice_subscriber = state.slack.as_ref().map(|slack| {
+            event_subscribers::spawn_slack_notice_subscriber(
+                &state.event_bus,
+                Arc::clone(slack),
+                ct.clone(),
+            )
+        });
+
+        let links = &state.config.approval_links;
+        let _approval_link_subscriber =
+            (links.enabled && links.email && state.config.smtp.is_enabled()).then(|| {
+                event_subscribers::spawn_approval_link_subscriber(
+                    &state.event_bus,
+                    Arc::clone(&state.config),
+                    Arc::clone(&state.db),
+                    ct.clone(),
+                )
+            });
+
+        let _simulator_handle = state.simulator.as_ref().map(|script| {
+            simulated_operator::spawn_simulated_operator(
+                Arc::clone(&state),
+                Arc::clone(script),
+                ct.clone(),
+            )
+        });
+
+        let _steering_expiry_handle =
+            steering_expiry::spawn_steering_expiry_task(Arc::clone(&state), ct.clone());
+        let _time_box_handle = session_timebox::spawn_time_box_task(Arc::clone(&state), ct.clone());
+        let _scheduled_apply_handle =
+            scheduled_apply::spawn_scheduled_apply_task(Arc::clone(&state), ct.clone());
+        let _file_change_watcher_handle =
+            file_change_watcher::spawn_file_change_watcher(Arc::clone(&state), ct.clone());
+        let _heartbeat_handle =
+            heartbeat_enforcer::spawn_heartbeat_task(Arc::clone(&state), ct.clone());
+        let _queue_alarm_handle =
+            queue_alarms::spawn_queue_alarm_task(Arc::clone(&state), ct.clone());
+        let _watchdog_handle = watchdog::spawn_watchdog_task(Arc::clone(&state), ct.clone());
+        let _auto_checkpoint_handle =
+            auto_checkpoints::spawn_auto_checkpoint_task(Arc::clone(&state), ct.clone());
+        let _policy_drift_handle =
+            policy_drift::spawn_policy_drift_task(Arc::clone(&state), ct.clone());
+
+        // ── Spawn ACP event consumer (T099) ────────────────────
+        // Spawned after AppState is built so the consumer has access to the full
+        // state: stall detectors (for StreamActivity), Slack service (for crash
+        // notifications), and the database (for pending clearance resolution).
+        if let Some(rx) = acp_event_recv {
+            let consumer_ct = ct.clone();
+            let consumer_state = Arc::clone(&state);
+            tokio::spawn(async move {
+                acp_events::run_acp_event_consumer(rx, consumer_ct, consumer_state).await;
+            });
+            info!("acp event consumer started");
+        }
+
+        // ── Check for interrupted sessions from prior crash (T082) ──
+        check_interrupted_on_startup(&state).await;
+        if state.config.recovery.keeps_pending() {
+            crate::orchestrator::rearm::rearm_pending(&state).await;
+        }
+        if state.config.recovery.on_startup >= StartupRecovery::Repost {
+            crate::orchestrator::rearm::expire_unclaimed(&state).await;
+        }
+
+        // ── Spawn stall event consumer ──────────────────────
+        let _stall_consumer_handle = if let Some(ref slack) = state.slack {
+            let default_channel = state.config.slack.channel_id.clone();
+            // T097: Pass the driver so the consumer can deliver ACP nudges on-stream.
+            let stall_driver: Option<Arc<dyn AgentDriver>> = state.acp_driver.as_ref().map(|d| {
+                let drv: Arc<dyn AgentDriver> = d.clone();
+                drv
+            });
+            Some(stall_consumer::spawn_stall_event_consumer(
+                stall_rx,
+                Arc::clone(slack),
+                default_channel,
+                Arc::clone(&state.db),
+                stall_driver,
+                ct.clone(),
+            ))
+        } else {
+            info!("stall event consumer not started (no slack service)");
+            // Drop the receiver so senders fail fast.
+            drop(stall_rx);
+            None
+        };
+
+        // ── Spawn child process monitor ─────────────────────
+        let _child_monitor_handle = if let Some(ref slack) = state.slack {
+            let default_channel = state.config.slack.channel_id.clone();
+            Some(child_monitor::spawn_child_monitor(
+                Arc::clone(&state.active_children),
+                Arc::clone(slack),
+                default_channel,
+                Arc::clone(&state.db),
+                Arc::clone(&state.config),
+                ct.clone(),
+            ))
+        } else {
+            info!("child process monitor not started (no slack service)");
+            None
+        };
+
+        // ── Wire socket mode with the live AppState (T093/T094 fix) ────
+        // Start socket mode AFTER AppState is built so the interaction
+        // callbacks share the same pending_prompts/approvals/waits maps
+        // as the MCP transport and can resolve oneshot channels correctly.
+        if let (Some(ref svc), Some(ref mut rt)) = (&state.slack, slack_runtime.as_mut()) {
+            let (svc, socket_state) = (Arc::clone(svc), Arc::clone(&state));
+            rt.socket_task = Some(watchdog::supervise(
+                watchdog::Component::SocketMode,
+                Arc::clone(&subsystems),
+                watchdog::RestartPolicy::default(),
+                ct.clone(),
+                move || svc.start_socket_mode(Arc::clone(&socket_state)),
+            ));
+            info!("slack socket mode started with live app state");
+        }
+
+        // ── Start transports ────────────────────────────────
+        // The HTTP transport starts in BOTH MCP and ACP modes. In ACP mode,
+        // the endpoint lets agent subprocesses call MCP tools (check_clearance,
+        // transmit, auto_check, etc.) via HTTP. Without it, tools are unreachable
+        // from spawned ACP sessions (HITL-003 / FR-032).
+        let start_stdio =
+            mode == ServerMode::Mcp && matches!(transport, Transport::Stdio | Transport::Both);
+        let start_sse = matches!(transport, Transport::Sse | Transport::Both);
+
+        if mode == ServerMode::Acp {
+            info!(
+                "ACP mode: stdio transport disabled; HTTP transport starting for MCP tool access \
+                 by ACP subprocesses (HITL-003)"
+            );
+        }
+
+        // Bind BEFORE spawning anything that serves. A bind failure means the
+        // port is already in use (e.g., second instance). Fail cleanly rather
+        // than leaving Slack and stdio running with no HTTP front-end.
+        let listener = if start_sse {
+            match sse::bind_http(&state).await {
+                Ok(listener) => Some(listener),
+                Err(err) => {
+                    error!(%err, "failed to bind HTTP transport — shutting down");
+                    ct.cancel();
+                    // Abort Slack runtime so the process can exit cleanly.
+                    if let Some(ref rt) = slack_runtime {
+                        if let Some(ref socket) = rt.socket_task {
+                            socket.abort();
+                        }
+                        rt.queue_task.abort();
+                    }
+                    return Err(err);
+                }
+            }
+        } else {
+            info!("SSE transport disabled (--transport stdio)");
+            None
+        };
+        let http_addr = listener
+            .as_ref()
+            .and_then(|listener| listener.local_addr().ok());
+
+        let stdio_handle = if start_stdio {
+            let stdio_ct = ct.clone();
+            let stdio_state = Arc::clone(&state);
+            let stdio_shutdown_ct = ct.clone();
+            Some(tokio::spawn(async move {
+                if let Err(err) = transport::serve_stdio(stdio_state, stdio_ct).await {
+                    error!(%err, "stdio transport failed — initiating shutdown");
+                    stdio_shutdown_ct.cancel();
+                }
+            }))
+        } else {
+            info!(
+                "stdio transport disabled (--transport {})",
+                match transport {
+                    Transport::Sse => "sse",
+                    _ => "unknown",
+                }
+            );
+            None
+        };
+
+        let sse_handle = listener.map(|listener| {
+            let sse_ct = ct.clone();
+            let sse_state = Arc::clone(&state);
+            let sse_shutdown_ct = ct.clone();
+            tokio::spawn(async move {
+                if let Err(err) = sse::serve_with_listener(listener, sse_state, sse_ct).await {
+                    error!(%err, "http transport failed — initiating shutdown");
+                    sse_shutdown_ct.cancel();
+                }
+            })
+        });
+
+        let grpc_handle = spawn_grpc(&state, &ct).await;
+
+        info!(?transport, ?mode, "server ready");
+
+        Ok(Server {
+            state,
+            ct,
+            http_addr,
+            slack_runtime,
+            stdio_handle,
+            sse_handle,
+            grpc_handle,
+            retention_handle,
+            _policy_watcher: policy_watcher,
+            _config_watcher: config_watcher,
+        })
+    }
+}
+
+/// A running server, returned by [`ServerBuilder::start`].
+pub struct Server {
+    state: Arc<AppState>,
+    ct: CancellationToken,
+    http_addr: Option<SocketAddr>,
+    slack_runtime: Option<SlackRuntime>,
+    stdio_handle: Option<JoinHandle<()>>,
+    sse_handle: Option<JoinHandle<()>>,
+    grpc_handle: Option<JoinHandle<()>>,
+    retention_handle: JoinHandle<()>,
+    // Kept alive for the server's lifetime — dropping them stops the notify
+    // subscriptions and hot-reload stops working.
+    _policy_watcher: PolicyWatcher,
+    _config_watcher: Option<ConfigWatcher>,
+}
+
+impl Server {
+    /// Shared application state: database, Slack service, event bus, and
+    /// the pending request maps.
+    #[must_use]
+    pub fn state(&self) -> &Arc<AppState> {
+        &self.state
+    }
+
+    /// Address the HTTP transport listens on, when it was started.
+    #[must_use]
+    pub fn http_addr(&self) -> Option<SocketAddr> {
+        self.http_addr
+    }
+
+    /// Serve until `shutdown` completes, then shut down gracefully: pending
+    /// approvals, prompts and sessions are marked interrupted, the operator
+    /// is notified, and background tasks are stopped. Gives up after ten
+    /// seconds.
+    ///
+    /// # Errors
+    ///
+    /// Currently infallible; failures during shutdown are logged.
+    pub async fn run_until(self, shutdown: impl Future<Output = ()>) -> Result<()> {
+        shutdown.await;
+        self.ct.cancel();
+
+        let shutdown_fut = async {
+            // 1. Persist interrupted state.
+            if let Err(err) = graceful_shutdown(&self.state).await {
+                error!(%err, "error during graceful shutdown persistence");
+            }
+
+            // 2. Unconditional queue drain (T072): let the outgoing Slack message
+            //    queue flush any enqueued messages (e.g. shutdown notification)
+            //    before the background worker task is aborted.
+            tokio::time::sleep(QUEUE_DRAIN_DELAY).await;
+
+            // 3. Abort Slack runtime tasks (they have no cancellation token).
+            if let Some(ref rt) = self.slack_runtime {
+                if let Some(ref socket) = rt.socket_task {
+                    socket.abort();
+                }
+                rt.queue_task.abort();
+                info!("slack runtime tasks aborted");
+            }
+
+            // 4. Wait for transport handles.
+            if let Some(h) = self.stdio_handle {
+                let _ = h.await;
+            }
+            if let Some(h) = self.sse_handle {
+                let _ = h.await;
+            }
+            if let Some(h) = self.grpc_handle {
+                let _ = h.await;
+            }
+            let _ = self.retention_handle.await;
+
+            // 5. Stop downstream MCP servers started by proxy mode.
+            self.state.proxy.shutdown().await;
+        };
+
+        if tokio::time::timeout(SHUTDOWN_TIMEOUT, shutdown_fut)
+            .await
+            .is_err()
+        {
+            error!(
+                timeout_secs = SHUTDOWN_TIMEOUT.as_secs(),
+                "graceful shutdown timed out — exiting"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Start the gRPC control plane when `[grpc] enabled` (ADR-0019).
+///
+/// Unlike the HTTP transport, a failure here is logged and the server
+/// runs without it: the IPC socket still offers every command.
+#[cfg(feature = "grpc")]
+async fn spawn_grpc(state: &Arc<AppState>, ct: &CancellationToken) -> Option<JoinHandle<()>> {
+    use crate::ipc::grpc;
+
+    if !state.config.grpc.enabled {
+        return None;
+    }
+    if state.config.grpc.token.is_empty() {
+        warn!("[grpc] enabled without a token (GRPC_TOKEN) — gRPC control plane not started");
+        return None;
+    }
+    let listener = match grpc::bind_grpc(state).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            error!(%err, "gRPC control plane not started");
+            return None;
+        }
+    };
+    let (state, ct) = (Arc::clone(state), ct.clone());
+    Some(tokio::spawn(async move {
+        if let Err(err) = grpc::serve_with_listener(listener, state, ct).await {
+            error!(%err, "gRPC control plane stopped");
+        }
+    }))
+}
+
+/// Without the `grpc` feature, only warn that `[grpc] enabled` is ignored.
+#[cfg(not(feature = "grpc"))]
+#[allow(clippy::unused_async)]
+async fn spawn_grpc(state: &Arc<AppState>, _ct: &CancellationToken) -> Option<JoinHandle<()>> {
+    if state.config.grpc.enabled {
+        warn!("[grpc] enabled but this build lacks the `grpc` feature — ignoring");
+    }
+    None
+}
+
+/// Mark all in-flight state as interrupted on graceful shutdown (T081).
+///
+/// - Marks pending approval requests and prompts as `Interrupted`, unless
+///   `[recovery]` keeps them for the next startup to re-arm.
+/// - Marks active/paused sessions as `Interrupted` with `terminated_at`.
+/// - Posts a final notification to Slack.
+///
+/// # Errors
+///
+/// Returns `AppError` if any persistence or Slack operation fails.
+async fn graceful_shutdown(state: &AppState) -> Result<()> {
+    let _span = tracing::info_span!("graceful_shutdown").entered();
+
+    let session_repo = SessionRepo::new(Arc::clone(&state.db));
+    let approval_repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let prompt_repo = PromptRepo::new(Arc::clone(&state.db));
+
+    // Mark all pending approval requests as Interrupted, unless they are
+    // re-armed on the next startup.
+    let rearm = state.config.recovery.keeps_pending();
+    let pending_approvals = approval_repo.list_pending().await.unwrap_or_default();
+    for approval in pending_approvals.iter().filter(|_| !rearm) {
+        if let Err(err) = approval_repo
+            .update_status(&approval.id, ApprovalStatus::Interrupted)
+            .await
+        {
+            error!(request_id = %approval.id, %err, "failed to interrupt approval");
+        }
+    }
+
+    // Mark all pending prompts as Interrupted (set decision to Stop).
+    let pending_prompts = prompt_repo.list_pending().await.unwrap_or_default();
+    for prompt in pending_prompts.iter().filter(|_| !rearm) {
+        if let Err(err) = prompt_repo
+            .update_decision(
+                &prompt.id,
+                PromptDecision::Stop,
+                Some("server shutdown".into()),
+            )
+            .await
+        {
+            error!(prompt_id = %prompt.id, %err, "failed to interrupt prompt");
+        }
+    }
+
+    // Mark all active/paused sessions as Interrupted.
+    let live_sessions = session_repo
+        .list_active_or_paused()
+        .await
+        .unwrap_or_default();
+    for session in &live_sessions {
+        if let Err(err) = session_repo
+            .set_terminated(&session.id, SessionStatus::Interrupted)
+            .await
+        {
+            error!(session_id = %session.id, %err, "failed to interrupt session");
+        }
+    }
+
+    // Post final notification to Slack.
+    if let Some(ref slack) = state.slack {
+        let ch = &state.config.slack.channel_id;
+        if ch.is_empty() {
+            info!("no global Slack channel configured; skipping shutdown notification");
+        } else {
+            let channel = slack_morphism::prelude::SlackChannelId(ch.clone());
+            let text = if rearm {
+                format!(
+                    "\u{26a0}\u{fe0f} Server shutting down. {} session(s) interrupted; {} approval(s) and {} prompt(s) stay pending until restart.",
+                    live_sessions.len(),
+                    pending_approvals.len(),
+                    pending_prompts.len(),
+                )
+            } else {
+                format!(
+                    "\u{26a0}\u{fe0f} Server shutting down. {} session(s), {} approval(s), {} prompt(s) interrupted.",
+                    live_sessions.len(),
+                    pending_approvals.len(),
+                    pending_prompts.len(),
+                )
+            };
+            let msg = SlackMessage::plain(channel, text);
+            if let Err(err) = slack.enqueue(msg).await {
+                error!(%err, "failed to post shutdown notification to slack");
+            }
+        }
+    }
+
+    info!(
+        sessions = live_sessions.len(),
+        approvals = pending_approvals.len(),
+        prompts = pending_prompts.len(),
+        "graceful shutdown persistence complete"
+    );
+
+    Ok(())
+}
+
+/// Check for interrupted sessions on startup, recover them per
+/// `[recovery] on_startup`, and post a summary to Slack (T082).
+///
+/// On server restart, any sessions that were Active/Online are now orphaned
+/// (their agent processes are dead). This function first marks all such
+/// sessions as Interrupted, then counts pending requests, reactivates or
+/// respawns recent sessions if configured, and posts a recovery summary to
+/// Slack.
+async fn check_interrupted_on_startup(state: &Arc<AppState>) {
+    let _span = tracing::info_span!("startup_recovery_check").entered();
+
+    let session_repo = SessionRepo::new(Arc::clone(&state.db));
+    let approval_repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let prompt_repo = PromptRepo::new(Arc::clone(&state.db));
+
+    // Mark all Active sessions as Interrupted — their agent processes were
+    // lost when the server shut down.
+    let active = session_repo.list_active().await.unwrap_or_default();
+    for session in &active {
+        if let Err(err) = session_repo
+            .update_status(&session.id, SessionStatus::Interrupted)
+            .await
+        {
+            warn!(%err, session_id = %session.id, "failed to mark active session as interrupted");
+        }
+    }
+
+    let interrupted = session_repo.list_interrupted().await.unwrap_or_default();
+
+    if interrupted.is_empty() {
+        info!("no interrupted sessions found on startup");
+        return;
+    }
+
+    info!(
+        count = interrupted.len(),
+        "found interrupted sessions on startup"
+    );
+
+    // Count and report pending requests across all interrupted sessions.
+    let mut total_approvals = 0usize;
+    let mut total_prompts = 0usize;
+
+    for session in &interrupted {
+        if let Ok(Some(_)) = approval_repo.get_pending_for_session(&session.id).await {
+            total_approvals += 1;
+        }
+        if let Ok(Some(_)) = prompt_repo.get_pending_for_session(&session.id).await {
+            total_prompts += 1;
+        }
+    }
+
+    let recovered = startup_recovery::recover(state, &interrupted).await;
+
+    // Post recovery summary to Slack.
+    if let Some(ref slack) = state.slack {
+        let ch = &state.config.slack.channel_id;
+        if ch.is_empty() {
+            info!("no global Slack channel configured; skipping startup recovery notification");
+        } else {
+            let channel = slack_morphism::prelude::SlackChannelId(ch.clone());
+            let mut text = format!(
+                "\u{1f504} Server restarted. Found {} interrupted session(s) \
+                 with {} pending approval(s) and {} pending prompt(s).",
+                interrupted.len(),
+                total_approvals,
+                total_prompts,
+            );
+            if recovered == Recovered::default() {
+                text.push_str(" Agents can use `recover_state` to resume.");
+            } else {
+                let _ = write!(
+                    text,
+                    " Reactivated {} session(s) for their agents to reconnect \
+                     and respawned {} agent(s).",
+                    recovered.reactivated, recovered.respawned,
+                );
+            }
+            let msg = SlackMessage::plain(channel, text);
+            if let Err(err) = slack.enqueue(msg).await {
+                error!(%err, "failed to post startup recovery notification");
+            }
+        }
+    }
+}
+
+/// Complete on Ctrl+C, or on SIGTERM on Unix.
+pub async fn shutdown_signal() {
+    let ctrl_c = tokio::signal::ctrl_c();
+    #[cfg(unix)]
+    {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    _ = ctrl_c => {}
+                    _ = sigterm.recv() => {}
+                }
+            }
+            Err(err) => {
+                tracing::warn!(%err, "failed to register SIGTERM handler, using ctrl-c only");
+                let _ = ctrl_c.await;
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        if let Err(err) = ctrl_c.await {
+            tracing::error!(%err, "ctrl-c signal handler failed");
+        }
+    }
+}
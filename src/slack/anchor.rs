@@ -0,0 +1,107 @@
+//! Per-request Slack thread anchors.
+//!
+//! Each request an agent blocks on — an approval, a continuation prompt, a
+//! standby wait, a stall alert — is posted as a single message, and every
+//! later message about that request refers back to it. An [`Anchor`]
+//! remembers the request message and the thread its follow-ups belong in:
+//! the session thread when the request was posted inside one, otherwise the
+//! request message itself. Timeout notices go to the anchor's thread and the
+//! request message is updated in place once the request resolves, so a
+//! request never leaves stale buttons or stray channel-root notices behind.
+
+use slack_morphism::prelude::{SlackBlock, SlackChannelId, SlackTs};
+use tracing::warn;
+
+use crate::slack::blocks;
+use crate::slack::client::{SlackMessage, SlackService};
+
+/// Where a request was posted in Slack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Anchor {
+    /// Channel the request message lives in.
+    pub channel: SlackChannelId,
+    /// Timestamp of the request message.
+    pub ts: SlackTs,
+    /// Thread follow-ups are posted in.
+    pub thread_ts: SlackTs,
+}
+
+impl Anchor {
+    /// Anchor for a message posted at `ts`, inside `thread_ts` when it was
+    /// posted as a thread reply.
+    #[must_use]
+    pub fn new(channel: SlackChannelId, ts: SlackTs, thread_ts: Option<SlackTs>) -> Self {
+        let thread_ts = thread_ts.unwrap_or_else(|| ts.clone());
+        Self {
+            channel,
+            ts,
+            thread_ts,
+        }
+    }
+
+    /// The anchor recorded on a request (its `slack_ts` and `thread_ts`
+    /// columns) in `channel`, or `None` when its message was never posted.
+    #[must_use]
+    pub fn stored(channel: &str, ts: Option<&str>, thread_ts: Option<&str>) -> Option<Self> {
+        let ts = SlackTs(ts?.to_owned());
+        Some(Self::new(
+            SlackChannelId(channel.to_owned()),
+            ts,
+            thread_ts.map(|t| SlackTs(t.to_owned())),
+        ))
+    }
+
+    /// Post `message` directly and anchor the request to it.
+    ///
+    /// Returns `None` (after logging) when the post fails; callers carry on
+    /// without an anchor, as they did before anchoring existed.
+    pub async fn post(slack: &SlackService, message: SlackMessage) -> Option<Self> {
+        let channel = message.channel.clone();
+        let thread_ts = message.thread_ts.clone();
+        match slack.post_message_direct(message).await {
+            Ok(ts) => Some(Self::new(channel, ts, thread_ts)),
+            Err(err) => {
+                warn!(%err, "failed to post anchored request message");
+                None
+            }
+        }
+    }
+
+    /// Queue a follow-up message in the anchor's thread.
+    pub async fn follow_up(
+        &self,
+        slack: &SlackService,
+        text: String,
+        blocks: Option<Vec<SlackBlock>>,
+    ) {
+        let message = SlackMessage {
+            channel: self.channel.clone(),
+            text: Some(text),
+            blocks,
+            thread_ts: Some(self.thread_ts.clone()),
+        };
+        if let Err(err) = slack.enqueue(message).await {
+            warn!(%err, "failed to post follow-up in request thread");
+        }
+    }
+
+    /// Replace the request message with a final status line.
+    ///
+    /// The edit is low priority: while Slack's rate budget is tight it
+    /// waits, and only the latest status of the message is applied.
+    pub async fn resolve(&self, slack: &SlackService, status: &str) {
+        self.replace(slack, vec![blocks::text_section(status)])
+            .await;
+    }
+
+    /// Replace the request message with `blocks`, at the same low priority
+    /// as [`Self::resolve`].
+    pub async fn replace(&self, slack: &SlackService, blocks: Vec<SlackBlock>) {
+        if let Err(err) = slack
+            .update_message_low_priority(self.channel.clone(), self.ts.clone(), blocks)
+            .await
+        {
+            warn!(%err, ts = %self.ts.0, "failed to update request message in place");
+        }
+    }
+}
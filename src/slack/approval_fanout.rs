@@ -0,0 +1,130 @@
+//! Approval fan-out to a workspace's extra approval channels.
+//!
+//! A workspace can list `[[workspace.approval_channels]]` besides its own
+//! channel — a security channel for critical changes, say. An approval
+//! request whose risk reaches an entry's `min_risk` is posted there too,
+//! with live buttons. Every copy, the one in the workspace channel
+//! included, is tracked in `approval_message`. The first authorized
+//! decision in any channel resolves the request (later clicks only see the
+//! stored outcome), and the remaining copies are rewritten with it.
+
+use std::sync::Arc;
+
+use slack_morphism::prelude::{SlackBlock, SlackChannelId, SlackTs};
+use tracing::warn;
+
+use crate::models::approval::RiskLevel;
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::slack::anchor::Anchor;
+use crate::slack::blocks;
+use crate::slack::client::{SlackMessage, SlackService};
+use crate::state::AppState;
+
+/// Extra channels an approval of `risk` raised in `channel_id` is posted
+/// to, from the live workspace mapping table.
+#[must_use]
+pub fn channels(state: &AppState, channel_id: &str, risk: RiskLevel) -> Vec<String> {
+    let Ok(mappings) = state.workspace_mappings.read() else {
+        return Vec::new();
+    };
+    mappings
+        .iter()
+        .find(|m| m.channel_id == channel_id)
+        .map(|m| m.approval_fanout(risk))
+        .unwrap_or_default()
+}
+
+/// Post a copy of an approval request to each of `channels` and track
+/// every copy, the `primary` message included.
+///
+/// `copy_blocks` is the full request card, buttons included; a line
+/// pointing at the workspace channel is added before the buttons.
+pub async fn post_copies(
+    state: &AppState,
+    slack: &SlackService,
+    request_id: &str,
+    title: &str,
+    primary: Option<&Anchor>,
+    channels: &[String],
+    copy_blocks: Vec<SlackBlock>,
+) {
+    if channels.is_empty() {
+        return;
+    }
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    if let Some(primary) = primary {
+        if let Err(err) = repo
+            .add_message(request_id, &primary.channel.0, &primary.ts.0)
+            .await
+        {
+            warn!(%err, request_id, "failed to track approval message");
+        }
+    }
+    let note = primary.map_or_else(
+        || "\u{1f4e3} Also sent to the workspace channel.".to_owned(),
+        |p| format!("\u{1f4e3} Also sent to <#{}>.", p.channel.0),
+    );
+    let note = format!("{note} The first decision in any channel resolves it.");
+    let mut copy_blocks = copy_blocks;
+    let position = copy_blocks.len().saturating_sub(1);
+    copy_blocks.insert(position, blocks::text_section(&note));
+
+    for channel in channels {
+        let message = SlackMessage {
+            channel: SlackChannelId(channel.clone()),
+            text: Some(format!("\u{1f4cb} Approval Request: {title}")),
+            blocks: Some(copy_blocks.clone()),
+            thread_ts: None,
+        };
+        match slack.post_message_direct(message).await {
+            Ok(ts) => {
+                if let Err(err) = repo.add_message(request_id, channel, &ts.0).await {
+                    warn!(%err, request_id, channel, "failed to track approval copy");
+                }
+            }
+            Err(err) => warn!(%err, request_id, channel, "failed to post approval copy"),
+        }
+    }
+}
+
+/// Note that the copy at `channel`/`ts` already shows the decision made on
+/// it, so [`resolve_copies`] leaves it alone.
+pub async fn mark_resolved(state: &AppState, request_id: &str, channel: &str, ts: &str) {
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    if let Err(err) = repo.resolve_message(request_id, channel, ts).await {
+        warn!(%err, request_id, "failed to mark approval copy resolved");
+    }
+}
+
+/// Replace the buttons on every copy of `request_id` not yet updated with
+/// `status_line`. A no-op for requests that were not fanned out.
+pub async fn resolve_copies(state: &AppState, request_id: &str, status_line: &str) {
+    let Some(ref slack) = state.slack else { return };
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let copies = match repo.unresolved_messages(request_id).await {
+        Ok(copies) => copies,
+        Err(err) => {
+            warn!(%err, request_id, "failed to load approval copies");
+            return;
+        }
+    };
+    for (channel, ts) in copies {
+        // Claim the copy first so a concurrent resolver skips it.
+        if !matches!(
+            repo.resolve_message(request_id, &channel, &ts).await,
+            Ok(true)
+        ) {
+            continue;
+        }
+        if let Err(err) = slack
+            .update_message(
+                SlackChannelId(channel.clone()),
+                SlackTs(ts),
+                vec![blocks::text_section(status_line)],
+            )
+            .await
+        {
+            warn!(%err, request_id, channel, "failed to update approval copy");
+        }
+    }
+}
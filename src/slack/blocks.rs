@@ -5,15 +5,27 @@
 //!
 //! This module is the single source of truth for all Slack Block Kit message
 //! construction, shared between MCP tool handlers and ACP event handlers.
+//! It also renders blocks back to text: [`message_content`] fills in the
+//! `text` fallback Slack shows in notifications and reads to screen
+//! readers, and renders whole messages as text in
+//! [`SlackRenderMode::PlainText`].
 
+use chrono::{DateTime, Utc};
 use slack_morphism::prelude::{
     SlackActionBlockElement, SlackActionId, SlackActionsBlock, SlackBlock, SlackBlockButtonElement,
-    SlackBlockId, SlackBlockPlainTextInputElement, SlackBlockPlainTextOnly, SlackBlockText,
-    SlackCallbackId, SlackInputBlock, SlackInputBlockElement, SlackModalView, SlackSectionBlock,
-    SlackView,
+    SlackBlockChoiceItem, SlackBlockId, SlackBlockPlainTextInputElement, SlackBlockPlainTextOnly,
+    SlackBlockStaticSelectElement, SlackBlockText, SlackCallbackId, SlackContextBlockElement,
+    SlackInputBlock, SlackInputBlockElement, SlackMessageContent, SlackModalView,
+    SlackSectionBlock, SlackView,
 };
 
-use crate::models::approval::RiskLevel;
+use crate::config::{SlackDetailLevel, SlackRenderMode};
+use crate::diff::ownership::ApprovalContext;
+use crate::integrations::issues;
+use crate::models::approval::{ApprovalRequest, RiskLevel};
+use crate::models::conflict::{ApplyConflict, ConflictKind};
+use crate::models::preferences::{NotificationDelivery, UserPreferences};
+use crate::models::progress::ProgressStatus;
 use crate::models::prompt::PromptType;
 use crate::models::session::{ProtocolMode, Session, SessionMode, SessionStatus};
 
@@ -51,7 +63,7 @@ pub fn action_buttons(block_id: &str, buttons: &[(&str, &str, &str)]) -> SlackBl
     )
 }
 
-/// Build approval action buttons (Accept / Reject).
+/// Build approval action buttons (Accept / Reject / Snooze 30m).
 #[must_use]
 pub fn approval_buttons(request_id: &str) -> SlackBlock {
     action_buttons(
@@ -59,11 +71,31 @@ pub fn approval_buttons(request_id: &str) -> SlackBlock {
         &[
             ("approve_accept", "Accept", request_id),
             ("approve_reject", "Reject", request_id),
+            ("snooze_approval", "Snooze 30m", request_id),
         ],
     )
 }
 
-/// Build prompt action buttons (Continue / Refine / Stop).
+/// Build the Revive button shown on a timed-out approval request.
+#[must_use]
+pub fn revive_button(request_id: &str) -> SlackBlock {
+    action_buttons(
+        &format!("revive_{request_id}"),
+        &[("revive_approval", "Revive", request_id)],
+    )
+}
+
+/// Build the message a timed-out approval request is replaced with: its
+/// status line and a Revive button to re-open it.
+#[must_use]
+pub fn timed_out_approval(title: &str, request_id: &str) -> Vec<SlackBlock> {
+    vec![
+        text_section(&format!("\u{23f1}\u{fe0f} *Timed out* \u{2014} {title}")),
+        revive_button(request_id),
+    ]
+}
+
+/// Build prompt action buttons (Continue / Refine / Stop / Snooze 30m).
 #[must_use]
 pub fn prompt_buttons(prompt_id: &str) -> SlackBlock {
     action_buttons(
@@ -72,10 +104,35 @@ pub fn prompt_buttons(prompt_id: &str) -> SlackBlock {
             ("prompt_continue", "Continue", prompt_id),
             ("prompt_refine", "Refine", prompt_id),
             ("prompt_stop", "Stop", prompt_id),
+            ("snooze_prompt", "Snooze 30m", prompt_id),
         ],
     )
 }
 
+/// Build the configured quick-reply buttons for a prompt, or `None` when
+/// there are none.
+///
+/// Each button's `action_id` is `prompt_quick_<index>`, indexing into
+/// `replies`; its value is the `prompt_id`.
+#[must_use]
+pub fn quick_reply_buttons(prompt_id: &str, replies: &[String]) -> Option<SlackBlock> {
+    if replies.is_empty() {
+        return None;
+    }
+    let action_ids: Vec<String> = (0..replies.len())
+        .map(|index| format!("prompt_quick_{index}"))
+        .collect();
+    let buttons: Vec<(&str, &str, &str)> = action_ids
+        .iter()
+        .zip(replies)
+        .map(|(action_id, label)| (action_id.as_str(), label.as_str(), prompt_id))
+        .collect();
+    Some(action_buttons(
+        &format!("prompt_quick_{prompt_id}"),
+        &buttons,
+    ))
+}
+
 /// Build stall nudge action buttons (Nudge / Nudge with Instructions / Stop).
 #[must_use]
 pub fn nudge_buttons(alert_id: &str) -> SlackBlock {
@@ -106,6 +163,61 @@ pub fn wait_buttons(session_id: &str) -> SlackBlock {
     )
 }
 
+/// Build completion prompt action buttons (Terminate Session / Assign New Task).
+#[must_use]
+pub fn completion_buttons(session_id: &str) -> SlackBlock {
+    action_buttons(
+        &format!("completion_{session_id}"),
+        &[
+            ("completion_terminate", "Terminate Session", session_id),
+            ("completion_assign", "Assign New Task", session_id),
+        ],
+    )
+}
+
+/// Build knowledge note action buttons (Approve / Reject).
+#[must_use]
+pub fn knowledge_buttons(note_id: &str) -> SlackBlock {
+    action_buttons(
+        &format!("knowledge_{note_id}"),
+        &[
+            ("knowledge_approve", "Add to Knowledge Base", note_id),
+            ("knowledge_reject", "Reject", note_id),
+        ],
+    )
+}
+
+/// Build the Slack card for a proposed knowledge note: title, tags, the
+/// body as a quote, and the approve/reject buttons.
+#[must_use]
+pub fn knowledge_proposal_blocks(
+    note_id: &str,
+    title: &str,
+    body: &str,
+    tags: &[String],
+) -> Vec<SlackBlock> {
+    let mut header = format!(
+        "\u{1f4da} *Knowledge note proposed:* {}",
+        slack_escape(title)
+    );
+    if !tags.is_empty() {
+        let tags: Vec<String> = tags
+            .iter()
+            .map(|t| format!("`{}`", slack_escape(t)))
+            .collect();
+        header = format!("{header}\n*Tags:* {}", tags.join(" "));
+    }
+    let quoted: Vec<String> = truncate_text(&slack_escape(body), 2800)
+        .lines()
+        .map(|line| format!("> {line}"))
+        .collect();
+    vec![
+        text_section(&header),
+        text_section(&quoted.join("\n")),
+        knowledge_buttons(note_id),
+    ]
+}
+
 /// Build a plain text section block.
 #[must_use]
 pub fn text_section(text: &str) -> SlackBlock {
@@ -155,6 +267,25 @@ pub fn stall_alert_blocks(session_id: &str, idle_seconds: u64) -> Vec<SlackBlock
     ]
 }
 
+/// Build the prompt posted when an agent that reported all work done has
+/// gone quiet, asking the operator to terminate it or hand it more work.
+///
+/// Intended for posting directly to Slack when `StallEvent::Completed` fires.
+#[must_use]
+pub fn completion_prompt_blocks(session_id: &str, idle_seconds: u64) -> Vec<SlackBlock> {
+    vec![
+        severity_section(
+            "info",
+            &format!(
+                "*Agent appears finished* \u{2014} session `{session_id}` reported every \
+                 progress item done and has been idle for {idle_seconds}s. Terminate the \
+                 session or assign a new task?"
+            ),
+        ),
+        completion_buttons(session_id),
+    ]
+}
+
 /// T063 — Build an "Add to auto-approve?" action button for manual approval suggestions.
 ///
 /// Intended for posting after an operator manually approves a command, giving
@@ -170,6 +301,29 @@ pub fn auto_approve_suggestion_button(command: &str) -> SlackBlock {
     )
 }
 
+/// Build the Reconcile / Dismiss buttons of an auto-approve drift report;
+/// both carry the drifted workspace root.
+#[must_use]
+pub fn policy_drift_buttons(workspace_root: &str) -> SlackBlock {
+    action_buttons(
+        "policy_drift",
+        &[
+            ("policy_drift_reconcile", "Reconcile", workspace_root),
+            ("policy_drift_dismiss", "Dismiss", workspace_root),
+        ],
+    )
+}
+
+/// Build an auto-approve drift report: what each rule file lacks, then
+/// the buttons.
+#[must_use]
+pub fn policy_drift_blocks(description: &str, workspace_root: &str) -> Vec<SlackBlock> {
+    vec![
+        text_section(description),
+        policy_drift_buttons(workspace_root),
+    ]
+}
+
 /// Build terminal command approval blocks.
 ///
 /// Presents the command in a code fence with Approve / Reject buttons.
@@ -241,6 +395,96 @@ pub fn diff_force_warning_section(file_path: &str) -> SlackBlock {
     )
 }
 
+/// Build apply conflict buttons (Force Apply / Merge View / Reject). Force
+/// Apply is only offered when the diff still applies to the changed file.
+#[must_use]
+pub fn apply_conflict_buttons(conflict_id: &str, offer_force: bool) -> SlackBlock {
+    let mut buttons = Vec::with_capacity(3);
+    if offer_force {
+        buttons.push(("conflict_force", "Force Apply", conflict_id));
+    }
+    buttons.push(("conflict_merge", "Merge View", conflict_id));
+    buttons.push(("conflict_reject", "Reject", conflict_id));
+    action_buttons(&format!("conflict_{conflict_id}"), &buttons)
+}
+
+/// Build the card asking the operator to settle a diff `check_diff` could
+/// not apply: what conflicts, why, and the buttons.
+#[must_use]
+pub fn apply_conflict_blocks(
+    approval: &ApprovalRequest,
+    conflict: &ApplyConflict,
+) -> Vec<SlackBlock> {
+    let (reason, offer_force) = match conflict.kind {
+        ConflictKind::HashMismatch => {
+            ("the file changed on disk after the diff was proposed", true)
+        }
+        ConflictKind::HunkConflict => ("the diff no longer applies to the file", false),
+    };
+    let choices = if offer_force {
+        "Force Apply overwrites the changes on disk, Merge View hands the agent both versions \
+         to reconcile, Reject drops the diff."
+    } else {
+        "Merge View hands the agent both versions to reconcile, Reject drops the diff."
+    };
+    let summary = format!(
+        "\u{2694}\u{fe0f} *Apply conflict \u{2014} {reason}*\n*{}*\n\u{1f4c4} `{}` | Request `{}` \
+         (raised {})\n>{}\n{choices}",
+        slack_escape(&approval.title),
+        approval.file_path,
+        approval.short_id,
+        slack_datetime(approval.created_at),
+        slack_escape(&conflict.detail),
+    );
+    vec![
+        text_section(&summary),
+        apply_conflict_buttons(&conflict.id, offer_force),
+    ]
+}
+
+/// Build protected-path apply buttons (Confirm Apply / Cancel).
+#[must_use]
+pub fn protected_apply_buttons(request_id: &str) -> SlackBlock {
+    action_buttons(
+        &format!("confirm_{request_id}"),
+        &[
+            ("confirm_apply", "Confirm Apply", request_id),
+            ("confirm_cancel", "Cancel", request_id),
+        ],
+    )
+}
+
+/// Build the card asking the operator to confirm an approved diff to a
+/// protected path: the change summary, when it was requested, and the
+/// buttons.
+#[must_use]
+pub fn protected_apply_blocks(
+    approval: &ApprovalRequest,
+    (added, removed): (usize, usize),
+    force: bool,
+) -> Vec<SlackBlock> {
+    let mut summary = format!(
+        "\u{1f6e1}\u{fe0f} *Protected path \u{2014} confirm apply*\n*{}*\n\u{1f4c4} `{}` | \
+         +{added} \u{2212}{removed} lines | Risk: *{}*\nRequest `{}` (raised {}) was approved; the file \
+         is only written once you confirm.",
+        slack_escape(&approval.title),
+        approval.file_path,
+        approval.risk_level.as_str(),
+        approval.short_id,
+        slack_datetime(approval.created_at),
+    );
+    if force {
+        summary.push_str(
+            "\n\u{26a0}\u{fe0f} The file has changed since the proposal; confirming \
+             overwrites those changes.",
+        );
+    }
+    vec![
+        text_section(&summary),
+        protected_apply_buttons(&approval.id),
+    ]
+}
+
 /// Build blocks for a threaded snippet review reply.
 ///
 /// Each entry in `snippets` is `(label, language, content)`.  The content
@@ -250,11 +494,27 @@ pub fn diff_force_warning_section(file_path: &str) -> SlackBlock {
 /// separated by dividers.
 #[must_use]
 pub fn code_snippet_blocks(snippets: &[(&str, &str, &str)]) -> Vec<SlackBlock> {
+    snippet_blocks(
+        "\u{1f4dd} *Code snippets for review*\n_Curated by the agent \u{2014} most relevant sections_",
+        snippets,
+    )
+}
+
+/// Build blocks for a threaded reply of snippets the server generated
+/// from the diff (`diff::snippets`), in the same layout as
+/// [`code_snippet_blocks`].
+#[must_use]
+pub fn generated_snippet_blocks(snippets: &[(&str, &str, &str)]) -> Vec<SlackBlock> {
+    snippet_blocks(
+        "\u{1f4dd} *Code snippets for review*\n_Generated from the diff \u{2014} each changed region with its enclosing declaration_",
+        snippets,
+    )
+}
+
+fn snippet_blocks(header: &str, snippets: &[(&str, &str, &str)]) -> Vec<SlackBlock> {
     const MAX_CHARS: usize = 2_600;
 
-    let mut blocks: Vec<SlackBlock> = vec![text_section(
-        "\u{1f4dd} *Code snippets for review*\n_Curated by the agent \u{2014} most relevant sections_",
-    )];
+    let mut blocks: Vec<SlackBlock> = vec![text_section(header)];
 
     for &(label, language, content) in snippets {
         let truncated;
@@ -311,6 +571,103 @@ pub fn instruction_modal(callback_id: &str, title: &str, placeholder: &str) -> S
     )
 }
 
+/// Build the `/intercom prefs` modal, pre-filled with `prefs`.
+///
+/// Block and action IDs: `detail_block`/`detail_select` (a
+/// [`SlackDetailLevel`] name, or `default` for the server's
+/// `slack_detail_level`), `delivery_block`/`delivery_select` (a
+/// [`NotificationDelivery`] name) and the optional
+/// `quiet_block`/`quiet_text` (`HH:MM-HH:MM`).
+#[must_use]
+pub fn prefs_modal(
+    callback_id: &str,
+    prefs: &UserPreferences,
+    server_detail_level: SlackDetailLevel,
+) -> SlackView {
+    let detail_default = format!("Server default ({})", server_detail_level.as_str());
+    let detail_options = [
+        ("default", detail_default.as_str()),
+        ("minimal", "Minimal \u{2014} warnings and errors"),
+        ("standard", "Standard"),
+        ("verbose", "Verbose"),
+    ];
+    let detail_value = prefs
+        .detail_level
+        .map_or("default", SlackDetailLevel::as_str);
+    let delivery_options = [
+        (NotificationDelivery::Channel.as_str(), "Session thread"),
+        (NotificationDelivery::Dm.as_str(), "Direct message"),
+        (
+            NotificationDelivery::Both.as_str(),
+            "Thread and direct message",
+        ),
+    ];
+
+    let mut quiet = SlackBlockPlainTextInputElement::new(SlackActionId("quiet_text".to_owned()))
+        .with_placeholder(SlackBlockPlainTextOnly::from("22:00-07:00"));
+    if let Some(hours) = prefs.quiet_hours {
+        quiet = quiet.with_initial_value(hours.to_string());
+    }
+
+    let blocks: Vec<SlackBlock> = vec![
+        select_input(
+            "detail_block",
+            "detail_select",
+            "Detail level",
+            &detail_options,
+            detail_value,
+        ),
+        select_input(
+            "delivery_block",
+            "delivery_select",
+            "Deliver session notifications to",
+            &delivery_options,
+            prefs.delivery.as_str(),
+        ),
+        SlackInputBlock::new(
+            SlackBlockPlainTextOnly::from("Quiet hours"),
+            SlackInputBlockElement::PlainTextInput(quiet),
+        )
+        .with_block_id(SlackBlockId("quiet_block".to_owned()))
+        .with_optional(true)
+        .with_hint(SlackBlockPlainTextOnly::from(
+            "Server-local time. No direct messages except errors in this window.",
+        ))
+        .into(),
+    ];
+
+    SlackView::Modal(
+        SlackModalView::new(SlackBlockPlainTextOnly::from("Notification prefs"), blocks)
+            .with_callback_id(SlackCallbackId(callback_id.to_owned()))
+            .with_submit(SlackBlockPlainTextOnly::from("Save")),
+    )
+}
+
+/// An input block holding one static select of `(value, label)` options,
+/// with `initial` selected.
+fn select_input(
+    block_id: &str,
+    action_id: &str,
+    label: &str,
+    options: &[(&str, &str)],
+    initial: &str,
+) -> SlackBlock {
+    let choice = |(value, text): &(&str, &str)| {
+        SlackBlockChoiceItem::new(SlackBlockPlainTextOnly::from(*text), (*value).to_owned())
+    };
+    let mut select = SlackBlockStaticSelectElement::new(SlackActionId(action_id.to_owned()))
+        .with_options(options.iter().map(choice).collect());
+    if let Some(option) = options.iter().find(|(value, _)| *value == initial) {
+        select = select.with_initial_option(choice(option));
+    }
+    SlackInputBlock::new(
+        SlackBlockPlainTextOnly::from(label),
+        SlackInputBlockElement::StaticSelect(select),
+    )
+    .with_block_id(SlackBlockId(block_id.to_owned()))
+    .into()
+}
+
 /// Build the initial "Session started" Block Kit message for a new session.
 ///
 /// Posts as a top-level channel message whose Slack timestamp becomes the
@@ -321,7 +678,7 @@ pub fn instruction_modal(callback_id: &str, title: &str, placeholder: &str) -> S
 /// workspace root, and the session creation timestamp.
 #[must_use]
 pub fn session_started_blocks(session: &Session) -> Vec<SlackBlock> {
-    let short_id: String = session.id.chars().take(8).collect();
+    let short_id = &session.short_id;
     let protocol = match session.protocol_mode {
         ProtocolMode::Mcp => "MCP",
         ProtocolMode::Acp => "ACP",
@@ -331,18 +688,19 @@ pub fn session_started_blocks(session: &Session) -> Vec<SlackBlock> {
         SessionMode::Local => "local",
         SessionMode::Hybrid => "hybrid",
     };
-    let started = session.created_at.format("%Y-%m-%d %H:%M UTC");
+    let started = slack_datetime(session.created_at);
     let emoji = match session.protocol_mode {
         ProtocolMode::Acp => "\u{1f916}",
         ProtocolMode::Mcp => "\u{1f680}",
     };
-    let text = format!(
+    let mut text = format!(
         "{emoji} *Session started*\n\
-         *ID:* `{short_id}\u{2026}` | *Protocol:* {protocol} | *Mode:* {mode}\n\
+         *ID:* `{short_id}` | *Protocol:* {protocol} | *Mode:* {mode}\n\
          *Workspace:* `{workspace}`\n\
          *Started:* {started}",
         workspace = session.workspace_root,
     );
+    push_issue_line(&mut text, session);
     vec![text_section(&text)]
 }
 
@@ -353,35 +711,115 @@ pub fn session_started_blocks(session: &Session) -> Vec<SlackBlock> {
 /// termination reason, and wall-clock duration.
 #[must_use]
 pub fn session_ended_blocks(session: &Session, reason: &str) -> Vec<SlackBlock> {
-    let short_id: String = session.id.chars().take(8).collect();
+    let short_id = &session.short_id;
     let status_label = match session.status {
         SessionStatus::Terminated => "terminated",
         SessionStatus::Interrupted => "interrupted",
         _ => "ended",
     };
-    let duration_text = if let Some(ended_at) = session.terminated_at {
-        let secs = ended_at
-            .signed_duration_since(session.created_at)
-            .num_seconds()
-            .max(0);
-        if secs >= 3600 {
-            format!("{}h {}m", secs / 3600, (secs % 3600) / 60)
-        } else if secs >= 60 {
-            format!("{}m {}s", secs / 60, secs % 60)
-        } else {
-            format!("{secs}s")
-        }
-    } else {
-        "unknown".to_owned()
-    };
-    let text = format!(
-        "\u{1f3c1} *Session ended* \u{2014} `{short_id}\u{2026}`\n\
+    let duration_text = session.terminated_at.map_or_else(
+        || "unknown".to_owned(),
+        |ended_at| {
+            format_elapsed(
+                ended_at
+                    .signed_duration_since(session.created_at)
+                    .num_seconds(),
+            )
+        },
+    );
+    let mut text = format!(
+        "\u{1f3c1} *Session ended* \u{2014} `{short_id}`\n\
          *Status:* {status_label} | *Reason:* {reason}\n\
          *Duration:* {duration_text}",
     );
+    push_issue_line(&mut text, session);
     vec![text_section(&text)]
 }
 
+/// Build the summary posted when a session reaches its time-box deadline.
+///
+/// Lists the budget, the last tool the agent called, and the agent's most
+/// recent progress snapshot so the operator can decide whether to continue.
+#[must_use]
+pub fn time_box_expired_blocks(session: &Session, budget: chrono::Duration) -> Vec<SlackBlock> {
+    let short_id = &session.short_id;
+    let mut text = format!(
+        "\u{23f1}\u{fe0f} *Time box reached* \u{2014} `{short_id}`\n\
+         The agent was interrupted after its {budget} budget.\n\
+         *Last tool:* {last_tool}",
+        budget = format_elapsed(budget.num_seconds()),
+        last_tool = session.last_tool.as_deref().unwrap_or("none"),
+    );
+    push_issue_line(&mut text, session);
+    if let Some(ref items) = session.progress_snapshot {
+        text.push_str("\n*Progress:*");
+        for item in items {
+            let mark = match item.status {
+                ProgressStatus::Done => "\u{2705}",
+                ProgressStatus::InProgress => "\u{1f504}",
+                ProgressStatus::Pending => "\u{2b1c}",
+            };
+            text.push('\n');
+            text.push_str(mark);
+            text.push(' ');
+            text.push_str(&item.label);
+        }
+    }
+    vec![text_section(&text)]
+}
+
+/// Render the linked-issue line for a session, e.g. `🎫 Issue: PROJ-123`.
+///
+/// Issue URLs become links labelled with the short reference.
+#[must_use]
+pub fn issue_line(issue_ref: &str) -> String {
+    let label = slack_escape(&issues::issue_label(issue_ref));
+    if issue_ref.starts_with("https://") || issue_ref.starts_with("http://") {
+        format!("\u{1f3ab} *Issue:* <{issue_ref}|{label}>")
+    } else {
+        format!("\u{1f3ab} *Issue:* {label}")
+    }
+}
+
+/// Append the session's [`issue_line`] to `text`, if it has an issue.
+fn push_issue_line(text: &mut String, session: &Session) {
+    if let Some(ref issue_ref) = session.issue_ref {
+        text.push('\n');
+        text.push_str(&issue_line(issue_ref));
+    }
+}
+
+/// Render a non-negative number of seconds as `1h 5m`, `3m 20s`, or `42s`.
+pub(crate) fn format_elapsed(secs: i64) -> String {
+    let secs = secs.max(0);
+    if secs >= 3600 {
+        format!("{}h {}m", secs / 3600, (secs % 3600) / 60)
+    } else if secs >= 60 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+/// Build a link to the Slack message at `ts` in `channel`.
+///
+/// Replies inside a thread other than their own need `thread_ts` so Slack
+/// opens the thread rather than the channel.
+#[must_use]
+pub fn message_link(channel: &str, ts: &str, thread_ts: Option<&str>) -> String {
+    let mut url = format!(
+        "https://slack.com/archives/{channel}/p{}",
+        ts.replace('.', "")
+    );
+    if let Some(thread_ts) = thread_ts.filter(|thread_ts| *thread_ts != ts) {
+        url.push_str("?thread_ts=");
+        url.push_str(thread_ts);
+        url.push_str("&cid=");
+        url.push_str(channel);
+    }
+    url
+}
+
 // ── Shared approval and prompt block builders (D1) ───────────────────────────
 // Extracted from mcp/tools/ask_approval.rs and mcp/tools/forward_prompt.rs so
 // both MCP tool handlers and ACP event handlers use identical rendering logic.
@@ -405,6 +843,77 @@ pub fn slack_escape(s: &str) -> String {
         .replace('>', "&gt;")
 }
 
+/// Date and time of `at` in each reader's own time zone, for example
+/// "Mar 4th, 2026 at 2:05 PM". Clients that cannot render Slack's date
+/// syntax show the UTC fallback instead.
+#[must_use]
+pub fn slack_datetime(at: DateTime<Utc>) -> String {
+    slack_date(at, "{date_short_pretty} at {time}", "%Y-%m-%d %H:%M UTC")
+}
+
+/// Time of day of `at` in each reader's own time zone.
+#[must_use]
+pub fn slack_time(at: DateTime<Utc>) -> String {
+    slack_date(at, "{time}", "%H:%M UTC")
+}
+
+/// Calendar date of `at` in each reader's own time zone.
+#[must_use]
+pub fn slack_day(at: DateTime<Utc>) -> String {
+    slack_date(at, "{date_short_pretty}", "%Y-%m-%d")
+}
+
+/// Countdown line for a request posted at `now` that times out after
+/// `timeout_seconds`, e.g. "Expires 2:05 PM (in 5 minutes)" in the
+/// reader's time zone.
+#[must_use]
+pub fn expiry_line(now: DateTime<Utc>, timeout_seconds: u64) -> String {
+    let deadline = i64::try_from(timeout_seconds)
+        .ok()
+        .and_then(chrono::TimeDelta::try_seconds)
+        .and_then(|timeout| now.checked_add_signed(timeout))
+        .unwrap_or(DateTime::<Utc>::MAX_UTC);
+    expiry_line_at(deadline)
+}
+
+/// Countdown line for a request that times out at `deadline`.
+#[must_use]
+pub fn expiry_line_at(deadline: DateTime<Utc>) -> String {
+    format!(
+        "{EXPIRY_PREFIX}{}",
+        slack_date(deadline, "{time} ({ago})", "%H:%M UTC")
+    )
+}
+
+/// Start of every countdown line.
+const EXPIRY_PREFIX: &str = "\u{23f3} Expires ";
+
+/// Move the countdown line of `card` to `deadline`. Returns `false` when
+/// the card has no countdown.
+pub fn replace_expiry_line(card: &mut [SlackBlock], deadline: DateTime<Utc>) -> bool {
+    let Some(block) = card.iter_mut().find(|block| match block {
+        SlackBlock::Section(section) => section
+            .text
+            .as_ref()
+            .is_some_and(|text| block_text(text).starts_with(EXPIRY_PREFIX)),
+        _ => false,
+    }) else {
+        return false;
+    };
+    *block = text_section(&expiry_line_at(deadline));
+    true
+}
+
+/// Slack `<!date>` token for `at` with the given Slack date `tokens` and
+/// a chrono `fallback` format rendered in UTC.
+fn slack_date(at: DateTime<Utc>, tokens: &str, fallback: &str) -> String {
+    format!(
+        "<!date^{}^{tokens}|{}>",
+        at.timestamp(),
+        at.format(fallback)
+    )
+}
+
 /// Build Slack Block Kit blocks for an approval request message.
 ///
 /// Produces a header section with title, file path, and risk badge; an
@@ -449,6 +958,147 @@ pub fn build_approval_blocks(
     result
 }
 
+/// Most characters of hunk text one page of an approval card shows; Slack
+/// refuses section text over 3,000 characters.
+pub const MAX_HUNK_PAGE_CHARS: usize = 2_800;
+
+/// Split a unified diff into its hunks, each starting at its `@@` header.
+/// File header lines before the first hunk are dropped.
+#[must_use]
+pub fn diff_hunks(diff: &str) -> Vec<String> {
+    let mut hunks: Vec<String> = Vec::new();
+    for line in diff.lines() {
+        if line.starts_with("@@") {
+            hunks.push(String::new());
+        }
+        if let Some(hunk) = hunks.last_mut() {
+            hunk.push_str(line);
+            hunk.push('\n');
+        }
+    }
+    hunks
+}
+
+/// Build the paged hunk view of an approval card: hunk `index` of `diff`
+/// and Prev / Next buttons to page through the others.
+///
+/// The page is a section with block ID `hunk_page_<request_id>`; the
+/// buttons sit in an actions block `hunk_nav_<request_id>`, with action IDs
+/// `hunk_page_<target index>` and the request ID as value. A hunk longer
+/// than [`MAX_HUNK_PAGE_CHARS`] is cut short with a note. Returns `None`
+/// when the diff is short enough to show inline or has fewer than two
+/// hunks.
+#[must_use]
+pub fn hunk_page_blocks(request_id: &str, diff: &str, index: usize) -> Option<Vec<SlackBlock>> {
+    if diff.lines().count() <= INLINE_DIFF_THRESHOLD {
+        return None;
+    }
+    let hunks = diff_hunks(diff);
+    if hunks.len() < 2 {
+        return None;
+    }
+    let index = index.min(hunks.len() - 1);
+
+    let mut shown = String::new();
+    let mut cut = 0;
+    for line in hunks[index].lines() {
+        if cut > 0 || shown.len() + line.len() + 1 > MAX_HUNK_PAGE_CHARS {
+            cut += 1;
+        } else {
+            shown.push_str(line);
+            shown.push('\n');
+        }
+    }
+    let more = match cut {
+        0 => String::new(),
+        1 => "\n_1 more line in the uploaded diff_".to_owned(),
+        _ => format!("\n_{cut} more lines in the uploaded diff_"),
+    };
+    let text = format!(
+        "*Hunk {} of {}*\n```\n{shown}```{more}",
+        index + 1,
+        hunks.len()
+    );
+    let page = SlackBlock::Section(
+        SlackSectionBlock::new()
+            .with_text(SlackBlockText::MarkDown(text.into()))
+            .with_block_id(SlackBlockId(format!("hunk_page_{request_id}"))),
+    );
+
+    let prev = index.checked_sub(1).map(|i| format!("hunk_page_{i}"));
+    let next = (index + 1 < hunks.len()).then(|| format!("hunk_page_{}", index + 1));
+    let mut buttons: Vec<(&str, &str, &str)> = Vec::new();
+    if let Some(ref action_id) = prev {
+        buttons.push((action_id, "\u{25c0} Prev hunk", request_id));
+    }
+    if let Some(ref action_id) = next {
+        buttons.push((action_id, "Next hunk \u{25b6}", request_id));
+    }
+    let nav = action_buttons(&format!("hunk_nav_{request_id}"), &buttons);
+    Some(vec![page, nav])
+}
+
+/// Render the ownership context line(s) for an approval message.
+///
+/// Lists up to three authors who last touched the affected lines (with line
+/// counts and the latest commit date) and the matching CODEOWNERS entries,
+/// then @-mentions the owners mapped to Slack users — as a cc, or as the
+/// required approvers when owner approval is enforced.
+/// Returns `None` when there is no context to show.
+#[must_use]
+pub fn approval_context_text(context: &ApprovalContext) -> Option<String> {
+    if context.is_empty() {
+        return None;
+    }
+    let mut lines = Vec::new();
+    if !context.blame.is_empty() {
+        let authors: Vec<String> = context
+            .blame
+            .iter()
+            .map(|author| {
+                let noun = if author.lines == 1 { "line" } else { "lines" };
+                match author.last_touched {
+                    Some(at) => format!(
+                        "*{}* ({} {noun}, {})",
+                        slack_escape(&author.name),
+                        author.lines,
+                        slack_day(at)
+                    ),
+                    None => format!("*{}* ({} {noun})", slack_escape(&author.name), author.lines),
+                }
+            })
+            .collect();
+        lines.push(format!("\u{1f464} Last touched by: {}", authors.join(", ")));
+    }
+    if !context.owners.is_empty() {
+        let owners: Vec<String> = context
+            .owners
+            .iter()
+            .map(|owner| format!("`{}`", slack_escape(owner)))
+            .collect();
+        lines.push(format!(
+            "\u{1f3f7}\u{fe0f} Code owners: {}",
+            owners.join(", ")
+        ));
+    }
+    if !context.approvers.is_empty() {
+        let mentions: Vec<String> = context
+            .approvers
+            .iter()
+            .map(|id| format!("<@{id}>"))
+            .collect();
+        if context.approvers_required {
+            lines.push(format!(
+                "\u{1f514} Approval required from a code owner: {}",
+                mentions.join(" ")
+            ));
+        } else {
+            lines.push(format!("\u{1f514} cc {}", mentions.join(" ")));
+        }
+    }
+    Some(lines.join("\n"))
+}
+
 /// Build Slack Block Kit blocks for a continuation prompt message.
 ///
 /// Produces a header with the prompt type icon and label, the prompt text,
@@ -604,7 +1254,8 @@ pub fn build_text_only_wait(message: &str, timeout_seconds: u64) -> String {
 /// Build plain-text approval message for thread-only display (US17).
 ///
 /// When `diff` is `None`, a placeholder noting the diff was uploaded as
-/// a file attachment is rendered instead of an inline code block.
+/// a file attachment is rendered instead of an inline code block. `context`
+/// is the ownership summary from [`approval_context_text`], if any.
 #[must_use]
 pub fn build_text_only_approval(
     title: &str,
@@ -612,6 +1263,7 @@ pub fn build_text_only_approval(
     file_path: &str,
     risk_level: &RiskLevel,
     description: Option<&str>,
+    context: Option<&str>,
 ) -> String {
     let risk_icon = match risk_level {
         RiskLevel::Low => "\u{1f7e2}",
@@ -640,6 +1292,10 @@ pub fn build_text_only_approval(
         None => parts.push("_Diff uploaded as a file attachment in this thread._".to_owned()),
     }
 
+    if let Some(ctx) = context {
+        parts.push(ctx.to_owned());
+    }
+
     parts.push(
         "\n\u{1f4ac} Reply with `@agent-intercom` followed by: \
          `approve` or `reject <reason>`"
@@ -648,3 +1304,117 @@ pub fn build_text_only_approval(
 
     parts.join("\n")
 }
+
+// ── Text fallbacks and plain-text rendering ────────────────────────
+
+/// Longest `text` fallback sent alongside blocks. Notifications show only
+/// the start, and the blocks carry the full message.
+const FALLBACK_TEXT_MAX: usize = 3000;
+
+/// Longest plain-text rendering Slack accepts as a message `text`.
+const PLAIN_TEXT_MAX: usize = 40_000;
+
+/// Render `blocks` as text, one line per block: headers and sections
+/// (with their fields), context elements, image alt text, input labels
+/// and button labels. Dividers become a blank line.
+#[must_use]
+pub fn render_plain_text(blocks: &[SlackBlock]) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for block in blocks {
+        match block {
+            SlackBlock::Section(section) => {
+                lines.extend(section.text.as_ref().map(block_text));
+                lines.extend(section.fields.iter().flatten().map(block_text));
+            }
+            SlackBlock::Header(header) => lines.push(format!("*{}*", plain_only(&header.text))),
+            SlackBlock::Divider(_) => lines.push(String::new()),
+            SlackBlock::Image(image) => lines.push(format!("[image: {}]", image.alt_text)),
+            SlackBlock::Actions(actions) => {
+                let labels: Vec<String> = actions
+                    .elements
+                    .iter()
+                    .filter_map(|element| match element {
+                        SlackActionBlockElement::Button(button) => Some(plain_only(&button.text)),
+                        _ => None,
+                    })
+                    .collect();
+                if !labels.is_empty() {
+                    lines.push(format!("Buttons: {}", labels.join(", ")));
+                }
+            }
+            SlackBlock::Context(context) => {
+                let parts: Vec<&str> = context
+                    .elements
+                    .iter()
+                    .filter_map(|element| match element {
+                        SlackContextBlockElement::Plain(text) => Some(text.text.as_str()),
+                        SlackContextBlockElement::MarkDown(text) => Some(text.text.as_str()),
+                        SlackContextBlockElement::Image(_) => None,
+                    })
+                    .collect();
+                if !parts.is_empty() {
+                    lines.push(parts.join(" | "));
+                }
+            }
+            SlackBlock::Input(input) => lines.push(plain_only(&input.label)),
+            SlackBlock::Markdown(markdown) => lines.push(markdown.text.clone()),
+            _ => {}
+        }
+    }
+    lines.join("\n").trim().to_owned()
+}
+
+/// The `text` fallback for a message of `blocks`: their text rendering,
+/// cut to a length notifications can show.
+#[must_use]
+pub fn fallback_text(blocks: &[SlackBlock]) -> String {
+    truncate_text(&render_plain_text(blocks), FALLBACK_TEXT_MAX)
+}
+
+/// Message content for `text` and `blocks` as sent in `mode`.
+///
+/// With blocks, a missing `text` is filled in with [`fallback_text`] so
+/// every notification and screen reader gets a meaningful summary. In
+/// plain-text mode the blocks are rendered into `text` and dropped.
+#[must_use]
+pub fn message_content(
+    text: Option<String>,
+    blocks: Option<Vec<SlackBlock>>,
+    mode: SlackRenderMode,
+) -> SlackMessageContent {
+    let (text, blocks) = match (blocks, mode) {
+        (Some(blocks), SlackRenderMode::PlainText) => {
+            let rendered = render_plain_text(&blocks);
+            let text = if rendered.is_empty() {
+                text
+            } else {
+                Some(truncate_text(&rendered, PLAIN_TEXT_MAX))
+            };
+            (text, None)
+        }
+        (Some(blocks), SlackRenderMode::Blocks) => {
+            (text.or_else(|| Some(fallback_text(&blocks))), Some(blocks))
+        }
+        (None, _) => (text, None),
+    };
+    SlackMessageContent {
+        text,
+        blocks,
+        attachments: None,
+        upload: None,
+        files: None,
+        reactions: None,
+        metadata: None,
+    }
+}
+
+fn block_text(text: &SlackBlockText) -> String {
+    match text {
+        SlackBlockText::Plain(text) => text.text.clone(),
+        SlackBlockText::MarkDown(text) => text.text.clone(),
+    }
+}
+
+fn plain_only(text: &SlackBlockPlainTextOnly) -> String {
+    block_text(&SlackBlockText::from(text.clone()))
+}
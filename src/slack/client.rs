@@ -1,30 +1,57 @@
 //! Slack Socket Mode client with a small buffered send queue.
 //!
+//! Every Web API call is counted against one shared
+//! [`RateBudget`](crate::slack::rate_budget::RateBudget): a `429` pauses all
+//! callers for its `Retry-After`, and low-priority updates queued with
+//! [`SlackService::enqueue_low_priority`] or
+//! [`SlackService::update_message_low_priority`] are batched by the queue
+//! worker while the budget is tight.
+//!
+//! Every message goes out through [`blocks::message_content`]: Block Kit
+//! messages get a generated `text` fallback, and `[slack] render_mode =
+//! "plain_text"` sends them as text only. A message whose blocks Slack
+//! refuses (`invalid_blocks`) is retried once as text.
+//!
 //! Includes reconnection handling (T095 / SC-003): on each WebSocket
 //! hello event the client re-posts any pending interactive messages
 //! (approvals, prompts) that may have been lost during a disconnect.
 
+use std::future::Future;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use slack_morphism::errors::SlackClientError;
 use slack_morphism::prelude::{
-    SlackApiChatPostMessageRequest, SlackApiChatUpdateRequest, SlackApiConversationsHistoryRequest,
-    SlackApiFilesComplete, SlackApiFilesCompleteUploadExternalRequest,
-    SlackApiFilesGetUploadUrlExternalRequest, SlackApiToken, SlackApiTokenType, SlackApiTokenValue,
-    SlackApiViewsOpenRequest, SlackBlock, SlackChannelId, SlackClient,
-    SlackClientEventsListenerEnvironment, SlackClientHyperHttpsConnector, SlackClientSession,
-    SlackClientSocketModeConfig, SlackClientSocketModeListener, SlackFileSnippetType,
-    SlackHistoryMessage, SlackMessageContent, SlackSocketModeListenerCallbacks, SlackTeamId,
-    SlackTriggerId, SlackTs, SlackView,
+    SlackApiChatPostEphemeralRequest, SlackApiChatPostMessageRequest, SlackApiChatUpdateRequest,
+    SlackApiConversationsCreateRequest, SlackApiConversationsHistoryRequest,
+    SlackApiConversationsRepliesRequest, SlackApiFilesComplete,
+    SlackApiFilesCompleteUploadExternalRequest, SlackApiFilesGetUploadUrlExternalRequest,
+    SlackApiToken, SlackApiTokenType, SlackApiTokenValue, SlackApiViewsOpenRequest, SlackBlock,
+    SlackChannelId, SlackClient, SlackClientEventsListenerEnvironment,
+    SlackClientHyperHttpsConnector, SlackClientSession, SlackClientSocketModeConfig,
+    SlackClientSocketModeListener, SlackFileSnippetType, SlackHistoryMessage, SlackMessageContent,
+    SlackSocketModeListenerCallbacks, SlackTeamId, SlackTriggerId, SlackTs, SlackUserId, SlackView,
+};
+use tokio::{
+    sync::{mpsc, Mutex},
+    task::JoinHandle,
+    time::sleep,
 };
-use tokio::{sync::mpsc, task::JoinHandle, time::sleep};
 use tracing::{error, info, warn};
 
+use crate::models::approval::ApprovalRequest;
+use crate::models::prompt::ContinuationPrompt;
 use crate::models::session::SessionMode;
 use crate::persistence::session_repo::SessionRepo;
-use crate::slack::{commands, events, push_events};
+use crate::slack::history_cache::{HistoryCache, HistoryPage};
+use crate::slack::rate_budget::{Deferred, DeferredUpdates, Pressure, RateBudget};
+use crate::slack::socket_health::SocketHealth;
+use crate::slack::{blocks, commands, events, push_events};
 use crate::state::AppState;
-use crate::{config::SlackConfig, AppError, Result};
+use crate::{
+    config::{SlackConfig, SlackRateLimitConfig, SlackRenderMode},
+    AppError, Result,
+};
 
 const QUEUE_CAPACITY: usize = 256;
 const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
@@ -54,16 +81,8 @@ impl SlackMessage {
         }
     }
 
-    fn into_request(self) -> SlackApiChatPostMessageRequest {
-        let content = SlackMessageContent {
-            text: self.text,
-            blocks: self.blocks,
-            attachments: None,
-            upload: None,
-            files: None,
-            reactions: None,
-            metadata: None,
-        };
+    fn into_request(self, mode: SlackRenderMode) -> SlackApiChatPostMessageRequest {
+        let content = blocks::message_content(self.text, self.blocks, mode);
 
         SlackApiChatPostMessageRequest {
             channel: self.channel,
@@ -82,13 +101,31 @@ impl SlackMessage {
     }
 }
 
+/// Item on the outgoing queue.
+enum Outgoing {
+    /// Post as soon as the budget allows.
+    Post(SlackMessage),
+    /// Post or edit when the budget has room; coalesced by `key`.
+    LowPriority {
+        key: Option<String>,
+        update: Deferred,
+    },
+}
+
 /// Slack Socket Mode wrapper that owns a rate-limited outgoing queue.
 pub struct SlackService {
     client: Arc<SlackClient<SlackClientHyperHttpsConnector>>,
     bot_token: SlackApiToken,
     /// App-level token used to authenticate Socket Mode connections.
     app_token: SlackApiToken,
-    queue_tx: mpsc::Sender<SlackMessage>,
+    queue_tx: mpsc::Sender<Outgoing>,
+    /// Receiving end of the queue, held by whichever worker is running so
+    /// a restarted worker picks up where the last one stopped.
+    queue_rx: Arc<Mutex<mpsc::Receiver<Outgoing>>>,
+    budget: Arc<RateBudget>,
+    socket_health: Arc<SocketHealth>,
+    render_mode: SlackRenderMode,
+    history_cache: HistoryCache,
 }
 
 /// Join handles for Slack background tasks.
@@ -112,7 +149,10 @@ impl SlackService {
     /// # Errors
     ///
     /// Returns `AppError::Slack` if the HTTPS connector cannot be created.
-    pub fn start(config: &SlackConfig) -> Result<(Self, SlackRuntime)> {
+    pub fn start(
+        config: &SlackConfig,
+        rate_limit: &SlackRateLimitConfig,
+    ) -> Result<(Self, SlackRuntime)> {
         let connector = SlackClientHyperHttpsConnector::new()
             .map_err(|err| AppError::Slack(format!("failed to init slack connector: {err}")))?;
         let client = Arc::new(SlackClient::new(connector));
@@ -136,8 +176,16 @@ impl SlackService {
             token_type: Some(SlackApiTokenType::App),
         };
 
+        let budget = Arc::new(RateBudget::new(rate_limit.clone()));
         let (queue_tx, queue_rx) = mpsc::channel(QUEUE_CAPACITY);
-        let queue_task = Self::spawn_worker(client.clone(), bot_token.clone(), queue_rx);
+        let queue_rx = Arc::new(Mutex::new(queue_rx));
+        let queue_task = Self::spawn_worker(
+            client.clone(),
+            bot_token.clone(),
+            Arc::clone(&queue_rx),
+            Arc::clone(&budget),
+            config.render_mode,
+        );
 
         info!("slack service started; socket mode pending app_state injection");
 
@@ -147,6 +195,15 @@ impl SlackService {
                 bot_token,
                 app_token,
                 queue_tx,
+                queue_rx,
+                budget,
+                socket_health: Arc::new(SocketHealth::new(
+                    SlackClientSocketModeConfig::DEFAULT_CONNECTIONS_COUNT,
+                )),
+                render_mode: config.render_mode,
+                history_cache: HistoryCache::new(Duration::from_secs(
+                    rate_limit.history_cache_seconds,
+                )),
             },
             SlackRuntime {
                 queue_task,
@@ -165,7 +222,18 @@ impl SlackService {
     /// maps.
     pub fn start_socket_mode(&self, app_state: Arc<AppState>) -> JoinHandle<()> {
         info!("starting slack socket mode with live app state");
-        Self::spawn_socket_mode(&self.client, self.app_token.clone(), Some(app_state))
+        Self::spawn_socket_mode(
+            &self.client,
+            self.app_token.clone(),
+            Some(app_state),
+            Arc::clone(&self.socket_health),
+        )
+    }
+
+    /// Socket Mode connection state, shared with the listener task.
+    #[must_use]
+    pub fn socket_health(&self) -> &Arc<SocketHealth> {
+        &self.socket_health
     }
 
     /// Enqueue a message for async delivery.
@@ -175,11 +243,80 @@ impl SlackService {
     /// Returns `AppError::Slack` if the message queue is full.
     pub async fn enqueue(&self, message: SlackMessage) -> Result<()> {
         self.queue_tx
-            .send(message)
+            .send(Outgoing::Post(message))
             .await
             .map_err(|err| AppError::Slack(format!("failed to enqueue slack message: {err}")))
     }
 
+    /// Enqueue a message that may wait while the rate budget is tight.
+    ///
+    /// Posted in order like [`enqueue`] while the budget has room.
+    /// Otherwise it is held for up to `[slack_rate_limit]
+    /// batch_window_seconds`: a newer message with the same `key` replaces
+    /// it, and held messages for the same thread go out as one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Slack` if the message queue is closed.
+    pub async fn enqueue_low_priority(
+        &self,
+        message: SlackMessage,
+        key: Option<String>,
+    ) -> Result<()> {
+        self.queue_tx
+            .send(Outgoing::LowPriority {
+                key,
+                update: Deferred::Post(message),
+            })
+            .await
+            .map_err(|err| AppError::Slack(format!("failed to enqueue slack message: {err}")))
+    }
+
+    /// Queue an in-place update of a message that may wait while the rate
+    /// budget is tight; only the latest pending update of a message is sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Slack` if the message queue is closed.
+    pub async fn update_message_low_priority(
+        &self,
+        channel: SlackChannelId,
+        ts: SlackTs,
+        blocks: Vec<SlackBlock>,
+    ) -> Result<()> {
+        self.queue_tx
+            .send(Outgoing::LowPriority {
+                key: None,
+                update: Deferred::Edit {
+                    channel,
+                    ts,
+                    blocks,
+                },
+            })
+            .await
+            .map_err(|err| AppError::Slack(format!("failed to enqueue slack update: {err}")))
+    }
+
+    /// The Web API call budget shared by every Slack call.
+    #[must_use]
+    pub fn rate_budget(&self) -> &RateBudget {
+        &self.budget
+    }
+
+    /// Messages and updates waiting on the outgoing queue.
+    #[must_use]
+    pub fn queue_depth(&self) -> usize {
+        self.queue_tx.max_capacity() - self.queue_tx.capacity()
+    }
+
+    /// Wait out any `Retry-After`, run `call`, and count it in the budget.
+    async fn metered<T>(
+        &self,
+        call: impl Future<Output = std::result::Result<T, SlackClientError>>,
+    ) -> std::result::Result<T, SlackClientError> {
+        metered(&self.budget, call).await
+    }
+
     /// Post a message directly and return the Slack message timestamp.
     ///
     /// Unlike [`enqueue`], this bypasses the background queue so that
@@ -189,54 +326,82 @@ impl SlackService {
     ///
     /// Returns `AppError::Slack` if the Slack API call fails.
     pub async fn post_message_direct(&self, message: SlackMessage) -> Result<SlackTs> {
-        let request = message.into_request();
+        #[cfg(feature = "chaos")]
+        if crate::chaos::faults().drop_post() {
+            return Err(AppError::Slack(
+                "failed to post message: dropped by chaos fault".into(),
+            ));
+        }
+        let mut request = message.into_request(self.render_mode);
         let session = self.http_session();
-        let response = session
-            .chat_post_message(&request)
-            .await
-            .map_err(|err| AppError::Slack(format!("failed to post message: {err}")))?;
+        let mut result = self.metered(session.chat_post_message(&request)).await;
+        if let Err(ref err) = result {
+            if let Some(content) = blocks_refused(err, &request.content) {
+                request.content = content;
+                result = self.metered(session.chat_post_message(&request)).await;
+            }
+        }
+        let response =
+            result.map_err(|err| AppError::Slack(format!("failed to post message: {err}")))?;
         Ok(response.ts)
     }
 
+    /// Spawn a new outgoing queue worker, for restarting one that stopped.
+    ///
+    /// The worker waits until the previous one has released the queue.
+    #[must_use]
+    pub fn spawn_queue_worker(&self) -> JoinHandle<()> {
+        Self::spawn_worker(
+            Arc::clone(&self.client),
+            self.bot_token.clone(),
+            Arc::clone(&self.queue_rx),
+            Arc::clone(&self.budget),
+            self.render_mode,
+        )
+    }
+
     fn spawn_worker(
         client: Arc<SlackClient<SlackClientHyperHttpsConnector>>,
         token: SlackApiToken,
-        mut queue_rx: mpsc::Receiver<SlackMessage>,
+        queue_rx: Arc<Mutex<mpsc::Receiver<Outgoing>>>,
+        budget: Arc<RateBudget>,
+        mode: SlackRenderMode,
     ) -> JoinHandle<()> {
         tokio::spawn(async move {
-            /// Maximum consecutive retries before dropping a message.
-            const MAX_RETRIES: u32 = 5;
-
+            let mut queue_rx = queue_rx.lock_owned().await;
             let session = client.open_session(&token);
-            while let Some(message) = queue_rx.recv().await {
-                let request = message.into_request();
-                let mut backoff = INITIAL_RETRY_DELAY;
-                let mut attempt = 0u32;
-                loop {
-                    match session.chat_post_message(&request).await {
-                        Ok(_) => {
-                            info!("sent slack message");
-                            break;
-                        }
-                        Err(error) => {
-                            attempt += 1;
-                            if attempt >= MAX_RETRIES {
-                                error!(?error, attempt, "dropping slack message after max retries");
-                                break;
-                            }
-                            let delay = match &error {
-                                slack_morphism::errors::SlackClientError::RateLimitError(rate) => {
-                                    rate.retry_after.unwrap_or(backoff)
-                                }
-                                _ => backoff,
-                            };
-                            warn!(?error, delay=?delay, attempt, "slack post failed; retrying");
-                            sleep(delay).await;
-                            backoff = (backoff * 2).min(MAX_RETRY_DELAY);
+            let mut deferred = DeferredUpdates::default();
+            loop {
+                let deadline = deferred.deadline(budget.batch_window());
+                let item = tokio::select! {
+                    item = queue_rx.recv() => item,
+                    () = sleep_until(deadline) => {
+                        flush_deferred(&session, &budget, mode, &mut deferred).await;
+                        continue;
+                    }
+                };
+                match item {
+                    None => break,
+                    Some(Outgoing::Post(message)) => post(&session, &budget, mode, message).await,
+                    Some(Outgoing::LowPriority { key, update }) => {
+                        if deferred.is_empty()
+                            && budget.pressure(Instant::now()) == Pressure::Normal
+                        {
+                            send_deferred(&session, &budget, mode, update).await;
+                        } else {
+                            deferred.push(key, update, Instant::now());
                         }
                     }
                 }
+                if deferred.due(
+                    budget.pressure(Instant::now()),
+                    budget.batch_window(),
+                    Instant::now(),
+                ) {
+                    flush_deferred(&session, &budget, mode, &mut deferred).await;
+                }
             }
+            flush_deferred(&session, &budget, mode, &mut deferred).await;
             info!("slack sender task exiting");
         })
     }
@@ -245,6 +410,7 @@ impl SlackService {
         client: &Arc<SlackClient<SlackClientHyperHttpsConnector>>,
         app_token: SlackApiToken,
         app_state: Option<Arc<AppState>>,
+        health: Arc<SocketHealth>,
     ) -> JoinHandle<()> {
         let mut listener_env = SlackClientEventsListenerEnvironment::new(Arc::clone(client))
             .with_error_handler(|err, _client, _state| {
@@ -273,6 +439,9 @@ impl SlackService {
                     guard.get_user_state::<Arc<AppState>>().cloned()
                 };
                 if let Some(app) = app {
+                    if let Some(ref slack) = app.slack {
+                        slack.socket_health().record_hello(chrono::Utc::now());
+                    }
                     repost_pending_messages(&app).await;
                     // Post reconnect notification only when a Slack service is wired up.
                     if let Some(ref slack) = app.slack {
@@ -297,12 +466,15 @@ impl SlackService {
 
         let listener = SlackClientSocketModeListener::new(&config, listener_env, callbacks);
         tokio::spawn(async move {
-            if let Err(error) = listener.listen_for(&app_token).await {
-                error!(?error, "socket mode listen failed");
-                return;
+            loop {
+                if let Err(error) = listener.listen_for(&app_token).await {
+                    error!(?error, "socket mode listen failed");
+                    return;
+                }
+                if !serve_socket_mode(&listener, &health).await {
+                    break;
+                }
             }
-
-            listener.serve().await;
             info!("socket mode listener exited");
         })
     }
@@ -336,19 +508,98 @@ impl SlackService {
         &self,
         channel: SlackChannelId,
         limit: u16,
-    ) -> Result<(Vec<SlackHistoryMessage>, bool)> {
+    ) -> Result<HistoryPage> {
+        self.fetch_history_page(channel, limit, None, None).await
+    }
+
+    /// Fetch up to `limit` channel messages older than `before`, newest
+    /// first, bypassing the history cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Slack` if the Slack API call fails.
+    pub async fn fetch_history_before(
+        &self,
+        channel: SlackChannelId,
+        limit: u16,
+        before: SlackTs,
+    ) -> Result<HistoryPage> {
+        self.fetch_history_page(channel, limit, None, Some(before))
+            .await
+    }
+
+    /// Fetch up to `limit` replies in the thread rooted at `thread_ts`,
+    /// oldest first, without the root message.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Slack` if the Slack API call fails.
+    pub async fn fetch_thread_replies(
+        &self,
+        channel: SlackChannelId,
+        thread_ts: SlackTs,
+        limit: u16,
+    ) -> Result<HistoryPage> {
+        let request = SlackApiConversationsRepliesRequest {
+            channel,
+            ts: thread_ts.clone(),
+            cursor: None,
+            latest: None,
+            // The root message counts against the limit.
+            limit: Some(limit.saturating_add(1)),
+            oldest: None,
+            inclusive: None,
+        };
+
+        self.metered(self.http_session().conversations_replies(&request))
+            .await
+            .map(|response| {
+                let has_more = response.has_more.unwrap_or(false);
+                let replies = response
+                    .messages
+                    .into_iter()
+                    .filter(|m| m.origin.ts != thread_ts)
+                    .collect();
+                (replies, has_more)
+            })
+            .map_err(|err| AppError::Slack(format!("failed to read thread replies: {err}")))
+    }
+
+    /// Recent channel history through the per-channel [`HistoryCache`],
+    /// for callers that read the same channel repeatedly.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Slack` if a Slack API call is needed and fails.
+    pub async fn cached_history(&self, channel: SlackChannelId, limit: u16) -> Result<HistoryPage> {
+        let key = channel.0.clone();
+        self.history_cache
+            .read(&key, limit, |oldest, limit| {
+                self.fetch_history_page(channel, limit, oldest, None)
+            })
+            .await
+    }
+
+    /// Fetch one page of history, newest first, optionally only messages
+    /// newer than `oldest` or older than `latest`.
+    async fn fetch_history_page(
+        &self,
+        channel: SlackChannelId,
+        limit: u16,
+        oldest: Option<SlackTs>,
+        latest: Option<SlackTs>,
+    ) -> Result<HistoryPage> {
         let request = SlackApiConversationsHistoryRequest {
             channel: Some(channel),
             cursor: None,
-            latest: None,
+            latest,
             limit: Some(limit),
-            oldest: None,
+            oldest,
             inclusive: None,
             include_all_metadata: None,
         };
 
-        self.http_session()
-            .conversations_history(&request)
+        self.metered(self.http_session().conversations_history(&request))
             .await
             .map(|response| {
                 let has_more = response.has_more.unwrap_or(false);
@@ -357,6 +608,38 @@ impl SlackService {
             .map_err(|err| AppError::Slack(format!("failed to read history: {err}")))
     }
 
+    /// Check the bot token with `auth.test`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Slack` if the call fails or the token is refused.
+    pub async fn auth_test(&self) -> Result<()> {
+        self.metered(self.http_session().auth_test())
+            .await
+            .map(|_| ())
+            .map_err(|err| AppError::Slack(format!("auth.test failed: {err}")))
+    }
+
+    /// Create a public channel named `name` and return its ID.
+    ///
+    /// Requires the `channels:manage` bot scope.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Slack` if the Slack API call fails (for example,
+    /// when the name is taken).
+    pub async fn create_channel(&self, name: &str) -> Result<SlackChannelId> {
+        let request = SlackApiConversationsCreateRequest {
+            name: name.to_owned(),
+            is_private: Some(false),
+            user_ds: None,
+        };
+        self.metered(self.http_session().conversations_create(&request))
+            .await
+            .map(|response| response.channel.id)
+            .map_err(|err| AppError::Slack(format!("failed to create channel {name}: {err}")))
+    }
+
     /// Update an existing Slack message (e.g., replace buttons with static text).
     ///
     /// # Errors
@@ -368,23 +651,38 @@ impl SlackService {
         ts: SlackTs,
         blocks: Vec<SlackBlock>,
     ) -> Result<()> {
-        let request = SlackApiChatUpdateRequest::new(
+        let mut request = update_request(channel, ts, blocks, self.render_mode);
+        let session = self.http_session();
+        let mut result = self.metered(session.chat_update(&request)).await;
+        if let Err(ref err) = result {
+            if let Some(content) = blocks_refused(err, &request.content) {
+                request.content = content;
+                result = self.metered(session.chat_update(&request)).await;
+            }
+        }
+        result.map_err(|err| AppError::Slack(format!("failed to update message: {err}")))?;
+        Ok(())
+    }
+
+    /// Post a message only `user` can see in `channel`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Slack` if the Slack API call fails.
+    pub async fn post_ephemeral(
+        &self,
+        channel: SlackChannelId,
+        user: SlackUserId,
+        text: String,
+    ) -> Result<()> {
+        let request = SlackApiChatPostEphemeralRequest::new(
             channel,
-            SlackMessageContent {
-                text: None,
-                blocks: Some(blocks),
-                attachments: None,
-                upload: None,
-                files: None,
-                reactions: None,
-                metadata: None,
-            },
-            ts,
+            user,
+            SlackMessageContent::new().with_text(text),
         );
-        self.http_session()
-            .chat_update(&request)
+        self.metered(self.http_session().chat_post_ephemeral(&request))
             .await
-            .map_err(|err| AppError::Slack(format!("failed to update message: {err}")))?;
+            .map_err(|err| AppError::Slack(format!("failed to post ephemeral message: {err}")))?;
         Ok(())
     }
 
@@ -414,8 +712,8 @@ impl SlackService {
         let mut url_request =
             SlackApiFilesGetUploadUrlExternalRequest::new(filename.into(), content.len());
         url_request.snippet_type = snippet_type.map(|s| SlackFileSnippetType(s.into()));
-        let url_response = session
-            .get_upload_url_external(&url_request)
+        let url_response = self
+            .metered(session.get_upload_url_external(&url_request))
             .await
             .map_err(|err| AppError::Slack(format!("failed to get upload url: {err}")))?;
 
@@ -440,8 +738,7 @@ impl SlackService {
         let mut complete_request = SlackApiFilesCompleteUploadExternalRequest::new(vec![file_ref]);
         complete_request.channel_id = Some(channel);
         complete_request.thread_ts = thread_ts;
-        session
-            .files_complete_upload_external(&complete_request)
+        self.metered(session.files_complete_upload_external(&complete_request))
             .await
             .map_err(|err| AppError::Slack(format!("failed to complete upload: {err}")))?;
 
@@ -455,8 +752,7 @@ impl SlackService {
     /// Returns `AppError::Slack` if the API call fails.
     pub async fn open_modal(&self, trigger_id: SlackTriggerId, view: SlackView) -> Result<()> {
         let request = SlackApiViewsOpenRequest::new(trigger_id, view);
-        self.http_session()
-            .views_open(&request)
+        self.metered(self.http_session().views_open(&request))
             .await
             .map_err(|err| AppError::Slack(format!("failed to open modal: {err}")))?;
         Ok(())
@@ -513,6 +809,180 @@ impl SlackService {
     }
 }
 
+/// Wait out any `Retry-After` in `budget`, run `call`, and count it.
+async fn metered<T>(
+    budget: &RateBudget,
+    call: impl Future<Output = std::result::Result<T, SlackClientError>>,
+) -> std::result::Result<T, SlackClientError> {
+    let wait = budget.retry_after(Instant::now());
+    if !wait.is_zero() {
+        sleep(wait).await;
+    }
+    let result = call.await;
+    budget.observe(&result, Instant::now());
+    result
+}
+
+/// Sleep until `deadline`, or forever when there is none.
+async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+        None => std::future::pending().await,
+    }
+}
+
+fn update_request(
+    channel: SlackChannelId,
+    ts: SlackTs,
+    blocks: Vec<SlackBlock>,
+    mode: SlackRenderMode,
+) -> SlackApiChatUpdateRequest {
+    SlackApiChatUpdateRequest::new(
+        channel,
+        blocks::message_content(None, Some(blocks), mode),
+        ts,
+    )
+}
+
+/// `content` rendered as text when `error` is Slack refusing its blocks,
+/// for one retry without them.
+fn blocks_refused(
+    error: &SlackClientError,
+    content: &SlackMessageContent,
+) -> Option<SlackMessageContent> {
+    let SlackClientError::ApiError(api) = error else {
+        return None;
+    };
+    if !api.code.starts_with("invalid_blocks") || content.blocks.is_none() {
+        return None;
+    }
+    warn!(code = %api.code, "slack refused message blocks; resending as text");
+    Some(blocks::message_content(
+        content.text.clone(),
+        content.blocks.clone(),
+        SlackRenderMode::PlainText,
+    ))
+}
+
+/// Post a queued message, retrying failures with backoff. A `429` blocks
+/// the shared budget, so the retry waits out its `Retry-After`.
+async fn post(
+    session: &SlackClientSession<'_, SlackClientHyperHttpsConnector>,
+    budget: &RateBudget,
+    mode: SlackRenderMode,
+    message: SlackMessage,
+) {
+    /// Maximum consecutive retries before dropping a message.
+    const MAX_RETRIES: u32 = 5;
+
+    #[cfg(feature = "chaos")]
+    if crate::chaos::faults().drop_post() {
+        return;
+    }
+    let mut request = message.into_request(mode);
+    let mut backoff = INITIAL_RETRY_DELAY;
+    let mut attempt = 0u32;
+    loop {
+        match metered(budget, session.chat_post_message(&request)).await {
+            Ok(_) => {
+                info!("sent slack message");
+                break;
+            }
+            Err(error) => {
+                attempt += 1;
+                if let Some(content) = blocks_refused(&error, &request.content) {
+                    request.content = content;
+                    continue;
+                }
+                if attempt >= MAX_RETRIES {
+                    error!(?error, attempt, "dropping slack message after max retries");
+                    break;
+                }
+                let delay = match &error {
+                    SlackClientError::RateLimitError(_) => budget.retry_after(Instant::now()),
+                    _ => backoff,
+                };
+                warn!(?error, delay=?delay, attempt, "slack post failed; retrying");
+                sleep(delay).await;
+                backoff = (backoff * 2).min(MAX_RETRY_DELAY);
+            }
+        }
+    }
+}
+
+/// Send one low-priority update.
+async fn send_deferred(
+    session: &SlackClientSession<'_, SlackClientHyperHttpsConnector>,
+    budget: &RateBudget,
+    mode: SlackRenderMode,
+    update: Deferred,
+) {
+    match update {
+        Deferred::Post(message) => post(session, budget, mode, message).await,
+        Deferred::Edit {
+            channel,
+            ts,
+            blocks,
+        } => {
+            let request = update_request(channel, ts, blocks, mode);
+            if let Err(err) = metered(budget, session.chat_update(&request)).await {
+                warn!(%err, "failed to apply low-priority slack update");
+            }
+        }
+    }
+}
+
+/// Send every held low-priority update, merged per thread.
+async fn flush_deferred(
+    session: &SlackClientSession<'_, SlackClientHyperHttpsConnector>,
+    budget: &RateBudget,
+    mode: SlackRenderMode,
+    deferred: &mut DeferredUpdates,
+) {
+    if deferred.is_empty() {
+        return;
+    }
+    let held = deferred.len();
+    let updates = deferred.drain();
+    info!(held, sent = updates.len(), "flushing batched slack updates");
+    for update in updates {
+        send_deferred(session, budget, mode, update).await;
+    }
+}
+
+/// Serve Socket Mode until shutdown. Returns `true` when a forced
+/// reconnect (or a chaos fault) closed the connections and the listener
+/// should be registered again.
+async fn serve_socket_mode(
+    listener: &SlackClientSocketModeListener<SlackClientHyperHttpsConnector>,
+    health: &SocketHealth,
+) -> bool {
+    let restart = tokio::select! {
+        _ = listener.serve() => false,
+        () = health.reconnect_requested() => true,
+        () = socket_killed() => true,
+    };
+    if restart {
+        // Drop every connection; the next registration's hello runs the
+        // reconnect path.
+        listener.shutdown().await;
+    }
+    health.record_stopped();
+    restart
+}
+
+/// Resolve when a chaos fault kills the Socket Mode connection.
+#[cfg(feature = "chaos")]
+async fn socket_killed() {
+    crate::chaos::faults().socket_killed().await;
+}
+
+/// Never resolves: servers built without `chaos` inject no faults.
+#[cfg(not(feature = "chaos"))]
+async fn socket_killed() {
+    std::future::pending::<()>().await;
+}
+
 // ── Reconnection: re-post pending interactive messages (T095) ────────
 
 /// Re-post pending approvals and prompts after a Socket Mode reconnection.
@@ -520,11 +990,11 @@ impl SlackService {
 /// When the WebSocket drops and reconnects, any interactive messages that
 /// were in-flight may not be delivered. This function queries the DB for
 /// pending records and re-posts their interactive messages to Slack so
-/// the operator can still act on them.
+/// the operator can still act on them. Snoozed records are left out until
+/// their snooze ends.
 async fn repost_pending_messages(state: &AppState) {
     use crate::persistence::approval_repo::ApprovalRepo;
     use crate::persistence::prompt_repo::PromptRepo;
-    use crate::slack::blocks;
 
     let Some(ref slack) = state.slack else { return };
 
@@ -537,37 +1007,24 @@ async fn repost_pending_messages(state: &AppState) {
     }
     let channel = SlackChannelId(channel_str.clone());
 
+    let now = chrono::Utc::now();
+    let awake = |id: &str| state.snoozes.snoozed_until(id, now).is_none();
+
     // Re-post pending approval requests.
     let approval_repo = ApprovalRepo::new(Arc::clone(&state.db));
-    match approval_repo.list_pending().await {
+    match approval_repo.list_pending().await.map(|pending| {
+        pending
+            .into_iter()
+            .filter(|req| awake(&req.id))
+            .collect::<Vec<_>>()
+    }) {
         Ok(pending) if !pending.is_empty() => {
             info!(
                 count = pending.len(),
                 "re-posting pending approval requests after reconnect"
             );
             for req in pending {
-                let diff_preview = if req.diff_content.lines().count() < 20 {
-                    format!("```\n{}\n```", req.diff_content)
-                } else {
-                    format!("_(large diff, {} lines)_", req.diff_content.lines().count())
-                };
-                let text = format!(
-                    "\u{1f504} *Re-posted after reconnect*\n\
-                     *Approval:* {}\n\
-                     *File:* `{}`\n\
-                     *Risk:* {:?}\n\n{}",
-                    req.title, req.file_path, req.risk_level, diff_preview
-                );
-                let msg_blocks = vec![
-                    blocks::text_section(&text),
-                    blocks::approval_buttons(&req.id),
-                ];
-                let message = SlackMessage {
-                    channel: channel.clone(),
-                    text: Some(format!("[Re-posted] Approval: {}", req.title)),
-                    blocks: Some(msg_blocks),
-                    thread_ts: None,
-                };
+                let message = approval_repost(channel.clone(), &req, "Re-posted after reconnect");
                 if let Err(err) = slack.enqueue(message).await {
                     warn!(%err, request_id = %req.id, "failed to re-post approval");
                 }
@@ -581,28 +1038,24 @@ async fn repost_pending_messages(state: &AppState) {
 
     // Re-post pending continuation prompts.
     let prompt_repo = PromptRepo::new(Arc::clone(&state.db));
-    match prompt_repo.list_pending().await {
+    match prompt_repo.list_pending().await.map(|pending| {
+        pending
+            .into_iter()
+            .filter(|p| awake(&p.id))
+            .collect::<Vec<_>>()
+    }) {
         Ok(pending) if !pending.is_empty() => {
             info!(
                 count = pending.len(),
                 "re-posting pending prompts after reconnect"
             );
             for prompt in pending {
-                let text = format!(
-                    "\u{1f504} *Re-posted after reconnect*\n\
-                     *Prompt:* {:?}\n\n{}",
-                    prompt.prompt_type, prompt.prompt_text
+                let message = prompt_repost(
+                    channel.clone(),
+                    &prompt,
+                    state.config.prompts.quick_replies_for(prompt.prompt_type),
+                    "Re-posted after reconnect",
                 );
-                let msg_blocks = vec![
-                    blocks::text_section(&text),
-                    blocks::prompt_buttons(&prompt.id),
-                ];
-                let message = SlackMessage {
-                    channel: channel.clone(),
-                    text: Some(format!("[Re-posted] Prompt: {:?}", prompt.prompt_type)),
-                    blocks: Some(msg_blocks),
-                    thread_ts: None,
-                };
                 if let Err(err) = slack.enqueue(message).await {
                     warn!(%err, prompt_id = %prompt.id, "failed to re-post prompt");
                 }
@@ -615,6 +1068,61 @@ async fn repost_pending_messages(state: &AppState) {
     }
 }
 
+/// Interactive message re-posting a pending approval request, headed
+/// with `heading` (e.g. "Re-posted after reconnect").
+pub(crate) fn approval_repost(
+    channel: SlackChannelId,
+    req: &ApprovalRequest,
+    heading: &str,
+) -> SlackMessage {
+    let diff_preview = if req.diff_content.lines().count() < 20 {
+        format!("```\n{}\n```", req.diff_content)
+    } else {
+        format!("_(large diff, {} lines)_", req.diff_content.lines().count())
+    };
+    let text = format!(
+        "\u{1f504} *{heading}*\n\
+         *Approval:* {}\n\
+         *File:* `{}`\n\
+         *Risk:* {:?}\n\n{}",
+        req.title, req.file_path, req.risk_level, diff_preview
+    );
+    SlackMessage {
+        channel,
+        text: Some(format!("[Re-posted] Approval: {}", req.title)),
+        blocks: Some(vec![
+            blocks::text_section(&text),
+            blocks::approval_buttons(&req.id),
+        ]),
+        thread_ts: None,
+    }
+}
+
+/// Interactive message re-posting a pending prompt, headed with `heading`.
+pub(crate) fn prompt_repost(
+    channel: SlackChannelId,
+    prompt: &ContinuationPrompt,
+    quick_replies: &[String],
+    heading: &str,
+) -> SlackMessage {
+    let text = format!(
+        "\u{1f504} *{heading}*\n\
+         *Prompt:* {:?}\n\n{}",
+        prompt.prompt_type, prompt.prompt_text
+    );
+    let mut msg_blocks = vec![
+        blocks::text_section(&text),
+        blocks::prompt_buttons(&prompt.id),
+    ];
+    msg_blocks.extend(blocks::quick_reply_buttons(&prompt.id, quick_replies));
+    SlackMessage {
+        channel,
+        text: Some(format!("[Re-posted] Prompt: {:?}", prompt.prompt_type)),
+        blocks: Some(msg_blocks),
+        thread_ts: None,
+    }
+}
+
 // ── WebSocket disconnect/reconnect notifications (T137/T138, FR-042) ──────────
 
 /// Collect the Slack channel IDs of all currently-active sessions.
@@ -729,6 +1237,38 @@ pub async fn notify_ws_reconnect(
     }
 }
 
+/// Post a server-wide notice, such as a queue alarm or watchdog report, to
+/// `channel_id`, else the global channel, else every active session's
+/// channel.
+///
+/// Posts go straight to the Web API rather than through the outgoing
+/// queue, which may be the thing the notice reports on. Failures are
+/// logged at `WARN`.
+pub async fn post_server_notice(state: &AppState, channel_id: Option<&str>, text: &str) {
+    let Some(ref slack) = state.slack else {
+        return;
+    };
+    let configured = channel_id
+        .filter(|ch| !ch.is_empty())
+        .or_else(|| Some(state.config.slack.channel_id.as_str()).filter(|ch| !ch.is_empty()));
+    let channels = match configured {
+        Some(channel) => vec![SlackChannelId(channel.to_owned())],
+        None => match collect_active_session_channels(&state.db).await {
+            Ok(channels) => channels,
+            Err(err) => {
+                warn!(%err, "failed to collect channels for server notice");
+                return;
+            }
+        },
+    };
+    for channel in channels {
+        let msg = SlackMessage::plain(channel.clone(), text);
+        if let Err(err) = slack.post_message_direct(msg).await {
+            warn!(%err, channel = %channel.0, "failed to post server notice");
+        }
+    }
+}
+
 // ── Mode-aware routing helpers ───────────────────────────────────────
 
 /// Whether a message should be posted to Slack for the given mode.
@@ -7,12 +7,18 @@
 //! ACP-only commands (`session-start`, `session-stop`, `session-restart`)
 //! are gated behind `ServerMode::Acp` and rejected in MCP mode.
 //!
-//! Also provides remote file browsing (`list-files`, `show-file`).
+//! Also provides remote file browsing (`list-files`, `show-file`), the
+//! `decisions` audit view of recent approval outcomes, per-session
+//! notification mutes (`mute`, `unmute`), and the `logs` view of lines
+//! agents streamed with `stream_log`.
 
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
+use chrono::NaiveTime;
 use slack_morphism::prelude::{
     SlackChannelId, SlackClient, SlackClientEventsUserState, SlackClientHyperHttpsConnector,
     SlackCommandEvent, SlackCommandEventResponse, SlackMessageContent, SlackMessageResponseType,
@@ -22,22 +28,44 @@ use tracing::{debug, info, info_span, warn};
 
 use crate::acp::handshake;
 use crate::acp::spawner::SpawnConfig;
-use crate::audit::{AuditEntry, AuditEventType};
+use crate::audit::{self, AuditEntry, AuditEventType};
+use crate::config::Role;
 use crate::diff::path_safety::validate_path;
+use crate::driver::session_hooks::SessionHookEvent;
 use crate::driver::AgentDriver;
+use crate::integrations::{email, issues};
+use crate::mcp::{device_pairing, session_share};
 use crate::mode::ServerMode;
-use crate::models::session::truncate_session_title;
+use crate::models::approval::{ApprovalRequest, ApprovalStatus};
+use crate::models::session::{format_tags, parse_env_var, parse_tag, truncate_session_title};
 use crate::models::session::{ProtocolMode, Session, SessionMode, SessionStatus};
-use crate::orchestrator::{checkpoint_manager, session_manager, spawner};
+use crate::models::session_note::SessionNote;
+use crate::models::stall::{StallAlert, StallAlertStatus, StallResolution};
+use crate::orchestrator::runs::{self, RunSummary};
+use crate::orchestrator::session_logs::{DEFAULT_LOG_TAIL, LOG_BUFFER_CAPACITY};
+use crate::orchestrator::stall_stats::StallStats;
+use crate::orchestrator::{
+    checkpoint_manager, command_aliases, scheduled_apply, session_bulk, session_manager,
+    session_move, spawner, subtask, workspace_bootstrap, workspace_discovery, workspace_mappings,
+    workspace_snapshot,
+};
+use crate::persistence::approval_repo::ApprovalRepo;
 use crate::persistence::checkpoint_repo::CheckpointRepo;
 use crate::persistence::db::Database;
+use crate::persistence::device_repo::DeviceRepo;
 use crate::persistence::intercom_queue_repo::IntercomQueueRepo;
+use crate::persistence::run_repo::RunRepo;
+use crate::persistence::session_note_repo::SessionNoteRepo;
 use crate::persistence::session_repo::SessionRepo;
+use crate::persistence::snapshot_repo::SnapshotRepo;
+use crate::persistence::stall_repo::StallAlertRepo;
 use crate::slack::blocks;
 use crate::slack::client::SlackMessage;
+use crate::slack::handlers::parse_duration;
+use crate::slack::handlers::prefs as prefs_handler;
 use crate::slack::handlers::steer as steer_handler;
 use crate::slack::handlers::task as task_handler;
-use crate::state::AppState;
+use crate::state::{AppState, WaitResponse};
 
 /// Handle incoming `/acom` or `/arc` slash commands routed via Socket Mode.
 ///
@@ -82,16 +110,53 @@ pub async fn handle_command(
         "state lock acquired"
     );
 
+    // A redelivered envelope carries the same trigger ID; the first copy
+    // already ran, so running it again would repeat its side effects.
+    if let Some(ref app) = app_state {
+        let trigger = format!("trigger:{}", event.trigger_id);
+        if let Some(reason) = app
+            .replay_guard
+            .refuse(Some(&trigger), None, chrono::Utc::now())
+        {
+            warn!(user = %user_id, command = command_name, reason, "dropping replayed slash command");
+            return Ok(ephemeral_response("Already handled this command."));
+        }
+    }
+
     let response_text = if let Some(ref app) = app_state {
-        // Verify authorized user.
-        if let Err(err) = app.config.ensure_authorized(&user_id) {
-            warn!(%err, user = %user_id, "unauthorized slash command attempt");
-            "You are not authorized to use this command.".to_owned()
-        } else {
-            let channel = event.channel_id.to_string();
-            dispatch_command(command_name, &args, &user_id, &channel, app)
-                .await
-                .unwrap_or_else(|err| format!("Error: {err}"))
+        // Verify the user's role permits the command.
+        match app.config.role_of(&user_id) {
+            None => {
+                warn!(user = %user_id, "unauthorized slash command attempt");
+                "You are not authorized to use this command.".to_owned()
+            }
+            Some(role) if !command_permitted(role, command_name, &args) => {
+                warn!(user = %user_id, command = command_name, "observer attempted a command");
+                format!(
+                    "You have read-only access: `{command_name}` is not available to observers. \
+                     Use `/{} help` for what you can run.",
+                    slash_prefix(app.server_mode)
+                )
+            }
+            // A bare `prefs` opens the modal, which needs the trigger ID;
+            // the text summary stands in when the modal cannot be opened.
+            Some(_) if command_name == "prefs" && args.is_empty() => {
+                match prefs_handler::open_modal(&user_id, &event.trigger_id, app).await {
+                    Ok(()) => "Opening your notification preferences\u{2026}".to_owned(),
+                    Err(err) => {
+                        warn!(user = %user_id, %err, "failed to open preferences modal");
+                        prefs_handler::handle_command(&args, &user_id, app)
+                            .await
+                            .unwrap_or_else(|err| format!("Error: {err}"))
+                    }
+                }
+            }
+            Some(_) => {
+                let channel = event.channel_id.to_string();
+                dispatch_command(command_name, &args, &user_id, &channel, app)
+                    .await
+                    .unwrap_or_else(|err| format!("Error: {err}"))
+            }
         }
     } else {
         "Server state not available.".to_owned()
@@ -106,6 +171,76 @@ pub async fn handle_command(
     Ok(ephemeral_response(&response_text))
 }
 
+/// Command names handled by [`dispatch_command`]; `commands add` refuses
+/// them as aliases.
+pub const BUILTIN_COMMANDS: &[&str] = &[
+    "help",
+    "sessions",
+    "history",
+    "session-start",
+    "session-stop",
+    "session-restart",
+    "session-cleanup",
+    "session-pause",
+    "session-resume",
+    "session-clear",
+    "session-move",
+    "run-start",
+    "run-end",
+    "run-summary",
+    "mute",
+    "unmute",
+    "session-tag",
+    "note",
+    "logs",
+    "session-checkpoint",
+    "session-restore",
+    "session-checkpoints",
+    "workspace-snapshot",
+    "workspace-snapshots",
+    "workspace-restore",
+    "decisions",
+    "stalls",
+    "apply-at",
+    "list-files",
+    "show-file",
+    "steer",
+    "task",
+    "queue",
+    "standby-queue",
+    "workspace",
+    "commands",
+    "pair",
+    "share",
+    "prefs",
+];
+
+/// Whether `role` may run `command` with `args`.
+///
+/// Operators may run everything. Observers may only run commands that
+/// report state without changing it or revealing workspace files.
+#[must_use]
+pub fn command_permitted(role: Role, command: &str, args: &[&str]) -> bool {
+    match role {
+        Role::Operator => true,
+        Role::Observer => match command {
+            "help"
+            | "sessions"
+            | "history"
+            | "decisions"
+            | "stalls"
+            | "logs"
+            | "prefs"
+            | "run-summary"
+            | "session-checkpoints"
+            | "workspace-snapshots" => true,
+            "workspace" => matches!(args, ["list" | "pending"]),
+            "commands" => matches!(args, ["list"]),
+            _ => false,
+        },
+    }
+}
+
 /// Dispatch a parsed command to the correct handler.
 ///
 /// Routes `command` (the word after `/intercom`) to the appropriate sub-handler,
@@ -120,6 +255,7 @@ pub async fn handle_command(
 /// Returns `AppError` if the underlying sub-handler fails (e.g., database error,
 /// missing session, path validation failure). Mode-mismatch responses are returned
 /// as `Ok(String)` with an informational message rather than as errors.
+#[allow(clippy::too_many_lines)] // One match arm per command keeps the router readable.
 pub async fn dispatch_command(
     command: &str,
     args: &[&str],
@@ -135,16 +271,20 @@ pub async fn dispatch_command(
 
         "sessions" => handle_sessions(args, channel_id, db).await,
 
+        "history" => handle_history(args, state).await,
+
         // ACP-only session lifecycle commands.
         "session-start" if state.server_mode == ServerMode::Acp => {
-            let prompt = if args.is_empty() {
+            let (options, prompt_args) = parse_session_start_args(args)?;
+            if prompt_args.is_empty() {
                 return Err(crate::AppError::Config(
-                    "usage: session-start <prompt>".into(),
+                    "usage: session-start [--max-duration <duration>] [--issue <ref>] \
+                     [--tag key=value]... [--env KEY=VAL]... [--snapshot] <prompt>"
+                        .into(),
                 ));
-            } else {
-                args.join(" ")
-            };
-            handle_session_start(&prompt, user_id, channel_id, state).await
+            }
+            let prompt = prompt_args.join(" ");
+            handle_session_start(&prompt, options, user_id, channel_id, state).await
         }
 
         "session-stop" if state.server_mode == ServerMode::Acp => {
@@ -170,8 +310,9 @@ pub async fn dispatch_command(
         )),
 
         "session-pause" => {
-            let session_id = args.first().copied();
-            handle_session_pause(session_id, user_id, channel_id, state).await
+            let immediate = args.first() == Some(&"--now");
+            let session_id = args.get(usize::from(immediate)).copied();
+            handle_session_pause(session_id, immediate, user_id, channel_id, state).await
         }
 
         "session-resume" => {
@@ -184,9 +325,27 @@ pub async fn dispatch_command(
             handle_session_clear(session_id, user_id, channel_id, state).await
         }
 
+        "session-move" => handle_session_move(args, user_id, channel_id, state).await,
+
+        "run-start" => handle_run_start(args, user_id, channel_id, state).await,
+
+        "run-end" => handle_run_end(user_id, state).await,
+
+        "run-summary" => handle_run_summary(args, user_id, state).await,
+
+        "mute" => handle_mute(args, user_id, channel_id, state).await,
+
+        "unmute" => handle_unmute(args, user_id, channel_id, state).await,
+
+        "session-tag" => handle_session_tag(args, user_id, channel_id, state).await,
+
+        "note" => handle_note(args, user_id, channel_id, state).await,
+
+        "logs" => handle_logs(args, user_id, channel_id, state).await,
+
         "session-checkpoint" => {
             let (session_id, label) = parse_checkpoint_args(args);
-            handle_session_checkpoint(session_id, label, user_id, channel_id, db).await
+            handle_session_checkpoint(session_id, label, user_id, channel_id, state).await
         }
 
         "session-restore" => {
@@ -201,19 +360,47 @@ pub async fn dispatch_command(
             handle_session_checkpoints(session_id, user_id, channel_id, db).await
         }
 
+        "workspace-snapshot" => {
+            let label = (!args.is_empty()).then(|| args.join(" "));
+            handle_workspace_snapshot(label, user_id, channel_id, state).await
+        }
+
+        "workspace-snapshots" => handle_workspace_snapshots(user_id, channel_id, state).await,
+
+        "workspace-restore" => {
+            let snapshot_id = args.first().copied().ok_or_else(|| {
+                crate::AppError::Config("usage: workspace-restore <snapshot_id>".into())
+            })?;
+            handle_workspace_restore(snapshot_id, user_id, state).await
+        }
+
+        "decisions" => {
+            let (session_id, limit) = parse_history_args(command, args)?;
+            handle_decisions(session_id, limit, state).await
+        }
+
+        "stalls" => {
+            let (session_id, limit) = parse_history_args(command, args)?;
+            handle_stalls(session_id, limit, state).await
+        }
+
+        "apply-at" => handle_apply_at(args, user_id, state).await,
+
         "list-files" => handle_list_files(args, user_id, channel_id, state).await,
 
         "show-file" => handle_show_file(args, user_id, channel_id, state).await,
 
         "steer" => {
-            let text = if args.is_empty() {
+            let (options, words) = steer_handler::SteerOptions::from_args(args)?;
+            let text = if words.is_empty() {
                 return Err(crate::AppError::Config(
-                    "usage: steer <message text>".into(),
+                    "usage: steer [--now] [--ttl <duration>] <message text>".into(),
                 ));
             } else {
-                args.join(" ")
+                words.join(" ")
             };
-            steer_handler::store_from_slack(&text, Some(channel_id), None, state).await
+            steer_handler::store_from_slack_with(&text, options, Some(channel_id), None, state)
+                .await
         }
 
         "task" => {
@@ -228,9 +415,28 @@ pub async fn dispatch_command(
         "queue" if state.server_mode == ServerMode::Acp => handle_queue_command(args, state).await,
 
         "queue" => Ok(format!(
-            "`queue` is only available in ACP mode. Use `/{prefix} help` for commands."
+            "`queue` is only available in ACP mode. Use `/{prefix} standby-queue` to queue \
+             instructions for the agent's next standby."
+        )),
+
+        "standby-queue" if state.server_mode == ServerMode::Mcp => {
+            handle_instruction_queue(args, user_id, channel_id, state).await
+        }
+
+        "standby-queue" => Ok(format!(
+            "`standby-queue` is only available in MCP mode. Use `/{prefix} help` for commands."
         )),
 
+        "workspace" => handle_workspace(args, channel_id, state),
+
+        "commands" => handle_commands(args, user_id, state),
+
+        "pair" => handle_pair(args, user_id, state).await,
+
+        "share" => handle_share(args, state).await,
+
+        "prefs" => prefs_handler::handle_command(args, user_id, state).await,
+
         other => Ok(format!(
             "Unknown command: `{other}`. Use `/{prefix} help` for available commands."
         )),
@@ -240,7 +446,7 @@ pub async fn dispatch_command(
 // ── Slash prefix helper ──────────────────────────────────────────────
 
 /// Return the slash command prefix for the current server mode.
-fn slash_prefix(mode: ServerMode) -> &'static str {
+pub(crate) fn slash_prefix(mode: ServerMode) -> &'static str {
     match mode {
         ServerMode::Mcp => "acom",
         ServerMode::Acp => "arc",
@@ -261,35 +467,77 @@ fn handle_help(category: Option<&str>, mode: ServerMode) -> String {
     }
 }
 
+#[allow(clippy::too_many_lines)] // One help literal per category.
 fn format_full_help(prefix: &str, mode: ServerMode) -> String {
     let mut text = format!("*Available `/{prefix}` commands:*\n\n");
 
     text.push_str(
         "*Agent Steering*\n\
-         • `steer <message>` — Send a steering message to the agent (delivered on next ping)\n\
-         • `task <message>` — Queue a task for the agent (delivered on next session recovery)\n\n",
+         • `steer [--now] [--ttl <duration>] <message>` — Send a steering message to the agent (delivered on next ping)\n\
+         • `task <message>` — Queue a task for the agent (delivered on next session recovery)\n",
     );
+    if mode == ServerMode::Mcp {
+        text.push_str(
+            "• `standby-queue <instruction>` — Queue an instruction for the agent's next standby\n\
+             • `standby-queue list` — List queued standby instructions\n",
+        );
+    }
+    text.push('\n');
 
     text.push_str("*Session Management*\n");
     if mode == ServerMode::Acp {
         text.push_str(
-            "• `session-start <prompt>` — Start a new agent session\n\
+            "• `session-start [--max-duration <duration>] [--issue <ref>] [--tag key=value]... \
+             [--env KEY=VAL]... [--snapshot] <prompt>` — Start a new agent session\n\
              • `session-stop [session_id]` — Gracefully stop a running session\n\
              • `session-restart [session_id]` — Restart a session with its original prompt\n",
         );
     }
     text.push_str(
-        "• `session-pause [session_id]` — Pause a running session\n\
+        "• `session-pause [--now] [session_id]` — Pause after the agent's current step\n\
          • `session-resume [session_id]` — Resume a paused session\n\
          • `session-clear [session_id]` — Force-terminate and clean up a session\n\
-         • `sessions` — List all tracked sessions\n\n",
+         • `session-move [session_id] <#channel>` — Post the session's messages to another \
+         channel from now on\n\
+         • `sessions [--all] [--tag key=value]...` — List all tracked sessions\n\
+         • `history [--status S] [--tag key=value]... [--limit N] [--page P]` — Browse past \
+         sessions, newest first\n\
+         • `session-tag <session_id> <key=value|key=>...` — Add, change or remove session tags\n\
+         • `note <session_id> <text>` — Leave a timestamped note on a session for whoever \
+         resumes or reviews it\n\
+         • `mute <session_id> [duration]` — Hold back a session's status updates, broadcasts \
+         and heartbeat notices\n\
+         • `unmute <session_id>` — Post them again\n\
+         • `run-start <name>` / `run-end` — Group the sessions you start under a named run\n\
+         • `run-summary [run]` — Totals across a run's sessions\n\n",
     );
 
     text.push_str(
         "*Checkpoints*\n\
          • `session-checkpoint [session_id] [label]` — Create a checkpoint\n\
          • `session-restore <checkpoint_id>` — Restore a checkpoint\n\
-         • `session-checkpoints [session_id]` — List checkpoints\n\n",
+         • `session-checkpoints [session_id]` — List checkpoints\n\
+         • `workspace-snapshot [label]` — Archive the whole workspace so it can be put back\n\
+         • `workspace-snapshots` — List this workspace's snapshots\n\
+         • `workspace-restore <snapshot_id>` — Put the workspace back as a snapshot recorded \
+         it\n\n",
+    );
+
+    text.push_str(
+        "*Approvals*\n\
+         • `apply-at <request_id> <HH:MM>` — Approve a pending change and have the server apply \
+         it at the next HH:MM (server time), then run the `[scheduled_apply]` verification \
+         commands\n\n",
+    );
+
+    text.push_str(
+        "*Audit*\n\
+         • `decisions [session_id] [--limit N]` — Recent approval decisions with who decided, \
+         latency, and links to the requests\n\
+         • `stalls [session_id] [--limit N]` — Recent stalls, how they ended, and the \
+         false-positive rate\n\
+         • `logs <session_id> [-n N]` — Newest log lines the agent streamed with `stream_log` \
+         (default 200)\n\n",
     );
 
     text.push_str(
@@ -309,6 +557,43 @@ fn format_full_help(prefix: &str, mode: ServerMode) -> String {
         );
     }
 
+    text.push_str(
+        "*Workspaces*\n\
+         • `workspace list` — Show the workspace-to-channel mappings\n\
+         • `workspace add <workspace_id> <channel_id> [label]` — Map a workspace and save it to \
+         config.toml\n\
+         • `workspace remove <workspace_id>` — Remove a mapping from the live table and \
+         config.toml\n\
+         • `workspace pending` — Unmapped workspaces agents have connected with\n\
+         • `workspace approve <workspace_id> [channel_id]` — Map a pending workspace (to its \
+         created channel or this one) and save it to config.toml\n\
+         • `workspace reject <workspace_id>` — Ignore a pending workspace until restart\n\n",
+    );
+
+    text.push_str(
+        "*Command aliases*\n\
+         • `commands list` — Show the `[commands]` aliases and the shell commands they run\n\
+         • `commands add <alias> <command>` — Register an alias and save it to config.toml\n\n",
+    );
+
+    text.push_str(
+        "*Mobile approver*\n\
+         • `pair` — Show a QR code that pairs your phone's browser with the approval dashboard\n\
+         • `pair list` — List your paired browsers\n\
+         • `pair revoke <device_id|all>` — Sign out one or all paired browsers\n\n\
+         *Notifications*\n\
+         • `prefs` — Choose your detail level, delivery (thread, DM or both) and quiet hours \
+         for the sessions you own\n\
+         • `prefs show | detail <level|default> | delivery <channel|dm|both> | \
+         quiet <HH:MM-HH:MM|off> | reset` — The same without the modal\n\n",
+    );
+
+    text.push_str(
+        "*Sharing*\n\
+         • `share <session_id> [--ttl <duration>]` — Read-only browser link to a session's \
+         progress and decisions, for people who should watch but not approve\n\n",
+    );
+
     text.push_str(
         "*General*\n\
          • `help [category]` — Show this help (categories: session, checkpoint, files, steering)",
@@ -321,17 +606,42 @@ fn format_session_help(prefix: &str, mode: ServerMode) -> String {
     let mut text = String::from("*Session commands:*\n");
     if mode == ServerMode::Acp {
         text.push_str(
-            "• `session-start <prompt>` — Start a new agent session with the given prompt\n\
+            "• `session-start [--max-duration <duration>] [--issue <ref>] [--tag key=value]... \
+             [--env KEY=VAL]... [--snapshot] <prompt>` — Start a new agent session with the given prompt. With `--max-duration 2h` \
+             the agent is told to wrap up shortly before the budget runs out and interrupted when \
+             it does. With `--issue PROJ-123` (or `owner/repo#42`, or an issue URL) the issue is \
+             shown on the session and a completion comment is posted to it when the session ends. \
+             Each `--tag team=payments` labels the session for filtering and usage attribution. \
+             Each `--env NODE_ENV=staging` sets a variable on the agent process; only names \
+             allowed by `[session_env]` are accepted. `--snapshot` archives the workspace first (see `workspace-snapshot`)\n\
              • `session-stop [session_id]` — Gracefully stop a running session (sends interrupt \
              first)\n\
              • `session-restart [session_id]` — Restart a session with its original prompt\n",
         );
     }
     text.push_str(
-        "• `session-pause [session_id]` — Pause a running session (defaults to active session)\n\
+        "• `session-pause [--now] [session_id]` — Pause a running session (defaults to active session). \
+         The agent is told to stop at its next blocking tool call; `--now` only marks the session paused\n\
          • `session-resume [session_id]` — Resume a paused session\n\
          • `session-clear [session_id]` — Force-terminate and clean up a session\n\
-         • `sessions` — List all tracked sessions with state and timestamps",
+         • `session-move [session_id] <#channel>` — Re-route the session to another channel: a \
+         new thread is started there and the old thread gets a pointer to it\n\
+         • `sessions [--all] [--tag key=value]...` — List all tracked sessions with state and \
+         timestamps; each `--tag` keeps only sessions carrying that tag\n\
+         • `history [--status S] [--tag key=value]... [--limit N] [--page P]` — Page through \
+         sessions, newest first. Lists ended (terminated or interrupted) sessions unless \
+         `--status` names another status or `all`; 10 per page by default\n\
+         • `session-tag <session_id> <key=value|key=>...` — Set tags on a session; `key=` \
+         removes the tag\n\
+         • `mute <session_id> [duration]` — Hold back the session's status updates, broadcasts \
+         and heartbeat notices for `duration` (e.g. `2h`) or until `unmute`. Approvals and \
+         prompts are still posted\n\
+         • `unmute <session_id>` — Post the session's non-critical messages again\n\
+         • `run-start <name>` — Group the sessions you start from now on, and their subtasks \
+         and restarts, under a named run\n\
+         • `run-end` — End your active run and summarise it\n\
+         • `run-summary [run]` — Sessions, approvals, files changed and stalls across a run \
+         (by ID prefix or name; defaults to your active or latest run)",
     );
     let _ = prefix; // used by callers for consistency; format kept static
     text
@@ -350,7 +660,13 @@ pub fn format_checkpoint_help(prefix: &str) -> String {
      hashes. When `session_id` is omitted, the most-recent active session in this channel is \
      used. If no session is active, the command fails with a descriptive error.\n\
      • `session-restore <checkpoint_id>` — Restore a checkpoint (warns of diverged files)\n\
-     • `session-checkpoints [session_id]` — List all checkpoints for a session"
+     • `session-checkpoints [session_id]` — List all checkpoints for a session\n\
+     • `workspace-snapshot [label]` — Archive every workspace file, except `[snapshots] \
+     exclude`, into `.intercom/snapshots/`. `session-start --snapshot` takes one before the \
+     agent starts.\n\
+     • `workspace-snapshots` — List the snapshots kept for this channel's workspace\n\
+     • `workspace-restore <snapshot_id>` — Restore files that changed or were deleted and delete \
+     files added since the snapshot. The current state is snapshotted first."
         .to_owned()
 }
 
@@ -367,10 +683,17 @@ fn format_steering_help(prefix: &str) -> String {
     "*Agent steering commands:*\n\
      • `steer <message>` — Send a steering message to the active agent session. The message is \
      queued and delivered on the agent's next `ping` call. Use this to redirect focus or provide \
-     guidance without interrupting the current operation.\n\
+     guidance without interrupting the current operation. `--now` interrupts the agent's current \
+     turn and delivers immediately (queued first if the agent is unreachable); `--ttl 10m` drops \
+     the message, with a notice, if it is still undelivered after that long.\n\
      • `task <message>` — Queue a task item for the agent. Tasks are delivered in bulk on the \
      agent's next session recovery (`reboot` call), making them ideal for asynchronous to-do \
-     items that the agent should pick up at the start of its next session."
+     items that the agent should pick up at the start of its next session.\n\
+     • `standby-queue <instruction>` — (MCP mode) Queue an instruction for the agent's next \
+     `standby`. Queue several in a row: the wait resumes with the first and the rest are \
+     delivered as steering, in order. If the agent is already in standby the instruction \
+     resumes it directly. `standby-queue list` shows what is queued. (`queue` is the ACP \
+     numbered file queue.)"
         .to_owned()
 }
 
@@ -568,6 +891,82 @@ fn transfer_to_backlogit(text: &str, workspace_root: Option<&Path>) -> crate::Re
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
 }
 
+// ── Standby instruction queue (MCP mode) ─────────────────────────────
+
+/// Handle `standby-queue` (MCP mode): queue an instruction for the agent's
+/// next `standby`, or list the queue with `standby-queue list`.
+///
+/// An instruction queued while the agent is already in standby (and the
+/// session is not paused) resolves the wait directly.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` without arguments, `AppError::NotFound` when
+/// the channel has no session of the user's, or `AppError::Unauthorized`
+/// when the user does not own it.
+async fn handle_instruction_queue(
+    args: &[&str],
+    user_id: &str,
+    channel_id: &str,
+    state: &Arc<AppState>,
+) -> crate::Result<String> {
+    if args.is_empty() {
+        return Err(crate::AppError::Config(
+            "usage: standby-queue <instruction> | standby-queue list".into(),
+        ));
+    }
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let session = resolve_command_session(None, user_id, channel_id, &repo).await?;
+    spawner::verify_session_owner(&session, user_id)?;
+
+    if args == ["list"] {
+        let queued = state.instruction_queue.list(&session.id);
+        if queued.is_empty() {
+            return Ok(format!(
+                "No instructions queued for session `{}`.",
+                session.id
+            ));
+        }
+        let mut reply = format!(
+            "*Queued for session `{}`* \u{2014} the next standby resumes with 1, the rest \
+             follow as steering:\n",
+            session.id
+        );
+        for (index, instruction) in queued.iter().enumerate() {
+            let _ = writeln!(reply, "{}. {instruction}", index + 1);
+        }
+        return Ok(reply.trim_end().to_owned());
+    }
+
+    let instruction = args.join(" ");
+    // Earlier queued instructions go first; a paused agent waits for resume.
+    if session.status != SessionStatus::Paused
+        && state.instruction_queue.list(&session.id).is_empty()
+    {
+        match state
+            .mcp_driver()
+            .resolve_wait(&session.id, Some(instruction.clone()))
+            .await
+        {
+            Ok(()) => {
+                info!(session_id = %session.id, "standby resolved with operator instruction");
+                return Ok(format!(
+                    "Session `{}` was in standby; instruction delivered.",
+                    session.id
+                ));
+            }
+            Err(crate::AppError::NotFound(_)) => {}
+            Err(err) => return Err(err),
+        }
+    }
+    let position = state.instruction_queue.push(&session.id, instruction);
+    Ok(format!(
+        "Queued instruction {position} for session `{}`. It is delivered when the agent next \
+         enters standby.",
+        session.id
+    ))
+}
+
 // ── Session commands (T067, T072) ────────────────────────────────────
 
 /// Resolve the session a slash command should operate on.
@@ -622,21 +1021,43 @@ async fn handle_sessions(
     db: &Arc<Database>,
 ) -> crate::Result<String> {
     let repo = SessionRepo::new(Arc::clone(db));
-    let all_flag = args.contains(&"--all");
+    let mut all_flag = false;
+    let mut tags = Vec::new();
+    let mut rest = args;
+    loop {
+        match rest {
+            ["--all", tail @ ..] => {
+                all_flag = true;
+                rest = tail;
+            }
+            ["--tag", value, tail @ ..] => {
+                tags.push(parse_tag(value)?);
+                rest = tail;
+            }
+            [] => break,
+            _ => {
+                return Err(crate::AppError::Config(
+                    "usage: sessions [--all] [--tag key=value]...".into(),
+                ))
+            }
+        }
+    }
 
-    let sessions = if all_flag {
+    let mut sessions = if all_flag {
         // HITL-002 / FR-048: --all shows every session in this channel.
         repo.list_all_by_channel(channel_id).await?
     } else {
         // HITL-008 / FR-051: default listing shows active and paused sessions.
         repo.list_active_or_paused().await?
     };
+    sessions.retain(|session| session.has_tags(&tags));
 
     if sessions.is_empty() {
-        return Ok(if all_flag {
-            "No sessions found in this channel.".to_owned()
-        } else {
-            "No active sessions.".to_owned()
+        return Ok(match (all_flag, tags.is_empty()) {
+            (true, true) => "No sessions found in this channel.".to_owned(),
+            (false, true) => "No active sessions.".to_owned(),
+            (true, false) => "No sessions with those tags in this channel.".to_owned(),
+            (false, false) => "No active sessions with those tags.".to_owned(),
         });
     }
 
@@ -647,44 +1068,313 @@ async fn handle_sessions(
     };
     let mut lines = vec![header.to_owned()];
 
+    lines.extend(sessions.iter().map(format_session_line));
+
+    Ok(lines.join("\n"))
+}
+
+/// Render one `sessions` / `history` line for `session`.
+fn format_session_line(session: &Session) -> String {
+    let short_id = &session.short_id;
+    let protocol = match session.protocol_mode {
+        ProtocolMode::Acp => "ACP",
+        ProtocolMode::Mcp => "MCP",
+    };
+    // T165: status icons — 🟢 Active, ⏸ Paused, 🔴 Terminated, 💀 Interrupted.
+    let icon = match session.status {
+        SessionStatus::Active => "\u{1f7e2}",
+        SessionStatus::Paused => "\u{23f8}",
+        SessionStatus::Terminated => "\u{1f534}",
+        SessionStatus::Interrupted => "\u{1f480}",
+        SessionStatus::Created => "\u{23f3}",
+    };
+    let client = session
+        .client_name
+        .as_deref()
+        .map(|name| match session.client_version.as_deref() {
+            Some(version) if !version.is_empty() => format!(" via {name} {version}"),
+            _ => format!(" via {name}"),
+        })
+        .unwrap_or_default();
+    // T159/FR-049: show title when available.
+    let title_suffix = session
+        .title
+        .as_deref()
+        .map(|t| format!(" | _{t}_"))
+        .unwrap_or_default();
+    let issue_suffix = session
+        .issue_ref
+        .as_deref()
+        .map(|i| format!(" | \u{1f3ab} {}", issues::issue_label(i)))
+        .unwrap_or_default();
+    let tag_suffix = if session.tags.is_empty() {
+        String::new()
+    } else {
+        format!(" | \u{1f3f7} {}", format_tags(&session.tags))
+    };
+    let mute_suffix = if session.is_muted(chrono::Utc::now()) {
+        " | \u{1f507} muted"
+    } else {
+        ""
+    };
+    // Connectivity only means something while the session can still run.
+    let connectivity = if matches!(
+        session.status,
+        SessionStatus::Terminated | SessionStatus::Interrupted
+    ) {
+        String::new()
+    } else {
+        format!(
+            " | {} {}",
+            session.connectivity_status.indicator(),
+            session.connectivity_status.as_str()
+        )
+    };
+    format!(
+        "{icon} `{short_id}` — {protocol}{client}{connectivity} | owner: `{}`{title_suffix}{issue_suffix}\
+         {tag_suffix}{mute_suffix}",
+        session.owner_user_id
+    )
+}
+
+/// Filters and page of a `history` listing, parsed by
+/// [`parse_session_history_args`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionHistoryQuery {
+    /// Statuses to list; empty lists every session.
+    pub statuses: Vec<SessionStatus>,
+    /// Tags a listed session must all carry (`--tag key=value`).
+    pub tags: Vec<(String, String)>,
+    /// Sessions per page (`--limit`).
+    pub limit: u32,
+    /// 1-based page number (`--page`).
+    pub page: u32,
+}
+
+/// Parse `history [--status S] [--tag key=value]... [--limit N] [--page P]`
+/// arguments.
+///
+/// `--status` takes a session status, `ended` (terminated or interrupted,
+/// the default) or `all`. `--tag` may be repeated.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` if a flag has no value or an unknown value,
+/// a tag is not `key=value`,
+/// `--limit` is not a number from 1 to 50, or `--page` is not a positive
+/// number.
+pub fn parse_session_history_args(args: &[&str]) -> crate::Result<SessionHistoryQuery> {
+    let usage = || {
+        crate::AppError::Config(
+            "usage: history [--status ended|all|terminated|interrupted|active|paused|created] \
+             [--tag key=value]... [--limit N] [--page P]"
+                .into(),
+        )
+    };
+    let mut query = SessionHistoryQuery {
+        statuses: vec![SessionStatus::Terminated, SessionStatus::Interrupted],
+        tags: Vec::new(),
+        limit: DEFAULT_HISTORY_LIMIT,
+        page: 1,
+    };
+    let mut rest = args;
+    while let Some((flag, tail)) = rest.split_first() {
+        let (value, tail) = tail.split_first().ok_or_else(usage)?;
+        match *flag {
+            "--status" => {
+                query.statuses = match *value {
+                    "all" => Vec::new(),
+                    "ended" => vec![SessionStatus::Terminated, SessionStatus::Interrupted],
+                    "terminated" => vec![SessionStatus::Terminated],
+                    "interrupted" => vec![SessionStatus::Interrupted],
+                    "active" => vec![SessionStatus::Active],
+                    "paused" => vec![SessionStatus::Paused],
+                    "created" => vec![SessionStatus::Created],
+                    _ => return Err(usage()),
+                };
+            }
+            "--tag" => query.tags.push(parse_tag(value)?),
+            "--limit" => {
+                query.limit = value
+                    .parse()
+                    .ok()
+                    .filter(|n| (1..=MAX_HISTORY_LIMIT).contains(n))
+                    .ok_or_else(usage)?;
+            }
+            "--page" => {
+                query.page = value.parse().ok().filter(|n| *n >= 1).ok_or_else(usage)?;
+            }
+            _ => return Err(usage()),
+        }
+        rest = tail;
+    }
+    Ok(query)
+}
+
+/// Handle `history`: one page of past (or any) sessions, newest first, with
+/// when each ended or last changed.
+async fn handle_history(args: &[&str], state: &Arc<AppState>) -> crate::Result<String> {
+    let query = parse_session_history_args(args)?;
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+
+    let total =
+        u64::try_from(repo.count_by_status(&query.statuses, &query.tags).await?).unwrap_or(0);
+    let mut scope = match query.statuses.as_slice() {
+        [] => "sessions".to_owned(),
+        [SessionStatus::Terminated, SessionStatus::Interrupted] => "ended sessions".to_owned(),
+        [status] => format!("{} sessions", status.as_str()),
+        _ => "matching sessions".to_owned(),
+    };
+    if !query.tags.is_empty() {
+        let tags: Vec<String> = query.tags.iter().map(|(k, v)| format!("{k}={v}")).collect();
+        let _ = write!(scope, " tagged {}", tags.join(" "));
+    }
+    if total == 0 {
+        return Ok(format!("No {scope}."));
+    }
+
+    let pages = total.div_ceil(u64::from(query.limit));
+    if u64::from(query.page) > pages {
+        return Ok(format!(
+            "Page {} is past the end: {total} {scope} fill {pages} page(s) of {}.",
+            query.page, query.limit
+        ));
+    }
+    let offset = u64::from(query.page - 1) * u64::from(query.limit);
+    let sessions = repo
+        .list_page(&query.statuses, &query.tags, query.limit, offset)
+        .await?;
+
+    let mut lines = vec![format!(
+        "*Session history* — {scope}, page {} of {pages} ({total} total):",
+        query.page
+    )];
     for session in &sessions {
-        let short_id: String = session.id.chars().take(8).collect();
-        let protocol = match session.protocol_mode {
-            ProtocolMode::Acp => "ACP",
-            ProtocolMode::Mcp => "MCP",
+        let (label, at) = match session.terminated_at {
+            Some(at) => ("ended", at),
+            None => ("updated", session.updated_at),
         };
-        // T165: status icons — 🟢 Active, ⏸ Paused, 🔴 Terminated, 💀 Interrupted.
-        let icon = match session.status {
-            crate::models::session::SessionStatus::Active => "\u{1f7e2}",
-            crate::models::session::SessionStatus::Paused => "\u{23f8}",
-            crate::models::session::SessionStatus::Terminated => "\u{1f534}",
-            crate::models::session::SessionStatus::Interrupted => "\u{1f480}",
-            crate::models::session::SessionStatus::Created => "\u{23f3}",
-        };
-        // T159/FR-049: show title when available.
-        let title_suffix = session
-            .title
-            .as_deref()
-            .map(|t| format!(" | _{t}_"))
-            .unwrap_or_default();
         lines.push(format!(
-            "{icon} `{short_id}…` — {protocol} | owner: `{}`{title_suffix}",
-            session.owner_user_id
+            "\u{2022} {label} {} {}",
+            blocks::slack_datetime(at),
+            format_session_line(session)
         ));
     }
+    if u64::from(query.page) < pages {
+        let mut next: Vec<String> = vec!["history".into()];
+        for (flag, value) in args.chunks(2).filter_map(|pair| match pair {
+            [flag, value] if *flag != "--page" => Some((flag, value)),
+            _ => None,
+        }) {
+            next.push(format!("{flag} {value}"));
+        }
+        next.push(format!("--page {}", query.page + 1));
+        let prefix = slash_prefix(state.server_mode);
+        lines.push(format!("Next page: `/{prefix} {}`", next.join(" ")));
+    }
 
     Ok(lines.join("\n"))
 }
 
+/// Leading `session-start` flags, parsed by [`parse_session_start_args`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionStartOptions {
+    /// Time box for the session (`--max-duration`).
+    pub max_duration: Option<chrono::Duration>,
+    /// Issue the session works on (`--issue`).
+    pub issue: Option<String>,
+    /// Labels for the session (`--tag key=value`, repeatable).
+    pub tags: BTreeMap<String, String>,
+    /// Variables for the agent process (`--env KEY=VAL`, repeatable).
+    pub env: BTreeMap<String, String>,
+    /// Snapshot the workspace before the agent starts (`--snapshot`).
+    pub snapshot: bool,
+}
+
+/// Split leading `--max-duration <duration>`, `--issue <ref>`,
+/// `--tag key=value`, `--env KEY=VAL` and `--snapshot` flags off
+/// `session-start` arguments, returning them and the remaining prompt words.
+///
+/// Whether an `--env` name is allowed is checked when the session starts.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` if a flag has no value or its value is not a
+/// valid duration, issue reference, tag or environment variable.
+pub fn parse_session_start_args<'a>(
+    args: &[&'a str],
+) -> crate::Result<(SessionStartOptions, Vec<&'a str>)> {
+    let mut options = SessionStartOptions::default();
+    let mut rest = args;
+    loop {
+        match rest {
+            ["--max-duration", value, tail @ ..] => {
+                options.max_duration = Some(parse_duration(value)?);
+                rest = tail;
+            }
+            ["--issue", value, tail @ ..] => {
+                issues::validate_issue_ref(value)?;
+                options.issue = Some((*value).to_owned());
+                rest = tail;
+            }
+            ["--tag", value, tail @ ..] => {
+                let (key, value) = parse_tag(value)?;
+                options.tags.insert(key, value);
+                rest = tail;
+            }
+            ["--env", value, tail @ ..] => {
+                let (name, value) = parse_env_var(value)?;
+                options.env.insert(name, value);
+                rest = tail;
+            }
+            ["--snapshot", tail @ ..] => {
+                options.snapshot = true;
+                rest = tail;
+            }
+            ["--max-duration"] => {
+                return Err(crate::AppError::Config(
+                    "usage: --max-duration <duration, e.g. 2h>".into(),
+                ))
+            }
+            ["--issue"] => {
+                return Err(crate::AppError::Config(
+                    "usage: --issue <PROJ-123 | owner/repo#42 | issue URL>".into(),
+                ))
+            }
+            ["--tag"] => {
+                return Err(crate::AppError::Config(
+                    "usage: --tag <key=value, e.g. team=payments>".into(),
+                ))
+            }
+            ["--env"] => {
+                return Err(crate::AppError::Config(
+                    "usage: --env <KEY=VAL, e.g. NODE_ENV=staging>".into(),
+                ))
+            }
+            _ => return Ok((options, rest.to_vec())),
+        }
+    }
+}
+
 async fn handle_session_start(
     prompt: &str,
+    options: SessionStartOptions,
     user_id: &str,
     channel_id: &str,
     state: &Arc<AppState>,
 ) -> crate::Result<String> {
     match state.server_mode {
-        ServerMode::Acp => handle_acp_session_start(prompt, user_id, channel_id, state).await,
-        ServerMode::Mcp => handle_mcp_session_start(prompt, user_id, state).await,
+        ServerMode::Acp => {
+            handle_acp_session_start(prompt, options, None, user_id, channel_id, state).await
+        }
+        ServerMode::Mcp if options != SessionStartOptions::default() => {
+            Err(crate::AppError::Config(
+                "--max-duration, --issue, --tag, --env and --snapshot are only supported for ACP \
+                 sessions"
+                    .into(),
+            ))
+        }
+        ServerMode::Mcp => handle_mcp_session_start(prompt, user_id, channel_id, state).await,
     }
 }
 
@@ -695,12 +1385,15 @@ async fn handle_session_start(
 #[allow(clippy::too_many_lines)]
 async fn handle_acp_session_start(
     prompt: &str,
+    options: SessionStartOptions,
+    restart_of: Option<&Session>,
     user_id: &str,
     channel_id: &str,
     state: &Arc<AppState>,
 ) -> crate::Result<String> {
     // Validate ACP configuration before attempting to spawn.
     state.config.validate_for_acp_mode()?;
+    state.config.session_env.check(&options.env)?;
 
     let repo = SessionRepo::new(Arc::clone(&state.db));
 
@@ -720,7 +1413,7 @@ async fn handle_acp_session_start(
     // T154: hold a read-lock on the hot-reload workspace_mappings for the
     // duration of channel resolution so a concurrent config reload cannot
     // produce an inconsistent (channel, workspace_root) pair.
-    let (workspace_root, workspace_name) = {
+    let (workspace_root, workspace_name, bootstrap) = {
         let mappings = state
             .workspace_mappings
             .read()
@@ -738,7 +1431,7 @@ async fn handle_acp_session_start(
             .or_else(|| root.file_name().and_then(|n| n.to_str()))
             .unwrap_or("workspace")
             .to_owned();
-        (root, name)
+        (root, name, mapping.and_then(|m| m.bootstrap.clone()))
     };
 
     // Build the session record with ACP-specific fields.
@@ -752,6 +1445,25 @@ async fn handle_acp_session_start(
     session.channel_id = Some(channel_id.to_owned());
     // T159 / FR-049: store truncated prompt as session title (max 80 chars).
     session.title = Some(truncate_session_title(prompt));
+    session.deadline = options
+        .max_duration
+        .map(|budget| session.created_at + budget);
+    session.max_duration_seconds = options.max_duration.map(|budget| budget.num_seconds());
+    session.issue_ref = options.issue;
+    session.tags = options.tags;
+    session.env = state.config.session_env.redacted(&options.env);
+    // A restart takes over its predecessor's thread, run and place in the
+    // session tree; other sessions join the operator's active run.
+    if let Some(old) = restart_of {
+        session.restart_of = Some(old.id.clone());
+        session.parent_session_id = old.parent_session_id.clone();
+        session.run_id = old.run_id.clone();
+        if old.channel_id.as_deref() == Some(channel_id) {
+            session.thread_ts = old.thread_ts.clone();
+        }
+    } else {
+        session.run_id = runs::active_run_id(&state.db, user_id).await?;
+    }
 
     let created = repo.create(&session).await?;
     let session_id = created.id.clone();
@@ -766,18 +1478,48 @@ async fn handle_acp_session_start(
     let bg_workspace_root = workspace_root;
     let bg_workspace_name = workspace_name.clone();
     let bg_session_id = session_id.clone();
+    let bg_short_id = created.short_id.clone();
+    let bg_snapshot_by = options.snapshot.then(|| user_id.to_owned());
+    let bg_env = options.env.clone();
+    let bootstrapping = bootstrap.is_some();
 
     tokio::spawn(async move {
-        if let Err(err) = finish_acp_session_start(
-            &bg_session_id,
-            &bg_prompt,
-            &bg_channel,
-            &bg_workspace_root,
-            &bg_workspace_name,
-            &bg_state,
-        )
-        .await
-        {
+        let started = async {
+            // A session started with --snapshot does not run without its
+            // safety net.
+            if let Some(ref created_by) = bg_snapshot_by {
+                workspace_snapshot::create(
+                    &bg_state,
+                    &bg_workspace_root,
+                    Some(format!("before session {bg_short_id}")),
+                    created_by,
+                    Some(bg_session_id.clone()),
+                )
+                .await?;
+            }
+            // Nor in a workspace its bootstrap commands could not prepare.
+            if let Some(ref bootstrap) = bootstrap {
+                workspace_bootstrap::run(
+                    bootstrap,
+                    &bg_workspace_root,
+                    &bg_env,
+                    &bg_session_id,
+                    &bg_state.session_logs,
+                )
+                .await?;
+            }
+            finish_acp_session_start(
+                &bg_session_id,
+                &bg_prompt,
+                &bg_channel,
+                &bg_workspace_root,
+                &bg_workspace_name,
+                bg_env,
+                &bg_state,
+            )
+            .await
+        };
+        if let Err(err) = started.await {
             warn!(
                 session_id = %bg_session_id,
                 %err,
@@ -801,8 +1543,7 @@ async fn handle_acp_session_start(
                 let msg = SlackMessage {
                     channel: SlackChannelId(bg_channel.clone()),
                     text: Some(format!(
-                        "\u{274c} ACP session `{}` failed to start: {err}",
-                        bg_session_id.chars().take(8).collect::<String>()
+                        "\u{274c} ACP session `{bg_short_id}` failed to start: {err}"
                     )),
                     blocks: None,
                     thread_ts: None,
@@ -812,9 +1553,24 @@ async fn handle_acp_session_start(
         }
     });
 
+    let time_box = created
+        .deadline
+        .map(|deadline| format!(" (time box ends {})", blocks::slack_time(deadline)))
+        .unwrap_or_default();
+    let issue = created
+        .issue_ref
+        .as_deref()
+        .map(|issue_ref| format!(" for {}", issues::issue_label(issue_ref)))
+        .unwrap_or_default();
+    let preparation = match (options.snapshot, bootstrapping) {
+        (true, true) => ", after snapshotting and bootstrapping the workspace",
+        (true, false) => ", after snapshotting the workspace",
+        (false, true) => ", after bootstrapping the workspace",
+        (false, false) => "",
+    };
     Ok(format!(
-        "\u{23f3} Starting ACP session `{}` in `{workspace_name}`…",
-        session_id.chars().take(8).collect::<String>()
+        "\u{23f3} Starting ACP session `{}` in `{workspace_name}`{issue}{time_box}{preparation}…",
+        created.short_id
     ))
 }
 
@@ -827,6 +1583,7 @@ async fn finish_acp_session_start(
     channel_id: &str,
     workspace_root: &Path,
     workspace_name: &str,
+    env: BTreeMap<String, String>,
     state: &Arc<AppState>,
 ) -> crate::Result<()> {
     let repo = SessionRepo::new(Arc::clone(&state.db));
@@ -836,6 +1593,7 @@ async fn finish_acp_session_start(
         host_cli: state.config.host_cli.clone(),
         host_cli_args: state.config.host_cli_args.clone(),
         workspace_root: workspace_root.to_path_buf(),
+        env,
     };
 
     let mut conn = crate::acp::spawner::spawn_agent(&spawn_cfg, session_id)?;
@@ -891,10 +1649,20 @@ async fn finish_acp_session_start(
     if let (Some(ref acp_driver), Some(ref event_tx)) = (&state.acp_driver, &state.acp_event_tx) {
         use tokio_util::sync::CancellationToken;
 
-        let (msg_tx, msg_rx) = tokio::sync::mpsc::channel(256);
+        let writer_limits = acp_driver.limits();
+        let (msg_tx, msg_rx) = tokio::sync::mpsc::channel(writer_limits.queue_capacity);
         // T132: register_session now returns the per-session sequence counter
         // shared with run_writer.
         let seq_counter = acp_driver.register_session(session_id, msg_tx).await;
+        let session_driver: Arc<dyn crate::driver::AgentDriver> = acp_driver.clone();
+        state
+            .driver_registry
+            .register(session_id, session_driver)
+            .await;
+        let writer_metrics = acp_driver
+            .writer_metrics(session_id)
+            .await
+            .unwrap_or_default();
 
         // Register the agent-assigned session ID so `send_prompt` can include
         // it in `session/prompt` messages.
@@ -940,7 +1708,8 @@ async fn finish_acp_session_start(
         let writer_ct = session_ct.clone();
         let writer_db = Arc::clone(&state.db);
         // T133/T134: pass seq_counter and db so the writer can stamp sequence
-        // numbers and mark sessions Interrupted on broken-pipe failures.
+        // numbers and mark sessions Interrupted on broken-pipe failures. The
+        // write timeout also interrupts a session whose agent stops reading.
         tokio::spawn(crate::acp::writer::run_writer(
             writer_session_id,
             conn.stdin,
@@ -948,6 +1717,8 @@ async fn finish_acp_session_start(
             writer_ct,
             seq_counter,
             writer_db,
+            writer_limits.write_timeout,
+            writer_metrics,
         ));
 
         state
@@ -979,16 +1750,17 @@ async fn finish_acp_session_start(
 
     // T058 / S036: Post "session started" as the thread root and record ts.
     // All subsequent messages for this session will be posted as thread replies.
+    // A restart already owns its predecessor's thread and posts there.
     if let Some(ref slack) = state.slack {
         let started_blocks = blocks::session_started_blocks(&active);
         let msg = SlackMessage {
             channel: SlackChannelId(channel_id.to_owned()),
             text: Some(format!(
                 "\u{1f916} ACP session `{}` started in `{workspace_name}`",
-                active.id.chars().take(8).collect::<String>()
+                active.short_id
             )),
             blocks: Some(started_blocks),
-            thread_ts: None,
+            thread_ts: active.thread_ts.clone().map(SlackTs),
         };
         match slack.post_message_direct(msg).await {
             Ok(ts) => {
@@ -1024,6 +1796,7 @@ async fn finish_acp_session_start(
 async fn handle_mcp_session_start(
     prompt: &str,
     user_id: &str,
+    channel_id: &str,
     state: &Arc<AppState>,
 ) -> crate::Result<String> {
     let repo = SessionRepo::new(Arc::clone(&state.db));
@@ -1038,6 +1811,7 @@ async fn handle_mcp_session_start(
         prompt,
         &workspace_root,
         user_id,
+        Some(channel_id),
         &state.config,
         &repo,
         state.config.http_port,
@@ -1051,14 +1825,25 @@ async fn handle_mcp_session_start(
         .await
         .insert(session.id.clone(), child);
 
+    if let Some(run_id) = runs::active_run_id(&state.db, user_id).await? {
+        repo.set_run_id(&session.id, &run_id).await?;
+    }
+
     Ok(format!(
         "Session `{}` started with prompt: _{}_",
         session.id, prompt
     ))
 }
 
+/// Pause a session after the agent's current step, or immediately with
+/// `--now`.
+///
+/// The default records a pause request that the agent's next blocking tool
+/// call answers with a pause directive; the session becomes `Paused` then.
+/// `--now` flips the stored status without the agent noticing.
 async fn handle_session_pause(
     session_id: Option<&str>,
+    immediate: bool,
     user_id: &str,
     channel_id: &str,
     state: &Arc<AppState>,
@@ -1068,17 +1853,28 @@ async fn handle_session_pause(
     let session = resolve_command_session(session_id, user_id, channel_id, &repo).await?;
     spawner::verify_session_owner(&session, user_id)?;
 
-    let paused = session_manager::pause_session(&session.id, &repo).await?;
+    let reply = if immediate {
+        state.pause_requests.cancel(&session.id);
+        let paused = session_manager::pause_session(&session.id, &repo).await?;
+        format!("Session `{}` paused.", paused.id)
+    } else {
+        session_manager::request_pause(&session.id, &repo, &state.pause_requests).await?;
+        format!(
+            "Pause requested for session `{}`. It pauses when the agent makes its next \
+             blocking tool call.",
+            session.id
+        )
+    };
 
     // HITL-007: audit-log the ACP session pause event.
     emit_audit(
         state.audit_logger.as_ref(),
         AuditEventType::AcpSessionPause,
-        &paused.id,
+        &session.id,
         Some(user_id),
     );
 
-    Ok(format!("Session `{}` paused.", paused.id))
+    Ok(reply)
 }
 
 async fn handle_session_resume(
@@ -1092,8 +1888,27 @@ async fn handle_session_resume(
     let session = resolve_command_session(session_id, user_id, channel_id, &repo).await?;
     spawner::verify_session_owner(&session, user_id)?;
 
+    // A pause the agent has not reached yet is simply withdrawn.
+    if state.pause_requests.cancel(&session.id) {
+        return Ok(format!(
+            "Pending pause for session `{}` cancelled.",
+            session.id
+        ));
+    }
+
     let resumed = session_manager::resume_session(&session.id, &repo).await?;
 
+    // Release an agent that is waiting in `standby` after a pause directive.
+    if let Some(tx) = state.pending_waits.lock().await.remove(&resumed.id) {
+        let response = WaitResponse {
+            status: "resumed".to_owned(),
+            instruction: None,
+        };
+        if tx.send(response).is_err() {
+            warn!(session_id = %resumed.id, "wait oneshot receiver already dropped");
+        }
+    }
+
     // HITL-007: audit-log the ACP session resume event.
     emit_audit(
         state.audit_logger.as_ref(),
@@ -1160,10 +1975,32 @@ async fn handle_session_stop(
     if let Some(ref acp_driver) = state.acp_driver {
         acp_driver.deregister_session(&session.id).await;
     }
+    state.driver_registry.deregister(&session.id).await;
 
     if let Some(ref slack) = state.slack {
-        session_manager::notify_session_ended(&terminated, "stopped by operator", slack).await;
+        session_manager::notify_session_ended(
+            &terminated,
+            "stopped by operator",
+            slack,
+            &audit::log_dir(state.config.default_workspace_root()),
+        )
+        .await;
+    }
+    if let Err(err) = subtask::report_to_parent(&state.db, &terminated, "stopped by operator").await
+    {
+        warn!(%err, session_id = %terminated.id, "failed to report subtask to parent");
     }
+    issues::spawn_completion_report(
+        Arc::clone(&state.config),
+        terminated.clone(),
+        "stopped by operator".to_owned(),
+    );
+    email::spawn_summary_email(
+        Arc::clone(&state.config),
+        Arc::clone(&state.db),
+        terminated.clone(),
+        "stopped by operator".to_owned(),
+    );
 
     info!(session_id = %terminated.id, user_id, "ACP session stopped by operator");
 
@@ -1210,8 +2047,8 @@ async fn handle_session_cleanup(
         if let Some(ref acp_driver) = state.acp_driver {
             acp_driver.deregister_session(&session.id).await;
         }
-        let short_id: String = session.id.chars().take(8).collect();
-        cleaned.push(format!("`{short_id}…`"));
+        state.driver_registry.deregister(&session.id).await;
+        cleaned.push(format!("`{}`", session.short_id));
         info!(session_id = %session.id, user_id, "interrupted session cleaned up");
 
         // HITL-007: audit-log each cleaned-up session.
@@ -1240,20 +2077,7 @@ async fn handle_session_clear(
     let session = resolve_command_session(session_id, user_id, channel_id, &repo).await?;
     spawner::verify_session_owner(&session, user_id)?;
 
-    // Remove the child from the registry before awaiting terminate_session
-    // to avoid holding the lock guard across an await point.
-    let mut child = state.active_children.lock().await.remove(&session.id);
-    let terminated = session_manager::terminate_session(&session.id, &repo, child.as_mut()).await?;
-
-    // Deregister the ACP driver's in-memory state for this session.
-    if let Some(ref acp_driver) = state.acp_driver {
-        acp_driver.deregister_session(&session.id).await;
-    }
-
-    // T060 / S094: Post session-ended summary as a threaded reply.
-    if let Some(ref slack) = state.slack {
-        session_manager::notify_session_ended(&terminated, "terminated by operator", slack).await;
-    }
+    let terminated = session_bulk::clear_session(state, &session, "terminated by operator").await?;
 
     // HITL-007: audit-log the session clear/terminate event.
     emit_audit(
@@ -1328,6 +2152,7 @@ async fn handle_session_restart(
     if let Some(ref acp_driver) = state.acp_driver {
         acp_driver.deregister_session(&old_session_id).await;
     }
+    state.driver_registry.deregister(&old_session_id).await;
 
     // Notify the Slack thread that the session was restarted.
     if let Some(ref slack) = state.slack {
@@ -1344,15 +2169,733 @@ async fn handle_session_restart(
         }
     }
 
-    // Spawn the new ACP session with the original prompt.
-    handle_acp_session_start(&original_prompt, user_id, channel_id, state).await
+    // Spawn the new ACP session with the original prompt, keeping its time
+    // box, issue, tags and the environment variables whose values were
+    // recorded. Sessions from before `max_duration_seconds` was stored fall
+    // back to their deadline, if it is still set.
+    let (env, dropped) = restart_env(&session.env);
+    let max_duration = session
+        .max_duration_seconds
+        .map(chrono::Duration::seconds)
+        .or_else(|| {
+            session
+                .deadline
+                .map(|deadline| deadline - session.created_at)
+        });
+    let options = SessionStartOptions {
+        max_duration,
+        issue: session.issue_ref.clone(),
+        tags: session.tags.clone(),
+        env,
+        ..SessionStartOptions::default()
+    };
+    let mut reply = handle_acp_session_start(
+        &original_prompt,
+        options,
+        Some(&session),
+        user_id,
+        channel_id,
+        state,
+    )
+    .await?;
+    if !dropped.is_empty() {
+        let names: Vec<String> = dropped.iter().map(|name| format!("`{name}`")).collect();
+        let _ = write!(
+            reply,
+            "\n\u{26a0}\u{fe0f} Not carried over, since secret values are not stored: {}. \
+             Stop the session and start it again with `--env` to set them.",
+            names.join(", ")
+        );
+    }
+    Ok(reply)
 }
 
-// ── Audit helpers (HITL-007) ─────────────────────────────────────────
+/// Split a session's recorded `env` into the variables `session-restart`
+/// passes on and the names of those it drops because only
+/// [`REDACTED`](crate::config::REDACTED) was stored for them.
+#[must_use]
+pub fn restart_env(recorded: &BTreeMap<String, String>) -> (BTreeMap<String, String>, Vec<String>) {
+    let (redacted, kept): (BTreeMap<_, _>, BTreeMap<_, _>) = recorded
+        .iter()
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .partition(|(_, value)| value.as_str() == crate::config::REDACTED);
+    (kept, redacted.into_keys().collect())
+}
 
-/// Emit an audit log entry for an ACP session lifecycle event.
-///
-/// A no-op when the audit logger is not configured. Logs a warning
+// ── Runs ─────────────────────────────────────────────────────────────
+
+/// Sessions listed by `run-summary` before the rest are counted.
+const MAX_RUN_SESSIONS_LISTED: usize = 20;
+
+/// Handle `run-start <name>`.
+async fn handle_run_start(
+    args: &[&str],
+    user_id: &str,
+    channel_id: &str,
+    state: &Arc<AppState>,
+) -> crate::Result<String> {
+    let run = runs::start_run(&state.db, &args.join(" "), user_id, Some(channel_id)).await?;
+    Ok(format!(
+        "\u{1f3c1} Run *{}* started. Sessions you start join it until `run-end`.",
+        run.name
+    ))
+}
+
+/// Handle `run-end`: end the operator's active run and summarise it.
+async fn handle_run_end(user_id: &str, state: &Arc<AppState>) -> crate::Result<String> {
+    let run = runs::end_run(&state.db, user_id).await?;
+    let summary = RunSummary::load(&state.db, run).await?;
+    Ok(format_run_summary(&summary, chrono::Utc::now()))
+}
+
+/// Handle `run-summary [run]`: the named run (ID prefix or name), or else
+/// the operator's active or most recent run.
+async fn handle_run_summary(
+    args: &[&str],
+    user_id: &str,
+    state: &Arc<AppState>,
+) -> crate::Result<String> {
+    let repo = RunRepo::new(Arc::clone(&state.db));
+    let run = if args.is_empty() {
+        match repo.active_for(user_id).await? {
+            Some(run) => Some(run),
+            None => repo.latest_for(user_id).await?,
+        }
+    } else {
+        repo.find(args.join(" ").trim_matches('"')).await?
+    };
+    let run = run.ok_or_else(|| {
+        crate::AppError::NotFound(if args.is_empty() {
+            "you have not started a run; use `run-start <name>`".to_owned()
+        } else {
+            format!("no run matches `{}`", args.join(" "))
+        })
+    })?;
+    let summary = RunSummary::load(&state.db, run).await?;
+    Ok(format_run_summary(&summary, chrono::Utc::now()))
+}
+
+/// Render a run summary: totals, then one line per session.
+fn format_run_summary(summary: &RunSummary, now: chrono::DateTime<chrono::Utc>) -> String {
+    let run = &summary.run;
+    let state = if run.is_active() { "active" } else { "ended" };
+    let mut lines = vec![
+        format!(
+            "*Run \"{}\"* — {state} · {} · started by <@{}>",
+            run.name,
+            blocks::format_elapsed(summary.elapsed_seconds(now)),
+            run.owner_user_id
+        ),
+        format!(
+            "{} sessions · {} running · {} stalls",
+            summary.sessions.len(),
+            summary.running(),
+            summary.stalls
+        ),
+        format!(
+            "Approvals: {} approved · {} rejected · {} pending · {} files changed",
+            summary.approved, summary.rejected, summary.pending, summary.files_changed
+        ),
+    ];
+    for session in summary.sessions.iter().take(MAX_RUN_SESSIONS_LISTED) {
+        lines.push(format_session_line(session));
+    }
+    if summary.sessions.len() > MAX_RUN_SESSIONS_LISTED {
+        lines.push(format!(
+            "…and {} more",
+            summary.sessions.len() - MAX_RUN_SESSIONS_LISTED
+        ));
+    }
+    lines.join("\n")
+}
+
+// ── Session move ─────────────────────────────────────────────────────
+
+/// Handle `session-move [session_id] <channel>`.
+async fn handle_session_move(
+    args: &[&str],
+    user_id: &str,
+    channel_id: &str,
+    state: &Arc<AppState>,
+) -> crate::Result<String> {
+    let (session_id, target) = match args {
+        [target] => (None, *target),
+        [session_id, target] => (Some(*session_id), *target),
+        _ => {
+            return Err(crate::AppError::Config(
+                "usage: session-move [session_id] <#channel>".into(),
+            ))
+        }
+    };
+    let target = parse_channel_ref(target);
+    if target.starts_with('#') {
+        return Err(crate::AppError::Config(format!(
+            "cannot resolve `{target}`: pick the channel from the autocomplete so it is sent \
+             as a mention, or pass its ID"
+        )));
+    }
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let session = resolve_command_session(session_id, user_id, channel_id, &repo).await?;
+    spawner::verify_session_owner(&session, user_id)?;
+
+    session_move::move_session(state, &session, &target, user_id).await?;
+    emit_audit(
+        state.audit_logger.as_ref(),
+        AuditEventType::SessionMove,
+        &session.id,
+        Some(user_id),
+    );
+    Ok(format!(
+        "Session `{}` moved to <#{target}>. New messages start a thread there.",
+        session.id
+    ))
+}
+
+// ── Notification mute ────────────────────────────────────────────────
+
+/// Handle `mute <session_id> [duration]`: hold back the session's status
+/// updates, broadcasts and heartbeat notices, for `duration` or until
+/// `unmute`. Approvals and prompts are still posted.
+async fn handle_mute(
+    args: &[&str],
+    user_id: &str,
+    channel_id: &str,
+    state: &Arc<AppState>,
+) -> crate::Result<String> {
+    let (session_id, duration) = match args {
+        [session_id] => (*session_id, None),
+        [session_id, duration] => (*session_id, Some(parse_duration(duration)?)),
+        _ => {
+            return Err(crate::AppError::Config(
+                "usage: mute <session_id> [duration, e.g. 2h]".into(),
+            ))
+        }
+    };
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let session = resolve_command_session(Some(session_id), user_id, channel_id, &repo).await?;
+    spawner::verify_session_owner(&session, user_id)?;
+
+    let until = duration
+        .map(|duration| {
+            chrono::Utc::now()
+                .checked_add_signed(duration)
+                .ok_or_else(|| crate::AppError::Config("mute duration is too long".into()))
+        })
+        .transpose()?;
+    repo.set_muted(&session.id, true, until).await?;
+    info!(session_id = %session.id, user_id, ?until, "session notifications muted");
+
+    let how_long = until.map_or_else(
+        || "until `unmute`".to_owned(),
+        |until| format!("until {}", blocks::slack_datetime(until)),
+    );
+    Ok(format!(
+        "Session `{}` muted {how_long}. Status updates, broadcasts and heartbeat notices are \
+         held back; approvals and prompts still come through.",
+        session.id
+    ))
+}
+
+/// Handle `unmute <session_id>`.
+async fn handle_unmute(
+    args: &[&str],
+    user_id: &str,
+    channel_id: &str,
+    state: &Arc<AppState>,
+) -> crate::Result<String> {
+    let [session_id] = args else {
+        return Err(crate::AppError::Config("usage: unmute <session_id>".into()));
+    };
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let session = resolve_command_session(Some(session_id), user_id, channel_id, &repo).await?;
+    spawner::verify_session_owner(&session, user_id)?;
+
+    if !session.is_muted(chrono::Utc::now()) {
+        return Ok(format!("Session `{}` is not muted.", session.id));
+    }
+    repo.set_muted(&session.id, false, None).await?;
+    info!(session_id = %session.id, user_id, "session notifications unmuted");
+    Ok(format!("Session `{}` unmuted.", session.id))
+}
+
+// ── Session tags ─────────────────────────────────────────────────────
+
+/// Handle `session-tag <session_id> <key=value|key=>...`: set or change tags
+/// on a session, or remove them with an empty value.
+async fn handle_session_tag(
+    args: &[&str],
+    user_id: &str,
+    channel_id: &str,
+    state: &Arc<AppState>,
+) -> crate::Result<String> {
+    let Some((session_id, changes)) = args.split_first().filter(|(_, c)| !c.is_empty()) else {
+        return Err(crate::AppError::Config(
+            "usage: session-tag <session_id> <key=value|key=>...".into(),
+        ));
+    };
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let session = resolve_command_session(Some(session_id), user_id, channel_id, &repo).await?;
+    spawner::verify_session_owner(&session, user_id)?;
+
+    let mut tags = session.tags.clone();
+    for change in changes {
+        match change.strip_suffix('=') {
+            Some(key) if !key.contains('=') => {
+                tags.remove(key);
+            }
+            _ => {
+                let (key, value) = parse_tag(change)?;
+                tags.insert(key, value);
+            }
+        }
+    }
+    repo.set_tags(&session.id, &tags).await?;
+    info!(session_id = %session.id, user_id, tags = %format_tags(&tags), "session tags updated");
+
+    Ok(if tags.is_empty() {
+        format!("Session `{}` has no tags.", session.id)
+    } else {
+        format!("Session `{}` tags: {}", session.id, format_tags(&tags))
+    })
+}
+
+/// Handle `note <session_id> <text>`.
+async fn handle_note(
+    args: &[&str],
+    user_id: &str,
+    channel_id: &str,
+    state: &Arc<AppState>,
+) -> crate::Result<String> {
+    let Some((session_id, words)) = args.split_first().filter(|(_, w)| !w.is_empty()) else {
+        return Err(crate::AppError::Config(
+            "usage: note <session_id> <text>".into(),
+        ));
+    };
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    // Any operator may annotate any session, ended ones included.
+    let session = resolve_command_session(Some(session_id), user_id, channel_id, &repo).await?;
+
+    let note = SessionNote::new(session.id.clone(), user_id.to_owned(), words.join(" "));
+    SessionNoteRepo::new(Arc::clone(&state.db))
+        .create(&note)
+        .await?;
+    info!(session_id = %session.id, user_id, "session note added");
+
+    Ok(format!(
+        "Noted on session `{}` at {}.",
+        session.id,
+        note.created_at.format("%Y-%m-%d %H:%M UTC")
+    ))
+}
+
+// ── Streamed logs ────────────────────────────────────────────────────
+
+/// Room for `logs` output in one ephemeral response; older lines are left
+/// out beyond it.
+const LOGS_RESPONSE_BUDGET: usize = 3400;
+
+/// Handle `logs <session_id> [-n N]`.
+async fn handle_logs(
+    args: &[&str],
+    user_id: &str,
+    channel_id: &str,
+    state: &Arc<AppState>,
+) -> crate::Result<String> {
+    let (session_id, count) = parse_logs_args(args)?;
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    // Read-only, like `decisions`: any authorized user may look.
+    let session = resolve_command_session(Some(session_id), user_id, channel_id, &repo).await?;
+
+    let lines = state.session_logs.tail(&session.id, count);
+    if lines.is_empty() {
+        return Ok(format!(
+            "Session `{}` has not streamed any log lines.",
+            session.id
+        ));
+    }
+
+    // Keep the newest lines that fit, then restore oldest-first order.
+    let mut used = 0;
+    let mut shown: Vec<String> = lines
+        .iter()
+        .rev()
+        .map(|line| blocks::truncate_text(&line.render(), 300))
+        .take_while(|text| {
+            used += text.len() + 1;
+            used <= LOGS_RESPONSE_BUDGET
+        })
+        .collect();
+    shown.reverse();
+    let omitted = lines.len() - shown.len();
+
+    let mut text = format!(
+        "*Logs for session `{}`* (last {} of {} buffered):\n```\n{}\n```",
+        session.id,
+        shown.len(),
+        state.session_logs.buffered(&session.id),
+        shown.join("\n"),
+    );
+    if omitted > 0 {
+        let _ = write!(
+            text,
+            "\n_{omitted} older line(s) did not fit — use `agent-intercom-ctl logs` for the full \
+             tail._"
+        );
+    }
+    Ok(text)
+}
+
+/// Parse `logs` arguments: a session ID and an optional `-n N` line count
+/// from 1 to [`LOG_BUFFER_CAPACITY`] (default [`DEFAULT_LOG_TAIL`]).
+///
+/// # Errors
+///
+/// Returns `AppError::Config` if the session ID is missing or the count is
+/// not a number in range.
+pub fn parse_logs_args<'a>(args: &[&'a str]) -> crate::Result<(&'a str, usize)> {
+    let usage = || crate::AppError::Config("usage: logs <session_id> [-n N]".into());
+    let mut session_id = None;
+    let mut count = DEFAULT_LOG_TAIL;
+    let mut rest = args;
+    while let Some((first, tail)) = rest.split_first() {
+        if *first == "-n" {
+            let (value, tail) = tail.split_first().ok_or_else(usage)?;
+            count = value
+                .parse()
+                .ok()
+                .filter(|n| (1..=LOG_BUFFER_CAPACITY).contains(n))
+                .ok_or_else(usage)?;
+            rest = tail;
+        } else if session_id.is_none() {
+            session_id = Some(*first);
+            rest = tail;
+        } else {
+            return Err(usage());
+        }
+    }
+    Ok((session_id.ok_or_else(usage)?, count))
+}
+
+// ── Workspace mappings ───────────────────────────────────────────────
+
+/// Handle `workspace list | add | remove | pending | approve | reject`.
+fn handle_workspace(args: &[&str], channel_id: &str, state: &AppState) -> crate::Result<String> {
+    match args {
+        ["list"] => Ok(format_workspace_list(state)),
+        ["add", workspace_id, channel, label @ ..] => {
+            let target = parse_channel_ref(channel);
+            let mapping = crate::config::WorkspaceMapping {
+                workspace_id: (*workspace_id).to_owned(),
+                channel_id: target.clone(),
+                label: (!label.is_empty()).then(|| label.join(" ")),
+                path: None,
+                proxy: Vec::new(),
+                approval_channels: Vec::new(),
+                working_hours: None,
+                bootstrap: None,
+            };
+            let saved = workspace_mappings::add(state, mapping)?;
+            info!(workspace_id, channel_id = %target, saved, "workspace mapping added");
+            Ok(format!(
+                "Workspace `{workspace_id}` mapped to <#{target}> {}. New sessions route there.",
+                persistence_note(saved, "saved to")
+            ))
+        }
+        ["remove", workspace_id] => {
+            let (removed, saved) = workspace_mappings::remove(state, workspace_id)?;
+            info!(workspace_id, saved, "workspace mapping removed");
+            Ok(format!(
+                "Workspace `{workspace_id}` unmapped from <#{}> {}. Running sessions keep their \
+                 channel; new ones are not routed to Slack.",
+                removed.channel_id,
+                persistence_note(saved, "removed from")
+            ))
+        }
+        ["pending"] => Ok(format_pending_workspaces(state)),
+        ["approve", workspace_id, rest @ ..] if rest.len() <= 1 => {
+            approve_workspace(workspace_id, rest.first().copied(), channel_id, state)
+        }
+        ["reject", workspace_id] => {
+            if !state.workspace_discovery.dismiss(workspace_id) {
+                return Err(crate::AppError::NotFound(format!(
+                    "workspace '{workspace_id}' is not pending"
+                )));
+            }
+            info!(workspace_id, "workspace discovery rejected");
+            Ok(format!(
+                "Workspace `{workspace_id}` rejected. Its sessions run without Slack routing \
+                 until the server restarts."
+            ))
+        }
+        _ => Err(crate::AppError::Config(
+            "usage: workspace list | add <workspace_id> <channel_id> [label] | \
+             remove <workspace_id> | pending | approve <workspace_id> [channel_id] | \
+             reject <workspace_id>"
+                .into(),
+        )),
+    }
+}
+
+// ── Command aliases ──────────────────────────────────────────────────
+
+/// Handle `commands list | add <alias> <command>`.
+fn handle_commands(args: &[&str], user_id: &str, state: &AppState) -> crate::Result<String> {
+    match args {
+        ["list"] => Ok(format_command_aliases(state)),
+        ["add", alias, command @ ..] if !command.is_empty() => {
+            let command = command.join(" ");
+            let saved = command_aliases::add(state, alias, &command)?;
+            info!(alias, command, user_id, saved, "command alias added");
+            if let Some(ref logger) = state.audit_logger {
+                let entry = AuditEntry::new(AuditEventType::CommandAliasAdded)
+                    .with_operator(user_id.to_owned())
+                    .with_command(command.clone())
+                    .with_parameters(serde_json::json!({ "alias": alias }));
+                if let Err(err) = logger.log_entry(entry) {
+                    warn!(%err, "audit log write failed (command alias)");
+                }
+            }
+            Ok(format!(
+                "Alias `{alias}` now runs `{command}` {}.",
+                persistence_note(saved, "saved to")
+            ))
+        }
+        _ => Err(crate::AppError::Config(
+            "usage: commands list | add <alias> <command>".into(),
+        )),
+    }
+}
+
+/// Render every command alias for `commands list`.
+fn format_command_aliases(state: &AppState) -> String {
+    let aliases = command_aliases::list(state);
+    if aliases.is_empty() {
+        return "No command aliases are configured. Add one with `commands add <alias> \
+                <command>`."
+            .to_owned();
+    }
+    let mut text = String::from("*Command aliases:*");
+    for (alias, command) in &aliases {
+        let _ = write!(text, "\n\u{2022} `{alias}` \u{2192} `{command}`");
+    }
+    text
+}
+
+// ── Device pairing ───────────────────────────────────────────────────
+
+/// Handle `pair | pair list | pair revoke <device_id|all>`.
+async fn handle_pair(args: &[&str], user_id: &str, state: &AppState) -> crate::Result<String> {
+    let repo = DeviceRepo::new(Arc::clone(&state.db));
+    match args {
+        [] => {
+            let offer =
+                device_pairing::create_offer(&state.config.approval_links, &repo, user_id).await?;
+            info!(user_id, "device pairing offered");
+            Ok(format!(
+                "Scan with your phone to open the approval dashboard:\n```\n{}```\n<{}|Pair this \
+                 browser> \u{b7} works once, until {}",
+                offer.qr,
+                offer.url,
+                blocks::slack_time(offer.expires_at)
+            ))
+        }
+        ["list"] => {
+            let devices = repo.list_for_user(user_id, chrono::Utc::now()).await?;
+            if devices.is_empty() {
+                return Ok("No paired browsers. Pair one with `pair`.".to_owned());
+            }
+            let mut text = String::from("*Paired browsers*\n");
+            for device in &devices {
+                let paired = device
+                    .paired_at
+                    .map_or_else(String::new, blocks::slack_datetime);
+                let seen = device.last_seen_at.map_or_else(
+                    || "never used".to_owned(),
+                    |at| format!("last used {}", blocks::slack_datetime(at)),
+                );
+                let _ = writeln!(
+                    text,
+                    "• `{}` {} \u{b7} paired {paired} \u{b7} {seen}",
+                    device.short_id(),
+                    device.label.as_deref().unwrap_or("unknown browser")
+                );
+            }
+            Ok(text)
+        }
+        ["revoke", target] if !target.is_empty() => {
+            let prefix = (*target != "all").then_some(*target);
+            let removed = repo.revoke(user_id, prefix).await?;
+            if removed == 0 {
+                return Err(crate::AppError::NotFound(format!(
+                    "no paired browser matches '{target}'"
+                )));
+            }
+            info!(user_id, target, removed, "paired devices revoked");
+            Ok(format!("Signed out {removed} paired browser(s)."))
+        }
+        _ => Err(crate::AppError::Config(
+            "usage: pair | pair list | pair revoke <device_id|all>".into(),
+        )),
+    }
+}
+
+/// Handle `share <session_id> [--ttl <duration>]`.
+async fn handle_share(args: &[&str], state: &AppState) -> crate::Result<String> {
+    let usage = || crate::AppError::Config("usage: share <session_id> [--ttl <duration>]".into());
+    let (session_id, ttl) = match args {
+        [session_id] => (
+            *session_id,
+            i64::try_from(state.config.approval_links.share_ttl_seconds).unwrap_or(i64::MAX),
+        ),
+        [session_id, "--ttl", ttl] | ["--ttl", ttl, session_id] => {
+            (*session_id, parse_duration(ttl)?.num_seconds())
+        }
+        _ => return Err(usage()),
+    };
+    let session = history_session(Some(session_id), &SessionRepo::new(Arc::clone(&state.db)))
+        .await?
+        .ok_or_else(usage)?;
+    let url = session_share::share_url(
+        &state.config.approval_links,
+        &session.id,
+        ttl,
+        chrono::Utc::now().timestamp(),
+    )?;
+    info!(session_id = session.id, ttl, "read-only share link created");
+    Ok(format!(
+        "Read-only link to session `{}` \u{b7} expires in {}:\n{url}\nAnyone with the link \
+         can watch the session's progress and decisions, but cannot approve anything.",
+        session.id,
+        blocks::format_elapsed(ttl)
+    ))
+}
+
+/// Where a runtime mapping change was recorded.
+fn persistence_note(saved: bool, action: &str) -> String {
+    if saved {
+        format!("and {action} config.toml")
+    } else {
+        "for this run only (config.toml was not changed)".to_owned()
+    }
+}
+
+/// Render the live `[[workspace]]` routing table for `workspace list`.
+fn format_workspace_list(state: &AppState) -> String {
+    let mappings = state
+        .workspace_mappings
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone();
+    let mut text = if mappings.is_empty() {
+        "No workspaces are mapped. Add one with `workspace add <workspace_id> <channel_id>`."
+            .to_owned()
+    } else {
+        String::from("*Workspace mappings:*")
+    };
+    for mapping in &mappings {
+        let _ = write!(
+            text,
+            "\n\u{2022} `{}` \u{2192} <#{}>",
+            mapping.workspace_id, mapping.channel_id
+        );
+        if let Some(label) = mapping.label.as_deref() {
+            let _ = write!(text, " \u{2014} {label}");
+        }
+        if let Some(path) = mapping.path.as_deref() {
+            let _ = write!(text, " (`{}`)", path.display());
+        }
+    }
+    let pending = state.workspace_discovery.pending().len();
+    if pending > 0 {
+        let _ = write!(
+            text,
+            "\n_{pending} unmapped workspace(s) pending \u{2014} see `workspace pending`._"
+        );
+    }
+    text
+}
+
+/// Render unmapped workspaces for `workspace pending`.
+fn format_pending_workspaces(state: &AppState) -> String {
+    let pending = state.workspace_discovery.pending();
+    if pending.is_empty() {
+        return "No unmapped workspaces are pending.".into();
+    }
+    let mut text = String::from("*Pending workspaces:*");
+    for entry in pending {
+        let channel = entry
+            .channel_id
+            .map_or_else(String::new, |id| format!(" \u{2192} <#{id}>"));
+        let _ = write!(
+            text,
+            "\n\u{2022} `{}`{channel} \u{2014} {} connection(s) since {}",
+            entry.workspace_id,
+            entry.connections,
+            blocks::slack_datetime(entry.first_seen),
+        );
+    }
+    text
+}
+
+/// Handle `workspace approve <id> [channel_id]`: map to the given channel,
+/// else the channel created for the workspace, else the current channel.
+fn approve_workspace(
+    workspace_id: &str,
+    explicit: Option<&str>,
+    channel_id: &str,
+    state: &AppState,
+) -> crate::Result<String> {
+    let pending = state
+        .workspace_discovery
+        .pending()
+        .into_iter()
+        .find(|entry| entry.workspace_id == workspace_id)
+        .ok_or_else(|| {
+            crate::AppError::NotFound(format!("workspace '{workspace_id}' is not pending"))
+        })?;
+    let target = match (explicit, pending.channel_id) {
+        (Some(explicit), _) => parse_channel_ref(explicit),
+        (None, Some(created)) => created,
+        (None, None) if channel_id == state.config.workspace_discovery.unmapped_channel_id => {
+            return Err(crate::AppError::Config(
+                "this is the shared unmapped channel; pass the channel to map the workspace \
+                 to: workspace approve <workspace_id> <channel_id>"
+                    .into(),
+            ))
+        }
+        (None, None) => channel_id.to_owned(),
+    };
+    let mapping = crate::config::WorkspaceMapping {
+        workspace_id: workspace_id.to_owned(),
+        channel_id: target.clone(),
+        label: None,
+        path: None,
+        proxy: Vec::new(),
+        approval_channels: Vec::new(),
+        working_hours: None,
+        bootstrap: None,
+    };
+    let saved = workspace_discovery::approve(state, mapping)?;
+    info!(workspace_id, channel_id = %target, saved, "workspace mapping approved");
+    Ok(format!(
+        "Workspace `{workspace_id}` mapped to <#{target}> {}. New sessions route there.",
+        persistence_note(saved, "saved to")
+    ))
+}
+
+/// Accept a bare channel ID or a Slack channel mention (`<#C123|name>`).
+fn parse_channel_ref(raw: &str) -> String {
+    raw.strip_prefix("<#")
+        .and_then(|rest| rest.strip_suffix('>'))
+        .map_or(raw, |inner| inner.split('|').next().unwrap_or(inner))
+        .to_owned()
+}
+
+// ── Audit helpers (HITL-007) ─────────────────────────────────────────
+
+/// Emit an audit log entry for an ACP session lifecycle event.
+///
+/// A no-op when the audit logger is not configured. Logs a warning
 /// if the underlying write fails so the handler's happy path is not
 /// interrupted by audit I/O errors.
 fn emit_audit(
@@ -1407,10 +2950,10 @@ async fn handle_session_checkpoint(
     label: Option<&str>,
     user_id: &str,
     channel_id: &str,
-    db: &Arc<Database>,
+    state: &Arc<AppState>,
 ) -> crate::Result<String> {
-    let session_repo = SessionRepo::new(Arc::clone(db));
-    let checkpoint_repo = CheckpointRepo::new(Arc::clone(db));
+    let session_repo = SessionRepo::new(Arc::clone(&state.db));
+    let checkpoint_repo = CheckpointRepo::new(Arc::clone(&state.db));
 
     let session = resolve_command_session(session_id, user_id, channel_id, &session_repo).await?;
     spawner::verify_session_owner(&session, user_id)?;
@@ -1419,6 +2962,16 @@ async fn handle_session_checkpoint(
         checkpoint_manager::create_checkpoint(&session.id, label, &session_repo, &checkpoint_repo)
             .await?;
 
+    state
+        .notify_session_hook(
+            &session.id,
+            SessionHookEvent::CheckpointCreated {
+                checkpoint_id: checkpoint.id.clone(),
+                label: checkpoint.label.clone(),
+            },
+        )
+        .await;
+
     let label_text = checkpoint.label.as_deref().unwrap_or("(unnamed)");
 
     Ok(format!(
@@ -1492,16 +3045,413 @@ async fn handle_session_checkpoints(
         checkpoints.len()
     )];
     for cp in &checkpoints {
-        let label = cp.label.as_deref().unwrap_or("(unnamed)");
+        let label = cp.label.as_deref().unwrap_or(if cp.automatic {
+            "(automatic)"
+        } else {
+            "(unnamed)"
+        });
         lines.push(format!(
             "• `{}` — _{}_  (created: {})",
-            cp.id, label, cp.created_at
+            cp.id,
+            label,
+            blocks::slack_datetime(cp.created_at)
         ));
     }
 
     Ok(lines.join("\n"))
 }
 
+// ── Workspace snapshots ──────────────────────────────────────────────
+
+/// The workspace a snapshot command acts on: that of the caller's session
+/// in this channel, else the channel's `[[workspace]]` path, else
+/// `default_workspace_root`.
+async fn resolve_command_workspace(
+    user_id: &str,
+    channel_id: &str,
+    state: &AppState,
+) -> crate::Result<PathBuf> {
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    match resolve_command_session(None, user_id, channel_id, &repo).await {
+        Ok(session) => return Ok(PathBuf::from(session.workspace_root)),
+        Err(crate::AppError::NotFound(_)) => {}
+        Err(err) => return Err(err),
+    }
+    let mappings = state
+        .workspace_mappings
+        .read()
+        .map_err(|_| crate::AppError::Config("workspace_mappings lock poisoned".to_owned()))?;
+    Ok(mappings
+        .iter()
+        .find(|m| m.channel_id == channel_id)
+        .and_then(|m| m.path.clone())
+        .unwrap_or_else(|| state.config.default_workspace_root.clone()))
+}
+
+async fn handle_workspace_snapshot(
+    label: Option<String>,
+    user_id: &str,
+    channel_id: &str,
+    state: &Arc<AppState>,
+) -> crate::Result<String> {
+    let root = resolve_command_workspace(user_id, channel_id, state).await?;
+    let snapshot = workspace_snapshot::create(state, &root, label, user_id, None).await?;
+    Ok(format!(
+        "\u{1f4e6} Snapshot `{}` of `{}` taken: {} files, {} KiB. Restore it with \
+         `workspace-restore {}`.",
+        workspace_snapshot::display_id(&snapshot),
+        root.display(),
+        snapshot.file_count,
+        snapshot.total_bytes.div_ceil(1024),
+        workspace_snapshot::display_id(&snapshot)
+    ))
+}
+
+async fn handle_workspace_snapshots(
+    user_id: &str,
+    channel_id: &str,
+    state: &Arc<AppState>,
+) -> crate::Result<String> {
+    let root = resolve_command_workspace(user_id, channel_id, state).await?;
+    let snapshots = SnapshotRepo::new(Arc::clone(&state.db))
+        .list_for_workspace(&root.to_string_lossy())
+        .await?;
+    if snapshots.is_empty() {
+        return Ok(format!("No snapshots of `{}`.", root.display()));
+    }
+
+    let mut lines = vec![format!(
+        "*Snapshots of `{}`* ({} kept, newest first):",
+        root.display(),
+        snapshots.len()
+    )];
+    for snapshot in &snapshots {
+        let label = snapshot.label.as_deref().unwrap_or("(unnamed)");
+        lines.push(format!(
+            "• `{}` — _{}_ by <@{}>, {} files, {} KiB (taken: {})",
+            workspace_snapshot::display_id(snapshot),
+            label,
+            snapshot.created_by,
+            snapshot.file_count,
+            snapshot.total_bytes.div_ceil(1024),
+            blocks::slack_datetime(snapshot.created_at)
+        ));
+    }
+    Ok(lines.join("\n"))
+}
+
+async fn handle_workspace_restore(
+    snapshot_id: &str,
+    user_id: &str,
+    state: &Arc<AppState>,
+) -> crate::Result<String> {
+    let summary = workspace_snapshot::restore(state, snapshot_id, user_id).await?;
+    Ok(format!(
+        "\u{21a9}\u{fe0f} Restored snapshot `{}` to `{}`: {} files written back, {} removed. \
+         The state before the restore is snapshot `{}`.",
+        workspace_snapshot::display_id(&summary.snapshot),
+        summary.snapshot.workspace_root,
+        summary.restored,
+        summary.removed,
+        workspace_snapshot::display_id(&summary.safety)
+    ))
+}
+
+// ── History views (decisions, stalls) ────────────────────────────────
+
+/// Number of entries a history view lists when `--limit` is not given.
+const DEFAULT_HISTORY_LIMIT: u32 = 10;
+
+/// Upper bound on `--limit`, keeping the reply within one message.
+const MAX_HISTORY_LIMIT: u32 = 50;
+
+/// Parse `<command> [session_id] [--limit N]` arguments of a history view
+/// (`decisions`, `stalls`).
+///
+/// # Errors
+///
+/// Returns `AppError::Config` if `--limit` has no value or its value is not
+/// a number from 1 to 50.
+pub fn parse_history_args<'a>(
+    command: &str,
+    args: &[&'a str],
+) -> crate::Result<(Option<&'a str>, u32)> {
+    let usage = || crate::AppError::Config(format!("usage: {command} [session_id] [--limit N]"));
+    let mut session_id = None;
+    let mut limit = DEFAULT_HISTORY_LIMIT;
+    let mut rest = args;
+    while let Some((first, tail)) = rest.split_first() {
+        if *first == "--limit" {
+            let (value, tail) = tail.split_first().ok_or_else(usage)?;
+            limit = value
+                .parse()
+                .ok()
+                .filter(|n| (1..=MAX_HISTORY_LIMIT).contains(n))
+                .ok_or_else(usage)?;
+            rest = tail;
+        } else if session_id.is_none() {
+            session_id = Some(*first);
+            rest = tail;
+        } else {
+            return Err(usage());
+        }
+    }
+    Ok((session_id, limit))
+}
+
+/// List the last `limit` approval decisions, newest first.
+///
+/// Approval records supply what was asked and when; the audit log supplies
+/// who decided and when, from which the latency is derived. Decisions with
+/// no audit entry (e.g. made via `agent-intercom-ctl`) are still listed,
+/// just without those two details.
+async fn handle_decisions(
+    session_id: Option<&str>,
+    limit: u32,
+    state: &Arc<AppState>,
+) -> crate::Result<String> {
+    let session_repo = SessionRepo::new(Arc::clone(&state.db));
+    let session = history_session(session_id, &session_repo).await?;
+
+    let approvals = ApprovalRepo::new(Arc::clone(&state.db))
+        .list_decided(session.as_ref().map(|s| s.id.as_str()), limit)
+        .await?;
+    let scope = session.as_ref().map_or_else(
+        || "all sessions".to_owned(),
+        |s| format!("session `{}`", s.id),
+    );
+    if approvals.is_empty() {
+        return Ok(format!("No decided approval requests for {scope}."));
+    }
+
+    let ids: Vec<String> = approvals.iter().map(|a| a.id.clone()).collect();
+    let log_dir = audit::log_dir(state.config.default_workspace_root());
+    let decisions = tokio::task::spawn_blocking(move || {
+        let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+        audit::reader::find_decisions(&log_dir, &ids)
+    })
+    .await
+    .unwrap_or_default();
+
+    let mut channels: HashMap<String, Option<String>> = HashMap::new();
+    if let Some(ref session) = session {
+        channels.insert(session.id.clone(), session.channel_id.clone());
+    }
+
+    let mut lines = vec![format!(
+        "*Recent decisions for {scope}* ({} shown):",
+        approvals.len()
+    )];
+    for approval in &approvals {
+        if !channels.contains_key(&approval.session_id) {
+            let channel = session_repo
+                .get_by_id(&approval.session_id)
+                .await?
+                .and_then(|s| s.channel_id);
+            channels.insert(approval.session_id.clone(), channel);
+        }
+        let channel = channels.get(&approval.session_id).cloned().flatten();
+        lines.push(format_decision_line(
+            approval,
+            decisions.get(&approval.id),
+            channel.as_deref(),
+        ));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+/// Resolve the optional session a history view is scoped to, by ID or
+/// unique ID prefix.
+async fn history_session(
+    session_id: Option<&str>,
+    repo: &SessionRepo,
+) -> crate::Result<Option<Session>> {
+    let Some(id) = session_id else {
+        return Ok(None);
+    };
+    if let Some(session) = repo.get_by_id(id).await? {
+        return Ok(Some(session));
+    }
+    repo.get_by_prefix(id)
+        .await?
+        .map(Some)
+        .ok_or_else(|| crate::AppError::NotFound(format!("session {id} not found")))
+}
+
+/// Render one `decisions` line for `approval` and its audit entry.
+fn format_decision_line(
+    approval: &ApprovalRequest,
+    decision: Option<&AuditEntry>,
+    channel: Option<&str>,
+) -> String {
+    let outcome = match approval.status {
+        ApprovalStatus::Rejected => "\u{274c} *Rejected*",
+        ApprovalStatus::Consumed => "\u{2705} *Approved* (applied)",
+        _ => "\u{2705} *Approved*",
+    };
+    let mut line = format!(
+        "\u{2022} {outcome} — _{}_ `{}`",
+        blocks::slack_escape(&approval.title),
+        approval.file_path
+    );
+
+    match decision {
+        Some(entry) => {
+            let by = match entry.operator_id.as_deref() {
+                Some(operator) if operator.contains(':') => format!("`{operator}`"),
+                Some(operator) => format!("<@{operator}>"),
+                None => "unknown".to_owned(),
+            };
+            let latency = (entry.timestamp - approval.created_at).num_seconds();
+            let _ = write!(line, " · by {by} after {}", blocks::format_elapsed(latency));
+            if let Some(ref reason) = entry.reason {
+                let _ = write!(line, " · \"{}\"", blocks::slack_escape(reason));
+            }
+        }
+        None => line.push_str(" · decided outside Slack (no audit record)"),
+    }
+
+    if let (Some(channel), Some(ts)) = (channel, approval.slack_ts.as_deref()) {
+        let url = blocks::message_link(channel, ts, approval.thread_ts.as_deref());
+        let _ = write!(line, " · <{url}|view request>");
+    }
+    line
+}
+
+/// Handle `apply-at <request_id> <HH:MM>`: approve a pending request and
+/// schedule the server to apply it.
+async fn handle_apply_at(
+    args: &[&str],
+    user_id: &str,
+    state: &Arc<AppState>,
+) -> crate::Result<String> {
+    let [request, at] = args else {
+        return Err(crate::AppError::Config(
+            "usage: apply-at <request_id> <HH:MM>".into(),
+        ));
+    };
+    let at = NaiveTime::parse_from_str(at, "%H:%M")
+        .map_err(|_| crate::AppError::Config(format!("invalid time '{at}'; use HH:MM")))?;
+    let approval = scheduled_apply::schedule(state, request, at, user_id).await?;
+    let verify = if state.config.scheduled_apply.verify_commands.is_empty() {
+        ""
+    } else {
+        ", runs the verification commands,"
+    };
+    Ok(format!(
+        "Approved `{}` ({}). The server applies it at {}{verify} and reports in the session \
+         thread.",
+        approval.short_id,
+        approval.title,
+        approval
+            .apply_after
+            .map_or_else(String::new, blocks::slack_time)
+    ))
+}
+
+/// Share of false positives above which `stalls` suggests raising the
+/// inactivity threshold.
+const STALL_FALSE_POSITIVE_HINT_PERCENT: usize = 50;
+
+/// Resolved stalls needed before `stalls` offers tuning advice.
+const STALL_HINT_MIN_RESOLVED: usize = 5;
+
+/// Summarise stall outcomes and list the last `limit` stalls, newest first.
+///
+/// Statistics cover every recorded stall in scope, not just the listed
+/// ones, so the false-positive rate reflects the whole history.
+async fn handle_stalls(
+    session_id: Option<&str>,
+    limit: u32,
+    state: &Arc<AppState>,
+) -> crate::Result<String> {
+    let session_repo = SessionRepo::new(Arc::clone(&state.db));
+    let session = history_session(session_id, &session_repo).await?;
+
+    let alerts = StallAlertRepo::new(Arc::clone(&state.db))
+        .list_recent(session.as_ref().map(|s| s.id.as_str()))
+        .await?;
+    let scope = session.as_ref().map_or_else(
+        || "all sessions".to_owned(),
+        |s| format!("session `{}`", s.id),
+    );
+    if alerts.is_empty() {
+        return Ok(format!("No stalls recorded for {scope}."));
+    }
+
+    let totals = StallStats::from_alerts(&alerts);
+    let mut lines = vec![format!("*Stall history for {scope}*")];
+    let mut summary = format!("{} stalls · {} open", totals.total, totals.open);
+    if totals.escalated_open > 0 {
+        let _ = write!(summary, " ({} escalated)", totals.escalated_open);
+    }
+    lines.push(summary);
+    if let Some(percent) = totals.false_positive_percent() {
+        let mut outcomes = format!(
+            "False positives: {} of {} resolved ({percent}%) · recovered after nudge: {} · stopped: {}",
+            totals.self_recovered,
+            totals.resolved(),
+            totals.nudged,
+            totals.stopped
+        );
+        if let Some(median) = totals.median_resolution_seconds {
+            let _ = write!(
+                outcomes,
+                " · median time to resolve: {}",
+                blocks::format_elapsed(median)
+            );
+        }
+        lines.push(outcomes);
+        if totals.resolved() >= STALL_HINT_MIN_RESOLVED
+            && percent >= STALL_FALSE_POSITIVE_HINT_PERCENT
+        {
+            lines.push(format!(
+                "\u{1f4a1} Most stalls resolve on their own — consider raising \
+                 `[stall] inactivity_threshold_seconds` (currently {}s).",
+                state.config.stall.inactivity_threshold_seconds
+            ));
+        }
+    }
+
+    let limit = usize::try_from(limit).unwrap_or(usize::MAX);
+    lines.push(format!(
+        "*Recent stalls* ({} shown):",
+        alerts.len().min(limit)
+    ));
+    lines.extend(alerts.iter().take(limit).map(format_stall_line));
+    Ok(lines.join("\n"))
+}
+
+/// Render one `stalls` line for `alert`.
+fn format_stall_line(alert: &StallAlert) -> String {
+    let outcome = match alert.resolution {
+        Some(StallResolution::SelfRecovered) => "\u{2705} self-recovered",
+        Some(StallResolution::Nudged) => "\u{1f514} recovered after nudge",
+        Some(StallResolution::Stopped) => "\u{1f6d1} stopped",
+        None if alert.status == StallAlertStatus::Escalated => "\u{26a0}\u{fe0f} open (escalated)",
+        None => "\u{23f3} open",
+    };
+    let mut line = format!(
+        "\u{2022} {} `{}…` — idle {}",
+        blocks::slack_datetime(alert.created_at),
+        alert.session_id.chars().take(8).collect::<String>(),
+        blocks::format_elapsed(alert.idle_seconds)
+    );
+    if let Some(ref tool) = alert.last_tool {
+        let _ = write!(line, " after `{tool}`");
+    }
+    let _ = write!(line, " · {outcome}");
+    if alert.nudge_count > 0 {
+        let _ = write!(line, " · {} nudge(s)", alert.nudge_count);
+    }
+    if let Some(resolved_at) = alert.resolved_at {
+        let secs = (resolved_at - alert.created_at).num_seconds();
+        let _ = write!(line, " in {}", blocks::format_elapsed(secs));
+    }
+    line
+}
+
 // ── File browsing commands (T076, T077) ──────────────────────────────
 
 /// Handle the `list-files` slash command (T076).
@@ -1,7 +1,7 @@
 //! Slack interaction dispatch handler (T093, T094).
 //!
-//! Receives interactive payloads (button presses, modal submissions)
-//! via Socket Mode. Applies a centralized authorization guard (FR-013,
+//! Receives interactive payloads (button presses, modal submissions,
+//! global and message shortcuts) via Socket Mode. Applies a centralized authorization guard (FR-013,
 //! SC-009) and double-submission prevention (FR-022) before dispatching
 //! to the appropriate handler by `action_id` prefix.
 //!
@@ -17,16 +17,29 @@
 //! buttons are immediately replaced with a "Processing…" indicator via
 //! `chat.update` *before* the handler executes. This guarantees at-most-
 //! once semantics even if the handler is slow.
+//!
+//! ## Replay Protection
+//!
+//! Envelopes Slack redelivers, and actions stamped outside the replay
+//! window, are dropped before authorization (see [`crate::slack::replay`]).
+//!
+//! ## Ordering
+//!
+//! Interactions acting on the same approval, prompt, stall alert, wait or
+//! knowledge note are handled one at a time, in arrival order (see
+//! [`crate::slack::interaction_queue`]). A second click therefore sees the
+//! first click's decision already recorded and its message edits done.
 
 use std::sync::Arc;
 
+use chrono::Utc;
 use slack_morphism::prelude::{
     SlackBasicChannelInfo, SlackClient, SlackClientEventsUserState, SlackClientHyperHttpsConnector,
     SlackHistoryMessage, SlackInteractionEvent,
 };
 use tracing::{info, warn};
 
-use crate::slack::{blocks, handlers};
+use crate::slack::{blocks, handlers, interaction_queue, modal_context, replay};
 use crate::state::AppState;
 
 // ── Centralized authorization check (T093 / FR-013, SC-009) ──────────
@@ -83,12 +96,12 @@ async fn replace_buttons_with_processing(
 ///
 /// Applies a centralized authorization guard and double-submission
 /// prevention before dispatching to the correct handler by `action_id`
-/// prefix.
+/// prefix. Interactions on the same subject are serialized through
+/// [`AppState::interactions`].
 ///
 /// # Errors
 ///
 /// Returns an error if the interaction cannot be processed.
-#[allow(clippy::too_many_lines)] // Dispatch logic requires exhaustive match arms.
 pub async fn handle_interaction(
     event: SlackInteractionEvent,
     _client: Arc<SlackClient<SlackClientHyperHttpsConnector>>,
@@ -100,7 +113,40 @@ pub async fn handle_interaction(
         guard.get_user_state::<Arc<AppState>>().cloned()
     };
 
-    match &event {
+    // ── Replay protection ────────────────────────────────────────
+    // Slack redelivers envelopes it did not see acknowledged; acting on
+    // the copy would apply the same decision twice.
+    if let Some(ref app) = app_state {
+        let key = replay::interaction_key(&event);
+        if let Some(reason) =
+            app.replay_guard
+                .refuse(key.as_deref(), replay::interaction_time(&event), Utc::now())
+        {
+            warn!(key, reason, "dropping replayed slack interaction");
+            return Ok(());
+        }
+    }
+
+    // Interactions on the same approval, prompt or wait run one at a time.
+    let subject = interaction_queue::interaction_subject(&event);
+    match app_state {
+        Some(ref app) => {
+            let queue = Arc::clone(&app.interactions);
+            queue
+                .run(subject.as_deref(), dispatch(&event, app_state))
+                .await
+        }
+        None => dispatch(&event, None).await,
+    }
+}
+
+/// Authorize and route one interaction to its handler.
+#[allow(clippy::too_many_lines)] // Dispatch logic requires exhaustive match arms.
+async fn dispatch(
+    event: &SlackInteractionEvent,
+    app_state: Option<Arc<AppState>>,
+) -> slack_morphism::UserCallbackResult<()> {
+    match event {
         SlackInteractionEvent::BlockActions(block_event) => {
             let user_id = block_event
                 .user
@@ -137,14 +183,20 @@ pub async fn handle_interaction(
                 // the modal without submitting, the original buttons must
                 // remain clickable (FR-017). The ViewSubmission handler
                 // replaces the buttons with a final status once the modal
-                // is submitted.
-                let opens_modal = actions.iter().any(|a| {
+                // is submitted. Snoozing and paging through diff hunks
+                // leave the decision open, so their buttons stay as well.
+                let keeps_buttons = actions.iter().any(|a| {
+                    let id = a.action_id.to_string();
                     matches!(
-                        a.action_id.to_string().as_str(),
-                        "wait_resume_instruct" | "prompt_refine" | "approve_reject"
-                    )
+                        id.as_str(),
+                        "wait_resume_instruct"
+                            | "prompt_refine"
+                            | "approve_reject"
+                            | "snooze_approval"
+                            | "snooze_prompt"
+                    ) || id.starts_with("hunk_page_")
                 });
-                if !opens_modal {
+                if !keeps_buttons {
                     replace_buttons_with_processing(
                         block_event.channel.as_ref(),
                         block_event.message.as_ref(),
@@ -158,7 +210,19 @@ pub async fn handle_interaction(
                     info!(action_id, user_id, "dispatching block action");
 
                     // Route by action_id prefix to the correct handler.
-                    if action_id.starts_with("approve_") {
+                    if action_id.starts_with("snooze_") {
+                        if let Err(err) = handlers::snooze::handle_snooze_action(
+                            action,
+                            &user_id,
+                            block_event.channel.as_ref(),
+                            block_event.message.as_ref(),
+                            app,
+                        )
+                        .await
+                        {
+                            warn!(%err, action_id, "snooze action failed");
+                        }
+                    } else if action_id.starts_with("approve_") {
                         if let Err(err) = handlers::approval::handle_approval_action(
                             action,
                             &user_id,
@@ -171,6 +235,30 @@ pub async fn handle_interaction(
                         {
                             warn!(%err, action_id, "approval action failed");
                         }
+                    } else if action_id.starts_with("hunk_page_") {
+                        if let Err(err) = handlers::hunk_nav::handle_hunk_nav_action(
+                            action,
+                            &user_id,
+                            block_event.channel.as_ref(),
+                            block_event.message.as_ref(),
+                            app,
+                        )
+                        .await
+                        {
+                            warn!(%err, action_id, "hunk navigation failed");
+                        }
+                    } else if action_id.starts_with("revive_") {
+                        if let Err(err) = handlers::revive::handle_revive_action(
+                            action,
+                            &user_id,
+                            block_event.channel.as_ref(),
+                            block_event.message.as_ref(),
+                            app,
+                        )
+                        .await
+                        {
+                            warn!(%err, action_id, "revive action failed");
+                        }
                     } else if action_id.starts_with("prompt_") {
                         if let Err(err) = handlers::prompt::handle_prompt_action(
                             action,
@@ -196,6 +284,30 @@ pub async fn handle_interaction(
                         {
                             warn!(%err, action_id, "nudge action failed");
                         }
+                    } else if action_id.starts_with("completion_") {
+                        if let Err(err) = handlers::completion::handle_completion_action(
+                            action,
+                            &user_id,
+                            block_event.channel.as_ref(),
+                            block_event.message.as_ref(),
+                            app,
+                        )
+                        .await
+                        {
+                            warn!(%err, action_id, "completion action failed");
+                        }
+                    } else if action_id.starts_with("knowledge_") {
+                        if let Err(err) = handlers::knowledge::handle_knowledge_action(
+                            action,
+                            &user_id,
+                            block_event.channel.as_ref(),
+                            block_event.message.as_ref(),
+                            app,
+                        )
+                        .await
+                        {
+                            warn!(%err, action_id, "knowledge action failed");
+                        }
                     } else if action_id.starts_with("wait_") {
                         if let Err(err) = handlers::wait::handle_wait_action(
                             action,
@@ -209,6 +321,42 @@ pub async fn handle_interaction(
                         {
                             warn!(%err, action_id, "wait action failed");
                         }
+                    } else if action_id.starts_with("confirm_") {
+                        if let Err(err) = handlers::apply_confirm::handle_apply_confirm_action(
+                            action,
+                            &user_id,
+                            block_event.channel.as_ref(),
+                            block_event.message.as_ref(),
+                            app,
+                        )
+                        .await
+                        {
+                            warn!(%err, action_id, "apply confirmation action failed");
+                        }
+                    } else if action_id.starts_with("conflict_") {
+                        if let Err(err) = handlers::conflict::handle_conflict_action(
+                            action,
+                            &user_id,
+                            block_event.channel.as_ref(),
+                            block_event.message.as_ref(),
+                            app,
+                        )
+                        .await
+                        {
+                            warn!(%err, action_id, "conflict action failed");
+                        }
+                    } else if action_id.starts_with("policy_drift_") {
+                        if let Err(err) = handlers::policy_drift::handle_policy_drift_action(
+                            action,
+                            &user_id,
+                            block_event.channel.as_ref(),
+                            block_event.message.as_ref(),
+                            app,
+                        )
+                        .await
+                        {
+                            warn!(%err, action_id, "policy drift action failed");
+                        }
                     } else if action_id.starts_with("auto_approve_") {
                         if let Err(err) = handlers::command_approve::handle_auto_approve_action(
                             action,
@@ -269,14 +417,83 @@ pub async fn handle_interaction(
             if callback_id.is_empty() {
                 info!(user_id, "modal closed (no callback_id)");
             } else {
-                let mut ctx = app.pending_modal_contexts.lock().await;
-                let removed = ctx.remove(&callback_id).is_some();
+                let removed = modal_context::take(app, &callback_id).await.is_some();
                 info!(
                     user_id,
                     callback_id, removed, "modal dismissed without submission"
                 );
             }
         }
+        SlackInteractionEvent::Shortcut(shortcut_event) => {
+            let user_id = shortcut_event.user.id.to_string();
+
+            let Some(ref app) = app_state else {
+                warn!("app state not available; cannot process shortcut");
+                return Ok(());
+            };
+
+            if !is_authorized(&user_id, app) {
+                return Ok(());
+            }
+
+            let callback_id = shortcut_event.callback_id.to_string();
+            info!(callback_id, user_id, "dispatching global shortcut");
+
+            if let Err(err) = handlers::shortcut::handle_global_shortcut(
+                &callback_id,
+                &user_id,
+                &shortcut_event.trigger_id,
+                app,
+            )
+            .await
+            {
+                warn!(%err, callback_id, "global shortcut failed");
+            }
+        }
+        SlackInteractionEvent::MessageAction(action_event) => {
+            let user_id = action_event.user.id.to_string();
+
+            let Some(ref app) = app_state else {
+                warn!("app state not available; cannot process message shortcut");
+                return Ok(());
+            };
+
+            if !is_authorized(&user_id, app) {
+                return Ok(());
+            }
+
+            let callback_id = action_event.callback_id.to_string();
+            let (Some(channel), Some(message)) = (&action_event.channel, &action_event.message)
+            else {
+                warn!(
+                    callback_id,
+                    "message shortcut without channel or message; ignoring"
+                );
+                return Ok(());
+            };
+            let text = message.content.text.as_deref().unwrap_or_default();
+            // Steer the thread the message belongs to, or the message's own
+            // thread when it is top-level.
+            let thread_ts = message
+                .origin
+                .thread_ts
+                .as_ref()
+                .unwrap_or(&message.origin.ts);
+            info!(callback_id, user_id, "dispatching message shortcut");
+
+            if let Err(err) = handlers::shortcut::handle_message_shortcut(
+                &callback_id,
+                &user_id,
+                text,
+                channel.id.as_ref(),
+                thread_ts.as_ref(),
+                app,
+            )
+            .await
+            {
+                warn!(%err, callback_id, "message shortcut failed");
+            }
+        }
         _ => {
             info!(?event, "unhandled interaction event type");
         }
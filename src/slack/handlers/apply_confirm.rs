@@ -0,0 +1,121 @@
+//! Protected-path apply confirmation handler.
+//!
+//! Handles the Confirm Apply and Cancel buttons that `check_diff` posts
+//! before writing a file matching `[protected_paths]`. The same people who
+//! may approve the request may confirm it: the session owner, or a mapped
+//! code owner when CODEOWNERS routing requires one.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use slack_morphism::prelude::{
+    SlackBasicChannelInfo, SlackHistoryMessage, SlackInteractionActionInfo,
+};
+use tracing::{info, warn};
+
+use crate::diff::ownership;
+use crate::orchestrator::apply_guard::ApplyDecision;
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::blocks;
+use crate::slack::handlers::check_approval_authority;
+use crate::state::AppState;
+
+/// Process a single protected-path confirmation button action from Slack.
+///
+/// # Arguments
+///
+/// * `action` — the `SlackInteractionActionInfo` with `action_id` and
+///   `value` (the approval request ID).
+/// * `user_id` — Slack user ID of the operator who clicked.
+/// * `channel` — channel where the card lives.
+/// * `message` — the original Slack message (for `chat.update`).
+/// * `state` — shared application state.
+///
+/// # Errors
+///
+/// Returns an error string if the request is unknown or the user may not
+/// decide it.
+pub async fn handle_apply_confirm_action(
+    action: &SlackInteractionActionInfo,
+    user_id: &str,
+    channel: Option<&SlackBasicChannelInfo>,
+    message: Option<&SlackHistoryMessage>,
+    state: &Arc<AppState>,
+) -> Result<(), String> {
+    let action_id = action.action_id.to_string();
+    let request_id = action
+        .value
+        .as_deref()
+        .ok_or_else(|| "apply confirmation action missing request_id value".to_owned())?;
+
+    let decision = match action_id.as_str() {
+        "confirm_apply" => ApplyDecision::Confirmed {
+            user_id: user_id.to_owned(),
+        },
+        "confirm_cancel" => ApplyDecision::Cancelled {
+            user_id: user_id.to_owned(),
+        },
+        _ => return Err(format!("unknown apply confirmation action_id: {action_id}")),
+    };
+
+    // ── Same authority as the approval itself (FR-031) ───
+    let approval = ApprovalRepo::new(Arc::clone(&state.db))
+        .get_by_id(request_id)
+        .await
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| format!("approval request {request_id} not found"))?;
+    if let Ok(Some(session)) = SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(&approval.session_id)
+        .await
+    {
+        let required = ownership::required_approvers(
+            &state.config.codeowners,
+            Path::new(&session.workspace_root),
+            &approval.file_path,
+        );
+        if let Err(err) = check_approval_authority(&session, &required, user_id) {
+            warn!(
+                user_id,
+                request_id, "apply confirmation rejected: not an approver"
+            );
+            return Err(err.to_string());
+        }
+    }
+
+    let confirmed = matches!(decision, ApplyDecision::Confirmed { .. });
+    let status_text = if !state.apply_confirmations.resolve(request_id, decision) {
+        format!(
+            "\u{23f1}\u{fe0f} *Confirmation expired* \u{2014} `{}` was not written; you are \
+             asked again if the agent retries `check_diff`",
+            approval.file_path
+        )
+    } else if confirmed {
+        info!(request_id, user_id, "protected apply confirmed");
+        format!(
+            "\u{2705} *Apply confirmed* by <@{user_id}> \u{2014} `{}`",
+            approval.file_path
+        )
+    } else {
+        info!(request_id, user_id, "protected apply cancelled");
+        format!(
+            "\u{274c} *Apply cancelled* by <@{user_id}> \u{2014} `{}`",
+            approval.file_path
+        )
+    };
+
+    // ── Replace buttons with static status (FR-022) ──────
+    if let Some(ref slack) = state.slack {
+        let msg_ts = message.map(|m| m.origin.ts.clone());
+        let chan_id = channel.map(|c| c.id.clone());
+
+        if let (Some(ts), Some(ch)) = (msg_ts, chan_id) {
+            let replacement_blocks = vec![blocks::text_section(&status_text)];
+            if let Err(err) = slack.update_message(ch, ts, replacement_blocks).await {
+                warn!(%err, request_id, "failed to replace apply confirmation buttons");
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -2,10 +2,13 @@
 //!
 //! Handles Accept and Reject button presses from Slack interactive messages
 //! for approval requests. Verifies the acting user belongs to
-//! `authorized_user_ids` (FR-013), updates the database, resolves the
+//! `authorized_user_ids` (FR-013) and may decide the request (session owner,
+//! or a mapped code owner under CODEOWNERS routing), updates the database, resolves the
 //! blocking oneshot channel, and replaces interactive buttons with a
-//! static status line (FR-022).
+//! static status line (FR-022). A click on a request that was already
+//! resolved only shows the stored outcome (see [`super::resolved`]).
 
+use std::path::Path;
 use std::sync::Arc;
 
 use slack_morphism::prelude::{
@@ -14,11 +17,12 @@ use slack_morphism::prelude::{
 use tracing::{info, warn};
 
 use crate::audit::{AuditEntry, AuditEventType};
+use crate::diff::ownership;
 use crate::models::approval::ApprovalStatus;
 use crate::persistence::approval_repo::ApprovalRepo;
 use crate::persistence::session_repo::SessionRepo;
-use crate::slack::blocks;
-use crate::slack::handlers::{check_session_ownership, command_approve};
+use crate::slack::handlers::{check_approval_authority, command_approve, resolved, snooze};
+use crate::slack::{approval_fanout, blocks, modal_context};
 use crate::state::{AppState, ApprovalResponse};
 
 /// Process a single approval button action from Slack.
@@ -154,30 +158,51 @@ pub async fn handle_approval_action(
 
     // ── T068c / FR-031: Verify session ownership ─────────
     // Look up the approval record to find its session, then confirm the
-    // acting user is the session owner. Command approvals (handled above)
+    // acting user is the session owner — or, when CODEOWNERS routing
+    // requires it, a mapped code owner of the file. Command approvals (handled above)
     // have no DB record and are therefore exempt from this check.
     // Also capture session_id for thread-reply fallback cleanup (F-20).
-    let approval_session_id: String = {
+    let (approval_session_id, stored_status) = {
         let approval_repo = ApprovalRepo::new(Arc::clone(&state.db));
         if let Ok(Some(record)) = approval_repo.get_by_id(request_id).await {
             let session_repo = SessionRepo::new(Arc::clone(&state.db));
             if let Ok(Some(session)) = session_repo.get_by_id(&record.session_id).await {
-                if let Err(err) = check_session_ownership(&session, user_id) {
+                let required = ownership::required_approvers(
+                    &state.config.codeowners,
+                    Path::new(&session.workspace_root),
+                    &record.file_path,
+                );
+                if let Err(err) = check_approval_authority(&session, &required, user_id) {
                     warn!(
                         user_id,
                         request_id,
                         owner = %session.owner_user_id,
+                        code_owners = required.len(),
                         "approval action rejected: non-owner attempt (FR-031)"
                     );
                     return Err(err.to_string());
                 }
             }
-            record.session_id
+            (record.session_id, Some(record.status))
         } else {
-            String::new()
+            (String::new(), None)
         }
     };
 
+    // ── Late click on a resolved request ─────────────────
+    // The stored outcome stands; show it instead of deciding again.
+    if let Some(status) = stored_status.filter(|s| *s != ApprovalStatus::Pending) {
+        info!(
+            request_id,
+            ?status,
+            user_id,
+            "approval click after resolution"
+        );
+        let outcome = resolved::approval_outcome(status);
+        resolved::report(state, resolved::coords(channel, message), user_id, &outcome).await;
+        return Ok(());
+    }
+
     // ── Determine status from action_id ──────────────────
     let (status, reason) = if action_id == "approve_accept" {
         (ApprovalStatus::Approved, None::<String>)
@@ -194,8 +219,7 @@ pub async fn handle_approval_action(
             let msg_ts = message.map(|m| m.origin.ts.to_string());
             let chan_id = channel.map(|c| c.id.to_string());
             if let (Some(ts), Some(ch)) = (msg_ts, chan_id) {
-                let mut ctx = state.pending_modal_contexts.lock().await;
-                ctx.insert(callback_id.clone(), (ch, ts));
+                modal_context::remember(state, &callback_id, ch, ts).await;
             }
 
             let modal = blocks::instruction_modal(
@@ -206,8 +230,7 @@ pub async fn handle_approval_action(
             if let Err(err) = slack.open_modal(trigger_id.clone(), modal).await {
                 warn!(%err, request_id, "failed to open rejection reason modal; activating thread-reply fallback (F-16)");
                 // Clean up cached context on failure.
-                let mut ctx = state.pending_modal_contexts.lock().await;
-                ctx.remove(&callback_id);
+                let _ = modal_context::take(state, &callback_id).await;
                 // F-16/F-17: register thread-reply fallback when modal is unavailable.
                 // Use the parent thread_ts (root of the Slack thread) as the map key so
                 // that incoming replies, which report thread_ts = root, find the entry.
@@ -223,6 +246,7 @@ pub async fn handle_approval_action(
                     let button_msg_ts = message.map(|m| m.origin.ts.clone());
                     let state_clone = Arc::clone(state);
                     let request_id_owned = request_id.to_owned();
+                    let session_id_owned = approval_session_id.clone();
                     let user_id_owned = user_id.to_owned();
                     crate::slack::handlers::thread_reply::activate_thread_reply_fallback(
                         chan_id.as_str(),
@@ -236,18 +260,31 @@ pub async fn handle_approval_action(
                         request_id,
                         move |reply_text| async move {
                             let approval_repo = ApprovalRepo::new(Arc::clone(&state_clone.db));
-                            if let Err(db_err) = approval_repo
-                                .update_status(&request_id_owned, ApprovalStatus::Rejected)
+                            match approval_repo
+                                .decide_with_reason(
+                                    &request_id_owned,
+                                    ApprovalStatus::Rejected,
+                                    Some(&reply_text),
+                                )
                                 .await
                             {
-                                warn!(
+                                Ok(true) => {}
+                                Ok(false) => {
+                                    warn!(
+                                        request_id = request_id_owned,
+                                        "thread-reply fallback: request already resolved; reply ignored"
+                                    );
+                                    return;
+                                }
+                                Err(db_err) => warn!(
                                     request_id = request_id_owned,
                                     %db_err,
                                     "thread-reply fallback: failed to update approval status in DB"
-                                );
+                                ),
                             }
                             if let Some(ref logger) = state_clone.audit_logger {
                                 let entry = AuditEntry::new(AuditEventType::Rejection)
+                                    .with_session(session_id_owned.clone())
                                     .with_request_id(request_id_owned.clone())
                                     .with_operator(user_id_owned.clone())
                                     .with_reason(reply_text.clone());
@@ -259,7 +296,8 @@ pub async fn handle_approval_action(
                                 }
                             }
                             if let Err(driver_err) = state_clone
-                                .driver
+                                .driver_for(&session_id_owned)
+                                .await
                                 .resolve_clearance(&request_id_owned, false, Some(reply_text))
                                 .await
                             {
@@ -277,6 +315,9 @@ pub async fn handle_approval_action(
                 return Err(format!("failed to open rejection reason modal: {err}"));
             }
         }
+        // The operator is writing a reason; do not let the request time
+        // out under them.
+        extend_for_modal(state, request_id, channel, message).await;
         // Return early — the rejection is NOT finalised here; it will be
         // completed when the ViewSubmission event arrives from the modal.
         return Ok(());
@@ -286,10 +327,28 @@ pub async fn handle_approval_action(
 
     // ── Update DB record ─────────────────────────────────
     let approval_repo = ApprovalRepo::new(Arc::clone(&state.db));
-    approval_repo
-        .update_status(request_id, status)
+    let decided = approval_repo
+        .decide(request_id, status)
         .await
         .map_err(|err| format!("failed to update approval status: {err}"))?;
+    if !decided && stored_status.is_some() {
+        // Resolved (e.g. timed out) between the check above and now.
+        let current = approval_repo
+            .get_by_id(request_id)
+            .await
+            .ok()
+            .flatten()
+            .map_or(ApprovalStatus::Expired, |record| record.status);
+        info!(
+            request_id,
+            ?current,
+            user_id,
+            "approval click lost the race"
+        );
+        let outcome = resolved::approval_outcome(current);
+        resolved::report(state, resolved::coords(channel, message), user_id, &outcome).await;
+        return Ok(());
+    }
 
     info!(
         request_id,
@@ -305,6 +364,7 @@ pub async fn handle_approval_action(
             _ => AuditEventType::Rejection,
         };
         let mut entry = AuditEntry::new(event_type)
+            .with_session(approval_session_id.clone())
             .with_request_id(request_id.to_owned())
             .with_operator(user_id.to_owned());
         if let Some(ref r) = reason {
@@ -319,7 +379,8 @@ pub async fn handle_approval_action(
     {
         let approved = matches!(status, ApprovalStatus::Approved);
         if let Err(err) = state
-            .driver
+            .driver_for(&approval_session_id)
+            .await
             .resolve_clearance(request_id, approved, reason.clone())
             .await
         {
@@ -352,6 +413,9 @@ pub async fn handle_approval_action(
             {
                 warn!(%err, request_id, "failed to replace approval buttons");
             }
+            // A fanned-out request's other copies are rewritten by the
+            // waiting `ask_approval` call; this one already shows who decided.
+            approval_fanout::mark_resolved(state, request_id, &ch.0, &ts.0).await;
         } else {
             warn!(
                 request_id,
@@ -387,3 +451,26 @@ pub async fn handle_approval_action(
 
     Ok(())
 }
+
+/// Extend `request_id` for the rejection modal just opened and move the
+/// countdown on its card, if the timeout was close.
+async fn extend_for_modal(
+    state: &AppState,
+    request_id: &str,
+    channel: Option<&SlackBasicChannelInfo>,
+    message: Option<&SlackHistoryMessage>,
+) {
+    let (Some(slack), Some(channel), Some(message)) = (state.slack.as_ref(), channel, message)
+    else {
+        return;
+    };
+    let mut card = message.content.blocks.clone().unwrap_or_default();
+    if snooze::extend_for_activity(state, request_id, &mut card) && !card.is_empty() {
+        if let Err(err) = slack
+            .update_message(channel.id.clone(), message.origin.ts.clone(), card)
+            .await
+        {
+            warn!(%err, request_id, "failed to update approval countdown");
+        }
+    }
+}
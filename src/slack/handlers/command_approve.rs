@@ -5,7 +5,7 @@
 //! the workspace's `.intercom/settings.json` policy file, enabling future
 //! auto-approval.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use slack_morphism::prelude::{
@@ -109,6 +109,16 @@ pub fn generate_pattern(command: &str) -> String {
 ///
 /// Returns `crate::AppError` on I/O or JSON serialisation failures.
 pub fn write_pattern_to_settings(settings_path: &Path, command: &str) -> crate::Result<()> {
+    add_pattern_to_settings(settings_path, &generate_pattern(command))
+}
+
+/// Add the regex `pattern` itself to `settings_path`, as
+/// [`write_pattern_to_settings`] does for a command's generated pattern.
+///
+/// # Errors
+///
+/// Returns `crate::AppError` on I/O or JSON serialisation failures.
+pub fn add_pattern_to_settings(settings_path: &Path, pattern: &str) -> crate::Result<()> {
     // Load existing settings or start with an empty object.
     let mut root: serde_json::Value = if settings_path.exists() {
         let raw = std::fs::read_to_string(settings_path)
@@ -118,8 +128,6 @@ pub fn write_pattern_to_settings(settings_path: &Path, command: &str) -> crate::
         serde_json::json!({})
     };
 
-    let pattern = generate_pattern(command);
-
     // Write to `chat.tools.terminal.autoApprove` as a map — the same format
     // used by VS Code in *.code-workspace and .vscode/settings.json.
     let obj = root
@@ -131,9 +139,9 @@ pub fn write_pattern_to_settings(settings_path: &Path, command: &str) -> crate::
     let map = commands_val.as_object_mut().ok_or_else(|| {
         crate::AppError::Config("chat.tools.terminal.autoApprove is not an object".into())
     })?;
-    if !map.contains_key(&pattern) {
+    if !map.contains_key(pattern) {
         map.insert(
-            pattern,
+            pattern.to_owned(),
             serde_json::json!({ "approve": true, "matchCommandLine": true }),
         );
     }
@@ -156,7 +164,7 @@ pub fn write_pattern_to_settings(settings_path: &Path, command: &str) -> crate::
 /// Returns the portion of `line` before the first comment-starting `//` that
 /// is not enclosed in double quotes.  The original string is returned unchanged
 /// if no such `//` is found.
-fn strip_jsonc_line_comment(line: &str) -> std::borrow::Cow<'_, str> {
+pub(crate) fn strip_jsonc_line_comment(line: &str) -> std::borrow::Cow<'_, str> {
     let mut in_string = false;
     let mut escape_next = false;
     let chars: Vec<char> = line.chars().collect();
@@ -308,14 +316,30 @@ pub fn write_pattern_to_workspace_file(
     workspace_root: &Path,
     command: &str,
 ) -> crate::Result<bool> {
-    // Find the first *.code-workspace file in the workspace root (not recursive).
-    let workspace_file = std::fs::read_dir(workspace_root)
+    add_pattern_to_workspace_file(workspace_root, &generate_pattern(command))
+}
+
+/// The first `*.code-workspace` file in `workspace_root` (not recursive).
+///
+/// # Errors
+///
+/// Returns `crate::AppError::Config` if the workspace root cannot be read.
+pub fn find_workspace_file(workspace_root: &Path) -> crate::Result<Option<PathBuf>> {
+    Ok(std::fs::read_dir(workspace_root)
         .map_err(|e| crate::AppError::Config(format!("read workspace root: {e}")))?
         .filter_map(std::result::Result::ok)
         .map(|e| e.path())
-        .find(|p| p.extension().and_then(|s| s.to_str()) == Some("code-workspace"));
+        .find(|p| p.extension().and_then(|s| s.to_str()) == Some("code-workspace")))
+}
 
-    let Some(ws_path) = workspace_file else {
+/// Add the regex `pattern` itself to the workspace's `*.code-workspace`
+/// file, as [`write_pattern_to_workspace_file`] does for a command.
+///
+/// # Errors
+///
+/// Returns `crate::AppError` on I/O or JSON parse/serialisation failures.
+pub fn add_pattern_to_workspace_file(workspace_root: &Path, pattern: &str) -> crate::Result<bool> {
+    let Some(ws_path) = find_workspace_file(workspace_root)? else {
         return Ok(false);
     };
 
@@ -331,8 +355,6 @@ pub fn write_pattern_to_workspace_file(
     let mut root: serde_json::Value = serde_json::from_str(&stripped)
         .map_err(|e| crate::AppError::Config(format!("parse workspace file: {e}")))?;
 
-    let pattern = generate_pattern(command);
-
     // Navigate to (or create) settings.chat.tools.terminal.autoApprove.
     let obj = root
         .as_object_mut()
@@ -352,7 +374,7 @@ pub fn write_pattern_to_workspace_file(
 
     // Early return if pattern already present — skips the full file rewrite
     // and preserves any user-maintained JSONC comments in the workspace file.
-    if map.contains_key(&pattern) {
+    if map.contains_key(pattern) {
         return Ok(true);
     }
 
@@ -360,7 +382,7 @@ pub fn write_pattern_to_workspace_file(
     // Try to insert the new entry into the raw (comment-preserved) file text
     // via targeted brace-matching rather than re-serialising the entire JSON.
     // Falls back to a full rewrite only if the autoApprove section is absent.
-    if let Some(modified) = try_insert_pattern_preserving(&raw, &pattern) {
+    if let Some(modified) = try_insert_pattern_preserving(&raw, pattern) {
         let parent = ws_path.parent().unwrap_or(std::path::Path::new("."));
         let tmp = tempfile::NamedTempFile::new_in(parent)
             .map_err(|e| crate::AppError::Config(format!("create temp file: {e}")))?;
@@ -376,7 +398,7 @@ pub fn write_pattern_to_workspace_file(
     warn!("autoApprove section not found in workspace file; falling back to full rewrite");
 
     map.insert(
-        pattern,
+        pattern.to_owned(),
         serde_json::json!({ "approve": true, "matchCommandLine": true }),
     );
 
@@ -410,6 +432,16 @@ pub fn write_pattern_to_vscode_settings(
     workspace_root: &Path,
     command: &str,
 ) -> crate::Result<bool> {
+    add_pattern_to_vscode_settings(workspace_root, &generate_pattern(command))
+}
+
+/// Add the regex `pattern` itself to `.vscode/settings.json`, as
+/// [`write_pattern_to_vscode_settings`] does for a command.
+///
+/// # Errors
+///
+/// Returns `crate::AppError` on I/O or JSON parse/serialisation failures.
+pub fn add_pattern_to_vscode_settings(workspace_root: &Path, pattern: &str) -> crate::Result<bool> {
     let vscode_path = workspace_root.join(".vscode").join("settings.json");
     if !vscode_path.exists() {
         return Ok(false);
@@ -425,8 +457,6 @@ pub fn write_pattern_to_vscode_settings(
     let mut root: serde_json::Value = serde_json::from_str(&stripped)
         .map_err(|e| crate::AppError::Config(format!("parse .vscode/settings.json: {e}")))?;
 
-    let pattern = generate_pattern(command);
-
     // The autoApprove map is at the top level in .vscode/settings.json
     // (unlike *.code-workspace where it is nested under "settings").
     let obj = root.as_object_mut().ok_or_else(|| {
@@ -441,12 +471,12 @@ pub fn write_pattern_to_vscode_settings(
 
     // Early return if pattern already present — skips the full file rewrite
     // and preserves any user-maintained JSONC comments in the settings file.
-    if map.contains_key(&pattern) {
+    if map.contains_key(pattern) {
         return Ok(true);
     }
 
     // ── Comment-preserving insertion (RI-05) ───────────────
-    if let Some(modified) = try_insert_pattern_preserving(&raw, &pattern) {
+    if let Some(modified) = try_insert_pattern_preserving(&raw, pattern) {
         let parent = vscode_path.parent().unwrap_or(std::path::Path::new("."));
         let tmp = tempfile::NamedTempFile::new_in(parent)
             .map_err(|e| crate::AppError::Config(format!("create temp file: {e}")))?;
@@ -461,7 +491,7 @@ pub fn write_pattern_to_vscode_settings(
     warn!("autoApprove section not found in .vscode/settings.json; falling back to full rewrite");
 
     map.insert(
-        pattern,
+        pattern.to_owned(),
         serde_json::json!({ "approve": true, "matchCommandLine": true }),
     );
 
@@ -0,0 +1,173 @@
+//! Completion prompt interaction handler.
+//!
+//! Handles the Terminate Session and Assign New Task buttons on the prompt
+//! posted when an agent that reported all of its work done goes quiet.
+//! Terminating runs the same path as `/intercom session-clear`; assigning
+//! asks the operator to reply in the thread with the next task and steers
+//! the agent with it.
+
+use std::sync::Arc;
+
+use slack_morphism::prelude::{
+    SlackBasicChannelInfo, SlackHistoryMessage, SlackInteractionActionInfo,
+};
+use tracing::{info, warn};
+
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::handlers::{check_session_ownership, steer, thread_reply};
+use crate::slack::{blocks, commands};
+use crate::state::AppState;
+
+/// Process a single completion prompt button action from Slack.
+///
+/// # Arguments
+///
+/// * `action` — the `SlackInteractionActionInfo` with `action_id` and
+///   `value` (the `session_id`).
+/// * `user_id` — Slack user ID of the operator who clicked.
+/// * `channel` — channel where the prompt message lives.
+/// * `message` — the original Slack message (for `chat.update` and the
+///   thread to collect the new task in).
+/// * `state` — shared application state.
+///
+/// # Errors
+///
+/// Returns an error string if processing fails.
+pub async fn handle_completion_action(
+    action: &SlackInteractionActionInfo,
+    user_id: &str,
+    channel: Option<&SlackBasicChannelInfo>,
+    message: Option<&SlackHistoryMessage>,
+    state: &Arc<AppState>,
+) -> Result<(), String> {
+    let action_id = action.action_id.to_string();
+    let session_id = action
+        .value
+        .as_deref()
+        .ok_or_else(|| "completion action missing session_id value".to_owned())?;
+
+    // ── Verify authorized user (FR-013) ──────────────────
+    if !state
+        .config
+        .authorized_user_ids
+        .contains(&user_id.to_owned())
+    {
+        warn!(
+            user_id,
+            session_id, "unauthorized user attempted completion action"
+        );
+        return Err("user not authorized for completion actions".into());
+    }
+
+    let session = SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(session_id)
+        .await
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| format!("session {session_id} not found"))?;
+    check_session_ownership(&session, user_id).map_err(|err| err.to_string())?;
+
+    let chan_id = channel.map(|c| c.id.to_string());
+
+    let status_text = match action_id.as_str() {
+        "completion_terminate" => {
+            commands::dispatch_command(
+                "session-clear",
+                &[session_id],
+                user_id,
+                chan_id.as_deref().unwrap_or_default(),
+                state,
+            )
+            .await
+            .map_err(|err| format!("failed to terminate session: {err}"))?;
+
+            if let Some(ref detectors) = state.stall_detectors {
+                detectors.lock().await.remove(session_id);
+            }
+
+            info!(session_id, user_id, "finished session terminated");
+            format!("\u{1f6d1} *Terminated* by <@{user_id}>")
+        }
+        "completion_assign" => {
+            let Some(ref slack) = state.slack else {
+                return Err("slack unavailable for new task reply".into());
+            };
+            // Collect the task in the prompt's thread, or start one under
+            // the prompt when it was posted at the top level.
+            let thread_ts = message.map(|m| {
+                m.origin
+                    .thread_ts
+                    .as_ref()
+                    .map_or_else(|| m.origin.ts.0.clone(), |ts| ts.0.clone())
+            });
+            let (Some(thread_ts), Some(chan_id)) = (thread_ts, chan_id) else {
+                return Err("missing message coordinates for new task reply".into());
+            };
+
+            let state_clone = Arc::clone(state);
+            let session_id_owned = session_id.to_owned();
+            thread_reply::activate_thread_reply_fallback(
+                chan_id.as_str(),
+                thread_ts.as_str(),
+                session_id.to_owned(),
+                user_id.to_owned(),
+                "Please reply in this thread with the agent's next task.",
+                message.map(|m| m.origin.ts.clone()),
+                slack,
+                Arc::clone(&state.pending_thread_replies),
+                &format!("completion_assign:{session_id}"),
+                move |reply_text| async move {
+                    assign_task(&state_clone, &session_id_owned, &reply_text).await;
+                },
+            )
+            .await?;
+            return Ok(());
+        }
+        _ => return Err(format!("unknown completion action_id: {action_id}")),
+    };
+
+    // ── Replace buttons with static status (FR-022) ──────
+    if let Some(ref slack) = state.slack {
+        let msg_ts = message.map(|m| m.origin.ts.clone());
+        let chan_id = channel.map(|c| c.id.clone());
+
+        if let (Some(ts), Some(ch)) = (msg_ts, chan_id) {
+            let replacement_blocks = vec![blocks::text_section(&status_text)];
+            if let Err(err) = slack.update_message(ch, ts, replacement_blocks).await {
+                warn!(%err, session_id, "failed to replace completion prompt buttons");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Steer a finished session with the operator's next task.
+///
+/// The session's work is no longer complete, so the completion grace stops
+/// applying until its next progress snapshot.
+async fn assign_task(state: &AppState, session_id: &str, task: &str) {
+    let session = match SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(session_id)
+        .await
+    {
+        Ok(Some(session)) => session,
+        Ok(None) => {
+            warn!(session_id, "session ended before its new task arrived");
+            return;
+        }
+        Err(err) => {
+            warn!(%err, session_id, "failed to load session for new task");
+            return;
+        }
+    };
+    if let Some(ref detectors) = state.stall_detectors {
+        if let Some(handle) = detectors.lock().await.get(session_id) {
+            handle.set_work_complete(false);
+            handle.reset();
+        }
+    }
+    match steer::steer_session(state, &session, task).await {
+        Ok(()) => info!(session_id, "new task assigned to finished session"),
+        Err(err) => warn!(%err, session_id, "failed to assign new task"),
+    }
+}
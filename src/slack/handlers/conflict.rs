@@ -0,0 +1,159 @@
+//! Apply conflict arbitration handler.
+//!
+//! Handles the Force Apply, Merge View and Reject buttons that `check_diff`
+//! posts when an approved diff cannot be applied as proposed. The same
+//! people who may approve the request may settle its conflicts: the session
+//! owner, or a mapped code owner when CODEOWNERS routing requires one.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use slack_morphism::prelude::{
+    SlackBasicChannelInfo, SlackHistoryMessage, SlackInteractionActionInfo,
+};
+use tracing::{info, warn};
+
+use crate::diff::ownership;
+use crate::models::conflict::ConflictKind;
+use crate::orchestrator::apply_guard::ApplyDecision;
+use crate::persistence::conflict_repo::ConflictRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::blocks;
+use crate::slack::handlers::check_approval_authority;
+use crate::state::AppState;
+
+/// Process a single apply conflict button action from Slack.
+///
+/// # Arguments
+///
+/// * `action` — the `SlackInteractionActionInfo` with `action_id` and
+///   `value` (the conflict record ID).
+/// * `user_id` — Slack user ID of the operator who clicked.
+/// * `channel` — channel where the card lives.
+/// * `message` — the original Slack message (for `chat.update`).
+/// * `state` — shared application state.
+///
+/// # Errors
+///
+/// Returns an error string if the conflict is unknown, the user may not
+/// decide it, or Force Apply is pressed on a diff that no longer applies.
+pub async fn handle_conflict_action(
+    action: &SlackInteractionActionInfo,
+    user_id: &str,
+    channel: Option<&SlackBasicChannelInfo>,
+    message: Option<&SlackHistoryMessage>,
+    state: &Arc<AppState>,
+) -> Result<(), String> {
+    let action_id = action.action_id.to_string();
+    let conflict_id = action
+        .value
+        .as_deref()
+        .ok_or_else(|| "conflict action missing conflict_id value".to_owned())?;
+
+    let conflict = ConflictRepo::new(Arc::clone(&state.db))
+        .get_by_id(conflict_id)
+        .await
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| format!("apply conflict {conflict_id} not found"))?;
+
+    let decision = match action_id.as_str() {
+        "conflict_force" if conflict.kind == ConflictKind::HunkConflict => {
+            return Err(format!(
+                "apply conflict {conflict_id} cannot be forced: the diff no longer applies"
+            ));
+        }
+        "conflict_force" => ApplyDecision::Confirmed {
+            user_id: user_id.to_owned(),
+        },
+        "conflict_merge" => ApplyDecision::Merge {
+            user_id: user_id.to_owned(),
+        },
+        "conflict_reject" => ApplyDecision::Cancelled {
+            user_id: user_id.to_owned(),
+        },
+        _ => return Err(format!("unknown conflict action_id: {action_id}")),
+    };
+
+    // ── Same authority as the approval itself (FR-031) ───
+    if let Ok(Some(session)) = SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(&conflict.session_id)
+        .await
+    {
+        let required = ownership::required_approvers(
+            &state.config.codeowners,
+            Path::new(&session.workspace_root),
+            &conflict.file_path,
+        );
+        if let Err(err) = check_approval_authority(&session, &required, user_id) {
+            warn!(
+                user_id,
+                conflict_id, "conflict decision rejected: not an approver"
+            );
+            return Err(err.to_string());
+        }
+    }
+
+    if !state
+        .apply_confirmations
+        .resolve(conflict_id, decision.clone())
+    {
+        let text = format!(
+            "\u{23f1}\u{fe0f} *Conflict expired* \u{2014} `{}` was not written; you are asked \
+             again if the agent retries `check_diff`",
+            conflict.file_path
+        );
+        replace_buttons(state, channel, message, conflict_id, &text).await;
+        return Ok(());
+    }
+    let status_text = match decision {
+        ApplyDecision::Confirmed { .. } => {
+            info!(conflict_id, user_id, "conflicting diff force-applied");
+            format!(
+                "\u{26a0}\u{fe0f} *Force apply* by <@{user_id}> \u{2014} `{}`",
+                conflict.file_path
+            )
+        }
+        ApplyDecision::Merge { .. } => {
+            info!(
+                conflict_id,
+                user_id, "merge view requested for conflicting diff"
+            );
+            format!(
+                "\u{1f500} *Merge requested* by <@{user_id}> \u{2014} `{}`; the agent re-proposes \
+                 from the merge view",
+                conflict.file_path
+            )
+        }
+        ApplyDecision::Cancelled { .. } => {
+            info!(conflict_id, user_id, "conflicting diff rejected");
+            format!(
+                "\u{274c} *Diff rejected* by <@{user_id}> \u{2014} `{}`",
+                conflict.file_path
+            )
+        }
+    };
+
+    replace_buttons(state, channel, message, conflict_id, &status_text).await;
+    Ok(())
+}
+
+/// Replace the card's buttons with a static status line (FR-022).
+async fn replace_buttons(
+    state: &AppState,
+    channel: Option<&SlackBasicChannelInfo>,
+    message: Option<&SlackHistoryMessage>,
+    conflict_id: &str,
+    status_text: &str,
+) {
+    let Some(ref slack) = state.slack else {
+        return;
+    };
+    let msg_ts = message.map(|m| m.origin.ts.clone());
+    let chan_id = channel.map(|c| c.id.clone());
+    if let (Some(ts), Some(ch)) = (msg_ts, chan_id) {
+        let replacement_blocks = vec![blocks::text_section(status_text)];
+        if let Err(err) = slack.update_message(ch, ts, replacement_blocks).await {
+            warn!(%err, conflict_id, "failed to replace conflict buttons");
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Hunk navigation handler.
+//!
+//! Approval cards for long diffs with several hunks carry a paged view of
+//! the diff: one hunk at a time with Prev / Next buttons
+//! ([`blocks::hunk_page_blocks`]). Paging rewrites only that view in the
+//! message, so the rest of the card, its decision buttons included, stays
+//! as it was. Paging decides nothing, so any authorized user may page.
+//! Paging an approval about to time out extends it
+//! ([`snooze::extend_for_activity`]).
+
+use std::sync::Arc;
+
+use slack_morphism::prelude::{
+    SlackBasicChannelInfo, SlackBlock, SlackBlockId, SlackHistoryMessage,
+    SlackInteractionActionInfo,
+};
+use tracing::{info, warn};
+
+use crate::models::approval::ApprovalStatus;
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::slack::blocks;
+use crate::slack::handlers::snooze;
+use crate::state::AppState;
+
+/// Process a Prev / Next hunk button action from Slack.
+///
+/// # Arguments
+///
+/// * `action` — the `SlackInteractionActionInfo` with `action_id`
+///   `hunk_page_<index>` and the `request_id` as `value`.
+/// * `user_id` — Slack user ID of the operator who clicked.
+/// * `channel` — channel where the message lives.
+/// * `message` — the approval card, rewritten in place.
+/// * `state` — shared application state.
+///
+/// # Errors
+///
+/// Returns an error string if the action is malformed, the request is not
+/// found or no longer pending, or the card has no paged view.
+pub async fn handle_hunk_nav_action(
+    action: &SlackInteractionActionInfo,
+    user_id: &str,
+    channel: Option<&SlackBasicChannelInfo>,
+    message: Option<&SlackHistoryMessage>,
+    state: &Arc<AppState>,
+) -> Result<(), String> {
+    let action_id = action.action_id.to_string();
+    let index: usize = action_id
+        .strip_prefix("hunk_page_")
+        .and_then(|index| index.parse().ok())
+        .ok_or_else(|| format!("unknown hunk action_id: {action_id}"))?;
+    let request_id = action
+        .value
+        .as_deref()
+        .ok_or_else(|| "hunk action missing request_id value".to_owned())?;
+
+    // ── Verify authorized user (FR-013) ──────────────────
+    if !state
+        .config
+        .authorized_user_ids
+        .contains(&user_id.to_owned())
+    {
+        warn!(
+            user_id,
+            request_id, "unauthorized user attempted hunk paging"
+        );
+        return Err("user not authorized for hunk paging".into());
+    }
+
+    let record = ApprovalRepo::new(Arc::clone(&state.db))
+        .get_by_id(request_id)
+        .await
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| format!("approval request {request_id} not found"))?;
+    // A decided card has been rewritten; putting the old blocks back
+    // would restore its buttons.
+    if record.status != ApprovalStatus::Pending {
+        return Err(format!("approval request {request_id} is already decided"));
+    }
+    let page = blocks::hunk_page_blocks(request_id, &record.diff_content, index)
+        .ok_or_else(|| format!("approval request {request_id} has no hunks to page"))?;
+
+    let (Some(slack), Some(channel), Some(message)) = (state.slack.as_ref(), channel, message)
+    else {
+        return Ok(());
+    };
+    let current = message.content.blocks.clone().unwrap_or_default();
+    let mut updated = replace_page(current, request_id, page)
+        .ok_or_else(|| format!("approval card for {request_id} has no paged diff"))?;
+    snooze::extend_for_activity(state, request_id, &mut updated);
+    slack
+        .update_message(channel.id.clone(), message.origin.ts.clone(), updated)
+        .await
+        .map_err(|err| err.to_string())?;
+    info!(request_id, user_id, index, "approval diff paged");
+    Ok(())
+}
+
+/// `card` with its paged view of `request_id` swapped for `page`, or `None`
+/// when the card has none.
+#[must_use]
+pub fn replace_page(
+    card: Vec<SlackBlock>,
+    request_id: &str,
+    page: Vec<SlackBlock>,
+) -> Option<Vec<SlackBlock>> {
+    let page_id = format!("hunk_page_{request_id}");
+    let nav_id = format!("hunk_nav_{request_id}");
+    let position = card
+        .iter()
+        .position(|block| block_id(block) == Some(page_id.as_str()))?;
+    let mut updated: Vec<SlackBlock> = card
+        .into_iter()
+        .filter(|block| {
+            let id = block_id(block);
+            id != Some(page_id.as_str()) && id != Some(nav_id.as_str())
+        })
+        .collect();
+    updated.splice(position..position, page);
+    Some(updated)
+}
+
+fn block_id(block: &SlackBlock) -> Option<&str> {
+    let id: Option<&SlackBlockId> = match block {
+        SlackBlock::Section(section) => section.block_id.as_ref(),
+        SlackBlock::Actions(actions) => actions.block_id.as_ref(),
+        _ => None,
+    };
+    id.map(|id| id.0.as_str())
+}
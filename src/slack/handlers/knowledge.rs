@@ -0,0 +1,123 @@
+//! Knowledge note interaction handler.
+//!
+//! Handles the Add to Knowledge Base and Reject buttons on the card posted
+//! by `propose_knowledge`. Only the first decision counts; a late click on
+//! a note that was already decided just shows the recorded outcome.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use slack_morphism::prelude::{
+    SlackBasicChannelInfo, SlackHistoryMessage, SlackInteractionActionInfo,
+};
+use tracing::{info, warn};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::models::knowledge::KnowledgeStatus;
+use crate::persistence::knowledge_repo::KnowledgeRepo;
+use crate::slack::blocks;
+use crate::state::AppState;
+
+/// Process a single knowledge note button action from Slack.
+///
+/// # Arguments
+///
+/// * `action` — the `SlackInteractionActionInfo` with `action_id` and
+///   `value` (the note ID).
+/// * `user_id` — Slack user ID of the operator who clicked.
+/// * `channel` — channel where the card lives.
+/// * `message` — the original Slack message (for `chat.update`).
+/// * `state` — shared application state.
+///
+/// # Errors
+///
+/// Returns an error string if processing fails.
+pub async fn handle_knowledge_action(
+    action: &SlackInteractionActionInfo,
+    user_id: &str,
+    channel: Option<&SlackBasicChannelInfo>,
+    message: Option<&SlackHistoryMessage>,
+    state: &Arc<AppState>,
+) -> Result<(), String> {
+    let action_id = action.action_id.to_string();
+    let note_id = action
+        .value
+        .as_deref()
+        .ok_or_else(|| "knowledge action missing note_id value".to_owned())?;
+
+    // ── Verify authorized user (FR-013) ──────────────────
+    if !state
+        .config
+        .authorized_user_ids
+        .contains(&user_id.to_owned())
+    {
+        warn!(
+            user_id,
+            note_id, "unauthorized user attempted knowledge action"
+        );
+        return Err("user not authorized for knowledge actions".into());
+    }
+
+    let status = match action_id.as_str() {
+        "knowledge_approve" => KnowledgeStatus::Approved,
+        "knowledge_reject" => KnowledgeStatus::Rejected,
+        _ => return Err(format!("unknown knowledge action_id: {action_id}")),
+    };
+
+    let repo = KnowledgeRepo::new(Arc::clone(&state.db));
+    let decided = repo
+        .decide(note_id, status, user_id, Utc::now())
+        .await
+        .map_err(|err| err.to_string())?;
+    let note = repo
+        .get(note_id)
+        .await
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| format!("knowledge note {note_id} not found"))?;
+
+    if decided {
+        info!(
+            note_id,
+            user_id,
+            status = status.as_str(),
+            "knowledge note decided"
+        );
+        if let Some(ref logger) = state.audit_logger {
+            let entry = AuditEntry::new(AuditEventType::KnowledgeDecided)
+                .with_session(note.proposed_by.clone())
+                .with_request_id(note.id.clone())
+                .with_operator(user_id.to_owned())
+                .with_result(status.as_str().to_owned());
+            if let Err(err) = logger.log_entry(entry) {
+                warn!(%err, "audit log write failed (knowledge decided)");
+            }
+        }
+    }
+
+    let title = blocks::slack_escape(&note.title);
+    let by = note.decided_by.as_deref().unwrap_or(user_id);
+    let status_text = match note.status {
+        KnowledgeStatus::Approved => {
+            format!("\u{1f4da} *Added to knowledge base* by <@{by}>: {title}")
+        }
+        KnowledgeStatus::Rejected => {
+            format!("\u{274c} *Knowledge note rejected* by <@{by}>: {title}")
+        }
+        KnowledgeStatus::Pending => return Err(format!("knowledge note {note_id} still pending")),
+    };
+
+    // ── Replace buttons with static status (FR-022) ──────
+    if let Some(ref slack) = state.slack {
+        let msg_ts = message.map(|m| m.origin.ts.clone());
+        let chan_id = channel.map(|c| c.id.clone());
+
+        if let (Some(ts), Some(ch)) = (msg_ts, chan_id) {
+            let replacement_blocks = vec![blocks::text_section(&status_text)];
+            if let Err(err) = slack.update_message(ch, ts, replacement_blocks).await {
+                warn!(%err, note_id, "failed to replace knowledge note buttons");
+            }
+        }
+    }
+
+    Ok(())
+}
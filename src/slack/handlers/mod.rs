@@ -1,13 +1,26 @@
 //! Slack interaction handler sub-modules.
 //!
 //! Also exposes shared helpers for session ownership verification (FR-031 /
-//! T068c) that are used by all interactive action handlers.
+//! T068c) that are used by all interactive action handlers, the approval
+//! authority check that layers CODEOWNERS routing on top of it, and the
+//! duration parser used by command flags such as `--ttl`.
 
+pub mod apply_confirm;
 pub mod approval;
 pub mod command_approve;
+pub mod completion;
+pub mod conflict;
+pub mod hunk_nav;
+pub mod knowledge;
 pub mod modal;
 pub mod nudge;
+pub mod policy_drift;
+pub mod prefs;
 pub mod prompt;
+pub mod resolved;
+pub mod revive;
+pub mod shortcut;
+pub mod snooze;
 pub mod steer;
 pub mod task;
 pub mod thread_reply;
@@ -47,3 +60,73 @@ pub fn check_session_ownership(session: &Session, acting_user_id: &str) -> Resul
         session.owner_user_id
     )))
 }
+
+/// Verify that `acting_user_id` may decide an approval request.
+///
+/// When `required_approvers` is non-empty (CODEOWNERS routing with
+/// `require_owner_approval`), only those users may decide — the session
+/// owner included only if listed. Otherwise this is the session-ownership
+/// check.
+///
+/// # Errors
+///
+/// Returns [`AppError::Unauthorized`] when the acting user may not decide.
+pub fn check_approval_authority(
+    session: &Session,
+    required_approvers: &[String],
+    acting_user_id: &str,
+) -> Result<()> {
+    if required_approvers.is_empty() {
+        return check_session_ownership(session, acting_user_id);
+    }
+    if required_approvers.iter().any(|id| id == acting_user_id) {
+        return Ok(());
+    }
+    let mentions: Vec<String> = required_approvers
+        .iter()
+        .map(|id| format!("<@{id}>"))
+        .collect();
+    Err(AppError::Unauthorized(format!(
+        "this change touches owned code; only a code owner can decide it: {}",
+        mentions.join(" ")
+    )))
+}
+
+/// Longest duration [`parse_duration`] accepts, so callers can add the
+/// result to a timestamp without overflowing.
+pub const MAX_DURATION_SECONDS: i64 = 365 * 86_400;
+
+/// Parse a duration such as `90s`, `10m`, `2h`, or `1d`. A bare number is seconds.
+///
+/// # Errors
+///
+/// Returns [`AppError::Config`] for an unknown unit, a non-numeric or zero
+/// amount, or a value longer than 365 days.
+pub fn parse_duration(value: &str) -> Result<chrono::Duration> {
+    let invalid = || {
+        AppError::Config(format!(
+            "invalid duration '{value}' — use e.g. 90s, 10m, 2h"
+        ))
+    };
+    let (digits, unit) = value
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or((value, "s"), |idx| value.split_at(idx));
+    let amount: i64 = digits.parse().map_err(|_| invalid())?;
+    if amount == 0 {
+        return Err(invalid());
+    }
+    let seconds = match unit {
+        "s" => Some(amount),
+        "m" => amount.checked_mul(60),
+        "h" => amount.checked_mul(3600),
+        "d" => amount.checked_mul(86_400),
+        _ => None,
+    }
+    .ok_or_else(invalid)?;
+    if seconds > MAX_DURATION_SECONDS {
+        return Err(AppError::Config(format!(
+            "duration '{value}' is longer than the 365d maximum"
+        )));
+    }
+    chrono::Duration::try_seconds(seconds).ok_or_else(invalid)
+}
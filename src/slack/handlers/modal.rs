@@ -17,7 +17,9 @@ use crate::models::approval::ApprovalStatus;
 use crate::models::prompt::PromptDecision;
 use crate::persistence::approval_repo::ApprovalRepo;
 use crate::persistence::prompt_repo::PromptRepo;
-use crate::slack::blocks;
+use crate::slack::handlers::shortcut::{self, ShortcutAction};
+use crate::slack::handlers::{prefs, resolved};
+use crate::slack::{approval_fanout, blocks, modal_context};
 use crate::state::AppState;
 
 /// Process a modal `ViewSubmission` event from Slack.
@@ -25,6 +27,9 @@ use crate::state::AppState;
 /// The `callback_id` on the view encodes `{source}:{entity_id}`:
 /// - `wait_instruct:{session_id}` — resolves a pending `wait_for_instruction`
 /// - `prompt_refine:{prompt_id}` — resolves a pending `forward_prompt`
+/// - `shortcut_steer:{user_id}` / `shortcut_task:{user_id}` — completes a
+///   global shortcut (see [`shortcut`])
+/// - `prefs:{user_id}` — saves notification preferences (see [`prefs`])
 ///
 /// The instruction text is read from
 /// `view.state.values["instruction_block"]["instruction_text"].value`.
@@ -60,6 +65,12 @@ pub async fn handle_view_submission(
         .split_once(':')
         .ok_or_else(|| format!("malformed callback_id: {callback_id}"))?;
 
+    // The preferences modal carries selections, not instruction text.
+    if source == prefs::MODAL_SOURCE {
+        prefs::submit_modal(event, &user_id, state).await;
+        return Ok(());
+    }
+
     // ── Extract instruction text from view state ─────────
     let instruction = event
         .view
@@ -89,6 +100,16 @@ pub async fn handle_view_submission(
         "approval_reject" => {
             resolve_approval_reject(entity_id, &instruction, &user_id, state).await
         }
+        shortcut::STEER_MODAL_SOURCE => {
+            shortcut::submit_modal(ShortcutAction::Steer, &instruction, &user_id, state)
+                .await
+                .map_err(|err| err.to_string())
+        }
+        shortcut::TASK_MODAL_SOURCE => {
+            shortcut::submit_modal(ShortcutAction::Task, &instruction, &user_id, state)
+                .await
+                .map_err(|err| err.to_string())
+        }
         _ => Err(format!("unknown modal source: {source}")),
     }
 }
@@ -105,7 +126,8 @@ async fn resolve_wait(
     // Scope the mutex guard so it is dropped before any `.await` call.
     {
         if let Err(err) = state
-            .driver
+            .driver_for(session_id)
+            .await
             .resolve_wait(session_id, Some(instruction.to_owned()))
             .await
         {
@@ -133,21 +155,36 @@ async fn resolve_prompt(
 ) -> Result<(), String> {
     let callback_id = format!("prompt_refine:{prompt_id}");
 
-    // Update DB record with the refined instruction.
+    // Update DB record with the refined instruction, unless the prompt was
+    // answered (or timed out) while the modal was open.
     let prompt_repo = PromptRepo::new(Arc::clone(&state.db));
-    prompt_repo
-        .update_decision(
+    let decided = prompt_repo
+        .decide(
             prompt_id,
             PromptDecision::Refine,
             Some(instruction.to_owned()),
         )
         .await
         .map_err(|err| format!("failed to update prompt decision: {err}"))?;
+    let record = prompt_repo.get_by_id(prompt_id).await.ok().flatten();
+    if let (false, Some(record)) = (decided, &record) {
+        if let Some(decision) = record.decision {
+            info!(
+                prompt_id,
+                user_id, "refine submitted after the prompt was answered"
+            );
+            let outcome = resolved::prompt_outcome(decision, record.instruction.as_deref());
+            report_late(&callback_id, user_id, &outcome, state).await;
+            return Ok(());
+        }
+    }
+    let session_id = record.map(|record| record.session_id).unwrap_or_default();
 
     // Resolve the oneshot channel — scope the guard so it drops before `.await`.
     {
         if let Err(err) = state
-            .driver
+            .driver_for(&session_id)
+            .await
             .resolve_prompt(prompt_id, "refine", Some(instruction.to_owned()))
             .await
         {
@@ -179,12 +216,20 @@ async fn resolve_approval_reject(
 ) -> Result<(), String> {
     let callback_id = format!("approval_reject:{request_id}");
 
-    // Update DB record with Rejected status.
+    // Update DB record with Rejected status, unless the request was
+    // resolved (e.g. timed out) while the modal was open.
     let approval_repo = ApprovalRepo::new(Arc::clone(&state.db));
-    approval_repo
-        .update_status(request_id, ApprovalStatus::Rejected)
+    let decided = approval_repo
+        .decide_with_reason(request_id, ApprovalStatus::Rejected, Some(reason))
         .await
         .map_err(|err| format!("failed to update approval status: {err}"))?;
+    let record = approval_repo.get_by_id(request_id).await.ok().flatten();
+    if let (false, Some(record)) = (decided, &record) {
+        info!(request_id, user_id, status = ?record.status, "rejection submitted after resolution");
+        let outcome = resolved::approval_outcome(record.status);
+        report_late(&callback_id, user_id, &outcome, state).await;
+        return Ok(());
+    }
 
     info!(
         request_id,
@@ -193,10 +238,13 @@ async fn resolve_approval_reject(
         "approval rejected via modal"
     );
 
+    let session_id = record.map(|record| record.session_id).unwrap_or_default();
+
     // Resolve the oneshot channel so the agent receives the rejection.
     {
         if let Err(err) = state
-            .driver
+            .driver_for(&session_id)
+            .await
             .resolve_clearance(request_id, false, Some(reason.to_owned()))
             .await
         {
@@ -220,6 +268,15 @@ async fn resolve_approval_reject(
     Ok(())
 }
 
+/// Report a modal submitted after its request was resolved, on the
+/// message the modal was opened from.
+async fn report_late(callback_id: &str, user_id: &str, outcome: &str, state: &Arc<AppState>) {
+    let coords = modal_context::take(state, callback_id)
+        .await
+        .map(|(channel, ts)| (SlackChannelId::new(channel), SlackTs::new(ts)));
+    resolved::report(state, coords, user_id, outcome).await;
+}
+
 /// Replace the "⏳ Processing…" indicator on the original Slack message
 /// with a permanent status line (FR-022).
 ///
@@ -227,10 +284,7 @@ async fn resolve_approval_reject(
 /// was opened and calls `chat.update`. Silently logs on failure — the
 /// oneshot has already been resolved so the agent is not blocked.
 async fn update_original_message(callback_id: &str, status_text: &str, state: &Arc<AppState>) {
-    let context = {
-        let mut ctx = state.pending_modal_contexts.lock().await;
-        ctx.remove(callback_id)
-    };
+    let context = modal_context::take(state, callback_id).await;
 
     let Some((channel_str, ts_str)) = context else {
         warn!(
@@ -242,6 +296,9 @@ async fn update_original_message(callback_id: &str, status_text: &str, state: &A
 
     let Some(ref slack) = state.slack else { return };
 
+    if let Some(request_id) = callback_id.strip_prefix("approval_reject:") {
+        approval_fanout::mark_resolved(state, request_id, &channel_str, &ts_str).await;
+    }
     let channel = SlackChannelId::new(channel_str);
     let ts = SlackTs::new(ts_str);
     let replacement_blocks = vec![blocks::text_section(status_text)];
@@ -12,12 +12,16 @@ use slack_morphism::prelude::{
 };
 use tracing::{info, warn};
 
-use crate::models::stall::StallAlertStatus;
+use crate::models::session::ProtocolMode;
+use crate::models::stall::{StallAlertStatus, StallResolution};
 use crate::persistence::session_repo::SessionRepo;
 use crate::persistence::stall_repo::StallAlertRepo;
 use crate::slack::blocks;
 use crate::state::AppState;
 
+/// Message delivered to the agent when the operator presses **Nudge**.
+const NUDGE_TEXT: &str = "The operator nudged you — please continue working on your current task.";
+
 /// Process a single stall-alert button action from Slack.
 ///
 /// # Arguments
@@ -62,13 +66,16 @@ pub async fn handle_nudge_action(
     let session_repo = SessionRepo::new(Arc::clone(&state.db));
 
     // ── Load the alert to get the session_id ─────────────
+    // Alert buttons carry the session ID; older messages carry the alert ID.
     let alert: crate::models::stall::StallAlert = {
-        let opt = stall_repo
-            .get_by_id(alert_id)
-            .await
-            .map_err(|e| e.to_string())?;
+        let opt = match stall_repo.get_by_id(alert_id).await {
+            Ok(None) => stall_repo.latest_open_for_session(alert_id).await,
+            other => other,
+        }
+        .map_err(|e| e.to_string())?;
         opt.ok_or_else(|| format!("stall alert {alert_id} not found"))?
     };
+    let alert_id = alert.id.as_str();
 
     let status_text: String;
 
@@ -79,7 +86,6 @@ pub async fn handle_nudge_action(
             .await
             .map_err(|err| format!("failed to increment nudge: {err}"))?;
 
-        // Send intercom/nudge notification to agent via stall detector handle.
         if let Some(ref detectors) = state.stall_detectors {
             let guards = detectors.lock().await;
             if let Some(handle) = guards.get(&alert.session_id) {
@@ -87,6 +93,19 @@ pub async fn handle_nudge_action(
             }
         }
 
+        // Push the nudge to a connected MCP client as `intercom/nudge`;
+        // ACP sessions only get the detector reset above.
+        let is_mcp = matches!(
+            session_repo.get_by_id(&alert.session_id).await,
+            Ok(Some(ref session)) if session.protocol_mode == ProtocolMode::Mcp
+        );
+        if is_mcp {
+            let driver = state.driver_for(&alert.session_id).await;
+            if let Err(err) = driver.send_prompt(&alert.session_id, NUDGE_TEXT).await {
+                warn!(%err, alert_id, session_id = %alert.session_id, "failed to deliver nudge");
+            }
+        }
+
         info!(alert_id, user_id, "nudge sent to agent");
         status_text = format!("\u{1f44a} *Nudged* by <@{user_id}>");
     } else if action_id == "stall_nudge_instruct" {
@@ -106,6 +125,9 @@ pub async fn handle_nudge_action(
             .update_status(alert_id, StallAlertStatus::Dismissed)
             .await
             .map_err(|err| format!("failed to dismiss alert: {err}"))?;
+        if let Err(err) = stall_repo.resolve(alert_id, StallResolution::Stopped).await {
+            warn!(%err, alert_id, "failed to record stall resolution");
+        }
 
         // Terminate the session.
         let _ = session_repo
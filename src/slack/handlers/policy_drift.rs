@@ -0,0 +1,86 @@
+//! Reconcile and Dismiss buttons on auto-approve drift reports.
+//!
+//! The buttons carry the drifted workspace root. It must still be one of
+//! the watched workspaces; Reconcile then copies every missing pattern
+//! into every rule file (see [`policy_drift::reconcile`]) and both buttons
+//! are replaced by a status line.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use slack_morphism::prelude::{
+    SlackBasicChannelInfo, SlackHistoryMessage, SlackInteractionActionInfo,
+};
+use tracing::{info, warn};
+
+use crate::orchestrator::policy_drift;
+use crate::slack::blocks;
+use crate::state::AppState;
+
+/// Handle a `policy_drift_reconcile` or `policy_drift_dismiss` button click.
+///
+/// # Errors
+///
+/// Returns a descriptive error string if the workspace is not watched or
+/// the rule files cannot be reconciled.
+pub async fn handle_policy_drift_action(
+    action: &SlackInteractionActionInfo,
+    user_id: &str,
+    channel: Option<&SlackBasicChannelInfo>,
+    message: Option<&SlackHistoryMessage>,
+    state: &Arc<AppState>,
+) -> Result<(), String> {
+    let action_id = action.action_id.to_string();
+    let root = PathBuf::from(
+        action
+            .value
+            .as_deref()
+            .ok_or_else(|| "policy drift action missing workspace value".to_owned())?,
+    );
+    if !policy_drift::watched_workspaces(state)
+        .iter()
+        .any(|(watched, _)| *watched == root)
+    {
+        return Err(format!(
+            "policy drift action names an unknown workspace: {}",
+            root.display()
+        ));
+    }
+
+    let status_text = match action_id.as_str() {
+        "policy_drift_reconcile" => {
+            let target = root.clone();
+            let written = tokio::task::spawn_blocking(move || policy_drift::reconcile(&target))
+                .await
+                .map_err(|e| format!("spawn_blocking join error (reconcile): {e}"))?
+                .map_err(|e| format!("failed to reconcile auto-approve rules: {e}"))?;
+            info!(user_id, root = %root.display(), written, "auto-approve rules reconciled");
+            format!(
+                "\u{2705} *Auto-approve rules reconciled* by <@{user_id}> \u{2014} {written} \
+                 pattern{} added in `{}`",
+                if written == 1 { "" } else { "s" },
+                root.display()
+            )
+        }
+        "policy_drift_dismiss" => {
+            info!(user_id, root = %root.display(), "auto-approve drift dismissed");
+            format!(
+                "\u{1f6ab} *Auto-approve drift dismissed* by <@{user_id}> \u{2014} `{}`",
+                root.display()
+            )
+        }
+        other => return Err(format!("unknown policy_drift action_id: {other}")),
+    };
+
+    if let Some(ref slack) = state.slack {
+        let msg_ts = message.map(|m| m.origin.ts.clone());
+        let chan_id = channel.map(|c| c.id.clone());
+        if let (Some(ts), Some(ch)) = (msg_ts, chan_id) {
+            let replacement = vec![blocks::text_section(&status_text)];
+            if let Err(err) = slack.update_message(ch, ts, replacement).await {
+                warn!(%err, user_id, action_id, "failed to replace policy drift buttons");
+            }
+        }
+    }
+    Ok(())
+}
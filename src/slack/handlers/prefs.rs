@@ -0,0 +1,196 @@
+//! Per-operator notification preferences (`/intercom prefs`).
+//!
+//! `prefs` with no arguments opens a modal; the text forms below do the
+//! same from clients that cannot show one. Preferences are stored per
+//! Slack user in `user_preferences` and applied to broadcasts from the
+//! sessions the user owns (see [`remote_log`](crate::mcp::tools::remote_log)).
+//!
+//! | Form | Effect |
+//! |---|---|
+//! | `prefs show` | Show the current preferences |
+//! | `prefs detail <minimal\|standard\|verbose\|default>` | Detail level; `default` follows `slack_detail_level` |
+//! | `prefs delivery <channel\|dm\|both>` | Session thread, a DM instead, or both |
+//! | `prefs quiet <HH:MM-HH:MM\|off>` | No DMs except errors in this server-local window |
+//! | `prefs reset` | Forget every preference |
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use slack_morphism::prelude::{
+    SlackActionId, SlackBlockId, SlackChannelId, SlackInteractionViewSubmissionEvent,
+    SlackTriggerId,
+};
+use tracing::{info, warn};
+
+use crate::config::SlackDetailLevel;
+use crate::models::preferences::{NotificationDelivery, QuietHours, UserPreferences};
+use crate::persistence::preferences_repo::PreferencesRepo;
+use crate::slack::blocks;
+use crate::slack::client::SlackMessage;
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// `callback_id` source of the preferences modal.
+pub const MODAL_SOURCE: &str = "prefs";
+
+const USAGE: &str = "usage: prefs [show | detail <minimal|standard|verbose|default> | \
+                     delivery <channel|dm|both> | quiet <HH:MM-HH:MM|off> | reset]";
+
+/// The stored preferences of `user_id`, or ones that change nothing.
+async fn load(repo: &PreferencesRepo, user_id: &str) -> Result<UserPreferences> {
+    Ok(repo
+        .get(user_id)
+        .await?
+        .unwrap_or_else(|| UserPreferences::new(user_id)))
+}
+
+/// Open the preferences modal for `user_id`.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the preferences cannot be read, or
+/// `AppError::Slack` when the modal cannot be opened.
+pub async fn open_modal(
+    user_id: &str,
+    trigger_id: &SlackTriggerId,
+    state: &AppState,
+) -> Result<()> {
+    let Some(ref slack) = state.slack else {
+        return Err(AppError::Slack("slack client not available".into()));
+    };
+    let prefs = load(&PreferencesRepo::new(Arc::clone(&state.db)), user_id).await?;
+    let modal = blocks::prefs_modal(
+        &format!("{MODAL_SOURCE}:{user_id}"),
+        &prefs,
+        state.config.slack_detail_level,
+    );
+    slack.open_modal(trigger_id.clone(), modal).await
+}
+
+/// Handle the text forms of `prefs` for `user_id`.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for unknown arguments or values, or
+/// `AppError::Db` if the preferences cannot be read or stored.
+pub async fn handle_command(args: &[&str], user_id: &str, state: &AppState) -> Result<String> {
+    let repo = PreferencesRepo::new(Arc::clone(&state.db));
+    let mut prefs = load(&repo, user_id).await?;
+    match args {
+        [] | ["show"] => return Ok(describe(&prefs, state.config.slack_detail_level)),
+        ["reset"] => {
+            repo.delete(user_id).await?;
+            info!(user_id, "notification preferences reset");
+            return Ok("Preferences reset to the server defaults.".to_owned());
+        }
+        ["detail", "default"] => prefs.detail_level = None,
+        ["detail", level] => prefs.detail_level = Some(SlackDetailLevel::parse(level)?),
+        ["delivery", delivery] => prefs.delivery = NotificationDelivery::parse(delivery)?,
+        ["quiet", "off"] => prefs.quiet_hours = None,
+        ["quiet", range] => prefs.quiet_hours = Some(QuietHours::parse(range)?),
+        _ => return Err(AppError::Config(USAGE.into())),
+    }
+    save(&repo, prefs, state).await
+}
+
+/// Store preferences submitted through the modal and DM the operator the
+/// outcome, since a closed modal has nowhere else to show it.
+pub async fn submit_modal(
+    event: &SlackInteractionViewSubmissionEvent,
+    user_id: &str,
+    state: &AppState,
+) {
+    let reply = match apply_modal(event, user_id, state).await {
+        Ok(summary) => summary,
+        Err(err) => {
+            warn!(user_id, %err, "notification preferences not saved");
+            format!("\u{26a0}\u{fe0f} Preferences not saved: {err}")
+        }
+    };
+    let Some(ref slack) = state.slack else { return };
+    if let Err(err) = slack
+        .enqueue(SlackMessage::plain(
+            SlackChannelId(user_id.to_owned()),
+            reply,
+        ))
+        .await
+    {
+        warn!(%err, user_id, "failed to post preferences reply");
+    }
+}
+
+async fn apply_modal(
+    event: &SlackInteractionViewSubmissionEvent,
+    user_id: &str,
+    state: &AppState,
+) -> Result<String> {
+    let values = event.view.state_params.state.as_ref().map(|s| &s.values);
+    let field = |block: &str, action: &str| {
+        values
+            .and_then(|v| v.get(&SlackBlockId(block.to_owned())))
+            .and_then(|b| b.get(&SlackActionId(action.to_owned())))
+    };
+    let selected = |block: &str, action: &str| {
+        field(block, action)
+            .and_then(|v| v.selected_option.as_ref())
+            .map(|o| o.value.clone())
+    };
+
+    let mut prefs = UserPreferences::new(user_id);
+    prefs.detail_level = match selected("detail_block", "detail_select").as_deref() {
+        None | Some("default") => None,
+        Some(level) => Some(SlackDetailLevel::parse(level)?),
+    };
+    if let Some(delivery) = selected("delivery_block", "delivery_select") {
+        prefs.delivery = NotificationDelivery::parse(&delivery)?;
+    }
+    prefs.quiet_hours = field("quiet_block", "quiet_text")
+        .and_then(|v| v.value.as_deref())
+        .map(str::trim)
+        .filter(|range| !range.is_empty())
+        .map(QuietHours::parse)
+        .transpose()?;
+
+    save(&PreferencesRepo::new(Arc::clone(&state.db)), prefs, state).await
+}
+
+async fn save(
+    repo: &PreferencesRepo,
+    mut prefs: UserPreferences,
+    state: &AppState,
+) -> Result<String> {
+    prefs.updated_at = Utc::now();
+    repo.upsert(&prefs).await?;
+    info!(
+        user_id = prefs.user_id,
+        detail_level = prefs.detail_level.map(SlackDetailLevel::as_str),
+        delivery = prefs.delivery.as_str(),
+        quiet_hours = prefs.quiet_hours.map(|q| q.to_string()),
+        "notification preferences saved"
+    );
+    Ok(format!(
+        "Preferences saved.\n{}",
+        describe(&prefs, state.config.slack_detail_level)
+    ))
+}
+
+/// Summary of `prefs` as shown by `prefs show`.
+#[must_use]
+pub fn describe(prefs: &UserPreferences, server_detail_level: SlackDetailLevel) -> String {
+    let detail = prefs.detail_level.map_or_else(
+        || format!("server default ({})", server_detail_level.as_str()),
+        |level| level.as_str().to_owned(),
+    );
+    let delivery = match prefs.delivery {
+        NotificationDelivery::Channel => "session thread",
+        NotificationDelivery::Dm => "direct message",
+        NotificationDelivery::Both => "session thread and direct message",
+    };
+    let quiet = prefs
+        .quiet_hours
+        .map_or_else(|| "none".to_owned(), |q| format!("{q} (server time)"));
+    format!(
+        "*Notification preferences*\n\u{2022} Detail level: {detail}\n\u{2022} Delivery: \
+         {delivery}\n\u{2022} Quiet hours: {quiet}"
+    )
+}
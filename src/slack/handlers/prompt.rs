@@ -1,10 +1,11 @@
 //! Prompt interaction handler (T058).
 //!
-//! Handles Continue, Refine, and Stop button presses from Slack
-//! forwarded prompt messages. Verifies the acting user belongs to
-//! `authorized_user_ids` (FR-013), updates the database, resolves the
-//! blocking oneshot channel, and replaces interactive buttons with a
-//! static status line (FR-022).
+//! Handles Continue, Refine, Stop, and configured quick-reply button
+//! presses from Slack forwarded prompt messages. Verifies the acting user
+//! belongs to `authorized_user_ids` (FR-013), updates the database,
+//! resolves the blocking oneshot channel, and replaces interactive buttons
+//! with a static status line (FR-022). A click on a prompt that was
+//! already answered only shows the stored outcome (see [`super::resolved`]).
 
 use std::sync::Arc;
 
@@ -16,8 +17,8 @@ use tracing::{info, warn};
 use crate::models::prompt::PromptDecision;
 use crate::persistence::prompt_repo::PromptRepo;
 use crate::persistence::session_repo::SessionRepo;
-use crate::slack::blocks;
-use crate::slack::handlers::check_session_ownership;
+use crate::slack::handlers::{check_session_ownership, resolved};
+use crate::slack::{blocks, modal_context};
 use crate::state::AppState;
 
 /// Process a single prompt button action from Slack.
@@ -68,7 +69,7 @@ pub async fn handle_prompt_action(
     // Look up the prompt record to find its session, then confirm the
     // acting user is the session owner. Also capture session_id here for
     // thread-reply fallback registration (F-20 cleanup on termination).
-    let prompt_session_id: String = {
+    let (prompt_session_id, prompt_type, answered) = {
         let prompt_repo = PromptRepo::new(Arc::clone(&state.db));
         if let Ok(Some(record)) = prompt_repo.get_by_id(prompt_id).await {
             let session_repo = SessionRepo::new(Arc::clone(&state.db));
@@ -83,12 +84,27 @@ pub async fn handle_prompt_action(
                     return Err(err.to_string());
                 }
             }
-            record.session_id
+            let answered = record.decision.map(|d| (d, record.instruction));
+            (record.session_id, Some(record.prompt_type), answered)
         } else {
-            String::new()
+            (String::new(), None, None)
         }
     };
 
+    // ── Late click on an answered prompt ─────────────────
+    // The stored decision stands; show it instead of deciding again.
+    if let Some((decision, instruction)) = answered {
+        info!(
+            prompt_id,
+            ?decision,
+            user_id,
+            "prompt click after resolution"
+        );
+        let outcome = resolved::prompt_outcome(decision, instruction.as_deref());
+        resolved::report(state, resolved::coords(channel, message), user_id, &outcome).await;
+        return Ok(());
+    }
+
     // ── Determine decision from action_id ────────────────
     let (decision, instruction) = if action_id == "prompt_continue" {
         (PromptDecision::Continue, None)
@@ -116,6 +132,7 @@ pub async fn handle_prompt_action(
                     let button_msg_ts = message.map(|m| m.origin.ts.clone());
                     let state_clone = Arc::clone(state);
                     let prompt_id_owned = prompt_id.to_owned();
+                    let session_id_owned = prompt_session_id.clone();
                     crate::slack::handlers::thread_reply::activate_thread_reply_fallback(
                         chan_id.as_str(),
                         thread_ts.as_str(),
@@ -128,22 +145,31 @@ pub async fn handle_prompt_action(
                         prompt_id,
                         move |reply_text| async move {
                             let repo = PromptRepo::new(Arc::clone(&state_clone.db));
-                            if let Err(db_err) = repo
-                                .update_decision(
+                            match repo
+                                .decide(
                                     &prompt_id_owned,
                                     PromptDecision::Refine,
                                     Some(reply_text.clone()),
                                 )
                                 .await
                             {
-                                warn!(
+                                Ok(true) => {}
+                                Ok(false) => {
+                                    warn!(
+                                        prompt_id = prompt_id_owned,
+                                        "thread-reply fallback: prompt already answered; reply ignored"
+                                    );
+                                    return;
+                                }
+                                Err(db_err) => warn!(
                                     prompt_id = prompt_id_owned,
                                     %db_err,
                                     "thread-reply fallback: failed to update prompt decision in DB"
-                                );
+                                ),
                             }
                             if let Err(driver_err) = state_clone
-                                .driver
+                                .driver_for(&session_id_owned)
+                                .await
                                 .resolve_prompt(&prompt_id_owned, "refine", Some(reply_text))
                                 .await
                             {
@@ -169,8 +195,7 @@ pub async fn handle_prompt_action(
             let msg_ts = message.map(|m| m.origin.ts.to_string());
             let chan_id = channel.map(|c| c.id.to_string());
             if let (Some(ts), Some(ch)) = (msg_ts, chan_id) {
-                let mut ctx = state.pending_modal_contexts.lock().await;
-                ctx.insert(callback_id.clone(), (ch, ts));
+                modal_context::remember(state, &callback_id, ch, ts).await;
             }
 
             let modal = blocks::instruction_modal(
@@ -181,8 +206,7 @@ pub async fn handle_prompt_action(
             if let Err(err) = slack.open_modal(trigger_id.clone(), modal).await {
                 warn!(%err, prompt_id, "failed to open refine modal; activating thread-reply fallback (F-16)");
                 // Clean up cached context on failure.
-                let mut ctx = state.pending_modal_contexts.lock().await;
-                ctx.remove(&callback_id);
+                let _ = modal_context::take(state, &callback_id).await;
                 // F-16/F-17: register thread-reply fallback when modal is unavailable.
                 // Use the parent thread_ts (root of the Slack thread) as the map key so
                 // that incoming replies, which report thread_ts = root, find the entry.
@@ -198,6 +222,7 @@ pub async fn handle_prompt_action(
                     let button_msg_ts = message.map(|m| m.origin.ts.clone());
                     let state_clone = Arc::clone(state);
                     let prompt_id_owned = prompt_id.to_owned();
+                    let session_id_owned = prompt_session_id.clone();
                     crate::slack::handlers::thread_reply::activate_thread_reply_fallback(
                         chan_id.as_str(),
                         thread_ts.as_str(),
@@ -210,22 +235,31 @@ pub async fn handle_prompt_action(
                         prompt_id,
                         move |reply_text| async move {
                             let repo = PromptRepo::new(Arc::clone(&state_clone.db));
-                            if let Err(db_err) = repo
-                                .update_decision(
+                            match repo
+                                .decide(
                                     &prompt_id_owned,
                                     PromptDecision::Refine,
                                     Some(reply_text.clone()),
                                 )
                                 .await
                             {
-                                warn!(
+                                Ok(true) => {}
+                                Ok(false) => {
+                                    warn!(
+                                        prompt_id = prompt_id_owned,
+                                        "thread-reply fallback: prompt already answered; reply ignored"
+                                    );
+                                    return;
+                                }
+                                Err(db_err) => warn!(
                                     prompt_id = prompt_id_owned,
                                     %db_err,
                                     "thread-reply fallback: failed to update prompt decision in DB"
-                                );
+                                ),
                             }
                             if let Err(driver_err) = state_clone
-                                .driver
+                                .driver_for(&session_id_owned)
+                                .await
                                 .resolve_prompt(&prompt_id_owned, "refine", Some(reply_text))
                                 .await
                             {
@@ -248,16 +282,46 @@ pub async fn handle_prompt_action(
         return Ok(());
     } else if action_id == "prompt_stop" {
         (PromptDecision::Stop, None)
+    } else if let Some(index) = action_id.strip_prefix("prompt_quick_") {
+        // Quick replies refine the prompt with the button's preset text.
+        let reply = index
+            .parse::<usize>()
+            .ok()
+            .zip(prompt_type)
+            .and_then(|(index, prompt_type)| {
+                state
+                    .config
+                    .prompts
+                    .quick_replies_for(prompt_type)
+                    .get(index)
+                    .cloned()
+            })
+            .ok_or_else(|| format!("unknown quick reply: {action_id}"))?;
+        (PromptDecision::Refine, Some(reply))
     } else {
         return Err(format!("unknown prompt action_id: {action_id}"));
     };
 
+    let instruction_label = instruction.as_deref().map(blocks::slack_escape);
+
     // ── Update DB record ─────────────────────────────────
     let prompt_repo = PromptRepo::new(Arc::clone(&state.db));
-    prompt_repo
-        .update_decision(prompt_id, decision, instruction.clone())
+    let decided = prompt_repo
+        .decide(prompt_id, decision, instruction.clone())
         .await
         .map_err(|err| format!("failed to update prompt decision: {err}"))?;
+    if !decided && prompt_type.is_some() {
+        // Answered (e.g. timed out) between the check above and now.
+        if let Some(record) = prompt_repo.get_by_id(prompt_id).await.ok().flatten() {
+            if let Some(current) = record.decision {
+                info!(prompt_id, ?current, user_id, "prompt click lost the race");
+                let outcome = resolved::prompt_outcome(current, record.instruction.as_deref());
+                resolved::report(state, resolved::coords(channel, message), user_id, &outcome)
+                    .await;
+                return Ok(());
+            }
+        }
+    }
 
     info!(prompt_id, ?decision, user_id, "prompt decision recorded");
 
@@ -269,7 +333,8 @@ pub async fn handle_prompt_action(
             PromptDecision::Stop => "stop",
         };
         if let Err(err) = state
-            .driver
+            .driver_for(&prompt_session_id)
+            .await
             .resolve_prompt(prompt_id, decision_str, instruction)
             .await
         {
@@ -283,9 +348,10 @@ pub async fn handle_prompt_action(
             PromptDecision::Continue => {
                 format!("\u{25b6}\u{fe0f} *Continue* selected by <@{user_id}>")
             }
-            PromptDecision::Refine => {
-                format!("\u{270f}\u{fe0f} *Refine* selected by <@{user_id}>")
-            }
+            PromptDecision::Refine => format!(
+                "\u{270f}\u{fe0f} *{}* selected by <@{user_id}>",
+                instruction_label.as_deref().unwrap_or("Refine")
+            ),
             PromptDecision::Stop => {
                 format!("\u{23f9}\u{fe0f} *Stop* selected by <@{user_id}>")
             }
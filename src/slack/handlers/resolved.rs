@@ -0,0 +1,91 @@
+//! Clicks on approvals and prompts that were already resolved.
+//!
+//! A button can outlive its request: the timeout fires while the operator
+//! is reading, or a reconnect re-posts a message that was answered in the
+//! meantime. The stored outcome is authoritative and a late click changes
+//! nothing. Instead the message is rewritten with that outcome and the
+//! clicking user is told privately why their click had no effect.
+
+use slack_morphism::prelude::{
+    SlackBasicChannelInfo, SlackChannelId, SlackHistoryMessage, SlackTs, SlackUserId,
+};
+use tracing::warn;
+
+use crate::models::approval::ApprovalStatus;
+use crate::models::prompt::PromptDecision;
+use crate::slack::blocks;
+use crate::state::AppState;
+
+/// Status line for an approval request resolved as `status`.
+#[must_use]
+pub fn approval_outcome(status: ApprovalStatus) -> String {
+    match status {
+        ApprovalStatus::Pending => "\u{23f3} *Pending*",
+        ApprovalStatus::Approved => "\u{2705} *Approved*",
+        ApprovalStatus::Rejected => "\u{274c} *Rejected*",
+        ApprovalStatus::Expired => "\u{23f1}\u{fe0f} *Timed out*",
+        ApprovalStatus::Consumed => "\u{2705} *Approved* and applied",
+        ApprovalStatus::Interrupted => "\u{26a0}\u{fe0f} *Interrupted* by a server restart",
+    }
+    .to_owned()
+}
+
+/// Status line for a prompt answered with `decision`.
+#[must_use]
+pub fn prompt_outcome(decision: PromptDecision, instruction: Option<&str>) -> String {
+    match (decision, instruction) {
+        (PromptDecision::Continue, _) => "\u{25b6}\u{fe0f} *Continue*".to_owned(),
+        (PromptDecision::Refine, Some(text)) => format!(
+            "\u{270f}\u{fe0f} *Refine*: {}",
+            blocks::slack_escape(&blocks::truncate_text(text, 80))
+        ),
+        (PromptDecision::Refine, None) => "\u{270f}\u{fe0f} *Refine*".to_owned(),
+        (PromptDecision::Stop, _) => "\u{23f9}\u{fe0f} *Stop*".to_owned(),
+    }
+}
+
+/// Channel and timestamp of the clicked message, when Slack sent both.
+#[must_use]
+pub fn coords(
+    channel: Option<&SlackBasicChannelInfo>,
+    message: Option<&SlackHistoryMessage>,
+) -> Option<(SlackChannelId, SlackTs)> {
+    Some((channel?.id.clone(), message?.origin.ts.clone()))
+}
+
+/// Show `outcome` on the request message at `coords` and tell `user_id`,
+/// ephemerally, that their click arrived after it.
+pub async fn report(
+    state: &AppState,
+    coords: Option<(SlackChannelId, SlackTs)>,
+    user_id: &str,
+    outcome: &str,
+) {
+    let Some(ref slack) = state.slack else { return };
+    let Some((channel, ts)) = coords else {
+        warn!(user_id, "late click without message coordinates");
+        return;
+    };
+    if let Err(err) = slack
+        .update_message(
+            channel.clone(),
+            ts,
+            vec![blocks::text_section(&format!(
+                "{outcome} (already resolved)"
+            ))],
+        )
+        .await
+    {
+        warn!(%err, "failed to show authoritative outcome");
+    }
+    let notice = format!(
+        "Your click arrived after this request was already resolved: {outcome}. \
+         Nothing was changed."
+    );
+    if let Err(err) = slack
+        .post_ephemeral(channel, SlackUserId(user_id.to_owned()), notice)
+        .await
+    {
+        warn!(%err, user_id, "failed to notify late clicker");
+    }
+}
@@ -0,0 +1,277 @@
+//! Revive button handler.
+//!
+//! A `check_clearance` request that times out is replaced by a status line
+//! and a Revive button. Reviving re-opens the request with a fresh timeout
+//! and the full approval card, so the operator can still decide it without
+//! the agent resubmitting the proposal. The agent's original call has
+//! already returned `timeout`; the late decision reaches it as a steering
+//! message naming the `request_id` to apply with `check_diff`.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use slack_morphism::prelude::{
+    SlackBasicChannelInfo, SlackBlock, SlackChannelId, SlackHistoryMessage,
+    SlackInteractionActionInfo, SlackTs, SlackUserId,
+};
+use tokio::sync::oneshot;
+use tracing::{info, warn};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::diff::ownership;
+use crate::models::approval::{ApprovalRequest, ApprovalStatus};
+use crate::models::session::{Session, SessionStatus};
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::anchor::Anchor;
+use crate::slack::blocks;
+use crate::slack::handlers::{check_approval_authority, resolved, steer};
+use crate::state::{AppState, ApprovalResponse};
+
+/// Process a Revive button action from Slack.
+///
+/// # Arguments
+///
+/// * `action` — the `SlackInteractionActionInfo` whose `value` is the
+///   `request_id`.
+/// * `user_id` — Slack user ID of the operator who clicked.
+/// * `channel` — channel where the message lives.
+/// * `message` — the timed-out request message, rewritten in place.
+/// * `state` — shared application state.
+///
+/// # Errors
+///
+/// Returns an error string if the user may not decide the request or the
+/// database lookup fails.
+pub async fn handle_revive_action(
+    action: &SlackInteractionActionInfo,
+    user_id: &str,
+    channel: Option<&SlackBasicChannelInfo>,
+    message: Option<&SlackHistoryMessage>,
+    state: &Arc<AppState>,
+) -> Result<(), String> {
+    let request_id = action
+        .value
+        .as_deref()
+        .ok_or_else(|| "revive action missing request_id value".to_owned())?;
+
+    // ── Verify authorized user (FR-013) ──────────────────
+    if !state
+        .config
+        .authorized_user_ids
+        .contains(&user_id.to_owned())
+    {
+        warn!(user_id, request_id, "unauthorized user attempted revive");
+        return Err("user not authorized for revive actions".into());
+    }
+
+    let approvals = ApprovalRepo::new(Arc::clone(&state.db));
+    let record = approvals
+        .get_by_id(request_id)
+        .await
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| format!("approval request {request_id} not found"))?;
+    let session = SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(&record.session_id)
+        .await
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| format!("session {} not found", record.session_id))?;
+    let required = ownership::required_approvers(
+        &state.config.codeowners,
+        Path::new(&session.workspace_root),
+        &record.file_path,
+    );
+    check_approval_authority(&session, &required, user_id).map_err(|err| err.to_string())?;
+
+    let coords = resolved::coords(channel, message);
+    if record.status != ApprovalStatus::Expired {
+        let outcome = resolved::approval_outcome(record.status);
+        resolved::report(state, coords, user_id, &outcome).await;
+        return Ok(());
+    }
+    if matches!(
+        session.status,
+        SessionStatus::Terminated | SessionStatus::Interrupted
+    ) {
+        refuse(state, &record, coords, user_id).await;
+        return Ok(());
+    }
+
+    // Register the waiter before the request becomes decidable, so a click
+    // on the new buttons always finds it.
+    let (tx, rx) = oneshot::channel::<ApprovalResponse>();
+    state
+        .pending_approvals
+        .lock()
+        .await
+        .insert(record.id.clone(), tx);
+    let reopened = approvals.reopen(&record.id).await.unwrap_or_else(|err| {
+        warn!(%err, request_id, "failed to re-open approval request");
+        false
+    });
+    if !reopened {
+        state.pending_approvals.lock().await.remove(&record.id);
+        let current = approvals
+            .get_by_id(request_id)
+            .await
+            .ok()
+            .flatten()
+            .map_or(record.status, |r| r.status);
+        let outcome = resolved::approval_outcome(current);
+        resolved::report(state, coords, user_id, &outcome).await;
+        return Ok(());
+    }
+    info!(request_id, user_id, "approval request revived");
+
+    if let Some(ref logger) = state.audit_logger {
+        let entry = AuditEntry::new(AuditEventType::ApprovalRevived)
+            .with_session(record.session_id.clone())
+            .with_request_id(record.id.clone())
+            .with_operator(user_id.to_owned());
+        if let Err(err) = logger.log_entry(entry) {
+            warn!(%err, "audit log write failed (approval revive)");
+        }
+    }
+
+    let anchor = message.zip(channel).map(|(m, c)| {
+        Anchor::new(
+            c.id.clone(),
+            m.origin.ts.clone(),
+            m.origin.thread_ts.clone(),
+        )
+    });
+    if let (Some(slack), Some(anchor)) = (state.slack.as_ref(), anchor.as_ref()) {
+        let card = revived_card(&record, user_id, state.config.timeouts.approval_seconds);
+        if let Err(err) = slack
+            .update_message(anchor.channel.clone(), anchor.ts.clone(), card)
+            .await
+        {
+            warn!(%err, request_id, "failed to show revived approval request");
+        }
+    }
+
+    tokio::spawn(await_decision(
+        Arc::clone(state),
+        record,
+        session,
+        anchor,
+        rx,
+    ));
+    Ok(())
+}
+
+/// The approval card a revived request is shown with.
+fn revived_card(record: &ApprovalRequest, user_id: &str, timeout_seconds: u64) -> Vec<SlackBlock> {
+    let mut card = blocks::build_approval_blocks(
+        &record.title,
+        record.description.as_deref(),
+        &record.diff_content,
+        &record.file_path,
+        record.risk_level,
+    );
+    card.extend(
+        blocks::hunk_page_blocks(&record.id, &record.diff_content, 0)
+            .into_iter()
+            .flatten(),
+    );
+    card.push(blocks::text_section(&format!(
+        "\u{1f194} `{}`",
+        record.short_id
+    )));
+    card.push(blocks::text_section(&format!(
+        "\u{267b}\u{fe0f} Revived by <@{user_id}> after it timed out"
+    )));
+    card.push(blocks::text_section(&blocks::expiry_line(
+        chrono::Utc::now(),
+        timeout_seconds,
+    )));
+    card.push(blocks::approval_buttons(&record.id));
+    card
+}
+
+/// Wait for a decision on a revived request and pass it to the agent, or
+/// let the request time out again.
+async fn await_decision(
+    state: Arc<AppState>,
+    record: ApprovalRequest,
+    session: Session,
+    anchor: Option<Anchor>,
+    rx: oneshot::Receiver<ApprovalResponse>,
+) {
+    let timeout = Duration::from_secs(state.config.timeouts.approval_seconds);
+    let response = state.snoozes.wait(&record.id, timeout, rx).await;
+    state.pending_approvals.lock().await.remove(&record.id);
+
+    match response {
+        Some(Ok(response)) => {
+            let text = decision_notice(&record, &response);
+            if let Err(err) = steer::steer_session(&state, &session, &text).await {
+                warn!(%err, request_id = %record.id, "failed to notify agent of revived decision");
+            }
+        }
+        Some(Err(_)) => {}
+        None => {
+            info!(request_id = %record.id, "revived approval request timed out again");
+            let expired = ApprovalRepo::new(Arc::clone(&state.db))
+                .decide(&record.id, ApprovalStatus::Expired)
+                .await
+                .unwrap_or(false);
+            if let (true, Some(slack), Some(anchor)) = (expired, state.slack.as_ref(), anchor) {
+                anchor
+                    .replace(slack, blocks::timed_out_approval(&record.title, &record.id))
+                    .await;
+            }
+        }
+    }
+}
+
+/// Steering text telling the agent how a revived request was decided.
+#[must_use]
+pub fn decision_notice(record: &ApprovalRequest, response: &ApprovalResponse) -> String {
+    let subject = format!(
+        "Approval request `{}` ({}) timed out earlier but was revived by the operator",
+        record.short_id, record.title
+    );
+    if response.status == "approved" {
+        format!(
+            "{subject} and is now approved. Apply it with check_diff using request_id `{}`.",
+            record.id
+        )
+    } else {
+        format!(
+            "{subject} and rejected: {}. Do not apply it.",
+            response.reason.as_deref().unwrap_or("no reason given")
+        )
+    }
+}
+
+/// Tell `user_id` the request cannot be revived because its session ended,
+/// and drop the Revive button.
+async fn refuse(
+    state: &AppState,
+    record: &ApprovalRequest,
+    coords: Option<(SlackChannelId, SlackTs)>,
+    user_id: &str,
+) {
+    let (Some(slack), Some((channel, ts))) = (state.slack.as_ref(), coords) else {
+        return;
+    };
+    let status = format!(
+        "\u{23f1}\u{fe0f} *Timed out* \u{2014} {} (session ended)",
+        record.title
+    );
+    if let Err(err) = slack
+        .update_message(channel.clone(), ts, vec![blocks::text_section(&status)])
+        .await
+    {
+        warn!(%err, request_id = %record.id, "failed to drop revive button");
+    }
+    let notice = "This approval request cannot be revived: its agent session has ended.";
+    if let Err(err) = slack
+        .post_ephemeral(channel, SlackUserId(user_id.to_owned()), notice.to_owned())
+        .await
+    {
+        warn!(%err, user_id, "failed to explain refused revive");
+    }
+}
@@ -0,0 +1,231 @@
+//! Slack shortcut and Workflow Builder entry points.
+//!
+//! Adds no behavior of its own: every entry point funnels into the
+//! existing steering and task-inbox handlers so a shortcut or workflow run
+//! is indistinguishable from the equivalent `/intercom steer` or
+//! `/intercom task` command.
+//!
+//! | Entry point | Callback ID | Effect |
+//! |---|---|---|
+//! | Global shortcut "Steer agent" | [`STEER_SHORTCUT`] | Opens a modal; the text steers an active session |
+//! | Global shortcut "Create task" | [`TASK_SHORTCUT`] | Opens a modal; the text is queued in the task inbox |
+//! | Message shortcut "Steer agent" | [`STEER_MESSAGE_SHORTCUT`] | Steers the session owning the message's thread with its text |
+//! | Message shortcut "Create task" | [`TASK_MESSAGE_SHORTCUT`] | Queues the message text as a task for its channel |
+//!
+//! ## Workflow Builder
+//!
+//! A workflow drives the same actions with a **Send a message** step into a
+//! channel the bot has joined, whose text is `steer <message>` or
+//! `task <message>`. Workflow messages are posted by a bot rather than a
+//! user, so they are only accepted from bot IDs listed in
+//! `[slack] workflow_bot_ids`; messages from any other bot are ignored as
+//! before.
+
+use std::sync::Arc;
+
+use slack_morphism::prelude::{SlackChannelId, SlackTriggerId, SlackTs};
+use tracing::{info, warn};
+
+use crate::slack::blocks;
+use crate::slack::client::SlackMessage;
+use crate::slack::handlers::{steer, task};
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Callback ID of the "Steer agent" global shortcut.
+pub const STEER_SHORTCUT: &str = "intercom_steer";
+
+/// Callback ID of the "Create task" global shortcut.
+pub const TASK_SHORTCUT: &str = "intercom_task";
+
+/// Callback ID of the "Steer agent" message shortcut.
+pub const STEER_MESSAGE_SHORTCUT: &str = "intercom_steer_message";
+
+/// Callback ID of the "Create task" message shortcut.
+pub const TASK_MESSAGE_SHORTCUT: &str = "intercom_task_message";
+
+/// Modal source for the global "Steer agent" shortcut (`shortcut_steer:{user_id}`).
+pub const STEER_MODAL_SOURCE: &str = "shortcut_steer";
+
+/// Modal source for the global "Create task" shortcut (`shortcut_task:{user_id}`).
+pub const TASK_MODAL_SOURCE: &str = "shortcut_task";
+
+/// Intercom action reachable from a shortcut or workflow step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutAction {
+    /// Deliver the text as a steering message.
+    Steer,
+    /// Queue the text in the task inbox.
+    Task,
+}
+
+impl ShortcutAction {
+    /// Parse a workflow message of the form `steer <text>` or `task <text>`.
+    ///
+    /// Returns `None` for any other message, including a bare keyword with
+    /// no text.
+    #[must_use]
+    pub fn from_workflow_text(text: &str) -> Option<(Self, &str)> {
+        let (keyword, rest) = text.trim().split_once(char::is_whitespace)?;
+        let action = match keyword.to_ascii_lowercase().as_str() {
+            "steer" => Self::Steer,
+            "task" => Self::Task,
+            _ => return None,
+        };
+        let rest = rest.trim();
+        (!rest.is_empty()).then_some((action, rest))
+    }
+
+    /// Run the action for `text` posted in `channel_id`.
+    ///
+    /// Steering resolves the target session by thread first and channel
+    /// second, exactly like a thread reply; without a channel it falls back
+    /// to any active session.
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying steering or task-inbox error, e.g. when no
+    /// session is active in the channel.
+    pub async fn run(
+        self,
+        text: &str,
+        channel_id: Option<&str>,
+        thread_ts: Option<&str>,
+        state: &Arc<AppState>,
+    ) -> Result<String> {
+        match self {
+            Self::Steer => steer::store_from_slack(text, channel_id, thread_ts, state).await,
+            Self::Task => task::store_from_slack(text, channel_id, state).await,
+        }
+    }
+}
+
+/// Open the instruction modal for a global shortcut.
+///
+/// # Errors
+///
+/// Returns `AppError::Slack` for an unknown callback ID or when the modal
+/// cannot be opened.
+pub async fn handle_global_shortcut(
+    callback_id: &str,
+    user_id: &str,
+    trigger_id: &SlackTriggerId,
+    state: &AppState,
+) -> Result<()> {
+    let (source, title, placeholder) = match callback_id {
+        STEER_SHORTCUT => (
+            STEER_MODAL_SOURCE,
+            "Steer agent",
+            "Message for the running agent…",
+        ),
+        TASK_SHORTCUT => (
+            TASK_MODAL_SOURCE,
+            "Create task",
+            "Work item for the next agent session…",
+        ),
+        other => return Err(AppError::Slack(format!("unknown shortcut '{other}'"))),
+    };
+    let Some(ref slack) = state.slack else {
+        return Err(AppError::Slack("slack client not available".into()));
+    };
+    let modal = blocks::instruction_modal(&format!("{source}:{user_id}"), title, placeholder);
+    slack.open_modal(trigger_id.clone(), modal).await
+}
+
+/// Run a message shortcut against the message it was invoked on.
+///
+/// The outcome is posted back in the message's thread; failures are
+/// reported there too so the operator is never left guessing.
+///
+/// # Errors
+///
+/// Returns `AppError::Slack` for an unknown callback ID.
+pub async fn handle_message_shortcut(
+    callback_id: &str,
+    user_id: &str,
+    text: &str,
+    channel_id: &str,
+    thread_ts: &str,
+    state: &Arc<AppState>,
+) -> Result<()> {
+    let action = match callback_id {
+        STEER_MESSAGE_SHORTCUT => ShortcutAction::Steer,
+        TASK_MESSAGE_SHORTCUT => ShortcutAction::Task,
+        other => return Err(AppError::Slack(format!("unknown shortcut '{other}'"))),
+    };
+    info!(user_id, channel_id, ?action, "message shortcut invoked");
+    let reply = match action
+        .run(text, Some(channel_id), Some(thread_ts), state)
+        .await
+    {
+        Ok(result) => format!("{result} (via shortcut by <@{user_id}>)"),
+        Err(err) => format!("\u{26a0}\u{fe0f} Shortcut failed: {err}"),
+    };
+    post_reply(state, channel_id, Some(thread_ts), reply).await;
+    Ok(())
+}
+
+/// Complete a global shortcut's modal, replying to the operator by DM.
+///
+/// # Errors
+///
+/// Returns the underlying steering or task-inbox error.
+pub async fn submit_modal(
+    action: ShortcutAction,
+    text: &str,
+    user_id: &str,
+    state: &Arc<AppState>,
+) -> Result<()> {
+    let result = action.run(text, None, None, state).await?;
+    post_reply(state, user_id, None, result).await;
+    Ok(())
+}
+
+/// Run a Workflow Builder message if it came from an allowed workflow bot.
+///
+/// Returns `false` when the bot is not listed in `workflow_bot_ids` or the
+/// text is not a `steer`/`task` command, leaving the caller's default
+/// handling of bot messages in place.
+pub async fn handle_workflow_message(
+    bot_id: &str,
+    text: &str,
+    channel_id: &str,
+    message_ts: &str,
+    state: &Arc<AppState>,
+) -> bool {
+    if !state
+        .config
+        .slack
+        .workflow_bot_ids
+        .iter()
+        .any(|id| id == bot_id)
+    {
+        return false;
+    }
+    let Some((action, body)) = ShortcutAction::from_workflow_text(text) else {
+        return false;
+    };
+    info!(bot_id, channel_id, ?action, "workflow step received");
+    let reply = match action.run(body, Some(channel_id), None, state).await {
+        Ok(result) => result,
+        Err(err) => {
+            warn!(bot_id, channel_id, %err, "workflow step failed");
+            format!("\u{26a0}\u{fe0f} Workflow step failed: {err}")
+        }
+    };
+    post_reply(state, channel_id, Some(message_ts), reply).await;
+    true
+}
+
+async fn post_reply(state: &AppState, channel: &str, thread_ts: Option<&str>, text: String) {
+    let Some(ref slack) = state.slack else { return };
+    let message = SlackMessage {
+        channel: SlackChannelId(channel.to_owned()),
+        text: Some(text),
+        blocks: None,
+        thread_ts: thread_ts.map(|ts| SlackTs(ts.to_owned())),
+    };
+    if let Err(err) = slack.enqueue(message).await {
+        warn!(%err, channel, "failed to post shortcut reply");
+    }
+}
@@ -0,0 +1,271 @@
+//! Snooze button handler.
+//!
+//! Handles "Snooze 30m" on approval and prompt messages. Snoozing pushes the
+//! request's timeout back by [`SNOOZE_DURATION`], confirms in the message's
+//! thread, and reminds the operator there when the snooze ends if the
+//! request is still undecided. The decision buttons stay live throughout.
+//!
+//! Working on an approval close to its timeout (opening the rejection
+//! modal, paging the diff) extends it as well, through
+//! [`extend_for_activity`].
+
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use slack_morphism::prelude::{
+    SlackBasicChannelInfo, SlackBlock, SlackChannelId, SlackHistoryMessage,
+    SlackInteractionActionInfo, SlackTs,
+};
+use tracing::{info, warn};
+
+use crate::diff::ownership;
+use crate::models::approval::ApprovalStatus;
+use crate::orchestrator::snooze::SNOOZE_DURATION;
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::prompt_repo::PromptRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::blocks;
+use crate::slack::client::SlackMessage;
+use crate::slack::handlers::{check_approval_authority, check_session_ownership};
+use crate::state::AppState;
+
+/// What a snooze button defers.
+#[derive(Debug, Clone, Copy)]
+enum Snoozed {
+    Approval,
+    Prompt,
+}
+
+impl Snoozed {
+    fn noun(self) -> &'static str {
+        match self {
+            Self::Approval => "approval request",
+            Self::Prompt => "prompt",
+        }
+    }
+}
+
+/// Process a snooze button action from Slack.
+///
+/// # Arguments
+///
+/// * `action` — the `SlackInteractionActionInfo` with `action_id`
+///   (`snooze_approval` or `snooze_prompt`) and `value` (the request or
+///   prompt ID).
+/// * `user_id` — Slack user ID of the operator who clicked.
+/// * `channel` — channel where the message lives.
+/// * `message` — the original Slack message (for the thread to reply in).
+/// * `state` — shared application state.
+///
+/// # Errors
+///
+/// Returns an error string if the user may not decide the request or it is
+/// no longer pending.
+pub async fn handle_snooze_action(
+    action: &SlackInteractionActionInfo,
+    user_id: &str,
+    channel: Option<&SlackBasicChannelInfo>,
+    message: Option<&SlackHistoryMessage>,
+    state: &Arc<AppState>,
+) -> Result<(), String> {
+    let action_id = action.action_id.to_string();
+    let id = action
+        .value
+        .as_deref()
+        .ok_or_else(|| "snooze action missing id value".to_owned())?;
+    let kind = match action_id.as_str() {
+        "snooze_approval" => Snoozed::Approval,
+        "snooze_prompt" => Snoozed::Prompt,
+        _ => return Err(format!("unknown snooze action_id: {action_id}")),
+    };
+
+    // ── Verify authorized user (FR-013) ──────────────────
+    if !state
+        .config
+        .authorized_user_ids
+        .contains(&user_id.to_owned())
+    {
+        warn!(user_id, id, "unauthorized user attempted snooze");
+        return Err("user not authorized for snooze actions".into());
+    }
+
+    check_may_snooze(state, kind, id, user_id).await?;
+
+    let until = state.snoozes.snooze(id, Utc::now());
+    info!(id, user_id, %until, "{} snoozed", kind.noun());
+
+    let thread_ts = message.map(|m| {
+        m.origin
+            .thread_ts
+            .as_ref()
+            .map_or_else(|| m.origin.ts.0.clone(), |ts| ts.0.clone())
+    });
+    let (Some(channel_id), Some(thread_ts)) = (channel.map(|c| c.id.to_string()), thread_ts) else {
+        return Ok(());
+    };
+    post(
+        state,
+        &channel_id,
+        &thread_ts,
+        format!(
+            "\u{1f4a4} <@{user_id}> snoozed this {} until {}. Its timeout moves back by {} \
+             minutes; a reminder follows when the snooze ends.",
+            kind.noun(),
+            blocks::slack_time(until),
+            SNOOZE_DURATION.as_secs() / 60,
+        ),
+    )
+    .await;
+
+    tokio::spawn(remind(
+        Arc::clone(state),
+        kind,
+        id.to_owned(),
+        user_id.to_owned(),
+        until,
+        channel_id,
+        thread_ts,
+    ));
+    Ok(())
+}
+
+/// Give approval `request_id` `[timeouts] decision_grace_seconds` before it
+/// times out when it is close to timing out and the operator is working on
+/// it, and move the countdown on its `card`. Returns whether the timeout
+/// was extended.
+pub fn extend_for_activity(state: &AppState, request_id: &str, card: &mut [SlackBlock]) -> bool {
+    let Some(grace) = state.config.timeouts.decision_grace() else {
+        return false;
+    };
+    let Some(deadline) = state
+        .snoozes
+        .extend_for_activity(request_id, grace, Utc::now())
+    else {
+        return false;
+    };
+    info!(request_id, %deadline, "approval timeout extended for operator activity");
+    blocks::replace_expiry_line(card, deadline);
+    true
+}
+
+/// Verify `id` is still pending and `user_id` may decide it.
+async fn check_may_snooze(
+    state: &AppState,
+    kind: Snoozed,
+    id: &str,
+    user_id: &str,
+) -> Result<(), String> {
+    let sessions = SessionRepo::new(Arc::clone(&state.db));
+    match kind {
+        Snoozed::Approval => {
+            // Terminal command approvals have no database record.
+            if state
+                .pending_command_approvals
+                .lock()
+                .await
+                .contains_key(id)
+            {
+                return Ok(());
+            }
+            let record = ApprovalRepo::new(Arc::clone(&state.db))
+                .get_by_id(id)
+                .await
+                .map_err(|err| err.to_string())?
+                .ok_or_else(|| format!("approval request {id} not found"))?;
+            if record.status != ApprovalStatus::Pending {
+                return Err(format!("approval request {id} is already decided"));
+            }
+            if let Ok(Some(session)) = sessions.get_by_id(&record.session_id).await {
+                let required = ownership::required_approvers(
+                    &state.config.codeowners,
+                    Path::new(&session.workspace_root),
+                    &record.file_path,
+                );
+                check_approval_authority(&session, &required, user_id)
+                    .map_err(|err| err.to_string())?;
+            }
+        }
+        Snoozed::Prompt => {
+            let record = PromptRepo::new(Arc::clone(&state.db))
+                .get_by_id(id)
+                .await
+                .map_err(|err| err.to_string())?
+                .ok_or_else(|| format!("prompt {id} not found"))?;
+            if record.decision.is_some() {
+                return Err(format!("prompt {id} is already decided"));
+            }
+            if let Ok(Some(session)) = sessions.get_by_id(&record.session_id).await {
+                check_session_ownership(&session, user_id).map_err(|err| err.to_string())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether `id` is still awaiting a decision.
+async fn is_pending(state: &AppState, kind: Snoozed, id: &str) -> bool {
+    match kind {
+        Snoozed::Approval => {
+            state
+                .pending_command_approvals
+                .lock()
+                .await
+                .contains_key(id)
+                || matches!(
+                    ApprovalRepo::new(Arc::clone(&state.db)).get_by_id(id).await,
+                    Ok(Some(record)) if record.status == ApprovalStatus::Pending
+                )
+        }
+        Snoozed::Prompt => matches!(
+            PromptRepo::new(Arc::clone(&state.db)).get_by_id(id).await,
+            Ok(Some(record)) if record.decision.is_none()
+        ),
+    }
+}
+
+/// Remind `user_id` once the snooze ending at `until` is over, unless the
+/// request was decided or snoozed again meanwhile.
+async fn remind(
+    state: Arc<AppState>,
+    kind: Snoozed,
+    id: String,
+    user_id: String,
+    until: DateTime<Utc>,
+    channel_id: String,
+    thread_ts: String,
+) {
+    tokio::time::sleep(SNOOZE_DURATION).await;
+    if state.snoozes.snoozed_until(&id, until).is_some() {
+        return;
+    }
+    if !is_pending(&state, kind, &id).await {
+        state.snoozes.forget(&id);
+        return;
+    }
+    post(
+        &state,
+        &channel_id,
+        &thread_ts,
+        format!(
+            "\u{23f0} <@{user_id}> snooze over \u{2014} this {} is still waiting for your decision.",
+            kind.noun()
+        ),
+    )
+    .await;
+}
+
+async fn post(state: &AppState, channel_id: &str, thread_ts: &str, text: String) {
+    let Some(ref slack) = state.slack else {
+        return;
+    };
+    let msg = SlackMessage {
+        channel: SlackChannelId(channel_id.to_owned()),
+        text: Some(text),
+        blocks: None,
+        thread_ts: Some(SlackTs(thread_ts.to_owned())),
+    };
+    if let Err(err) = slack.enqueue(msg).await {
+        warn!(%err, "failed to post snooze notice");
+    }
+}
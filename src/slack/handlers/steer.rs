@@ -4,19 +4,84 @@
 //! mentions, slash commands, and IPC requests. Messages are associated
 //! with the active session for the originating channel and delivered
 //! to the agent on the next `ping` call.
+//!
+//! MCP sessions with a live connection are additionally sent an
+//! `intercom/steering` notification as soon as the message is stored, so
+//! agents that handle it can react without waiting for `ping`. The message
+//! stays queued either way; `ping` remains the authoritative delivery path
+//! and clients can de-duplicate on `message_id`.
+//!
+//! # Priority and expiry
+//!
+//! [`SteerOptions`] carries a [`SteeringPriority`] and optional TTL.
+//! Interrupt-priority messages are delivered through the driver's
+//! `interrupt` + `send_prompt` flow when the agent is reachable, and queue
+//! ahead of normal messages otherwise. Messages whose TTL elapses before
+//! delivery are dropped by the steering expiry task and reported to the
+//! operator.
 
 use std::sync::Arc;
 
 use tracing::{info, warn};
 
 use crate::audit::{AuditEntry, AuditEventType};
+use crate::driver::mcp_driver::STEERING_METHOD;
+use crate::driver::session_hooks::SessionHookEvent;
 use crate::driver::AgentDriver;
-use crate::models::session::{ConnectivityStatus, ProtocolMode};
-use crate::models::steering::{SteeringMessage, SteeringSource};
+use crate::models::session::{ConnectivityStatus, ProtocolMode, Session};
+use crate::models::steering::{SteeringMessage, SteeringPriority, SteeringSource};
 use crate::persistence::session_repo::SessionRepo;
 use crate::persistence::steering_repo::SteeringRepo;
 use crate::state::AppState;
 
+/// Delivery options for a steering message.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SteerOptions {
+    /// Delivery urgency.
+    pub priority: SteeringPriority,
+    /// Drop the message if it is still undelivered after this long.
+    pub ttl: Option<chrono::Duration>,
+}
+
+impl SteerOptions {
+    /// Split leading `--now` / `--ttl <duration>` flags off slash-command
+    /// arguments, returning the options and the remaining message words.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` if `--ttl` is missing its value or the
+    /// value is not a valid duration (see [`parse_duration`](super::parse_duration)).
+    pub fn from_args<'a>(args: &[&'a str]) -> crate::Result<(Self, Vec<&'a str>)> {
+        let mut options = Self::default();
+        let mut rest = args.iter().copied().peekable();
+        while let Some(&flag) = rest.peek() {
+            match flag {
+                "--now" => {
+                    options.priority = SteeringPriority::Interrupt;
+                    rest.next();
+                }
+                "--ttl" => {
+                    rest.next();
+                    let value = rest.next().ok_or_else(|| {
+                        crate::AppError::Config("usage: --ttl <duration, e.g. 10m>".into())
+                    })?;
+                    options.ttl = Some(super::parse_duration(value)?);
+                }
+                _ => break,
+            }
+        }
+        Ok((options, rest.collect()))
+    }
+
+    fn apply(self, msg: SteeringMessage) -> SteeringMessage {
+        let msg = msg.with_priority(self.priority);
+        match self.ttl {
+            Some(ttl) => msg.with_ttl(ttl),
+            None => msg,
+        }
+    }
+}
+
 /// Store a steering message from a Slack channel.
 ///
 /// Looks up the active session for the given channel, then stores the
@@ -32,12 +97,27 @@ use crate::state::AppState;
 /// # Errors
 ///
 /// Returns an `AppError` if session lookup or message insertion fails.
-#[allow(clippy::too_many_lines)]
 pub async fn store_from_slack(
     text: &str,
     channel_id: Option<&str>,
     thread_ts: Option<&str>,
     state: &Arc<AppState>,
+) -> crate::Result<String> {
+    store_from_slack_with(text, SteerOptions::default(), channel_id, thread_ts, state).await
+}
+
+/// [`store_from_slack`] with explicit priority and TTL.
+///
+/// # Errors
+///
+/// Returns an `AppError` if session lookup or message insertion fails.
+#[allow(clippy::too_many_lines)]
+pub async fn store_from_slack_with(
+    text: &str,
+    options: SteerOptions,
+    channel_id: Option<&str>,
+    thread_ts: Option<&str>,
+    state: &Arc<AppState>,
 ) -> crate::Result<String> {
     if text.trim().is_empty() {
         return Err(crate::AppError::Config(
@@ -94,59 +174,36 @@ pub async fn store_from_slack(
 
     // T088 / S060: For ACP sessions that are currently Online, deliver the
     // steering message directly via the driver stream instead of queuing it.
-    if session.protocol_mode == ProtocolMode::Acp
-        && session.connectivity_status == ConnectivityStatus::Online
-    {
-        if let Some(ref acp_driver) = state.acp_driver {
-            // Ensure agent_session_id is registered in the driver's in-memory
-            // map (may be absent after a server restart while the session's
-            // agent process is still alive).
-            if let Some(ref asid) = session.agent_session_id {
-                acp_driver
-                    .register_agent_session_id(&session.id, asid)
-                    .await;
-            }
-            match acp_driver.send_prompt(&session.id, text).await {
-                Ok(()) => {
-                    info!(
-                        session_id = %session.id,
-                        channel_id = ?channel_id,
-                        "steering message delivered directly via ACP driver (session online)"
-                    );
-
-                    // HITL-007: audit-log direct steering delivery.
-                    emit_steer_audit(state.audit_logger.as_ref(), &session.id);
-
-                    return Ok(format!(
-                        "Steering message delivered directly to agent in session `{}`.",
-                        session.id
-                    ));
-                }
-                Err(err) => {
-                    // Writer may be gone (server restart, agent process exited).
-                    // Fall through to offline queue rather than returning an error.
-                    warn!(
-                        session_id = %session.id,
-                        %err,
-                        "ACP direct delivery failed — falling back to queue"
-                    );
-                }
-            }
-        }
+    if deliver_to_online_acp(state, &session, text, options.priority).await {
+        info!(
+            session_id = %session.id,
+            channel_id = ?channel_id,
+            "steering message delivered directly via ACP driver (session online)"
+        );
+
+        // HITL-007: audit-log direct steering delivery.
+        emit_steer_audit(state.audit_logger.as_ref(), &session.id);
+
+        return Ok(format!(
+            "Steering message delivered directly to agent in session `{}`.",
+            session.id
+        ));
     }
 
-    let msg = SteeringMessage::new(
+    let msg = options.apply(SteeringMessage::new(
         session.id.clone(),
         channel_id.map(str::to_owned),
         text.to_owned(),
         SteeringSource::Slack,
-    );
+    ));
 
     let steering_repo = SteeringRepo::new(Arc::clone(&state.db));
     steering_repo.insert(&msg).await?;
 
     // HITL-007: audit-log queued steering message.
     emit_steer_audit(state.audit_logger.as_ref(), &session.id);
+    notify_steering_queued(state, &msg).await;
+    let pushed = push_steering_to_mcp(state, &session, &msg).await;
 
     info!(
         session_id = %session.id,
@@ -179,6 +236,14 @@ pub async fn store_from_slack(
         }
     }
 
+    if pushed {
+        return Ok(format!(
+            "Steering message sent to the connected agent in session `{}` \
+             (also queued for the next `ping`).",
+            session.id
+        ));
+    }
+
     Ok(format!(
         "Steering message queued for session `{}`. It will be delivered on the next `ping`.",
         session.id
@@ -194,6 +259,22 @@ pub async fn store_from_slack(
 ///
 /// Returns an `AppError` if session lookup or message insertion fails.
 pub async fn store_from_ipc(text: &str, state: &Arc<AppState>) -> crate::Result<serde_json::Value> {
+    store_from_ipc_with(text, SteerOptions::default(), state).await
+}
+
+/// [`store_from_ipc`] with explicit priority and TTL.
+///
+/// Interrupt-priority messages to an online ACP session are delivered
+/// immediately rather than queued.
+///
+/// # Errors
+///
+/// Returns an `AppError` if session lookup or message insertion fails.
+pub async fn store_from_ipc_with(
+    text: &str,
+    options: SteerOptions,
+    state: &Arc<AppState>,
+) -> crate::Result<serde_json::Value> {
     if text.trim().is_empty() {
         return Err(crate::AppError::Config(
             "steering message text cannot be empty".into(),
@@ -210,15 +291,28 @@ pub async fn store_from_ipc(text: &str, state: &Arc<AppState>) -> crate::Result<
         crate::AppError::Config("no active session to steer — start a session first".into())
     })?;
 
-    let msg = SteeringMessage::new(
+    if options.priority == SteeringPriority::Interrupt
+        && deliver_to_online_acp(state, &session, text, options.priority).await
+    {
+        emit_steer_audit(state.audit_logger.as_ref(), &session.id);
+        return Ok(serde_json::json!({
+            "session_id": session.id,
+            "queued": false,
+            "delivered": true,
+        }));
+    }
+
+    let msg = options.apply(SteeringMessage::new(
         session.id.clone(),
         None,
         text.to_owned(),
         SteeringSource::Ipc,
-    );
+    ));
 
     let steering_repo = SteeringRepo::new(Arc::clone(&state.db));
     steering_repo.insert(&msg).await?;
+    notify_steering_queued(state, &msg).await;
+    let pushed = push_steering_to_mcp(state, &session, &msg).await;
 
     info!(
         session_id = %session.id,
@@ -228,9 +322,40 @@ pub async fn store_from_ipc(text: &str, state: &Arc<AppState>) -> crate::Result<
     Ok(serde_json::json!({
         "session_id": session.id,
         "queued": true,
+        "pushed": pushed,
     }))
 }
 
+/// Steer a known session on the server's behalf, e.g. the time-box
+/// wrap-up warning.
+///
+/// Delivered straight to an online ACP agent; otherwise queued for the
+/// next `ping` (and pushed to a connected MCP client) like an operator
+/// message from the session's channel.
+///
+/// # Errors
+///
+/// Returns `AppError::Db` if the message cannot be queued.
+pub async fn steer_session(state: &AppState, session: &Session, text: &str) -> crate::Result<()> {
+    if deliver_to_online_acp(state, session, text, SteeringPriority::Normal).await {
+        info!(session_id = %session.id, "server steering delivered directly via ACP driver");
+        return Ok(());
+    }
+    let msg = SteeringMessage::new(
+        session.id.clone(),
+        session.channel_id.clone(),
+        text.to_owned(),
+        SteeringSource::Slack,
+    );
+    SteeringRepo::new(Arc::clone(&state.db))
+        .insert(&msg)
+        .await?;
+    notify_steering_queued(state, &msg).await;
+    push_steering_to_mcp(state, session, &msg).await;
+    info!(session_id = %session.id, "server steering message queued");
+    Ok(())
+}
+
 /// Ingest a Slack app mention as a steering message.
 ///
 /// Strips the bot mention prefix (e.g., `<@U1234>`) from the text before
@@ -278,6 +403,109 @@ fn emit_steer_audit(logger: Option<&Arc<dyn crate::audit::AuditLogger>>, session
     }
 }
 
+/// Tell a subscribed agent that `msg` is waiting for its next `ping`.
+async fn notify_steering_queued(state: &AppState, msg: &SteeringMessage) {
+    state
+        .notify_session_hook(
+            &msg.session_id,
+            SessionHookEvent::SteeringQueued {
+                message_id: msg.id.clone(),
+                message: msg.message.clone(),
+                source: msg.source,
+            },
+        )
+        .await;
+}
+
+/// Deliver `text` straight onto an online ACP session's stream.
+///
+/// Interrupt priority first cancels the agent's current turn. Returns
+/// `false` — leaving the caller to queue the message — for MCP or
+/// offline sessions, or when the write fails (writer gone after a server
+/// restart or agent exit).
+async fn deliver_to_online_acp(
+    state: &AppState,
+    session: &Session,
+    text: &str,
+    priority: SteeringPriority,
+) -> bool {
+    if session.protocol_mode != ProtocolMode::Acp
+        || session.connectivity_status != ConnectivityStatus::Online
+    {
+        return false;
+    }
+    let Some(ref acp_driver) = state.acp_driver else {
+        return false;
+    };
+    // Ensure agent_session_id is registered in the driver's in-memory map
+    // (may be absent after a server restart while the session's agent
+    // process is still alive).
+    if let Some(ref asid) = session.agent_session_id {
+        acp_driver
+            .register_agent_session_id(&session.id, asid)
+            .await;
+    }
+    if priority == SteeringPriority::Interrupt {
+        if let Err(err) = acp_driver.interrupt(&session.id).await {
+            warn!(session_id = %session.id, %err, "interrupt before steering failed");
+            return false;
+        }
+    }
+    match acp_driver.send_prompt(&session.id, text).await {
+        Ok(()) => true,
+        Err(err) => {
+            warn!(
+                session_id = %session.id,
+                %err,
+                "ACP direct delivery failed — falling back to queue"
+            );
+            false
+        }
+    }
+}
+
+/// Push `msg` to the session's connected MCP client as an
+/// `intercom/steering` notification, interrupting first for
+/// interrupt-priority messages.
+///
+/// Returns `true` when the notification was handed to the transport. ACP
+/// sessions, MCP sessions without a live binding, and interrupt-priority
+/// messages whose interrupt could not be delivered return `false`, leaving
+/// the message queued for the next `ping`.
+async fn push_steering_to_mcp(state: &AppState, session: &Session, msg: &SteeringMessage) -> bool {
+    if session.protocol_mode != ProtocolMode::Mcp {
+        return false;
+    }
+    let Some(driver) = state.driver_registry.get(&session.id).await else {
+        return false;
+    };
+    if msg.priority == SteeringPriority::Interrupt {
+        if let Err(err) = driver.interrupt(&session.id).await {
+            warn!(%err, session_id = %session.id, "interrupt before steering push failed");
+            return false;
+        }
+    }
+    let params = serde_json::json!({
+        "session_id": session.id,
+        "message_id": msg.id,
+        "message": msg.message,
+        "source": msg.source,
+        "priority": msg.priority,
+        "expires_at": msg.expires_at,
+        "created_at": msg.created_at,
+    });
+    match driver.notify(&session.id, STEERING_METHOD, params).await {
+        Ok(()) => {
+            info!(session_id = %session.id, message_id = %msg.id, "steering message pushed");
+            true
+        }
+        Err(err) => {
+            warn!(%err, session_id = %session.id, "failed to push steering notification");
+            false
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::strip_mention;
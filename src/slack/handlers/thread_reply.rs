@@ -253,7 +253,7 @@ pub async fn cleanup_session_fallbacks(session_id: &str, pending: &PendingThread
 ///
 /// Returns `Err(reason)` if posting the fallback instruction message fails
 /// (zombie-guard applied: pending entry removed, waiter task not spawned).
-#[allow(clippy::too_many_arguments)] // 10 args needed to encapsulate 3 callers' fallback state
+#[allow(clippy::too_many_arguments)] // 10 args needed to encapsulate 4 callers' fallback state
 pub async fn activate_thread_reply_fallback<F, Fut>(
     chan_id: &str,
     thread_ts: &str,
@@ -14,8 +14,8 @@ use slack_morphism::prelude::{
 use tracing::{info, warn};
 
 use crate::persistence::session_repo::SessionRepo;
-use crate::slack::blocks;
 use crate::slack::handlers::check_session_ownership;
+use crate::slack::{blocks, modal_context};
 use crate::state::AppState;
 
 /// Route a `wait_resume_instruct` press takes after inspecting the triggering
@@ -162,7 +162,8 @@ pub async fn handle_wait_action(
                         callback_id.as_str(),
                         move |reply_text| async move {
                             if let Err(err) = state_clone
-                                .driver
+                                .driver_for(&session_id_owned)
+                                .await
                                 .resolve_wait(&session_id_owned, Some(reply_text))
                                 .await
                             {
@@ -189,8 +190,7 @@ pub async fn handle_wait_action(
             let msg_ts = message.map(|m| m.origin.ts.to_string());
             let chan_id = channel.map(|c| c.id.to_string());
             if let (Some(ts), Some(ch)) = (msg_ts, chan_id) {
-                let mut ctx = state.pending_modal_contexts.lock().await;
-                ctx.insert(callback_id.clone(), (ch, ts));
+                modal_context::remember(state, &callback_id, ch, ts).await;
             }
 
             let modal = blocks::instruction_modal(
@@ -201,8 +201,7 @@ pub async fn handle_wait_action(
             if let Err(err) = slack.open_modal(trigger_id.clone(), modal).await {
                 warn!(%err, session_id, "failed to open instruction modal; activating thread-reply fallback (F-16)");
                 // Clean up cached context on failure.
-                let mut ctx = state.pending_modal_contexts.lock().await;
-                ctx.remove(&callback_id);
+                let _ = modal_context::take(state, &callback_id).await;
                 // F-16/F-17: register thread-reply fallback when modal is unavailable.
                 // Use the parent thread_ts (root of the Slack thread) as the map key so
                 // that incoming replies, which report thread_ts = root, find the entry.
@@ -230,7 +229,8 @@ pub async fn handle_wait_action(
                         callback_id.as_str(),
                         move |reply_text| async move {
                             if let Err(err) = state_clone
-                                .driver
+                                .driver_for(&session_id_owned)
+                                .await
                                 .resolve_wait(&session_id_owned, Some(reply_text))
                                 .await
                             {
@@ -262,7 +262,8 @@ pub async fn handle_wait_action(
     // ── Resolve oneshot channel via driver ───────────────
     {
         if let Err(err) = state
-            .driver
+            .driver_for(session_id)
+            .await
             .resolve_wait(session_id, instruction.clone())
             .await
         {
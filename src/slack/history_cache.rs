@@ -0,0 +1,136 @@
+//! Short-lived cache of channel history for the channel history resource.
+//!
+//! Agents re-read `slack://channel/{id}/recent` often, and each read used to
+//! cost a full `conversations.history` call. [`HistoryCache`] keeps each
+//! channel's newest [`CAPACITY`] messages: reads within
+//! `[slack_rate_limit] history_cache_seconds` of the last check are served
+//! from memory, and later reads fetch only the messages newer than the
+//! newest cached one (the `oldest` cursor) and prepend them. The channel is
+//! fetched in full when the cache holds fewer messages than a read asks for,
+//! and at least every [`FULL_REFRESH`], so edits and deletions of cached
+//! messages show up eventually.
+
+use std::collections::{hash_map, HashMap};
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use slack_morphism::prelude::{SlackHistoryMessage, SlackTs};
+use tokio::sync::Mutex;
+
+use crate::Result;
+
+/// Most messages cached per channel; also the page size of every fetch.
+pub const CAPACITY: u16 = 100;
+
+/// Longest a channel goes between full fetches.
+pub const FULL_REFRESH: Duration = Duration::from_mins(5);
+
+/// A page of history, newest first, and whether older messages exist.
+pub type HistoryPage = (Vec<SlackHistoryMessage>, bool);
+
+/// Cached history of one channel.
+struct Entry {
+    /// Newest messages, newest first.
+    messages: Vec<SlackHistoryMessage>,
+    /// Whether the channel has messages older than those cached.
+    has_more: bool,
+    /// When the channel was last fetched in full.
+    filled_at: Instant,
+    /// When the channel was last checked for new messages.
+    checked_at: Instant,
+}
+
+impl Entry {
+    fn new(page: HistoryPage, now: Instant) -> Self {
+        Self {
+            messages: page.0,
+            has_more: page.1,
+            filled_at: now,
+            checked_at: now,
+        }
+    }
+
+    /// Whether a read of `limit` messages can be answered from this entry.
+    fn covers(&self, limit: u16) -> bool {
+        self.messages.len() >= usize::from(limit) || !self.has_more
+    }
+
+    fn page(&self, limit: u16) -> HistoryPage {
+        let limit = usize::from(limit);
+        let messages = self.messages.iter().take(limit).cloned().collect();
+        (messages, self.messages.len() > limit || self.has_more)
+    }
+}
+
+/// Per-channel history cache; see the [module docs](self).
+pub struct HistoryCache {
+    ttl: Duration,
+    /// Held across fetches, so concurrent reads share one API call.
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl HistoryCache {
+    /// Cache that serves reads for `ttl` after each check. A zero `ttl`
+    /// disables caching: every read fetches.
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Read the newest `limit` messages of `channel`.
+    ///
+    /// `fetch(oldest, limit)` calls `conversations.history`, returning at
+    /// most `limit` messages newer than `oldest` (all messages when `None`),
+    /// newest first. It is called at most once per read.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `fetch`; the cached entry is left as it was.
+    pub async fn read<F, Fut>(&self, channel: &str, limit: u16, fetch: F) -> Result<HistoryPage>
+    where
+        F: FnOnce(Option<SlackTs>, u16) -> Fut,
+        Fut: Future<Output = Result<HistoryPage>>,
+    {
+        if self.ttl.is_zero() {
+            return fetch(None, limit).await;
+        }
+        let mut entries = self.entries.lock().await;
+        let now = Instant::now();
+        let entry = match entries.entry(channel.to_owned()) {
+            hash_map::Entry::Occupied(slot)
+                if slot.get().covers(limit)
+                    && now.duration_since(slot.get().filled_at) < FULL_REFRESH =>
+            {
+                let entry = slot.into_mut();
+                if now.duration_since(entry.checked_at) >= self.ttl {
+                    let newest = entry.messages.first().map(|m| m.origin.ts.clone());
+                    let fetched_all = newest.is_none();
+                    let (mut newer, more) = fetch(newest, CAPACITY).await?;
+                    if fetched_all || more {
+                        // Either nothing was cached to continue from, or
+                        // there are more new messages than one page: the
+                        // page is the channel's newest either way.
+                        *entry = Entry::new((newer, more), now);
+                    } else {
+                        newer.append(&mut entry.messages);
+                        if newer.len() > usize::from(CAPACITY) {
+                            newer.truncate(usize::from(CAPACITY));
+                            entry.has_more = true;
+                        }
+                        entry.messages = newer;
+                        entry.checked_at = now;
+                    }
+                }
+                entry
+            }
+            slot => {
+                let page = fetch(None, CAPACITY).await?;
+                slot.insert_entry(Entry::new(page, now)).into_mut()
+            }
+        };
+        Ok(entry.page(limit))
+    }
+}
@@ -0,0 +1,113 @@
+//! Per-subject serialization of Slack interactions.
+//!
+//! Socket Mode hands every interaction to its own task, so a double-click
+//! on Accept, or an Accept racing the Reject modal of the same approval,
+//! would otherwise run side by side: both read the request as pending,
+//! both write a status, and the message edits land in either order.
+//! [`InteractionQueue`] runs the interactions that act on the same
+//! subject — the approval, prompt, stall alert, wait or knowledge note
+//! named by the button value or modal `callback_id` — one at a time, in
+//! arrival order. Interactions on different subjects still run in
+//! parallel, and a subject's lane is dropped once nothing is queued on it.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use slack_morphism::prelude::{SlackInteractionEvent, SlackView};
+
+/// Lanes of the subjects with an interaction running or waiting.
+#[derive(Debug, Default)]
+pub struct InteractionQueue {
+    lanes: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+impl InteractionQueue {
+    /// Create an empty queue.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `task` once every earlier task queued on `subject` has
+    /// finished. Tasks without a subject run immediately.
+    pub async fn run<F: Future>(&self, subject: Option<&str>, task: F) -> F::Output {
+        let Some(subject) = subject else {
+            return task.await;
+        };
+        let lane = Arc::clone(
+            self.lanes
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .entry(subject.to_owned())
+                .or_default(),
+        );
+        // `tokio::sync::Mutex` queues waiters fairly, so tasks run in the
+        // order they reached this point.
+        let output = {
+            let _turn = lane.lock().await;
+            task.await
+        };
+        self.release(subject, lane);
+        output
+    }
+
+    /// Drop `subject`'s lane when no other task holds it.
+    fn release(&self, subject: &str, lane: Arc<tokio::sync::Mutex<()>>) {
+        let mut lanes = self.lanes.lock().unwrap_or_else(PoisonError::into_inner);
+        drop(lane);
+        if lanes
+            .get(subject)
+            .is_some_and(|lane| Arc::strong_count(lane) == 1)
+        {
+            lanes.remove(subject);
+        }
+    }
+
+    /// Subjects with an interaction running or waiting.
+    #[must_use]
+    pub fn active(&self) -> usize {
+        self.lanes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+}
+
+/// Subject an interaction acts on, or `None` when it touches no shared
+/// record (shortcuts) or names none.
+///
+/// Buttons carry their subject's ID as `value`; modals carry it after the
+/// `:` of their `callback_id` (`approval_reject:<request_id>`), so a
+/// button and the modal it opened share a subject.
+#[must_use]
+pub fn interaction_subject(event: &SlackInteractionEvent) -> Option<String> {
+    let callback_id = match event {
+        SlackInteractionEvent::BlockActions(e) => {
+            let value = e
+                .actions
+                .iter()
+                .flatten()
+                .find_map(|a| a.value.as_deref().filter(|v| !v.is_empty()));
+            return value.map(str::to_owned).or_else(|| {
+                e.message
+                    .as_ref()
+                    .map(|m| format!("message:{}", m.origin.ts))
+            });
+        }
+        SlackInteractionEvent::ViewSubmission(e) => modal_callback_id(&e.view.view),
+        SlackInteractionEvent::ViewClosed(e) => modal_callback_id(&e.view.view),
+        _ => None,
+    }?;
+    callback_id
+        .split_once(':')
+        .map(|(_, subject)| subject.to_owned())
+        .filter(|subject| !subject.is_empty())
+}
+
+fn modal_callback_id(view: &SlackView) -> Option<String> {
+    match view {
+        SlackView::Modal(modal) => modal.callback_id.as_ref().map(ToString::to_string),
+        SlackView::Home(_) => None,
+    }
+}
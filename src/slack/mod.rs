@@ -1,8 +1,17 @@
 //! Slack bridge layer modules.
 
+pub mod anchor;
+pub mod approval_fanout;
 pub mod blocks;
 pub mod client;
 pub mod commands;
 pub mod events;
 pub mod handlers;
+pub mod history_cache;
+pub mod interaction_queue;
+pub mod modal_context;
 pub mod push_events;
+pub mod rate_budget;
+pub mod replay;
+pub mod request_cards;
+pub mod socket_health;
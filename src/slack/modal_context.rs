@@ -0,0 +1,50 @@
+//! Where each open Slack modal was opened from.
+//!
+//! A button that opens a modal (Refine, Resume with Instructions, a
+//! rejection reason) records the `(channel_id, message_ts)` of its message
+//! under the modal `callback_id`, so the submission can replace the
+//! "⏳ Processing…" indicator there (FR-022). The coordinates are kept in
+//! [`AppState::pending_modal_contexts`] and in the `modal_context` table,
+//! so a submission after a server restart or socket reconnect still
+//! updates the right message.
+
+use std::sync::Arc;
+
+use tracing::warn;
+
+use crate::persistence::modal_context_repo::ModalContextRepo;
+use crate::state::AppState;
+
+/// Record that the modal `callback_id` was opened from the message at
+/// `message_ts` in `channel_id`.
+pub async fn remember(state: &AppState, callback_id: &str, channel_id: String, message_ts: String) {
+    if let Err(err) = ModalContextRepo::new(Arc::clone(&state.db))
+        .save(callback_id, &channel_id, &message_ts)
+        .await
+    {
+        warn!(%err, callback_id, "failed to persist modal context");
+    }
+    state
+        .pending_modal_contexts
+        .lock()
+        .await
+        .insert(callback_id.to_owned(), (channel_id, message_ts));
+}
+
+/// Remove and return the `(channel_id, message_ts)` the modal
+/// `callback_id` was opened from, or `None` when it is unknown.
+pub async fn take(state: &AppState, callback_id: &str) -> Option<(String, String)> {
+    let cached = state
+        .pending_modal_contexts
+        .lock()
+        .await
+        .remove(callback_id);
+    let stored = ModalContextRepo::new(Arc::clone(&state.db))
+        .take(callback_id)
+        .await
+        .unwrap_or_else(|err| {
+            warn!(%err, callback_id, "failed to read persisted modal context");
+            None
+        });
+    cached.or(stored)
+}
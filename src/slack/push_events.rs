@@ -3,7 +3,8 @@
 //! Dispatches incoming push events (delivered via Socket Mode) to the
 //! appropriate steering handler. App mentions and thread messages are
 //! treated as operator steering input for the active session in the
-//! originating channel.
+//! originating channel. Messages from allow-listed Workflow Builder bots
+//! are run as workflow steps (see [`handlers::shortcut`]).
 
 use std::sync::Arc;
 
@@ -40,6 +41,18 @@ pub async fn handle_push_event(
         return Ok(());
     };
 
+    // Slack retries push events it did not see acknowledged; steering the
+    // agent twice with the same message would be a visible duplicate.
+    let event_id = format!("event:{}", callback.event_id);
+    if let Some(reason) = app.replay_guard.refuse(
+        Some(&event_id),
+        Some(callback.event_time.0),
+        chrono::Utc::now(),
+    ) {
+        warn!(event_id, reason, "push event: dropping replayed delivery");
+        return Ok(());
+    }
+
     match callback.event {
         SlackEventCallbackBody::AppMention(mention) => {
             let user_id = mention.user.to_string();
@@ -98,6 +111,27 @@ pub async fn handle_push_event(
         }
 
         SlackEventCallbackBody::Message(msg) => {
+            // Workflow Builder steps arrive as bot messages; run them when
+            // the workflow's bot is allow-listed.
+            if let (Some(bot_id), Some(channel_id)) = (&msg.sender.bot_id, &msg.origin.channel) {
+                let text = msg
+                    .content
+                    .as_ref()
+                    .and_then(|c| c.text.as_deref())
+                    .unwrap_or_default();
+                if handlers::shortcut::handle_workflow_message(
+                    bot_id.as_ref(),
+                    text,
+                    channel_id.as_ref(),
+                    msg.origin.ts.as_ref(),
+                    &app,
+                )
+                .await
+                {
+                    return Ok(());
+                }
+            }
+
             // Skip bot messages and subtypes (edits, deletes, joins, etc.)
             // to avoid echo loops and irrelevant noise.
             if msg.sender.bot_id.is_some() {
@@ -0,0 +1,294 @@
+//! Shared Slack Web API call budget and low-priority update batching.
+//!
+//! Every call [`SlackService`](crate::slack::client::SlackService) makes is
+//! counted in one [`RateBudget`], and a `429` response's `Retry-After`
+//! pauses every caller until it expires, instead of each caller retrying on
+//! its own schedule and drawing more `429`s. When the budget runs tight
+//! ([`Pressure::Near`] or [`Pressure::Limited`]), low-priority updates —
+//! heartbeat status lines and in-place status edits — wait in
+//! [`DeferredUpdates`]: a newer update with the same key replaces the older
+//! one, and posts bound for the same thread are merged into one message.
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use slack_morphism::errors::SlackClientError;
+use slack_morphism::prelude::{SlackBlock, SlackChannelId, SlackTs};
+
+use crate::config::SlackRateLimitConfig;
+use crate::slack::blocks;
+use crate::slack::client::SlackMessage;
+
+/// Window `calls_per_minute` is measured over.
+const WINDOW: Duration = Duration::from_mins(1);
+
+/// Pause after a `429` that carries no `Retry-After`.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+/// Most blocks Slack accepts in one message.
+const MAX_BLOCKS: usize = 50;
+
+/// How close the server is to Slack's rate limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pressure {
+    /// Below `near_limit_percent` of the budget.
+    Normal,
+    /// At or past `near_limit_percent` of the budget.
+    Near,
+    /// Slack answered `429` and its `Retry-After` has not yet expired.
+    Limited,
+}
+
+/// Slack API calls made in the last minute and any `Retry-After` in force.
+#[derive(Debug)]
+pub struct RateBudget {
+    config: SlackRateLimitConfig,
+    state: Mutex<BudgetState>,
+}
+
+#[derive(Debug, Default)]
+struct BudgetState {
+    calls: VecDeque<Instant>,
+    blocked_until: Option<Instant>,
+    rate_limited: u64,
+}
+
+impl BudgetState {
+    fn expire(&mut self, now: Instant) {
+        while self
+            .calls
+            .front()
+            .is_some_and(|at| now.saturating_duration_since(*at) >= WINDOW)
+        {
+            self.calls.pop_front();
+        }
+        if self.blocked_until.is_some_and(|until| until <= now) {
+            self.blocked_until = None;
+        }
+    }
+}
+
+impl RateBudget {
+    /// Empty budget for `config`.
+    #[must_use]
+    pub fn new(config: SlackRateLimitConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(BudgetState::default()),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, BudgetState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Count one API call made at `now`.
+    pub fn record_call(&self, now: Instant) {
+        let mut state = self.state();
+        state.expire(now);
+        state.calls.push_back(now);
+    }
+
+    /// Count a `429` answered at `now` and block callers for `retry_after`
+    /// (one second when Slack sent none). Returns the wait.
+    pub fn record_rate_limited(&self, retry_after: Option<Duration>, now: Instant) -> Duration {
+        let wait = retry_after.unwrap_or(DEFAULT_RETRY_AFTER);
+        let mut state = self.state();
+        state.expire(now);
+        state.calls.push_back(now);
+        state.rate_limited += 1;
+        let until = now + wait;
+        state.blocked_until = Some(state.blocked_until.map_or(until, |b| b.max(until)));
+        wait
+    }
+
+    /// Count the call that produced `result`.
+    pub fn observe<T>(&self, result: &std::result::Result<T, SlackClientError>, now: Instant) {
+        match result {
+            Err(SlackClientError::RateLimitError(rate)) => {
+                self.record_rate_limited(rate.retry_after, now);
+            }
+            _ => self.record_call(now),
+        }
+    }
+
+    /// Time left before Slack accepts calls again; zero when not blocked.
+    #[must_use]
+    pub fn retry_after(&self, now: Instant) -> Duration {
+        let mut state = self.state();
+        state.expire(now);
+        state
+            .blocked_until
+            .map_or(Duration::ZERO, |until| until.saturating_duration_since(now))
+    }
+
+    /// Calls made in the minute before `now`.
+    #[must_use]
+    pub fn calls_in_window(&self, now: Instant) -> usize {
+        let mut state = self.state();
+        state.expire(now);
+        state.calls.len()
+    }
+
+    /// `429` responses seen since start.
+    #[must_use]
+    pub fn rate_limited_count(&self) -> u64 {
+        self.state().rate_limited
+    }
+
+    /// Current pressure on the budget.
+    #[must_use]
+    pub fn pressure(&self, now: Instant) -> Pressure {
+        let mut state = self.state();
+        state.expire(now);
+        if state.blocked_until.is_some() {
+            return Pressure::Limited;
+        }
+        let threshold = u64::from(self.config.calls_per_minute)
+            * u64::from(self.config.near_limit_percent)
+            / 100;
+        if state.calls.len() as u64 >= threshold.max(1) {
+            Pressure::Near
+        } else {
+            Pressure::Normal
+        }
+    }
+
+    /// Longest a low-priority update is held while the budget is tight.
+    #[must_use]
+    pub fn batch_window(&self) -> Duration {
+        Duration::from_secs(self.config.batch_window_seconds)
+    }
+}
+
+/// A low-priority update waiting for budget.
+#[derive(Debug, Clone)]
+pub enum Deferred {
+    /// A new message.
+    Post(SlackMessage),
+    /// Replace the blocks of the message at `ts`.
+    Edit {
+        /// Channel of the message.
+        channel: SlackChannelId,
+        /// Timestamp of the message.
+        ts: SlackTs,
+        /// Replacement blocks.
+        blocks: Vec<SlackBlock>,
+    },
+}
+
+impl Deferred {
+    /// Edits of one message always coalesce, whatever key the caller gave.
+    fn key(&self, key: Option<String>) -> Option<String> {
+        match self {
+            Self::Post(_) => key,
+            Self::Edit { channel, ts, .. } => Some(format!("edit:{}:{}", channel.0, ts.0)),
+        }
+    }
+}
+
+/// Low-priority updates held back while the budget is tight.
+#[derive(Debug, Default)]
+pub struct DeferredUpdates {
+    entries: Vec<(Option<String>, Deferred)>,
+    oldest: Option<Instant>,
+}
+
+impl DeferredUpdates {
+    /// Hold `update`, replacing a held update with the same `key`.
+    pub fn push(&mut self, key: Option<String>, update: Deferred, now: Instant) {
+        let key = update.key(key);
+        if let Some(ref key) = key {
+            self.entries.retain(|(k, _)| k.as_ref() != Some(key));
+        }
+        self.entries.push((key, update));
+        self.oldest.get_or_insert(now);
+    }
+
+    /// Number of held updates.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether nothing is held.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// When the oldest held update has waited `window`.
+    #[must_use]
+    pub fn deadline(&self, window: Duration) -> Option<Instant> {
+        self.oldest.map(|oldest| oldest + window)
+    }
+
+    /// Whether held updates should go out now: the budget has recovered or
+    /// the oldest has waited `window`.
+    #[must_use]
+    pub fn due(&self, pressure: Pressure, window: Duration, now: Instant) -> bool {
+        !self.is_empty()
+            && (pressure == Pressure::Normal
+                || self
+                    .deadline(window)
+                    .is_some_and(|deadline| deadline <= now))
+    }
+
+    /// Take every held update, merging posts bound for the same thread into
+    /// one message (split where it would exceed Slack's 50 blocks).
+    pub fn drain(&mut self) -> Vec<Deferred> {
+        self.oldest = None;
+        let mut out: Vec<Deferred> = Vec::new();
+        for (_, update) in self.entries.drain(..) {
+            let Deferred::Post(message) = update else {
+                out.push(update);
+                continue;
+            };
+            let target = out.iter_mut().rev().find_map(|held| match held {
+                Deferred::Post(held)
+                    if held.channel == message.channel
+                        && held.thread_ts == message.thread_ts
+                        && block_count(held) + block_count(&message) <= MAX_BLOCKS =>
+                {
+                    Some(held)
+                }
+                _ => None,
+            });
+            match target {
+                Some(held) => merge(held, message),
+                None => out.push(Deferred::Post(message)),
+            }
+        }
+        out
+    }
+}
+
+/// Blocks `message` occupies once merged.
+fn block_count(message: &SlackMessage) -> usize {
+    message
+        .blocks
+        .as_ref()
+        .map_or(usize::from(message.text.is_some()), Vec::len)
+}
+
+/// Append `next` to `held`. Once either carries blocks, plain-text
+/// messages become text sections so nothing is hidden behind the blocks.
+fn merge(held: &mut SlackMessage, next: SlackMessage) {
+    if held.blocks.is_some() || next.blocks.is_some() {
+        let mut merged = held
+            .blocks
+            .take()
+            .unwrap_or_else(|| held.text.iter().map(|t| blocks::text_section(t)).collect());
+        merged.extend(
+            next.blocks
+                .clone()
+                .unwrap_or_else(|| next.text.iter().map(|t| blocks::text_section(t)).collect()),
+        );
+        held.blocks = Some(merged);
+    }
+    held.text = match (held.text.take(), next.text) {
+        (Some(a), Some(b)) => Some(format!("{a}\n{b}")),
+        (a, b) => a.or(b),
+    };
+}
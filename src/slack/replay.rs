@@ -0,0 +1,112 @@
+//! Replay protection for Socket Mode deliveries.
+//!
+//! Slack redelivers an envelope whose acknowledgement it did not see in
+//! time, and a reconnect can hand the same envelope to a second socket.
+//! Acting on both copies would apply an approval twice or run a command
+//! alias twice. The SDK acknowledges envelopes itself and does not expose
+//! their IDs, so deliveries are keyed by what Slack keeps stable across
+//! retries: the `trigger_id` of interactions and slash commands, and the
+//! `event_id` of push events.
+//!
+//! A key is remembered for [`REPLAY_WINDOW`]. Deliveries stamped with an
+//! action or event time older than that are refused outright, since their
+//! first copy may already have aged out of the cache.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use slack_morphism::prelude::{SlackInteractionEvent, SlackTs};
+
+/// How long a delivery key is remembered, and the oldest action or event
+/// time accepted. Matches the tolerance Slack applies to signed requests.
+pub const REPLAY_WINDOW: TimeDelta = TimeDelta::minutes(5);
+
+/// Delivery keys seen within [`REPLAY_WINDOW`], with when they were seen.
+#[derive(Debug, Default)]
+pub struct ReplayGuard {
+    seen: Mutex<HashMap<String, DateTime<Utc>>>,
+}
+
+impl ReplayGuard {
+    /// Create an empty guard.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Why a delivery keyed `key` and stamped `at` must be dropped at
+    /// `now` — `"stale"` or `"duplicate"` — or `None` to handle it.
+    ///
+    /// Accepted keys are remembered for [`REPLAY_WINDOW`]; expired keys are
+    /// dropped on the way. Deliveries without a key are only checked for
+    /// age.
+    pub fn refuse(
+        &self,
+        key: Option<&str>,
+        at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Option<&'static str> {
+        if at.is_some_and(|at| now - at > REPLAY_WINDOW) {
+            return Some("stale");
+        }
+        let key = key?;
+        let mut seen = self.seen.lock().unwrap_or_else(PoisonError::into_inner);
+        seen.retain(|_, seen_at| now - *seen_at < REPLAY_WINDOW);
+        if seen.contains_key(key) {
+            return Some("duplicate");
+        }
+        seen.insert(key.to_owned(), now);
+        None
+    }
+}
+
+/// Dedup key of an interaction, or `None` for kinds with no side effects
+/// (`block_suggestion`) or no stable identity.
+#[must_use]
+pub fn interaction_key(event: &SlackInteractionEvent) -> Option<String> {
+    let trigger = match event {
+        SlackInteractionEvent::BlockActions(e) => Some(&e.trigger_id),
+        SlackInteractionEvent::MessageAction(e) => Some(&e.trigger_id),
+        SlackInteractionEvent::Shortcut(e) => Some(&e.trigger_id),
+        SlackInteractionEvent::ViewSubmission(e) => {
+            return Some(e.trigger_id.as_ref().map_or_else(
+                || {
+                    format!(
+                        "view:{}:{}",
+                        e.view.state_params.id, e.view.state_params.hash
+                    )
+                },
+                |trigger| format!("trigger:{trigger}"),
+            ));
+        }
+        SlackInteractionEvent::ViewClosed(e) => e.trigger_id.as_ref(),
+        SlackInteractionEvent::BlockSuggestion(_) | SlackInteractionEvent::DialogSubmission(_) => {
+            None
+        }
+    };
+    trigger.map(|trigger| format!("trigger:{trigger}"))
+}
+
+/// Oldest action time carried by an interaction, if any.
+#[must_use]
+pub fn interaction_time(event: &SlackInteractionEvent) -> Option<DateTime<Utc>> {
+    let actions = match event {
+        SlackInteractionEvent::BlockActions(e) => e.actions.as_ref(),
+        SlackInteractionEvent::MessageAction(e) => e.actions.as_ref(),
+        SlackInteractionEvent::Shortcut(e) => e.actions.as_ref(),
+        _ => None,
+    }?;
+    actions
+        .iter()
+        .filter_map(|action| action.action_ts.as_ref().and_then(ts_time))
+        .min()
+}
+
+/// Time of a Slack `ts` such as `1700000000.123456`.
+fn ts_time(ts: &SlackTs) -> Option<DateTime<Utc>> {
+    let (secs, micros) = ts.0.split_once('.').unwrap_or((ts.0.as_str(), "0"));
+    let secs = secs.parse::<i64>().ok()?;
+    let micros = format!("{micros:0<6}").get(..6)?.parse::<u32>().ok()?;
+    DateTime::from_timestamp(secs, micros * 1_000)
+}
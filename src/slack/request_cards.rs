@@ -0,0 +1,474 @@
+//! Slack cards for the requests MCP agents block on.
+//!
+//! `ask_approval` and `forward_prompt` only persist their request and
+//! publish it on the event bus; the Slack request subscriber
+//! ([`spawn_slack_request_subscriber`](crate::orchestrator::event_subscribers::spawn_slack_request_subscriber))
+//! posts the card built here from the stored record and records the card's
+//! [`Anchor`] on it. The waiting handler reads the anchor back when it has
+//! to mark the request timed out.
+//!
+//! Sessions living in a thread get text-only cards answered by @-mention
+//! (US17); channel-root cards carry Block Kit buttons.
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
+use tracing::{info_span, warn, Instrument};
+
+use crate::config::SnippetFallback;
+use crate::diff::ownership::ApprovalContext;
+use crate::diff::snippets;
+use crate::driver::CodeSnippet;
+use crate::mcp::approval_link;
+use crate::mcp::tools::ask_approval::read_original_file_for_attachment;
+use crate::models::approval::{ApprovalRequest, ApprovalStatus};
+use crate::models::session::Session;
+use crate::orchestrator::approval_conflicts;
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::prompt_repo::PromptRepo;
+use crate::persistence::session_repo::SessionRepo;
+use crate::slack::anchor::Anchor;
+use crate::slack::client::{SlackMessage, SlackService};
+use crate::slack::{approval_fanout, blocks};
+use crate::state::AppState;
+
+/// The stored approval request `request_id` and its session, or `None`
+/// (after logging) when either is gone.
+async fn load_approval(state: &AppState, request_id: &str) -> Option<(ApprovalRequest, Session)> {
+    let record = match ApprovalRepo::new(Arc::clone(&state.db))
+        .get_by_id(request_id)
+        .await
+    {
+        Ok(Some(record)) => record,
+        Ok(None) => {
+            warn!(request_id, "approval request vanished before it was posted");
+            return None;
+        }
+        Err(err) => {
+            warn!(%err, request_id, "failed to load approval request for slack");
+            return None;
+        }
+    };
+    match SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(&record.session_id)
+        .await
+    {
+        Ok(Some(session)) => Some((record, session)),
+        Ok(None) => None,
+        Err(err) => {
+            warn!(%err, request_id, "failed to load session for approval request");
+            None
+        }
+    }
+}
+
+/// Post the card for approval request `request_id` to `channel_id`.
+///
+/// The card shows the diff widened to whole functions, ownership and
+/// conflict context, and the agent's curated `snippets` (or the
+/// `[snippets] fallback`). Copies go to the workspace's extra approval
+/// channels. A request that is no longer pending is not posted.
+#[allow(clippy::too_many_lines)] // One card, several Slack messages.
+pub async fn post_approval(
+    state: &AppState,
+    slack: &SlackService,
+    channel_id: &str,
+    request_id: &str,
+    curated: &[CodeSnippet],
+) {
+    let Some((record, session)) = load_approval(state, request_id).await else {
+        return;
+    };
+    if record.status != ApprovalStatus::Pending {
+        return;
+    }
+
+    let channel = SlackChannelId(channel_id.to_owned());
+    let session_thread_ts = session.thread_ts.clone().map(SlackTs);
+    let workspace_root = PathBuf::from(&session.workspace_root);
+    let language = crate::slack::commands::file_extension_language(&record.file_path);
+
+    // ── Original file and the diff as displayed ──────────────
+    // Only the operator's view is widened; the stored diff is applied as sent.
+    let original_content =
+        match crate::diff::validate_workspace_path(&workspace_root, &record.file_path) {
+            Ok(path) => read_original_file_for_attachment(&path, &record.original_hash).await,
+            Err(_) => None,
+        };
+    let display_diff = original_content
+        .as_deref()
+        .and_then(|original| {
+            crate::diff::context::expand_context(&record.diff_content, original, language)
+        })
+        .unwrap_or_else(|| record.diff_content.clone());
+
+    // ── Ownership context and other sessions' requests ───────
+    let approval_context = ApprovalContext::gather(
+        &workspace_root,
+        &record.file_path,
+        &record.diff_content,
+        &state.config.codeowners,
+    )
+    .await;
+    let conflict_text = approval_conflicts::warning(&state.db, &session, &record.file_path).await;
+    let context_text = match (
+        conflict_text,
+        blocks::approval_context_text(&approval_context),
+    ) {
+        (Some(conflicts), Some(ownership)) => Some(format!("{conflicts}\n{ownership}")),
+        (conflicts, ownership) => conflicts.or(ownership),
+    };
+
+    let expiry = blocks::expiry_line(chrono::Utc::now(), state.config.timeouts.approval_seconds);
+    // The Block Kit card with buttons, for the channel root and for every
+    // fan-out copy.
+    let approval_card = || {
+        let mut card = blocks::build_approval_blocks(
+            &record.title,
+            record.description.as_deref(),
+            &display_diff,
+            &record.file_path,
+            record.risk_level,
+        );
+        card.extend(
+            blocks::hunk_page_blocks(request_id, &record.diff_content, 0)
+                .into_iter()
+                .flatten(),
+        );
+        if let Some(ref ctx) = context_text {
+            card.push(blocks::text_section(ctx));
+        }
+        card.push(blocks::text_section(&format!(
+            "\u{1f194} `{}`",
+            record.short_id
+        )));
+        if let Some(link) = approval_link::slack_line(&state.config.approval_links, request_id) {
+            card.push(blocks::text_section(&link));
+        }
+        card.push(blocks::text_section(&expiry));
+        card.push(blocks::approval_buttons(request_id));
+        card
+    };
+
+    let diff_line_count = display_diff.lines().count();
+    let sanitized = record.file_path.replace(['/', '.'], "_");
+    let anchor = if session_thread_ts.is_some() {
+        // US17: text-only thread approval — no blocks/buttons. Inline for
+        // short diffs, uploaded as a thread snippet for large ones (RI-004).
+        let inline_diff =
+            (diff_line_count <= blocks::INLINE_DIFF_THRESHOLD).then_some(display_diff.as_str());
+        let mut text_body = blocks::build_text_only_approval(
+            &record.title,
+            inline_diff,
+            &record.file_path,
+            &record.risk_level,
+            record.description.as_deref(),
+            context_text.as_deref(),
+        );
+        let _ = write!(text_body, "\n\u{1f194} `{}`\n{expiry}", record.short_id);
+        if let Some(link) = approval_link::slack_line(&state.config.approval_links, request_id) {
+            text_body.push_str("\n\n");
+            text_body.push_str(&link);
+        }
+        let msg = SlackMessage {
+            channel: channel.clone(),
+            text: Some(text_body),
+            blocks: None,
+            thread_ts: session_thread_ts.clone(),
+        };
+        let anchor = Anchor::post(slack, msg).await;
+        if diff_line_count > blocks::INLINE_DIFF_THRESHOLD {
+            let upload_span = info_span!("slack_upload_diff_thread", request_id);
+            async {
+                if let Err(err) = slack
+                    .upload_file(
+                        channel.clone(),
+                        &format!("{sanitized}.diff.txt"),
+                        &display_diff,
+                        session_thread_ts.clone(),
+                        Some("text"),
+                    )
+                    .await
+                {
+                    warn!(%err, "failed to upload diff snippet to thread");
+                }
+            }
+            .instrument(upload_span)
+            .await;
+        }
+        anchor
+    } else {
+        if diff_line_count > blocks::INLINE_DIFF_THRESHOLD {
+            // Upload large diff as a file snippet. Pass snippet_type "text"
+            // so Slack pre-classifies the file before its content scanner
+            // runs, preventing the "Binary" label.
+            let upload_span = info_span!("slack_upload_diff", request_id);
+            async {
+                if let Err(err) = slack
+                    .upload_file(
+                        channel.clone(),
+                        &format!("{sanitized}.diff.txt"),
+                        &display_diff,
+                        None,
+                        Some("text"),
+                    )
+                    .await
+                {
+                    warn!(%err, "failed to upload diff snippet to slack");
+                }
+            }
+            .instrument(upload_span)
+            .await;
+        }
+
+        let post_span = info_span!("slack_post_approval", request_id);
+        let approval_ts = async {
+            let msg = SlackMessage {
+                channel: channel.clone(),
+                text: Some(format!("\u{1f4cb} Approval Request: {}", record.title)),
+                blocks: Some(approval_card()),
+                thread_ts: None,
+            };
+            match slack.post_message_direct(msg).await {
+                Ok(ts) => {
+                    // S036: the session had no thread yet, so this approval
+                    // message becomes its root.
+                    if let Err(err) = SessionRepo::new(Arc::clone(&state.db))
+                        .set_thread_ts(&session.id, &ts.0)
+                        .await
+                    {
+                        warn!(%err, session_id = %session.id,
+                            "failed to record thread_ts from approval message");
+                    }
+                    Some(ts)
+                }
+                Err(err) => {
+                    warn!(%err, "failed to post approval message");
+                    None
+                }
+            }
+        }
+        .instrument(post_span)
+        .await;
+
+        // ── Snippet thread (preferred) or file upload (fallback) ──
+        //
+        // Curated snippets are posted as a threaded reply of inline code
+        // blocks. Without them, `[snippets] fallback` decides: generate
+        // them from the diff, upload the full original file for review
+        // (T084–T086), or post nothing. A diff that yields no snippets
+        // falls back to the upload.
+        let fallback = state.config.snippets.fallback;
+        let snippet_blocks = if !curated.is_empty() {
+            Some(blocks::code_snippet_blocks(
+                &curated
+                    .iter()
+                    .map(|s| (s.label.as_str(), s.language.as_str(), s.content.as_str()))
+                    .collect::<Vec<_>>(),
+            ))
+        } else if fallback == SnippetFallback::Generate {
+            let generated = snippets::generate(
+                &record.diff_content,
+                original_content.as_deref(),
+                language,
+                state.config.snippets.max_snippets,
+            );
+            (!generated.is_empty()).then(|| {
+                blocks::generated_snippet_blocks(
+                    &generated
+                        .iter()
+                        .map(|s| (s.label.as_str(), language, s.content.as_str()))
+                        .collect::<Vec<_>>(),
+                )
+            })
+        } else {
+            None
+        };
+        if let Some(snippet_blocks) = snippet_blocks {
+            if let Some(ref ts) = approval_ts {
+                let msg = SlackMessage {
+                    channel: channel.clone(),
+                    text: Some("Code snippets for review".into()),
+                    blocks: Some(snippet_blocks),
+                    thread_ts: Some(ts.clone()),
+                };
+                if let Err(err) = slack.enqueue(msg).await {
+                    warn!(%err, "failed to post snippet thread");
+                }
+            }
+        } else if fallback != SnippetFallback::None {
+            // Skipped for new files (T085) or unreadable files (T086).
+            if let Some(ref original) = original_content {
+                let orig_span = info_span!("slack_upload_original", request_id);
+                async {
+                    if let Err(err) = slack
+                        .upload_file(
+                            channel.clone(),
+                            &format!("{sanitized}.original.txt"),
+                            original,
+                            None,
+                            Some(language),
+                        )
+                        .await
+                    {
+                        warn!(%err, "failed to upload original file to slack");
+                    }
+                }
+                .instrument(orig_span)
+                .await;
+            }
+        }
+
+        // S037: posted at channel root, the approval message is its own
+        // thread (and, being the session's first message, the session
+        // thread too).
+        approval_ts.map(|ts| Anchor::new(channel.clone(), ts, None))
+    };
+
+    // ── Fan out to the workspace's extra approval channels ──
+    let fanout = approval_fanout::channels(state, channel_id, record.risk_level);
+    approval_fanout::post_copies(
+        state,
+        slack,
+        request_id,
+        &record.title,
+        anchor.as_ref(),
+        &fanout,
+        approval_card(),
+    )
+    .await;
+
+    if let Some(anchor) = anchor {
+        if let Err(err) = ApprovalRepo::new(Arc::clone(&state.db))
+            .set_slack_anchor(request_id, &anchor.ts.0, &anchor.thread_ts.0)
+            .await
+        {
+            warn!(%err, request_id, "failed to record approval message anchor");
+        }
+    }
+}
+
+/// Post the card for continuation prompt `prompt_id` to `channel_id`.
+///
+/// A prompt already answered (by an auto-policy rule, say) is not posted.
+pub async fn post_prompt(
+    state: &AppState,
+    slack: &SlackService,
+    channel_id: &str,
+    prompt_id: &str,
+) {
+    let prompts = PromptRepo::new(Arc::clone(&state.db));
+    let prompt = match prompts.get_by_id(prompt_id).await {
+        Ok(Some(prompt)) if prompt.decision.is_none() => prompt,
+        Ok(_) => return,
+        Err(err) => {
+            warn!(%err, prompt_id, "failed to load prompt for slack");
+            return;
+        }
+    };
+    let session_thread_ts = match SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(&prompt.session_id)
+        .await
+    {
+        Ok(Some(session)) => session.thread_ts.map(SlackTs),
+        Ok(None) => return,
+        Err(err) => {
+            warn!(%err, prompt_id, "failed to load session for prompt");
+            return;
+        }
+    };
+
+    let channel = SlackChannelId(channel_id.to_owned());
+    let expiry = blocks::expiry_line(chrono::Utc::now(), state.config.timeouts.prompt_seconds);
+    let anchor = if session_thread_ts.is_some() {
+        // US17: text-only thread prompt — no blocks.
+        let mut text_body = blocks::build_text_only_prompt(
+            &prompt.prompt_text,
+            prompt.prompt_type,
+            prompt.elapsed_seconds,
+            prompt.actions_taken,
+        );
+        text_body.push('\n');
+        text_body.push_str(&expiry);
+        let msg = SlackMessage {
+            channel,
+            text: Some(text_body),
+            blocks: None,
+            thread_ts: session_thread_ts,
+        };
+        Anchor::post(slack, msg).await
+    } else {
+        let mut message_blocks = blocks::build_prompt_blocks(
+            &prompt.prompt_text,
+            prompt.prompt_type,
+            prompt.elapsed_seconds,
+            prompt.actions_taken,
+            prompt_id,
+        );
+        // Above the buttons, which close the card.
+        let buttons_at = message_blocks.len().saturating_sub(1);
+        message_blocks.insert(buttons_at, blocks::text_section(&expiry));
+        message_blocks.extend(blocks::quick_reply_buttons(
+            prompt_id,
+            state.config.prompts.quick_replies_for(prompt.prompt_type),
+        ));
+        let msg = SlackMessage {
+            channel,
+            text: Some(format!(
+                "\u{1f4ac} {} Prompt: {}",
+                blocks::prompt_type_label(prompt.prompt_type),
+                blocks::truncate_text(&prompt.prompt_text, 100),
+            )),
+            blocks: Some(message_blocks),
+            thread_ts: None,
+        };
+        Anchor::post(slack, msg)
+            .instrument(info_span!("slack_post_prompt", prompt_id))
+            .await
+    };
+
+    if let Some(anchor) = anchor {
+        if let Err(err) = prompts
+            .set_slack_anchor(prompt_id, &anchor.ts.0, &anchor.thread_ts.0)
+            .await
+        {
+            warn!(%err, prompt_id, "failed to record prompt message anchor");
+        }
+    }
+}
+
+/// The anchor recorded for approval request `request_id` in `channel_id`,
+/// once its card has been posted.
+pub async fn approval_anchor(
+    state: &AppState,
+    channel_id: &str,
+    request_id: &str,
+) -> Option<Anchor> {
+    let record = ApprovalRepo::new(Arc::clone(&state.db))
+        .get_by_id(request_id)
+        .await
+        .ok()
+        .flatten()?;
+    Anchor::stored(
+        channel_id,
+        record.slack_ts.as_deref(),
+        record.thread_ts.as_deref(),
+    )
+}
+
+/// The anchor recorded for continuation prompt `prompt_id` in
+/// `channel_id`, once its card has been posted.
+pub async fn prompt_anchor(state: &AppState, channel_id: &str, prompt_id: &str) -> Option<Anchor> {
+    let prompt = PromptRepo::new(Arc::clone(&state.db))
+        .get_by_id(prompt_id)
+        .await
+        .ok()
+        .flatten()?;
+    Anchor::stored(
+        channel_id,
+        prompt.slack_ts.as_deref(),
+        prompt.thread_ts.as_deref(),
+    )
+}
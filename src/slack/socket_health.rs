@@ -0,0 +1,128 @@
+//! Socket Mode connection health and forced reconnects.
+//!
+//! Slack occasionally leaves a Socket Mode WebSocket half-open: the
+//! connection looks alive, but no events arrive. [`SocketHealth`] records
+//! each `hello` Slack sends when a connection opens, and the drops implied
+//! by reconnects, for `GET /health/ready` and `GET /metrics`. An operator
+//! who sees a stale `last_hello` runs `agent-intercom-ctl slack reconnect`,
+//! which [`SocketHealth::request_reconnect`] turns into a clean shutdown
+//! and re-registration of the listener.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use tracing::warn;
+
+/// Point-in-time copy of [`SocketHealth`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SocketHealthSnapshot {
+    /// Whether a `hello` arrived since the listener was last (re)started.
+    pub connected: bool,
+    /// First `hello` since the listener was last (re)started.
+    pub connected_since: Option<DateTime<Utc>>,
+    /// Most recent `hello` on any connection.
+    pub last_hello: Option<DateTime<Utc>>,
+    /// Connections lost since start: dropped and reopened by the listener,
+    /// or closed by a forced reconnect.
+    pub disconnects: u64,
+    /// Reconnects requested with `slack reconnect`.
+    pub forced_reconnects: u64,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    connected_since: Option<DateTime<Utc>>,
+    last_hello: Option<DateTime<Utc>>,
+    hellos_since_start: u32,
+    disconnects: u64,
+    forced_reconnects: u64,
+}
+
+/// Live Socket Mode connection state, shared by the listener task, the
+/// health endpoints and the `slack-reconnect` IPC command.
+#[derive(Debug)]
+pub struct SocketHealth {
+    /// Connections the listener opens per registration.
+    connections: u32,
+    inner: Mutex<Inner>,
+    serving: AtomicBool,
+    reconnect: Notify,
+}
+
+impl SocketHealth {
+    /// Health for a listener that keeps `connections` connections open.
+    #[must_use]
+    pub fn new(connections: u32) -> Self {
+        Self {
+            connections: connections.max(1),
+            inner: Mutex::new(Inner::default()),
+            serving: AtomicBool::new(false),
+            reconnect: Notify::new(),
+        }
+    }
+
+    fn inner(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Record a `hello` received at `now`. Past the listener's initial
+    /// connections, each `hello` is a connection reopened after a drop.
+    pub fn record_hello(&self, now: DateTime<Utc>) {
+        let mut inner = self.inner();
+        inner.last_hello = Some(now);
+        inner.hellos_since_start = inner.hellos_since_start.saturating_add(1);
+        if inner.connected_since.is_none() {
+            inner.connected_since = Some(now);
+        } else if inner.hellos_since_start > self.connections {
+            inner.disconnects += 1;
+        }
+    }
+
+    /// Record that the listener stopped serving, closing its connections.
+    pub fn record_stopped(&self) {
+        self.serving.store(false, Ordering::Relaxed);
+        let mut inner = self.inner();
+        if inner.connected_since.take().is_some() {
+            inner.disconnects += 1;
+        }
+        inner.hellos_since_start = 0;
+    }
+
+    /// Ask the running listener to drop its connections and register
+    /// again. Returns `false` when no listener is running.
+    pub fn request_reconnect(&self) -> bool {
+        let serving = self.serving.load(Ordering::Relaxed);
+        if serving {
+            warn!("forcing socket mode reconnect");
+            self.inner().forced_reconnects += 1;
+            self.reconnect.notify_waiters();
+        }
+        serving
+    }
+
+    /// Resolve when [`request_reconnect`](Self::request_reconnect) is
+    /// called. Used by the listener task, which counts as running while it
+    /// waits.
+    pub async fn reconnect_requested(&self) {
+        let notified = self.reconnect.notified();
+        self.serving.store(true, Ordering::Relaxed);
+        notified.await;
+        self.serving.store(false, Ordering::Relaxed);
+    }
+
+    /// Current state.
+    #[must_use]
+    pub fn snapshot(&self) -> SocketHealthSnapshot {
+        let inner = self.inner();
+        SocketHealthSnapshot {
+            connected: inner.connected_since.is_some(),
+            connected_since: inner.connected_since,
+            last_hello: inner.last_hello,
+            disconnects: inner.disconnects,
+            forced_reconnects: inner.forced_reconnects,
+        }
+    }
+}
@@ -15,11 +15,31 @@ use tokio::sync::{oneshot, Mutex};
 
 use crate::audit::AuditLogger;
 use crate::config::GlobalConfig;
+use crate::driver::event_bus::EventBus;
+use crate::driver::registry::DriverRegistry;
+use crate::driver::session_hooks::{SessionHookEvent, SessionHooks, EVENT_METHOD};
 use crate::driver::AgentDriver;
+use crate::log_filter::LogFilter;
+use crate::mcp::diff_staging::DiffStaging;
+use crate::mcp::proxy::ProxyRegistry;
 use crate::mode::ServerMode;
+use crate::orchestrator::apply_guard::ApplyConfirmations;
+use crate::orchestrator::command_aliases::CommandAliases;
+use crate::orchestrator::heartbeat_enforcer::HeartbeatLedger;
+use crate::orchestrator::instruction_queue::InstructionQueue;
+use crate::orchestrator::rearm::RearmedRequests;
+use crate::orchestrator::session_logs::SessionLogs;
+use crate::orchestrator::session_manager::PauseRequests;
+use crate::orchestrator::session_move::SessionChannels;
+use crate::orchestrator::simulated_operator::OperatorScript;
+use crate::orchestrator::snooze::SnoozeTable;
 use crate::orchestrator::stall_detector::{StallDetectorHandle, StallEvent};
+use crate::orchestrator::watchdog::Subsystems;
+use crate::orchestrator::workspace_discovery::WorkspaceDiscovery;
 use crate::policy::watcher::PolicyCache;
 use crate::slack::client::SlackService;
+use crate::slack::interaction_queue::InteractionQueue;
+use crate::slack::replay::ReplayGuard;
 
 /// Response payload delivered through a pending approval oneshot channel.
 #[derive(Debug, Clone)]
@@ -75,7 +95,8 @@ pub type StallDetectors = Arc<Mutex<HashMap<String, StallDetectorHandle>>>;
 /// or "Refine"), it stores the original message's `(channel_id, message_ts)`
 /// keyed by the modal `callback_id`. The `ViewSubmission` handler retrieves
 /// these later to replace the "⏳ Processing…" indicator with a final status
-/// line (FR-022).
+/// line (FR-022). Read and written through [`crate::slack::modal_context`],
+/// which also persists them.
 pub type PendingModalContexts = Arc<Mutex<HashMap<String, (String, String)>>>;
 
 /// Thread-safe map of pending thread-reply oneshot senders keyed by a composite
@@ -173,4 +194,111 @@ pub struct AppState {
     /// to wire the reader/writer tasks. Slack handlers resolve operator decisions
     /// through the `driver` field (which points to the same underlying `AcpDriver`).
     pub acp_driver: Option<Arc<crate::driver::acp_driver::AcpDriver>>,
+    /// Per-session driver bindings for mixed MCP/ACP deployments.
+    ///
+    /// ACP sessions register the `AcpDriver` when their streams are wired;
+    /// MCP sessions register an `McpDriver` over the shared pending maps on
+    /// connect. Operator actions resolve through [`AppState::driver_for`]
+    /// so each decision reaches the protocol that owns the session.
+    pub driver_registry: Arc<DriverRegistry>,
+    /// Broadcast bus carrying agent events from both MCP and ACP drivers.
+    ///
+    /// Audit, metrics, and Slack status subscribers are spawned against it
+    /// at startup; producers publish without knowing who is listening.
+    pub event_bus: Arc<EventBus>,
+    /// Session hook subscriptions registered by agents via `intercom/subscribe`.
+    pub session_hooks: Arc<SessionHooks>,
+    /// Pending "pause after current step" requests.
+    pub pause_requests: Arc<PauseRequests>,
+    /// Last heartbeat of each MCP session, for `[stall] heartbeat_required`.
+    pub heartbeats: Arc<HeartbeatLedger>,
+    /// Instructions queued for each session's next standby.
+    pub instruction_queue: Arc<InstructionQueue>,
+    /// Snoozed approval and prompt decisions.
+    pub snoozes: Arc<SnoozeTable>,
+    /// `check_diff` calls waiting for a protected-path confirmation.
+    pub apply_confirmations: Arc<ApplyConfirmations>,
+    /// Log lines agents streamed with `stream_log`, per session.
+    pub session_logs: Arc<SessionLogs>,
+    /// Channels of sessions moved with `session-move` or spawned into a
+    /// channel.
+    pub session_channels: Arc<SessionChannels>,
+    /// Unmapped workspaces awaiting operator approval.
+    pub workspace_discovery: Arc<WorkspaceDiscovery>,
+    /// `config.toml` the server was loaded from; runtime `[[workspace]]`
+    /// edits are written back to it.
+    pub config_path: Option<std::path::PathBuf>,
+    /// Command aliases added with `commands add` since startup, on top of
+    /// `config.commands`.
+    pub command_aliases: Arc<CommandAliases>,
+    /// Connections to the downstream MCP servers of `[[workspace.proxy]]`.
+    pub proxy: Arc<ProxyRegistry>,
+    /// Recently handled Slack deliveries, for dropping redelivered envelopes.
+    pub replay_guard: Arc<ReplayGuard>,
+    /// Per-subject ordering of Slack interactions (button clicks, modal
+    /// submissions).
+    pub interactions: Arc<InteractionQueue>,
+    /// Requests re-armed at startup, awaiting their agent's `reboot`.
+    pub rearmed: Arc<RearmedRequests>,
+    /// Chunked diff uploads awaiting `check_clearance`.
+    pub diff_staging: Arc<DiffStaging>,
+    /// Supervised background tasks: running state and restarts.
+    pub subsystems: Arc<Subsystems>,
+    /// Scripted operator answering approvals and prompts
+    /// (`--simulate-operator`); `None` when a human decides.
+    pub simulator: Option<Arc<OperatorScript>>,
+    /// Reloadable tracing filter for the `log-level` IPC command; `None`
+    /// when the embedding binary did not install one.
+    pub log_filter: Option<Arc<LogFilter>>,
+}
+
+impl AppState {
+    /// Return the driver responsible for `session_id`.
+    ///
+    /// Falls back to the server-wide [`driver`](Self::driver) when the
+    /// session has no explicit binding (e.g. sessions created before the
+    /// registry existed, or records whose session could not be resolved).
+    pub async fn driver_for(&self, session_id: &str) -> Arc<dyn AgentDriver> {
+        self.driver_registry.resolve(session_id, &self.driver).await
+    }
+
+    /// Build an MCP driver over this state's shared pending-request maps.
+    ///
+    /// Used to bind MCP sessions in the [`driver_registry`](Self::driver_registry)
+    /// regardless of the server mode chosen at startup.
+    #[must_use]
+    pub fn mcp_driver(&self) -> Arc<dyn AgentDriver> {
+        Arc::new(self.new_mcp_driver())
+    }
+
+    /// Like [`mcp_driver`](Self::mcp_driver), but bound to a connected
+    /// client so the driver can push notifications to it.
+    #[must_use]
+    pub fn mcp_driver_with_peer(
+        &self,
+        peer: rmcp::service::Peer<rmcp::service::RoleServer>,
+    ) -> Arc<dyn AgentDriver> {
+        Arc::new(self.new_mcp_driver().with_peer(peer))
+    }
+
+    fn new_mcp_driver(&self) -> crate::driver::mcp_driver::McpDriver {
+        crate::driver::mcp_driver::McpDriver::new(
+            Arc::clone(&self.pending_approvals),
+            Arc::clone(&self.pending_prompts),
+            Arc::clone(&self.pending_waits),
+        )
+    }
+
+    /// Deliver `event` to the agent of `session_id` if it subscribed to the
+    /// event's kind. Delivery failures are logged, never returned.
+    pub async fn notify_session_hook(&self, session_id: &str, event: SessionHookEvent) {
+        if !self.session_hooks.is_subscribed(session_id, event.kind()) {
+            return;
+        }
+        let params = event.params(session_id);
+        let driver = self.driver_for(session_id).await;
+        if let Err(err) = driver.notify(session_id, EVENT_METHOD, params).await {
+            tracing::warn!(%err, session_id, "failed to deliver session hook event");
+        }
+    }
 }
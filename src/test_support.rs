@@ -0,0 +1,573 @@
+//! Builders and fakes for testing tool handlers (feature `test-support`).
+//!
+//! Compiled only with `--features test-support`. Lets tests outside this
+//! crate exercise the blocking approval lifecycle — a handler parks on a
+//! `oneshot` registered in [`AppState::pending_approvals`] until an
+//! operator decides — without copying the integration suite's private
+//! helpers:
+//!
+//! - [`config`] and [`AppStateBuilder`] build an in-memory `AppState`
+//!   with no Slack client, whose default driver resolves through the
+//!   state's own pending maps as the running server does.
+//! - [`SessionBuilder`] and [`ApprovalBuilder`] persist sessions and
+//!   approval requests.
+//! - [`register_approval`] parks a receiver the way a tool handler does,
+//!   and [`record_driver`] binds a [`RecordingDriver`] to a session to
+//!   capture every decision and notification that reaches its agent.
+//!
+//! ```ignore
+//! let state = AppStateBuilder::new(config("/tmp/ws")?).build().await?;
+//! let session = SessionBuilder::new("/tmp/ws").create(&state.db).await?;
+//! let request = ApprovalBuilder::new(&session.id).create(&state.db).await?;
+//! let recorder = record_driver(&state, &session.id).await;
+//! let rx = register_approval(&state, &request.id).await;
+//! state
+//!     .driver_for(&session.id)
+//!     .await
+//!     .resolve_clearance(&request.id, true, None)
+//!     .await?;
+//! assert_eq!(rx.await?.status, "approved");
+//! assert_eq!(recorder.calls().len(), 1);
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use tokio::sync::oneshot;
+
+use crate::audit::{AuditEntry, AuditLogger};
+use crate::config::GlobalConfig;
+use crate::driver::mcp_driver::McpDriver;
+use crate::driver::AgentDriver;
+use crate::mode::ServerMode;
+use crate::models::approval::{ApprovalRequest, RiskLevel};
+use crate::models::session::{Session, SessionMode, SessionStatus};
+use crate::persistence::approval_repo::ApprovalRepo;
+use crate::persistence::db::{self, Database};
+use crate::persistence::session_repo::SessionRepo;
+use crate::state::{AppState, ApprovalResponse};
+use crate::Result;
+
+/// Owner of sessions created by [`SessionBuilder`] unless overridden.
+pub const TEST_OWNER: &str = "U_TEST_OWNER";
+
+/// Minimal configuration rooted at `workspace_root`: channel `C_TEST`,
+/// two-second approval, prompt and wait timeouts, stall detection off.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` if `workspace_root` does not exist.
+pub fn config(workspace_root: &str) -> Result<GlobalConfig> {
+    GlobalConfig::from_toml_str(&format!(
+        r#"
+default_workspace_root = '{root}'
+http_port = 0
+ipc_name = "test-support"
+max_concurrent_sessions = 5
+host_cli = "echo"
+
+[slack]
+channel_id = "C_TEST"
+
+[timeouts]
+approval_seconds = 2
+prompt_seconds = 2
+wait_seconds = 2
+
+[stall]
+enabled = false
+inactivity_threshold_seconds = 300
+escalation_threshold_seconds = 120
+max_retries = 3
+default_nudge_message = "continue"
+"#,
+        root = workspace_root.replace('\\', "\\\\"),
+    ))
+}
+
+/// Builder for an [`AppState`] with no Slack client.
+pub struct AppStateBuilder {
+    config: GlobalConfig,
+    db: Option<Arc<Database>>,
+    driver: Option<Arc<dyn AgentDriver>>,
+    audit_logger: Option<Arc<dyn AuditLogger>>,
+}
+
+impl AppStateBuilder {
+    /// Start from `config`, an in-memory database and an MCP driver.
+    #[must_use]
+    pub fn new(config: GlobalConfig) -> Self {
+        Self {
+            config,
+            db: None,
+            driver: None,
+            audit_logger: None,
+        }
+    }
+
+    /// Use an existing database instead of a fresh in-memory one.
+    #[must_use]
+    pub fn db(mut self, db: Arc<Database>) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    /// Use `driver` as the state's default driver, for example a
+    /// [`RecordingDriver`].
+    #[must_use]
+    pub fn driver(mut self, driver: Arc<dyn AgentDriver>) -> Self {
+        self.driver = Some(driver);
+        self
+    }
+
+    /// Write audit entries to `logger`, for example a
+    /// [`RecordingAuditLogger`].
+    #[must_use]
+    pub fn audit_logger(mut self, logger: Arc<dyn AuditLogger>) -> Self {
+        self.audit_logger = Some(logger);
+        self
+    }
+
+    /// Build the state.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the in-memory database cannot be opened.
+    pub async fn build(self) -> Result<Arc<AppState>> {
+        let db = match self.db {
+            Some(db) => db,
+            None => Arc::new(db::connect_memory().await?),
+        };
+        let pending_approvals = Arc::default();
+        let pending_prompts = Arc::default();
+        let pending_waits = Arc::default();
+        let driver = self.driver.unwrap_or_else(|| {
+            Arc::new(McpDriver::new(
+                Arc::clone(&pending_approvals),
+                Arc::clone(&pending_prompts),
+                Arc::clone(&pending_waits),
+            ))
+        });
+        Ok(Arc::new(AppState {
+            config: Arc::new(self.config),
+            db,
+            slack: None,
+            pending_approvals,
+            pending_prompts,
+            pending_waits,
+            pending_modal_contexts: Arc::default(),
+            pending_thread_replies: Arc::default(),
+            stall_detectors: None,
+            ipc_auth_token: None,
+            policy_cache: Arc::default(),
+            audit_logger: self.audit_logger,
+            active_children: Arc::default(),
+            pending_command_approvals: Arc::default(),
+            stall_event_tx: None,
+            driver,
+            server_mode: ServerMode::Mcp,
+            workspace_mappings: Arc::default(),
+            acp_event_tx: None,
+            acp_driver: None,
+            driver_registry: Arc::default(),
+            event_bus: Arc::default(),
+            session_hooks: Arc::default(),
+            pause_requests: Arc::default(),
+            heartbeats: Arc::default(),
+            instruction_queue: Arc::default(),
+            snoozes: Arc::default(),
+            apply_confirmations: Arc::default(),
+            session_logs: Arc::default(),
+            session_channels: Arc::default(),
+            workspace_discovery: Arc::default(),
+            config_path: None,
+            command_aliases: Arc::default(),
+            proxy: Arc::default(),
+            replay_guard: Arc::default(),
+            interactions: Arc::default(),
+            rearmed: Arc::default(),
+            diff_staging: Arc::default(),
+            subsystems: Arc::default(),
+            simulator: None,
+            log_filter: None,
+        }))
+    }
+}
+
+/// Builder for a persisted [`Session`], active by default.
+pub struct SessionBuilder {
+    session: Session,
+}
+
+impl SessionBuilder {
+    /// A remote-mode session in `workspace_root`, owned by [`TEST_OWNER`].
+    #[must_use]
+    pub fn new(workspace_root: &str) -> Self {
+        let mut session = Session::new(
+            TEST_OWNER.into(),
+            workspace_root.into(),
+            Some("test session".into()),
+            SessionMode::Remote,
+        );
+        session.status = SessionStatus::Active;
+        Self { session }
+    }
+
+    /// Owning Slack user.
+    #[must_use]
+    pub fn owner(mut self, user_id: &str) -> Self {
+        user_id.clone_into(&mut self.session.owner_user_id);
+        self
+    }
+
+    /// Operational mode.
+    #[must_use]
+    pub fn mode(mut self, mode: SessionMode) -> Self {
+        self.session.mode = mode;
+        self
+    }
+
+    /// Lifecycle status.
+    #[must_use]
+    pub fn status(mut self, status: SessionStatus) -> Self {
+        self.session.status = status;
+        self
+    }
+
+    /// Title shown in Slack.
+    #[must_use]
+    pub fn title(mut self, title: &str) -> Self {
+        self.session.title = Some(title.to_owned());
+        self
+    }
+
+    /// Insert the session.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the insert fails.
+    pub async fn create(self, db: &Arc<Database>) -> Result<Session> {
+        SessionRepo::new(Arc::clone(db)).create(&self.session).await
+    }
+}
+
+/// Builder for a persisted pending [`ApprovalRequest`].
+pub struct ApprovalBuilder {
+    request: ApprovalRequest,
+}
+
+impl ApprovalBuilder {
+    /// A low-risk request in `session_id` creating `src/lib.rs`.
+    #[must_use]
+    pub fn new(session_id: &str) -> Self {
+        Self {
+            request: ApprovalRequest::new(
+                session_id.to_owned(),
+                "test change".into(),
+                None,
+                "+fn added() {}\n".into(),
+                "src/lib.rs".into(),
+                RiskLevel::Low,
+                "new_file".into(),
+            ),
+        }
+    }
+
+    /// Title shown on the approval card.
+    #[must_use]
+    pub fn title(mut self, title: &str) -> Self {
+        title.clone_into(&mut self.request.title);
+        self
+    }
+
+    /// Workspace-relative file the change targets.
+    #[must_use]
+    pub fn file_path(mut self, file_path: &str) -> Self {
+        file_path.clone_into(&mut self.request.file_path);
+        self
+    }
+
+    /// Proposed diff or full file content.
+    #[must_use]
+    pub fn diff(mut self, diff: &str) -> Self {
+        diff.clone_into(&mut self.request.diff_content);
+        self
+    }
+
+    /// Risk classification.
+    #[must_use]
+    pub fn risk_level(mut self, risk_level: RiskLevel) -> Self {
+        self.request.risk_level = risk_level;
+        self
+    }
+
+    /// Insert the request.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Db` if the insert fails.
+    pub async fn create(self, db: &Arc<Database>) -> Result<ApprovalRequest> {
+        ApprovalRepo::new(Arc::clone(db))
+            .create(&self.request)
+            .await
+    }
+}
+
+/// Park on `request_id` the way a blocking tool handler does: register a
+/// sender in [`AppState::pending_approvals`] and return its receiver,
+/// which yields once the request is decided through a driver.
+pub async fn register_approval(
+    state: &AppState,
+    request_id: &str,
+) -> oneshot::Receiver<ApprovalResponse> {
+    let (tx, rx) = oneshot::channel();
+    state
+        .pending_approvals
+        .lock()
+        .await
+        .insert(request_id.to_owned(), tx);
+    rx
+}
+
+/// Bind a [`RecordingDriver`] over the state's MCP driver to `session_id`
+/// in the [driver registry](AppState::driver_registry), so every decision
+/// and notification for the session is captured and still delivered.
+pub async fn record_driver(state: &AppState, session_id: &str) -> Arc<RecordingDriver> {
+    let driver = Arc::new(RecordingDriver::wrapping(state.mcp_driver()));
+    state
+        .driver_registry
+        .register(session_id, Arc::clone(&driver) as Arc<dyn AgentDriver>)
+        .await;
+    driver
+}
+
+/// A call that reached a [`RecordingDriver`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DriverCall {
+    /// [`AgentDriver::resolve_clearance`].
+    Clearance {
+        /// Request decided.
+        request_id: String,
+        /// Whether it was approved.
+        approved: bool,
+        /// Rejection reason.
+        reason: Option<String>,
+    },
+    /// [`AgentDriver::send_prompt`].
+    Prompt {
+        /// Session prompted.
+        session_id: String,
+        /// Prompt or nudge text.
+        prompt: String,
+    },
+    /// [`AgentDriver::interrupt`].
+    Interrupt {
+        /// Session interrupted.
+        session_id: String,
+    },
+    /// [`AgentDriver::resolve_prompt`].
+    PromptDecision {
+        /// Prompt decided.
+        prompt_id: String,
+        /// `continue`, `refine` or `stop`.
+        decision: String,
+        /// Revised instruction.
+        instruction: Option<String>,
+    },
+    /// [`AgentDriver::resolve_wait`].
+    Wait {
+        /// Session resumed.
+        session_id: String,
+        /// Instruction it resumed with.
+        instruction: Option<String>,
+    },
+    /// [`AgentDriver::notify`].
+    Notify {
+        /// Session notified.
+        session_id: String,
+        /// Notification method.
+        method: String,
+        /// Notification parameters.
+        params: serde_json::Value,
+    },
+}
+
+/// Fake driver that records every call before passing it to an inner
+/// driver, so pending oneshots still resolve.
+pub struct RecordingDriver {
+    inner: Option<Arc<dyn AgentDriver>>,
+    calls: Mutex<Vec<DriverCall>>,
+}
+
+impl RecordingDriver {
+    /// Record calls and pass them to `inner`, typically
+    /// [`AppState::mcp_driver`].
+    #[must_use]
+    pub fn wrapping(inner: Arc<dyn AgentDriver>) -> Self {
+        Self {
+            inner: Some(inner),
+            calls: Mutex::default(),
+        }
+    }
+
+    /// Record calls and report success without delivering anything.
+    #[must_use]
+    pub fn detached() -> Self {
+        Self {
+            inner: None,
+            calls: Mutex::default(),
+        }
+    }
+
+    /// Calls recorded so far, oldest first.
+    #[must_use]
+    pub fn calls(&self) -> Vec<DriverCall> {
+        self.calls
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    fn record(&self, call: DriverCall) -> Option<&Arc<dyn AgentDriver>> {
+        self.calls
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(call);
+        self.inner.as_ref()
+    }
+}
+
+impl AgentDriver for RecordingDriver {
+    fn resolve_clearance(
+        &self,
+        request_id: &str,
+        approved: bool,
+        reason: Option<String>,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+        let inner = self.record(DriverCall::Clearance {
+            request_id: request_id.to_owned(),
+            approved,
+            reason: reason.clone(),
+        });
+        match inner {
+            Some(inner) => inner.resolve_clearance(request_id, approved, reason),
+            None => Box::pin(async { Ok(()) }),
+        }
+    }
+
+    fn send_prompt(
+        &self,
+        session_id: &str,
+        prompt: &str,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+        let inner = self.record(DriverCall::Prompt {
+            session_id: session_id.to_owned(),
+            prompt: prompt.to_owned(),
+        });
+        match inner {
+            Some(inner) => inner.send_prompt(session_id, prompt),
+            None => Box::pin(async { Ok(()) }),
+        }
+    }
+
+    fn interrupt(&self, session_id: &str) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+        let inner = self.record(DriverCall::Interrupt {
+            session_id: session_id.to_owned(),
+        });
+        match inner {
+            Some(inner) => inner.interrupt(session_id),
+            None => Box::pin(async { Ok(()) }),
+        }
+    }
+
+    fn resolve_prompt(
+        &self,
+        prompt_id: &str,
+        decision: &str,
+        instruction: Option<String>,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+        let inner = self.record(DriverCall::PromptDecision {
+            prompt_id: prompt_id.to_owned(),
+            decision: decision.to_owned(),
+            instruction: instruction.clone(),
+        });
+        match inner {
+            Some(inner) => inner.resolve_prompt(prompt_id, decision, instruction),
+            None => Box::pin(async { Ok(()) }),
+        }
+    }
+
+    fn resolve_wait(
+        &self,
+        session_id: &str,
+        instruction: Option<String>,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+        let inner = self.record(DriverCall::Wait {
+            session_id: session_id.to_owned(),
+            instruction: instruction.clone(),
+        });
+        match inner {
+            Some(inner) => inner.resolve_wait(session_id, instruction),
+            None => Box::pin(async { Ok(()) }),
+        }
+    }
+
+    fn notify(
+        &self,
+        session_id: &str,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+        let inner = self.record(DriverCall::Notify {
+            session_id: session_id.to_owned(),
+            method: method.to_owned(),
+            params: params.clone(),
+        });
+        match inner {
+            Some(inner) => inner.notify(session_id, method, params),
+            None => Box::pin(async { Ok(()) }),
+        }
+    }
+}
+
+/// Audit logger that keeps entries in memory.
+#[derive(Default)]
+pub struct RecordingAuditLogger {
+    entries: Mutex<Vec<AuditEntry>>,
+}
+
+impl RecordingAuditLogger {
+    /// Entries logged so far, oldest first.
+    #[must_use]
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl AuditLogger for RecordingAuditLogger {
+    fn log_entry(&self, entry: AuditEntry) -> Result<()> {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(entry);
+        Ok(())
+    }
+}
+
+/// Pending approval senders of `state`, by request ID; handy for asserting
+/// a handler registered or released its oneshot.
+pub async fn pending_approval_ids(state: &AppState) -> Vec<String> {
+    let mut ids: Vec<String> = state
+        .pending_approvals
+        .lock()
+        .await
+        .keys()
+        .cloned()
+        .collect();
+    ids.sort();
+    ids
+}
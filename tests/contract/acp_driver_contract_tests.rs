@@ -426,3 +426,46 @@ async fn acp_driver_resolve_wait_missing_agent_sid_returns_not_found() {
     assert!(result.is_err());
     assert!(matches!(result.unwrap_err(), AppError::NotFound(_)));
 }
+
+// ── backpressure — saturated writer queue ───────────────────────────────────
+
+/// When the session's writer queue is full (agent not draining stdin),
+/// `resolve_clearance` fails with `AppError::Acp` within the write timeout
+/// instead of blocking the caller, and the queue metrics record it.
+#[tokio::test]
+async fn acp_driver_resolve_clearance_on_saturated_queue_fails_fast() {
+    use agent_intercom::acp::writer::WriterLimits;
+    use agent_intercom::config::AcpWriterOverflow;
+    use std::time::Duration;
+
+    let driver = AcpDriver::with_limits(WriterLimits {
+        queue_capacity: 1,
+        write_timeout: Duration::from_millis(50),
+        overflow: AcpWriterOverflow::Wait,
+    });
+    let (tx, _rx) = mpsc::channel(1);
+    driver.register_session("sess-011", tx).await;
+    driver.register_clearance("sess-011", "req-fill").await;
+    driver.register_clearance("sess-011", "req-blocked").await;
+
+    driver
+        .resolve_clearance("req-fill", true, None)
+        .await
+        .expect("first response fits in the queue");
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(2),
+        driver.resolve_clearance("req-blocked", true, None),
+    )
+    .await
+    .expect("resolve_clearance must not block past the write timeout");
+
+    assert!(matches!(result, Err(AppError::Acp(_))));
+    let metrics = driver
+        .writer_metrics("sess-011")
+        .await
+        .expect("metrics registered with the session");
+    let snap = metrics.snapshot();
+    assert_eq!(snap.enqueued, 1);
+    assert_eq!(snap.enqueue_timeouts, 1);
+}
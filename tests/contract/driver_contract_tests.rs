@@ -89,3 +89,15 @@ async fn mcp_driver_resolve_clearance_unknown_id_returns_not_found() {
         "error must be NotFound, got: {err}"
     );
 }
+
+// ── send_prompt without a connection ─────────────────────────────────────────
+
+#[tokio::test]
+async fn mcp_driver_send_prompt_without_peer_returns_not_found() {
+    let driver = McpDriver::new_empty();
+    let result = driver.send_prompt("sess-1", "continue").await;
+    assert!(
+        matches!(result, Err(AppError::NotFound(_))),
+        "unbound driver cannot deliver nudges: {result:?}"
+    );
+}
@@ -200,6 +200,60 @@ fn limit_maximum_is_100() {
     assert!(limit > 100, "limit 101 exceeds maximum");
 }
 
+// ─── Pagination and thread parameters ───────────────────────────────────
+
+#[test]
+fn uri_parsing_ignores_query() {
+    use agent_intercom::mcp::resources::slack_channel::parse_channel_uri;
+
+    let uri = format!("slack://channel/{VALID_CHANNEL_ID}/recent?limit=5&threads=true");
+    assert_eq!(parse_channel_uri(&uri), Some(VALID_CHANNEL_ID));
+}
+
+#[test]
+fn query_defaults_without_parameters() {
+    use agent_intercom::mcp::resources::slack_channel::{parse_query, DEFAULT_LIMIT};
+
+    let query = parse_query("slack://channel/C0123456789/recent").expect("parses");
+    assert_eq!(query.limit, DEFAULT_LIMIT);
+    assert_eq!(query.cursor, None);
+    assert!(!query.threads);
+}
+
+#[test]
+fn query_parses_limit_cursor_and_threads() {
+    use agent_intercom::mcp::resources::slack_channel::parse_query;
+
+    let query = parse_query(
+        "slack://channel/C0123456789/recent?limit=500&cursor=1707300000.000100&threads=true&x=1",
+    )
+    .expect("parses");
+    assert_eq!(query.limit, 100, "limit is clamped");
+    assert_eq!(query.cursor.as_deref(), Some("1707300000.000100"));
+    assert!(query.threads);
+
+    let query = parse_query("slack://channel/C0123456789/recent?limit=abc").expect("parses");
+    assert_eq!(query.limit, 20, "unparseable limit falls back to default");
+}
+
+#[test]
+fn query_rejects_invalid_cursor_and_threads() {
+    use agent_intercom::mcp::resources::slack_channel::parse_query;
+    use agent_intercom::AppError;
+
+    for uri in [
+        "slack://channel/C0123456789/recent?cursor=abc",
+        "slack://channel/C0123456789/recent?cursor=1707300000",
+        "slack://channel/C0123456789/recent?cursor=.000100",
+        "slack://channel/C0123456789/recent?threads=yes",
+    ] {
+        assert!(
+            matches!(parse_query(uri), Err(AppError::Config(_))),
+            "'{uri}' should be rejected"
+        );
+    }
+}
+
 // ─── Resource metadata ─────────────────────────────────────────────────
 
 #[test]
@@ -258,3 +312,163 @@ fn output_conforms_to_contract_schema() {
         }
     }
 }
+
+// ─── Session report resource ────────────────────────────────────────────
+
+#[test]
+fn report_uri_parsing_extracts_session_id() {
+    use agent_intercom::mcp::resources::session_report::parse_report_uri;
+
+    assert_eq!(
+        parse_report_uri("intercom://session/3f2a9c1e/report"),
+        Some("3f2a9c1e")
+    );
+    for uri in [
+        "intercom://session//report",
+        "intercom://session/3f2a9c1e/recent",
+        "slack://channel/C123/recent",
+        "",
+    ] {
+        assert!(
+            parse_report_uri(uri).is_none(),
+            "malformed URI '{uri}' should be rejected"
+        );
+    }
+}
+
+#[test]
+fn report_template_is_markdown() {
+    use agent_intercom::mcp::resources::session_report::resource_template;
+
+    let template = resource_template();
+    assert_eq!(template.raw.uri_template, "intercom://session/{id}/report");
+    assert_eq!(template.raw.mime_type.as_deref(), Some("text/markdown"));
+}
+
+// ─── Session timeline resource ──────────────────────────────────────────
+
+#[test]
+fn timeline_uri_parsing_extracts_session_id() {
+    use agent_intercom::mcp::resources::session_timeline::parse_timeline_uri;
+
+    assert_eq!(
+        parse_timeline_uri("intercom://session/3f2a9c1e/timeline"),
+        Some("3f2a9c1e")
+    );
+    for uri in [
+        "intercom://session//timeline",
+        "intercom://session/3f2a9c1e/report",
+        "slack://channel/C123/recent",
+        "",
+    ] {
+        assert!(
+            parse_timeline_uri(uri).is_none(),
+            "malformed URI '{uri}' should be rejected"
+        );
+    }
+}
+
+#[test]
+fn timeline_template_is_json() {
+    use agent_intercom::mcp::resources::session_timeline::resource_template;
+
+    let template = resource_template();
+    assert_eq!(
+        template.raw.uri_template,
+        "intercom://session/{id}/timeline"
+    );
+    assert_eq!(template.raw.mime_type.as_deref(), Some("application/json"));
+}
+
+// ─── Session logs resource ──────────────────────────────────────────────
+
+#[test]
+fn logs_uri_parsing_extracts_session_id() {
+    use agent_intercom::mcp::resources::session_logs::parse_logs_uri;
+
+    assert_eq!(
+        parse_logs_uri("intercom://session/3f2a9c1e/logs"),
+        Some("3f2a9c1e")
+    );
+    for uri in [
+        "intercom://session//logs",
+        "intercom://session/3f2a9c1e/timeline",
+        "intercom://session/3f2a9c1e/logs/extra",
+        "",
+    ] {
+        assert!(
+            parse_logs_uri(uri).is_none(),
+            "malformed URI '{uri}' should be rejected"
+        );
+    }
+}
+
+#[test]
+fn logs_template_is_json() {
+    use agent_intercom::mcp::resources::session_logs::resource_template;
+
+    let template = resource_template();
+    assert_eq!(template.raw.uri_template, "intercom://session/{id}/logs");
+    assert_eq!(template.raw.mime_type.as_deref(), Some("application/json"));
+}
+
+// ─── Session context resources ──────────────────────────────────────────
+
+#[test]
+fn context_uri_parsing_extracts_session_and_view() {
+    use agent_intercom::mcp::resources::session_context::{parse_context_uri, ContextView};
+
+    for (suffix, view) in [
+        ("decisions", ContextView::Decisions),
+        ("plan", ContextView::Plan),
+        ("steering", ContextView::Steering),
+    ] {
+        let uri = format!("intercom://session/3f2a9c1e/{suffix}");
+        assert_eq!(parse_context_uri(&uri), Some(("3f2a9c1e", view)));
+    }
+    for uri in [
+        "intercom://session//plan",
+        "intercom://session/3f2a9c1e/report",
+        "intercom://session/3f2a9c1e/plan/extra",
+        "",
+    ] {
+        assert!(
+            parse_context_uri(uri).is_none(),
+            "malformed URI '{uri}' should be rejected"
+        );
+    }
+}
+
+#[test]
+fn context_templates_and_session_resources_are_markdown() {
+    use agent_intercom::mcp::resources::session_context::{resource_templates, resources_for};
+
+    let templates: Vec<String> = resource_templates()
+        .into_iter()
+        .map(|t| {
+            assert_eq!(t.raw.mime_type.as_deref(), Some("text/markdown"));
+            t.raw.uri_template
+        })
+        .collect();
+    assert_eq!(
+        templates,
+        [
+            "intercom://session/{id}/decisions",
+            "intercom://session/{id}/plan",
+            "intercom://session/{id}/steering",
+        ]
+    );
+
+    let uris: Vec<String> = resources_for("abc")
+        .into_iter()
+        .map(|r| r.raw.uri)
+        .collect();
+    assert_eq!(
+        uris,
+        [
+            "intercom://session/abc/decisions",
+            "intercom://session/abc/plan",
+            "intercom://session/abc/steering",
+        ]
+    );
+}
@@ -70,6 +70,20 @@ async fn session_table_has_expected_columns() {
         "restart_of",
         "agent_session_id",
         "title",
+        "deadline",
+        "wrap_up_sent",
+        "issue_ref",
+        "parent_session_id",
+        "muted",
+        "muted_until",
+        "deleted_at",
+        "tags",
+        "run_id",
+        "client_name",
+        "client_version",
+        "env",
+        "max_duration_seconds",
+        "short_id",
     ];
 
     assert_eq!(
@@ -107,6 +121,11 @@ async fn approval_request_table_has_expected_columns() {
         "slack_ts",
         "created_at",
         "consumed_at",
+        "thread_ts",
+        "short_id",
+        "apply_after",
+        "stale_at",
+        "decision_reason",
     ];
 
     assert_eq!(
@@ -141,10 +160,12 @@ async fn steering_message_table_has_origin_session_id_column() {
         "created_at",
         "consumed",
         "origin_session_id",
+        "priority",
+        "expires_at",
     ];
 
     assert_eq!(
         column_names, expected,
-        "steering_message table should include additive origin_session_id column"
+        "steering_message table should include additive origin_session_id, priority and expires_at columns"
     );
 }
@@ -1,14 +1,19 @@
 #![allow(clippy::expect_used, clippy::unwrap_used, missing_docs)]
 
 mod integration {
+    mod simulated_operator_tests;
     mod test_helpers;
 
     mod acp_lifecycle_tests;
     mod acp_mcp_bridge_tests;
     mod approval_flow_tests;
+    mod auto_checkpoint_tests;
     mod call_tool_dispatch_tests;
     mod channel_override_tests;
     mod checkpoint_manager_tests;
+    mod checkpoint_tool_tests;
+    mod client_gating_tests;
+    mod config_endpoint_tests;
     mod crash_recovery_tests;
     mod diff_apply_tests;
     mod handler_accept_diff_tests;
@@ -20,33 +25,72 @@ mod integration {
     mod handler_recover_tests;
     mod handler_remote_log_tests;
     mod health_endpoint_tests;
+    mod issue_link_tests;
     mod nudge_flow_tests;
     mod on_initialized_tests;
+    mod progress_keepalive_tests;
     mod prompt_flow_tests;
     mod retention_tests;
     mod session_lifecycle_tests;
     mod session_manager_tests;
+    mod session_pause_tests;
+    mod session_summary_email_tests;
+    mod session_timebox_tests;
     mod shutdown_recovery_tests;
     mod stall_escalation_tests;
+    mod standby_wake_tests;
 
     mod acp_event_integration;
+    mod approval_link_tests;
+    mod approval_stats_tests;
+    mod approval_webhook_tests;
     mod at_mention_routing_integration_tests;
+    mod device_pairing_tests;
+    mod diff_staging_tests;
     mod disconnect_tests;
+    #[cfg(feature = "grpc")]
+    mod grpc_control_tests;
+    mod health_ready_tests;
+    mod heartbeat_enforcement_tests;
+    mod http_body_limit_tests;
     mod inbox_flow_tests;
+    mod instruction_queue_tests;
     mod ipc_server_tests;
+    mod knowledge_flow_tests;
     mod mcp_dispatch_tests;
+    mod mcp_notification_tests;
+    mod mcp_proxy_tests;
+    mod modal_context_tests;
+    mod path_lock_flow_tests;
+    mod policy_drift_tests;
     mod policy_watcher_tests;
+    mod prefs_command_tests;
+    mod prompt_policy_tests;
+    mod protected_path_tests;
     mod push_events_tests;
+    mod relay_flow_tests;
+    mod run_command_tests;
+    mod scheduled_apply_tests;
+    mod session_context_flow_tests;
+    mod session_inheritance_tests;
+    mod session_note_tests;
+    mod session_share_tests;
     mod shutdown_tests;
     mod slack_fallback_tests;
     mod slack_interaction_tests;
     mod slack_modal_flow_tests;
+    mod slack_shortcut_tests;
     mod slack_threading_tests;
+    mod stale_approval_tests;
     mod startup_tests;
     mod stdio_transport_tests;
     mod steering_flow_tests;
+    mod stream_log_flow_tests;
     mod streamable_http_tests;
+    mod subtask_flow_tests;
     mod thread_reply_integration;
     mod thread_routing_tests;
+    mod working_hours_tests;
     mod workspace_routing_tests;
+    mod workspace_snapshot_tests;
 }
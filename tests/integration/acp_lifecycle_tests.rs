@@ -27,6 +27,7 @@ async fn acp_session_start_creates_session_with_acp_protocol_mode() {
         host_cli: "echo".to_owned(),
         host_cli_args: Vec::new(),
         workspace_root: std::env::temp_dir(),
+        env: std::collections::BTreeMap::new(),
     };
 
     // Build a Session the way the ACP session-start handler does.
@@ -63,6 +63,27 @@ async fn spawn_acp_http_server() -> (
         workspace_mappings: Arc::default(),
         acp_event_tx: None,
         acp_driver: None,
+        driver_registry: Arc::default(),
+        event_bus: Arc::default(),
+        session_hooks: Arc::default(),
+        pause_requests: Arc::default(),
+        heartbeats: Arc::default(),
+        instruction_queue: Arc::default(),
+        snoozes: Arc::default(),
+        apply_confirmations: Arc::default(),
+        session_logs: Arc::default(),
+        session_channels: Arc::default(),
+        workspace_discovery: Arc::default(),
+        config_path: None,
+        command_aliases: Arc::default(),
+        proxy: Arc::default(),
+        replay_guard: Arc::default(),
+        interactions: Arc::default(),
+        rearmed: Arc::default(),
+        diff_staging: Arc::default(),
+        subsystems: Arc::default(),
+        simulator: None,
+        log_filter: None,
     });
 
     let ct = CancellationToken::new();
@@ -205,3 +205,31 @@ async fn approval_flow_pending_for_session_query() {
         Some(ApprovalStatus::Pending)
     );
 }
+
+#[tokio::test]
+async fn approval_flow_late_decision_keeps_first_outcome() {
+    let _config = test_config();
+    let database = Arc::new(db::connect_memory().await.expect("db connect"));
+    let repo = ApprovalRepo::new(database);
+
+    let request = sample_request("session-race");
+    let request_id = request.id.clone();
+    repo.create(&request).await.expect("create");
+
+    // The timeout wins; the operator's click arrives afterwards.
+    assert!(repo
+        .decide(&request_id, ApprovalStatus::Expired)
+        .await
+        .expect("expire"));
+    assert!(!repo
+        .decide(&request_id, ApprovalStatus::Approved)
+        .await
+        .expect("late approve"));
+
+    let fetched = repo
+        .get_by_id(&request_id)
+        .await
+        .expect("fetch")
+        .expect("approval should exist");
+    assert_eq!(fetched.status, ApprovalStatus::Expired);
+}
@@ -0,0 +1,275 @@
+//! Integration tests for signed approval links.
+//!
+//! The approval page router is served on an ephemeral port and driven with
+//! `reqwest`, with an agent parked on `pending_approvals` as
+//! `check_clearance` would be.
+//!
+//! Tests cover:
+//! - A valid link renders the request without deciding it
+//! - Posting the page's form approves or rejects and resolves the agent
+//! - Expired, forged and re-targeted links are refused
+//! - Unknown and already-decided requests are reported
+
+use std::sync::Arc;
+
+use agent_intercom::config::ApprovalLinksConfig;
+use agent_intercom::mcp::approval_link;
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::persistence::approval_repo::ApprovalRepo;
+use agent_intercom::state::{AppState, ApprovalResponse};
+use tokio::sync::oneshot;
+
+use super::test_helpers::{create_active_session, test_app_state, test_config};
+
+const SECRET: &str = "link-test-secret";
+
+struct Harness {
+    state: Arc<AppState>,
+    base_url: String,
+    client: reqwest::Client,
+    _temp: tempfile::TempDir,
+}
+
+async fn harness() -> Harness {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path").to_owned();
+    let mut config = test_config(&root);
+    config.approval_links.enabled = true;
+    config.approval_links.base_url = Some("https://intercom.acme.test".into());
+    config.approval_links.secret = SECRET.into();
+    let state = test_app_state(config).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let base_url = format!("http://{}", listener.local_addr().expect("addr"));
+    let router = approval_link::router(Arc::clone(&state));
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+
+    Harness {
+        state,
+        base_url,
+        client: reqwest::Client::new(),
+        _temp: temp,
+    }
+}
+
+impl Harness {
+    /// Persist a pending approval and park a waiter on it.
+    async fn pending(&self) -> (String, oneshot::Receiver<ApprovalResponse>) {
+        let root = self.state.config.default_workspace_root().to_string_lossy();
+        let session = create_active_session(&self.state.db, &root).await;
+        let approval = ApprovalRequest::new(
+            session.id,
+            "Add <parser>".into(),
+            Some("Adds the parser".into()),
+            "+fn parse() {}".into(),
+            "src/parser.rs".into(),
+            RiskLevel::High,
+            "new_file".into(),
+        );
+        ApprovalRepo::new(Arc::clone(&self.state.db))
+            .create(&approval)
+            .await
+            .expect("create approval");
+        let (tx, rx) = oneshot::channel();
+        self.state
+            .pending_approvals
+            .lock()
+            .await
+            .insert(approval.id.clone(), tx);
+        (approval.id, rx)
+    }
+
+    async fn get(&self, request_id: &str, exp: i64, sig: &str) -> (u16, String) {
+        let response = self
+            .client
+            .get(format!(
+                "{}/approvals/{request_id}?exp={exp}&sig={sig}",
+                self.base_url
+            ))
+            .send()
+            .await
+            .expect("send");
+        let status = response.status().as_u16();
+        (status, response.text().await.unwrap_or_default())
+    }
+
+    async fn post(&self, request_id: &str, exp: i64, sig: &str, fields: &str) -> (u16, String) {
+        let response = self
+            .client
+            .post(format!("{}/approvals/{request_id}", self.base_url))
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(format!("exp={exp}&sig={sig}&{fields}"))
+            .send()
+            .await
+            .expect("send");
+        let status = response.status().as_u16();
+        (status, response.text().await.unwrap_or_default())
+    }
+
+    async fn status_of(&self, request_id: &str) -> ApprovalStatus {
+        ApprovalRepo::new(Arc::clone(&self.state.db))
+            .get_by_id(request_id)
+            .await
+            .expect("get")
+            .expect("record")
+            .status
+    }
+}
+
+fn now() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+#[tokio::test]
+async fn valid_link_renders_request_without_deciding() {
+    let h = harness().await;
+    let (id, _rx) = h.pending().await;
+    let exp = now() + 600;
+    let sig = approval_link::sign(SECRET, &id, exp);
+
+    let (status, page) = h.get(&id, exp, &sig).await;
+    assert_eq!(status, 200, "{page}");
+    assert!(page.contains("Add &lt;parser&gt;"), "{page}");
+    assert!(page.contains("src/parser.rs"), "{page}");
+    assert!(page.contains("+fn parse() {}"), "{page}");
+    assert!(page.contains(&format!("value=\"{sig}\"")), "{page}");
+    assert_eq!(h.status_of(&id).await, ApprovalStatus::Pending);
+}
+
+#[tokio::test]
+async fn approve_form_resolves_waiting_agent() {
+    let h = harness().await;
+    let (id, rx) = h.pending().await;
+    let exp = now() + 600;
+    let sig = approval_link::sign(SECRET, &id, exp);
+
+    let (status, page) = h.post(&id, exp, &sig, "decision=approve&reason=").await;
+    assert_eq!(status, 200, "{page}");
+    assert!(page.contains("Approved"), "{page}");
+
+    let response = rx.await.expect("agent resolved");
+    assert_eq!(response.status, "approved");
+    assert_eq!(h.status_of(&id).await, ApprovalStatus::Approved);
+
+    let (status, _) = h.post(&id, exp, &sig, "decision=reject").await;
+    assert_eq!(status, 409);
+    let (status, _) = h.get(&id, exp, &sig).await;
+    assert_eq!(status, 409);
+}
+
+#[tokio::test]
+async fn reject_form_carries_reason() {
+    let h = harness().await;
+    let (id, rx) = h.pending().await;
+    let exp = now() + 600;
+    let sig = approval_link::sign(SECRET, &id, exp);
+
+    let (status, _) = h
+        .post(&id, exp, &sig, "decision=reject&reason=tests+are+red")
+        .await;
+    assert_eq!(status, 200);
+
+    let response = rx.await.expect("agent resolved");
+    assert_eq!(response.status, "rejected");
+    assert_eq!(response.reason.as_deref(), Some("tests are red"));
+    assert_eq!(h.status_of(&id).await, ApprovalStatus::Rejected);
+}
+
+#[tokio::test]
+async fn expired_forged_or_retargeted_links_are_refused() {
+    let h = harness().await;
+    let (id, _rx) = h.pending().await;
+    let (other, _other_rx) = h.pending().await;
+
+    let expired = now() - 1;
+    let sig = approval_link::sign(SECRET, &id, expired);
+    assert_eq!(h.get(&id, expired, &sig).await.0, 401);
+    assert_eq!(h.post(&id, expired, &sig, "decision=approve").await.0, 401);
+
+    let exp = now() + 600;
+    let forged = approval_link::sign("wrong-secret", &id, exp);
+    assert_eq!(h.post(&id, exp, &forged, "decision=approve").await.0, 401);
+
+    let for_other = approval_link::sign(SECRET, &other, exp);
+    assert_eq!(
+        h.post(&id, exp, &for_other, "decision=approve").await.0,
+        401
+    );
+
+    let sig = approval_link::sign(SECRET, &id, exp);
+    assert_eq!(h.post(&id, exp + 60, &sig, "decision=approve").await.0, 401);
+    assert_eq!(h.get(&id, exp, "").await.0, 401);
+
+    assert_eq!(h.status_of(&id).await, ApprovalStatus::Pending);
+}
+
+#[tokio::test]
+async fn unknown_request_is_not_found() {
+    let h = harness().await;
+    let exp = now() + 600;
+    let sig = approval_link::sign(SECRET, "no-such-request", exp);
+    assert_eq!(h.get("no-such-request", exp, &sig).await.0, 404);
+    assert_eq!(
+        h.post("no-such-request", exp, &sig, "decision=approve")
+            .await
+            .0,
+        404
+    );
+}
+
+#[test]
+fn link_url_is_signed_and_verifiable() {
+    let config = ApprovalLinksConfig {
+        enabled: true,
+        base_url: Some("https://intercom.acme.test/".into()),
+        ttl_seconds: 900,
+        secret: SECRET.into(),
+        ..ApprovalLinksConfig::default()
+    };
+    let url = approval_link::link_url(&config, "req-1", 1_700_000_000).expect("enabled");
+    let sig = approval_link::sign(SECRET, "req-1", 1_700_000_900);
+    assert_eq!(
+        url,
+        format!("https://intercom.acme.test/approvals/req-1?exp=1700000900&sig={sig}")
+    );
+    assert_eq!(sig.len(), 64);
+
+    assert!(approval_link::verify(SECRET, "req-1", 1_700_000_900, &sig, 1_700_000_899).is_ok());
+    assert!(approval_link::verify(SECRET, "req-1", 1_700_000_900, &sig, 1_700_000_900).is_err());
+    assert!(approval_link::verify(SECRET, "req-2", 1_700_000_900, &sig, 1_700_000_000).is_err());
+    assert!(approval_link::verify(SECRET, "req-1", 1_700_000_900, "zz", 1_700_000_000).is_err());
+
+    let disabled = ApprovalLinksConfig::default();
+    assert!(approval_link::link_url(&disabled, "req-1", 1_700_000_000).is_none());
+    assert!(approval_link::slack_line(&disabled, "req-1").is_none());
+    assert!(approval_link::slack_line(&config, "req-1")
+        .expect("enabled")
+        .contains("|Decide in a browser>"));
+}
+
+#[test]
+fn empty_secret_never_signs_or_verifies_links() {
+    let mut config = ApprovalLinksConfig {
+        enabled: true,
+        base_url: Some("https://intercom.acme.test".into()),
+        ..ApprovalLinksConfig::default()
+    };
+    assert!(approval_link::link_url(&config, "req-1", 1_700_000_000).is_none());
+    let forged = approval_link::sign("", "req-1", 1_700_000_900);
+    assert!(approval_link::verify("", "req-1", 1_700_000_900, &forged, 1_700_000_000).is_err());
+
+    config.ensure_secret();
+    assert_eq!(config.secret.len(), 64);
+    assert!(approval_link::link_url(&config, "req-1", 1_700_000_000).is_some());
+
+    let generated = config.secret.clone();
+    config.ensure_secret();
+    assert_eq!(config.secret, generated, "a loaded secret is kept");
+    let mut disabled = ApprovalLinksConfig::default();
+    disabled.ensure_secret();
+    assert!(disabled.secret.is_empty());
+}
@@ -0,0 +1,93 @@
+//! Integration tests for the `approval_stats` tool.
+//!
+//! Tests cover:
+//! - Workspace scope counts every session in the caller's workspace and
+//!   reads rejection reasons from the audit log
+//! - Session scope counts only the calling session
+
+use std::path::Path;
+use std::sync::Arc;
+
+use serde_json::json;
+
+use agent_intercom::audit::{self, AuditEntry, AuditEventType, AuditLogger, JsonlAuditWriter};
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::persistence::approval_repo::ApprovalRepo;
+
+use super::test_helpers::{
+    connect_mcp_client_as, create_active_session, test_app_state, test_config,
+};
+
+async fn decided(
+    repo: &ApprovalRepo,
+    session_id: &str,
+    risk_level: RiskLevel,
+    status: ApprovalStatus,
+) -> ApprovalRequest {
+    let request = ApprovalRequest::new(
+        session_id.into(),
+        "change".into(),
+        None,
+        "+x".into(),
+        "src/lib.rs".into(),
+        risk_level,
+        "hash".into(),
+    );
+    let created = repo.create(&request).await.expect("create");
+    repo.update_status(&created.id, status)
+        .await
+        .expect("status");
+    created
+}
+
+#[tokio::test]
+async fn approval_stats_by_scope() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let other = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(test_config(root)).await;
+
+    let me = create_active_session(&state.db, root).await;
+    let peer = create_active_session(&state.db, root).await;
+    let elsewhere =
+        create_active_session(&state.db, other.path().to_str().expect("utf8 path")).await;
+
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    decided(&repo, &me.id, RiskLevel::Low, ApprovalStatus::Approved).await;
+    let rejected = decided(&repo, &me.id, RiskLevel::High, ApprovalStatus::Rejected).await;
+    decided(&repo, &peer.id, RiskLevel::Low, ApprovalStatus::Consumed).await;
+    decided(
+        &repo,
+        &elsewhere.id,
+        RiskLevel::Low,
+        ApprovalStatus::Rejected,
+    )
+    .await;
+
+    let writer = JsonlAuditWriter::new(audit::log_dir(Path::new(root))).expect("audit writer");
+    writer
+        .log_entry(
+            AuditEntry::new(AuditEventType::Rejection)
+                .with_request_id(rejected.id.clone())
+                .with_reason("missing tests".into()),
+        )
+        .expect("audit entry");
+
+    let mut client = connect_mcp_client_as(&state, &me.id).await;
+    let workspace = client.call_tool(2, "approval_stats", json!({})).await;
+    assert_eq!(workspace["scope"], "workspace");
+    assert_eq!(workspace["overall"]["decided"], 3);
+    assert_eq!(workspace["by_risk_level"]["low"]["approval_percent"], 100);
+    assert_eq!(workspace["by_risk_level"]["high"]["rejected"], 1);
+    assert_eq!(
+        workspace["top_rejection_reasons"],
+        json!([{ "reason": "missing tests", "count": 1 }])
+    );
+
+    let session = client
+        .call_tool(3, "approval_stats", json!({ "scope": "session" }))
+        .await;
+    assert_eq!(session["scope"], "session");
+    assert_eq!(session["overall"]["decided"], 2);
+    assert_eq!(session["overall"]["approval_percent"], 50);
+}
@@ -0,0 +1,463 @@
+//! Integration tests for the signed approval webhook.
+//!
+//! The webhook router is served on an ephemeral port and driven with
+//! `reqwest`, with an agent parked on `pending_approvals` as
+//! `check_clearance` would be.
+//!
+//! Tests cover:
+//! - A signed approval resolves the waiting agent and the record
+//! - A signed rejection carries its reason to the agent
+//! - Bad signatures, stale timestamps and missing headers are refused
+//! - A signature made for one request is refused on another
+//! - Unknown and already-decided requests are reported; of two racing
+//!   decisions exactly one wins
+//! - An empty secret verifies nothing
+//! - Code-owner routing applies to the webhook's `actor`
+//! - The wait endpoint long-polls until a decision or its timeout, returns
+//!   rejection reasons, and accepts only the separate wait secret
+
+use std::sync::Arc;
+
+use agent_intercom::mcp::approval_webhook::{self, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::persistence::approval_repo::ApprovalRepo;
+use agent_intercom::state::{AppState, ApprovalResponse};
+use serde_json::json;
+use tokio::sync::oneshot;
+
+use super::test_helpers::{create_active_session, test_app_state, test_config};
+
+const SECRET: &str = "webhook-test-secret";
+const WAIT_SECRET: &str = "webhook-wait-secret";
+
+struct Harness {
+    state: Arc<AppState>,
+    base_url: String,
+    client: reqwest::Client,
+    _temp: tempfile::TempDir,
+}
+
+async fn harness() -> Harness {
+    harness_with_wait_secret(WAIT_SECRET).await
+}
+
+async fn harness_with_wait_secret(wait_secret: &str) -> Harness {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path").to_owned();
+    let mut config = test_config(&root);
+    config.webhooks.enabled = true;
+    config.webhooks.secret = SECRET.into();
+    config.webhooks.wait_secret = wait_secret.into();
+    // Inert until a test writes a CODEOWNERS file.
+    config.codeowners.require_owner_approval = true;
+    config.codeowners.slack_users = [("@parser-team".to_owned(), "U_CODE_OWNER".to_owned())]
+        .into_iter()
+        .collect();
+    let state = test_app_state(config).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let base_url = format!("http://{}", listener.local_addr().expect("addr"));
+    let router = approval_webhook::router(Arc::clone(&state));
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+
+    Harness {
+        state,
+        base_url,
+        client: reqwest::Client::new(),
+        _temp: temp,
+    }
+}
+
+impl Harness {
+    /// Persist a pending approval and park a waiter on it.
+    async fn pending(&self) -> (String, oneshot::Receiver<ApprovalResponse>) {
+        let root = self.state.config.default_workspace_root().to_string_lossy();
+        let session = create_active_session(&self.state.db, &root).await;
+        let approval = ApprovalRequest::new(
+            session.id,
+            "Add parser".into(),
+            None,
+            "+fn parse() {}".into(),
+            "src/parser.rs".into(),
+            RiskLevel::Low,
+            "new_file".into(),
+        );
+        ApprovalRepo::new(Arc::clone(&self.state.db))
+            .create(&approval)
+            .await
+            .expect("create approval");
+        let (tx, rx) = oneshot::channel();
+        self.state
+            .pending_approvals
+            .lock()
+            .await
+            .insert(approval.id.clone(), tx);
+        (approval.id, rx)
+    }
+
+    async fn post(
+        &self,
+        request_id: &str,
+        body: &serde_json::Value,
+        timestamp: i64,
+        signature: Option<String>,
+    ) -> (u16, serde_json::Value) {
+        let body = body.to_string();
+        let path = decision_path(request_id);
+        let signature = signature
+            .unwrap_or_else(|| approval_webhook::sign(SECRET, timestamp, &path, body.as_bytes()));
+        let response = self
+            .client
+            .post(format!("{}{path}", self.base_url))
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, signature)
+            .header("content-type", "application/json")
+            .body(body)
+            .send()
+            .await
+            .expect("send");
+        let status = response.status().as_u16();
+        (status, response.json().await.unwrap_or_default())
+    }
+
+    async fn wait(&self, request_id: &str, timeout: u64, secret: &str) -> (u16, serde_json::Value) {
+        let path = format!("/api/v1/approvals/{request_id}/wait");
+        let timestamp = now();
+        let response = self
+            .client
+            .get(format!("{}{path}?timeout={timeout}", self.base_url))
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(
+                SIGNATURE_HEADER,
+                approval_webhook::sign(secret, timestamp, &path, b""),
+            )
+            .send()
+            .await
+            .expect("send");
+        let status = response.status().as_u16();
+        (status, response.json().await.unwrap_or_default())
+    }
+
+    async fn status_of(&self, request_id: &str) -> ApprovalStatus {
+        ApprovalRepo::new(Arc::clone(&self.state.db))
+            .get_by_id(request_id)
+            .await
+            .expect("get")
+            .expect("record")
+            .status
+    }
+}
+
+fn decision_path(request_id: &str) -> String {
+    format!("/api/v1/approvals/{request_id}/decision")
+}
+
+fn now() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+#[tokio::test]
+async fn signed_approval_resolves_waiting_agent() {
+    let h = harness().await;
+    let (id, rx) = h.pending().await;
+
+    let (status, body) = h
+        .post(
+            &id,
+            &json!({ "decision": "approve", "actor": "ci-bot" }),
+            now(),
+            None,
+        )
+        .await;
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(body["status"], "approved");
+
+    let response = rx.await.expect("agent resolved");
+    assert_eq!(response.status, "approved");
+    assert_eq!(h.status_of(&id).await, ApprovalStatus::Approved);
+
+    let (status, body) = h
+        .post(&id, &json!({ "decision": "reject" }), now(), None)
+        .await;
+    assert_eq!(status, 409, "{body}");
+    assert_eq!(body["status"], "approved");
+}
+
+#[tokio::test]
+async fn racing_decisions_resolve_exactly_once() {
+    let h = harness().await;
+    let (id, _rx) = h.pending().await;
+
+    let approve = json!({ "decision": "approve" });
+    let reject = json!({ "decision": "reject", "reason": "too late" });
+    let ((first, _), (second, _)) = tokio::join!(
+        h.post(&id, &approve, now(), None),
+        h.post(&id, &reject, now(), None),
+    );
+
+    let mut statuses = [first, second];
+    statuses.sort_unstable();
+    assert_eq!(statuses, [200, 409]);
+    let expected = if first == 200 {
+        ApprovalStatus::Approved
+    } else {
+        ApprovalStatus::Rejected
+    };
+    assert_eq!(h.status_of(&id).await, expected);
+}
+
+#[tokio::test]
+async fn code_owned_requests_need_a_code_owner_actor() {
+    let h = harness().await;
+    let root = h.state.config.default_workspace_root();
+    std::fs::write(root.join("CODEOWNERS"), "src/parser.rs @parser-team\n").expect("codeowners");
+    let (id, rx) = h.pending().await;
+
+    let (status, body) = h
+        .post(
+            &id,
+            &json!({ "decision": "approve", "actor": "ci-bot" }),
+            now(),
+            None,
+        )
+        .await;
+    assert_eq!(status, 403, "{body}");
+    assert_eq!(h.status_of(&id).await, ApprovalStatus::Pending);
+
+    let (status, body) = h
+        .post(
+            &id,
+            &json!({ "decision": "approve", "actor": "U_CODE_OWNER" }),
+            now(),
+            None,
+        )
+        .await;
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(rx.await.expect("agent resolved").status, "approved");
+}
+
+#[tokio::test]
+async fn signed_rejection_carries_reason() {
+    let h = harness().await;
+    let (id, rx) = h.pending().await;
+
+    let (status, _) = h
+        .post(
+            &id,
+            &json!({ "decision": "reject", "reason": "tests are red" }),
+            now(),
+            None,
+        )
+        .await;
+    assert_eq!(status, 200);
+
+    let response = rx.await.expect("agent resolved");
+    assert_eq!(response.status, "rejected");
+    assert_eq!(response.reason.as_deref(), Some("tests are red"));
+    assert_eq!(h.status_of(&id).await, ApprovalStatus::Rejected);
+}
+
+#[tokio::test]
+async fn unsigned_or_stale_requests_are_refused() {
+    let h = harness().await;
+    let (id, _rx) = h.pending().await;
+    let body = json!({ "decision": "approve" });
+
+    let forged = approval_webhook::sign(
+        "wrong-secret",
+        now(),
+        &decision_path(&id),
+        body.to_string().as_bytes(),
+    );
+    let (status, _) = h.post(&id, &body, now(), Some(forged)).await;
+    assert_eq!(status, 401);
+
+    let (status, _) = h.post(&id, &body, now() - 3600, None).await;
+    assert_eq!(status, 401);
+
+    let (status, _) = h.post(&id, &body, now(), Some("sha256=zz".into())).await;
+    assert_eq!(status, 401);
+
+    assert_eq!(h.status_of(&id).await, ApprovalStatus::Pending);
+}
+
+#[tokio::test]
+async fn signature_for_one_request_is_refused_on_another() {
+    let h = harness().await;
+    let (signed_id, _signed_rx) = h.pending().await;
+    let (target_id, _target_rx) = h.pending().await;
+    let body = json!({ "decision": "approve" });
+
+    let timestamp = now();
+    let replayed = approval_webhook::sign(
+        SECRET,
+        timestamp,
+        &decision_path(&signed_id),
+        body.to_string().as_bytes(),
+    );
+    let (status, _) = h.post(&target_id, &body, timestamp, Some(replayed)).await;
+    assert_eq!(status, 401);
+
+    assert_eq!(h.status_of(&target_id).await, ApprovalStatus::Pending);
+    assert_eq!(h.status_of(&signed_id).await, ApprovalStatus::Pending);
+}
+
+#[tokio::test]
+async fn unknown_request_is_not_found() {
+    let h = harness().await;
+    let (status, body) = h
+        .post(
+            "no-such-request",
+            &json!({ "decision": "approve" }),
+            now(),
+            None,
+        )
+        .await;
+    assert_eq!(status, 404, "{body}");
+
+    let (id, _rx) = h.pending().await;
+    let (status, _) = h
+        .post(&id, &json!({ "decision": "maybe" }), now(), None)
+        .await;
+    assert_eq!(status, 400);
+}
+
+#[tokio::test]
+async fn wait_returns_decision_made_while_polling() {
+    let h = harness().await;
+    let (id, _rx) = h.pending().await;
+
+    // A severed agent starts polling; the operator decides meanwhile.
+    let poll = {
+        let id = id.clone();
+        let client = h.client.clone();
+        let base_url = h.base_url.clone();
+        tokio::spawn(async move {
+            let path = format!("/api/v1/approvals/{id}/wait");
+            let timestamp = now();
+            client
+                .get(format!("{base_url}{path}?timeout=10"))
+                .header(TIMESTAMP_HEADER, timestamp.to_string())
+                .header(
+                    SIGNATURE_HEADER,
+                    approval_webhook::sign(WAIT_SECRET, timestamp, &path, b""),
+                )
+                .send()
+                .await
+                .expect("send")
+                .json::<serde_json::Value>()
+                .await
+                .expect("json")
+        })
+    };
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+    let (status, _) = h
+        .post(&id, &json!({ "decision": "approve" }), now(), None)
+        .await;
+    assert_eq!(status, 200);
+
+    let body = tokio::time::timeout(std::time::Duration::from_secs(5), poll)
+        .await
+        .expect("wait answered before its timeout")
+        .expect("poll task");
+    assert_eq!(body["status"], "approved");
+
+    // Decided requests answer immediately.
+    let (status, body) = h.wait(&id, 10, WAIT_SECRET).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["status"], "approved");
+}
+
+#[tokio::test]
+async fn wait_returns_rejection_reason() {
+    let h = harness().await;
+    let (id, _rx) = h.pending().await;
+
+    let (status, _) = h
+        .post(
+            &id,
+            &json!({ "decision": "reject", "reason": "touches prod config" }),
+            now(),
+            None,
+        )
+        .await;
+    assert_eq!(status, 200);
+
+    let (status, body) = h.wait(&id, 10, WAIT_SECRET).await;
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(body["status"], "rejected");
+    assert_eq!(body["reason"], "touches prod config");
+}
+
+#[tokio::test]
+async fn wait_times_out_pending_and_refuses_bad_requests() {
+    let h = harness().await;
+    let (id, _rx) = h.pending().await;
+
+    let (status, body) = h.wait(&id, 0, WAIT_SECRET).await;
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(body["status"], "pending");
+
+    let (status, _) = h.wait(&id, 0, "wrong-secret").await;
+    assert_eq!(status, 401);
+
+    // The decision secret does not open the read-only endpoint.
+    let (status, _) = h.wait(&id, 0, SECRET).await;
+    assert_eq!(status, 401);
+
+    let (status, _) = h.wait("no-such-request", 0, WAIT_SECRET).await;
+    assert_eq!(status, 404);
+}
+
+#[tokio::test]
+async fn wait_is_not_mounted_without_a_wait_secret() {
+    let h = harness_with_wait_secret("").await;
+    let (id, _rx) = h.pending().await;
+
+    let (status, _) = h.wait(&id, 0, "").await;
+    assert_eq!(status, 404);
+}
+
+#[test]
+fn verify_accepts_only_matching_signatures() {
+    let body = br#"{"decision":"approve"}"#;
+    let path = decision_path("a1");
+    let signature = approval_webhook::sign(SECRET, 1_700_000_000, &path, body);
+    assert!(signature.starts_with("sha256="));
+    assert_eq!(signature.len(), "sha256=".len() + 64);
+
+    let check = |timestamp: &str, signature: &str, path: &str, body: &[u8], now: i64| {
+        approval_webhook::verify(
+            SECRET,
+            Some(timestamp),
+            Some(signature),
+            path,
+            body,
+            now,
+            300,
+        )
+    };
+    assert!(check("1700000000", &signature, &path, body, 1_700_000_100).is_ok());
+    assert!(check("1700000000", &signature, &path, body, 1_700_000_400).is_err());
+    assert!(check("1700000001", &signature, &path, body, 1_700_000_000).is_err());
+    assert!(check("1700000000", &signature, &path, b"{}", 1_700_000_000).is_err());
+    let other = decision_path("a2");
+    assert!(check("1700000000", &signature, &other, body, 1_700_000_000).is_err());
+    assert!(approval_webhook::verify(SECRET, None, Some(&signature), &path, body, 0, 300).is_err());
+
+    let forged = approval_webhook::sign("", 1_700_000_000, &path, body);
+    assert!(approval_webhook::verify(
+        "",
+        Some("1700000000"),
+        Some(&forged),
+        &path,
+        body,
+        1_700_000_000,
+        300
+    )
+    .is_err());
+}
@@ -0,0 +1,115 @@
+//! Integration tests for automatic periodic checkpoints (`[checkpoints]`).
+//!
+//! Tests cover:
+//! - Sessions are checkpointed only once their newest checkpoint is an
+//!   interval old
+//! - Pruning keeps the newest automatic checkpoints and spares milestones
+//!   unless `keep_milestones` is off
+//! - Nothing happens while automatic checkpoints are disabled
+
+use std::sync::Arc;
+
+use agent_intercom::orchestrator::{auto_checkpoints, checkpoint_manager};
+use agent_intercom::persistence::checkpoint_repo::CheckpointRepo;
+use agent_intercom::persistence::session_repo::SessionRepo;
+use chrono::{Duration, Utc};
+
+use super::test_helpers::{create_active_session, test_app_state, test_config};
+
+#[tokio::test]
+async fn due_sessions_are_checkpointed_and_pruned_sparing_milestones() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let mut config = test_config(root);
+    config.checkpoints.interval_minutes = 10;
+    config.checkpoints.keep_last = 2;
+    let state = test_app_state(config).await;
+    let session = create_active_session(&state.db, root).await;
+    let session_repo = SessionRepo::new(Arc::clone(&state.db));
+    let checkpoint_repo = CheckpointRepo::new(Arc::clone(&state.db));
+
+    checkpoint_manager::create_checkpoint(
+        &session.id,
+        Some("before refactor"),
+        &session_repo,
+        &checkpoint_repo,
+    )
+    .await
+    .expect("milestone");
+
+    // The milestone is fresh, so nothing is due yet.
+    let taken = auto_checkpoints::checkpoint_due(&state, Utc::now() + Duration::minutes(5))
+        .await
+        .expect("sweep");
+    assert_eq!(taken, 0);
+
+    for minutes in [11, 22, 33] {
+        let taken =
+            auto_checkpoints::checkpoint_due(&state, Utc::now() + Duration::minutes(minutes))
+                .await
+                .expect("sweep");
+        assert_eq!(taken, 1);
+    }
+
+    let checkpoints = checkpoint_repo
+        .list_for_session(&session.id)
+        .await
+        .expect("list");
+    assert_eq!(checkpoints.len(), 3);
+    assert_eq!(checkpoints.iter().filter(|cp| cp.automatic).count(), 2);
+    assert!(checkpoints
+        .iter()
+        .any(|cp| cp.label.as_deref() == Some("before refactor") && !cp.automatic));
+}
+
+#[tokio::test]
+async fn milestones_are_pruned_when_not_kept() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let mut config = test_config(root);
+    config.checkpoints.interval_minutes = 10;
+    config.checkpoints.keep_last = 1;
+    config.checkpoints.keep_milestones = false;
+    let state = test_app_state(config).await;
+    let session = create_active_session(&state.db, root).await;
+    let checkpoint_repo = CheckpointRepo::new(Arc::clone(&state.db));
+
+    checkpoint_manager::create_checkpoint(
+        &session.id,
+        Some("milestone"),
+        &SessionRepo::new(Arc::clone(&state.db)),
+        &checkpoint_repo,
+    )
+    .await
+    .expect("milestone");
+
+    let taken = auto_checkpoints::checkpoint_due(&state, Utc::now() + Duration::minutes(11))
+        .await
+        .expect("sweep");
+    assert_eq!(taken, 1);
+
+    let checkpoints = checkpoint_repo
+        .list_for_session(&session.id)
+        .await
+        .expect("list");
+    assert_eq!(checkpoints.len(), 1);
+    assert!(checkpoints[0].automatic);
+}
+
+#[tokio::test]
+async fn disabled_checkpoints_take_nothing() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+    let session = create_active_session(&state.db, root).await;
+
+    let taken = auto_checkpoints::checkpoint_due(&state, Utc::now() + Duration::days(1))
+        .await
+        .expect("sweep");
+    assert_eq!(taken, 0);
+    let checkpoints = CheckpointRepo::new(Arc::clone(&state.db))
+        .list_for_session(&session.id)
+        .await
+        .expect("list");
+    assert!(checkpoints.is_empty());
+}
@@ -75,6 +75,27 @@ async fn test_state() -> Arc<AppState> {
         workspace_mappings: Arc::default(),
         acp_event_tx: None,
         acp_driver: None,
+        driver_registry: Arc::default(),
+        event_bus: Arc::default(),
+        session_hooks: Arc::default(),
+        pause_requests: Arc::default(),
+        heartbeats: Arc::default(),
+        instruction_queue: Arc::default(),
+        snoozes: Arc::default(),
+        apply_confirmations: Arc::default(),
+        session_logs: Arc::default(),
+        session_channels: Arc::default(),
+        workspace_discovery: Arc::default(),
+        config_path: None,
+        command_aliases: Arc::default(),
+        proxy: Arc::default(),
+        replay_guard: Arc::default(),
+        interactions: Arc::default(),
+        rearmed: Arc::default(),
+        diff_staging: Arc::default(),
+        subsystems: Arc::default(),
+        simulator: None,
+        log_filter: None,
     })
 }
 
@@ -174,6 +195,27 @@ default_nudge_message = "continue"
         workspace_mappings: Arc::default(),
         acp_event_tx: None,
         acp_driver: None,
+        driver_registry: Arc::default(),
+        event_bus: Arc::default(),
+        session_hooks: Arc::default(),
+        pause_requests: Arc::default(),
+        heartbeats: Arc::default(),
+        instruction_queue: Arc::default(),
+        snoozes: Arc::default(),
+        apply_confirmations: Arc::default(),
+        session_logs: Arc::default(),
+        session_channels: Arc::default(),
+        workspace_discovery: Arc::default(),
+        config_path: None,
+        command_aliases: Arc::default(),
+        proxy: Arc::default(),
+        replay_guard: Arc::default(),
+        interactions: Arc::default(),
+        rearmed: Arc::default(),
+        diff_staging: Arc::default(),
+        subsystems: Arc::default(),
+        simulator: None,
+        log_filter: None,
     });
 
     // No override, no config channel → None.
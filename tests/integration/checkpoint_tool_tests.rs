@@ -0,0 +1,70 @@
+//! Integration tests for the agent-initiated `checkpoint` tool.
+//!
+//! Tests cover:
+//! - A labelled checkpoint is stored for the calling session with the
+//!   workspace file hashes
+//! - Empty and oversized labels are rejected
+
+use std::sync::Arc;
+
+use agent_intercom::persistence::checkpoint_repo::CheckpointRepo;
+use serde_json::json;
+
+use super::test_helpers::{connect_mcp_client, test_app_state, test_config};
+
+#[tokio::test]
+async fn checkpoint_tool_stores_labelled_checkpoint() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    std::fs::write(temp.path().join("lib.rs"), "fn main() {}").expect("write file");
+    let state = test_app_state(test_config(root)).await;
+    let (mut client, session_id) = connect_mcp_client(&state).await;
+
+    let created = client
+        .call_tool(
+            2,
+            "checkpoint",
+            json!({ "label": "  tests passing before refactor " }),
+        )
+        .await;
+    assert_eq!(created["status"], "created");
+    assert_eq!(created["label"], "tests passing before refactor");
+    assert_eq!(created["files_hashed"], 1);
+
+    let checkpoints = CheckpointRepo::new(Arc::clone(&state.db))
+        .list_for_session(&session_id)
+        .await
+        .expect("list checkpoints");
+    assert_eq!(checkpoints.len(), 1);
+    assert_eq!(checkpoints[0].id, created["checkpoint_id"]);
+    assert_eq!(
+        checkpoints[0].label.as_deref(),
+        Some("tests passing before refactor")
+    );
+    assert!(checkpoints[0].file_hashes.contains_key("lib.rs"));
+}
+
+#[tokio::test]
+async fn checkpoint_tool_rejects_bad_labels() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+    let (mut client, session_id) = connect_mcp_client(&state).await;
+
+    for (id, label) in [(2, " ".to_owned()), (3, "x".repeat(201))] {
+        let response = client
+            .request(
+                id,
+                "tools/call",
+                json!({ "name": "checkpoint", "arguments": { "label": label } }),
+            )
+            .await;
+        assert!(response["error"].is_object(), "{response}");
+    }
+
+    let checkpoints = CheckpointRepo::new(Arc::clone(&state.db))
+        .list_for_session(&session_id)
+        .await
+        .expect("list checkpoints");
+    assert!(checkpoints.is_empty());
+}
@@ -0,0 +1,121 @@
+//! Integration tests for per-client tool gating (`[clients]`).
+//!
+//! Covers:
+//! - The client name and version from the `initialize` handshake are
+//!   recorded on the session and shown by `/intercom sessions`
+//! - A denied tool is left out of `tools/list` and refused by `tools/call`
+//! - `client = "unknown"` rules spare clients listed in `known`
+
+use std::sync::Arc;
+
+use agent_intercom::config::{ClientToolRule, ClientsConfig};
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::slack::commands::dispatch_command;
+use agent_intercom::state::AppState;
+use serde_json::{json, Value};
+
+use super::test_helpers::{connect_mcp_client, test_app_state, test_config, McpTestClient};
+
+async fn gated_state(root: &str, clients: ClientsConfig) -> Arc<AppState> {
+    let mut config = test_config(root);
+    config.clients = clients;
+    test_app_state(config).await
+}
+
+fn deny_for(client: &str, tools: &[&str]) -> ClientToolRule {
+    ClientToolRule {
+        client: client.into(),
+        deny: tools.iter().map(|tool| (*tool).to_owned()).collect(),
+    }
+}
+
+async fn tool_names(client: &mut McpTestClient) -> Vec<String> {
+    let listing = client.request(2, "tools/list", json!({})).await;
+    listing["result"]["tools"]
+        .as_array()
+        .expect("tools array")
+        .iter()
+        .filter_map(|tool| tool["name"].as_str().map(str::to_owned))
+        .collect()
+}
+
+async fn call(client: &mut McpTestClient, name: &str) -> Value {
+    client
+        .request(3, "tools/call", json!({ "name": name, "arguments": {} }))
+        .await
+}
+
+#[tokio::test]
+async fn handshake_client_is_recorded_and_listed() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let root = root.path().to_str().expect("utf8 path");
+    let state = gated_state(root, ClientsConfig::default()).await;
+    let (_client, session_id) = connect_mcp_client(&state).await;
+
+    let session = SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(&session_id)
+        .await
+        .expect("get session")
+        .expect("session exists");
+    assert_eq!(session.client_name.as_deref(), Some("test-client"));
+    assert_eq!(session.client_version.as_deref(), Some("0.0.0"));
+
+    let listing = dispatch_command("sessions", &[], "U_TEST", "C_TEST", &state)
+        .await
+        .expect("sessions listing");
+    assert!(listing.contains("MCP via test-client 0.0.0"), "{listing}");
+}
+
+#[tokio::test]
+async fn denied_tool_is_hidden_and_refused() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let root = root.path().to_str().expect("utf8 path");
+    let clients = ClientsConfig {
+        known: Vec::new(),
+        rules: vec![deny_for("test-*", &["switch_freq"])],
+    };
+    let state = gated_state(root, clients).await;
+    let (mut client, _session_id) = connect_mcp_client(&state).await;
+
+    let names = tool_names(&mut client).await;
+    assert!(!names.iter().any(|name| name == "switch_freq"), "{names:?}");
+    assert!(names.iter().any(|name| name == "ping"), "{names:?}");
+
+    let refused = call(&mut client, "switch_freq").await;
+    assert_eq!(refused["result"]["isError"], true, "{refused}");
+    let text = refused["result"]["content"][0]["text"]
+        .as_str()
+        .expect("refusal text");
+    assert!(
+        text.contains("not available to MCP client 'test-client'"),
+        "{text}"
+    );
+}
+
+#[tokio::test]
+async fn unknown_rules_spare_known_clients() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let root = root.path().to_str().expect("utf8 path");
+
+    let unknown = ClientsConfig {
+        known: vec!["claude-code".into()],
+        rules: vec![deny_for("unknown", &["switch_*"])],
+    };
+    let state = gated_state(root, unknown.clone()).await;
+    let (mut client, _session_id) = connect_mcp_client(&state).await;
+    assert!(!tool_names(&mut client)
+        .await
+        .iter()
+        .any(|name| name == "switch_freq"));
+
+    let known = ClientsConfig {
+        known: vec!["test-client".into()],
+        ..unknown
+    };
+    let state = gated_state(root, known).await;
+    let (mut client, _session_id) = connect_mcp_client(&state).await;
+    assert!(tool_names(&mut client)
+        .await
+        .iter()
+        .any(|name| name == "switch_freq"));
+}
@@ -0,0 +1,93 @@
+//! Integration tests for `GET /api/v1/config` and the `intercom://config`
+//! resource.
+//!
+//! Tests cover:
+//! - The endpoint answers the redacted configuration and sorted aliases
+//! - The resource serves the same document, with hot-reloaded workspaces
+//!   and aliases added at runtime
+
+use std::sync::PoisonError;
+
+use agent_intercom::config::WorkspaceMapping;
+use agent_intercom::mcp::config_api;
+use agent_intercom::mcp::resources::server_config;
+use agent_intercom::orchestrator::command_aliases;
+use rmcp::model::{ReadResourceRequestParam, ResourceContents};
+use serde_json::Value;
+
+use super::test_helpers::{test_app_state, test_config};
+
+fn config_with_aliases(root: &str) -> agent_intercom::config::GlobalConfig {
+    let mut config = test_config(root);
+    config
+        .commands
+        .insert("test".into(), "cargo test --workspace".into());
+    config.commands.insert("build".into(), "cargo build".into());
+    config
+}
+
+#[tokio::test]
+async fn config_endpoint_lists_redacted_config_and_aliases() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let mut config = config_with_aliases(temp.path().to_str().expect("utf8"));
+    config.slack.bot_token = "xoxb-secret".into();
+    let state = test_app_state(config).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let base_url = format!("http://{}", listener.local_addr().expect("addr"));
+    let router = config_api::router(state);
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+
+    let resp = reqwest::get(format!("{base_url}{}", config_api::CONFIG_PATH))
+        .await
+        .expect("get");
+    assert_eq!(resp.status().as_u16(), 200);
+    let body = resp.text().await.expect("body");
+    assert!(!body.contains("xoxb-secret"), "{body}");
+
+    let body: Value = serde_json::from_str(&body).expect("json");
+    assert_eq!(body["config"]["slack"]["channel_id"], "C_TEST");
+    assert_eq!(body["command_aliases"][0]["alias"], "build");
+    assert_eq!(body["command_aliases"][0]["command"], "cargo build");
+    assert_eq!(body["command_aliases"][1]["alias"], "test");
+}
+
+#[tokio::test]
+async fn config_resource_reflects_runtime_changes() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(config_with_aliases(temp.path().to_str().expect("utf8"))).await;
+    state
+        .workspace_mappings
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push(WorkspaceMapping {
+            workspace_id: "repo".into(),
+            channel_id: "C001".into(),
+            label: None,
+            path: None,
+            proxy: Vec::new(),
+            approval_channels: Vec::new(),
+            working_hours: None,
+            bootstrap: None,
+        });
+    command_aliases::add(&state, "lint", "cargo clippy").expect("add alias");
+
+    assert!(server_config::is_config_uri(server_config::RESOURCE_URI));
+    let request = ReadResourceRequestParam {
+        uri: server_config::RESOURCE_URI.into(),
+    };
+    let result = server_config::read_resource(&request, &state);
+    let ResourceContents::TextResourceContents { ref text, .. } = result.contents[0] else {
+        panic!("expected text contents");
+    };
+    let body: Value = serde_json::from_str(text).expect("json");
+    assert_eq!(body, server_config::document(&state));
+    assert_eq!(body["config"]["workspace"][0]["workspace_id"], "repo");
+    assert_eq!(body["command_aliases"].as_array().map(Vec::len), Some(3));
+    assert_eq!(body["command_aliases"][1]["alias"], "lint");
+    assert_eq!(body["config"]["commands"]["lint"], "cargo clippy");
+}
@@ -0,0 +1,314 @@
+//! Integration tests for QR-code device pairing and the approval dashboard.
+//!
+//! The pairing and dashboard router is served on an ephemeral port and
+//! driven with `reqwest`, starting from the pairing URL `pair` replies with.
+//!
+//! Tests cover:
+//! - `pair` offers a QR code and a single-use pairing URL
+//! - Redeeming the URL sets the device cookie; a second redemption fails
+//! - The dashboard lists only the operator's own pending approvals
+//! - Deciding from the dashboard resolves the waiting agent
+//! - Unpaired, revoked and foreign requests are refused
+//! - Code-owner routing decides who may approve from the dashboard
+
+use std::sync::Arc;
+
+use agent_intercom::mcp::device_pairing;
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::models::session::{Session, SessionMode};
+use agent_intercom::persistence::approval_repo::ApprovalRepo;
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::slack::commands::dispatch_command;
+use agent_intercom::state::{AppState, ApprovalResponse};
+use tokio::sync::oneshot;
+
+use super::test_helpers::{create_active_session, test_app_state, test_config};
+
+const OWNER: &str = "U_TEST_OWNER";
+const CHANNEL: &str = "C_TEST";
+
+struct Harness {
+    state: Arc<AppState>,
+    base_url: String,
+    client: reqwest::Client,
+    _temp: tempfile::TempDir,
+}
+
+async fn harness() -> Harness {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path").to_owned();
+    let mut config = test_config(&root);
+    config.approval_links.enabled = true;
+    config.approval_links.base_url = Some("https://intercom.acme.test".into());
+    config.approval_links.secret = "pairing-test-secret".into();
+    config.authorized_user_ids = vec![OWNER.into(), "U_OTHER".into()];
+    // Inert until a test writes a CODEOWNERS file.
+    config.codeowners.require_owner_approval = true;
+    config.codeowners.slack_users = [("@parser-team".to_owned(), "U_OTHER".to_owned())]
+        .into_iter()
+        .collect();
+    let state = test_app_state(config).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let base_url = format!("http://{}", listener.local_addr().expect("addr"));
+    let router = device_pairing::router(Arc::clone(&state));
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+
+    Harness {
+        state,
+        base_url,
+        client: reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("client"),
+        _temp: temp,
+    }
+}
+
+impl Harness {
+    /// Run `pair` as `user_id` and return the pairing path and the reply.
+    async fn offer(&self, user_id: &str) -> (String, String) {
+        let reply = dispatch_command("pair", &[], user_id, CHANNEL, &self.state)
+            .await
+            .expect("pair");
+        let start = reply.find("/pair/").expect("pairing url");
+        let path: String = reply[start..]
+            .chars()
+            .take_while(|c| *c != '|' && *c != '>')
+            .collect();
+        (path, reply)
+    }
+
+    /// Redeem the pairing `path` and return the status and device cookie.
+    async fn redeem(&self, path: &str) -> (u16, Option<String>) {
+        let response = self
+            .client
+            .post(format!("{}{path}", self.base_url))
+            .header("user-agent", "Mobile Safari")
+            .send()
+            .await
+            .expect("send");
+        let cookie = response
+            .headers()
+            .get("set-cookie")
+            .map(|value| value.to_str().expect("ascii").to_owned());
+        (response.status().as_u16(), cookie)
+    }
+
+    /// Pair a browser for `user_id` and return its `Cookie` header value.
+    async fn paired(&self, user_id: &str) -> String {
+        let (path, _) = self.offer(user_id).await;
+        let (status, cookie) = self.redeem(&path).await;
+        assert_eq!(status, 303);
+        let cookie = cookie.expect("set-cookie");
+        cookie.split(';').next().expect("cookie pair").to_owned()
+    }
+
+    async fn get(&self, path: &str, cookie: &str) -> (u16, String) {
+        let response = self
+            .client
+            .get(format!("{}{path}", self.base_url))
+            .header("cookie", cookie)
+            .send()
+            .await
+            .expect("send");
+        let status = response.status().as_u16();
+        (status, response.text().await.unwrap_or_default())
+    }
+
+    async fn post(&self, path: &str, cookie: &str, fields: &str) -> (u16, String) {
+        let response = self
+            .client
+            .post(format!("{}{path}", self.base_url))
+            .header("cookie", cookie)
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(fields.to_owned())
+            .send()
+            .await
+            .expect("send");
+        let status = response.status().as_u16();
+        (status, response.text().await.unwrap_or_default())
+    }
+
+    /// Persist a pending approval in a session owned by `owner` and park a
+    /// waiter on it.
+    async fn pending(
+        &self,
+        owner: &str,
+        title: &str,
+    ) -> (String, oneshot::Receiver<ApprovalResponse>) {
+        let root = self.state.config.default_workspace_root().to_string_lossy();
+        let session_id = if owner == OWNER {
+            create_active_session(&self.state.db, &root).await.id
+        } else {
+            let session = Session::new(owner.into(), root.into_owned(), None, SessionMode::Remote);
+            SessionRepo::new(Arc::clone(&self.state.db))
+                .create(&session)
+                .await
+                .expect("create session")
+                .id
+        };
+        let approval = ApprovalRequest::new(
+            session_id,
+            title.into(),
+            None,
+            "+fn parse() {}".into(),
+            "src/parser.rs".into(),
+            RiskLevel::Low,
+            "new_file".into(),
+        );
+        ApprovalRepo::new(Arc::clone(&self.state.db))
+            .create(&approval)
+            .await
+            .expect("create approval");
+        let (tx, rx) = oneshot::channel();
+        self.state
+            .pending_approvals
+            .lock()
+            .await
+            .insert(approval.id.clone(), tx);
+        (approval.id, rx)
+    }
+}
+
+#[tokio::test]
+async fn pair_offers_qr_code_and_single_use_url() {
+    let h = harness().await;
+    let (path, reply) = h.offer(OWNER).await;
+    assert!(reply.contains("```"), "{reply}");
+    assert!(reply.contains('\u{2588}'), "QR code blocks: {reply}");
+    assert!(
+        reply.contains(&format!("https://intercom.acme.test{path}")),
+        "{reply}"
+    );
+
+    let (status, page) = h.get(&path, "").await;
+    assert_eq!(status, 200, "{page}");
+    assert!(page.contains("Pair this browser"), "{page}");
+
+    let (status, cookie) = h.redeem(&path).await;
+    assert_eq!(status, 303);
+    let cookie = cookie.expect("set-cookie");
+    assert!(cookie.starts_with("intercom_device="), "{cookie}");
+    assert!(cookie.contains("HttpOnly"), "{cookie}");
+    assert!(cookie.contains("Secure"), "{cookie}");
+
+    assert_eq!(h.redeem(&path).await.0, 401);
+    assert_eq!(h.get(&path, "").await.0, 401);
+
+    let devices = dispatch_command("pair", &["list"], OWNER, CHANNEL, &h.state)
+        .await
+        .expect("list");
+    assert!(devices.contains("Mobile Safari"), "{devices}");
+}
+
+#[tokio::test]
+async fn dashboard_lists_only_own_pending_approvals() {
+    let h = harness().await;
+    let cookie = h.paired(OWNER).await;
+    let (status, page) = h.get("/dashboard", &cookie).await;
+    assert_eq!(status, 200, "{page}");
+    assert!(page.contains("Nothing is waiting"), "{page}");
+
+    let (mine, _rx) = h.pending(OWNER, "Add <parser>").await;
+    let (theirs, _their_rx) = h.pending("U_OTHER", "Drop tables").await;
+
+    let (status, page) = h.get("/dashboard", &cookie).await;
+    assert_eq!(status, 200, "{page}");
+    assert!(page.contains("Add &lt;parser&gt;"), "{page}");
+    assert!(
+        page.contains(&format!("/dashboard/approvals/{mine}")),
+        "{page}"
+    );
+    assert!(!page.contains("Drop tables"), "{page}");
+
+    let path = format!("/dashboard/approvals/{theirs}");
+    assert_eq!(h.get(&path, &cookie).await.0, 404);
+    assert_eq!(h.post(&path, &cookie, "decision=approve").await.0, 404);
+}
+
+#[tokio::test]
+async fn dashboard_decision_resolves_waiting_agent() {
+    let h = harness().await;
+    let cookie = h.paired(OWNER).await;
+    let (id, rx) = h.pending(OWNER, "Add parser").await;
+    let path = format!("/dashboard/approvals/{id}");
+
+    let (status, page) = h.get(&path, &cookie).await;
+    assert_eq!(status, 200, "{page}");
+    assert!(page.contains("+fn parse() {}"), "{page}");
+
+    let (status, page) = h
+        .post(&path, &cookie, "decision=reject&reason=not+now")
+        .await;
+    assert_eq!(status, 200, "{page}");
+    let response = rx.await.expect("agent resolved");
+    assert_eq!(response.status, "rejected");
+    assert_eq!(response.reason.as_deref(), Some("not now"));
+
+    let status = ApprovalRepo::new(Arc::clone(&h.state.db))
+        .get_by_id(&id)
+        .await
+        .expect("get")
+        .expect("record")
+        .status;
+    assert_eq!(status, ApprovalStatus::Rejected);
+    assert_eq!(h.get(&path, &cookie).await.0, 409);
+}
+
+#[tokio::test]
+async fn dashboard_decisions_follow_code_owner_routing() {
+    let h = harness().await;
+    let root = h.state.config.default_workspace_root();
+    std::fs::write(root.join("CODEOWNERS"), "src/parser.rs @parser-team\n").expect("codeowners");
+    let (id, rx) = h.pending(OWNER, "Add parser").await;
+    let path = format!("/dashboard/approvals/{id}");
+
+    let owner = h.paired(OWNER).await;
+    assert_eq!(h.get(&path, &owner).await.0, 200);
+    let (status, page) = h.post(&path, &owner, "decision=approve").await;
+    assert_eq!(status, 403, "{page}");
+    assert!(page.contains("code owner"), "{page}");
+
+    let code_owner = h.paired("U_OTHER").await;
+    assert_eq!(h.get(&path, &code_owner).await.0, 200);
+    let (status, page) = h.post(&path, &code_owner, "decision=approve").await;
+    assert_eq!(status, 200, "{page}");
+    assert_eq!(rx.await.expect("agent resolved").status, "approved");
+}
+
+#[tokio::test]
+async fn unpaired_and_revoked_browsers_are_refused() {
+    let h = harness().await;
+    assert_eq!(h.get("/dashboard", "").await.0, 401);
+    assert_eq!(h.get("/dashboard", "intercom_device=forged").await.0, 401);
+
+    let cookie = h.paired(OWNER).await;
+    assert_eq!(h.get("/dashboard", &cookie).await.0, 200);
+
+    let reply = dispatch_command("pair", &["revoke", "all"], OWNER, CHANNEL, &h.state)
+        .await
+        .expect("revoke");
+    assert!(reply.contains("Signed out 1"), "{reply}");
+    assert_eq!(h.get("/dashboard", &cookie).await.0, 401);
+
+    let err = dispatch_command("pair", &["revoke", "all"], OWNER, CHANNEL, &h.state)
+        .await
+        .expect_err("nothing left to revoke");
+    assert!(err.to_string().contains("no paired browser"), "{err}");
+}
+
+#[tokio::test]
+async fn pair_requires_approval_links() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path").to_owned();
+    let state = test_app_state(test_config(&root)).await;
+    let err = dispatch_command("pair", &[], OWNER, CHANNEL, &state)
+        .await
+        .expect_err("approval links disabled");
+    assert!(err.to_string().contains("[approval_links]"), "{err}");
+}
@@ -0,0 +1,131 @@
+//! Integration tests for chunked diff staging.
+//!
+//! The staging router is served on an ephemeral port and driven with
+//! `reqwest`.
+//!
+//! Tests cover:
+//! - Chunks appended in order are returned whole by `take`
+//! - A stale offset is refused with the current length
+//! - Uploads past `[limits] max_diff_bytes` are refused
+//! - Unknown uploads are reported
+
+use std::sync::Arc;
+
+use agent_intercom::mcp::diff_staging;
+use agent_intercom::state::AppState;
+use serde_json::Value;
+
+use super::test_helpers::{test_app_state, test_config};
+
+struct Harness {
+    state: Arc<AppState>,
+    base_url: String,
+    client: reqwest::Client,
+    _temp: tempfile::TempDir,
+}
+
+async fn harness(max_diff_bytes: usize) -> Harness {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path").to_owned();
+    let mut config = test_config(&root);
+    config.limits.max_diff_bytes = max_diff_bytes;
+    let state = test_app_state(config).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let base_url = format!("http://{}", listener.local_addr().expect("addr"));
+    let router = diff_staging::router(Arc::clone(&state));
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+
+    Harness {
+        state,
+        base_url,
+        client: reqwest::Client::new(),
+        _temp: temp,
+    }
+}
+
+impl Harness {
+    async fn open(&self) -> String {
+        let resp = self
+            .client
+            .post(format!("{}/api/v1/staging", self.base_url))
+            .send()
+            .await
+            .expect("open");
+        assert_eq!(resp.status(), 201);
+        let body: Value = resp.json().await.expect("json");
+        body["staging_id"].as_str().expect("staging_id").to_owned()
+    }
+
+    async fn append(&self, id: &str, offset: usize, chunk: &'static str) -> (u16, Value) {
+        let resp = self
+            .client
+            .post(format!(
+                "{}/api/v1/staging/{id}?offset={offset}",
+                self.base_url
+            ))
+            .body(chunk)
+            .send()
+            .await
+            .expect("append");
+        let status = resp.status().as_u16();
+        (status, resp.json().await.expect("json"))
+    }
+}
+
+#[tokio::test]
+async fn chunks_are_reassembled_in_order() {
+    let h = harness(1024).await;
+    let id = h.open().await;
+
+    let (status, body) = h.append(&id, 0, "--- a/lib.rs\n").await;
+    assert_eq!(status, 200);
+    assert_eq!(body["bytes"], 13);
+    let (status, body) = h.append(&id, 13, "+++ b/lib.rs\n").await;
+    assert_eq!(status, 200);
+    assert_eq!(body["bytes"], 26);
+
+    let staged = h.state.diff_staging.take(&id).expect("staged diff");
+    assert_eq!(staged, b"--- a/lib.rs\n+++ b/lib.rs\n");
+    assert!(h.state.diff_staging.take(&id).is_none(), "take consumes");
+}
+
+#[tokio::test]
+async fn stale_offset_is_refused_with_current_length() {
+    let h = harness(1024).await;
+    let id = h.open().await;
+    h.append(&id, 0, "first chunk").await;
+
+    let (status, body) = h.append(&id, 0, "first chunk").await;
+    assert_eq!(status, 409);
+    assert_eq!(body["bytes"], 11);
+    assert_eq!(
+        h.state.diff_staging.take(&id).expect("staged diff"),
+        b"first chunk"
+    );
+}
+
+#[tokio::test]
+async fn upload_past_limit_is_refused() {
+    let h = harness(8).await;
+    let id = h.open().await;
+
+    let (status, _) = h.append(&id, 0, "12345").await;
+    assert_eq!(status, 200);
+    let (status, body) = h.append(&id, 5, "6789").await;
+    assert_eq!(status, 413);
+    assert!(body["error"]
+        .as_str()
+        .is_some_and(|e| e.contains("max_diff_bytes")));
+}
+
+#[tokio::test]
+async fn unknown_upload_is_not_found() {
+    let h = harness(1024).await;
+    let (status, _) = h.append("no-such-upload", 0, "x").await;
+    assert_eq!(status, 404);
+}
@@ -11,6 +11,8 @@
 //! | S037 | Direct-connection server drop marks active session as Terminated |
 //! | S038 | Spawned-agent server drop leaves the session unchanged (no-op) |
 //! | S039 | Fresh server drop with no session ID set is a safe no-op |
+//! | S040 | A reconnect within the grace window resumes the dropped session |
+//! | S041 | A dropped session nobody reclaims is terminated after the grace window |
 //!
 //! # Note on `set_session_id_for_testing`
 //!
@@ -24,13 +26,13 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use agent_intercom::mcp::handler::IntercomServer;
-use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::models::session::{ConnectivityStatus, Session, SessionMode, SessionStatus};
 use agent_intercom::persistence::db;
 use agent_intercom::persistence::session_repo::SessionRepo;
 use agent_intercom::state::AppState;
 use tokio::sync::Mutex;
 
-use super::test_helpers::test_config;
+use super::test_helpers::{connect_mcp_client, test_app_state, test_config};
 
 // ── S037: drop marks active direct-connection session as Terminated ──────────
 
@@ -40,7 +42,8 @@ use super::test_helpers::test_config;
 async fn drop_marks_direct_session_terminated() {
     let temp = tempfile::tempdir().expect("tempdir");
     let root = temp.path().to_str().expect("utf8");
-    let config = test_config(root);
+    let mut config = test_config(root);
+    config.timeouts.reconnect_grace_seconds = 0;
     let database = Arc::new(db::connect_memory().await.expect("db connect"));
     let state = Arc::new(AppState {
         config: Arc::new(config),
@@ -63,6 +66,27 @@ async fn drop_marks_direct_session_terminated() {
         workspace_mappings: Arc::default(),
         acp_event_tx: None,
         acp_driver: None,
+        driver_registry: Arc::default(),
+        event_bus: Arc::default(),
+        session_hooks: Arc::default(),
+        pause_requests: Arc::default(),
+        heartbeats: Arc::default(),
+        instruction_queue: Arc::default(),
+        snoozes: Arc::default(),
+        apply_confirmations: Arc::default(),
+        session_logs: Arc::default(),
+        session_channels: Arc::default(),
+        workspace_discovery: Arc::default(),
+        config_path: None,
+        command_aliases: Arc::default(),
+        proxy: Arc::default(),
+        replay_guard: Arc::default(),
+        interactions: Arc::default(),
+        rearmed: Arc::default(),
+        diff_staging: Arc::default(),
+        subsystems: Arc::default(),
+        simulator: None,
+        log_filter: None,
     });
 
     // Create and activate a local session.
@@ -136,6 +160,27 @@ async fn drop_spawned_server_does_not_terminate_session() {
         workspace_mappings: Arc::default(),
         acp_event_tx: None,
         acp_driver: None,
+        driver_registry: Arc::default(),
+        event_bus: Arc::default(),
+        session_hooks: Arc::default(),
+        pause_requests: Arc::default(),
+        heartbeats: Arc::default(),
+        instruction_queue: Arc::default(),
+        snoozes: Arc::default(),
+        apply_confirmations: Arc::default(),
+        session_logs: Arc::default(),
+        session_channels: Arc::default(),
+        workspace_discovery: Arc::default(),
+        config_path: None,
+        command_aliases: Arc::default(),
+        proxy: Arc::default(),
+        replay_guard: Arc::default(),
+        interactions: Arc::default(),
+        rearmed: Arc::default(),
+        diff_staging: Arc::default(),
+        subsystems: Arc::default(),
+        simulator: None,
+        log_filter: None,
     });
 
     // Create and activate a remote (spawned) session.
@@ -205,6 +250,27 @@ async fn drop_fresh_server_with_no_session_is_noop() {
         workspace_mappings: Arc::default(),
         acp_event_tx: None,
         acp_driver: None,
+        driver_registry: Arc::default(),
+        event_bus: Arc::default(),
+        session_hooks: Arc::default(),
+        pause_requests: Arc::default(),
+        heartbeats: Arc::default(),
+        instruction_queue: Arc::default(),
+        snoozes: Arc::default(),
+        apply_confirmations: Arc::default(),
+        session_logs: Arc::default(),
+        session_channels: Arc::default(),
+        workspace_discovery: Arc::default(),
+        config_path: None,
+        command_aliases: Arc::default(),
+        proxy: Arc::default(),
+        replay_guard: Arc::default(),
+        interactions: Arc::default(),
+        rearmed: Arc::default(),
+        diff_staging: Arc::default(),
+        subsystems: Arc::default(),
+        simulator: None,
+        log_filter: None,
     });
 
     // Drop an uninitialized server — no session ID set.
@@ -221,3 +287,59 @@ async fn drop_fresh_server_with_no_session_is_noop() {
         "no sessions should exist after a no-op drop"
     );
 }
+
+// ── S040 / S041: reconnect grace window ──────────────────────────────────────
+
+/// Wait until the session `id` is marked offline after its client dropped.
+async fn wait_offline(repo: &SessionRepo, id: &str) {
+    for _ in 0..100 {
+        let session = repo.get_by_id(id).await.expect("get").expect("exists");
+        if session.connectivity_status == ConnectivityStatus::Offline {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    panic!("session {id} was not marked offline after disconnect");
+}
+
+/// S040 — A client reconnecting within `reconnect_grace_seconds` (an IDE
+/// window reload) takes the dropped session back instead of creating one.
+#[tokio::test]
+async fn reconnect_within_grace_resumes_session() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+
+    let (client, first) = connect_mcp_client(&state).await;
+    drop(client);
+    wait_offline(&repo, &first).await;
+
+    let (_client, second) = connect_mcp_client(&state).await;
+    assert_eq!(second, first, "reload must resume the dropped session");
+    let active = repo.list_active().await.expect("list active");
+    assert_eq!(active.len(), 1, "no second session may be created");
+    assert_eq!(active[0].connectivity_status, ConnectivityStatus::Online);
+}
+
+/// S041 — A dropped session that no client reclaims within the grace
+/// window is terminated once it expires.
+#[tokio::test]
+async fn unreclaimed_session_terminates_after_grace() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let mut config = test_config(root);
+    config.timeouts.reconnect_grace_seconds = 1;
+    let state = test_app_state(config).await;
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+
+    let (client, id) = connect_mcp_client(&state).await;
+    drop(client);
+    wait_offline(&repo, &id).await;
+    let session = repo.get_by_id(&id).await.expect("get").expect("exists");
+    assert_eq!(session.status, SessionStatus::Active, "kept during grace");
+
+    tokio::time::sleep(std::time::Duration::from_millis(1_500)).await;
+    let session = repo.get_by_id(&id).await.expect("get").expect("exists");
+    assert_eq!(session.status, SessionStatus::Terminated);
+}
@@ -0,0 +1,228 @@
+//! Integration tests for the gRPC control plane (feature `grpc`, ADR-0019).
+//!
+//! Validates:
+//! - Calls without the bearer token are refused as unauthenticated
+//! - `ListSessions` returns active sessions through the IPC `list` handler
+//! - `Approve` resolves a pending approval; handler errors map to
+//!   `FAILED_PRECONDITION`
+//! - `StreamEvents` follows the event bus, filtered by kind and session
+//! - The server refuses to start without a token
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use agent_intercom::driver::AgentEvent;
+use agent_intercom::ipc::grpc::proto::intercom_control_client::IntercomControlClient;
+use agent_intercom::ipc::grpc::proto::{self, SessionStatus};
+use agent_intercom::ipc::grpc::{bind_grpc, serve_with_listener};
+use agent_intercom::models::approval::{ApprovalRequest, RiskLevel};
+use agent_intercom::models::session::ProtocolMode;
+use agent_intercom::persistence::approval_repo::ApprovalRepo;
+use agent_intercom::state::{AppState, ApprovalResponse};
+use agent_intercom::AppError;
+use tokio::sync::oneshot;
+use tokio_util::sync::CancellationToken;
+use tonic::transport::Channel;
+use tonic::{Code, Request};
+
+use super::test_helpers::{create_active_session, test_app_state, test_config};
+
+const TOKEN: &str = "grpc-test-token";
+
+/// Start the control plane for `state` and connect a client to it.
+async fn serve(state: &Arc<AppState>, ct: &CancellationToken) -> IntercomControlClient<Channel> {
+    let listener = bind_grpc(state).await.expect("bind grpc");
+    let addr = listener.local_addr().expect("local addr");
+    tokio::spawn(serve_with_listener(listener, Arc::clone(state), ct.clone()));
+    IntercomControlClient::connect(format!("http://{addr}"))
+        .await
+        .expect("connect grpc client")
+}
+
+async fn grpc_state(root: &str, token: &str) -> Arc<AppState> {
+    let mut config = test_config(root);
+    config.grpc.port = 0;
+    config.grpc.token = token.to_owned();
+    test_app_state(config).await
+}
+
+fn authorized<T>(message: T) -> Request<T> {
+    let mut request = Request::new(message);
+    request.metadata_mut().insert(
+        "authorization",
+        format!("Bearer {TOKEN}").parse().expect("metadata value"),
+    );
+    request
+}
+
+#[tokio::test]
+async fn grpc_refuses_calls_without_the_token() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let state = grpc_state(tmp.path().to_str().expect("utf8"), TOKEN).await;
+    let ct = CancellationToken::new();
+    let mut client = serve(&state, &ct).await;
+
+    let missing = client
+        .list_sessions(proto::ListSessionsRequest::default())
+        .await
+        .expect_err("missing token refused");
+    assert_eq!(missing.code(), Code::Unauthenticated);
+
+    let mut wrong = Request::new(proto::ListSessionsRequest::default());
+    wrong.metadata_mut().insert(
+        "authorization",
+        "Bearer not-the-token".parse().expect("metadata"),
+    );
+    let wrong = client.list_sessions(wrong).await.expect_err("bad token");
+    assert_eq!(wrong.code(), Code::Unauthenticated);
+    ct.cancel();
+}
+
+#[tokio::test]
+async fn grpc_list_sessions_returns_active_sessions() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let state = grpc_state(root, TOKEN).await;
+    let session = create_active_session(&state.db, root).await;
+    let ct = CancellationToken::new();
+    let mut client = serve(&state, &ct).await;
+
+    let response = client
+        .list_sessions(authorized(proto::ListSessionsRequest::default()))
+        .await
+        .expect("list sessions")
+        .into_inner();
+    ct.cancel();
+
+    assert_eq!(response.sessions.len(), 1);
+    let listed = &response.sessions[0];
+    assert_eq!(listed.session_id, session.id);
+    assert_eq!(listed.status(), SessionStatus::Active);
+    assert_eq!(listed.workspace_root, root);
+}
+
+#[tokio::test]
+async fn grpc_approve_resolves_pending_approval() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let state = grpc_state(root, TOKEN).await;
+    let session = create_active_session(&state.db, root).await;
+    let approval = ApprovalRepo::new(Arc::clone(&state.db))
+        .create(&ApprovalRequest::new(
+            session.id.clone(),
+            "grpc proposal".into(),
+            None,
+            "diff".into(),
+            "src/lib.rs".into(),
+            RiskLevel::Low,
+            "abc123".into(),
+        ))
+        .await
+        .expect("create approval");
+    let (tx, rx) = oneshot::channel::<ApprovalResponse>();
+    state
+        .pending_approvals
+        .lock()
+        .await
+        .insert(approval.id.clone(), tx);
+    let ct = CancellationToken::new();
+    let mut client = serve(&state, &ct).await;
+
+    let outcome = client
+        .approve(authorized(proto::ApproveRequest {
+            request_id: approval.id.clone(),
+        }))
+        .await
+        .expect("approve")
+        .into_inner();
+    assert_eq!(outcome.request_id, approval.id);
+    assert_eq!(outcome.status, "approved");
+    let response = tokio::time::timeout(Duration::from_secs(2), rx)
+        .await
+        .expect("resolved in time")
+        .expect("oneshot fired");
+    assert_eq!(response.status, "approved");
+
+    let unknown = client
+        .report(authorized(proto::ReportRequest {
+            session_id: "no-such-session".into(),
+        }))
+        .await
+        .expect_err("unknown session refused");
+    assert_eq!(unknown.code(), Code::FailedPrecondition);
+    ct.cancel();
+}
+
+#[tokio::test]
+async fn grpc_stream_events_follows_the_bus_with_filters() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let state = grpc_state(tmp.path().to_str().expect("utf8"), TOKEN).await;
+    let ct = CancellationToken::new();
+    let mut client = serve(&state, &ct).await;
+
+    let mut stream = client
+        .stream_events(authorized(proto::StreamEventsRequest {
+            session_ids: vec!["s-1".into()],
+            kinds: vec!["status_updated".into()],
+        }))
+        .await
+        .expect("stream events")
+        .into_inner();
+    // The subscription is registered once the call is accepted; publish
+    // only after the bus sees it.
+    for _ in 0..50 {
+        if state.event_bus.subscriber_count() > 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let status = |session_id: &str, message: &str| AgentEvent::StatusUpdated {
+        session_id: session_id.into(),
+        message: message.into(),
+    };
+    state.event_bus.publish(
+        ProtocolMode::Mcp,
+        None,
+        AgentEvent::StreamActivity {
+            session_id: "s-1".into(),
+        },
+    );
+    state
+        .event_bus
+        .publish(ProtocolMode::Mcp, None, status("s-2", "other session"));
+    state.event_bus.publish(
+        ProtocolMode::Acp,
+        Some("C_TEST".into()),
+        status("s-1", "building"),
+    );
+
+    let event = tokio::time::timeout(Duration::from_secs(2), stream.message())
+        .await
+        .expect("event in time")
+        .expect("stream ok")
+        .expect("one event");
+    assert_eq!(event.source, "acp");
+    assert_eq!(event.session_id, "s-1");
+    assert_eq!(event.kind, "status_updated");
+    assert_eq!(event.channel_id.as_deref(), Some("C_TEST"));
+    let payload: serde_json::Value =
+        serde_json::from_str(&event.payload_json).expect("payload json");
+    assert_eq!(payload["message"], "building");
+
+    ct.cancel();
+    let end = tokio::time::timeout(Duration::from_secs(2), stream.message())
+        .await
+        .expect("stream ends on shutdown");
+    assert!(!matches!(end, Ok(Some(_))), "no event after shutdown");
+}
+
+#[tokio::test]
+async fn grpc_refuses_to_serve_without_a_token() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let state = grpc_state(tmp.path().to_str().expect("utf8"), "").await;
+    let listener = bind_grpc(&state).await.expect("bind grpc");
+
+    let result = serve_with_listener(listener, state, CancellationToken::new()).await;
+    assert!(matches!(result, Err(AppError::Config(_))), "{result:?}");
+}
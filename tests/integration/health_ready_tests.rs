@@ -0,0 +1,180 @@
+//! Integration tests for `/health/ready` and `/metrics`.
+//!
+//! The health router is served on an ephemeral port and driven with
+//! `reqwest`.
+//!
+//! Tests cover:
+//! - A server without Slack is ready once its database responds
+//! - With Slack, readiness waits for a Socket Mode hello
+//! - `/metrics` exposes Socket Mode, rate budget and event bus counters
+//! - A supervised task waiting to restart makes the server unready and
+//!   shows in both endpoints
+//! - `/metrics` exposes each ACP session's writer queue counters
+
+use std::sync::Arc;
+
+use agent_intercom::driver::acp_driver::AcpDriver;
+use agent_intercom::driver::AgentDriver;
+use agent_intercom::mcp::health;
+use agent_intercom::orchestrator::watchdog::Component;
+use agent_intercom::slack::client::SlackService;
+use agent_intercom::state::AppState;
+use serde_json::Value;
+
+use super::test_helpers::{test_app_state, test_config};
+
+async fn serve(state: Arc<AppState>) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let base_url = format!("http://{}", listener.local_addr().expect("addr"));
+    let router = health::router(state);
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+    base_url
+}
+
+async fn get(url: String) -> (u16, String) {
+    let resp = reqwest::get(url).await.expect("get");
+    let status = resp.status().as_u16();
+    (status, resp.text().await.expect("body"))
+}
+
+#[tokio::test]
+async fn ready_without_slack_needs_only_database() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(test_config(temp.path().to_str().expect("utf8"))).await;
+    let base_url = serve(state).await;
+
+    let (status, body) = get(format!("{base_url}/health/ready")).await;
+    assert_eq!(status, 200, "{body}");
+    let body: Value = serde_json::from_str(&body).expect("json");
+    assert_eq!(body["ready"], true);
+    assert_eq!(body["database"], true);
+    assert!(body["slack"].is_null());
+
+    let (status, body) = get(format!("{base_url}/metrics")).await;
+    assert_eq!(status, 200);
+    assert!(
+        body.contains("agent_intercom_events_published_total 0"),
+        "{body}"
+    );
+    assert!(!body.contains("slack_socket"), "{body}");
+}
+
+#[tokio::test]
+async fn ready_with_slack_waits_for_socket_hello() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let config = test_config(temp.path().to_str().expect("utf8"));
+    let (slack, _runtime) =
+        SlackService::start(&config.slack, &config.slack_rate_limit).expect("slack service");
+    let slack = Arc::new(slack);
+    let Ok(mut state) = Arc::try_unwrap(test_app_state(config).await) else {
+        panic!("state is shared");
+    };
+    state.slack = Some(Arc::clone(&slack));
+    let base_url = serve(Arc::new(state)).await;
+
+    let (status, body) = get(format!("{base_url}/health/ready")).await;
+    assert_eq!(status, 503, "{body}");
+    let body: Value = serde_json::from_str(&body).expect("json");
+    assert_eq!(body["ready"], false);
+    assert_eq!(body["slack"]["connected"], false);
+
+    slack.socket_health().record_hello(chrono::Utc::now());
+    let (status, body) = get(format!("{base_url}/health/ready")).await;
+    assert_eq!(status, 200, "{body}");
+    let body: Value = serde_json::from_str(&body).expect("json");
+    assert_eq!(body["slack"]["connected"], true);
+    assert_eq!(body["slack"]["disconnects"], 0);
+
+    let (_, body) = get(format!("{base_url}/metrics")).await;
+    assert!(
+        body.contains("agent_intercom_slack_socket_connected 1"),
+        "{body}"
+    );
+    assert!(
+        body.contains("agent_intercom_slack_socket_disconnects_total 0"),
+        "{body}"
+    );
+    assert!(
+        body.contains("agent_intercom_slack_rate_limited_total 0"),
+        "{body}"
+    );
+}
+
+#[tokio::test]
+async fn ready_and_metrics_report_supervised_subsystems() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(test_config(temp.path().to_str().expect("utf8"))).await;
+    state.subsystems.mark_running(Component::Retention);
+    state.subsystems.mark_running(Component::SlackQueue);
+    let base_url = serve(Arc::clone(&state)).await;
+
+    let (status, body) = get(format!("{base_url}/health/ready")).await;
+    assert_eq!(status, 200, "{body}");
+
+    let _ = state
+        .subsystems
+        .record(Component::Retention, "panicked: disk full");
+    let (status, body) = get(format!("{base_url}/health/ready")).await;
+    assert_eq!(status, 503, "{body}");
+    let body: Value = serde_json::from_str(&body).expect("json");
+    assert_eq!(body["ready"], false);
+    let retention = &body["subsystems"][1];
+    assert_eq!(retention["component"], "retention", "{body}");
+    assert_eq!(retention["running"], false);
+    assert_eq!(retention["restarts"], 1);
+    assert_eq!(retention["last_error"], "panicked: disk full");
+
+    let (_, body) = get(format!("{base_url}/metrics")).await;
+    assert!(
+        body.contains("agent_intercom_subsystem_up{subsystem=\"retention\"} 0"),
+        "{body}"
+    );
+    assert!(
+        body.contains("agent_intercom_subsystem_up{subsystem=\"slack_queue\"} 1"),
+        "{body}"
+    );
+    assert!(
+        body.contains("agent_intercom_subsystem_restarts_total{subsystem=\"retention\"} 1"),
+        "{body}"
+    );
+
+    state.subsystems.mark_running(Component::Retention);
+    let (status, body) = get(format!("{base_url}/health/ready")).await;
+    assert_eq!(status, 200, "{body}");
+}
+
+#[tokio::test]
+async fn metrics_expose_acp_writer_queues() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let Ok(mut state) =
+        Arc::try_unwrap(test_app_state(test_config(temp.path().to_str().expect("utf8"))).await)
+    else {
+        panic!("state is shared");
+    };
+    let acp = Arc::new(AcpDriver::new());
+    let (tx, _rx) = tokio::sync::mpsc::channel(16);
+    acp.register_session("sess-metrics", tx).await;
+    acp.register_agent_session_id("sess-metrics", "agent-1")
+        .await;
+    acp.send_prompt("sess-metrics", "hello")
+        .await
+        .expect("enqueue");
+    state.acp_driver = Some(acp);
+    let base_url = serve(Arc::new(state)).await;
+
+    let (status, body) = get(format!("{base_url}/metrics")).await;
+    assert_eq!(status, 200);
+    for sample in [
+        "agent_intercom_acp_writer_enqueued_total{session=\"sess-metrics\"} 1",
+        "agent_intercom_acp_writer_written_total{session=\"sess-metrics\"} 0",
+        "agent_intercom_acp_writer_rejected_total{session=\"sess-metrics\"} 0",
+        "agent_intercom_acp_writer_queue_depth{session=\"sess-metrics\"} 1",
+        "agent_intercom_acp_writer_queue_high_water{session=\"sess-metrics\"} 1",
+    ] {
+        assert!(body.contains(sample), "missing {sample}:\n{body}");
+    }
+}
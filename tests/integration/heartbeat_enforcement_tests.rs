@@ -0,0 +1,110 @@
+//! Integration tests for heartbeat-required mode (`[stall] heartbeat_required`).
+//!
+//! - Tool calls other than `ping` are refused once the heartbeat is overdue
+//! - The sweep marks the overdue session `stalled`
+//! - Only a `ping` with a status lifts the block and marks the session
+//!   `online` again
+//! - Without the requirement, any `ping` marks an offline or stalled
+//!   session `online`
+
+use std::sync::Arc;
+
+use agent_intercom::models::session::ConnectivityStatus;
+use agent_intercom::orchestrator::heartbeat_enforcer::{self, HEARTBEAT_REQUIRED_STATUS};
+use agent_intercom::persistence::session_repo::SessionRepo;
+use chrono::{Duration, Utc};
+use serde_json::json;
+
+use super::test_helpers::{connect_mcp_client, test_app_state, test_config};
+
+#[tokio::test]
+async fn overdue_session_is_blocked_until_it_pings_with_a_status() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let mut config = test_config(temp.path().to_str().expect("utf8"));
+    config.stall.heartbeat_required = true;
+    config.stall.heartbeat_deadline_seconds = 60;
+    let state = test_app_state(config).await;
+    let (mut client, session_id) = connect_mcp_client(&state).await;
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+
+    let result = client.call_tool(2, "relay_receive", json!({})).await;
+    assert_eq!(result["messages"], json!([]), "{result}");
+
+    // Last heartbeat two minutes ago: past the 60s deadline.
+    state
+        .heartbeats
+        .record(&session_id, Utc::now() - Duration::minutes(2));
+    let refused = client.call_tool(3, "relay_receive", json!({})).await;
+    assert_eq!(refused["status"], HEARTBEAT_REQUIRED_STATUS, "{refused}");
+    assert_eq!(refused["session_id"], session_id.as_str());
+
+    heartbeat_enforcer::enforce_heartbeats(&state, Utc::now()).await;
+    let session = repo
+        .get_by_id(&session_id)
+        .await
+        .expect("get")
+        .expect("found");
+    assert_eq!(session.connectivity_status, ConnectivityStatus::Stalled);
+
+    // A bare ping is not a heartbeat in this mode.
+    let ping = client.call_tool(4, "ping", json!({})).await;
+    assert_eq!(ping["heartbeat_deadline_seconds"], 60, "{ping}");
+    let still_refused = client.call_tool(5, "relay_receive", json!({})).await;
+    assert_eq!(still_refused["status"], HEARTBEAT_REQUIRED_STATUS);
+
+    client
+        .call_tool(
+            6,
+            "ping",
+            json!({ "status_message": "running the test suite" }),
+        )
+        .await;
+    let session = repo
+        .get_by_id(&session_id)
+        .await
+        .expect("get")
+        .expect("found");
+    assert_eq!(session.connectivity_status, ConnectivityStatus::Online);
+    let result = client.call_tool(7, "relay_receive", json!({})).await;
+    assert_eq!(result["messages"], json!([]), "{result}");
+}
+
+#[tokio::test]
+async fn optional_heartbeats_never_block() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(test_config(temp.path().to_str().expect("utf8"))).await;
+    let (mut client, session_id) = connect_mcp_client(&state).await;
+
+    state
+        .heartbeats
+        .record(&session_id, Utc::now() - Duration::hours(1));
+    let result = client.call_tool(2, "relay_receive", json!({})).await;
+    assert_eq!(result["messages"], json!([]), "{result}");
+
+    let ping = client.call_tool(3, "ping", json!({})).await;
+    assert!(ping.get("heartbeat_deadline_seconds").is_none(), "{ping}");
+}
+
+#[tokio::test]
+async fn ping_marks_session_online() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(test_config(temp.path().to_str().expect("utf8"))).await;
+    let (mut client, session_id) = connect_mcp_client(&state).await;
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+
+    for (id, status) in [
+        (2, ConnectivityStatus::Offline),
+        (3, ConnectivityStatus::Stalled),
+    ] {
+        repo.set_connectivity_status(&session_id, status)
+            .await
+            .expect("set");
+        client.call_tool(id, "ping", json!({})).await;
+        let session = repo
+            .get_by_id(&session_id)
+            .await
+            .expect("get")
+            .expect("found");
+        assert_eq!(session.connectivity_status, ConnectivityStatus::Online);
+    }
+}
@@ -0,0 +1,158 @@
+//! Integration tests for the `/mcp` request body limit.
+//!
+//! The HTTP transport is served on an ephemeral port and driven with
+//! `reqwest`.
+//!
+//! Tests cover:
+//! - An `initialize` body larger than the old 64 KiB cap is sanitized and accepted
+//! - A large `tools/call` body on an established session streams through to the tool
+//! - Bodies over `[limits] max_body_bytes` are refused with 413
+
+use std::time::Duration;
+
+use agent_intercom::mcp::sse::serve_with_listener;
+use tokio_util::sync::CancellationToken;
+
+use super::test_helpers::{test_app_state, test_config};
+
+const ACCEPT: &str = "application/json, text/event-stream";
+
+struct Harness {
+    base_url: String,
+    client: reqwest::Client,
+    ct: CancellationToken,
+    _temp: tempfile::TempDir,
+}
+
+impl Drop for Harness {
+    fn drop(&mut self) {
+        self.ct.cancel();
+    }
+}
+
+async fn harness(max_body_bytes: usize) -> Harness {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path").to_owned();
+    let mut config = test_config(&root);
+    config.limits.max_body_bytes = max_body_bytes;
+    let state = test_app_state(config).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let base_url = format!("http://{}", listener.local_addr().expect("addr"));
+    let ct = CancellationToken::new();
+    let server_ct = ct.clone();
+    tokio::spawn(async move {
+        let _ = serve_with_listener(listener, state, server_ct).await;
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    Harness {
+        base_url,
+        client: reqwest::Client::new(),
+        ct,
+        _temp: temp,
+    }
+}
+
+/// An `initialize` request padded to `padding` bytes, using the protocol
+/// version and capabilities the middleware has to rewrite.
+fn initialize_body(padding: usize) -> String {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-11-25",
+            "capabilities": { "elicitation": {}, "tasks": { "list": {} } },
+            "clientInfo": { "name": "x".repeat(padding), "version": "0.1" }
+        }
+    })
+    .to_string()
+}
+
+impl Harness {
+    async fn post(&self, session: Option<&str>, body: String) -> reqwest::Response {
+        let mut request = self
+            .client
+            .post(format!("{}/mcp", self.base_url))
+            .header("Content-Type", "application/json")
+            .header("Accept", ACCEPT)
+            .body(body);
+        if let Some(session) = session {
+            request = request.header("Mcp-Session-Id", session);
+        }
+        tokio::time::timeout(Duration::from_secs(10), request.send())
+            .await
+            .expect("response in time")
+            .expect("POST /mcp")
+    }
+
+    /// Complete the handshake and return the `Mcp-Session-Id`.
+    async fn initialize(&self, padding: usize) -> String {
+        let resp = self.post(None, initialize_body(padding)).await;
+        assert_eq!(resp.status(), 200);
+        let session = resp
+            .headers()
+            .get("Mcp-Session-Id")
+            .and_then(|v| v.to_str().ok())
+            .expect("session header")
+            .to_owned();
+        let text = resp.text().await.expect("initialize body");
+        assert!(text.contains("serverInfo"), "{text}");
+
+        let initialized = r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#;
+        let resp = self.post(Some(&session), initialized.to_owned()).await;
+        assert!(resp.status().is_success(), "{}", resp.status());
+        session
+    }
+}
+
+#[tokio::test]
+async fn large_initialize_body_is_sanitized_and_accepted() {
+    let h = harness(1024 * 1024).await;
+    h.initialize(200 * 1024).await;
+}
+
+#[tokio::test]
+async fn large_tool_call_body_streams_through_to_the_tool() {
+    let h = harness(1024 * 1024).await;
+    let session = h.initialize(0).await;
+
+    // Well over the old 64 KiB cap. The tool answering at all proves the
+    // body reached rmcp intact and parsed.
+    let call = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {
+            "name": "check_clearance",
+            "arguments": {
+                "title": "big change",
+                "diff": "+".repeat(300 * 1024),
+                "file_path": "src/lib.rs"
+            }
+        }
+    });
+    let resp = h.post(Some(&session), call.to_string()).await;
+    assert_eq!(resp.status(), 200);
+    let text = tokio::time::timeout(Duration::from_secs(10), resp.text())
+        .await
+        .expect("tool reply in time")
+        .expect("tool reply");
+    assert!(text.contains(r#""id":2,"result""#), "{text}");
+}
+
+#[tokio::test]
+async fn body_over_limit_is_refused() {
+    let h = harness(16 * 1024).await;
+
+    let resp = h.post(None, initialize_body(32 * 1024)).await;
+    assert_eq!(resp.status(), 413);
+    let text = resp.text().await.expect("body");
+    assert!(text.contains("max_body_bytes"), "{text}");
+
+    let resp = h.post(Some("any-session"), "x".repeat(32 * 1024)).await;
+    assert_eq!(resp.status(), 413);
+}
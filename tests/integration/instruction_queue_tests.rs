@@ -0,0 +1,160 @@
+//! Integration tests for the standby instruction queue (`standby-queue`).
+//!
+//! - `standby` with queued instructions resumes at once with the first and
+//!   delivers the rest as steering, in order
+//! - `standby-queue <text>` resolves a pending standby directly, and
+//!   otherwise queues the instruction for `standby-queue list`
+//! - a paused session's standby is left for `session-resume`
+
+use std::sync::Arc;
+
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::persistence::steering_repo::SteeringRepo;
+use agent_intercom::slack::commands::dispatch_command;
+use agent_intercom::state::{AppState, WaitResponse};
+use serde_json::json;
+use tokio::sync::oneshot;
+
+use super::test_helpers::{connect_mcp_client, test_app_state, test_config};
+
+const OWNER: &str = "U_TEST_OWNER";
+const CHANNEL: &str = "C_TEST";
+
+/// An active session owned by [`OWNER`] in [`CHANNEL`].
+async fn channel_session(state: &Arc<AppState>, root: &str) -> Session {
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let mut session = Session::new(OWNER.into(), root.into(), None, SessionMode::Remote);
+    session.channel_id = Some(CHANNEL.into());
+    let created = repo.create(&session).await.expect("create session");
+    repo.update_status(&created.id, SessionStatus::Active)
+        .await
+        .expect("activate session")
+}
+
+#[tokio::test]
+async fn standby_resumes_with_first_queued_instruction() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(test_config(temp.path().to_str().expect("utf8"))).await;
+    let (mut client, session_id) = connect_mcp_client(&state).await;
+    for instruction in ["write the tests", "run clippy", "open a PR"] {
+        state
+            .instruction_queue
+            .push(&session_id, instruction.to_owned());
+    }
+
+    let result = client.call_tool(2, "standby", json!({})).await;
+    assert_eq!(result["status"], "resumed", "{result}");
+    assert_eq!(result["instruction"], "write the tests", "{result}");
+
+    let steered: Vec<String> = SteeringRepo::new(Arc::clone(&state.db))
+        .fetch_unconsumed(&session_id)
+        .await
+        .expect("steering")
+        .into_iter()
+        .map(|msg| msg.message)
+        .collect();
+    assert_eq!(steered, ["run clippy", "open a PR"]);
+    assert!(state.instruction_queue.list(&session_id).is_empty());
+}
+
+#[tokio::test]
+async fn queue_resolves_a_waiting_standby() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+    let session = channel_session(&state, root).await;
+    let (tx, rx) = oneshot::channel::<WaitResponse>();
+    state
+        .pending_waits
+        .lock()
+        .await
+        .insert(session.id.clone(), tx);
+
+    let reply = dispatch_command(
+        "standby-queue",
+        &["run", "the", "tests"],
+        OWNER,
+        CHANNEL,
+        &state,
+    )
+    .await
+    .expect("queue");
+    assert!(reply.contains("delivered"), "{reply}");
+
+    let response = rx.await.expect("standby released");
+    assert_eq!(response.status, "resumed");
+    assert_eq!(response.instruction.as_deref(), Some("run the tests"));
+    assert!(state.instruction_queue.list(&session.id).is_empty());
+}
+
+#[tokio::test]
+async fn queue_holds_instructions_until_standby_and_lists_them() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+    let session = channel_session(&state, root).await;
+
+    let empty = dispatch_command("standby-queue", &["list"], OWNER, CHANNEL, &state)
+        .await
+        .expect("list");
+    assert!(empty.contains("No instructions queued"), "{empty}");
+
+    let reply = dispatch_command("standby-queue", &["first", "task"], OWNER, CHANNEL, &state)
+        .await
+        .expect("queue first");
+    assert!(reply.contains("Queued instruction 1"), "{reply}");
+    dispatch_command("standby-queue", &["second", "task"], OWNER, CHANNEL, &state)
+        .await
+        .expect("queue second");
+
+    let listed = dispatch_command("standby-queue", &["list"], OWNER, CHANNEL, &state)
+        .await
+        .expect("list");
+    assert!(
+        listed.contains("1. first task") && listed.contains("2. second task"),
+        "{listed}"
+    );
+    assert_eq!(
+        state.instruction_queue.list(&session.id),
+        ["first task", "second task"]
+    );
+}
+
+#[tokio::test]
+async fn paused_session_keeps_queued_instruction() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+    let session = channel_session(&state, root).await;
+    SessionRepo::new(Arc::clone(&state.db))
+        .update_status(&session.id, SessionStatus::Paused)
+        .await
+        .expect("pause");
+    let (tx, mut rx) = oneshot::channel::<WaitResponse>();
+    state
+        .pending_waits
+        .lock()
+        .await
+        .insert(session.id.clone(), tx);
+
+    let reply = dispatch_command("standby-queue", &["next", "step"], OWNER, CHANNEL, &state)
+        .await
+        .expect("queue");
+    assert!(reply.contains("Queued instruction 1"), "{reply}");
+    assert!(rx.try_recv().is_err(), "a paused standby waits for resume");
+    assert_eq!(state.instruction_queue.list(&session.id), ["next step"]);
+}
+
+#[tokio::test]
+async fn queue_requires_an_instruction() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+    channel_session(&state, root).await;
+
+    let err = dispatch_command("standby-queue", &[], OWNER, CHANNEL, &state)
+        .await
+        .expect_err("usage");
+    assert!(err.to_string().contains("usage: standby-queue"), "{err}");
+}
@@ -6,9 +6,20 @@
 //! - S055: Missing auth token rejected
 //! - S057: `list` command returns active sessions
 //! - S059: `approve` resolves pending approval via oneshot
+//! - `approve` accepts a unique ID prefix and refuses an ambiguous one
 //! - S060: `reject` resolves with reason via oneshot
 //! - S062: `resume` resolves pending wait via oneshot
+//! - `resume` routes through the driver registered for the session
 //! - S064: `mode` command changes session operational mode
+//! - `report` renders a session's Markdown report by ID prefix
+//! - `logs` returns a session's newest streamed log lines
+//! - `session-delete` hides ended sessions only; `session-restore` undoes it
+//! - `sessions` applies a bulk action server-side and refuses unfiltered runs
+//! - `chaos` is served only by builds with the `chaos` feature
+//! - `log-level` changes and resets the tracing filter, when one is installed
+//! - `slack-status` and `slack-reconnect` are refused on servers without Slack
+//! - The typed `IpcClient` round-trips commands and surfaces server errors
+//! - Every command is audited with its peer and outcome, never its token
 //!
 //! FR-008 — IPC Server Command Dispatch
 
@@ -16,14 +27,22 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+use agent_intercom::audit::{AuditEntry, AuditEventType, AuditLogger};
 use agent_intercom::config::GlobalConfig;
+use agent_intercom::driver::acp_driver::AcpDriver;
+use agent_intercom::driver::session_hooks::SessionHookKind;
+use agent_intercom::ipc::client::{IpcClient, IpcCommand};
 use agent_intercom::ipc::server::spawn_ipc_server;
-use agent_intercom::models::approval::{ApprovalRequest, RiskLevel};
+use agent_intercom::log_filter::LogFilter;
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
 use agent_intercom::models::session::{SessionMode, SessionStatus};
+use agent_intercom::orchestrator::session_bulk::BulkAction;
+use agent_intercom::orchestrator::session_logs::{LogLevel, LogLine};
 use agent_intercom::persistence::approval_repo::ApprovalRepo;
 use agent_intercom::persistence::db;
 use agent_intercom::persistence::session_repo::SessionRepo;
 use agent_intercom::state::{AppState, ApprovalResponse, WaitResponse};
+use agent_intercom::AppError;
 use interprocess::local_socket::{
     traits::Stream as SyncStreamTrait, GenericNamespaced, Stream, ToNsName,
 };
@@ -31,7 +50,7 @@ use sqlx::SqlitePool;
 use tokio::sync::{oneshot, Mutex};
 use tokio_util::sync::CancellationToken;
 
-use super::test_helpers::create_active_session;
+use super::test_helpers::{create_active_session, create_interrupted_session};
 
 // ── Helpers ───────────────────────────────────────────────────────────────────
 
@@ -99,6 +118,27 @@ fn ipc_app_state(
         workspace_mappings: Arc::default(),
         acp_event_tx: None,
         acp_driver: None,
+        driver_registry: Arc::default(),
+        event_bus: Arc::default(),
+        session_hooks: Arc::default(),
+        pause_requests: Arc::default(),
+        heartbeats: Arc::default(),
+        instruction_queue: Arc::default(),
+        snoozes: Arc::default(),
+        apply_confirmations: Arc::default(),
+        session_logs: Arc::default(),
+        session_channels: Arc::default(),
+        workspace_discovery: Arc::default(),
+        config_path: None,
+        command_aliases: Arc::default(),
+        proxy: Arc::default(),
+        replay_guard: Arc::default(),
+        interactions: Arc::default(),
+        rearmed: Arc::default(),
+        diff_staging: Arc::default(),
+        subsystems: Arc::default(),
+        simulator: None,
+        log_filter: None,
     })
 }
 
@@ -315,6 +355,62 @@ async fn ipc_approve_resolves_pending_approval() {
     assert!(approval_resp.reason.is_none());
 }
 
+#[tokio::test]
+async fn ipc_approve_accepts_unique_prefix_and_lists_ambiguous_candidates() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let ipc_name = unique_ipc_name();
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+
+    let session = create_active_session(&db, root).await;
+    let repo = ApprovalRepo::new(Arc::clone(&db));
+    let mut approvals = Vec::new();
+    for title in ["first", "second"] {
+        let approval = ApprovalRequest::new(
+            session.id.clone(),
+            title.into(),
+            None,
+            "diff content".into(),
+            "src/lib.rs".into(),
+            RiskLevel::Low,
+            "abc123".into(),
+        );
+        approvals.push(repo.create(&approval).await.expect("create approval"));
+    }
+
+    let state = ipc_app_state(Arc::clone(&db), root, &ipc_name, None);
+    let ct = CancellationToken::new();
+    spawn_ipc_server(Arc::clone(&state), ct.clone()).expect("spawn ipc server");
+
+    let ambiguous = send_ipc(
+        ipc_name.clone(),
+        serde_json::json!({"command": "approve", "id": "apr-"}),
+    )
+    .await;
+    assert!(!ambiguous["ok"].as_bool().unwrap_or(true), "{ambiguous}");
+    let message = ambiguous["error"].as_str().unwrap_or_default();
+    assert!(message.contains("ambiguous approval prefix"), "{message}");
+    for approval in &approvals {
+        assert!(message.contains(&approval.short_id), "{message}");
+    }
+
+    let prefix = approvals[0].id[..12].to_uppercase();
+    let resp = send_ipc(
+        ipc_name,
+        serde_json::json!({"command": "approve", "id": prefix}),
+    )
+    .await;
+    ct.cancel();
+    assert!(resp["ok"].as_bool().unwrap_or(false), "{resp}");
+    assert_eq!(resp["data"]["request_id"], approvals[0].id.as_str());
+    let stored = repo
+        .get_by_id(&approvals[0].id)
+        .await
+        .expect("query")
+        .expect("exists");
+    assert_eq!(stored.status, ApprovalStatus::Approved);
+}
+
 // ── S060: reject resolves oneshot with reason ─────────────────────────────────
 
 #[tokio::test]
@@ -424,6 +520,81 @@ async fn ipc_resume_resolves_pending_wait() {
     assert_eq!(wait_resp.instruction.as_deref(), Some("deploy to staging"));
 }
 
+#[tokio::test]
+async fn ipc_resume_routes_through_registered_session_driver() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let ipc_name = unique_ipc_name();
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let session = create_active_session(&db, root).await;
+
+    // Bind the session to an ACP driver with a live writer channel. No MCP
+    // wait is pending, so the resume must be delivered over the ACP stream.
+    let acp = Arc::new(AcpDriver::new());
+    let (writer_tx, mut writer_rx) = tokio::sync::mpsc::channel::<serde_json::Value>(4);
+    acp.register_session(&session.id, writer_tx).await;
+    acp.register_agent_session_id(&session.id, "agent-sess-1")
+        .await;
+
+    let state = ipc_app_state(Arc::clone(&db), root, &ipc_name, None);
+    state.driver_registry.register(&session.id, acp).await;
+
+    let ct = CancellationToken::new();
+    spawn_ipc_server(Arc::clone(&state), ct.clone()).expect("spawn ipc server");
+
+    let resp = send_ipc(
+        ipc_name,
+        serde_json::json!({
+            "command": "resume",
+            "id": session.id,
+            "instruction": "run the tests"
+        }),
+    )
+    .await;
+    ct.cancel();
+
+    assert!(
+        resp["ok"].as_bool().unwrap_or(false),
+        "resume should succeed: {resp}"
+    );
+    let msg = tokio::time::timeout(Duration::from_secs(1), writer_rx.recv())
+        .await
+        .expect("prompt written within 1 s")
+        .expect("writer channel open");
+    assert_eq!(msg["method"], "session/prompt");
+    assert_eq!(msg["params"]["sessionId"], "agent-sess-1");
+    assert_eq!(msg["params"]["prompt"][0]["text"], "run the tests");
+}
+
+#[tokio::test]
+async fn ipc_resume_unregistered_session_without_wait_is_rejected() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let ipc_name = unique_ipc_name();
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let session = create_active_session(&db, root).await;
+
+    let state = ipc_app_state(Arc::clone(&db), root, &ipc_name, None);
+    let ct = CancellationToken::new();
+    spawn_ipc_server(Arc::clone(&state), ct.clone()).expect("spawn ipc server");
+
+    let resp = send_ipc(
+        ipc_name,
+        serde_json::json!({ "command": "resume", "id": session.id }),
+    )
+    .await;
+    ct.cancel();
+
+    assert!(!resp["ok"].as_bool().unwrap_or(true));
+    assert!(
+        resp["error"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("is not waiting"),
+        "unexpected error: {resp}"
+    );
+}
+
 // ── S064: mode command changes session operational mode ───────────────────────
 
 #[tokio::test]
@@ -470,3 +641,559 @@ async fn ipc_mode_changes_session_mode() {
         "session mode should be Hybrid in DB"
     );
 }
+
+#[tokio::test]
+async fn ipc_mode_change_notifies_subscribed_agent() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let ipc_name = unique_ipc_name();
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let session = create_active_session(&db, root).await;
+
+    let acp = Arc::new(AcpDriver::new());
+    let (writer_tx, mut writer_rx) = tokio::sync::mpsc::channel::<serde_json::Value>(4);
+    acp.register_session(&session.id, writer_tx).await;
+
+    let state = ipc_app_state(Arc::clone(&db), root, &ipc_name, None);
+    state.driver_registry.register(&session.id, acp).await;
+    state
+        .session_hooks
+        .subscribe(&session.id, [SessionHookKind::ModeChanged]);
+
+    let ct = CancellationToken::new();
+    spawn_ipc_server(Arc::clone(&state), ct.clone()).expect("spawn ipc server");
+
+    let resp = send_ipc(
+        ipc_name,
+        serde_json::json!({"command": "mode", "mode": "local"}),
+    )
+    .await;
+    ct.cancel();
+
+    assert!(resp["ok"].as_bool().unwrap_or(false), "mode failed: {resp}");
+    let msg = tokio::time::timeout(Duration::from_secs(1), writer_rx.recv())
+        .await
+        .expect("hook event written within 1 s")
+        .expect("writer channel open");
+    assert_eq!(msg["method"], "intercom/event");
+    assert_eq!(msg["params"]["kind"], "mode_changed");
+    assert_eq!(msg["params"]["session_id"], session.id.as_str());
+    assert_eq!(msg["params"]["previous_mode"], "remote");
+    assert_eq!(msg["params"]["current_mode"], "local");
+    assert!(msg.get("id").is_none(), "hook events are notifications");
+}
+
+// ── report renders the session's Markdown report ─────────────────────────────
+
+#[tokio::test]
+async fn ipc_report_returns_session_markdown() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let ipc_name = unique_ipc_name();
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let session = create_active_session(&db, root).await;
+
+    let state = ipc_app_state(Arc::clone(&db), root, &ipc_name, None);
+    let ct = CancellationToken::new();
+    spawn_ipc_server(Arc::clone(&state), ct.clone()).expect("spawn ipc server");
+
+    let prefix: String = session.id.chars().take(8).collect();
+    let resp = send_ipc(
+        ipc_name.clone(),
+        serde_json::json!({"command": "report", "id": prefix}),
+    )
+    .await;
+    let missing = send_ipc(
+        ipc_name,
+        serde_json::json!({"command": "report", "id": "no-such-session"}),
+    )
+    .await;
+    ct.cancel();
+
+    assert!(
+        resp["ok"].as_bool().unwrap_or(false),
+        "report failed: {resp}"
+    );
+    assert_eq!(resp["data"]["session_id"], session.id.as_str());
+    let markdown = resp["data"]["markdown"].as_str().expect("markdown");
+    assert!(markdown.starts_with("# Session report"), "{markdown}");
+    assert!(markdown.contains("## Transcript"), "{markdown}");
+
+    assert!(!missing["ok"].as_bool().unwrap_or(true));
+    assert!(
+        missing["error"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("not found"),
+        "{missing}"
+    );
+}
+
+// ── logs returns the newest streamed log lines ───────────────────────────────
+
+#[tokio::test]
+async fn ipc_logs_returns_newest_lines() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let ipc_name = unique_ipc_name();
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let session = create_active_session(&db, root).await;
+
+    let state = ipc_app_state(Arc::clone(&db), root, &ipc_name, None);
+    for n in 1..=3 {
+        state.session_logs.push(
+            &session.id,
+            LogLine {
+                at: chrono::Utc::now(),
+                level: LogLevel::Info,
+                message: format!("step {n}"),
+                fields: serde_json::Map::new(),
+            },
+        );
+    }
+    let ct = CancellationToken::new();
+    spawn_ipc_server(Arc::clone(&state), ct.clone()).expect("spawn ipc server");
+
+    let prefix: String = session.id.chars().take(8).collect();
+    let resp = send_ipc(
+        ipc_name.clone(),
+        serde_json::json!({"command": "logs", "id": prefix, "limit": 2}),
+    )
+    .await;
+    let too_many = send_ipc(
+        ipc_name,
+        serde_json::json!({"command": "logs", "id": prefix, "limit": 5000}),
+    )
+    .await;
+    ct.cancel();
+
+    assert!(resp["ok"].as_bool().unwrap_or(false), "logs failed: {resp}");
+    assert_eq!(resp["data"]["session_id"], session.id.as_str());
+    assert_eq!(resp["data"]["buffered"], 3);
+    let messages: Vec<&str> = resp["data"]["lines"]
+        .as_array()
+        .expect("lines")
+        .iter()
+        .filter_map(|line| line["message"].as_str())
+        .collect();
+    assert_eq!(messages, ["step 2", "step 3"]);
+
+    assert!(!too_many["ok"].as_bool().unwrap_or(true), "{too_many}");
+}
+
+// ── session-delete / session-restore toggle soft deletion ────────────────────
+
+#[tokio::test]
+async fn ipc_session_delete_hides_ended_sessions_until_restored() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let ipc_name = unique_ipc_name();
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let active = create_active_session(&db, root).await;
+    let ended = create_interrupted_session(&db, root).await;
+
+    let state = ipc_app_state(Arc::clone(&db), root, &ipc_name, None);
+    let ct = CancellationToken::new();
+    spawn_ipc_server(Arc::clone(&state), ct.clone()).expect("spawn ipc server");
+
+    let mut client = IpcClient::connect(&ipc_name).await.expect("connect");
+    let refused = client.delete_session(&active.id).await;
+    let prefix: String = ended.id.chars().take(8).collect();
+    let deleted = client.delete_session(&prefix).await.expect("delete");
+    let repo = SessionRepo::new(Arc::clone(&db));
+    let hidden = repo.list_interrupted().await.expect("list interrupted");
+    let again = client.delete_session(&ended.id).await;
+    let restored = client.restore_session(&prefix).await.expect("restore");
+    let visible = repo.list_interrupted().await.expect("list interrupted");
+    ct.cancel();
+
+    match refused {
+        Err(AppError::Ipc(msg)) => assert!(msg.contains("only ended sessions"), "{msg}"),
+        other => panic!("expected refusal, got {other:?}"),
+    }
+    assert_eq!(deleted.session_id, ended.id);
+    assert!(deleted.deleted);
+    assert!(hidden.is_empty());
+    assert!(matches!(again, Err(AppError::Ipc(msg)) if msg.contains("already deleted")));
+    assert!(!restored.deleted);
+    assert_eq!(visible.len(), 1);
+}
+
+// ── sessions applies a bulk action in one call ──────────────────────────────
+
+#[tokio::test]
+async fn ipc_bulk_sessions_clears_matching_sessions() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let ipc_name = unique_ipc_name();
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let active = create_active_session(&db, root).await;
+    let interrupted = create_interrupted_session(&db, root).await;
+
+    let state = ipc_app_state(Arc::clone(&db), root, &ipc_name, None);
+    let ct = CancellationToken::new();
+    spawn_ipc_server(Arc::clone(&state), ct.clone()).expect("spawn ipc server");
+
+    let mut client = IpcClient::connect(&ipc_name).await.expect("connect");
+    let unfiltered = client
+        .bulk_sessions(&IpcCommand::Sessions {
+            action: BulkAction::Clear,
+            statuses: Vec::new(),
+            owner: None,
+            older_than: None,
+            all: false,
+        })
+        .await;
+    let outcome = client
+        .bulk_sessions(&IpcCommand::Sessions {
+            action: BulkAction::Clear,
+            statuses: vec![SessionStatus::Interrupted],
+            owner: None,
+            older_than: None,
+            all: false,
+        })
+        .await
+        .expect("bulk clear");
+    let bad_age = send_ipc(
+        ipc_name,
+        serde_json::json!({"command": "sessions", "action": "pause", "older_than": "soon"}),
+    )
+    .await;
+    ct.cancel();
+
+    assert!(
+        matches!(unfiltered, Err(AppError::Ipc(ref msg)) if msg.contains("refusing")),
+        "{unfiltered:?}"
+    );
+    assert_eq!(outcome.action, BulkAction::Clear);
+    assert_eq!(outcome.matched, 1);
+    assert_eq!(outcome.succeeded, [interrupted.id.as_str()]);
+    let repo = SessionRepo::new(Arc::clone(&db));
+    let cleared = repo
+        .get_by_id(&interrupted.id)
+        .await
+        .expect("fetch")
+        .expect("session");
+    assert_eq!(cleared.status, SessionStatus::Terminated);
+    let untouched = repo
+        .get_by_id(&active.id)
+        .await
+        .expect("fetch")
+        .expect("session");
+    assert_eq!(untouched.status, SessionStatus::Active);
+    assert!(!bad_age["ok"].as_bool().unwrap_or(true), "{bad_age}");
+}
+
+// ── chaos sets fault injection on chaos builds only ──────────────────────────
+
+#[tokio::test]
+async fn ipc_chaos_command_matches_build() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let ipc_name = unique_ipc_name();
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let state = ipc_app_state(db, root, &ipc_name, None);
+    let ct = CancellationToken::new();
+    spawn_ipc_server(Arc::clone(&state), ct.clone()).expect("spawn ipc server");
+
+    let mut client = IpcClient::connect(&ipc_name).await.expect("connect");
+    let outcome = client
+        .chaos(&IpcCommand::Chaos {
+            drop_percent: Some(0),
+            delay_ms: None,
+            kill_socket: true,
+            reset: true,
+        })
+        .await;
+    ct.cancel();
+
+    if cfg!(feature = "chaos") {
+        let outcome = outcome.expect("chaos");
+        assert_eq!(outcome.drop_percent, 0);
+        assert_eq!(outcome.delay_ms, 0);
+        assert!(!outcome.socket_killed, "no socket mode connection to kill");
+    } else {
+        assert!(
+            matches!(outcome, Err(AppError::Ipc(ref msg)) if msg.contains("--features chaos")),
+            "{outcome:?}"
+        );
+    }
+}
+
+// ── log-level reloads the tracing filter ────────────────────────────────────
+
+#[tokio::test]
+async fn ipc_log_level_sets_and_resets_filter() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let ipc_name = unique_ipc_name();
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let reloads = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let counter = Arc::clone(&reloads);
+    let mut state = Arc::into_inner(ipc_app_state(db, root, &ipc_name, None)).expect("sole owner");
+    state.log_filter = Some(Arc::new(LogFilter::with_apply(
+        "info,sqlx=warn",
+        move |_| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        },
+    )));
+    let state = Arc::new(state);
+    let ct = CancellationToken::new();
+    spawn_ipc_server(Arc::clone(&state), ct.clone()).expect("spawn ipc server");
+
+    let mut client = IpcClient::connect(&ipc_name).await.expect("connect");
+    let log_level = |level: Option<&str>, target: Option<&str>, reset: bool| IpcCommand::LogLevel {
+        level: level.map(str::to_owned),
+        target: target.map(str::to_owned),
+        reset,
+    };
+    let current = client
+        .log_level(&log_level(None, None, false))
+        .await
+        .expect("current");
+    let targeted = client
+        .log_level(&log_level(
+            Some("debug"),
+            Some("agent_intercom::slack"),
+            false,
+        ))
+        .await
+        .expect("set target");
+    let unknown = client
+        .log_level(&log_level(Some("loud"), None, false))
+        .await;
+    let reset = client
+        .log_level(&log_level(None, None, true))
+        .await
+        .expect("reset");
+    ct.cancel();
+
+    assert_eq!(current.filter, "info,sqlx=warn");
+    assert_eq!(
+        targeted.filter,
+        "info,sqlx=warn,agent_intercom::slack=debug"
+    );
+    assert!(
+        matches!(unknown, Err(AppError::Ipc(ref msg)) if msg.contains("unknown log level")),
+        "{unknown:?}"
+    );
+    assert_eq!(reset.filter, "info,sqlx=warn");
+    assert_eq!(reloads.load(std::sync::atomic::Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn ipc_log_level_refused_without_filter() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let ipc_name = unique_ipc_name();
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let state = ipc_app_state(db, root, &ipc_name, None);
+    let ct = CancellationToken::new();
+    spawn_ipc_server(Arc::clone(&state), ct.clone()).expect("spawn ipc server");
+
+    let resp = send_ipc(
+        ipc_name,
+        serde_json::json!({"command": "log-level", "level": "debug"}),
+    )
+    .await;
+    ct.cancel();
+
+    assert!(!resp["ok"].as_bool().unwrap_or(true), "{resp}");
+    assert!(
+        resp["error"]
+            .as_str()
+            .is_some_and(|e| e.contains("does not support runtime log levels")),
+        "{resp}"
+    );
+}
+
+// ── typed client round-trips commands over one connection ───────────────────
+
+#[tokio::test]
+async fn ipc_client_round_trips_typed_commands() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let ipc_name = unique_ipc_name();
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let session = create_active_session(&db, root).await;
+    SessionRepo::new(Arc::clone(&db))
+        .set_tags(
+            &session.id,
+            &[("team".to_owned(), "payments".to_owned())].into(),
+        )
+        .await
+        .expect("tag session");
+
+    let state = ipc_app_state(
+        Arc::clone(&db),
+        root,
+        &ipc_name,
+        Some("secret-token".into()),
+    );
+    state.session_logs.push(
+        &session.id,
+        LogLine {
+            at: chrono::Utc::now(),
+            level: LogLevel::Warn,
+            message: "retrying".into(),
+            fields: serde_json::Map::new(),
+        },
+    );
+    let ct = CancellationToken::new();
+    spawn_ipc_server(Arc::clone(&state), ct.clone()).expect("spawn ipc server");
+
+    let mut client = IpcClient::connect(&ipc_name)
+        .await
+        .expect("connect")
+        .with_auth_token("secret-token");
+    let sessions = client.list().await.expect("list");
+    let tagged = client
+        .list_tagged(&["team=payments".into()])
+        .await
+        .expect("tagged list");
+    let untagged = client
+        .list_tagged(&["team=search".into()])
+        .await
+        .expect("tagged list");
+    let bad_tag = client.list_tagged(&["team".into()]).await;
+    let change = client.set_mode(SessionMode::Local).await.expect("mode");
+    let logs = client.logs(&session.id, None).await.expect("logs");
+    let missing = client.report("no-such-session").await;
+    ct.cancel();
+
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].session_id, session.id);
+    assert_eq!(sessions[0].status, SessionStatus::Active);
+    assert_eq!(tagged.len(), 1);
+    assert_eq!(tagged[0].tags["team"], "payments");
+    assert!(untagged.is_empty());
+    assert!(matches!(bad_tag, Err(AppError::Ipc(_))), "{bad_tag:?}");
+    assert_eq!(change.previous_mode, SessionMode::Remote);
+    assert_eq!(change.current_mode, SessionMode::Local);
+    assert_eq!(logs.buffered, 1);
+    assert_eq!(logs.lines[0].level, LogLevel::Warn);
+    assert!(
+        matches!(missing, Err(AppError::Ipc(_))),
+        "server errors surface as AppError::Ipc: {missing:?}"
+    );
+}
+
+#[tokio::test]
+async fn ipc_client_without_token_is_unauthorized() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let ipc_name = unique_ipc_name();
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+
+    let state = ipc_app_state(db, root, &ipc_name, Some("secret-token".into()));
+    let ct = CancellationToken::new();
+    spawn_ipc_server(Arc::clone(&state), ct.clone()).expect("spawn ipc server");
+
+    let mut client = IpcClient::connect(&ipc_name).await.expect("connect");
+    let result = client.list().await;
+    ct.cancel();
+
+    match result {
+        Err(AppError::Ipc(msg)) => assert_eq!(msg, "unauthorized"),
+        other => panic!("expected unauthorized, got {other:?}"),
+    }
+}
+
+/// Audit logger that keeps entries in memory.
+#[derive(Default)]
+struct RecordingLogger {
+    entries: std::sync::Mutex<Vec<AuditEntry>>,
+}
+
+impl AuditLogger for RecordingLogger {
+    fn log_entry(&self, entry: AuditEntry) -> agent_intercom::Result<()> {
+        self.entries.lock().expect("entries").push(entry);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn ipc_commands_are_audited_with_peer_and_outcome() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let ipc_name = unique_ipc_name();
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let logger = Arc::new(RecordingLogger::default());
+
+    let mut state = Arc::into_inner(ipc_app_state(
+        db,
+        root,
+        &ipc_name,
+        Some("secret-token".into()),
+    ))
+    .expect("sole owner");
+    state.audit_logger = Some(Arc::clone(&logger) as Arc<dyn AuditLogger>);
+    let state = Arc::new(state);
+    let ct = CancellationToken::new();
+    spawn_ipc_server(Arc::clone(&state), ct.clone()).expect("spawn ipc server");
+
+    let mut client = IpcClient::connect(&ipc_name)
+        .await
+        .expect("connect")
+        .with_auth_token("secret-token");
+    client.list().await.expect("list");
+    client
+        .reject("req-1", Some("too risky"))
+        .await
+        .expect("reject");
+    let _ = client.report("no-such-session").await;
+    ct.cancel();
+
+    let entries = logger.entries.lock().expect("entries").clone();
+    assert_eq!(entries.len(), 3, "{entries:?}");
+    assert!(entries
+        .iter()
+        .all(|e| e.event_type == AuditEventType::IpcCommand));
+    assert!(entries.iter().all(|e| e
+        .operator_id
+        .as_deref()
+        .is_some_and(|op| op.starts_with("ipc:"))));
+
+    assert_eq!(entries[0].command.as_deref(), Some("list"));
+    assert_eq!(entries[0].result_summary.as_deref(), Some("ok"));
+
+    let reject = &entries[1];
+    assert_eq!(reject.command.as_deref(), Some("reject"));
+    assert_eq!(reject.request_id.as_deref(), Some("req-1"));
+    let params = reject.parameters.as_ref().expect("parameters");
+    assert_eq!(params["reason"], "too risky");
+    assert!(params.get("auth_token").is_none(), "{params}");
+    assert!(params.get("command").is_none(), "{params}");
+
+    assert_eq!(entries[2].command.as_deref(), Some("report"));
+    assert!(entries[2]
+        .result_summary
+        .as_deref()
+        .is_some_and(|r| r.starts_with("error: ")));
+}
+
+#[tokio::test]
+async fn ipc_slack_commands_refused_without_slack() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let ipc_name = unique_ipc_name();
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let state = ipc_app_state(db, root, &ipc_name, None);
+    let ct = CancellationToken::new();
+    spawn_ipc_server(Arc::clone(&state), ct.clone()).expect("spawn ipc server");
+
+    let mut client = IpcClient::connect(&ipc_name).await.expect("connect");
+    let status = client.slack_status().await.expect_err("no slack");
+    let reconnect = client.slack_reconnect().await.expect_err("no slack");
+    ct.cancel();
+
+    assert!(
+        status.to_string().contains("slack is not configured"),
+        "{status}"
+    );
+    assert!(
+        reconnect.to_string().contains("slack is not configured"),
+        "{reconnect}"
+    );
+}
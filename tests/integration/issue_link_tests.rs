@@ -0,0 +1,159 @@
+//! Integration tests for issue-linked sessions (`session-start --issue`).
+//!
+//! Runs a local HTTP stub in place of the GitHub and Jira APIs and checks
+//! that ending a linked session posts exactly one completion comment to the
+//! right endpoint with the right credentials.
+
+use std::sync::Arc;
+
+use agent_intercom::config::{IssueProvider, IssuesConfig};
+use agent_intercom::integrations::issues::report_completion;
+use agent_intercom::models::progress::{ProgressItem, ProgressStatus};
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use axum::extract::{OriginalUri, State};
+use axum::http::HeaderMap;
+use axum::routing::post;
+use axum::{Json, Router};
+use serde_json::Value;
+use tokio::sync::Mutex;
+
+/// One request captured by the tracker stub: path, `Authorization` header, body.
+type Captured = Arc<Mutex<Vec<(String, String, Value)>>>;
+
+/// Start a stub tracker API and return its base URL and captured requests.
+async fn start_tracker_stub() -> (String, Captured) {
+    let captured: Captured = Arc::default();
+    let app = Router::new()
+        .route(
+            "/{*path}",
+            post(
+                |State(log): State<Captured>,
+                 OriginalUri(uri): OriginalUri,
+                 headers: HeaderMap,
+                 Json(body): Json<Value>| async move {
+                    let auth = headers
+                        .get("authorization")
+                        .and_then(|v| v.to_str().ok())
+                        .unwrap_or_default()
+                        .to_owned();
+                    log.lock().await.push((uri.path().to_owned(), auth, body));
+                    Json(serde_json::json!({ "id": 1 }))
+                },
+            ),
+        )
+        .with_state(Arc::clone(&captured));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind stub");
+    let addr = listener.local_addr().expect("stub addr");
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    (format!("http://{addr}"), captured)
+}
+
+fn ended_session(issue_ref: Option<&str>) -> Session {
+    let mut session = Session::new(
+        "U_OWNER".into(),
+        "/workspace".into(),
+        Some("fix the flaky test".into()),
+        SessionMode::Remote,
+    );
+    session.title = Some("fix the flaky test".into());
+    session.status = SessionStatus::Terminated;
+    session.terminated_at = Some(session.created_at + chrono::Duration::minutes(65));
+    session.last_tool = Some("check_clearance".into());
+    session.progress_snapshot = Some(vec![ProgressItem {
+        label: "reproduce".into(),
+        status: ProgressStatus::Done,
+    }]);
+    session.issue_ref = issue_ref.map(str::to_owned);
+    session
+}
+
+#[tokio::test]
+async fn github_completion_comment_is_posted_to_the_issue() {
+    let (base_url, captured) = start_tracker_stub().await;
+    let config = IssuesConfig {
+        provider: Some(IssueProvider::Github),
+        base_url: Some(base_url),
+        token: "ghp_test".into(),
+        ..IssuesConfig::default()
+    };
+
+    let posted = report_completion(
+        &config,
+        &ended_session(Some("https://github.com/acme/api/issues/42")),
+        "stopped by operator",
+    )
+    .await
+    .expect("comment posted");
+    assert!(posted);
+
+    let requests = captured.lock().await;
+    assert_eq!(requests.len(), 1);
+    let (path, auth, body) = &requests[0];
+    assert_eq!(path, "/repos/acme/api/issues/42/comments");
+    assert_eq!(auth, "Bearer ghp_test");
+    let text = body["body"].as_str().expect("comment body");
+    assert!(text.contains("stopped by operator"), "{text}");
+    assert!(text.contains("1h 5m"), "{text}");
+    assert!(text.contains("[x] reproduce"), "{text}");
+}
+
+#[tokio::test]
+async fn jira_completion_comment_uses_basic_auth_when_user_is_set() {
+    let (base_url, captured) = start_tracker_stub().await;
+    let config = IssuesConfig {
+        provider: Some(IssueProvider::Jira),
+        base_url: Some(base_url),
+        token: "secret".into(),
+        user: "bot@acme.test".into(),
+        ..IssuesConfig::default()
+    };
+
+    report_completion(&config, &ended_session(Some("PROJ-123")), "done")
+        .await
+        .expect("comment posted");
+
+    let requests = captured.lock().await;
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].0, "/rest/api/2/issue/PROJ-123/comment");
+    assert!(requests[0].1.starts_with("Basic "), "{}", requests[0].1);
+}
+
+#[tokio::test]
+async fn unlinked_session_or_missing_provider_posts_nothing() {
+    let (base_url, captured) = start_tracker_stub().await;
+    let mut config = IssuesConfig {
+        provider: Some(IssueProvider::Github),
+        base_url: Some(base_url),
+        ..IssuesConfig::default()
+    };
+
+    let posted = report_completion(&config, &ended_session(None), "done")
+        .await
+        .expect("no-op");
+    assert!(!posted);
+
+    config.provider = None;
+    let posted = report_completion(&config, &ended_session(Some("PROJ-1")), "done")
+        .await
+        .expect("no-op");
+    assert!(!posted);
+    assert!(captured.lock().await.is_empty());
+}
+
+#[tokio::test]
+async fn provider_mismatch_is_reported_without_posting() {
+    let (base_url, captured) = start_tracker_stub().await;
+    let config = IssuesConfig {
+        provider: Some(IssueProvider::Github),
+        base_url: Some(base_url),
+        ..IssuesConfig::default()
+    };
+
+    let result = report_completion(&config, &ended_session(Some("PROJ-9")), "done").await;
+    assert!(result.is_err(), "a Jira key cannot go to GitHub");
+    assert!(captured.lock().await.is_empty());
+}
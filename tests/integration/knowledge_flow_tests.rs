@@ -0,0 +1,186 @@
+//! Integration tests for the operator-curated knowledge base.
+//!
+//! Tests cover:
+//! - An approved note is served by `intercom://knowledge`; pending and
+//!   rejected notes are not
+//! - Unauthorized users cannot decide notes
+//! - `propose_knowledge` refuses when the knowledge base is disabled or
+//!   Slack is unavailable, and rejects an empty title
+
+use std::sync::Arc;
+
+use serde_json::json;
+use slack_morphism::prelude::{SlackActionId, SlackActionType, SlackInteractionActionInfoInit};
+
+use agent_intercom::models::knowledge::{KnowledgeNote, KnowledgeStatus};
+use agent_intercom::persistence::knowledge_repo::KnowledgeRepo;
+use agent_intercom::slack::handlers::knowledge::handle_knowledge_action;
+
+use super::test_helpers::{connect_mcp_client, test_app_state, test_config};
+
+const OPERATOR: &str = "U_OPERATOR";
+
+fn make_action(
+    action_id: &str,
+    value: &str,
+) -> slack_morphism::prelude::SlackInteractionActionInfo {
+    slack_morphism::prelude::SlackInteractionActionInfo::from(SlackInteractionActionInfoInit {
+        action_type: SlackActionType("button".into()),
+        action_id: SlackActionId(action_id.into()),
+    })
+    .with_value(value.into())
+}
+
+fn pending_note(title: &str) -> KnowledgeNote {
+    KnowledgeNote::new(
+        title.into(),
+        "Start the stub server, then `cargo test --test e2e`.".into(),
+        vec!["testing".into()],
+        "s1".into(),
+        "/repo".into(),
+    )
+}
+
+#[tokio::test]
+async fn approved_notes_are_served_by_the_resource() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let mut config = test_config(root);
+    config.knowledge.enabled = true;
+    config.authorized_user_ids = vec![OPERATOR.into()];
+    let state = test_app_state(config).await;
+
+    let repo = KnowledgeRepo::new(Arc::clone(&state.db));
+    let approved = pending_note("How to run the e2e suite");
+    let rejected = pending_note("Skip the flaky tests");
+    let pending = pending_note("Still undecided");
+    for note in [&approved, &rejected, &pending] {
+        repo.insert(note).await.expect("insert");
+    }
+
+    handle_knowledge_action(
+        &make_action("knowledge_approve", &approved.id),
+        OPERATOR,
+        None,
+        None,
+        &state,
+    )
+    .await
+    .expect("approve");
+    handle_knowledge_action(
+        &make_action("knowledge_reject", &rejected.id),
+        OPERATOR,
+        None,
+        None,
+        &state,
+    )
+    .await
+    .expect("reject");
+    // A late click does not overturn the first decision.
+    handle_knowledge_action(
+        &make_action("knowledge_approve", &rejected.id),
+        OPERATOR,
+        None,
+        None,
+        &state,
+    )
+    .await
+    .expect("late click");
+    let stored = repo.get(&rejected.id).await.expect("get").expect("note");
+    assert_eq!(stored.status, KnowledgeStatus::Rejected);
+
+    let (mut client, _session_id) = connect_mcp_client(&state).await;
+    let listed = client.request(2, "resources/list", json!({})).await;
+    let uris: Vec<&str> = listed["result"]["resources"]
+        .as_array()
+        .expect("resources")
+        .iter()
+        .filter_map(|r| r["uri"].as_str())
+        .collect();
+    assert!(uris.contains(&"intercom://knowledge"), "{listed}");
+
+    let read = client
+        .request(
+            3,
+            "resources/read",
+            json!({ "uri": "intercom://knowledge" }),
+        )
+        .await;
+    let text = read["result"]["contents"][0]["text"]
+        .as_str()
+        .unwrap_or_else(|| panic!("resource text expected: {read}"));
+    let body: serde_json::Value = serde_json::from_str(text).expect("json");
+    let notes = body["notes"].as_array().expect("notes");
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0]["title"], "How to run the e2e suite");
+    assert_eq!(notes[0]["approved_by"], OPERATOR);
+}
+
+#[tokio::test]
+async fn unauthorized_user_cannot_decide() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let mut config = test_config(root);
+    config.knowledge.enabled = true;
+    config.authorized_user_ids = vec![OPERATOR.into()];
+    let state = test_app_state(config).await;
+
+    let repo = KnowledgeRepo::new(Arc::clone(&state.db));
+    let note = pending_note("note");
+    repo.insert(&note).await.expect("insert");
+
+    let result = handle_knowledge_action(
+        &make_action("knowledge_approve", &note.id),
+        "U_STRANGER",
+        None,
+        None,
+        &state,
+    )
+    .await;
+    assert!(result.is_err());
+    let stored = repo.get(&note.id).await.expect("get").expect("note");
+    assert_eq!(stored.status, KnowledgeStatus::Pending);
+}
+
+#[tokio::test]
+async fn propose_knowledge_refusals() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+
+    let state = test_app_state(test_config(root)).await;
+    let (mut client, _session_id) = connect_mcp_client(&state).await;
+    let disabled = client
+        .call_tool(2, "propose_knowledge", json!({ "title": "t", "body": "b" }))
+        .await;
+    assert_eq!(disabled["error_code"], "knowledge_disabled");
+    let read = client
+        .request(
+            3,
+            "resources/read",
+            json!({ "uri": "intercom://knowledge" }),
+        )
+        .await;
+    assert!(read["error"].is_object(), "{read}");
+
+    let mut config = test_config(root);
+    config.knowledge.enabled = true;
+    let state = test_app_state(config).await;
+    let (mut client, _session_id) = connect_mcp_client(&state).await;
+    let no_slack = client
+        .call_tool(
+            2,
+            "propose_knowledge",
+            json!({ "title": "t", "body": "b", "tags": ["ci"] }),
+        )
+        .await;
+    assert_eq!(no_slack["error_code"], "slack_unavailable");
+
+    let empty_title = client
+        .request(
+            3,
+            "tools/call",
+            json!({ "name": "propose_knowledge", "arguments": { "title": " ", "body": "b" } }),
+        )
+        .await;
+    assert!(empty_title["error"].is_object(), "{empty_title}");
+}
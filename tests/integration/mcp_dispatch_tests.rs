@@ -5,7 +5,7 @@
 //! - S003: `recover_state` tool call dispatched via HTTP transport
 //! - S006: Unknown tool name returns MCP error response
 //! - S007: Malformed arguments return descriptive MCP error
-//! - S010: `tools/list` returns exactly 9 registered tools
+//! - S010: `tools/list` returns exactly 20 registered tools
 //!
 //! Uses the rmcp 0.13 Streamable HTTP protocol: POST to `/mcp` for every
 //! request, with the `Mcp-Session-Id` header on subsequent requests.
@@ -69,6 +69,27 @@ async fn spawn_test_server() -> (String, CancellationToken) {
         workspace_mappings: Arc::default(),
         acp_event_tx: None,
         acp_driver: None,
+        driver_registry: Arc::default(),
+        event_bus: Arc::default(),
+        session_hooks: Arc::default(),
+        pause_requests: Arc::default(),
+        heartbeats: Arc::default(),
+        instruction_queue: Arc::default(),
+        snoozes: Arc::default(),
+        apply_confirmations: Arc::default(),
+        session_logs: Arc::default(),
+        session_channels: Arc::default(),
+        workspace_discovery: Arc::default(),
+        config_path: None,
+        command_aliases: Arc::default(),
+        proxy: Arc::default(),
+        replay_guard: Arc::default(),
+        interactions: Arc::default(),
+        rearmed: Arc::default(),
+        diff_staging: Arc::default(),
+        subsystems: Arc::default(),
+        simulator: None,
+        log_filter: None,
     });
 
     let server_ct = ct.clone();
@@ -276,9 +297,9 @@ impl McpConnection {
     }
 }
 
-// ── S010: tools/list returns 9 tools ─────────────────────────
+// ── S010: tools/list returns 20 tools ────────────────────────
 
-/// S010 — Verify the MCP transport serves exactly 9 registered tools.
+/// S010 — Verify the MCP transport serves exactly 20 registered tools.
 ///
 /// `list_tools()` does not require an active session, making this the
 /// simplest transport-level smoke test for the tool router.
@@ -293,8 +314,8 @@ async fn transport_list_tools_returns_nine_tools() {
 
     assert_eq!(
         tools.len(),
-        9,
-        "expected exactly 9 registered tools; got {tools:?}"
+        20,
+        "expected exactly 20 registered tools; got {tools:?}"
     );
 
     ct.cancel();
@@ -412,11 +433,15 @@ async fn transport_malformed_args_returns_error() {
 
     ct.cancel();
 }
-// ── T033: tools/list returns 9 new intercom-themed names ─────
-
-/// T033 — Verify `tools/list` returns exactly 9 tools with the new
-/// intercom-themed names (`check_clearance`, `check_diff`, `auto_check`,
-/// `transmit`, `standby`, `ping`, `broadcast`, `reboot`, `switch_freq`).
+// ── T033: tools/list returns the intercom-themed names ───────
+
+/// T033 — Verify `tools/list` returns exactly the nine intercom-themed
+/// tools (`check_clearance`, `check_diff`, `auto_check`, `transmit`,
+/// `standby`, `ping`, `broadcast`, `reboot`, `switch_freq`) plus the
+/// `relay_send` / `relay_receive` pair, `spawn_subtask`, `stream_log` and
+/// the `store_context` / `get_context` pair, `propose_knowledge`,
+/// `approval_stats`, the `acquire_lock` / `release_lock` pair and
+/// `checkpoint`.
 #[tokio::test]
 async fn transport_list_tools_uses_new_intercom_names() {
     let (base_url, ct) = spawn_test_server().await;
@@ -436,6 +461,17 @@ async fn transport_list_tools_uses_new_intercom_names() {
         "broadcast",
         "reboot",
         "switch_freq",
+        "relay_send",
+        "relay_receive",
+        "spawn_subtask",
+        "stream_log",
+        "store_context",
+        "get_context",
+        "propose_knowledge",
+        "approval_stats",
+        "acquire_lock",
+        "release_lock",
+        "checkpoint",
     ]
     .iter()
     .copied()
@@ -446,7 +482,7 @@ async fn transport_list_tools_uses_new_intercom_names() {
 
     assert_eq!(
         actual_names, expected_names,
-        "tools/list should return exactly the 9 intercom-themed names; got {actual_names:?}"
+        "tools/list should return exactly the 20 registered names; got {actual_names:?}"
     );
 
     ct.cancel();
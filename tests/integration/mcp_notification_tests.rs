@@ -0,0 +1,128 @@
+//! Integration tests for server-initiated MCP notifications.
+//!
+//! Serves an `IntercomServer` over an in-memory duplex transport and drives
+//! the client side with raw newline-delimited JSON-RPC, then checks that:
+//!
+//! - `send_prompt` on an MCP session emits `intercom/nudge`
+//! - `interrupt` on an MCP session emits `intercom/interrupt`
+//! - steering stored from Slack emits `intercom/steering` and stays queued
+//! - `intercom/subscribe` registers session hooks delivered as `intercom/event`
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use agent_intercom::driver::session_hooks::{SessionHookEvent, SessionHookKind};
+use agent_intercom::models::session::SessionMode;
+use agent_intercom::persistence::steering_repo::SteeringRepo;
+use agent_intercom::slack::handlers::steer;
+use serde_json::json;
+
+use super::test_helpers::{connect_mcp_client, test_app_state, test_config};
+
+#[tokio::test]
+async fn send_prompt_emits_intercom_nudge() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(test_config(temp.path().to_str().expect("utf8"))).await;
+    let (mut client, session_id) = connect_mcp_client(&state).await;
+
+    state
+        .driver_for(&session_id)
+        .await
+        .send_prompt(&session_id, "keep going")
+        .await
+        .expect("nudge delivered");
+
+    let msg = client.recv().await;
+    assert_eq!(msg["method"], "intercom/nudge");
+    assert_eq!(msg["params"]["session_id"], session_id.as_str());
+    assert_eq!(msg["params"]["message"], "keep going");
+}
+
+#[tokio::test]
+async fn interrupt_emits_intercom_interrupt() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(test_config(temp.path().to_str().expect("utf8"))).await;
+    let (mut client, session_id) = connect_mcp_client(&state).await;
+
+    state
+        .driver_for(&session_id)
+        .await
+        .interrupt(&session_id)
+        .await
+        .expect("interrupt delivered");
+
+    let msg = client.recv().await;
+    assert_eq!(msg["method"], "intercom/interrupt");
+    assert_eq!(msg["params"]["session_id"], session_id.as_str());
+}
+
+#[tokio::test]
+async fn steering_is_pushed_and_remains_queued() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(test_config(temp.path().to_str().expect("utf8"))).await;
+    let (mut client, session_id) = connect_mcp_client(&state).await;
+
+    let reply = steer::store_from_slack("focus on the parser", None, None, &state)
+        .await
+        .expect("steering stored");
+    assert!(reply.contains("sent to the connected agent"), "{reply}");
+
+    let msg = client.recv().await;
+    assert_eq!(msg["method"], "intercom/steering");
+    assert_eq!(msg["params"]["session_id"], session_id.as_str());
+    assert_eq!(msg["params"]["message"], "focus on the parser");
+    assert_eq!(msg["params"]["source"], "slack");
+
+    let queued = SteeringRepo::new(Arc::clone(&state.db))
+        .fetch_unconsumed(&session_id)
+        .await
+        .expect("fetch");
+    assert_eq!(
+        queued.len(),
+        1,
+        "ping stays the authoritative delivery path"
+    );
+    assert_eq!(msg["params"]["message_id"], queued[0].id.as_str());
+}
+
+#[tokio::test]
+async fn subscribe_notification_enables_session_hooks() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(test_config(temp.path().to_str().expect("utf8"))).await;
+    let (mut client, session_id) = connect_mcp_client(&state).await;
+
+    client
+        .send(json!({
+            "jsonrpc": "2.0",
+            "method": "intercom/subscribe",
+            "params": { "events": ["mode_changed"] }
+        }))
+        .await;
+    for _ in 0..100 {
+        if state
+            .session_hooks
+            .is_subscribed(&session_id, SessionHookKind::ModeChanged)
+        {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert!(!state
+        .session_hooks
+        .is_subscribed(&session_id, SessionHookKind::CheckpointCreated));
+
+    state
+        .notify_session_hook(
+            &session_id,
+            SessionHookEvent::ModeChanged {
+                previous_mode: SessionMode::Local,
+                current_mode: SessionMode::Hybrid,
+            },
+        )
+        .await;
+
+    let msg = client.recv().await;
+    assert_eq!(msg["method"], "intercom/event");
+    assert_eq!(msg["params"]["kind"], "mode_changed");
+    assert_eq!(msg["params"]["current_mode"], "hybrid");
+}
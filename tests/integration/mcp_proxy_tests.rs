@@ -0,0 +1,354 @@
+//! Integration tests for MCP proxy mode (`[[workspace.proxy]]`).
+//!
+//! An in-process stub MCP server stands in for the downstream server; an
+//! agent connects to agent-intercom over an in-memory transport and uses
+//! the re-exported tools.
+//!
+//! Tests cover:
+//! - Selected downstream tools listed as `<server>__<tool>`
+//! - Calls auto-approved by workspace policy are forwarded
+//! - Calls needing approval are refused without Slack, never forwarded
+//! - Calls denied by the policy's `proxy` rules are refused
+//! - The general `tools` list never forwards a proxied call
+//! - A server that is slow to start does not hold up the others
+//! - Unselected tools and connections without a workspace see nothing
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use agent_intercom::config::{ProxyServerConfig, WorkspaceMapping};
+use agent_intercom::mcp::proxy::Downstream;
+use agent_intercom::state::AppState;
+use rmcp::handler::server::ServerHandler;
+use rmcp::model::{
+    CallToolRequestParam, CallToolResult, Content, ListToolsResult, PaginatedRequestParam,
+    ServerCapabilities, ServerInfo, Tool,
+};
+use rmcp::service::{RequestContext, RoleServer};
+use rmcp::ServiceExt;
+use serde_json::json;
+
+use super::test_helpers::{connect_mcp_client_on, test_app_state, test_config};
+
+const CHANNEL: &str = "C_PROXY";
+
+/// Downstream stub exposing `echo`, `shout` and `hidden`.
+#[derive(Clone, Default)]
+struct StubServer {
+    calls: Arc<AtomicUsize>,
+}
+
+impl ServerHandler for StubServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            ..Default::default()
+        }
+    }
+
+    fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> impl Future<Output = Result<ListToolsResult, rmcp::ErrorData>> + Send + '_ {
+        let schema = Arc::new(
+            json!({ "type": "object" })
+                .as_object()
+                .cloned()
+                .expect("object schema"),
+        );
+        let tools = ["echo", "shout", "hidden"]
+            .into_iter()
+            .map(|name| Tool::new(name, format!("stub {name}"), Arc::clone(&schema)))
+            .collect();
+        std::future::ready(Ok(ListToolsResult::with_all_items(tools)))
+    }
+
+    fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> impl Future<Output = Result<CallToolResult, rmcp::ErrorData>> + Send + '_ {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        let text = request
+            .arguments
+            .and_then(|args| args.get("text").and_then(|t| t.as_str()).map(str::to_owned))
+            .unwrap_or_default();
+        let reply = if request.name == "shout" {
+            text.to_uppercase()
+        } else {
+            text
+        };
+        std::future::ready(Ok(CallToolResult::success(vec![Content::text(reply)])))
+    }
+}
+
+fn proxy_config() -> ProxyServerConfig {
+    ProxyServerConfig {
+        name: "stub".into(),
+        command: "stub-mcp-server".into(),
+        args: Vec::new(),
+        env: HashMap::new(),
+        tools: vec!["echo".into(), "shout".into()],
+    }
+}
+
+/// State whose workspace proxies the stub, with `policy` as the workspace
+/// auto-approve settings.
+async fn proxied_state(
+    root: &std::path::Path,
+    policy: serde_json::Value,
+) -> (Arc<AppState>, StubServer) {
+    std::fs::create_dir_all(root.join(".intercom")).expect("policy dir");
+    std::fs::write(root.join(".intercom/settings.json"), policy.to_string()).expect("policy");
+
+    let state = test_app_state(test_config(root.to_str().expect("utf8 path"))).await;
+    state
+        .workspace_mappings
+        .write()
+        .expect("mappings lock")
+        .push(WorkspaceMapping {
+            workspace_id: "proxied".into(),
+            channel_id: CHANNEL.into(),
+            label: None,
+            path: Some(root.to_path_buf()),
+            proxy: vec![proxy_config()],
+            approval_channels: Vec::new(),
+            working_hours: None,
+            bootstrap: None,
+        });
+
+    let stub = StubServer::default();
+    let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+    let server = stub.clone();
+    tokio::spawn(async move {
+        if let Ok(service) = server.serve(server_io).await {
+            let _ = service.waiting().await;
+        }
+    });
+    let downstream = Downstream::connect(proxy_config(), client_io)
+        .await
+        .expect("downstream connects");
+    state.proxy.register("proxied", downstream).await;
+    (state, stub)
+}
+
+fn tool_names(response: &serde_json::Value) -> Vec<String> {
+    response["result"]["tools"]
+        .as_array()
+        .expect("tools array")
+        .iter()
+        .filter_map(|t| t["name"].as_str().map(str::to_owned))
+        .collect()
+}
+
+#[tokio::test]
+async fn selected_downstream_tools_are_reexported() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let (state, _stub) = proxied_state(temp.path(), json!({})).await;
+    let (mut client, _session) = connect_mcp_client_on(&state, Some(CHANNEL)).await;
+
+    let names = tool_names(&client.request(2, "tools/list", json!({})).await);
+    assert!(names.contains(&"stub__echo".to_owned()), "{names:?}");
+    assert!(names.contains(&"stub__shout".to_owned()), "{names:?}");
+    assert!(!names.iter().any(|n| n == "stub__hidden"), "{names:?}");
+    assert!(names.contains(&"check_clearance".to_owned()), "{names:?}");
+}
+
+#[tokio::test]
+async fn policy_approved_call_is_forwarded() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let (state, stub) = proxied_state(
+        temp.path(),
+        json!({ "enabled": true, "proxy": { "auto_approve": ["stub.shout"] } }),
+    )
+    .await;
+    let (mut client, _session) = connect_mcp_client_on(&state, Some(CHANNEL)).await;
+
+    let response = client
+        .request(
+            2,
+            "tools/call",
+            json!({ "name": "stub__shout", "arguments": { "text": "hello" } }),
+        )
+        .await;
+    assert_eq!(
+        response["result"]["content"][0]["text"], "HELLO",
+        "{response}"
+    );
+    assert_eq!(stub.calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn unapproved_call_without_slack_is_refused() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let (state, stub) = proxied_state(
+        temp.path(),
+        json!({ "enabled": true, "proxy": { "auto_approve": ["stub.shout"] } }),
+    )
+    .await;
+    let (mut client, _session) = connect_mcp_client_on(&state, Some(CHANNEL)).await;
+
+    let response = client
+        .request(
+            2,
+            "tools/call",
+            json!({ "name": "stub__echo", "arguments": { "text": "hello" } }),
+        )
+        .await;
+    assert_eq!(response["result"]["isError"], true, "{response}");
+    let text = response["result"]["content"][0]["text"]
+        .as_str()
+        .expect("refusal text");
+    assert!(
+        text.contains("`stub.echo` requires operator approval"),
+        "{text}"
+    );
+    assert_eq!(stub.calls.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn policy_denied_call_is_refused() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let (state, stub) = proxied_state(
+        temp.path(),
+        json!({ "enabled": true, "proxy": { "auto_approve": ["stub.*"], "deny": ["stub.shout"] } }),
+    )
+    .await;
+    let (mut client, _session) = connect_mcp_client_on(&state, Some(CHANNEL)).await;
+
+    let response = client
+        .request(
+            2,
+            "tools/call",
+            json!({ "name": "stub__shout", "arguments": { "text": "hello" } }),
+        )
+        .await;
+    assert_eq!(response["result"]["isError"], true, "{response}");
+    let text = response["result"]["content"][0]["text"]
+        .as_str()
+        .expect("refusal text");
+    assert!(
+        text.contains("`stub.shout` is denied by workspace policy (proxy:deny:stub.shout)"),
+        "{text}"
+    );
+
+    let response = client
+        .request(
+            3,
+            "tools/call",
+            json!({ "name": "stub__echo", "arguments": { "text": "hello" } }),
+        )
+        .await;
+    assert_eq!(
+        response["result"]["content"][0]["text"], "hello",
+        "{response}"
+    );
+    assert_eq!(stub.calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn general_tool_rules_do_not_forward_proxied_calls() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let (state, stub) = proxied_state(
+        temp.path(),
+        json!({ "enabled": true, "tools": ["stub.shout"] }),
+    )
+    .await;
+    let (mut client, _session) = connect_mcp_client_on(&state, Some(CHANNEL)).await;
+
+    let response = client
+        .request(
+            2,
+            "tools/call",
+            json!({ "name": "stub__shout", "arguments": { "text": "hello" } }),
+        )
+        .await;
+    assert_eq!(response["result"]["isError"], true, "{response}");
+    assert_eq!(stub.calls.load(Ordering::SeqCst), 0);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn slow_server_start_does_not_block_other_servers() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let (state, _stub) = proxied_state(temp.path(), json!({})).await;
+    // A server that never answers the MCP handshake.
+    let slow = ProxyServerConfig {
+        name: "slow".into(),
+        command: "sleep".into(),
+        args: vec!["30".into()],
+        env: HashMap::new(),
+        tools: vec!["*".into()],
+    };
+    let workspace = {
+        let mut mappings = state.workspace_mappings.write().expect("mappings lock");
+        let mapping = mappings
+            .iter_mut()
+            .find(|m| m.workspace_id == "proxied")
+            .expect("proxied workspace");
+        mapping.proxy.push(slow);
+        mapping.clone()
+    };
+    let root = temp.path().to_path_buf();
+
+    let starting = {
+        let state = Arc::clone(&state);
+        let workspace = workspace.clone();
+        let root = root.clone();
+        tokio::spawn(async move {
+            let _ = state
+                .proxy
+                .resolve(&workspace, &root, "slow__anything")
+                .await;
+        })
+    };
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    let resolved = tokio::time::timeout(
+        std::time::Duration::from_secs(2),
+        state.proxy.resolve(&workspace, &root, "stub__echo"),
+    )
+    .await
+    .expect("stub resolves while slow is starting");
+    assert!(resolved.is_ok());
+    starting.abort();
+}
+
+#[tokio::test]
+async fn unselected_tool_is_unknown() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let (state, stub) = proxied_state(
+        temp.path(),
+        json!({ "enabled": true, "proxy": { "auto_approve": ["stub.hidden"] } }),
+    )
+    .await;
+    let (mut client, _session) = connect_mcp_client_on(&state, Some(CHANNEL)).await;
+
+    let response = client
+        .request(
+            2,
+            "tools/call",
+            json!({ "name": "stub__hidden", "arguments": {} }),
+        )
+        .await;
+    assert!(
+        response["error"]["message"]
+            .as_str()
+            .is_some_and(|m| m.contains("unknown tool 'stub__hidden'")),
+        "{response}"
+    );
+    assert_eq!(stub.calls.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn connection_without_workspace_sees_no_proxied_tools() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let (state, _stub) = proxied_state(temp.path(), json!({})).await;
+    let (mut client, _session) = connect_mcp_client_on(&state, None).await;
+
+    let names = tool_names(&client.request(2, "tools/list", json!({})).await);
+    assert!(!names.iter().any(|n| n.starts_with("stub__")), "{names:?}");
+}
@@ -0,0 +1,48 @@
+//! Integration tests for pending modal context persistence.
+//!
+//! Covers:
+//! - Coordinates remembered before a restart are found by a new `AppState`
+//!   on the same database, and taken only once
+//! - Unknown callback IDs yield nothing
+
+use std::sync::Arc;
+
+use agent_intercom::slack::modal_context;
+
+use super::test_helpers::{test_app_state, test_app_state_with_db, test_config};
+
+#[tokio::test]
+async fn modal_context_survives_restart_and_is_taken_once() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let root = root.path().to_str().expect("utf8 path");
+    let before = test_app_state(test_config(root)).await;
+    modal_context::remember(
+        &before,
+        "prompt_refine:p-1",
+        "C_TEST".into(),
+        "1700000000.000100".into(),
+    )
+    .await;
+
+    // A restarted server starts with an empty in-memory map.
+    let after = test_app_state_with_db(test_config(root), Arc::clone(&before.db));
+    assert!(after.pending_modal_contexts.lock().await.is_empty());
+
+    let coords = modal_context::take(&after, "prompt_refine:p-1").await;
+    assert_eq!(
+        coords,
+        Some(("C_TEST".to_owned(), "1700000000.000100".to_owned()))
+    );
+    assert_eq!(modal_context::take(&after, "prompt_refine:p-1").await, None);
+}
+
+#[tokio::test]
+async fn unknown_modal_context_is_none() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let root = root.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+    assert_eq!(
+        modal_context::take(&state, "wait_instruct:nope").await,
+        None
+    );
+}
@@ -0,0 +1,133 @@
+//! Integration tests for the `acquire_lock` / `release_lock` tool pair.
+//!
+//! Tests cover:
+//! - A directory lock covers its files and blocks other sessions in the
+//!   same workspace, but not sessions in other workspaces
+//! - A queued request is granted once the holder releases
+//! - Paths escaping the workspace are rejected
+//! - Locks of ended sessions no longer block
+
+use agent_intercom::models::session::SessionStatus;
+use agent_intercom::persistence::session_repo::SessionRepo;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::test_helpers::{
+    connect_mcp_client_as, create_active_session, test_app_state, test_config,
+};
+
+#[tokio::test]
+async fn conflicting_locks_queue_until_released() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let other = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(test_config(root)).await;
+    let first = create_active_session(&state.db, root).await;
+    let second = create_active_session(&state.db, root).await;
+    let elsewhere =
+        create_active_session(&state.db, other.path().to_str().expect("utf8 path")).await;
+
+    let mut a = connect_mcp_client_as(&state, &first.id).await;
+    let mut b = connect_mcp_client_as(&state, &second.id).await;
+    let mut c = connect_mcp_client_as(&state, &elsewhere.id).await;
+
+    let held = a
+        .call_tool(
+            2,
+            "acquire_lock",
+            json!({ "path": "src/", "reason": "refactor" }),
+        )
+        .await;
+    assert_eq!(held["status"], "acquired", "{held}");
+    assert_eq!(held["path"], "src");
+    let covered = a
+        .call_tool(3, "acquire_lock", json!({ "path": "src/lib.rs" }))
+        .await;
+    assert_eq!(covered["lock_id"], held["lock_id"], "{covered}");
+
+    let refused = b
+        .call_tool(
+            2,
+            "acquire_lock",
+            json!({ "path": "src/lib.rs", "timeout_seconds": 0 }),
+        )
+        .await;
+    assert_eq!(refused["status"], "timeout", "{refused}");
+    assert_eq!(refused["blocked_by"]["session_id"], first.id.as_str());
+    assert_eq!(refused["blocked_by"]["path"], "src");
+
+    let unrelated = c
+        .call_tool(2, "acquire_lock", json!({ "path": "src/lib.rs" }))
+        .await;
+    assert_eq!(unrelated["status"], "acquired", "{unrelated}");
+
+    let waiter = tokio::spawn(async move {
+        let granted = b
+            .call_tool(
+                3,
+                "acquire_lock",
+                json!({ "path": "./src/../src/main.rs", "timeout_seconds": 5 }),
+            )
+            .await;
+        (b, granted)
+    });
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let released = a.call_tool(4, "release_lock", json!({})).await;
+    assert_eq!(released["released"], json!(["src"]), "{released}");
+
+    let (_b, granted) = waiter.await.expect("waiter");
+    assert_eq!(granted["status"], "acquired", "{granted}");
+    assert_eq!(granted["path"], "src/main.rs");
+
+    let blocked = a
+        .call_tool(
+            5,
+            "acquire_lock",
+            json!({ "path": ".", "timeout_seconds": 0 }),
+        )
+        .await;
+    assert_eq!(blocked["status"], "timeout", "{blocked}");
+    assert_eq!(blocked["blocked_by"]["path"], "src/main.rs");
+
+    SessionRepo::new(Arc::clone(&state.db))
+        .update_status(&second.id, SessionStatus::Terminated)
+        .await
+        .expect("terminate");
+    let swept = a
+        .call_tool(
+            6,
+            "acquire_lock",
+            json!({ "path": ".", "timeout_seconds": 0 }),
+        )
+        .await;
+    assert_eq!(swept["status"], "acquired", "{swept}");
+}
+
+#[tokio::test]
+async fn lock_paths_must_stay_in_the_workspace() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+    let session = create_active_session(&state.db, root).await;
+    let mut client = connect_mcp_client_as(&state, &session.id).await;
+
+    let response = client
+        .request(
+            2,
+            "tools/call",
+            json!({ "name": "acquire_lock", "arguments": { "path": "../outside" } }),
+        )
+        .await;
+    assert!(
+        response["error"]["message"]
+            .as_str()
+            .is_some_and(|m| m.contains("escape")),
+        "{response}"
+    );
+
+    let nothing = client
+        .call_tool(3, "release_lock", json!({ "path": "src" }))
+        .await;
+    assert_eq!(nothing["released"], json!([]), "{nothing}");
+}
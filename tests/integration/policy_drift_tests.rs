@@ -0,0 +1,49 @@
+//! Integration tests for periodic auto-approve drift checks
+//! (`[policy_drift]`).
+//!
+//! Tests cover:
+//! - A drift is reported once, again when it changes, and forgotten once
+//!   the files agree
+
+use std::collections::BTreeMap;
+
+use agent_intercom::orchestrator::policy_drift::{check_drift, reconcile};
+
+use super::test_helpers::{test_app_state, test_config};
+
+#[tokio::test]
+async fn drift_is_reported_once_per_change() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+    let intercom = temp.path().join(".intercom").join("settings.json");
+    let vscode = temp.path().join(".vscode").join("settings.json");
+    std::fs::create_dir_all(intercom.parent().expect("parent")).expect("mkdir");
+    std::fs::create_dir_all(vscode.parent().expect("parent")).expect("mkdir");
+    std::fs::write(
+        &intercom,
+        r#"{ "chat.tools.terminal.autoApprove": { "^cargo test": true } }"#,
+    )
+    .expect("write");
+    std::fs::write(&vscode, "{}").expect("write");
+
+    let mut reported = BTreeMap::new();
+    let fresh = check_drift(&state, &mut reported).await;
+    assert_eq!(fresh.len(), 1);
+    assert_eq!(
+        fresh[0].0.workspace_root,
+        state.config.default_workspace_root()
+    );
+    assert!(check_drift(&state, &mut reported).await.is_empty());
+
+    std::fs::write(
+        &vscode,
+        r#"{ "chat.tools.terminal.autoApprove": { "^npm ci": true } }"#,
+    )
+    .expect("write");
+    assert_eq!(check_drift(&state, &mut reported).await.len(), 1);
+
+    reconcile(temp.path()).expect("reconcile");
+    assert!(check_drift(&state, &mut reported).await.is_empty());
+    assert!(reported.is_empty());
+}
@@ -0,0 +1,102 @@
+//! Integration tests for per-operator notification preferences (`prefs`).
+//!
+//! - the text forms store the operator's detail level, delivery and quiet
+//!   hours, and `prefs show` reports them
+//! - each operator's preferences are kept apart
+//! - invalid values are rejected without changing anything
+//! - `prefs reset` returns the operator to the server defaults
+
+use std::sync::Arc;
+
+use agent_intercom::config::SlackDetailLevel;
+use agent_intercom::models::preferences::NotificationDelivery;
+use agent_intercom::persistence::preferences_repo::PreferencesRepo;
+use agent_intercom::slack::commands::dispatch_command;
+
+use super::test_helpers::{test_app_state, test_config};
+
+const OPERATOR: &str = "U_PREFS";
+const CHANNEL: &str = "C_TEST";
+
+#[tokio::test]
+async fn text_forms_store_and_show_preferences() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(test_config(temp.path().to_str().expect("utf8"))).await;
+
+    for args in [
+        &["detail", "minimal"][..],
+        &["delivery", "both"],
+        &["quiet", "22:00-07:00"],
+    ] {
+        let reply = dispatch_command("prefs", args, OPERATOR, CHANNEL, &state)
+            .await
+            .expect("prefs");
+        assert!(reply.starts_with("Preferences saved."), "{reply}");
+    }
+
+    let stored = PreferencesRepo::new(Arc::clone(&state.db))
+        .get(OPERATOR)
+        .await
+        .expect("get")
+        .expect("stored");
+    assert_eq!(stored.detail_level, Some(SlackDetailLevel::Minimal));
+    assert_eq!(stored.delivery, NotificationDelivery::Both);
+    assert_eq!(
+        stored.quiet_hours.map(|q| q.to_string()).as_deref(),
+        Some("22:00-07:00")
+    );
+
+    let shown = dispatch_command("prefs", &["show"], OPERATOR, CHANNEL, &state)
+        .await
+        .expect("show");
+    assert!(shown.contains("Detail level: minimal"), "{shown}");
+    assert!(shown.contains("22:00-07:00"), "{shown}");
+
+    let other = dispatch_command("prefs", &[], "U_OTHER", CHANNEL, &state)
+        .await
+        .expect("show other");
+    assert!(other.contains("server default"), "{other}");
+    assert!(other.contains("Quiet hours: none"), "{other}");
+}
+
+#[tokio::test]
+async fn invalid_values_change_nothing() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(test_config(temp.path().to_str().expect("utf8"))).await;
+
+    for args in [
+        &["detail", "chatty"][..],
+        &["delivery", "email"],
+        &["quiet", "08:00-08:00"],
+        &["volume", "11"],
+    ] {
+        let result = dispatch_command("prefs", args, OPERATOR, CHANNEL, &state).await;
+        assert!(result.is_err(), "{args:?} accepted");
+    }
+
+    let stored = PreferencesRepo::new(Arc::clone(&state.db))
+        .get(OPERATOR)
+        .await
+        .expect("get");
+    assert!(stored.is_none(), "{stored:?}");
+}
+
+#[tokio::test]
+async fn reset_returns_to_server_defaults() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(test_config(temp.path().to_str().expect("utf8"))).await;
+    dispatch_command("prefs", &["delivery", "dm"], OPERATOR, CHANNEL, &state)
+        .await
+        .expect("delivery");
+
+    let reply = dispatch_command("prefs", &["reset"], OPERATOR, CHANNEL, &state)
+        .await
+        .expect("reset");
+
+    assert!(reply.contains("reset"), "{reply}");
+    let stored = PreferencesRepo::new(Arc::clone(&state.db))
+        .get(OPERATOR)
+        .await
+        .expect("get");
+    assert!(stored.is_none(), "{stored:?}");
+}
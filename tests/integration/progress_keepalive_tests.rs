@@ -0,0 +1,88 @@
+//! Integration tests for progress keepalives on blocking tools.
+//!
+//! - a pending `check_clearance` with a `progressToken` sends increasing
+//!   `notifications/progress` until it is decided
+//! - without a `progressToken` no notifications are sent
+
+use std::sync::Arc;
+
+use serde_json::{json, Value};
+use tokio_util::sync::CancellationToken;
+
+use agent_intercom::orchestrator::simulated_operator::{spawn_simulated_operator, OperatorScript};
+use agent_intercom::state::AppState;
+
+use super::test_helpers::{connect_mcp_client, test_app_state, test_config_no_channel};
+
+/// A state whose simulated operator approves after `delay_ms`, with
+/// keepalives every second.
+async fn slow_operator_state(root: &str, delay_ms: u64) -> (Arc<AppState>, CancellationToken) {
+    let policy = format!("delay_ms = {delay_ms}\n");
+    let script = Arc::new(OperatorScript::from_toml_str(&policy).expect("policy"));
+    let mut config = test_config_no_channel(root);
+    config.timeouts.progress_interval_seconds = 1;
+    let mut state = Arc::try_unwrap(test_app_state(config).await)
+        .unwrap_or_else(|_| panic!("state is not shared yet"));
+    state.simulator = Some(Arc::clone(&script));
+    let state = Arc::new(state);
+    let cancel = CancellationToken::new();
+    let _task = spawn_simulated_operator(Arc::clone(&state), script, cancel.clone());
+    (state, cancel)
+}
+
+fn clearance_call(id: u64, meta: Option<Value>) -> Value {
+    let mut params = json!({
+        "name": "check_clearance",
+        "arguments": { "title": "tweak", "diff": "+x", "file_path": "src/lib.rs" },
+    });
+    if let Some(meta) = meta {
+        params["_meta"] = meta;
+    }
+    json!({ "jsonrpc": "2.0", "id": id, "method": "tools/call", "params": params })
+}
+
+#[tokio::test]
+async fn pending_clearance_sends_progress_keepalives() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let (state, cancel) = slow_operator_state(temp.path().to_str().expect("utf8"), 2500).await;
+    let (mut client, _session_id) = connect_mcp_client(&state).await;
+
+    client
+        .send(clearance_call(
+            2,
+            Some(json!({ "progressToken": "clearance-1" })),
+        ))
+        .await;
+    let mut progress = Vec::new();
+    let response = loop {
+        let msg = client.recv().await;
+        if msg["id"] == 2 {
+            break msg;
+        }
+        if msg["method"] == "notifications/progress" {
+            assert_eq!(msg["params"]["progressToken"], "clearance-1", "{msg}");
+            progress.push(msg["params"]["progress"].as_f64().expect("progress"));
+        }
+    };
+
+    assert!(response["result"].is_object(), "{response}");
+    assert!(progress.len() >= 2, "{progress:?}");
+    assert!(
+        progress.windows(2).all(|pair| pair[0] < pair[1]),
+        "{progress:?}"
+    );
+    cancel.cancel();
+}
+
+#[tokio::test]
+async fn clearance_without_progress_token_sends_no_keepalives() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let (state, cancel) = slow_operator_state(temp.path().to_str().expect("utf8"), 1500).await;
+    let (mut client, _session_id) = connect_mcp_client(&state).await;
+
+    // The decision arrives after the first keepalive would have been sent.
+    client.send(clearance_call(2, None)).await;
+    let response = client.recv().await;
+    assert_eq!(response["id"], 2, "{response}");
+    cancel.cancel();
+}
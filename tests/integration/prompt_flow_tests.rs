@@ -255,3 +255,35 @@ async fn prompt_flow_all_prompt_types_persist() {
         assert_eq!(created.prompt_type, *prompt_type);
     }
 }
+
+#[tokio::test]
+async fn prompt_flow_late_decision_keeps_first_answer() {
+    let _config = test_config();
+    let database = Arc::new(db::connect_memory().await.expect("db connect"));
+    let repo = PromptRepo::new(database);
+
+    let prompt = sample_prompt("session-race");
+    let prompt_id = prompt.id.clone();
+    repo.create(&prompt).await.expect("create");
+
+    assert!(repo
+        .decide(&prompt_id, PromptDecision::Stop, None)
+        .await
+        .expect("stop"));
+    assert!(!repo
+        .decide(
+            &prompt_id,
+            PromptDecision::Refine,
+            Some("too late".to_owned())
+        )
+        .await
+        .expect("late refine"));
+
+    let fetched = repo
+        .get_by_id(&prompt_id)
+        .await
+        .expect("fetch")
+        .expect("prompt should exist");
+    assert_eq!(fetched.decision, Some(PromptDecision::Stop));
+    assert!(fetched.instruction.is_none());
+}
@@ -0,0 +1,150 @@
+//! Integration tests for prompt auto-policies (`[[prompts.auto]]`).
+//!
+//! - a rule with `after_consecutive` answers only once the session's run of
+//!   same-type prompts is long enough, and another type breaks the run
+//! - the decision is stored on the prompt; a `refine` rule returns its
+//!   instruction
+//! - without rules every prompt is left to the operator
+
+use std::sync::Arc;
+
+use agent_intercom::config::PromptAutoRule;
+use agent_intercom::models::prompt::{ContinuationPrompt, PromptDecision, PromptType};
+use agent_intercom::models::session::Session;
+use agent_intercom::orchestrator::prompt_policy::auto_decide;
+use agent_intercom::persistence::prompt_repo::PromptRepo;
+use agent_intercom::state::AppState;
+
+use super::test_helpers::{create_active_session, test_app_state, test_config};
+
+fn rule(prompt_type: PromptType, decision: PromptDecision) -> PromptAutoRule {
+    PromptAutoRule {
+        prompt_type,
+        decision,
+        instruction: None,
+        after_consecutive: 1,
+        hours: None,
+        weekdays_only: false,
+    }
+}
+
+async fn state_with_rules(root: &str, rules: Vec<PromptAutoRule>) -> Arc<AppState> {
+    let mut config = test_config(root);
+    config.prompts.auto = rules;
+    test_app_state(config).await
+}
+
+/// Persist a prompt of `prompt_type` and run the auto-policy on it.
+async fn forward(
+    state: &AppState,
+    session: &Session,
+    prompt_type: PromptType,
+) -> (ContinuationPrompt, Option<(PromptDecision, Option<String>)>) {
+    let prompt = ContinuationPrompt::new(
+        session.id.clone(),
+        "tests keep failing".into(),
+        prompt_type,
+        None,
+        None,
+    );
+    let created = PromptRepo::new(Arc::clone(&state.db))
+        .create(&prompt)
+        .await
+        .expect("create prompt");
+    let outcome = auto_decide(state, session, &created).await;
+    (created, outcome)
+}
+
+#[tokio::test]
+async fn stops_after_consecutive_error_recovery_prompts() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let mut stop = rule(PromptType::ErrorRecovery, PromptDecision::Stop);
+    stop.after_consecutive = 3;
+    let state = state_with_rules(root, vec![stop]).await;
+    let session = create_active_session(&state.db, root).await;
+
+    assert!(forward(&state, &session, PromptType::ErrorRecovery)
+        .await
+        .1
+        .is_none());
+    assert!(forward(&state, &session, PromptType::ErrorRecovery)
+        .await
+        .1
+        .is_none());
+    // A prompt of another type breaks the run.
+    assert!(forward(&state, &session, PromptType::Clarification)
+        .await
+        .1
+        .is_none());
+    assert!(forward(&state, &session, PromptType::ErrorRecovery)
+        .await
+        .1
+        .is_none());
+    assert!(forward(&state, &session, PromptType::ErrorRecovery)
+        .await
+        .1
+        .is_none());
+
+    let (prompt, outcome) = forward(&state, &session, PromptType::ErrorRecovery).await;
+    assert_eq!(outcome, Some((PromptDecision::Stop, None)));
+    let stored = PromptRepo::new(Arc::clone(&state.db))
+        .get_by_id(&prompt.id)
+        .await
+        .expect("query")
+        .expect("prompt exists");
+    assert_eq!(stored.decision, Some(PromptDecision::Stop));
+}
+
+#[tokio::test]
+async fn refine_rule_returns_its_instruction() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let mut refine = rule(PromptType::ResourceWarning, PromptDecision::Refine);
+    refine.instruction = Some("Free disk space and continue".into());
+    let state = state_with_rules(
+        root,
+        vec![
+            refine,
+            rule(PromptType::ResourceWarning, PromptDecision::Stop),
+        ],
+    )
+    .await;
+    let session = create_active_session(&state.db, root).await;
+
+    let (prompt, outcome) = forward(&state, &session, PromptType::ResourceWarning).await;
+    assert_eq!(
+        outcome,
+        Some((
+            PromptDecision::Refine,
+            Some("Free disk space and continue".into())
+        )),
+        "the first matching rule wins"
+    );
+    let stored = PromptRepo::new(Arc::clone(&state.db))
+        .get_by_id(&prompt.id)
+        .await
+        .expect("query")
+        .expect("prompt exists");
+    assert_eq!(
+        stored.instruction.as_deref(),
+        Some("Free disk space and continue")
+    );
+}
+
+#[tokio::test]
+async fn prompts_without_matching_rule_are_left_to_operator() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = state_with_rules(root, Vec::new()).await;
+    let session = create_active_session(&state.db, root).await;
+
+    let (prompt, outcome) = forward(&state, &session, PromptType::Continuation).await;
+    assert!(outcome.is_none());
+    let stored = PromptRepo::new(Arc::clone(&state.db))
+        .get_by_id(&prompt.id)
+        .await
+        .expect("query")
+        .expect("prompt exists");
+    assert_eq!(stored.decision, None);
+}
@@ -0,0 +1,79 @@
+//! Integration tests for `[protected_paths]` in `check_diff`.
+//!
+//! Tests cover:
+//! - A protected file is not written without an operator's confirmation;
+//!   without Slack the call fails and the approval stays usable
+//! - Unprotected files in the same workspace apply as before
+
+use std::path::Path;
+use std::sync::Arc;
+
+use serde_json::json;
+
+use agent_intercom::mcp::tools::util::compute_file_hash;
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::persistence::approval_repo::ApprovalRepo;
+use agent_intercom::persistence::session_repo::SessionRepo;
+
+use super::test_helpers::{connect_mcp_client, test_app_state, test_config};
+
+async fn approved(repo: &ApprovalRepo, session_id: &str, root: &Path, file_path: &str) -> String {
+    let hash = compute_file_hash(&root.join(file_path))
+        .await
+        .expect("hash");
+    let request = ApprovalRequest::new(
+        session_id.into(),
+        "Add file".into(),
+        None,
+        "created\n".into(),
+        file_path.into(),
+        RiskLevel::High,
+        hash,
+    );
+    let created = repo.create(&request).await.expect("create");
+    repo.update_status(&created.id, ApprovalStatus::Approved)
+        .await
+        .expect("approve");
+    created.id
+}
+
+#[tokio::test]
+async fn protected_path_needs_confirmation_others_apply() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let mut config = test_config(temp.path().to_str().expect("utf8"));
+    config.protected_paths.patterns = vec!["migrations/**".into()];
+    let state = test_app_state(config).await;
+    let (mut client, session_id) = connect_mcp_client(&state).await;
+    let session = SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(&session_id)
+        .await
+        .expect("query")
+        .expect("session");
+    let root = Path::new(&session.workspace_root);
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+
+    let protected = approved(&repo, &session_id, root, "migrations/001_init.sql").await;
+    let result = client
+        .call_tool(2, "check_diff", json!({ "request_id": protected }))
+        .await;
+    assert_eq!(result["error_code"], "confirmation_unavailable", "{result}");
+    assert!(!root.join("migrations/001_init.sql").exists());
+    let record = repo
+        .get_by_id(&protected)
+        .await
+        .expect("query")
+        .expect("approval");
+    assert_eq!(record.status, ApprovalStatus::Approved);
+    assert!(!state.apply_confirmations.is_pending(&protected));
+
+    let plain = approved(&repo, &session_id, root, "notes.txt").await;
+    let result = client
+        .call_tool(3, "check_diff", json!({ "request_id": plain }))
+        .await;
+    assert_eq!(result["status"], "applied", "{result}");
+    assert!(result.get("confirmed_by").is_none());
+    assert_eq!(
+        std::fs::read_to_string(root.join("notes.txt")).expect("written"),
+        "created\n"
+    );
+}
@@ -0,0 +1,233 @@
+//! Integration tests for the `relay_send` / `relay_receive` tool pair.
+//!
+//! A producer connects directly (auto-created session); the consumer is a
+//! pre-created session connected as a spawned agent would be.
+//!
+//! Tests cover:
+//! - A message is delivered once, with its payload
+//! - `topic` filters what `relay_receive` collects
+//! - `relay_receive` waits up to `timeout_seconds` for a message
+//! - An oversized `timeout_seconds` is capped rather than overflowing
+//! - The Slack notice is published on the event bus, in the recipient's
+//!   thread
+//! - Self, unknown, finished, prefix-named and foreign recipients are
+//!   rejected
+
+use std::sync::Arc;
+
+use agent_intercom::driver::AgentEvent;
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::persistence::relay_repo::RelayRepo;
+use agent_intercom::persistence::session_repo::SessionRepo;
+use serde_json::json;
+
+use super::test_helpers::{
+    connect_mcp_client, connect_mcp_client_as, create_active_session, test_app_state, test_config,
+};
+
+#[tokio::test]
+async fn relay_message_is_delivered_once() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+    let (mut producer, producer_id) = connect_mcp_client(&state).await;
+    let consumer_id = create_active_session(&state.db, root).await.id;
+
+    let sent = producer
+        .call_tool(
+            2,
+            "relay_send",
+            json!({
+                "to_session_id": consumer_id,
+                "topic": "build",
+                "payload": { "artifact": "target/app", "ok": true }
+            }),
+        )
+        .await;
+    assert_eq!(sent["status"], "sent", "{sent}");
+    assert_eq!(sent["to_session_id"], consumer_id.as_str());
+
+    let mut consumer = connect_mcp_client_as(&state, &consumer_id).await;
+    let skipped = consumer
+        .call_tool(2, "relay_receive", json!({ "topic": "review" }))
+        .await;
+    assert_eq!(skipped["messages"], json!([]), "{skipped}");
+
+    let received = consumer.call_tool(3, "relay_receive", json!({})).await;
+    let messages = received["messages"].as_array().expect("messages array");
+    assert_eq!(messages.len(), 1, "{received}");
+    assert_eq!(messages[0]["message_id"], sent["message_id"]);
+    assert_eq!(messages[0]["from_session_id"], producer_id.as_str());
+    assert_eq!(messages[0]["topic"], "build");
+    assert_eq!(messages[0]["payload"]["artifact"], "target/app");
+
+    let again = consumer.call_tool(4, "relay_receive", json!({})).await;
+    assert_eq!(again["messages"], json!([]), "{again}");
+
+    let history = RelayRepo::new(Arc::clone(&state.db))
+        .list_for_session(&producer_id)
+        .await
+        .expect("list");
+    assert!(history[0].delivered_at.is_some());
+}
+
+#[tokio::test]
+async fn relay_receive_waits_for_a_message() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+    let (mut producer, _producer_id) = connect_mcp_client(&state).await;
+    let consumer_id = create_active_session(&state.db, root).await.id;
+    let mut consumer = connect_mcp_client_as(&state, &consumer_id).await;
+
+    let target = consumer_id.clone();
+    let send = tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+        producer
+            .call_tool(
+                2,
+                "relay_send",
+                json!({ "to_session_id": target, "payload": "done" }),
+            )
+            .await
+    });
+
+    let received = consumer
+        .call_tool(2, "relay_receive", json!({ "timeout_seconds": 1 }))
+        .await;
+    assert_eq!(received["messages"][0]["payload"], "done", "{received}");
+    assert_eq!(send.await.expect("send task")["status"], "sent");
+}
+
+#[tokio::test]
+async fn relay_receive_caps_huge_timeout() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+    let (mut producer, _producer_id) = connect_mcp_client(&state).await;
+    let consumer_id = create_active_session(&state.db, root).await.id;
+    let mut consumer = connect_mcp_client_as(&state, &consumer_id).await;
+
+    let sent = producer
+        .call_tool(
+            2,
+            "relay_send",
+            json!({ "to_session_id": consumer_id, "payload": "ready" }),
+        )
+        .await;
+    assert_eq!(sent["status"], "sent", "{sent}");
+
+    let received = consumer
+        .call_tool(2, "relay_receive", json!({ "timeout_seconds": u64::MAX }))
+        .await;
+    assert_eq!(received["messages"][0]["payload"], "ready", "{received}");
+}
+
+#[tokio::test]
+async fn relay_notice_is_published_on_the_event_bus() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+    let mut events = state.event_bus.subscribe();
+    let (mut producer, _producer_id) = connect_mcp_client(&state).await;
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let mut consumer = Session::new(
+        "U_TEST_OWNER".into(),
+        root.into(),
+        Some("consumer".into()),
+        SessionMode::Remote,
+    );
+    consumer.channel_id = Some("C_RELAY".into());
+    consumer.thread_ts = Some("1700000000.000100".into());
+    let consumer = repo.create(&consumer).await.expect("create consumer");
+    repo.update_status(&consumer.id, SessionStatus::Active)
+        .await
+        .expect("activate consumer");
+
+    let sent = producer
+        .call_tool(
+            2,
+            "relay_send",
+            json!({ "to_session_id": consumer.id, "payload": "hello" }),
+        )
+        .await;
+    assert_eq!(sent["status"], "sent", "{sent}");
+
+    let mut notices = Vec::new();
+    while let Ok(bus_event) = events.try_recv() {
+        if let AgentEvent::NoticePosted {
+            session_id,
+            message,
+        } = bus_event.event
+        {
+            notices.push((session_id, message));
+        }
+    }
+    assert_eq!(notices.len(), 1, "only the recipient has a channel");
+    let (session_id, message) = &notices[0];
+    assert_eq!(session_id, &consumer.id);
+    assert_eq!(message.channel.0, "C_RELAY");
+    assert_eq!(
+        message.thread_ts.as_ref().map(|ts| ts.0.as_str()),
+        Some("1700000000.000100")
+    );
+    assert!(
+        message.text.as_deref().is_some_and(|t| t.contains("Relay")),
+        "{message:?}"
+    );
+}
+
+#[tokio::test]
+async fn relay_send_rejects_invalid_recipients() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+    let (mut producer, producer_id) = connect_mcp_client(&state).await;
+    let finished = create_active_session(&state.db, root).await;
+    SessionRepo::new(Arc::clone(&state.db))
+        .set_terminated(&finished.id, SessionStatus::Terminated)
+        .await
+        .expect("terminate");
+    let sibling = create_active_session(&state.db, root).await;
+    let prefix: String = sibling.id.chars().take(8).collect();
+    let other = tempfile::tempdir().expect("other workspace");
+    let foreign = SessionRepo::new(Arc::clone(&state.db))
+        .create(&Session::new(
+            "U_SOMEONE_ELSE".into(),
+            other.path().to_str().expect("utf8 path").into(),
+            Some("foreign".into()),
+            SessionMode::Remote,
+        ))
+        .await
+        .expect("create foreign session");
+
+    for (id, recipient, expected) in [
+        (2, producer_id.as_str(), "cannot target the calling session"),
+        (3, "no-such-session", "unknown session"),
+        (4, finished.id.as_str(), "is terminated"),
+        (5, prefix.as_str(), "unknown session"),
+        (6, foreign.id.as_str(), "unknown session"),
+    ] {
+        let response = producer
+            .request(
+                id,
+                "tools/call",
+                json!({
+                    "name": "relay_send",
+                    "arguments": { "to_session_id": recipient, "payload": {} }
+                }),
+            )
+            .await;
+        assert!(
+            response["error"]["message"]
+                .as_str()
+                .is_some_and(|m| m.contains(expected)),
+            "{response}"
+        );
+    }
+    assert!(RelayRepo::new(Arc::clone(&state.db))
+        .list_for_session(&producer_id)
+        .await
+        .expect("list")
+        .is_empty());
+}
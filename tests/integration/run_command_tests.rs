@@ -0,0 +1,146 @@
+//! Integration tests for named runs (`run-start`, `run-end`, `run-summary`).
+//!
+//! - `run-start` opens one run per operator; sessions started meanwhile
+//!   join it, and subtasks inherit it
+//! - `run-summary` totals sessions, approvals and files across the run
+//! - `run-end` closes the run and reports the same summary
+
+use std::sync::Arc;
+
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::orchestrator::{runs, spawner};
+use agent_intercom::persistence::approval_repo::ApprovalRepo;
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::slack::commands::dispatch_command;
+use agent_intercom::state::AppState;
+
+use super::test_helpers::{test_app_state, test_config};
+
+const OPERATOR: &str = "U_RUNNER";
+const CHANNEL: &str = "C_TEST";
+
+/// An active session started by [`OPERATOR`], joined to their active run
+/// as `session-start` does.
+async fn start_session(state: &Arc<AppState>, root: &str) -> Session {
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let mut session = Session::new(OPERATOR.into(), root.into(), None, SessionMode::Remote);
+    session.run_id = runs::active_run_id(&state.db, OPERATOR)
+        .await
+        .expect("active run");
+    let created = repo.create(&session).await.expect("create");
+    repo.update_status(&created.id, SessionStatus::Active)
+        .await
+        .expect("activate")
+}
+
+async fn decided_approval(state: &Arc<AppState>, session: &Session, file: &str) {
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let request = ApprovalRequest::new(
+        session.id.clone(),
+        format!("edit {file}"),
+        None,
+        "diff".into(),
+        file.into(),
+        RiskLevel::Low,
+        "hash".into(),
+    );
+    let created = repo.create(&request).await.expect("approval");
+    repo.update_status(&created.id, ApprovalStatus::Approved)
+        .await
+        .expect("approve");
+}
+
+#[tokio::test]
+async fn sessions_started_during_a_run_are_summarised_together() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+
+    let outsider = start_session(&state, root).await;
+    let reply = dispatch_command(
+        "run-start",
+        &["\"release", "hardening\""],
+        OPERATOR,
+        CHANNEL,
+        &state,
+    )
+    .await
+    .expect("run-start");
+    assert!(reply.contains("*release hardening*"), "{reply}");
+    assert!(
+        dispatch_command("run-start", &["again"], OPERATOR, CHANNEL, &state)
+            .await
+            .is_err(),
+        "second active run accepted"
+    );
+
+    let first = start_session(&state, root).await;
+    let second = start_session(&state, root).await;
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let (subtask, _child) = spawner::spawn_subtask(
+        &first,
+        "write tests",
+        Some(CHANNEL),
+        &state.config,
+        &repo,
+        state.config.http_port,
+    )
+    .await
+    .expect("subtask");
+    assert_eq!(subtask.run_id, first.run_id);
+    assert!(first.run_id.is_some());
+    assert_eq!(outsider.run_id, None);
+    decided_approval(&state, &first, "src/lib.rs").await;
+    decided_approval(&state, &second, "src/lib.rs").await;
+    decided_approval(&state, &outsider, "src/main.rs").await;
+
+    let summary = dispatch_command("run-summary", &[], OPERATOR, CHANNEL, &state)
+        .await
+        .expect("run-summary");
+    assert!(
+        summary.contains("*Run \"release hardening\"* — active"),
+        "{summary}"
+    );
+    assert!(summary.contains("3 sessions · 3 running"), "{summary}");
+    assert!(summary.contains("2 approved"), "{summary}");
+    assert!(summary.contains("1 files changed"), "{summary}");
+    assert!(summary.contains(&first.short_id), "{summary}");
+    assert!(!summary.contains(&outsider.short_id), "{summary}");
+
+    let ended = dispatch_command("run-end", &[], OPERATOR, CHANNEL, &state)
+        .await
+        .expect("run-end");
+    assert!(ended.contains("— ended"), "{ended}");
+    assert_eq!(
+        runs::active_run_id(&state.db, OPERATOR)
+            .await
+            .expect("active run"),
+        None
+    );
+    assert!(start_session(&state, root).await.run_id.is_none());
+
+    let by_name = dispatch_command(
+        "run-summary",
+        &["release", "hardening"],
+        "U_OTHER",
+        CHANNEL,
+        &state,
+    )
+    .await
+    .expect("run-summary by name");
+    assert!(by_name.contains("3 sessions"), "{by_name}");
+}
+
+#[tokio::test]
+async fn run_commands_without_a_run_explain_themselves() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(test_config(temp.path().to_str().expect("utf8"))).await;
+
+    let end = dispatch_command("run-end", &[], OPERATOR, CHANNEL, &state).await;
+    assert!(end.is_err());
+    let summary = dispatch_command("run-summary", &[], OPERATOR, CHANNEL, &state).await;
+    assert!(summary.is_err());
+    let blank = dispatch_command("run-start", &["\"\""], OPERATOR, CHANNEL, &state).await;
+    assert!(blank.is_err());
+}
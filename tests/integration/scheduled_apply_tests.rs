@@ -0,0 +1,198 @@
+//! Integration tests for `apply-at` scheduled applies.
+//!
+//! Tests cover:
+//! - `apply-at` approves a pending request and schedules it; `check_diff`
+//!   refuses it until the server applies it
+//! - A due request is applied, verified and reported to the agent
+//! - A file changed before the scheduled time is left alone and the
+//!   request handed back to the agent
+
+use std::path::Path;
+use std::sync::Arc;
+
+use serde_json::json;
+
+use agent_intercom::mcp::tools::util::compute_file_hash;
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::orchestrator::scheduled_apply;
+use agent_intercom::persistence::approval_repo::ApprovalRepo;
+use agent_intercom::persistence::steering_repo::SteeringRepo;
+use agent_intercom::slack::commands::dispatch_command;
+
+use super::test_helpers::{
+    connect_mcp_client_as, create_active_session, test_app_state, test_config,
+};
+
+async fn pending(
+    repo: &ApprovalRepo,
+    session_id: &str,
+    root: &Path,
+    file_path: &str,
+) -> ApprovalRequest {
+    let hash = compute_file_hash(&root.join(file_path))
+        .await
+        .expect("hash");
+    let request = ApprovalRequest::new(
+        session_id.into(),
+        "Add notes".into(),
+        None,
+        "scheduled\n".into(),
+        file_path.into(),
+        RiskLevel::Low,
+        hash,
+    );
+    repo.create(&request).await.expect("create")
+}
+
+#[tokio::test]
+async fn apply_at_schedules_and_sweep_applies_and_verifies() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let mut config = test_config(root);
+    config.scheduled_apply.verify_commands =
+        vec!["exit 0".into(), "exit 3".into(), "exit 0".into()];
+    let state = test_app_state(config).await;
+    let session = create_active_session(&state.db, root).await;
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let approval = pending(&repo, &session.id, temp.path(), "notes.txt").await;
+
+    let reply = dispatch_command(
+        "apply-at",
+        &[approval.short_id.as_str(), "23:59"],
+        "U_TEST_OWNER",
+        "C_TEST",
+        &state,
+    )
+    .await
+    .expect("apply-at");
+    assert!(reply.contains("Approved"), "{reply}");
+
+    let scheduled = repo
+        .get_by_id(&approval.id)
+        .await
+        .expect("query")
+        .expect("approval");
+    assert_eq!(scheduled.status, ApprovalStatus::Approved);
+    let due = scheduled.apply_after.expect("scheduled");
+
+    let mut client = connect_mcp_client_as(&state, &session.id).await;
+    let result = client
+        .call_tool(2, "check_diff", json!({ "request_id": approval.id }))
+        .await;
+    assert_eq!(result["error_code"], "apply_scheduled", "{result}");
+
+    scheduled_apply::apply_due(&state, due - chrono::Duration::seconds(1))
+        .await
+        .expect("early sweep");
+    assert!(!temp.path().join("notes.txt").exists());
+
+    scheduled_apply::apply_due(&state, due)
+        .await
+        .expect("sweep");
+    assert_eq!(
+        std::fs::read_to_string(temp.path().join("notes.txt")).expect("written"),
+        "scheduled\n"
+    );
+    let applied = repo
+        .get_by_id(&approval.id)
+        .await
+        .expect("query")
+        .expect("approval");
+    assert_eq!(applied.status, ApprovalStatus::Consumed);
+
+    let steering = SteeringRepo::new(Arc::clone(&state.db))
+        .fetch_unconsumed(&session.id)
+        .await
+        .expect("steering");
+    let notice = &steering.last().expect("agent notified").message;
+    assert!(notice.contains("has now been applied"), "{notice}");
+    assert!(
+        notice.contains("`exit 0` passed; `exit 3` exit 3"),
+        "{notice}"
+    );
+}
+
+#[tokio::test]
+async fn apply_at_leaves_changed_file_to_the_agent() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+    let session = create_active_session(&state.db, root).await;
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let approval = pending(&repo, &session.id, temp.path(), "notes.txt").await;
+
+    dispatch_command(
+        "apply-at",
+        &[approval.id.as_str(), "06:00"],
+        "U_TEST_OWNER",
+        "C_TEST",
+        &state,
+    )
+    .await
+    .expect("apply-at");
+    std::fs::write(temp.path().join("notes.txt"), "edited meanwhile\n").expect("edit");
+
+    let due = repo
+        .get_by_id(&approval.id)
+        .await
+        .expect("query")
+        .and_then(|a| a.apply_after)
+        .expect("scheduled");
+    scheduled_apply::apply_due(&state, due)
+        .await
+        .expect("sweep");
+
+    assert_eq!(
+        std::fs::read_to_string(temp.path().join("notes.txt")).expect("read"),
+        "edited meanwhile\n"
+    );
+    let record = repo
+        .get_by_id(&approval.id)
+        .await
+        .expect("query")
+        .expect("approval");
+    assert_eq!(record.status, ApprovalStatus::Approved);
+    assert!(record.apply_after.is_none());
+
+    let steering = SteeringRepo::new(Arc::clone(&state.db))
+        .fetch_unconsumed(&session.id)
+        .await
+        .expect("steering");
+    let notice = &steering.last().expect("agent notified").message;
+    assert!(notice.contains("could not be applied"), "{notice}");
+}
+
+#[tokio::test]
+async fn apply_at_refuses_other_users_and_decided_requests() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+    let session = create_active_session(&state.db, root).await;
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let approval = pending(&repo, &session.id, temp.path(), "notes.txt").await;
+    let args = [approval.short_id.as_str(), "18:00"];
+
+    let err = dispatch_command("apply-at", &args, "U_SOMEONE_ELSE", "C_TEST", &state)
+        .await
+        .expect_err("not the owner");
+    assert!(err.to_string().contains("owner"), "{err}");
+
+    let err = dispatch_command(
+        "apply-at",
+        &[args[0], "6pm"],
+        "U_TEST_OWNER",
+        "C_TEST",
+        &state,
+    )
+    .await
+    .expect_err("bad time");
+    assert!(err.to_string().contains("HH:MM"), "{err}");
+
+    repo.decide(&approval.id, ApprovalStatus::Rejected)
+        .await
+        .expect("reject");
+    let err = dispatch_command("apply-at", &args, "U_TEST_OWNER", "C_TEST", &state)
+        .await
+        .expect_err("decided");
+    assert!(err.to_string().contains("already decided"), "{err}");
+}
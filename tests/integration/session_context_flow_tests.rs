@@ -0,0 +1,103 @@
+//! Integration tests for the `store_context` / `get_context` tools.
+//!
+//! Tests cover:
+//! - Stored notes are read back, replaced, removed with `null`, and
+//!   returned by `reboot`
+//! - Empty keys and oversized values are rejected
+
+use serde_json::json;
+
+use super::test_helpers::{connect_mcp_client, test_app_state, test_config};
+
+#[tokio::test]
+async fn stored_context_is_read_back_and_returned_by_reboot() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+    let (mut client, session_id) = connect_mcp_client(&state).await;
+
+    let stored = client
+        .call_tool(
+            2,
+            "store_context",
+            json!({ "key": "ci_url", "value": "https://ci.example/1" }),
+        )
+        .await;
+    assert_eq!(
+        stored,
+        json!({ "status": "stored", "key": "ci_url", "entries": 1 })
+    );
+    client
+        .call_tool(
+            3,
+            "store_context",
+            json!({ "key": "decision", "value": { "db": "sqlite" } }),
+        )
+        .await;
+    client
+        .call_tool(4, "store_context", json!({ "key": "scratch", "value": 1 }))
+        .await;
+    let removed = client
+        .call_tool(
+            5,
+            "store_context",
+            json!({ "key": "scratch", "value": null }),
+        )
+        .await;
+    assert_eq!(removed["status"], "deleted");
+    assert_eq!(removed["entries"], 2);
+
+    let one = client
+        .call_tool(6, "get_context", json!({ "key": "decision" }))
+        .await;
+    assert_eq!(one["entries"][0]["value"], json!({ "db": "sqlite" }));
+
+    let all = client.call_tool(7, "get_context", json!({})).await;
+    let keys: Vec<&str> = all["entries"]
+        .as_array()
+        .expect("entries")
+        .iter()
+        .filter_map(|entry| entry["key"].as_str())
+        .collect();
+    assert_eq!(keys, ["ci_url", "decision"]);
+
+    let recovered = client
+        .call_tool(8, "reboot", json!({ "session_id": session_id }))
+        .await;
+    assert_eq!(recovered["status"], "recovered");
+    assert_eq!(recovered["context"][0]["key"], "ci_url");
+    assert_eq!(recovered["context"][0]["value"], "https://ci.example/1");
+    assert_eq!(recovered["context"][1]["key"], "decision");
+}
+
+#[tokio::test]
+async fn store_context_rejects_bad_input() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+    let (mut client, _session_id) = connect_mcp_client(&state).await;
+
+    let empty_key = client
+        .request(
+            2,
+            "tools/call",
+            json!({ "name": "store_context", "arguments": { "key": "  ", "value": 1 } }),
+        )
+        .await;
+    assert!(empty_key["error"].is_object(), "{empty_key}");
+
+    let big = client
+        .request(
+            3,
+            "tools/call",
+            json!({
+                "name": "store_context",
+                "arguments": { "key": "blob", "value": "a".repeat(5000) }
+            }),
+        )
+        .await;
+    assert!(big["error"].is_object(), "{big}");
+
+    let all = client.call_tool(4, "get_context", json!({})).await;
+    assert_eq!(all, json!({ "entries": [] }));
+}
@@ -0,0 +1,150 @@
+//! Integration tests for ownership and channel inheritance across spawned
+//! sessions.
+//!
+//! - `/spawn` records the channel it was requested from on the session
+//! - subtasks inherit the parent's owner down the session tree and post
+//!   to the parent's channel in a thread of their own
+//! - a spawned agent connecting with only `session_id` is routed to the
+//!   session's recorded channel
+//! - replies in a thread shared by a crashed session and its restart reach
+//!   the restart
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::orchestrator::spawner;
+use agent_intercom::persistence::session_repo::SessionRepo;
+
+use super::test_helpers::{connect_mcp_client_as, test_app_state, test_config};
+
+const OPERATOR: &str = "U_OPERATOR";
+const THREAD_TS: &str = "1700000000.000100";
+
+async fn active_session(repo: &SessionRepo, root: &str, channel: Option<&str>) -> Session {
+    let mut session = Session::new(
+        OPERATOR.into(),
+        root.into(),
+        Some("build feature X".into()),
+        SessionMode::Remote,
+    );
+    session.channel_id = channel.map(str::to_owned);
+    session.thread_ts = channel.map(|_| THREAD_TS.to_owned());
+    let created = repo.create(&session).await.expect("create");
+    repo.update_status(&created.id, SessionStatus::Active)
+        .await
+        .expect("activate")
+}
+
+#[tokio::test]
+async fn spawn_records_requesting_channel() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let mut config = test_config(root);
+    config.authorized_user_ids = vec![OPERATOR.into()];
+    let state = test_app_state(config).await;
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+
+    let (session, _child) = spawner::spawn_session(
+        "build feature X",
+        root,
+        OPERATOR,
+        Some("C_SPAWN"),
+        &state.config,
+        &repo,
+        state.config.http_port,
+    )
+    .await
+    .expect("spawn");
+
+    assert_eq!(session.owner_user_id, OPERATOR);
+    assert_eq!(session.channel_id.as_deref(), Some("C_SPAWN"));
+    assert_eq!(session.thread_ts, None, "the first message starts a thread");
+}
+
+#[tokio::test]
+async fn subtasks_inherit_owner_and_channel_down_the_tree() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let parent = active_session(&repo, root, Some("C_PARENT")).await;
+    let port = state.config.http_port;
+
+    let (child, _c1) =
+        spawner::spawn_subtask(&parent, "write tests", None, &state.config, &repo, port)
+            .await
+            .expect("spawn child");
+    let (grandchild, _c2) = spawner::spawn_subtask(
+        &child,
+        "run clippy",
+        Some("C_MOVED"),
+        &state.config,
+        &repo,
+        port,
+    )
+    .await
+    .expect("spawn grandchild");
+
+    assert_eq!(child.owner_user_id, OPERATOR);
+    assert_eq!(child.channel_id.as_deref(), Some("C_PARENT"));
+    assert_eq!(child.thread_ts, None, "subtasks get a thread of their own");
+    assert_eq!(child.parent_session_id.as_deref(), Some(parent.id.as_str()));
+    assert_eq!(grandchild.owner_user_id, OPERATOR);
+    assert_eq!(grandchild.channel_id.as_deref(), Some("C_MOVED"));
+    assert_eq!(
+        grandchild.parent_session_id.as_deref(),
+        Some(child.id.as_str())
+    );
+    spawner::verify_session_owner(&grandchild, OPERATOR).expect("operator owns grandchild");
+    assert!(spawner::verify_session_owner(&grandchild, "U_STRANGER").is_err());
+}
+
+#[tokio::test]
+async fn spawned_connection_posts_to_recorded_channel() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let session = active_session(&repo, root, Some("C_SPAWN")).await;
+
+    let _client = connect_mcp_client_as(&state, &session.id).await;
+
+    for _ in 0..100 {
+        if state.session_channels.get(&session.id).is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(
+        state.session_channels.get(&session.id).as_deref(),
+        Some("C_SPAWN")
+    );
+}
+
+#[tokio::test]
+async fn thread_replies_reach_the_restarted_session() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let crashed = active_session(&repo, root, Some("C_TEST")).await;
+
+    let (resumed, _child) = spawner::respawn_session(
+        &crashed,
+        &state.config,
+        &repo,
+        &state.db,
+        state.config.http_port,
+    )
+    .await
+    .expect("respawn");
+
+    let found = repo
+        .find_by_channel_and_thread("C_TEST", THREAD_TS)
+        .await
+        .expect("find")
+        .expect("session in thread");
+    assert_eq!(found.id, resumed.id);
+    assert_eq!(found.owner_user_id, OPERATOR);
+}
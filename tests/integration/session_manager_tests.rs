@@ -1,7 +1,8 @@
 //! Integration tests for session manager orchestrator functions.
 //!
-//! Validates `pause_session`, `resume_session`, `terminate_session`,
-//! and `resolve_session` through the orchestrator module.
+//! Validates `pause_session`, `request_pause`, `resume_session`,
+//! `terminate_session`, and `resolve_session` through the orchestrator
+//! module.
 
 use std::sync::Arc;
 
@@ -10,6 +11,7 @@ use agent_intercom::orchestrator::session_manager;
 use agent_intercom::orchestrator::spawner;
 use agent_intercom::persistence::db;
 use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::AppError;
 
 // ── Pause active session ─────────────────────────────────────
 
@@ -35,6 +37,49 @@ async fn pause_session_sets_status_to_paused() {
     assert_eq!(paused.status, SessionStatus::Paused);
 }
 
+// ── Pause after current step ─────────────────────────────────
+
+#[tokio::test]
+async fn request_pause_is_taken_once_and_requires_active_session() {
+    let database = Arc::new(db::connect_memory().await.expect("db"));
+    let repo = SessionRepo::new(Arc::clone(&database));
+    let requests = session_manager::PauseRequests::new();
+
+    let session = Session::new(
+        "U_OWNER".into(),
+        "/test/ws".into(),
+        None,
+        SessionMode::Remote,
+    );
+    let created = repo.create(&session).await.expect("create");
+
+    let result = session_manager::request_pause(&created.id, &repo, &requests).await;
+    assert!(
+        matches!(result, Err(AppError::Policy(_))),
+        "created sessions cannot be paused: {result:?}"
+    );
+
+    repo.update_status(&created.id, SessionStatus::Active)
+        .await
+        .expect("activate");
+    session_manager::request_pause(&created.id, &repo, &requests)
+        .await
+        .expect("request");
+    assert!(requests.is_requested(&created.id));
+
+    let paused = session_manager::take_pending_pause(&created.id, &repo, &requests)
+        .await
+        .expect("take")
+        .expect("pending request applied");
+    assert_eq!(paused.status, SessionStatus::Paused);
+    assert!(
+        session_manager::take_pending_pause(&created.id, &repo, &requests)
+            .await
+            .expect("take again")
+            .is_none()
+    );
+}
+
 // ── Resume paused session ────────────────────────────────────
 
 #[tokio::test]
@@ -0,0 +1,59 @@
+//! Integration tests for `/intercom note`.
+//!
+//! Tests cover:
+//! - A note is stored by session prefix and returned by `reboot`
+//! - Missing text is refused with usage; unknown sessions are not found
+
+use serde_json::json;
+
+use agent_intercom::slack::commands::dispatch_command;
+
+use super::test_helpers::{connect_mcp_client, test_app_state, test_config};
+
+#[tokio::test]
+async fn note_is_stored_and_returned_by_reboot() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+    let (mut client, session_id) = connect_mcp_client(&state).await;
+    let prefix: String = session_id.chars().take(8).collect();
+
+    let reply = dispatch_command(
+        "note",
+        &[prefix.as_str(), "paused", "because", "prod", "incident"],
+        "U_LEAD",
+        "C_TEST",
+        &state,
+    )
+    .await
+    .expect("note");
+    assert!(reply.starts_with("Noted on session"), "{reply}");
+
+    let recovered = client
+        .call_tool(2, "reboot", json!({ "session_id": session_id }))
+        .await;
+    assert_eq!(recovered["status"], "recovered");
+    assert_eq!(recovered["notes"][0]["author"], "U_LEAD");
+    assert_eq!(
+        recovered["notes"][0]["text"],
+        "paused because prod incident"
+    );
+    assert!(recovered["notes"][0]["created_at"].is_string());
+}
+
+#[tokio::test]
+async fn note_requires_text_and_a_known_session() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+
+    let err = dispatch_command("note", &["abc"], "U_LEAD", "C_TEST", &state)
+        .await
+        .expect_err("no text");
+    assert!(err.to_string().contains("usage: note"), "{err}");
+
+    let err = dispatch_command("note", &["missing", "hi"], "U_LEAD", "C_TEST", &state)
+        .await
+        .expect_err("unknown session");
+    assert!(err.to_string().contains("not found"), "{err}");
+}
@@ -0,0 +1,129 @@
+//! Integration tests for "pause after current step".
+//!
+//! - A pending pause answers the agent's next blocking tool call with a
+//!   `pause_requested` directive and moves the session to `Paused`
+//! - `session-pause` records the request; `session-resume` withdraws it
+//!   while still pending
+//! - `session-pause --now` pauses immediately, and `session-resume`
+//!   releases an agent waiting in `standby`
+
+use std::sync::Arc;
+
+use agent_intercom::models::session::SessionStatus;
+use agent_intercom::orchestrator::session_manager::{self, PAUSE_REQUESTED_STATUS};
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::slack::commands::dispatch_command;
+use agent_intercom::state::WaitResponse;
+use serde_json::json;
+use tokio::sync::oneshot;
+
+use super::test_helpers::{connect_mcp_client, create_active_session, test_app_state, test_config};
+
+const OWNER: &str = "U_TEST_OWNER";
+
+#[tokio::test]
+async fn pending_pause_answers_next_blocking_call_with_directive() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(test_config(temp.path().to_str().expect("utf8"))).await;
+    let (mut client, session_id) = connect_mcp_client(&state).await;
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+
+    session_manager::request_pause(&session_id, &repo, &state.pause_requests)
+        .await
+        .expect("request pause");
+
+    let result = client.call_tool(2, "ping", json!({})).await;
+    assert_eq!(result["status"], PAUSE_REQUESTED_STATUS);
+    assert_eq!(result["session_id"], session_id.as_str());
+    assert!(result["directive"]
+        .as_str()
+        .is_some_and(|d| d.contains("standby")));
+
+    let session = repo
+        .get_by_id(&session_id)
+        .await
+        .expect("get")
+        .expect("found");
+    assert_eq!(session.status, SessionStatus::Paused);
+    assert!(
+        !state.pause_requests.is_requested(&session_id),
+        "request is consumed by the checkpoint"
+    );
+}
+
+#[tokio::test]
+async fn pause_command_records_request_and_resume_withdraws_it() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+    let session = create_active_session(&state.db, root).await;
+
+    let reply = dispatch_command("session-pause", &[&session.id], OWNER, "C_TEST", &state)
+        .await
+        .expect("pause");
+    assert!(reply.contains("Pause requested"), "{reply}");
+    assert!(state.pause_requests.is_requested(&session.id));
+
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let stored = repo
+        .get_by_id(&session.id)
+        .await
+        .expect("get")
+        .expect("found");
+    assert_eq!(
+        stored.status,
+        SessionStatus::Active,
+        "session stays active until the agent reaches a checkpoint"
+    );
+
+    let reply = dispatch_command("session-resume", &[&session.id], OWNER, "C_TEST", &state)
+        .await
+        .expect("resume");
+    assert!(reply.contains("cancelled"), "{reply}");
+    assert!(!state.pause_requests.is_requested(&session.id));
+}
+
+#[tokio::test]
+async fn resume_releases_agent_waiting_in_standby() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+    let session = create_active_session(&state.db, root).await;
+
+    dispatch_command(
+        "session-pause",
+        &["--now", &session.id],
+        OWNER,
+        "C_TEST",
+        &state,
+    )
+    .await
+    .expect("pause now");
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let stored = repo
+        .get_by_id(&session.id)
+        .await
+        .expect("get")
+        .expect("found");
+    assert_eq!(stored.status, SessionStatus::Paused);
+
+    let (tx, rx) = oneshot::channel::<WaitResponse>();
+    state
+        .pending_waits
+        .lock()
+        .await
+        .insert(session.id.clone(), tx);
+
+    dispatch_command("session-resume", &[&session.id], OWNER, "C_TEST", &state)
+        .await
+        .expect("resume");
+
+    let response = rx.await.expect("standby released");
+    assert_eq!(response.status, "resumed");
+    let stored = repo
+        .get_by_id(&session.id)
+        .await
+        .expect("get")
+        .expect("found");
+    assert_eq!(stored.status, SessionStatus::Active);
+}
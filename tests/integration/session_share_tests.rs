@@ -0,0 +1,209 @@
+//! Integration tests for read-only session share links.
+//!
+//! The share router is served on an ephemeral port and driven with
+//! `reqwest`, starting from the link `share` replies with.
+//!
+//! Tests cover:
+//! - `share` returns a signed link that renders progress and requests
+//! - The page has no decision form and refuses `POST`
+//! - Expired, forged and approval-scoped signatures are refused
+
+use std::sync::Arc;
+
+use agent_intercom::mcp::{approval_link, session_share};
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::models::progress::{ProgressItem, ProgressStatus};
+use agent_intercom::persistence::approval_repo::ApprovalRepo;
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::slack::commands::dispatch_command;
+use agent_intercom::state::AppState;
+
+use super::test_helpers::{create_active_session, test_app_state, test_config};
+
+const SECRET: &str = "share-test-secret";
+
+struct Harness {
+    state: Arc<AppState>,
+    base_url: String,
+    client: reqwest::Client,
+    _temp: tempfile::TempDir,
+}
+
+async fn harness() -> Harness {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path").to_owned();
+    let mut config = test_config(&root);
+    config.approval_links.enabled = true;
+    config.approval_links.base_url = Some("https://intercom.acme.test".into());
+    config.approval_links.secret = SECRET.into();
+    let state = test_app_state(config).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let base_url = format!("http://{}", listener.local_addr().expect("addr"));
+    let router = session_share::router(Arc::clone(&state));
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+
+    Harness {
+        state,
+        base_url,
+        client: reqwest::Client::new(),
+        _temp: temp,
+    }
+}
+
+impl Harness {
+    /// An active session with progress, one pending and one rejected request.
+    async fn session(&self) -> String {
+        let root = self.state.config.default_workspace_root().to_string_lossy();
+        let session = create_active_session(&self.state.db, &root).await;
+        SessionRepo::new(Arc::clone(&self.state.db))
+            .update_progress_snapshot(
+                &session.id,
+                Some(vec![ProgressItem {
+                    label: "Write <parser>".into(),
+                    status: ProgressStatus::InProgress,
+                }]),
+            )
+            .await
+            .expect("progress");
+
+        let repo = ApprovalRepo::new(Arc::clone(&self.state.db));
+        for (title, status) in [
+            ("Add parser", ApprovalStatus::Pending),
+            ("Drop tables", ApprovalStatus::Rejected),
+        ] {
+            let approval = ApprovalRequest::new(
+                session.id.clone(),
+                title.into(),
+                None,
+                "+fn parse() {}".into(),
+                "src/parser.rs".into(),
+                RiskLevel::Low,
+                "new_file".into(),
+            );
+            repo.create(&approval).await.expect("create approval");
+            repo.update_status(&approval.id, status)
+                .await
+                .expect("status");
+        }
+        session.id
+    }
+
+    async fn get(&self, session_id: &str, exp: i64, sig: &str) -> (u16, String) {
+        let response = self
+            .client
+            .get(format!(
+                "{}/share/{session_id}?exp={exp}&sig={sig}",
+                self.base_url
+            ))
+            .send()
+            .await
+            .expect("send");
+        let status = response.status().as_u16();
+        (status, response.text().await.unwrap_or_default())
+    }
+}
+
+fn now() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+#[tokio::test]
+async fn share_command_link_renders_read_only_view() {
+    let h = harness().await;
+    let id = h.session().await;
+
+    let reply = dispatch_command("share", &[&id[..8]], "U_TEST_OWNER", "C_TEST", &h.state)
+        .await
+        .expect("share");
+    let start = reply.find("/share/").expect("share url");
+    let path = reply[start..].split_whitespace().next().expect("path");
+    assert!(
+        reply.contains("https://intercom.acme.test/share/"),
+        "{reply}"
+    );
+    assert!(reply.contains("expires in 24h 0m"), "{reply}");
+
+    let response = h
+        .client
+        .get(format!("{}{path}", h.base_url))
+        .send()
+        .await
+        .expect("send");
+    assert_eq!(response.status().as_u16(), 200);
+    let page = response.text().await.expect("body");
+    assert!(page.contains("Write &lt;parser&gt;"), "{page}");
+    assert!(
+        page.contains("<dt>Connection</dt><dd>\u{1f7e2} online</dd>"),
+        "{page}"
+    );
+    assert!(page.contains("Waiting for approval"), "{page}");
+    assert!(page.contains("Add parser"), "{page}");
+    assert!(page.contains("Drop tables"), "{page}");
+    assert!(!page.contains("<form"), "{page}");
+
+    let response = h
+        .client
+        .post(format!("{}{path}", h.base_url))
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body("decision=approve")
+        .send()
+        .await
+        .expect("send");
+    assert_eq!(response.status().as_u16(), 405);
+}
+
+#[tokio::test]
+async fn share_ttl_can_be_overridden() {
+    let h = harness().await;
+    let id = h.session().await;
+    let reply = dispatch_command(
+        "share",
+        &[&id, "--ttl", "2h"],
+        "U_TEST_OWNER",
+        "C_TEST",
+        &h.state,
+    )
+    .await
+    .expect("share");
+    assert!(reply.contains("expires in 2h 0m"), "{reply}");
+
+    let err = dispatch_command("share", &[], "U_TEST_OWNER", "C_TEST", &h.state)
+        .await
+        .expect_err("usage");
+    assert!(err.to_string().contains("usage: share"), "{err}");
+}
+
+#[tokio::test]
+async fn expired_forged_or_approval_scoped_links_are_refused() {
+    let h = harness().await;
+    let id = h.session().await;
+
+    let expired = now() - 1;
+    let sig = session_share::sign(SECRET, &id, expired);
+    assert_eq!(h.get(&id, expired, &sig).await.0, 401);
+
+    let exp = now() + 600;
+    let forged = session_share::sign("wrong-secret", &id, exp);
+    assert_eq!(h.get(&id, exp, &forged).await.0, 401);
+
+    let approval_sig = approval_link::sign(SECRET, &id, exp);
+    assert_eq!(h.get(&id, exp, &approval_sig).await.0, 401);
+
+    let sig = session_share::sign(SECRET, &id, exp);
+    assert_eq!(h.get(&id, exp, &sig).await.0, 200);
+    assert_eq!(
+        h.get(
+            "no-such-session",
+            exp,
+            &session_share::sign(SECRET, "no-such-session", exp)
+        )
+        .await
+        .0,
+        404
+    );
+}
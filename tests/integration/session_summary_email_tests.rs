@@ -0,0 +1,149 @@
+//! Integration tests for emailed session summaries (`[smtp]`).
+//!
+//! Runs a minimal SMTP server stub and checks that an ended session's
+//! summary, gathered from the database, is delivered to the owner.
+
+use std::sync::Arc;
+
+use agent_intercom::config::{SmtpConfig, SmtpSecurity};
+use agent_intercom::integrations::email::{send_summary, SessionSummary};
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::persistence::approval_repo::ApprovalRepo;
+use agent_intercom::persistence::db;
+use agent_intercom::persistence::session_repo::SessionRepo;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::Mutex;
+
+/// Envelope recipients and message data received by the SMTP stub.
+type Inbox = Arc<Mutex<Vec<(Vec<String>, String)>>>;
+
+/// Start an SMTP stub that accepts every message; returns its port.
+async fn start_smtp_stub() -> (u16, Inbox) {
+    let inbox: Inbox = Arc::default();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind stub");
+    let port = listener.local_addr().expect("stub addr").port();
+    let received = Arc::clone(&inbox);
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let received = Arc::clone(&received);
+            tokio::spawn(async move {
+                let (read, mut write) = stream.into_split();
+                let mut lines = BufReader::new(read).lines();
+                let _ = write.write_all(b"220 stub ESMTP\r\n").await;
+                let mut rcpt = Vec::new();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let verb = line.to_ascii_uppercase();
+                    let reply: &[u8] = if verb.starts_with("EHLO") || verb.starts_with("HELO") {
+                        b"250 stub\r\n"
+                    } else if let Some(to) = verb.strip_prefix("RCPT TO:") {
+                        rcpt.push(to.trim_matches(['<', '>', ' ']).to_ascii_lowercase());
+                        b"250 ok\r\n"
+                    } else if verb == "DATA" {
+                        let _ = write.write_all(b"354 go ahead\r\n").await;
+                        let mut data = String::new();
+                        while let Ok(Some(body)) = lines.next_line().await {
+                            if body == "." {
+                                break;
+                            }
+                            data.push_str(&body);
+                            data.push('\n');
+                        }
+                        received
+                            .lock()
+                            .await
+                            .push((std::mem::take(&mut rcpt), data));
+                        b"250 queued\r\n"
+                    } else if verb == "QUIT" {
+                        let _ = write.write_all(b"221 bye\r\n").await;
+                        break;
+                    } else {
+                        b"250 ok\r\n"
+                    };
+                    let _ = write.write_all(reply).await;
+                }
+            });
+        }
+    });
+    (port, inbox)
+}
+
+#[tokio::test]
+async fn ended_session_summary_is_mailed_to_owner() {
+    let (port, inbox) = start_smtp_stub().await;
+    let database = Arc::new(db::connect_memory().await.expect("in-memory db"));
+    let sessions = SessionRepo::new(Arc::clone(&database));
+    let mut session = Session::new(
+        "U_ALICE".into(),
+        "/workspace".into(),
+        Some("rename the config loader".into()),
+        SessionMode::Remote,
+    );
+    session.status = SessionStatus::Active;
+    let session = sessions.create(&session).await.expect("create session");
+
+    let approvals = ApprovalRepo::new(Arc::clone(&database));
+    let request = ApprovalRequest::new(
+        session.id.clone(),
+        "Rename loader".into(),
+        None,
+        "+fn load()\n".into(),
+        "src/config.rs".into(),
+        RiskLevel::Low,
+        "new_file".into(),
+    );
+    approvals.create(&request).await.expect("create approval");
+    approvals
+        .update_status(&request.id, ApprovalStatus::Approved)
+        .await
+        .expect("approve");
+    let ended = sessions
+        .set_terminated(&session.id, SessionStatus::Terminated)
+        .await
+        .expect("terminate");
+
+    let config = SmtpConfig {
+        host: Some("127.0.0.1".into()),
+        port,
+        security: SmtpSecurity::None,
+        from: Some("intercom@acme.test".into()),
+        recipients: [("U_ALICE".to_owned(), "alice@acme.test".to_owned())]
+            .into_iter()
+            .collect(),
+        ..SmtpConfig::default()
+    };
+    let summary = SessionSummary::gather(&database, ended, "stopped by operator")
+        .await
+        .expect("gather summary");
+    assert_eq!(summary.files_changed(), ["src/config.rs"]);
+
+    let sent = send_summary(&config, &summary).await.expect("summary sent");
+    assert!(sent);
+
+    let inbox = inbox.lock().await;
+    assert_eq!(inbox.len(), 1);
+    let (recipients, data) = &inbox[0];
+    assert_eq!(recipients, &["alice@acme.test"]);
+    assert!(data.contains("multipart/alternative"), "{data}");
+    assert!(data.contains("text/html"), "{data}");
+}
+
+#[tokio::test]
+async fn disabled_smtp_sends_nothing() {
+    let database = Arc::new(db::connect_memory().await.expect("in-memory db"));
+    let session = Session::new(
+        "U_ALICE".into(),
+        "/workspace".into(),
+        None,
+        SessionMode::Remote,
+    );
+    let summary = SessionSummary::gather(&database, session, "done")
+        .await
+        .expect("gather summary");
+    let sent = send_summary(&SmtpConfig::default(), &summary)
+        .await
+        .expect("no-op");
+    assert!(!sent);
+}
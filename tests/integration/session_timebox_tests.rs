@@ -0,0 +1,118 @@
+//! Integration tests for session time boxes (`session-start --max-duration`).
+//!
+//! - The wrap-up warning is queued as steering once the remaining budget
+//!   drops below the lead time, and only once
+//! - At the deadline the deadline is cleared so it is enforced only once
+//! - Sessions without a deadline are ignored
+
+use std::sync::Arc;
+
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::orchestrator::session_timebox::{enforce_time_boxes, wrap_up_lead};
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::persistence::steering_repo::SteeringRepo;
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+
+use super::test_helpers::{create_active_session, test_app_state, test_config};
+
+/// Create an active session with a time box of `budget`.
+async fn create_time_boxed_session(db: &Arc<SqlitePool>, root: &str, budget: Duration) -> Session {
+    let repo = SessionRepo::new(Arc::clone(db));
+    let mut session = Session::new(
+        "U_TEST_OWNER".into(),
+        root.into(),
+        Some("long task".into()),
+        SessionMode::Remote,
+    );
+    session.deadline = Some(session.created_at + budget);
+    let created = repo.create(&session).await.expect("create session");
+    repo.update_status(&created.id, SessionStatus::Active)
+        .await
+        .expect("activate session")
+}
+
+#[test]
+fn wrap_up_lead_is_a_tenth_of_the_budget_within_bounds() {
+    assert_eq!(wrap_up_lead(Duration::minutes(60)), Duration::minutes(6));
+    assert_eq!(wrap_up_lead(Duration::minutes(5)), Duration::minutes(1));
+    assert_eq!(wrap_up_lead(Duration::hours(8)), Duration::minutes(15));
+}
+
+#[tokio::test]
+async fn wrap_up_is_steered_once_when_budget_runs_low() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+    let session = create_time_boxed_session(&state.db, root, Duration::hours(1)).await;
+    let steering = SteeringRepo::new(Arc::clone(&state.db));
+
+    // Before the lead window: nothing happens.
+    enforce_time_boxes(&state, session.created_at + Duration::minutes(30))
+        .await
+        .expect("sweep");
+    assert!(steering
+        .fetch_unconsumed(&session.id)
+        .await
+        .expect("fetch")
+        .is_empty());
+
+    // Inside the lead window: one wrap-up message.
+    let near_end = session.created_at + Duration::minutes(55);
+    enforce_time_boxes(&state, near_end).await.expect("sweep");
+    enforce_time_boxes(&state, near_end + Duration::minutes(1))
+        .await
+        .expect("sweep again");
+
+    let queued = steering.fetch_unconsumed(&session.id).await.expect("fetch");
+    assert_eq!(queued.len(), 1, "wrap-up is sent once");
+    assert!(
+        queued[0].message.contains("Wrap up"),
+        "{}",
+        queued[0].message
+    );
+
+    let stored = SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(&session.id)
+        .await
+        .expect("get")
+        .expect("found");
+    assert!(stored.wrap_up_sent);
+}
+
+#[tokio::test]
+async fn deadline_is_enforced_once_and_cleared() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+    let session = create_time_boxed_session(&state.db, root, Duration::minutes(10)).await;
+    let untimed = create_active_session(&state.db, root).await;
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+
+    assert_eq!(repo.list_time_boxed().await.expect("list").len(), 1);
+
+    enforce_time_boxes(&state, Utc::now() + Duration::minutes(11))
+        .await
+        .expect("sweep");
+
+    let stored = repo
+        .get_by_id(&session.id)
+        .await
+        .expect("get")
+        .expect("found");
+    assert_eq!(stored.deadline, None);
+    assert_eq!(
+        stored.status,
+        SessionStatus::Active,
+        "the session stays open for the operator"
+    );
+    assert!(repo.list_time_boxed().await.expect("list").is_empty());
+
+    let untimed = repo
+        .get_by_id(&untimed.id)
+        .await
+        .expect("get")
+        .expect("found");
+    assert_eq!(untimed.deadline, None);
+    assert!(!untimed.wrap_up_sent);
+}
@@ -2,13 +2,19 @@
 //!
 //! Validates that `graceful_shutdown` marks pending approvals, prompts,
 //! and sessions as Interrupted, and that `check_interrupted_on_startup`
-//! correctly identifies interrupted sessions after restart.
+//! correctly identifies interrupted sessions after restart, that
+//! `rearm_pending` lets decisions on pending requests reach the agent, and
+//! that `[recovery] on_startup` reactivates sessions, for any
+//! `max_age_minutes`, and expires re-armed requests after a fresh timeout.
 
 use std::sync::Arc;
 
+use agent_intercom::config::StartupRecovery;
 use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
 use agent_intercom::models::prompt::{ContinuationPrompt, PromptDecision, PromptType};
-use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::models::session::{ConnectivityStatus, Session, SessionMode, SessionStatus};
+use agent_intercom::orchestrator::rearm::{self, Rearmed};
+use agent_intercom::orchestrator::startup_recovery::{self, Recovered};
 use agent_intercom::persistence::approval_repo::ApprovalRepo;
 use agent_intercom::persistence::db;
 use agent_intercom::persistence::prompt_repo::PromptRepo;
@@ -353,6 +359,240 @@ async fn startup_recovery_counts_pending_per_session() {
     assert_eq!(total_prompts, 1);
 }
 
+// ── Startup re-arm: pending requests wait for the agent ──────
+
+#[tokio::test]
+async fn startup_rearm_delivers_decision_to_reconnecting_agent() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+
+    let session = create_active_session_in_db(&state.db, root).await;
+    let approval = ApprovalRequest::new(
+        session.id.clone(),
+        "kept approval".into(),
+        None,
+        "diff content".into(),
+        "file.rs".into(),
+        RiskLevel::Low,
+        "abc123".into(),
+    );
+    ApprovalRepo::new(Arc::clone(&state.db))
+        .create(&approval)
+        .await
+        .expect("create approval");
+    let prompt = ContinuationPrompt::new(
+        session.id.clone(),
+        "kept prompt".into(),
+        PromptType::Continuation,
+        None,
+        None,
+    );
+    PromptRepo::new(Arc::clone(&state.db))
+        .create(&prompt)
+        .await
+        .expect("create prompt");
+
+    assert_eq!(rearm::rearm_pending(&state).await, (1, 1));
+    assert!(state
+        .pending_approvals
+        .lock()
+        .await
+        .contains_key(&approval.id));
+    assert!(state.pending_prompts.lock().await.contains_key(&prompt.id));
+    assert!(state.rearmed.contains(&approval.id));
+    assert!(state.rearmed.contains(&prompt.id));
+
+    // The operator decides before the agent is back.
+    state
+        .mcp_driver()
+        .resolve_clearance(&approval.id, true, None)
+        .await
+        .expect("resolve re-armed approval");
+
+    // The reconnecting agent collects the approval first, then the prompt.
+    let (id, rearmed) = state
+        .rearmed
+        .take_for_session(&session.id)
+        .expect("re-armed approval");
+    assert_eq!(id, approval.id);
+    let Rearmed::Approval(rx) = rearmed else {
+        panic!("expected an approval, got {rearmed:?}");
+    };
+    assert_eq!(rx.await.expect("decision").status, "approved");
+
+    let (id, rearmed) = state
+        .rearmed
+        .take_for_session(&session.id)
+        .expect("re-armed prompt");
+    assert_eq!(id, prompt.id);
+    assert!(matches!(rearmed, Rearmed::Prompt(_)));
+    assert!(state.rearmed.take_for_session(&session.id).is_none());
+}
+
+// ── Startup recovery levels ──────────────────────────────────
+
+#[tokio::test]
+async fn startup_reactivate_revives_recent_sessions_until_grace_ends() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let mut config = test_config(root);
+    config.recovery.on_startup = StartupRecovery::Reactivate;
+    config.timeouts.reconnect_grace_seconds = 1;
+    let state = test_app_state(config).await;
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+
+    let recent = create_active_session_in_db(&state.db, root).await;
+    repo.set_terminated(&recent.id, SessionStatus::Interrupted)
+        .await
+        .expect("interrupt recent");
+    let old = create_active_session_in_db(&state.db, root).await;
+    repo.set_terminated(&old.id, SessionStatus::Interrupted)
+        .await
+        .expect("interrupt old");
+    let long_ago = (chrono::Utc::now() - chrono::Duration::hours(2)).to_rfc3339();
+    sqlx::query("UPDATE session SET updated_at = ?1 WHERE id = ?2")
+        .bind(&long_ago)
+        .bind(&old.id)
+        .execute(state.db.as_ref())
+        .await
+        .expect("age old session");
+
+    let interrupted = repo.list_interrupted().await.expect("list");
+    let recovered = startup_recovery::recover(&state, &interrupted).await;
+    assert_eq!(
+        recovered,
+        Recovered {
+            reactivated: 1,
+            respawned: 0
+        }
+    );
+
+    let revived = repo
+        .get_by_id(&recent.id)
+        .await
+        .expect("get")
+        .expect("exists");
+    assert_eq!(revived.status, SessionStatus::Active);
+    assert_eq!(revived.connectivity_status, ConnectivityStatus::Offline);
+    assert!(revived.terminated_at.is_none());
+    let untouched = repo.get_by_id(&old.id).await.expect("get").expect("exists");
+    assert_eq!(untouched.status, SessionStatus::Interrupted);
+
+    // No agent reconnects within the grace window.
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+    let unclaimed = repo
+        .get_by_id(&recent.id)
+        .await
+        .expect("get")
+        .expect("exists");
+    assert_eq!(unclaimed.status, SessionStatus::Interrupted);
+}
+
+#[tokio::test]
+async fn startup_reactivate_accepts_unbounded_max_age() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let mut config = test_config(root);
+    config.recovery.on_startup = StartupRecovery::Reactivate;
+    config.recovery.max_age_minutes = u64::MAX;
+    let state = test_app_state(config).await;
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+
+    let session = create_active_session_in_db(&state.db, root).await;
+    repo.set_terminated(&session.id, SessionStatus::Interrupted)
+        .await
+        .expect("interrupt");
+
+    let interrupted = repo.list_interrupted().await.expect("list");
+    let recovered = startup_recovery::recover(&state, &interrupted).await;
+    assert_eq!(recovered.reactivated, 1);
+}
+
+#[tokio::test]
+async fn startup_notify_leaves_sessions_interrupted() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+
+    let session = create_active_session_in_db(&state.db, root).await;
+    repo.set_terminated(&session.id, SessionStatus::Interrupted)
+        .await
+        .expect("interrupt");
+
+    let interrupted = repo.list_interrupted().await.expect("list");
+    let recovered = startup_recovery::recover(&state, &interrupted).await;
+    assert_eq!(recovered, Recovered::default());
+    let session = repo
+        .get_by_id(&session.id)
+        .await
+        .expect("get")
+        .expect("exists");
+    assert_eq!(session.status, SessionStatus::Interrupted);
+}
+
+#[tokio::test]
+async fn reposted_requests_expire_after_fresh_timeout_unless_decided() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let mut config = test_config(root);
+    config.timeouts.approval_seconds = 1;
+    config.timeouts.prompt_seconds = 1;
+    let state = test_app_state(config).await;
+
+    let session = create_active_session_in_db(&state.db, root).await;
+    let approval = ApprovalRequest::new(
+        session.id.clone(),
+        "stale approval".into(),
+        None,
+        "diff content".into(),
+        "file.rs".into(),
+        RiskLevel::Low,
+        "abc123".into(),
+    );
+    ApprovalRepo::new(Arc::clone(&state.db))
+        .create(&approval)
+        .await
+        .expect("create approval");
+    let prompt = ContinuationPrompt::new(
+        session.id.clone(),
+        "decided prompt".into(),
+        PromptType::Continuation,
+        None,
+        None,
+    );
+    PromptRepo::new(Arc::clone(&state.db))
+        .create(&prompt)
+        .await
+        .expect("create prompt");
+
+    assert_eq!(rearm::rearm_pending(&state).await, (1, 1));
+    rearm::expire_unclaimed(&state).await;
+
+    // The operator answers the prompt in time; the approval is left alone.
+    PromptRepo::new(Arc::clone(&state.db))
+        .decide(&prompt.id, PromptDecision::Stop, None)
+        .await
+        .expect("decide prompt");
+
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+
+    let expired = ApprovalRepo::new(Arc::clone(&state.db))
+        .get_by_id(&approval.id)
+        .await
+        .expect("get")
+        .expect("exists");
+    assert_eq!(expired.status, ApprovalStatus::Expired);
+    assert!(!state.rearmed.contains(&approval.id));
+    assert!(!state
+        .pending_approvals
+        .lock()
+        .await
+        .contains_key(&approval.id));
+    assert!(state.rearmed.contains(&prompt.id));
+}
+
 /// Create and activate a session directly in the database.
 async fn create_active_session_in_db(db: &Arc<sqlx::SqlitePool>, workspace_root: &str) -> Session {
     let repo = SessionRepo::new(Arc::clone(db));
@@ -0,0 +1,97 @@
+//! Integration tests for `--simulate-operator`.
+//!
+//! Tests cover:
+//! - `check_clearance` is approved or rejected by policy without Slack,
+//!   rejections carrying structured feedback
+//! - `transmit` prompts are answered by policy without Slack
+
+use std::sync::Arc;
+
+use serde_json::json;
+use tokio_util::sync::CancellationToken;
+
+use agent_intercom::orchestrator::simulated_operator::{spawn_simulated_operator, OperatorScript};
+use agent_intercom::state::AppState;
+
+use super::test_helpers::{connect_mcp_client, test_app_state, test_config_no_channel};
+
+const POLICY: &str = r#"
+delay_ms = 10
+
+[[approval]]
+path = "migrations/**"
+decision = "reject"
+reason = "migrations need a human"
+
+[[prompt]]
+prompt_type = "clarification"
+decision = "refine"
+instruction = "keep it small"
+"#;
+
+async fn simulated_state(root: &str) -> (Arc<AppState>, CancellationToken) {
+    let script = Arc::new(OperatorScript::from_toml_str(POLICY).expect("policy"));
+    let mut state = Arc::try_unwrap(test_app_state(test_config_no_channel(root)).await)
+        .unwrap_or_else(|_| panic!("state is not shared yet"));
+    state.simulator = Some(Arc::clone(&script));
+    let state = Arc::new(state);
+    let cancel = CancellationToken::new();
+    let _task = spawn_simulated_operator(Arc::clone(&state), script, cancel.clone());
+    (state, cancel)
+}
+
+#[tokio::test]
+async fn simulated_operator_decides_clearance() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let (state, cancel) = simulated_state(root).await;
+    let (mut client, _session_id) = connect_mcp_client(&state).await;
+
+    let approved = client
+        .call_tool(
+            2,
+            "check_clearance",
+            json!({ "title": "tweak", "diff": "+x", "file_path": "src/lib.rs" }),
+        )
+        .await;
+    assert_eq!(approved["status"], "approved", "{approved}");
+    assert!(approved.get("feedback").is_none(), "{approved}");
+
+    let rejected = client
+        .call_tool(
+            3,
+            "check_clearance",
+            json!({ "title": "schema", "diff": "+x", "file_path": "migrations/001.sql" }),
+        )
+        .await;
+    assert_eq!(rejected["status"], "rejected", "{rejected}");
+    assert_eq!(rejected["reason"], "migrations need a human");
+    assert_eq!(rejected["feedback"]["category"], "other", "{rejected}");
+    assert_eq!(rejected["feedback"]["summary"], "migrations need a human");
+    assert_eq!(rejected["feedback"]["required_changes"], json!([]));
+    cancel.cancel();
+}
+
+#[tokio::test]
+async fn simulated_operator_answers_prompts() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let (state, cancel) = simulated_state(root).await;
+    let (mut client, _session_id) = connect_mcp_client(&state).await;
+
+    let refined = client
+        .call_tool(
+            2,
+            "transmit",
+            json!({ "prompt_text": "which file?", "prompt_type": "clarification" }),
+        )
+        .await;
+    assert_eq!(refined["decision"], "refine", "{refined}");
+    assert_eq!(refined["instruction"], "keep it small");
+
+    let continued = client
+        .call_tool(3, "transmit", json!({ "prompt_text": "keep going?" }))
+        .await;
+    assert_eq!(continued["decision"], "continue", "{continued}");
+    cancel.cancel();
+}
@@ -8,6 +8,10 @@
 //! - S-T1-009: Approval accept resolves oneshot and updates DB
 //! - S-T1-010: Approval reject (via modal submission path)
 //! - S-T1-011: Prompt continue resolves oneshot
+//! - Prompt quick reply refines with the configured preset
+//! - Snooze defers a pending approval or prompt and refuses decided ones
+//! - Revive re-opens an expired approval and steers the agent with the
+//!   late decision; pending requests and ended sessions are not revived
 //! - S-T1-025: Stall nudge increments DB counter
 //! - S-T1-026: Wait resume resolves oneshot
 
@@ -32,6 +36,7 @@ use agent_intercom::persistence::db;
 use agent_intercom::persistence::prompt_repo::PromptRepo;
 use agent_intercom::persistence::session_repo::SessionRepo;
 use agent_intercom::persistence::stall_repo::StallAlertRepo;
+use agent_intercom::persistence::steering_repo::SteeringRepo;
 use agent_intercom::slack::handlers;
 use agent_intercom::state::{
     AppState, ApprovalResponse, PendingApprovals, PendingPrompts, PendingWaits, PromptResponse,
@@ -91,8 +96,19 @@ async fn app_state_with_maps(
     waits: PendingWaits,
 ) -> Arc<AppState> {
     let toml = test_config_toml(workspace_root);
+    app_state_from_toml(&toml, authorized_user, approvals, prompts, waits).await
+}
+
+/// Like [`app_state_with_maps`], but from a caller-supplied config.
+async fn app_state_from_toml(
+    toml: &str,
+    authorized_user: &str,
+    approvals: PendingApprovals,
+    prompts: PendingPrompts,
+    waits: PendingWaits,
+) -> Arc<AppState> {
     let mut config =
-        agent_intercom::config::GlobalConfig::from_toml_str(&toml).expect("valid test config");
+        agent_intercom::config::GlobalConfig::from_toml_str(toml).expect("valid test config");
     config.authorized_user_ids = vec![authorized_user.to_owned()];
 
     let db = Arc::new(db::connect_memory().await.expect("db connect"));
@@ -123,6 +139,27 @@ async fn app_state_with_maps(
         workspace_mappings: Arc::default(),
         acp_event_tx: None,
         acp_driver: None,
+        driver_registry: Arc::default(),
+        event_bus: Arc::default(),
+        session_hooks: Arc::default(),
+        pause_requests: Arc::default(),
+        heartbeats: Arc::default(),
+        instruction_queue: Arc::default(),
+        snoozes: Arc::default(),
+        apply_confirmations: Arc::default(),
+        session_logs: Arc::default(),
+        session_channels: Arc::default(),
+        workspace_discovery: Arc::default(),
+        config_path: None,
+        command_aliases: Arc::default(),
+        proxy: Arc::default(),
+        replay_guard: Arc::default(),
+        interactions: Arc::default(),
+        rearmed: Arc::default(),
+        diff_staging: Arc::default(),
+        subsystems: Arc::default(),
+        simulator: None,
+        log_filter: None,
     })
 }
 
@@ -419,6 +456,260 @@ async fn simulated_prompt_stop_resolves_oneshot_and_updates_db() {
     );
 }
 
+// ── Prompt quick reply ────────────────────────────────────────────────────────
+
+/// A quick-reply button refines the prompt with its configured preset text.
+#[tokio::test]
+async fn simulated_prompt_quick_reply_refines_with_preset() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let user = "U_TEST_OWNER";
+
+    let toml = format!(
+        "{}\n[prompts.quick_replies]\ncontinuation = [\"Retry\", \"Skip test\"]\n",
+        test_config_toml(root)
+    );
+    let (approvals, prompts, waits) = make_maps();
+    let state = app_state_from_toml(&toml, user, approvals, prompts, waits).await;
+
+    let session = create_session(&state.db, user, root).await;
+    let prompt = create_prompt(&state.db, &session.id).await;
+    let prompt_id = prompt.id.clone();
+
+    let (tx, rx) = oneshot::channel::<PromptResponse>();
+    state
+        .pending_prompts
+        .lock()
+        .await
+        .insert(prompt_id.clone(), tx);
+
+    let action = make_action("prompt_quick_1", &prompt_id);
+    let result =
+        handlers::prompt::handle_prompt_action(&action, user, &no_trigger(), None, None, &state)
+            .await;
+    assert!(result.is_ok(), "quick reply must return Ok: {result:?}");
+
+    let response = tokio::time::timeout(Duration::from_millis(500), rx)
+        .await
+        .expect("oneshot must resolve")
+        .expect("oneshot must not be dropped");
+    assert_eq!(response.decision, "refine");
+    assert_eq!(response.instruction.as_deref(), Some("Skip test"));
+
+    let updated = PromptRepo::new(Arc::clone(&state.db))
+        .get_by_id(&prompt_id)
+        .await
+        .expect("db query")
+        .expect("record must exist");
+    assert_eq!(updated.decision, Some(PromptDecision::Refine));
+    assert_eq!(updated.instruction.as_deref(), Some("Skip test"));
+
+    // A second click on the answered prompt leaves the decision alone.
+    let action = make_action("prompt_quick_0", &prompt_id);
+    let result =
+        handlers::prompt::handle_prompt_action(&action, user, &no_trigger(), None, None, &state)
+            .await;
+    assert!(result.is_ok(), "late click must return Ok: {result:?}");
+    let unchanged = PromptRepo::new(Arc::clone(&state.db))
+        .get_by_id(&prompt_id)
+        .await
+        .expect("db query")
+        .expect("record must exist");
+    assert_eq!(unchanged.instruction.as_deref(), Some("Skip test"));
+
+    // An index with no configured preset is rejected.
+    let pending = create_prompt(&state.db, &session.id).await;
+    let action = make_action("prompt_quick_7", &pending.id);
+    let result =
+        handlers::prompt::handle_prompt_action(&action, user, &no_trigger(), None, None, &state)
+            .await;
+    assert!(result.is_err(), "unconfigured quick reply must fail");
+}
+
+// ── Snooze ────────────────────────────────────────────────────────────────────
+
+/// `snooze_approval` and `snooze_prompt` record a snooze for pending items
+/// and leave the oneshots untouched.
+#[tokio::test]
+async fn snooze_defers_pending_approval_and_prompt() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let user = "U_TEST_OWNER";
+
+    let (approvals, prompts, waits) = make_maps();
+    let state = app_state_with_maps(root, user, approvals, prompts, waits).await;
+    let session = create_session(&state.db, user, root).await;
+    let approval = create_approval(&state.db, &session.id).await;
+    let prompt = create_prompt(&state.db, &session.id).await;
+
+    let (tx, mut rx) = oneshot::channel::<ApprovalResponse>();
+    state
+        .pending_approvals
+        .lock()
+        .await
+        .insert(approval.id.clone(), tx);
+
+    for (action_id, id) in [
+        ("snooze_approval", &approval.id),
+        ("snooze_prompt", &prompt.id),
+    ] {
+        let action = make_action(action_id, id);
+        handlers::snooze::handle_snooze_action(&action, user, None, None, &state)
+            .await
+            .expect("snooze should succeed");
+        assert!(
+            state.snoozes.snoozed_until(id, Utc::now()).is_some(),
+            "{action_id} should snooze {id}"
+        );
+    }
+
+    assert!(
+        rx.try_recv().is_err(),
+        "snooze must not resolve the approval"
+    );
+    let stored = ApprovalRepo::new(Arc::clone(&state.db))
+        .get_by_id(&approval.id)
+        .await
+        .expect("query")
+        .expect("approval exists");
+    assert_eq!(stored.status, ApprovalStatus::Pending);
+}
+
+/// Snoozing an already-decided prompt is refused.
+#[tokio::test]
+async fn snooze_rejects_decided_prompt() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let user = "U_TEST_OWNER";
+
+    let (approvals, prompts, waits) = make_maps();
+    let state = app_state_with_maps(root, user, approvals, prompts, waits).await;
+    let session = create_session(&state.db, user, root).await;
+    let prompt = create_prompt(&state.db, &session.id).await;
+    PromptRepo::new(Arc::clone(&state.db))
+        .update_decision(&prompt.id, PromptDecision::Continue, None)
+        .await
+        .expect("decide prompt");
+
+    let action = make_action("snooze_prompt", &prompt.id);
+    let result = handlers::snooze::handle_snooze_action(&action, user, None, None, &state).await;
+
+    assert!(result.is_err(), "decided prompt must not be snoozed");
+    assert!(state
+        .snoozes
+        .snoozed_until(&prompt.id, Utc::now())
+        .is_none());
+}
+
+// ── Revive ────────────────────────────────────────────────────────────────────
+
+/// `revive_approval` re-opens an expired approval; accepting it then
+/// steers the session with the `request_id` to apply.
+#[tokio::test]
+async fn revive_reopens_expired_approval_and_steers_decision() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let user = "U_TEST_OWNER";
+
+    let (approvals, prompts, waits) = make_maps();
+    let state = app_state_with_maps(root, user, approvals, prompts, waits).await;
+    let session = create_session(&state.db, user, root).await;
+    let approval = create_approval(&state.db, &session.id).await;
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    assert!(repo
+        .decide(&approval.id, ApprovalStatus::Expired)
+        .await
+        .expect("expire"));
+
+    let action = make_action("revive_approval", &approval.id);
+    handlers::revive::handle_revive_action(&action, user, None, None, &state)
+        .await
+        .expect("revive should succeed");
+
+    let stored = repo
+        .get_by_id(&approval.id)
+        .await
+        .expect("query")
+        .expect("approval exists");
+    assert_eq!(stored.status, ApprovalStatus::Pending);
+    assert!(state
+        .pending_approvals
+        .lock()
+        .await
+        .contains_key(&approval.id));
+
+    let accept = make_action("approve_accept", &approval.id);
+    handlers::approval::handle_approval_action(&accept, user, &no_trigger(), None, None, &state)
+        .await
+        .expect("accept should succeed");
+
+    let steering = SteeringRepo::new(Arc::clone(&state.db));
+    let queued = tokio::time::timeout(Duration::from_secs(2), async {
+        loop {
+            let queued = steering
+                .fetch_unconsumed(&session.id)
+                .await
+                .expect("steering query");
+            if !queued.is_empty() {
+                return queued;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("agent steered with the decision");
+    assert!(
+        queued[0].message.contains("now approved"),
+        "{}",
+        queued[0].message
+    );
+    assert!(
+        queued[0].message.contains(&approval.id),
+        "{}",
+        queued[0].message
+    );
+}
+
+/// A pending approval, or an expired one whose session ended, is not
+/// re-opened.
+#[tokio::test]
+async fn revive_leaves_pending_and_ended_session_approvals_alone() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let user = "U_TEST_OWNER";
+
+    let (approvals, prompts, waits) = make_maps();
+    let state = app_state_with_maps(root, user, approvals, prompts, waits).await;
+    let session = create_session(&state.db, user, root).await;
+    let pending = create_approval(&state.db, &session.id).await;
+    let expired = create_approval(&state.db, &session.id).await;
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    repo.decide(&expired.id, ApprovalStatus::Expired)
+        .await
+        .expect("expire");
+    SessionRepo::new(Arc::clone(&state.db))
+        .update_status(&session.id, SessionStatus::Terminated)
+        .await
+        .expect("terminate session");
+
+    for id in [&pending.id, &expired.id] {
+        let action = make_action("revive_approval", id);
+        handlers::revive::handle_revive_action(&action, user, None, None, &state)
+            .await
+            .expect("refusal is reported, not an error");
+    }
+
+    assert!(state.pending_approvals.lock().await.is_empty());
+    for (id, want) in [
+        (&pending.id, ApprovalStatus::Pending),
+        (&expired.id, ApprovalStatus::Expired),
+    ] {
+        let stored = repo.get_by_id(id).await.expect("query").expect("exists");
+        assert_eq!(stored.status, want);
+    }
+    assert!(!repo.reopen(&pending.id).await.expect("reopen"));
+}
+
 // ── S-T1-025: Stall nudge ─────────────────────────────────────────────────────
 
 /// S-T1-025 — `stall_nudge` increments the nudge count in the DB without error.
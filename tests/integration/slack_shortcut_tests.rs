@@ -0,0 +1,169 @@
+//! Integration tests for Slack shortcuts and Workflow Builder steps.
+//!
+//! Tests cover:
+//! - Workflow message text parsing (`steer …` / `task …`)
+//! - A message shortcut steers the session owning the message's thread
+//! - A message shortcut queues the message as a task for its channel
+//! - Workflow messages run only for allow-listed bot IDs
+//! - Unknown shortcut callback IDs are reported
+
+use std::sync::Arc;
+
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::persistence::inbox_repo::InboxRepo;
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::persistence::steering_repo::SteeringRepo;
+use agent_intercom::slack::handlers::shortcut::{self, ShortcutAction};
+use agent_intercom::state::AppState;
+
+use super::test_helpers::{test_app_state, test_config};
+
+const CHANNEL: &str = "C_SHORTCUT";
+const THREAD: &str = "1700002000.000001";
+
+async fn session_in_channel(state: &Arc<AppState>, root: &str) -> Session {
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let mut session = Session::new("U_OWNER".into(), root.into(), None, SessionMode::Remote);
+    session.channel_id = Some(CHANNEL.into());
+    session.thread_ts = Some(THREAD.into());
+    let created = repo.create(&session).await.expect("create session");
+    repo.update_status(&created.id, SessionStatus::Active)
+        .await
+        .expect("activate session")
+}
+
+#[test]
+fn workflow_text_parses_steer_and_task() {
+    assert_eq!(
+        ShortcutAction::from_workflow_text("steer  focus on the parser "),
+        Some((ShortcutAction::Steer, "focus on the parser"))
+    );
+    assert_eq!(
+        ShortcutAction::from_workflow_text("Task write release notes"),
+        Some((ShortcutAction::Task, "write release notes"))
+    );
+    assert_eq!(ShortcutAction::from_workflow_text("steer"), None);
+    assert_eq!(ShortcutAction::from_workflow_text("steer   "), None);
+    assert_eq!(ShortcutAction::from_workflow_text("deploy now"), None);
+}
+
+#[tokio::test]
+async fn message_shortcut_steers_thread_session() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+    let session = session_in_channel(&state, root).await;
+
+    shortcut::handle_message_shortcut(
+        shortcut::STEER_MESSAGE_SHORTCUT,
+        "U_OPERATOR",
+        "look at the flaky test first",
+        CHANNEL,
+        THREAD,
+        &state,
+    )
+    .await
+    .expect("shortcut");
+
+    let queued = SteeringRepo::new(Arc::clone(&state.db))
+        .fetch_unconsumed(&session.id)
+        .await
+        .expect("fetch");
+    assert_eq!(queued.len(), 1);
+    assert_eq!(queued[0].message, "look at the flaky test first");
+}
+
+#[tokio::test]
+async fn message_shortcut_creates_task() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+
+    shortcut::handle_message_shortcut(
+        shortcut::TASK_MESSAGE_SHORTCUT,
+        "U_OPERATOR",
+        "bump the MSRV",
+        CHANNEL,
+        THREAD,
+        &state,
+    )
+    .await
+    .expect("shortcut");
+
+    let tasks = InboxRepo::new(Arc::clone(&state.db))
+        .fetch_unconsumed_by_channel(Some(CHANNEL))
+        .await
+        .expect("fetch");
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].message, "bump the MSRV");
+}
+
+#[tokio::test]
+async fn workflow_message_requires_allow_listed_bot() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let mut config = test_config(root);
+    config.slack.workflow_bot_ids = vec!["B_WORKFLOW".into()];
+    let state = test_app_state(config).await;
+    let session = session_in_channel(&state, root).await;
+    let steering = SteeringRepo::new(Arc::clone(&state.db));
+
+    assert!(
+        !shortcut::handle_workflow_message(
+            "B_OTHER",
+            "steer stop and summarize",
+            CHANNEL,
+            "1700002100.000001",
+            &state,
+        )
+        .await
+    );
+    assert!(
+        !shortcut::handle_workflow_message(
+            "B_WORKFLOW",
+            "hello team",
+            CHANNEL,
+            "1700002100.000002",
+            &state,
+        )
+        .await
+    );
+    assert!(steering
+        .fetch_unconsumed(&session.id)
+        .await
+        .expect("fetch")
+        .is_empty());
+
+    assert!(
+        shortcut::handle_workflow_message(
+            "B_WORKFLOW",
+            "steer stop and summarize",
+            CHANNEL,
+            "1700002100.000003",
+            &state,
+        )
+        .await
+    );
+    let queued = steering.fetch_unconsumed(&session.id).await.expect("fetch");
+    assert_eq!(queued.len(), 1);
+    assert_eq!(queued[0].message, "stop and summarize");
+}
+
+#[tokio::test]
+async fn unknown_shortcut_is_reported() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+
+    let err = shortcut::handle_message_shortcut(
+        "intercom_unknown",
+        "U_OPERATOR",
+        "text",
+        CHANNEL,
+        THREAD,
+        &state,
+    )
+    .await
+    .expect_err("unknown callback");
+    assert!(err.to_string().contains("intercom_unknown"));
+}
@@ -0,0 +1,140 @@
+//! Integration tests for stale approvals detected by the file-change
+//! watcher.
+//!
+//! Tests cover:
+//! - A pending request whose file changes is rejected and marked stale
+//! - An approved request whose file changes is refused by `check_diff` as
+//!   `stale_diff` and the agent is steered to re-propose it
+//! - Requests whose file is unchanged are left alone
+
+use std::path::Path;
+use std::sync::Arc;
+
+use serde_json::json;
+
+use agent_intercom::mcp::tools::util::compute_file_hash;
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::orchestrator::file_change_watcher;
+use agent_intercom::persistence::approval_repo::ApprovalRepo;
+use agent_intercom::persistence::steering_repo::SteeringRepo;
+
+use super::test_helpers::{
+    connect_mcp_client_as, create_active_session, test_app_state, test_config,
+};
+
+async fn pending(
+    repo: &ApprovalRepo,
+    session_id: &str,
+    root: &Path,
+    file_path: &str,
+) -> ApprovalRequest {
+    let hash = compute_file_hash(&root.join(file_path))
+        .await
+        .expect("hash");
+    let request = ApprovalRequest::new(
+        session_id.into(),
+        "Update notes".into(),
+        None,
+        "proposed\n".into(),
+        file_path.into(),
+        RiskLevel::Low,
+        hash,
+    );
+    repo.create(&request).await.expect("create")
+}
+
+#[tokio::test]
+async fn changed_file_rejects_pending_request_as_stale() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    std::fs::write(temp.path().join("notes.txt"), "original\n").expect("seed");
+    let state = test_app_state(test_config(root)).await;
+    let session = create_active_session(&state.db, root).await;
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let approval = pending(&repo, &session.id, temp.path(), "notes.txt").await;
+
+    std::fs::write(temp.path().join("notes.txt"), "edited by hand\n").expect("edit");
+    let marked = file_change_watcher::check_unapplied(&state)
+        .await
+        .expect("check");
+    assert_eq!(marked, 1);
+
+    let record = repo
+        .get_by_id(&approval.id)
+        .await
+        .expect("query")
+        .expect("approval");
+    assert_eq!(record.status, ApprovalStatus::Rejected);
+    assert!(record.stale_at.is_some());
+
+    let again = file_change_watcher::check_unapplied(&state)
+        .await
+        .expect("recheck");
+    assert_eq!(again, 0, "a stale request is not reported twice");
+}
+
+#[tokio::test]
+async fn changed_file_makes_approved_request_stale_for_check_diff() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    std::fs::write(temp.path().join("notes.txt"), "original\n").expect("seed");
+    let state = test_app_state(test_config(root)).await;
+    let session = create_active_session(&state.db, root).await;
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let approval = pending(&repo, &session.id, temp.path(), "notes.txt").await;
+    repo.decide(&approval.id, ApprovalStatus::Approved)
+        .await
+        .expect("approve");
+
+    std::fs::write(temp.path().join("notes.txt"), "edited by hand\n").expect("edit");
+    file_change_watcher::check_unapplied(&state)
+        .await
+        .expect("check");
+
+    let record = repo
+        .get_by_id(&approval.id)
+        .await
+        .expect("query")
+        .expect("approval");
+    assert_eq!(record.status, ApprovalStatus::Approved);
+    assert!(record.stale_at.is_some());
+
+    let steering = SteeringRepo::new(Arc::clone(&state.db))
+        .fetch_unconsumed(&session.id)
+        .await
+        .expect("steering");
+    let notice = &steering.last().expect("agent notified").message;
+    assert!(notice.contains("is stale"), "{notice}");
+
+    let mut client = connect_mcp_client_as(&state, &session.id).await;
+    let result = client
+        .call_tool(2, "check_diff", json!({ "request_id": approval.id }))
+        .await;
+    assert_eq!(result["error_code"], "stale_diff", "{result}");
+    assert_eq!(
+        std::fs::read_to_string(temp.path().join("notes.txt")).expect("read"),
+        "edited by hand\n"
+    );
+}
+
+#[tokio::test]
+async fn unchanged_file_is_left_alone() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    std::fs::write(temp.path().join("notes.txt"), "original\n").expect("seed");
+    let state = test_app_state(test_config(root)).await;
+    let session = create_active_session(&state.db, root).await;
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let existing = pending(&repo, &session.id, temp.path(), "notes.txt").await;
+    let new_file = pending(&repo, &session.id, temp.path(), "new.txt").await;
+
+    let marked = file_change_watcher::check_unapplied(&state)
+        .await
+        .expect("check");
+    assert_eq!(marked, 0);
+    for id in [&existing.id, &new_file.id] {
+        let record = repo.get_by_id(id).await.expect("query").expect("approval");
+        assert_eq!(record.status, ApprovalStatus::Pending);
+        assert!(record.stale_at.is_none());
+    }
+}
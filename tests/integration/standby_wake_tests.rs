@@ -0,0 +1,26 @@
+//! Integration tests for scheduled standby wake-ups (`wake_at`).
+//!
+//! - a `wake_at` that is neither `HH:MM` nor a future RFC 3339 time is
+//!   rejected before the agent is parked
+
+use serde_json::json;
+
+use super::test_helpers::{connect_mcp_client, test_app_state, test_config};
+
+#[tokio::test]
+async fn standby_rejects_unusable_wake_at() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let state = test_app_state(test_config(temp.path().to_str().expect("utf8"))).await;
+    let (mut client, _session_id) = connect_mcp_client(&state).await;
+
+    for (id, wake_at) in [(2, "tomorrow"), (3, "25:00"), (4, "2020-01-01T08:00:00Z")] {
+        let response = client
+            .request(
+                id,
+                "tools/call",
+                json!({ "name": "standby", "arguments": { "wake_at": wake_at } }),
+            )
+            .await;
+        assert!(response["error"].is_object(), "{wake_at}: {response}");
+    }
+}
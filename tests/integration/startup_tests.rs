@@ -1,11 +1,17 @@
 //! Integration tests for server startup reliability (US2).
 //!
 //! Covers scenarios S023-S026: normal startup, port conflict detection,
-//! clean exit on bind failure.
+//! clean exit on bind failure; and the embeddable `ServerBuilder`.
+
+use std::sync::Arc;
 
 use agent_intercom::mcp::sse::bind_http;
+use agent_intercom::models::session::SessionStatus;
+use agent_intercom::persistence::db;
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::server::{ServerBuilder, Transport};
 
-use super::test_helpers::test_app_state;
+use super::test_helpers::{create_active_session, test_app_state, test_config};
 
 /// Create a config with `http_port` set to a specific value.
 fn config_with_port(port: u16) -> agent_intercom::config::GlobalConfig {
@@ -115,3 +121,71 @@ async fn second_bind_on_same_port_fails() {
     let result = bind_http(&state2).await;
     assert!(result.is_err(), "second bind on port {port} should fail");
 }
+
+// ── Embedded server (ServerBuilder) ──────────────────────────────────
+
+/// An embedded server uses the supplied database, recovers sessions left
+/// active by a previous run, serves HTTP, and shuts down on request.
+#[tokio::test]
+async fn server_builder_runs_embedded_server() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let database = Arc::new(db::connect_memory().await.expect("db connect"));
+    let stale = create_active_session(&database, root).await;
+
+    let server = ServerBuilder::new(test_config(root))
+        .load_credentials(false)
+        .database(Arc::clone(&database))
+        .transport(Transport::Sse)
+        .start()
+        .await
+        .expect("server starts");
+
+    assert!(
+        server.state().slack.is_none(),
+        "no bot token means no Slack"
+    );
+    let addr = server.http_addr().expect("http transport started");
+    assert_ne!(addr.port(), 0);
+    tokio::net::TcpStream::connect(addr)
+        .await
+        .expect("http transport accepts connections");
+
+    let sessions = SessionRepo::new(Arc::clone(&database));
+    let recovered = sessions
+        .get_by_id(&stale.id)
+        .await
+        .expect("query")
+        .expect("session exists");
+    assert_eq!(recovered.status, SessionStatus::Interrupted);
+
+    server.run_until(async {}).await.expect("clean shutdown");
+    assert!(
+        tokio::net::TcpStream::connect(addr).await.is_err(),
+        "http transport stopped"
+    );
+}
+
+/// `start` returns the bind error instead of exiting the process.
+#[tokio::test]
+async fn server_builder_fails_on_occupied_port() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let occupied = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("initial bind succeeds");
+    let port = occupied.local_addr().expect("has local addr").port();
+
+    let result = ServerBuilder::new(test_config(root))
+        .load_credentials(false)
+        .database(Arc::new(db::connect_memory().await.expect("db connect")))
+        .transport(Transport::Sse)
+        .http_port(port)
+        .start()
+        .await;
+
+    assert!(
+        matches!(result, Err(agent_intercom::AppError::Config(_))),
+        "expected a bind error"
+    );
+}
@@ -7,11 +7,13 @@
 //! - Channel-scoped routing (S007)
 //! - Concurrent messages stored in arrival order (S009)
 //! - Terminated-session messages remain unconsumed (S008)
+//! - Expired messages are dropped by the expiry sweep
 
 use std::sync::Arc;
 
 use agent_intercom::models::session::SessionStatus;
 use agent_intercom::models::steering::{SteeringMessage, SteeringSource};
+use agent_intercom::orchestrator::steering_expiry::expire_steering;
 use agent_intercom::persistence::session_repo::SessionRepo;
 use agent_intercom::persistence::steering_repo::SteeringRepo;
 
@@ -231,3 +233,40 @@ async fn steering_messages_stored_in_insertion_order() {
         assert_eq!(m.message, format!("message {i}"));
     }
 }
+
+// ── TTL: expiry sweep drops undelivered messages ────────────────────────
+
+#[tokio::test]
+async fn expiry_sweep_drops_expired_messages_only() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = test_app_state(test_config(root)).await;
+    let session = create_active_session(&state.db, root).await;
+    let repo = SteeringRepo::new(Arc::clone(&state.db));
+
+    let mut expired = SteeringMessage::new(
+        session.id.clone(),
+        None,
+        "too late".to_owned(),
+        SteeringSource::Slack,
+    );
+    expired.created_at -= chrono::Duration::minutes(2);
+    let expired = expired.with_ttl(chrono::Duration::minutes(1));
+    repo.insert(&expired).await.expect("insert expired");
+    let kept = SteeringMessage::new(
+        session.id.clone(),
+        None,
+        "still relevant".to_owned(),
+        SteeringSource::Slack,
+    )
+    .with_ttl(chrono::Duration::hours(1));
+    repo.insert(&kept).await.expect("insert kept");
+
+    let dropped = expire_steering(&state).await.expect("sweep");
+    assert_eq!(dropped.len(), 1);
+    assert_eq!(dropped[0].id, expired.id);
+
+    let pending = repo.fetch_unconsumed(&session.id).await.expect("fetch");
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, kept.id);
+}
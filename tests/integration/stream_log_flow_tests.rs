@@ -0,0 +1,94 @@
+//! Integration tests for the `stream_log` tool and its readers.
+//!
+//! Tests cover:
+//! - Lines an agent streams are buffered per session, not posted, and read
+//!   back by `/intercom logs` and the `intercom://session/{id}/logs`
+//!   resource
+//! - Invalid levels and oversized fields are rejected
+
+use agent_intercom::slack::commands::dispatch_command;
+use serde_json::json;
+
+use super::test_helpers::{connect_mcp_client, test_app_state, test_config};
+
+#[tokio::test]
+async fn streamed_lines_are_readable_by_command_and_resource() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+    let (mut client, session_id) = connect_mcp_client(&state).await;
+
+    let first = client
+        .call_tool(2, "stream_log", json!({ "message": "compiling crate" }))
+        .await;
+    assert_eq!(first, json!({ "status": "buffered", "buffered": 1 }));
+    let second = client
+        .call_tool(
+            3,
+            "stream_log",
+            json!({
+                "message": "test failed",
+                "level": "error",
+                "fields": { "test": "parses_config", "attempt": 2 }
+            }),
+        )
+        .await;
+    assert_eq!(second["buffered"], 2, "{second}");
+
+    let prefix: String = session_id.chars().take(8).collect();
+    let text = dispatch_command("logs", &[&prefix, "-n", "1"], "U_OP", "C_TEST", &state)
+        .await
+        .expect("logs command");
+    assert!(text.contains("last 1 of 2 buffered"), "{text}");
+    assert!(
+        text.contains("ERROR test failed attempt=2 test=parses_config"),
+        "{text}"
+    );
+    assert!(!text.contains("compiling crate"), "{text}");
+
+    let read = client
+        .request(
+            4,
+            "resources/read",
+            json!({ "uri": format!("intercom://session/{session_id}/logs") }),
+        )
+        .await;
+    let body = read["result"]["contents"][0]["text"]
+        .as_str()
+        .unwrap_or_else(|| panic!("resource contents expected: {read}"));
+    let logs: serde_json::Value = serde_json::from_str(body).expect("json");
+    assert_eq!(logs["session_id"], session_id.as_str());
+    assert_eq!(logs["lines"][0]["message"], "compiling crate");
+    assert_eq!(logs["lines"][0]["level"], "info");
+    assert_eq!(logs["lines"][1]["fields"]["test"], "parses_config");
+}
+
+#[tokio::test]
+async fn stream_log_rejects_bad_input() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+    let (mut client, session_id) = connect_mcp_client(&state).await;
+
+    let bad_level = client
+        .request(
+            2,
+            "tools/call",
+            json!({ "name": "stream_log", "arguments": { "message": "x", "level": "fatal" } }),
+        )
+        .await;
+    assert!(bad_level["error"].is_object(), "{bad_level}");
+
+    let big = client
+        .request(
+            3,
+            "tools/call",
+            json!({
+                "name": "stream_log",
+                "arguments": { "message": "x", "fields": { "blob": "a".repeat(5000) } }
+            }),
+        )
+        .await;
+    assert!(big["error"].is_object(), "{big}");
+    assert_eq!(state.session_logs.buffered(&session_id), 0);
+}
@@ -0,0 +1,77 @@
+//! Integration tests for the `spawn_subtask` tool.
+//!
+//! The approval wait itself needs Slack; these tests cover the checks that
+//! run before a request reaches the operator.
+//!
+//! Tests cover:
+//! - An empty prompt is rejected as invalid parameters
+//! - The concurrent session limit is enforced before asking the operator
+//! - Without Slack the request is refused instead of blocking
+
+use std::sync::Arc;
+
+use agent_intercom::persistence::approval_repo::ApprovalRepo;
+use serde_json::json;
+
+use super::test_helpers::{connect_mcp_client, test_app_state, test_config};
+
+#[tokio::test]
+async fn spawn_subtask_rejects_empty_prompt() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+    let (mut client, _sid) = connect_mcp_client(&state).await;
+
+    let response = client
+        .request(
+            2,
+            "tools/call",
+            json!({ "name": "spawn_subtask", "arguments": { "prompt": "  " } }),
+        )
+        .await;
+    assert!(
+        response["error"]["message"]
+            .as_str()
+            .is_some_and(|m| m.contains("prompt cannot be empty")),
+        "{response}"
+    );
+}
+
+#[tokio::test]
+async fn spawn_subtask_enforces_session_limit() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let mut config = test_config(root);
+    config.max_concurrent_sessions = 1;
+    let state = test_app_state(config).await;
+    let (mut client, sid) = connect_mcp_client(&state).await;
+
+    let result = client
+        .call_tool(2, "spawn_subtask", json!({ "prompt": "write the docs" }))
+        .await;
+    assert_eq!(result["status"], "error", "{result}");
+    assert_eq!(result["error_code"], "session_limit");
+    assert!(ApprovalRepo::new(Arc::clone(&state.db))
+        .list_for_session(&sid)
+        .await
+        .expect("list")
+        .is_empty());
+}
+
+#[tokio::test]
+async fn spawn_subtask_without_slack_is_refused() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let state = test_app_state(test_config(root)).await;
+    let (mut client, _sid) = connect_mcp_client(&state).await;
+
+    let result = client
+        .call_tool(
+            2,
+            "spawn_subtask",
+            json!({ "prompt": "write the docs", "title": "Docs" }),
+        )
+        .await;
+    assert_eq!(result["status"], "error", "{result}");
+    assert_eq!(result["error_code"], "slack_unavailable");
+}
@@ -1,11 +1,12 @@
 //! Shared test helpers for handler-level integration tests.
 //!
 //! Provides reusable construction of `AppState`, `GlobalConfig`,
-//! active sessions, and other prerequisites so individual test
-//! modules can focus on behaviour rather than boilerplate.
+//! active sessions, an in-memory MCP client, and other prerequisites so
+//! individual test modules can focus on behaviour rather than boilerplate.
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use agent_intercom::config::GlobalConfig;
 use agent_intercom::driver::mcp_driver::McpDriver;
@@ -15,7 +16,10 @@ use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
 use agent_intercom::persistence::db;
 use agent_intercom::persistence::session_repo::SessionRepo;
 use agent_intercom::state::AppState;
+use rmcp::ServiceExt;
+use serde_json::{json, Value};
 use sqlx::SqlitePool;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, DuplexStream, ReadHalf, WriteHalf};
 use tokio::sync::Mutex;
 
 /// Build a minimal `GlobalConfig` pointing at the given `workspace_root`
@@ -103,6 +107,27 @@ pub async fn test_app_state(config: GlobalConfig) -> Arc<AppState> {
         workspace_mappings: Arc::default(),
         acp_event_tx: None,
         acp_driver: None,
+        driver_registry: Arc::default(),
+        event_bus: Arc::default(),
+        session_hooks: Arc::default(),
+        pause_requests: Arc::default(),
+        heartbeats: Arc::default(),
+        instruction_queue: Arc::default(),
+        snoozes: Arc::default(),
+        apply_confirmations: Arc::default(),
+        session_logs: Arc::default(),
+        session_channels: Arc::default(),
+        workspace_discovery: Arc::default(),
+        config_path: None,
+        command_aliases: Arc::default(),
+        proxy: Arc::default(),
+        replay_guard: Arc::default(),
+        interactions: Arc::default(),
+        rearmed: Arc::default(),
+        diff_staging: Arc::default(),
+        subsystems: Arc::default(),
+        simulator: None,
+        log_filter: None,
     })
 }
 
@@ -130,6 +155,27 @@ pub fn test_app_state_with_db(config: GlobalConfig, db: Arc<SqlitePool>) -> Arc<
         workspace_mappings: Arc::default(),
         acp_event_tx: None,
         acp_driver: None,
+        driver_registry: Arc::default(),
+        event_bus: Arc::default(),
+        session_hooks: Arc::default(),
+        pause_requests: Arc::default(),
+        heartbeats: Arc::default(),
+        instruction_queue: Arc::default(),
+        snoozes: Arc::default(),
+        apply_confirmations: Arc::default(),
+        session_logs: Arc::default(),
+        session_channels: Arc::default(),
+        workspace_discovery: Arc::default(),
+        config_path: None,
+        command_aliases: Arc::default(),
+        proxy: Arc::default(),
+        replay_guard: Arc::default(),
+        interactions: Arc::default(),
+        rearmed: Arc::default(),
+        diff_staging: Arc::default(),
+        subsystems: Arc::default(),
+        simulator: None,
+        log_filter: None,
     })
 }
 
@@ -191,3 +237,133 @@ pub async fn create_interrupted_session(db: &Arc<SqlitePool>, workspace_root: &s
         .await
         .expect("interrupt session")
 }
+
+/// Client end of an in-memory MCP connection.
+pub struct McpTestClient {
+    reader: BufReader<ReadHalf<DuplexStream>>,
+    writer: WriteHalf<DuplexStream>,
+}
+
+impl McpTestClient {
+    pub async fn send(&mut self, msg: Value) {
+        let mut line = msg.to_string();
+        line.push('\n');
+        self.writer
+            .write_all(line.as_bytes())
+            .await
+            .expect("write to server");
+    }
+
+    pub async fn recv(&mut self) -> Value {
+        let mut line = String::new();
+        tokio::time::timeout(Duration::from_secs(2), self.reader.read_line(&mut line))
+            .await
+            .expect("server message within 2 s")
+            .expect("read from server");
+        serde_json::from_str(&line).expect("server sent valid json")
+    }
+
+    /// Send request `method` and return the response with the same `id`,
+    /// skipping any notifications sent before it.
+    pub async fn request(&mut self, id: u64, method: &str, params: Value) -> Value {
+        self.send(json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params
+        }))
+        .await;
+        loop {
+            let msg = self.recv().await;
+            if msg["id"] == id {
+                return msg;
+            }
+        }
+    }
+
+    /// Call tool `name` and return its first content item parsed as JSON,
+    /// skipping any notifications sent before the response.
+    pub async fn call_tool(&mut self, id: u64, name: &str, arguments: Value) -> Value {
+        let msg = self
+            .request(
+                id,
+                "tools/call",
+                json!({ "name": name, "arguments": arguments }),
+            )
+            .await;
+        let text = msg["result"]["content"][0]["text"]
+            .as_str()
+            .unwrap_or_else(|| panic!("tool result expected: {msg}"));
+        serde_json::from_str(text).expect("tool result is json")
+    }
+}
+
+/// Connect a client, complete the MCP handshake, and return it with the
+/// auto-created session ID once the session's driver is registered.
+pub async fn connect_mcp_client(state: &Arc<AppState>) -> (McpTestClient, String) {
+    connect_mcp_client_on(state, None).await
+}
+
+/// Like [`connect_mcp_client`], with a per-connection Slack channel override.
+pub async fn connect_mcp_client_on(
+    state: &Arc<AppState>,
+    channel_id: Option<&str>,
+) -> (McpTestClient, String) {
+    let client = handshake(test_server(Arc::clone(state), channel_id)).await;
+
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    for _ in 0..100 {
+        if let Some(session) = repo.list_active().await.expect("list").into_iter().next() {
+            if state.driver_registry.contains(&session.id).await {
+                return (client, session.id);
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("session was not registered after initialize");
+}
+
+/// Connect a client bound to the existing session `session_id`, as a
+/// spawned agent would, and complete the MCP handshake.
+pub async fn connect_mcp_client_as(state: &Arc<AppState>, session_id: &str) -> McpTestClient {
+    handshake(IntercomServer::with_overrides(
+        Arc::clone(state),
+        None,
+        Some(session_id.to_owned()),
+    ))
+    .await
+}
+
+/// Serve `server` over an in-memory transport and complete the handshake.
+async fn handshake(server: IntercomServer) -> McpTestClient {
+    let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+    tokio::spawn(async move {
+        if let Ok(service) = server.serve(tokio::io::split(server_io)).await {
+            let _ = service.waiting().await;
+        }
+    });
+
+    let (reader, writer) = tokio::io::split(client_io);
+    let mut client = McpTestClient {
+        reader: BufReader::new(reader),
+        writer,
+    };
+    client
+        .send(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": { "name": "test-client", "version": "0.0.0" }
+            }
+        }))
+        .await;
+    let init = client.recv().await;
+    assert_eq!(init["id"], 1, "initialize response expected: {init}");
+    client
+        .send(json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+        .await;
+    client
+}
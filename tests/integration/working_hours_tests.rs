@@ -0,0 +1,117 @@
+//! Integration tests for working-hours routing between a workspace's
+//! channels (`[workspace.working_hours]`).
+//!
+//! Covers:
+//! - A session in the workspace channel moves to the after-hours channel
+//!   outside working hours, and back within them
+//! - A session already in the right channel is left alone
+//! - A session moved to an unrelated channel is not routed
+
+use std::sync::{Arc, PoisonError};
+
+use agent_intercom::config::{WorkingHours, WorkspaceMapping};
+use agent_intercom::orchestrator::working_hours::route_session;
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::state::AppState;
+use chrono::{NaiveDate, NaiveDateTime};
+
+use super::test_helpers::{create_active_session, test_app_state, test_config};
+
+/// Wednesday 14 October 2026 at `hour`:00.
+fn wednesday_at(hour: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2026, 10, 14)
+        .and_then(|date| date.and_hms_opt(hour, 0, 0))
+        .expect("valid time")
+}
+
+async fn routed_state(root: &str) -> Arc<AppState> {
+    let state = test_app_state(test_config(root)).await;
+    state
+        .workspace_mappings
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push(WorkspaceMapping {
+            workspace_id: "team".into(),
+            channel_id: "C_TEAM".into(),
+            label: None,
+            path: None,
+            proxy: Vec::new(),
+            approval_channels: Vec::new(),
+            working_hours: Some(WorkingHours {
+                hours: "09:00-17:00".into(),
+                weekdays_only: true,
+                after_hours_channel_id: "C_ONCALL".into(),
+            }),
+            bootstrap: None,
+        });
+    state
+}
+
+async fn session_in(state: &AppState, root: &str, channel: &str) -> String {
+    let session = create_active_session(&state.db, root).await;
+    SessionRepo::new(Arc::clone(&state.db))
+        .rebind_channel(&session.id, channel, None)
+        .await
+        .expect("bind channel");
+    session.id
+}
+
+async fn channel_of(state: &AppState, session_id: &str) -> Option<String> {
+    SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(session_id)
+        .await
+        .expect("get session")
+        .and_then(|session| session.channel_id)
+}
+
+#[tokio::test]
+async fn session_moves_to_after_hours_channel_and_back() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let root = root.path().to_str().expect("utf8 path");
+    let state = routed_state(root).await;
+    let id = session_in(&state, root, "C_TEAM").await;
+
+    let moved = route_session(&state, &id, wednesday_at(20))
+        .await
+        .expect("route");
+    assert_eq!(moved.as_deref(), Some("C_ONCALL"));
+    assert_eq!(channel_of(&state, &id).await.as_deref(), Some("C_ONCALL"));
+    assert_eq!(state.session_channels.get(&id).as_deref(), Some("C_ONCALL"));
+
+    let moved = route_session(&state, &id, wednesday_at(10))
+        .await
+        .expect("route");
+    assert_eq!(moved.as_deref(), Some("C_TEAM"));
+    assert_eq!(channel_of(&state, &id).await.as_deref(), Some("C_TEAM"));
+}
+
+#[tokio::test]
+async fn session_in_right_channel_is_not_moved() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let root = root.path().to_str().expect("utf8 path");
+    let state = routed_state(root).await;
+    let id = session_in(&state, root, "C_TEAM").await;
+
+    let moved = route_session(&state, &id, wednesday_at(10))
+        .await
+        .expect("route");
+    assert!(moved.is_none());
+    assert!(state.session_channels.get(&id).is_none());
+}
+
+#[tokio::test]
+async fn session_in_unrelated_channel_is_not_routed() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let root = root.path().to_str().expect("utf8 path");
+    let state = routed_state(root).await;
+    let id = session_in(&state, root, "C_ELSEWHERE").await;
+
+    let moved = route_session(&state, &id, wednesday_at(20))
+        .await
+        .expect("route");
+    assert!(moved.is_none());
+    assert_eq!(
+        channel_of(&state, &id).await.as_deref(),
+        Some("C_ELSEWHERE")
+    );
+}
@@ -263,6 +263,10 @@ async fn config_reload_concurrent_with_session_creation_is_consistent() {
             channel_id: "C-ORIGINAL".to_owned(),
             label: None,
             path: None,
+            proxy: Vec::new(),
+            approval_channels: Vec::new(),
+            working_hours: None,
+            bootstrap: None,
         }]));
 
     let mut reader_handles = Vec::new();
@@ -294,6 +298,10 @@ async fn config_reload_concurrent_with_session_creation_is_consistent() {
             channel_id: "C-RELOADED".to_owned(),
             label: None,
             path: None,
+            proxy: Vec::new(),
+            approval_channels: Vec::new(),
+            working_hours: None,
+            bootstrap: None,
         }];
     });
 
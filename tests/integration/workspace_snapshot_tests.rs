@@ -0,0 +1,191 @@
+//! Integration tests for workspace snapshots and restore.
+//!
+//! Tests cover:
+//! - `workspace-snapshot` archives the workspace without excluded paths
+//! - `workspace-restore` writes back changed and deleted files, removes
+//!   added ones, and snapshots the state it replaced
+//! - Only the newest `retention` snapshots are kept
+//! - A damaged archive is refused before the workspace is touched
+//! - A restore refuses to write through a symlink leading out of the
+//!   workspace
+
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use agent_intercom::models::snapshot::WorkspaceSnapshot;
+use agent_intercom::orchestrator::workspace_snapshot;
+use agent_intercom::persistence::snapshot_repo::SnapshotRepo;
+use agent_intercom::slack::commands::dispatch_command;
+use agent_intercom::state::AppState;
+
+use super::test_helpers::{test_app_state, test_config};
+
+fn seed(root: &Path) {
+    fs::create_dir_all(root.join("src")).expect("mkdir");
+    fs::create_dir_all(root.join("target/debug")).expect("mkdir");
+    fs::write(root.join("src/main.rs"), "fn main() {}\n").expect("write");
+    fs::write(root.join("README.md"), "hello\n").expect("write");
+    fs::write(root.join("target/debug/app"), "binary").expect("write");
+}
+
+async fn snapshots(state: &AppState, root: &str) -> Vec<WorkspaceSnapshot> {
+    SnapshotRepo::new(Arc::clone(&state.db))
+        .list_for_workspace(root)
+        .await
+        .expect("list")
+}
+
+#[tokio::test]
+async fn snapshot_and_restore_round_trip() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    seed(temp.path());
+    let state = test_app_state(test_config(root)).await;
+
+    let reply = dispatch_command(
+        "workspace-snapshot",
+        &["before", "migration"],
+        "U_TEST_OWNER",
+        "C_TEST",
+        &state,
+    )
+    .await
+    .expect("snapshot");
+    assert!(reply.contains("2 files"), "{reply}");
+    let taken = snapshots(&state, root).await.remove(0);
+    assert_eq!(taken.label.as_deref(), Some("before migration"));
+    assert!(workspace_snapshot::archive_path(temp.path(), &taken.id).is_file());
+
+    fs::write(temp.path().join("src/main.rs"), "broken").expect("edit");
+    fs::remove_file(temp.path().join("README.md")).expect("delete");
+    fs::write(temp.path().join("src/extra.rs"), "// new").expect("add");
+    fs::write(temp.path().join("target/debug/app"), "rebuilt").expect("rebuild");
+
+    let reply = dispatch_command(
+        "workspace-restore",
+        &[workspace_snapshot::display_id(&taken)],
+        "U_TEST_OWNER",
+        "C_TEST",
+        &state,
+    )
+    .await
+    .expect("restore");
+    assert!(reply.contains("2 files written back, 1 removed"), "{reply}");
+
+    assert_eq!(
+        fs::read_to_string(temp.path().join("src/main.rs")).expect("read"),
+        "fn main() {}\n"
+    );
+    assert_eq!(
+        fs::read_to_string(temp.path().join("README.md")).expect("read"),
+        "hello\n"
+    );
+    assert!(!temp.path().join("src/extra.rs").exists());
+    assert_eq!(
+        fs::read_to_string(temp.path().join("target/debug/app")).expect("read"),
+        "rebuilt",
+        "excluded paths are left alone"
+    );
+
+    let all = snapshots(&state, root).await;
+    assert_eq!(all.len(), 2);
+    let safety = &all[0];
+    assert!(
+        safety
+            .label
+            .as_deref()
+            .is_some_and(|l| l.starts_with("before restore of")),
+        "{safety:?}"
+    );
+    assert_eq!(safety.file_count, 2);
+
+    let listing = dispatch_command("workspace-snapshots", &[], "U_TEST_OWNER", "C_TEST", &state)
+        .await
+        .expect("list");
+    assert!(listing.contains("before migration"), "{listing}");
+}
+
+#[tokio::test]
+async fn snapshots_beyond_retention_are_deleted() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    seed(temp.path());
+    let mut config = test_config(root);
+    config.snapshots.retention = 2;
+    let state = test_app_state(config).await;
+
+    let mut taken = Vec::new();
+    for label in ["one", "two", "three"] {
+        taken.push(
+            workspace_snapshot::create(
+                &state,
+                temp.path(),
+                Some(label.into()),
+                "U_TEST_OWNER",
+                None,
+            )
+            .await
+            .expect("snapshot"),
+        );
+    }
+
+    let kept = snapshots(&state, root).await;
+    let labels: Vec<_> = kept.iter().filter_map(|s| s.label.as_deref()).collect();
+    assert_eq!(labels, ["three", "two"]);
+    assert!(!workspace_snapshot::archive_path(temp.path(), &taken[0].id).exists());
+    assert!(!workspace_snapshot::manifest_path(temp.path(), &taken[0].id).exists());
+}
+
+#[tokio::test]
+async fn damaged_snapshot_is_refused_before_touching_the_workspace() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    seed(temp.path());
+    let state = test_app_state(test_config(root)).await;
+
+    let taken = workspace_snapshot::create(&state, temp.path(), None, "U_TEST_OWNER", None)
+        .await
+        .expect("snapshot");
+    let manifest = workspace_snapshot::manifest_path(temp.path(), &taken.id);
+    let tampered =
+        fs::read_to_string(&manifest)
+            .expect("manifest")
+            .replacen(':', ": \"0\", \"x\":", 1);
+    fs::write(&manifest, tampered).expect("tamper");
+    fs::write(temp.path().join("src/extra.rs"), "// new").expect("add");
+
+    let err = workspace_snapshot::restore(&state, &taken.id, "U_TEST_OWNER")
+        .await
+        .expect_err("damaged");
+    assert!(err.to_string().contains("damaged"), "{err}");
+    assert!(temp.path().join("src/extra.rs").exists());
+    assert_eq!(snapshots(&state, root).await.len(), 1, "no safety snapshot");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn restore_refuses_to_write_through_an_escaping_symlink() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let outside = tempfile::tempdir().expect("outside");
+    let root = temp.path().to_str().expect("utf8");
+    seed(temp.path());
+    let state = test_app_state(test_config(root)).await;
+
+    let taken = workspace_snapshot::create(&state, temp.path(), None, "U_TEST_OWNER", None)
+        .await
+        .expect("snapshot");
+    fs::remove_dir_all(temp.path().join("src")).expect("remove src");
+    fs::write(outside.path().join("main.rs"), "outside").expect("write outside");
+    std::os::unix::fs::symlink(outside.path(), temp.path().join("src")).expect("symlink");
+
+    let err = workspace_snapshot::restore(&state, &taken.id, "U_TEST_OWNER")
+        .await
+        .expect_err("escaping symlink");
+    assert!(err.to_string().contains("symlink"), "{err}");
+    assert_eq!(
+        fs::read_to_string(outside.path().join("main.rs")).expect("read"),
+        "outside",
+        "nothing written outside the workspace"
+    );
+}
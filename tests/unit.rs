@@ -8,8 +8,12 @@ mod unit {
     mod acp_permission_tests;
     mod acp_reader_steering_delivery;
     mod acp_session_tests;
+    mod apply_guard_tests;
+    mod approval_conflict_tests;
     mod approval_repo_tests;
+    mod approval_stats_tests;
     mod ask_approval_tests;
+    mod audit_reader_tests;
     mod audit_tests;
     mod audit_writer_tests;
     mod blocks_approval_tests;
@@ -18,43 +22,103 @@ mod unit {
     mod blocks_session_tests;
     mod blocks_stall_tests;
     mod blocks_tests;
+    mod broadcast_routing_tests;
+    #[cfg(feature = "chaos")]
+    mod chaos_tests;
     mod checkpoint_tests;
     mod child_monitor_tests;
     mod cli_tests;
+    mod command_alias_tests;
     mod command_approve_tests;
     mod command_exec_tests;
     mod command_routing_tests;
     mod command_tests;
+    mod config_env_tests;
+    mod config_profile_tests;
     mod config_tests;
+    mod conflict_repo_tests;
+    mod context_repo_tests;
     mod correlation_id_uniqueness;
     mod credential_loading_tests;
+    mod decisions_command_tests;
+    mod diff_context_tests;
+    mod diff_snippets_tests;
     mod diff_tests;
+    mod driver_registry_tests;
     mod driver_trait_tests;
     mod error_tests;
+    mod event_bus_tests;
+    mod heartbeat_ledger_tests;
     mod heartbeat_tests;
+    mod hunk_nav_tests;
     mod inbox_repo_tests;
+    mod instruction_queue_tests;
+    mod interaction_queue_tests;
     mod intercom_queue_command_tests;
     mod intercom_queue_tests;
+    mod ipc_security_tests;
+    mod issue_ref_tests;
+    mod knowledge_repo_tests;
+    mod lock_repo_tests;
+    mod log_filter_tests;
+    mod log_rotation_tests;
+    mod merge_view_tests;
     mod mode_routing_tests;
     mod model_tests;
+    mod mute_command_tests;
     mod offline_queue_tests;
+    mod ownership_tests;
     mod path_validation_tests;
+    mod policy_drift_tests;
     mod policy_evaluator_tests;
     mod policy_tests;
+    mod preferences_tests;
+    mod prompt_policy_tests;
     mod prompt_repo_tests;
+    mod queue_alarm_tests;
+    mod rejection_feedback_tests;
+    mod relay_repo_tests;
+    mod run_repo_tests;
+    mod scheduled_apply_tests;
+    mod session_bulk_tests;
+    mod session_context_resource_tests;
+    mod session_history_tests;
+    mod session_hooks_tests;
+    mod session_logs_tests;
     mod session_model_tests;
+    mod session_move_tests;
     mod session_repo_count_acp;
     mod session_repo_tests;
+    mod session_report_tests;
     mod session_routing_tests;
     mod session_status;
+    mod session_summary_email_tests;
+    mod session_timeline_tests;
+    mod simulated_operator_tests;
+    mod slack_anchor_tests;
     mod slack_client_tests;
+    mod slack_history_cache_tests;
+    mod slack_rate_budget_tests;
+    mod slack_replay_tests;
+    mod slack_socket_health_tests;
     mod slack_thread_mention_routing;
+    mod snooze_tests;
     mod sse_workspace_only_routing;
     mod stall_consumer_tests;
     mod stall_detector_tests;
     mod stall_repo_tests;
+    mod stalls_command_tests;
+    mod steer_options_tests;
     mod steering_repo_tests;
+    mod subtask_report_tests;
+    mod test_helpers;
+    #[cfg(feature = "test-support")]
+    mod test_support_tests;
     mod thread_reply_fallback;
     mod version_tests;
+    mod watchdog_tests;
+    mod workspace_bootstrap_tests;
+    mod workspace_command_tests;
+    mod workspace_discovery_tests;
     mod workspace_mapping_tests;
 }
@@ -287,6 +287,8 @@ async fn outbound_messages_have_monotonic_sequence_numbers() {
         cancel,
         Arc::clone(&counter),
         Arc::clone(&pool),
+        std::time::Duration::from_secs(5),
+        Arc::default(),
     ));
 
     let reader_handle = tokio::spawn(async move {
@@ -368,6 +370,8 @@ async fn write_failure_returns_acp_error() {
         cancel,
         counter,
         Arc::clone(&pool),
+        std::time::Duration::from_secs(5),
+        Arc::default(),
     )
     .await;
 
@@ -377,6 +381,145 @@ async fn write_failure_returns_acp_error() {
     );
 }
 
+// ── Bounded writer queue: write timeout, overflow policy, metrics ──────────
+
+/// A write that never completes (agent stopped reading stdin) must time out
+/// and return `AppError::Acp` instead of blocking forever.
+#[tokio::test]
+async fn stuck_stdin_write_times_out() {
+    use agent_intercom::acp::writer::{run_writer, WriterMetrics};
+    use agent_intercom::persistence::db;
+    use agent_intercom::AppError;
+    use serde_json::json;
+    use std::sync::{atomic::AtomicU64, Arc};
+    use tokio::sync::mpsc;
+    use tokio_util::sync::CancellationToken;
+
+    let pool = Arc::new(db::connect_memory().await.expect("in-memory db"));
+
+    // Keep the read side alive but never read from it: a 1-byte duplex buffer
+    // fills immediately and the next write parks forever.
+    let (_read_side, write_side) = tokio::io::duplex(1);
+    let (msg_tx, msg_rx) = mpsc::channel(10);
+    let metrics = Arc::new(WriterMetrics::default());
+
+    msg_tx
+        .send(json!({"method": "test/stuck", "padding": "x".repeat(64)}))
+        .await
+        .expect("send must succeed");
+
+    let result = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        run_writer(
+            "sess-stuck".to_owned(),
+            write_side,
+            msg_rx,
+            CancellationToken::new(),
+            Arc::new(AtomicU64::new(0)),
+            Arc::clone(&pool),
+            std::time::Duration::from_millis(100),
+            Arc::clone(&metrics),
+        ),
+    )
+    .await
+    .expect("writer must not block past its write timeout");
+
+    assert!(
+        matches!(result, Err(AppError::Acp(ref msg)) if msg.contains("timed out")),
+        "stuck write must return AppError::Acp mentioning the timeout, got: {result:?}"
+    );
+    assert_eq!(metrics.snapshot().write_timeouts, 1);
+}
+
+/// With the `reject` overflow policy a full queue fails immediately and the
+/// rejection is counted.
+#[tokio::test]
+async fn enqueue_reject_policy_fails_fast_when_full() {
+    use agent_intercom::acp::writer::{enqueue, WriterLimits, WriterMetrics};
+    use agent_intercom::config::AcpWriterOverflow;
+    use agent_intercom::AppError;
+    use serde_json::json;
+    use tokio::sync::mpsc;
+
+    let limits = WriterLimits {
+        queue_capacity: 1,
+        write_timeout: std::time::Duration::from_secs(30),
+        overflow: AcpWriterOverflow::Reject,
+    };
+    let metrics = WriterMetrics::default();
+    let (tx, _rx) = mpsc::channel(limits.queue_capacity);
+
+    enqueue(&tx, json!({"n": 1}), &limits, &metrics, "sess-full")
+        .await
+        .expect("first message fits");
+    let result = enqueue(&tx, json!({"n": 2}), &limits, &metrics, "sess-full").await;
+
+    assert!(
+        matches!(result, Err(AppError::Acp(ref msg)) if msg.contains("queue full")),
+        "full queue must be rejected, got: {result:?}"
+    );
+    let snap = metrics.snapshot();
+    assert_eq!(snap.enqueued, 1);
+    assert_eq!(snap.rejected, 1);
+    assert_eq!(snap.depth, 1);
+    assert_eq!(snap.high_water, 1);
+}
+
+/// With the `wait` overflow policy a full queue waits at most the write
+/// timeout before failing.
+#[tokio::test]
+async fn enqueue_wait_policy_times_out_when_full() {
+    use agent_intercom::acp::writer::{enqueue, WriterLimits, WriterMetrics};
+    use agent_intercom::config::AcpWriterOverflow;
+    use agent_intercom::AppError;
+    use serde_json::json;
+    use tokio::sync::mpsc;
+
+    let limits = WriterLimits {
+        queue_capacity: 1,
+        write_timeout: std::time::Duration::from_millis(50),
+        overflow: AcpWriterOverflow::Wait,
+    };
+    let metrics = WriterMetrics::default();
+    let (tx, _rx) = mpsc::channel(limits.queue_capacity);
+
+    enqueue(&tx, json!({"n": 1}), &limits, &metrics, "sess-wait")
+        .await
+        .expect("first message fits");
+    let result = enqueue(&tx, json!({"n": 2}), &limits, &metrics, "sess-wait").await;
+
+    assert!(
+        matches!(result, Err(AppError::Acp(ref msg)) if msg.contains("queue full")),
+        "saturated queue must time out, got: {result:?}"
+    );
+    assert_eq!(metrics.snapshot().enqueue_timeouts, 1);
+}
+
+/// Enqueueing onto a closed channel reports a write failure.
+#[tokio::test]
+async fn enqueue_closed_channel_returns_write_failed() {
+    use agent_intercom::acp::writer::{enqueue, WriterLimits, WriterMetrics};
+    use agent_intercom::AppError;
+    use serde_json::json;
+    use tokio::sync::mpsc;
+
+    let limits = WriterLimits::default();
+    let metrics = WriterMetrics::default();
+    let (tx, rx) = mpsc::channel(4);
+    drop(rx);
+
+    let result = enqueue(&tx, json!({"n": 1}), &limits, &metrics, "sess-closed").await;
+    assert!(
+        matches!(result, Err(AppError::Acp(ref msg)) if msg.contains("write failed")),
+        "closed channel must return write failed, got: {result:?}"
+    );
+    assert_eq!(
+        metrics.snapshot().depth,
+        0,
+        "failed enqueue must release its slot"
+    );
+}
+
 // ── T143 (S104, S105, S106): Token-bucket rate limiter ───────────────────────
 
 /// S104 — Normal traffic at or below `max_rate` messages per second is always
@@ -6,7 +6,9 @@
 //! - T035 (S025): startup timeout kills the process if no ready signal arrives
 //! - T036 (S026): empty prompt is rejected by `handshake::send_prompt`
 //! - T037b (S075): spawned process does NOT inherit `SLACK_BOT_TOKEN`
+//! - Operator-set `session-start --env` variables reach the process
 
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use tokio::sync::mpsc;
@@ -24,6 +26,7 @@ fn echo_config() -> SpawnConfig {
         host_cli: echo_exe(),
         host_cli_args: Vec::new(),
         workspace_root: std::env::temp_dir(),
+        env: BTreeMap::new(),
     }
 }
 
@@ -58,14 +61,15 @@ fn echo_args() -> Vec<String> {
 /// driver interrupt path is reachable without panicking.
 #[tokio::test]
 async fn acp_session_stop_terminates_child_process() {
-    use agent_intercom::driver::mcp_driver::McpDriver;
+    use agent_intercom::driver::acp_driver::AcpDriver;
+    use agent_intercom::driver::AgentDriver;
 
     // SpawnConfig import proves the spawner types are accessible.
     let _config = echo_config();
 
     // For ACP sessions the orchestrator calls driver.interrupt(session_id).
-    // McpDriver's interrupt is idempotent — unknown sessions return Ok(()).
-    let driver = McpDriver::new_empty();
+    // AcpDriver's interrupt is idempotent — unknown sessions return Ok(()).
+    let driver = AcpDriver::new();
     let result = driver.interrupt("acp-session-stop-test").await;
     assert!(
         result.is_ok(),
@@ -244,6 +248,29 @@ async fn crash_with_pending_clearance_resolves_as_timeout() {
     );
 }
 
+// ── session-start --env ──────────────────────────────────────────────────────
+
+/// Variables in `SpawnConfig::env` are set on the agent process.
+#[cfg(unix)]
+#[tokio::test]
+async fn operator_env_reaches_the_process() {
+    use tokio::io::AsyncBufReadExt;
+
+    let mut config = echo_config();
+    config.host_cli_args = vec!["-c".to_owned(), "echo \"$NODE_ENV\"".to_owned()];
+    config
+        .env
+        .insert("NODE_ENV".to_owned(), "staging".to_owned());
+
+    let mut conn = spawn_agent(&config, "session-env").expect("spawn");
+    let mut line = String::new();
+    tokio::time::timeout(Duration::from_secs(5), conn.stdout.read_line(&mut line))
+        .await
+        .expect("output in time")
+        .expect("read");
+    assert_eq!(line.trim(), "staging");
+}
+
 // ── T150 (S109, S110): Session Active in DB before reader processes events ────
 
 /// S109 — The session DB record must be committed as `active` before the ACP
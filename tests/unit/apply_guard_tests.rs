@@ -0,0 +1,55 @@
+//! Unit tests for protected-path apply confirmations.
+//!
+//! Validates:
+//! - A registered wait receives the operator's decision
+//! - Unanswered waits time out and are forgotten
+//! - Decisions with nobody waiting are reported as such
+//! - Diff line counts for unified diffs and whole-file content
+
+use std::time::Duration;
+
+use agent_intercom::orchestrator::apply_guard::{diff_stats, ApplyConfirmations, ApplyDecision};
+
+#[tokio::test]
+async fn wait_receives_decision() {
+    let confirmations = ApplyConfirmations::default();
+    let rx = confirmations.register("req-1");
+    assert!(confirmations.is_pending("req-1"));
+
+    let decision = ApplyDecision::Confirmed {
+        user_id: "U1".into(),
+    };
+    assert!(confirmations.resolve("req-1", decision.clone()));
+    let received = confirmations
+        .wait("req-1", rx, Duration::from_secs(1))
+        .await;
+    assert_eq!(received, Some(decision));
+    assert!(!confirmations.is_pending("req-1"));
+}
+
+#[tokio::test]
+async fn unanswered_wait_times_out() {
+    let confirmations = ApplyConfirmations::default();
+    let rx = confirmations.register("req-1");
+    let received = confirmations
+        .wait("req-1", rx, Duration::from_millis(20))
+        .await;
+    assert_eq!(received, None);
+    assert!(!confirmations.is_pending("req-1"));
+
+    let late = ApplyDecision::Cancelled {
+        user_id: "U1".into(),
+    };
+    assert!(!confirmations.resolve("req-1", late));
+}
+
+#[test]
+fn diff_stats_counts_changed_lines() {
+    let diff = "--- a/deploy.yaml\n+++ b/deploy.yaml\n@@ -1,2 +1,3 @@\n replicas: 2\n-image: v1\n\
+                +image: v2\n+tag: stable\n";
+    assert_eq!(diff_stats(diff), (2, 1));
+    assert_eq!(
+        diff_stats("CREATE TABLE t (id INT);\nDROP TABLE u;\n"),
+        (2, 0)
+    );
+}
@@ -0,0 +1,126 @@
+//! Unit tests for approval conflicts between concurrent sessions.
+//!
+//! Tests cover:
+//! - Pending, approved and applied requests for the same file from other
+//!   sessions in the workspace conflict; the caller's own, other files,
+//!   other workspaces and rejected requests do not
+//! - Approved and applied requests stop conflicting after the window
+//! - Each conflict is described on its own warning line
+
+use std::sync::Arc;
+
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::models::session::{Session, SessionMode};
+use agent_intercom::orchestrator::approval_conflicts::{self, RECENT_WINDOW_MINUTES};
+use agent_intercom::persistence::{approval_repo::ApprovalRepo, db, session_repo::SessionRepo};
+use chrono::{Duration, Utc};
+
+async fn session(db: &Arc<db::Database>, root: &str) -> Session {
+    SessionRepo::new(Arc::clone(db))
+        .create(&Session::new(
+            "U1".into(),
+            root.into(),
+            None,
+            SessionMode::Remote,
+        ))
+        .await
+        .expect("session")
+}
+
+async fn request(
+    db: &Arc<db::Database>,
+    session: &Session,
+    file: &str,
+    status: ApprovalStatus,
+) -> ApprovalRequest {
+    let repo = ApprovalRepo::new(Arc::clone(db));
+    let created = repo
+        .create(&ApprovalRequest::new(
+            session.id.clone(),
+            format!("edit {file}"),
+            None,
+            "+x".into(),
+            file.into(),
+            RiskLevel::Low,
+            "hash".into(),
+        ))
+        .await
+        .expect("request");
+    if status != ApprovalStatus::Pending {
+        let decided = if status == ApprovalStatus::Consumed {
+            ApprovalStatus::Approved
+        } else {
+            status
+        };
+        repo.update_status(&created.id, decided)
+            .await
+            .expect("status");
+    }
+    if status == ApprovalStatus::Consumed {
+        repo.mark_consumed(&created.id).await.expect("consume");
+    }
+    created
+}
+
+#[tokio::test]
+async fn same_file_requests_from_other_sessions_conflict() {
+    let db = Arc::new(db::connect_memory().await.expect("db"));
+    let me = session(&db, "/repo").await;
+    let peer = session(&db, "/repo").await;
+    let elsewhere = session(&db, "/other").await;
+
+    let pending = request(&db, &peer, "src/lib.rs", ApprovalStatus::Pending).await;
+    let applied = request(&db, &peer, "src/lib.rs", ApprovalStatus::Consumed).await;
+    request(&db, &peer, "src/lib.rs", ApprovalStatus::Rejected).await;
+    request(&db, &peer, "src/main.rs", ApprovalStatus::Pending).await;
+    request(&db, &me, "src/lib.rs", ApprovalStatus::Pending).await;
+    request(&db, &elsewhere, "src/lib.rs", ApprovalStatus::Pending).await;
+
+    let now = Utc::now();
+    let conflicts = approval_conflicts::find(&db, &me, "src/lib.rs", now)
+        .await
+        .expect("find");
+    let mut ids: Vec<&str> = conflicts.iter().map(|c| c.request.id.as_str()).collect();
+    ids.sort_unstable();
+    let mut expected = vec![pending.id.as_str(), applied.id.as_str()];
+    expected.sort_unstable();
+    assert_eq!(ids, expected);
+    assert!(conflicts
+        .iter()
+        .all(|c| c.session_short_id == peer.short_id));
+
+    let later = now + Duration::minutes(RECENT_WINDOW_MINUTES + 1);
+    let conflicts = approval_conflicts::find(&db, &me, "src/lib.rs", later)
+        .await
+        .expect("find");
+    assert_eq!(conflicts.len(), 1, "only the pending request remains");
+    assert_eq!(conflicts[0].request.id, pending.id);
+}
+
+#[tokio::test]
+async fn conflicts_are_described_one_per_line() {
+    let db = Arc::new(db::connect_memory().await.expect("db"));
+    let me = session(&db, "/repo").await;
+    let peer = session(&db, "/repo").await;
+    let pending = request(&db, &peer, "src/lib.rs", ApprovalStatus::Pending).await;
+    let approved = request(&db, &peer, "src/lib.rs", ApprovalStatus::Approved).await;
+
+    assert_eq!(approval_conflicts::describe(&[]), None);
+    let conflicts = approval_conflicts::find(&db, &me, "src/lib.rs", Utc::now())
+        .await
+        .expect("find");
+    let text = approval_conflicts::describe(&conflicts).expect("warning");
+    assert_eq!(text.lines().count(), 2, "{text}");
+    assert!(text.contains(&format!(
+        "Session `{}` also has a pending change to this file (`{}`)",
+        peer.short_id, pending.short_id
+    )));
+    assert!(text.contains(&format!(
+        "approved change to this file that may not be applied yet (`{}`)",
+        approved.short_id
+    )));
+    assert_eq!(
+        approval_conflicts::warning(&db, &me, "src/lib.rs").await,
+        Some(text)
+    );
+}
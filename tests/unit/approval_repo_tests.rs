@@ -6,6 +6,10 @@
 //! - `update_status` transitions and `get_pending_for_session`
 //! - `mark_consumed` sets `consumed_at` and enforces single-use
 //! - Double-consume returns `AlreadyConsumed` error
+//! - `set_slack_anchor` records the request message and its thread
+//! - `list_decided` returns decided requests newest first, per session
+//! - `resolve` accepts the UUID, the short ID, or a unique prefix of either
+//! - Fanned-out approval messages are tracked and each resolves only once
 
 use std::sync::Arc;
 
@@ -82,6 +86,27 @@ async fn update_status_changes_status() {
     assert_eq!(fetched.status, ApprovalStatus::Approved);
 }
 
+#[tokio::test]
+async fn set_slack_anchor_records_message_and_thread() {
+    let db = db::connect_memory().await.expect("db");
+    let repo = ApprovalRepo::new(Arc::new(db));
+
+    let req = sample_request("sess-anchor");
+    let id = req.id.clone();
+    repo.create(&req).await.expect("create");
+    let fetched = repo.get_by_id(&id).await.expect("query").expect("exists");
+    assert!(fetched.slack_ts.is_none());
+    assert!(fetched.thread_ts.is_none());
+
+    repo.set_slack_anchor(&id, "1700000000.000200", "1700000000.000100")
+        .await
+        .expect("anchor");
+
+    let fetched = repo.get_by_id(&id).await.expect("query").expect("exists");
+    assert_eq!(fetched.slack_ts.as_deref(), Some("1700000000.000200"));
+    assert_eq!(fetched.thread_ts.as_deref(), Some("1700000000.000100"));
+}
+
 #[tokio::test]
 async fn get_pending_for_session_returns_pending_only() {
     let db = db::connect_memory().await.expect("db");
@@ -232,3 +257,120 @@ async fn pending_clearance_survives_db_restart() {
     assert_eq!(restored.id, saved_id);
     assert_eq!(restored.status, ApprovalStatus::Pending);
 }
+
+#[tokio::test]
+async fn list_decided_returns_newest_decisions_first() {
+    let db = db::connect_memory().await.expect("db");
+    let repo = ApprovalRepo::new(Arc::new(db));
+
+    let mut ids = Vec::new();
+    for (offset, status) in [
+        (30, ApprovalStatus::Approved),
+        (20, ApprovalStatus::Rejected),
+        (10, ApprovalStatus::Pending),
+        (5, ApprovalStatus::Expired),
+    ] {
+        let mut req = sample_request("sess-a");
+        req.created_at -= chrono::Duration::minutes(offset);
+        repo.create(&req).await.expect("create");
+        repo.update_status(&req.id, status).await.expect("status");
+        ids.push(req.id);
+    }
+    let other = sample_request("sess-b");
+    repo.create(&other).await.expect("create");
+    repo.update_status(&other.id, ApprovalStatus::Approved)
+        .await
+        .expect("status");
+    repo.mark_consumed(&other.id).await.expect("consume");
+
+    let all = repo.list_decided(None, 10).await.expect("list");
+    let all_ids: Vec<&str> = all.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(all_ids, [other.id.as_str(), &ids[1], &ids[0]]);
+
+    let scoped = repo.list_decided(Some("sess-a"), 1).await.expect("list");
+    assert_eq!(scoped.len(), 1);
+    assert_eq!(scoped[0].id, ids[1]);
+}
+
+#[tokio::test]
+async fn resolve_accepts_uuid_or_short_id() {
+    let db = db::connect_memory().await.expect("db");
+    let repo = ApprovalRepo::new(Arc::new(db));
+
+    let created = repo
+        .create(&sample_request("sess-c"))
+        .await
+        .expect("create");
+    assert!(created.short_id.starts_with("apr-"), "{}", created.short_id);
+
+    let by_short = repo
+        .resolve(&created.short_id.to_uppercase())
+        .await
+        .expect("resolve")
+        .expect("found by short id");
+    assert_eq!(by_short.id, created.id);
+    let by_uuid = repo
+        .resolve(&created.id)
+        .await
+        .expect("resolve")
+        .expect("found by uuid");
+    assert_eq!(by_uuid.short_id, created.short_id);
+    assert!(repo.resolve("apr-none").await.expect("resolve").is_none());
+
+    let by_prefix = repo
+        .resolve(&created.id[..8])
+        .await
+        .expect("resolve")
+        .expect("found by uuid prefix");
+    assert_eq!(by_prefix.id, created.id);
+
+    let other = repo
+        .create(&sample_request("sess-c"))
+        .await
+        .expect("create");
+    let err = repo.resolve("apr-").await.expect_err("ambiguous");
+    let message = err.to_string();
+    assert!(message.contains("ambiguous approval prefix"), "{message}");
+    assert!(message.contains(&created.short_id), "{message}");
+    assert!(message.contains(&other.short_id), "{message}");
+}
+
+#[tokio::test]
+async fn approval_messages_resolve_once() {
+    let db = db::connect_memory().await.expect("db");
+    let repo = ApprovalRepo::new(Arc::new(db));
+    let created = repo
+        .create(&sample_request("sess-fan"))
+        .await
+        .expect("create");
+
+    repo.add_message(&created.id, "C_MAIN", "1.1")
+        .await
+        .expect("add");
+    repo.add_message(&created.id, "C_SEC", "2.2")
+        .await
+        .expect("add");
+    repo.add_message(&created.id, "C_SEC", "2.2")
+        .await
+        .expect("duplicate add ignored");
+    assert_eq!(
+        repo.unresolved_messages(&created.id).await.expect("list"),
+        vec![
+            ("C_MAIN".to_owned(), "1.1".to_owned()),
+            ("C_SEC".to_owned(), "2.2".to_owned())
+        ]
+    );
+
+    assert!(repo
+        .resolve_message(&created.id, "C_SEC", "2.2")
+        .await
+        .expect("resolve"));
+    assert!(!repo
+        .resolve_message(&created.id, "C_SEC", "2.2")
+        .await
+        .expect("resolve again"));
+    assert_eq!(
+        repo.unresolved_messages(&created.id).await.expect("list"),
+        vec![("C_MAIN".to_owned(), "1.1".to_owned())]
+    );
+}
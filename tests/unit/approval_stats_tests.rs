@@ -0,0 +1,91 @@
+//! Unit tests for `ApprovalStats` (approval outcomes by risk level).
+//!
+//! Tests cover:
+//! - Outcomes are counted overall and per risk level; undecided requests
+//!   are ignored
+//! - Rejection reasons are grouped ignoring case and ranked by frequency
+
+use std::collections::HashMap;
+
+use agent_intercom::audit::{AuditEntry, AuditEventType};
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::orchestrator::approval_stats::{ApprovalStats, Outcomes};
+
+fn request(risk_level: RiskLevel, status: ApprovalStatus) -> ApprovalRequest {
+    let mut request = ApprovalRequest::new(
+        "s1".into(),
+        "change".into(),
+        None,
+        "+x".into(),
+        "src/lib.rs".into(),
+        risk_level,
+        "hash".into(),
+    );
+    request.status = status;
+    request
+}
+
+fn rejection(request: &ApprovalRequest, reason: &str) -> (String, AuditEntry) {
+    (
+        request.id.clone(),
+        AuditEntry::new(AuditEventType::Rejection)
+            .with_request_id(request.id.clone())
+            .with_reason(reason.into()),
+    )
+}
+
+#[test]
+fn outcomes_are_counted_by_risk_level() {
+    let requests = vec![
+        request(RiskLevel::Low, ApprovalStatus::Approved),
+        request(RiskLevel::Low, ApprovalStatus::Consumed),
+        request(RiskLevel::Low, ApprovalStatus::Rejected),
+        request(RiskLevel::High, ApprovalStatus::Rejected),
+        request(RiskLevel::Critical, ApprovalStatus::Pending),
+        request(RiskLevel::Critical, ApprovalStatus::Expired),
+    ];
+    let stats = ApprovalStats::from_requests(&requests, &HashMap::new());
+
+    assert_eq!(
+        stats.overall,
+        Outcomes {
+            approved: 2,
+            rejected: 2
+        }
+    );
+    assert_eq!(stats.low.decided(), 3);
+    assert_eq!(stats.low.approval_percent(), Some(67));
+    assert_eq!(stats.high.approval_percent(), Some(0));
+    assert_eq!(stats.critical.approval_percent(), None);
+    assert!(stats.rejection_reasons.is_empty());
+
+    let json = stats.to_json();
+    assert_eq!(json["overall"]["approval_percent"], 50);
+    assert_eq!(json["by_risk_level"]["critical"]["decided"], 0);
+    assert!(json["by_risk_level"]["critical"]["approval_percent"].is_null());
+}
+
+#[test]
+fn rejection_reasons_are_grouped_and_ranked() {
+    let requests: Vec<ApprovalRequest> = (0..4)
+        .map(|_| request(RiskLevel::High, ApprovalStatus::Rejected))
+        .collect();
+    let decisions: HashMap<String, AuditEntry> = [
+        rejection(&requests[0], "Missing tests"),
+        rejection(&requests[1], "  missing tests "),
+        rejection(&requests[2], "wrong file"),
+        rejection(&requests[3], " "),
+    ]
+    .into_iter()
+    .collect();
+
+    let stats = ApprovalStats::from_requests(&requests, &decisions);
+    assert_eq!(
+        stats.rejection_reasons,
+        vec![
+            ("Missing tests".to_owned(), 2),
+            ("wrong file".to_owned(), 1)
+        ]
+    );
+    assert_eq!(stats.overall.rejected, 4);
+}
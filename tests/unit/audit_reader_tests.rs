@@ -0,0 +1,112 @@
+//! Unit tests for `audit::reader::find_decisions`.
+//!
+//! Validates:
+//! - Approval and rejection entries are found by request ID
+//! - Other event types and unknown requests are ignored
+//! - The newest entry for a request wins across daily files
+//! - A missing log directory yields no decisions
+//! - Session entries are read oldest first from the session's start date
+
+use std::fs;
+
+use agent_intercom::audit::reader::{entries_for_session, find_decisions};
+use agent_intercom::audit::{AuditEntry, AuditEventType};
+
+fn line(event_type: AuditEventType, request_id: &str, operator: &str) -> String {
+    let entry = AuditEntry::new(event_type)
+        .with_request_id(request_id.to_owned())
+        .with_operator(operator.to_owned());
+    serde_json::to_string(&entry).expect("serialize")
+}
+
+#[test]
+fn finds_decisions_by_request_id() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let contents = [
+        line(AuditEventType::Approval, "req-1", "U1"),
+        "not json".to_owned(),
+        line(AuditEventType::CommandApproval, "req-2", "U2"),
+        line(AuditEventType::Rejection, "req-3", "U3"),
+        line(AuditEventType::Approval, "req-other", "U4"),
+    ]
+    .join("\n");
+    fs::write(dir.path().join("audit-2026-01-02.jsonl"), contents).expect("write");
+
+    let found = find_decisions(dir.path(), &["req-1", "req-2", "req-3"]);
+
+    assert_eq!(found.len(), 2);
+    assert_eq!(found["req-1"].operator_id.as_deref(), Some("U1"));
+    assert_eq!(found["req-3"].event_type, AuditEventType::Rejection);
+    assert!(!found.contains_key("req-2"));
+}
+
+#[test]
+fn newest_entry_wins_across_files() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        dir.path().join("audit-2026-01-01.jsonl"),
+        line(AuditEventType::Approval, "req-1", "U_OLD"),
+    )
+    .expect("write");
+    fs::write(
+        dir.path().join("audit-2026-01-02.jsonl"),
+        [
+            line(AuditEventType::Approval, "req-1", "U_EARLIER"),
+            line(AuditEventType::Rejection, "req-1", "U_NEW"),
+        ]
+        .join("\n"),
+    )
+    .expect("write");
+    fs::write(
+        dir.path().join("notes.jsonl"),
+        line(AuditEventType::Approval, "req-1", "U_IGNORED"),
+    )
+    .expect("write");
+
+    let found = find_decisions(dir.path(), &["req-1"]);
+
+    assert_eq!(found["req-1"].operator_id.as_deref(), Some("U_NEW"));
+}
+
+#[test]
+fn missing_directory_yields_nothing() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let found = find_decisions(&dir.path().join("absent"), &["req-1"]);
+    assert!(found.is_empty());
+}
+
+#[test]
+fn entries_for_session_reads_from_start_date() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let tool = |session: &str, tool: &str| {
+        serde_json::to_string(
+            &AuditEntry::new(AuditEventType::ToolCall)
+                .with_session(session.to_owned())
+                .with_tool(tool.to_owned()),
+        )
+        .expect("serialize")
+    };
+    fs::write(
+        dir.path().join("audit-2026-01-01.jsonl"),
+        tool("sess-1", "before_start"),
+    )
+    .expect("write");
+    fs::write(
+        dir.path().join("audit-2026-01-03.jsonl"),
+        [tool("sess-1", "third"), tool("sess-2", "elsewhere")].join("\n"),
+    )
+    .expect("write");
+    fs::write(
+        dir.path().join("audit-2026-01-02.jsonl"),
+        [tool("sess-1", "first"), tool("sess-1", "second")].join("\n"),
+    )
+    .expect("write");
+
+    let since = chrono::NaiveDate::from_ymd_opt(2026, 1, 2).expect("date");
+    let tools: Vec<String> = entries_for_session(dir.path(), "sess-1", since)
+        .into_iter()
+        .filter_map(|entry| entry.tool_name)
+        .collect();
+
+    assert_eq!(tools, ["first", "second", "third"]);
+}
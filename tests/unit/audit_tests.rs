@@ -62,6 +62,21 @@ fn all_acp_event_types_serialize_with_snake_case() {
         (AuditEventType::AcpSessionResume, "acp_session_resume"),
         (AuditEventType::AcpSteerDelivered, "acp_steer_delivered"),
         (AuditEventType::AcpTaskQueued, "acp_task_queued"),
+        (AuditEventType::AgentEvent, "agent_event"),
+        (AuditEventType::SteeringExpired, "steering_expired"),
+        (
+            AuditEventType::SessionTimeBoxExpired,
+            "session_time_box_expired",
+        ),
+        (AuditEventType::RelaySent, "relay_sent"),
+        (AuditEventType::RelayDelivered, "relay_delivered"),
+        (AuditEventType::KnowledgeProposed, "knowledge_proposed"),
+        (AuditEventType::KnowledgeDecided, "knowledge_decided"),
+        (AuditEventType::PromptAutoDecision, "prompt_auto_decision"),
+        (AuditEventType::SessionMove, "session_move"),
+        (AuditEventType::IpcCommand, "ipc_command"),
+        (AuditEventType::LockAcquired, "lock_acquired"),
+        (AuditEventType::LockReleased, "lock_released"),
     ];
 
     for (event_type, expected) in cases {
@@ -17,12 +17,14 @@
 //! | S055 | Audit directory missing — `JsonlAuditWriter::new` creates it automatically |
 //! | S056 | Date change produces separate files per day |
 //! | S057 | Concurrent audit writes produce valid, readable JSONL |
+//! | —    | Session entries copied to `sessions/<id>.jsonl`; unsafe IDs skipped |
 
 use std::fs;
 use std::sync::Arc;
 
 use agent_intercom::audit::writer::JsonlAuditWriter;
-use agent_intercom::audit::{AuditEntry, AuditEventType, AuditLogger};
+use agent_intercom::audit::{session_log_path, AuditEntry, AuditEventType, AuditLogger};
+use agent_intercom::orchestrator::session_manager::newest_lines;
 
 /// Helper: build a minimal `AuditEntry` for the given event type.
 fn entry(event_type: AuditEventType) -> AuditEntry {
@@ -262,6 +264,66 @@ fn concurrent_writes_produce_valid_jsonl() {
     }
 }
 
+// ── Per-session log files ─────────────────────────────────────────────────────
+
+/// Entries naming a session are copied to that session's own file; entries
+/// for other sessions or none at all are not.
+#[test]
+fn session_entries_copied_to_session_log() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let writer = JsonlAuditWriter::new(temp.path().to_owned()).expect("writer");
+
+    for entry in [
+        AuditEntry::new(AuditEventType::ToolCall)
+            .with_session("sess-a".to_owned())
+            .with_tool("ping".to_owned()),
+        AuditEntry::new(AuditEventType::ToolCall).with_session("sess-b".to_owned()),
+        entry(AuditEventType::ToolCall),
+        AuditEntry::new(AuditEventType::Approval)
+            .with_session("sess-a".to_owned())
+            .with_request_id("req-1".to_owned()),
+    ] {
+        writer.log_entry(entry).expect("log");
+    }
+
+    let path = session_log_path(temp.path(), "sess-a").expect("safe id");
+    assert_eq!(path, temp.path().join("sessions").join("sess-a.jsonl"));
+    let contents = fs::read_to_string(&path).expect("session log exists");
+    let entries: Vec<AuditEntry> = contents
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid entry"))
+        .collect();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].tool_name.as_deref(), Some("ping"));
+    assert_eq!(entries[1].event_type, AuditEventType::Approval);
+
+    // The daily file still holds every entry.
+    assert_eq!(read_only_jsonl_file(temp.path()).lines().count(), 4);
+}
+
+/// IDs that are not a plain file stem never name a session log path.
+#[test]
+fn session_log_path_rejects_unsafe_ids() {
+    let dir = std::path::Path::new("/logs");
+    for id in ["", "../escape", "a/b", "a\\b", "."] {
+        assert!(
+            session_log_path(dir, id).is_none(),
+            "{id:?} must be rejected"
+        );
+    }
+    assert!(session_log_path(dir, "0b7c-Uuid_1").is_some());
+}
+
+/// Logs over the upload limit keep their newest whole lines.
+#[test]
+fn newest_lines_keeps_trailing_whole_lines() {
+    let log = "first line\nsecond\nthird\n";
+    assert_eq!(newest_lines(log, 100), log);
+    assert_eq!(newest_lines(log, 13), "second\nthird\n");
+    assert_eq!(newest_lines(log, 10), "third\n");
+    assert_eq!(newest_lines(log, 3), "");
+}
+
 // ── Helper: read the single JSONL file in the log directory ──────────────────
 
 /// Read the contents of the single JSONL file in `dir` (panics if not exactly one).
@@ -3,11 +3,18 @@
 //! Covers `action_buttons()`, `approval_buttons()`, `text_section()`,
 //! `wait_buttons()`, `severity_section()`, `code_snippet_blocks()`,
 //! `diff_section()`, `diff_applied_section()`, `diff_conflict_section()`,
-//! `diff_force_warning_section()`, `auto_approve_suggestion_button()`,
-//! `slack_escape()`, and `truncate_text()`.
+//! `diff_force_warning_section()`, `apply_conflict_buttons()`,
+//! `apply_conflict_blocks()`,
+//! `auto_approve_suggestion_button()`,
+//! `slack_escape()`, the `<!date>` timestamp and expiry helpers,
+//! `truncate_text()`, and the text fallbacks `render_plain_text()`,
+//! `fallback_text()` and `message_content()`.
 //!
 //! Scenario references: S-T1-004, S-T1-006, S-T1-007, S-T1-008 (FR-001)
 
+use agent_intercom::config::SlackRenderMode;
+use agent_intercom::models::approval::{ApprovalRequest, RiskLevel};
+use agent_intercom::models::conflict::{ApplyConflict, ConflictKind};
 use agent_intercom::slack::blocks;
 
 // ── wait_buttons ──────────────────────────────────────────────────────────────
@@ -328,6 +335,77 @@ fn diff_force_warning_section_contains_file_path() {
     assert!(json.contains("src/main.rs"), "file path must appear");
 }
 
+// ── apply_conflict_buttons ────────────────────────────────────────────────────
+
+/// Force Apply is only offered when the diff still applies.
+#[test]
+fn apply_conflict_buttons_offer_force_only_when_it_can_apply() {
+    let json = serde_json::to_string(&blocks::apply_conflict_buttons("conflict:1", true))
+        .expect("serialize block");
+    for action_id in ["conflict_force", "conflict_merge", "conflict_reject"] {
+        assert!(json.contains(action_id), "{action_id} must appear");
+    }
+    assert!(json.contains("conflict:1"), "conflict id must be the value");
+
+    let json = serde_json::to_string(&blocks::apply_conflict_buttons("conflict:1", false))
+        .expect("serialize block");
+    assert!(
+        !json.contains("conflict_force"),
+        "force must not be offered"
+    );
+    assert!(json.contains("conflict_merge"));
+}
+
+/// The conflict card only mentions Force Apply when the button is shown.
+#[test]
+fn apply_conflict_blocks_text_matches_the_offered_buttons() {
+    let approval = ApprovalRequest::new(
+        "session-1".into(),
+        "Refactor parser".into(),
+        None,
+        "--- a/src/main.rs\n+++ b/src/main.rs\n".into(),
+        "src/main.rs".into(),
+        RiskLevel::Low,
+        "hash".into(),
+    );
+    let conflict = |kind| {
+        ApplyConflict::new(
+            approval.id.clone(),
+            "session-1".into(),
+            "src/main.rs".into(),
+            kind,
+            "detail".into(),
+        )
+    };
+
+    let json = serde_json::to_string(&blocks::apply_conflict_blocks(
+        &approval,
+        &conflict(ConflictKind::HashMismatch),
+    ))
+    .expect("serialize blocks");
+    assert!(json.contains("Force Apply overwrites"), "{json}");
+    assert!(json.contains("conflict_force"), "{json}");
+
+    let json = serde_json::to_string(&blocks::apply_conflict_blocks(
+        &approval,
+        &conflict(ConflictKind::HunkConflict),
+    ))
+    .expect("serialize blocks");
+    assert!(!json.contains("Force Apply"), "{json}");
+    assert!(json.contains("Merge View hands"), "{json}");
+}
+
+/// Drift reports carry the workspace root on Reconcile and Dismiss.
+#[test]
+fn policy_drift_blocks_carry_the_workspace_root() {
+    let json = serde_json::to_string(&blocks::policy_drift_blocks("drifted", "/work/app"))
+        .expect("serialize blocks");
+    for action_id in ["policy_drift_reconcile", "policy_drift_dismiss"] {
+        assert!(json.contains(action_id), "{action_id} must appear");
+    }
+    assert!(json.contains("/work/app"), "root must be the value");
+}
+
 // ── auto_approve_suggestion_button ────────────────────────────────────────────
 
 /// S-T1-008p — `auto_approve_suggestion_button` contains the `auto_approve_add` action ID.
@@ -365,6 +443,57 @@ fn auto_approve_suggestion_button_values_contain_command() {
     );
 }
 
+// ── timestamps ────────────────────────────────────────────────────────────────
+
+/// Timestamps render in the reader's time zone, with a UTC fallback.
+#[test]
+fn slack_date_helpers_use_date_syntax_with_utc_fallback() {
+    let at = chrono::DateTime::from_timestamp(1_700_000_000, 0).expect("timestamp");
+    assert_eq!(
+        blocks::slack_datetime(at),
+        "<!date^1700000000^{date_short_pretty} at {time}|2023-11-14 22:13 UTC>"
+    );
+    assert_eq!(
+        blocks::slack_time(at),
+        "<!date^1700000000^{time}|22:13 UTC>"
+    );
+    assert_eq!(
+        blocks::slack_day(at),
+        "<!date^1700000000^{date_short_pretty}|2023-11-14>"
+    );
+}
+
+/// The expiry line counts down to `now + timeout` and saturates instead of
+/// overflowing.
+#[test]
+fn expiry_line_counts_down_to_deadline() {
+    let now = chrono::DateTime::from_timestamp(1_700_000_000, 0).expect("timestamp");
+    assert_eq!(
+        blocks::expiry_line(now, 300),
+        "\u{23f3} Expires <!date^1700000300^{time} ({ago})|22:18 UTC>"
+    );
+    assert!(blocks::expiry_line(now, u64::MAX).starts_with("\u{23f3} Expires <!date^"));
+}
+
+#[test]
+fn replace_expiry_line_moves_the_countdown() {
+    let now = chrono::DateTime::from_timestamp(1_700_000_000, 0).expect("timestamp");
+    let later = now + chrono::Duration::minutes(5);
+    let mut card = vec![
+        blocks::text_section("*Approve this*"),
+        blocks::text_section(&blocks::expiry_line(now, 60)),
+        blocks::approval_buttons("req-1"),
+    ];
+
+    assert!(blocks::replace_expiry_line(&mut card, later));
+    let text = blocks::render_plain_text(&card);
+    assert!(text.contains(&blocks::expiry_line_at(later)), "{text}");
+    assert!(!text.contains("1700000060"), "{text}");
+
+    let mut no_countdown = vec![blocks::text_section("*Approve this*")];
+    assert!(!blocks::replace_expiry_line(&mut no_countdown, later));
+}
+
 // ── slack_escape ──────────────────────────────────────────────────────────────
 
 /// Ampersands are escaped to `&amp;`.
@@ -523,6 +652,15 @@ fn approval_buttons_has_correct_action_ids() {
     );
 }
 
+/// `approval_buttons` offers a snooze carrying the request ID.
+#[test]
+fn approval_buttons_include_snooze() {
+    let blk = blocks::approval_buttons("req:direct-001");
+    let json = serde_json::to_string(&blk).expect("serialize block");
+    assert!(json.contains("snooze_approval"));
+    assert!(json.contains("Snooze 30m"));
+}
+
 /// `approval_buttons` encodes the `block_id` as `"approval_{{request_id}}"`.
 #[test]
 fn approval_buttons_block_id_format() {
@@ -535,3 +673,62 @@ fn approval_buttons_block_id_format() {
         "approval_buttons block_id must be 'approval_{{request_id}}'"
     );
 }
+
+// ── text fallbacks and plain-text rendering ───────────────────────────────────
+
+/// An approval card renders to text with its title, file and button labels.
+#[test]
+fn fallback_text_describes_approval_card() {
+    let mut blks = blocks::build_approval_blocks(
+        "Add retry",
+        Some("Wraps the call"),
+        "+retry()",
+        "src/net.rs",
+        RiskLevel::High,
+    );
+    blks.push(blocks::approval_buttons("req-1"));
+    let text = blocks::fallback_text(&blks);
+    assert!(text.contains("*Add retry*"));
+    assert!(text.contains("`src/net.rs`"));
+    assert!(text.contains("Wraps the call"));
+    assert!(text.ends_with("Buttons: Accept, Reject, Snooze 30m"));
+}
+
+/// `fallback_text` stays short enough for notifications.
+#[test]
+fn fallback_text_is_truncated() {
+    let long = "x".repeat(10_000);
+    let text = blocks::fallback_text(&[blocks::text_section(&long)]);
+    assert!(text.len() <= 3000);
+    assert!(text.ends_with("..."));
+}
+
+/// In blocks mode a missing `text` is generated and a given one is kept.
+#[test]
+fn message_content_fills_missing_text() {
+    let blks = vec![blocks::text_section("Session started")];
+    let content = blocks::message_content(None, Some(blks.clone()), SlackRenderMode::Blocks);
+    assert_eq!(content.text.as_deref(), Some("Session started"));
+    assert_eq!(content.blocks.as_ref().map(Vec::len), Some(1));
+
+    let content =
+        blocks::message_content(Some("Summary".into()), Some(blks), SlackRenderMode::Blocks);
+    assert_eq!(content.text.as_deref(), Some("Summary"));
+}
+
+/// Plain-text mode renders the blocks into `text` and sends none.
+#[test]
+fn message_content_plain_text_drops_blocks() {
+    let blks = vec![
+        blocks::text_section("Waiting for instructions"),
+        blocks::wait_buttons("sess-1"),
+    ];
+    let content = blocks::message_content(
+        Some("Agent waiting".into()),
+        Some(blks),
+        SlackRenderMode::PlainText,
+    );
+    assert!(content.blocks.is_none());
+    let text = content.text.expect("text");
+    assert!(text.starts_with("Waiting for instructions\nButtons: Resume"));
+}
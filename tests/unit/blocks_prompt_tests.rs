@@ -1,7 +1,8 @@
 //! Unit tests for Block Kit prompt message builders.
 //!
-//! Covers `build_prompt_blocks()`, `prompt_buttons()`, `prompt_type_icon()`,
-//! and `prompt_type_label()` across all four prompt types.
+//! Covers `build_prompt_blocks()`, `prompt_buttons()`,
+//! `quick_reply_buttons()`, `prompt_type_icon()`, and `prompt_type_label()`
+//! across all four prompt types.
 //!
 //! Scenario references: S-T1-002 (FR-001, FR-009)
 
@@ -80,6 +81,27 @@ fn prompt_buttons_values_contain_prompt_id() {
     );
 }
 
+// ── quick_reply_buttons ───────────────────────────────────────────────────────
+
+/// Quick replies render one button per preset, indexed in order.
+#[test]
+fn quick_reply_buttons_index_presets_in_order() {
+    let replies = ["Retry".to_owned(), "Skip test".to_owned()];
+    let blk = blocks::quick_reply_buttons("prompt:q1", &replies).expect("buttons");
+    let json = serde_json::to_string(&blk).expect("serialize block");
+    assert!(json.contains("prompt_quick_prompt:q1"), "block_id: {json}");
+    let retry = json.find("prompt_quick_0").expect("first action id");
+    let skip = json.find("prompt_quick_1").expect("second action id");
+    assert!(retry < skip, "presets keep their configured order");
+    assert!(json.contains("Retry") && json.contains("Skip test"));
+}
+
+/// No presets means no quick-reply block.
+#[test]
+fn quick_reply_buttons_none_without_presets() {
+    assert!(blocks::quick_reply_buttons("prompt:q1", &[]).is_none());
+}
+
 // ── build_prompt_blocks ───────────────────────────────────────────────────────
 
 /// S-T1-002g — `build_prompt_blocks` includes the prompt text.
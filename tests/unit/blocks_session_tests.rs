@@ -1,10 +1,14 @@
 //! Unit tests for Block Kit session lifecycle message builders.
 //!
 //! Covers `session_started_blocks()` and `session_ended_blocks()` for both
-//! MCP and ACP protocol modes.
+//! MCP and ACP protocol modes, and the time-box summary
+//! `time_box_expired_blocks()`.
 //!
 //! Scenario references: S-T1-005 (FR-001)
 
+use std::collections::BTreeMap;
+
+use agent_intercom::models::progress::{ProgressItem, ProgressStatus};
 use agent_intercom::models::session::{
     ConnectivityStatus, ProtocolMode, Session, SessionMode, SessionStatus,
 };
@@ -15,6 +19,7 @@ use chrono::{TimeZone, Utc};
 fn make_session(protocol_mode: ProtocolMode, mode: SessionMode) -> Session {
     Session {
         id: "abc12345-0000-0000-0000-000000000000".to_owned(),
+        short_id: "ses-7f3k".to_owned(),
         owner_user_id: "U123".to_owned(),
         workspace_root: "D:\\projects\\myapp".to_owned(),
         status: SessionStatus::Active,
@@ -35,19 +40,32 @@ fn make_session(protocol_mode: ProtocolMode, mode: SessionMode) -> Session {
         restart_of: None,
         agent_session_id: None,
         title: None,
+        deadline: None,
+        max_duration_seconds: None,
+        wrap_up_sent: false,
+        issue_ref: None,
+        parent_session_id: None,
+        muted: false,
+        muted_until: None,
+        deleted_at: None,
+        tags: BTreeMap::new(),
+        env: BTreeMap::new(),
+        run_id: None,
+        client_name: None,
+        client_version: None,
     }
 }
 
 // ── session_started_blocks ─────────────────────────────────────────────────────
 
-/// S-T1-005a — Output contains the first 8 characters of the session ID.
+/// S-T1-005a — Output contains the session short ID.
 #[test]
 fn session_started_blocks_contains_short_id() {
     let session = make_session(ProtocolMode::Mcp, SessionMode::Remote);
     let blks = blocks::session_started_blocks(&session);
     let json = serde_json::to_string(&blks).expect("serialize blocks");
     assert!(
-        json.contains("abc12345"),
+        json.contains("ses-7f3k"),
         "short ID prefix must appear; got: {json}"
     );
 }
@@ -175,6 +193,7 @@ fn make_terminated_session(duration_secs: i64) -> Session {
     let ended = created + chrono::Duration::seconds(duration_secs);
     Session {
         id: "abc12345-0000-0000-0000-000000000000".to_owned(),
+        short_id: "ses-7f3k".to_owned(),
         owner_user_id: "U123".to_owned(),
         workspace_root: "/workspace".to_owned(),
         status: SessionStatus::Terminated,
@@ -195,6 +214,19 @@ fn make_terminated_session(duration_secs: i64) -> Session {
         restart_of: None,
         agent_session_id: None,
         title: None,
+        deadline: None,
+        max_duration_seconds: None,
+        wrap_up_sent: false,
+        issue_ref: None,
+        parent_session_id: None,
+        muted: false,
+        muted_until: None,
+        deleted_at: None,
+        tags: BTreeMap::new(),
+        env: BTreeMap::new(),
+        run_id: None,
+        client_name: None,
+        client_version: None,
     }
 }
 
@@ -205,7 +237,7 @@ fn session_ended_blocks_contains_short_id() {
     let blks = blocks::session_ended_blocks(&session, "user request");
     let json = serde_json::to_string(&blks).expect("serialize blocks");
     assert!(
-        json.contains("abc12345"),
+        json.contains("ses-7f3k"),
         "short ID prefix must appear in session ended blocks"
     );
 }
@@ -308,3 +340,37 @@ fn session_ended_blocks_returns_one_block() {
         "session_ended_blocks must return exactly 1 block"
     );
 }
+
+// ── time_box_expired_blocks ───────────────────────────────────────────────────
+
+/// The time-box summary names the budget, the last tool, and the progress.
+#[test]
+fn time_box_expired_blocks_summarises_budget_and_progress() {
+    let mut session = make_session(ProtocolMode::Acp, SessionMode::Remote);
+    session.last_tool = Some("check_clearance".to_owned());
+    session.progress_snapshot = Some(vec![
+        ProgressItem {
+            label: "parser rewrite".to_owned(),
+            status: ProgressStatus::Done,
+        },
+        ProgressItem {
+            label: "migrate callers".to_owned(),
+            status: ProgressStatus::InProgress,
+        },
+    ]);
+    let blks = blocks::time_box_expired_blocks(&session, chrono::Duration::hours(2));
+    let json = serde_json::to_string(&blks).expect("serialize blocks");
+    assert!(json.contains("2h 0m"), "budget must appear: {json}");
+    assert!(json.contains("check_clearance"), "last tool must appear");
+    assert!(json.contains("parser rewrite") && json.contains("migrate callers"));
+}
+
+/// A linked issue is shown on the session-started message.
+#[test]
+fn session_started_blocks_show_linked_issue() {
+    let mut session = make_session(ProtocolMode::Acp, SessionMode::Remote);
+    session.issue_ref = Some("PROJ-123".to_owned());
+    let json =
+        serde_json::to_string(&blocks::session_started_blocks(&session)).expect("serialize blocks");
+    assert!(json.contains("*Issue:* PROJ-123"), "{json}");
+}
@@ -198,3 +198,21 @@ fn stall_alert_blocks_includes_idle_duration() {
         "stall_alert_blocks must reflect idle duration; got: {json}"
     );
 }
+
+// ── completion_prompt_blocks ─────────────────────────────────────────────────
+
+#[test]
+fn completion_prompt_blocks_offer_terminate_and_assign() {
+    let blks = blocks::completion_prompt_blocks("sess:done", 120);
+    assert_eq!(blks.len(), 2);
+    let text = serde_json::to_string(&blks[0]).expect("serialize prompt");
+    assert!(text.contains("appears finished"), "{text}");
+    assert!(
+        text.contains("sess:done") && text.contains("120s"),
+        "{text}"
+    );
+    let buttons = serde_json::to_string(&blks[1]).expect("serialize buttons");
+    assert!(buttons.contains("completion_terminate"), "{buttons}");
+    assert!(buttons.contains("completion_assign"), "{buttons}");
+    assert_eq!(buttons.matches("sess:done").count(), 3, "{buttons}");
+}
@@ -0,0 +1,273 @@
+//! Unit tests for `broadcast` level routing (`[broadcast]`).
+//!
+//! Verifies that `broadcast_messages`:
+//! - posts once to the session thread by default, as before routing existed
+//! - adds a top-level channel post, DMs and an escalation mention per level
+//! - folds the thread and channel posts together when there is no thread
+//! - DMs every authorized user for sessions owned by the local agent
+//! - applies the owner's `/intercom prefs` delivery and quiet hours
+
+use agent_intercom::config::GlobalConfig;
+use agent_intercom::mcp::tools::remote_log::{broadcast_messages, OwnerDelivery};
+use agent_intercom::models::preferences::{NotificationDelivery, QuietHours, UserPreferences};
+use agent_intercom::models::session::{Session, SessionMode};
+use chrono::NaiveTime;
+use slack_morphism::prelude::SlackTs;
+
+const ROUTES: &str = r#"
+[broadcast]
+warning = ["thread", "channel"]
+error = ["thread", "channel", "dm", "escalate"]
+"#;
+
+fn config(broadcast: &str) -> GlobalConfig {
+    let toml = format!(
+        r#"
+default_workspace_root = '{root}'
+host_cli = "echo"
+
+[slack]
+channel_id = "C_TEST"
+
+[timeouts]
+approval_seconds = 5
+prompt_seconds = 5
+wait_seconds = 5
+
+[stall]
+enabled = false
+inactivity_threshold_seconds = 300
+escalation_threshold_seconds = 120
+max_retries = 3
+default_nudge_message = "continue"
+{broadcast}
+"#,
+        root = std::env::temp_dir().to_string_lossy().replace('\\', "\\\\"),
+    );
+    let mut config = GlobalConfig::from_toml_str(&toml).expect("valid test config");
+    config.authorized_user_ids = vec!["U_ONE".into(), "U_TWO".into()];
+    config
+}
+
+fn session(owner: &str) -> Session {
+    Session::new(owner.into(), "/ws".into(), None, SessionMode::Remote)
+}
+
+const THREAD_TS: &str = "1700000000.000100";
+
+#[allow(clippy::unnecessary_wraps)] // Matches the `thread_ts` argument.
+fn thread() -> Option<SlackTs> {
+    Some(SlackTs(THREAD_TS.into()))
+}
+
+/// (channel, `thread_ts`, text) of each message, in posting order.
+fn summary(
+    config: &GlobalConfig,
+    session: &Session,
+    thread_ts: Option<SlackTs>,
+    level: &str,
+) -> Vec<(String, Option<String>, String)> {
+    summary_for(config, session, OwnerDelivery::default(), thread_ts, level)
+}
+
+/// [`summary`] with the owner's delivery preferences applied.
+fn summary_for(
+    config: &GlobalConfig,
+    session: &Session,
+    owner: OwnerDelivery,
+    thread_ts: Option<SlackTs>,
+    level: &str,
+) -> Vec<(String, Option<String>, String)> {
+    broadcast_messages(
+        config,
+        session,
+        owner,
+        "C_TEST",
+        thread_ts,
+        level,
+        "disk full",
+    )
+    .into_iter()
+    .map(|msg| {
+        (
+            msg.channel.to_string(),
+            msg.thread_ts.map(|ts| ts.0),
+            msg.text.unwrap_or_default(),
+        )
+    })
+    .collect()
+}
+
+fn owner(delivery: NotificationDelivery, quiet: bool) -> OwnerDelivery {
+    OwnerDelivery { delivery, quiet }
+}
+
+#[test]
+fn default_route_posts_once_to_thread() {
+    let config = config("");
+    let session = session("U_OWNER");
+
+    let messages = summary(&config, &session, thread(), "error");
+
+    assert_eq!(
+        messages,
+        [(
+            "C_TEST".to_owned(),
+            Some(THREAD_TS.to_owned()),
+            "disk full".to_owned()
+        )]
+    );
+}
+
+#[test]
+fn info_stays_in_thread_while_warning_also_reaches_channel() {
+    let config = config(ROUTES);
+    let session = session("U_OWNER");
+
+    assert_eq!(summary(&config, &session, thread(), "info").len(), 1);
+
+    let warning = summary(&config, &session, thread(), "warning");
+    assert_eq!(warning.len(), 2);
+    assert_eq!(warning[1].0, "C_TEST");
+    assert_eq!(warning[1].1, None, "channel post is top-level");
+    assert!(
+        warning[1].2.contains("Broadcast from session"),
+        "{warning:?}"
+    );
+    assert!(!warning[1].2.contains("<!here>"), "{warning:?}");
+}
+
+#[test]
+fn error_escalates_in_channel_and_dms_owner() {
+    let config = config(ROUTES);
+    let session = session("U_OWNER");
+
+    let error = summary(&config, &session, thread(), "error");
+
+    assert_eq!(error.len(), 3, "{error:?}");
+    assert!(error[1].2.starts_with("<!here>"), "{error:?}");
+    assert_eq!(error[2].0, "U_OWNER");
+    assert!(!error[2].2.contains("<!here>"), "{error:?}");
+}
+
+#[test]
+fn thread_and_channel_merge_without_session_thread() {
+    let config = config(ROUTES);
+    let session = session("U_OWNER");
+
+    let error = summary(&config, &session, None, "error");
+
+    let channel_posts: Vec<_> = error.iter().filter(|m| m.0 == "C_TEST").collect();
+    assert_eq!(channel_posts.len(), 1, "{error:?}");
+    assert!(channel_posts[0].2.starts_with("<!here>"), "{error:?}");
+}
+
+#[test]
+fn local_agent_sessions_dm_every_authorized_user() {
+    let config = config(ROUTES);
+    let session = session("agent:local");
+
+    let dms: Vec<String> = summary(&config, &session, thread(), "error")
+        .into_iter()
+        .skip(2)
+        .map(|m| m.0)
+        .collect();
+
+    assert_eq!(dms, ["U_ONE", "U_TWO"]);
+}
+
+#[test]
+fn dm_delivery_moves_thread_post_to_owner_dm() {
+    let config = config("");
+    let session = session("U_OWNER");
+
+    let info = summary_for(
+        &config,
+        &session,
+        owner(NotificationDelivery::Dm, false),
+        thread(),
+        "info",
+    );
+
+    assert_eq!(info.len(), 1, "{info:?}");
+    assert_eq!(info[0].0, "U_OWNER");
+    assert_eq!(info[0].1, None);
+}
+
+#[test]
+fn both_delivery_keeps_thread_post_and_adds_dm() {
+    let config = config("");
+    let session = session("U_OWNER");
+
+    let info = summary_for(
+        &config,
+        &session,
+        owner(NotificationDelivery::Both, false),
+        thread(),
+        "info",
+    );
+
+    let channels: Vec<&str> = info.iter().map(|m| m.0.as_str()).collect();
+    assert_eq!(channels, ["C_TEST", "U_OWNER"]);
+    assert_eq!(info[0].1.as_deref(), Some(THREAD_TS));
+}
+
+#[test]
+fn quiet_hours_keep_thread_post_and_send_no_dm() {
+    let config = config(ROUTES);
+    let session = session("U_OWNER");
+
+    let info = summary_for(
+        &config,
+        &session,
+        owner(NotificationDelivery::Dm, true),
+        thread(),
+        "info",
+    );
+    assert_eq!(info.len(), 1, "{info:?}");
+    assert_eq!(info[0].0, "C_TEST");
+    assert_eq!(info[0].1.as_deref(), Some(THREAD_TS));
+
+    let error = summary_for(
+        &config,
+        &session,
+        owner(NotificationDelivery::Both, true),
+        thread(),
+        "error",
+    );
+    assert!(error.iter().all(|m| m.0 == "C_TEST"), "{error:?}");
+}
+
+#[test]
+fn local_agent_sessions_ignore_owner_delivery() {
+    let config = config("");
+    let session = session("agent:local");
+
+    let info = summary_for(
+        &config,
+        &session,
+        owner(NotificationDelivery::Dm, false),
+        thread(),
+        "info",
+    );
+
+    assert_eq!(info.len(), 1, "{info:?}");
+    assert_eq!(info[0].0, "C_TEST");
+}
+
+#[test]
+fn owner_delivery_is_never_quiet_for_errors() {
+    let mut prefs = UserPreferences::new("U_OWNER");
+    prefs.delivery = NotificationDelivery::Both;
+    prefs.quiet_hours = Some(QuietHours::parse("22:00-07:00").expect("range"));
+    let night = NaiveTime::from_hms_opt(23, 30, 0).expect("time");
+    let noon = NaiveTime::from_hms_opt(12, 0, 0).expect("time");
+
+    assert!(OwnerDelivery::new(Some(&prefs), "info", night).quiet);
+    assert!(!OwnerDelivery::new(Some(&prefs), "error", night).quiet);
+    assert!(!OwnerDelivery::new(Some(&prefs), "info", noon).quiet);
+    assert_eq!(
+        OwnerDelivery::new(None, "info", night),
+        OwnerDelivery::default()
+    );
+}
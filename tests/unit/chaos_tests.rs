@@ -0,0 +1,63 @@
+//! Unit tests for the fault injection layer (feature `chaos`).
+//!
+//! Tests cover:
+//! - Dropped posts are spread evenly at the configured percentage
+//! - `reset` turns every fault off
+//! - A socket kill reaches a waiting connection and is refused otherwise
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use agent_intercom::chaos::Faults;
+
+#[test]
+fn drop_percent_spreads_drops_evenly() {
+    let faults = Faults::default();
+    assert!(!(0..10).any(|_| faults.drop_post()), "off by default");
+
+    faults.set_drop_percent(50);
+    let pattern: Vec<bool> = (0..6).map(|_| faults.drop_post()).collect();
+    assert_eq!(pattern, [false, true, false, true, false, true]);
+
+    faults.set_drop_percent(25);
+    assert_eq!((0..100).filter(|_| faults.drop_post()).count(), 25);
+
+    faults.set_drop_percent(200);
+    assert_eq!(faults.drop_percent(), 100);
+    assert!((0..5).all(|_| faults.drop_post()));
+}
+
+#[test]
+fn reset_turns_faults_off() {
+    let faults = Faults::default();
+    faults.set_drop_percent(100);
+    faults.set_resolution_delay(Duration::from_millis(1500));
+    assert_eq!(faults.resolution_delay(), Duration::from_millis(1500));
+
+    faults.reset();
+    assert_eq!(faults.drop_percent(), 0);
+    assert_eq!(faults.resolution_delay(), Duration::ZERO);
+    assert!(!faults.drop_post());
+}
+
+#[tokio::test]
+async fn kill_socket_reaches_listening_connection() {
+    let faults = Arc::new(Faults::default());
+    assert!(!faults.kill_socket(), "nothing is listening yet");
+
+    let listener = tokio::spawn({
+        let faults = Arc::clone(&faults);
+        async move { faults.socket_killed().await }
+    });
+    tokio::time::timeout(Duration::from_secs(2), async {
+        while !faults.kill_socket() {
+            tokio::task::yield_now().await;
+        }
+    })
+    .await
+    .expect("listener registered");
+    tokio::time::timeout(Duration::from_secs(2), listener)
+        .await
+        .expect("listener woke")
+        .expect("join");
+}
@@ -0,0 +1,206 @@
+//! Unit tests for the `commands list|add` slash commands.
+//!
+//! Validates:
+//! - `list` shows the configured `[commands]` aliases, sorted
+//! - `add` registers an alias live, writes it to `config.toml` keeping the
+//!   file's comments, and records an audit entry
+//! - Without a config file the alias applies for the run only
+//! - Invalid, built-in and duplicate aliases are refused
+//! - Observers may list aliases but not add them
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use agent_intercom::audit::{AuditEntry, AuditEventType, AuditLogger};
+use agent_intercom::config::{GlobalConfig, Role};
+use agent_intercom::orchestrator::command_aliases;
+use agent_intercom::slack::commands::{command_permitted, dispatch_command};
+use agent_intercom::state::AppState;
+
+use super::test_helpers::base_app_state;
+
+const USER: &str = "U_TEST";
+
+fn config_toml(workspace_root: &str) -> String {
+    format!(
+        r#"# Operator notes survive runtime edits.
+default_workspace_root = '{root}'
+http_port = 0
+ipc_name = "test-command-alias"
+max_concurrent_sessions = 5
+host_cli = "echo"
+
+[slack]
+channel_id = "C_TEST"
+
+[timeouts]
+approval_seconds = 5
+prompt_seconds = 5
+wait_seconds = 5
+
+[stall]
+enabled = false
+inactivity_threshold_seconds = 300
+escalation_threshold_seconds = 120
+max_retries = 3
+default_nudge_message = "continue"
+
+# Shortcuts for the team.
+[commands]
+status = "git status"
+build = "cargo build"
+"#,
+        root = workspace_root.replace('\\', "\\\\"),
+    )
+}
+
+/// Audit logger that keeps entries in memory.
+#[derive(Default)]
+struct RecordingLogger {
+    entries: std::sync::Mutex<Vec<AuditEntry>>,
+}
+
+impl AuditLogger for RecordingLogger {
+    fn log_entry(&self, entry: AuditEntry) -> agent_intercom::Result<()> {
+        self.entries.lock().expect("entries").push(entry);
+        Ok(())
+    }
+}
+
+/// Build state from a config written to `dir`; `persist` controls whether
+/// the server knows the file path.
+async fn app_state(
+    dir: &Path,
+    persist: bool,
+    logger: Option<Arc<RecordingLogger>>,
+) -> (Arc<AppState>, PathBuf) {
+    let path = dir.join("config.toml");
+    let raw = config_toml(dir.to_str().expect("utf8"));
+    std::fs::write(&path, &raw).expect("write config");
+    let mut config = GlobalConfig::from_toml_str(&raw).expect("valid test config");
+    config.authorized_user_ids = vec![USER.to_owned()];
+    let state = Arc::new(AppState {
+        audit_logger: logger.map(|l| l as Arc<dyn AuditLogger>),
+        config_path: persist.then(|| path.clone()),
+        ..base_app_state(config).await
+    });
+    (state, path)
+}
+
+async fn run(state: &Arc<AppState>, args: &[&str]) -> agent_intercom::Result<String> {
+    dispatch_command("commands", args, USER, "C_OPS", state).await
+}
+
+#[tokio::test]
+async fn list_shows_configured_aliases_sorted() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let (state, _) = app_state(tmp.path(), true, None).await;
+
+    let reply = run(&state, &["list"]).await.expect("list");
+    let build = reply.find("`build` \u{2192} `cargo build`").expect("build");
+    let status = reply
+        .find("`status` \u{2192} `git status`")
+        .expect("status");
+    assert!(build < status, "{reply}");
+}
+
+#[tokio::test]
+async fn add_registers_live_writes_config_and_audits() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let logger = Arc::new(RecordingLogger::default());
+    let (state, path) = app_state(tmp.path(), true, Some(Arc::clone(&logger))).await;
+
+    let reply = run(&state, &["add", "test", "cargo", "test", "--workspace"])
+        .await
+        .expect("add");
+    assert!(reply.contains("saved to config.toml"), "{reply}");
+    assert_eq!(
+        command_aliases::list(&state)
+            .get("test")
+            .map(String::as_str),
+        Some("cargo test --workspace")
+    );
+    let listed = run(&state, &["list"]).await.expect("list");
+    assert!(
+        listed.contains("`test` \u{2192} `cargo test --workspace`"),
+        "{listed}"
+    );
+
+    let saved = std::fs::read_to_string(&path).expect("read config");
+    assert!(saved.contains("# Shortcuts for the team."));
+    let reloaded = GlobalConfig::from_toml_str(&saved).expect("edited config parses");
+    assert_eq!(reloaded.commands.len(), 3);
+    assert_eq!(
+        reloaded.commands.get("test").map(String::as_str),
+        Some("cargo test --workspace")
+    );
+
+    let entries = logger.entries.lock().expect("entries");
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].event_type, AuditEventType::CommandAliasAdded);
+    assert_eq!(entries[0].operator_id.as_deref(), Some(USER));
+    assert_eq!(
+        entries[0].command.as_deref(),
+        Some("cargo test --workspace")
+    );
+}
+
+#[tokio::test]
+async fn without_config_path_alias_lasts_for_the_run() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let (state, path) = app_state(tmp.path(), false, None).await;
+    let before = std::fs::read_to_string(&path).expect("read config");
+
+    let reply = run(&state, &["add", "lint", "cargo clippy"])
+        .await
+        .expect("add");
+    assert!(reply.contains("for this run only"), "{reply}");
+    assert!(command_aliases::list(&state).contains_key("lint"));
+    assert_eq!(std::fs::read_to_string(&path).expect("read config"), before);
+}
+
+#[tokio::test]
+async fn invalid_builtin_and_duplicate_aliases_are_refused() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let (state, path) = app_state(tmp.path(), true, None).await;
+    let before = std::fs::read_to_string(&path).expect("read config");
+
+    let invalid = run(&state, &["add", "rm;rf", "ls"])
+        .await
+        .expect_err("invalid alias");
+    assert!(invalid.to_string().contains("invalid alias"), "{invalid}");
+
+    let builtin = run(&state, &["add", "sessions", "ls"])
+        .await
+        .expect_err("built-in");
+    assert!(builtin.to_string().contains("built-in"), "{builtin}");
+
+    let duplicate = run(&state, &["add", "status", "git status -s"])
+        .await
+        .expect_err("duplicate");
+    assert!(
+        duplicate.to_string().contains("already runs"),
+        "{duplicate}"
+    );
+
+    let usage = run(&state, &["add", "lint"]).await.expect_err("usage");
+    assert!(usage.to_string().contains("usage: commands"), "{usage}");
+
+    assert_eq!(command_aliases::list(&state).len(), 2);
+    assert_eq!(std::fs::read_to_string(&path).expect("read config"), before);
+}
+
+#[test]
+fn observers_may_list_but_not_add() {
+    assert!(command_permitted(Role::Observer, "commands", &["list"]));
+    assert!(!command_permitted(
+        Role::Observer,
+        "commands",
+        &["add", "lint", "cargo clippy"]
+    ));
+    assert!(command_permitted(
+        Role::Operator,
+        "commands",
+        &["add", "lint", "cargo clippy"]
+    ));
+}
@@ -5,17 +5,21 @@
 //! - S-T1-021: Malformed arguments → descriptive usage message
 //! - S-T1-022: MCP mode accepts valid commands (steer)
 //! - S-T1-023: ACP-only commands are rejected in MCP mode with mode-mismatch message
+//! - Observers are limited to read-only commands
+//! - `session-restart` names the redacted variables it cannot pass on
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
-use agent_intercom::config::GlobalConfig;
+use agent_intercom::config::{GlobalConfig, Role, REDACTED};
 use agent_intercom::driver::mcp_driver::McpDriver;
 use agent_intercom::mode::ServerMode;
 use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
 use agent_intercom::persistence::db;
 use agent_intercom::persistence::session_repo::SessionRepo;
-use agent_intercom::slack::commands::dispatch_command;
+use agent_intercom::slack::commands::{
+    command_permitted, dispatch_command, parse_session_start_args, restart_env,
+};
 use agent_intercom::state::AppState;
 use tokio::sync::Mutex;
 
@@ -82,6 +86,27 @@ async fn app_state_with_mode(
         workspace_mappings: Arc::default(),
         acp_event_tx: None,
         acp_driver: None,
+        driver_registry: Arc::default(),
+        event_bus: Arc::default(),
+        session_hooks: Arc::default(),
+        pause_requests: Arc::default(),
+        heartbeats: Arc::default(),
+        instruction_queue: Arc::default(),
+        snoozes: Arc::default(),
+        apply_confirmations: Arc::default(),
+        session_logs: Arc::default(),
+        session_channels: Arc::default(),
+        workspace_discovery: Arc::default(),
+        config_path: None,
+        command_aliases: Arc::default(),
+        proxy: Arc::default(),
+        replay_guard: Arc::default(),
+        interactions: Arc::default(),
+        rearmed: Arc::default(),
+        diff_staging: Arc::default(),
+        subsystems: Arc::default(),
+        simulator: None,
+        log_filter: None,
     })
 }
 
@@ -303,12 +328,20 @@ async fn session_start_rejects_duplicate_channel_in_hot_reload_snapshot() {
             channel_id: "CDUP".into(),
             label: None,
             path: None,
+            proxy: Vec::new(),
+            approval_channels: Vec::new(),
+            working_hours: None,
+            bootstrap: None,
         });
         mappings.push(WorkspaceMapping {
             workspace_id: "repo-b".into(),
             channel_id: "CDUP".into(),
             label: None,
             path: None,
+            proxy: Vec::new(),
+            approval_channels: Vec::new(),
+            working_hours: None,
+            bootstrap: None,
         });
     }
 
@@ -439,3 +472,188 @@ async fn unknown_command_returns_descriptive_message() {
         "response must name the unknown command: {msg}"
     );
 }
+
+// ── session-start --max-duration ──────────────────────────────────────────────
+
+/// `--max-duration` is split off before the prompt words.
+#[test]
+fn session_start_args_parse_max_duration() {
+    let (options, prompt) =
+        parse_session_start_args(&["--max-duration", "2h", "fix", "the", "build"]).expect("parse");
+    assert_eq!(options.max_duration, Some(chrono::Duration::hours(2)));
+    assert_eq!(prompt, ["fix", "the", "build"]);
+
+    let (options, prompt) = parse_session_start_args(&["fix", "--max-duration"]).expect("parse");
+    assert_eq!(
+        options.max_duration, None,
+        "the flag is only recognised before the prompt"
+    );
+    assert_eq!(prompt, ["fix", "--max-duration"]);
+}
+
+/// A missing, malformed or out-of-range duration is a usage error.
+#[test]
+fn session_start_args_reject_bad_max_duration() {
+    assert!(parse_session_start_args(&["--max-duration"]).is_err());
+    assert!(parse_session_start_args(&["--max-duration", "soon", "go"]).is_err());
+    assert!(
+        parse_session_start_args(&["--max-duration", "106751991167300d", "go"]).is_err(),
+        "a budget past the timestamp range must not reach `created_at + budget`"
+    );
+}
+
+// ── session-start --issue ─────────────────────────────────────────────────────
+
+/// `--issue` combines with `--max-duration` in either order.
+#[test]
+fn session_start_args_parse_issue() {
+    let (options, prompt) =
+        parse_session_start_args(&["--issue", "PROJ-123", "--max-duration", "1h", "fix", "it"])
+            .expect("parse");
+    assert_eq!(options.issue.as_deref(), Some("PROJ-123"));
+    assert_eq!(options.max_duration, Some(chrono::Duration::hours(1)));
+    assert_eq!(prompt, ["fix", "it"]);
+
+    let (options, _) =
+        parse_session_start_args(&["--max-duration", "1h", "--issue", "acme/api#42", "go"])
+            .expect("parse");
+    assert_eq!(options.issue.as_deref(), Some("acme/api#42"));
+}
+
+/// A missing or unrecognisable issue reference is a usage error.
+#[test]
+fn session_start_args_reject_bad_issue() {
+    assert!(parse_session_start_args(&["--issue"]).is_err());
+    assert!(parse_session_start_args(&["--issue", "not an issue", "go"]).is_err());
+    assert!(parse_session_start_args(&["--issue", "lowercase-1", "go"]).is_err());
+}
+
+/// `--tag` is repeatable and the last value for a key wins.
+#[test]
+fn session_start_args_parse_tags() {
+    let (options, prompt) = parse_session_start_args(&[
+        "--tag",
+        "team=payments",
+        "--issue",
+        "PROJ-1",
+        "--tag",
+        "ticket=PROJ-1",
+        "--tag",
+        "team=billing",
+        "go",
+    ])
+    .expect("parse");
+    assert_eq!(prompt, ["go"]);
+    assert_eq!(options.tags.len(), 2);
+    assert_eq!(options.tags["team"], "billing");
+    assert_eq!(options.tags["ticket"], "PROJ-1");
+
+    assert!(parse_session_start_args(&["--tag"]).is_err());
+    assert!(parse_session_start_args(&["--tag", "payments", "go"]).is_err());
+}
+
+/// `--env` is repeatable; names are checked against `[session_env]` later.
+#[test]
+fn session_start_args_parse_env() {
+    let (options, prompt) = parse_session_start_args(&[
+        "--env",
+        "NODE_ENV=staging",
+        "--tag",
+        "team=web",
+        "--env",
+        "EMPTY=",
+        "go",
+    ])
+    .expect("parse");
+    assert_eq!(prompt, ["go"]);
+    assert_eq!(options.env["NODE_ENV"], "staging");
+    assert_eq!(options.env["EMPTY"], "");
+
+    assert!(parse_session_start_args(&["--env"]).is_err());
+    assert!(parse_session_start_args(&["--env", "NODE_ENV", "go"]).is_err());
+    assert!(parse_session_start_args(&["--env", "1BAD=x", "go"]).is_err());
+}
+
+/// `--snapshot` takes no value and combines with the other flags.
+#[test]
+fn session_start_args_parse_snapshot() {
+    let (options, prompt) =
+        parse_session_start_args(&["--snapshot", "--max-duration", "1h", "migrate", "the", "db"])
+            .expect("parse");
+    assert!(options.snapshot);
+    assert_eq!(options.max_duration, Some(chrono::Duration::hours(1)));
+    assert_eq!(prompt, ["migrate", "the", "db"]);
+
+    let (options, _) = parse_session_start_args(&["go"]).expect("parse");
+    assert!(!options.snapshot);
+}
+
+// ── session-restart ───────────────────────────────────────────────────────────
+
+/// Redacted variables cannot be passed on and are named for the operator.
+#[test]
+fn restart_env_names_redacted_variables() {
+    let recorded = BTreeMap::from([
+        ("API_TOKEN".to_owned(), REDACTED.to_owned()),
+        ("NODE_ENV".to_owned(), "staging".to_owned()),
+        ("DB_PASSWORD".to_owned(), REDACTED.to_owned()),
+    ]);
+    let (env, dropped) = restart_env(&recorded);
+    assert_eq!(
+        env,
+        BTreeMap::from([("NODE_ENV".to_owned(), "staging".to_owned())])
+    );
+    assert_eq!(dropped, ["API_TOKEN", "DB_PASSWORD"]);
+}
+
+// ── Roles ─────────────────────────────────────────────────────────────────────
+
+/// Operators win over observers; unknown users have no role.
+#[test]
+fn role_of_resolves_operators_then_observers() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let mut config = make_config(temp.path().to_str().expect("utf8"), "U_OPERATOR");
+    config.observer_user_ids = vec!["U_WATCHER".into(), "U_OPERATOR".into()];
+
+    assert_eq!(config.role_of("U_OPERATOR"), Some(Role::Operator));
+    assert_eq!(config.role_of("U_WATCHER"), Some(Role::Observer));
+    assert_eq!(config.role_of("U_STRANGER"), None);
+    assert!(config.ensure_authorized("U_WATCHER").is_err());
+}
+
+/// Observers may only run commands that report state.
+#[test]
+fn observers_may_only_run_read_only_commands() {
+    for (command, args) in [
+        ("help", &[][..]),
+        ("sessions", &["--all"][..]),
+        ("history", &[][..]),
+        ("decisions", &[][..]),
+        ("stalls", &[][..]),
+        ("logs", &["abc"][..]),
+        ("workspace", &["list"][..]),
+        ("workspace-snapshots", &[][..]),
+    ] {
+        assert!(
+            command_permitted(Role::Observer, command, args),
+            "{command} {args:?}"
+        );
+    }
+    for (command, args) in [
+        ("steer", &["go"][..]),
+        ("session-stop", &[][..]),
+        ("session-resume", &[][..]),
+        ("workspace", &["add", "ws", "C1"][..]),
+        ("show-file", &["src/main.rs"][..]),
+        ("pair", &[][..]),
+        ("share", &["abc"][..]),
+        ("workspace-snapshot", &[][..]),
+        ("workspace-restore", &["abc"][..]),
+    ] {
+        assert!(
+            !command_permitted(Role::Observer, command, args),
+            "{command} {args:?}"
+        );
+        assert!(command_permitted(Role::Operator, command, args));
+    }
+}
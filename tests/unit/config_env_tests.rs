@@ -0,0 +1,107 @@
+//! Unit tests for `INTERCOM_*` environment overrides over `config.toml`.
+
+use agent_intercom::config::GlobalConfig;
+use agent_intercom::AppError;
+
+fn base_toml(workspace: &str) -> String {
+    format!(
+        r#"
+default_workspace_root = '{workspace}'
+ipc_name = "agent-intercom"
+host_cli = "claude"
+
+[slack]
+channel_id = "C123"
+
+[timeouts]
+approval_seconds = 3600
+prompt_seconds = 1800
+wait_seconds = 0
+
+[stall]
+enabled = false
+inactivity_threshold_seconds = 300
+escalation_threshold_seconds = 120
+max_retries = 3
+default_nudge_message = "continue"
+"#
+    )
+}
+
+fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()))
+        .collect()
+}
+
+fn parse(pairs: &[(&str, &str)]) -> agent_intercom::Result<GlobalConfig> {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let toml = base_toml(temp.path().to_str().expect("utf8"));
+    GlobalConfig::from_toml_layers(&toml, None, vars(pairs))
+}
+
+#[test]
+fn overrides_top_level_and_nested_keys() {
+    let config = parse(&[
+        ("INTERCOM_HTTP_PORT", "4100"),
+        ("INTERCOM_STALL__ENABLED", "true"),
+        ("INTERCOM_SLACK__CHANNEL_ID", "C999"),
+        ("INTERCOM_HOST_CLI_ARGS", r#"["--stdio", "-v"]"#),
+        ("INTERCOM_ACP__MAX_SESSIONS", "9"),
+        ("PATH", "/usr/bin"),
+    ])
+    .expect("overrides apply");
+
+    assert_eq!(config.http_port, 4100);
+    assert!(config.stall.enabled);
+    assert_eq!(config.slack.channel_id, "C999");
+    assert_eq!(config.host_cli_args, ["--stdio", "-v"]);
+    assert_eq!(config.acp.max_sessions, 9);
+    assert_eq!(
+        config.stall.max_retries, 3,
+        "untouched keys keep file values"
+    );
+}
+
+#[test]
+fn string_keys_take_raw_text() {
+    let config = parse(&[
+        ("INTERCOM_IPC_NAME", "1234"),
+        ("INTERCOM_COMMANDS__STATUS", "git status -s"),
+    ])
+    .expect("overrides apply");
+
+    assert_eq!(config.ipc_name, "1234");
+    assert_eq!(
+        config.commands.get("status").map(String::as_str),
+        Some("git status -s")
+    );
+}
+
+#[test]
+fn unknown_key_names_the_variable() {
+    let Err(AppError::Config(msg)) = parse(&[("INTERCOM_STALL__ENABLD", "true")]) else {
+        panic!("unknown override must fail");
+    };
+    assert!(msg.contains("INTERCOM_STALL__ENABLD"), "{msg}");
+    assert!(msg.contains("stall.enabld"), "{msg}");
+}
+
+#[test]
+fn invalid_value_is_rejected() {
+    assert!(parse(&[("INTERCOM_HTTP_PORT", "not a port")]).is_err());
+    assert!(parse(&[("INTERCOM_HTTP_PORT", "70000")]).is_err());
+}
+
+#[test]
+fn spawner_variables_are_not_overrides() {
+    let config = parse(&[
+        ("INTERCOM_SESSION_ID", "3f2a9c1e"),
+        ("INTERCOM_MCP_URL", "http://127.0.0.1:3000/mcp"),
+        ("INTERCOM_WORKSPACE_ROOT", "/elsewhere"),
+    ])
+    .expect("reserved variables are ignored");
+
+    assert_eq!(config.http_port, 3000);
+}
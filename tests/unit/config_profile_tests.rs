@@ -0,0 +1,112 @@
+//! Unit tests for `[profile.<name>]` overlays in `config.toml`.
+
+use agent_intercom::config::{GlobalConfig, SlackDetailLevel};
+use agent_intercom::AppError;
+
+fn profiled_toml(workspace: &str) -> String {
+    format!(
+        r#"
+default_workspace_root = '{workspace}'
+host_cli = "claude"
+http_port = 3000
+
+[slack]
+channel_id = "C-BASE"
+
+[timeouts]
+approval_seconds = 3600
+prompt_seconds = 1800
+wait_seconds = 0
+
+[stall]
+enabled = true
+inactivity_threshold_seconds = 300
+escalation_threshold_seconds = 120
+max_retries = 3
+default_nudge_message = "continue"
+
+[profile.dev]
+http_port = 4000
+slack_detail_level = "verbose"
+
+[profile.dev.slack]
+channel_id = "C-DEV"
+
+[profile.dev.timeouts]
+approval_seconds = 60
+
+[profile.prod]
+slack_detail_level = "minimal"
+"#
+    )
+}
+
+fn parse(profile: Option<&str>, vars: &[(&str, &str)]) -> agent_intercom::Result<GlobalConfig> {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let toml = profiled_toml(temp.path().to_str().expect("utf8"));
+    let vars = vars.iter().map(|(k, v)| ((*k).to_owned(), (*v).to_owned()));
+    GlobalConfig::from_toml_layers(&toml, profile, vars)
+}
+
+#[test]
+fn profile_overrides_base_and_merges_nested_tables() {
+    let config = parse(Some("dev"), &[]).expect("dev profile");
+
+    assert_eq!(config.http_port, 4000);
+    assert_eq!(config.slack_detail_level, SlackDetailLevel::Verbose);
+    assert_eq!(config.slack.channel_id, "C-DEV");
+    assert_eq!(config.timeouts.approval_seconds, 60);
+    assert_eq!(
+        config.timeouts.prompt_seconds, 1800,
+        "keys the profile does not set keep base values"
+    );
+    assert!(config.stall.enabled);
+}
+
+#[test]
+fn base_settings_apply_without_a_profile() {
+    let config = parse(None, &[]).expect("base");
+
+    assert_eq!(config.http_port, 3000);
+    assert_eq!(config.slack.channel_id, "C-BASE");
+    assert_eq!(config.slack_detail_level, SlackDetailLevel::Standard);
+}
+
+#[test]
+fn intercom_profile_selects_and_flag_wins() {
+    let from_env = parse(None, &[("INTERCOM_PROFILE", "prod")]).expect("env profile");
+    assert_eq!(from_env.slack_detail_level, SlackDetailLevel::Minimal);
+
+    let flag = parse(Some("dev"), &[("INTERCOM_PROFILE", "prod")]).expect("flag profile");
+    assert_eq!(flag.slack_detail_level, SlackDetailLevel::Verbose);
+}
+
+#[test]
+fn environment_overrides_win_over_profile() {
+    let config = parse(Some("dev"), &[("INTERCOM_HTTP_PORT", "5000")]).expect("layers");
+
+    assert_eq!(config.http_port, 5000);
+    assert_eq!(config.slack.channel_id, "C-DEV");
+}
+
+#[test]
+fn unknown_profile_lists_defined_ones() {
+    let Err(AppError::Config(msg)) = parse(Some("staging"), &[]) else {
+        panic!("unknown profile must fail");
+    };
+    assert!(msg.contains("'staging'"), "{msg}");
+    assert!(msg.contains("dev, prod"), "{msg}");
+}
+
+#[test]
+fn misspelled_key_in_profile_is_rejected() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let toml = profiled_toml(temp.path().to_str().expect("utf8"))
+        .replace("[profile.prod]\n", "[profile.prod]\nhttp_prot = 1\n");
+
+    assert!(GlobalConfig::from_toml_layers(&toml, Some("prod"), Vec::new()).is_err());
+    assert_eq!(
+        GlobalConfig::profile_names(&toml).expect("names"),
+        ["dev", "prod"]
+    );
+}
@@ -0,0 +1,86 @@
+//! Unit tests for `ConflictRepo` (apply conflict arbitration).
+//!
+//! Tests cover:
+//! - Conflicts round-trip with their kind and start unresolved
+//! - The first resolution wins; later ones change nothing
+//! - Conflicts are listed by approval request, oldest first
+
+use std::sync::Arc;
+
+use agent_intercom::models::conflict::{ApplyConflict, ConflictKind, ConflictResolution};
+use agent_intercom::persistence::{conflict_repo::ConflictRepo, db};
+use chrono::Utc;
+
+async fn repo() -> ConflictRepo {
+    ConflictRepo::new(Arc::new(db::connect_memory().await.expect("db")))
+}
+
+fn conflict(request_id: &str, kind: ConflictKind) -> ApplyConflict {
+    ApplyConflict::new(
+        request_id.into(),
+        "session-1".into(),
+        "src/main.rs".into(),
+        kind,
+        "file content has changed since proposal was created".into(),
+    )
+}
+
+#[tokio::test]
+async fn first_resolution_wins() {
+    let repo = repo().await;
+    let pending = conflict("req-1", ConflictKind::HashMismatch);
+    repo.create(&pending).await.expect("create");
+
+    let stored = repo
+        .get_by_id(&pending.id)
+        .await
+        .expect("query")
+        .expect("conflict");
+    assert_eq!(stored.kind, ConflictKind::HashMismatch);
+    assert_eq!(stored.resolution, None);
+
+    assert!(repo
+        .resolve(
+            &pending.id,
+            ConflictResolution::Merge,
+            Some("U1"),
+            Utc::now()
+        )
+        .await
+        .expect("resolve"));
+    assert!(!repo
+        .resolve(&pending.id, ConflictResolution::Expired, None, Utc::now())
+        .await
+        .expect("resolve again"));
+
+    let stored = repo
+        .get_by_id(&pending.id)
+        .await
+        .expect("query")
+        .expect("conflict");
+    assert_eq!(stored.resolution, Some(ConflictResolution::Merge));
+    assert_eq!(stored.resolved_by.as_deref(), Some("U1"));
+    assert!(stored.resolved_at.is_some());
+}
+
+#[tokio::test]
+async fn conflicts_listed_by_request() {
+    let repo = repo().await;
+    let first = conflict("req-1", ConflictKind::HashMismatch);
+    let mut second = conflict("req-1", ConflictKind::HunkConflict);
+    second.created_at = first.created_at + chrono::Duration::seconds(1);
+    repo.create(&second).await.expect("create");
+    repo.create(&first).await.expect("create");
+    repo.create(&conflict("req-2", ConflictKind::HunkConflict))
+        .await
+        .expect("create");
+
+    let listed: Vec<_> = repo
+        .list_for_request("req-1")
+        .await
+        .expect("list")
+        .into_iter()
+        .map(|c| c.id)
+        .collect();
+    assert_eq!(listed, vec![first.id, second.id]);
+}
@@ -0,0 +1,68 @@
+//! Unit tests for `ContextRepo` (per-session key/value context).
+//!
+//! Tests cover:
+//! - Values round-trip and an upsert replaces the previous value
+//! - Entries are scoped to their session and listed by key
+//! - Deleting reports whether the key existed
+
+use std::sync::Arc;
+
+use agent_intercom::persistence::{context_repo::ContextRepo, db};
+use chrono::Utc;
+use serde_json::json;
+
+async fn repo() -> ContextRepo {
+    ContextRepo::new(Arc::new(db::connect_memory().await.expect("db")))
+}
+
+#[tokio::test]
+async fn upsert_replaces_previous_value() {
+    let repo = repo().await;
+    repo.upsert("s1", "ci_url", &json!("https://ci/1"), Utc::now())
+        .await
+        .expect("insert");
+    repo.upsert(
+        "s1",
+        "ci_url",
+        &json!({ "url": "https://ci/2" }),
+        Utc::now(),
+    )
+    .await
+    .expect("update");
+
+    let entry = repo.get("s1", "ci_url").await.expect("get").expect("entry");
+    assert_eq!(entry.value, json!({ "url": "https://ci/2" }));
+    assert_eq!(repo.count_for_session("s1").await.expect("count"), 1);
+}
+
+#[tokio::test]
+async fn entries_are_scoped_to_session_and_ordered_by_key() {
+    let repo = repo().await;
+    for (session, key) in [("s1", "zeta"), ("s1", "alpha"), ("s2", "alpha")] {
+        repo.upsert(session, key, &json!(session), Utc::now())
+            .await
+            .expect("upsert");
+    }
+
+    let keys: Vec<String> = repo
+        .list_for_session("s1")
+        .await
+        .expect("list")
+        .into_iter()
+        .map(|entry| entry.key)
+        .collect();
+    assert_eq!(keys, ["alpha", "zeta"]);
+    assert!(repo.get("s2", "zeta").await.expect("get").is_none());
+}
+
+#[tokio::test]
+async fn delete_reports_whether_key_existed() {
+    let repo = repo().await;
+    repo.upsert("s1", "decision", &json!("use sqlx"), Utc::now())
+        .await
+        .expect("upsert");
+
+    assert!(repo.delete("s1", "decision").await.expect("delete"));
+    assert!(!repo.delete("s1", "decision").await.expect("delete again"));
+    assert_eq!(repo.count_for_session("s1").await.expect("count"), 0);
+}
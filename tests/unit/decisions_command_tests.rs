@@ -0,0 +1,183 @@
+//! Unit tests for the `decisions` slash command.
+//!
+//! Validates:
+//! - History-view argument parsing for `[session_id] [--limit N]`
+//! - Decisions combine approval records with the audit log's operator and
+//!   timing, and link to the request message
+//! - Decisions without an audit record are still listed
+//! - Empty history and unknown sessions are reported
+
+use std::sync::Arc;
+
+use agent_intercom::audit::{self, AuditEntry, AuditEventType, AuditLogger, JsonlAuditWriter};
+use agent_intercom::config::GlobalConfig;
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::models::session::{Session, SessionMode};
+use agent_intercom::persistence::approval_repo::ApprovalRepo;
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::slack::commands::{dispatch_command, parse_history_args};
+use agent_intercom::state::AppState;
+
+use super::test_helpers::test_app_state;
+
+const USER: &str = "U_TEST";
+
+fn make_config(workspace_root: &str) -> GlobalConfig {
+    let toml = format!(
+        r#"
+default_workspace_root = '{root}'
+http_port = 0
+ipc_name = "test-decisions"
+max_concurrent_sessions = 5
+host_cli = "echo"
+
+[slack]
+channel_id = "C_TEST"
+
+[timeouts]
+approval_seconds = 5
+prompt_seconds = 5
+wait_seconds = 5
+
+[stall]
+enabled = false
+inactivity_threshold_seconds = 300
+escalation_threshold_seconds = 120
+max_retries = 3
+default_nudge_message = "continue"
+"#,
+        root = workspace_root.replace('\\', "\\\\"),
+    );
+    let mut config = GlobalConfig::from_toml_str(&toml).expect("valid test config");
+    config.authorized_user_ids = vec![USER.to_owned()];
+    config
+}
+
+async fn app_state(workspace_root: &str) -> Arc<AppState> {
+    test_app_state(make_config(workspace_root)).await
+}
+
+async fn create_session(state: &AppState, root: &str) -> Session {
+    let mut session = Session::new(USER.into(), root.into(), None, SessionMode::Remote);
+    session.channel_id = Some("C_TEST".into());
+    SessionRepo::new(Arc::clone(&state.db))
+        .create(&session)
+        .await
+        .expect("create session")
+}
+
+/// Persist a decided approval raised five minutes ago.
+async fn decided(
+    state: &AppState,
+    session_id: &str,
+    title: &str,
+    status: ApprovalStatus,
+) -> String {
+    let repo = ApprovalRepo::new(Arc::clone(&state.db));
+    let mut req = ApprovalRequest::new(
+        session_id.to_owned(),
+        title.to_owned(),
+        None,
+        "+x".to_owned(),
+        "src/lib.rs".to_owned(),
+        RiskLevel::Low,
+        "new_file".to_owned(),
+    );
+    req.created_at -= chrono::Duration::minutes(5);
+    repo.create(&req).await.expect("create approval");
+    repo.update_status(&req.id, status).await.expect("status");
+    req.id
+}
+
+#[test]
+fn parses_session_and_limit() {
+    assert_eq!(
+        parse_history_args("decisions", &[]).expect("parse"),
+        (None, 10)
+    );
+    assert_eq!(
+        parse_history_args("decisions", &["abc", "--limit", "3"]).expect("parse"),
+        (Some("abc"), 3)
+    );
+    assert_eq!(
+        parse_history_args("decisions", &["--limit", "25"]).expect("parse"),
+        (None, 25)
+    );
+    assert!(parse_history_args("decisions", &["--limit"]).is_err());
+    assert!(parse_history_args("decisions", &["--limit", "0"]).is_err());
+    assert!(parse_history_args("decisions", &["--limit", "500"]).is_err());
+    assert!(parse_history_args("decisions", &["a", "b"]).is_err());
+}
+
+#[tokio::test]
+async fn lists_decisions_with_operator_latency_and_link() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let state = app_state(root).await;
+    let session = create_session(&state, root).await;
+
+    let approved = decided(&state, &session.id, "Add parser", ApprovalStatus::Approved).await;
+    ApprovalRepo::new(Arc::clone(&state.db))
+        .set_slack_anchor(&approved, "1700000000.000100", "1699999999.000001")
+        .await
+        .expect("anchor");
+    let rejected = decided(&state, &session.id, "Drop table", ApprovalStatus::Rejected).await;
+    decided(&state, &session.id, "Via ctl", ApprovalStatus::Approved).await;
+
+    let writer = JsonlAuditWriter::new(audit::log_dir(tmp.path())).expect("writer");
+    writer
+        .log_entry(
+            AuditEntry::new(AuditEventType::Approval)
+                .with_request_id(approved)
+                .with_operator("U_ALICE".into()),
+        )
+        .expect("log");
+    writer
+        .log_entry(
+            AuditEntry::new(AuditEventType::Rejection)
+                .with_request_id(rejected)
+                .with_operator("webhook:ci-bot".into())
+                .with_reason("tests are red".into()),
+        )
+        .expect("log");
+
+    let reply = dispatch_command("decisions", &[&session.id[..8]], USER, "C_TEST", &state)
+        .await
+        .expect("decisions");
+
+    assert!(
+        reply.contains(&format!("session `{}`", session.id)),
+        "{reply}"
+    );
+    assert!(reply.contains("(3 shown)"), "{reply}");
+    assert!(reply.contains("by <@U_ALICE> after 5m"), "{reply}");
+    assert!(
+        reply.contains(
+            "<https://slack.com/archives/C_TEST/p1700000000000100\
+             ?thread_ts=1699999999.000001&cid=C_TEST|view request>"
+        ),
+        "{reply}"
+    );
+    assert!(reply.contains("*Rejected* — _Drop table_"), "{reply}");
+    assert!(reply.contains("by `webhook:ci-bot`"), "{reply}");
+    assert!(reply.contains("\"tests are red\""), "{reply}");
+    assert!(
+        reply.contains("_Via ctl_ `src/lib.rs` · decided outside Slack"),
+        "{reply}"
+    );
+}
+
+#[tokio::test]
+async fn reports_empty_history_and_unknown_sessions() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let state = app_state(root).await;
+
+    let reply = dispatch_command("decisions", &[], USER, "C_TEST", &state)
+        .await
+        .expect("decisions");
+    assert_eq!(reply, "No decided approval requests for all sessions.");
+
+    let result = dispatch_command("decisions", &["nope"], USER, "C_TEST", &state).await;
+    assert!(result.is_err(), "unknown session must be an error");
+}
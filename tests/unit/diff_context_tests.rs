@@ -0,0 +1,149 @@
+//! Unit tests for language-aware diff context expansion
+//! (`diff::context`).
+//!
+//! Tests cover:
+//! - Rust hunks widened to the whole function, with its doc comment and
+//!   signature, and the widened diff still applying cleanly
+//! - Hunks in one function merged into a single hunk
+//! - Python hunks widened to the enclosing `def` and its decorator
+//! - Oversized blocks, unsupported languages and stale diffs left alone
+
+use std::fmt::Write as _;
+
+use agent_intercom::diff::context::{expand_context, MAX_BLOCK_LINES};
+
+const RUST: &str = "\
+use std::fmt;
+
+/// Adds things.
+pub fn first(a: i32) -> i32 {
+    let b = a + 1;
+    let c = b + 1;
+    let d = c + 1;
+    let e = d + 1;
+    e
+}
+
+/// Retries the call.
+#[must_use]
+pub fn second(
+    attempts: u32,
+) -> u32 {
+    let mut n = 0;
+    for _ in 0..attempts {
+        n += 1;
+    }
+    let x = n * 2;
+    let y = x * 2;
+    let z = y * 2;
+    let w = z * 2;
+    if w > 100 {
+        return 100;
+    }
+    w
+}
+
+fn third() {}
+";
+
+fn patch(original: &str, modified: &str) -> String {
+    diffy::create_patch(original, modified).to_string()
+}
+
+#[test]
+fn rust_hunk_widens_to_the_whole_function() {
+    let modified = RUST.replace("let y = x * 2;", "let y = x * 3;");
+    let diff = patch(RUST, &modified);
+    let expanded = expand_context(&diff, RUST, "rust").expect("expanded");
+
+    for line in [
+        " /// Retries the call.",
+        " #[must_use]",
+        " pub fn second(",
+        "     let mut n = 0;",
+        "-    let y = x * 2;",
+        "+    let y = x * 3;",
+        "     w",
+        " }",
+    ] {
+        assert!(
+            expanded.lines().any(|l| l == line),
+            "missing {line:?}:\n{expanded}"
+        );
+    }
+    assert!(!expanded.contains("let e = d + 1;"), "{expanded}");
+    assert!(!expanded.contains("fn third"), "{expanded}");
+
+    let parsed = diffy::Patch::from_str(&expanded).expect("valid diff");
+    assert_eq!(diffy::apply(RUST, &parsed).expect("applies"), modified);
+}
+
+#[test]
+fn hunks_in_one_function_merge() {
+    let modified = RUST
+        .replace("let mut n = 0;", "let mut n = 1;")
+        .replace("return 100;", "return 99;");
+    let diff = patch(RUST, &modified);
+    let expanded = expand_context(&diff, RUST, "rust").expect("expanded");
+
+    assert_eq!(expanded.matches("@@ -").count(), 1, "{expanded}");
+    let parsed = diffy::Patch::from_str(&expanded).expect("valid diff");
+    assert_eq!(diffy::apply(RUST, &parsed).expect("applies"), modified);
+}
+
+#[test]
+fn python_hunk_widens_to_the_enclosing_def() {
+    let original = "\
+import os
+
+
+@retry(times=3)
+def fetch(url):
+    session = make_session()
+    if url:
+        a = 1
+        b = 2
+        c = 3
+        d = 4
+        return session.get(url)
+    return None
+
+
+def other():
+    pass
+";
+    let modified = original.replace("c = 3", "c = 30");
+    let diff = patch(original, &modified);
+    let expanded = expand_context(&diff, original, "python").expect("expanded");
+
+    assert!(
+        expanded.contains(" @retry(times=3)\n def fetch(url):"),
+        "{expanded}"
+    );
+    assert!(expanded.contains("     return None"), "{expanded}");
+    assert!(!expanded.contains("def other"), "{expanded}");
+    let parsed = diffy::Patch::from_str(&expanded).expect("valid diff");
+    assert_eq!(diffy::apply(original, &parsed).expect("applies"), modified);
+}
+
+#[test]
+fn oversized_blocks_and_unsupported_input_are_left_alone() {
+    let mut long = String::from("fn long() {\n");
+    for i in 0..MAX_BLOCK_LINES + 10 {
+        let _ = writeln!(long, "    let v{i} = {i};");
+    }
+    long.push_str("}\n");
+    let modified = long.replace("let v30 = 30;", "let v30 = 31;");
+    assert_eq!(
+        expand_context(&patch(&long, &modified), &long, "rust"),
+        None
+    );
+
+    let modified = RUST.replace("let y = x * 2;", "let y = x * 3;");
+    let diff = patch(RUST, &modified);
+    assert_eq!(expand_context(&diff, RUST, "markdown"), None);
+    assert_eq!(expand_context("fn whole_file() {}\n", RUST, "rust"), None);
+
+    let stale = RUST.replace("let x = n * 2;", "let x = n * 5;");
+    assert_eq!(expand_context(&diff, &stale, "rust"), None);
+}
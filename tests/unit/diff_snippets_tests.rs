@@ -0,0 +1,113 @@
+//! Unit tests for review snippets generated from a diff (`diff::snippets`).
+//!
+//! Tests cover:
+//! - One snippet per hunk, widened to the enclosing function and labelled
+//!   with its declaration and the line range after the change
+//! - The declaration found above a hunk that starts inside a long block
+//! - Deletion-only hunks, raw file content, the `max` cap and long regions
+
+use std::fmt::Write as _;
+
+use agent_intercom::diff::snippets::{generate, MAX_SNIPPET_CHARS};
+
+const RUST: &str = "\
+use std::fmt;
+
+/// Adds one.
+pub fn first(a: i32) -> i32 {
+    let b = a + 1;
+    b
+}
+
+fn between() {}
+
+/// Retries the call.
+pub async fn second(attempts: u32) -> u32 {
+    let mut n = 0;
+    for _ in 0..attempts {
+        n += 1;
+    }
+    n
+}
+";
+
+fn patch(original: &str, modified: &str) -> String {
+    diffy::create_patch(original, modified).to_string()
+}
+
+#[test]
+fn one_snippet_per_hunk_labelled_with_its_declaration() {
+    let modified = RUST
+        .replace("let b = a + 1;", "let b = a + 2;")
+        .replace("n += 1;", "n += 2;");
+    let diff = patch(RUST, &modified);
+    let snippets = generate(&diff, Some(RUST), "rust", 5);
+
+    assert_eq!(snippets.len(), 2, "{snippets:?}");
+    assert_eq!(
+        snippets[0].label,
+        "pub fn first(a: i32) -> i32 \u{2014} lines 3\u{2013}7"
+    );
+    assert!(snippets[0]
+        .content
+        .starts_with("/// Adds one.\npub fn first"));
+    assert!(snippets[0].content.contains("let b = a + 2;"));
+    assert!(!snippets[0].content.contains("a + 1"));
+    assert!(snippets[1]
+        .label
+        .starts_with("pub async fn second(attempts: u32) -> u32 \u{2014} lines "));
+    assert!(
+        snippets[1].content.ends_with("    n\n}"),
+        "{:?}",
+        snippets[1]
+    );
+}
+
+#[test]
+fn declaration_above_a_hunk_is_used_when_the_block_is_too_long() {
+    let mut original = String::from("fn long() {\n");
+    for i in 0..100 {
+        let _ = writeln!(original, "    let v{i} = {i};");
+    }
+    original.push_str("}\n");
+    let modified = original.replace("let v50 = 50;", "let v50 = 51;");
+    let snippets = generate(&patch(&original, &modified), Some(&original), "rust", 5);
+
+    assert_eq!(snippets.len(), 1);
+    assert_eq!(snippets[0].label, "fn long() \u{2014} lines 49\u{2013}55");
+    assert!(snippets[0].content.contains("let v50 = 51;"));
+}
+
+#[test]
+fn deletions_raw_content_and_caps() {
+    let original = "a\nb\nc\n";
+    let diff = "--- a/f.txt\n+++ b/f.txt\n@@ -1,3 +0,0 @@\n-a\n-b\n-c\n";
+    let snippets = generate(diff, Some(original), "text", 5);
+    assert_eq!(snippets[0].label, "Lines 1\u{2013}3 (removed)");
+    assert_eq!(snippets[0].content, "a\nb\nc");
+
+    let raw = generate("fn main() {}\n", None, "rust", 5);
+    assert_eq!(raw.len(), 1);
+    assert_eq!(raw[0].label, "Whole file \u{2014} line 1");
+
+    let modified = RUST
+        .replace("let b = a + 1;", "let b = a + 2;")
+        .replace("n += 1;", "n += 2;");
+    assert_eq!(
+        generate(&patch(RUST, &modified), Some(RUST), "rust", 1).len(),
+        1
+    );
+    assert!(generate("", None, "rust", 5).is_empty());
+
+    let long: String = (0..500).fold(String::new(), |mut out, i| {
+        let _ = writeln!(out, "line number {i} of a long new file");
+        out
+    });
+    let snippet = &generate(&long, None, "text", 5)[0];
+    assert!(snippet.content.len() <= MAX_SNIPPET_CHARS + 40);
+    assert!(
+        snippet.content.ends_with("more lines)"),
+        "{}",
+        snippet.content
+    );
+}
@@ -0,0 +1,71 @@
+//! Unit tests for the per-session [`DriverRegistry`].
+
+use std::sync::Arc;
+
+use agent_intercom::driver::acp_driver::AcpDriver;
+use agent_intercom::driver::mcp_driver::McpDriver;
+use agent_intercom::driver::registry::DriverRegistry;
+use agent_intercom::driver::AgentDriver;
+
+/// Compare two drivers by allocation, ignoring vtable metadata.
+fn same_driver(a: &Arc<dyn AgentDriver>, b: &Arc<dyn AgentDriver>) -> bool {
+    std::ptr::eq(Arc::as_ptr(a).cast::<()>(), Arc::as_ptr(b).cast::<()>())
+}
+
+#[tokio::test]
+async fn register_and_get_returns_bound_driver() {
+    let registry = DriverRegistry::new();
+    let acp: Arc<dyn AgentDriver> = Arc::new(AcpDriver::new());
+
+    assert!(registry
+        .register("sess-1", Arc::clone(&acp))
+        .await
+        .is_none());
+
+    let found = registry.get("sess-1").await.expect("driver registered");
+    assert!(same_driver(&found, &acp));
+    assert!(registry.contains("sess-1").await);
+    assert_eq!(registry.len().await, 1);
+}
+
+#[tokio::test]
+async fn register_replaces_previous_binding() {
+    let registry = DriverRegistry::new();
+    let first = McpDriver::new_empty();
+    let second: Arc<dyn AgentDriver> = Arc::new(AcpDriver::new());
+
+    registry.register("sess-1", Arc::clone(&first)).await;
+    let previous = registry
+        .register("sess-1", Arc::clone(&second))
+        .await
+        .expect("previous binding returned");
+
+    assert!(same_driver(&previous, &first));
+    let current = registry.get("sess-1").await.expect("driver registered");
+    assert!(same_driver(&current, &second));
+}
+
+#[tokio::test]
+async fn deregister_removes_binding() {
+    let registry = DriverRegistry::new();
+    registry.register("sess-1", McpDriver::new_empty()).await;
+
+    assert!(registry.deregister("sess-1").await.is_some());
+    assert!(registry.get("sess-1").await.is_none());
+    assert!(registry.is_empty().await);
+    assert!(registry.deregister("sess-1").await.is_none());
+}
+
+#[tokio::test]
+async fn resolve_falls_back_for_unknown_session() {
+    let registry = DriverRegistry::new();
+    let fallback = McpDriver::new_empty();
+    let acp: Arc<dyn AgentDriver> = Arc::new(AcpDriver::new());
+    registry.register("acp-sess", Arc::clone(&acp)).await;
+
+    let bound = registry.resolve("acp-sess", &fallback).await;
+    assert!(same_driver(&bound, &acp));
+
+    let unbound = registry.resolve("mcp-sess", &fallback).await;
+    assert!(same_driver(&unbound, &fallback));
+}
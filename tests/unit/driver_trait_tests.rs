@@ -2,6 +2,7 @@
 
 use agent_intercom::driver::AgentEvent;
 use agent_intercom::models::progress::{ProgressItem, ProgressStatus};
+use agent_intercom::AppError;
 
 #[test]
 fn agent_event_clearance_requested_constructs_and_accesses_fields() {
@@ -13,6 +14,7 @@ fn agent_event_clearance_requested_constructs_and_accesses_fields() {
         diff: Some("--- /dev/null\n+++ b/src/driver/mod.rs".into()),
         file_path: "src/driver/mod.rs".into(),
         risk_level: "low".into(),
+        snippets: Vec::new(),
     };
 
     if let AgentEvent::ClearanceRequested {
@@ -23,6 +25,7 @@ fn agent_event_clearance_requested_constructs_and_accesses_fields() {
         diff,
         file_path,
         risk_level,
+        snippets,
     } = &event
     {
         assert_eq!(request_id, "req-001");
@@ -32,6 +35,7 @@ fn agent_event_clearance_requested_constructs_and_accesses_fields() {
         assert!(diff.is_some());
         assert_eq!(file_path, "src/driver/mod.rs");
         assert_eq!(risk_level, "low");
+        assert!(snippets.is_empty());
     } else {
         panic!("wrong variant");
     }
@@ -47,6 +51,7 @@ fn agent_event_clearance_requested_with_no_diff() {
         diff: None,
         file_path: "src/old.rs".into(),
         risk_level: "high".into(),
+        snippets: Vec::new(),
     };
 
     if let AgentEvent::ClearanceRequested { diff, .. } = &event {
@@ -213,19 +218,20 @@ fn agent_event_clones_correctly() {
     }
 }
 
-// ── T024: interrupt unknown session is idempotent ─────────────────────────────
+// ── T024: interrupt without a connected client fails ──────────────────────────
 
-/// T024 — `McpDriver::interrupt` on an unknown `session_id` must return `Ok(())`
-/// because interruption is defined as idempotent in the `AgentDriver` contract.
+/// T024 — `McpDriver::interrupt` without a connected client must return
+/// `NotFound` rather than pretend the agent was interrupted, so callers fall
+/// back to queueing.
 #[tokio::test]
-async fn mcp_driver_interrupt_unknown_session_is_idempotent() {
+async fn mcp_driver_interrupt_without_client_is_not_found() {
     use agent_intercom::driver::mcp_driver::McpDriver;
 
     let driver = McpDriver::new_empty();
     let result = driver.interrupt("session-does-not-exist").await;
     assert!(
-        result.is_ok(),
-        "interrupt on unknown session must be Ok(())"
+        matches!(result, Err(AppError::NotFound(_))),
+        "interrupt without a client must be NotFound, got {result:?}"
     );
 }
 
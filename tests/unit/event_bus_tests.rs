@@ -0,0 +1,196 @@
+//! Unit tests for the agent event bus and its subscribers.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use agent_intercom::audit::{AuditEntry, AuditEventType, AuditLogger};
+use agent_intercom::driver::event_bus::{BusEvent, EventBus};
+use agent_intercom::driver::AgentEvent;
+use agent_intercom::models::session::ProtocolMode;
+use agent_intercom::orchestrator::event_subscribers::{
+    audit_entry_for, spawn_audit_subscriber, spawn_metrics_subscriber,
+};
+use agent_intercom::slack::client::SlackMessage;
+use slack_morphism::prelude::SlackChannelId;
+use tokio_util::sync::CancellationToken;
+
+fn heartbeat(session_id: &str) -> AgentEvent {
+    AgentEvent::HeartbeatReceived {
+        session_id: session_id.into(),
+        progress: None,
+    }
+}
+
+fn terminated(session_id: &str) -> AgentEvent {
+    AgentEvent::SessionTerminated {
+        session_id: session_id.into(),
+        exit_code: Some(1),
+        reason: "crashed".into(),
+    }
+}
+
+/// Audit logger that keeps entries in memory.
+#[derive(Default)]
+struct RecordingLogger {
+    entries: Mutex<Vec<AuditEntry>>,
+}
+
+impl AuditLogger for RecordingLogger {
+    fn log_entry(&self, entry: AuditEntry) -> agent_intercom::Result<()> {
+        self.entries.lock().unwrap().push(entry);
+        Ok(())
+    }
+}
+
+/// Poll `check` until it returns true or one second elapses.
+async fn eventually(mut check: impl FnMut() -> bool) -> bool {
+    for _ in 0..100 {
+        if check() {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    false
+}
+
+#[test]
+fn agent_event_exposes_session_id_and_kind() {
+    let event = terminated("sess-1");
+    assert_eq!(event.session_id(), "sess-1");
+    assert_eq!(event.kind(), "session_terminated");
+
+    let event = AgentEvent::StreamActivity {
+        session_id: "sess-2".into(),
+    };
+    assert_eq!(event.session_id(), "sess-2");
+    assert_eq!(event.kind(), "stream_activity");
+
+    let event = AgentEvent::NoticePosted {
+        session_id: "sess-3".into(),
+        message: SlackMessage::plain(SlackChannelId("C1".into()), "applied"),
+    };
+    assert_eq!(event.session_id(), "sess-3");
+    assert_eq!(event.kind(), "notice_posted");
+    let bus_event = BusEvent {
+        source: ProtocolMode::Mcp,
+        channel_id: Some("C1".into()),
+        event,
+    };
+    assert!(
+        audit_entry_for(&bus_event).is_none(),
+        "tools audit their own notices"
+    );
+}
+
+#[tokio::test]
+async fn every_subscriber_receives_published_event() {
+    let bus = EventBus::default();
+    let mut first = bus.subscribe();
+    let mut second = bus.subscribe();
+    assert_eq!(bus.subscriber_count(), 2);
+
+    bus.publish(ProtocolMode::Mcp, Some("C1".into()), heartbeat("sess-1"));
+
+    for rx in [&mut first, &mut second] {
+        let received = rx.recv().await.expect("event delivered");
+        assert_eq!(received.source, ProtocolMode::Mcp);
+        assert_eq!(received.channel_id.as_deref(), Some("C1"));
+        assert_eq!(received.event.session_id(), "sess-1");
+    }
+}
+
+#[test]
+fn publish_without_subscribers_is_counted_and_dropped() {
+    let bus = EventBus::default();
+    bus.publish(ProtocolMode::Acp, None, heartbeat("sess-1"));
+    assert_eq!(bus.metrics().snapshot().published, 1);
+}
+
+#[tokio::test]
+async fn metrics_subscriber_counts_events_by_kind() {
+    let bus = EventBus::default();
+    let cancel = CancellationToken::new();
+    let handle = spawn_metrics_subscriber(&bus, cancel.clone());
+
+    bus.publish(ProtocolMode::Mcp, None, heartbeat("sess-1"));
+    bus.publish(ProtocolMode::Acp, None, heartbeat("sess-2"));
+    bus.publish(ProtocolMode::Acp, None, terminated("sess-2"));
+
+    let metrics = bus.metrics();
+    assert!(
+        eventually(|| metrics.snapshot().by_kind.values().sum::<u64>() == 3).await,
+        "metrics subscriber should observe all events"
+    );
+    let snapshot = metrics.snapshot();
+    assert_eq!(snapshot.by_kind.get("heartbeat_received"), Some(&2));
+    assert_eq!(snapshot.by_kind.get("session_terminated"), Some(&1));
+    assert_eq!(snapshot.published, 3);
+
+    cancel.cancel();
+    handle.await.expect("subscriber exits on cancel");
+}
+
+#[tokio::test]
+async fn audit_subscriber_records_only_significant_events() {
+    let bus = EventBus::default();
+    let logger = Arc::new(RecordingLogger::default());
+    let cancel = CancellationToken::new();
+    let handle = spawn_audit_subscriber(&bus, logger.clone(), cancel.clone());
+
+    bus.publish(ProtocolMode::Acp, None, heartbeat("sess-1"));
+    bus.publish(ProtocolMode::Acp, None, terminated("sess-1"));
+
+    assert!(eventually(|| !logger.entries.lock().unwrap().is_empty()).await);
+    cancel.cancel();
+    handle.await.expect("subscriber exits on cancel");
+
+    let entries = logger.entries.lock().unwrap();
+    assert_eq!(entries.len(), 1, "heartbeats are not audited");
+    assert_eq!(entries[0].event_type, AuditEventType::AgentEvent);
+    assert_eq!(entries[0].session_id.as_deref(), Some("sess-1"));
+    assert_eq!(
+        entries[0].result_summary.as_deref(),
+        Some("session_terminated")
+    );
+    assert_eq!(entries[0].reason.as_deref(), Some("crashed"));
+}
+
+#[test]
+fn audit_entry_for_clearance_carries_request_id() {
+    let bus_event = BusEvent {
+        source: ProtocolMode::Mcp,
+        channel_id: None,
+        event: AgentEvent::ClearanceRequested {
+            request_id: "req-1".into(),
+            session_id: "sess-1".into(),
+            title: "Edit".into(),
+            description: String::new(),
+            diff: None,
+            file_path: "src/lib.rs".into(),
+            risk_level: "low".into(),
+            snippets: Vec::new(),
+        },
+    };
+    let entry = audit_entry_for(&bus_event).expect("clearance is audited");
+    assert_eq!(entry.request_id.as_deref(), Some("req-1"));
+    assert_eq!(entry.result_summary.as_deref(), Some("clearance_requested"));
+}
+
+#[tokio::test]
+async fn lagging_subscriber_records_dropped_events() {
+    let bus = EventBus::new(2);
+    let cancel = CancellationToken::new();
+    // The subscriber registers immediately but, on the single-threaded test
+    // runtime, does not run until this task yields — so it falls behind.
+    let handle = spawn_metrics_subscriber(&bus, cancel.clone());
+    for i in 0..5 {
+        bus.publish(ProtocolMode::Acp, None, heartbeat(&format!("sess-{i}")));
+    }
+
+    let metrics = bus.metrics();
+    assert!(eventually(|| metrics.snapshot().lagged == 3).await);
+    assert!(eventually(|| metrics.snapshot().by_kind.get("heartbeat_received") == Some(&2)).await);
+
+    cancel.cancel();
+    handle.await.expect("subscriber exits on cancel");
+}
@@ -0,0 +1,60 @@
+//! Unit tests for the heartbeat-required ledger.
+//!
+//! Validates:
+//! - The deadline clock starts at a session's first tool call
+//! - Time spent in an in-flight tool call does not count against the agent
+//! - Overdue sessions are reported as newly stalled once per lapse
+//! - A heartbeat clears the stalled mark
+
+use std::time::Duration;
+
+use agent_intercom::orchestrator::heartbeat_enforcer::HeartbeatLedger;
+use chrono::{TimeDelta, Utc};
+
+const DEADLINE: Duration = Duration::from_mins(1);
+
+#[test]
+fn clock_starts_at_first_sight() {
+    let ledger = HeartbeatLedger::new();
+    let start = Utc::now();
+
+    assert!(!ledger.is_overdue("s1", start, DEADLINE));
+    assert!(!ledger.is_overdue("s1", start + TimeDelta::seconds(60), DEADLINE));
+    assert!(ledger.is_overdue("s1", start + TimeDelta::seconds(61), DEADLINE));
+}
+
+#[test]
+fn waiting_on_the_server_is_not_counted() {
+    let ledger = HeartbeatLedger::new();
+    let start = Utc::now();
+    ledger.record("s1", start);
+
+    // A ten-minute approval wait starting 30s after the heartbeat.
+    ledger.start_call("s1", start + TimeDelta::seconds(30));
+    assert!(
+        ledger
+            .take_newly_overdue(start + TimeDelta::minutes(5), DEADLINE)
+            .is_empty(),
+        "not overdue while the call is being served"
+    );
+    ledger.finish_call("s1", start + TimeDelta::seconds(630));
+
+    assert!(!ledger.is_overdue("s1", start + TimeDelta::seconds(650), DEADLINE));
+    assert!(ledger.is_overdue("s1", start + TimeDelta::seconds(665), DEADLINE));
+}
+
+#[test]
+fn overdue_sessions_are_reported_once_until_heartbeat() {
+    let ledger = HeartbeatLedger::new();
+    let start = Utc::now();
+    ledger.record("s1", start);
+    ledger.record("s2", start + TimeDelta::seconds(50));
+
+    let later = start + TimeDelta::seconds(90);
+    assert_eq!(ledger.take_newly_overdue(later, DEADLINE), ["s1"]);
+    assert!(ledger.take_newly_overdue(later, DEADLINE).is_empty());
+
+    assert!(ledger.record("s1", later), "heartbeat clears the stall");
+    assert!(!ledger.record("s1", later), "only once");
+    assert!(!ledger.is_overdue("s1", later, DEADLINE));
+}
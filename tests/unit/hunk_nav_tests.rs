@@ -0,0 +1,102 @@
+//! Unit tests for paging through the hunks of an approval diff.
+//!
+//! Covers `blocks::diff_hunks`, `blocks::hunk_page_blocks` (page text,
+//! Prev / Next buttons, long-hunk cut-off) and `hunk_nav::replace_page`.
+
+use std::fmt::Write as _;
+
+use agent_intercom::slack::blocks;
+use agent_intercom::slack::handlers::hunk_nav;
+
+/// A diff with `hunks` hunks of eight changed lines each.
+fn multi_hunk_diff(hunks: usize) -> String {
+    let mut diff = String::from("--- a/src/lib.rs\n+++ b/src/lib.rs\n");
+    for h in 0..hunks {
+        let _ = writeln!(diff, "@@ -{0},4 +{0},4 @@", h * 100 + 1);
+        for i in 0..4 {
+            let _ = write!(diff, "-old {h}.{i}\n+new {h}.{i}\n");
+        }
+    }
+    diff
+}
+
+fn json(blocks: &[slack_morphism::prelude::SlackBlock]) -> String {
+    serde_json::to_string(blocks).expect("serialize blocks")
+}
+
+#[test]
+fn diff_hunks_splits_at_headers_and_drops_file_header() {
+    let hunks = blocks::diff_hunks(&multi_hunk_diff(3));
+    assert_eq!(hunks.len(), 3);
+    assert!(hunks[1].starts_with("@@ -101,4 +101,4 @@\n"));
+    assert!(hunks.iter().all(|hunk| !hunk.contains("+++ b/")));
+}
+
+#[test]
+fn first_page_shows_first_hunk_and_next_only() {
+    let page = blocks::hunk_page_blocks("req-1", &multi_hunk_diff(3), 0).expect("paged");
+    let json = json(&page);
+    assert!(json.contains("Hunk 1 of 3"), "{json}");
+    assert!(
+        json.contains("new 0.3") && !json.contains("new 1.0"),
+        "{json}"
+    );
+    assert!(json.contains("hunk_page_req-1") && json.contains("hunk_nav_req-1"));
+    assert!(json.contains("\"action_id\":\"hunk_page_1\""), "{json}");
+    assert!(!json.contains("Prev hunk"), "{json}");
+}
+
+#[test]
+fn middle_page_has_both_buttons_and_index_is_clamped() {
+    let diff = multi_hunk_diff(3);
+    let json_middle = json(&blocks::hunk_page_blocks("req-1", &diff, 1).expect("paged"));
+    assert!(json_middle.contains("\"action_id\":\"hunk_page_0\""));
+    assert!(json_middle.contains("\"action_id\":\"hunk_page_2\""));
+
+    let json_past = json(&blocks::hunk_page_blocks("req-1", &diff, 9).expect("paged"));
+    assert!(json_past.contains("Hunk 3 of 3"), "{json_past}");
+    assert!(!json_past.contains("Next hunk"), "{json_past}");
+}
+
+#[test]
+fn short_or_single_hunk_diffs_are_not_paged() {
+    assert!(blocks::hunk_page_blocks("req-1", "@@ -1 +1 @@\n-a\n+b\n", 0).is_none());
+    let single: String = std::iter::once("@@ -1,30 +1,30 @@\n".to_owned())
+        .chain((0..30).map(|i| format!("+line {i}\n")))
+        .collect();
+    assert!(blocks::hunk_page_blocks("req-1", &single, 0).is_none());
+}
+
+#[test]
+fn long_hunk_is_cut_to_fit_a_section() {
+    let mut diff = multi_hunk_diff(1);
+    diff.push_str("@@ -500,400 +500,400 @@\n");
+    for i in 0..400 {
+        let _ = writeln!(diff, "+a fairly long added line number {i}");
+    }
+    let page = blocks::hunk_page_blocks("req-1", &diff, 1).expect("paged");
+    let json = json(&page);
+    assert!(json.contains("more lines in the uploaded diff"), "{json}");
+    assert!(json.len() < 3_500, "page is {} bytes", json.len());
+}
+
+#[test]
+fn replace_page_swaps_only_the_paged_view() {
+    let diff = multi_hunk_diff(3);
+    let mut card = vec![blocks::text_section("header")];
+    card.extend(blocks::hunk_page_blocks("req-1", &diff, 0).expect("paged"));
+    card.push(blocks::approval_buttons("req-1"));
+
+    let next = blocks::hunk_page_blocks("req-1", &diff, 1).expect("paged");
+    let updated = hunk_nav::replace_page(card, "req-1", next).expect("replaced");
+    let json = json(&updated);
+    assert_eq!(updated.len(), 4);
+    assert!(json.contains("Hunk 2 of 3") && !json.contains("Hunk 1 of 3"));
+    assert!(
+        json.contains("approve_accept"),
+        "decision buttons stay: {json}"
+    );
+
+    let plain = vec![blocks::text_section("header")];
+    assert!(hunk_nav::replace_page(plain, "req-1", Vec::new()).is_none());
+}
@@ -0,0 +1,25 @@
+//! Unit tests for the per-session standby instruction queue.
+
+use agent_intercom::orchestrator::instruction_queue::InstructionQueue;
+
+#[test]
+fn push_reports_position_per_session() {
+    let queue = InstructionQueue::new();
+    assert_eq!(queue.push("s1", "a".into()), 1);
+    assert_eq!(queue.push("s1", "b".into()), 2);
+    assert_eq!(queue.push("s2", "c".into()), 1);
+}
+
+#[test]
+fn take_all_drains_in_order() {
+    let queue = InstructionQueue::new();
+    queue.push("s1", "a".into());
+    queue.push("s1", "b".into());
+    queue.push("s2", "c".into());
+
+    assert_eq!(queue.list("s1"), ["a", "b"]);
+    assert_eq!(queue.take_all("s1"), ["a", "b"]);
+    assert!(queue.list("s1").is_empty());
+    assert!(queue.take_all("s1").is_empty());
+    assert_eq!(queue.list("s2"), ["c"], "other sessions are untouched");
+}
@@ -0,0 +1,126 @@
+//! Unit tests for per-subject ordering of Slack interactions
+//! (`slack::interaction_queue`).
+//!
+//! Tests cover:
+//! - Tasks on one subject run one at a time, in arrival order
+//! - Tasks on different subjects, or with no subject, run in parallel
+//! - A subject's lane is dropped once its tasks finish
+//! - Buttons are keyed by their value and modals by their `callback_id`,
+//!   so a button and the modal it opened share a subject
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use agent_intercom::slack::interaction_queue::{interaction_subject, InteractionQueue};
+use slack_morphism::prelude::SlackInteractionEvent;
+use tokio::sync::Barrier;
+
+#[tokio::test]
+async fn same_subject_runs_in_arrival_order() {
+    let queue = Arc::new(InteractionQueue::new());
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let mut tasks = Vec::new();
+    for n in 0..5 {
+        let queue = Arc::clone(&queue);
+        let log = Arc::clone(&log);
+        tasks.push(tokio::spawn(async move {
+            queue
+                .run(Some("req-1"), async {
+                    log.lock().unwrap().push(format!("start {n}"));
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    log.lock().unwrap().push(format!("end {n}"));
+                })
+                .await;
+        }));
+        // Let each task reach the queue before the next is spawned.
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+    for task in tasks {
+        task.await.expect("task");
+    }
+
+    let expected: Vec<String> = (0..5)
+        .flat_map(|n| [format!("start {n}"), format!("end {n}")])
+        .collect();
+    assert_eq!(*log.lock().unwrap(), expected);
+    assert_eq!(queue.active(), 0);
+}
+
+#[tokio::test]
+async fn different_subjects_run_in_parallel() {
+    let queue = Arc::new(InteractionQueue::new());
+    // Every task waits for all three: serializing any two would hang.
+    let barrier = Arc::new(Barrier::new(3));
+
+    let run = |subject: Option<&'static str>| {
+        let queue = Arc::clone(&queue);
+        let barrier = Arc::clone(&barrier);
+        tokio::spawn(async move {
+            queue
+                .run(subject, async {
+                    barrier.wait().await;
+                })
+                .await;
+        })
+    };
+    let tasks = [run(Some("req-1")), run(Some("req-2")), run(None)];
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        for task in tasks {
+            task.await.expect("task");
+        }
+    })
+    .await
+    .expect("subjects ran in parallel");
+    assert_eq!(queue.active(), 0);
+}
+
+#[test]
+fn buttons_and_modals_share_a_subject() {
+    let click: SlackInteractionEvent = serde_json::from_value(serde_json::json!({
+        "type": "block_actions",
+        "team": { "id": "T1" },
+        "user": { "id": "U1" },
+        "api_app_id": "A1",
+        "container": { "type": "message", "message_ts": "1700000000.000100" },
+        "trigger_id": "1.2.a",
+        "actions": [
+            { "type": "button", "action_id": "approve_reject", "value": "req-1" }
+        ]
+    }))
+    .expect("block_actions payload");
+    assert_eq!(interaction_subject(&click).as_deref(), Some("req-1"));
+
+    let submit: SlackInteractionEvent = serde_json::from_value(serde_json::json!({
+        "type": "view_submission",
+        "team": { "id": "T1" },
+        "user": { "id": "U1" },
+        "view": {
+            "id": "V1",
+            "team_id": "T1",
+            "type": "modal",
+            "title": { "type": "plain_text", "text": "Reject" },
+            "blocks": [],
+            "callback_id": "approval_reject:req-1",
+            "state": { "values": {} },
+            "hash": "h1",
+            "app_id": "A1",
+            "bot_id": "B1"
+        },
+        "trigger_id": "1.2.b"
+    }))
+    .expect("view_submission payload");
+    assert_eq!(interaction_subject(&submit).as_deref(), Some("req-1"));
+
+    let shortcut: SlackInteractionEvent = serde_json::from_value(serde_json::json!({
+        "type": "shortcut",
+        "team": { "id": "T1" },
+        "user": { "id": "U1" },
+        "callback_id": "intercom_sessions",
+        "trigger_id": "1.2.c",
+        "action_ts": "1700000000.000200"
+    }))
+    .expect("shortcut payload");
+    assert_eq!(interaction_subject(&shortcut), None);
+}
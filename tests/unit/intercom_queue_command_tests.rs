@@ -71,20 +71,58 @@ async fn app_state_with_mode(
         workspace_mappings: Arc::default(),
         acp_event_tx: None,
         acp_driver: None,
+        driver_registry: Arc::default(),
+        event_bus: Arc::default(),
+        session_hooks: Arc::default(),
+        pause_requests: Arc::default(),
+        heartbeats: Arc::default(),
+        instruction_queue: Arc::default(),
+        snoozes: Arc::default(),
+        apply_confirmations: Arc::default(),
+        session_logs: Arc::default(),
+        session_channels: Arc::default(),
+        workspace_discovery: Arc::default(),
+        config_path: None,
+        command_aliases: Arc::default(),
+        proxy: Arc::default(),
+        replay_guard: Arc::default(),
+        interactions: Arc::default(),
+        rearmed: Arc::default(),
+        diff_staging: Arc::default(),
+        subsystems: Arc::default(),
+        simulator: None,
+        log_filter: None,
     })
 }
 
 #[tokio::test]
-async fn queue_rejected_in_mcp_mode() {
+async fn mcp_mode_queue_points_to_standby_queue() {
     let tmp = tempfile::tempdir().expect("tempdir");
     let root = tmp.path().to_str().expect("utf8");
     let user = "U_TEST";
     let state = app_state_with_mode(root, user, ServerMode::Mcp).await;
 
-    let result = dispatch_command("queue", &["list"], user, "C_TEST", &state).await;
+    // `queue` is the ACP file queue; MCP standby instructions use
+    // `standby-queue`.
+    let message = dispatch_command("queue", &["list"], user, "C_TEST", &state)
+        .await
+        .expect("queue response");
+    assert!(message.contains("only available in ACP mode"), "{message}");
+    assert!(message.contains("standby-queue"), "{message}");
+    assert!(!tmp.path().join(".intercom").exists());
+}
+
+#[tokio::test]
+async fn acp_mode_rejects_standby_queue() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let user = "U_TEST";
+    let state = app_state_with_mode(root, user, ServerMode::Acp).await;
 
-    let message = result.expect("queue response");
-    assert!(message.contains("only available in ACP mode"));
+    let message = dispatch_command("standby-queue", &["list"], user, "C_TEST", &state)
+        .await
+        .expect("standby-queue response");
+    assert!(message.contains("only available in MCP mode"), "{message}");
 }
 
 #[tokio::test]
@@ -0,0 +1,70 @@
+//! Unit tests for IPC access control (`ipc_allowed_groups`,
+//! `ipc_allowed_uids`).
+//!
+//! Validates:
+//! - The default DACL is protected and allows only the owner and `SYSTEM`
+//! - Configured groups are appended once, without duplicating the defaults
+//! - Entries that are neither SIDs nor SDDL aliases are refused
+//! - Unix peers are allowed only as the server's UID or a listed UID
+//! - Peer credentials of a real socket connection resolve to our UID
+
+use agent_intercom::ipc::security::{pipe_sddl, uid_allowed, validate_group};
+use agent_intercom::AppError;
+
+#[test]
+fn default_dacl_allows_owner_and_system_only() {
+    assert_eq!(pipe_sddl(&[]).expect("sddl"), "D:P(A;;GA;;;OW)(A;;GA;;;SY)");
+}
+
+#[test]
+fn configured_groups_are_appended_without_duplicates() {
+    let groups = vec!["S-1-5-32-544".to_owned(), "SY".to_owned(), "IU".to_owned()];
+
+    assert_eq!(
+        pipe_sddl(&groups).expect("sddl"),
+        "D:P(A;;GA;;;OW)(A;;GA;;;SY)(A;;GA;;;S-1-5-32-544)(A;;GA;;;IU)"
+    );
+}
+
+#[test]
+fn invalid_groups_are_refused() {
+    for group in ["", "Everyone", "WD)(A;;GA;;;WD", "S-1-5-", "S-1-x", "b"] {
+        let err = validate_group(group).expect_err("must reject");
+        assert!(matches!(err, AppError::Config(_)), "{group}: {err}");
+    }
+    assert!(pipe_sddl(&["Users".to_owned()]).is_err());
+    validate_group("S-1-5-21-1004336348-1177238915-682003330-512").expect("domain SID");
+}
+
+#[test]
+fn peer_uid_must_be_server_or_listed() {
+    assert!(uid_allowed(1000, 1000, &[]));
+    assert!(uid_allowed(1001, 1000, &[1001, 1002]));
+    assert!(!uid_allowed(1003, 1000, &[1001, 1002]));
+    assert!(!uid_allowed(0, 1000, &[]));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn peer_uid_of_local_connection_is_server_uid() {
+    use agent_intercom::ipc::security::{peer_uid, server_uid};
+    use interprocess::local_socket::tokio::prelude::*;
+    use interprocess::local_socket::{GenericNamespaced, ListenerOptions};
+
+    let name = format!("tsec{}", uuid::Uuid::new_v4().simple());
+    let listener = ListenerOptions::new()
+        .name(
+            name.clone()
+                .to_ns_name::<GenericNamespaced>()
+                .expect("name"),
+        )
+        .create_tokio()
+        .expect("listener");
+    let connect = interprocess::local_socket::tokio::Stream::connect(
+        name.to_ns_name::<GenericNamespaced>().expect("name"),
+    );
+    let (accepted, _client) = tokio::join!(listener.accept(), connect);
+
+    let uid = peer_uid(&accepted.expect("accept")).expect("peer credentials");
+    assert_eq!(uid, server_uid());
+}
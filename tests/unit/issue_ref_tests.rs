@@ -0,0 +1,107 @@
+//! Unit tests for issue references (`integrations::issues`).
+//!
+//! Tests cover:
+//! - Accepted reference forms and validation errors
+//! - Resolution against the configured provider and default repository
+//! - Short labels and Slack rendering
+
+use agent_intercom::config::{IssueProvider, IssuesConfig};
+use agent_intercom::integrations::issues::{
+    issue_label, resolve_target, validate_issue_ref, IssueTarget,
+};
+use agent_intercom::slack::blocks;
+
+fn github() -> IssuesConfig {
+    IssuesConfig {
+        provider: Some(IssueProvider::Github),
+        default_repo: Some("acme/api".into()),
+        ..IssuesConfig::default()
+    }
+}
+
+#[test]
+fn accepted_issue_forms_validate() {
+    for issue in [
+        "PROJ-123",
+        "OPS2-7",
+        "acme/api#42",
+        "#42",
+        "42",
+        "https://github.com/acme/api/issues/42",
+        "https://github.com/acme/api/pull/7",
+        "https://acme.atlassian.net/browse/PROJ-123",
+    ] {
+        assert!(
+            validate_issue_ref(issue).is_ok(),
+            "{issue} should be accepted"
+        );
+    }
+    for issue in [
+        "proj-123",
+        "PROJ-",
+        "acme#x",
+        "https://example.com/page",
+        "fix",
+    ] {
+        assert!(
+            validate_issue_ref(issue).is_err(),
+            "{issue} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn github_references_resolve_with_default_repo() {
+    let config = github();
+    let expected = IssueTarget::Github {
+        repo: "acme/api".into(),
+        number: 42,
+    };
+    assert_eq!(resolve_target(&config, "#42").expect("resolve"), expected);
+    assert_eq!(
+        resolve_target(&config, "acme/api#42").expect("resolve"),
+        expected
+    );
+    assert_eq!(
+        resolve_target(&config, "https://github.com/acme/api/issues/42").expect("resolve"),
+        expected
+    );
+
+    let no_default = IssuesConfig {
+        default_repo: None,
+        ..github()
+    };
+    assert!(resolve_target(&no_default, "42").is_err());
+}
+
+#[test]
+fn jira_references_require_jira_provider() {
+    let jira = IssuesConfig {
+        provider: Some(IssueProvider::Jira),
+        ..IssuesConfig::default()
+    };
+    assert_eq!(
+        resolve_target(&jira, "https://acme.atlassian.net/browse/PROJ-5").expect("resolve"),
+        IssueTarget::Jira {
+            key: "PROJ-5".into()
+        }
+    );
+    assert!(resolve_target(&github(), "PROJ-5").is_err());
+    assert!(resolve_target(&jira, "acme/api#1").is_err());
+}
+
+#[test]
+fn issue_line_links_urls_with_short_label() {
+    assert_eq!(
+        issue_label("https://github.com/acme/api/issues/42"),
+        "acme/api#42"
+    );
+    assert_eq!(issue_label("PROJ-1"), "PROJ-1");
+
+    let line = blocks::issue_line("https://acme.atlassian.net/browse/PROJ-1");
+    assert!(
+        line.contains("<https://acme.atlassian.net/browse/PROJ-1|PROJ-1>"),
+        "{line}"
+    );
+    assert!(blocks::issue_line("PROJ-1").ends_with("PROJ-1"));
+}
@@ -0,0 +1,81 @@
+//! Unit tests for `KnowledgeRepo` (shared knowledge notes).
+//!
+//! Tests cover:
+//! - Notes round-trip with their tags and start pending
+//! - Only approved notes are listed
+//! - The first decision wins; later decisions are ignored
+
+use std::sync::Arc;
+
+use agent_intercom::models::knowledge::{KnowledgeNote, KnowledgeStatus};
+use agent_intercom::persistence::{db, knowledge_repo::KnowledgeRepo};
+use chrono::Utc;
+
+async fn repo() -> KnowledgeRepo {
+    KnowledgeRepo::new(Arc::new(db::connect_memory().await.expect("db")))
+}
+
+fn note(title: &str) -> KnowledgeNote {
+    KnowledgeNote::new(
+        title.into(),
+        "Run `cargo test --test e2e` with the stub server up.".into(),
+        vec!["testing".into()],
+        "s1".into(),
+        "/repo".into(),
+    )
+}
+
+#[tokio::test]
+async fn insert_and_get_round_trip() {
+    let repo = repo().await;
+    let note = note("How to run the e2e suite");
+    repo.insert(&note).await.expect("insert");
+
+    let stored = repo.get(&note.id).await.expect("get").expect("note");
+    assert_eq!(stored, note);
+    assert_eq!(stored.status, KnowledgeStatus::Pending);
+    assert!(repo.get("note:missing").await.expect("get").is_none());
+}
+
+#[tokio::test]
+async fn only_approved_notes_are_listed() {
+    let repo = repo().await;
+    let approved = note("approved");
+    let rejected = note("rejected");
+    let pending = note("pending");
+    for n in [&approved, &rejected, &pending] {
+        repo.insert(n).await.expect("insert");
+    }
+    repo.decide(&approved.id, KnowledgeStatus::Approved, "U1", Utc::now())
+        .await
+        .expect("approve");
+    repo.decide(&rejected.id, KnowledgeStatus::Rejected, "U1", Utc::now())
+        .await
+        .expect("reject");
+
+    let listed = repo.list_approved().await.expect("list");
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, approved.id);
+    assert_eq!(listed[0].decided_by.as_deref(), Some("U1"));
+    assert!(listed[0].decided_at.is_some());
+}
+
+#[tokio::test]
+async fn first_decision_wins() {
+    let repo = repo().await;
+    let note = note("flaky test workaround");
+    repo.insert(&note).await.expect("insert");
+
+    assert!(repo
+        .decide(&note.id, KnowledgeStatus::Rejected, "U1", Utc::now())
+        .await
+        .expect("reject"));
+    assert!(!repo
+        .decide(&note.id, KnowledgeStatus::Approved, "U2", Utc::now())
+        .await
+        .expect("approve"));
+
+    let stored = repo.get(&note.id).await.expect("get").expect("note");
+    assert_eq!(stored.status, KnowledgeStatus::Rejected);
+    assert_eq!(stored.decided_by.as_deref(), Some("U1"));
+}
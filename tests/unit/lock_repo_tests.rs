@@ -0,0 +1,128 @@
+//! Unit tests for workspace path locks (`acquire_lock`).
+//!
+//! Tests cover:
+//! - Lock paths overlap when equal or when one is a directory of the other
+//! - Conflicting requests are granted in request order
+//! - Locks of ended sessions are swept and no longer block
+
+use std::sync::Arc;
+
+use agent_intercom::models::path_lock::{paths_overlap, LockGrant, PathLock};
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::persistence::{db, lock_repo::LockRepo, session_repo::SessionRepo};
+
+const ROOT: &str = "/work/repo";
+
+async fn database() -> Arc<db::Database> {
+    Arc::new(db::connect_memory().await.expect("db"))
+}
+
+async fn active_session(db: &Arc<db::Database>) -> Session {
+    let repo = SessionRepo::new(Arc::clone(db));
+    let session = repo
+        .create(&Session::new(
+            "U1".into(),
+            ROOT.into(),
+            None,
+            SessionMode::Remote,
+        ))
+        .await
+        .expect("create");
+    repo.update_status(&session.id, SessionStatus::Active)
+        .await
+        .expect("activate")
+}
+
+async fn request(repo: &LockRepo, session: &Session, path: &str) -> PathLock {
+    let lock = PathLock::queued(session.id.clone(), ROOT.into(), path.into(), None);
+    repo.enqueue(&lock).await.expect("enqueue");
+    lock
+}
+
+fn blocker(grant: LockGrant) -> Option<String> {
+    match grant {
+        LockGrant::Acquired(_) => None,
+        LockGrant::Blocked(lock) => Some(lock.id),
+    }
+}
+
+#[test]
+fn paths_overlap_by_directory() {
+    assert!(paths_overlap("src", "src"));
+    assert!(paths_overlap("src", "src/lib.rs"));
+    assert!(paths_overlap("src/db/pool.rs", "src"));
+    assert!(paths_overlap(".", "docs/guide.md"));
+    assert!(!paths_overlap("src", "src2/lib.rs"));
+    assert!(!paths_overlap("src/lib.rs", "src/main.rs"));
+}
+
+#[tokio::test]
+async fn conflicting_requests_are_granted_in_order() {
+    let db = database().await;
+    let repo = LockRepo::new(Arc::clone(&db));
+    let (a, b, c) = (
+        active_session(&db).await,
+        active_session(&db).await,
+        active_session(&db).await,
+    );
+
+    let held = request(&repo, &a, "src").await;
+    assert_eq!(
+        blocker(repo.try_grant(&held.id).await.expect("grant")),
+        None
+    );
+    let first = request(&repo, &b, "src/lib.rs").await;
+    let second = request(&repo, &c, "src").await;
+    let unrelated = request(&repo, &c, "docs").await;
+
+    assert_eq!(
+        blocker(repo.try_grant(&second.id).await.expect("grant")),
+        Some(held.id.clone())
+    );
+    assert_eq!(
+        blocker(repo.try_grant(&unrelated.id).await.expect("grant")),
+        None
+    );
+
+    let released = repo.release(&a.id, None).await.expect("release");
+    assert_eq!(released.len(), 1);
+    assert_eq!(
+        blocker(repo.try_grant(&second.id).await.expect("grant")),
+        Some(first.id.clone()),
+        "an earlier queued request goes first"
+    );
+    assert_eq!(
+        blocker(repo.try_grant(&first.id).await.expect("grant")),
+        None
+    );
+    assert_eq!(
+        repo.held_by(&b.id).await.expect("held")[0].path,
+        "src/lib.rs"
+    );
+}
+
+#[tokio::test]
+async fn locks_of_ended_sessions_are_swept() {
+    let db = database().await;
+    let repo = LockRepo::new(Arc::clone(&db));
+    let (a, b) = (active_session(&db).await, active_session(&db).await);
+
+    let held = request(&repo, &a, ".").await;
+    repo.try_grant(&held.id).await.expect("grant");
+    let waiting = request(&repo, &b, "src/lib.rs").await;
+    assert!(blocker(repo.try_grant(&waiting.id).await.expect("grant")).is_some());
+
+    SessionRepo::new(Arc::clone(&db))
+        .update_status(&a.id, SessionStatus::Terminated)
+        .await
+        .expect("terminate");
+    assert_eq!(
+        blocker(repo.try_grant(&waiting.id).await.expect("grant")),
+        None
+    );
+    assert_eq!(
+        repo.list_for_workspace(ROOT).await.expect("list").len(),
+        1,
+        "the ended session's lock is gone"
+    );
+}
@@ -0,0 +1,77 @@
+//! Unit tests for `log_filter::LogFilter`.
+//!
+//! Validates:
+//! - A bare level replaces the default and keeps per-target directives
+//! - A target level replaces only that target's directive
+//! - Invalid levels and targets are refused without reloading
+//! - `reset` restores the startup filter
+
+use std::sync::{Arc, Mutex};
+
+use agent_intercom::log_filter::LogFilter;
+use agent_intercom::AppError;
+
+fn recording(spec: &str) -> (LogFilter, Arc<Mutex<Vec<String>>>) {
+    let installed = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&installed);
+    let filter = LogFilter::with_apply(spec, move |filter| {
+        sink.lock().expect("lock").push(filter.to_string());
+        Ok(())
+    });
+    (filter, installed)
+}
+
+#[test]
+fn default_level_keeps_target_directives() {
+    let (filter, installed) = recording("info, sqlx=warn");
+
+    let current = filter.set("DEBUG", None).expect("set");
+
+    assert_eq!(current, "debug,sqlx=warn");
+    assert_eq!(filter.current(), current);
+    assert_eq!(installed.lock().expect("lock").len(), 1);
+}
+
+#[test]
+fn target_level_replaces_only_that_target() {
+    let (filter, _) = recording("info,agent_intercom::slack=warn,sqlx=warn");
+
+    let current = filter
+        .set("trace", Some("agent_intercom::slack"))
+        .expect("set");
+
+    assert_eq!(current, "info,sqlx=warn,agent_intercom::slack=trace");
+}
+
+#[test]
+fn invalid_input_is_refused_without_reload() {
+    let (filter, installed) = recording("info");
+
+    let level = filter.set("verbose", None);
+    let target = filter.set("debug", Some("a=b"));
+
+    assert!(matches!(level, Err(AppError::Config(ref m)) if m.contains("unknown log level")));
+    assert!(matches!(target, Err(AppError::Config(ref m)) if m.contains("invalid log target")));
+    assert_eq!(filter.current(), "info");
+    assert!(installed.lock().expect("lock").is_empty());
+}
+
+#[test]
+fn reset_restores_startup_filter() {
+    let (filter, installed) = recording("warn,agent_intercom=info");
+    filter.set("off", None).expect("set");
+    filter.set("debug", Some("rmcp")).expect("set target");
+
+    let current = filter.reset().expect("reset");
+
+    assert_eq!(current, "warn,agent_intercom=info");
+    assert_eq!(installed.lock().expect("lock").len(), 3);
+}
+
+#[test]
+fn failed_reload_keeps_previous_filter() {
+    let filter = LogFilter::with_apply("info", |_| Err(AppError::Config("subscriber gone".into())));
+
+    assert!(filter.set("debug", None).is_err());
+    assert_eq!(filter.current(), "info");
+}
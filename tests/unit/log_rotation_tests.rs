@@ -0,0 +1,154 @@
+//! Unit tests for `audit::rotation` and size-based audit rotation.
+//!
+//! Validates:
+//! - The audit writer rotates to compressed segments at `max_file_bytes`
+//! - Audit readers see entries in compressed segments
+//! - `prune` compresses stale live files and deletes expired files
+//! - `RotatingFile` rotates the tracing log by size
+
+use std::fs;
+use std::io::Write;
+
+use agent_intercom::audit::reader::{entries_for_session, find_decisions};
+use agent_intercom::audit::rotation::{self, RotatingFile};
+use agent_intercom::audit::writer::JsonlAuditWriter;
+use agent_intercom::audit::{AuditEntry, AuditEventType, AuditLogger};
+use agent_intercom::config::LoggingConfig;
+use chrono::{TimeZone, Utc};
+
+fn names(dir: &std::path::Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .expect("read dir")
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+fn small_files() -> LoggingConfig {
+    LoggingConfig {
+        max_file_bytes: 300,
+        ..LoggingConfig::default()
+    }
+}
+
+#[test]
+fn writer_rotates_to_compressed_segments_by_size() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let writer = JsonlAuditWriter::new(temp.path().to_owned())
+        .expect("writer")
+        .with_logging(small_files());
+
+    for n in 0..6 {
+        let entry = AuditEntry::new(AuditEventType::Approval)
+            .with_session("sess-1".to_owned())
+            .with_request_id(format!("req-{n}"));
+        writer.log_entry(entry).expect("log");
+    }
+
+    let today = Utc::now().date_naive();
+    let files = names(temp.path());
+    assert!(files.len() > 1, "expected rotated segments: {files:?}");
+    assert_eq!(files.last(), Some(&format!("audit-{today}.jsonl")));
+    assert_eq!(files[0], format!("audit-{today}.001.jsonl.gz"));
+    let live = fs::metadata(temp.path().join(format!("audit-{today}.jsonl"))).expect("meta");
+    assert!(live.len() <= 300, "live file is {} bytes", live.len());
+
+    let found = find_decisions(temp.path(), &["req-0", "req-5"]);
+    assert_eq!(found.len(), 2, "decisions in compressed segments are found");
+    let entries = entries_for_session(temp.path(), "sess-1", today);
+    let ids: Vec<_> = entries
+        .iter()
+        .filter_map(|e| e.request_id.as_deref())
+        .collect();
+    assert_eq!(ids, ["req-0", "req-1", "req-2", "req-3", "req-4", "req-5"]);
+}
+
+#[test]
+fn prune_compresses_stale_files_and_deletes_expired_ones() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    fs::write(dir.join("audit-2026-10-16.jsonl"), "{}\n").expect("write");
+    fs::write(dir.join("audit-2026-10-17.jsonl"), "{}\n").expect("write");
+    fs::write(dir.join("audit-2026-06-01.001.jsonl.gz"), "").expect("write");
+    fs::write(dir.join("notes.txt"), "keep").expect("write");
+    let tracing_dir = dir.join("server");
+    fs::create_dir_all(&tracing_dir).expect("mkdir");
+    fs::write(tracing_dir.join("intercom.log"), "live").expect("write");
+    fs::write(tracing_dir.join("intercom.log.2026-06-01.001.gz"), "").expect("write");
+    fs::write(tracing_dir.join("intercom.log.2026-10-16.001.gz"), "").expect("write");
+
+    let logging = LoggingConfig {
+        retention_days: 30,
+        file: Some(tracing_dir.join("intercom.log")),
+        ..LoggingConfig::default()
+    };
+    let now = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+    let deleted = rotation::prune(dir, &logging, now);
+
+    assert_eq!(deleted, 2);
+    assert_eq!(
+        names(dir),
+        [
+            "audit-2026-10-16.001.jsonl.gz",
+            "audit-2026-10-17.jsonl",
+            "notes.txt"
+        ]
+    );
+    assert_eq!(
+        rotation::read_to_string(&dir.join("audit-2026-10-16.001.jsonl.gz")).expect("read"),
+        "{}\n"
+    );
+    assert_eq!(
+        names(&tracing_dir),
+        ["intercom.log", "intercom.log.2026-10-16.001.gz"]
+    );
+}
+
+#[test]
+fn prune_keeps_everything_with_zero_retention() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    fs::write(temp.path().join("audit-2020-01-01.001.jsonl.gz"), "").expect("write");
+    let logging = LoggingConfig {
+        retention_days: 0,
+        ..LoggingConfig::default()
+    };
+
+    assert_eq!(rotation::prune(temp.path(), &logging, Utc::now()), 0);
+    assert_eq!(names(temp.path()), ["audit-2020-01-01.001.jsonl.gz"]);
+}
+
+#[test]
+fn rotating_file_rotates_by_size() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let path = temp.path().join("logs").join("intercom.log");
+    let mut file = RotatingFile::open(&path, &small_files()).expect("open");
+
+    let line = [b'x'; 99];
+    for _ in 0..4 {
+        file.write_all(&line).expect("write");
+        file.write_all(b"\n").expect("write");
+    }
+    file.flush().expect("flush");
+
+    let today = Utc::now().date_naive();
+    let files = names(&temp.path().join("logs"));
+    assert_eq!(
+        files,
+        [
+            "intercom.log".to_owned(),
+            format!("intercom.log.{today}.001.gz")
+        ]
+    );
+    let rotated = rotation::read_to_string(
+        &temp
+            .path()
+            .join("logs")
+            .join(format!("intercom.log.{today}.001.gz")),
+    )
+    .expect("read");
+    assert_eq!(rotated.len(), 300);
+    assert_eq!(fs::read_to_string(&path).expect("read").len(), 100);
+}
@@ -0,0 +1,52 @@
+//! Unit tests for the three-way merge view of conflicting diffs.
+//!
+//! Validates:
+//! - Each hunk becomes a conflict region holding the current, base and
+//!   proposed lines, with the untouched lines of the file kept around it
+//! - Whole-file content becomes one region with an empty base
+//! - Unparseable diffs produce no view
+
+use agent_intercom::diff::merge_view::{merge_view, region_count};
+
+#[test]
+fn hunks_become_conflict_regions_in_the_current_file() {
+    let current = "fn main() {\n    let port = 9090;\n    serve(port);\n}\n";
+    let diff = "--- a/src/main.rs\n+++ b/src/main.rs\n@@ -1,4 +1,4 @@\n fn main() {\n-    \
+                let port = 8080;\n+    let port = 3000;\n     serve(port);\n }\n";
+
+    let view = merge_view(current, diff).expect("view");
+    assert_eq!(region_count(&view), 1);
+    assert_eq!(
+        view,
+        "<<<<<<< current\nfn main() {\n    let port = 9090;\n    serve(port);\n}\n\
+         ||||||| base\nfn main() {\n    let port = 8080;\n    serve(port);\n}\n\
+         =======\nfn main() {\n    let port = 3000;\n    serve(port);\n}\n\
+         >>>>>>> proposed\n"
+    );
+}
+
+#[test]
+fn lines_outside_hunks_are_kept() {
+    let current = "a\nb\nc\nd\ne\nf\n";
+    let diff = "--- a/f.txt\n+++ b/f.txt\n@@ -3,1 +3,1 @@\n-x\n+y\n";
+
+    let view = merge_view(current, diff).expect("view");
+    assert_eq!(
+        view,
+        "a\nb\n<<<<<<< current\nc\n||||||| base\nx\n=======\ny\n>>>>>>> proposed\nd\ne\nf\n"
+    );
+}
+
+#[test]
+fn whole_file_content_is_one_region() {
+    let view = merge_view("old\n", "new\n").expect("view");
+    assert_eq!(
+        view,
+        "<<<<<<< current\nold\n||||||| base\n=======\nnew\n>>>>>>> proposed\n"
+    );
+}
+
+#[test]
+fn unparseable_diff_has_no_view() {
+    assert!(merge_view("a\n", "--- a/f\n+++ b/f\n@@ garbage @@\n").is_none());
+}
@@ -6,7 +6,7 @@ use agent_intercom::models::{
     approval::{ApprovalRequest, ApprovalStatus, RiskLevel},
     checkpoint::Checkpoint,
     policy::{FilePatterns, WorkspacePolicy},
-    progress::{ProgressItem, ProgressStatus},
+    progress::{all_done, ProgressItem, ProgressStatus},
     prompt::{ContinuationPrompt, PromptDecision, PromptType},
     session::{Session, SessionMode, SessionStatus},
     stall::{StallAlert, StallAlertStatus},
@@ -323,3 +323,23 @@ fn file_patterns_default() {
     assert!(default.write.is_empty());
     assert!(default.read.is_empty());
 }
+
+// ── Progress ─────────────────────────────────────────
+
+#[test]
+fn all_done_requires_every_item_done() {
+    let item = |status| ProgressItem {
+        label: "step".into(),
+        status,
+    };
+    assert!(all_done(&[
+        item(ProgressStatus::Done),
+        item(ProgressStatus::Done)
+    ]));
+    assert!(!all_done(&[
+        item(ProgressStatus::Done),
+        item(ProgressStatus::InProgress)
+    ]));
+    assert!(!all_done(&[item(ProgressStatus::Pending)]));
+    assert!(!all_done(&[]), "an empty snapshot reports no finished work");
+}
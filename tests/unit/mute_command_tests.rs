@@ -0,0 +1,170 @@
+//! Unit tests for the `mute` and `unmute` slash commands.
+//!
+//! Validates:
+//! - `mute <session> <duration>` sets a timed mute; without a duration the
+//!   mute holds until `unmute`
+//! - `unmute` clears the mute, and `sessions` marks muted sessions
+//! - Only the session owner may mute, and bad or oversized arguments return
+//!   usage errors
+
+use std::sync::Arc;
+
+use agent_intercom::config::GlobalConfig;
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::slack::commands::dispatch_command;
+use agent_intercom::state::AppState;
+
+use super::test_helpers::test_app_state;
+
+fn make_config(workspace_root: &str, user: &str) -> GlobalConfig {
+    let toml = format!(
+        r#"
+default_workspace_root = '{root}'
+http_port = 0
+ipc_name = "test-mute-command"
+max_concurrent_sessions = 5
+host_cli = "echo"
+
+[slack]
+channel_id = "C_TEST"
+
+[timeouts]
+approval_seconds = 5
+prompt_seconds = 5
+wait_seconds = 5
+
+[stall]
+enabled = false
+inactivity_threshold_seconds = 300
+escalation_threshold_seconds = 120
+max_retries = 3
+default_nudge_message = "continue"
+"#,
+        root = workspace_root.replace('\\', "\\\\"),
+    );
+    let mut config = GlobalConfig::from_toml_str(&toml).expect("valid test config");
+    config.authorized_user_ids = vec![user.to_owned()];
+    config
+}
+
+async fn app_state(workspace_root: &str, user: &str) -> Arc<AppState> {
+    test_app_state(make_config(workspace_root, user)).await
+}
+
+async fn active_session(state: &AppState, owner: &str, root: &str) -> Session {
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let mut session = Session::new(owner.into(), root.into(), None, SessionMode::Remote);
+    session.channel_id = Some("C_TEST".into());
+    let created = repo.create(&session).await.expect("create session");
+    repo.update_status(&created.id, SessionStatus::Active)
+        .await
+        .expect("activate session")
+}
+
+async fn reload(state: &AppState, id: &str) -> Session {
+    SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(id)
+        .await
+        .expect("query")
+        .expect("session exists")
+}
+
+#[tokio::test]
+async fn mute_with_duration_sets_timed_mute() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let user = "U_TEST";
+    let state = app_state(root, user).await;
+    let session = active_session(&state, user, root).await;
+
+    let reply = dispatch_command("mute", &[&session.id, "2h"], user, "C_TEST", &state)
+        .await
+        .expect("mute response");
+    assert!(reply.contains("muted until"), "{reply}");
+
+    let muted = reload(&state, &session.id).await;
+    assert!(muted.muted);
+    let until = muted.muted_until.expect("timed mute");
+    let remaining = until - chrono::Utc::now();
+    assert!(remaining > chrono::Duration::minutes(119), "{remaining}");
+    assert!(remaining <= chrono::Duration::hours(2), "{remaining}");
+}
+
+#[tokio::test]
+async fn mute_without_duration_holds_until_unmute() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let user = "U_TEST";
+    let state = app_state(root, user).await;
+    let session = active_session(&state, user, root).await;
+
+    let reply = dispatch_command("mute", &[&session.id], user, "C_TEST", &state)
+        .await
+        .expect("mute response");
+    assert!(reply.contains("until `unmute`"), "{reply}");
+    let muted = reload(&state, &session.id).await;
+    assert!(muted.muted);
+    assert_eq!(muted.muted_until, None);
+
+    let listing = dispatch_command("sessions", &[], user, "C_TEST", &state)
+        .await
+        .expect("sessions response");
+    assert!(listing.contains("muted"), "{listing}");
+
+    let reply = dispatch_command("unmute", &[&session.id], user, "C_TEST", &state)
+        .await
+        .expect("unmute response");
+    assert!(reply.contains("unmuted"), "{reply}");
+    assert!(!reload(&state, &session.id).await.muted);
+
+    let reply = dispatch_command("unmute", &[&session.id], user, "C_TEST", &state)
+        .await
+        .expect("second unmute response");
+    assert!(reply.contains("is not muted"), "{reply}");
+}
+
+#[tokio::test]
+async fn mute_rejects_non_owner() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let user = "U_TEST";
+    let state = app_state(root, user).await;
+    let session = active_session(&state, "U_OTHER", root).await;
+
+    let result = dispatch_command("mute", &[&session.id], user, "C_TEST", &state).await;
+
+    assert!(result.is_err(), "non-owner must not mute");
+    assert!(!reload(&state, &session.id).await.muted);
+}
+
+#[tokio::test]
+async fn mute_reports_usage_errors() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let user = "U_TEST";
+    let state = app_state(root, user).await;
+    let session = active_session(&state, user, root).await;
+
+    let err = dispatch_command("mute", &[], user, "C_TEST", &state)
+        .await
+        .expect_err("missing session id");
+    assert!(err.to_string().contains("usage: mute"), "{err}");
+
+    let err = dispatch_command("mute", &[&session.id, "soon"], user, "C_TEST", &state)
+        .await
+        .expect_err("bad duration");
+    assert!(err.to_string().contains("invalid duration"), "{err}");
+
+    let err = dispatch_command(
+        "mute",
+        &[&session.id, "106751991167300d"],
+        user,
+        "C_TEST",
+        &state,
+    )
+    .await
+    .expect_err("oversized duration");
+    assert!(err.to_string().contains("365d maximum"), "{err}");
+    assert!(!reload(&state, &session.id).await.muted);
+}
@@ -0,0 +1,239 @@
+//! Unit tests for approval ownership context (`diff::ownership`).
+//!
+//! Tests cover:
+//! - CODEOWNERS pattern matching (anchoring, directories, last match wins)
+//! - Hunk range extraction from unified diffs
+//! - `git blame --line-porcelain` aggregation
+//! - Slack rendering of the context line
+//! - Required approvers under CODEOWNERS routing
+
+use std::process::Command;
+
+use agent_intercom::config::CodeOwnersConfig;
+use agent_intercom::diff::ownership::{
+    affected_ranges, blame_summary, required_approvers, summarise_porcelain, ApprovalContext,
+    BlameAuthor, CodeOwners,
+};
+use agent_intercom::slack::blocks;
+
+const CODEOWNERS: &str = "\
+# Default owners
+*                 @org/core
+*.md              @docs-team   # inline comment
+/src/slack/       @alice @bob
+docs/             @docs-team
+/build/logs       @ops
+src/vendor/**
+";
+
+// ─── CODEOWNERS matching ──────────────────────────────────────────────
+
+#[test]
+fn codeowners_last_matching_rule_wins() {
+    let owners = CodeOwners::parse(CODEOWNERS);
+    assert_eq!(owners.owners_for("src/main.rs"), ["@org/core"]);
+    assert_eq!(owners.owners_for("src/slack/blocks.rs"), ["@alice", "@bob"]);
+    assert_eq!(owners.owners_for("src/README.md"), ["@docs-team"]);
+    assert_eq!(owners.owners_for("src/slack/README.md"), ["@alice", "@bob"]);
+}
+
+#[test]
+fn codeowners_unanchored_patterns_match_at_any_depth() {
+    let owners = CodeOwners::parse(CODEOWNERS);
+    assert_eq!(owners.owners_for("nested/deep/notes.md"), ["@docs-team"]);
+}
+
+#[test]
+fn codeowners_directory_rules_cover_their_contents() {
+    let owners = CodeOwners::parse(CODEOWNERS);
+    assert_eq!(owners.owners_for("docs/guide/setup.txt"), ["@docs-team"]);
+    assert_eq!(owners.owners_for("build/logs/today.log"), ["@ops"]);
+    assert_eq!(
+        owners.owners_for("src/slack"),
+        ["@org/core"],
+        "a trailing-slash rule must not match a file of the same name"
+    );
+}
+
+#[test]
+fn codeowners_rule_without_owners_clears_ownership() {
+    let owners = CodeOwners::parse(CODEOWNERS);
+    assert!(owners.owners_for("src/vendor/lib/x.rs").is_empty());
+}
+
+#[test]
+fn codeowners_load_prefers_github_directory() {
+    let ws = tempfile::tempdir().expect("tempdir");
+    std::fs::create_dir_all(ws.path().join(".github")).expect("mkdir");
+    std::fs::write(ws.path().join(".github/CODEOWNERS"), "* @github-dir\n").expect("write");
+    std::fs::write(ws.path().join("CODEOWNERS"), "* @root\n").expect("write");
+
+    let owners = CodeOwners::load(ws.path()).expect("codeowners found");
+    assert_eq!(owners.owners_for("a.rs"), ["@github-dir"]);
+    assert!(CodeOwners::load(&ws.path().join("missing")).is_none());
+}
+
+// ─── affected_ranges ──────────────────────────────────────────────────
+
+#[test]
+fn affected_ranges_reads_old_side_of_each_hunk() {
+    let diff = "\
+--- a/lib.rs
++++ b/lib.rs
+@@ -3,4 +3,4 @@
+ a
+-b
++B
+ c
+ d
+@@ -20,2 +20,3 @@
+ x
++y
+ z
+";
+    assert_eq!(affected_ranges(diff), Some(vec![(3, 6), (20, 21)]));
+}
+
+#[test]
+fn affected_ranges_is_none_for_full_file_content() {
+    assert_eq!(affected_ranges("fn main() {}\n"), None);
+}
+
+// ─── blame aggregation ────────────────────────────────────────────────
+
+#[test]
+fn summarise_porcelain_groups_by_author_and_skips_uncommitted() {
+    let porcelain = "\
+aaaa 1 1 1
+author Alice
+author-time 1700000000
+\tline one
+bbbb 2 2 1
+author Bob
+author-time 1600000000
+\tline two
+aaaa 3 3 1
+author Alice
+author-time 1710000000
+\tline three
+0000 4 4 1
+author Not Committed Yet
+author-time 1720000000
+\tline four
+";
+    let summary = summarise_porcelain(porcelain);
+    assert_eq!(summary.len(), 2);
+    assert_eq!(summary[0].name, "Alice");
+    assert_eq!(summary[0].lines, 2);
+    assert_eq!(
+        summary[0].last_touched.map(|t| t.timestamp()),
+        Some(1_710_000_000)
+    );
+    assert_eq!(summary[1].name, "Bob");
+}
+
+#[tokio::test]
+async fn blame_summary_is_empty_outside_a_git_repository() {
+    let ws = tempfile::tempdir().expect("tempdir");
+    std::fs::write(ws.path().join("a.rs"), "fn a() {}\n").expect("write");
+    assert!(blame_summary(ws.path(), "a.rs", "fn a() {}\n")
+        .await
+        .is_empty());
+}
+
+#[tokio::test]
+async fn blame_summary_reports_committed_author() {
+    let ws = tempfile::tempdir().expect("tempdir");
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .arg("-C")
+            .arg(ws.path())
+            .args([
+                "-c",
+                "user.name=Carol",
+                "-c",
+                "user.email=carol@example.com",
+            ])
+            .args(args)
+            .output()
+            .is_ok_and(|out| out.status.success())
+    };
+    if !git(&["init", "-q"]) {
+        // git is not installed; nothing to verify.
+        return;
+    }
+    std::fs::write(ws.path().join("a.rs"), "one\ntwo\nthree\n").expect("write");
+    assert!(git(&["add", "a.rs"]));
+    assert!(git(&["commit", "-q", "-m", "init"]));
+
+    let diff = "--- a/a.rs\n+++ b/a.rs\n@@ -2,1 +2,1 @@\n-two\n+TWO\n";
+    let summary = blame_summary(ws.path(), "a.rs", diff).await;
+    assert_eq!(summary.len(), 1);
+    assert_eq!(summary[0].name, "Carol");
+    assert_eq!(summary[0].lines, 1);
+}
+
+// ─── rendering ────────────────────────────────────────────────────────
+
+#[test]
+fn approval_context_text_lists_authors_and_owners() {
+    let context = ApprovalContext {
+        blame: vec![BlameAuthor {
+            name: "Alice".to_owned(),
+            lines: 1,
+            last_touched: chrono::DateTime::from_timestamp(1_700_000_000, 0),
+        }],
+        owners: vec!["@org/core".to_owned()],
+        ..ApprovalContext::default()
+    };
+    let text = blocks::approval_context_text(&context).expect("context text");
+    assert!(
+        text.contains("*Alice* (1 line, <!date^1700000000^{date_short_pretty}|2023-11-14>)"),
+        "{text}"
+    );
+    assert!(text.contains("`@org/core`"), "{text}");
+    assert!(blocks::approval_context_text(&ApprovalContext::default()).is_none());
+}
+
+#[test]
+fn approval_context_text_mentions_mapped_owners() {
+    let mut context = ApprovalContext {
+        owners: vec!["@alice".to_owned()],
+        approvers: vec!["U_ALICE".to_owned()],
+        ..ApprovalContext::default()
+    };
+    let cc = blocks::approval_context_text(&context).expect("context text");
+    assert!(cc.contains("cc <@U_ALICE>"), "{cc}");
+
+    context.approvers_required = true;
+    let required = blocks::approval_context_text(&context).expect("context text");
+    assert!(
+        required.contains("Approval required from a code owner: <@U_ALICE>"),
+        "{required}"
+    );
+}
+
+// ─── required approvers ───────────────────────────────────────────────
+
+#[test]
+fn required_approvers_only_when_enforced_and_mapped() {
+    let ws = tempfile::tempdir().expect("tempdir");
+    std::fs::write(ws.path().join("CODEOWNERS"), CODEOWNERS).expect("write");
+    let mut config = CodeOwnersConfig {
+        slack_users: [("@alice".to_owned(), "U_ALICE".to_owned())]
+            .into_iter()
+            .collect(),
+        require_owner_approval: false,
+    };
+    assert!(required_approvers(&config, ws.path(), "src/slack/blocks.rs").is_empty());
+
+    config.require_owner_approval = true;
+    assert_eq!(
+        required_approvers(&config, ws.path(), "src/slack/blocks.rs"),
+        ["U_ALICE"]
+    );
+    assert!(
+        required_approvers(&config, ws.path(), "src/main.rs").is_empty(),
+        "files without a mapped owner fall back to the session owner"
+    );
+}
@@ -0,0 +1,92 @@
+//! Unit tests for auto-approve drift between the rule files.
+//!
+//! Validates:
+//! - Files that agree, or a lone file, are not drift
+//! - Missing patterns are reported per file; `false` entries are ignored
+//! - Reconciling copies every missing pattern into every file, keeping
+//!   JSONC comments
+
+use std::path::Path;
+
+use agent_intercom::orchestrator::policy_drift::{detect, reconcile, RuleFileKind};
+
+fn write(root: &Path, relative: &str, content: &str) {
+    let path = root.join(relative);
+    std::fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+    std::fs::write(path, content).expect("write");
+}
+
+#[test]
+fn agreeing_or_lone_files_are_not_drift() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    write(
+        temp.path(),
+        ".intercom/settings.json",
+        r#"{ "chat.tools.terminal.autoApprove": { "^cargo test": true } }"#,
+    );
+    assert_eq!(detect(temp.path()).expect("detect"), None);
+
+    write(
+        temp.path(),
+        ".vscode/settings.json",
+        "{\n  // shared with the server\n  \"chat.tools.terminal.autoApprove\": {\n    \"^cargo test\": { \"approve\": true, \"matchCommandLine\": true }\n  }\n}\n",
+    );
+    assert_eq!(detect(temp.path()).expect("detect"), None);
+}
+
+#[test]
+fn drift_lists_what_each_file_lacks() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    write(
+        temp.path(),
+        ".intercom/settings.json",
+        r#"{ "chat.tools.terminal.autoApprove": ["^cargo test", "^git status"] }"#,
+    );
+    write(
+        temp.path(),
+        "app.code-workspace",
+        r#"{ "settings": { "chat.tools.terminal.autoApprove": { "^cargo test": true, "^npm ci": true, "^rm": false } } }"#,
+    );
+
+    let drift = detect(temp.path()).expect("detect").expect("drift");
+    let missing: Vec<(RuleFileKind, Vec<&str>)> = drift
+        .missing()
+        .into_iter()
+        .map(|(file, lacking)| (file.kind, lacking))
+        .collect();
+    assert_eq!(
+        missing,
+        vec![
+            (RuleFileKind::IntercomSettings, vec!["^npm ci"]),
+            (RuleFileKind::CodeWorkspace, vec!["^git status"]),
+        ]
+    );
+    let text = drift.describe();
+    assert!(
+        text.contains("`app.code-workspace` is missing 1 pattern:"),
+        "{text}"
+    );
+    assert!(!text.contains("^rm"), "{text}");
+}
+
+#[test]
+fn reconcile_copies_missing_patterns_into_every_file() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    write(
+        temp.path(),
+        ".intercom/settings.json",
+        r#"{ "enabled": true, "chat.tools.terminal.autoApprove": { "^cargo test": true } }"#,
+    );
+    write(
+        temp.path(),
+        "app.code-workspace",
+        "{\n  \"settings\": {\n    // keep me\n    \"chat.tools.terminal.autoApprove\": {\n      \"^npm ci\": true\n    }\n  }\n}\n",
+    );
+    write(temp.path(), ".vscode/settings.json", "{}\n");
+
+    assert_eq!(reconcile(temp.path()).expect("reconcile"), 4);
+    assert_eq!(detect(temp.path()).expect("detect"), None);
+    let workspace = std::fs::read_to_string(temp.path().join("app.code-workspace")).expect("read");
+    assert!(workspace.contains("// keep me"), "{workspace}");
+    assert_eq!(reconcile(temp.path()).expect("reconcile again"), 0);
+}
@@ -4,7 +4,9 @@
 //! `risk_level_threshold` enforcement, and use of the pre-compiled `RegexSet`.
 
 use agent_intercom::models::approval::RiskLevel;
-use agent_intercom::models::policy::{CompiledWorkspacePolicy, FilePatterns, WorkspacePolicy};
+use agent_intercom::models::policy::{
+    CompiledWorkspacePolicy, FilePatterns, ProxyAction, ProxyRules, WorkspacePolicy,
+};
 use agent_intercom::policy::evaluator::{AutoApproveContext, PolicyEvaluator};
 
 /// Helper to build a policy with the given overrides applied to defaults.
@@ -17,6 +19,7 @@ fn policy(enabled: bool, commands: &[&str], tools: &[&str]) -> CompiledWorkspace
         risk_level_threshold: RiskLevel::Low,
         log_auto_approved: false,
         summary_interval_seconds: 300,
+        proxy: ProxyRules::default(),
     })
 }
 
@@ -353,3 +356,101 @@ fn evaluator_uses_precompiled_regex_set_for_command_matching() {
     let denied = PolicyEvaluator::check("rm -rf /", &None, &wp);
     assert!(!denied.auto_approved, "unmatched command must be denied");
 }
+
+// ─── Proxied tool call classification ─────────────────────────────────
+
+fn proxy_policy(enabled: bool, rules: ProxyRules) -> CompiledWorkspacePolicy {
+    CompiledWorkspacePolicy::from_policy(WorkspacePolicy {
+        enabled,
+        proxy: rules,
+        ..WorkspacePolicy::default()
+    })
+}
+
+fn gateway_rules() -> ProxyRules {
+    ProxyRules {
+        auto_approve: vec!["fs.*".to_owned()],
+        require_approval: vec!["fs.write_file".to_owned()],
+        deny: vec!["shell.*".to_owned()],
+        ..ProxyRules::default()
+    }
+}
+
+#[test]
+fn proxy_deny_and_require_rules_win_over_auto_approve() {
+    let wp = proxy_policy(true, gateway_rules());
+
+    let denied = PolicyEvaluator::classify_proxy_call("shell.exec", &wp);
+    assert_eq!(denied.action, ProxyAction::Deny);
+    assert_eq!(denied.matched_rule.as_deref(), Some("proxy:deny:shell.*"));
+
+    let held = PolicyEvaluator::classify_proxy_call("fs.write_file", &wp);
+    assert_eq!(held.action, ProxyAction::RequireApproval);
+    assert_eq!(
+        held.matched_rule.as_deref(),
+        Some("proxy:require_approval:fs.write_file")
+    );
+
+    let auto = PolicyEvaluator::classify_proxy_call("fs.read_text_file", &wp);
+    assert_eq!(auto.action, ProxyAction::AutoApprove);
+    assert_eq!(
+        auto.matched_rule.as_deref(),
+        Some("proxy:auto_approve:fs.*")
+    );
+}
+
+#[test]
+fn proxy_auto_approve_requires_enabled_policy() {
+    let wp = proxy_policy(false, gateway_rules());
+
+    let held = PolicyEvaluator::classify_proxy_call("fs.read_text_file", &wp);
+    assert_eq!(held.action, ProxyAction::RequireApproval);
+    assert!(held.matched_rule.is_none());
+
+    // Deny rules apply even when auto-approve is switched off.
+    let denied = PolicyEvaluator::classify_proxy_call("shell.exec", &wp);
+    assert_eq!(denied.action, ProxyAction::Deny);
+}
+
+#[test]
+fn proxy_call_ignores_general_rules_and_uses_default_action() {
+    let wp = CompiledWorkspacePolicy::from_policy(WorkspacePolicy {
+        enabled: true,
+        tools: vec!["git.status".to_owned()],
+        proxy: ProxyRules {
+            default_action: ProxyAction::Deny,
+            ..ProxyRules::default()
+        },
+        ..WorkspacePolicy::default()
+    });
+
+    let listed = PolicyEvaluator::classify_proxy_call("git.status", &wp);
+    assert_eq!(listed.action, ProxyAction::Deny);
+    assert!(listed.matched_rule.is_none());
+}
+
+#[test]
+fn proxy_call_risk_comes_from_risk_rules() {
+    let wp = proxy_policy(
+        true,
+        ProxyRules {
+            auto_approve: vec!["fs.*".to_owned(), "db.*".to_owned()],
+            high_risk: vec!["fs.write_*".to_owned()],
+            critical_risk: vec!["db.drop_*".to_owned()],
+            ..ProxyRules::default()
+        },
+    );
+
+    let read = PolicyEvaluator::classify_proxy_call("fs.read_file", &wp);
+    assert_eq!(read.risk_level, RiskLevel::Low);
+    assert_eq!(read.action, ProxyAction::AutoApprove);
+
+    let write = PolicyEvaluator::classify_proxy_call("fs.write_file", &wp);
+    assert_eq!(write.risk_level, RiskLevel::High);
+    assert_eq!(write.action, ProxyAction::AutoApprove);
+
+    // Critical calls are never auto-approved.
+    let drop = PolicyEvaluator::classify_proxy_call("db.drop_table", &wp);
+    assert_eq!(drop.risk_level, RiskLevel::Critical);
+    assert_eq!(drop.action, ProxyAction::RequireApproval);
+}
@@ -7,7 +7,9 @@ use std::fs;
 use std::path::Path;
 
 use agent_intercom::models::approval::RiskLevel;
-use agent_intercom::models::policy::{CompiledWorkspacePolicy, FilePatterns, WorkspacePolicy};
+use agent_intercom::models::policy::{
+    CompiledWorkspacePolicy, FilePatterns, ProxyAction, ProxyRules, WorkspacePolicy,
+};
 use agent_intercom::policy::loader::PolicyLoader;
 
 /// Helper: write a policy JSON file under `workspace_root/.intercom/settings.json`.
@@ -72,6 +74,38 @@ fn loads_minimal_policy_with_defaults() {
     assert_eq!(policy.raw.risk_level_threshold, RiskLevel::Low);
     assert!(!policy.raw.log_auto_approved);
     assert_eq!(policy.raw.summary_interval_seconds, 300);
+    assert_eq!(policy.raw.proxy, ProxyRules::default());
+    assert_eq!(
+        policy.raw.proxy.default_action,
+        ProxyAction::RequireApproval
+    );
+}
+
+#[test]
+fn loads_proxy_classification_rules() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    write_policy(
+        dir.path(),
+        r#"{
+            "enabled": true,
+            "proxy": {
+                "auto_approve": ["fs.read_*"],
+                "require_approval": ["fs.write_file"],
+                "deny": ["shell.*"],
+                "default_action": "deny"
+            }
+        }"#,
+    );
+
+    let policy = PolicyLoader::load(dir.path()).expect("should parse proxy rules");
+
+    assert_eq!(policy.raw.proxy.auto_approve, vec!["fs.read_*".to_owned()]);
+    assert_eq!(
+        policy.raw.proxy.require_approval,
+        vec!["fs.write_file".to_owned()]
+    );
+    assert_eq!(policy.raw.proxy.deny, vec!["shell.*".to_owned()]);
+    assert_eq!(policy.raw.proxy.default_action, ProxyAction::Deny);
 }
 
 // ─── Malformed file fallback to deny-all ──────────────────────────────
@@ -217,6 +251,7 @@ fn compiled_policy_has_expected_pattern_count() {
         risk_level_threshold: RiskLevel::Low,
         log_auto_approved: false,
         summary_interval_seconds: 300,
+        proxy: ProxyRules::default(),
     };
     let compiled = CompiledWorkspacePolicy::from_policy(raw);
     assert_eq!(
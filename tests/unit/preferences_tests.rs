@@ -0,0 +1,115 @@
+//! Unit tests for operator notification preferences (`/intercom prefs`).
+//!
+//! Tests cover:
+//! - Quiet hours parse `HH:MM-HH:MM` and wrap past midnight
+//! - Delivery values round-trip through their wire form
+//! - `PreferencesRepo` round-trips, replaces and deletes preferences
+
+use std::sync::Arc;
+
+use agent_intercom::config::SlackDetailLevel;
+use agent_intercom::models::preferences::{NotificationDelivery, QuietHours, UserPreferences};
+use agent_intercom::persistence::{db, preferences_repo::PreferencesRepo};
+use chrono::NaiveTime;
+
+fn at(hour: u32, minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).expect("time")
+}
+
+async fn repo() -> PreferencesRepo {
+    PreferencesRepo::new(Arc::new(db::connect_memory().await.expect("db")))
+}
+
+#[test]
+fn quiet_hours_parse_and_display() {
+    let quiet = QuietHours::parse(" 09:30 - 17:00 ").expect("range");
+
+    assert_eq!(quiet.start, at(9, 30));
+    assert_eq!(quiet.end, at(17, 0));
+    assert_eq!(quiet.to_string(), "09:30-17:00");
+}
+
+#[test]
+fn quiet_hours_reject_malformed_and_empty_ranges() {
+    for range in [
+        "",
+        "22:00",
+        "22:00-",
+        "25:00-07:00",
+        "10pm-7am",
+        "08:00-08:00",
+    ] {
+        assert!(QuietHours::parse(range).is_err(), "{range:?} accepted");
+    }
+}
+
+#[test]
+fn quiet_hours_contain_daytime_window() {
+    let quiet = QuietHours::parse("12:00-13:00").expect("range");
+
+    assert!(quiet.contains(at(12, 0)));
+    assert!(quiet.contains(at(12, 59)));
+    assert!(!quiet.contains(at(13, 0)));
+    assert!(!quiet.contains(at(11, 59)));
+}
+
+#[test]
+fn quiet_hours_wrap_past_midnight() {
+    let quiet = QuietHours::parse("22:00-07:00").expect("range");
+
+    assert!(quiet.contains(at(22, 0)));
+    assert!(quiet.contains(at(3, 0)));
+    assert!(!quiet.contains(at(7, 0)));
+    assert!(!quiet.contains(at(12, 0)));
+}
+
+#[test]
+fn delivery_round_trips_wire_form() {
+    for delivery in [
+        NotificationDelivery::Channel,
+        NotificationDelivery::Dm,
+        NotificationDelivery::Both,
+    ] {
+        assert_eq!(
+            NotificationDelivery::parse(delivery.as_str()).expect("parse"),
+            delivery
+        );
+    }
+    assert!(NotificationDelivery::parse("email").is_err());
+}
+
+#[tokio::test]
+async fn repo_round_trips_and_replaces_preferences() {
+    let repo = repo().await;
+    assert!(repo.get("U1").await.expect("get").is_none());
+
+    let mut prefs = UserPreferences::new("U1");
+    prefs.detail_level = Some(SlackDetailLevel::Minimal);
+    prefs.delivery = NotificationDelivery::Both;
+    prefs.quiet_hours = Some(QuietHours::parse("22:00-07:00").expect("range"));
+    repo.upsert(&prefs).await.expect("insert");
+
+    let stored = repo.get("U1").await.expect("get").expect("stored");
+    assert_eq!(stored.detail_level, Some(SlackDetailLevel::Minimal));
+    assert_eq!(stored.delivery, NotificationDelivery::Both);
+    assert_eq!(stored.quiet_hours, prefs.quiet_hours);
+
+    let replacement = UserPreferences::new("U1");
+    repo.upsert(&replacement).await.expect("replace");
+    let stored = repo.get("U1").await.expect("get").expect("stored");
+    assert_eq!(stored.detail_level, None);
+    assert_eq!(stored.delivery, NotificationDelivery::Channel);
+    assert_eq!(stored.quiet_hours, None);
+}
+
+#[tokio::test]
+async fn repo_delete_reports_whether_preferences_existed() {
+    let repo = repo().await;
+    repo.upsert(&UserPreferences::new("U1"))
+        .await
+        .expect("insert");
+
+    assert!(repo.delete("U1").await.expect("delete"));
+    assert!(!repo.delete("U1").await.expect("delete again"));
+    assert!(repo.get("U1").await.expect("get").is_none());
+}
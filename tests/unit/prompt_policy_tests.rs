@@ -0,0 +1,43 @@
+//! Unit tests for prompt auto-policy helpers (`[[prompts.auto]]`).
+
+use agent_intercom::config::PromptAutoRule;
+use agent_intercom::models::prompt::{ContinuationPrompt, PromptDecision, PromptType};
+use agent_intercom::orchestrator::prompt_policy::{consecutive_of_type, describe};
+
+fn prompt(prompt_type: PromptType) -> ContinuationPrompt {
+    ContinuationPrompt::new("s1".into(), "text".into(), prompt_type, None, None)
+}
+
+#[test]
+fn consecutive_counts_trailing_run_of_type() {
+    let history = [
+        prompt(PromptType::ErrorRecovery),
+        prompt(PromptType::Continuation),
+        prompt(PromptType::ErrorRecovery),
+        prompt(PromptType::ErrorRecovery),
+    ];
+    assert_eq!(consecutive_of_type(&history, PromptType::ErrorRecovery), 2);
+    assert_eq!(consecutive_of_type(&history, PromptType::Continuation), 0);
+    assert_eq!(consecutive_of_type(&[], PromptType::Continuation), 0);
+}
+
+#[test]
+fn describe_lists_rule_conditions() {
+    let mut rule = PromptAutoRule {
+        prompt_type: PromptType::ResourceWarning,
+        decision: PromptDecision::Continue,
+        instruction: None,
+        after_consecutive: 1,
+        hours: None,
+        weekdays_only: false,
+    };
+    assert_eq!(describe(&rule), "resource_warning");
+
+    rule.after_consecutive = 3;
+    rule.hours = Some("09:00-17:00".into());
+    rule.weekdays_only = true;
+    assert_eq!(
+        describe(&rule),
+        "resource_warning after 3 in a row during 09:00-17:00 on weekdays"
+    );
+}
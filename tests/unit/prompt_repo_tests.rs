@@ -6,6 +6,7 @@
 //! - `get_pending_for_session` returns only undecided prompts
 //! - `update_decision` records decision and optional instruction
 //! - `list_pending` returns all undecided prompts across sessions
+//! - `set_slack_anchor` records the prompt message and its thread
 
 use std::sync::Arc;
 
@@ -81,6 +82,24 @@ async fn update_decision_continue() {
     assert!(fetched.instruction.is_none());
 }
 
+#[tokio::test]
+async fn set_slack_anchor_records_message_and_thread() {
+    let db = db::connect_memory().await.expect("db");
+    let repo = PromptRepo::new(Arc::new(db));
+
+    let prompt = sample_prompt("sess-anchor");
+    let id = prompt.id.clone();
+    repo.create(&prompt).await.expect("create");
+
+    repo.set_slack_anchor(&id, "1700000000.000300", "1700000000.000300")
+        .await
+        .expect("anchor");
+
+    let fetched = repo.get_by_id(&id).await.expect("query").expect("exists");
+    assert_eq!(fetched.slack_ts.as_deref(), Some("1700000000.000300"));
+    assert_eq!(fetched.thread_ts.as_deref(), Some("1700000000.000300"));
+}
+
 #[tokio::test]
 async fn update_decision_refine_with_instruction() {
     let db = db::connect_memory().await.expect("db");
@@ -0,0 +1,225 @@
+//! Unit tests for internal queue alarms (`orchestrator::queue_alarms`).
+//!
+//! Covers:
+//! - An alarm is raised only after the queue stays over its threshold for
+//!   the sustain period, once per run, and cleared when it drains
+//! - A dip below the threshold restarts the sustain period
+//! - A threshold of `0` disables the queue
+//! - Alarm text carries the escalation mention only when raised
+//! - Pending steering and approval counts skip delivered, expired and
+//!   decided entries
+//! - `[alarms]` defaults and validation
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use agent_intercom::config::{AlarmsConfig, GlobalConfig};
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::models::session::{Session, SessionMode};
+use agent_intercom::models::steering::{SteeringMessage, SteeringSource};
+use agent_intercom::orchestrator::queue_alarms::{self, AlarmChange, QueueAlarms, QueueKind};
+use agent_intercom::persistence::{
+    approval_repo::ApprovalRepo, db, session_repo::SessionRepo, steering_repo::SteeringRepo,
+};
+use agent_intercom::AppError;
+
+const SUSTAIN: Duration = Duration::from_mins(1);
+
+#[test]
+fn alarm_raised_after_sustain_and_cleared_once_drained() {
+    let mut alarms = QueueAlarms::default();
+    let start = Instant::now();
+    let queue = QueueKind::Steering;
+
+    assert_eq!(alarms.observe(queue, 12, 10, SUSTAIN, start), None);
+    assert_eq!(
+        alarms.observe(queue, 15, 10, SUSTAIN, start + Duration::from_secs(30)),
+        None
+    );
+    assert_eq!(
+        alarms.observe(queue, 20, 10, SUSTAIN, start + SUSTAIN),
+        Some(AlarmChange::Raised {
+            queue,
+            depth: 20,
+            threshold: 10,
+            sustained: SUSTAIN,
+        })
+    );
+    assert!(alarms.is_raised(queue));
+    assert_eq!(
+        alarms.observe(queue, 30, 10, SUSTAIN, start + SUSTAIN * 2),
+        None,
+        "raised once per run"
+    );
+    assert_eq!(
+        alarms.observe(queue, 3, 10, SUSTAIN, start + SUSTAIN * 3),
+        Some(AlarmChange::Cleared {
+            queue,
+            depth: 3,
+            threshold: 10,
+        })
+    );
+    assert!(!alarms.is_raised(queue));
+    assert_eq!(
+        alarms.observe(queue, 3, 10, SUSTAIN, start + SUSTAIN * 4),
+        None
+    );
+}
+
+#[test]
+fn dip_below_threshold_restarts_sustain_period() {
+    let mut alarms = QueueAlarms::default();
+    let start = Instant::now();
+    let queue = QueueKind::SlackOutbound;
+
+    assert_eq!(alarms.observe(queue, 10, 10, SUSTAIN, start), None);
+    assert_eq!(
+        alarms.observe(queue, 9, 10, SUSTAIN, start + Duration::from_secs(40)),
+        None,
+        "dipping below an unraised alarm sends no clear notice"
+    );
+    assert_eq!(
+        alarms.observe(queue, 10, 10, SUSTAIN, start + Duration::from_secs(50)),
+        None
+    );
+    assert_eq!(
+        alarms.observe(queue, 10, 10, SUSTAIN, start + Duration::from_secs(100)),
+        None,
+        "only 50s over the threshold since the dip"
+    );
+    assert!(matches!(
+        alarms.observe(queue, 10, 10, SUSTAIN, start + Duration::from_secs(110)),
+        Some(AlarmChange::Raised { .. })
+    ));
+}
+
+#[test]
+fn zero_threshold_disables_queue() {
+    let mut alarms = QueueAlarms::default();
+    let start = Instant::now();
+    let queue = QueueKind::PendingApprovals;
+
+    assert_eq!(alarms.observe(queue, 500, 0, SUSTAIN, start), None);
+    assert_eq!(
+        alarms.observe(queue, 500, 0, SUSTAIN, start + SUSTAIN),
+        None
+    );
+    assert!(!alarms.is_raised(queue));
+}
+
+#[test]
+fn describe_mentions_only_raised_alarms() {
+    let raised = AlarmChange::Raised {
+        queue: QueueKind::SlackOutbound,
+        depth: 140,
+        threshold: 128,
+        sustained: Duration::from_secs(135),
+    };
+    let text = queue_alarms::describe(&raised, Some("<!here>"));
+    assert!(text.contains("<!here>"), "{text}");
+    assert!(
+        text.contains("Slack outgoing queue has held 140 items"),
+        "{text}"
+    );
+    assert!(text.contains("threshold 128"), "{text}");
+    assert!(text.contains("135s"), "{text}");
+    assert!(!queue_alarms::describe(&raised, None).contains("<!here>"));
+
+    let cleared = AlarmChange::Cleared {
+        queue: QueueKind::PendingApprovals,
+        depth: 2,
+        threshold: 25,
+    };
+    let text = queue_alarms::describe(&cleared, Some("<!here>"));
+    assert!(!text.contains("<!here>"), "{text}");
+    assert!(text.contains("Pending approvals is down to 2"), "{text}");
+}
+
+#[tokio::test]
+async fn pending_counts_skip_delivered_expired_and_decided() {
+    let db = Arc::new(db::connect_memory().await.expect("db"));
+    let session = SessionRepo::new(Arc::clone(&db))
+        .create(&Session::new(
+            "U1".into(),
+            "/repo".into(),
+            None,
+            SessionMode::Remote,
+        ))
+        .await
+        .expect("session");
+
+    let steering = SteeringRepo::new(Arc::clone(&db));
+    let steer = |text: &str| {
+        SteeringMessage::new(session.id.clone(), None, text.into(), SteeringSource::Slack)
+    };
+    steering.insert(&steer("queued")).await.expect("insert");
+    let delivered = steering.insert(&steer("delivered")).await.expect("insert");
+    steering
+        .mark_consumed(&delivered.id)
+        .await
+        .expect("consume");
+    steering
+        .insert(&steer("expired").with_ttl(chrono::Duration::seconds(-1)))
+        .await
+        .expect("insert");
+    assert_eq!(steering.count_pending().await.expect("count"), 1);
+
+    let approvals = ApprovalRepo::new(Arc::clone(&db));
+    let mut ids = Vec::new();
+    for file in ["a.rs", "b.rs", "c.rs"] {
+        let request = approvals
+            .create(&ApprovalRequest::new(
+                session.id.clone(),
+                format!("edit {file}"),
+                None,
+                "+x".into(),
+                file.into(),
+                RiskLevel::Low,
+                "hash".into(),
+            ))
+            .await
+            .expect("request");
+        ids.push(request.id);
+    }
+    approvals
+        .update_status(&ids[0], ApprovalStatus::Rejected)
+        .await
+        .expect("reject");
+    assert_eq!(approvals.count_pending().await.expect("count"), 2);
+}
+
+#[test]
+fn alarms_config_defaults_and_validation() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let base = format!(
+        "default_workspace_root = '{root}'\n\
+         host_cli = \"claude\"\n\
+         [slack]\n\
+         [timeouts]\n\
+         approval_seconds = 60\n\
+         prompt_seconds = 60\n\
+         wait_seconds = 0\n\
+         [stall]\n\
+         inactivity_threshold_seconds = 300\n\
+         escalation_threshold_seconds = 120\n\
+         max_retries = 3\n\
+         default_nudge_message = \"continue\"\n"
+    );
+
+    let config = GlobalConfig::from_toml_str(&base).expect("config parses");
+    assert_eq!(config.alarms, AlarmsConfig::default());
+    assert!(config.alarms.enabled);
+    assert_eq!(QueueKind::PendingApprovals.threshold(&config.alarms), 25);
+
+    let custom = format!("{base}[alarms]\nsteering = 0\nsustain_seconds = 30\n");
+    let config = GlobalConfig::from_toml_str(&custom).expect("config parses");
+    assert_eq!(QueueKind::Steering.threshold(&config.alarms), 0);
+    assert_eq!(config.alarms.sustain_seconds, 30);
+
+    let invalid = format!("{base}[alarms]\ncheck_interval_seconds = 0\n");
+    assert!(matches!(
+        GlobalConfig::from_toml_str(&invalid),
+        Err(AppError::Config(ref msg)) if msg.contains("check_interval_seconds")
+    ));
+}
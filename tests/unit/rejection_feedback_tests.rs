@@ -0,0 +1,53 @@
+//! Unit tests for structured rejection feedback parsing.
+
+use agent_intercom::models::rejection::{RejectionCategory, RejectionFeedback};
+
+#[test]
+fn category_prefix_and_list_items_are_split_out() {
+    let feedback = RejectionFeedback::from_reason(Some(
+        "security: this leaks the token into logs\n- redact `token` before logging\n2) add a test",
+    ));
+    assert_eq!(feedback.category, RejectionCategory::Security);
+    assert_eq!(feedback.summary, "this leaks the token into logs");
+    assert_eq!(
+        feedback.required_changes,
+        vec!["redact `token` before logging", "add a test"]
+    );
+    assert!(feedback.policy_rules.is_empty());
+}
+
+#[test]
+fn policy_references_imply_policy_category() {
+    let feedback = RejectionFeedback::from_reason(Some(
+        "touches a protected file (policy:protected_paths), see policy:protected_paths.",
+    ));
+    assert_eq!(feedback.category, RejectionCategory::Policy);
+    assert_eq!(feedback.policy_rules, vec!["protected_paths"]);
+}
+
+#[test]
+fn plain_reason_is_summary_with_other_category() {
+    let feedback = RejectionFeedback::from_reason(Some("not now: wait for the release"));
+    assert_eq!(feedback.category, RejectionCategory::Other);
+    assert_eq!(feedback.summary, "not now: wait for the release");
+    assert!(feedback.required_changes.is_empty());
+
+    let empty = RejectionFeedback::from_reason(None);
+    assert_eq!(empty.category, RejectionCategory::Other);
+    assert!(empty.summary.is_empty());
+}
+
+#[test]
+fn stale_reasons_are_categorised() {
+    let feedback = RejectionFeedback::from_reason(Some(
+        "stale: src/lib.rs changed on disk after this diff was proposed",
+    ));
+    assert_eq!(feedback.category, RejectionCategory::Stale);
+}
+
+#[test]
+fn hyphenated_and_numeric_prose_is_not_a_list_item() {
+    let feedback = RejectionFeedback::from_reason(Some("-5 is the wrong offset\n1.5x too slow"));
+    assert!(feedback.required_changes.is_empty());
+    assert_eq!(feedback.summary, "-5 is the wrong offset 1.5x too slow");
+}
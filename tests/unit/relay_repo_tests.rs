@@ -0,0 +1,109 @@
+//! Unit tests for `RelayRepo` (session-to-session relay messages).
+//!
+//! Tests cover:
+//! - Payload round-trip through persistence
+//! - Pending messages scoped to the recipient, filtered by topic
+//! - `mark_delivered` claims a message exactly once
+//! - Listing a session's sent and received messages
+
+use std::sync::Arc;
+
+use agent_intercom::models::relay::RelayMessage;
+use agent_intercom::persistence::{db, relay_repo::RelayRepo};
+use chrono::Utc;
+use serde_json::json;
+
+async fn repo() -> RelayRepo {
+    RelayRepo::new(Arc::new(db::connect_memory().await.expect("db")))
+}
+
+fn message(from: &str, to: &str, topic: Option<&str>) -> RelayMessage {
+    RelayMessage::new(
+        from.to_owned(),
+        to.to_owned(),
+        topic.map(str::to_owned),
+        json!({ "files": ["src/lib.rs"], "ok": true }),
+    )
+}
+
+#[tokio::test]
+async fn insert_round_trips_payload() {
+    let repo = repo().await;
+    let sent = repo
+        .insert(&message("producer", "consumer", Some("build")))
+        .await
+        .expect("insert");
+
+    let pending = repo.fetch_pending("consumer", None).await.expect("fetch");
+    assert_eq!(pending, vec![sent]);
+    assert_eq!(pending[0].payload["files"][0], "src/lib.rs");
+    assert!(pending[0].id.starts_with("relay:"));
+}
+
+#[tokio::test]
+async fn fetch_pending_is_scoped_to_recipient_and_topic() {
+    let repo = repo().await;
+    let build = repo
+        .insert(&message("producer", "consumer", Some("build")))
+        .await
+        .expect("insert");
+    let review = repo
+        .insert(&message("producer", "consumer", Some("review")))
+        .await
+        .expect("insert");
+    repo.insert(&message("consumer", "producer", Some("build")))
+        .await
+        .expect("insert");
+
+    let all = repo.fetch_pending("consumer", None).await.expect("fetch");
+    assert_eq!(
+        all.iter().map(|m| m.id.as_str()).collect::<Vec<_>>(),
+        [build.id.as_str(), review.id.as_str()]
+    );
+    let reviews = repo
+        .fetch_pending("consumer", Some("review"))
+        .await
+        .expect("fetch");
+    assert_eq!(reviews, vec![review]);
+}
+
+#[tokio::test]
+async fn mark_delivered_claims_once() {
+    let repo = repo().await;
+    let sent = repo
+        .insert(&message("producer", "consumer", None))
+        .await
+        .expect("insert");
+
+    assert!(repo
+        .mark_delivered(&sent.id, Utc::now())
+        .await
+        .expect("mark"));
+    assert!(!repo
+        .mark_delivered(&sent.id, Utc::now())
+        .await
+        .expect("mark"));
+    assert!(repo
+        .fetch_pending("consumer", None)
+        .await
+        .expect("fetch")
+        .is_empty());
+}
+
+#[tokio::test]
+async fn list_for_session_includes_sent_and_received() {
+    let repo = repo().await;
+    repo.insert(&message("producer", "consumer", None))
+        .await
+        .expect("insert");
+    repo.insert(&message("consumer", "producer", None))
+        .await
+        .expect("insert");
+    repo.insert(&message("producer", "other", None))
+        .await
+        .expect("insert");
+
+    let listed = repo.list_for_session("consumer").await.expect("list");
+    assert_eq!(listed.len(), 2);
+    assert!(listed[0].created_at <= listed[1].created_at);
+}
@@ -0,0 +1,110 @@
+//! Unit tests for `RunRepo` and run membership (`run-start`).
+//!
+//! Tests cover:
+//! - An operator has at most one active run, and ending it frees the slot
+//! - Runs are found by ID prefix or name, newest first
+//! - Sessions are listed by run, oldest first
+
+use std::sync::Arc;
+
+use agent_intercom::models::run::Run;
+use agent_intercom::models::session::{Session, SessionMode};
+use agent_intercom::persistence::{db, run_repo::RunRepo, session_repo::SessionRepo};
+use chrono::{Duration, Utc};
+
+async fn database() -> Arc<db::Database> {
+    Arc::new(db::connect_memory().await.expect("db"))
+}
+
+fn run(name: &str, owner: &str) -> Run {
+    Run::new(name.into(), owner.into(), Some("C_TEST".into()))
+}
+
+#[tokio::test]
+async fn one_active_run_per_operator() {
+    let repo = RunRepo::new(database().await);
+    let first = run("release hardening", "U1");
+    repo.create(&first).await.expect("create");
+
+    assert!(repo.create(&run("second", "U1")).await.is_err());
+    repo.create(&run("other operator", "U2"))
+        .await
+        .expect("other operator");
+    assert_eq!(
+        repo.active_for("U1").await.expect("active").map(|r| r.id),
+        Some(first.id.clone())
+    );
+
+    let ended = repo
+        .end(&first.id, Utc::now())
+        .await
+        .expect("end")
+        .expect("ended");
+    assert!(!ended.is_active());
+    assert!(repo
+        .end(&first.id, Utc::now())
+        .await
+        .expect("end again")
+        .is_none());
+    assert!(repo.active_for("U1").await.expect("active").is_none());
+    repo.create(&run("second", "U1")).await.expect("new run");
+}
+
+#[tokio::test]
+async fn find_matches_id_prefix_or_newest_name() {
+    let repo = RunRepo::new(database().await);
+    let mut older = run("Nightly", "U1");
+    older.started_at = Utc::now() - Duration::days(1);
+    older.ended_at = Some(Utc::now() - Duration::hours(20));
+    repo.create(&older).await.expect("older");
+    let newer = run("nightly", "U2");
+    repo.create(&newer).await.expect("newer");
+
+    let by_name = repo.find("NIGHTLY").await.expect("find").expect("found");
+    assert_eq!(by_name.id, newer.id);
+    let by_prefix = repo
+        .find(&older.id[..8])
+        .await
+        .expect("find")
+        .expect("found");
+    assert_eq!(by_prefix.id, older.id);
+    assert!(repo.find("weekly").await.expect("find").is_none());
+    assert_eq!(
+        repo.latest_for("U1").await.expect("latest").map(|r| r.id),
+        Some(older.id)
+    );
+}
+
+#[tokio::test]
+async fn sessions_are_listed_by_run() {
+    let db = database().await;
+    let sessions = SessionRepo::new(Arc::clone(&db));
+    let mut member = Session::new("U1".into(), "/ws".into(), None, SessionMode::Remote);
+    member.run_id = Some("run-1".into());
+    sessions.create(&member).await.expect("member");
+    let joined = Session::new("U1".into(), "/ws".into(), None, SessionMode::Remote);
+    sessions.create(&joined).await.expect("joined");
+    sessions
+        .set_run_id(&joined.id, "run-1")
+        .await
+        .expect("join");
+    sessions
+        .create(&Session::new(
+            "U1".into(),
+            "/ws".into(),
+            None,
+            SessionMode::Remote,
+        ))
+        .await
+        .expect("outsider");
+
+    let listed: Vec<String> = sessions
+        .list_by_run("run-1")
+        .await
+        .expect("list")
+        .into_iter()
+        .map(|s| s.id)
+        .collect();
+
+    assert_eq!(listed, [member.id, joined.id]);
+}
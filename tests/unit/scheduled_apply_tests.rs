@@ -0,0 +1,99 @@
+//! Unit tests for scheduled applies.
+//!
+//! Validates:
+//! - `HH:MM` resolves to its next occurrence in server-local time
+//! - Verification commands run in order and stop at the first failure
+//! - Operator and agent notices for applied and refused changes
+
+use chrono::{Local, NaiveTime, Timelike, Utc};
+
+use agent_intercom::config::ScheduledApplyConfig;
+use agent_intercom::models::approval::{ApprovalRequest, RiskLevel};
+use agent_intercom::orchestrator::scheduled_apply::{
+    agent_notice, next_occurrence, operator_notice, run_verification, ScheduledOutcome,
+    VerifyOutcome,
+};
+
+fn approval() -> ApprovalRequest {
+    ApprovalRequest::new(
+        "session-1".into(),
+        "Add index".into(),
+        None,
+        "CREATE INDEX i ON t (c);\n".into(),
+        "db/index.sql".into(),
+        RiskLevel::Low,
+        "new_file".into(),
+    )
+}
+
+#[test]
+fn next_occurrence_is_within_a_day_at_the_requested_local_time() {
+    let now = Utc::now();
+    for at in ["00:00", "06:30", "18:00", "23:59"] {
+        let at = NaiveTime::parse_from_str(at, "%H:%M").expect("time");
+        let due = next_occurrence(now, at);
+        assert!(due > now, "{at}: {due} not after {now}");
+        assert!(due - now <= chrono::Duration::days(1), "{at}: {due}");
+        let local = due.with_timezone(&Local);
+        assert_eq!((local.hour(), local.minute()), (at.hour(), at.minute()));
+    }
+}
+
+#[tokio::test]
+async fn verification_stops_at_first_failure() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let config = ScheduledApplyConfig {
+        verify_commands: vec!["exit 0".into(), "exit 3".into(), "exit 0".into()],
+        ..ScheduledApplyConfig::default()
+    };
+    let checks = run_verification(&config, temp.path()).await;
+    assert_eq!(
+        checks,
+        vec![
+            VerifyOutcome {
+                command: "exit 0".into(),
+                passed: true,
+                detail: "passed".into(),
+            },
+            VerifyOutcome {
+                command: "exit 3".into(),
+                passed: false,
+                detail: "exit 3".into(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn notices_report_outcome_and_verification() {
+    let approval = approval();
+    let applied = ScheduledOutcome::Applied {
+        bytes: 25,
+        checks: vec![VerifyOutcome {
+            command: "make check".into(),
+            passed: false,
+            detail: "exit 2: 1 test failed".into(),
+        }],
+    };
+    let text = operator_notice(&approval, &applied);
+    assert!(text.contains("`db/index.sql`"), "{text}");
+    assert!(text.contains("25 bytes"), "{text}");
+    assert!(
+        text.contains("\u{274c} `make check` \u{2014} exit 2: 1 test failed"),
+        "{text}"
+    );
+    let text = agent_notice(&approval, &applied);
+    assert!(text.contains("has now been applied"), "{text}");
+    assert!(
+        text.contains("`make check` exit 2: 1 test failed"),
+        "{text}"
+    );
+
+    let refused = ScheduledOutcome::NotApplied {
+        reason: "the file has changed since the diff was proposed".into(),
+    };
+    assert!(operator_notice(&approval, &refused).contains("Not applied"));
+    let text = agent_notice(&approval, &refused);
+    assert!(text.contains(&approval.id), "{text}");
+    assert!(text.contains("check_diff"), "{text}");
+}
@@ -0,0 +1,193 @@
+//! Unit tests for bulk session operations (`agent-intercom-ctl sessions`).
+//!
+//! Validates:
+//! - Selectors match by status, owner and age, and skip soft-deleted
+//!   sessions
+//! - `clear` terminates matching sessions, `pause` records pause requests,
+//!   `delete` hides ended sessions
+//! - Sessions the action does not apply to are reported without stopping
+//!   the rest
+
+use std::sync::Arc;
+
+use agent_intercom::config::GlobalConfig;
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::orchestrator::session_bulk::{apply, BulkAction, SessionSelector};
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::state::AppState;
+
+use super::test_helpers::test_app_state;
+
+fn make_config() -> GlobalConfig {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8").replace('\\', "\\\\");
+    let toml = format!(
+        r#"
+default_workspace_root = '{root}'
+http_port = 0
+ipc_name = "test-session-bulk"
+max_concurrent_sessions = 5
+host_cli = "echo"
+
+[slack]
+channel_id = "C_TEST"
+
+[timeouts]
+approval_seconds = 5
+prompt_seconds = 5
+wait_seconds = 5
+
+[stall]
+enabled = false
+inactivity_threshold_seconds = 300
+escalation_threshold_seconds = 120
+max_retries = 3
+default_nudge_message = "continue"
+"#
+    );
+    GlobalConfig::from_toml_str(&toml).expect("valid test config")
+}
+
+async fn app_state() -> Arc<AppState> {
+    test_app_state(make_config()).await
+}
+
+/// Create a session for `owner` with `status`, last updated `days_ago`.
+async fn session(state: &AppState, owner: &str, status: SessionStatus, days_ago: i64) -> Session {
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let created = repo
+        .create(&Session::new(
+            owner.into(),
+            "/test/workspace".into(),
+            None,
+            SessionMode::Remote,
+        ))
+        .await
+        .expect("create session");
+    let updated_at = chrono::Utc::now() - chrono::Duration::days(days_ago);
+    sqlx::query("UPDATE session SET status = ?1, updated_at = ?2 WHERE id = ?3")
+        .bind(status.as_str())
+        .bind(updated_at.to_rfc3339())
+        .bind(&created.id)
+        .execute(state.db.as_ref())
+        .await
+        .expect("backdate session");
+    repo.get_by_id(&created.id)
+        .await
+        .expect("fetch")
+        .expect("session exists")
+}
+
+async fn status_of(state: &AppState, id: &str) -> SessionStatus {
+    SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(id)
+        .await
+        .expect("fetch")
+        .expect("session exists")
+        .status
+}
+
+#[tokio::test]
+async fn clear_terminates_old_interrupted_sessions_only() {
+    let state = app_state().await;
+    let old = session(&state, "U1", SessionStatus::Interrupted, 10).await;
+    let recent = session(&state, "U1", SessionStatus::Interrupted, 1).await;
+    let active = session(&state, "U1", SessionStatus::Active, 10).await;
+
+    let selector = SessionSelector {
+        statuses: vec![SessionStatus::Interrupted],
+        older_than: Some(chrono::Duration::days(7)),
+        ..SessionSelector::default()
+    };
+    let outcome = apply(&state, BulkAction::Clear, &selector)
+        .await
+        .expect("bulk clear");
+
+    assert_eq!(outcome.matched, 1);
+    assert_eq!(outcome.succeeded, [old.id.as_str()]);
+    assert!(outcome.failed.is_empty());
+    assert_eq!(status_of(&state, &old.id).await, SessionStatus::Terminated);
+    assert_eq!(
+        status_of(&state, &recent.id).await,
+        SessionStatus::Interrupted
+    );
+    assert_eq!(status_of(&state, &active.id).await, SessionStatus::Active);
+}
+
+#[tokio::test]
+async fn clear_by_owner_uses_live_and_interrupted_defaults() {
+    let state = app_state().await;
+    let mine = session(&state, "U1", SessionStatus::Active, 0).await;
+    let paused = session(&state, "U1", SessionStatus::Paused, 0).await;
+    let done = session(&state, "U1", SessionStatus::Terminated, 0).await;
+    let theirs = session(&state, "U2", SessionStatus::Active, 0).await;
+
+    let selector = SessionSelector {
+        owner: Some("U1".into()),
+        ..SessionSelector::default()
+    };
+    let outcome = apply(&state, BulkAction::Clear, &selector)
+        .await
+        .expect("bulk clear");
+
+    assert_eq!(outcome.matched, 2);
+    assert!(outcome.succeeded.contains(&mine.id));
+    assert!(outcome.succeeded.contains(&paused.id));
+    assert!(!outcome.succeeded.contains(&done.id));
+    assert_eq!(status_of(&state, &theirs.id).await, SessionStatus::Active);
+}
+
+#[tokio::test]
+async fn pause_requests_and_reports_sessions_it_cannot_pause() {
+    let state = app_state().await;
+    let active = session(&state, "U1", SessionStatus::Active, 0).await;
+    let paused = session(&state, "U1", SessionStatus::Paused, 0).await;
+
+    let selector = SessionSelector {
+        statuses: vec![SessionStatus::Active, SessionStatus::Paused],
+        ..SessionSelector::default()
+    };
+    let outcome = apply(&state, BulkAction::Pause, &selector)
+        .await
+        .expect("bulk pause");
+
+    assert_eq!(outcome.succeeded, [active.id.as_str()]);
+    assert!(state.pause_requests.is_requested(&active.id));
+    assert_eq!(outcome.failed.len(), 1);
+    assert_eq!(outcome.failed[0].session_id, paused.id);
+    assert!(
+        outcome.failed[0].error.contains("cannot be paused"),
+        "{:?}",
+        outcome.failed
+    );
+}
+
+#[tokio::test]
+async fn delete_hides_ended_sessions_and_skips_them_afterwards() {
+    let state = app_state().await;
+    let ended = session(&state, "U1", SessionStatus::Terminated, 3).await;
+    let active = session(&state, "U1", SessionStatus::Active, 3).await;
+
+    let outcome = apply(&state, BulkAction::Delete, &SessionSelector::default())
+        .await
+        .expect("bulk delete");
+    assert_eq!(outcome.succeeded, [ended.id.as_str()]);
+
+    let again = apply(&state, BulkAction::Delete, &SessionSelector::default())
+        .await
+        .expect("second bulk delete");
+    assert_eq!(again.matched, 0);
+
+    let forced = apply(
+        &state,
+        BulkAction::Delete,
+        &SessionSelector {
+            statuses: vec![SessionStatus::Active],
+            ..SessionSelector::default()
+        },
+    )
+    .await
+    .expect("bulk delete of active");
+    assert_eq!(forced.failed.len(), 1);
+    assert_eq!(forced.failed[0].session_id, active.id);
+}
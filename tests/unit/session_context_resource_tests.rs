@@ -0,0 +1,125 @@
+//! Unit tests for the session context resources
+//! (`mcp::resources::session_context`).
+//!
+//! Tests cover:
+//! - Decisions list newest first, skip pending requests and cap the count
+//! - Plan shows the task and progress checklist, or a hint without one
+//! - Steering backlog marks interrupts and reports an empty queue
+
+use chrono::{Duration, Utc};
+
+use agent_intercom::mcp::resources::session_context::{
+    decisions_markdown, plan_markdown, steering_markdown, RECENT_DECISIONS,
+};
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::models::progress::{ProgressItem, ProgressStatus};
+use agent_intercom::models::prompt::{ContinuationPrompt, PromptDecision, PromptType};
+use agent_intercom::models::session::{Session, SessionMode};
+use agent_intercom::models::steering::{SteeringMessage, SteeringPriority, SteeringSource};
+
+fn approval(title: &str, status: ApprovalStatus, minutes_ago: i64) -> ApprovalRequest {
+    let mut request = ApprovalRequest::new(
+        "s1".into(),
+        title.into(),
+        None,
+        "+x".into(),
+        "src/lib.rs".into(),
+        RiskLevel::Low,
+        "sha256:abc".into(),
+    );
+    request.status = status;
+    request.created_at = Utc::now() - Duration::minutes(minutes_ago);
+    request
+}
+
+#[test]
+fn decisions_are_newest_first_without_pending() {
+    let approvals = vec![
+        approval("Old change", ApprovalStatus::Rejected, 30),
+        approval("Waiting change", ApprovalStatus::Pending, 1),
+        approval("New change", ApprovalStatus::Consumed, 5),
+    ];
+    let mut prompt = ContinuationPrompt::new(
+        "s1".into(),
+        "Keep going?".into(),
+        PromptType::Continuation,
+        None,
+        None,
+    );
+    prompt.decision = Some(PromptDecision::Refine);
+    prompt.instruction = Some("focus on tests".into());
+    prompt.created_at = Utc::now() - Duration::minutes(10);
+
+    let text = decisions_markdown(&approvals, &[prompt]);
+    assert!(!text.contains("Waiting change"), "{text}");
+    let new = text.find("**applied** approval").expect("applied listed");
+    let refine = text.find("**refine** prompt: Keep going? — \"focus on tests\"");
+    let old = text.find("**rejected** approval").expect("rejected listed");
+    assert!(refine.is_some_and(|r| new < r && r < old), "{text}");
+
+    let many: Vec<_> = (0..30)
+        .map(|i| approval("Change", ApprovalStatus::Approved, i))
+        .collect();
+    let text = decisions_markdown(&many, &[]);
+    assert_eq!(text.matches("\n- ").count(), RECENT_DECISIONS);
+
+    assert!(decisions_markdown(&[], &[]).contains("No operator decisions yet."));
+}
+
+#[test]
+fn plan_shows_task_and_progress() {
+    let mut session = Session::new(
+        "U1".into(),
+        "/ws".into(),
+        Some("Fix the retry loop\nthen add tests".into()),
+        SessionMode::Remote,
+    );
+    let text = plan_markdown(&session);
+    assert!(
+        text.contains("> Fix the retry loop\n> then add tests"),
+        "{text}"
+    );
+    assert!(
+        text.contains("send a `progress_snapshot` with `ping`"),
+        "{text}"
+    );
+
+    session.title = Some("Retry loop".into());
+    session.progress_snapshot = Some(vec![
+        ProgressItem {
+            label: "Reproduce".into(),
+            status: ProgressStatus::Done,
+        },
+        ProgressItem {
+            label: "Patch".into(),
+            status: ProgressStatus::InProgress,
+        },
+    ]);
+    let text = plan_markdown(&session);
+    assert!(text.contains("**Retry loop**"), "{text}");
+    assert!(text.contains("- [x] Reproduce\n- [~] Patch"), "{text}");
+}
+
+#[test]
+fn steering_backlog_lists_queued_messages() {
+    assert!(steering_markdown(&[]).contains("No steering messages are waiting."));
+
+    let normal = SteeringMessage::new(
+        "s1".into(),
+        None,
+        "use the new API".into(),
+        SteeringSource::Ipc,
+    );
+    let interrupt =
+        SteeringMessage::new("s1".into(), None, "stop now".into(), SteeringSource::Slack)
+            .with_priority(SteeringPriority::Interrupt);
+    let text = steering_markdown(&[normal, interrupt]);
+    assert!(
+        text.contains("(operator via local CLI) use the new API"),
+        "{text}"
+    );
+    assert!(
+        text.contains("(operator via Slack) **interrupt** stop now"),
+        "{text}"
+    );
+}
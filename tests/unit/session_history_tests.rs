@@ -0,0 +1,247 @@
+//! Unit tests for the `history` slash command.
+//!
+//! Validates:
+//! - Flags parse into statuses, page size and page number, defaulting to
+//!   ended sessions, 10 per page
+//! - Pages list sessions newest first with a pointer to the next page
+//! - Pages past the end and bad flags are reported
+//! - `--tag` narrows `history` and `sessions`; `session-tag` edits tags
+//! - `sessions` shows the connectivity of live sessions
+
+use std::sync::Arc;
+
+use agent_intercom::config::GlobalConfig;
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::slack::commands::{
+    dispatch_command, parse_session_history_args, SessionHistoryQuery,
+};
+use agent_intercom::state::AppState;
+
+use super::test_helpers::test_app_state;
+
+const USER: &str = "U_TEST";
+
+fn make_config() -> GlobalConfig {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8").replace('\\', "\\\\");
+    let toml = format!(
+        r#"
+default_workspace_root = '{root}'
+http_port = 0
+ipc_name = "test-session-history"
+max_concurrent_sessions = 5
+host_cli = "echo"
+
+[slack]
+channel_id = "C_TEST"
+
+[timeouts]
+approval_seconds = 5
+prompt_seconds = 5
+wait_seconds = 5
+
+[stall]
+enabled = false
+inactivity_threshold_seconds = 300
+escalation_threshold_seconds = 120
+max_retries = 3
+default_nudge_message = "continue"
+"#
+    );
+    let mut config = GlobalConfig::from_toml_str(&toml).expect("valid test config");
+    config.authorized_user_ids = vec![USER.to_owned()];
+    config
+}
+
+async fn app_state() -> Arc<AppState> {
+    test_app_state(make_config()).await
+}
+
+/// Create a session titled `title` that ended with `status`.
+async fn ended_session(state: &AppState, title: &str, status: SessionStatus) -> Session {
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let mut session = Session::new(
+        USER.into(),
+        "/test/workspace".into(),
+        None,
+        SessionMode::Remote,
+    );
+    session.title = Some(title.to_owned());
+    let created = repo.create(&session).await.expect("create session");
+    repo.set_terminated(&created.id, status)
+        .await
+        .expect("end session")
+}
+
+async fn run(state: &Arc<AppState>, args: &[&str]) -> agent_intercom::Result<String> {
+    dispatch_command("history", args, USER, "C_OPS", state).await
+}
+
+#[test]
+fn parse_defaults_to_ended_sessions() {
+    let query = parse_session_history_args(&[]).expect("defaults");
+    assert_eq!(
+        query,
+        SessionHistoryQuery {
+            statuses: vec![SessionStatus::Terminated, SessionStatus::Interrupted],
+            tags: Vec::new(),
+            limit: 10,
+            page: 1,
+        }
+    );
+
+    let query = parse_session_history_args(&["--status", "all", "--limit", "20", "--page", "2"])
+        .expect("flags");
+    assert!(query.statuses.is_empty());
+    assert_eq!((query.limit, query.page), (20, 2));
+}
+
+#[test]
+fn parse_rejects_bad_flags() {
+    for args in [
+        &["--status", "gone"][..],
+        &["--limit", "0"],
+        &["--limit", "51"],
+        &["--page", "0"],
+        &["--page"],
+        &["--tag", "team"],
+        &["terminated"],
+    ] {
+        let err = parse_session_history_args(args).expect_err("invalid flags");
+        let err = err.to_string();
+        assert!(
+            err.contains("usage: history") || err.contains("invalid tag"),
+            "{args:?}: {err}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn pages_list_newest_first_with_next_page_hint() {
+    let state = app_state().await;
+    for title in ["first", "second", "third"] {
+        ended_session(&state, title, SessionStatus::Terminated).await;
+    }
+    ended_session(&state, "crashed", SessionStatus::Interrupted).await;
+
+    let page_one = run(&state, &["--status", "terminated", "--limit", "2"])
+        .await
+        .expect("page 1");
+    assert!(
+        page_one.contains("terminated sessions, page 1 of 2 (3 total)"),
+        "{page_one}"
+    );
+    assert!(page_one.contains("_third_"), "{page_one}");
+    assert!(!page_one.contains("_first_"), "{page_one}");
+    assert!(!page_one.contains("_crashed_"), "{page_one}");
+    assert!(
+        page_one.contains("`/acom history --status terminated --limit 2 --page 2`"),
+        "{page_one}"
+    );
+
+    let page_two = run(
+        &state,
+        &["--status", "terminated", "--limit", "2", "--page", "2"],
+    )
+    .await
+    .expect("page 2");
+    assert!(page_two.contains("_first_"), "{page_two}");
+    assert!(page_two.contains("ended "), "{page_two}");
+    assert!(!page_two.contains("Next page"), "{page_two}");
+
+    let ended = run(&state, &[]).await.expect("default listing");
+    assert!(
+        ended.contains("ended sessions, page 1 of 1 (4 total)"),
+        "{ended}"
+    );
+    assert!(ended.contains("\u{1f480}"), "{ended}");
+}
+
+#[tokio::test]
+async fn empty_and_past_the_end_pages_are_reported() {
+    let state = app_state().await;
+    assert_eq!(run(&state, &[]).await.expect("empty"), "No ended sessions.");
+
+    ended_session(&state, "only", SessionStatus::Terminated).await;
+    let past = run(&state, &["--page", "3"]).await.expect("past the end");
+    assert!(past.contains("Page 3 is past the end"), "{past}");
+}
+
+#[tokio::test]
+async fn tags_filter_history_and_sessions() {
+    let state = app_state().await;
+    let payments = ended_session(&state, "payments", SessionStatus::Terminated).await;
+    ended_session(&state, "search", SessionStatus::Terminated).await;
+
+    let reply = dispatch_command(
+        "session-tag",
+        &[&payments.id[..8], "team=payments", "ticket=PROJ-1"],
+        USER,
+        "C_OPS",
+        &state,
+    )
+    .await
+    .expect("tag");
+    assert!(
+        reply.contains("tags: team=payments ticket=PROJ-1"),
+        "{reply}"
+    );
+
+    let page = run(&state, &["--tag", "team=payments"])
+        .await
+        .expect("tagged history");
+    assert!(
+        page.contains("ended sessions tagged team=payments, page 1 of 1 (1 total)"),
+        "{page}"
+    );
+    assert!(page.contains("_payments_"), "{page}");
+    assert!(page.contains("team=payments ticket=PROJ-1"), "{page}");
+    assert!(!page.contains("_search_"), "{page}");
+    assert_eq!(
+        run(&state, &["--tag", "team=search"])
+            .await
+            .expect("no match"),
+        "No ended sessions tagged team=search."
+    );
+
+    let reply = dispatch_command(
+        "session-tag",
+        &[&payments.id, "ticket="],
+        USER,
+        "C_OPS",
+        &state,
+    )
+    .await
+    .expect("untag");
+    assert!(reply.ends_with("tags: team=payments"), "{reply}");
+
+    let mut active = Session::new(
+        USER.into(),
+        "/test/workspace".into(),
+        None,
+        SessionMode::Remote,
+    );
+    active.status = SessionStatus::Active;
+    active.title = Some("live".into());
+    active.tags.insert("team".into(), "search".into());
+    SessionRepo::new(Arc::clone(&state.db))
+        .create(&active)
+        .await
+        .expect("create active");
+    let listing = dispatch_command("sessions", &["--tag", "team=search"], USER, "C_OPS", &state)
+        .await
+        .expect("sessions");
+    assert!(listing.contains("_live_"), "{listing}");
+    assert!(listing.contains("MCP | \u{1f7e2} online"), "{listing}");
+    let none = dispatch_command(
+        "sessions",
+        &["--tag", "team=payments"],
+        USER,
+        "C_OPS",
+        &state,
+    )
+    .await
+    .expect("sessions");
+    assert_eq!(none, "No active sessions with those tags.");
+}
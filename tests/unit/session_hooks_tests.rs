@@ -0,0 +1,118 @@
+//! Unit tests for session hook subscriptions and event payloads.
+
+use agent_intercom::acp::reader::parse_inbound_line;
+use agent_intercom::driver::acp_driver::AcpDriver;
+use agent_intercom::driver::session_hooks::{
+    SessionHookEvent, SessionHookKind, SessionHooks, EVENT_METHOD,
+};
+use agent_intercom::driver::{AgentDriver, AgentEvent};
+use agent_intercom::models::session::SessionMode;
+use agent_intercom::models::steering::SteeringSource;
+
+#[test]
+fn unsubscribed_session_receives_nothing() {
+    let hooks = SessionHooks::new();
+    assert!(!hooks.is_subscribed("s1", SessionHookKind::SteeringQueued));
+}
+
+#[test]
+fn subscribe_replaces_previous_kinds() {
+    let hooks = SessionHooks::new();
+    hooks.subscribe("s1", [SessionHookKind::SteeringQueued]);
+    hooks.subscribe("s1", [SessionHookKind::ModeChanged]);
+
+    assert!(!hooks.is_subscribed("s1", SessionHookKind::SteeringQueued));
+    assert!(hooks.is_subscribed("s1", SessionHookKind::ModeChanged));
+    assert!(!hooks.is_subscribed("s2", SessionHookKind::ModeChanged));
+}
+
+#[test]
+fn empty_subscription_and_clear_unsubscribe() {
+    let hooks = SessionHooks::new();
+    hooks.subscribe("s1", SessionHookKind::ALL);
+    hooks.subscribe("s1", []);
+    assert!(!hooks.is_subscribed("s1", SessionHookKind::CheckpointCreated));
+
+    hooks.subscribe("s1", SessionHookKind::ALL);
+    hooks.clear("s1");
+    assert!(!hooks.is_subscribed("s1", SessionHookKind::CheckpointCreated));
+}
+
+#[test]
+fn event_params_flatten_payload_with_kind_and_session() {
+    let event = SessionHookEvent::SteeringQueued {
+        message_id: "steer:1".into(),
+        message: "focus on tests".into(),
+        source: SteeringSource::Slack,
+    };
+    assert_eq!(event.kind(), SessionHookKind::SteeringQueued);
+
+    let params = event.params("s1");
+    assert_eq!(params["kind"], "steering_queued");
+    assert_eq!(params["session_id"], "s1");
+    assert_eq!(params["message_id"], "steer:1");
+    assert_eq!(params["message"], "focus on tests");
+    assert_eq!(params["source"], "slack");
+
+    let params = SessionHookEvent::ModeChanged {
+        previous_mode: SessionMode::Remote,
+        current_mode: SessionMode::Hybrid,
+    }
+    .params("s1");
+    assert_eq!(params["kind"], "mode_changed");
+    assert_eq!(params["current_mode"], "hybrid");
+}
+
+#[test]
+fn acp_subscribe_parses_listed_kinds() {
+    let line = r#"{"jsonrpc":"2.0","method":"intercom/subscribe","params":{"events":["checkpoint_created"]}}"#;
+    let event = parse_inbound_line("s1", line)
+        .expect("parse")
+        .expect("event");
+    let AgentEvent::HooksSubscribed { session_id, events } = event else {
+        panic!("expected HooksSubscribed, got {event:?}");
+    };
+    assert_eq!(session_id, "s1");
+    assert_eq!(events, vec![SessionHookKind::CheckpointCreated]);
+}
+
+#[test]
+fn acp_subscribe_without_params_covers_all_kinds() {
+    let line = r#"{"jsonrpc":"2.0","method":"intercom/subscribe"}"#;
+    let event = parse_inbound_line("s1", line)
+        .expect("parse")
+        .expect("event");
+    let AgentEvent::HooksSubscribed { events, .. } = event else {
+        panic!("expected HooksSubscribed, got {event:?}");
+    };
+    assert_eq!(events, SessionHookKind::ALL.to_vec());
+}
+
+#[test]
+fn acp_subscribe_rejects_unknown_kind() {
+    let line = r#"{"method":"intercom/subscribe","params":{"events":["bogus"]}}"#;
+    assert!(parse_inbound_line("s1", line).is_err());
+}
+
+#[tokio::test]
+async fn acp_driver_notify_writes_json_rpc_notification() {
+    let driver = AcpDriver::new();
+    let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+    driver.register_session("s1", tx).await;
+
+    let params = SessionHookEvent::CheckpointCreated {
+        checkpoint_id: "cp-1".into(),
+        label: None,
+    }
+    .params("s1");
+    driver
+        .notify("s1", EVENT_METHOD, params)
+        .await
+        .expect("notify");
+
+    let msg = rx.recv().await.expect("message written");
+    assert_eq!(msg["jsonrpc"], "2.0");
+    assert_eq!(msg["method"], "intercom/event");
+    assert_eq!(msg["params"]["kind"], "checkpoint_created");
+    assert!(msg.get("id").is_none());
+}
@@ -0,0 +1,92 @@
+//! Unit tests for streamed session logs (`orchestrator::session_logs`).
+//!
+//! Tests cover:
+//! - A full buffer drops its oldest line; `tail` returns the newest lines
+//!   oldest first, per session
+//! - `render` formats time, level, message and fields on one line
+//! - `parse_logs_args` reads the session ID and `-n`, rejecting bad input
+
+use agent_intercom::orchestrator::session_logs::{
+    LogLevel, LogLine, SessionLogs, DEFAULT_LOG_TAIL,
+};
+use agent_intercom::slack::commands::parse_logs_args;
+use chrono::{TimeZone, Utc};
+
+fn line(message: &str) -> LogLine {
+    LogLine {
+        at: Utc::now(),
+        level: LogLevel::Info,
+        message: message.to_owned(),
+        fields: serde_json::Map::new(),
+    }
+}
+
+#[test]
+fn full_buffer_drops_oldest_line() {
+    let logs = SessionLogs::new(3);
+    for n in 1..=4 {
+        logs.push("s1", line(&format!("line {n}")));
+    }
+    logs.push("s2", line("other session"));
+
+    let tail: Vec<String> = logs.tail("s1", 10).into_iter().map(|l| l.message).collect();
+    assert_eq!(tail, ["line 2", "line 3", "line 4"]);
+    assert_eq!(logs.buffered("s1"), 3);
+    assert_eq!(logs.buffered("s2"), 1);
+    assert!(logs.tail("missing", 10).is_empty());
+}
+
+#[test]
+fn tail_returns_newest_lines_oldest_first() {
+    let logs = SessionLogs::default();
+    for n in 1..=5 {
+        logs.push("s1", line(&format!("line {n}")));
+    }
+
+    let tail: Vec<String> = logs.tail("s1", 2).into_iter().map(|l| l.message).collect();
+    assert_eq!(tail, ["line 4", "line 5"]);
+}
+
+#[test]
+fn render_includes_level_and_fields() {
+    let mut fields = serde_json::Map::new();
+    fields.insert("attempt".into(), serde_json::json!(2));
+    fields.insert("url".into(), serde_json::json!("https://example.com"));
+    let line = LogLine {
+        at: Utc.with_ymd_and_hms(2026, 1, 2, 12, 4, 31).unwrap(),
+        level: LogLevel::Warn,
+        message: "retrying fetch".into(),
+        fields,
+    };
+
+    assert_eq!(
+        line.render(),
+        "12:04:31 WARN  retrying fetch attempt=2 url=https://example.com"
+    );
+}
+
+#[test]
+fn parse_logs_args_reads_session_and_count() {
+    assert_eq!(
+        parse_logs_args(&["abc123"]).expect("parse"),
+        ("abc123", DEFAULT_LOG_TAIL)
+    );
+    assert_eq!(
+        parse_logs_args(&["abc123", "-n", "50"]).expect("parse"),
+        ("abc123", 50)
+    );
+    assert_eq!(
+        parse_logs_args(&["-n", "5", "abc123"]).expect("parse"),
+        ("abc123", 5)
+    );
+    for bad in [
+        &[][..],
+        &["-n", "5"][..],
+        &["abc123", "-n"][..],
+        &["abc123", "-n", "0"][..],
+        &["abc123", "-n", "5000"][..],
+        &["abc123", "extra"][..],
+    ] {
+        assert!(parse_logs_args(bad).is_err(), "{bad:?} should be rejected");
+    }
+}
@@ -1,7 +1,9 @@
-//! Unit tests for `ProtocolMode` serde serialization (T005) and session title
-//! truncation (T156 / HITL-002 / FR-049).
+//! Unit tests for `ProtocolMode` serde serialization (T005), session title
+//! truncation (T156 / HITL-002 / FR-049), notification mutes and tags.
 
-use agent_intercom::models::session::{truncate_session_title, ProtocolMode};
+use agent_intercom::models::session::{
+    format_tags, parse_tag, truncate_session_title, ProtocolMode, Session, SessionMode,
+};
 
 #[test]
 fn protocol_mode_mcp_serializes_to_snake_case() {
@@ -92,3 +94,64 @@ fn truncate_session_title_empty_prompt() {
     let title = truncate_session_title("");
     assert_eq!(title, "");
 }
+
+/// A mute without an end holds until unmuted; a timed mute lapses.
+#[test]
+fn is_muted_respects_muted_until() {
+    let now = chrono::Utc::now();
+    let mut session = Session::new("U1".into(), "/ws".into(), None, SessionMode::Remote);
+    assert!(!session.is_muted(now));
+
+    session.muted = true;
+    assert!(session.is_muted(now));
+
+    session.muted_until = Some(now + chrono::Duration::minutes(5));
+    assert!(session.is_muted(now));
+    assert!(!session.is_muted(now + chrono::Duration::minutes(5)));
+}
+
+// ── Tags ─────────────────────────────────────────────────────────────────────
+
+#[test]
+fn parse_tag_splits_on_first_equals() {
+    assert_eq!(
+        parse_tag("team=payments").expect("tag"),
+        ("team".to_owned(), "payments".to_owned())
+    );
+    assert_eq!(
+        parse_tag("query=a=b").expect("tag"),
+        ("query".to_owned(), "a=b".to_owned())
+    );
+}
+
+#[test]
+fn parse_tag_rejects_malformed_tags() {
+    let long_key = format!("{}=x", "k".repeat(33));
+    let long_value = format!("k={}", "v".repeat(129));
+    for raw in [
+        "team",
+        "=payments",
+        "team=",
+        "my team=payments",
+        &long_key,
+        &long_value,
+    ] {
+        let err = parse_tag(raw).expect_err("invalid tag");
+        assert!(err.to_string().contains("invalid tag"), "{raw}: {err}");
+    }
+}
+
+#[test]
+fn has_tags_requires_every_filter() {
+    let mut session = Session::new("U1".into(), "/ws".into(), None, SessionMode::Remote);
+    session.tags.insert("team".into(), "payments".into());
+    session.tags.insert("ticket".into(), "PROJ-1".into());
+    assert_eq!(format_tags(&session.tags), "team=payments ticket=PROJ-1");
+
+    assert!(session.has_tags(&[]));
+    assert!(session.has_tags(&[("team".into(), "payments".into())]));
+    assert!(!session.has_tags(&[
+        ("team".into(), "payments".into()),
+        ("ticket".into(), "PROJ-2".into()),
+    ]));
+}
@@ -0,0 +1,211 @@
+//! Unit tests for the `session-move` slash command.
+//!
+//! Validates:
+//! - Moving a session records the new channel, clears the old thread, and
+//!   re-routes the MCP connection bound to it
+//! - The session is taken from the current channel when no ID is given
+//! - Only the owner may move a session; ended sessions, the current channel,
+//!   unresolved `#names` and bad arguments are refused
+
+use std::sync::Arc;
+
+use agent_intercom::config::GlobalConfig;
+use agent_intercom::mcp::handler::IntercomServer;
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::slack::commands::dispatch_command;
+use agent_intercom::state::AppState;
+use agent_intercom::AppError;
+
+use super::test_helpers::test_app_state;
+
+fn make_config(workspace_root: &str, user: &str) -> GlobalConfig {
+    let toml = format!(
+        r#"
+default_workspace_root = '{root}'
+http_port = 0
+ipc_name = "test-session-move"
+max_concurrent_sessions = 5
+host_cli = "echo"
+
+[slack]
+channel_id = "C_TEST"
+
+[timeouts]
+approval_seconds = 5
+prompt_seconds = 5
+wait_seconds = 5
+
+[stall]
+enabled = false
+inactivity_threshold_seconds = 300
+escalation_threshold_seconds = 120
+max_retries = 3
+default_nudge_message = "continue"
+"#,
+        root = workspace_root.replace('\\', "\\\\"),
+    );
+    let mut config = GlobalConfig::from_toml_str(&toml).expect("valid test config");
+    config.authorized_user_ids = vec![user.to_owned()];
+    config
+}
+
+async fn app_state(workspace_root: &str, user: &str) -> Arc<AppState> {
+    test_app_state(make_config(workspace_root, user)).await
+}
+
+async fn active_session(state: &AppState, owner: &str, root: &str) -> Session {
+    let repo = SessionRepo::new(Arc::clone(&state.db));
+    let mut session = Session::new(owner.into(), root.into(), None, SessionMode::Remote);
+    session.channel_id = Some("C_TEST".into());
+    session.thread_ts = Some("1700000000.000100".into());
+    let created = repo.create(&session).await.expect("create session");
+    repo.update_status(&created.id, SessionStatus::Active)
+        .await
+        .expect("activate session")
+}
+
+async fn reload(state: &AppState, id: &str) -> Session {
+    SessionRepo::new(Arc::clone(&state.db))
+        .get_by_id(id)
+        .await
+        .expect("query")
+        .expect("session exists")
+}
+
+#[tokio::test]
+async fn move_rebinds_session_and_mcp_connection() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let user = "U_TEST";
+    let state = app_state(root, user).await;
+    let session = active_session(&state, user, root).await;
+    let server = IntercomServer::with_overrides(
+        Arc::clone(&state),
+        Some("C_TEST".into()),
+        Some(session.id.clone()),
+    );
+    assert_eq!(server.routed_channel_id().as_deref(), Some("C_TEST"));
+
+    let reply = dispatch_command(
+        "session-move",
+        &[&session.id, "<#C_TEAM|team>"],
+        user,
+        "C_TEST",
+        &state,
+    )
+    .await
+    .expect("move response");
+    assert!(reply.contains("moved to <#C_TEAM>"), "{reply}");
+
+    let moved = reload(&state, &session.id).await;
+    assert_eq!(moved.channel_id.as_deref(), Some("C_TEAM"));
+    assert_eq!(moved.thread_ts, None, "without Slack no anchor is posted");
+    assert_eq!(server.routed_channel_id().as_deref(), Some("C_TEAM"));
+    assert_eq!(server.effective_channel_id(), Some("C_TEST"));
+}
+
+#[tokio::test]
+async fn move_defaults_to_the_channel_session() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let user = "U_TEST";
+    let state = app_state(root, user).await;
+    let session = active_session(&state, user, root).await;
+
+    dispatch_command("session-move", &["C_TEAM"], user, "C_TEST", &state)
+        .await
+        .expect("move response");
+
+    assert_eq!(
+        reload(&state, &session.id).await.channel_id.as_deref(),
+        Some("C_TEAM")
+    );
+}
+
+#[tokio::test]
+async fn move_rejects_non_owner_and_ended_sessions() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let user = "U_TEST";
+    let state = app_state(root, user).await;
+
+    let foreign = active_session(&state, "U_OTHER", root).await;
+    let err = dispatch_command(
+        "session-move",
+        &[&foreign.id, "C_TEAM"],
+        user,
+        "C_TEST",
+        &state,
+    )
+    .await
+    .expect_err("non-owner must not move");
+    assert!(matches!(err, AppError::Unauthorized(_)), "{err}");
+
+    let ended = active_session(&state, user, root).await;
+    SessionRepo::new(Arc::clone(&state.db))
+        .set_terminated(&ended.id, SessionStatus::Terminated)
+        .await
+        .expect("terminate");
+    let err = dispatch_command(
+        "session-move",
+        &[&ended.id, "C_TEAM"],
+        user,
+        "C_TEST",
+        &state,
+    )
+    .await
+    .expect_err("ended session");
+    assert!(err.to_string().contains("has ended"), "{err}");
+    assert_eq!(
+        reload(&state, &foreign.id).await.channel_id.as_deref(),
+        Some("C_TEST")
+    );
+}
+
+#[tokio::test]
+async fn move_reports_bad_targets_and_usage() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let user = "U_TEST";
+    let state = app_state(root, user).await;
+    let session = active_session(&state, user, root).await;
+
+    let err = dispatch_command(
+        "session-move",
+        &[&session.id, "C_TEST"],
+        user,
+        "C_TEST",
+        &state,
+    )
+    .await
+    .expect_err("same channel");
+    assert!(err.to_string().contains("already posts to"), "{err}");
+
+    let err = dispatch_command(
+        "session-move",
+        &[&session.id, "#team"],
+        user,
+        "C_TEST",
+        &state,
+    )
+    .await
+    .expect_err("plain channel name");
+    assert!(err.to_string().contains("cannot resolve"), "{err}");
+
+    let err = dispatch_command("session-move", &[], user, "C_TEST", &state)
+        .await
+        .expect_err("missing channel");
+    assert!(err.to_string().contains("usage: session-move"), "{err}");
+    assert!(state.session_channels.get(&session.id).is_none());
+}
+
+#[tokio::test]
+async fn rebind_channel_reports_unknown_session() {
+    let state = app_state(".", "U_TEST").await;
+    let err = SessionRepo::new(Arc::clone(&state.db))
+        .rebind_channel("missing", "C_TEAM", None)
+        .await
+        .expect_err("unknown session");
+    assert!(matches!(err, AppError::NotFound(_)), "{err}");
+}
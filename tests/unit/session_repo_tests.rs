@@ -3,6 +3,56 @@ use std::sync::Arc;
 use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
 use agent_intercom::persistence::{db, session_repo::SessionRepo};
 
+/// The recorded agent environment survives a round trip.
+#[tokio::test]
+async fn env_round_trips() {
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let repo = SessionRepo::new(db);
+
+    let mut session = Session::new(
+        "U123".into(),
+        "/test/workspace".into(),
+        None,
+        SessionMode::Remote,
+    );
+    session.env.insert("NODE_ENV".into(), "staging".into());
+    session.env.insert("NPM_TOKEN".into(), "[redacted]".into());
+    repo.create(&session).await.expect("create");
+
+    let fetched = repo
+        .get_by_id(&session.id)
+        .await
+        .expect("fetch")
+        .expect("exists");
+    assert_eq!(fetched.env, session.env);
+}
+
+/// The time-box length survives a round trip and outlives a cleared deadline.
+#[tokio::test]
+async fn max_duration_survives_cleared_deadline() {
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let repo = SessionRepo::new(db);
+
+    let mut session = Session::new(
+        "U123".into(),
+        "/test/workspace".into(),
+        None,
+        SessionMode::Remote,
+    );
+    session.deadline = Some(session.created_at + chrono::Duration::minutes(30));
+    session.max_duration_seconds = Some(1800);
+    repo.create(&session).await.expect("create");
+    repo.clear_deadline(&session.id).await.expect("clear");
+
+    let fetched = repo
+        .get_by_id(&session.id)
+        .await
+        .expect("fetch")
+        .expect("exists");
+    assert_eq!(fetched.deadline, None);
+    assert_eq!(fetched.max_duration_seconds, Some(1800));
+}
+
 /// T005: In-memory `connect_memory()` creates pool with all 5 tables.
 #[tokio::test]
 async fn in_memory_connect_creates_five_tables() {
@@ -160,3 +210,276 @@ async fn invalid_transition_rejected() {
     let result = repo.update_status(&created.id, SessionStatus::Paused).await;
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn set_muted_round_trips_and_unmute_clears_until() {
+    let db = db::connect_memory().await.expect("db");
+    let repo = SessionRepo::new(Arc::new(db));
+
+    let session = Session::new("U1".into(), "/ws".into(), None, SessionMode::Remote);
+    let created = repo.create(&session).await.expect("create");
+    assert!(!repo.is_muted(&created.id).await);
+
+    let until = chrono::Utc::now() + chrono::Duration::hours(1);
+    repo.set_muted(&created.id, true, Some(until))
+        .await
+        .expect("mute");
+    let fetched = repo
+        .get_by_id(&created.id)
+        .await
+        .expect("query")
+        .expect("exists");
+    assert!(fetched.muted);
+    assert_eq!(
+        fetched.muted_until.map(|dt| dt.timestamp()),
+        Some(until.timestamp())
+    );
+    assert!(repo.is_muted(&created.id).await);
+
+    repo.set_muted(&created.id, false, Some(until))
+        .await
+        .expect("unmute");
+    let fetched = repo
+        .get_by_id(&created.id)
+        .await
+        .expect("query")
+        .expect("exists");
+    assert!(!fetched.muted);
+    assert_eq!(fetched.muted_until, None);
+    assert!(!repo.is_muted("missing-session").await);
+}
+
+#[tokio::test]
+async fn list_page_pages_sessions_by_status_newest_first() {
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let repo = SessionRepo::new(Arc::clone(&db));
+
+    let mut ids = Vec::new();
+    for (minute, status) in [
+        (1, SessionStatus::Terminated),
+        (2, SessionStatus::Interrupted),
+        (3, SessionStatus::Active),
+        (4, SessionStatus::Terminated),
+        (5, SessionStatus::Terminated),
+    ] {
+        let session = Session::new(
+            "U123".into(),
+            "/test/workspace".into(),
+            None,
+            SessionMode::Remote,
+        );
+        let created = repo.create(&session).await.expect("create session");
+        sqlx::query("UPDATE session SET status = ?1, updated_at = ?2 WHERE id = ?3")
+            .bind(status.as_str())
+            .bind(format!("2026-10-01T12:0{minute}:00+00:00"))
+            .bind(&created.id)
+            .execute(db.as_ref())
+            .await
+            .expect("backdate session");
+        ids.push(created.id);
+    }
+
+    let terminated = [SessionStatus::Terminated];
+    assert_eq!(
+        repo.count_by_status(&terminated, &[]).await.expect("count"),
+        3
+    );
+    assert_eq!(repo.count_by_status(&[], &[]).await.expect("count all"), 5);
+
+    let first = repo
+        .list_page(&terminated, &[], 2, 0)
+        .await
+        .expect("page 1");
+    let first: Vec<&str> = first.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(first, [ids[4].as_str(), ids[3].as_str()]);
+    let second = repo
+        .list_page(&terminated, &[], 2, 2)
+        .await
+        .expect("page 2");
+    assert_eq!(second.len(), 1);
+    assert_eq!(second[0].id, ids[0]);
+
+    let ended = repo
+        .list_page(
+            &[SessionStatus::Terminated, SessionStatus::Interrupted],
+            &[],
+            10,
+            0,
+        )
+        .await
+        .expect("ended");
+    assert_eq!(ended.len(), 4);
+    assert!(ended.iter().all(|s| s.status != SessionStatus::Active));
+}
+
+#[tokio::test]
+async fn tags_round_trip_and_filter_pages() {
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let repo = SessionRepo::new(Arc::clone(&db));
+
+    let mut payments = Session::new(
+        "U123".into(),
+        "/test/workspace".into(),
+        None,
+        SessionMode::Remote,
+    );
+    payments.tags.insert("team".into(), "payments".into());
+    payments.tags.insert("ticket".into(), "PROJ-1".into());
+    repo.create(&payments).await.expect("create tagged");
+    let untagged = Session::new(
+        "U123".into(),
+        "/test/workspace".into(),
+        None,
+        SessionMode::Remote,
+    );
+    let untagged = repo.create(&untagged).await.expect("create untagged");
+
+    let fetched = repo
+        .get_by_id(&payments.id)
+        .await
+        .expect("fetch")
+        .expect("exists");
+    assert_eq!(fetched.tags, payments.tags);
+    assert!(fetched.env.is_empty());
+
+    let team = [("team".to_owned(), "payments".to_owned())];
+    assert_eq!(repo.count_by_status(&[], &team).await.expect("count"), 1);
+    let page = repo.list_page(&[], &team, 10, 0).await.expect("page");
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].id, payments.id);
+
+    let both = [
+        ("team".to_owned(), "payments".to_owned()),
+        ("ticket".to_owned(), "PROJ-2".to_owned()),
+    ];
+    assert_eq!(repo.count_by_status(&[], &both).await.expect("count"), 0);
+
+    let mut tags = fetched.tags.clone();
+    tags.insert("team".into(), "search".into());
+    repo.set_tags(&untagged.id, &tags).await.expect("set tags");
+    assert_eq!(repo.count_by_status(&[], &team).await.expect("count"), 1);
+    let search = [("team".to_owned(), "search".to_owned())];
+    assert_eq!(repo.count_by_status(&[], &search).await.expect("count"), 1);
+
+    repo.set_tags(&untagged.id, &std::collections::BTreeMap::new())
+        .await
+        .expect("clear tags");
+    let cleared = repo
+        .get_by_id(&untagged.id)
+        .await
+        .expect("fetch")
+        .expect("exists");
+    assert!(cleared.tags.is_empty());
+    assert!(matches!(
+        repo.set_tags("no-such-session", &tags).await,
+        Err(agent_intercom::AppError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn short_id_is_generated_and_resolves_like_a_prefix() {
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let repo = SessionRepo::new(Arc::clone(&db));
+
+    let session = Session::new(
+        "U123".into(),
+        "/test/workspace".into(),
+        None,
+        SessionMode::Remote,
+    );
+    let created = repo.create(&session).await.expect("create");
+    assert!(created.short_id.starts_with("ses-"), "{}", created.short_id);
+    assert_eq!(created.short_id.len(), "ses-".len() + 4);
+
+    let typed = format!("  {} ", created.short_id.to_uppercase());
+    let by_short = repo
+        .get_by_prefix(&typed)
+        .await
+        .expect("lookup")
+        .expect("found by short id");
+    assert_eq!(by_short.id, created.id);
+    assert_eq!(by_short.short_id, created.short_id);
+
+    let by_uuid = repo
+        .get_by_prefix(&created.id[..8])
+        .await
+        .expect("lookup")
+        .expect("found by uuid prefix");
+    assert_eq!(by_uuid.id, created.id);
+    assert!(repo
+        .get_by_prefix("ses-zzzzzzzz")
+        .await
+        .expect("lookup")
+        .is_none());
+
+    let partial = &created.short_id[..created.short_id.len() - 1];
+    let by_partial = repo
+        .get_by_prefix(partial)
+        .await
+        .expect("lookup")
+        .expect("found by short id prefix");
+    assert_eq!(by_partial.id, created.id);
+
+    let other = repo
+        .create(&Session::new(
+            "U123".into(),
+            "/test/workspace".into(),
+            None,
+            SessionMode::Remote,
+        ))
+        .await
+        .expect("create other");
+    let err = repo.get_by_prefix("SES-").await.expect_err("ambiguous");
+    let message = err.to_string();
+    assert!(message.contains("ambiguous session prefix 'ses-' matches 2 sessions"));
+    assert!(message.contains(&created.short_id), "{message}");
+    assert!(message.contains(&other.short_id), "{message}");
+}
+
+#[tokio::test]
+async fn soft_deleted_sessions_are_hidden_until_restored() {
+    let db = Arc::new(db::connect_memory().await.expect("db connect"));
+    let repo = SessionRepo::new(Arc::clone(&db));
+
+    let mut session = Session::new(
+        "U123".into(),
+        "/test/workspace".into(),
+        None,
+        SessionMode::Remote,
+    );
+    session.channel_id = Some("C_TEST".into());
+    let created = repo.create(&session).await.expect("create session");
+    repo.set_terminated(&created.id, SessionStatus::Interrupted)
+        .await
+        .expect("interrupt session");
+
+    repo.set_deleted(&created.id, true).await.expect("delete");
+    let hidden = repo
+        .get_by_id(&created.id)
+        .await
+        .expect("fetch")
+        .expect("row kept");
+    assert!(hidden.deleted_at.is_some());
+    assert!(repo.list_interrupted().await.expect("list").is_empty());
+    assert!(repo
+        .get_most_recent_interrupted()
+        .await
+        .expect("recent")
+        .is_none());
+    assert!(repo
+        .list_all_by_channel("C_TEST")
+        .await
+        .expect("by channel")
+        .is_empty());
+    assert_eq!(repo.count_by_status(&[], &[]).await.expect("count"), 0);
+
+    repo.set_deleted(&created.id, false).await.expect("restore");
+    assert_eq!(repo.list_interrupted().await.expect("list").len(), 1);
+    assert_eq!(repo.count_by_status(&[], &[]).await.expect("count"), 1);
+
+    let missing = repo.set_deleted("no-such-session", true).await;
+    assert!(matches!(
+        missing,
+        Err(agent_intercom::AppError::NotFound(_))
+    ));
+}
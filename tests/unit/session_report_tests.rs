@@ -0,0 +1,214 @@
+//! Unit tests for Markdown session reports (`orchestrator::session_report`).
+//!
+//! Tests cover:
+//! - Loading a session's approvals, prompts, steering and notes by ID or prefix
+//! - Chronological transcript (including relay messages and operator notes)
+//!   and decisions table
+//! - Applied diffs fenced safely, rejected diffs left out
+
+use std::sync::Arc;
+
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::models::prompt::{ContinuationPrompt, PromptDecision, PromptType};
+use agent_intercom::models::relay::RelayMessage;
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::models::session_note::SessionNote;
+use agent_intercom::models::steering::{SteeringMessage, SteeringSource};
+use agent_intercom::orchestrator::session_report::SessionReport;
+use agent_intercom::persistence::approval_repo::ApprovalRepo;
+use agent_intercom::persistence::db;
+use agent_intercom::persistence::prompt_repo::PromptRepo;
+use agent_intercom::persistence::relay_repo::RelayRepo;
+use agent_intercom::persistence::session_note_repo::SessionNoteRepo;
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::persistence::steering_repo::SteeringRepo;
+use agent_intercom::AppError;
+
+async fn seeded_db() -> (Arc<db::Database>, Session) {
+    let database = Arc::new(db::connect_memory().await.expect("in-memory db"));
+    let sessions = SessionRepo::new(Arc::clone(&database));
+    let mut session = Session::new(
+        "U_OWNER".into(),
+        "/workspace".into(),
+        Some("Fix the retry loop".into()),
+        SessionMode::Remote,
+    );
+    session.status = SessionStatus::Active;
+    session.title = Some("Fix the retry loop".into());
+    session.tags.insert("team".into(), "payments".into());
+    let session = sessions.create(&session).await.expect("create session");
+
+    let approvals = ApprovalRepo::new(Arc::clone(&database));
+    let applied = ApprovalRequest::new(
+        session.id.clone(),
+        "Bound retries".into(),
+        None,
+        "--- a/src/retry.rs\n+++ b/src/retry.rs\n@@ -1 +1 @@\n-loop {}\n+for _ in 0..3 {}\n// ```\n"
+            .into(),
+        "src/retry.rs".into(),
+        RiskLevel::Low,
+        "hash".into(),
+    );
+    approvals.create(&applied).await.expect("create approval");
+    approvals
+        .update_status(&applied.id, ApprovalStatus::Consumed)
+        .await
+        .expect("consume");
+    let rejected = ApprovalRequest::new(
+        session.id.clone(),
+        "Delete tests".into(),
+        None,
+        "-#[test]\n".into(),
+        "tests/retry.rs".into(),
+        RiskLevel::High,
+        "hash".into(),
+    );
+    approvals.create(&rejected).await.expect("create approval");
+    approvals
+        .update_status(&rejected.id, ApprovalStatus::Rejected)
+        .await
+        .expect("reject");
+
+    let prompts = PromptRepo::new(Arc::clone(&database));
+    let prompt = ContinuationPrompt::new(
+        session.id.clone(),
+        "Tests pass.\nContinue?".into(),
+        PromptType::Continuation,
+        None,
+        None,
+    );
+    prompts.create(&prompt).await.expect("create prompt");
+    prompts
+        .update_decision(
+            &prompt.id,
+            PromptDecision::Refine,
+            Some("also add a test".into()),
+        )
+        .await
+        .expect("decide");
+
+    let steering = SteeringRepo::new(Arc::clone(&database));
+    steering
+        .insert(&SteeringMessage::new(
+            session.id.clone(),
+            None,
+            "use exponential backoff".into(),
+            SteeringSource::Ipc,
+        ))
+        .await
+        .expect("insert steering");
+
+    RelayRepo::new(Arc::clone(&database))
+        .insert(&RelayMessage::new(
+            session.id.clone(),
+            "consumer-session".into(),
+            Some("build".into()),
+            serde_json::json!({ "ok": true }),
+        ))
+        .await
+        .expect("insert relay");
+
+    SessionNoteRepo::new(Arc::clone(&database))
+        .create(&SessionNote::new(
+            session.id.clone(),
+            "U_LEAD".into(),
+            "paused for the\nprod incident".into(),
+        ))
+        .await
+        .expect("create note");
+
+    let session = sessions
+        .set_terminated(&session.id, SessionStatus::Terminated)
+        .await
+        .expect("terminate");
+    (database, session)
+}
+
+#[tokio::test]
+async fn report_loads_by_prefix_and_renders_sections() {
+    let (database, session) = seeded_db().await;
+    let prefix: String = session.id.chars().take(8).collect();
+    let report = SessionReport::load(&database, &prefix)
+        .await
+        .expect("report loads");
+    assert_eq!(report.session.id, session.id);
+    assert_eq!(report.approvals.len(), 2);
+    assert_eq!(report.prompts.len(), 1);
+    assert_eq!(report.steering.len(), 1);
+    assert_eq!(report.relays.len(), 1);
+    assert_eq!(report.notes.len(), 1);
+
+    let md = report.to_markdown();
+    assert!(
+        md.starts_with("# Session report: Fix the retry loop\n"),
+        "{md}"
+    );
+    assert!(md.contains("| Final status | terminated |"), "{md}");
+    assert!(md.contains("| Tags | `team=payments` |"), "{md}");
+    assert!(md.contains("> Fix the retry loop"), "{md}");
+    for section in ["## Transcript", "## Decisions", "## Diffs applied"] {
+        assert!(md.contains(section), "missing {section}: {md}");
+    }
+    assert!(
+        md.contains("Agent asked (continuation): Tests pass. Continue? — operator chose **refine**: also add a test"),
+        "{md}"
+    );
+    assert!(
+        md.contains("Operator steered via local CLI: use exponential backoff (not delivered)"),
+        "{md}"
+    );
+    assert!(
+        md.contains(
+            "Agent relayed a message [build] to session `consumer-session` (not delivered)"
+        ),
+        "{md}"
+    );
+    assert!(
+        md.contains("Operator U_LEAD noted: paused for the prod incident"),
+        "{md}"
+    );
+    assert!(
+        md.contains("| Approval: Delete tests (`tests/retry.rs`) | rejected |"),
+        "{md}"
+    );
+}
+
+#[tokio::test]
+async fn report_transcript_is_chronological() {
+    let (database, session) = seeded_db().await;
+    let md = SessionReport::load(&database, &session.id)
+        .await
+        .expect("report loads")
+        .to_markdown();
+    let started = md.find("Session started").expect("start entry");
+    let ended = md.find("Session ended (terminated)").expect("end entry");
+    assert!(started < ended, "{md}");
+}
+
+#[tokio::test]
+async fn report_includes_only_applied_diffs_with_safe_fence() {
+    let (database, session) = seeded_db().await;
+    let md = SessionReport::load(&database, &session.id)
+        .await
+        .expect("report loads")
+        .to_markdown();
+    let diffs = &md[md.find("## Diffs applied").expect("diff section")..];
+    assert!(
+        diffs.contains("### `src/retry.rs` — Bound retries"),
+        "{diffs}"
+    );
+    assert!(
+        diffs.contains("````diff\n"),
+        "fence must be longer than the backticks inside the diff: {diffs}"
+    );
+    assert!(!diffs.contains("tests/retry.rs"), "{diffs}");
+}
+
+#[tokio::test]
+async fn report_for_unknown_session_is_not_found() {
+    let database = Arc::new(db::connect_memory().await.expect("in-memory db"));
+    let err = SessionReport::load(&database, "missing")
+        .await
+        .expect_err("no session");
+    assert!(matches!(err, AppError::NotFound(_)), "{err}");
+}
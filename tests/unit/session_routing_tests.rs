@@ -10,13 +10,14 @@
 //! - S046 (`find_by_channel_and_thread` disambiguates multiple sessions)
 //! - S047 (non-existent `thread_ts` → None)
 //! - S076 / FR-031 (non-owner action rejected by `check_session_ownership`)
+//! - CODEOWNERS routing (`check_approval_authority`)
 
 use std::sync::Arc;
 
 use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
 use agent_intercom::persistence::{db, session_repo::SessionRepo};
 use agent_intercom::slack::blocks;
-use agent_intercom::slack::handlers::check_session_ownership;
+use agent_intercom::slack::handlers::{check_approval_authority, check_session_ownership};
 use agent_intercom::AppError;
 use slack_morphism::prelude::{SlackChannelId, SlackTs};
 
@@ -177,11 +178,11 @@ fn session_started_blocks_contains_expected_content() {
     );
     // Serialize blocks to JSON to assert on human-readable content.
     let json = serde_json::to_string(&rendered_blocks).expect("serialize blocks");
-    // The first 8 chars of the session ID should appear somewhere.
-    let id_prefix: String = session.id.chars().take(8).collect();
+    // The session's short ID should appear somewhere.
     assert!(
-        json.contains(&id_prefix),
-        "session_started_blocks must include session ID prefix: {id_prefix}"
+        json.contains(&session.short_id),
+        "session_started_blocks must include session short ID: {}",
+        session.short_id
     );
 }
 
@@ -409,3 +410,24 @@ fn empty_owner_skips_ownership_check() {
         "empty owner_user_id must skip the ownership check"
     );
 }
+
+/// With required code owners, only they may decide — not the session owner.
+#[test]
+fn approval_authority_defers_to_required_code_owners() {
+    let session = Session::new(
+        "U_OWNER".into(),
+        "/workspace/owned".into(),
+        None,
+        SessionMode::Remote,
+    );
+    let required = ["U_ALICE".to_owned()];
+
+    assert!(check_approval_authority(&session, &required, "U_ALICE").is_ok());
+    assert!(matches!(
+        check_approval_authority(&session, &required, "U_OWNER"),
+        Err(AppError::Unauthorized(_))
+    ));
+    // No required owners: plain session-ownership check.
+    assert!(check_approval_authority(&session, &[], "U_OWNER").is_ok());
+    assert!(check_approval_authority(&session, &[], "U_ALICE").is_err());
+}
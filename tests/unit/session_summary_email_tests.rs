@@ -0,0 +1,167 @@
+//! Unit tests for emailed session summaries (`integrations::email`).
+//!
+//! Tests cover:
+//! - Markdown and HTML rendering of prompt, duration, approvals and files
+//! - HTML escaping of agent-supplied text
+//! - Recipient selection (owner mapping, `cc` fallback)
+//! - Approval link emails go to the owner only
+
+use agent_intercom::config::SmtpConfig;
+use agent_intercom::integrations::email::{
+    build_approval_link_message, build_message, ApprovalLinkNotice, SessionSummary,
+};
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+
+fn approval(title: &str, file: &str, status: ApprovalStatus) -> ApprovalRequest {
+    let mut request = ApprovalRequest::new(
+        "s1".into(),
+        title.into(),
+        None,
+        "+x\n".into(),
+        file.into(),
+        RiskLevel::Low,
+        "new_file".into(),
+    );
+    request.status = status;
+    request
+}
+
+fn summary() -> SessionSummary {
+    let mut session = Session::new(
+        "U_ALICE".into(),
+        "/workspace".into(),
+        Some("Fix <the> flaky test".into()),
+        SessionMode::Remote,
+    );
+    session.title = Some("Fix the flaky test".into());
+    session.status = SessionStatus::Terminated;
+    session.terminated_at = Some(session.created_at + chrono::Duration::minutes(90));
+    SessionSummary {
+        session,
+        reason: "stopped by operator".into(),
+        approvals: vec![
+            approval("Fix retry", "src/retry.rs", ApprovalStatus::Consumed),
+            approval("Drop test", "tests/flaky.rs", ApprovalStatus::Rejected),
+            approval("Tweak retry", "src/retry.rs", ApprovalStatus::Approved),
+        ],
+    }
+}
+
+fn smtp_config() -> SmtpConfig {
+    SmtpConfig {
+        host: Some("smtp.acme.test".into()),
+        from: Some("intercom@acme.test".into()),
+        recipients: [("U_ALICE".to_owned(), "alice@acme.test".to_owned())]
+            .into_iter()
+            .collect(),
+        ..SmtpConfig::default()
+    }
+}
+
+#[test]
+fn markdown_summary_lists_facts_approvals_and_files() {
+    let md = summary().to_markdown();
+    assert!(md.contains("**Final status:** terminated"), "{md}");
+    assert!(md.contains("**Duration:** 1h 30m"), "{md}");
+    assert!(
+        md.contains("**Approvals:** 3 requested, 2 approved, 1 rejected"),
+        "{md}"
+    );
+    assert!(md.contains("> Fix <the> flaky test"), "{md}");
+    assert!(
+        md.contains("- rejected — Drop test (`tests/flaky.rs`)"),
+        "{md}"
+    );
+    assert_eq!(md.matches("- `src/retry.rs`").count(), 1, "{md}");
+    assert!(
+        !md.contains("- `tests/flaky.rs`"),
+        "rejected files were not changed"
+    );
+}
+
+#[test]
+fn html_summary_escapes_agent_text() {
+    let html = summary().to_html();
+    assert!(html.contains("Fix &lt;the&gt; flaky test"), "{html}");
+    assert!(!html.contains("<the>"), "{html}");
+    assert!(html.contains("<code>src/retry.rs</code>"), "{html}");
+}
+
+#[test]
+fn summary_without_approvals_says_so() {
+    let mut summary = summary();
+    summary.approvals.clear();
+    let md = summary.to_markdown();
+    assert!(md.contains("No approval requests."), "{md}");
+    assert!(md.contains("## Files changed\n\nNone."), "{md}");
+}
+
+#[test]
+fn message_goes_to_owner_and_copies_cc() {
+    let mut config = smtp_config();
+    config.cc = vec!["archive@acme.test".into()];
+    let message = build_message(&config, &summary())
+        .expect("valid config")
+        .expect("has recipients");
+    let headers = String::from_utf8_lossy(&message.formatted()).to_string();
+    assert!(headers.contains("To: alice@acme.test"), "{headers}");
+    assert!(headers.contains("Cc: archive@acme.test"), "{headers}");
+    assert!(headers.contains("Subject: Session"), "{headers}");
+}
+
+#[test]
+fn unmapped_owner_falls_back_to_cc_or_skips() {
+    let mut config = smtp_config();
+    config.recipients.clear();
+    assert!(build_message(&config, &summary())
+        .expect("valid config")
+        .is_none());
+
+    config.cc = vec!["archive@acme.test".into()];
+    let message = build_message(&config, &summary())
+        .expect("valid config")
+        .expect("cc recipient");
+    let headers = String::from_utf8_lossy(&message.formatted()).to_string();
+    assert!(headers.contains("To: archive@acme.test"), "{headers}");
+}
+
+#[test]
+fn invalid_recipient_is_a_config_error() {
+    let mut config = smtp_config();
+    config
+        .recipients
+        .insert("U_ALICE".into(), "not an address".into());
+    let err = build_message(&config, &summary()).expect_err("invalid address");
+    assert!(err.to_string().contains("recipients"), "{err}");
+}
+
+#[test]
+fn approval_link_email_goes_to_owner_only() {
+    let mut config = smtp_config();
+    config.cc = vec!["archive@acme.test".into()];
+    let mut notice = ApprovalLinkNotice {
+        owner_id: "U_ALICE".into(),
+        title: "Drop <table>".into(),
+        file_path: "db/schema.sql".into(),
+        risk_level: "critical".into(),
+        url: "https://intercom.acme.test/approvals/a1?exp=1&sig=ab".into(),
+        expires_in: "15m 0s".into(),
+    };
+    let message = build_approval_link_message(&config, &notice)
+        .expect("valid config")
+        .expect("owner mapped");
+    let formatted = String::from_utf8_lossy(&message.formatted()).to_string();
+    assert!(formatted.contains("To: alice@acme.test"), "{formatted}");
+    assert!(!formatted.contains("archive@acme.test"), "{formatted}");
+    assert!(
+        formatted.contains("Subject: Approval needed"),
+        "{formatted}"
+    );
+    assert!(formatted.contains("Drop &lt;table&gt;"), "{formatted}");
+
+    notice.owner_id = "U_BOB".into();
+    assert!(build_approval_link_message(&config, &notice)
+        .expect("valid config")
+        .is_none());
+}
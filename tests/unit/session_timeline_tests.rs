@@ -0,0 +1,214 @@
+//! Unit tests for JSON session timelines (`orchestrator::session_timeline`).
+//!
+//! Tests cover:
+//! - Loading tool calls and decisions from the audit log alongside
+//!   approvals, prompts, steering, stalls and notes from the repositories
+//! - Chronological ordering and the `kind`-tagged JSON shape
+//! - Other sessions' audit entries are left out
+
+use std::path::Path;
+use std::sync::Arc;
+
+use agent_intercom::audit::{self, AuditEntry, AuditEventType, AuditLogger, JsonlAuditWriter};
+use agent_intercom::models::approval::{ApprovalRequest, ApprovalStatus, RiskLevel};
+use agent_intercom::models::prompt::{ContinuationPrompt, PromptType};
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::models::session_note::SessionNote;
+use agent_intercom::models::stall::StallAlert;
+use agent_intercom::models::steering::{SteeringMessage, SteeringSource};
+use agent_intercom::orchestrator::session_timeline::{SessionTimeline, TimelineEvent};
+use agent_intercom::persistence::approval_repo::ApprovalRepo;
+use agent_intercom::persistence::db;
+use agent_intercom::persistence::prompt_repo::PromptRepo;
+use agent_intercom::persistence::session_note_repo::SessionNoteRepo;
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::persistence::stall_repo::StallAlertRepo;
+use agent_intercom::persistence::steering_repo::SteeringRepo;
+use chrono::{Duration, Utc};
+
+fn tool_call(session_id: &str, tool: &str) -> AuditEntry {
+    AuditEntry::new(AuditEventType::ToolCall)
+        .with_session(session_id.to_owned())
+        .with_tool(tool.to_owned())
+        .with_result("ok".into())
+}
+
+/// Seed one of each repository record, minutes apart, plus audit entries.
+async fn seeded(log_dir: &Path) -> (Arc<db::Database>, Session) {
+    let database = Arc::new(db::connect_memory().await.expect("in-memory db"));
+
+    let mut session = Session::new(
+        "U_OWNER".into(),
+        "/workspace".into(),
+        Some("Fix the retry loop".into()),
+        SessionMode::Remote,
+    );
+    session.created_at = Utc::now() - Duration::minutes(10);
+    session.status = SessionStatus::Active;
+    let session = SessionRepo::new(Arc::clone(&database))
+        .create(&session)
+        .await
+        .expect("create session");
+    let at = |minutes: i64| session.created_at + Duration::minutes(minutes);
+
+    let mut approval = ApprovalRequest::new(
+        session.id.clone(),
+        "Bound retries".into(),
+        None,
+        "+for _ in 0..3 {}".into(),
+        "src/retry.rs".into(),
+        RiskLevel::Low,
+        "hash".into(),
+    );
+    approval.created_at = at(2);
+    let approvals = ApprovalRepo::new(Arc::clone(&database));
+    approvals.create(&approval).await.expect("create approval");
+    approvals
+        .update_status(&approval.id, ApprovalStatus::Approved)
+        .await
+        .expect("approve");
+
+    let mut prompt = ContinuationPrompt::new(
+        session.id.clone(),
+        "Continue?".into(),
+        PromptType::Continuation,
+        None,
+        None,
+    );
+    prompt.created_at = at(4);
+    PromptRepo::new(Arc::clone(&database))
+        .create(&prompt)
+        .await
+        .expect("create prompt");
+
+    let mut steering = SteeringMessage::new(
+        session.id.clone(),
+        None,
+        "use backoff".into(),
+        SteeringSource::Slack,
+    );
+    steering.created_at = at(5);
+    SteeringRepo::new(Arc::clone(&database))
+        .insert(&steering)
+        .await
+        .expect("insert steering");
+
+    let mut stall = StallAlert::new(
+        session.id.clone(),
+        Some("heartbeat".into()),
+        at(5),
+        300,
+        None,
+    );
+    stall.created_at = at(6);
+    StallAlertRepo::new(Arc::clone(&database))
+        .create(&stall)
+        .await
+        .expect("create stall");
+
+    let mut note = SessionNote::new(
+        session.id.clone(),
+        "U_LEAD".into(),
+        "paused for the prod incident".into(),
+    );
+    note.created_at = at(7);
+    SessionNoteRepo::new(Arc::clone(&database))
+        .create(&note)
+        .await
+        .expect("create note");
+
+    let writer = JsonlAuditWriter::new(log_dir.to_path_buf()).expect("writer");
+    writer
+        .log_entry(tool_call(&session.id, "ask_approval"))
+        .expect("log");
+    writer
+        .log_entry(tool_call("other-session", "heartbeat"))
+        .expect("log");
+    writer
+        .log_entry(
+            AuditEntry::new(AuditEventType::Approval)
+                .with_request_id(approval.id.clone())
+                .with_operator("U_OWNER".into()),
+        )
+        .expect("log");
+    (database, session)
+}
+
+#[tokio::test]
+async fn timeline_merges_audit_and_repositories_in_order() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let log_dir = audit::log_dir(temp.path());
+    let (database, session) = seeded(&log_dir).await;
+
+    let prefix: String = session.id.chars().take(8).collect();
+    let timeline = SessionTimeline::load(&database, &log_dir, &prefix)
+        .await
+        .expect("timeline loads");
+
+    assert_eq!(timeline.session_id, session.id);
+    let kinds: Vec<&str> = timeline
+        .events
+        .iter()
+        .map(|entry| match entry.event {
+            TimelineEvent::SessionStarted { .. } => "session_started",
+            TimelineEvent::ApprovalRequested { .. } => "approval_requested",
+            TimelineEvent::PromptForwarded { .. } => "prompt_forwarded",
+            TimelineEvent::Steering { .. } => "steering",
+            TimelineEvent::Stall { .. } => "stall",
+            TimelineEvent::Note { .. } => "note",
+            TimelineEvent::ToolCall { .. } => "tool_call",
+            TimelineEvent::ApprovalDecided { .. } => "approval_decided",
+            TimelineEvent::SessionEnded { .. } => "session_ended",
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            "session_started",
+            "approval_requested",
+            "prompt_forwarded",
+            "steering",
+            "stall",
+            "note",
+            "tool_call",
+            "approval_decided",
+        ]
+    );
+    assert!(timeline.events.windows(2).all(|w| w[0].at <= w[1].at));
+
+    let json = serde_json::to_value(&timeline).expect("serialize");
+    assert_eq!(json["status"], "active");
+    let events = json["events"].as_array().expect("events array");
+    assert_eq!(events[1]["kind"], "approval_requested");
+    assert_eq!(events[1]["status"], "approved");
+    assert_eq!(events[4]["last_tool"], "heartbeat");
+    assert_eq!(events[5]["author"], "U_LEAD");
+    assert_eq!(events[5]["text"], "paused for the prod incident");
+    assert_eq!(events[6]["tool"], "ask_approval");
+    assert_eq!(events[7]["operator"], "U_OWNER");
+    assert_eq!(events[7]["approved"], true);
+}
+
+#[tokio::test]
+async fn timeline_without_audit_log_still_loads() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let database = Arc::new(db::connect_memory().await.expect("in-memory db"));
+    let session = SessionRepo::new(Arc::clone(&database))
+        .create(&Session::new(
+            "U_OWNER".into(),
+            "/workspace".into(),
+            None,
+            SessionMode::Remote,
+        ))
+        .await
+        .expect("create session");
+
+    let timeline = SessionTimeline::load(&database, &temp.path().join("absent"), &session.id)
+        .await
+        .expect("timeline loads");
+
+    assert_eq!(
+        timeline.events.iter().map(|e| &e.event).collect::<Vec<_>>(),
+        [&TimelineEvent::SessionStarted { prompt: None }]
+    );
+}
@@ -0,0 +1,101 @@
+//! Unit tests for the simulated operator policy.
+//!
+//! Tests cover:
+//! - Defaults when the policy is empty
+//! - First-match rule ordering for approvals and prompts
+//! - Invalid TOML, unknown keys, and bad path globs are rejected
+
+use agent_intercom::models::approval::RiskLevel;
+use agent_intercom::models::prompt::{PromptDecision, PromptType};
+use agent_intercom::orchestrator::simulated_operator::OperatorScript;
+
+#[test]
+fn empty_policy_approves_and_continues() {
+    let script = OperatorScript::from_toml_str("").expect("valid");
+    assert_eq!(script.delay_ms, 0);
+    assert_eq!(
+        script.decide_approval("src/lib.rs", RiskLevel::Critical),
+        (true, None)
+    );
+    assert_eq!(
+        script.decide_prompt(PromptType::ErrorRecovery),
+        (PromptDecision::Continue, None)
+    );
+}
+
+#[test]
+fn approval_rules_first_match_wins() {
+    let script = OperatorScript::from_toml_str(
+        r#"
+default_approval = "reject"
+
+[[approval]]
+path = "migrations/**"
+decision = "reject"
+reason = "migrations need a human"
+
+[[approval]]
+risk_level = "low"
+decision = "approve"
+"#,
+    )
+    .expect("valid");
+
+    assert_eq!(
+        script.decide_approval("migrations/001.sql", RiskLevel::Low),
+        (false, Some("migrations need a human".into()))
+    );
+    assert_eq!(
+        script.decide_approval("src/lib.rs", RiskLevel::Low),
+        (true, None)
+    );
+    let (approved, reason) = script.decide_approval("src/lib.rs", RiskLevel::High);
+    assert!(!approved);
+    assert!(reason.is_some(), "default rejection carries a reason");
+}
+
+#[test]
+fn prompt_rules_match_type_and_keep_refine_instruction() {
+    let script = OperatorScript::from_toml_str(
+        r#"
+default_prompt = "stop"
+
+[[prompt]]
+prompt_type = "clarification"
+decision = "refine"
+instruction = "use the existing helper"
+
+[[prompt]]
+prompt_type = "continuation"
+decision = "continue"
+instruction = "ignored"
+"#,
+    )
+    .expect("valid");
+
+    assert_eq!(
+        script.decide_prompt(PromptType::Clarification),
+        (
+            PromptDecision::Refine,
+            Some("use the existing helper".into())
+        )
+    );
+    assert_eq!(
+        script.decide_prompt(PromptType::Continuation),
+        (PromptDecision::Continue, None)
+    );
+    assert_eq!(
+        script.decide_prompt(PromptType::ErrorRecovery),
+        (PromptDecision::Stop, None)
+    );
+}
+
+#[test]
+fn invalid_policies_are_rejected() {
+    assert!(OperatorScript::from_toml_str("delay_ms = \"soon\"").is_err());
+    assert!(OperatorScript::from_toml_str("approve_all = true").is_err());
+    assert!(OperatorScript::from_toml_str(
+        "[[approval]]\npath = \"src/[\"\ndecision = \"approve\"\n"
+    )
+    .is_err());
+}
@@ -0,0 +1,54 @@
+//! Unit tests for per-request Slack thread anchors (`slack::anchor`).
+//!
+//! Covers:
+//! - A request posted at channel root is its own thread
+//! - A request posted inside the session thread keeps that thread
+//! - A stored anchor is rebuilt from the request's columns, and a request
+//!   never posted has none
+
+use agent_intercom::slack::anchor::Anchor;
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
+
+#[test]
+fn root_request_anchors_its_own_thread() {
+    let anchor = Anchor::new(
+        SlackChannelId("C_ANCHOR".into()),
+        SlackTs("1700000000.000200".into()),
+        None,
+    );
+    assert_eq!(anchor.ts, anchor.thread_ts);
+    assert_eq!(anchor.channel.0, "C_ANCHOR");
+}
+
+#[test]
+fn threaded_request_keeps_session_thread() {
+    let anchor = Anchor::new(
+        SlackChannelId("C_ANCHOR".into()),
+        SlackTs("1700000000.000200".into()),
+        Some(SlackTs("1700000000.000100".into())),
+    );
+    assert_eq!(anchor.ts.0, "1700000000.000200");
+    assert_eq!(anchor.thread_ts.0, "1700000000.000100");
+}
+
+#[test]
+fn stored_anchor_is_rebuilt_from_request_columns() {
+    let anchor = Anchor::stored(
+        "C_ANCHOR",
+        Some("1700000000.000200"),
+        Some("1700000000.000100"),
+    )
+    .expect("posted request has an anchor");
+    assert_eq!(anchor.channel.0, "C_ANCHOR");
+    assert_eq!(anchor.ts.0, "1700000000.000200");
+    assert_eq!(anchor.thread_ts.0, "1700000000.000100");
+
+    let root = Anchor::stored("C_ANCHOR", Some("1700000000.000200"), None)
+        .expect("posted request has an anchor");
+    assert_eq!(root.ts, root.thread_ts);
+}
+
+#[test]
+fn unposted_request_has_no_stored_anchor() {
+    assert!(Anchor::stored("C_ANCHOR", None, Some("1700000000.000100")).is_none());
+}
@@ -0,0 +1,140 @@
+//! Unit tests for the channel history cache (`slack::history_cache`).
+//!
+//! Tests cover:
+//! - Reads within the TTL are served without fetching
+//! - Stale reads fetch only messages newer than the newest cached one
+//! - An overflowing incremental page replaces the cache
+//! - Reads asking for more than is cached fetch in full
+//! - A zero TTL disables caching
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use agent_intercom::slack::history_cache::{HistoryCache, HistoryPage, CAPACITY};
+use agent_intercom::Result;
+use slack_morphism::prelude::{SlackHistoryMessage, SlackTs};
+
+fn message(ts: &str) -> SlackHistoryMessage {
+    serde_json::from_value(serde_json::json!({ "ts": ts, "text": format!("msg {ts}") }))
+        .expect("history message")
+}
+
+fn timestamps(page: &HistoryPage) -> Vec<String> {
+    page.0.iter().map(|m| m.origin.ts.0.clone()).collect()
+}
+
+/// Records each fetch's `(oldest, limit)` and answers with `page`.
+struct FakeSlack {
+    calls: Mutex<Vec<(Option<String>, u16)>>,
+}
+
+impl FakeSlack {
+    fn new() -> Self {
+        Self {
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    async fn read(
+        &self,
+        cache: &HistoryCache,
+        limit: u16,
+        page: (&[&str], bool),
+    ) -> Result<HistoryPage> {
+        cache
+            .read("C1", limit, |oldest: Option<SlackTs>, limit| async move {
+                self.calls
+                    .lock()
+                    .expect("lock")
+                    .push((oldest.map(|ts| ts.0), limit));
+                Ok((page.0.iter().map(|ts| message(ts)).collect(), page.1))
+            })
+            .await
+    }
+
+    fn calls(&self) -> Vec<(Option<String>, u16)> {
+        self.calls.lock().expect("lock").clone()
+    }
+}
+
+#[tokio::test]
+async fn fresh_reads_are_served_from_cache() {
+    let cache = HistoryCache::new(Duration::from_mins(1));
+    let slack = FakeSlack::new();
+
+    let page = slack
+        .read(&cache, 2, (&["3.0", "2.0", "1.0"], false))
+        .await
+        .expect("first read");
+    assert_eq!(timestamps(&page), ["3.0", "2.0"]);
+    assert!(page.1, "more cached than asked for");
+
+    let page = slack
+        .read(&cache, 3, (&[], false))
+        .await
+        .expect("cached read");
+    assert_eq!(timestamps(&page), ["3.0", "2.0", "1.0"]);
+    assert!(!page.1);
+    assert_eq!(slack.calls(), [(None, CAPACITY)], "fetched once, in full");
+}
+
+#[tokio::test]
+async fn stale_reads_fetch_only_newer_messages() {
+    let cache = HistoryCache::new(Duration::from_millis(1));
+    let slack = FakeSlack::new();
+
+    slack
+        .read(&cache, 5, (&["2.0", "1.0"], false))
+        .await
+        .expect("first read");
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    let page = slack
+        .read(&cache, 5, (&["4.0", "3.0"], false))
+        .await
+        .expect("incremental read");
+    assert_eq!(timestamps(&page), ["4.0", "3.0", "2.0", "1.0"]);
+    assert_eq!(
+        slack.calls(),
+        [(None, CAPACITY), (Some("2.0".to_owned()), CAPACITY)]
+    );
+
+    // More new messages than one page: the page replaces the cache.
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    let page = slack
+        .read(&cache, 5, (&["9.0", "8.0"], true))
+        .await
+        .expect("overflowing read");
+    assert_eq!(timestamps(&page), ["9.0", "8.0"]);
+    assert!(page.1, "older messages exist");
+}
+
+#[tokio::test]
+async fn reads_beyond_the_cache_fetch_in_full() {
+    let cache = HistoryCache::new(Duration::from_mins(1));
+    let slack = FakeSlack::new();
+
+    slack
+        .read(&cache, 1, (&["2.0"], true))
+        .await
+        .expect("first read");
+    let page = slack
+        .read(&cache, 3, (&["2.0", "1.5", "1.0"], true))
+        .await
+        .expect("wider read");
+    assert_eq!(timestamps(&page), ["2.0", "1.5", "1.0"]);
+    assert_eq!(slack.calls(), [(None, CAPACITY), (None, CAPACITY)]);
+}
+
+#[tokio::test]
+async fn zero_ttl_always_fetches() {
+    let cache = HistoryCache::new(Duration::ZERO);
+    let slack = FakeSlack::new();
+
+    for _ in 0..2 {
+        slack
+            .read(&cache, 20, (&["1.0"], false))
+            .await
+            .expect("read");
+    }
+    assert_eq!(slack.calls(), [(None, 20), (None, 20)]);
+}
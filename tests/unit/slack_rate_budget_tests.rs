@@ -0,0 +1,220 @@
+//! Unit tests for the shared Slack rate budget (`slack::rate_budget`).
+//!
+//! Covers:
+//! - Pressure rises to `Near` at `near_limit_percent` and expires after a minute
+//! - A `429` blocks every caller for its `Retry-After`
+//! - Held updates with the same key coalesce; edits of one message coalesce
+//! - Held posts for one thread merge into a single message
+//! - Held updates are due once the budget recovers or the window passes
+//! - `[slack_rate_limit]` defaults and validation
+
+use std::time::{Duration, Instant};
+
+use agent_intercom::config::{GlobalConfig, SlackRateLimitConfig};
+use agent_intercom::slack::client::SlackMessage;
+use agent_intercom::slack::rate_budget::{Deferred, DeferredUpdates, Pressure, RateBudget};
+use agent_intercom::AppError;
+use slack_morphism::prelude::{SlackChannelId, SlackTs};
+
+fn budget(calls_per_minute: u32) -> RateBudget {
+    RateBudget::new(SlackRateLimitConfig {
+        calls_per_minute,
+        near_limit_percent: 50,
+        batch_window_seconds: 10,
+        ..SlackRateLimitConfig::default()
+    })
+}
+
+fn post(channel: &str, thread: Option<&str>, text: &str) -> Deferred {
+    let mut message = SlackMessage::plain(SlackChannelId(channel.into()), text);
+    message.thread_ts = thread.map(|ts| SlackTs(ts.into()));
+    Deferred::Post(message)
+}
+
+fn texts(updates: &[Deferred]) -> Vec<String> {
+    updates
+        .iter()
+        .map(|update| match update {
+            Deferred::Post(message) => message.text.clone().unwrap_or_default(),
+            Deferred::Edit { ts, .. } => format!("edit {}", ts.0),
+        })
+        .collect()
+}
+
+#[test]
+fn pressure_tracks_calls_in_the_last_minute() {
+    let budget = budget(10);
+    let start = Instant::now();
+    for n in 0..4 {
+        budget.record_call(start + Duration::from_secs(n));
+    }
+    assert_eq!(
+        budget.pressure(start + Duration::from_secs(4)),
+        Pressure::Normal
+    );
+
+    budget.record_call(start + Duration::from_secs(5));
+    assert_eq!(
+        budget.pressure(start + Duration::from_secs(5)),
+        Pressure::Near
+    );
+    assert_eq!(budget.calls_in_window(start + Duration::from_secs(5)), 5);
+
+    let later = start + Duration::from_secs(62);
+    assert_eq!(budget.calls_in_window(later), 2);
+    assert_eq!(budget.pressure(later), Pressure::Normal);
+}
+
+#[test]
+fn rate_limit_blocks_until_retry_after() {
+    let budget = budget(50);
+    let now = Instant::now();
+
+    let wait = budget.record_rate_limited(Some(Duration::from_secs(3)), now);
+
+    assert_eq!(wait, Duration::from_secs(3));
+    assert_eq!(budget.pressure(now), Pressure::Limited);
+    assert_eq!(
+        budget.retry_after(now + Duration::from_secs(1)),
+        Duration::from_secs(2)
+    );
+    assert_eq!(
+        budget.retry_after(now + Duration::from_secs(3)),
+        Duration::ZERO
+    );
+    assert_eq!(
+        budget.pressure(now + Duration::from_secs(3)),
+        Pressure::Normal
+    );
+    assert_eq!(budget.rate_limited_count(), 1);
+}
+
+#[test]
+fn rate_limit_without_retry_after_waits_one_second() {
+    let budget = budget(50);
+    let now = Instant::now();
+
+    assert_eq!(
+        budget.record_rate_limited(None, now),
+        Duration::from_secs(1)
+    );
+    // A shorter Retry-After never shortens a block already in force.
+    budget.record_rate_limited(Some(Duration::from_millis(10)), now);
+    assert_eq!(budget.retry_after(now), Duration::from_secs(1));
+}
+
+#[test]
+fn same_key_keeps_only_latest_update() {
+    let mut held = DeferredUpdates::default();
+    let now = Instant::now();
+    held.push(Some("status:a".into()), post("C1", None, "a1"), now);
+    held.push(Some("status:b".into()), post("C2", None, "b1"), now);
+    held.push(Some("status:a".into()), post("C1", None, "a2"), now);
+
+    assert_eq!(held.len(), 2);
+    assert_eq!(texts(&held.drain()), ["b1", "a2"]);
+    assert!(held.is_empty());
+}
+
+#[test]
+fn edits_of_one_message_coalesce() {
+    let mut held = DeferredUpdates::default();
+    let now = Instant::now();
+    for status in ["first", "second"] {
+        held.push(
+            None,
+            Deferred::Edit {
+                channel: SlackChannelId("C1".into()),
+                ts: SlackTs("1.1".into()),
+                blocks: vec![agent_intercom::slack::blocks::text_section(status)],
+            },
+            now,
+        );
+    }
+
+    let updates = held.drain();
+
+    assert_eq!(updates.len(), 1);
+    let Deferred::Edit { ref blocks, .. } = updates[0] else {
+        panic!("expected an edit");
+    };
+    let json = serde_json::to_string(blocks).expect("blocks serialize");
+    assert!(json.contains("second") && !json.contains("first"), "{json}");
+}
+
+#[test]
+fn posts_for_one_thread_merge() {
+    let mut held = DeferredUpdates::default();
+    let now = Instant::now();
+    held.push(None, post("C1", Some("9.9"), "one"), now);
+    held.push(None, post("C2", None, "elsewhere"), now);
+    held.push(None, post("C1", Some("9.9"), "two"), now);
+    held.push(None, post("C1", None, "channel root"), now);
+
+    let updates = held.drain();
+
+    assert_eq!(texts(&updates), ["one\ntwo", "elsewhere", "channel root"]);
+}
+
+#[test]
+fn merged_blocks_keep_plain_text_messages_visible() {
+    let mut held = DeferredUpdates::default();
+    let now = Instant::now();
+    let mut with_blocks = SlackMessage::plain(SlackChannelId("C1".into()), "fallback");
+    with_blocks.blocks = Some(vec![agent_intercom::slack::blocks::text_section("card")]);
+    held.push(None, Deferred::Post(with_blocks), now);
+    held.push(None, post("C1", None, "plain"), now);
+
+    let updates = held.drain();
+
+    let Deferred::Post(ref merged) = updates[0] else {
+        panic!("expected a post");
+    };
+    assert_eq!(merged.blocks.as_ref().map(Vec::len), Some(2));
+    assert_eq!(merged.text.as_deref(), Some("fallback\nplain"));
+}
+
+#[test]
+fn held_updates_are_due_on_recovery_or_after_window() {
+    let mut held = DeferredUpdates::default();
+    let window = Duration::from_secs(10);
+    let now = Instant::now();
+    assert!(!held.due(Pressure::Normal, window, now));
+
+    held.push(None, post("C1", None, "x"), now);
+
+    assert!(held.due(Pressure::Normal, window, now));
+    assert!(!held.due(Pressure::Near, window, now + Duration::from_secs(9)));
+    assert!(held.due(Pressure::Limited, window, now + window));
+    assert_eq!(held.deadline(window), Some(now + window));
+}
+
+#[test]
+fn rate_limit_config_defaults_and_validation() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let base = format!(
+        "default_workspace_root = '{root}'\n\
+         host_cli = \"claude\"\n\
+         [slack]\n\
+         [timeouts]\n\
+         approval_seconds = 60\n\
+         prompt_seconds = 60\n\
+         wait_seconds = 0\n\
+         [stall]\n\
+         inactivity_threshold_seconds = 300\n\
+         escalation_threshold_seconds = 120\n\
+         max_retries = 3\n\
+         default_nudge_message = \"continue\"\n"
+    );
+
+    let config = GlobalConfig::from_toml_str(&base).expect("config parses");
+    assert_eq!(config.slack_rate_limit, SlackRateLimitConfig::default());
+    assert_eq!(config.slack_rate_limit.calls_per_minute, 50);
+
+    let invalid = format!("{base}[slack_rate_limit]\nnear_limit_percent = 0\n");
+    assert!(matches!(
+        GlobalConfig::from_toml_str(&invalid),
+        Err(AppError::Config(ref msg)) if msg.contains("near_limit_percent")
+    ));
+}
@@ -0,0 +1,98 @@
+//! Unit tests for Socket Mode replay protection (`slack::replay`).
+//!
+//! Tests cover:
+//! - A key is accepted once, refused as a duplicate within the window and
+//!   accepted again once it has expired
+//! - Deliveries stamped before the window are refused as stale
+//! - Interactions are keyed by `trigger_id` and timed by their oldest
+//!   `action_ts`
+
+use agent_intercom::slack::replay::{
+    interaction_key, interaction_time, ReplayGuard, REPLAY_WINDOW,
+};
+use chrono::{DateTime, TimeDelta, Utc};
+use slack_morphism::prelude::SlackInteractionEvent;
+
+fn block_action(trigger_id: &str, action_ts: &str) -> SlackInteractionEvent {
+    serde_json::from_value(serde_json::json!({
+        "type": "block_actions",
+        "team": { "id": "T1" },
+        "user": { "id": "U1" },
+        "api_app_id": "A1",
+        "container": { "type": "message", "message_ts": "1700000000.000100" },
+        "trigger_id": trigger_id,
+        "actions": [
+            { "type": "button", "action_id": "approve_accept", "action_ts": action_ts }
+        ]
+    }))
+    .expect("block_actions payload")
+}
+
+#[test]
+fn duplicate_keys_are_refused_until_they_expire() {
+    let guard = ReplayGuard::new();
+    let now = Utc::now();
+
+    assert_eq!(guard.refuse(Some("trigger:1"), None, now), None);
+    assert_eq!(guard.refuse(Some("trigger:2"), None, now), None);
+    let retry = now + TimeDelta::seconds(30);
+    assert_eq!(
+        guard.refuse(Some("trigger:1"), None, retry),
+        Some("duplicate")
+    );
+
+    let later = now + REPLAY_WINDOW;
+    assert_eq!(guard.refuse(Some("trigger:1"), None, later), None);
+    assert_eq!(guard.refuse(None, None, later), None);
+    assert_eq!(guard.refuse(None, None, later), None);
+}
+
+#[test]
+fn deliveries_older_than_the_window_are_stale() {
+    let guard = ReplayGuard::new();
+    let now = Utc::now();
+
+    let old = now - REPLAY_WINDOW - TimeDelta::seconds(1);
+    assert_eq!(
+        guard.refuse(Some("event:E1"), Some(old), now),
+        Some("stale")
+    );
+    // A stale delivery is not remembered, so it never shadows a fresh one.
+    let recent = now - TimeDelta::seconds(5);
+    assert_eq!(guard.refuse(Some("event:E1"), Some(recent), now), None);
+}
+
+#[test]
+fn interactions_are_keyed_by_trigger_and_timed_by_action() {
+    let event = block_action("123.456.abc", "1700000000.250000");
+    assert_eq!(
+        interaction_key(&event).as_deref(),
+        Some("trigger:123.456.abc")
+    );
+    let expected = DateTime::from_timestamp(1_700_000_000, 250_000_000).expect("time");
+    assert_eq!(interaction_time(&event), Some(expected));
+
+    let guard = ReplayGuard::new();
+    let now = expected + TimeDelta::seconds(2);
+    let key = interaction_key(&event);
+    let at = interaction_time(&event);
+    assert_eq!(guard.refuse(key.as_deref(), at, now), None);
+    let redelivered = block_action("123.456.abc", "1700000000.250000");
+    assert_eq!(
+        guard.refuse(
+            interaction_key(&redelivered).as_deref(),
+            interaction_time(&redelivered),
+            now + TimeDelta::seconds(10),
+        ),
+        Some("duplicate")
+    );
+    let other_tap = block_action("123.456.def", "1700000001.000000");
+    assert_eq!(
+        guard.refuse(
+            interaction_key(&other_tap).as_deref(),
+            interaction_time(&other_tap),
+            now + TimeDelta::seconds(10),
+        ),
+        None
+    );
+}
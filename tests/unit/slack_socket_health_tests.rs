@@ -0,0 +1,82 @@
+//! Unit tests for Socket Mode connection health.
+//!
+//! Validates:
+//! - The first hello marks the listener connected; the initial connections'
+//!   hellos are not drops
+//! - Hellos past the initial connections count as reconnects after drops
+//! - Stopping the listener disconnects and counts the drop
+//! - `request_reconnect` reaches a waiting listener only
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use agent_intercom::slack::socket_health::SocketHealth;
+use chrono::{TimeZone, Utc};
+
+#[test]
+fn initial_hellos_connect_without_counting_drops() {
+    let health = SocketHealth::new(2);
+    let opened = Utc.with_ymd_and_hms(2026, 10, 17, 9, 0, 0).unwrap();
+    let second = opened + chrono::Duration::seconds(1);
+
+    assert!(!health.snapshot().connected);
+    health.record_hello(opened);
+    health.record_hello(second);
+
+    let snapshot = health.snapshot();
+    assert!(snapshot.connected);
+    assert_eq!(snapshot.connected_since, Some(opened));
+    assert_eq!(snapshot.last_hello, Some(second));
+    assert_eq!(snapshot.disconnects, 0);
+}
+
+#[test]
+fn later_hellos_count_reconnects() {
+    let health = SocketHealth::new(1);
+    let opened = Utc.with_ymd_and_hms(2026, 10, 17, 9, 0, 0).unwrap();
+    health.record_hello(opened);
+    health.record_hello(opened + chrono::Duration::hours(4));
+
+    let snapshot = health.snapshot();
+    assert_eq!(snapshot.disconnects, 1);
+    assert_eq!(snapshot.connected_since, Some(opened));
+}
+
+#[test]
+fn stopping_disconnects() {
+    let health = SocketHealth::new(1);
+    health.record_hello(Utc::now());
+    health.record_stopped();
+    health.record_stopped();
+
+    let snapshot = health.snapshot();
+    assert!(!snapshot.connected);
+    assert!(snapshot.connected_since.is_none());
+    assert_eq!(snapshot.disconnects, 1);
+
+    // A fresh registration's first hello is a connect, not another drop.
+    health.record_hello(Utc::now());
+    assert_eq!(health.snapshot().disconnects, 1);
+}
+
+#[tokio::test]
+async fn reconnect_reaches_waiting_listener_only() {
+    let health = Arc::new(SocketHealth::new(1));
+    assert!(!health.request_reconnect(), "no listener yet");
+
+    let waiter = Arc::clone(&health);
+    let task = tokio::spawn(async move { waiter.reconnect_requested().await });
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !health.request_reconnect() {
+            tokio::task::yield_now().await;
+        }
+    })
+    .await
+    .expect("listener registered");
+    tokio::time::timeout(Duration::from_secs(5), task)
+        .await
+        .expect("reconnect delivered")
+        .expect("task");
+
+    assert_eq!(health.snapshot().forced_reconnects, 1);
+}
@@ -0,0 +1,120 @@
+//! Unit tests for snoozed decisions (`orchestrator::snooze`).
+//!
+//! Tests cover:
+//! - Each snooze moves the end forward and adds to the timeout extension
+//! - `snoozed_until` is empty once the snooze has ended or been forgotten
+//! - `wait` returns the decision, times out when not snoozed, and keeps
+//!   waiting past the original timeout when snoozed
+//! - Activity extends only a waited-on request close to its timeout, to
+//!   the grace period, without snoozing it
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use agent_intercom::orchestrator::snooze::{SnoozeTable, SNOOZE_DURATION};
+use chrono::Utc;
+use tokio::sync::oneshot;
+
+#[test]
+fn snooze_extends_timeout_each_time() {
+    let table = SnoozeTable::new();
+    let now = Utc::now();
+
+    let first = table.snooze("req-1", now);
+    assert_eq!(first, now + SNOOZE_DURATION);
+    assert_eq!(table.extension("req-1"), SNOOZE_DURATION);
+
+    let later = now + chrono::Duration::minutes(10);
+    let second = table.snooze("req-1", later);
+    assert_eq!(second, later + SNOOZE_DURATION);
+    assert_eq!(table.extension("req-1"), SNOOZE_DURATION * 2);
+    assert_eq!(table.extension("req-2"), Duration::ZERO);
+}
+
+#[test]
+fn snoozed_until_ends_with_snooze() {
+    let table = SnoozeTable::new();
+    let now = Utc::now();
+    let until = table.snooze("req-1", now);
+
+    assert_eq!(table.snoozed_until("req-1", now), Some(until));
+    assert_eq!(table.snoozed_until("req-1", until), None);
+    assert_eq!(table.snoozed_until("req-2", now), None);
+
+    table.forget("req-1");
+    assert_eq!(table.snoozed_until("req-1", now), None);
+    assert_eq!(table.extension("req-1"), Duration::ZERO);
+}
+
+#[tokio::test]
+async fn wait_returns_decision() {
+    let table = SnoozeTable::new();
+    let (tx, rx) = oneshot::channel();
+    tx.send("approved").expect("send");
+
+    let outcome = table.wait("req-1", Duration::from_secs(5), rx).await;
+    assert!(matches!(outcome, Some(Ok("approved"))));
+}
+
+#[tokio::test]
+async fn wait_times_out_without_snooze() {
+    let table = SnoozeTable::new();
+    let (_tx, rx) = oneshot::channel::<()>();
+
+    let outcome = table.wait("req-1", Duration::from_millis(20), rx).await;
+    assert!(outcome.is_none());
+}
+
+#[tokio::test]
+async fn snoozed_wait_outlasts_original_timeout() {
+    let table = Arc::new(SnoozeTable::new());
+    let (tx, rx) = oneshot::channel();
+    table.snooze("req-1", Utc::now());
+
+    let waiter = {
+        let table = Arc::clone(&table);
+        tokio::spawn(async move { table.wait("req-1", Duration::from_millis(20), rx).await })
+    };
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(!waiter.is_finished(), "snooze should extend the timeout");
+
+    tx.send(42).expect("send");
+    let outcome = waiter.await.expect("join");
+    assert!(matches!(outcome, Some(Ok(42))));
+    assert_eq!(
+        table.extension("req-1"),
+        Duration::ZERO,
+        "wait forgets the snooze"
+    );
+}
+
+#[tokio::test]
+async fn activity_near_timeout_extends_wait_to_grace() {
+    let table = Arc::new(SnoozeTable::new());
+    let grace = Duration::from_millis(300);
+    assert_eq!(table.extend_for_activity("req-1", grace, Utc::now()), None);
+
+    let (tx, rx) = oneshot::channel();
+    let waiter = {
+        let table = Arc::clone(&table);
+        tokio::spawn(async move { table.wait("req-1", Duration::from_millis(120), rx).await })
+    };
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    let now = Utc::now();
+    assert_eq!(
+        table.extend_for_activity("req-1", grace, now),
+        Some(now + grace)
+    );
+    assert_eq!(table.snoozed_until("req-1", now), None, "not a snooze");
+    assert_eq!(
+        table.extend_for_activity("req-1", grace, Utc::now()),
+        None,
+        "enough time remains"
+    );
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert!(!waiter.is_finished(), "activity should extend the timeout");
+    tx.send(7).expect("send");
+    assert!(matches!(waiter.await.expect("join"), Some(Ok(7))));
+    assert_eq!(table.extend_for_activity("req-1", grace, Utc::now()), None);
+}
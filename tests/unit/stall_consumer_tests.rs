@@ -1,16 +1,24 @@
 //! Unit tests for stall event consumer (FR-028, FR-029, FR-030).
 //!
 //! Validates that the consumer reads [`StallEvent`]s from the mpsc channel
-//! and produces the correct Slack messages for each event variant.
+//! and produces the correct Slack messages for each event variant, and that
+//! each event is recorded in the session's stall alert history,
+//! including how it was resolved, and in its connectivity status.
 
 use std::sync::Arc;
 
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
-use agent_intercom::orchestrator::stall_consumer::spawn_stall_event_consumer;
+use agent_intercom::models::session::{ConnectivityStatus, Session, SessionMode};
+use agent_intercom::models::stall::{StallAlertStatus, StallResolution};
+use agent_intercom::orchestrator::stall_consumer::{
+    record_stall_event, spawn_stall_event_consumer, track_connectivity,
+};
 use agent_intercom::orchestrator::stall_detector::StallEvent;
-use agent_intercom::persistence::db::Database;
+use agent_intercom::persistence::db::{self, Database};
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::persistence::stall_repo::StallAlertRepo;
 use agent_intercom::slack::client::SlackService;
 
 /// The consumer task exits cleanly when the cancellation token fires
@@ -129,3 +137,163 @@ async fn spawn_returns_join_handle() {
     ) -> tokio::task::JoinHandle<()>;
     let _: ConsumerFn = spawn_stall_event_consumer;
 }
+
+/// Stall events open an alert and walk it through its lifecycle.
+#[tokio::test]
+async fn stall_events_are_recorded_as_alerts() {
+    let database = Arc::new(db::connect_memory().await.expect("db"));
+    let repo = StallAlertRepo::new(Arc::clone(&database));
+    let session_id = "sess-stall".to_owned();
+
+    record_stall_event(
+        &database,
+        &StallEvent::Stalled {
+            session_id: session_id.clone(),
+            idle_seconds: 300,
+        },
+        Some("1700000000.000100".into()),
+    )
+    .await;
+    record_stall_event(
+        &database,
+        &StallEvent::AutoNudge {
+            session_id: session_id.clone(),
+            nudge_count: 1,
+        },
+        None,
+    )
+    .await;
+
+    let alerts = repo.list_for_session(&session_id).await.expect("list");
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].idle_seconds, 300);
+    assert_eq!(alerts[0].nudge_count, 1);
+    assert_eq!(alerts[0].status, StallAlertStatus::Nudged);
+    assert_eq!(alerts[0].slack_ts.as_deref(), Some("1700000000.000100"));
+
+    record_stall_event(
+        &database,
+        &StallEvent::Escalated {
+            session_id: session_id.clone(),
+            nudge_count: 3,
+        },
+        None,
+    )
+    .await;
+    record_stall_event(
+        &database,
+        &StallEvent::SelfRecovered {
+            session_id: session_id.clone(),
+        },
+        None,
+    )
+    .await;
+
+    let alerts = repo.list_for_session(&session_id).await.expect("list");
+    assert_eq!(alerts[0].status, StallAlertStatus::SelfRecovered);
+    assert_eq!(alerts[0].resolution, Some(StallResolution::Nudged));
+    assert!(alerts[0].resolved_at.is_some());
+
+    // A later recovery with no open alert records nothing new.
+    record_stall_event(
+        &database,
+        &StallEvent::SelfRecovered {
+            session_id: session_id.clone(),
+        },
+        None,
+    )
+    .await;
+    assert_eq!(
+        repo.list_for_session(&session_id)
+            .await
+            .expect("list")
+            .len(),
+        1
+    );
+}
+
+/// A stall the agent recovers from without any nudge is a false positive.
+#[tokio::test]
+async fn unnudged_recovery_is_recorded_as_self_recovered() {
+    let database = Arc::new(db::connect_memory().await.expect("db"));
+    let repo = StallAlertRepo::new(Arc::clone(&database));
+
+    for event in [
+        StallEvent::Stalled {
+            session_id: "sess-fp".into(),
+            idle_seconds: 120,
+        },
+        StallEvent::SelfRecovered {
+            session_id: "sess-fp".into(),
+        },
+    ] {
+        record_stall_event(&database, &event, None).await;
+    }
+
+    let alerts = repo.list_for_session("sess-fp").await.expect("list");
+    assert_eq!(alerts[0].resolution, Some(StallResolution::SelfRecovered));
+}
+
+/// A finished agent going quiet is not a stall.
+#[tokio::test]
+async fn completion_is_not_recorded_as_a_stall() {
+    let database = Arc::new(db::connect_memory().await.expect("db"));
+    record_stall_event(
+        &database,
+        &StallEvent::Completed {
+            session_id: "sess-done".into(),
+            idle_seconds: 120,
+        },
+        None,
+    )
+    .await;
+
+    let alerts = StallAlertRepo::new(Arc::clone(&database))
+        .list_for_session("sess-done")
+        .await
+        .expect("list");
+    assert!(alerts.is_empty(), "{alerts:?}");
+}
+
+/// A stall marks the session stalled and self-recovery marks it online.
+#[tokio::test]
+async fn stall_events_drive_connectivity_status() {
+    let database = Arc::new(db::connect_memory().await.expect("db"));
+    let repo = SessionRepo::new(Arc::clone(&database));
+    let session = repo
+        .create(&Session::new(
+            "U1".into(),
+            "/test/workspace".into(),
+            None,
+            SessionMode::Remote,
+        ))
+        .await
+        .expect("create");
+    let connectivity = || async {
+        repo.get_by_id(&session.id)
+            .await
+            .expect("get")
+            .expect("found")
+            .connectivity_status
+    };
+
+    let stalled = StallEvent::Stalled {
+        session_id: session.id.clone(),
+        idle_seconds: 120,
+    };
+    track_connectivity(&database, &stalled).await;
+    assert_eq!(connectivity().await, ConnectivityStatus::Stalled);
+
+    let nudged = StallEvent::AutoNudge {
+        session_id: session.id.clone(),
+        nudge_count: 1,
+    };
+    track_connectivity(&database, &nudged).await;
+    assert_eq!(connectivity().await, ConnectivityStatus::Stalled);
+
+    let recovered = StallEvent::SelfRecovered {
+        session_id: session.id.clone(),
+    };
+    track_connectivity(&database, &recovered).await;
+    assert_eq!(connectivity().await, ConnectivityStatus::Online);
+}
@@ -1,14 +1,17 @@
 //! Unit tests for stall detection (T110, T056).
 //!
 //! Validates timer firing, reset, pause/resume, consecutive nudge
-//! counting, self-recovery detection, and stall notification content.
+//! counting, self-recovery detection, adaptive thresholds, completion
+//! detection, and stall notification content.
 
 use std::time::Duration;
 
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
-use agent_intercom::orchestrator::stall_detector::{StallDetector, StallEvent};
+use agent_intercom::orchestrator::stall_detector::{
+    AdaptiveThreshold, StallDetector, StallDetectorHandle, StallEvent,
+};
 use agent_intercom::slack::blocks;
 
 /// Helper to create a detector with short thresholds for testing.
@@ -464,3 +467,163 @@ async fn nudge_retry_exhaustion_notifies_operator() {
     ct.cancel();
     drop(handle);
 }
+
+// ── Adaptive thresholds ──────────────────────────────────────────────────────
+
+#[test]
+fn adaptive_threshold_uses_fixed_value_until_warmed_up() {
+    let fixed = Duration::from_mins(5);
+    let mut adaptive = AdaptiveThreshold::new(4, Duration::from_secs(1), Duration::from_hours(1));
+
+    for _ in 0..4 {
+        adaptive.observe(Duration::from_secs(10));
+        assert_eq!(adaptive.threshold(fixed), fixed);
+    }
+    adaptive.observe(Duration::from_secs(10));
+    assert_eq!(adaptive.threshold(fixed), Duration::from_secs(40));
+}
+
+#[test]
+fn adaptive_threshold_follows_longer_intervals_within_bounds() {
+    let fixed = Duration::from_mins(5);
+    let mut adaptive = AdaptiveThreshold::new(4, Duration::from_mins(1), Duration::from_mins(30));
+
+    for _ in 0..5 {
+        adaptive.observe(Duration::from_secs(5));
+    }
+    assert_eq!(
+        adaptive.threshold(fixed),
+        Duration::from_mins(1),
+        "short intervals are floored at the minimum"
+    );
+
+    // A long compile phase pulls the average up, but not past the maximum.
+    for _ in 0..20 {
+        adaptive.observe(Duration::from_mins(15));
+    }
+    assert_eq!(adaptive.threshold(fixed), Duration::from_mins(30));
+}
+
+/// Send `count` resets `interval` apart so the detector learns `interval`.
+async fn train(handle: &StallDetectorHandle, count: u32, interval: Duration) {
+    for _ in 0..count {
+        tokio::time::sleep(interval).await;
+        handle.reset();
+    }
+}
+
+#[tokio::test]
+async fn adaptive_detector_fires_before_fixed_threshold_for_chatty_agents() {
+    let (detector, mut rx, ct) = test_detector("adaptive-fast", 5, 60, 3);
+    let handle = detector
+        .with_adaptive(AdaptiveThreshold::new(
+            2,
+            Duration::from_millis(50),
+            Duration::from_secs(10),
+        ))
+        .spawn();
+
+    train(&handle, 5, Duration::from_millis(50)).await;
+
+    let event = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+        .await
+        .expect("learned threshold should fire well before the fixed 5s")
+        .expect("channel open");
+    assert!(matches!(event, StallEvent::Stalled { .. }), "{event:?}");
+
+    ct.cancel();
+    drop(handle);
+}
+
+#[tokio::test]
+async fn adaptive_detector_tolerates_learned_long_intervals() {
+    let (detector, mut rx, ct) = test_detector("adaptive-slow", 1, 60, 3);
+    let handle = detector
+        .with_adaptive(AdaptiveThreshold::new(
+            8,
+            Duration::from_millis(10),
+            Duration::from_secs(10),
+        ))
+        .spawn();
+
+    train(&handle, 5, Duration::from_millis(200)).await;
+
+    // Learned threshold is ~1.6s, so silence past the fixed 1s is no stall.
+    let result = tokio::time::timeout(Duration::from_millis(1200), rx.recv()).await;
+    assert!(result.is_err(), "no stall expected, got {result:?}");
+
+    ct.cancel();
+    drop(handle);
+}
+
+#[tokio::test]
+async fn completed_work_reports_finished_after_grace() {
+    let (detector, mut rx, ct) = test_detector("done", 60, 1, 3);
+    let handle = detector
+        .with_completion_grace(Duration::from_millis(200))
+        .spawn();
+    handle.set_work_complete(true);
+    handle.reset();
+
+    let event = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+        .await
+        .expect("grace should elapse well before the 60s threshold")
+        .expect("channel open");
+    assert!(
+        matches!(event, StallEvent::Completed { ref session_id, .. } if session_id == "done"),
+        "{event:?}"
+    );
+    assert!(!handle.is_stalled());
+
+    // A finished agent is not nudged while it stays quiet.
+    let result = tokio::time::timeout(Duration::from_millis(1500), rx.recv()).await;
+    assert!(result.is_err(), "no follow-up expected, got {result:?}");
+
+    ct.cancel();
+    drop(handle);
+}
+
+#[tokio::test]
+async fn completion_grace_ignored_until_work_complete() {
+    let (detector, mut rx, ct) = test_detector("busy", 1, 60, 3);
+    let handle = detector
+        .with_completion_grace(Duration::from_millis(100))
+        .spawn();
+
+    let event = tokio::time::timeout(Duration::from_secs(3), rx.recv())
+        .await
+        .expect("should stall at the inactivity threshold")
+        .expect("channel open");
+    assert!(matches!(event, StallEvent::Stalled { .. }), "{event:?}");
+
+    ct.cancel();
+    drop(handle);
+}
+
+#[tokio::test]
+async fn new_activity_after_completion_rearms_the_detector() {
+    let (detector, mut rx, ct) = test_detector("again", 1, 60, 3);
+    let handle = detector
+        .with_completion_grace(Duration::from_millis(100))
+        .spawn();
+    handle.set_work_complete(true);
+    handle.reset();
+
+    let event = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+        .await
+        .expect("completed")
+        .expect("channel open");
+    assert!(matches!(event, StallEvent::Completed { .. }), "{event:?}");
+
+    // A new task: the snapshot is no longer complete and the agent goes quiet.
+    handle.set_work_complete(false);
+    handle.reset();
+    let event = tokio::time::timeout(Duration::from_secs(3), rx.recv())
+        .await
+        .expect("stall after new work")
+        .expect("channel open");
+    assert!(matches!(event, StallEvent::Stalled { .. }), "{event:?}");
+
+    ct.cancel();
+    drop(handle);
+}
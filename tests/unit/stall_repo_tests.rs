@@ -7,13 +7,16 @@
 //! - `update_status` changes lifecycle state
 //! - `increment_nudge_count` atomically bumps count and sets `nudged`
 //! - `dismiss` marks alert as dismissed
+//! - `list_for_session` returns a session's alerts oldest first
+//! - `resolve` records an outcome once; resolved alerts are no longer open
+//! - `list_recent` returns alerts newest first, optionally per session
 
 use std::sync::Arc;
 
 use chrono::Utc;
 
 use agent_intercom::models::progress::{ProgressItem, ProgressStatus};
-use agent_intercom::models::stall::{StallAlert, StallAlertStatus};
+use agent_intercom::models::stall::{StallAlert, StallAlertStatus, StallResolution};
 use agent_intercom::persistence::{db, stall_repo::StallAlertRepo};
 
 fn sample_alert(session_id: &str) -> StallAlert {
@@ -150,3 +153,75 @@ async fn dismiss_sets_dismissed_status() {
     let fetched = repo.get_by_id(&id).await.expect("query").expect("exists");
     assert_eq!(fetched.status, StallAlertStatus::Dismissed);
 }
+
+#[tokio::test]
+async fn list_for_session_returns_alerts_oldest_first() {
+    let db = db::connect_memory().await.expect("db");
+    let repo = StallAlertRepo::new(Arc::new(db));
+
+    let mut older = sample_alert("sess-1");
+    older.created_at -= chrono::Duration::minutes(5);
+    let newer = sample_alert("sess-1");
+    repo.create(&newer).await.expect("create");
+    repo.create(&older).await.expect("create");
+    repo.create(&sample_alert("sess-2")).await.expect("create");
+
+    let alerts = repo.list_for_session("sess-1").await.expect("list");
+    let ids: Vec<&str> = alerts.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, [older.id.as_str(), newer.id.as_str()]);
+}
+
+#[tokio::test]
+async fn resolve_records_first_outcome_and_closes_alert() {
+    let db = db::connect_memory().await.expect("db");
+    let repo = StallAlertRepo::new(Arc::new(db));
+
+    let alert = sample_alert("sess-1");
+    repo.create(&alert).await.expect("create");
+    let open = repo
+        .latest_open_for_session("sess-1")
+        .await
+        .expect("query")
+        .expect("open alert");
+    assert_eq!(open.id, alert.id);
+
+    repo.resolve(&alert.id, StallResolution::Nudged)
+        .await
+        .expect("resolve");
+    repo.resolve(&alert.id, StallResolution::Stopped)
+        .await
+        .expect("resolve again");
+
+    let fetched = repo
+        .get_by_id(&alert.id)
+        .await
+        .expect("query")
+        .expect("exists");
+    assert_eq!(fetched.resolution, Some(StallResolution::Nudged));
+    assert!(fetched.resolved_at.is_some());
+    assert!(repo
+        .latest_open_for_session("sess-1")
+        .await
+        .expect("query")
+        .is_none());
+}
+
+#[tokio::test]
+async fn list_recent_returns_newest_first() {
+    let db = db::connect_memory().await.expect("db");
+    let repo = StallAlertRepo::new(Arc::new(db));
+
+    let mut older = sample_alert("sess-1");
+    older.created_at -= chrono::Duration::minutes(5);
+    let newer = sample_alert("sess-2");
+    repo.create(&older).await.expect("create");
+    repo.create(&newer).await.expect("create");
+
+    let all = repo.list_recent(None).await.expect("list");
+    let ids: Vec<&str> = all.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, [newer.id.as_str(), older.id.as_str()]);
+
+    let scoped = repo.list_recent(Some("sess-1")).await.expect("list");
+    assert_eq!(scoped.len(), 1);
+    assert_eq!(scoped[0].id, older.id);
+}
@@ -0,0 +1,177 @@
+//! Unit tests for the `stalls` slash command.
+//!
+//! Validates:
+//! - Outcome statistics and the false-positive rate cover every recorded stall
+//! - A high false-positive rate suggests raising the inactivity threshold
+//! - `--limit` caps the listed stalls, not the statistics
+//! - Empty history is reported and usage errors name the command
+
+use std::sync::Arc;
+
+use agent_intercom::config::GlobalConfig;
+use agent_intercom::models::session::{Session, SessionMode};
+use agent_intercom::models::stall::{StallAlert, StallAlertStatus, StallResolution};
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::persistence::stall_repo::StallAlertRepo;
+use agent_intercom::slack::commands::{dispatch_command, parse_history_args};
+use agent_intercom::state::AppState;
+use chrono::{Duration, Utc};
+
+use super::test_helpers::test_app_state;
+
+const USER: &str = "U_TEST";
+
+fn make_config(workspace_root: &str) -> GlobalConfig {
+    let toml = format!(
+        r#"
+default_workspace_root = '{root}'
+http_port = 0
+ipc_name = "test-stalls"
+max_concurrent_sessions = 5
+host_cli = "echo"
+
+[slack]
+channel_id = "C_TEST"
+
+[timeouts]
+approval_seconds = 5
+prompt_seconds = 5
+wait_seconds = 5
+
+[stall]
+enabled = false
+inactivity_threshold_seconds = 300
+escalation_threshold_seconds = 120
+max_retries = 3
+default_nudge_message = "continue"
+"#,
+        root = workspace_root.replace('\\', "\\\\"),
+    );
+    let mut config = GlobalConfig::from_toml_str(&toml).expect("valid test config");
+    config.authorized_user_ids = vec![USER.to_owned()];
+    config
+}
+
+async fn app_state(workspace_root: &str) -> Arc<AppState> {
+    test_app_state(make_config(workspace_root)).await
+}
+
+async fn create_session(state: &AppState, root: &str) -> Session {
+    let mut session = Session::new(USER.into(), root.into(), None, SessionMode::Remote);
+    session.channel_id = Some("C_TEST".into());
+    SessionRepo::new(Arc::clone(&state.db))
+        .create(&session)
+        .await
+        .expect("create session")
+}
+
+/// Persist a stall raised ten minutes ago, resolved two minutes later when
+/// `resolution` is given.
+async fn stall(state: &AppState, session_id: &str, resolution: Option<StallResolution>) {
+    let mut alert = StallAlert::new(
+        session_id.to_owned(),
+        Some("ask_approval".into()),
+        Utc::now(),
+        300,
+        None,
+    );
+    alert.created_at -= Duration::minutes(10);
+    if let Some(resolution) = resolution {
+        alert.status = StallAlertStatus::SelfRecovered;
+        alert.resolution = Some(resolution);
+        alert.resolved_at = Some(alert.created_at + Duration::minutes(2));
+        if resolution == StallResolution::Nudged {
+            alert.nudge_count = 1;
+        }
+    }
+    StallAlertRepo::new(Arc::clone(&state.db))
+        .create(&alert)
+        .await
+        .expect("create stall");
+}
+
+#[tokio::test]
+async fn summarises_outcomes_and_suggests_raising_threshold() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let state = app_state(root).await;
+    let session = create_session(&state, root).await;
+
+    for _ in 0..4 {
+        stall(&state, &session.id, Some(StallResolution::SelfRecovered)).await;
+    }
+    stall(&state, &session.id, Some(StallResolution::Nudged)).await;
+    stall(&state, &session.id, None).await;
+
+    let reply = dispatch_command("stalls", &[], USER, "C_TEST", &state)
+        .await
+        .expect("stalls");
+
+    assert!(
+        reply.contains("*Stall history for all sessions*"),
+        "{reply}"
+    );
+    assert!(reply.contains("6 stalls · 1 open"), "{reply}");
+    assert!(
+        reply.contains("False positives: 4 of 5 resolved (80%)"),
+        "{reply}"
+    );
+    assert!(reply.contains("recovered after nudge: 1"), "{reply}");
+    assert!(reply.contains("median time to resolve: 2m 0s"), "{reply}");
+    assert!(
+        reply.contains("`[stall] inactivity_threshold_seconds` (currently 300s)"),
+        "{reply}"
+    );
+    assert!(reply.contains("(6 shown)"), "{reply}");
+    assert!(
+        reply.contains("idle 5m 0s after `ask_approval` · \u{1f514} recovered after nudge · 1 nudge(s) in 2m 0s"),
+        "{reply}"
+    );
+}
+
+#[tokio::test]
+async fn limit_caps_listing_but_not_statistics() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let state = app_state(root).await;
+    let session = create_session(&state, root).await;
+
+    stall(&state, &session.id, Some(StallResolution::Stopped)).await;
+    stall(&state, &session.id, Some(StallResolution::Nudged)).await;
+
+    let reply = dispatch_command(
+        "stalls",
+        &[&session.id[..8], "--limit", "1"],
+        USER,
+        "C_TEST",
+        &state,
+    )
+    .await
+    .expect("stalls");
+
+    assert!(
+        reply.contains(&format!("session `{}`", session.id)),
+        "{reply}"
+    );
+    assert!(
+        reply.contains("False positives: 0 of 2 resolved (0%)"),
+        "{reply}"
+    );
+    assert!(reply.contains("(1 shown)"), "{reply}");
+    assert!(!reply.contains("consider raising"), "{reply}");
+}
+
+#[tokio::test]
+async fn reports_empty_history_and_usage() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = tmp.path().to_str().expect("utf8");
+    let state = app_state(root).await;
+
+    let reply = dispatch_command("stalls", &[], USER, "C_TEST", &state)
+        .await
+        .expect("stalls");
+    assert_eq!(reply, "No stalls recorded for all sessions.");
+
+    let err = parse_history_args("stalls", &["--limit"]).expect_err("usage");
+    assert!(err.to_string().contains("usage: stalls"), "{err}");
+}
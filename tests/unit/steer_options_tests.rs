@@ -0,0 +1,101 @@
+//! Unit tests for steering priority and TTL options.
+//!
+//! Covers `parse_duration`, `SteerOptions::from_args` flag parsing for the
+//! `/intercom steer` command, and `SteeringMessage` expiry helpers.
+
+use agent_intercom::models::steering::{SteeringMessage, SteeringPriority, SteeringSource};
+use agent_intercom::slack::handlers::parse_duration;
+use agent_intercom::slack::handlers::steer::SteerOptions;
+use agent_intercom::AppError;
+use chrono::{Duration, Utc};
+
+#[test]
+fn parse_duration_accepts_units_and_bare_seconds() {
+    assert_eq!(parse_duration("90s").expect("s"), Duration::seconds(90));
+    assert_eq!(parse_duration("10m").expect("m"), Duration::minutes(10));
+    assert_eq!(parse_duration("2h").expect("h"), Duration::hours(2));
+    assert_eq!(parse_duration("1d").expect("d"), Duration::days(1));
+    assert_eq!(parse_duration("45").expect("bare"), Duration::seconds(45));
+    assert_eq!(parse_duration("365d").expect("max"), Duration::days(365));
+}
+
+#[test]
+fn parse_duration_rejects_invalid_values() {
+    for bad in [
+        "",
+        "0",
+        "0m",
+        "m",
+        "10w",
+        "-5m",
+        "1.5h",
+        "99999999999999999999d",
+        "366d",
+        "9223372036854775807s",
+        "2562047788015215h",
+    ] {
+        assert!(
+            matches!(parse_duration(bad), Err(AppError::Config(_))),
+            "expected config error for {bad:?}"
+        );
+    }
+}
+
+#[test]
+fn from_args_without_flags_keeps_defaults() {
+    let (options, rest) = SteerOptions::from_args(&["focus", "on", "tests"]).expect("parse");
+    assert_eq!(options, SteerOptions::default());
+    assert_eq!(options.priority, SteeringPriority::Normal);
+    assert_eq!(rest, ["focus", "on", "tests"]);
+}
+
+#[test]
+fn from_args_parses_leading_flags() {
+    let (options, rest) =
+        SteerOptions::from_args(&["--ttl", "5m", "--now", "stop", "--now"]).expect("parse");
+    assert_eq!(options.priority, SteeringPriority::Interrupt);
+    assert_eq!(options.ttl, Some(Duration::minutes(5)));
+    // Flags after the first message word are part of the message.
+    assert_eq!(rest, ["stop", "--now"]);
+}
+
+#[test]
+fn from_args_ttl_without_value_is_error() {
+    assert!(matches!(
+        SteerOptions::from_args(&["--ttl"]),
+        Err(AppError::Config(_))
+    ));
+}
+
+#[test]
+fn with_ttl_sets_expiry_relative_to_creation() {
+    let msg = SteeringMessage::new(
+        "sess".to_owned(),
+        None,
+        "hi".to_owned(),
+        SteeringSource::Ipc,
+    );
+    assert!(!msg.is_expired(Utc::now() + Duration::days(365)));
+
+    let msg = msg.with_ttl(Duration::minutes(5));
+    assert_eq!(msg.expires_at, Some(msg.created_at + Duration::minutes(5)));
+    assert!(!msg.is_expired(msg.created_at + Duration::minutes(4)));
+    assert!(msg.is_expired(msg.created_at + Duration::minutes(5)));
+}
+
+#[test]
+fn oversized_ttl_is_rejected_or_never_expires() {
+    assert!(matches!(
+        SteerOptions::from_args(&["--ttl", "106751991167300d", "hi"]),
+        Err(AppError::Config(_))
+    ));
+
+    let msg = SteeringMessage::new(
+        "sess".to_owned(),
+        None,
+        "hi".to_owned(),
+        SteeringSource::Ipc,
+    )
+    .with_ttl(Duration::MAX);
+    assert_eq!(msg.expires_at, None);
+}
@@ -8,7 +8,7 @@
 
 use std::sync::Arc;
 
-use agent_intercom::models::steering::{SteeringMessage, SteeringSource};
+use agent_intercom::models::steering::{SteeringMessage, SteeringPriority, SteeringSource};
 use agent_intercom::persistence::{db, steering_repo::SteeringRepo};
 
 fn sample_msg(session_id: &str, channel_id: Option<&str>, text: &str) -> SteeringMessage {
@@ -384,4 +384,90 @@ async fn legacy_db_without_origin_column_migrates_additively() {
         .expect("fetch resumed");
     assert_eq!(resumed.len(), 1);
     assert_eq!(resumed[0].origin_session_id.as_deref(), Some("sess-legacy"));
+
+    // The legacy source CHECK was widened to accept subtask reports.
+    repo.insert(&SteeringMessage::new(
+        "sess-resumed".to_owned(),
+        None,
+        "child finished".to_owned(),
+        SteeringSource::Subtask,
+    ))
+    .await
+    .expect("insert subtask report");
+    let msgs = repo.fetch_unconsumed("sess-resumed").await.expect("fetch");
+    assert_eq!(msgs[1].source, SteeringSource::Subtask);
+}
+
+// ─── Priority and TTL ────────────────────────────────────────────────
+
+#[tokio::test]
+async fn fetch_unconsumed_puts_interrupt_priority_first() {
+    let db = db::connect_memory().await.expect("db");
+    let repo = SteeringRepo::new(Arc::new(db));
+
+    repo.insert(&sample_msg("sess-p", None, "normal first"))
+        .await
+        .expect("insert normal");
+    repo.insert(&sample_msg("sess-p", None, "urgent").with_priority(SteeringPriority::Interrupt))
+        .await
+        .expect("insert interrupt");
+    repo.insert(&sample_msg("sess-p", None, "normal second"))
+        .await
+        .expect("insert normal");
+
+    let msgs = repo.fetch_unconsumed("sess-p").await.expect("fetch");
+    let texts: Vec<&str> = msgs.iter().map(|m| m.message.as_str()).collect();
+    assert_eq!(texts, ["urgent", "normal first", "normal second"]);
+    assert_eq!(msgs[0].priority, SteeringPriority::Interrupt);
+    assert_eq!(msgs[1].priority, SteeringPriority::Normal);
+}
+
+#[tokio::test]
+async fn fetch_unconsumed_skips_expired_messages() {
+    let db = db::connect_memory().await.expect("db");
+    let repo = SteeringRepo::new(Arc::new(db));
+
+    let mut stale = sample_msg("sess-ttl", None, "stale");
+    stale.created_at -= chrono::Duration::minutes(10);
+    repo.insert(&stale.with_ttl(chrono::Duration::minutes(5)))
+        .await
+        .expect("insert stale");
+    let fresh = sample_msg("sess-ttl", None, "fresh").with_ttl(chrono::Duration::hours(1));
+    repo.insert(&fresh).await.expect("insert fresh");
+
+    let msgs = repo.fetch_unconsumed("sess-ttl").await.expect("fetch");
+    assert_eq!(msgs.len(), 1);
+    assert_eq!(msgs[0].message, "fresh");
+    assert_eq!(
+        msgs[0].expires_at.map(|t| t.timestamp_micros()),
+        fresh.expires_at.map(|t| t.timestamp_micros())
+    );
+}
+
+#[tokio::test]
+async fn drop_expired_removes_and_returns_only_expired_unconsumed() {
+    let db = db::connect_memory().await.expect("db");
+    let repo = SteeringRepo::new(Arc::new(db));
+
+    let expiring =
+        sample_msg("sess-drop", None, "expiring").with_ttl(chrono::Duration::seconds(30));
+    repo.insert(&expiring).await.expect("insert expiring");
+    repo.insert(&sample_msg("sess-drop", None, "no ttl"))
+        .await
+        .expect("insert no ttl");
+    let long = sample_msg("sess-drop", None, "long ttl").with_ttl(chrono::Duration::hours(1));
+    repo.insert(&long).await.expect("insert long");
+
+    let later = chrono::Utc::now() + chrono::Duration::minutes(1);
+    let dropped = repo.drop_expired(later).await.expect("drop");
+    assert_eq!(dropped.len(), 1);
+    assert_eq!(dropped[0].id, expiring.id);
+
+    let remaining = repo.fetch_unconsumed("sess-drop").await.expect("fetch");
+    assert_eq!(remaining.len(), 2);
+    assert!(repo
+        .drop_expired(later)
+        .await
+        .expect("drop again")
+        .is_empty());
 }
@@ -0,0 +1,118 @@
+//! Unit tests for subtask completion reports (`orchestrator::subtask`).
+//!
+//! Tests cover:
+//! - A finished child queues a `subtask` steering message for its parent
+//! - Sessions without a parent, and finished parents, get no report
+//! - The report text summarizes the child's last progress snapshot
+//! - `parent_session_id` round-trips through persistence
+
+use std::sync::Arc;
+
+use agent_intercom::models::progress::{ProgressItem, ProgressStatus};
+use agent_intercom::models::session::{Session, SessionMode, SessionStatus};
+use agent_intercom::models::steering::SteeringSource;
+use agent_intercom::orchestrator::subtask::{report_text, report_to_parent};
+use agent_intercom::persistence::db::{self, Database};
+use agent_intercom::persistence::session_repo::SessionRepo;
+use agent_intercom::persistence::steering_repo::SteeringRepo;
+
+async fn active(db: &Arc<Database>, parent: Option<&Session>) -> Session {
+    let repo = SessionRepo::new(Arc::clone(db));
+    let mut session = Session::new(
+        "U_OWNER".into(),
+        "/tmp/ws".into(),
+        Some("work".into()),
+        SessionMode::Remote,
+    );
+    if let Some(parent) = parent {
+        session.channel_id.clone_from(&parent.channel_id);
+        session.parent_session_id = Some(parent.id.clone());
+    }
+    let created = repo.create(&session).await.expect("create");
+    repo.update_status(&created.id, SessionStatus::Active)
+        .await
+        .expect("activate")
+}
+
+#[tokio::test]
+async fn finished_child_reports_to_parent() {
+    let db = Arc::new(db::connect_memory().await.expect("db"));
+    let parent = active(&db, None).await;
+    let child = active(&db, Some(&parent)).await;
+    let stored = SessionRepo::new(Arc::clone(&db))
+        .get_by_id(&child.id)
+        .await
+        .expect("get")
+        .expect("child");
+    assert_eq!(
+        stored.parent_session_id.as_deref(),
+        Some(parent.id.as_str())
+    );
+
+    let queued = report_to_parent(&db, &child, "agent process exited normally")
+        .await
+        .expect("report")
+        .expect("queued");
+
+    let pending = SteeringRepo::new(Arc::clone(&db))
+        .fetch_unconsumed(&parent.id)
+        .await
+        .expect("fetch");
+    assert_eq!(pending, vec![queued]);
+    assert_eq!(pending[0].source, SteeringSource::Subtask);
+    assert!(pending[0].message.contains(&child.id));
+    assert!(pending[0].message.contains("exited normally"));
+}
+
+#[tokio::test]
+async fn no_report_without_live_parent() {
+    let db = Arc::new(db::connect_memory().await.expect("db"));
+    let parent = active(&db, None).await;
+    let child = active(&db, Some(&parent)).await;
+
+    assert!(report_to_parent(&db, &parent, "done")
+        .await
+        .expect("report")
+        .is_none());
+
+    SessionRepo::new(Arc::clone(&db))
+        .set_terminated(&parent.id, SessionStatus::Terminated)
+        .await
+        .expect("terminate");
+    assert!(report_to_parent(&db, &child, "done")
+        .await
+        .expect("report")
+        .is_none());
+    assert!(SteeringRepo::new(Arc::clone(&db))
+        .list_for_session(&parent.id)
+        .await
+        .expect("list")
+        .is_empty());
+}
+
+#[test]
+fn report_text_summarizes_progress() {
+    let mut child = Session::new(
+        "U_OWNER".into(),
+        "/tmp/ws".into(),
+        None,
+        SessionMode::Remote,
+    );
+    assert_eq!(
+        report_text(&child, "stopped by operator"),
+        format!("Subtask `{}` ended: stopped by operator.", child.id)
+    );
+
+    child.progress_snapshot = Some(vec![
+        ProgressItem {
+            label: "write parser".into(),
+            status: ProgressStatus::Done,
+        },
+        ProgressItem {
+            label: "add tests".into(),
+            status: ProgressStatus::InProgress,
+        },
+    ]);
+    assert!(report_text(&child, "stopped by operator")
+        .ends_with("Progress: 1/2 done (open: add tests)."));
+}
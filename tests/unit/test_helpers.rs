@@ -0,0 +1,67 @@
+//! Shared fixtures for unit tests that drive slash commands against an
+//! in-memory `AppState`.
+
+use std::sync::Arc;
+
+use agent_intercom::config::GlobalConfig;
+use agent_intercom::driver::mcp_driver::McpDriver;
+use agent_intercom::mode::ServerMode;
+use agent_intercom::persistence::db;
+use agent_intercom::state::AppState;
+
+/// Build an `AppState` with in-memory `SQLite`, no Slack client and an
+/// unbound MCP driver.
+///
+/// Returned unwrapped so tests can override fields with struct update
+/// syntax: `AppState { config_path, ..base_app_state(config).await }`.
+pub async fn base_app_state(config: GlobalConfig) -> AppState {
+    let database = Arc::new(db::connect_memory().await.expect("db connect"));
+    AppState {
+        config: Arc::new(config),
+        db: database,
+        slack: None,
+        pending_approvals: Arc::default(),
+        pending_prompts: Arc::default(),
+        pending_waits: Arc::default(),
+        pending_modal_contexts: Arc::default(),
+        pending_thread_replies: Arc::default(),
+        stall_detectors: None,
+        ipc_auth_token: None,
+        policy_cache: Arc::default(),
+        audit_logger: None,
+        active_children: Arc::default(),
+        pending_command_approvals: Arc::default(),
+        stall_event_tx: None,
+        driver: McpDriver::new_empty(),
+        server_mode: ServerMode::Mcp,
+        workspace_mappings: Arc::default(),
+        acp_event_tx: None,
+        acp_driver: None,
+        driver_registry: Arc::default(),
+        event_bus: Arc::default(),
+        session_hooks: Arc::default(),
+        pause_requests: Arc::default(),
+        heartbeats: Arc::default(),
+        instruction_queue: Arc::default(),
+        snoozes: Arc::default(),
+        apply_confirmations: Arc::default(),
+        session_logs: Arc::default(),
+        session_channels: Arc::default(),
+        workspace_discovery: Arc::default(),
+        config_path: None,
+        command_aliases: Arc::default(),
+        proxy: Arc::default(),
+        replay_guard: Arc::default(),
+        interactions: Arc::default(),
+        rearmed: Arc::default(),
+        diff_staging: Arc::default(),
+        subsystems: Arc::default(),
+        simulator: None,
+        log_filter: None,
+    }
+}
+
+/// Build an `AppState` as [`base_app_state`] does, ready to share.
+pub async fn test_app_state(config: GlobalConfig) -> Arc<AppState> {
+    Arc::new(base_app_state(config).await)
+}
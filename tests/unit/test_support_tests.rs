@@ -0,0 +1,157 @@
+//! Unit tests for the `test_support` module (feature `test-support`).
+//!
+//! Validates:
+//! - The built state resolves approvals through its own pending map
+//! - A recording driver bound to a session captures decisions and still
+//!   delivers them, and a detached one only records
+//! - Builders persist sessions and approvals with their overrides
+
+use std::sync::Arc;
+
+use agent_intercom::audit::{AuditEntry, AuditEventType, AuditLogger};
+use agent_intercom::driver::AgentDriver;
+use agent_intercom::models::approval::{ApprovalStatus, RiskLevel};
+use agent_intercom::models::session::SessionStatus;
+use agent_intercom::persistence::approval_repo::ApprovalRepo;
+use agent_intercom::test_support::{
+    config, pending_approval_ids, record_driver, register_approval, AppStateBuilder,
+    ApprovalBuilder, DriverCall, RecordingAuditLogger, RecordingDriver, SessionBuilder, TEST_OWNER,
+};
+
+#[tokio::test]
+async fn approval_lifecycle_resolves_through_recording_driver() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = AppStateBuilder::new(config(root).expect("config"))
+        .build()
+        .await
+        .expect("state");
+    let session = SessionBuilder::new(root)
+        .create(&state.db)
+        .await
+        .expect("session");
+    let request = ApprovalBuilder::new(&session.id)
+        .create(&state.db)
+        .await
+        .expect("approval");
+
+    let recorder = record_driver(&state, &session.id).await;
+    let rx = register_approval(&state, &request.id).await;
+    assert_eq!(pending_approval_ids(&state).await, vec![request.id.clone()]);
+
+    state
+        .driver_for(&session.id)
+        .await
+        .resolve_clearance(&request.id, false, Some("needs tests".into()))
+        .await
+        .expect("resolve");
+    let response = rx.await.expect("decided");
+    assert_eq!(response.status, "rejected");
+    assert_eq!(response.reason.as_deref(), Some("needs tests"));
+    assert!(pending_approval_ids(&state).await.is_empty());
+    assert_eq!(
+        recorder.calls(),
+        vec![DriverCall::Clearance {
+            request_id: request.id,
+            approved: false,
+            reason: Some("needs tests".into()),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn default_driver_shares_pending_map() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = AppStateBuilder::new(config(root).expect("config"))
+        .build()
+        .await
+        .expect("state");
+
+    let rx = register_approval(&state, "req-1").await;
+    state
+        .driver
+        .resolve_clearance("req-1", true, None)
+        .await
+        .expect("resolve");
+    assert_eq!(rx.await.expect("decided").status, "approved");
+}
+
+#[tokio::test]
+async fn detached_driver_and_audit_logger_only_record() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let driver = Arc::new(RecordingDriver::detached());
+    let logger = Arc::new(RecordingAuditLogger::default());
+    let state = AppStateBuilder::new(config(root).expect("config"))
+        .driver(Arc::clone(&driver) as Arc<dyn AgentDriver>)
+        .audit_logger(Arc::clone(&logger) as Arc<dyn AuditLogger>)
+        .build()
+        .await
+        .expect("state");
+
+    state
+        .driver
+        .send_prompt("sess-1", "continue")
+        .await
+        .expect("prompt");
+    assert_eq!(
+        driver.calls(),
+        vec![DriverCall::Prompt {
+            session_id: "sess-1".into(),
+            prompt: "continue".into(),
+        }]
+    );
+
+    let audit = state.audit_logger.as_ref().expect("logger");
+    audit
+        .log_entry(AuditEntry::new(AuditEventType::SessionTerminate))
+        .expect("log");
+    assert_eq!(logger.entries().len(), 1);
+}
+
+#[tokio::test]
+async fn builders_apply_overrides() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8");
+    let state = AppStateBuilder::new(config(root).expect("config"))
+        .build()
+        .await
+        .expect("state");
+
+    let session = SessionBuilder::new(root)
+        .create(&state.db)
+        .await
+        .expect("session");
+    assert_eq!(session.status, SessionStatus::Active);
+    assert_eq!(session.owner_user_id, TEST_OWNER);
+
+    let paused = SessionBuilder::new(root)
+        .owner("U_OTHER")
+        .title("paused work")
+        .status(SessionStatus::Paused)
+        .create(&state.db)
+        .await
+        .expect("session");
+    assert_eq!(paused.status, SessionStatus::Paused);
+    assert_eq!(paused.owner_user_id, "U_OTHER");
+    assert_eq!(paused.title.as_deref(), Some("paused work"));
+
+    let request = ApprovalBuilder::new(&session.id)
+        .title("Drop tables")
+        .file_path("db/schema.sql")
+        .diff("-CREATE TABLE t;\n")
+        .risk_level(RiskLevel::Critical)
+        .create(&state.db)
+        .await
+        .expect("approval");
+    let stored = ApprovalRepo::new(Arc::clone(&state.db))
+        .get_by_id(&request.id)
+        .await
+        .expect("get")
+        .expect("found");
+    assert_eq!(stored.status, ApprovalStatus::Pending);
+    assert_eq!(stored.title, "Drop tables");
+    assert_eq!(stored.file_path, "db/schema.sql");
+    assert_eq!(stored.risk_level, RiskLevel::Critical);
+}
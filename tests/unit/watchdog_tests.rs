@@ -0,0 +1,207 @@
+//! Unit tests for the self-monitoring watchdog (`orchestrator::watchdog`).
+//!
+//! Covers:
+//! - The database probe writes and reads back through `SQLite`
+//! - Restart backoff doubles up to its cap
+//! - A supervised task that exits or panics is restarted and recorded,
+//!   and the supervisor stops on cancellation
+//! - Degraded mode is reported once per failing set, and recovery once
+//! - Notice text names failing components and carries the mention
+//! - `[watchdog]` defaults and validation
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use agent_intercom::config::{GlobalConfig, WatchdogConfig};
+use agent_intercom::orchestrator::watchdog::{
+    self, Component, HealthChange, HealthTracker, RestartPolicy, Subsystems,
+};
+use agent_intercom::persistence::db;
+use agent_intercom::AppError;
+use tokio_util::sync::CancellationToken;
+
+const FAST: RestartPolicy = RestartPolicy {
+    initial_delay: Duration::from_millis(5),
+    max_delay: Duration::from_millis(20),
+};
+
+#[tokio::test]
+async fn database_probe_round_trips() {
+    let db = db::connect_memory().await.expect("db");
+    db::probe(&db).await.expect("first probe");
+    db::probe(&db).await.expect("probe overwrites its row");
+}
+
+#[test]
+fn restart_delay_doubles_up_to_cap() {
+    let policy = RestartPolicy::default();
+    assert_eq!(policy.delay(0), Duration::from_secs(1));
+    assert_eq!(policy.delay(1), Duration::from_secs(2));
+    assert_eq!(policy.delay(3), Duration::from_secs(8));
+    assert_eq!(policy.delay(20), Duration::from_mins(5));
+    assert_eq!(policy.delay(u32::MAX), Duration::from_mins(5));
+}
+
+#[tokio::test]
+async fn supervisor_restarts_stopped_task_until_cancelled() {
+    let subsystems = Arc::new(Subsystems::default());
+    let cancel = CancellationToken::new();
+    let runs = Arc::new(AtomicU32::new(0));
+
+    let spawned = Arc::clone(&runs);
+    let handle = watchdog::supervise(
+        Component::Retention,
+        Arc::clone(&subsystems),
+        FAST,
+        cancel.clone(),
+        move || {
+            let run = spawned.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                assert!(run != 1, "second run panics");
+            })
+        },
+    );
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while runs.load(Ordering::SeqCst) < 3 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("task restarted");
+
+    cancel.cancel();
+    tokio::time::timeout(Duration::from_secs(5), handle)
+        .await
+        .expect("supervisor stops on cancel")
+        .expect("supervisor does not panic");
+
+    assert!(subsystems.count(Component::Retention) >= 2);
+    assert_eq!(subsystems.count(Component::SocketMode), 0);
+    let reported = subsystems.take_unreported();
+    assert_eq!(reported[0].reason, "exited");
+    assert_eq!(reported[0].count, 1);
+    assert!(
+        reported[1]
+            .reason
+            .starts_with("panicked: second run panics"),
+        "{}",
+        reported[1].reason
+    );
+    assert!(subsystems.take_unreported().is_empty(), "reported once");
+}
+
+#[test]
+fn health_changes_reported_once_per_failing_set() {
+    let mut health = HealthTracker::default();
+    let ok = || Ok(());
+    let db_down = || Err(AppError::Db("disk I/O error".into()));
+
+    assert_eq!(
+        health.observe(&[(Component::Database, ok()), (Component::SlackApi, ok())]),
+        None
+    );
+    assert!(matches!(
+        health.observe(&[
+            (Component::Database, db_down()),
+            (Component::SlackApi, ok())
+        ]),
+        Some(HealthChange::Degraded { ref failing })
+            if failing.len() == 1
+                && failing[0].0 == Component::Database
+                && failing[0].1.contains("disk I/O error")
+    ));
+    assert!(health.is_degraded());
+    assert_eq!(
+        health.observe(&[
+            (Component::Database, db_down()),
+            (Component::SlackApi, ok())
+        ]),
+        None,
+        "same failing set is not reported again"
+    );
+    assert!(matches!(
+        health.observe(&[
+            (Component::Database, db_down()),
+            (Component::SlackApi, Err(AppError::Slack("invalid_auth".into()))),
+        ]),
+        Some(HealthChange::Degraded { ref failing }) if failing.len() == 2
+    ));
+    assert_eq!(
+        health.observe(&[(Component::Database, ok()), (Component::SlackApi, ok())]),
+        Some(HealthChange::Recovered)
+    );
+    assert!(!health.is_degraded());
+}
+
+#[test]
+fn describe_names_failures_and_mentions_only_degraded() {
+    let degraded = HealthChange::Degraded {
+        failing: vec![
+            (Component::Database, "database error: locked".into()),
+            (Component::Ipc, "ipc error: refused".into()),
+        ],
+    };
+    let text = watchdog::describe(&degraded, Some("<!here>"));
+    assert!(text.contains("<!here>"), "{text}");
+    assert!(text.contains("Degraded mode"), "{text}");
+    assert!(
+        text.contains("*Database*: database error: locked"),
+        "{text}"
+    );
+    assert!(
+        text.contains("*IPC endpoint*: ipc error: refused"),
+        "{text}"
+    );
+    assert!(!watchdog::describe(&degraded, None).contains("<!here>"));
+
+    let text = watchdog::describe(&HealthChange::Recovered, Some("<!here>"));
+    assert!(!text.contains("<!here>"), "{text}");
+    assert!(text.contains("Recovered"), "{text}");
+
+    let subsystems = Subsystems::default();
+    let restart = subsystems.record(Component::SocketMode, "exited");
+    let text = watchdog::describe_restart(&restart);
+    assert!(
+        text.contains("Slack Socket Mode listener exited and was restarted (restart 1)"),
+        "{text}"
+    );
+}
+
+#[test]
+fn watchdog_config_defaults_and_validation() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path().to_str().expect("utf8 path");
+    let base = format!(
+        "default_workspace_root = '{root}'\n\
+         host_cli = \"claude\"\n\
+         [slack]\n\
+         [timeouts]\n\
+         approval_seconds = 60\n\
+         prompt_seconds = 60\n\
+         wait_seconds = 0\n\
+         [stall]\n\
+         inactivity_threshold_seconds = 300\n\
+         escalation_threshold_seconds = 120\n\
+         max_retries = 3\n\
+         default_nudge_message = \"continue\"\n"
+    );
+
+    let config = GlobalConfig::from_toml_str(&base).expect("config parses");
+    assert_eq!(config.watchdog, WatchdogConfig::default());
+    assert!(config.watchdog.enabled);
+    assert!(!config.watchdog.ipc);
+    assert_eq!(config.watchdog.interval_seconds, 60);
+
+    let custom = format!("{base}[watchdog]\nipc = true\ninterval_seconds = 15\n");
+    let config = GlobalConfig::from_toml_str(&custom).expect("config parses");
+    assert!(config.watchdog.ipc);
+    assert_eq!(config.watchdog.interval_seconds, 15);
+
+    let invalid = format!("{base}[watchdog]\ninterval_seconds = 0\n");
+    assert!(matches!(
+        GlobalConfig::from_toml_str(&invalid),
+        Err(AppError::Config(ref msg)) if msg.contains("interval_seconds")
+    ));
+}
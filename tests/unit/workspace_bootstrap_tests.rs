@@ -0,0 +1,115 @@
+//! Unit tests for workspace bootstrap commands.
+//!
+//! Validates:
+//! - Commands run in order in the workspace root with the operator's
+//!   environment, stopping at the first failure
+//! - Output is captured into the session's log buffer
+//! - A failure names the command that broke the bootstrap
+//! - A timed-out command is killed with everything it started
+
+use std::collections::BTreeMap;
+
+use agent_intercom::config::WorkspaceBootstrap;
+use agent_intercom::orchestrator::session_logs::{LogLevel, SessionLogs};
+use agent_intercom::orchestrator::workspace_bootstrap::{run, run_commands};
+use agent_intercom::AppError;
+
+fn bootstrap(commands: &[&str]) -> WorkspaceBootstrap {
+    WorkspaceBootstrap {
+        commands: commands.iter().map(|c| (*c).to_owned()).collect(),
+        timeout_seconds: 30,
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn commands_run_in_order_and_stop_at_the_first_failure() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let env = BTreeMap::from([("BOOT_FLAG".to_owned(), "on".to_owned())]);
+    let outcomes = run_commands(
+        &bootstrap(&[
+            "echo \"$BOOT_FLAG\" > flag.txt",
+            "echo broken >&2; exit 3",
+            "touch never.txt",
+        ]),
+        temp.path(),
+        &env,
+    )
+    .await;
+
+    assert_eq!(outcomes.len(), 2);
+    assert!(outcomes[0].passed);
+    assert!(!outcomes[1].passed);
+    assert_eq!(outcomes[1].detail, "exit 3: broken");
+    assert_eq!(outcomes[1].stderr, "broken\n");
+    let flag = std::fs::read_to_string(temp.path().join("flag.txt")).expect("flag");
+    assert_eq!(flag.trim(), "on");
+    assert!(!temp.path().join("never.txt").exists());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn run_captures_output_and_names_the_failed_command() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let logs = SessionLogs::default();
+    let err = run(
+        &bootstrap(&["echo fetched", "echo lockfile out of date >&2; exit 1"]),
+        temp.path(),
+        &BTreeMap::new(),
+        "session-1",
+        &logs,
+    )
+    .await
+    .expect_err("bootstrap fails");
+
+    let AppError::Acp(message) = err else {
+        panic!("unexpected error: {err:?}");
+    };
+    assert!(
+        message.contains("`echo lockfile out of date >&2; exit 1`"),
+        "{message}"
+    );
+    assert!(message.contains("lockfile out of date"), "{message}");
+
+    let lines = logs.tail("session-1", 100);
+    assert!(lines.iter().any(|l| l.message == "fetched"));
+    assert!(lines
+        .iter()
+        .any(|l| l.level == LogLevel::Warn && l.message == "lockfile out of date"));
+    assert_eq!(lines.last().map(|l| l.level), Some(LogLevel::Error));
+
+    let logs = SessionLogs::default();
+    run(
+        &bootstrap(&["exit 0"]),
+        temp.path(),
+        &BTreeMap::new(),
+        "session-2",
+        &logs,
+    )
+    .await
+    .expect("bootstrap passes");
+    assert_eq!(logs.buffered("session-2"), 2);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn timeout_kills_the_whole_process_group() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let outcomes = run_commands(
+        &WorkspaceBootstrap {
+            commands: vec!["(sleep 2; touch late.txt) & wait".to_owned()],
+            timeout_seconds: 1,
+        },
+        temp.path(),
+        &BTreeMap::new(),
+    )
+    .await;
+
+    assert_eq!(outcomes.len(), 1);
+    assert_eq!(outcomes[0].detail, "timed out after 1 seconds");
+    tokio::time::sleep(std::time::Duration::from_millis(2500)).await;
+    assert!(
+        !temp.path().join("late.txt").exists(),
+        "a background child outlived the timeout"
+    );
+}
@@ -0,0 +1,165 @@
+//! Unit tests for the `workspace list|add|remove` slash commands.
+//!
+//! Validates:
+//! - `add` maps a workspace live and writes a `[[workspace]]` entry to
+//!   `config.toml`, keeping the file's comments
+//! - `remove` drops the mapping from the live table and the file
+//! - Without a config file the change applies for the run only
+//! - Duplicate IDs or channels and unknown workspaces are refused
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use agent_intercom::config::GlobalConfig;
+use agent_intercom::slack::commands::dispatch_command;
+use agent_intercom::state::AppState;
+use agent_intercom::AppError;
+
+use super::test_helpers::base_app_state;
+
+const USER: &str = "U_TEST";
+
+fn config_toml(workspace_root: &str) -> String {
+    format!(
+        r#"# Operator notes survive runtime edits.
+default_workspace_root = '{root}'
+http_port = 0
+ipc_name = "test-workspace-command"
+max_concurrent_sessions = 5
+host_cli = "echo"
+
+[slack]
+channel_id = "C_TEST"
+
+[timeouts]
+approval_seconds = 5
+prompt_seconds = 5
+wait_seconds = 5
+
+[stall]
+enabled = false
+inactivity_threshold_seconds = 300
+escalation_threshold_seconds = 120
+max_retries = 3
+default_nudge_message = "continue"
+
+# Frontend repo.
+[[workspace]]
+workspace_id = "web"
+channel_id = "C_WEB"
+label = "Web"
+"#,
+        root = workspace_root.replace('\\', "\\\\"),
+    )
+}
+
+/// Build state from a config written to `dir`; `persist` controls whether
+/// the server knows the file path.
+async fn app_state(dir: &Path, persist: bool) -> (Arc<AppState>, PathBuf) {
+    let path = dir.join("config.toml");
+    let raw = config_toml(dir.to_str().expect("utf8"));
+    std::fs::write(&path, &raw).expect("write config");
+    let mut config = GlobalConfig::from_toml_str(&raw).expect("valid test config");
+    config.authorized_user_ids = vec![USER.to_owned()];
+    let mappings = config.workspaces.clone();
+    let state = Arc::new(AppState {
+        workspace_mappings: Arc::new(std::sync::RwLock::new(mappings)),
+        config_path: persist.then(|| path.clone()),
+        ..base_app_state(config).await
+    });
+    (state, path)
+}
+
+async fn run(state: &Arc<AppState>, args: &[&str]) -> agent_intercom::Result<String> {
+    dispatch_command("workspace", args, USER, "C_OPS", state).await
+}
+
+#[tokio::test]
+async fn list_shows_live_mappings() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let (state, _) = app_state(tmp.path(), true).await;
+
+    let reply = run(&state, &["list"]).await.expect("list");
+    assert!(
+        reply.contains("`web` \u{2192} <#C_WEB> \u{2014} Web"),
+        "{reply}"
+    );
+}
+
+#[tokio::test]
+async fn add_maps_live_and_writes_config_keeping_comments() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let (state, path) = app_state(tmp.path(), true).await;
+
+    let reply = run(&state, &["add", "api", "<#C_API|api>", "API", "service"])
+        .await
+        .expect("add");
+    assert!(reply.contains("saved to config.toml"), "{reply}");
+
+    let live = state.workspace_mappings.read().expect("mappings").clone();
+    assert_eq!(live.len(), 2);
+    assert_eq!(live[1].channel_id, "C_API");
+    assert_eq!(live[1].label.as_deref(), Some("API service"));
+
+    let saved = std::fs::read_to_string(&path).expect("read config");
+    assert!(saved.starts_with("# Operator notes survive runtime edits."));
+    assert!(saved.contains("# Frontend repo."));
+    let reloaded = GlobalConfig::from_toml_str(&saved).expect("edited config parses");
+    assert_eq!(reloaded.workspaces, live);
+}
+
+#[tokio::test]
+async fn remove_drops_mapping_from_table_and_file() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let (state, path) = app_state(tmp.path(), true).await;
+
+    let reply = run(&state, &["remove", "web"]).await.expect("remove");
+    assert!(reply.contains("removed from config.toml"), "{reply}");
+    assert!(state
+        .workspace_mappings
+        .read()
+        .expect("mappings")
+        .is_empty());
+
+    let saved = std::fs::read_to_string(&path).expect("read config");
+    let reloaded = GlobalConfig::from_toml_str(&saved).expect("edited config parses");
+    assert!(reloaded.workspaces.is_empty());
+    assert!(saved.contains("# Operator notes survive runtime edits."));
+}
+
+#[tokio::test]
+async fn without_config_path_changes_last_for_the_run() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let (state, path) = app_state(tmp.path(), false).await;
+    let before = std::fs::read_to_string(&path).expect("read config");
+
+    let reply = run(&state, &["add", "api", "C_API"]).await.expect("add");
+    assert!(reply.contains("for this run only"), "{reply}");
+    assert_eq!(state.workspace_mappings.read().expect("mappings").len(), 2);
+    assert_eq!(std::fs::read_to_string(&path).expect("read config"), before);
+}
+
+#[tokio::test]
+async fn conflicting_or_unknown_workspaces_are_refused() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let (state, _) = app_state(tmp.path(), true).await;
+
+    let same_id = run(&state, &["add", "web", "C_OTHER"])
+        .await
+        .expect_err("duplicate workspace");
+    assert!(same_id.to_string().contains("already mapped"), "{same_id}");
+
+    let same_channel = run(&state, &["add", "docs", "C_WEB"])
+        .await
+        .expect_err("duplicate channel");
+    assert!(same_channel.to_string().contains("'web'"), "{same_channel}");
+
+    let unknown = run(&state, &["remove", "nope"])
+        .await
+        .expect_err("unknown workspace");
+    assert!(matches!(unknown, AppError::NotFound(_)), "{unknown}");
+
+    let usage = run(&state, &["add", "api"]).await.expect_err("usage");
+    assert!(usage.to_string().contains("usage: workspace"), "{usage}");
+    assert_eq!(state.workspace_mappings.read().expect("mappings").len(), 1);
+}
@@ -0,0 +1,240 @@
+//! Unit tests for unmapped workspace discovery (`[workspace_discovery]`).
+//!
+//! Validates:
+//! - Connections for an unmapped workspace are recorded once as pending,
+//!   and routed to the created channel or the unmapped fallback
+//! - Discovery is inert unless enabled
+//! - `workspace approve` maps the workspace live and appends a
+//!   `[[workspace]]` block that the config loader accepts
+//! - `workspace reject` stops routing, and bad requests are refused
+
+use std::path::Path;
+use std::sync::Arc;
+
+use agent_intercom::config::GlobalConfig;
+use agent_intercom::orchestrator::workspace_discovery::{
+    channel_name, resolve_unmapped, Observed, WorkspaceDiscovery,
+};
+use agent_intercom::slack::commands::dispatch_command;
+use agent_intercom::state::AppState;
+use agent_intercom::AppError;
+
+use super::test_helpers::base_app_state;
+
+const USER: &str = "U_TEST";
+
+fn config_toml(workspace_root: &str, discovery: &str) -> String {
+    format!(
+        r#"
+default_workspace_root = '{root}'
+http_port = 0
+ipc_name = "test-workspace-discovery"
+max_concurrent_sessions = 5
+host_cli = "echo"
+
+[slack]
+channel_id = "C_TEST"
+
+[timeouts]
+approval_seconds = 5
+prompt_seconds = 5
+wait_seconds = 5
+
+[stall]
+enabled = false
+inactivity_threshold_seconds = 300
+escalation_threshold_seconds = 120
+max_retries = 3
+default_nudge_message = "continue"
+
+{discovery}
+"#,
+        root = workspace_root.replace('\\', "\\\\"),
+    )
+}
+
+/// Write a config with `discovery` to `dir` and build state that persists
+/// approvals to it.
+async fn app_state(dir: &Path, discovery: &str) -> (Arc<AppState>, std::path::PathBuf) {
+    let path = dir.join("config.toml");
+    let raw = config_toml(dir.to_str().expect("utf8"), discovery);
+    std::fs::write(&path, &raw).expect("write config");
+    let mut config = GlobalConfig::from_toml_str(&raw).expect("valid test config");
+    config.authorized_user_ids = vec![USER.to_owned()];
+    let state = Arc::new(AppState {
+        config_path: Some(path.clone()),
+        ..base_app_state(config).await
+    });
+    (state, path)
+}
+
+const ENABLED: &str = r#"
+[workspace_discovery]
+enabled = true
+unmapped_channel_id = "C_UNMAPPED"
+"#;
+
+#[test]
+fn observe_records_pending_once_and_dismiss_sticks() {
+    let discovery = WorkspaceDiscovery::default();
+
+    assert_eq!(discovery.observe("repo-a"), Observed::New);
+    assert_eq!(
+        discovery.observe("repo-a"),
+        Observed::Pending { channel_id: None }
+    );
+    discovery.set_channel("repo-a", "C_NEW".into());
+    assert_eq!(
+        discovery.observe("repo-a"),
+        Observed::Pending {
+            channel_id: Some("C_NEW".into())
+        }
+    );
+
+    let pending = discovery.pending();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].connections, 3);
+
+    assert!(discovery.dismiss("repo-a"));
+    assert!(discovery.pending().is_empty());
+    assert_eq!(discovery.observe("repo-a"), Observed::Dismissed);
+    assert!(!discovery.dismiss("never-seen"));
+}
+
+#[test]
+fn channel_name_is_slack_safe() {
+    assert_eq!(
+        channel_name("intercom-", "Repo.A/main"),
+        "intercom-repo-a-main"
+    );
+    assert_eq!(channel_name("", &"x".repeat(100)).len(), 80);
+}
+
+#[tokio::test]
+async fn resolve_unmapped_is_inert_when_disabled() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let (state, _) = app_state(tmp.path(), "").await;
+
+    assert_eq!(resolve_unmapped(&state, "repo-a"), None);
+    assert!(state.workspace_discovery.pending().is_empty());
+}
+
+#[tokio::test]
+async fn resolve_unmapped_prefers_created_channel_over_fallback() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let (state, _) = app_state(tmp.path(), ENABLED).await;
+
+    assert_eq!(
+        resolve_unmapped(&state, "repo-a").as_deref(),
+        Some("C_UNMAPPED")
+    );
+    state
+        .workspace_discovery
+        .set_channel("repo-a", "C_REPO_A".into());
+    assert_eq!(
+        resolve_unmapped(&state, "repo-a").as_deref(),
+        Some("C_REPO_A")
+    );
+}
+
+#[tokio::test]
+async fn approve_maps_live_and_appends_to_config() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let (state, path) = app_state(tmp.path(), ENABLED).await;
+    resolve_unmapped(&state, "repo \"a\"");
+
+    let listing = dispatch_command("workspace", &["pending"], USER, "C_UNMAPPED", &state)
+        .await
+        .expect("pending");
+    assert!(listing.contains("`repo \"a\"`"), "{listing}");
+
+    let reply = dispatch_command(
+        "workspace",
+        &["approve", "repo \"a\"", "<#C_REPO|repo-a>"],
+        USER,
+        "C_UNMAPPED",
+        &state,
+    )
+    .await
+    .expect("approve");
+    assert!(reply.contains("saved to config.toml"), "{reply}");
+
+    let live = state.workspace_mappings.read().expect("mappings").clone();
+    assert_eq!(live.len(), 1);
+    assert_eq!(live[0].workspace_id, "repo \"a\"");
+    assert_eq!(live[0].channel_id, "C_REPO");
+    assert!(state.workspace_discovery.pending().is_empty());
+
+    let saved = std::fs::read_to_string(&path).expect("read config");
+    let reloaded = GlobalConfig::from_toml_str(&saved).expect("appended config parses");
+    assert_eq!(reloaded.workspaces, live);
+}
+
+#[tokio::test]
+async fn approve_refuses_shared_channel_unknown_and_duplicate() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let (state, _) = app_state(tmp.path(), ENABLED).await;
+    resolve_unmapped(&state, "repo-a");
+    resolve_unmapped(&state, "repo-b");
+
+    let shared = dispatch_command(
+        "workspace",
+        &["approve", "repo-a"],
+        USER,
+        "C_UNMAPPED",
+        &state,
+    )
+    .await
+    .expect_err("unmapped channel needs an explicit target");
+    assert!(matches!(shared, AppError::Config(_)), "{shared}");
+
+    let unknown = dispatch_command("workspace", &["approve", "repo-z"], USER, "C_X", &state)
+        .await
+        .expect_err("not pending");
+    assert!(matches!(unknown, AppError::NotFound(_)), "{unknown}");
+
+    dispatch_command(
+        "workspace",
+        &["approve", "repo-a"],
+        USER,
+        "C_SHARED",
+        &state,
+    )
+    .await
+    .expect("approve into current channel");
+    let taken = dispatch_command(
+        "workspace",
+        &["approve", "repo-b"],
+        USER,
+        "C_SHARED",
+        &state,
+    )
+    .await
+    .expect_err("channel already mapped");
+    assert!(taken.to_string().contains("repo-a"), "{taken}");
+    assert_eq!(state.workspace_discovery.pending().len(), 1);
+}
+
+#[tokio::test]
+async fn reject_stops_routing_until_restart() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let (state, _) = app_state(tmp.path(), ENABLED).await;
+    resolve_unmapped(&state, "repo-a");
+
+    let reply = dispatch_command(
+        "workspace",
+        &["reject", "repo-a"],
+        USER,
+        "C_UNMAPPED",
+        &state,
+    )
+    .await
+    .expect("reject");
+    assert!(reply.contains("rejected"), "{reply}");
+    assert_eq!(resolve_unmapped(&state, "repo-a"), None);
+
+    let usage = dispatch_command("workspace", &[], USER, "C_UNMAPPED", &state)
+        .await
+        .expect_err("usage");
+    assert!(usage.to_string().contains("usage: workspace"), "{usage}");
+}
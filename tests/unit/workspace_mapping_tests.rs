@@ -241,3 +241,47 @@ fn workspace_id_is_sole_routing_mechanism() {
         "workspace_id must be the only routing mechanism (F-10)"
     );
 }
+
+// ── Approval fan-out channels ─────────────────────────────────────────────────
+
+/// `[[workspace.approval_channels]]` entries select extra channels by risk.
+#[test]
+fn approval_channels_filter_by_min_risk() {
+    use agent_intercom::models::approval::RiskLevel;
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let base = base_toml(tmp.path().to_str().expect("utf8"));
+    let toml = format!(
+        "{}\n[[workspace.approval_channels]]\nchannel_id = \"C_TEAM\"\n\
+         \n[[workspace.approval_channels]]\nchannel_id = \"C_SECURITY\"\nmin_risk = \"critical\"\n",
+        with_mapping(&base, "my-repo", "C_WORKSPACE", None)
+    );
+
+    let config = GlobalConfig::from_toml_str(&toml).expect("config parses");
+    let mapping = &config.workspaces[0];
+    assert_eq!(mapping.approval_fanout(RiskLevel::Low), vec!["C_TEAM"]);
+    assert_eq!(mapping.approval_fanout(RiskLevel::High), vec!["C_TEAM"]);
+    assert_eq!(
+        mapping.approval_fanout(RiskLevel::Critical),
+        vec!["C_TEAM", "C_SECURITY"]
+    );
+}
+
+/// An approval channel may not repeat or be the workspace channel itself.
+#[test]
+fn approval_channels_reject_duplicates_and_workspace_channel() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let base = base_toml(tmp.path().to_str().expect("utf8"));
+    let mapping = with_mapping(&base, "my-repo", "C_WORKSPACE", None);
+
+    let own = format!("{mapping}\n[[workspace.approval_channels]]\nchannel_id = \"C_WORKSPACE\"\n");
+    let err = GlobalConfig::from_toml_str(&own).expect_err("workspace channel rejected");
+    assert!(err.to_string().contains("C_WORKSPACE"), "{err}");
+
+    let twice = format!(
+        "{mapping}\n[[workspace.approval_channels]]\nchannel_id = \"C_SEC\"\n\
+         \n[[workspace.approval_channels]]\nchannel_id = \"C_SEC\"\n"
+    );
+    let err = GlobalConfig::from_toml_str(&twice).expect_err("duplicate rejected");
+    assert!(err.to_string().contains("C_SEC"), "{err}");
+}